@@ -107,8 +107,11 @@ pub const SKILL_BINDINGS: &[SkillBinding] = &[
     SkillBinding { cli_path: &["agent", "revert"],      basename: "golem-rollback",              kind: SkillKind::Common, summary: "Roll back agent state." },
     SkillBinding { cli_path: &["agent", "revert"],      basename: "golem-undo-agent-state",      kind: SkillKind::Common, summary: "Revert agent state by undoing operations." },
     SkillBinding { cli_path: &["agent", "oplog"],       basename: "golem-debug-agent-history",   kind: SkillKind::Common, summary: "Query the agent operation log." },
+    SkillBinding { cli_path: &["agent", "search-oplogs"], basename: "golem-debug-agent-history", kind: SkillKind::Common, summary: "Search the operation logs of many agents." },
     SkillBinding { cli_path: &["agent", "files"],       basename: "golem-view-agent-files",      kind: SkillKind::Common, summary: "List files in an agent's virtual filesystem." },
     SkillBinding { cli_path: &["agent", "file-contents"], basename: "golem-view-agent-files",    kind: SkillKind::Common, summary: "Read files from an agent's virtual filesystem." },
+    SkillBinding { cli_path: &["agent", "export"],      basename: "golem-profiles-and-environments", kind: SkillKind::Common, summary: "Export an agent to move it to another environment." },
+    SkillBinding { cli_path: &["agent", "import"],      basename: "golem-profiles-and-environments", kind: SkillKind::Common, summary: "Import an exported agent into an environment." },
     SkillBinding { cli_path: &["agent", "cancel-invocation"], basename: "golem-cancel-queued-invocation", kind: SkillKind::Common, summary: "Cancel a queued invocation." },
     SkillBinding { cli_path: &["agent", "interrupt"],   basename: "golem-interrupt-resume-agent", kind: SkillKind::Common, summary: "Interrupt or resume an agent." },
     SkillBinding { cli_path: &["agent", "resume"],      basename: "golem-interrupt-resume-agent", kind: SkillKind::Common, summary: "Interrupt or resume an agent." },
@@ -400,6 +400,9 @@ impl RustBridgeGenerator {
                             idempotency_key: None,
                             deployment_revision: None,
                             owner_account_email: None,
+                            deadline: None,
+                            at_oplog_index: None,
+                            at_timestamp: None,
                         },
                     )
                     .await?;
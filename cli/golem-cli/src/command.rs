@@ -30,6 +30,8 @@ use crate::command::server::ServerSubcommand;
 use crate::command::shared_args::{
     BuildArgs, ForceBuildArg, OptionalComponentName, OptionalComponentNames, PostDeployArgs,
 };
+#[cfg(feature = "server-commands")]
+use crate::command::test::TestArgs;
 use crate::command::worker::AgentSubcommand;
 use crate::config::ProfileName;
 use crate::error::ShowClapHelpTarget;
@@ -92,6 +94,7 @@ impl GolemCliCommand {
                     vec!["profile"],
                     vec!["repl"],
                     vec!["server"],
+                    vec!["test"],
                 ],
                 arg_id_exclude: vec![
                     "app_manifest_path",
@@ -862,6 +865,13 @@ pub enum GolemCliSubcommand {
         #[clap(subcommand)]
         subcommand: ServerSubcommand,
     },
+    /// Run agent test scripts against an ephemeral in-process executor with deterministic clock and random sources and mocked outgoing HTTP
+    #[cfg(feature = "server-commands")]
+    #[command(after_help = crate::command_examples::TEST)]
+    Test {
+        #[clap(flatten)]
+        args: TestArgs,
+    },
     /// Manage Golem accounts
     Account {
         #[clap(subcommand)]
@@ -1240,7 +1250,7 @@ pub mod worker {
     use crate::command::shared_args::{
         AgentFunctionArgument, AgentFunctionName, AgentIdArgs, PostDeployArgs, StreamArgs,
     };
-    use crate::model::worker::{AgentListMode, AgentUpdateMode};
+    use crate::model::worker::{AgentListMode, AgentTimeTravelPoint, AgentUpdateMode};
     use chrono::{DateTime, Utc};
     use clap::Subcommand;
     use golem_client::model::ScanCursor;
@@ -1248,6 +1258,7 @@ pub mod worker {
     use golem_common::model::agent::AgentTypeName;
     use golem_common::model::component::{ComponentName, ComponentRevision};
     use golem_common::model::worker::AgentConfigEntryDto;
+    use std::path::PathBuf;
     use uuid::Uuid;
 
     #[derive(Debug, Subcommand)]
@@ -1309,6 +1320,14 @@ pub mod worker {
             /// Schedule the invocation at a specific time (ISO 8601 / RFC 3339 format, e.g. 2026-03-15T10:30:00Z)
             #[clap(long, requires = "trigger")]
             schedule_at: Option<DateTime<Utc>>,
+            /// Interrupt the invocation if it is still running after the given duration
+            /// (humantime format, e.g. `30s`, `5m`). Applies on top of the method's own timeout.
+            #[clap(long)]
+            timeout: Option<humantime::Duration>,
+            /// Invoke a read-only method on a temporary replay of the agent as of the given oplog
+            /// index or RFC 3339 timestamp, without changing the agent itself
+            #[clap(long, conflicts_with_all = ["trigger", "idempotency_key"])]
+            at: Option<AgentTimeTravelPoint>,
         },
         /// Get agent metadata
         #[command(after_help = crate::command_examples::AGENT_GET)]
@@ -1462,6 +1481,35 @@ pub mod worker {
             )]
             query: Option<String>,
         },
+        /// Searches the oplogs of all agents of the selected components
+        #[command(after_help = crate::command_examples::AGENT_SEARCH_OPLOGS)]
+        SearchOplogs {
+            /// Lucene-style query against oplog entries (case-insensitive terms, AND/OR/NOT, phrases, regex).
+            /// See --help for matchable terms.
+            #[arg(long_help = crate::command_glossary::OPLOG_QUERY_LONG)]
+            query: String,
+            /// Optional filter for a specific agent type. Mutually exclusive with `--component-name`.
+            #[arg(long, conflicts_with = "component_name")]
+            agent_type_name: Option<AgentTypeName>,
+            /// Optional filter for a specific component. Mutually exclusive with `--agent-type-name`.
+            /// If neither is given, all components of the application are searched.
+            #[arg(long, conflicts_with = "agent_type_name")]
+            component_name: Option<ComponentName>,
+            /// Search every deployed component of the environment instead of the application's
+            /// components. Mutually exclusive with `--agent-type-name` and `--component-name`.
+            #[arg(long, conflicts_with_all = ["agent_type_name", "component_name"])]
+            environment_wide: bool,
+            /// Filter for the searched agents, in the same format as `agent list --filter`.
+            /// Can be used multiple times (AND condition is applied between them).
+            #[arg(long)]
+            filter: Vec<String>,
+            /// Only show oplog entries created at or after this time (RFC 3339)
+            #[arg(long)]
+            since: Option<DateTime<Utc>>,
+            /// Only show oplog entries created at or before this time (RFC 3339)
+            #[arg(long)]
+            until: Option<DateTime<Utc>>,
+        },
         /// DESTRUCTIVE: Rewrites the agent's oplog by undoing recent operations. Reverted entries are lost. This action is irreversible. Use `-Y/--yes` to skip the interactive confirmation.
         #[command(after_help = crate::command_examples::AGENT_REVERT)]
         Revert {
@@ -1486,6 +1534,32 @@ pub mod worker {
             )]
             number_of_invocations: Option<u64>,
         },
+        /// Exports an agent into a portable archive file.
+        ///
+        /// The archive contains the agent's full oplog, the oplog payloads stored outside of
+        /// it, the agent's metadata and config, and references to the component revisions the
+        /// agent used. It can be imported into another environment or cluster with `agent import`.
+        #[command(after_help = crate::command_examples::AGENT_EXPORT)]
+        Export {
+            #[command(flatten)]
+            agent_id: AgentIdArgs,
+            /// Local path of the archive file to create
+            #[arg(long)]
+            output: PathBuf,
+        },
+        /// Imports an agent from an archive created by `agent export`.
+        ///
+        /// The agent is recreated with the given agent ID in the selected environment, which can
+        /// be on a different server or cluster than the exported one. The target component must
+        /// have all the component revisions the archived agent used, built from the same WASM.
+        #[command(after_help = crate::command_examples::AGENT_IMPORT)]
+        Import {
+            #[command(flatten)]
+            agent_id: AgentIdArgs,
+            /// Local path of the archive file to import
+            #[arg(long)]
+            archive: PathBuf,
+        },
         /// Cancels an enqueued invocation if it has not started yet
         #[command(after_help = crate::command_examples::AGENT_CANCEL_INVOCATION)]
         CancelInvocation {
@@ -1558,12 +1632,39 @@ pub mod worker {
             #[arg(long)]
             plugin_priority: Option<i32>,
         },
+        /// Preview which agents of an agent type are affected by its lifecycle policy.
+        ///
+        /// Lists the idle, failed and exited agents together with the lifecycle action
+        /// (deletion or archival) pending on them and the time it is due, assuming they
+        /// stay inactive until then.
+        #[command(after_help = crate::command_examples::AGENT_LIFECYCLE_PREVIEW)]
+        LifecyclePreview {
+            /// Agent type name
+            agent_type_name: AgentTypeName,
+        },
+        /// Show the lifecycle policy actions already applied to the agents of an agent type.
+        ///
+        /// Lists the agents that were deleted or archived by the agent type's lifecycle policy,
+        /// oldest first. Entries are kept for the executors' configured audit retention period.
+        #[command(after_help = crate::command_examples::AGENT_LIFECYCLE_AUDIT)]
+        LifecycleAudit {
+            /// Agent type name
+            agent_type_name: AgentTypeName,
+            /// Only show actions applied at or after this time (RFC 3339)
+            #[arg(long)]
+            since: Option<DateTime<Utc>>,
+            /// Only show actions applied at or before this time (RFC 3339)
+            #[arg(long)]
+            until: Option<DateTime<Utc>>,
+        },
     }
 }
 
 pub mod agent_type {
+    use crate::model::AgentTypeSchemaFormatArg;
     use clap::Subcommand;
     use golem_common::model::agent::AgentTypeName;
+    use std::path::PathBuf;
 
     #[derive(Debug, Subcommand)]
     pub enum AgentTypeSubcommand {
@@ -1576,6 +1677,21 @@ pub mod agent_type {
             /// Agent type name
             agent_type_name: AgentTypeName,
         },
+        /// Export the schema of a deployed agent type as Protobuf IDL or bundled JSON Schema
+        ///
+        /// The exported schema covers the agent's constructor, methods and data types,
+        /// so services outside of Golem can validate and encode agent payloads.
+        #[command(after_help = crate::command_examples::AGENT_TYPE_EXPORT_SCHEMA)]
+        ExportSchema {
+            /// Agent type name
+            agent_type_name: AgentTypeName,
+            /// Schema format to export
+            #[arg(long, value_enum, default_value = "protobuf")]
+            schema_format: AgentTypeSchemaFormatArg,
+            /// Local path to write the schema to. If omitted, the schema is printed to stdout.
+            #[arg(long)]
+            output: Option<PathBuf>,
+        },
     }
 }
 
@@ -2367,6 +2483,45 @@ pub mod server {
     }
 }
 
+pub mod test {
+    use clap::Args;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Args)]
+    pub struct TestArgs {
+        /// Test script files (YAML) to run. Each script lists tests made of agent invocations
+        /// with expected results, and oplog queries with expected match counts.
+        #[clap(required = true)]
+        pub scripts: Vec<PathBuf>,
+
+        /// Write the test results as a JUnit XML report to the given file
+        #[clap(long)]
+        pub junit: Option<PathBuf>,
+
+        /// Forward outgoing HTTP requests without a recorded response to their original
+        /// destination and save the responses into the scripts' recordings files, instead of
+        /// failing them
+        #[clap(long)]
+        pub record: bool,
+
+        /// Seed of the deterministic random sources
+        #[clap(long, default_value_t = 0)]
+        pub seed: u64,
+
+        /// Wall clock time observed by the first host call of each agent (RFC 3339 format)
+        #[clap(long, default_value = "2025-01-01T00:00:00Z")]
+        pub start_time: humantime::Timestamp,
+
+        /// Amount the clocks advance with each oplog entry of an agent (humantime format)
+        #[clap(long, default_value = "1ms")]
+        pub clock_step: humantime::Duration,
+
+        /// Keep the data directory of the ephemeral executor and print its location
+        #[clap(long)]
+        pub keep_data_dir: bool,
+    }
+}
+
 pub fn builtin_exec_subcommands() -> BTreeSet<String> {
     GolemCliCommand::command()
         .find_subcommand("exec")
@@ -192,6 +192,10 @@ pub const AGENT_INVOKE: &str = "Examples:
   # Schedule for the future (RFC 3339 / ISO 8601, UTC)
   golem-cli agent invoke --schedule-at 2026-03-15T10:30:00Z 'Reporter()' send_report
 
+  # Call a read-only method against the agent's state as of an oplog index or a point in time
+  golem-cli agent invoke --at 42 'Counter(\"c1\")' get_value
+  golem-cli agent invoke --at 2026-03-15T10:30:00Z 'Counter(\"c1\")' get_value
+
   # Stream only log entries, no invocation markers
   golem-cli agent invoke --logs-only 'MyAgent()' run
 
@@ -298,6 +302,20 @@ pub const AGENT_OPLOG: &str = "Examples:
   golem-cli agent oplog 'CounterAgent(\"my-counter\")' --query 'error'
   golem-cli agent oplog 'CounterAgent(\"my-counter\")' --query 'function_name:increment'";
 
+pub const AGENT_SEARCH_OPLOGS: &str = "Examples:
+  # Find the agents of the current application that logged an error
+  golem-cli agent search-oplogs 'error'
+
+  # Search a single component within a time range
+  golem-cli agent search-oplogs 'api.example.com' --component-name my:component \\
+    --since 2026-01-01T10:00:00Z --until 2026-01-01T10:15:00Z
+
+  # Search only the agents of one agent type
+  golem-cli agent search-oplogs 'function_name:increment' --agent-type-name CounterAgent
+
+  # Search every deployed component of the environment
+  golem-cli agent search-oplogs 'error' --environment-wide";
+
 pub const AGENT_REVERT: &str = "Examples:
   # Undo the agent's last N invocations
   golem-cli agent revert 'CounterAgent(\"c1\")' --number-of-invocations 1
@@ -307,6 +325,20 @@ pub const AGENT_REVERT: &str = "Examples:
 
   # The two flags are mutually exclusive";
 
+pub const AGENT_EXPORT: &str = "Examples:
+  # Export an agent into an archive file
+  golem-cli agent export 'CounterAgent(\"c1\")' --output c1.agent
+
+  # Export an agent from a non-default environment
+  golem-cli agent export 'staging/CounterAgent(\"c1\")' --output c1.agent";
+
+pub const AGENT_IMPORT: &str = "Examples:
+  # Recreate an exported agent in another environment
+  golem-cli agent import 'prod/CounterAgent(\"c1\")' --archive c1.agent
+
+  # Import into a different cluster by selecting another profile
+  golem-cli agent import 'CounterAgent(\"c1\")' --archive c1.agent --profile other-cluster";
+
 pub const AGENT_CANCEL_INVOCATION: &str = "Examples:
   # Cancel a still-enqueued invocation by its idempotency key
   golem-cli agent cancel-invocation 'CounterAgent(\"my-counter\")' my-key-123
@@ -351,6 +383,20 @@ pub const AGENT_DEACTIVATE_PLUGIN: &str = "Examples:
   # If multiple installations of the same plugin exist, disambiguate by priority
   golem-cli agent deactivate-plugin --plugin-name my-plugin --plugin-priority 10 'CounterAgent(\"c1\")'";
 
+pub const AGENT_LIFECYCLE_PREVIEW: &str = "Examples:
+  # Show which agents of an agent type are going to be deleted or archived by its lifecycle policy
+  golem-cli agent lifecycle-preview CounterAgent
+
+  # Same, as JSON
+  golem-cli agent lifecycle-preview CounterAgent --format json";
+
+pub const AGENT_LIFECYCLE_AUDIT: &str = "Examples:
+  # Show which agents of an agent type were deleted or archived by its lifecycle policy
+  golem-cli agent lifecycle-audit CounterAgent
+
+  # Only show the actions applied in a given time range
+  golem-cli agent lifecycle-audit CounterAgent --since 2026-01-01T00:00:00Z --until 2026-02-01T00:00:00Z";
+
 // Agent type commands ------------------------------------------------------------------------------
 
 pub const AGENT_TYPE_LIST: &str = "Examples:
@@ -367,6 +413,13 @@ pub const AGENT_TYPE_GET: &str = "Examples:
   # JSON output
   golem-cli agent-type get CounterAgent --format json";
 
+pub const AGENT_TYPE_EXPORT_SCHEMA: &str = "Examples:
+  # Print the Protobuf IDL of a deployed agent type
+  golem-cli agent-type export-schema CounterAgent
+
+  # Write a bundled JSON Schema document to a file
+  golem-cli agent-type export-schema CounterAgent --schema-format json-schema --output counter-agent.schema.json";
+
 // Component commands -------------------------------------------------------------------------------
 
 pub const COMPONENT_LIST: &str = "Examples:
@@ -566,6 +619,21 @@ pub const SERVER_CLEAN: &str = "Examples:
   # Wipe the local server's persistent data directory
   golem-cli server clean";
 
+// Test commands ------------------------------------------------------------------------------------
+
+pub const TEST: &str = "Examples:
+  # Run test scripts against an ephemeral in-process executor (run from app root)
+  golem test tests/counter.yaml tests/weather.yaml
+
+  # Produce a JUnit report for CI
+  golem test tests/*.yaml --junit target/golem-tests.xml
+
+  # Record the responses of real HTTP endpoints into the scripts' recordings files
+  golem test tests/weather.yaml --record
+
+  # Use a different seed and start time for the deterministic sources
+  golem test tests/counter.yaml --seed 42 --start-time 2030-01-01T00:00:00Z";
+
 // Account commands ---------------------------------------------------------------------------------
 
 pub const ACCOUNT_GET: &str = "Examples:
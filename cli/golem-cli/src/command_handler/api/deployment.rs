@@ -255,6 +255,8 @@ impl ApiDeploymentCommandHandler {
                             .openapi_prefix
                             .clone(),
                         agents: deployable_http_api_deployment.agents.clone(),
+                        rate_limits: deployable_http_api_deployment.rate_limits.clone(),
+                        graphql_endpoint: deployable_http_api_deployment.graphql_endpoint.clone(),
                     },
                 )
                 .await
@@ -354,6 +356,16 @@ impl ApiDeploymentCommandHandler {
             diff::DiffForHashOf::ValueDiff { diff } => !diff.agents_changes.is_empty(),
         };
 
+        let rate_limits_changed = match diff {
+            diff::DiffForHashOf::HashDiff { .. } => true,
+            diff::DiffForHashOf::ValueDiff { diff } => diff.rate_limits_changed,
+        };
+
+        let graphql_endpoint_changed = match diff {
+            diff::DiffForHashOf::HashDiff { .. } => true,
+            diff::DiffForHashOf::ValueDiff { diff } => diff.graphql_endpoint_changed,
+        };
+
         let deployment = self
             .ctx
             .golem_clients()
@@ -378,6 +390,21 @@ impl ApiDeploymentCommandHandler {
                     } else {
                         None
                     },
+                    rate_limits: if rate_limits_changed {
+                        Some(deployable_http_api_deployment.rate_limits.clone())
+                    } else {
+                        None
+                    },
+                    graphql_endpoint: if graphql_endpoint_changed {
+                        Some(
+                            deployable_http_api_deployment
+                                .graphql_endpoint
+                                .clone()
+                                .unwrap_or_default(),
+                        )
+                    } else {
+                        None
+                    },
                 },
             )
             .await
@@ -68,7 +68,10 @@ use golem_client::api::{
 use golem_client::model::{ApplicationCreation, DeploymentCreation, DeploymentRollback};
 use golem_common::model::account::AccountId;
 use golem_common::model::agent::schema_evolution::validate_schema_evolution;
-use golem_common::model::agent::{AgentConfigSource, AgentTypeName, DeployedRegisteredAgentType};
+use golem_common::model::agent::{
+    AgentConfigSource, AgentTypeName, AgentTypeSchemaExport, AgentTypeSchemaFormat,
+    DeployedRegisteredAgentType,
+};
 use golem_common::model::agent_secret::{AgentSecretPath, CanonicalAgentSecretPath};
 use golem_common::model::application::ApplicationName;
 use golem_common::model::component::{ComponentDto, ComponentName};
@@ -522,6 +525,48 @@ impl AppCommandHandler {
         Ok(())
     }
 
+    pub async fn cmd_export_agent_type_schema(
+        &self,
+        agent_type_name: AgentTypeName,
+        format: AgentTypeSchemaFormat,
+        output: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let environment = self
+            .ctx
+            .environment_handler()
+            .resolve_environment(EnvironmentResolveMode::Any)
+            .await?;
+
+        let Some(export) = self
+            .export_agent_type_schema(&environment, agent_type_name.0.as_str(), format)
+            .await?
+        else {
+            log_error(format!(
+                "Agent type {} not found",
+                agent_type_name.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        match output {
+            Some(output) => {
+                fs::write_str(&output, &export.content)?;
+                log_action(
+                    "Exported",
+                    format!(
+                        "{} schema of agent type {} to {}",
+                        export.format,
+                        agent_type_name.0.log_color_highlight(),
+                        output.display().to_string().log_color_highlight()
+                    ),
+                );
+            }
+            None => log_preformatted(&export.content),
+        }
+
+        Ok(())
+    }
+
     pub async fn list_agent_types(
         &self,
         environment: &ResolvedEnvironmentIdentity,
@@ -571,6 +616,33 @@ impl AppCommandHandler {
             .await
     }
 
+    pub async fn export_agent_type_schema(
+        &self,
+        environment: &ResolvedEnvironmentIdentity,
+        agent_type_name: &str,
+        format: AgentTypeSchemaFormat,
+    ) -> anyhow::Result<Option<AgentTypeSchemaExport>> {
+        environment
+            .with_current_deployment_revision_or_default_warn(
+                |current_deployment_revision| async move {
+                    Ok(self
+                        .ctx
+                        .golem_clients()
+                        .await?
+                        .environment
+                        .export_deployment_agent_type_schema(
+                            &environment.environment_id.0,
+                            current_deployment_revision.into(),
+                            agent_type_name,
+                            &format,
+                        )
+                        .await
+                        .map_service_error_not_found_as_opt()?)
+                },
+            )
+            .await
+    }
+
     async fn deploy_by_version(
         &self,
         version: String,
@@ -1086,6 +1158,8 @@ impl AppCommandHandler {
                         webhooks_prefix: http_api_deployment.webhooks_prefix.clone(),
                         openapi_endpoint_prefix: http_api_deployment.openapi_prefix.clone(),
                         agents,
+                        rate_limits: http_api_deployment.rate_limits.clone(),
+                        graphql_endpoint: http_api_deployment.graphql_endpoint.clone(),
                     }
                     .into(),
                 );
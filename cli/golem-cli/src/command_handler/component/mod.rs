@@ -881,6 +881,10 @@ impl ComponentCommandHandler {
                     files_source: component.source().to_path_buf(),
                     files: resolved_agent.files().to_vec(),
                     plugins: resolve_plugin_parameters(component_name, resolved_agent.plugins())?,
+                    lifecycle: resolved_agent
+                        .lifecycle()
+                        .map(|lifecycle| lifecycle.to_policy())
+                        .filter(|policy| !policy.is_disabled()),
                 },
             );
         }
@@ -1046,6 +1050,7 @@ impl ComponentCommandHandler {
                 config,
                 files_by_path,
                 plugins_by_grant_id,
+                lifecycle: manifest_config.lifecycle.clone(),
             };
 
             agent_type_provision_configs.insert(agent_type_name.0.clone(), provision_config.into());
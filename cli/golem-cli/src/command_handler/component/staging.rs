@@ -578,6 +578,7 @@ impl<'a> ComponentStager<'a> {
                         AgentTypeProvisionConfigUpdate {
                             env: Some(creation.env),
                             config: Some(creation.config),
+                            lifecycle: Some(creation.lifecycle.unwrap_or_default()),
                             files_to_add_or_update: self
                                 .files_to_add_or_update_for_agent(name, creation.files),
                             files_to_remove,
@@ -681,6 +682,7 @@ impl<'a> ComponentStager<'a> {
                 AgentTypeProvisionConfigUpdate {
                     env: Some(creation.env),
                     config: Some(creation.config),
+                    lifecycle: Some(creation.lifecycle.unwrap_or_default()),
                     files_to_add_or_update: self
                         .files_to_add_or_update_for_agent(name, creation.files),
                     files_to_remove,
@@ -18,6 +18,8 @@ use self::secret::SecretCommandHandler;
 use crate::command::agent_type::AgentTypeSubcommand;
 #[cfg(feature = "server-commands")]
 use crate::command::server::ServerSubcommand;
+#[cfg(feature = "server-commands")]
+use crate::command::test::TestArgs;
 use crate::command::{
     GolemCliCommand, GolemCliCommandParseResult, GolemCliFallbackCommand, GolemCliGlobalFlags,
     GolemCliSubcommand,
@@ -84,6 +86,13 @@ pub trait CommandHandlerHooks: Sync + Send {
         subcommand: ServerSubcommand,
     ) -> impl std::future::Future<Output = anyhow::Result<()>>;
 
+    #[cfg(feature = "server-commands")]
+    fn handler_test_command(
+        &self,
+        ctx: Arc<Context>,
+        args: TestArgs,
+    ) -> impl std::future::Future<Output = anyhow::Result<()>>;
+
     // Used for auto starting the default server
     #[cfg(feature = "server-commands")]
     fn run_server() -> impl std::future::Future<Output = anyhow::Result<()>> + Send;
@@ -397,6 +406,20 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                             .cmd_get_agent_type(agent_type_name)
                             .await
                     }
+                    AgentTypeSubcommand::ExportSchema {
+                        agent_type_name,
+                        schema_format,
+                        output,
+                    } => {
+                        self.ctx
+                            .app_handler()
+                            .cmd_export_agent_type_schema(
+                                agent_type_name,
+                                schema_format.into(),
+                                output,
+                            )
+                            .await
+                    }
                 },
                 GolemCliSubcommand::Api { subcommand } => {
                     self.ctx.api_handler().handle_command(subcommand).await
@@ -413,6 +436,12 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                         .handler_server_commands(self.ctx.clone(), subcommand)
                         .await
                 }
+                #[cfg(feature = "server-commands")]
+                GolemCliSubcommand::Test { args } => {
+                    self.hooks
+                        .handler_test_command(self.ctx.clone(), args)
+                        .await
+                }
                 GolemCliSubcommand::Account { subcommand } => {
                     self.ctx.account_handler().handle_command(subcommand).await
                 }
@@ -28,7 +28,9 @@ use golem_client::api::PluginClient;
 use golem_client::model::PluginRegistrationCreation;
 use golem_common::model::account::AccountSummary;
 use golem_common::model::base64::Base64;
-use golem_common::model::plugin_registration::{OplogProcessorPluginSpec, PluginSpecDto};
+use golem_common::model::plugin_registration::{
+    InvocationInterceptorPluginSpec, OplogProcessorPluginSpec, PluginSpecDto,
+};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -149,6 +151,12 @@ impl PluginCommandHandler {
                         component_revision: spec.component_revision,
                     })
                 }
+                PluginTypeSpecificManifest::InvocationInterceptor(spec) => {
+                    PluginSpecDto::InvocationInterceptor(InvocationInterceptorPluginSpec {
+                        component_id: spec.component_id.into(),
+                        component_revision: spec.component_revision,
+                    })
+                }
             };
 
             let clients = self.ctx.golem_clients().await?;
@@ -24,6 +24,7 @@ use crate::command_handler::worker::stream::WorkerConnection;
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
 use crate::error::service::{MapServiceError, ServiceError};
+use crate::fs;
 use crate::fuzzy::{Error, FuzzySearch};
 use crate::log::{
     LogColorize, LogIndent, log_action, log_error, log_error_action, log_failed_to, log_warn,
@@ -33,7 +34,8 @@ use crate::model::component::ComponentNameMatchKind;
 use crate::model::deploy::{TryUpdateAllWorkersResult, WorkerUpdateAttempt};
 use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::text::action_result::{
-    AgentDeleteResult, AgentPluginToggleResult, AgentRevertResult,
+    AgentDeleteResult, AgentExportResult, AgentImportResult, AgentPluginToggleResult,
+    AgentRevertResult,
 };
 use crate::model::text::fmt::{log_fuzzy_match, log_text_view};
 use crate::model::text::help::{
@@ -53,8 +55,9 @@ use crate::model::environment::{
     EnvironmentReference, EnvironmentResolveMode, ResolvedEnvironmentIdentity,
 };
 use crate::model::worker::{
-    AgentListMode, AgentMetadata, AgentMetadataView, AgentNameMatch, AgentUpdateMode,
-    AgentsMetadataResponseView, RawAgentId,
+    AgentLifecycleAuditView, AgentLifecyclePreviewView, AgentListMode, AgentMetadata,
+    AgentMetadataView, AgentNameMatch, AgentTimeTravelPoint, AgentUpdateMode,
+    AgentsMetadataResponseView, ParsedAgentInvocation, RawAgentId,
 };
 use golem_client::api::{AgentClient, ComponentClient, WorkerClient};
 use golem_client::model::ScanCursor;
@@ -76,7 +79,7 @@ use golem_common::model::oplog::{OplogCursor, PublicOplogEntry};
 use golem_common::model::worker::{
     AgentConfigEntryDto, RevertLastInvocations, RevertToOplogIndex, UpdateRecord,
 };
-use golem_common::model::{AgentFilter, FilterComparator, IdempotencyKey, OplogIndex};
+use golem_common::model::{AgentFilter, FilterComparator, IdempotencyKey, OplogIndex, Timestamp};
 use golem_wasm::analysis::AnalysedType;
 
 use crossterm::cursor::{Hide, MoveTo, Show};
@@ -88,7 +91,7 @@ use itertools::{EitherOrBoth, Itertools};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Stdout, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -101,6 +104,12 @@ pub struct WorkerCommandHandler {
     ctx: Arc<Context>,
 }
 
+/// What a cross-agent oplog search runs over
+enum OplogSearchTarget {
+    Component(Uuid),
+    Environment(Uuid),
+}
+
 impl WorkerCommandHandler {
     pub fn new(ctx: Arc<Context>) -> Self {
         Self { ctx }
@@ -127,6 +136,8 @@ impl WorkerCommandHandler {
                     stream_args,
                     post_deploy_args,
                     schedule_at,
+                    timeout: invocation_timeout,
+                    at,
                 } => {
                     self.cmd_invoke(
                         agent_name,
@@ -138,6 +149,8 @@ impl WorkerCommandHandler {
                         stream_args,
                         post_deploy_args,
                         schedule_at,
+                        invocation_timeout,
+                        at,
                     )
                     .await
                 }
@@ -219,6 +232,26 @@ impl WorkerCommandHandler {
                     from,
                     query,
                 } => self.cmd_oplog(agent_name, from, query).await,
+                AgentSubcommand::SearchOplogs {
+                    query,
+                    agent_type_name,
+                    component_name,
+                    environment_wide,
+                    filter: filters,
+                    since,
+                    until,
+                } => {
+                    self.cmd_search_oplogs(
+                        query,
+                        agent_type_name,
+                        component_name,
+                        environment_wide,
+                        filters,
+                        since,
+                        until,
+                    )
+                    .await
+                }
                 AgentSubcommand::Revert {
                     agent_id: agent_name,
                     last_oplog_index,
@@ -227,6 +260,14 @@ impl WorkerCommandHandler {
                     self.cmd_revert(agent_name, last_oplog_index, number_of_invocations)
                         .await
                 }
+                AgentSubcommand::Export {
+                    agent_id: agent_name,
+                    output,
+                } => self.cmd_export(agent_name, output).await,
+                AgentSubcommand::Import {
+                    agent_id: agent_name,
+                    archive,
+                } => self.cmd_import(agent_name, archive).await,
                 AgentSubcommand::CancelInvocation {
                     agent_id: agent_name,
                     idempotency_key,
@@ -258,6 +299,17 @@ impl WorkerCommandHandler {
                     self.cmd_deactivate_plugin(agent_name, plugin_name, plugin_priority)
                         .await
                 }
+                AgentSubcommand::LifecyclePreview { agent_type_name } => {
+                    self.cmd_lifecycle_preview(agent_type_name).await
+                }
+                AgentSubcommand::LifecycleAudit {
+                    agent_type_name,
+                    since,
+                    until,
+                } => {
+                    self.cmd_lifecycle_audit(agent_type_name, since, until)
+                        .await
+                }
             }
         })
     }
@@ -348,6 +400,8 @@ impl WorkerCommandHandler {
         stream_args: StreamArgs,
         post_deploy_args: Option<PostDeployArgs>,
         schedule_at: Option<DateTime<Utc>>,
+        invocation_timeout: Option<humantime::Duration>,
+        at: Option<AgentTimeTravelPoint>,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
 
@@ -474,9 +528,11 @@ impl WorkerCommandHandler {
             log_action(
                 "Invoking",
                 format!(
-                    "agent {}/{} ",
+                    "agent {}/{} {}",
                     format_agent_name_match(&agent_name_match),
-                    method_name.log_color_highlight()
+                    method_name.log_color_highlight(),
+                    at.map(|at| format!("as of {}", at.to_string().log_color_highlight()))
+                        .unwrap_or_default()
                 ),
             );
             AgentInvocationMode::Await
@@ -490,6 +546,8 @@ impl WorkerCommandHandler {
             &source_language,
         )?;
 
+        // The output of a time-travel invocation is produced by a temporary replay, not the agent
+        let no_stream = no_stream || at.is_some();
         let mut connect_handle = if !no_stream {
             let connection = WorkerConnection::new(
                 self.ctx.worker_service_url().clone(),
@@ -528,6 +586,17 @@ impl WorkerCommandHandler {
             idempotency_key: Some(idempotency_key.value.clone()),
             deployment_revision: None,
             owner_account_email: None,
+            deadline: invocation_timeout
+                .map(|timeout| chrono::Duration::from_std(*timeout).map(|t| Utc::now() + t))
+                .transpose()?,
+            at_oplog_index: match at {
+                Some(AgentTimeTravelPoint::OplogIndex(idx)) => Some(idx.as_u64()),
+                _ => None,
+            },
+            at_timestamp: match at {
+                Some(AgentTimeTravelPoint::Timestamp(timestamp)) => Some(timestamp),
+                _ => None,
+            },
         };
 
         let clients = self.ctx.golem_clients().await?;
@@ -731,6 +800,101 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    async fn cmd_search_oplogs(
+        &self,
+        query: String,
+        agent_type_name: Option<AgentTypeName>,
+        component_name: Option<ComponentName>,
+        environment_wide: bool,
+        filters: Vec<String>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let (targets, filters) = if environment_wide {
+            let environment = self
+                .ctx
+                .environment_handler()
+                .resolve_environment(EnvironmentResolveMode::Any)
+                .await?;
+            (
+                vec![OplogSearchTarget::Environment(environment.environment_id.0)],
+                filters,
+            )
+        } else {
+            let (components, filters) = self
+                .resolve_list_components(agent_type_name, component_name, filters)
+                .await?;
+            (
+                components
+                    .iter()
+                    .map(|component| OplogSearchTarget::Component(component.id.0))
+                    .collect(),
+                filters,
+            )
+        };
+
+        let batch_size = self.ctx.http_batch_size();
+        let mut had_matches = false;
+        for target in &targets {
+            let mut cursor = Option::<String>::None;
+            loop {
+                let result = {
+                    let clients = self.ctx.golem_clients().await?;
+                    match target {
+                        OplogSearchTarget::Component(component_id) => {
+                            clients
+                                .worker
+                                .search_component_oplogs(
+                                    component_id,
+                                    &query,
+                                    since.as_ref(),
+                                    until.as_ref(),
+                                    Some(filters.as_slice()),
+                                    cursor.as_deref(),
+                                    Some(batch_size),
+                                    None,
+                                )
+                                .await
+                        }
+                        OplogSearchTarget::Environment(environment_id) => {
+                            clients
+                                .worker
+                                .search_environment_oplogs(
+                                    environment_id,
+                                    &query,
+                                    since.as_ref(),
+                                    until.as_ref(),
+                                    Some(filters.as_slice()),
+                                    cursor.as_deref(),
+                                    Some(batch_size),
+                                    None,
+                                )
+                                .await
+                        }
+                    }
+                    .map_service_error()?
+                };
+
+                if !result.matches.is_empty() {
+                    had_matches = true;
+                    self.ctx.log_handler().log_view(&result.matches)?;
+                }
+
+                match result.cursor {
+                    Some(next_cursor) => cursor = Some(next_cursor),
+                    None => break,
+                }
+            }
+        }
+
+        if !had_matches {
+            log_warn("No results.")
+        }
+
+        Ok(())
+    }
+
     async fn cmd_revert(
         &self,
         agent_name: AgentIdArgs,
@@ -797,6 +961,93 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    async fn cmd_export(&self, agent_name: AgentIdArgs, output: PathBuf) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let agent_name_match = self.match_agent_name(agent_name.agent_id).await?;
+        let (component, agent_name) = self
+            .component_by_agent_name_match(&agent_name_match)
+            .await?;
+
+        log_action(
+            "Exporting",
+            format!("agent {}", format_agent_name_match(&agent_name_match)),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+        let archive = clients
+            .worker
+            .export_agent(&component.id.0, &agent_name.0)
+            .await
+            .map_service_error()?;
+
+        if output.exists() && !self.confirm_file_overwrite(&output.to_string_lossy())? {
+            log_action(
+                "Export cancelled",
+                format!("by user for file {}", output.log_color_highlight()),
+            );
+            return Ok(());
+        }
+
+        fs::write(&output, &archive)?;
+
+        log_action(
+            "Exported",
+            format!(
+                "agent {} to {}",
+                format_agent_name_match(&agent_name_match),
+                output.log_color_highlight()
+            ),
+        );
+
+        self.ctx.log_handler().log_view(&AgentExportResult {
+            exported: true,
+            agent: agent_name.0.clone(),
+            archive: output,
+            archive_size: archive.len() as u64,
+        })?;
+
+        Ok(())
+    }
+
+    async fn cmd_import(&self, agent_name: AgentIdArgs, archive: PathBuf) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let agent_name_match = self.match_agent_name(agent_name.agent_id).await?;
+        let (component, agent_name) = self
+            .component_by_agent_name_match(&agent_name_match)
+            .await?;
+
+        let contents = fs::read(&archive)?;
+
+        log_action(
+            "Importing",
+            format!(
+                "agent {} from {}",
+                format_agent_name_match(&agent_name_match),
+                archive.log_color_highlight()
+            ),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+        clients
+            .worker
+            .import_agent(&component.id.0, &agent_name.0, contents)
+            .await
+            .map_service_error()?;
+
+        log_action(
+            "Imported",
+            format!("agent {}", format_agent_name_match(&agent_name_match)),
+        );
+
+        self.ctx.log_handler().log_view(&AgentImportResult {
+            imported: true,
+            agent: agent_name.0.clone(),
+            archive,
+        })?;
+
+        Ok(())
+    }
+
     async fn cmd_cancel_invocation(
         &self,
         agent_name: AgentIdArgs,
@@ -878,6 +1129,155 @@ impl WorkerCommandHandler {
         }
     }
 
+    async fn cmd_lifecycle_preview(&self, agent_type_name: AgentTypeName) -> anyhow::Result<()> {
+        let (components, filters) = self
+            .resolve_list_components(Some(agent_type_name.clone()), None, Vec::new())
+            .await?;
+
+        if components.iter().all(|component| {
+            component
+                .metadata
+                .agent_type_lifecycle_policy(&agent_type_name)
+                .is_none()
+        }) {
+            log_warn(format!(
+                "Agent type {} has no lifecycle policy",
+                agent_type_name.0.log_color_highlight()
+            ));
+            return Ok(());
+        }
+
+        let clients = self.ctx.golem_clients().await?;
+        let mut affected = Vec::<AgentLifecyclePreviewView>::new();
+
+        for component in &components {
+            let (workers, _) = self
+                .list_component_workers(
+                    &component.component_name,
+                    &component.id,
+                    Some(&filters),
+                    None,
+                    None,
+                    false,
+                )
+                .await?;
+
+            for worker in workers {
+                // The policy of the agent's own component revision is the one being enforced
+                let worker_component = self
+                    .ctx
+                    .component_handler()
+                    .get_component_revision_by_id(
+                        &worker.agent_id.component_id,
+                        worker.component_revision,
+                    )
+                    .await?;
+                let actions = worker_component
+                    .metadata
+                    .agent_type_lifecycle_policy(&agent_type_name)
+                    .map(|policy| policy.actions_for_status(worker.status))
+                    .unwrap_or_default();
+                if actions.is_empty() {
+                    continue;
+                }
+
+                let last_entry = clients
+                    .worker
+                    .get_oplog(
+                        &component.id.0,
+                        &worker.agent_id.agent_id,
+                        Some(worker.last_oplog_index.as_u64()),
+                        1,
+                        None,
+                        None,
+                    )
+                    .await
+                    .map_service_error()?;
+                let Some(last_activity) = last_entry
+                    .entries
+                    .first()
+                    .map(|entry| entry.entry.timestamp())
+                else {
+                    continue;
+                };
+
+                for (action, period) in actions {
+                    affected.push(AgentLifecyclePreviewView {
+                        component_name: component.component_name.clone(),
+                        agent_name: worker.agent_id.agent_id.as_str().into(),
+                        status: worker.status,
+                        last_activity,
+                        action,
+                        due_at: Timestamp::from(
+                            last_activity.to_millis() + period.as_millis() as u64,
+                        ),
+                    });
+                }
+            }
+        }
+
+        affected.sort_by_key(|entry| entry.due_at.to_millis());
+        self.ctx.log_handler().log_view(&affected)?;
+
+        Ok(())
+    }
+
+    async fn cmd_lifecycle_audit(
+        &self,
+        agent_type_name: AgentTypeName,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let (components, _) = self
+            .resolve_list_components(Some(agent_type_name.clone()), None, Vec::new())
+            .await?;
+
+        let clients = self.ctx.golem_clients().await?;
+        let mut applied = Vec::<AgentLifecycleAuditView>::new();
+
+        for component in &components {
+            let mut from = since;
+            loop {
+                let page = clients
+                    .worker
+                    .get_lifecycle_audit(&component.id.0, from.as_ref(), until.as_ref(), None)
+                    .await
+                    .map_service_error()?;
+
+                applied.extend(
+                    page.entries
+                        .into_iter()
+                        .filter(|entry| {
+                            LegacyParsedAgentId::parse_agent_type_name(&entry.agent_id.agent_id)
+                                .is_ok_and(|name| name == agent_type_name)
+                        })
+                        .map(|entry| AgentLifecycleAuditView {
+                            component_name: component.component_name.clone(),
+                            agent_name: entry.agent_id.agent_id.as_str().into(),
+                            timestamp: entry.timestamp,
+                            action: entry.action,
+                            last_oplog_index: entry.last_oplog_index,
+                        }),
+                );
+
+                match page.next_from {
+                    Some(next_from) => from = Some(next_from),
+                    None => break,
+                }
+            }
+        }
+
+        if applied.is_empty() {
+            log_warn("No results.");
+            return Ok(());
+        }
+
+        applied.sort_by_key(|entry| entry.timestamp.to_millis());
+        self.ctx.log_handler().log_view(&applied)?;
+
+        Ok(())
+    }
+
     async fn list_with_refresh(
         &self,
         components: &[ComponentDto],
@@ -2199,6 +2599,77 @@ impl WorkerCommandHandler {
     ) -> RawAgentId {
         try_recanonicalize_agent_name_with_parsed(agent_name, component).0
     }
+
+    /// Parses an agent ID in any of the syntaxes accepted by the CLI against the agent types of
+    /// the given component
+    pub fn parse_agent_id(
+        &self,
+        component: &ComponentDto,
+        agent_name: &RawAgentId,
+    ) -> anyhow::Result<(LegacyParsedAgentId, AgentType)> {
+        let agent_name = self.try_recanonicalize_agent_name(agent_name, component);
+        let (agent_id, agent_type) =
+            LegacyParsedAgentId::parse_and_resolve_type(&agent_name.0, &component.metadata)
+                .map_err(|err| anyhow!("Invalid agent ID {}: {err}", agent_name.0))?;
+        let agent_id = normalize_public_agent_id(&agent_id, &agent_type)?;
+        Ok((agent_id, agent_type))
+    }
+
+    /// Parses an agent method invocation against the agent types of the given component, the same
+    /// way as `agent invoke`, but without resolving or deploying anything on a server.
+    pub fn parse_agent_invocation(
+        &self,
+        component: &ComponentDto,
+        agent_name: &RawAgentId,
+        function_name: &str,
+        arguments: Vec<AgentFunctionArgument>,
+    ) -> anyhow::Result<ParsedAgentInvocation> {
+        let (agent_id, agent_type) = self.parse_agent_id(component, agent_name)?;
+
+        let method_name = match resolve_agent_method_name(function_name, &agent_type) {
+            Ok(match_) => match_.option,
+            Err(Error::Ambiguous { raw_options, .. }) => bail!(
+                "The requested method name ({function_name}) is ambiguous, it matches {}",
+                raw_options.join(", ")
+            ),
+            Err(Error::NotFound { .. }) => bail!(
+                "The requested method name ({function_name}) was not found in agent type {}",
+                agent_type.type_name
+            ),
+        };
+
+        let source_language = SourceLanguage::from(agent_type.source_language.as_str());
+        let method_parameters = parse_method_parameters_with_error_table(
+            &agent_type,
+            &method_name,
+            arguments,
+            &source_language,
+        )
+        .map_err(|err| {
+            // The parameter errors are already logged as a table
+            if err.is::<NonSuccessfulExit>() {
+                anyhow!("Invalid arguments for method {method_name}")
+            } else {
+                err
+            }
+        })?;
+        let input_schema = agent_type
+            .methods
+            .iter()
+            .find(|method| method.name == method_name)
+            .map(|method| method.input_schema.clone())
+            .ok_or_else(|| anyhow!("Method '{method_name}' not found in agent type"))?;
+        let method_parameters =
+            DataValue::try_from_untyped_json(method_parameters, input_schema)
+                .map_err(|err| anyhow!("Invalid arguments for method {method_name}: {err}"))?;
+
+        Ok(ParsedAgentInvocation {
+            agent_id,
+            agent_type,
+            method_name,
+            method_parameters,
+        })
+    }
 }
 
 /// Re-canonicalize an agent id string against the component's agent type
@@ -3222,6 +3693,7 @@ mod tests {
                 output_schema: DataSchema::Tuple(NamedElementSchemas::empty()),
                 http_endpoint: vec![],
                 read_only: None,
+                timeout_nanos: None,
             }],
             dependencies: vec![],
             mode,
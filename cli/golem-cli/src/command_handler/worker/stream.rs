@@ -328,6 +328,10 @@ impl WorkerConnection {
                                         .await;
                                 }
                             }
+                            AgentEvent::WebSocketSend { .. }
+                            | AgentEvent::WebSocketClose { .. } => {
+                                // WebSocket traffic is only relevant for the http api gateway
+                            }
                             AgentEvent::ClientLagged {
                                 number_of_missed_messages,
                             } => {
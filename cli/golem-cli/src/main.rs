@@ -23,6 +23,7 @@ use std::sync::Arc;
 #[cfg(feature = "server-commands")]
 mod hooks {
     use golem_cli::command::server::ServerSubcommand;
+    use golem_cli::command::test::TestArgs;
     use golem_cli::command_handler::CommandHandlerHooks;
     use golem_cli::context::Context;
 
@@ -41,6 +42,15 @@ mod hooks {
             unimplemented!()
         }
 
+        #[cfg(feature = "server-commands")]
+        async fn handler_test_command(
+            &self,
+            _ctx: Arc<Context>,
+            _args: TestArgs,
+        ) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
         #[cfg(feature = "server-commands")]
         async fn run_server() -> anyhow::Result<()> {
             unimplemented!()
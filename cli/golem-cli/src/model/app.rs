@@ -545,6 +545,7 @@ impl Application {
                         plugins: Some(template_layer_props.plugins.value().clone()),
                         files_merge_mode: None,
                         files: Some(template_layer_props.files.value().clone()),
+                        lifecycle: None,
                     };
 
                     let template_id =
@@ -1352,6 +1353,10 @@ impl<'a> Agent<'a> {
     pub fn files(&self) -> &[app_raw::InitialComponentFile] {
         &self.resolved.properties.files
     }
+
+    pub fn lifecycle(&self) -> Option<&app_raw::AgentLifecycle> {
+        self.resolved.properties.lifecycle.as_ref()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1513,6 +1518,7 @@ impl<'a> Component<'a> {
             plugins: Some(self.layer_properties().plugins.value().clone()),
             files_merge_mode: None,
             files: Some(self.layer_properties().files.value().clone()),
+            lifecycle: None,
         }
     }
 }
@@ -1764,6 +1770,9 @@ impl Layer for AgentLayer {
                     properties.files.clone().unwrap_or_default(),
                 ),
             );
+            value
+                .lifecycle
+                .apply_layer(id, selection, properties.lifecycle.clone());
         }
 
         Ok(())
@@ -1781,6 +1790,7 @@ pub struct AgentLayerProperties {
     env: MapProperty<AgentLayer, String, String>,
     plugins: VecProperty<AgentLayer, app_raw::PluginInstallation>,
     files: VecProperty<AgentLayer, app_raw::InitialComponentFile>,
+    lifecycle: OptionalProperty<AgentLayer, app_raw::AgentLifecycle>,
 }
 
 impl AgentLayerProperties {
@@ -1799,6 +1809,7 @@ impl AgentLayerProperties {
         self.env.compact_trace();
         self.plugins.compact_trace();
         self.files.compact_trace();
+        self.lifecycle.compact_trace();
     }
 
     pub fn with_compacted_traces(&self) -> Self {
@@ -1833,6 +1844,7 @@ pub struct AgentProperties {
     pub env: BTreeMap<String, String>,
     pub plugins: Vec<app_raw::PluginInstallation>,
     pub files: Vec<app_raw::InitialComponentFile>,
+    pub lifecycle: Option<app_raw::AgentLifecycle>,
 }
 
 impl AgentProperties {
@@ -1847,6 +1859,7 @@ impl AgentProperties {
                 .collect(),
             plugins: layer_properties.plugins.value().clone(),
             files: layer_properties.files.value().clone(),
+            lifecycle: layer_properties.lifecycle.value().clone(),
         }
     }
 }
@@ -2498,7 +2511,9 @@ mod app_builder {
                                         (
                                             k,
                                             HttpApiDeploymentAgentOptions {
-                                                security: resolve_agent_security(validation, &v)
+                                                security: resolve_agent_security(validation, &v),
+                                                rate_limits: v.rate_limits,
+                                                method_rate_limits: v.method_rate_limits.into_iter().collect(),
                                             }
                                         )
                                     )
@@ -2514,6 +2529,8 @@ mod app_builder {
                                         ),
                                         openapi_prefix: HttpApiDeploymentCreation::normalize_openapi_endpoint_prefix(api_deployment.openapi_endpoint.unwrap_or(HttpApiDeploymentCreation::default_openapi_endpoint_prefix())),
                                         agents,
+                                        rate_limits: api_deployment.rate_limits,
+                                        graphql_endpoint: api_deployment.graphql_endpoint.and_then(HttpApiDeploymentCreation::normalize_graphql_endpoint),
                                     },
                                 ));
                             }
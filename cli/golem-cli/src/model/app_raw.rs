@@ -21,9 +21,11 @@ use crate::{APP_MANIFEST_JSON_SCHEMA, fs};
 use anyhow::{Context, anyhow};
 use golem_common::model::agent::AgentTypeName;
 use golem_common::model::component::{AgentFilePermissions, CanonicalFilePath};
+use golem_common::model::component_metadata::AgentLifecyclePolicy;
 use golem_common::model::diff;
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentName;
+use golem_common::model::http_api_deployment::HttpApiRateLimit;
 use golem_common::model::quota::{EnforcementAction, ResourceLimit, ResourceName};
 use golem_common::model::security_scheme::SecuritySchemeName;
 use indexmap::IndexMap;
@@ -33,6 +35,7 @@ use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
+use std::time::Duration;
 use strum::IntoEnumIterator;
 use url::Url;
 
@@ -298,6 +301,7 @@ impl ComponentTemplate {
                 plugins: self.plugins.clone(),
                 files_merge_mode: self.files_merge_mode,
                 files: self.files.clone(),
+                lifecycle: None,
             },
         }
     }
@@ -357,6 +361,7 @@ impl Component {
                 plugins: self.plugins.clone(),
                 files_merge_mode: self.files_merge_mode,
                 files: self.files.clone(),
+                lifecycle: None,
             },
         }
     }
@@ -412,6 +417,7 @@ impl ComponentPreset {
                 plugins: self.plugins,
                 files_merge_mode: self.files_merge_mode,
                 files: self.files,
+                lifecycle: None,
             },
         }
     }
@@ -436,6 +442,8 @@ pub struct Agent {
     pub files_merge_mode: Option<VecMergeMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<InitialComponentFile>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<AgentLifecycle>,
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub presets: IndexMap<String, AgentPreset>,
 }
@@ -450,6 +458,7 @@ impl Agent {
             plugins: self.plugins.clone(),
             files_merge_mode: self.files_merge_mode,
             files: self.files.clone(),
+            lifecycle: self.lifecycle.clone(),
         }
     }
 }
@@ -473,6 +482,8 @@ pub struct AgentPreset {
     pub files_merge_mode: Option<VecMergeMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<InitialComponentFile>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<AgentLifecycle>,
 }
 
 impl AgentPreset {
@@ -485,6 +496,41 @@ impl AgentPreset {
             plugins: self.plugins,
             files_merge_mode: self.files_merge_mode,
             files: self.files,
+            lifecycle: self.lifecycle,
+        }
+    }
+}
+
+/// Lifecycle policy of an agent type, with durations in humantime format (e.g. `30days`, `12h`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AgentLifecycle {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "humantime_serde"
+    )]
+    pub delete_after_idle: Option<Duration>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "humantime_serde"
+    )]
+    pub delete_after_exit: Option<Duration>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "humantime_serde"
+    )]
+    pub archive_after_idle: Option<Duration>,
+}
+
+impl AgentLifecycle {
+    pub fn to_policy(&self) -> AgentLifecyclePolicy {
+        AgentLifecyclePolicy {
+            delete_after_idle_nanos: self.delete_after_idle.map(|d| d.as_nanos() as u64),
+            delete_after_exit_nanos: self.delete_after_exit.map(|d| d.as_nanos() as u64),
+            archive_after_idle_nanos: self.archive_after_idle.map(|d| d.as_nanos() as u64),
         }
     }
 }
@@ -523,6 +569,10 @@ pub struct HttpApiDeployment {
     pub openapi_endpoint: Option<String>,
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub agents: IndexMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits: Vec<HttpApiRateLimit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql_endpoint: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -714,6 +764,8 @@ pub struct AgentLayerProperties {
     pub files_merge_mode: Option<VecMergeMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<InitialComponentFile>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<AgentLifecycle>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -723,6 +775,10 @@ pub struct HttpApiDeploymentAgentOptions {
     pub security_scheme: Option<SecuritySchemeName>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub test_session_header_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits: Vec<HttpApiRateLimit>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub method_rate_limits: IndexMap<String, Vec<HttpApiRateLimit>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1323,6 +1379,19 @@ mod test {
             .boxed()
     }
 
+    fn arb_agent_lifecycle_model() -> BoxedStrategy<AgentLifecycle> {
+        let arb_duration = || arb_opt((1u64..=10_000_000).prop_map(Duration::from_secs).boxed());
+        (arb_duration(), arb_duration(), arb_duration())
+            .prop_map(
+                |(delete_after_idle, delete_after_exit, archive_after_idle)| AgentLifecycle {
+                    delete_after_idle,
+                    delete_after_exit,
+                    archive_after_idle,
+                },
+            )
+            .boxed()
+    }
+
     fn arb_agent_preset_model() -> BoxedStrategy<AgentPreset> {
         (
             any::<bool>(),
@@ -1333,6 +1402,7 @@ mod test {
             arb_opt(prop::collection::vec(arb_plugin_installation_model(), 0..=2).boxed()),
             arb_opt(arb_vec_merge_mode_model()),
             arb_opt(prop::collection::vec(arb_initial_component_file_model(), 0..=2).boxed()),
+            arb_opt(arb_agent_lifecycle_model()),
         )
             .prop_map(
                 |(
@@ -1344,6 +1414,7 @@ mod test {
                     plugins,
                     files_merge_mode,
                     files,
+                    lifecycle,
                 )| AgentPreset {
                     default: is_default.then_some(Marker),
                     config,
@@ -1353,6 +1424,7 @@ mod test {
                     plugins,
                     files_merge_mode,
                     files,
+                    lifecycle,
                 },
             )
             .boxed()
@@ -1368,6 +1440,7 @@ mod test {
             arb_opt(prop::collection::vec(arb_plugin_installation_model(), 0..=2).boxed()),
             arb_opt(arb_vec_merge_mode_model()),
             arb_opt(prop::collection::vec(arb_initial_component_file_model(), 0..=2).boxed()),
+            arb_opt(arb_agent_lifecycle_model()),
             prop::collection::vec((arb_ident(), arb_agent_preset_model()), 0..=2)
                 .prop_map(IndexMap::from_iter),
         )
@@ -1381,6 +1454,7 @@ mod test {
                     plugins,
                     files_merge_mode,
                     files,
+                    lifecycle,
                     presets,
                 )| Agent {
                     templates,
@@ -1391,6 +1465,7 @@ mod test {
                     plugins,
                     files_merge_mode,
                     files,
+                    lifecycle,
                     presets,
                 },
             )
@@ -1499,6 +1574,8 @@ mod test {
                                 HttpApiDeploymentAgentOptions {
                                     security_scheme,
                                     test_session_header_name,
+                                    rate_limits: vec![],
+                                    method_rate_limits: IndexMap::new(),
                                 }
                             },
                         ),
@@ -1513,6 +1590,8 @@ mod test {
                     webhook_url,
                     openapi_endpoint,
                     agents,
+                    rate_limits: vec![],
+                    graphql_endpoint: None,
                 },
             )
             .boxed()
@@ -16,7 +16,9 @@ use crate::agent_id_display::SourceLanguage;
 use crate::model::environment::ResolvedEnvironmentIdentity;
 use crate::model::worker::RawAgentId;
 use chrono::{DateTime, Utc};
-use golem_common::base_model::component_metadata::AgentTypeProvisionConfig;
+use golem_common::base_model::component_metadata::{
+    AgentLifecyclePolicy, AgentTypeProvisionConfig,
+};
 use golem_common::model::agent::AgentTypeName;
 use golem_common::model::agent::{
     AgentType, ComponentModelElementSchema, DataSchema, ElementSchema,
@@ -133,6 +135,7 @@ pub struct AgentTypeManifestProvisionConfig {
     pub files_source: PathBuf,
     pub files: Vec<app_raw::InitialComponentFile>,
     pub plugins: Vec<app_raw::PluginInstallation>,
+    pub lifecycle: Option<AgentLifecyclePolicy>,
 }
 
 impl AgentTypeManifestProvisionConfig {
@@ -157,6 +160,7 @@ impl AgentTypeManifestProvisionConfig {
             config: self.config.clone(),
             files,
             plugin_installations: resolved_plugins,
+            lifecycle: self.lifecycle.clone(),
         }
     }
 }
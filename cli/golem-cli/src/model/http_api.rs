@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use golem_common::model::agent::AgentTypeName;
-use golem_common::model::http_api_deployment::HttpApiDeploymentAgentOptions;
+use golem_common::model::http_api_deployment::{HttpApiDeploymentAgentOptions, HttpApiRateLimit};
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug)]
@@ -21,6 +21,8 @@ pub struct HttpApiDeploymentDeployProperties {
     pub webhooks_prefix: String,
     pub openapi_prefix: String,
     pub agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+    pub rate_limits: Vec<HttpApiRateLimit>,
+    pub graphql_endpoint: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -17,7 +17,7 @@ use crate::log::log_error;
 use anyhow::{anyhow, bail};
 use golem_client::model::AgentInvocationResult;
 use golem_common::model::IdempotencyKey;
-use golem_common::model::agent::{AgentType, DataSchema, DataValue, ElementValue};
+use golem_common::model::agent::{AgentMethod, AgentType, DataSchema, DataValue, ElementValue};
 use golem_common::schema::adapters::value_and_type_to_typed_schema_value;
 use golem_wasm::analysis::{AnalysedType, TypeTuple};
 use golem_wasm::{Value, ValueAndType};
@@ -44,6 +44,27 @@ impl InvokeResultView {
         result: AgentInvocationResult,
         agent_type: &AgentType,
         method_name: &str,
+    ) -> Self {
+        let result = Self::try_get_agent_result(&result, agent_type, method_name);
+        Self::new_agent_invoke_result(idempotency_key, result, agent_type, method_name)
+    }
+
+    /// Creates the view of an agent invocation result that was already converted to a
+    /// [`DataValue`], for example when invoking an executor directly
+    pub fn new_agent_invoke_data_value(
+        idempotency_key: IdempotencyKey,
+        result: Option<DataValue>,
+        agent_type: &AgentType,
+        method_name: &str,
+    ) -> Self {
+        Self::new_agent_invoke_result(idempotency_key, Ok(result), agent_type, method_name)
+    }
+
+    fn new_agent_invoke_result(
+        idempotency_key: IdempotencyKey,
+        result: anyhow::Result<Option<DataValue>>,
+        agent_type: &AgentType,
+        method_name: &str,
     ) -> Self {
         let source_language = SourceLanguage::from(agent_type.source_language.as_str());
 
@@ -57,7 +78,7 @@ impl InvokeResultView {
         .to_string();
 
         let (is_void_result, result_values) =
-            match Self::try_get_agent_results(&result, agent_type, method_name) {
+            match Self::try_get_agent_results(result, agent_type, method_name) {
                 Ok(r) => r,
                 Err(err) => {
                     log_error(format!("{err}"));
@@ -114,16 +135,39 @@ impl InvokeResultView {
         }
     }
 
-    fn try_get_agent_results(
+    fn try_get_agent_result(
         result: &AgentInvocationResult,
         agent_type: &AgentType,
         method_name: &str,
-    ) -> anyhow::Result<(bool, Vec<ValueAndType>)> {
-        let method = agent_type
+    ) -> anyhow::Result<Option<DataValue>> {
+        let method = Self::find_method(agent_type, method_name)?;
+
+        let Some(ref untyped) = result.result else {
+            return Ok(None);
+        };
+
+        DataValue::try_from_untyped_json(untyped.clone(), method.output_schema.clone())
+            .map(Some)
+            .map_err(|e| anyhow!("Failed to parse agent result: {e}"))
+    }
+
+    fn find_method<'a>(
+        agent_type: &'a AgentType,
+        method_name: &str,
+    ) -> anyhow::Result<&'a AgentMethod> {
+        agent_type
             .methods
             .iter()
             .find(|m| m.name == method_name)
-            .ok_or_else(|| anyhow!("Method '{method_name}' not found in agent type"))?;
+            .ok_or_else(|| anyhow!("Method '{method_name}' not found in agent type"))
+    }
+
+    fn try_get_agent_results(
+        result: anyhow::Result<Option<DataValue>>,
+        agent_type: &AgentType,
+        method_name: &str,
+    ) -> anyhow::Result<(bool, Vec<ValueAndType>)> {
+        let method = Self::find_method(agent_type, method_name)?;
 
         let output_schemas = match &method.output_schema {
             DataSchema::Tuple(schemas) => &schemas.elements,
@@ -134,14 +178,10 @@ impl InvokeResultView {
             return Ok((true, vec![]));
         }
 
-        let Some(ref untyped) = result.result else {
+        let Some(data_value) = result? else {
             return Ok((false, vec![]));
         };
 
-        let data_value =
-            DataValue::try_from_untyped_json(untyped.clone(), method.output_schema.clone())
-                .map_err(|e| anyhow!("Failed to parse agent result: {e}"))?;
-
         let DataValue::Tuple(elements) = data_value else {
             bail!("Non-tuple agent result not supported for result rendering");
         };
@@ -40,6 +40,7 @@ use clap::builder::{StringValueParser, TypedValueParser};
 use clap::error::{ContextKind, ContextValue, ErrorKind};
 use clap::{Arg, Error};
 use golem_common::model::account::AccountId;
+use golem_common::model::agent::AgentTypeSchemaFormat;
 use golem_common::model::quota::EnforcementAction;
 use golem_common::model::security_scheme::ProviderKind;
 use serde::{Deserialize, Serialize};
@@ -334,6 +335,22 @@ impl From<EnforcementActionArg> for EnforcementAction {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum AgentTypeSchemaFormatArg {
+    Protobuf,
+    JsonSchema,
+}
+
+impl From<AgentTypeSchemaFormatArg> for AgentTypeSchemaFormat {
+    fn from(value: AgentTypeSchemaFormatArg) -> Self {
+        match value {
+            AgentTypeSchemaFormatArg::Protobuf => AgentTypeSchemaFormat::Protobuf,
+            AgentTypeSchemaFormatArg::JsonSchema => AgentTypeSchemaFormat::JsonSchema,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
 #[clap(rename_all = "lower")]
 pub enum ProviderKindArg {
@@ -22,6 +22,7 @@ use uuid::Uuid;
 #[serde(tag = "type")]
 pub enum PluginTypeSpecificManifest {
     OplogProcessor(OplogProcessorManifest),
+    InvocationInterceptor(InvocationInterceptorManifest),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -31,6 +32,13 @@ pub struct OplogProcessorManifest {
     pub component_revision: ComponentRevision,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvocationInterceptorManifest {
+    pub component_id: Uuid,
+    pub component_revision: ComponentRevision,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PluginManifest {
@@ -50,6 +50,29 @@ impl TextView for AgentRevertResult {
     fn log(&self) {}
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentExportResult {
+    pub exported: bool,
+    pub agent: String,
+    pub archive: PathBuf,
+    pub archive_size: u64,
+}
+
+impl TextView for AgentExportResult {
+    fn log(&self) {}
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentImportResult {
+    pub imported: bool,
+    pub agent: String,
+    pub archive: PathBuf,
+}
+
+impl TextView for AgentImportResult {
+    fn log(&self) {}
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct AgentPluginToggleResult {
     pub activated: bool,
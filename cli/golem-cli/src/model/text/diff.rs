@@ -143,6 +143,12 @@ impl TextView for DeploymentDiff {
                             if diff.openapi_endpoint_changed {
                                 logln("    - openapi_endpoint");
                             }
+                            if diff.rate_limits_changed {
+                                logln("    - rate_limits");
+                            }
+                            if diff.graphql_endpoint_changed {
+                                logln("    - graphql_endpoint");
+                            }
                             if !diff.agents_changes.is_empty() {
                                 logln("    - agents");
                                 for (agent_name, agent_diff) in &diff.agents_changes {
@@ -173,6 +179,9 @@ impl TextView for DeploymentDiff {
                                             if diff.test_session_header_changed {
                                                 logln("        - test_session_header");
                                             }
+                                            if diff.rate_limits_changed {
+                                                logln("        - rate_limits");
+                                            }
                                         }
                                     }
                                 }
@@ -303,6 +312,9 @@ fn log_provision_config_diff(diff: &AgentTypeProvisionConfigDiff) {
             diff.plugin_changes.len()
         ));
     }
+    if diff.lifecycle_changed {
+        logln("        - lifecycle policy");
+    }
 }
 
 pub fn log_unified_diff(diff: &str) {
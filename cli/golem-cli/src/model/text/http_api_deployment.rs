@@ -16,7 +16,9 @@ use crate::model::text::fmt::{
     Column, FieldsBuilder, MessageWithFields, TextView, format_main_id, format_message_highlight,
     log_table, new_table_full_condensed,
 };
-use golem_common::model::http_api_deployment::{HttpApiDeployment, HttpApiDeploymentAgentSecurity};
+use golem_common::model::http_api_deployment::{
+    HttpApiDeployment, HttpApiDeploymentAgentSecurity, HttpApiRateLimit,
+};
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +50,13 @@ fn http_api_deployment_fields(dep: &HttpApiDeployment) -> Vec<(String, String)>
         .fmt_field("OpenAPI endpoint", &dep.openapi_endpoint_prefix, |d| {
             d.clone()
         })
+        .fmt_field_optional(
+            "Rate limits",
+            &dep.rate_limits,
+            !dep.rate_limits.is_empty(),
+            |rate_limits| format_rate_limits(rate_limits),
+        )
+        .fmt_field_option("GraphQL endpoint", &dep.graphql_endpoint, |d| d.clone())
         .fmt_field("Agents", &dep.agents, |agents| {
             let mut result = String::new();
             for (agent_name, agent_options) in agents {
@@ -61,6 +70,19 @@ fn http_api_deployment_fields(dep: &HttpApiDeployment) -> Vec<(String, String)>
                         result.push_str(&format!("  Test session header: {}", inner.header_name));
                     }
                 }
+                if !agent_options.rate_limits.is_empty() {
+                    result.push_str(&format!(
+                        "  Rate limits: {}",
+                        format_rate_limits(&agent_options.rate_limits)
+                    ));
+                }
+                for (method_name, rate_limits) in &agent_options.method_rate_limits {
+                    result.push_str(&format!(
+                        "  Rate limits of {}: {}",
+                        method_name,
+                        format_rate_limits(rate_limits)
+                    ));
+                }
             }
             result
         });
@@ -68,6 +90,19 @@ fn http_api_deployment_fields(dep: &HttpApiDeployment) -> Vec<(String, String)>
     fields.build()
 }
 
+fn format_rate_limits(rate_limits: &[HttpApiRateLimit]) -> String {
+    rate_limits
+        .iter()
+        .map(|rate_limit| {
+            format!(
+                "{}/{} (burst {}) per {}",
+                rate_limit.value, rate_limit.period, rate_limit.max, rate_limit.key
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl TextView for Vec<HttpApiDeployment> {
     fn log(&self) {
         let mut table = new_table_full_condensed(vec![
@@ -139,12 +139,16 @@ fn plugin_registration_fields(plugin: &PluginRegistrationDto) -> Vec<(String, St
         .field("Type", &plugin.typ_as_str())
         .fmt_field_option(
             "Component ID",
-            &plugin.oplog_processor_component_id(),
+            &plugin
+                .oplog_processor_component_id()
+                .or(plugin.invocation_interceptor_component_id()),
             format_id,
         )
         .fmt_field_option(
             "Component Version",
-            &plugin.oplog_processor_component_revision(),
+            &plugin
+                .oplog_processor_component_revision()
+                .or(plugin.invocation_interceptor_component_revision()),
             format_id,
         );
 
@@ -12,14 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::agent_id_display::SourceLanguage;
+use crate::agent_id_display::{SourceLanguage, render_type_for_language};
 use crate::log::{LogColorize, logln};
 use crate::model::deploy::TryUpdateAllWorkersResult;
 use crate::model::environment::EnvironmentReference;
 use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::text::fmt::*;
 use crate::model::worker::{
-    AgentMetadataView, AgentNameMatch, AgentsMetadataResponseView, RawAgentId,
+    AgentLifecycleAuditView, AgentLifecyclePreviewView, AgentMetadataView, AgentNameMatch,
+    AgentsMetadataResponseView, RawAgentId,
 };
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
@@ -37,11 +38,13 @@ use golem_common::model::agent::{
 };
 use golem_common::model::component::ComponentName;
 use golem_common::model::oplog::{
-    MultipartPartData, PluginInstallationDescription, PublicAgentInvocation,
+    AgentOplogSearchMatch, MultipartPartData, PluginInstallationDescription, PublicAgentInvocation,
     PublicAgentInvocationResult, PublicAttributeValue, PublicOplogEntry, PublicSnapshotData,
     PublicUpdateDescription, StringAttributeValue,
 };
-use golem_common::model::worker::{AgentConfigEntryDto, UpdateRecord};
+use golem_common::model::worker::{
+    AgentConfigEntryDto, PromiseMetadata, PromiseState, UpdateRecord,
+};
 use golem_wasm::{ValueAndType, print_value_and_type};
 use indoc::indoc;
 use itertools::Itertools;
@@ -108,6 +111,41 @@ fn format_untyped_config(config: &[AgentConfigEntryDto]) -> String {
         .join("\n")
 }
 
+fn format_promises(promises: &[PromiseMetadata], source_language: &SourceLanguage) -> String {
+    promises
+        .iter()
+        .map(|promise| {
+            let state = match promise.state {
+                PromiseState::Pending => "pending".yellow(),
+                PromiseState::Completed => "completed".green(),
+                PromiseState::TimedOut => "timed out".red(),
+                PromiseState::Cancelled => "cancelled".bright_black(),
+            };
+            let mut line = format!(
+                "{}: {state}",
+                promise.oplog_index.to_string().log_color_highlight()
+            );
+            if let Some(deadline) = &promise.deadline {
+                let _ = write!(line, ", deadline {deadline}");
+            }
+            if let Some(payload_type) = &promise.payload_type {
+                let payload_type =
+                    match golem_common::schema::adapters::analysed_type_to_schema_graph(
+                        payload_type,
+                    ) {
+                        Ok(graph) => {
+                            let root = graph.root.clone();
+                            render_type_for_language(source_language, &graph, &root, false)
+                        }
+                        Err(_) => "<unknown>".to_string(),
+                    };
+                let _ = write!(line, ", payload {payload_type}");
+            }
+            line
+        })
+        .join("\n")
+}
+
 fn to_sorted_btree_map(map: &HashMap<String, String>) -> BTreeMap<String, String> {
     map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
 }
@@ -225,6 +263,12 @@ impl MessageWithFields for WorkerGetView {
                 self.metadata.pending_invocation_count > 0,
                 |n| n.to_string(),
             )
+            .fmt_field_optional(
+                "Promises",
+                &self.metadata.promises,
+                !self.metadata.promises.is_empty(),
+                |promises| format_promises(promises, &self.metadata.source_language),
+            )
             .fmt_field_optional(
                 "Last error",
                 &self.metadata.last_error,
@@ -266,6 +310,52 @@ impl TextView for AgentsMetadataResponseView {
     }
 }
 
+impl TextView for Vec<AgentLifecyclePreviewView> {
+    fn log(&self) {
+        let mut table = new_table_full_condensed(vec![
+            Column::new("Component"),
+            Column::new("Agent"),
+            Column::new("Status").fixed(),
+            Column::new("Last activity").fixed(),
+            Column::new("Action").fixed(),
+            Column::new("Due at").fixed(),
+        ]);
+        for entry in self {
+            table.add_row(vec![
+                entry.component_name.0.clone(),
+                entry.agent_name.0.clone(),
+                format_status(&entry.status),
+                entry.last_activity.to_string(),
+                entry.action.to_string(),
+                entry.due_at.to_string(),
+            ]);
+        }
+        log_table(table);
+    }
+}
+
+impl TextView for Vec<AgentLifecycleAuditView> {
+    fn log(&self) {
+        let mut table = new_table_full_condensed(vec![
+            Column::new("Component"),
+            Column::new("Agent"),
+            Column::new("Applied at").fixed(),
+            Column::new("Action").fixed(),
+            Column::new("Last oplog index").fixed(),
+        ]);
+        for entry in self {
+            table.add_row(vec![
+                entry.component_name.0.clone(),
+                entry.agent_name.0.clone(),
+                entry.timestamp.to_string(),
+                entry.action.to_string(),
+                entry.last_oplog_index.to_string(),
+            ]);
+        }
+        log_table(table);
+    }
+}
+
 impl AgentsMetadataResponseView {
     fn status_color(status: &AgentStatus, colorize: bool) -> ComfyColor {
         if colorize {
@@ -436,6 +526,19 @@ impl TextView for Vec<(u64, PublicOplogEntry)> {
     }
 }
 
+impl TextView for Vec<AgentOplogSearchMatch> {
+    fn log(&self) {
+        for search_match in self {
+            logln(format!(
+                "{} {}: ",
+                format_id(&search_match.agent_id.agent_id),
+                format_main_id(&format!("#{:0>5}", search_match.oplog_index.as_u64()))
+            ));
+            search_match.entry.log()
+        }
+    }
+}
+
 impl TextView for PublicOplogEntry {
     fn log(&self) {
         let pad = "          ";
@@ -556,6 +659,7 @@ impl TextView for PublicOplogEntry {
                     PublicAgentInvocationResult::LoadSnapshot(_) => "load snapshot",
                     PublicAgentInvocationResult::SaveSnapshot(_) => "save snapshot",
                     PublicAgentInvocationResult::ProcessOplogEntries(_) => "process oplog entries",
+                    PublicAgentInvocationResult::InterceptInvocation(_) => "intercept invocation",
                 };
                 logln(format!(
                     "{} ({})",
@@ -583,6 +687,9 @@ impl TextView for PublicOplogEntry {
                     PublicAgentInvocationResult::ProcessOplogEntries(result) => {
                         log_optional_error(pad, &result.error);
                     }
+                    PublicAgentInvocationResult::InterceptInvocation(result) => {
+                        log_optional_error(pad, &result.error);
+                    }
                     PublicAgentInvocationResult::SaveSnapshot(snapshot_result) => {
                         log_snapshot_data(pad, &snapshot_result.snapshot);
                     }
@@ -721,6 +828,21 @@ impl TextView for PublicOplogEntry {
                         format_id(&inner_params.target_revision),
                     ));
                 }
+                PublicAgentInvocation::InterceptInvocation(inner_params) => {
+                    logln(format!(
+                        "{} {}",
+                        format_message_highlight("ENQUEUED INTERCEPT INVOCATION"),
+                        format_id(&inner_params.method_name),
+                    ));
+                    logln(format!(
+                        "{pad}at:                {}",
+                        format_id(&params.timestamp)
+                    ));
+                    logln(format!(
+                        "{pad}idempotency key:   {}",
+                        format_id(&inner_params.idempotency_key),
+                    ));
+                }
             },
             PublicOplogEntry::PendingUpdate(params) => {
                 logln(format_message_highlight("ENQUEUED UPDATE"));
@@ -1073,6 +1195,32 @@ impl TextView for PublicOplogEntry {
                     format_id(&params.name)
                 ));
             }
+            PublicOplogEntry::WebSocketConnected(params) => {
+                logln(format_message_highlight("WEBSOCKET CONNECTED"));
+                logln(format!(
+                    "{pad}at:                {}",
+                    format_id(&params.timestamp)
+                ));
+                logln(format!(
+                    "{pad}connection id:     {}",
+                    format_id(&params.connection_id)
+                ));
+                logln(format!(
+                    "{pad}method name:       {}",
+                    format_id(&params.method_name)
+                ));
+            }
+            PublicOplogEntry::WebSocketDisconnected(params) => {
+                logln(format_message_highlight("WEBSOCKET DISCONNECTED"));
+                logln(format!(
+                    "{pad}at:                {}",
+                    format_id(&params.timestamp)
+                ));
+                logln(format!(
+                    "{pad}connection id:     {}",
+                    format_id(&params.connection_id)
+                ));
+            }
         }
     }
 }
@@ -23,10 +23,12 @@ use clap_verbosity_flag::Verbosity;
 use colored::control::SHOULD_COLORIZE;
 use golem_common::base_model::component_metadata::AgentTypeProvisionConfig;
 use golem_common::model::account::AccountId;
-use golem_common::model::agent::{AgentTypeName, LegacyParsedAgentId};
+use golem_common::model::agent::{AgentType, AgentTypeName, DataValue, LegacyParsedAgentId};
 use golem_common::model::component::{ComponentName, ComponentRevision};
+use golem_common::model::component_metadata::AgentLifecycleAction;
 use golem_common::model::environment::EnvironmentId;
-use golem_common::model::worker::{AgentConfigEntryDto, UpdateRecord};
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::worker::{AgentConfigEntryDto, PromiseMetadata, UpdateRecord};
 use golem_common::model::{AgentId, AgentResourceDescription, AgentStatus, Timestamp};
 use serde_derive::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
@@ -114,6 +116,38 @@ impl Display for AgentListMode {
     }
 }
 
+/// Point of an agent's history a read-only invocation is made against, given either as an
+/// oplog index or as an RFC 3339 timestamp
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AgentTimeTravelPoint {
+    OplogIndex(OplogIndex),
+    Timestamp(chrono::DateTime<chrono::Utc>),
+}
+
+impl Display for AgentTimeTravelPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgentTimeTravelPoint::OplogIndex(idx) => write!(f, "oplog index {idx}"),
+            AgentTimeTravelPoint::Timestamp(timestamp) => write!(f, "{}", timestamp.to_rfc3339()),
+        }
+    }
+}
+
+impl FromStr for AgentTimeTravelPoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(idx) = s.parse::<u64>() {
+            return Ok(AgentTimeTravelPoint::OplogIndex(OplogIndex::from_u64(idx)));
+        }
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|timestamp| AgentTimeTravelPoint::Timestamp(timestamp.to_utc()))
+            .map_err(|_| {
+                format!("Invalid time-travel point: {s}. Expected an oplog index or an RFC 3339 timestamp")
+            })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AgentMetadataView {
@@ -136,6 +170,7 @@ pub struct AgentMetadataView {
     pub component_size: u64,
     pub total_linear_memory_size: u64,
     pub exported_resource_instances: HashMap<String, AgentResourceDescription>,
+    pub promises: Vec<PromiseMetadata>,
     #[serde(skip)]
     pub source_language: SourceLanguage,
 }
@@ -161,6 +196,7 @@ impl From<AgentMetadata> for AgentMetadataView {
             component_size: value.component_size,
             total_linear_memory_size: value.total_linear_memory_size,
             exported_resource_instances: value.exported_resource_instances,
+            promises: value.promises,
             source_language: SourceLanguage::default(),
         }
     }
@@ -197,6 +233,8 @@ pub struct AgentMetadata {
     pub component_size: u64,
     pub total_linear_memory_size: u64,
     pub exported_resource_instances: HashMap<String, AgentResourceDescription>,
+    pub last_oplog_index: OplogIndex,
+    pub promises: Vec<PromiseMetadata>,
 }
 
 impl AgentMetadata {
@@ -226,10 +264,36 @@ impl AgentMetadata {
                     .into_iter()
                     .map(|desc| (desc.key.to_string(), desc.description)),
             ),
+            last_oplog_index: value.last_oplog_index,
+            promises: value.promises,
         }
     }
 }
 
+/// A lifecycle policy action that is going to be applied to an agent unless it becomes active
+/// again before `due_at`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentLifecyclePreviewView {
+    pub component_name: ComponentName,
+    pub agent_name: RawAgentId,
+    pub status: AgentStatus,
+    pub last_activity: Timestamp,
+    pub action: AgentLifecycleAction,
+    pub due_at: Timestamp,
+}
+
+/// A lifecycle policy action that was applied to an agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentLifecycleAuditView {
+    pub component_name: ComponentName,
+    pub agent_name: RawAgentId,
+    pub timestamp: Timestamp,
+    pub action: AgentLifecycleAction,
+    pub last_oplog_index: OplogIndex,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AgentsMetadataResponseView {
     pub agents: Vec<AgentMetadataView>,
@@ -291,3 +355,12 @@ impl AgentNameMatch {
         self
     }
 }
+
+/// An agent method invocation parsed from the same agent ID, method name and argument syntax
+/// that `agent invoke` accepts
+pub struct ParsedAgentInvocation {
+    pub agent_id: LegacyParsedAgentId,
+    pub agent_type: AgentType,
+    pub method_name: String,
+    pub method_parameters: DataValue,
+}
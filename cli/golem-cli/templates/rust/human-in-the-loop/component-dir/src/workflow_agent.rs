@@ -40,9 +40,12 @@ impl WorkflowAgent for WorkflowAgentImpl {
             .await;
 
         // 2. Pause here until the promise is completed
-        let result: Decision = await_promise_json(&approval_promise_id)
+        let outcome = await_promise_json::<Decision>(&approval_promise_id)
             .await
             .expect("Invalid promise payload");
+        let Some(result) = outcome.completed() else {
+            return format!("Workflow {} was not approved in time ⌛", self.id);
+        };
 
         // 3. Continue based on human input
         if result == Decision::Approved {
@@ -36,8 +36,11 @@ export class WorkflowAgent extends BaseAgent {
     await approver.requestApproval(this.workflowId, approvalPromiseId);
 
     // 2. Pause here until promise is completed
-    const resultBytes = await awaitPromise(approvalPromiseId);
-    const result = new TextDecoder().decode(resultBytes);
+    const outcome = await awaitPromise(approvalPromiseId);
+    if (outcome.tag !== "completed") {
+      return `Workflow ${this.workflowId} was not approved in time ⌛`;
+    }
+    const result = new TextDecoder().decode(outcome.val);
 
     // 3. Continue based on human input
     if (result === "approved") {
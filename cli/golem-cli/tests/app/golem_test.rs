@@ -0,0 +1,141 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Tracing;
+use crate::app::{TestContext, cmd, flag};
+use golem_cli::fs;
+use indoc::indoc;
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(Tracing);
+
+const COUNTER_AGENT: &str = indoc! { r#"
+    use golem_rust::{agent_definition, agent_implementation, endpoint};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[agent_definition(mount = "/counters/{name}")]
+    pub trait CounterAgent {
+        fn new(name: String) -> Self;
+
+        #[endpoint(post = "/increment")]
+        fn increment(&mut self) -> u32;
+
+        fn wall_clock_seconds(&self) -> u64;
+    }
+
+    struct CounterImpl {
+        _name: String,
+        count: u32,
+    }
+
+    #[agent_implementation]
+    impl CounterAgent for CounterImpl {
+        fn new(name: String) -> Self {
+            Self {
+                _name: name,
+                count: 0,
+            }
+        }
+
+        fn increment(&mut self) -> u32 {
+            self.count += 1;
+            self.count
+        }
+
+        fn wall_clock_seconds(&self) -> u64 {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        }
+    }
+"# };
+
+const PASSING_SCRIPT: &str = indoc! { r#"
+    tests:
+      - name: increments the counter
+        steps:
+          - invoke:
+              agent: CounterAgent("first")
+              function: increment
+              expect: "1"
+          - invoke:
+              agent: CounterAgent("first")
+              function: increment
+              expect: "2"
+          - oplog:
+              agent: CounterAgent("first")
+              query: increment
+              min: 2
+      - name: observes the deterministic wall clock
+        steps:
+          - invoke:
+              agent: CounterAgent("second")
+              function: wall_clock_seconds
+              expect: "1893456000"
+"# };
+
+const FAILING_SCRIPT: &str = indoc! { r#"
+    tests:
+      - name: expects a wrong count
+        steps:
+          - invoke:
+              agent: CounterAgent("third")
+              function: increment
+              expect: "2"
+"# };
+
+#[test]
+async fn golem_test_runs_scripts_on_an_in_process_executor() {
+    let mut ctx = TestContext::new();
+    let app_name = "golem-test";
+
+    let outputs = ctx
+        .cli([flag::YES, cmd::NEW, app_name, flag::TEMPLATE, "rust"])
+        .await;
+    assert!(outputs.success_or_dump());
+
+    ctx.cd(app_name);
+
+    fs::write_str(ctx.cwd_path_join("src/counter_agent.rs"), COUNTER_AGENT).unwrap();
+    fs::write_str(ctx.cwd_path_join("tests/counter.yaml"), PASSING_SCRIPT).unwrap();
+    fs::write_str(ctx.cwd_path_join("tests/failing.yaml"), FAILING_SCRIPT).unwrap();
+
+    // No server is started, the scripts run on the executor embedded in the golem binary
+    let outputs = ctx
+        .golem([
+            cmd::TEST,
+            "tests/counter.yaml",
+            flag::JUNIT,
+            "counter.xml",
+            flag::START_TIME,
+            "2030-01-01T00:00:00Z",
+        ])
+        .await;
+    assert!(outputs.success_or_dump());
+
+    let report = fs::read_to_string(ctx.cwd_path_join("counter.xml")).unwrap();
+    assert!(report.contains(r#"<testsuites name="golem test" tests="2" failures="0">"#));
+    assert!(report.contains(r#"<testcase name="increments the counter""#));
+    assert!(report.contains(r#"<testcase name="observes the deterministic wall clock""#));
+
+    let outputs = ctx
+        .golem([cmd::TEST, "tests/failing.yaml", flag::JUNIT, "failing.xml"])
+        .await;
+    assert!(!outputs.success());
+
+    let report = fs::read_to_string(ctx.cwd_path_join("failing.xml")).unwrap();
+    assert!(report.contains(r#"<testsuites name="golem test" tests="1" failures="1">"#));
+    assert!(report.contains("expected increment to return"));
+}
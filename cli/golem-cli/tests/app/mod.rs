@@ -19,6 +19,7 @@ mod app;
 
 mod build_and_deploy_all;
 mod directory_source_ifs;
+mod golem_test;
 mod plugins;
 
 inherit_test_dep!(Tracing);
@@ -73,6 +74,7 @@ mod cmd {
     pub static REGISTER: &str = "register";
     pub static REPL: &str = "repl";
     pub static TEMPLATES: &str = "templates";
+    pub static TEST: &str = "test";
 }
 
 mod flag {
@@ -80,10 +82,12 @@ mod flag {
     pub static COMPONENT_NAME: &str = "--component-name";
     pub static FORCE_BUILD: &str = "--force-build";
     pub static FORMAT: &str = "--format";
+    pub static JUNIT: &str = "--junit";
     pub static LANGUAGE: &str = "--language";
     pub static RESET: &str = "--reset";
     pub static SCRIPT: &str = "--script";
     pub static SHOW_SENSITIVE: &str = "--show-sensitive";
+    pub static START_TIME: &str = "--start-time";
     pub static STEP: &str = "--step";
     pub static TEMPLATE: &str = "--template";
     pub static YES: &str = "--yes";
@@ -423,6 +427,25 @@ impl TestContext {
 
     #[must_use]
     async fn cli<I, S>(&self, args: I) -> Output
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.run_binary(&self.golem_cli_path, "golem-cli", args)
+            .await
+    }
+
+    /// Runs the `golem` binary, for the commands only available in it besides the server
+    #[must_use]
+    async fn golem<I, S>(&self, args: I) -> Output
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.run_binary(&self.golem_path, "golem", args).await
+    }
+
+    async fn run_binary<I, S>(&self, binary_path: &Path, prefix: &str, args: I) -> Output
     where
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>,
@@ -447,9 +470,13 @@ impl TestContext {
             "> working directory:".bold(),
             working_dir.display()
         );
-        println!("{} {}", "> golem-cli".bold(), args.iter().join(" ").blue());
+        println!(
+            "{} {}",
+            format!("> {prefix}").bold(),
+            args.iter().join(" ").blue()
+        );
 
-        let mut child = Command::new(&self.golem_cli_path)
+        let mut child = Command::new(binary_path)
             .args(args)
             .envs(&self.env)
             .current_dir(&working_dir)
@@ -458,7 +485,7 @@ impl TestContext {
             .spawn()
             .unwrap();
 
-        Output::stream_and_collect(self.quiet, "golem-cli", &mut child)
+        Output::stream_and_collect(self.quiet, prefix, &mut child)
             .await
             .unwrap()
     }
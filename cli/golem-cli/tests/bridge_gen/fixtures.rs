@@ -66,6 +66,7 @@ pub fn single_agent_wrapper_types() -> Vec<AgentType> {
                 }),
                 http_endpoint: Vec::new(),
                 read_only: None,
+                timeout_nanos: None,
             },
             AgentMethod {
                 name: "f2".to_string(),
@@ -97,6 +98,7 @@ pub fn single_agent_wrapper_types() -> Vec<AgentType> {
                 }),
                 http_endpoint: Vec::new(),
                 read_only: None,
+                timeout_nanos: None,
             },
         ],
         dependencies: vec![],
@@ -172,6 +174,7 @@ pub fn multi_agent_wrapper_2_types() -> Vec<AgentType> {
                 }),
                 http_endpoint: Vec::new(),
                 read_only: None,
+                timeout_nanos: None,
             }],
             dependencies: vec![],
             mode: AgentMode::Durable,
@@ -235,6 +238,7 @@ pub fn multi_agent_wrapper_2_types() -> Vec<AgentType> {
                 }),
                 http_endpoint: Vec::new(),
                 read_only: None,
+                timeout_nanos: None,
             }],
             dependencies: vec![],
             mode: AgentMode::Durable,
@@ -126,6 +126,7 @@ fn rust_counter_agent() -> GeneratedPackage {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
@@ -269,6 +270,7 @@ fn ephemeral_config_agent() -> AgentType {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Ephemeral,
@@ -308,6 +310,7 @@ fn durable_local_config_primitive_agent() -> AgentType {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
@@ -349,6 +352,7 @@ fn durable_local_config_complex_agent() -> AgentType {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
@@ -199,6 +199,7 @@ fn ts_counter_agent() -> GeneratedPackage {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
@@ -258,6 +259,7 @@ fn ts_collision_parameter_names_agent() -> GeneratedPackage {
             output_schema: DataSchema::Tuple(NamedElementSchemas { elements: vec![] }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
@@ -302,6 +304,7 @@ fn ts_config_nested_local_types_agent_type() -> AgentType {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
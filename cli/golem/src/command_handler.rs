@@ -15,6 +15,7 @@
 use anyhow::anyhow;
 use clap_verbosity_flag::Verbosity;
 use golem_cli::command::server::{RunArgs, ServerSubcommand};
+use golem_cli::command::test::TestArgs;
 use golem_cli::command_handler::CommandHandlerHooks;
 use golem_cli::context::Context;
 use std::path::{Path, PathBuf};
@@ -23,6 +24,7 @@ use tracing::debug;
 
 use crate::compat::map_local_server_startup_error;
 use crate::launch::{LaunchArgs, launch_golem_services};
+use crate::test_harness::run_test_scripts;
 
 pub struct ServerCommandHandler;
 
@@ -72,6 +74,18 @@ impl CommandHandlerHooks for ServerCommandHandler {
         }
     }
 
+    async fn handler_test_command(&self, ctx: Arc<Context>, args: TestArgs) -> anyhow::Result<()> {
+        if !ctx.server_no_limit_change() {
+            let file_limit_increase_result = rlimit::increase_nofile_limit(1000000);
+            debug!(
+                "File limit increase result: {:?}",
+                file_limit_increase_result
+            );
+        }
+
+        run_test_scripts(ctx, args).await
+    }
+
     async fn run_server() -> anyhow::Result<()> {
         let args = RunArgs::default();
         let data_dir = default_data_dir()?;
@@ -21,6 +21,7 @@ pub mod command_handler;
 pub mod compat;
 pub mod launch;
 mod router;
+pub mod test_harness;
 
 #[cfg(test)]
 test_r::enable!();
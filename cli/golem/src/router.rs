@@ -68,6 +68,14 @@ pub async fn start_router(
         // Worker endpoints
         .at("/v1/agents/create-agent", worker_service_api.clone())
         .at("/v1/agents/invoke-agent", worker_service_api.clone())
+        .at(
+            "/v1/components/:component_id/lifecycle-audit",
+            worker_service_api.clone(),
+        )
+        .at(
+            "/v1/components/:component_id/oplog/search",
+            worker_service_api.clone(),
+        )
         .at(
             "/v1/components/:component_id/workers",
             worker_service_api.clone(),
@@ -96,6 +104,10 @@ pub async fn start_router(
             "/v1/components/:component_id/workers/:agent_name/deactivate-plugin",
             worker_service_api.clone(),
         )
+        .at(
+            "/v1/components/:component_id/workers/:agent_name/export",
+            worker_service_api.clone(),
+        )
         .at(
             "/v1/components/:component_id/workers/:agent_name/file-contents/:file_name",
             worker_service_api.clone(),
@@ -108,6 +120,10 @@ pub async fn start_router(
             "/v1/components/:component_id/workers/:agent_name/fork",
             worker_service_api.clone(),
         )
+        .at(
+            "/v1/components/:component_id/workers/:agent_name/import",
+            worker_service_api.clone(),
+        )
         .at(
             "/v1/components/:component_id/workers/:agent_name/interrupt",
             worker_service_api.clone(),
@@ -140,6 +156,10 @@ pub async fn start_router(
             "/v1/components/:component_id/workers/:agent_name/update",
             worker_service_api.clone(),
         )
+        .at(
+            "/v1/envs/:environment_id/oplog/search",
+            worker_service_api.clone(),
+        )
         // Metrics
         .at("/metrics", metrics)
         // Everything else is routed to registry service
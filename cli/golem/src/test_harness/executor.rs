@@ -0,0 +1,575 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-process worker executor running the components of the application under test.
+//!
+//! Like the executor of `golem-worker-executor-test-utils`, it runs on a single shard with
+//! unlimited quotas and local storage, and serves the components from memory instead of a
+//! registry. Invocations and oplog queries are sent to its gRPC API directly.
+
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use golem_api_grpc::proto::golem::worker::AgentInvocationMode;
+use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_client::WorkerExecutorClient;
+use golem_api_grpc::proto::golem::workerexecutor::v1::{
+    InvokeAgentRequest, SearchOplogRequest, invoke_agent_response, search_oplog_response,
+};
+use golem_cli::model::component::{AgentTypeManifestProvisionConfig, ComponentDeployProperties};
+use golem_common::base_model::component_metadata::AgentTypeProvisionConfig;
+use golem_common::base_model::worker::TypedAgentConfigEntry;
+use golem_common::config::DbSqliteConfig;
+use golem_common::model::account::{AccountEmail, AccountId};
+use golem_common::model::agent::{
+    AgentType, AgentTypeName, DataValue, LegacyParsedAgentId, UntypedDataValue,
+};
+use golem_common::model::agent_secret::CanonicalAgentSecretPath;
+use golem_common::model::application::{ApplicationId, ApplicationName};
+use golem_common::model::component::{ComponentId, ComponentName, ComponentRevision};
+use golem_common::model::component_metadata::ComponentMetadata;
+use golem_common::model::diff::Hash;
+use golem_common::model::environment::{EnvironmentId, EnvironmentName};
+use golem_common::model::retry_policy::NamedRetryPolicy;
+use golem_common::model::{AgentId, IdempotencyKey};
+use golem_service_base::clients::registry::RegistryService;
+use golem_service_base::config::{BlobStorageConfig, LocalFileSystemBlobStorageConfig};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_service_base::grpc::server::GrpcServerTlsConfig;
+use golem_service_base::model::AgentDeploymentDetails;
+use golem_service_base::model::agent_secret::AgentSecret;
+use golem_service_base::model::auth::AuthCtx;
+use golem_service_base::model::component::Component;
+use golem_service_base::model::oplog_export_sink::NamedOplogExportSink;
+use golem_service_base::storage::blob::BlobStorage;
+use golem_wasm::ValueAndType;
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use golem_worker_executor::durable_host::test_hooks::TestHooks;
+use golem_worker_executor::services::component::ComponentService;
+use golem_worker_executor::services::direct_invocation_auth::{
+    DirectInvocationAuthService, NoOpDirectInvocationAuthService,
+};
+use golem_worker_executor::services::environment_state::EnvironmentStateService;
+use golem_worker_executor::services::golem_config::{
+    AgentTypesServiceConfig, AgentTypesServiceLocalConfig, EnvironmentStateServiceConfig,
+    GolemConfig, GrpcApiConfig, IndexedStorageConfig, IndexedStorageKVStoreSqliteConfig,
+    KeyValueStorageConfig, QuotaServiceConfig, ResourceLimitsConfig, ResourceLimitsDisabledConfig,
+    SchedulerStorageConfig,
+};
+use golem_worker_executor::services::quota::{QuotaService, UnlimitedQuotaService};
+use golem_worker_executor::services::shard_manager::{
+    ShardManagerService, ShardManagerServiceSingleShard,
+};
+use golem_worker_executor::workerctx::default::{Context, ContextDeps};
+use golem_worker_executor::{Bootstrap, RunDetails, bootstrap_and_run_worker_executor};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::Mutex;
+use tokio::task::{JoinSet, spawn_blocking};
+use tonic::transport::Channel;
+use uuid::Uuid;
+use wasmtime::Engine;
+
+pub struct TestExecutor {
+    client: WorkerExecutorClient<Channel>,
+    account_id: AccountId,
+    environment_id: EnvironmentId,
+    _run_details: RunDetails,
+    _join_set: JoinSet<anyhow::Result<()>>,
+}
+
+impl TestExecutor {
+    /// Starts the executor storing its state in `data_dir`, serving the given components
+    pub async fn start(
+        data_dir: &Path,
+        test_hooks: TestHooks,
+        components: Vec<Component>,
+        wasm: HashMap<ComponentId, Vec<u8>>,
+    ) -> anyhow::Result<Self> {
+        let (account_id, environment_id) = components
+            .first()
+            .map(|component| (component.account_id, component.environment_id))
+            .ok_or_else(|| anyhow!("The application has no components to test"))?;
+
+        let config = GolemConfig {
+            blob_storage: BlobStorageConfig::LocalFileSystem(LocalFileSystemBlobStorageConfig {
+                root: data_dir.join("blobs"),
+            }),
+            key_value_storage: KeyValueStorageConfig::Sqlite(sqlite_config(
+                data_dir,
+                "worker-executor.db",
+            )),
+            indexed_storage: IndexedStorageConfig::KVStoreSqlite(
+                IndexedStorageKVStoreSqliteConfig {},
+            ),
+            scheduler_storage: SchedulerStorageConfig::Sqlite(sqlite_config(
+                data_dir,
+                "worker-executor-scheduler.db",
+            )),
+            http_port: 0,
+            grpc: GrpcApiConfig {
+                port: 0,
+                tls: GrpcServerTlsConfig::disabled(),
+                ..Default::default()
+            },
+            agent_types_service: AgentTypesServiceConfig::Local(AgentTypesServiceLocalConfig {}),
+            resource_limits: ResourceLimitsConfig::Disabled(ResourceLimitsDisabledConfig {}),
+            ..Default::default()
+        };
+
+        let bootstrap = TestBootstrap {
+            component_service: Arc::new(InMemoryComponentService {
+                components: components
+                    .into_iter()
+                    .map(|component| (component.id, component))
+                    .collect(),
+                wasm,
+                compiled: Mutex::new(HashMap::new()),
+            }),
+            test_hooks: Arc::new(test_hooks),
+        };
+
+        let mut join_set = JoinSet::new();
+        let run_details = bootstrap_and_run_worker_executor(
+            &bootstrap,
+            config,
+            golem_worker_executor::metrics::register_all(),
+            Handle::current(),
+            &mut join_set,
+            false,
+        )
+        .await?;
+
+        let grpc_port = run_details.grpc_port;
+        let start = Instant::now();
+        let channel = loop {
+            match Channel::from_shared(format!("http://127.0.0.1:{grpc_port}"))?
+                .connect()
+                .await
+            {
+                Ok(channel) => break channel,
+                Err(err) if start.elapsed() > Duration::from_secs(10) => {
+                    bail!("Failed to connect to the worker executor: {err}")
+                }
+                Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+            }
+        };
+        let client = WorkerExecutorClient::new(channel)
+            .max_decoding_message_size(32 * 1024 * 1024)
+            .max_encoding_message_size(32 * 1024 * 1024);
+
+        Ok(Self {
+            client,
+            account_id,
+            environment_id,
+            _run_details: run_details,
+            _join_set: join_set,
+        })
+    }
+
+    /// Invokes an agent method and awaits its result. The outer error is returned if the executor
+    /// could not be reached, the inner one if the invocation itself failed.
+    pub async fn invoke_and_await(
+        &self,
+        component_id: ComponentId,
+        agent_id: &LegacyParsedAgentId,
+        agent_type: &AgentType,
+        method_name: &str,
+        method_parameters: DataValue,
+    ) -> anyhow::Result<Result<Option<DataValue>, WorkerExecutorError>> {
+        let agent_id = AgentId::from_agent_id(component_id, agent_id)
+            .map_err(|err| anyhow!("Invalid agent ID: {err}"))?;
+
+        let response = self
+            .client
+            .clone()
+            .invoke_agent(InvokeAgentRequest {
+                agent_id: Some(agent_id.into()),
+                method_name: Some(method_name.to_string()),
+                method_parameters: Some(UntypedDataValue::from(method_parameters).into()),
+                mode: AgentInvocationMode::Await as i32,
+                schedule_at: None,
+                at: None,
+                idempotency_key: Some(IdempotencyKey::fresh().into()),
+                component_owner_account_id: Some(self.account_id.into()),
+                environment_id: Some(self.environment_id.into()),
+                auth_ctx: Some(AuthCtx::System.into()),
+                context: None,
+                principal: None,
+            })
+            .await?
+            .into_inner();
+
+        match response.result {
+            None => Err(anyhow!("No response from the worker executor")),
+            Some(invoke_agent_response::Result::Success(success)) => {
+                let Some(result) = success.result else {
+                    return Ok(Ok(None));
+                };
+                let output_schema = agent_type
+                    .methods
+                    .iter()
+                    .find(|method| method.name == method_name)
+                    .map(|method| method.output_schema.clone())
+                    .ok_or_else(|| anyhow!("Method '{method_name}' not found in agent type"))?;
+                let result = UntypedDataValue::try_from(result)
+                    .and_then(|result| DataValue::try_from_untyped(result, output_schema))
+                    .map_err(|err| anyhow!("Failed to convert the invocation result: {err}"))?;
+                Ok(Ok(Some(result)))
+            }
+            Some(invoke_agent_response::Result::Failure(error)) => {
+                let error = WorkerExecutorError::try_from(error)
+                    .map_err(|err| anyhow!("Failed to convert the invocation error: {err}"))?;
+                Ok(Err(error))
+            }
+        }
+    }
+
+    /// Counts the oplog entries of an agent matching a query
+    pub async fn count_oplog_matches(
+        &self,
+        component_id: ComponentId,
+        agent_id: &LegacyParsedAgentId,
+        query: &str,
+    ) -> anyhow::Result<usize> {
+        let agent_id = AgentId::from_agent_id(component_id, agent_id)
+            .map_err(|err| anyhow!("Invalid agent ID: {err}"))?;
+
+        let mut matches = 0;
+        let mut cursor = None;
+        loop {
+            let response = self
+                .client
+                .clone()
+                .search_oplog(SearchOplogRequest {
+                    agent_id: Some(agent_id.clone().into()),
+                    environment_id: Some(self.environment_id.into()),
+                    cursor,
+                    count: 100,
+                    query: query.to_string(),
+                    auth_ctx: Some(AuthCtx::System.into()),
+                    from: None,
+                    to: None,
+                })
+                .await?
+                .into_inner();
+
+            match response.result {
+                Some(search_oplog_response::Result::Success(chunk)) => {
+                    if chunk.entries.is_empty() {
+                        break;
+                    }
+                    matches += chunk.entries.len();
+                    cursor = chunk.next;
+                }
+                Some(search_oplog_response::Result::Failure(error)) => {
+                    bail!("Failed to search the oplog: {error:?}")
+                }
+                None => break,
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+/// Creates the metadata of an application component built by the CLI, owned by the given
+/// account and environment
+pub fn component_metadata(
+    component_name: &ComponentName,
+    properties: &ComponentDeployProperties,
+    wasm: &[u8],
+    account_id: AccountId,
+    environment_id: EnvironmentId,
+    application_id: ApplicationId,
+) -> anyhow::Result<Component> {
+    let provision_configs = properties
+        .agent_types
+        .iter()
+        .filter_map(|agent_type| {
+            properties
+                .agent_type_configs
+                .get(&agent_type.type_name)
+                .map(|config| {
+                    provision_config(agent_type, config)
+                        .map(|config| (agent_type.type_name.clone(), config))
+                })
+        })
+        .collect::<anyhow::Result<BTreeMap<AgentTypeName, AgentTypeProvisionConfig>>>()?;
+
+    let metadata = ComponentMetadata::analyse_component(
+        wasm,
+        properties.agent_types.clone(),
+        provision_configs,
+    )
+    .map_err(|err| anyhow!("Failed to analyse component {}: {err}", component_name.0))?;
+
+    Ok(Component {
+        id: ComponentId(Uuid::new_v4()),
+        revision: ComponentRevision::INITIAL,
+        environment_id,
+        component_name: component_name.clone(),
+        hash: Hash::empty(),
+        application_id,
+        account_id,
+        account_email: AccountEmail::new("test@golem"),
+        application_name: ApplicationName::try_from("test-app".to_string())
+            .map_err(|err| anyhow!("{err}"))?,
+        environment_name: EnvironmentName::try_from("test-env").map_err(|err| anyhow!("{err}"))?,
+        component_size: wasm.len() as u64,
+        metadata,
+        created_at: Default::default(),
+        wasm_hash: Hash::from(blake3::hash(wasm)),
+        object_store_key: "".to_string(),
+    })
+}
+
+fn provision_config(
+    agent_type: &AgentType,
+    config: &AgentTypeManifestProvisionConfig,
+) -> anyhow::Result<AgentTypeProvisionConfig> {
+    if !config.files.is_empty() || !config.plugins.is_empty() {
+        bail!(
+            "Agent {} has initial files or plugins, which are not supported by golem test",
+            agent_type.type_name
+        );
+    }
+
+    let config_entries = config
+        .config
+        .iter()
+        .map(|entry| {
+            let declaration = agent_type
+                .config
+                .iter()
+                .find(|declaration| declaration.path == entry.path)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Config key {} is not declared by agent {}",
+                        entry.path.join("."),
+                        agent_type.type_name
+                    )
+                })?;
+            let value = ValueAndType::parse_with_type(&entry.value.0, &declaration.value_type)
+                .map_err(|errors| {
+                    anyhow!(
+                        "Invalid value for config key {} of agent {}: {}",
+                        entry.path.join("."),
+                        agent_type.type_name,
+                        errors.join(", ")
+                    )
+                })?;
+            Ok(TypedAgentConfigEntry {
+                path: entry.path.clone(),
+                value,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(AgentTypeProvisionConfig {
+        env: config.env.clone(),
+        config: config_entries,
+        plugins: vec![],
+        files: vec![],
+        lifecycle: config.lifecycle.clone(),
+    })
+}
+
+fn sqlite_config(data_dir: &Path, file_name: &str) -> DbSqliteConfig {
+    DbSqliteConfig {
+        database: data_dir.join(file_name).to_string_lossy().into_owned(),
+        max_connections: 8,
+        foreign_keys: false,
+    }
+}
+
+struct TestBootstrap {
+    component_service: Arc<InMemoryComponentService>,
+    test_hooks: Arc<TestHooks>,
+}
+
+#[async_trait]
+impl Bootstrap<Context> for TestBootstrap {
+    fn create_shard_manager_service(
+        &self,
+        _shard_manager_client: Arc<dyn golem_service_base::clients::shard_manager::ShardManager>,
+    ) -> Arc<dyn ShardManagerService> {
+        Arc::new(ShardManagerServiceSingleShard)
+    }
+
+    fn create_quota_service(
+        &self,
+        _shard_manager_client: Arc<dyn golem_service_base::clients::shard_manager::ShardManager>,
+        _config: &QuotaServiceConfig,
+        _shutdown_token: tokio_util::sync::CancellationToken,
+    ) -> Arc<dyn QuotaService> {
+        Arc::new(UnlimitedQuotaService)
+    }
+
+    fn create_environment_state_service(
+        &self,
+        _config: &EnvironmentStateServiceConfig,
+        _registry_service: Arc<dyn RegistryService>,
+    ) -> Arc<dyn EnvironmentStateService> {
+        Arc::new(NoEnvironmentStateService)
+    }
+
+    fn create_component_service(
+        &self,
+        _golem_config: &GolemConfig,
+        _registry_service: Arc<dyn RegistryService>,
+        _blob_storage: Arc<dyn BlobStorage>,
+    ) -> Arc<dyn ComponentService> {
+        self.component_service.clone()
+    }
+
+    fn create_additional_deps(&self, _registry_service: Arc<dyn RegistryService>) -> ContextDeps {
+        ContextDeps {
+            test_hooks: Some(self.test_hooks.clone()),
+        }
+    }
+
+    fn create_direct_invocation_auth_service(
+        &self,
+        _registry_service: Arc<dyn RegistryService>,
+        _golem_config: &GolemConfig,
+    ) -> Arc<dyn DirectInvocationAuthService> {
+        Arc::new(NoOpDirectInvocationAuthService)
+    }
+}
+
+/// Serves the components of the application under test, each with a single revision
+struct InMemoryComponentService {
+    components: HashMap<ComponentId, Component>,
+    wasm: HashMap<ComponentId, Vec<u8>>,
+    compiled: Mutex<HashMap<ComponentId, wasmtime::component::Component>>,
+}
+
+impl InMemoryComponentService {
+    fn metadata(
+        &self,
+        component_id: ComponentId,
+        component_revision: Option<ComponentRevision>,
+    ) -> Result<Component, WorkerExecutorError> {
+        self.components
+            .get(&component_id)
+            .filter(|component| {
+                component_revision.is_none_or(|revision| revision == component.revision)
+            })
+            .cloned()
+            .ok_or_else(|| {
+                WorkerExecutorError::unknown(format!("No such component found: {component_id}"))
+            })
+    }
+}
+
+#[async_trait]
+impl ComponentService for InMemoryComponentService {
+    async fn get(
+        &self,
+        engine: &Engine,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+    ) -> Result<(wasmtime::component::Component, Component), WorkerExecutorError> {
+        let metadata = self.metadata(component_id, Some(component_revision))?;
+
+        let mut compiled = self.compiled.lock().await;
+        if let Some(component) = compiled.get(&component_id) {
+            return Ok((component.clone(), metadata));
+        }
+
+        let bytes = self.wasm.get(&component_id).cloned().ok_or_else(|| {
+            WorkerExecutorError::unknown(format!("No such component found: {component_id}"))
+        })?;
+        let engine = engine.clone();
+        let component = spawn_blocking(move || {
+            wasmtime::component::Component::from_binary(&engine, &bytes).map_err(|err| {
+                WorkerExecutorError::ComponentParseFailed {
+                    component_id,
+                    component_revision,
+                    reason: format!("{err}"),
+                }
+            })
+        })
+        .await
+        .map_err(|err| WorkerExecutorError::unknown(err.to_string()))??;
+        compiled.insert(component_id, component.clone());
+
+        Ok((component, metadata))
+    }
+
+    async fn get_metadata(
+        &self,
+        component_id: ComponentId,
+        forced_revision: Option<ComponentRevision>,
+    ) -> Result<Component, WorkerExecutorError> {
+        self.metadata(component_id, forced_revision)
+    }
+
+    async fn resolve_component(
+        &self,
+        component_reference: String,
+        _resolving_environment: EnvironmentId,
+        _resolving_application: ApplicationId,
+        _resolving_account: AccountId,
+    ) -> Result<Option<ComponentId>, WorkerExecutorError> {
+        Ok(self
+            .components
+            .values()
+            .find(|component| component.component_name.0 == component_reference)
+            .map(|component| component.id))
+    }
+
+    async fn all_cached_metadata(&self) -> Vec<Component> {
+        self.components.values().cloned().collect()
+    }
+
+    async fn invalidate_all_metadata_for_environment(&self, _environment_id: EnvironmentId) {}
+}
+
+/// There are no deployments, secrets, retry policies or oplog export sinks besides the defaults in
+/// tests
+struct NoEnvironmentStateService;
+
+#[async_trait]
+impl EnvironmentStateService for NoEnvironmentStateService {
+    async fn get_agent_deployment(
+        &self,
+        _environment: EnvironmentId,
+        _agent_type: &AgentTypeName,
+    ) -> Result<Option<AgentDeploymentDetails>, WorkerExecutorError> {
+        Ok(None)
+    }
+
+    async fn get_agent_secrets(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<HashMap<CanonicalAgentSecretPath, AgentSecret>, WorkerExecutorError> {
+        Ok(HashMap::new())
+    }
+
+    async fn get_retry_policies(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedRetryPolicy>, WorkerExecutorError> {
+        Ok(vec![])
+    }
+    async fn get_oplog_export_sinks(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedOplogExportSink>, WorkerExecutorError> {
+        Ok(vec![])
+    }
+}
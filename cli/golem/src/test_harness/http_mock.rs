@@ -0,0 +1,262 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::test_harness::script::HttpMock;
+use golem_worker_executor::durable_host::test_hooks::HTTP_MOCK_ORIGINAL_URL_HEADER;
+use poem::http::StatusCode;
+use poem::listener::{Acceptor, Listener, TcpListener};
+use poem::{Request, Response, Server};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use url::Url;
+
+/// Response headers not carried over into recordings, as they describe the original transfer
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &["connection", "content-length", "transfer-encoding"];
+
+/// Request headers not forwarded to the original destination when recording
+const SKIPPED_REQUEST_HEADERS: &[&str] = &["content-length", "host", HTTP_MOCK_ORIGINAL_URL_HEADER];
+
+/// HTTP server receiving the outgoing requests of agents redirected by the worker executor, and
+/// answering them with the mocked or recorded responses of the currently running test script.
+pub struct HttpMockServer {
+    url: Url,
+    state: Arc<Mutex<HttpMockState>>,
+    server: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct HttpMockState {
+    mocks: Vec<HttpMock>,
+    served: Vec<bool>,
+    record: bool,
+    recorded: Vec<HttpMock>,
+    unmatched: Vec<String>,
+}
+
+impl HttpMockServer {
+    pub async fn start() -> anyhow::Result<Self> {
+        let acceptor = TcpListener::bind("127.0.0.1:0").into_acceptor().await?;
+        let port = acceptor.local_addr()[0]
+            .as_socket_addr()
+            .expect("socket address")
+            .port();
+        let url = Url::parse(&format!("http://127.0.0.1:{port}"))?;
+
+        let state = Arc::new(Mutex::new(HttpMockState::default()));
+        let client = reqwest::Client::new();
+        let app = poem::endpoint::make({
+            let state = state.clone();
+            move |request: Request| {
+                let state = state.clone();
+                let client = client.clone();
+                async move { handle_request(&state, &client, request).await }
+            }
+        });
+        let server = tokio::spawn(async move {
+            let _ = Server::new_with_acceptor(acceptor).run(app).await;
+        });
+
+        Ok(Self { url, state, server })
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Replaces the served mocks. When `record` is set, requests without a mock are forwarded
+    /// to their original destination and their responses are recorded.
+    pub fn load(&self, mocks: Vec<HttpMock>, record: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.served = vec![false; mocks.len()];
+        state.mocks = mocks;
+        state.record = record;
+        state.recorded.clear();
+        state.unmatched.clear();
+    }
+
+    /// Responses recorded since the last [`Self::load`]
+    pub fn take_recorded(&self) -> Vec<HttpMock> {
+        std::mem::take(&mut self.state.lock().unwrap().recorded)
+    }
+
+    /// Requests that could not be answered since the last call
+    pub fn take_unmatched(&self) -> Vec<String> {
+        std::mem::take(&mut self.state.lock().unwrap().unmatched)
+    }
+}
+
+impl Drop for HttpMockServer {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+impl HttpMockState {
+    fn next_mock(&mut self, method: &str, url: &str) -> Option<HttpMock> {
+        let matching = self
+            .mocks
+            .iter()
+            .enumerate()
+            .filter(|(_, mock)| mock.matches(method, url))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let idx = matching
+            .iter()
+            .find(|idx| !self.served[**idx])
+            .or(matching.last())
+            .copied()?;
+        self.served[idx] = true;
+        Some(self.mocks[idx].clone())
+    }
+}
+
+async fn handle_request(
+    state: &Mutex<HttpMockState>,
+    client: &reqwest::Client,
+    request: Request,
+) -> Response {
+    let method = request.method().as_str().to_string();
+    let Some(url) = request
+        .headers()
+        .get(HTTP_MOCK_ORIGINAL_URL_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+    else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(format!("Missing {HTTP_MOCK_ORIGINAL_URL_HEADER} header"));
+    };
+
+    let (mock, record) = {
+        let mut state = state.lock().unwrap();
+        (state.next_mock(&method, &url), state.record)
+    };
+    if let Some(mock) = mock {
+        return to_response(&mock);
+    }
+
+    if record {
+        match forward(client, request, &method, &url).await {
+            Ok(mock) => {
+                let response = to_response(&mock);
+                let mut state = state.lock().unwrap();
+                state.mocks.push(mock.clone());
+                state.served.push(true);
+                state.recorded.push(mock);
+                response
+            }
+            Err(err) => {
+                let message = format!("Failed to record {method} {url}: {err:#}");
+                state.lock().unwrap().unmatched.push(message.clone());
+                Response::builder()
+                    .status(StatusCode::BAD_GATEWAY)
+                    .body(message)
+            }
+        }
+    } else {
+        let message = format!("No mocked or recorded response for {method} {url}");
+        state.lock().unwrap().unmatched.push(message.clone());
+        Response::builder()
+            .status(StatusCode::NOT_IMPLEMENTED)
+            .body(message)
+    }
+}
+
+async fn forward(
+    client: &reqwest::Client,
+    request: Request,
+    method: &str,
+    url: &str,
+) -> anyhow::Result<HttpMock> {
+    let mut builder = client.request(reqwest::Method::from_bytes(method.as_bytes())?, url);
+    for (name, value) in request.headers() {
+        if !SKIPPED_REQUEST_HEADERS.contains(&name.as_str()) {
+            builder = builder.header(name.as_str(), value.as_bytes());
+        }
+    }
+    let body = request.into_body().into_bytes().await?;
+    let response = builder.body(body.to_vec()).send().await?;
+
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter(|(name, _)| !SKIPPED_RESPONSE_HEADERS.contains(&name.as_str()))
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.to_string(), value.to_string()))
+        })
+        .collect::<BTreeMap<_, _>>();
+    let body = response.text().await?;
+
+    Ok(HttpMock {
+        method: method.to_string(),
+        url: url.to_string(),
+        status,
+        headers,
+        body,
+    })
+}
+
+fn to_response(mock: &HttpMock) -> Response {
+    let mut builder =
+        Response::builder().status(StatusCode::from_u16(mock.status).unwrap_or(StatusCode::OK));
+    for (name, value) in &mock.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    builder.body(mock.body.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn mock(url: &str, body: &str) -> HttpMock {
+        HttpMock {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            status: 200,
+            headers: BTreeMap::new(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn mocks_are_served_in_order_and_the_last_one_repeats() {
+        let mut state = HttpMockState {
+            served: vec![false; 3],
+            mocks: vec![
+                mock("https://example.com/a", "first"),
+                mock("https://example.com/b", "other"),
+                mock("https://example.com/a", "second"),
+            ],
+            ..Default::default()
+        };
+
+        let bodies = (0..3)
+            .map(|_| {
+                state
+                    .next_mock("get", "https://example.com/a")
+                    .unwrap()
+                    .body
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(bodies, vec!["first", "second", "second"]);
+        assert!(state.next_mock("POST", "https://example.com/a").is_none());
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Results of the tests of a single script
+pub struct TestSuiteResult {
+    pub name: String,
+    pub cases: Vec<TestCaseResult>,
+}
+
+impl TestSuiteResult {
+    pub fn failures(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.failure.is_some())
+            .count()
+    }
+}
+
+pub struct TestCaseResult {
+    pub name: String,
+    pub duration: Duration,
+    pub failure: Option<String>,
+}
+
+/// Renders the results in the JUnit XML format understood by most CI systems
+pub fn to_junit_xml(suites: &[TestSuiteResult]) -> String {
+    let tests = suites.iter().map(|suite| suite.cases.len()).sum::<usize>();
+    let failures = suites.iter().map(TestSuiteResult::failures).sum::<usize>();
+
+    let mut xml = String::new();
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="golem test" tests="{tests}" failures="{failures}">"#
+    );
+    for suite in suites {
+        let time = suite
+            .cases
+            .iter()
+            .map(|case| case.duration)
+            .sum::<Duration>();
+        let _ = writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.3}">"#,
+            escape(&suite.name),
+            suite.cases.len(),
+            suite.failures(),
+            time.as_secs_f64()
+        );
+        for case in &suite.cases {
+            let _ = write!(
+                xml,
+                r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+                escape(&case.name),
+                escape(&suite.name),
+                case.duration.as_secs_f64()
+            );
+            match &case.failure {
+                Some(failure) => {
+                    let _ = writeln!(xml, ">");
+                    let _ = writeln!(
+                        xml,
+                        r#"      <failure message="{}">{}</failure>"#,
+                        escape(failure.lines().next().unwrap_or_default()),
+                        escape(failure)
+                    );
+                    let _ = writeln!(xml, "    </testcase>");
+                }
+                None => {
+                    let _ = writeln!(xml, "/>");
+                }
+            }
+        }
+        let _ = writeln!(xml, "  </testsuite>");
+    }
+    let _ = writeln!(xml, "</testsuites>");
+    xml
+}
+
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            ch => result.push(ch),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn renders_junit_xml() {
+        let xml = to_junit_xml(&[TestSuiteResult {
+            name: "tests/counter.yaml".to_string(),
+            cases: vec![
+                TestCaseResult {
+                    name: "increments".to_string(),
+                    duration: Duration::from_millis(1500),
+                    failure: None,
+                },
+                TestCaseResult {
+                    name: "resets".to_string(),
+                    duration: Duration::from_millis(500),
+                    failure: Some("expected \"0\", got <1>".to_string()),
+                },
+            ],
+        }]);
+
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="golem test" tests="2" failures="1">
+  <testsuite name="tests/counter.yaml" tests="2" failures="1" time="2.000">
+    <testcase name="increments" classname="tests/counter.yaml" time="1.500"/>
+    <testcase name="resets" classname="tests/counter.yaml" time="0.500">
+      <failure message="expected &quot;0&quot;, got &lt;1&gt;">expected &quot;0&quot;, got &lt;1&gt;</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
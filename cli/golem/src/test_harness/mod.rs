@@ -0,0 +1,373 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `golem test`: runs agent test scripts against an ephemeral in-process executor.
+//!
+//! The application is built, and its components are served from memory by a worker executor
+//! using deterministic clocks and random sources, and redirecting all outgoing HTTP requests
+//! to [`HttpMockServer`]. The steps of the scripts call the executor directly, but they are
+//! parsed and rendered by the CLI, so they use the same agent ID and value syntax as
+//! `golem agent invoke`.
+
+mod executor;
+mod http_mock;
+mod junit;
+mod script;
+
+use crate::test_harness::executor::{TestExecutor, component_metadata};
+use crate::test_harness::http_mock::HttpMockServer;
+use crate::test_harness::junit::{TestCaseResult, TestSuiteResult, to_junit_xml};
+use crate::test_harness::script::{
+    HttpMock, InvokeStep, OplogStep, TestCase, TestScript, TestStep,
+};
+use anyhow::{Context as AnyhowContext, bail};
+use golem_cli::command::test::TestArgs;
+use golem_cli::command_handler::Handlers;
+use golem_cli::context::Context;
+use golem_cli::error::NonSuccessfulExit;
+use golem_cli::log::{
+    LogIndent, log_action, log_error_action, log_finished_ok, log_warn_action, logln,
+};
+use golem_cli::model::app::{ApplicationComponentSelectMode, BuildConfig};
+use golem_cli::model::invoke_result_view::InvokeResultView;
+use golem_cli::model::worker::{ParsedAgentInvocation, RawAgentId};
+use golem_common::model::IdempotencyKey;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::LegacyParsedAgentId;
+use golem_common::model::application::ApplicationId;
+use golem_common::model::component::{ComponentDto, ComponentId};
+use golem_common::model::environment::EnvironmentId;
+use golem_service_base::model::component::Component;
+use golem_worker_executor::durable_host::test_hooks::{Determinism, TestHooks};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub async fn run_test_scripts(ctx: Arc<Context>, args: TestArgs) -> anyhow::Result<()> {
+    let scripts = args
+        .scripts
+        .iter()
+        .map(|path| TestScript::load(path).map(|script| (path.clone(), script)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    ctx.app_handler()
+        .build(
+            &BuildConfig::new(),
+            vec![],
+            &ApplicationComponentSelectMode::All,
+        )
+        .await?;
+
+    let http_mock_server = HttpMockServer::start().await?;
+
+    let data_dir = tempfile::Builder::new()
+        .prefix("golem-test-")
+        .tempdir()
+        .context("Failed to create data directory for the test executor")?;
+
+    let (components, wasm) = load_components(&ctx).await?;
+
+    log_action("Starting", "test executor");
+    let executor = TestExecutor::start(
+        data_dir.path(),
+        TestHooks {
+            determinism: Some(Determinism {
+                start_time: *args.start_time,
+                clock_step: *args.clock_step,
+                random_seed: args.seed,
+            }),
+            http_mock_url: Some(http_mock_server.url().clone()),
+        },
+        components.clone(),
+        wasm,
+    )
+    .await?;
+
+    let runner = StepRunner {
+        ctx,
+        executor,
+        components: components.into_iter().map(ComponentDto::from).collect(),
+    };
+
+    let mut suites = Vec::new();
+    for (path, script) in &scripts {
+        suites.push(run_script(&runner, &http_mock_server, path, script, args.record).await?);
+    }
+
+    if let Some(junit) = &args.junit {
+        tokio::fs::write(junit, to_junit_xml(&suites))
+            .await
+            .with_context(|| format!("Failed to write JUnit report to {}", junit.display()))?;
+    }
+
+    if args.keep_data_dir {
+        logln(format!(
+            "Kept the data directory of the test executor at {}",
+            data_dir.keep().display()
+        ));
+    }
+
+    let tests = suites.iter().map(|suite| suite.cases.len()).sum::<usize>();
+    let failures = suites.iter().map(TestSuiteResult::failures).sum::<usize>();
+    if failures == 0 {
+        log_finished_ok(format!("running {tests} tests"));
+        Ok(())
+    } else {
+        log_error_action("Failed", format!("{failures} of {tests} tests"));
+        bail!(NonSuccessfulExit)
+    }
+}
+
+/// Loads the built components of the application, owned by a fresh account and environment
+async fn load_components(
+    ctx: &Arc<Context>,
+) -> anyhow::Result<(Vec<Component>, HashMap<ComponentId, Vec<u8>>)> {
+    let component_names = {
+        let app_ctx = ctx.app_context_lock().await;
+        app_ctx.some_or_err()?.component_names()
+    };
+
+    let account_id = AccountId::new();
+    let environment_id = EnvironmentId::new();
+    let application_id = ApplicationId::new();
+
+    let mut components = Vec::new();
+    let mut wasm = HashMap::new();
+    for component_name in &component_names {
+        let properties = ctx
+            .component_handler()
+            .component_deploy_properties(component_name)
+            .await?;
+        let bytes = tokio::fs::read(&properties.wasm_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read the built component {}",
+                    properties.wasm_path.display()
+                )
+            })?;
+        let component = component_metadata(
+            component_name,
+            &properties,
+            &bytes,
+            account_id,
+            environment_id,
+            application_id,
+        )?;
+        wasm.insert(component.id, bytes);
+        components.push(component);
+    }
+
+    Ok((components, wasm))
+}
+
+async fn run_script(
+    runner: &StepRunner,
+    http_mock_server: &HttpMockServer,
+    path: &Path,
+    script: &TestScript,
+    record: bool,
+) -> anyhow::Result<TestSuiteResult> {
+    let recordings_path = script.http_recordings_path(path);
+    let recordings = load_http_recordings(&recordings_path).await?;
+    let mut mocks = script.http_mocks.clone();
+    mocks.extend(recordings.iter().cloned());
+    http_mock_server.load(mocks, record);
+
+    log_action("Running", format!("tests of {}", path.display()));
+    let _indent = LogIndent::new();
+
+    let mut cases = Vec::new();
+    for test in &script.tests {
+        let start = Instant::now();
+        let mut failure = run_test(runner, test).await.err();
+        let unmatched = http_mock_server.take_unmatched();
+        if !unmatched.is_empty() {
+            let unmatched = unmatched.join("\n");
+            failure = Some(match failure {
+                Some(failure) => format!("{failure}\n{unmatched}"),
+                None => unmatched,
+            });
+        }
+
+        match &failure {
+            Some(failure) => log_error_action("Failed", format!("{}: {failure}", test.name)),
+            None => log_finished_ok(&test.name),
+        }
+        cases.push(TestCaseResult {
+            name: test.name.clone(),
+            duration: start.elapsed(),
+            failure,
+        });
+    }
+
+    let recorded = http_mock_server.take_recorded();
+    if !recorded.is_empty() {
+        log_warn_action(
+            "Recorded",
+            format!(
+                "{} HTTP responses into {}",
+                recorded.len(),
+                recordings_path.display()
+            ),
+        );
+        let mut recordings = recordings;
+        recordings.extend(recorded);
+        tokio::fs::write(&recordings_path, serde_json::to_vec_pretty(&recordings)?)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to write HTTP recordings to {}",
+                    recordings_path.display()
+                )
+            })?;
+    }
+
+    Ok(TestSuiteResult {
+        name: path.display().to_string(),
+        cases,
+    })
+}
+
+async fn load_http_recordings(path: &Path) -> anyhow::Result<Vec<HttpMock>> {
+    if tokio::fs::metadata(path).await.is_err() {
+        return Ok(Vec::new());
+    }
+    let content = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read HTTP recordings from {}", path.display()))?;
+    serde_json::from_slice(&content)
+        .with_context(|| format!("Failed to parse HTTP recordings from {}", path.display()))
+}
+
+async fn run_test(runner: &StepRunner, test: &TestCase) -> Result<(), String> {
+    for (idx, step) in test.steps.iter().enumerate() {
+        let result = match step {
+            TestStep::Invoke(step) => runner.run_invoke_step(step).await,
+            TestStep::Oplog(step) => runner.run_oplog_step(step).await,
+        };
+        result.map_err(|err| format!("step {}: {err}", idx + 1))?;
+    }
+    Ok(())
+}
+
+/// Parses the steps with the CLI and executes them on the test executor
+struct StepRunner {
+    ctx: Arc<Context>,
+    executor: TestExecutor,
+    components: Vec<ComponentDto>,
+}
+
+impl StepRunner {
+    async fn run_invoke_step(&self, step: &InvokeStep) -> Result<(), String> {
+        let component = self.component_of(&step.agent)?;
+        let ParsedAgentInvocation {
+            agent_id,
+            agent_type,
+            method_name,
+            method_parameters,
+        } = self
+            .ctx
+            .worker_handler()
+            .parse_agent_invocation(
+                component,
+                &RawAgentId(step.agent.clone()),
+                &step.function,
+                step.args.clone(),
+            )
+            .map_err(|err| format!("{err:#}"))?;
+
+        let result = self
+            .executor
+            .invoke_and_await(
+                component.id,
+                &agent_id,
+                &agent_type,
+                &method_name,
+                method_parameters,
+            )
+            .await
+            .map_err(|err| format!("{err:#}"))?;
+
+        match (&step.expect_error, result) {
+            (Some(expected_error), Ok(_)) => Err(format!(
+                "expected invoking {method_name} to fail with {expected_error:?}, but it succeeded"
+            )),
+            (Some(expected_error), Err(error)) => {
+                let error = error.to_string();
+                if error.contains(expected_error.as_str()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "expected invoking {method_name} to fail with {expected_error:?}, got:\n{error}"
+                    ))
+                }
+            }
+            (None, Err(error)) => Err(format!("invoking {method_name} failed:\n{error}")),
+            (None, Ok(result)) => {
+                let Some(expected) = &step.expect else {
+                    return Ok(());
+                };
+                let view = InvokeResultView::new_agent_invoke_data_value(
+                    IdempotencyKey::fresh(),
+                    result,
+                    &agent_type,
+                    &method_name,
+                );
+                let actual = view.result.unwrap_or_default();
+                if actual.trim() == expected.trim() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "expected {method_name} to return {expected:?}, got {actual:?}"
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn run_oplog_step(&self, step: &OplogStep) -> Result<(), String> {
+        let component = self.component_of(&step.agent)?;
+        let (agent_id, _) = self
+            .ctx
+            .worker_handler()
+            .parse_agent_id(component, &RawAgentId(step.agent.clone()))
+            .map_err(|err| format!("{err:#}"))?;
+
+        let matches = self
+            .executor
+            .count_oplog_matches(component.id, &agent_id, &step.query)
+            .await
+            .map_err(|err| format!("{err:#}"))?;
+
+        step.check(matches)
+            .map_err(|err| format!("oplog query {:?} of {}: {err}", step.query, step.agent))
+    }
+
+    /// Finds the component defining the type of an agent
+    fn component_of(&self, agent: &str) -> Result<&ComponentDto, String> {
+        let agent_type = LegacyParsedAgentId::parse_agent_type_name(agent)
+            .map_err(|err| format!("invalid agent ID {agent}: {err}"))?;
+        self.components
+            .iter()
+            .find(|component| {
+                component
+                    .metadata
+                    .find_agent_type_by_name(&agent_type)
+                    .is_some()
+            })
+            .ok_or_else(|| format!("agent type {agent_type} is not defined by the application"))
+    }
+}
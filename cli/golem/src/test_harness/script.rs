@@ -0,0 +1,240 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A `golem test` script, listing tests made of agent invocations and oplog assertions.
+///
+/// ```yaml
+/// httpMocks:
+///   - url: https://api.example.com/weather?city=Berlin
+///     body: '{"temperature": 21}'
+/// tests:
+///   - name: reports the temperature
+///     steps:
+///       - invoke:
+///           agent: weather-agent("berlin")
+///           function: current-temperature
+///           expect: "21"
+///       - oplog:
+///           agent: weather-agent("berlin")
+///           query: http
+///           count: 1
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TestScript {
+    /// JSON file of recorded HTTP responses, relative to the script. Defaults to the script's
+    /// path with the `.http.json` extension.
+    #[serde(default)]
+    pub http_recordings: Option<PathBuf>,
+    /// Responses to outgoing HTTP requests, taking precedence over the recorded ones
+    #[serde(default)]
+    pub http_mocks: Vec<HttpMock>,
+    pub tests: Vec<TestCase>,
+}
+
+impl TestScript {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read test script {}", path.display()))?;
+        serde_yaml::from_str(&source)
+            .with_context(|| format!("Failed to parse test script {}", path.display()))
+    }
+
+    pub fn http_recordings_path(&self, script_path: &Path) -> PathBuf {
+        match &self.http_recordings {
+            Some(path) => script_path
+                .parent()
+                .map(|dir| dir.join(path))
+                .unwrap_or_else(|| path.clone()),
+            None => script_path.with_extension("http.json"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TestCase {
+    pub name: String,
+    pub steps: Vec<TestStep>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum TestStep {
+    Invoke(InvokeStep),
+    Oplog(OplogStep),
+}
+
+/// Invokes an agent method and checks its result
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InvokeStep {
+    /// Agent ID, in the same form as accepted by `golem agent invoke`
+    pub agent: String,
+    pub function: String,
+    /// Arguments in the agent's source language syntax
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Expected result, rendered in the agent's source language syntax
+    #[serde(default)]
+    pub expect: Option<String>,
+    /// Expect the invocation to fail with an error containing the given text
+    #[serde(default)]
+    pub expect_error: Option<String>,
+}
+
+/// Queries the oplog of an agent and checks the number of matching entries
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OplogStep {
+    pub agent: String,
+    /// Oplog query, in the same syntax as accepted by `golem agent oplog --query`
+    pub query: String,
+    #[serde(default)]
+    pub count: Option<usize>,
+    #[serde(default)]
+    pub min: Option<usize>,
+    #[serde(default)]
+    pub max: Option<usize>,
+}
+
+impl OplogStep {
+    pub fn check(&self, matches: usize) -> Result<(), String> {
+        if let Some(count) = self.count
+            && matches != count
+        {
+            return Err(format!(
+                "expected {count} matching oplog entries, got {matches}"
+            ));
+        }
+        if let Some(min) = self.min
+            && matches < min
+        {
+            return Err(format!(
+                "expected at least {min} matching oplog entries, got {matches}"
+            ));
+        }
+        if let Some(max) = self.max
+            && matches > max
+        {
+            return Err(format!(
+                "expected at most {max} matching oplog entries, got {matches}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A canned response to an outgoing HTTP request, matched by method and original URL.
+///
+/// Mocks matching the same request are served in order, and the last one is repeated once all
+/// of them got served.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpMock {
+    #[serde(default = "default_http_mock_method")]
+    pub method: String,
+    pub url: String,
+    #[serde(default = "default_http_mock_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl HttpMock {
+    pub fn matches(&self, method: &str, url: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method) && self.url == url
+    }
+}
+
+fn default_http_mock_method() -> String {
+    "GET".to_string()
+}
+
+fn default_http_mock_status() -> u16 {
+    200
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn parses_test_script() {
+        let script: TestScript = serde_yaml::from_str(
+            r#"
+httpMocks:
+  - url: https://api.example.com/weather
+    body: '{"temperature": 21}'
+tests:
+  - name: reports the temperature
+    steps:
+      - invoke:
+          agent: weather-agent("berlin")
+          function: current-temperature
+          expect: "21"
+      - oplog:
+          agent: weather-agent("berlin")
+          query: http
+          min: 1
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(script.http_mocks.len(), 1);
+        assert_eq!(script.http_mocks[0].method, "GET");
+        assert_eq!(script.http_mocks[0].status, 200);
+        assert_eq!(script.tests.len(), 1);
+        assert!(matches!(
+            &script.tests[0].steps[0],
+            TestStep::Invoke(InvokeStep { expect: Some(expect), .. }) if expect == "21"
+        ));
+        assert!(matches!(
+            &script.tests[0].steps[1],
+            TestStep::Oplog(OplogStep { min: Some(1), .. })
+        ));
+    }
+
+    #[test]
+    fn default_http_recordings_path() {
+        let script: TestScript = serde_yaml::from_str("tests: []").unwrap();
+        assert_eq!(
+            script.http_recordings_path(Path::new("tests/weather.yaml")),
+            PathBuf::from("tests/weather.http.json")
+        );
+    }
+
+    #[test]
+    fn oplog_step_checks_bounds() {
+        let step = OplogStep {
+            agent: "agent".to_string(),
+            query: "query".to_string(),
+            count: None,
+            min: Some(1),
+            max: Some(2),
+        };
+        assert!(step.check(0).is_err());
+        assert!(step.check(1).is_ok());
+        assert!(step.check(2).is_ok());
+        assert!(step.check(3).is_err());
+    }
+}
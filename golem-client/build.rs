@@ -70,6 +70,14 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "UntypedDataValue",
                 "golem_common::model::agent::UntypedDataValue",
             ),
+            (
+                "AgentTypeSchemaExport",
+                "golem_common::model::agent::AgentTypeSchemaExport",
+            ),
+            (
+                "AgentTypeSchemaFormat",
+                "golem_common::model::agent::AgentTypeSchemaFormat",
+            ),
             // application
             (
                 "Application",
@@ -109,6 +117,23 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "RetryPolicyUpdate",
                 "golem_common::model::retry_policy::RetryPolicyUpdate",
             ),
+            // oplog export sink
+            (
+                "OplogExportSinkDto",
+                "golem_common::model::oplog_export::OplogExportSinkDto",
+            ),
+            (
+                "OplogExportSinkCreation",
+                "golem_common::model::oplog_export::OplogExportSinkCreation",
+            ),
+            (
+                "OplogExportSinkUpdate",
+                "golem_common::model::oplog_export::OplogExportSinkUpdate",
+            ),
+            (
+                "OplogExportTarget",
+                "golem_common::model::oplog_export::OplogExportTarget",
+            ),
             // auth
             ("Token", "golem_common::model::auth::Token"),
             ("TokenCreation", "golem_common::model::auth::TokenCreation"),
@@ -260,6 +285,10 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "ComponentTransformerPluginSpec",
                 "golem_common::model::plugin_registration::ComponentTransformerPluginSpec",
             ),
+            (
+                "InvocationInterceptorPluginSpec",
+                "golem_common::model::plugin_registration::InvocationInterceptorPluginSpec",
+            ),
             (
                 "OplogProcessorPluginSpec",
                 "golem_common::model::plugin_registration::OplogProcessorPluginSpec",
@@ -340,6 +369,14 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "AgentUpdateMode",
                 "golem_common::model::worker::AgentUpdateMode",
             ),
+            (
+                "AgentLifecycleAuditEntry",
+                "golem_common::model::worker::AgentLifecycleAuditEntry",
+            ),
+            (
+                "AgentLifecycleAction",
+                "golem_common::model::component_metadata::AgentLifecycleAction",
+            ),
             // oplog
             ("OplogCursor", "golem_common::model::oplog::OplogCursor"),
             (
@@ -350,6 +387,10 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "PublicOplogEntryWithIndex",
                 "golem_common::model::oplog::PublicOplogEntryWithIndex",
             ),
+            (
+                "AgentOplogSearchMatch",
+                "golem_common::model::oplog::AgentOplogSearchMatch",
+            ),
             // http_api_deployment
             (
                 "HttpApiDeployment",
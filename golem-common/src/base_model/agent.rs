@@ -19,6 +19,7 @@ use crate::base_model::component::{ComponentId, ComponentRevision};
 use crate::base_model::deployment::{CurrentDeploymentRevision, DeploymentRevision};
 use crate::base_model::diff::Hash as DiffHash;
 use crate::base_model::environment::EnvironmentId;
+use crate::declare_enums;
 use crate::model::Empty;
 use async_trait::async_trait;
 use golem_wasm::agentic::unstructured_binary::{AllowedMimeTypes, UnstructuredBinary};
@@ -147,6 +148,25 @@ impl From<DeployedRegisteredAgentType> for RegisteredAgentType {
     }
 }
 
+declare_enums! {
+    /// Format of an exported agent type schema
+    pub enum AgentTypeSchemaFormat {
+        Protobuf,
+        JsonSchema,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+/// Agent type schema rendered in a format consumable by external tooling
+pub struct AgentTypeSchemaExport {
+    pub agent_type_name: AgentTypeName,
+    pub format: AgentTypeSchemaFormat,
+    pub content: String,
+}
+
 /// Result of resolving an agent type by names, bundling the agent type
 /// with the environment it belongs to.
 #[derive(Clone)]
@@ -236,6 +256,11 @@ pub enum RegistryInvalidationEvent {
     },
     /// A permission card was revoked or deleted.
     CardRevoked { event_id: u64, card_ids: Vec<Uuid> },
+    /// An environment oplog export sink was created, updated, or deleted.
+    OplogExportSinkChanged {
+        event_id: u64,
+        environment_id: EnvironmentId,
+    },
 }
 
 impl RegistryInvalidationEvent {
@@ -253,6 +278,7 @@ impl RegistryInvalidationEvent {
             Self::ApplicationDeleted { event_id, .. } => *event_id,
             Self::EnvironmentDeleted { event_id, .. } => *event_id,
             Self::CardRevoked { event_id, .. } => *event_id,
+            Self::OplogExportSinkChanged { event_id, .. } => *event_id,
         }
     }
 }
@@ -531,7 +557,7 @@ pub struct AgentConstructor {
     feature = "full",
     derive(desert_rust::BinaryCodec, poem_openapi::Object, IntoValue, FromValue)
 )]
-#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", desert(evolution(FieldAdded("timeout_nanos", None))))]
 #[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
 #[serde(rename_all = "camelCase")]
 pub struct AgentMethod {
@@ -543,6 +569,11 @@ pub struct AgentMethod {
     pub http_endpoint: Vec<HttpEndpointDetails>,
     #[serde(default)]
     pub read_only: Option<ReadOnlyConfig>,
+    /// Maximum execution time of a single invocation of this method, in nanoseconds.
+    /// When set, the executor interrupts invocations running past it.
+    #[serde(default)]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub timeout_nanos: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1224,6 +1255,8 @@ pub enum HttpMethod {
     #[unit_case]
     Patch(Empty),
     Custom(CustomHttpMethod),
+    #[unit_case]
+    WebSocket(Empty),
 }
 
 #[cfg(feature = "full")]
@@ -1245,6 +1278,8 @@ impl TryFrom<HttpMethod> for http::Method {
                 let converted = http::Method::from_bytes(custom.value.as_bytes())?;
                 Ok(converted)
             }
+            // WebSocket endpoints are opened with a GET upgrade request
+            HttpMethod::WebSocket(_) => Ok(http::Method::GET),
         }
     }
 }
@@ -1635,6 +1670,7 @@ mod tests {
             }),
             http_endpoint: Vec::new(),
             read_only,
+            timeout_nanos: None,
         }
     }
 
@@ -37,6 +37,7 @@ pub mod error_code {
     pub const AGENT_SECRET_NOT_FOUND: &str = "AGENT_SECRET_NOT_FOUND";
     pub const AGENT_TYPE_NOT_FOUND: &str = "AGENT_TYPE_NOT_FOUND";
     pub const APPLICATION_NOT_FOUND: &str = "APPLICATION_NOT_FOUND";
+    pub const BULK_INVOCATION_JOB_NOT_FOUND: &str = "BULK_INVOCATION_JOB_NOT_FOUND";
     pub const COMPONENT_NOT_FOUND: &str = "COMPONENT_NOT_FOUND";
     pub const DEPLOYMENT_NOT_FOUND: &str = "DEPLOYMENT_NOT_FOUND";
     pub const DOMAIN_REGISTRATION_NOT_FOUND: &str = "DOMAIN_REGISTRATION_NOT_FOUND";
@@ -44,6 +45,7 @@ pub mod error_code {
     pub const FILE_NOT_FOUND: &str = "FILE_NOT_FOUND";
     pub const PLAN_NOT_FOUND: &str = "PLAN_NOT_FOUND";
     pub const PLUGIN_REGISTRATION_NOT_FOUND: &str = "PLUGIN_REGISTRATION_NOT_FOUND";
+    pub const OPLOG_EXPORT_SINK_NOT_FOUND: &str = "OPLOG_EXPORT_SINK_NOT_FOUND";
     pub const PERMISSION_SHARE_NOT_FOUND: &str = "PERMISSION_SHARE_NOT_FOUND";
     pub const RESOURCE_DEFINITION_NOT_FOUND: &str = "RESOURCE_DEFINITION_NOT_FOUND";
     pub const RETRY_POLICY_NOT_FOUND: &str = "RETRY_POLICY_NOT_FOUND";
@@ -82,6 +84,7 @@ pub mod error_code {
     pub const MCP_DEPLOYMENT_ALREADY_EXISTS: &str = "MCP_DEPLOYMENT_ALREADY_EXISTS";
     pub const PLUGIN_PRIORITY_CONFLICT: &str = "PLUGIN_PRIORITY_CONFLICT";
     pub const PLUGIN_REGISTRATION_ALREADY_EXISTS: &str = "PLUGIN_REGISTRATION_ALREADY_EXISTS";
+    pub const OPLOG_EXPORT_SINK_ALREADY_EXISTS: &str = "OPLOG_EXPORT_SINK_ALREADY_EXISTS";
     pub const PERMISSION_SHARE_ALREADY_EXISTS: &str = "PERMISSION_SHARE_ALREADY_EXISTS";
     pub const RESOURCE_ALREADY_EXISTS: &str = "RESOURCE_ALREADY_EXISTS";
     pub const RETRY_POLICY_ALREADY_EXISTS: &str = "RETRY_POLICY_ALREADY_EXISTS";
@@ -91,6 +94,7 @@ pub mod error_code {
 
     // --- Validation ---
     pub const AGENT_TYPE_NOT_DECLARED: &str = "AGENT_TYPE_NOT_DECLARED";
+    pub const AGENT_TYPE_SCHEMA_NOT_EXPORTABLE: &str = "AGENT_TYPE_SCHEMA_NOT_EXPORTABLE";
     pub const BAD_FILE_TYPE: &str = "BAD_FILE_TYPE";
     pub const COMPONENT_PROCESSING_ERROR: &str = "COMPONENT_PROCESSING_ERROR";
     pub const DEPLOYMENT_HASH_MISMATCH: &str = "DEPLOYMENT_HASH_MISMATCH";
@@ -117,6 +121,8 @@ pub mod error_code {
         pub const HTTP_API_INVALID_AGENT_WEBHOOK_SEGMENT_TYPE: &str =
             "HTTP_API_INVALID_AGENT_WEBHOOK_SEGMENT_TYPE";
         pub const HTTP_API_INVALID_ROUTE: &str = "HTTP_API_INVALID_ROUTE";
+        pub const HTTP_API_INVALID_RATE_LIMIT: &str = "HTTP_API_INVALID_RATE_LIMIT";
+        pub const HTTP_API_RATE_LIMIT_UNKNOWN_METHOD: &str = "HTTP_API_RATE_LIMIT_UNKNOWN_METHOD";
         pub const SECURITY_OVERRIDE_DISABLED: &str = "SECURITY_OVERRIDE_DISABLED";
         pub const ROUTE_IS_AMBIGUOUS: &str = "ROUTE_IS_AMBIGUOUS";
         pub const INVALID_HTTP_METHOD: &str = "INVALID_HTTP_METHOD";
@@ -140,6 +146,8 @@ pub mod error_code {
     pub const INVALID_CUSTOM_PROVIDER_ISSUER_URL: &str = "INVALID_CUSTOM_PROVIDER_ISSUER_URL";
     pub const INVALID_FILE_NAME: &str = "INVALID_FILE_NAME";
     pub const INVALID_FILE_PATH: &str = "INVALID_FILE_PATH";
+    pub const INVOCATION_INTERCEPTOR_COMPONENT_NOT_FOUND: &str =
+        "INVOCATION_INTERCEPTOR_COMPONENT_NOT_FOUND";
     pub const INVALID_OAUTH_SESSION: &str = "INVALID_OAUTH_SESSION";
     pub const INVALID_OPLOG_QUERY_PARAMS: &str = "INVALID_OPLOG_QUERY_PARAMS";
     pub const INVALID_OPLOG_PROCESSOR_PLUGIN: &str = "INVALID_OPLOG_PROCESSOR_PLUGIN";
@@ -156,6 +164,7 @@ pub mod error_code {
     pub const PLUGIN_INSTALLATION_NOT_FOUND: &str = "PLUGIN_INSTALLATION_NOT_FOUND";
     pub const REFERENCED_PLUGIN_NOT_FOUND: &str = "REFERENCED_PLUGIN_NOT_FOUND";
     pub const REQUEST_BODY_INVALID_JSON: &str = "REQUEST_BODY_INVALID_JSON";
+    pub const REQUEST_FORM_BODY_PARSING_FAILED: &str = "REQUEST_FORM_BODY_PARSING_FAILED";
     pub const REQUEST_DOMAIN_EXTRACTION_FAILED: &str = "REQUEST_DOMAIN_EXTRACTION_FAILED";
     pub const REQUEST_HEADER_NOT_ASCII: &str = "REQUEST_HEADER_NOT_ASCII";
     pub const REQUEST_JSON_BODY_PARSING_FAILED: &str = "REQUEST_JSON_BODY_PARSING_FAILED";
@@ -167,8 +176,13 @@ pub mod error_code {
     pub const REQUEST_BODY_INVALID_UTF8: &str = "REQUEST_BODY_INVALID_UTF8";
     pub const REQUEST_UNSUPPORTED_LANGUAGE: &str = "REQUEST_UNSUPPORTED_LANGUAGE";
     pub const REQUEST_UNSUPPORTED_TEXT_CONTENT_TYPE: &str = "REQUEST_UNSUPPORTED_TEXT_CONTENT_TYPE";
+    pub const REQUEST_UNSUPPORTED_FORM_CONTENT_TYPE: &str = "REQUEST_UNSUPPORTED_FORM_CONTENT_TYPE";
     pub const REQUEST_MULTI_VALUED_CONTENT_LANGUAGE: &str = "REQUEST_MULTI_VALUED_CONTENT_LANGUAGE";
     pub const REQUEST_VALUE_PARSING_FAILED: &str = "REQUEST_VALUE_PARSING_FAILED";
+    pub const REQUEST_UNEXPECTED_WEBSOCKET_MESSAGE: &str = "REQUEST_UNEXPECTED_WEBSOCKET_MESSAGE";
+    pub const REQUEST_WEBSOCKET_UPGRADE_FAILED: &str = "REQUEST_WEBSOCKET_UPGRADE_FAILED";
+    pub const OPLOG_EXPORT_SINK_INVALID_NAME: &str = "OPLOG_EXPORT_SINK_INVALID_NAME";
+    pub const OPLOG_EXPORT_SINK_INVALID_TARGET: &str = "OPLOG_EXPORT_SINK_INVALID_TARGET";
     pub const RETRY_POLICY_INVALID_POLICY_JSON: &str = "RETRY_POLICY_INVALID_POLICY_JSON";
     pub const RETRY_POLICY_INVALID_PREDICATE_JSON: &str = "RETRY_POLICY_INVALID_PREDICATE_JSON";
     pub const RESOURCE_LIMIT_TYPE_IMMUTABLE: &str = "RESOURCE_LIMIT_TYPE_IMMUTABLE";
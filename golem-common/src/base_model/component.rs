@@ -15,7 +15,7 @@
 use crate::base_model::account::AccountId;
 use crate::base_model::agent::{AgentFileContentHash, AgentType};
 use crate::base_model::application::ApplicationId;
-use crate::base_model::component_metadata::ComponentMetadata;
+use crate::base_model::component_metadata::{AgentLifecyclePolicy, ComponentMetadata};
 use crate::base_model::diff;
 use crate::base_model::environment::EnvironmentId;
 use crate::base_model::environment_plugin_grant::EnvironmentPluginGrantId;
@@ -159,6 +159,9 @@ declare_structs! {
         #[serde(default)]
         #[cfg_attr(feature = "full", oai(default))]
         pub files: BTreeMap<ArchiveFilePath, AgentFileOptions>,
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub lifecycle: Option<AgentLifecyclePolicy>,
     }
 
     #[derive(Default)]
@@ -177,6 +180,8 @@ declare_structs! {
         #[serde(default)]
         #[cfg_attr(feature = "full", oai(default))]
         pub file_permission_updates: BTreeMap<AgentFilePath, AgentFilePermissions>,
+        /// Replaces the lifecycle policy if set. An empty policy removes it.
+        pub lifecycle: Option<AgentLifecyclePolicy>,
     }
 
     pub struct AgentFileOptions {
@@ -205,7 +210,13 @@ declare_structs! {
 
     #[derive(Eq)]
     #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
-    #[cfg_attr(feature = "full", desert(evolution()))]
+    #[cfg_attr(
+        feature = "full",
+        desert(evolution(
+            FieldAdded("invocation_interceptor_component_id", None),
+            FieldAdded("invocation_interceptor_component_revision", None)
+        ))
+    )]
     pub struct InstalledPlugin {
         pub environment_plugin_grant_id: EnvironmentPluginGrantId,
         pub priority: PluginPriority,
@@ -218,6 +229,10 @@ declare_structs! {
         // oplog processor only
         pub oplog_processor_component_id: Option<ComponentId>,
         pub oplog_processor_component_revision: Option<ComponentRevision>,
+
+        // invocation interceptor only
+        pub invocation_interceptor_component_id: Option<ComponentId>,
+        pub invocation_interceptor_component_revision: Option<ComponentRevision>,
     }
 
     #[derive(Eq)]
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::base_model::AgentStatus;
 use crate::base_model::agent::AgentType;
 use crate::base_model::component::{InitialAgentFile, InstalledPlugin};
 use crate::base_model::worker::TypedAgentConfigEntry;
@@ -22,6 +23,7 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(
@@ -69,7 +71,10 @@ pub struct Producers {
     feature = "full",
     derive(desert_rust::BinaryCodec, poem_openapi::Object)
 )]
-#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(
+    feature = "full",
+    desert(evolution(FieldAdded("invocation_interceptor_interface", None)))
+)]
 #[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
 #[serde(rename_all = "camelCase")]
 pub struct KnownExports {
@@ -89,6 +94,10 @@ pub struct KnownExports {
     #[serde(default)]
     #[cfg_attr(feature = "full", oai(default))]
     pub oplog_processor_interface: Option<String>,
+    /// Exact exported interface name for `golem:agent/interceptor`
+    #[serde(default)]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub invocation_interceptor_interface: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -221,7 +230,7 @@ pub struct AgentInitialPermissionTemplate {
     feature = "full",
     derive(desert_rust::BinaryCodec, poem_openapi::Object)
 )]
-#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", desert(evolution(FieldAdded("lifecycle", None))))]
 #[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
 #[serde(rename_all = "camelCase")]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -238,4 +247,102 @@ pub struct AgentTypeProvisionConfig {
     #[serde(default)]
     #[cfg_attr(feature = "full", oai(default))]
     pub files: Vec<InitialAgentFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub lifecycle: Option<AgentLifecyclePolicy>,
+}
+
+/// Controls how long the agents of an agent type are kept after they stopped being used.
+///
+/// Each period is measured from the point the agent became idle (or failed, or exited) and is
+/// restarted whenever the agent gets invoked again. Periods that are not set are not applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "full",
+    derive(desert_rust::BinaryCodec, poem_openapi::Object)
+)]
+#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct AgentLifecyclePolicy {
+    /// Deletes the agent after it stayed idle (or failed) for the given period
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub delete_after_idle_nanos: Option<u64>,
+    /// Deletes the agent the given period after it exited. Falls back to
+    /// `deleteAfterIdleNanos` for exited agents if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub delete_after_exit_nanos: Option<u64>,
+    /// Moves the agent's whole oplog to the archive layers after it stayed idle (or failed, or
+    /// exited) for the given period
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub archive_after_idle_nanos: Option<u64>,
+}
+
+impl AgentLifecyclePolicy {
+    pub fn is_disabled(&self) -> bool {
+        self.delete_after_idle_nanos.is_none()
+            && self.delete_after_exit_nanos.is_none()
+            && self.archive_after_idle_nanos.is_none()
+    }
+
+    /// The period after which the given action is due, if the policy enables it
+    pub fn period_of(&self, action: AgentLifecycleAction) -> Option<Duration> {
+        match action {
+            AgentLifecycleAction::DeleteAfterIdle => self.delete_after_idle_nanos,
+            AgentLifecycleAction::DeleteAfterExit => self
+                .delete_after_exit_nanos
+                .or(self.delete_after_idle_nanos),
+            AgentLifecycleAction::ArchiveAfterIdle => self.archive_after_idle_nanos,
+        }
+        .map(Duration::from_nanos)
+    }
+
+    /// The actions to schedule when an agent enters the given status, with the period after
+    /// which each of them is due
+    pub fn actions_for_status(&self, status: AgentStatus) -> Vec<(AgentLifecycleAction, Duration)> {
+        let actions: &[AgentLifecycleAction] = match status {
+            AgentStatus::Idle | AgentStatus::Failed => &[
+                AgentLifecycleAction::DeleteAfterIdle,
+                AgentLifecycleAction::ArchiveAfterIdle,
+            ],
+            AgentStatus::Exited => &[
+                AgentLifecycleAction::DeleteAfterExit,
+                AgentLifecycleAction::ArchiveAfterIdle,
+            ],
+            _ => &[],
+        };
+        actions
+            .iter()
+            .filter_map(|action| self.period_of(*action).map(|period| (*action, period)))
+            .collect()
+    }
+}
+
+/// An action of an [`AgentLifecyclePolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec, poem_openapi::Enum))]
+#[cfg_attr(feature = "full", desert(evolution()))]
+pub enum AgentLifecycleAction {
+    DeleteAfterIdle,
+    DeleteAfterExit,
+    ArchiveAfterIdle,
+}
+
+impl AgentLifecycleAction {
+    pub fn is_deletion(&self) -> bool {
+        matches!(self, Self::DeleteAfterIdle | Self::DeleteAfterExit)
+    }
+}
+
+impl fmt::Display for AgentLifecycleAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeleteAfterIdle => write!(f, "delete-after-idle"),
+            Self::DeleteAfterExit => write!(f, "delete-after-exit"),
+            Self::ArchiveAfterIdle => write!(f, "archive-after-idle"),
+        }
+    }
 }
@@ -17,7 +17,8 @@ use crate::base_model::agent::AgentTypeName;
 use crate::base_model::diff;
 use crate::base_model::domain_registration::Domain;
 use crate::base_model::environment::EnvironmentId;
-use crate::{declare_revision, declare_structs, declare_unions, newtype_uuid};
+use crate::base_model::quota::TimePeriod;
+use crate::{declare_enums, declare_revision, declare_structs, declare_unions, newtype_uuid};
 use chrono::DateTime;
 use std::collections::BTreeMap;
 
@@ -34,6 +35,24 @@ declare_unions! {
     }
 }
 
+declare_enums! {
+    #[derive(Hash)]
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    #[cfg_attr(feature = "full", desert(evolution()))]
+    /// Identity of the client a rate limit is tracked for.
+    /// The client ip is the right-most hop that is not a trusted proxy. `X-Forwarded-For` is only
+    /// read when the peer is one of the gateway's `rate_limit.trusted_proxies`.
+    pub enum HttpApiRateLimitKey {
+        /// Address of the client: the right-most hop that is not a trusted proxy. `X-Forwarded-For`
+        /// is only read when the peer is one of the gateway's `rate_limit.trusted_proxies`.
+        ClientIp,
+        /// Subject of the authenticated OIDC session. Anonymous requests are keyed by client ip.
+        Principal,
+        /// Value of the api key header. Requests without the header are keyed by client ip.
+        ApiKey,
+    }
+}
+
 declare_structs! {
     #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
     #[cfg_attr(feature = "full", desert(evolution()))]
@@ -51,27 +70,68 @@ declare_structs! {
         pub security_scheme: SecuritySchemeName
     }
 
-    #[derive(Default)]
+    #[derive(Eq, Hash)]
     #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
     #[cfg_attr(feature = "full", desert(evolution()))]
+    /// Request rate limit enforced by the gateway before any agent is invoked.
+    /// Every client gets `value` requests per `period`, with bursts of up to `max` requests.
+    /// Requests over the limit are rejected with `429 Too Many Requests`.
+    pub struct HttpApiRateLimit {
+        pub key: HttpApiRateLimitKey,
+        /// Header carrying the api key when `key` is `api-key`. Defaults to `x-api-key`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub api_key_header: Option<String>,
+        pub value: u64,
+        pub period: TimePeriod,
+        /// Maximum burst capacity
+        pub max: u64
+    }
+
+    #[derive(Default)]
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    #[cfg_attr(
+        feature = "full",
+        desert(evolution(
+            FieldAdded("rate_limits", Vec::new()),
+            FieldAdded("method_rate_limits", BTreeMap::new())
+        ))
+    )]
     pub struct HttpApiDeploymentAgentOptions {
         /// Security option to use for all agent methods that require auth.
         /// Failure to provide a security option for an agent that requires one will lead to a deployment failure.
-        pub security: Option<HttpApiDeploymentAgentSecurity>
+        pub security: Option<HttpApiDeploymentAgentSecurity>,
+        /// Rate limits shared by all routes of the agent type.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub rate_limits: Vec<HttpApiRateLimit>,
+        /// Rate limits of the routes of individual agent methods, keyed by method name.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub method_rate_limits: BTreeMap<String, Vec<HttpApiRateLimit>>
     }
 
     pub struct HttpApiDeploymentCreation {
         pub domain: Domain,
         pub webhooks_prefix: String,
         pub openapi_endpoint_prefix: String,
-        pub agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>
+        pub agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+        /// Rate limits shared by all agent method routes of the deployment.
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub rate_limits: Vec<HttpApiRateLimit>,
+        /// Path of the GraphQL endpoint exposing the deployment's agents. Disabled when not set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub graphql_endpoint: Option<String>
     }
 
     pub struct HttpApiDeploymentUpdate {
         pub current_revision: HttpApiDeploymentRevision,
         pub webhook_prefix: Option<String>,
         pub openapi_endpoint_prefix: Option<String>,
-        pub agents: Option<BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>>
+        pub agents: Option<BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>>,
+        pub rate_limits: Option<Vec<HttpApiRateLimit>>,
+        /// Path of the GraphQL endpoint. An empty path disables the endpoint.
+        pub graphql_endpoint: Option<String>
     }
 
     pub struct HttpApiDeployment {
@@ -83,6 +143,11 @@ declare_structs! {
         pub agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
         pub webhooks_prefix: String,
         pub openapi_endpoint_prefix: String,
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub rate_limits: Vec<HttpApiRateLimit>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub graphql_endpoint: Option<String>,
         pub created_at: DateTime<chrono::Utc>,
     }
 }
@@ -115,6 +180,15 @@ impl HttpApiDeploymentCreation {
     pub fn normalize_openapi_endpoint_prefix(openapi_endpoint_prefix: String) -> String {
         Self::normalize_path_prefix(&openapi_endpoint_prefix, true)
     }
+
+    /// Normalizes a GraphQL endpoint path. Empty paths disable the endpoint.
+    pub fn normalize_graphql_endpoint(graphql_endpoint: String) -> Option<String> {
+        if graphql_endpoint.trim_matches('/').is_empty() {
+            None
+        } else {
+            Some(Self::normalize_path_prefix(&graphql_endpoint, false))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -187,4 +261,20 @@ mod tests {
             "/webhooks/v2/".to_string()
         );
     }
+
+    #[test]
+    fn normalize_graphql_endpoint_disables_empty_paths() {
+        assert_eq!(
+            HttpApiDeploymentCreation::normalize_graphql_endpoint("".to_string()),
+            None
+        );
+        assert_eq!(
+            HttpApiDeploymentCreation::normalize_graphql_endpoint("//".to_string()),
+            None
+        );
+        assert_eq!(
+            HttpApiDeploymentCreation::normalize_graphql_endpoint("graphql/".to_string()),
+            Some("/graphql".to_string())
+        );
+    }
 }
@@ -37,6 +37,7 @@ pub mod json;
 pub mod login;
 pub mod mcp_deployment;
 pub mod oplog;
+pub mod oplog_export;
 pub mod optional_field_update;
 pub mod path;
 pub mod permission_share;
@@ -584,13 +585,32 @@ impl IdempotencyKey {
     /// we generate a UUIDv5 in the ROOT_NS namespace and use that a unique namespace for generating
     /// the new idempotency key.
     pub fn derived(base: &IdempotencyKey, oplog_index: OplogIndex) -> Self {
-        let namespace = if let Ok(base_uuid) = Uuid::parse_str(&base.value) {
+        let name = format!("oplog-index-{oplog_index}");
+        Self::from_uuid(Uuid::new_v5(&base.namespace(), name.as_bytes()))
+    }
+
+    /// Generates a deterministic new idempotency key for one of the invocations fanned out to
+    /// multiple agents by a single operation, identified by the base idempotency key.
+    ///
+    /// The namespace is chosen the same way as in [`IdempotencyKey::derived`], while the name part
+    /// is derived from the target agent, so retrying the whole fan-out produces the same key for
+    /// each target regardless of the order or the set of other targets.
+    pub fn derived_for_target(base: &IdempotencyKey, target: &AgentId) -> Self {
+        Self::derived_for_name(base, &format!("target-{target}"))
+    }
+
+    /// Generates a deterministic new idempotency key from the base key and an arbitrary name,
+    /// in the same namespace as [`IdempotencyKey::derived`].
+    pub fn derived_for_name(base: &IdempotencyKey, name: &str) -> Self {
+        Self::from_uuid(Uuid::new_v5(&base.namespace(), name.as_bytes()))
+    }
+
+    fn namespace(&self) -> Uuid {
+        if let Ok(base_uuid) = Uuid::parse_str(&self.value) {
             base_uuid
         } else {
-            Uuid::new_v5(&Self::ROOT_NS, base.value.as_bytes())
-        };
-        let name = format!("oplog-index-{oplog_index}");
-        Self::from_uuid(Uuid::new_v5(&namespace, name.as_bytes()))
+            Uuid::new_v5(&Self::ROOT_NS, self.value.as_bytes())
+        }
     }
 }
 
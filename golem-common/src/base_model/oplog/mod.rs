@@ -668,5 +668,31 @@ oplog_entry! {
         public {
             name: String,
         }
+    },
+    /// A WebSocket connection was opened to the agent through the http api
+    WebSocketConnected {
+        hint: true
+        wit_raw_type: "websocket-connected-parameters"
+        wit_public_type: "websocket-connected-parameters"
+        raw {
+            connection_id: String,
+            method_name: String,
+        }
+        public {
+            connection_id: String,
+            method_name: String,
+        }
+    },
+    /// A WebSocket connection opened through the http api was closed
+    WebSocketDisconnected {
+        hint: true
+        wit_raw_type: "websocket-disconnected-parameters"
+        wit_public_type: "websocket-disconnected-parameters"
+        raw {
+            connection_id: String,
+        }
+        public {
+            connection_id: String,
+        }
     }
 }
@@ -199,6 +199,14 @@ macro_rules! oplog_entry {
                 }
             )),*
         }
+
+        impl PublicOplogEntry {
+            pub fn timestamp(&self) -> Timestamp {
+                match self {
+                    $(Self::$case(params) => params.timestamp),*
+                }
+            }
+        }
     }
 }
 
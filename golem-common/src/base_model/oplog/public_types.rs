@@ -18,7 +18,7 @@ use crate::base_model::invocation_context::{SpanId, TraceId};
 use crate::base_model::oplog::PublicOplogEntry;
 use crate::base_model::oplog::public_oplog_entry::{Deserialize, Serialize};
 use crate::base_model::retry_policy::{ApiPredicate, ApiRetryPolicy};
-use crate::base_model::{Empty, IdempotencyKey, OplogIndex, Timestamp};
+use crate::base_model::{AgentId, Empty, IdempotencyKey, OplogIndex, Timestamp};
 use crate::declare_structs;
 use crate::model::agent::{DataSchema, DataValue, UntypedDataValue};
 use golem_wasm_derive::{FromValue, IntoValue};
@@ -81,6 +81,13 @@ declare_structs! {
         pub oplog_index: OplogIndex,
         pub entry: PublicOplogEntry,
     }
+
+    /// An oplog entry matching a search query run across multiple agents
+    pub struct AgentOplogSearchMatch {
+        pub agent_id: AgentId,
+        pub oplog_index: OplogIndex,
+        pub entry: PublicOplogEntry,
+    }
 }
 
 #[derive(
@@ -357,6 +364,23 @@ pub struct ProcessOplogEntriesParameters {
     // TODO
 }
 
+#[derive(Clone, Debug, Serialize, PartialEq, Deserialize)]
+#[cfg_attr(feature = "full", derive(poem_openapi::Object, IntoValue, FromValue))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+    feature = "full",
+    wit(
+        name = "intercept-invocation-parameters",
+        owner = "golem:api@1.5.0/oplog"
+    )
+)]
+pub struct InterceptInvocationParameters {
+    pub idempotency_key: IdempotencyKey,
+    /// Name of the intercepted agent method
+    pub method_name: String,
+}
+
 #[derive(Clone, Debug, Serialize, PartialEq, Deserialize)]
 #[cfg_attr(feature = "full", derive(poem_openapi::Object, IntoValue, FromValue))]
 #[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
@@ -385,6 +409,7 @@ pub enum PublicAgentInvocation {
     LoadSnapshot(LoadSnapshotParameters),
     ProcessOplogEntries(ProcessOplogEntriesParameters),
     ManualUpdate(ManualUpdateParameters),
+    InterceptInvocation(InterceptInvocationParameters),
 }
 
 #[derive(Clone, Debug, Serialize, PartialEq, Deserialize)]
@@ -442,6 +467,19 @@ pub struct ProcessOplogEntriesResultParameters {
     pub error: Option<String>,
 }
 
+#[derive(Clone, Debug, Serialize, PartialEq, Deserialize)]
+#[cfg_attr(feature = "full", derive(poem_openapi::Object, IntoValue, FromValue))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+    feature = "full",
+    wit(name = "fallible-result-parameters", owner = "golem:api@1.5.0/oplog")
+)]
+pub struct InterceptInvocationResultParameters {
+    /// The rejection reason if the interceptor rejected the invocation
+    pub error: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, PartialEq, Deserialize)]
 #[cfg_attr(feature = "full", derive(poem_openapi::Union, IntoValue, FromValue))]
 #[cfg_attr(feature = "full", oai(discriminator_name = "type", one_of = true))]
@@ -458,6 +496,7 @@ pub enum PublicAgentInvocationResult {
     LoadSnapshot(FallibleResultParameters),
     SaveSnapshot(SaveSnapshotResultParameters),
     ProcessOplogEntries(ProcessOplogEntriesResultParameters),
+    InterceptInvocation(InterceptInvocationResultParameters),
 }
 
 #[derive(Clone, Debug, Serialize, PartialEq, Deserialize, IntoValue, FromValue)]
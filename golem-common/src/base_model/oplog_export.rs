@@ -0,0 +1,79 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::base_model::environment::EnvironmentId;
+use crate::{declare_revision, declare_structs, declare_unions, newtype_uuid};
+use std::collections::BTreeMap;
+
+newtype_uuid!(OplogExportSinkId);
+
+declare_revision!(OplogExportSinkRevision);
+
+declare_structs! {
+    /// Appends the entries to daily rotated JSON Lines files in the executors' export directory
+    pub struct JsonLinesFileExportTarget {}
+
+    /// Writes each batch as a separate JSON Lines object to the environment's custom blob storage
+    pub struct BlobStorageExportTarget {
+        /// Path prefix of the written objects
+        pub prefix: String,
+    }
+
+    /// Writes each batch as a separate Parquet file to the environment's custom blob storage,
+    /// partitioned by date
+    pub struct ParquetBlobStorageExportTarget {
+        /// Path prefix of the written objects
+        pub prefix: String,
+    }
+
+    /// POSTs each batch as a JSON Lines body to an HTTP endpoint
+    pub struct HttpExportTarget {
+        pub url: String,
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub headers: BTreeMap<String, String>,
+        pub timeout_ms: u64,
+    }
+}
+
+declare_unions! {
+    pub enum OplogExportTarget {
+        JsonLinesFile(JsonLinesFileExportTarget),
+        BlobStorage(BlobStorageExportTarget),
+        ParquetBlobStorage(ParquetBlobStorageExportTarget),
+        Http(HttpExportTarget),
+    }
+}
+
+declare_structs! {
+    pub struct OplogExportSinkDto {
+        pub id: OplogExportSinkId,
+        pub environment_id: EnvironmentId,
+        /// Identifies the sink in the checkpointed delivery positions; a sink created with the
+        /// name of a deleted one continues from where the deleted sink stopped
+        pub name: String,
+        pub revision: OplogExportSinkRevision,
+        pub target: OplogExportTarget,
+    }
+
+    pub struct OplogExportSinkCreation {
+        pub name: String,
+        pub target: OplogExportTarget,
+    }
+
+    pub struct OplogExportSinkUpdate {
+        pub current_revision: OplogExportSinkRevision,
+        pub target: Option<OplogExportTarget>,
+    }
+}
@@ -47,10 +47,16 @@ declare_structs! {
         pub component_id: ComponentId,
         pub component_revision: ComponentRevision
     }
+
+    pub struct InvocationInterceptorPluginSpec {
+        pub component_id: ComponentId,
+        pub component_revision: ComponentRevision
+    }
 }
 
 declare_unions! {
     pub enum PluginSpecDto {
-        OplogProcessor(OplogProcessorPluginSpec)
+        OplogProcessor(OplogProcessorPluginSpec),
+        InvocationInterceptor(InvocationInterceptorPluginSpec)
     }
 }
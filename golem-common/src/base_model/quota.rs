@@ -113,6 +113,7 @@ declare_enums! {
         Terminate
     }
 
+    #[derive(Hash)]
     #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
     pub enum TimePeriod {
         Second,
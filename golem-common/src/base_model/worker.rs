@@ -14,6 +14,7 @@
 
 use crate::base_model::account::AccountId;
 use crate::base_model::component::{AgentFilePermissions, ComponentRevision};
+use crate::base_model::component_metadata::AgentLifecycleAction;
 use crate::base_model::environment::EnvironmentId;
 use crate::base_model::environment_plugin_grant::EnvironmentPluginGrantId;
 use crate::base_model::json::NormalizedJsonValue;
@@ -23,6 +24,7 @@ use crate::base_model::{
     AgentFingerprint, AgentId, AgentResourceDescription, AgentStatus, OplogIndex, Timestamp,
 };
 use crate::{declare_enums, declare_structs, declare_unions};
+use golem_wasm::analysis::AnalysedType;
 use golem_wasm::json::ValueAndTypeJsonExtensions;
 use golem_wasm_derive::{FromValue, IntoValue};
 use std::collections::{HashMap, HashSet};
@@ -32,6 +34,13 @@ declare_enums! {
         Directory,
         File,
     }
+
+    pub enum PromiseState {
+        Pending,
+        Completed,
+        TimedOut,
+        Cancelled,
+    }
 }
 
 declare_unions! {
@@ -107,6 +116,14 @@ declare_structs! {
         pub description: AgentResourceDescription,
     }
 
+    pub struct PromiseMetadata {
+        /// Oplog index identifying the promise within its agent
+        pub oplog_index: OplogIndex,
+        pub state: PromiseState,
+        pub deadline: Option<Timestamp>,
+        pub payload_type: Option<AnalysedType>,
+    }
+
     pub struct AgentMetadataDto {
         pub agent_id: AgentId,
         pub environment_id: EnvironmentId,
@@ -140,7 +157,11 @@ declare_structs! {
         /// agent creation, globally unique across recreations of the same
         /// `AgentId`. Distinguishes a freshly-created agent from a previous,
         /// now-deleted instance that shared the same `AgentId`.
-        pub fingerprint: AgentFingerprint
+        pub fingerprint: AgentFingerprint,
+        /// Promises created by this agent, with their state, deadline and payload type.
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub promises: Vec<PromiseMetadata>
     }
 
     #[derive(IntoValue, FromValue)]
@@ -164,6 +185,15 @@ declare_structs! {
         pub permissions: Option<AgentFilePermissions>, // only for files
         pub size: Option<u64>,                         // only for files
     }
+
+    /// A lifecycle policy action that was applied to an agent
+    pub struct AgentLifecycleAuditEntry {
+        pub timestamp: Timestamp,
+        pub agent_id: AgentId,
+        pub action: AgentLifecycleAction,
+        /// The last oplog index of the agent at the time the action was applied
+        pub last_oplog_index: OplogIndex,
+    }
 }
 
 declare_enums! {
@@ -177,6 +177,7 @@ impl From<super::bindings::golem::agent::common::AgentMethod> for AgentMethod {
             output_schema: DataSchema::from(value.output_schema),
             http_endpoint: value.http_endpoint.into_iter().map(|v| v.into()).collect(),
             read_only: value.read_only.map(ReadOnlyConfig::from),
+            timeout_nanos: value.timeout,
         }
     }
 }
@@ -191,6 +192,7 @@ impl From<AgentMethod> for super::bindings::golem::agent::common::AgentMethod {
             output_schema: value.output_schema.into(),
             http_endpoint: value.http_endpoint.into_iter().map(|v| v.into()).collect(),
             read_only: value.read_only.map(Into::into),
+            timeout: value.timeout_nanos,
         }
     }
 }
@@ -923,6 +925,7 @@ impl From<HttpMethod> for super::bindings::golem::agent::common::HttpMethod {
             HttpMethod::Trace(_) => Self::Trace,
             HttpMethod::Patch(_) => Self::Patch,
             HttpMethod::Custom(c) => Self::Custom(c.value),
+            HttpMethod::WebSocket(_) => Self::Websocket,
         }
     }
 }
@@ -942,6 +945,9 @@ impl From<super::bindings::golem::agent::common::HttpMethod> for HttpMethod {
             super::bindings::golem::agent::common::HttpMethod::Custom(value) => {
                 Self::Custom(CustomHttpMethod { value })
             }
+            super::bindings::golem::agent::common::HttpMethod::Websocket => {
+                Self::WebSocket(Empty {})
+            }
         }
     }
 }
@@ -194,6 +194,7 @@ impl TryFrom<golem_api_grpc::proto::golem::component::AgentMethod> for AgentMeth
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
             read_only: value.read_only.map(TryInto::try_into).transpose()?,
+            timeout_nanos: value.timeout_nanos,
         })
     }
 }
@@ -208,6 +209,7 @@ impl From<AgentMethod> for golem_api_grpc::proto::golem::component::AgentMethod
             output_schema: Some(value.output_schema.into()),
             http_endpoint: value.http_endpoint.into_iter().map(Into::into).collect(),
             read_only: value.read_only.map(Into::into),
+            timeout_nanos: value.timeout_nanos,
         }
     }
 }
@@ -1128,6 +1130,7 @@ impl TryFrom<golem_api_grpc::proto::golem::component::HttpMethod> for HttpMethod
                     StandardHttpMethod::Options => Ok(Self::Options(Empty {})),
                     StandardHttpMethod::Trace => Ok(Self::Trace(Empty {})),
                     StandardHttpMethod::Patch => Ok(Self::Patch(Empty {})),
+                    StandardHttpMethod::Websocket => Ok(Self::WebSocket(Empty {})),
                     StandardHttpMethod::Unspecified => {
                         Err("Unknown http method variant".to_string())
                     }
@@ -1155,6 +1158,7 @@ impl From<HttpMethod> for golem_api_grpc::proto::golem::component::HttpMethod {
                 HttpMethod::Trace(_) => Value::Standard(StandardHttpMethod::Trace.into()),
                 HttpMethod::Patch(_) => Value::Standard(StandardHttpMethod::Patch.into()),
                 HttpMethod::Custom(c) => Value::Custom(c.value),
+                HttpMethod::WebSocket(_) => Value::Standard(StandardHttpMethod::Websocket.into()),
             }),
         }
     }
@@ -0,0 +1,230 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::base_model::agent::AgentMode;
+use crate::model::component::{ComponentName, ComponentRevision};
+use crate::model::oplog::{OplogEntry, OplogIndex, PayloadId};
+use crate::model::worker::TypedAgentConfigEntry;
+use crate::model::{AgentId, Timestamp};
+use crate::serialization::{deserialize, serialize};
+use bytes::{Buf, BytesMut};
+use desert_rust::BinaryCodec;
+
+/// A portable copy of a single agent, produced by exporting it from one environment and
+/// consumed by importing it into another one (possibly on a different cluster).
+///
+/// An archive is a sequence of length prefixed parts, so it can be produced and consumed
+/// without holding the whole agent in memory. The parts always come in this order:
+/// - a single [`AgentArchivePart::Header`]
+/// - every payload the oplog refers to that is stored outside the oplog itself
+/// - the agent's filesystem checkpoint, split into chunks, if its latest snapshot has one
+/// - the full oplog, starting with its `Create` entry, in batches
+/// - a single [`AgentArchivePart::End`], so a truncated archive is never mistaken for a
+///   complete one
+///
+/// Key-value buckets and blob store containers are not part of the archive, as they belong to
+/// the environment and are shared by its agents. Agents that used them can't be exported.
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub enum AgentArchivePart {
+    Header(AgentArchiveHeader),
+    Payload(AgentArchivePayload),
+    FilesystemCheckpoint(AgentArchiveFilesystemChunk),
+    Entries(Vec<OplogEntry>),
+    End,
+}
+
+impl AgentArchivePart {
+    /// Upper limit for the size of a single encoded part, protecting importers from allocating
+    /// arbitrary amounts of memory for a malformed length prefix
+    pub const MAX_SIZE: usize = 256 * 1024 * 1024;
+
+    /// Serializes the part, prefixed with its length
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        let data = serialize(self)?;
+        if data.len() > Self::MAX_SIZE {
+            return Err(format!(
+                "agent archive part of {} bytes exceeds the limit of {} bytes",
+                data.len(),
+                Self::MAX_SIZE
+            ));
+        }
+        let mut encoded = Vec::with_capacity(4 + data.len());
+        encoded.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        encoded.extend_from_slice(&data);
+        Ok(encoded)
+    }
+}
+
+/// Metadata of the exported agent
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct AgentArchiveHeader {
+    pub format_version: u16,
+    /// The ID of the agent in the environment it was exported from
+    pub agent_id: AgentId,
+    pub agent_mode: AgentMode,
+    pub component_name: ComponentName,
+    /// The component revision the agent was running at the time of the export
+    pub component_revision: ComponentRevision,
+    /// All the component revisions the oplog refers to, used for validating the target
+    /// component on import
+    pub component_revisions: Vec<AgentArchiveComponentRevision>,
+    pub env: Vec<(String, String)>,
+    pub config: Vec<TypedAgentConfigEntry>,
+    pub exported_at: Timestamp,
+}
+
+impl AgentArchiveHeader {
+    pub const FORMAT_VERSION: u16 = 2;
+}
+
+/// A component revision referenced by an exported oplog
+#[derive(Clone, Debug, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub struct AgentArchiveComponentRevision {
+    pub revision: ComponentRevision,
+    /// Hex encoded hash of the original WASM of the component revision
+    pub wasm_hash: String,
+}
+
+/// An oplog payload that was stored outside the oplog of the exported agent
+#[derive(Clone, Debug, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub struct AgentArchivePayload {
+    pub payload_id: PayloadId,
+    pub md5_hash: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// A chunk of the compressed filesystem checkpoint belonging to the snapshot entry at
+/// `snapshot_index`
+#[derive(Clone, Debug, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub struct AgentArchiveFilesystemChunk {
+    pub snapshot_index: OplogIndex,
+    pub data: Vec<u8>,
+}
+
+/// Splits the bytes of an agent archive, received in arbitrary chunks, back into its parts
+#[derive(Debug, Default)]
+pub struct AgentArchiveDecoder {
+    buffer: BytesMut,
+}
+
+impl AgentArchiveDecoder {
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete part, or `None` if more bytes are needed
+    pub fn next_part(&mut self) -> Result<Option<AgentArchivePart>, String> {
+        if self.buffer.len() < 4 {
+            return Ok(None);
+        }
+        let length = u32::from_be_bytes(
+            self.buffer[..4]
+                .try_into()
+                .expect("length prefix is 4 bytes"),
+        ) as usize;
+        if length > AgentArchivePart::MAX_SIZE {
+            return Err(format!(
+                "agent archive part of {length} bytes exceeds the limit of {} bytes",
+                AgentArchivePart::MAX_SIZE
+            ));
+        }
+        if self.buffer.len() < 4 + length {
+            return Ok(None);
+        }
+        self.buffer.advance(4);
+        let data = self.buffer.split_to(length);
+        deserialize(&data)
+            .map(Some)
+            .map_err(|err| format!("invalid agent archive part: {err}"))
+    }
+
+    /// Checks that the archive did not end in the middle of a part
+    pub fn finish(&self) -> Result<(), String> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            Err("agent archive is truncated".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn parts() -> Vec<AgentArchivePart> {
+        vec![
+            AgentArchivePart::Payload(AgentArchivePayload {
+                payload_id: PayloadId(Uuid::new_v4()),
+                md5_hash: vec![1, 2, 3],
+                data: vec![42; 1000],
+            }),
+            AgentArchivePart::FilesystemCheckpoint(AgentArchiveFilesystemChunk {
+                snapshot_index: OplogIndex::from_u64(12),
+                data: vec![7; 300],
+            }),
+            AgentArchivePart::Entries(vec![OplogEntry::NoOp {
+                timestamp: Timestamp::now_utc(),
+            }]),
+            AgentArchivePart::End,
+        ]
+    }
+
+    #[test]
+    fn parts_are_decoded_from_arbitrary_chunks() {
+        let parts = parts();
+        let encoded: Vec<u8> = parts
+            .iter()
+            .flat_map(|part| part.encode().unwrap())
+            .collect();
+
+        let mut decoder = AgentArchiveDecoder::default();
+        let mut decoded = Vec::new();
+        for chunk in encoded.chunks(7) {
+            decoder.push(chunk);
+            while let Some(part) = decoder.next_part().unwrap() {
+                decoded.push(part);
+            }
+        }
+
+        assert_eq!(decoded, parts);
+        assert!(decoder.finish().is_ok());
+    }
+
+    #[test]
+    fn truncated_archive_is_rejected() {
+        let encoded = parts()[0].encode().unwrap();
+
+        let mut decoder = AgentArchiveDecoder::default();
+        decoder.push(&encoded[..encoded.len() - 1]);
+
+        assert_eq!(decoder.next_part(), Ok(None));
+        assert!(decoder.finish().is_err());
+    }
+
+    #[test]
+    fn oversized_part_is_rejected() {
+        let mut decoder = AgentArchiveDecoder::default();
+        decoder.push(&u32::MAX.to_be_bytes());
+
+        assert!(decoder.next_part().is_err());
+    }
+}
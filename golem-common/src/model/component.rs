@@ -78,6 +78,7 @@ impl ComponentDto {
                                     )
                                 })
                                 .collect(),
+                            lifecycle: config.lifecycle.clone(),
                         };
                     Ok((name.0.clone(), state.into()))
                 })
@@ -137,6 +138,12 @@ mod protobuf {
                 oplog_processor_component_revision: value
                     .oplog_processor_component_revision
                     .map(|v| v.0),
+                invocation_interceptor_component_id: value
+                    .invocation_interceptor_component_id
+                    .map(|v| v.into()),
+                invocation_interceptor_component_revision: value
+                    .invocation_interceptor_component_revision
+                    .map(|v| v.0),
             }
         }
     }
@@ -168,6 +175,13 @@ mod protobuf {
                 oplog_processor_component_revision: value
                     .oplog_processor_component_revision
                     .map(ComponentRevision),
+                invocation_interceptor_component_id: value
+                    .invocation_interceptor_component_id
+                    .map(|v| v.try_into())
+                    .transpose()?,
+                invocation_interceptor_component_revision: value
+                    .invocation_interceptor_component_revision
+                    .map(ComponentRevision),
             })
         }
     }
@@ -179,6 +179,14 @@ impl ComponentMetadata {
             .map(|config| config.plugins.as_slice())
     }
 
+    pub fn agent_type_lifecycle_policy(
+        &self,
+        name: &AgentTypeName,
+    ) -> Option<&AgentLifecyclePolicy> {
+        self.agent_type_provision_config(name)
+            .and_then(|config| config.lifecycle.as_ref())
+    }
+
     pub fn is_agent(&self) -> bool {
         !self.data.agent_types.is_empty()
     }
@@ -199,6 +207,13 @@ impl ComponentMetadata {
         self.data.known_exports.oplog_processor_interface.is_some()
     }
 
+    pub fn has_invocation_interceptor(&self) -> bool {
+        self.data
+            .known_exports
+            .invocation_interceptor_interface
+            .is_some()
+    }
+
     /// Returns the fully-qualified WIT function name for `golem:api/load-snapshot.load`
     pub fn load_snapshot_function_name(&self) -> Option<String> {
         self.data
@@ -244,6 +259,24 @@ impl ComponentMetadata {
             .map(|iface| format!("{iface}.{{process}}"))
     }
 
+    /// Returns the fully-qualified WIT function name for `golem:agent/interceptor.before-invocation`
+    pub fn invocation_interceptor_before_function_name(&self) -> Option<String> {
+        self.data
+            .known_exports
+            .invocation_interceptor_interface
+            .as_ref()
+            .map(|iface| format!("{iface}.{{before-invocation}}"))
+    }
+
+    /// Returns the fully-qualified WIT function name for `golem:agent/interceptor.after-invocation`
+    pub fn invocation_interceptor_after_function_name(&self) -> Option<String> {
+        self.data
+            .known_exports
+            .invocation_interceptor_interface
+            .as_ref()
+            .map(|iface| format!("{iface}.{{after-invocation}}"))
+    }
+
     pub fn find_agent_type_by_name(&self, agent_type: &AgentTypeName) -> Option<AgentType> {
         self.data
             .agent_types
@@ -361,6 +394,7 @@ const AGENT_GUEST_PREFIX: &str = "golem:agent/guest";
 const SAVE_SNAPSHOT_PREFIX: &str = "golem:api/save-snapshot";
 const LOAD_SNAPSHOT_PREFIX: &str = "golem:api/load-snapshot";
 const OPLOG_PROCESSOR_PREFIX: &str = "golem:api/oplog-processor";
+const INVOCATION_INTERCEPTOR_PREFIX: &str = "golem:agent/interceptor";
 
 fn record_known_export(
     slot: &mut Option<String>,
@@ -405,6 +439,14 @@ pub fn extract_known_exports(exports: &[AnalysedExport]) -> AnalysisResult<Known
                     "oplog processor",
                     name,
                 )?;
+            } else if name == INVOCATION_INTERCEPTOR_PREFIX
+                || name.starts_with(&format!("{INVOCATION_INTERCEPTOR_PREFIX}@"))
+            {
+                record_known_export(
+                    &mut known.invocation_interceptor_interface,
+                    "invocation interceptor",
+                    name,
+                )?;
             }
         }
     }
@@ -604,7 +646,9 @@ impl Display for ComponentProcessingError {
 }
 
 mod protobuf {
-    use crate::base_model::component_metadata::{AgentTypeProvisionConfig, KnownExports};
+    use crate::base_model::component_metadata::{
+        AgentLifecycleAction, AgentLifecyclePolicy, AgentTypeProvisionConfig, KnownExports,
+    };
     use crate::model::agent::AgentTypeName;
     use crate::model::component_metadata::{
         ComponentMetadata, ComponentMetadataInnerData, LinearMemory, ProducerField, Producers,
@@ -746,6 +790,7 @@ mod protobuf {
                 save_snapshot_interface: value.save_snapshot_interface,
                 load_snapshot_interface: value.load_snapshot_interface,
                 oplog_processor_interface: value.oplog_processor_interface,
+                invocation_interceptor_interface: value.invocation_interceptor_interface,
             }
         }
     }
@@ -757,6 +802,7 @@ mod protobuf {
                 save_snapshot_interface: value.save_snapshot_interface,
                 load_snapshot_interface: value.load_snapshot_interface,
                 oplog_processor_interface: value.oplog_processor_interface,
+                invocation_interceptor_interface: value.invocation_interceptor_interface,
             }
         }
     }
@@ -836,10 +882,53 @@ mod protobuf {
                 config,
                 plugins,
                 files,
+                lifecycle: proto.lifecycle.map(AgentLifecyclePolicy::from),
             })
         }
     }
 
+    impl From<golem_api_grpc::proto::golem::component::AgentLifecyclePolicy> for AgentLifecyclePolicy {
+        fn from(proto: golem_api_grpc::proto::golem::component::AgentLifecyclePolicy) -> Self {
+            Self {
+                delete_after_idle_nanos: proto.delete_after_idle_nanos,
+                delete_after_exit_nanos: proto.delete_after_exit_nanos,
+                archive_after_idle_nanos: proto.archive_after_idle_nanos,
+            }
+        }
+    }
+
+    impl From<AgentLifecyclePolicy> for golem_api_grpc::proto::golem::component::AgentLifecyclePolicy {
+        fn from(policy: AgentLifecyclePolicy) -> Self {
+            Self {
+                delete_after_idle_nanos: policy.delete_after_idle_nanos,
+                delete_after_exit_nanos: policy.delete_after_exit_nanos,
+                archive_after_idle_nanos: policy.archive_after_idle_nanos,
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::component::AgentLifecycleAction> for AgentLifecycleAction {
+        fn from(proto: golem_api_grpc::proto::golem::component::AgentLifecycleAction) -> Self {
+            use golem_api_grpc::proto::golem::component::AgentLifecycleAction as Proto;
+
+            match proto {
+                Proto::DeleteAfterIdle => Self::DeleteAfterIdle,
+                Proto::DeleteAfterExit => Self::DeleteAfterExit,
+                Proto::ArchiveAfterIdle => Self::ArchiveAfterIdle,
+            }
+        }
+    }
+
+    impl From<AgentLifecycleAction> for golem_api_grpc::proto::golem::component::AgentLifecycleAction {
+        fn from(action: AgentLifecycleAction) -> Self {
+            match action {
+                AgentLifecycleAction::DeleteAfterIdle => Self::DeleteAfterIdle,
+                AgentLifecycleAction::DeleteAfterExit => Self::DeleteAfterExit,
+                AgentLifecycleAction::ArchiveAfterIdle => Self::ArchiveAfterIdle,
+            }
+        }
+    }
+
     impl From<AgentTypeProvisionConfig>
         for golem_api_grpc::proto::golem::component::AgentTypeProvisionConfig
     {
@@ -867,6 +956,7 @@ mod protobuf {
                         golem_api_grpc::proto::golem::component::InitialAgentFile::from(f)
                     })
                     .collect(),
+                lifecycle: config.lifecycle.map(Into::into),
             }
         }
     }
@@ -893,6 +983,7 @@ mod tests {
             instance_export("golem:api/save-snapshot@1.5.0"),
             instance_export("golem:api/load-snapshot@1.5.0"),
             instance_export("golem:api/oplog-processor@1.5.0"),
+            instance_export("golem:agent/interceptor@1.5.0"),
         ];
 
         let known = extract_known_exports(&exports).unwrap();
@@ -904,6 +995,7 @@ mod tests {
                 save_snapshot_interface: Some("golem:api/save-snapshot@1.5.0".to_string()),
                 load_snapshot_interface: Some("golem:api/load-snapshot@1.5.0".to_string()),
                 oplog_processor_interface: Some("golem:api/oplog-processor@1.5.0".to_string()),
+                invocation_interceptor_interface: Some("golem:agent/interceptor@1.5.0".to_string()),
             }
         );
     }
@@ -930,6 +1022,7 @@ mod tests {
                 save_snapshot_interface: Some("golem:api/save-snapshot@1.5.0".to_string()),
                 load_snapshot_interface: Some("golem:api/load-snapshot@1.5.0".to_string()),
                 oplog_processor_interface: Some("golem:api/oplog-processor@1.5.0".to_string()),
+                invocation_interceptor_interface: Some("golem:agent/interceptor@1.5.0".to_string()),
             },
             vec![],
             None,
@@ -958,5 +1051,13 @@ mod tests {
             metadata.oplog_processor_function_name(),
             Some("golem:api/oplog-processor@1.5.0.{process}".to_string())
         );
+        assert_eq!(
+            metadata.invocation_interceptor_before_function_name(),
+            Some("golem:agent/interceptor@1.5.0.{before-invocation}".to_string())
+        );
+        assert_eq!(
+            metadata.invocation_interceptor_after_function_name(),
+            Some("golem:agent/interceptor@1.5.0.{after-invocation}".to_string())
+        );
     }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::base_model::component_metadata::AgentLifecyclePolicy;
 use crate::base_model::json::NormalizedJsonValue;
 use crate::model::component::AgentFilePermissions;
 use crate::model::diff::DiffError;
@@ -73,6 +74,8 @@ pub struct AgentTypeProvisionConfig {
     pub files_by_path: BTreeMap<String, HashOf<AgentFile>>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub plugins_by_grant_id: BTreeMap<Uuid, PluginInstallation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<AgentLifecyclePolicy>,
 }
 
 impl Hashable for AgentTypeProvisionConfig {
@@ -92,6 +95,8 @@ pub struct AgentTypeProvisionConfigDiff {
     pub plugin_changes: BTreeMapDiff<Uuid, PluginInstallation>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub config_changes: BTreeMapDiff<String, NormalizedJsonValue>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub lifecycle_changed: bool,
 }
 
 impl Diffable for AgentTypeProvisionConfig {
@@ -111,18 +116,21 @@ impl Diffable for AgentTypeProvisionConfig {
             .config
             .diff_with_current(&current.config)?
             .unwrap_or_default();
+        let lifecycle_changed = new.lifecycle != current.lifecycle;
 
         Ok(
             if !env_changes.is_empty()
                 || !file_changes.is_empty()
                 || !plugin_changes.is_empty()
                 || !config_changes.is_empty()
+                || lifecycle_changed
             {
                 Some(AgentTypeProvisionConfigDiff {
                     env_changes,
                     file_changes,
                     plugin_changes,
                     config_changes,
+                    lifecycle_changed,
                 })
             } else {
                 None
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::BTreeMapDiff;
+use crate::base_model::http_api_deployment::HttpApiRateLimit;
 use crate::model::diff::{DiffError, Diffable, Hash, Hashable, hash_from_serialized_value};
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -22,6 +23,10 @@ use std::collections::BTreeMap;
 pub struct HttpApiDeploymentAgentOptions {
     pub security_scheme: Option<String>,
     pub test_session_header: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits: Vec<HttpApiRateLimit>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub method_rate_limits: BTreeMap<String, Vec<HttpApiRateLimit>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -29,6 +34,7 @@ pub struct HttpApiDeploymentAgentOptions {
 pub struct HttpApiDeploymentAgentOptionsDiff {
     pub security_scheme_changed: bool,
     pub test_session_header_changed: bool,
+    pub rate_limits_changed: bool,
 }
 
 impl Diffable for HttpApiDeploymentAgentOptions {
@@ -37,15 +43,20 @@ impl Diffable for HttpApiDeploymentAgentOptions {
     fn diff(new: &Self, current: &Self) -> Result<Option<Self::DiffResult>, DiffError> {
         let security_scheme_changed = new.security_scheme != current.security_scheme;
         let test_session_header_changed = new.test_session_header != current.test_session_header;
+        let rate_limits_changed = new.rate_limits != current.rate_limits
+            || new.method_rate_limits != current.method_rate_limits;
 
-        Ok(if security_scheme_changed || test_session_header_changed {
-            Some(HttpApiDeploymentAgentOptionsDiff {
-                security_scheme_changed,
-                test_session_header_changed,
-            })
-        } else {
-            None
-        })
+        Ok(
+            if security_scheme_changed || test_session_header_changed || rate_limits_changed {
+                Some(HttpApiDeploymentAgentOptionsDiff {
+                    security_scheme_changed,
+                    test_session_header_changed,
+                    rate_limits_changed,
+                })
+            } else {
+                None
+            },
+        )
     }
 }
 
@@ -55,6 +66,10 @@ pub struct HttpApiDeployment {
     pub webhooks_prefix: String,
     pub openapi_endpoint_prefix: String,
     pub agents: BTreeMap<String, HttpApiDeploymentAgentOptions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits: Vec<HttpApiRateLimit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graphql_endpoint: Option<String>,
 }
 
 impl Hashable for HttpApiDeployment {
@@ -68,6 +83,8 @@ impl Hashable for HttpApiDeployment {
 pub struct HttpApiDeploymentDiff {
     pub webhooks_url_changed: bool,
     pub openapi_endpoint_changed: bool,
+    pub rate_limits_changed: bool,
+    pub graphql_endpoint_changed: bool,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub agents_changes: BTreeMapDiff<String, HttpApiDeploymentAgentOptions>,
 }
@@ -79,15 +96,24 @@ impl Diffable for HttpApiDeployment {
         let webhooks_url_changed = new.webhooks_prefix != current.webhooks_prefix;
         let openapi_endpoint_changed =
             new.openapi_endpoint_prefix != current.openapi_endpoint_prefix;
+        let rate_limits_changed = new.rate_limits != current.rate_limits;
+        let graphql_endpoint_changed = new.graphql_endpoint != current.graphql_endpoint;
         let agents_changes = new
             .agents
             .diff_with_current(&current.agents)?
             .unwrap_or_default();
         Ok(
-            if webhooks_url_changed || openapi_endpoint_changed || !agents_changes.is_empty() {
+            if webhooks_url_changed
+                || openapi_endpoint_changed
+                || rate_limits_changed
+                || graphql_endpoint_changed
+                || !agents_changes.is_empty()
+            {
                 Some(Self::DiffResult {
                     webhooks_url_changed,
                     openapi_endpoint_changed,
+                    rate_limits_changed,
+                    graphql_endpoint_changed,
                     agents_changes,
                 })
             } else {
@@ -33,6 +33,8 @@ impl HttpApiDeploymentAgentOptions {
         diff::HttpApiDeploymentAgentOptions {
             security_scheme,
             test_session_header,
+            rate_limits: self.rate_limits.clone(),
+            method_rate_limits: self.method_rate_limits.clone(),
         }
     }
 }
@@ -47,6 +49,8 @@ impl HttpApiDeployment {
                 .iter()
                 .map(|(k, v)| (k.0.clone(), v.to_diffable()))
                 .collect(),
+            rate_limits: self.rate_limits.clone(),
+            graphql_endpoint: self.graphql_endpoint.clone(),
         }
     }
 }
@@ -23,11 +23,23 @@ use lazy_static::lazy_static;
 use nonempty_collections::NEVec;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
 pub use crate::base_model::invocation_context::*;
 use crate::base_model::oplog::PublicAttributeValue;
 
+/// Span attribute holding an absolute invocation deadline as an ISO-8601 timestamp.
+///
+/// Set by callers (API, CLI, wasm-rpc) and by the executor for methods with a timeout. Because
+/// spans are propagated across agent-to-agent RPC, nested invocations inherit every deadline set
+/// up the chain; the earliest one applies (see [`InvocationContextSpan::effective_deadline`]).
+pub const DEADLINE_ATTRIBUTE: &str = "deadline";
+
+/// Span attribute holding the id of the WebSocket connection whose incoming message triggered the
+/// invocation. Set by the http api gateway when invoking the agent method of a WebSocket endpoint.
+pub const WEBSOCKET_CONNECTION_ID_ATTRIBUTE: &str = "websocket.connection_id";
+
 impl TraceId {
     pub fn from_attribute_value(value: AttributeValue) -> Result<Self, String> {
         match value {
@@ -448,6 +460,18 @@ impl InvocationContextSpan {
         }
     }
 
+    /// Returns the earliest [`DEADLINE_ATTRIBUTE`] value found on this span, its linked contexts
+    /// and its ancestors. Values that are not valid timestamps are ignored.
+    pub fn effective_deadline(self: &Arc<Self>) -> Option<Timestamp> {
+        self.get_attribute_chain(DEADLINE_ATTRIBUTE)?
+            .into_iter()
+            .filter_map(|value| {
+                let AttributeValue::String(value) = value;
+                Timestamp::from_str(&value).ok()
+            })
+            .min()
+    }
+
     pub fn get_attributes(self: &Arc<Self>, inherit: bool) -> HashMap<String, Vec<AttributeValue>> {
         let mut current = self.clone();
         let mut result = HashMap::new();
@@ -1141,7 +1165,8 @@ mod protobuf {
 mod tests {
     use crate::model::Timestamp;
     use crate::model::invocation_context::{
-        AttributeValue, InvocationContextSpan, InvocationContextStack, SpanId, TraceId,
+        AttributeValue, DEADLINE_ATTRIBUTE, InvocationContextSpan, InvocationContextStack, SpanId,
+        TraceId,
     };
     use crate::serialization::{deserialize, serialize};
     use std::collections::HashSet;
@@ -1278,6 +1303,31 @@ mod tests {
         assert!(limited.spans[0].parent().is_none());
     }
 
+    #[test]
+    fn effective_deadline_is_earliest_in_chain() {
+        let stack = example_stack_1();
+        let span6 = stack.spans.first().clone();
+        assert_eq!(span6.effective_deadline(), None);
+
+        let early = Timestamp::from(1724701940000);
+        let late = Timestamp::from(1724701950000);
+        stack.spans[2].set_attribute(
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(early.to_string()),
+        );
+        span6.set_attribute(
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(late.to_string()),
+        );
+        assert_eq!(span6.effective_deadline(), Some(early));
+
+        span6.set_attribute(
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String("not-a-timestamp".to_string()),
+        );
+        assert_eq!(span6.effective_deadline(), Some(early));
+    }
+
     #[test]
     fn binary_serialization() {
         let stack = example_stack_1();
@@ -14,6 +14,7 @@
 
 pub mod account;
 pub mod agent;
+pub mod agent_archive;
 pub mod application;
 pub mod auth;
 pub mod base64;
@@ -38,6 +39,7 @@ pub mod permission_share;
 pub mod plan;
 pub mod plugin_registration;
 pub mod poem;
+pub mod promise;
 pub mod protobuf;
 pub mod quota;
 pub mod regions;
@@ -46,6 +48,7 @@ pub mod retry_policy;
 pub mod security_scheme;
 #[cfg(test)]
 mod tests;
+pub mod topic;
 pub mod worker;
 
 pub use crate::base_model::*;
@@ -57,7 +60,9 @@ pub use retry_policy::{
 
 use self::component::ComponentId;
 use self::component::{AgentFilePermissions, ComponentRevision};
+use self::component_metadata::AgentLifecycleAction;
 use self::environment::EnvironmentId;
+use self::topic::TopicFanOut;
 use self::worker::TypedAgentConfigEntry;
 use crate::base_model::agent::AgentMode;
 use crate::base_model::agent::LegacyParsedAgentId;
@@ -362,6 +367,34 @@ pub enum ScheduledAction {
         agent_created_by: AccountId,
         owned_agent_id: OwnedAgentId,
     },
+    /// Applies an action of the agent type's lifecycle policy, if the last oplog index did not
+    /// change since the action was scheduled.
+    ApplyLifecyclePolicy {
+        account_id: AccountId,
+        owned_agent_id: OwnedAgentId,
+        agent_mode: AgentMode,
+        last_oplog_index: OplogIndex,
+        action: AgentLifecycleAction,
+    },
+    /// Fails a given promise with a timeout, if it was not completed by its deadline
+    TimeOutPromise {
+        account_id: AccountId,
+        environment_id: EnvironmentId,
+        promise_id: PromiseId,
+    },
+    /// Delivers the topic messages queued for one subscribed agent, oldest first, by enqueueing
+    /// the invocations of the subscribed method. Stops at the first message of a publisher that
+    /// could not be delivered, and drops the queue if the agent no longer exists.
+    DeliverTopicMessage {
+        account_id: AccountId,
+        owned_agent_id: OwnedAgentId,
+    },
+    /// Queues the oldest pending fan-out of the subscription for every agent of its next page of
+    /// the agents of the subscribed agent type, then schedules the fan-out of what remains.
+    FanOutTopicMessage {
+        account_id: AccountId,
+        fan_out: Box<TopicFanOut>,
+    },
 }
 
 impl ScheduledAction {
@@ -375,6 +408,14 @@ impl ScheduledAction {
             ScheduledAction::ArchiveOplog { owned_agent_id, .. } => owned_agent_id.clone(),
             ScheduledAction::Invoke { owned_agent_id, .. } => owned_agent_id.clone(),
             ScheduledAction::Resume { owned_agent_id, .. } => owned_agent_id.clone(),
+            ScheduledAction::ApplyLifecyclePolicy { owned_agent_id, .. } => owned_agent_id.clone(),
+            ScheduledAction::TimeOutPromise {
+                environment_id,
+                promise_id,
+                ..
+            } => OwnedAgentId::new(*environment_id, &promise_id.agent_id),
+            ScheduledAction::DeliverTopicMessage { owned_agent_id, .. } => owned_agent_id.clone(),
+            ScheduledAction::FanOutTopicMessage { fan_out, .. } => fan_out.publisher.clone(),
         }
     }
 }
@@ -390,6 +431,24 @@ impl Display for ScheduledAction {
             }
             ScheduledAction::Invoke { owned_agent_id, .. } => write!(f, "invoke[{owned_agent_id}]"),
             ScheduledAction::Resume { owned_agent_id, .. } => write!(f, "resume[{owned_agent_id}]"),
+            ScheduledAction::ApplyLifecyclePolicy {
+                owned_agent_id,
+                action,
+                ..
+            } => write!(f, "lifecycle[{action}][{owned_agent_id}]"),
+            ScheduledAction::TimeOutPromise { promise_id, .. } => {
+                write!(f, "timeout[{promise_id}]")
+            }
+            ScheduledAction::DeliverTopicMessage { owned_agent_id, .. } => {
+                write!(f, "deliver[{owned_agent_id}]")
+            }
+            ScheduledAction::FanOutTopicMessage { fan_out, .. } => {
+                write!(
+                    f,
+                    "fan-out[{}][{}]",
+                    fan_out.topic, fan_out.subscription.subscriber
+                )
+            }
         }
     }
 }
@@ -833,6 +892,7 @@ pub enum AgentInvocationKind {
     LoadSnapshot,
     SaveSnapshot,
     ProcessOplogEntries,
+    InterceptInvocation,
 }
 
 #[derive(Clone, Debug, PartialEq, BinaryCodec)]
@@ -870,6 +930,15 @@ pub enum AgentInvocation {
         first_entry_index: OplogIndex,
         entries: Vec<OplogEntry>,
     },
+    /// Invokes an invocation interceptor plugin. `output` is `None` when intercepting the
+    /// start of the invocation and contains the method's output when intercepting its end.
+    InterceptInvocation {
+        idempotency_key: IdempotencyKey,
+        account_id: AccountId,
+        config: Vec<(String, String)>,
+        intercepted: InterceptedInvocation,
+        output: Option<UntypedDataValue>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, BinaryCodec)]
@@ -898,6 +967,12 @@ pub enum AgentInvocationPayload {
         first_entry_index: OplogIndex,
         entries: Vec<crate::model::oplog::OplogEntry>,
     },
+    InterceptInvocation {
+        account_id: AccountId,
+        config: Vec<(String, String)>,
+        intercepted: InterceptedInvocation,
+        output: Option<UntypedDataValue>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, BinaryCodec)]
@@ -909,6 +984,35 @@ pub enum AgentInvocationResult {
     LoadSnapshot { error: Option<String> },
     SaveSnapshot { snapshot: RawSnapshotData },
     ProcessOplogEntries { error: Option<String> },
+    InterceptInvocation { outcome: InterceptionOutcome },
+}
+
+/// An agent method invocation as seen by invocation interceptor plugins
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct InterceptedInvocation {
+    pub agent_id: AgentId,
+    pub method_name: String,
+    pub input: UntypedDataValue,
+    pub principal: Principal,
+    pub trace_id: String,
+    pub context_attributes: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub enum Interception {
+    Proceed,
+    Replace(UntypedDataValue),
+    Reject(String),
+}
+
+/// Decoded from the `interception-result` record returned by `golem:agent/interceptor`
+#[derive(Clone, Debug, PartialEq, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub struct InterceptionOutcome {
+    pub interception: Interception,
+    pub span_attributes: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -1026,6 +1130,18 @@ impl AgentInvocationResult {
                 AgentInvocationResult::ProcessOplogEntries { error: a },
                 AgentInvocationResult::ProcessOplogEntries { error: b },
             ) => a == b,
+            (
+                AgentInvocationResult::InterceptInvocation { outcome: a },
+                AgentInvocationResult::InterceptInvocation { outcome: b },
+            ) => {
+                a.span_attributes == b.span_attributes
+                    && match (&a.interception, &b.interception) {
+                        (Interception::Replace(a), Interception::Replace(b)) => {
+                            untyped_data_replay_equivalent(a, b)
+                        }
+                        (a, b) => a == b,
+                    }
+            }
             _ => false,
         }
     }
@@ -1079,6 +1195,18 @@ impl AgentInvocation {
                 first_entry_index,
                 entries,
             },
+            AgentInvocationPayload::InterceptInvocation {
+                account_id,
+                config,
+                intercepted,
+                output,
+            } => Self::InterceptInvocation {
+                idempotency_key,
+                account_id,
+                config,
+                intercepted,
+                output,
+            },
         }
     }
 
@@ -1151,6 +1279,22 @@ impl AgentInvocation {
                 },
                 InvocationContextStack::fresh(),
             ),
+            Self::InterceptInvocation {
+                idempotency_key,
+                account_id,
+                config,
+                intercepted,
+                output,
+            } => (
+                idempotency_key,
+                AgentInvocationPayload::InterceptInvocation {
+                    account_id,
+                    config,
+                    intercepted,
+                    output,
+                },
+                InvocationContextStack::fresh(),
+            ),
         }
     }
 
@@ -1169,6 +1313,9 @@ impl AgentInvocation {
             Self::ProcessOplogEntries {
                 idempotency_key, ..
             } => Some(idempotency_key),
+            Self::InterceptInvocation {
+                idempotency_key, ..
+            } => Some(idempotency_key),
             Self::SaveSnapshot { idempotency_key } => Some(idempotency_key),
             Self::LoadSnapshot {
                 idempotency_key, ..
@@ -1197,6 +1344,7 @@ impl AgentInvocation {
             Self::LoadSnapshot { .. } => AgentInvocationKind::LoadSnapshot,
             Self::SaveSnapshot { .. } => AgentInvocationKind::SaveSnapshot,
             Self::ProcessOplogEntries { .. } => AgentInvocationKind::ProcessOplogEntries,
+            Self::InterceptInvocation { .. } => AgentInvocationKind::InterceptInvocation,
         }
     }
 
@@ -1208,6 +1356,7 @@ impl AgentInvocation {
             Self::LoadSnapshot { .. } => "load-snapshot".to_string(),
             Self::SaveSnapshot { .. } => "save-snapshot".to_string(),
             Self::ProcessOplogEntries { .. } => "process-oplog-entries".to_string(),
+            Self::InterceptInvocation { .. } => "intercept-invocation".to_string(),
         }
     }
 }
@@ -1301,6 +1450,12 @@ impl Display for LogLevel {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebSocketMessage {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AgentEvent {
     StdOut {
@@ -1332,6 +1487,19 @@ pub enum AgentEvent {
         plugin_name: String,
         message: String,
     },
+    /// A message sent by the agent to the client of a WebSocket connection
+    WebSocketSend {
+        timestamp: Timestamp,
+        connection_id: String,
+        message: WebSocketMessage,
+    },
+    /// The agent requested closing a WebSocket connection
+    WebSocketClose {
+        timestamp: Timestamp,
+        connection_id: String,
+        code: Option<u16>,
+        reason: Option<String>,
+    },
     /// The client fell behind and the point it left of is no longer in our buffer.
     /// {number_of_skipped_messages} is the number of messages between the client left of and the point it is now at.
     ClientLagged { number_of_missed_messages: u64 },
@@ -1383,6 +1551,35 @@ impl Display for AgentEvent {
             } => {
                 write!(f, "<plugin-error> [{plugin_name}] {message}")
             }
+            AgentEvent::WebSocketSend {
+                connection_id,
+                message,
+                ..
+            } => match message {
+                WebSocketMessage::Text(text) => {
+                    write!(f, "<websocket-send> {connection_id} {text}")
+                }
+                WebSocketMessage::Binary(bytes) => {
+                    write!(
+                        f,
+                        "<websocket-send> {connection_id} <{} bytes>",
+                        bytes.len()
+                    )
+                }
+            },
+            AgentEvent::WebSocketClose {
+                connection_id,
+                code,
+                reason,
+                ..
+            } => {
+                write!(
+                    f,
+                    "<websocket-close> {connection_id} {} {}",
+                    code.map(|c| c.to_string()).unwrap_or_default(),
+                    reason.as_deref().unwrap_or_default()
+                )
+            }
             AgentEvent::ClientLagged {
                 number_of_missed_messages,
             } => {
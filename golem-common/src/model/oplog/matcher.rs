@@ -177,6 +177,21 @@ impl PublicOplogEntry {
                             query_path,
                             query,
                         ),
+                        PublicAgentInvocation::InterceptInvocation(inv_params) => {
+                            Self::string_match("intercept-invocation", &[], query_path, query)
+                                || Self::string_match(
+                                    &inv_params.method_name,
+                                    &[],
+                                    query_path,
+                                    query,
+                                )
+                                || Self::string_match(
+                                    &inv_params.idempotency_key.value,
+                                    &[],
+                                    query_path,
+                                    query,
+                                )
+                        }
                     }
             }
             PublicOplogEntry::AgentInvocationFinished(params) => {
@@ -254,6 +269,16 @@ impl PublicOplogEntry {
                             query_path,
                             query,
                         ),
+                        PublicAgentInvocation::InterceptInvocation(params) => {
+                            Self::string_match("intercept-invocation", &[], query_path, query)
+                                || Self::string_match(&params.method_name, &[], query_path, query)
+                                || Self::string_match(
+                                    &params.idempotency_key.value,
+                                    &[],
+                                    query_path,
+                                    query,
+                                )
+                        }
                     }
             }
             PublicOplogEntry::PendingUpdate(params) => {
@@ -415,6 +440,17 @@ impl PublicOplogEntry {
                 Self::string_match("removeretrypolicy", &[], query_path, query)
                     || Self::string_match("remove-retry-policy", &[], query_path, query)
             }
+            PublicOplogEntry::WebSocketConnected(params) => {
+                Self::string_match("websocketconnected", &[], query_path, query)
+                    || Self::string_match("websocket-connected", &[], query_path, query)
+                    || Self::string_match(&params.connection_id, &[], query_path, query)
+                    || Self::string_match(&params.method_name, &[], query_path, query)
+            }
+            PublicOplogEntry::WebSocketDisconnected(params) => {
+                Self::string_match("websocketdisconnected", &[], query_path, query)
+                    || Self::string_match("websocket-disconnected", &[], query_path, query)
+                    || Self::string_match(&params.connection_id, &[], query_path, query)
+            }
         }
     }
 
@@ -30,6 +30,7 @@ pub use public_types::*;
 pub use raw_types::*;
 
 use crate::model::component::ComponentRevision;
+use std::collections::HashMap;
 
 impl OplogEntry {
     pub fn is_end_atomic_region(&self, idx: OplogIndex) -> bool {
@@ -173,6 +174,71 @@ impl OplogEntry {
             _ => None,
         }
     }
+
+    /// Returns the payload IDs and MD5 hashes of all the payloads of this entry that are stored
+    /// outside the oplog.
+    pub fn external_payloads(&self) -> Vec<(&PayloadId, &[u8])> {
+        match self {
+            OplogEntry::Start {
+                request: Some(payload),
+                ..
+            } => payload.external_reference().into_iter().collect(),
+            OplogEntry::End {
+                response: Some(payload),
+                ..
+            } => payload.external_reference().into_iter().collect(),
+            OplogEntry::Cancelled {
+                partial: Some(payload),
+                ..
+            } => payload.external_reference().into_iter().collect(),
+            OplogEntry::AgentInvocationStarted { payload, .. } => {
+                payload.external_reference().into_iter().collect()
+            }
+            OplogEntry::AgentInvocationFinished { result, .. } => {
+                result.external_reference().into_iter().collect()
+            }
+            OplogEntry::PendingAgentInvocation { payload, .. } => {
+                payload.external_reference().into_iter().collect()
+            }
+            OplogEntry::PendingUpdate {
+                description: UpdateDescription::SnapshotBased { payload, .. },
+                ..
+            } => payload.external_reference().into_iter().collect(),
+            OplogEntry::Snapshot { data, .. } => data.external_reference().into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Points the external payloads of this entry to their new locations. Used when the oplog
+    /// is moved to a different agent, as external payloads are stored per agent.
+    pub fn relocate_external_payloads(
+        &mut self,
+        relocated: &HashMap<PayloadId, RawOplogPayload>,
+    ) -> Result<(), String> {
+        match self {
+            OplogEntry::Start {
+                request: Some(payload),
+                ..
+            } => payload.relocate(relocated),
+            OplogEntry::End {
+                response: Some(payload),
+                ..
+            } => payload.relocate(relocated),
+            OplogEntry::Cancelled {
+                partial: Some(payload),
+                ..
+            } => payload.relocate(relocated),
+            OplogEntry::AgentInvocationStarted { payload, .. } => payload.relocate(relocated),
+            OplogEntry::AgentInvocationFinished { result, .. } => result.relocate(relocated),
+            OplogEntry::PendingAgentInvocation { payload, .. } => payload.relocate(relocated),
+            OplogEntry::PendingUpdate {
+                description: UpdateDescription::SnapshotBased { payload, .. },
+                ..
+            } => payload.relocate(relocated),
+            OplogEntry::Snapshot { data, .. } => data.relocate(relocated),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Mutable state carried by a forward scan that validates a durable scope rooted at `root` has no
@@ -28,14 +28,17 @@ use crate::model::oplog::payload::types::{
 use crate::model::oplog::types::{
     AgentMetadataForGuests, SerializableDbColumn, SerializableDbResult, SerializableDbValue,
     SerializableHttpErrorCode, SerializableHttpMethod, SerializableHttpResponse,
-    SerializableInvokeResult, SerializableIpAddresses, SerializableRdbmsError,
-    SerializableRdbmsRequest, SerializableRpcError, SerializableScheduleId,
+    SerializableInvokeResult, SerializableIpAddresses, SerializableRdbmsBatchRequest,
+    SerializableRdbmsError, SerializableRdbmsRequest, SerializableRpcError, SerializableScheduleId,
     SerializableStreamError,
 };
+use crate::model::promise::PromiseOutcome;
 use crate::model::retry_policy::{NamedRetryPolicy, PredicateValue, RetryPolicy};
+use crate::model::topic::{TopicSubscriber, TopicSubscription};
 use crate::model::worker::RevertWorkerTarget;
 use crate::model::{
     AgentFingerprint, AgentId, ComponentId, ForkResult, IdempotencyKey, OplogIndex, PromiseId,
+    TransactionId,
 };
 use crate::oplog_payload;
 use crate::serialization::serialize;
@@ -107,6 +110,10 @@ oplog_payload! {
         GolemApiPromiseId {
             promise_id: PromiseId
         },
+        GolemApiPromiseOptions {
+            timeout_nanos: Option<u64>,
+            payload_type: Option<AnalysedType>
+        },
         GolemApiRevertAgent {
             agent_id: AgentId,
             target: RevertWorkerTarget
@@ -224,6 +231,26 @@ oplog_payload! {
         GolemRpcCreate {
             remote_agent_id: AgentId
         },
+        GolemRdbmsBatchRequest {
+            request: Option<SerializableRdbmsBatchRequest>
+        },
+        GolemRdbmsSavepoint {
+            transaction_id: Option<TransactionId>,
+            name: String
+        },
+        GolemAgentTopicSubscription {
+            topic: String,
+            subscription: TopicSubscription
+        },
+        GolemAgentTopicSubscriber {
+            topic: String,
+            subscriber: TopicSubscriber
+        },
+        GolemAgentTopicPublish {
+            topic: String,
+            idempotency_key: IdempotencyKey,
+            message: UntypedDataValue
+        },
     }
 }
 
@@ -293,6 +320,9 @@ oplog_payload! {
         GolemApiPromiseResult {
             result: Option<Vec<u8>>
         },
+        GolemApiPromiseOutcome {
+            result: Option<PromiseOutcome>
+        },
         GolemApiUnit {
             result: Result<(), String>,
         },
@@ -436,6 +466,16 @@ oplog_payload! {
         GolemRpcCreate {
             target_fingerprint: AgentFingerprint,
             target_environment_id: EnvironmentId
+        },
+        GolemRdbmsUnit {
+            result: Result<(), SerializableRdbmsError>
+        },
+        GolemRdbmsRowCounts {
+            result: Result<Vec<u64>, SerializableRdbmsError>
+        },
+        GolemAgentTopicPublish {
+            /// Number of invocations enqueued for the subscribers
+            result: Result<u64, SerializableRpcError>
         }
     }
 }
@@ -541,11 +581,14 @@ pub mod host_functions {
         (GolemAgentGetConfigValue => "golem::agent", "get_config_value", GolemAgentGetConfigValue, GolemAgentGetConfigValue),
         (GolemApiCreatePromise => "golem::api", "create_promise", NoInput, GolemApiPromiseId),
         (GolemApiCompletePromise => "golem::api", "complete_promise", GolemApiPromiseId, GolemApiPromiseCompletion),
+        (GolemApiCreatePromiseWithOptions => "golem::api", "create_promise_with_options", GolemApiPromiseOptions, GolemApiPromiseId),
+        (GolemApiCancelPromise => "golem::api", "cancel_promise", GolemApiPromiseId, GolemApiPromiseCompletion),
         (GolemApiGenerateIdempotencyKey => "golem::api", "generate_idempotency-key", NoInput, GolemApiIdempotencyKey),
         (GolemApiUpdateWorker => "golem::api", "update_worker", GolemApiUpdateAgent, GolemApiUnit),
         (GolemApiGetSelfMetadata => "golem::api", "get_self_metadata", NoInput, GolemApiSelfAgentMetadata),
         (GolemApiGetAgentMetadata => "golem::api", "get_agent_metadata", GolemApiAgentId, GolemApiAgentMetadata),
         (GolemApiGetPromiseResult => "golem::api", "get_promise_result", NoInput, GolemApiPromiseResult),
+        (GolemApiGetPromiseOutcome => "golem::api", "get_promise_outcome", NoInput, GolemApiPromiseOutcome),
         (GolemApiForkWorker => "golem::api", "fork_worker", GolemApiForkAgent, GolemApiUnit),
         (GolemApiRevertWorker => "golem::api", "revert_worker", GolemApiRevertAgent, GolemApiUnit),
         (GolemApiResolveComponentId => "golem::api", "resolve_component_id", GolemApiComponentSlug, GolemApiComponentId),
@@ -562,7 +605,28 @@ pub mod host_functions {
         (GolemApiRetryGetRetryPolicies => "golem::api::retry", "get_retry_policies", NoInput, GolemRetryPolicies),
         (GolemApiRetryGetRetryPolicyByName => "golem::api::retry", "get_retry_policy_by_name", GolemRetryPolicyByName, GolemRetryNamedPolicy),
         (GolemApiRetryResolveRetryPolicy => "golem::api::retry", "resolve_retry_policy", GolemRetryResolvePolicy, GolemRetryResolvedPolicy),
-        (GolemRpcWasmRpcNew => "golem::rpc::wasm-rpc", "new", GolemRpcCreate, GolemRpcCreate)
+        (GolemRpcWasmRpcNew => "golem::rpc::wasm-rpc", "new", GolemRpcCreate, GolemRpcCreate),
+        (RdbmsMysqlDbConnectionPrepare => "rdbms::mysql::db-connection", "prepare", GolemRdbmsRequest, GolemRdbmsUnit),
+        (RdbmsMysqlDbConnectionExecuteBatch => "rdbms::mysql::db-connection", "execute-batch", GolemRdbmsBatchRequest, GolemRdbmsRowCounts),
+        (RdbmsMysqlDbTransactionPrepare => "rdbms::mysql::db-transaction", "prepare", GolemRdbmsRequest, GolemRdbmsUnit),
+        (RdbmsMysqlDbTransactionExecuteBatch => "rdbms::mysql::db-transaction", "execute-batch", GolemRdbmsBatchRequest, GolemRdbmsRowCounts),
+        (RdbmsMysqlDbTransactionSavepoint => "rdbms::mysql::db-transaction", "savepoint", GolemRdbmsSavepoint, GolemRdbmsUnit),
+        (RdbmsMysqlDbTransactionReleaseSavepoint => "rdbms::mysql::db-transaction", "release-savepoint", GolemRdbmsSavepoint, GolemRdbmsUnit),
+        (RdbmsMysqlDbTransactionRollbackToSavepoint => "rdbms::mysql::db-transaction", "rollback-to-savepoint", GolemRdbmsSavepoint, GolemRdbmsUnit),
+        (RdbmsPostgresDbConnectionPrepare => "rdbms::postgres::db-connection", "prepare", GolemRdbmsRequest, GolemRdbmsUnit),
+        (RdbmsPostgresDbConnectionExecuteBatch => "rdbms::postgres::db-connection", "execute-batch", GolemRdbmsBatchRequest, GolemRdbmsRowCounts),
+        (RdbmsPostgresDbTransactionPrepare => "rdbms::postgres::db-transaction", "prepare", GolemRdbmsRequest, GolemRdbmsUnit),
+        (RdbmsPostgresDbTransactionExecuteBatch => "rdbms::postgres::db-transaction", "execute-batch", GolemRdbmsBatchRequest, GolemRdbmsRowCounts),
+        (RdbmsPostgresDbTransactionSavepoint => "rdbms::postgres::db-transaction", "savepoint", GolemRdbmsSavepoint, GolemRdbmsUnit),
+        (RdbmsPostgresDbTransactionReleaseSavepoint => "rdbms::postgres::db-transaction", "release-savepoint", GolemRdbmsSavepoint, GolemRdbmsUnit),
+        (RdbmsPostgresDbTransactionRollbackToSavepoint => "rdbms::postgres::db-transaction", "rollback-to-savepoint", GolemRdbmsSavepoint, GolemRdbmsUnit),
+        (RdbmsIgnite2DbConnectionPrepare => "rdbms::ignite2::db-connection", "prepare", GolemRdbmsRequest, GolemRdbmsUnit),
+        (RdbmsIgnite2DbConnectionExecuteBatch => "rdbms::ignite2::db-connection", "execute-batch", GolemRdbmsBatchRequest, GolemRdbmsRowCounts),
+        (RdbmsIgnite2DbTransactionPrepare => "rdbms::ignite2::db-transaction", "prepare", GolemRdbmsRequest, GolemRdbmsUnit),
+        (RdbmsIgnite2DbTransactionExecuteBatch => "rdbms::ignite2::db-transaction", "execute-batch", GolemRdbmsBatchRequest, GolemRdbmsRowCounts),
+        (GolemAgentSubscribe => "golem::agent", "subscribe", GolemAgentTopicSubscription, GolemRpcUnitOrFailure),
+        (GolemAgentUnsubscribe => "golem::agent", "unsubscribe", GolemAgentTopicSubscriber, GolemRpcUnitOrFailure),
+        (GolemAgentPublish => "golem::agent", "publish", GolemAgentTopicPublish, GolemAgentTopicPublish)
     }
 }
 
@@ -693,6 +757,31 @@ impl<T: BinaryCodec + Debug + Clone + PartialEq> OplogPayload<T> {
             }),
         }
     }
+
+    /// Returns the payload ID and MD5 hash if the payload is stored outside the oplog
+    pub fn external_reference(&self) -> Option<(&PayloadId, &[u8])> {
+        match self {
+            OplogPayload::External {
+                payload_id,
+                md5_hash,
+                ..
+            } => Some((payload_id, md5_hash)),
+            _ => None,
+        }
+    }
+
+    /// Replaces an external payload reference with its new location, if it has one in `relocated`
+    pub fn relocate(
+        &mut self,
+        relocated: &HashMap<PayloadId, RawOplogPayload>,
+    ) -> Result<(), String> {
+        if let OplogPayload::External { payload_id, .. } = self
+            && let Some(target) = relocated.get(payload_id)
+        {
+            *self = target.clone().into_payload()?;
+        }
+        Ok(())
+    }
 }
 
 impl<T: BinaryCodec + Debug + Clone + PartialEq> BinarySerializer for OplogPayload<T> {
@@ -842,6 +931,7 @@ impl<T: BinaryCodec + Debug + Clone + PartialEq> golem_wasm::FromValue for Oplog
 }
 
 /// Untyped version of OplogPayload
+#[derive(Clone)]
 pub enum RawOplogPayload {
     SerializedInline(Vec<u8>),
     External {
@@ -1532,6 +1532,15 @@ pub struct SerializableRdbmsRequest {
     pub transaction_id: Option<TransactionId>,
 }
 
+#[derive(Clone, Debug, PartialEq, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub struct SerializableRdbmsBatchRequest {
+    pub pool_key: RdbmsPoolKey,
+    pub statement: String,
+    pub params: Vec<Vec<SerializableDbValue>>,
+    pub transaction_id: Option<TransactionId>,
+}
+
 #[derive(Clone, Debug, PartialEq, BinaryCodec, IntoValue, FromValue)]
 #[desert(evolution())]
 pub struct SerializableDbValue {
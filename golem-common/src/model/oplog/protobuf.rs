@@ -14,7 +14,8 @@
 
 use super::{
     AgentError, AgentInitializationParameters, AgentInvocationOutputParameters,
-    AgentMethodInvocationParameters, AgentResourceId, FallibleResultParameters, JsonSnapshotData,
+    AgentMethodInvocationParameters, AgentResourceId, FallibleResultParameters,
+    InterceptInvocationParameters, InterceptInvocationResultParameters, JsonSnapshotData,
     LoadSnapshotParameters, LogLevel, ManualUpdateParameters, MultipartPartData,
     MultipartSnapshotData, MultipartSnapshotPart, OplogCursor, PluginInstallationDescription,
     ProcessOplogEntriesParameters, ProcessOplogEntriesResultParameters, PublicAgentInvocation,
@@ -26,7 +27,6 @@ use super::{
 };
 use crate::base_model::OplogIndex;
 use crate::base_model::agent::AgentMode;
-use crate::model::AgentInvocationResult;
 use crate::model::Empty;
 use crate::model::agent::DataValue;
 use crate::model::agent::UntypedDataValue;
@@ -47,16 +47,17 @@ use crate::model::oplog::public_oplog_entry::{
     PreCommitRemoteTransactionParams, PreRollbackRemoteTransactionParams, RemoveRetryPolicyParams,
     RestartParams, RevertParams, RolledBackRemoteTransactionParams, SetRetryPolicyParams,
     SetSpanAttributeParams, SnapshotParams, StartParams, StartSpanParams, SuccessfulUpdateParams,
-    SuspendParams,
+    SuspendParams, WebSocketConnectedParams, WebSocketDisconnectedParams,
 };
 use crate::model::oplog::{
     AgentTerminatedByQuotaError, DurableFunctionType, EphemeralCannotSuspendError,
-    EphemeralFuelExhaustedError, EphemeralSleepTooLongError, OplogEntry, PersistenceLevel,
-    ReadOnlyViolationError,
+    EphemeralFuelExhaustedError, EphemeralSleepTooLongError, InvocationDeadlineExceededError,
+    OplogEntry, PersistenceLevel, ReadOnlyViolationError,
 };
 use crate::model::quota::ResourceName;
 use crate::model::regions::OplogRegion;
 use crate::model::worker::TypedAgentConfigEntry;
+use crate::model::{AgentInvocationResult, Interception, InterceptionOutcome};
 use golem_api_grpc::proto::golem::worker::oplog_entry::Entry;
 use golem_api_grpc::proto::golem::worker::{
     AttributeValue, ExternalParentSpan, InvocationSpan, LocalInvocationSpan, invocation_span,
@@ -153,6 +154,12 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::AgentError> for AgentError {
                     host_function: inner.host_function,
                 }))
             }
+            Error::InvocationDeadlineExceeded(inner) => Ok(Self::InvocationDeadlineExceeded(
+                InvocationDeadlineExceededError {
+                    method: inner.method,
+                    deadline: inner.deadline.ok_or("Missing field: deadline")?.into(),
+                },
+            )),
         }
     }
 }
@@ -230,6 +237,13 @@ impl From<AgentError> for golem_api_grpc::proto::golem::worker::AgentError {
                 method,
                 host_function,
             }),
+            AgentError::InvocationDeadlineExceeded(InvocationDeadlineExceededError {
+                method,
+                deadline,
+            }) => Error::InvocationDeadlineExceeded(grpc_worker::InvocationDeadlineExceeded {
+                method,
+                deadline: Some(deadline.into()),
+            }),
         };
         Self { error: Some(error) }
     }
@@ -774,6 +788,19 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::OplogEntry> for PublicOplogEn
                     name: params.policy_name,
                 }),
             ),
+            oplog_entry::Entry::WebSocketConnected(params) => Ok(
+                PublicOplogEntry::WebSocketConnected(WebSocketConnectedParams {
+                    timestamp: params.timestamp.ok_or("Missing timestamp field")?.into(),
+                    connection_id: params.connection_id,
+                    method_name: params.method_name,
+                }),
+            ),
+            oplog_entry::Entry::WebSocketDisconnected(params) => Ok(
+                PublicOplogEntry::WebSocketDisconnected(WebSocketDisconnectedParams {
+                    timestamp: params.timestamp.ok_or("Missing timestamp field")?.into(),
+                    connection_id: params.connection_id,
+                }),
+            ),
         }
     }
 }
@@ -1294,6 +1321,27 @@ impl TryFrom<PublicOplogEntry> for golem_api_grpc::proto::golem::worker::OplogEn
                     )),
                 }
             }
+            PublicOplogEntry::WebSocketConnected(params) => {
+                golem_api_grpc::proto::golem::worker::OplogEntry {
+                    entry: Some(oplog_entry::Entry::WebSocketConnected(
+                        golem_api_grpc::proto::golem::worker::WebSocketConnectedParameters {
+                            timestamp: Some(params.timestamp.into()),
+                            connection_id: params.connection_id,
+                            method_name: params.method_name,
+                        },
+                    )),
+                }
+            }
+            PublicOplogEntry::WebSocketDisconnected(params) => {
+                golem_api_grpc::proto::golem::worker::OplogEntry {
+                    entry: Some(oplog_entry::Entry::WebSocketDisconnected(
+                        golem_api_grpc::proto::golem::worker::WebSocketDisconnectedParameters {
+                            timestamp: Some(params.timestamp.into()),
+                            connection_id: params.connection_id,
+                        },
+                    )),
+                }
+            }
         })
     }
 }
@@ -1527,6 +1575,15 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::PublicAgentInvocation>
                     target_revision: manual.target_revision.try_into()?,
                 },
             )),
+            Invocation::InterceptInvocation(intercept) => Ok(
+                PublicAgentInvocation::InterceptInvocation(InterceptInvocationParameters {
+                    idempotency_key: intercept
+                        .idempotency_key
+                        .ok_or("Missing idempotency_key field")?
+                        .into(),
+                    method_name: intercept.method_name,
+                }),
+            ),
         }
     }
 }
@@ -1660,6 +1717,14 @@ impl TryFrom<PublicAgentInvocation>
                     target_revision: manual.target_revision.into(),
                 },
             ),
+            PublicAgentInvocation::InterceptInvocation(intercept) => {
+                Invocation::InterceptInvocation(
+                    golem_api_grpc::proto::golem::worker::InterceptInvocationParameters {
+                        idempotency_key: Some(intercept.idempotency_key.into()),
+                        method_name: intercept.method_name,
+                    },
+                )
+            }
         };
         Ok(
             golem_api_grpc::proto::golem::worker::PublicAgentInvocation {
@@ -1755,6 +1820,13 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::PublicAgentInvocationResult>
                     },
                 ))
             }
+            ProtoResult::InterceptInvocation(result) => {
+                Ok(PublicAgentInvocationResult::InterceptInvocation(
+                    InterceptInvocationResultParameters {
+                        error: result.error,
+                    },
+                ))
+            }
         }
     }
 }
@@ -1867,6 +1939,13 @@ impl TryFrom<PublicAgentInvocationResult>
                     },
                 )
             }
+            PublicAgentInvocationResult::InterceptInvocation(result) => {
+                ProtoResult::InterceptInvocation(
+                    golem_api_grpc::proto::golem::worker::OptionalError {
+                        error: result.error,
+                    },
+                )
+            }
         };
         Ok(
             golem_api_grpc::proto::golem::worker::PublicAgentInvocationResult {
@@ -2489,6 +2568,15 @@ impl TryFrom<PublicOplogEntry> for OplogEntry {
                 timestamp: p.timestamp,
                 name: p.name,
             }),
+            PublicOplogEntry::WebSocketConnected(p) => Ok(OplogEntry::WebSocketConnected {
+                timestamp: p.timestamp,
+                connection_id: p.connection_id,
+                method_name: p.method_name,
+            }),
+            PublicOplogEntry::WebSocketDisconnected(p) => Ok(OplogEntry::WebSocketDisconnected {
+                timestamp: p.timestamp,
+                connection_id: p.connection_id,
+            }),
         }
     }
 }
@@ -2565,6 +2653,18 @@ fn public_agent_invocation_result_to_raw(
                 error: params.error,
             })
         }
+        PublicAgentInvocationResult::InterceptInvocation(params) => {
+            // The public representation only retains the rejection reason
+            Ok(AgentInvocationResult::InterceptInvocation {
+                outcome: InterceptionOutcome {
+                    interception: match params.error {
+                        Some(reason) => Interception::Reject(reason),
+                        None => Interception::Proceed,
+                    },
+                    span_attributes: Vec::new(),
+                },
+            })
+        }
     }
 }
 
@@ -2858,7 +2958,8 @@ impl TryFrom<OplogEntry> for golem_api_grpc::proto::golem::worker::RawOplogEntry
             RawRemoteTransactionParameters, RawRemoveRetryPolicyParameters, RawResourceTypeId,
             RawRevertParameters, RawSetRetryPolicyParameters, RawSetSpanAttributeParameters,
             RawSnapshotParameters, RawStartParameters, RawStartSpanParameters,
-            RawSuccessfulUpdateParameters, RawTimestampOnly,
+            RawSuccessfulUpdateParameters, RawTimestampOnly, RawWebSocketConnectedParameters,
+            RawWebSocketDisconnectedParameters,
         };
 
         let timestamp = value.timestamp();
@@ -3198,6 +3299,17 @@ impl TryFrom<OplogEntry> for golem_api_grpc::proto::golem::worker::RawOplogEntry
             OplogEntry::RemoveRetryPolicy { name, .. } => {
                 Entry::RemoveRetryPolicy(RawRemoveRetryPolicyParameters { name })
             }
+            OplogEntry::WebSocketConnected {
+                connection_id,
+                method_name,
+                ..
+            } => Entry::WebsocketConnected(RawWebSocketConnectedParameters {
+                connection_id,
+                method_name,
+            }),
+            OplogEntry::WebSocketDisconnected { connection_id, .. } => {
+                Entry::WebsocketDisconnected(RawWebSocketDisconnectedParameters { connection_id })
+            }
         };
 
         Ok(golem_api_grpc::proto::golem::worker::RawOplogEntry {
@@ -3613,6 +3725,15 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::RawOplogEntry> for OplogEntry
                 timestamp,
                 name: p.name,
             }),
+            Entry::WebsocketConnected(p) => Ok(OplogEntry::WebSocketConnected {
+                timestamp,
+                connection_id: p.connection_id,
+                method_name: p.method_name,
+            }),
+            Entry::WebsocketDisconnected(p) => Ok(OplogEntry::WebSocketDisconnected {
+                timestamp,
+                connection_id: p.connection_id,
+            }),
         }
     }
 }
@@ -3644,3 +3765,33 @@ mod read_only_violation_roundtrip {
         }
     }
 }
+
+#[cfg(test)]
+mod invocation_deadline_exceeded_roundtrip {
+    use crate::model::Timestamp;
+    use crate::model::oplog::{AgentError, InvocationDeadlineExceededError};
+    use proptest::prelude::*;
+    use test_r::test;
+
+    fn invocation_deadline_exceeded_strat() -> impl Strategy<Value = InvocationDeadlineExceededError>
+    {
+        (any::<String>(), 0u64..4_102_444_800_000).prop_map(|(method, deadline_millis)| {
+            InvocationDeadlineExceededError {
+                method,
+                deadline: Timestamp::from(deadline_millis),
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn agent_error_invocation_deadline_exceeded_protobuf_roundtrip(
+            err in invocation_deadline_exceeded_strat(),
+        ) {
+            let original = AgentError::InvocationDeadlineExceeded(err);
+            let proto: golem_api_grpc::proto::golem::worker::AgentError = original.clone().into();
+            let roundtrip: AgentError = proto.try_into().unwrap();
+            prop_assert_eq!(roundtrip, original);
+        }
+    }
+}
@@ -197,7 +197,7 @@ impl From<AtomicOplogIndex> for OplogIndex {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, BinaryCodec)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec)]
 #[desert(transparent)]
 pub struct PayloadId(pub Uuid);
 
@@ -382,6 +382,14 @@ pub struct ReadOnlyViolationError {
     pub host_function: String,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+#[wit(name = "invocation-deadline-exceeded", owner = "golem:api@1.5.0/oplog")]
+pub struct InvocationDeadlineExceededError {
+    pub method: String,
+    pub deadline: Timestamp,
+}
+
 /// Describes the error that occurred in the worker
 #[derive(Clone, Debug, PartialEq, Eq, Hash, BinaryCodec, IntoValue, FromValue)]
 #[wit(name = "worker-error", owner = "golem:api@1.5.0/oplog")]
@@ -417,6 +425,9 @@ pub enum AgentError {
     // A read-only agent method attempted to perform a side effect (outgoing HTTP / RPC) that is
     // disallowed by the read-only strictness mode.
     ReadOnlyViolation(ReadOnlyViolationError),
+    // The invocation was still running when its deadline passed. The deadline is the earliest of
+    // the method's declared timeout and the caller-provided deadline (permanent)
+    InvocationDeadlineExceeded(InvocationDeadlineExceededError),
 }
 
 impl AgentError {
@@ -441,6 +452,7 @@ impl AgentError {
             Self::EphemeralFuelExhausted(_) => "Ephemeral fuel exhausted",
             Self::EphemeralCannotSuspend(_) => "Ephemeral agent cannot suspend",
             Self::ReadOnlyViolation(_) => "Read-only agent method attempted a side effect",
+            Self::InvocationDeadlineExceeded(_) => "Invocation deadline exceeded",
         }
     }
 
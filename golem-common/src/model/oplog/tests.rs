@@ -26,7 +26,7 @@ use crate::model::oplog::public_oplog_entry::{
     PendingUpdateParams, PreCommitRemoteTransactionParams, PreRollbackRemoteTransactionParams,
     RemoveRetryPolicyParams, RestartParams, RevertParams, RolledBackRemoteTransactionParams,
     SetRetryPolicyParams, SetSpanAttributeParams, SnapshotParams, StartParams, StartSpanParams,
-    SuccessfulUpdateParams, SuspendParams,
+    SuccessfulUpdateParams, SuspendParams, WebSocketConnectedParams, WebSocketDisconnectedParams,
 };
 use crate::model::oplog::{
     AgentInitializationParameters, AgentInvocationOutputParameters,
@@ -893,6 +893,29 @@ fn remove_retry_policy_serialization_poem_serde_equivalence() {
     assert_eq!(entry, deserialized);
 }
 
+#[test]
+fn websocket_connected_serialization_poem_serde_equivalence() {
+    let entry = PublicOplogEntry::WebSocketConnected(WebSocketConnectedParams {
+        timestamp: Timestamp::now_utc().rounded(),
+        connection_id: "0d8bc5a6-3c5e-4f1b-9d2e-6a0f1b7c9e11".to_string(),
+        method_name: "on-message".to_string(),
+    });
+    let serialized = entry.to_json_string();
+    let deserialized: PublicOplogEntry = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(entry, deserialized);
+}
+
+#[test]
+fn websocket_disconnected_serialization_poem_serde_equivalence() {
+    let entry = PublicOplogEntry::WebSocketDisconnected(WebSocketDisconnectedParams {
+        timestamp: Timestamp::now_utc().rounded(),
+        connection_id: "0d8bc5a6-3c5e-4f1b-9d2e-6a0f1b7c9e11".to_string(),
+    });
+    let serialized = entry.to_json_string();
+    let deserialized: PublicOplogEntry = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(entry, deserialized);
+}
+
 #[test]
 fn oplog_entry_type_matches_wit() {
     use crate::component_introspection::wit_parser::{AnalysedTypeResolve, TypeName, TypeOwner};
@@ -1082,3 +1105,77 @@ mod scope_scan {
         assert!(scan(10, &entries, persist_all()));
     }
 }
+
+mod external_payloads {
+    use crate::model::Timestamp;
+    use crate::model::oplog::{OplogEntry, OplogPayload, PayloadId, RawOplogPayload};
+    use std::collections::HashMap;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn snapshot(timestamp: Timestamp, data: OplogPayload<Vec<u8>>) -> OplogEntry {
+        OplogEntry::Snapshot {
+            timestamp,
+            data,
+            mime_type: "application/octet-stream".to_string(),
+        }
+    }
+
+    #[test]
+    fn external_payloads_are_listed_and_relocated() {
+        let source_id = PayloadId(Uuid::new_v4());
+        let target_id = PayloadId(Uuid::new_v4());
+        let mut entry = snapshot(
+            Timestamp::now_utc(),
+            OplogPayload::External {
+                payload_id: source_id.clone(),
+                md5_hash: vec![1, 2, 3],
+                cached: None,
+            },
+        );
+
+        assert_eq!(
+            entry.external_payloads(),
+            vec![(&source_id, [1u8, 2, 3].as_slice())]
+        );
+
+        let relocated = HashMap::from([(
+            source_id,
+            RawOplogPayload::External {
+                payload_id: target_id.clone(),
+                md5_hash: vec![1, 2, 3],
+            },
+        )]);
+        entry.relocate_external_payloads(&relocated).unwrap();
+
+        assert_eq!(
+            entry.external_payloads(),
+            vec![(&target_id, [1u8, 2, 3].as_slice())]
+        );
+    }
+
+    #[test]
+    fn inline_payloads_are_left_untouched() {
+        let timestamp = Timestamp::now_utc();
+        let mut entry = snapshot(
+            timestamp,
+            OplogPayload::SerializedInline {
+                bytes: vec![4, 5, 6],
+                cached: None,
+            },
+        );
+
+        assert!(entry.external_payloads().is_empty());
+        entry.relocate_external_payloads(&HashMap::new()).unwrap();
+        assert_eq!(
+            entry,
+            snapshot(
+                timestamp,
+                OplogPayload::SerializedInline {
+                    bytes: vec![4, 5, 6],
+                    cached: None,
+                }
+            )
+        );
+    }
+}
@@ -27,6 +27,14 @@ impl PluginRegistrationDto {
     fn oplog_processor(&self) -> Option<&OplogProcessorPluginSpec> {
         match &self.spec {
             PluginSpecDto::OplogProcessor(inner) => Some(inner),
+            PluginSpecDto::InvocationInterceptor(_) => None,
+        }
+    }
+
+    fn invocation_interceptor(&self) -> Option<&InvocationInterceptorPluginSpec> {
+        match &self.spec {
+            PluginSpecDto::InvocationInterceptor(inner) => Some(inner),
+            PluginSpecDto::OplogProcessor(_) => None,
         }
     }
 
@@ -38,9 +46,20 @@ impl PluginRegistrationDto {
         self.oplog_processor().map(|inner| inner.component_revision)
     }
 
+    pub fn invocation_interceptor_component_id(&self) -> Option<ComponentId> {
+        self.invocation_interceptor()
+            .map(|inner| inner.component_id)
+    }
+
+    pub fn invocation_interceptor_component_revision(&self) -> Option<ComponentRevision> {
+        self.invocation_interceptor()
+            .map(|inner| inner.component_revision)
+    }
+
     pub fn typ_as_str(&self) -> &'static str {
         match &self.spec {
             PluginSpecDto::OplogProcessor(_) => "oplog processor",
+            PluginSpecDto::InvocationInterceptor(_) => "invocation interceptor",
         }
     }
 }
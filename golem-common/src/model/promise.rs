@@ -0,0 +1,109 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::Timestamp;
+use crate::schema::adapters::analysed_type::analysed_type_to_schema_graph;
+use crate::schema::render::json_value::from_json_value;
+use desert_rust::BinaryCodec;
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm_derive::{FromValue, IntoValue};
+
+/// Options a promise was created with
+#[derive(Debug, Clone, PartialEq, Default, BinaryCodec)]
+#[desert(evolution())]
+pub struct PromiseOptions {
+    /// The promise is failed with [`PromiseOutcome::TimedOut`] if it is not completed by this time
+    pub deadline: Option<Timestamp>,
+    /// Completions must be JSON payloads matching this type
+    pub payload_type: Option<AnalysedType>,
+}
+
+impl PromiseOptions {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Checks a completion payload against the promise's payload type, if it has one.
+    pub fn validate_payload(&self, data: &[u8]) -> Result<(), String> {
+        let Some(payload_type) = &self.payload_type else {
+            return Ok(());
+        };
+
+        let json: serde_json::Value = serde_json::from_slice(data)
+            .map_err(|e| format!("Promise payload is not valid JSON: {e}"))?;
+        let graph = analysed_type_to_schema_graph(payload_type)
+            .map_err(|e| format!("Unsupported promise payload type: {e}"))?;
+        from_json_value(&graph, &graph.root, &json)
+            .map_err(|e| format!("Promise payload does not match the expected type: {e}"))?;
+        Ok(())
+    }
+}
+
+/// The final outcome of a promise
+#[derive(Debug, Clone, PartialEq, Eq, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub enum PromiseOutcome {
+    /// The promise was completed with the given payload
+    Completed(Vec<u8>),
+    /// The promise was not completed before its deadline
+    TimedOut,
+    /// The promise was cancelled by the agent that created it
+    Cancelled,
+}
+
+impl PromiseOutcome {
+    /// The payload seen by guests using the untyped `get` accessor. Timed out and cancelled
+    /// promises have none, they can only be told apart through `get-outcome`.
+    pub fn into_completed_payload(self) -> Option<Vec<u8>> {
+        match self {
+            PromiseOutcome::Completed(data) => Some(data),
+            PromiseOutcome::TimedOut | PromiseOutcome::Cancelled => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm::analysis::analysed_type::{field, record, str, u32};
+    use test_r::test;
+
+    #[test]
+    fn untyped_promises_accept_any_payload() {
+        let options = PromiseOptions::default();
+        assert_eq!(options.validate_payload(b"\x00\x01not json"), Ok(()));
+    }
+
+    #[test]
+    fn typed_promises_validate_json_payloads() {
+        let options = PromiseOptions {
+            deadline: None,
+            payload_type: Some(record(vec![
+                field("order-id", str()),
+                field("amount", u32()),
+            ])),
+        };
+
+        assert_eq!(
+            options.validate_payload(br#"{"order-id": "abc", "amount": 10}"#),
+            Ok(())
+        );
+        assert!(
+            options
+                .validate_payload(br#"{"order-id": "abc", "amount": "ten"}"#)
+                .is_err()
+        );
+        assert!(options.validate_payload(b"not json").is_err());
+    }
+}
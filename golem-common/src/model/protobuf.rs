@@ -545,6 +545,32 @@ impl TryFrom<golem::worker::LogEvent> for AgentEvent {
                         message: event.message,
                     })
                 }
+                golem::worker::log_event::Event::WebsocketSend(event) => {
+                    Ok(AgentEvent::WebSocketSend {
+                        timestamp: event.timestamp.ok_or("Missing timestamp")?.into(),
+                        connection_id: event.connection_id,
+                        message: match event.message.ok_or("Missing message")? {
+                            golem::worker::web_socket_send::Message::Text(text) => {
+                                WebSocketMessage::Text(text)
+                            }
+                            golem::worker::web_socket_send::Message::Binary(bytes) => {
+                                WebSocketMessage::Binary(bytes)
+                            }
+                        },
+                    })
+                }
+                golem::worker::log_event::Event::WebsocketClose(event) => {
+                    Ok(AgentEvent::WebSocketClose {
+                        timestamp: event.timestamp.ok_or("Missing timestamp")?.into(),
+                        connection_id: event.connection_id,
+                        code: event
+                            .code
+                            .map(u16::try_from)
+                            .transpose()
+                            .map_err(|_| "Invalid WebSocket close code")?,
+                        reason: event.reason,
+                    })
+                }
             },
             None => Err("Missing event".to_string()),
         }
@@ -631,6 +657,41 @@ impl TryFrom<AgentEvent> for golem::worker::LogEvent {
                     },
                 )),
             }),
+            AgentEvent::WebSocketSend {
+                timestamp,
+                connection_id,
+                message,
+            } => Ok(golem::worker::LogEvent {
+                event: Some(golem::worker::log_event::Event::WebsocketSend(
+                    golem::worker::WebSocketSend {
+                        timestamp: Some(timestamp.into()),
+                        connection_id,
+                        message: Some(match message {
+                            WebSocketMessage::Text(text) => {
+                                golem::worker::web_socket_send::Message::Text(text)
+                            }
+                            WebSocketMessage::Binary(bytes) => {
+                                golem::worker::web_socket_send::Message::Binary(bytes)
+                            }
+                        }),
+                    },
+                )),
+            }),
+            AgentEvent::WebSocketClose {
+                timestamp,
+                connection_id,
+                code,
+                reason,
+            } => Ok(golem::worker::LogEvent {
+                event: Some(golem::worker::log_event::Event::WebsocketClose(
+                    golem::worker::WebSocketClose {
+                        timestamp: Some(timestamp.into()),
+                        connection_id,
+                        code: code.map(u32::from),
+                        reason,
+                    },
+                )),
+            }),
             AgentEvent::ClientLagged {
                 number_of_missed_messages,
             } => Ok(golem::worker::LogEvent {
@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::model::agent::AgentTypeName;
 use crate::model::component::{CanonicalFilePath, ComponentRevision};
 use crate::model::environment::EnvironmentId;
 use crate::model::oplog::OplogIndex;
+use crate::model::topic::{TopicSubscriber, TopicSubscription};
 use crate::model::worker::TypedAgentConfigEntry;
 use crate::model::{
     AccountEmail, AccountId, AgentFilter, AgentFingerprint, AgentId, AgentMetadata, AgentMode,
@@ -435,6 +437,70 @@ fn derived_idempotency_key() {
     assert_ne!(derived31, derived32);
 }
 
+#[test]
+fn derived_idempotency_key_for_target() {
+    let base1 = IdempotencyKey::fresh();
+    let base2 = IdempotencyKey {
+        value: "base2".to_string(),
+    };
+
+    let component_id = ComponentId::new();
+    let target1 = AgentId {
+        component_id,
+        agent_id: "subscriber(\"a\")".to_string(),
+    };
+    let target2 = AgentId {
+        component_id,
+        agent_id: "subscriber(\"b\")".to_string(),
+    };
+
+    let derived11a = IdempotencyKey::derived_for_target(&base1, &target1);
+    let derived11b = IdempotencyKey::derived_for_target(&base1, &target1);
+    let derived12 = IdempotencyKey::derived_for_target(&base1, &target2);
+    let derived21 = IdempotencyKey::derived_for_target(&base2, &target1);
+
+    assert_eq!(derived11a, derived11b);
+    assert_ne!(derived11a, derived12);
+    assert_ne!(derived11a, derived21);
+    assert_ne!(
+        derived11a,
+        IdempotencyKey::derived(&base1, OplogIndex::from_u64(1))
+    );
+}
+
+#[test]
+fn topic_delivery_keys_differ_per_subscription() {
+    let publish_key = IdempotencyKey::fresh();
+    let target = AgentId {
+        component_id: ComponentId::new(),
+        agent_id: "subscriber(\"a\")".to_string(),
+    };
+    let explicit = TopicSubscription {
+        subscriber: TopicSubscriber::Agent(target.clone()),
+        method_name: "on-message".to_string(),
+    };
+    let by_type = TopicSubscription {
+        subscriber: TopicSubscriber::AgentType(AgentTypeName("subscriber".to_string())),
+        method_name: "on-message".to_string(),
+    };
+
+    let explicit_key = explicit.delivery_idempotency_key(&publish_key, &target);
+    assert_eq!(
+        explicit_key,
+        explicit.delivery_idempotency_key(&publish_key, &target)
+    );
+    assert_ne!(
+        explicit_key,
+        by_type.delivery_idempotency_key(&publish_key, &target)
+    );
+    assert_ne!(
+        explicit_key,
+        explicit.delivery_idempotency_key(&IdempotencyKey::fresh(), &target)
+    );
+    // Delivery keys are used as schedule ids, so they must be UUIDs
+    assert!(Uuid::parse_str(&explicit_key.value).is_ok());
+}
+
 #[test]
 fn canonical_file_path_from_absolute() {
     let path = CanonicalFilePath::from_abs_str("/a/b/c").unwrap();
@@ -0,0 +1,115 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::agent::{AgentTypeName, UntypedDataValue};
+use crate::model::component::ComponentId;
+use crate::model::invocation_context::InvocationContextStack;
+use crate::model::oplog::OplogIndex;
+use crate::model::{AgentId, AgentInvocation, IdempotencyKey, OwnedAgentId, ScanCursor};
+use desert_rust::BinaryCodec;
+use golem_wasm_derive::{FromValue, IntoValue};
+use std::fmt::{Display, Formatter};
+
+/// The receiver of the messages published to a durable agent-to-agent topic
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub enum TopicSubscriber {
+    /// A single, explicitly subscribed agent
+    Agent(AgentId),
+    /// Every agent of an agent type
+    AgentType(AgentTypeName),
+}
+
+impl Display for TopicSubscriber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopicSubscriber::Agent(agent_id) => write!(f, "agent {agent_id}"),
+            TopicSubscriber::AgentType(agent_type) => write!(f, "agent type {agent_type}"),
+        }
+    }
+}
+
+/// A subscription of a topic: every published message is delivered to the subscriber
+/// by invoking `method_name` with the message as its input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub struct TopicSubscription {
+    pub subscriber: TopicSubscriber,
+    pub method_name: String,
+}
+
+impl TopicSubscription {
+    /// The idempotency key of delivering the message published with `publish_key` to one
+    /// target agent of this subscription. Different subscriptions reaching the same agent get
+    /// different keys, and retrying the delivery produces the same key.
+    pub fn delivery_idempotency_key(
+        &self,
+        publish_key: &IdempotencyKey,
+        target: &AgentId,
+    ) -> IdempotencyKey {
+        let subscription_key = IdempotencyKey::derived_for_name(
+            publish_key,
+            &format!("subscription-{}-{}", self.subscriber, self.method_name),
+        );
+        IdempotencyKey::derived_for_target(&subscription_key, target)
+    }
+}
+
+/// A message published to a topic, being delivered to the agents of a subscribed agent type
+/// one page of agents at a time.
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct TopicFanOut {
+    /// The publishing agent, also used to route the fan-out to a shard
+    pub publisher: OwnedAgentId,
+    pub topic: String,
+    pub subscription: TopicSubscription,
+    /// The component implementing the subscribed agent type
+    pub component_id: ComponentId,
+    pub publish_idempotency_key: IdempotencyKey,
+    /// The publisher's oplog index of the publish, ordering the messages of one publisher
+    pub publish_index: OplogIndex,
+    pub input: UntypedDataValue,
+    pub invocation_context: InvocationContextStack,
+    /// The next page of agents to deliver the message to
+    pub cursor: ScanCursor,
+}
+
+impl TopicFanOut {
+    /// The idempotency key identifying the scheduled fan-out of the current page, so that
+    /// retrying the publish or the previous page does not schedule the same page twice.
+    pub fn schedule_idempotency_key(&self) -> IdempotencyKey {
+        IdempotencyKey::derived_for_name(
+            &self.publish_idempotency_key,
+            &format!(
+                "fan-out-{}-{}-{}-{}",
+                self.subscription.subscriber,
+                self.subscription.method_name,
+                self.cursor.layer,
+                self.cursor.cursor
+            ),
+        )
+    }
+}
+
+/// A topic message waiting in the delivery queue of one subscribed agent. The queue is
+/// delivered one message at a time, in the order the publishers published them.
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct TopicDelivery {
+    pub publisher: AgentId,
+    /// The publisher's oplog index of the publish, ordering the messages of one publisher
+    pub publish_index: OplogIndex,
+    pub invocation: AgentInvocation,
+}
@@ -82,7 +82,7 @@ mod protobuf {
     use super::AgentMetadataDto;
     use super::{
         AgentConfigEntryDto, ExportedResourceMetadata, FailedUpdate, PendingUpdate,
-        SuccessfulUpdate, TypedAgentConfigEntry, UpdateRecord,
+        PromiseMetadata, PromiseState, SuccessfulUpdate, TypedAgentConfigEntry, UpdateRecord,
     };
     use super::{AgentUpdateMode, RevertLastInvocations, RevertToOplogIndex, RevertWorkerTarget};
     use crate::base_model::AgentFingerprint;
@@ -157,6 +157,11 @@ mod protobuf {
                     .fingerprint
                     .map(|uuid| AgentFingerprint(uuid.into()))
                     .unwrap_or_default(),
+                promises: value
+                    .promises
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<_>, _>>()?,
             })
         }
     }
@@ -206,6 +211,67 @@ mod protobuf {
                     .collect(),
                 oplog_idx: u64::from(value.last_oplog_index),
                 fingerprint: Some(value.fingerprint.0.into()),
+                promises: value.promises.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<golem_api_grpc::proto::golem::worker::PromiseMetadata> for PromiseMetadata {
+        type Error = String;
+
+        fn try_from(
+            value: golem_api_grpc::proto::golem::worker::PromiseMetadata,
+        ) -> Result<Self, Self::Error> {
+            Ok(Self {
+                oplog_index: OplogIndex::from_u64(value.oplog_index),
+                state: value.state().into(),
+                deadline: value.deadline.map(Into::into),
+                payload_type: value
+                    .payload_type
+                    .as_ref()
+                    .map(TryInto::try_into)
+                    .transpose()?,
+            })
+        }
+    }
+
+    impl From<PromiseMetadata> for golem_api_grpc::proto::golem::worker::PromiseMetadata {
+        fn from(value: PromiseMetadata) -> Self {
+            Self {
+                oplog_index: u64::from(value.oplog_index),
+                state: golem_api_grpc::proto::golem::worker::PromiseState::from(value.state).into(),
+                deadline: value.deadline.map(Into::into),
+                payload_type: value.payload_type.as_ref().map(Into::into),
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::worker::PromiseState> for PromiseState {
+        fn from(value: golem_api_grpc::proto::golem::worker::PromiseState) -> Self {
+            match value {
+                golem_api_grpc::proto::golem::worker::PromiseState::PromisePending => {
+                    PromiseState::Pending
+                }
+                golem_api_grpc::proto::golem::worker::PromiseState::PromiseCompleted => {
+                    PromiseState::Completed
+                }
+                golem_api_grpc::proto::golem::worker::PromiseState::PromiseTimedOut => {
+                    PromiseState::TimedOut
+                }
+                golem_api_grpc::proto::golem::worker::PromiseState::PromiseCancelled => {
+                    PromiseState::Cancelled
+                }
+            }
+        }
+    }
+
+    impl From<PromiseState> for golem_api_grpc::proto::golem::worker::PromiseState {
+        fn from(value: PromiseState) -> Self {
+            match value {
+                PromiseState::Pending => Self::PromisePending,
+                PromiseState::Completed => Self::PromiseCompleted,
+                PromiseState::TimedOut => Self::PromiseTimedOut,
+                PromiseState::Cancelled => Self::PromiseCancelled,
             }
         }
     }
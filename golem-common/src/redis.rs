@@ -741,6 +741,51 @@ impl RedisLabelledApi<'_> {
         )
     }
 
+    pub async fn zremrangebyscore<R, K, M, N>(&self, key: K, min: M, max: N) -> RedisResult<R>
+    where
+        R: FromValue,
+        K: AsRef<str>,
+        M: TryInto<ZRange> + Send,
+        M::Error: Into<RedisError> + Send,
+        N: TryInto<ZRange> + Send,
+        N::Error: Into<RedisError> + Send,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+        self.record(
+            start,
+            "ZREMRANGEBYSCORE",
+            self.pool
+                .zremrangebyscore(self.prefixed_key(key), min, max)
+                .await,
+        )
+    }
+
+    /// Runs a Lua script atomically; the keys are prefixed like in every other command
+    pub async fn eval<R, K, V>(&self, script: &str, keys: Vec<K>, args: V) -> RedisResult<R>
+    where
+        R: FromValue,
+        K: AsRef<str>,
+        V: TryInto<MultipleValues> + Send,
+        V::Error: Into<RedisError> + Send,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+        self.record(
+            start,
+            "EVAL",
+            self.pool
+                .eval(
+                    script,
+                    keys.iter()
+                        .map(|k| self.prefixed_key(k))
+                        .collect::<Vec<_>>(),
+                    args,
+                )
+                .await,
+        )
+    }
+
     pub async fn transaction<R, F, Fu>(&self, func: F) -> RedisResult<R>
     where
         R: FromValue,
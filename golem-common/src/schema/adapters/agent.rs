@@ -104,6 +104,7 @@ pub fn agent_method_to_schema(
         output_schema: data_schema_to_output_schema(&method.output_schema)?,
         http_endpoint: method.http_endpoint.clone(),
         read_only: method.read_only.clone(),
+        timeout_nanos: method.timeout_nanos,
     })
 }
 
@@ -123,6 +124,7 @@ pub fn schema_agent_method_to_legacy(
         output_schema: output_schema_to_data_schema(graph, &method.output_schema)?,
         http_endpoint: method.http_endpoint.clone(),
         read_only: method.read_only.clone(),
+        timeout_nanos: method.timeout_nanos,
     })
 }
 
@@ -777,6 +777,7 @@ fn agent_type_round_trip() {
             }),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Ephemeral,
@@ -841,6 +842,7 @@ fn agent_type_reverse_resolves_parent_refs() {
             output_schema: OutputSchema::Single(Box::new(SchemaType::ref_to(user_id.clone()))),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
@@ -205,7 +205,7 @@ pub struct AgentConstructorSchema {
 ///
 /// Mirrors the legacy `AgentMethod`, with `input_schema` / `output_schema`
 /// replaced by [`InputSchema`] / [`OutputSchema`]. Non-schema fields
-/// (`http_endpoint`, `read_only`) are carried verbatim from the legacy
+/// (`http_endpoint`, `read_only`, `timeout_nanos`) are carried verbatim from the legacy
 /// representation during the transition; they are not part of the schema
 /// layer's core concern.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -220,6 +220,8 @@ pub struct AgentMethodSchema {
     pub http_endpoint: Vec<HttpEndpointDetails>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub read_only: Option<ReadOnlyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_nanos: Option<u64>,
 }
 
 /// Dependent agent type, schema-layer form.
@@ -938,6 +938,7 @@ impl From<AgentMethodSchema> for proto::AgentMethodSchema {
             output_schema: Some(value.output_schema.into()),
             http_endpoint: value.http_endpoint.into_iter().map(Into::into).collect(),
             read_only: value.read_only.map(Into::into),
+            timeout_nanos: value.timeout_nanos,
         }
     }
 }
@@ -964,6 +965,7 @@ impl TryFrom<proto::AgentMethodSchema> for AgentMethodSchema {
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
             read_only: value.read_only.map(TryInto::try_into).transpose()?,
+            timeout_nanos: value.timeout_nanos,
         })
     }
 }
@@ -31,6 +31,9 @@ pub enum RenderError {
     /// A JSON conversion failed (e.g., a number out of range for the
     /// target integer width).
     Json(String),
+    /// A protobuf decoder saw malformed wire data (a truncated varint, an
+    /// unsupported wire type, …).
+    Protobuf(String),
     /// A record decoder saw a JSON object with a field that is not declared
     /// by the schema.
     UnexpectedField { record: String, field: String },
@@ -57,6 +60,7 @@ impl Display for RenderError {
             RenderError::Unsupported(what) => write!(f, "unsupported: {what}"),
             RenderError::Canonical(inner) => write!(f, "canonical: {inner}"),
             RenderError::Json(msg) => write!(f, "json: {msg}"),
+            RenderError::Protobuf(msg) => write!(f, "protobuf: {msg}"),
             RenderError::UnexpectedField { record, field } => {
                 write!(f, "unexpected field `{field}` on record `{record}`")
             }
@@ -15,7 +15,7 @@
 //! Renderer that produces a JSON Schema document from a `SchemaGraph`/
 //! `SchemaType`.
 
-use crate::schema::agent::{FieldSource, InputSchema, OutputSchema};
+use crate::schema::agent::{AgentTypeSchema, FieldSource, InputSchema, OutputSchema};
 use crate::schema::graph::SchemaGraph;
 use crate::schema::metadata::{MetadataEnvelope, TypeId};
 use crate::schema::schema_type::{
@@ -124,24 +124,26 @@ pub fn input_schema_to_json_schema(
     input: &InputSchema,
     config: JsonSchemaConfig,
 ) -> Value {
+    to_json_schema_with_config(graph, &user_supplied_record(input), config)
+}
+
+/// Project the **user-supplied** parameters of an [`InputSchema`] onto a
+/// synthetic record, dropping `FieldSource::AutoInjected` fields.
+pub(super) fn user_supplied_record(input: &InputSchema) -> SchemaType {
     let InputSchema::Parameters(fields) = input;
-    let user_fields: Vec<&crate::schema::agent::NamedField> = fields
+    let record_fields: Vec<NamedFieldType> = fields
         .iter()
         .filter(|f| matches!(f.source, FieldSource::UserSupplied))
-        .collect();
-    let record_fields: Vec<NamedFieldType> = user_fields
-        .iter()
         .map(|f| NamedFieldType {
             name: f.name.clone(),
             body: f.schema.clone(),
             metadata: f.metadata.clone(),
         })
         .collect();
-    let record = SchemaType::Record {
+    SchemaType::Record {
         fields: record_fields,
         metadata: MetadataEnvelope::default(),
-    };
-    to_json_schema_with_config(graph, &record, config)
+    }
 }
 
 /// Render an [`OutputSchema`] to an optional JSON Schema document.
@@ -163,6 +165,143 @@ pub fn output_schema_to_json_schema(
     }
 }
 
+/// Render a whole [`AgentTypeSchema`] to one bundled JSON Schema document.
+///
+/// The constructor parameters and every method's input and output are
+/// emitted as `$defs` entries (`<Agent>Constructor`, `<Method>Input`,
+/// `<Method>Output`) next to the agent's named definitions and the
+/// synthesised union-branch schemas, so all payloads share one `$defs`
+/// namespace. Inputs follow [`input_schema_to_json_schema`]: only
+/// user-supplied parameters are surfaced.
+///
+/// The root carries an `x-golem-agent` annotation mapping the constructor
+/// and each method (by its original name) to the `$ref` pointers of its
+/// payload schemas; a unit output maps to `null`. Dependencies are not
+/// included, as each one is an agent type with its own graph.
+pub fn agent_type_to_json_schema(agent_type: &AgentTypeSchema) -> Value {
+    let graph = &agent_type.schema;
+    let config = JsonSchemaConfig::WITHOUT_DRAFT_MARKER;
+
+    let constructor = user_supplied_record(&agent_type.constructor.input_schema);
+    let inputs: Vec<SchemaType> = agent_type
+        .methods
+        .iter()
+        .map(|m| user_supplied_record(&m.input_schema))
+        .collect();
+
+    // Branch names are resolved over a synthetic record spanning every
+    // payload root, so a union reachable from several payloads gets a
+    // single `$defs` entry per branch.
+    let mut roots = vec![NamedFieldType {
+        name: "constructor".to_string(),
+        body: constructor.clone(),
+        metadata: MetadataEnvelope::default(),
+    }];
+    for (method, input) in agent_type.methods.iter().zip(&inputs) {
+        roots.push(NamedFieldType {
+            name: format!("{}-input", method.name),
+            body: input.clone(),
+            metadata: MetadataEnvelope::default(),
+        });
+        if let Some(output) = method.output_schema.schema() {
+            roots.push(NamedFieldType {
+                name: format!("{}-output", method.name),
+                body: output.clone(),
+                metadata: MetadataEnvelope::default(),
+            });
+        }
+    }
+    let synthetic_root = SchemaType::Record {
+        fields: roots,
+        metadata: MetadataEnvelope::default(),
+    };
+
+    let table = build_branch_name_table(graph, &synthetic_root);
+    let mut defs = render_defs(graph, &table, config);
+    add_union_branch_defs(graph, &synthetic_root, &mut defs, &table, config);
+
+    let agent_name = sanitise_to_upper_camel(&agent_type.type_name.0);
+    let constructor_ref = insert_payload_def(
+        &mut defs,
+        &format!("{agent_name}Constructor"),
+        render_type(graph, &constructor, false, &table, config),
+        &agent_type.constructor.description,
+    );
+
+    let mut methods = Map::new();
+    for (method, input) in agent_type.methods.iter().zip(&inputs) {
+        let method_name = sanitise_to_upper_camel(&method.name);
+        let input_ref = insert_payload_def(
+            &mut defs,
+            &format!("{method_name}Input"),
+            render_type(graph, input, false, &table, config),
+            &method.description,
+        );
+        let output_ref = match method.output_schema.schema() {
+            Some(output) => Value::String(insert_payload_def(
+                &mut defs,
+                &format!("{method_name}Output"),
+                render_type(graph, output, false, &table, config),
+                "",
+            )),
+            None => Value::Null,
+        };
+        methods.insert(
+            method.name.clone(),
+            obj([("input", Value::String(input_ref)), ("output", output_ref)]),
+        );
+    }
+
+    let annotation = obj([
+        ("type", Value::String(agent_type.type_name.0.clone())),
+        ("constructor", Value::String(constructor_ref)),
+        ("methods", Value::Object(methods)),
+    ]);
+
+    let mut doc = Map::new();
+    doc.insert(
+        "$schema".to_string(),
+        Value::String(JSON_SCHEMA_DRAFT.to_string()),
+    );
+    doc.insert(
+        "title".to_string(),
+        Value::String(agent_type.type_name.0.clone()),
+    );
+    if !agent_type.description.is_empty() {
+        doc.insert(
+            "description".to_string(),
+            Value::String(agent_type.description.clone()),
+        );
+    }
+    doc.insert("$defs".to_string(), Value::Object(defs));
+    doc.insert("x-golem-agent".to_string(), annotation);
+    Value::Object(doc)
+}
+
+/// Insert a payload schema into `defs` under `base` (suffixed with a
+/// counter if the key is already taken) and return its `$ref` pointer.
+fn insert_payload_def(
+    defs: &mut Map<String, Value>,
+    base: &str,
+    mut body: Value,
+    description: &str,
+) -> String {
+    let mut key = base.to_string();
+    let mut counter = 2;
+    while defs.contains_key(&key) {
+        key = format!("{base}{counter}");
+        counter += 1;
+    }
+    if !description.is_empty()
+        && let Some(obj) = body.as_object_mut()
+    {
+        obj.entry("description")
+            .or_insert(Value::String(description.to_string()));
+    }
+    defs.insert(key.clone(), body);
+    ref_to_def_key(&key)
+}
+
 /// Whether `ty`, after following any `Ref` chain against `graph`, is an
 /// `option<…>`. Used to decide whether an input parameter is required.
 fn resolves_to_option(graph: &SchemaGraph, ty: &SchemaType) -> bool {
@@ -537,7 +676,7 @@ fn disambiguate(base: &str, path: &[String], taken: &HashSet<String>, canonical:
 /// suitable for both JSON Pointer member names and OpenAPI schema names
 /// (alphabet `[A-Za-z0-9]`). Non-alphanumerics are dropped and treated
 /// as word separators; a leading digit is prefixed with `_`.
-fn sanitise_to_upper_camel(s: &str) -> String {
+pub(super) fn sanitise_to_upper_camel(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     let mut upper_next = true;
     for ch in s.chars() {
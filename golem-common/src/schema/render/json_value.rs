@@ -978,7 +978,7 @@ fn mismatch(path: &PathStack, reason: String) -> RenderError {
     }
 }
 
-fn type_name(ty: &SchemaType) -> &'static str {
+pub(super) fn type_name(ty: &SchemaType) -> &'static str {
     match ty {
         SchemaType::Ref { .. } => "ref",
         SchemaType::Bool { .. } => "bool",
@@ -1019,7 +1019,7 @@ fn type_name(ty: &SchemaType) -> &'static str {
     }
 }
 
-fn value_name(value: &SchemaValue) -> &'static str {
+pub(super) fn value_name(value: &SchemaValue) -> &'static str {
     match value {
         SchemaValue::Bool(_) => "bool",
         SchemaValue::S8(_) => "s8",
@@ -25,6 +25,8 @@ pub mod error;
 pub mod json_schema;
 pub mod json_value;
 pub mod openapi;
+pub mod protobuf_idl;
+pub mod protobuf_value;
 pub mod walker;
 
 #[cfg(test)]
@@ -34,9 +36,11 @@ pub use cli_text::{type_to_cli_text, value_to_cli_text, value_to_cli_text_unreda
 pub use docs::graph_to_markdown;
 pub use error::RenderError;
 pub use json_schema::{
-    JsonSchemaConfig, input_schema_to_json_schema, output_schema_to_json_schema, to_json_schema,
-    to_json_schema_with_config,
+    JsonSchemaConfig, agent_type_to_json_schema, input_schema_to_json_schema,
+    output_schema_to_json_schema, to_json_schema, to_json_schema_with_config,
 };
 pub use json_value::{from_json_value, to_json_value};
 pub use openapi::to_openapi_components;
+pub use protobuf_idl::{ProtoFile, agent_type_to_proto_file, agent_type_to_protobuf};
+pub use protobuf_value::ProtoRequest;
 pub use walker::{SchemaWalker, WalkerError, resolve_ref, walk};
@@ -0,0 +1,964 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Renderer that produces a proto3 file from an [`AgentTypeSchema`].
+//!
+//! The agent type becomes a package with one `service`. Each method is a
+//! unary rpc taking `<Method>Request` (the constructor parameters that
+//! identify the agent, an optional phantom id and the `<Method>Input`
+//! parameters) and returning `<Method>Output`. Methods returning a list
+//! additionally get a server-streaming `<Method>Stream` rpc taking the same
+//! request and yielding one `<Method>Item` per element. Named definitions of
+//! the agent's graph become top-level messages or enums; anonymous
+//! composites are hoisted to top-level messages named after their position.
+//!
+//! Type mapping:
+//!
+//! - records, tuples and flags are messages (tuple fields are `item_<n>`,
+//!   flags are `bool` fields);
+//! - variants, unions and results are messages with a single `oneof`;
+//!   payload-less cases use `google.protobuf.Empty`;
+//! - enums are proto enums with a leading `<ENUM>_UNSPECIFIED = 0`, so
+//!   schema case `i` has number `i + 1`;
+//! - `option<T>` is an `optional` field, `list<T>` a `repeated` one and
+//!   `map<K, V>` a proto `map` when `K` is a string or integer, otherwise a
+//!   repeated `<Field>Entry` message. Shapes protobuf cannot nest directly
+//!   (lists of lists, options of lists, …) go through a one-field wrapper
+//!   message;
+//! - datetimes and durations use the well-known `Timestamp` / `Duration`
+//!   types; text, binary and quantity values use the local `Text`,
+//!   `Binary` and `Quantity` messages; paths, URLs, secrets and quota
+//!   tokens are strings.
+//!
+//! Field numbers follow declaration order, so they are only stable as long
+//! as the agent's signatures are. The structured [`ProtoFile`] is public so
+//! protocol layers can reuse the naming and numbering without parsing the
+//! rendered IDL.
+
+use crate::schema::agent::{AgentTypeSchema, OutputSchema};
+use crate::schema::graph::SchemaGraph;
+use crate::schema::metadata::TypeId;
+use crate::schema::render::error::RenderError;
+use crate::schema::render::json_schema::{sanitise_to_upper_camel, user_supplied_record};
+use crate::schema::schema_type::SchemaType;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+const EMPTY: &str = "google.protobuf.Empty";
+const EMPTY_IMPORT: &str = "google/protobuf/empty.proto";
+const TIMESTAMP: &str = "google.protobuf.Timestamp";
+const TIMESTAMP_IMPORT: &str = "google/protobuf/timestamp.proto";
+const DURATION: &str = "google.protobuf.Duration";
+const DURATION_IMPORT: &str = "google/protobuf/duration.proto";
+
+/// A proto3 file describing one agent type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoFile {
+    pub package: String,
+    pub imports: BTreeSet<String>,
+    pub definitions: Vec<ProtoDefinition>,
+    pub service: ProtoService,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtoDefinition {
+    Message(ProtoMessage),
+    Enum(ProtoEnum),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoMessage {
+    pub name: String,
+    pub doc: Option<String>,
+    /// When set, every field of the message is a member of this `oneof`.
+    pub oneof: Option<String>,
+    pub fields: Vec<ProtoField>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoField {
+    pub name: String,
+    pub number: u32,
+    pub label: ProtoLabel,
+    pub ty: ProtoType,
+    pub doc: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProtoLabel {
+    Singular,
+    Optional,
+    Repeated,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtoType {
+    Scalar(ProtoScalar),
+    /// A message or enum, by its name relative to the file's package (or
+    /// fully qualified for well-known types).
+    Named(String),
+    Map {
+        key: ProtoScalar,
+        value: Box<ProtoType>,
+    },
+}
+
+impl ProtoType {
+    fn is_map(&self) -> bool {
+        matches!(self, ProtoType::Map { .. })
+    }
+
+    fn render(&self) -> String {
+        match self {
+            ProtoType::Scalar(scalar) => scalar.as_str().to_string(),
+            ProtoType::Named(name) => name.clone(),
+            ProtoType::Map { key, value } => format!("map<{}, {}>", key.as_str(), value.render()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProtoScalar {
+    Bool,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Float,
+    Double,
+    String,
+    Bytes,
+}
+
+impl ProtoScalar {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtoScalar::Bool => "bool",
+            ProtoScalar::Int32 => "int32",
+            ProtoScalar::Int64 => "int64",
+            ProtoScalar::Uint32 => "uint32",
+            ProtoScalar::Uint64 => "uint64",
+            ProtoScalar::Float => "float",
+            ProtoScalar::Double => "double",
+            ProtoScalar::String => "string",
+            ProtoScalar::Bytes => "bytes",
+        }
+    }
+
+    /// Whether the scalar may be used as a proto `map` key.
+    fn is_map_key(&self) -> bool {
+        !matches!(
+            self,
+            ProtoScalar::Float | ProtoScalar::Double | ProtoScalar::Bytes
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoEnum {
+    pub name: String,
+    pub doc: Option<String>,
+    pub values: Vec<ProtoEnumValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoEnumValue {
+    pub name: String,
+    pub number: i32,
+    /// The schema case this value stands for; `None` for the
+    /// `<ENUM>_UNSPECIFIED` zero value.
+    pub case: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoService {
+    pub name: String,
+    pub doc: Option<String>,
+    pub rpcs: Vec<ProtoRpc>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtoRpc {
+    pub name: String,
+    /// The agent method this rpc invokes, by its original name.
+    pub method: String,
+    pub doc: Option<String>,
+    pub input: String,
+    pub output: String,
+    /// Whether the rpc streams the elements of the method's list result,
+    /// one `output` message each, instead of returning it whole.
+    pub server_streaming: bool,
+}
+
+/// Render an agent type to proto3 IDL text. See [`agent_type_to_proto_file`]
+/// for the structured form.
+pub fn agent_type_to_protobuf(agent_type: &AgentTypeSchema) -> Result<String, RenderError> {
+    Ok(agent_type_to_proto_file(agent_type)?.to_idl())
+}
+
+/// Build the structured proto3 description of an agent type.
+///
+/// Fails with [`RenderError::Unsupported`] for payloads protobuf cannot
+/// describe (`future` / `stream`) and for references to types missing from
+/// the agent's graph.
+pub fn agent_type_to_proto_file(agent_type: &AgentTypeSchema) -> Result<ProtoFile, RenderError> {
+    let graph = &agent_type.schema;
+    let mut builder = Builder::new();
+
+    // Payload messages claim their names first so they stay predictable;
+    // named definitions are disambiguated around them.
+    let constructor = builder.reserve("Constructor");
+    let mut rpc_names = HashSet::new();
+    let mut methods = Vec::with_capacity(agent_type.methods.len());
+    for method in &agent_type.methods {
+        let base = sanitise_to_upper_camel(&method.name);
+        let rpc = unique(&mut rpc_names, &base);
+        let request = builder.reserve(&format!("{rpc}Request"));
+        let input = builder.reserve(&format!("{rpc}Input"));
+        let output = builder.reserve(&format!("{rpc}Output"));
+        let item = match &method.output_schema {
+            OutputSchema::Single(ty) => list_element(graph, ty)?
+                .map(|element| (element, builder.reserve(&format!("{rpc}Item")))),
+            OutputSchema::Unit => None,
+        };
+        methods.push((method, rpc, request, input, output, item));
+    }
+    // Streaming rpcs are named after every unary one, so adding a list
+    // result never renames another method's rpc.
+    let methods: Vec<_> = methods
+        .into_iter()
+        .map(|(method, rpc, request, input, output, item)| {
+            let stream = item.map(|(element, item)| {
+                (
+                    unique(&mut rpc_names, &format!("{rpc}Stream")),
+                    element,
+                    item,
+                )
+            });
+            (method, rpc, request, input, output, stream)
+        })
+        .collect();
+
+    for def in &graph.defs {
+        let name = builder.reserve(def.name.as_deref().unwrap_or(def.id.as_str()));
+        builder.def_names.insert(def.id.clone(), name);
+    }
+    for def in &graph.defs {
+        let name = builder.def_names[&def.id].clone();
+        builder.define(name, &def.body)?;
+    }
+
+    builder.define(
+        constructor.clone(),
+        &documented(
+            user_supplied_record(&agent_type.constructor.input_schema),
+            &agent_type.constructor.description,
+        ),
+    )?;
+
+    let mut rpcs = Vec::with_capacity(methods.len());
+    for (method, rpc, request, input, output, stream) in methods {
+        builder.define(
+            input.clone(),
+            &documented(
+                user_supplied_record(&method.input_schema),
+                &method.description,
+            ),
+        )?;
+
+        let output_fields = match &method.output_schema {
+            OutputSchema::Unit => Vec::new(),
+            OutputSchema::Single(ty) => {
+                let (label, ty) = builder.field_type(ty, &format!("{output}_value"))?;
+                vec![field("value", 1, label, ty, None)]
+            }
+        };
+        builder.push_message(output.clone(), None, None, output_fields);
+
+        builder.push_message(
+            request.clone(),
+            Some(format!("Invocation of `{}`.", method.name)),
+            None,
+            vec![
+                field(
+                    "agent",
+                    1,
+                    ProtoLabel::Singular,
+                    ProtoType::Named(constructor.clone()),
+                    Some("Constructor parameters identifying the agent.".to_string()),
+                ),
+                field(
+                    "phantom_id",
+                    2,
+                    ProtoLabel::Optional,
+                    ProtoType::Scalar(ProtoScalar::String),
+                    None,
+                ),
+                field(
+                    "input",
+                    3,
+                    ProtoLabel::Singular,
+                    ProtoType::Named(input),
+                    None,
+                ),
+            ],
+        );
+
+        rpcs.push(ProtoRpc {
+            name: rpc,
+            method: method.name.clone(),
+            doc: non_empty(&method.description),
+            input: request.clone(),
+            output,
+            server_streaming: false,
+        });
+
+        if let Some((name, element, item)) = stream {
+            let (label, ty) = builder.field_type(element, &format!("{item}_value"))?;
+            builder.push_message(
+                item.clone(),
+                None,
+                None,
+                vec![field("value", 1, label, ty, None)],
+            );
+            rpcs.push(ProtoRpc {
+                name,
+                method: method.name.clone(),
+                doc: Some(format!(
+                    "Streams the elements returned by `{}`.",
+                    method.name
+                )),
+                input: request,
+                output: item,
+                server_streaming: true,
+            });
+        }
+    }
+
+    Ok(ProtoFile {
+        package: format!("golem.agent.{}", package_segment(&agent_type.type_name.0)),
+        imports: builder.imports,
+        definitions: builder.definitions,
+        service: ProtoService {
+            name: sanitise_to_upper_camel(&agent_type.type_name.0),
+            doc: non_empty(&agent_type.description),
+            rpcs,
+        },
+    })
+}
+
+impl ProtoFile {
+    /// Render the file as proto3 IDL text.
+    pub fn to_idl(&self) -> String {
+        let mut out = String::new();
+        out.push_str("syntax = \"proto3\";\n\n");
+        let _ = writeln!(out, "package {};", self.package);
+        if !self.imports.is_empty() {
+            out.push('\n');
+            for import in &self.imports {
+                let _ = writeln!(out, "import \"{import}\";");
+            }
+        }
+
+        out.push('\n');
+        write_doc(&mut out, "", self.service.doc.as_deref());
+        let _ = writeln!(out, "service {} {{", self.service.name);
+        for rpc in &self.service.rpcs {
+            write_doc(&mut out, "  ", rpc.doc.as_deref());
+            let stream = if rpc.server_streaming { "stream " } else { "" };
+            let _ = writeln!(
+                out,
+                "  rpc {}({}) returns ({stream}{});",
+                rpc.name, rpc.input, rpc.output
+            );
+        }
+        out.push_str("}\n");
+
+        for definition in &self.definitions {
+            out.push('\n');
+            match definition {
+                ProtoDefinition::Message(message) => write_message(&mut out, message),
+                ProtoDefinition::Enum(enumeration) => write_enum(&mut out, enumeration),
+            }
+        }
+        out
+    }
+}
+
+fn write_message(out: &mut String, message: &ProtoMessage) {
+    write_doc(out, "", message.doc.as_deref());
+    if message.fields.is_empty() {
+        let _ = writeln!(out, "message {} {{}}", message.name);
+        return;
+    }
+    let _ = writeln!(out, "message {} {{", message.name);
+    let indent = match &message.oneof {
+        Some(oneof) => {
+            let _ = writeln!(out, "  oneof {oneof} {{");
+            "    "
+        }
+        None => "  ",
+    };
+    for field in &message.fields {
+        write_doc(out, indent, field.doc.as_deref());
+        let label = match field.label {
+            ProtoLabel::Singular => "",
+            ProtoLabel::Optional => "optional ",
+            ProtoLabel::Repeated => "repeated ",
+        };
+        let _ = writeln!(
+            out,
+            "{indent}{label}{} {} = {};",
+            field.ty.render(),
+            field.name,
+            field.number
+        );
+    }
+    if message.oneof.is_some() {
+        out.push_str("  }\n");
+    }
+    out.push_str("}\n");
+}
+
+fn write_enum(out: &mut String, enumeration: &ProtoEnum) {
+    write_doc(out, "", enumeration.doc.as_deref());
+    let _ = writeln!(out, "enum {} {{", enumeration.name);
+    for value in &enumeration.values {
+        let _ = writeln!(out, "  {} = {};", value.name, value.number);
+    }
+    out.push_str("}\n");
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: Option<&str>) {
+    if let Some(doc) = doc {
+        for line in doc.lines() {
+            let _ = writeln!(out, "{indent}// {line}");
+        }
+    }
+}
+
+struct Builder {
+    taken: HashSet<String>,
+    enum_values: HashSet<String>,
+    def_names: HashMap<TypeId, String>,
+    helpers: HashMap<&'static str, String>,
+    imports: BTreeSet<String>,
+    definitions: Vec<ProtoDefinition>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self {
+            taken: HashSet::new(),
+            enum_values: HashSet::new(),
+            def_names: HashMap::new(),
+            helpers: HashMap::new(),
+            imports: BTreeSet::new(),
+            definitions: Vec::new(),
+        }
+    }
+
+    /// Claim a top-level type name derived from `hint`.
+    fn reserve(&mut self, hint: &str) -> String {
+        unique(&mut self.taken, &sanitise_to_upper_camel(hint))
+    }
+
+    fn push_message(
+        &mut self,
+        name: String,
+        doc: Option<String>,
+        oneof: Option<String>,
+        fields: Vec<ProtoField>,
+    ) {
+        self.definitions
+            .push(ProtoDefinition::Message(ProtoMessage {
+                name,
+                doc,
+                oneof,
+                fields,
+            }));
+    }
+
+    /// Emit the definition named `name` for `ty`. Composite types map to
+    /// their natural message or enum shape; anything else is wrapped in a
+    /// message with a single `value` field.
+    fn define(&mut self, name: String, ty: &SchemaType) -> Result<(), RenderError> {
+        let doc = ty.metadata().doc.clone();
+        // Claim the slot first so a definition precedes the anonymous
+        // types hoisted out of it.
+        let index = self.definitions.len();
+        self.push_message(name.clone(), None, None, Vec::new());
+
+        let definition = match ty {
+            SchemaType::Record { fields, .. } => {
+                let mut names = HashSet::new();
+                let mut proto_fields = Vec::with_capacity(fields.len());
+                for (i, f) in fields.iter().enumerate() {
+                    let (label, field_ty) =
+                        self.field_type(&f.body, &format!("{name}_{}", f.name))?;
+                    proto_fields.push(field(
+                        &unique(&mut names, &field_name(&f.name)),
+                        i as u32 + 1,
+                        label,
+                        field_ty,
+                        f.metadata.doc.clone(),
+                    ));
+                }
+                message(name, doc, None, proto_fields)
+            }
+            SchemaType::Tuple { elements, .. } => {
+                let mut proto_fields = Vec::with_capacity(elements.len());
+                for (i, element) in elements.iter().enumerate() {
+                    let (label, field_ty) = self.field_type(element, &format!("{name}_{i}"))?;
+                    proto_fields.push(field(
+                        &format!("item_{i}"),
+                        i as u32 + 1,
+                        label,
+                        field_ty,
+                        None,
+                    ));
+                }
+                message(name, doc, None, proto_fields)
+            }
+            SchemaType::Flags { flags, .. } => {
+                let mut names = HashSet::new();
+                let proto_fields = flags
+                    .iter()
+                    .enumerate()
+                    .map(|(i, flag)| {
+                        field(
+                            &unique(&mut names, &field_name(flag)),
+                            i as u32 + 1,
+                            ProtoLabel::Singular,
+                            ProtoType::Scalar(ProtoScalar::Bool),
+                            None,
+                        )
+                    })
+                    .collect();
+                message(name, doc, None, proto_fields)
+            }
+            SchemaType::Variant { cases, .. } => {
+                let mut names = HashSet::new();
+                let mut proto_fields = Vec::with_capacity(cases.len());
+                for (i, case) in cases.iter().enumerate() {
+                    let case_ty = match &case.payload {
+                        Some(payload) => {
+                            self.oneof_member(payload, &format!("{name}_{}", case.name))?
+                        }
+                        None => self.empty(),
+                    };
+                    proto_fields.push(field(
+                        &unique(&mut names, &field_name(&case.name)),
+                        i as u32 + 1,
+                        ProtoLabel::Singular,
+                        case_ty,
+                        case.metadata.doc.clone(),
+                    ));
+                }
+                message(name, doc, Some("value".to_string()), proto_fields)
+            }
+            SchemaType::Union { spec, .. } => {
+                let mut names = HashSet::new();
+                let mut proto_fields = Vec::with_capacity(spec.branches.len());
+                for (i, branch) in spec.branches.iter().enumerate() {
+                    let branch_ty =
+                        self.oneof_member(&branch.body, &format!("{name}_{}", branch.tag))?;
+                    proto_fields.push(field(
+                        &unique(&mut names, &field_name(&branch.tag)),
+                        i as u32 + 1,
+                        ProtoLabel::Singular,
+                        branch_ty,
+                        branch.metadata.doc.clone(),
+                    ));
+                }
+                message(name, doc, Some("value".to_string()), proto_fields)
+            }
+            SchemaType::Result { spec, .. } => {
+                let ok = match &spec.ok {
+                    Some(ok) => self.oneof_member(ok, &format!("{name}_ok"))?,
+                    None => self.empty(),
+                };
+                let err = match &spec.err {
+                    Some(err) => self.oneof_member(err, &format!("{name}_err"))?,
+                    None => self.empty(),
+                };
+                message(
+                    name,
+                    doc,
+                    Some("result".to_string()),
+                    vec![
+                        field("ok", 1, ProtoLabel::Singular, ok, None),
+                        field("err", 2, ProtoLabel::Singular, err, None),
+                    ],
+                )
+            }
+            SchemaType::Enum { cases, .. } => {
+                let prefix = upper_snake(&name);
+                let mut values = vec![ProtoEnumValue {
+                    name: unique(&mut self.enum_values, &format!("{prefix}_UNSPECIFIED")),
+                    number: 0,
+                    case: None,
+                }];
+                for (i, case) in cases.iter().enumerate() {
+                    values.push(ProtoEnumValue {
+                        name: unique(
+                            &mut self.enum_values,
+                            &format!("{prefix}_{}", upper_snake(case)),
+                        ),
+                        number: i as i32 + 1,
+                        case: Some(case.clone()),
+                    });
+                }
+                ProtoDefinition::Enum(ProtoEnum { name, doc, values })
+            }
+            other => {
+                let (label, value_ty) = self.field_type(other, &format!("{name}_value"))?;
+                message(
+                    name,
+                    doc,
+                    None,
+                    vec![field("value", 1, label, value_ty, None)],
+                )
+            }
+        };
+        self.definitions[index] = definition;
+        Ok(())
+    }
+
+    /// Map a schema type to the label and type of a field holding it.
+    fn field_type(
+        &mut self,
+        ty: &SchemaType,
+        hint: &str,
+    ) -> Result<(ProtoLabel, ProtoType), RenderError> {
+        match ty {
+            SchemaType::Ref { id, .. } => match self.def_names.get(id) {
+                Some(name) => singular(ProtoType::Named(name.clone())),
+                None => Err(RenderError::Unsupported(
+                    "reference to a type missing from the agent's schema graph",
+                )),
+            },
+
+            SchemaType::Bool { .. } => scalar(ProtoScalar::Bool),
+            SchemaType::S8 { .. } | SchemaType::S16 { .. } | SchemaType::S32 { .. } => {
+                scalar(ProtoScalar::Int32)
+            }
+            SchemaType::S64 { .. } => scalar(ProtoScalar::Int64),
+            SchemaType::U8 { .. } | SchemaType::U16 { .. } | SchemaType::U32 { .. } => {
+                scalar(ProtoScalar::Uint32)
+            }
+            SchemaType::U64 { .. } => scalar(ProtoScalar::Uint64),
+            SchemaType::F32 { .. } => scalar(ProtoScalar::Float),
+            SchemaType::F64 { .. } => scalar(ProtoScalar::Double),
+            SchemaType::Char { .. }
+            | SchemaType::String { .. }
+            | SchemaType::Path { .. }
+            | SchemaType::Url { .. }
+            | SchemaType::Secret { .. }
+            | SchemaType::QuotaToken { .. } => scalar(ProtoScalar::String),
+
+            SchemaType::Datetime { .. } => {
+                self.imports.insert(TIMESTAMP_IMPORT.to_string());
+                singular(ProtoType::Named(TIMESTAMP.to_string()))
+            }
+            SchemaType::Duration { .. } => {
+                self.imports.insert(DURATION_IMPORT.to_string());
+                singular(ProtoType::Named(DURATION.to_string()))
+            }
+            SchemaType::Text { .. } => singular(self.helper(
+                "Text",
+                vec![
+                    ("text", ProtoLabel::Singular, ProtoScalar::String),
+                    ("language", ProtoLabel::Optional, ProtoScalar::String),
+                ],
+            )),
+            SchemaType::Binary { .. } => singular(self.helper(
+                "Binary",
+                vec![
+                    ("data", ProtoLabel::Singular, ProtoScalar::Bytes),
+                    ("mime_type", ProtoLabel::Optional, ProtoScalar::String),
+                ],
+            )),
+            SchemaType::Quantity { .. } => singular(self.helper(
+                "Quantity",
+                vec![
+                    ("mantissa", ProtoLabel::Singular, ProtoScalar::Int64),
+                    ("scale", ProtoLabel::Singular, ProtoScalar::Int32),
+                    ("unit", ProtoLabel::Singular, ProtoScalar::String),
+                ],
+            )),
+
+            SchemaType::Record { .. }
+            | SchemaType::Tuple { .. }
+            | SchemaType::Flags { .. }
+            | SchemaType::Variant { .. }
+            | SchemaType::Union { .. }
+            | SchemaType::Result { .. }
+            | SchemaType::Enum { .. } => {
+                let name = self.reserve(hint);
+                self.define(name.clone(), ty)?;
+                singular(ProtoType::Named(name))
+            }
+
+            SchemaType::List { element, .. } | SchemaType::FixedList { element, .. } => {
+                let item_hint = format!("{hint}_item");
+                let (label, item_ty) = self.field_type(element, &item_hint)?;
+                if label == ProtoLabel::Singular && !item_ty.is_map() {
+                    Ok((ProtoLabel::Repeated, item_ty))
+                } else {
+                    let wrapper = self.wrap(&item_hint, label, item_ty);
+                    Ok((ProtoLabel::Repeated, wrapper))
+                }
+            }
+
+            SchemaType::Map { key, value, .. } => {
+                let (key_label, key_ty) = self.field_type(key, &format!("{hint}_key"))?;
+                let (value_label, value_ty) = self.field_type(value, &format!("{hint}_value"))?;
+                match key_ty {
+                    ProtoType::Scalar(key_scalar)
+                        if key_label == ProtoLabel::Singular
+                            && key_scalar.is_map_key()
+                            && value_label == ProtoLabel::Singular
+                            && !value_ty.is_map() =>
+                    {
+                        singular(ProtoType::Map {
+                            key: key_scalar,
+                            value: Box::new(value_ty),
+                        })
+                    }
+                    key_ty => {
+                        let name = self.reserve(&format!("{hint}_entry"));
+                        self.push_message(
+                            name.clone(),
+                            None,
+                            None,
+                            vec![
+                                field("key", 1, key_label, key_ty, None),
+                                field("value", 2, value_label, value_ty, None),
+                            ],
+                        );
+                        Ok((ProtoLabel::Repeated, ProtoType::Named(name)))
+                    }
+                }
+            }
+
+            SchemaType::Option { inner, .. } => {
+                let (label, inner_ty) = self.field_type(inner, hint)?;
+                if label == ProtoLabel::Singular && !inner_ty.is_map() {
+                    Ok((ProtoLabel::Optional, inner_ty))
+                } else {
+                    let wrapper = self.wrap(&format!("{hint}_value"), label, inner_ty);
+                    Ok((ProtoLabel::Optional, wrapper))
+                }
+            }
+
+            SchemaType::Future { .. } | SchemaType::Stream { .. } => Err(RenderError::Unsupported(
+                "future and stream payloads have no protobuf mapping",
+            )),
+        }
+    }
+
+    /// Like [`Self::field_type`], for a `oneof` member, which can be neither
+    /// `optional`, `repeated` nor a map.
+    fn oneof_member(&mut self, ty: &SchemaType, hint: &str) -> Result<ProtoType, RenderError> {
+        let (label, member_ty) = self.field_type(ty, hint)?;
+        if label == ProtoLabel::Singular && !member_ty.is_map() {
+            Ok(member_ty)
+        } else {
+            Ok(self.wrap(&format!("{hint}_value"), label, member_ty))
+        }
+    }
+
+    /// Emit a message holding a single `value` field and return its type.
+    fn wrap(&mut self, hint: &str, label: ProtoLabel, ty: ProtoType) -> ProtoType {
+        let name = self.reserve(hint);
+        self.push_message(
+            name.clone(),
+            None,
+            None,
+            vec![field("value", 1, label, ty, None)],
+        );
+        ProtoType::Named(name)
+    }
+
+    fn empty(&mut self) -> ProtoType {
+        self.imports.insert(EMPTY_IMPORT.to_string());
+        ProtoType::Named(EMPTY.to_string())
+    }
+
+    /// The shared message for a canonical scalar with an object shape,
+    /// emitted the first time it is needed.
+    fn helper(
+        &mut self,
+        kind: &'static str,
+        fields: Vec<(&str, ProtoLabel, ProtoScalar)>,
+    ) -> ProtoType {
+        if let Some(name) = self.helpers.get(kind) {
+            return ProtoType::Named(name.clone());
+        }
+        let name = self.reserve(kind);
+        self.helpers.insert(kind, name.clone());
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(i, (field_name, label, scalar))| {
+                field(
+                    field_name,
+                    i as u32 + 1,
+                    label,
+                    ProtoType::Scalar(scalar),
+                    None,
+                )
+            })
+            .collect();
+        self.push_message(name.clone(), None, None, fields);
+        ProtoType::Named(name)
+    }
+}
+
+fn singular(ty: ProtoType) -> Result<(ProtoLabel, ProtoType), RenderError> {
+    Ok((ProtoLabel::Singular, ty))
+}
+
+fn scalar(scalar: ProtoScalar) -> Result<(ProtoLabel, ProtoType), RenderError> {
+    singular(ProtoType::Scalar(scalar))
+}
+
+fn message(
+    name: String,
+    doc: Option<String>,
+    oneof: Option<String>,
+    fields: Vec<ProtoField>,
+) -> ProtoDefinition {
+    ProtoDefinition::Message(ProtoMessage {
+        name,
+        doc,
+        oneof,
+        fields,
+    })
+}
+
+fn field(
+    name: &str,
+    number: u32,
+    label: ProtoLabel,
+    ty: ProtoType,
+    doc: Option<String>,
+) -> ProtoField {
+    ProtoField {
+        name: name.to_string(),
+        number,
+        label,
+        ty,
+        doc,
+    }
+}
+
+/// The element type of a list (or fixed-size list) result, looking through
+/// references to named definitions.
+pub(super) fn list_element<'a>(
+    graph: &'a SchemaGraph,
+    ty: &'a SchemaType,
+) -> Result<Option<&'a SchemaType>, RenderError> {
+    let mut current = ty;
+    let mut visited = HashSet::new();
+    loop {
+        match current {
+            SchemaType::Ref { id, .. } => {
+                if !visited.insert(id) {
+                    return Err(RenderError::Unsupported("cyclic chain of type references"));
+                }
+                let def = graph.lookup(id).ok_or(RenderError::Unsupported(
+                    "reference to a type missing from the agent's schema graph",
+                ))?;
+                current = &def.body;
+            }
+            SchemaType::List { element, .. } | SchemaType::FixedList { element, .. } => {
+                return Ok(Some(element));
+            }
+            _ => return Ok(None),
+        }
+    }
+}
+
+/// Attach `description` as the doc of a synthetic payload record.
+fn documented(mut ty: SchemaType, description: &str) -> SchemaType {
+    ty.metadata_mut().doc = non_empty(description);
+    ty
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+/// Return `base`, or `base` with the first free numeric suffix, and mark it
+/// as taken.
+fn unique(taken: &mut HashSet<String>, base: &str) -> String {
+    let mut candidate = base.to_string();
+    let mut counter = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{base}{counter}");
+        counter += 1;
+    }
+    candidate
+}
+
+/// Keep schema field names as they are where protobuf allows it, so the
+/// proto JSON mapping stays close to the canonical JSON form; replace any
+/// other character with `_`.
+fn field_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        out.insert(0, 'f');
+        out.insert(1, '_');
+    }
+    out
+}
+
+fn upper_snake(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_uppercase());
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+    let out = out.trim_end_matches('_').to_string();
+    if out.is_empty() {
+        "VALUE".to_string()
+    } else {
+        out
+    }
+}
+
+fn package_segment(name: &str) -> String {
+    let segment = upper_snake(name).to_ascii_lowercase();
+    if segment.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{segment}")
+    } else {
+        segment
+    }
+}
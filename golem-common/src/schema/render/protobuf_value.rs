@@ -0,0 +1,1294 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protobuf binary codec for agent payloads: convert between the wire form
+//! of the messages described by [`super::protobuf_idl`] and value trees.
+//!
+//! The codec works from the schema directly, applying the same field
+//! numbering, wrapper messages and well-known types as the IDL renderer, so
+//! a client generated from the rendered `.proto` file and this codec agree
+//! without a descriptor pool. Decoding follows proto3 semantics: absent
+//! fields take their default value, unknown fields are skipped, the last
+//! occurrence of a singular field (or `oneof` member) wins and repeated
+//! scalars are accepted both packed and unpacked. The encoder writes every
+//! present field, including defaults, and never packs.
+
+use crate::schema::agent::{InputSchema, OutputSchema};
+use crate::schema::canonical;
+use crate::schema::graph::SchemaGraph;
+use crate::schema::render::error::RenderError;
+use crate::schema::render::json_schema::user_supplied_record;
+use crate::schema::render::json_value::{type_name, value_name};
+use crate::schema::render::protobuf_idl::list_element;
+use crate::schema::schema_type::{NamedFieldType, QuantityValue, SchemaType};
+use crate::schema::schema_value::{
+    BinaryValuePayload, DurationValuePayload, ResultValuePayload, SchemaValue, TextValuePayload,
+    UnionValuePayload, VariantValuePayload,
+};
+use chrono::DateTime;
+
+/// Nesting limit for decoded messages, matching the default of the
+/// reference protobuf implementations.
+const MAX_DEPTH: usize = 100;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// The arguments of an agent rpc, as carried by a `<Method>Request` message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtoRequest {
+    /// Values of the constructor's user-supplied parameters, in declaration
+    /// order.
+    pub constructor: Vec<SchemaValue>,
+    pub phantom_id: Option<String>,
+    /// Values of the method's user-supplied parameters, in declaration
+    /// order.
+    pub input: Vec<SchemaValue>,
+}
+
+/// Decode a `<Method>Request` message.
+pub fn decode_request(
+    graph: &SchemaGraph,
+    constructor: &InputSchema,
+    input: &InputSchema,
+    bytes: &[u8],
+) -> Result<ProtoRequest, RenderError> {
+    let mut decoder = Decoder::new(graph);
+    let fields = Fields::parse(bytes)?;
+
+    decoder.path.push(".agent".to_string());
+    let constructor = decoder.parameters(constructor, fields.last_len(1)?)?;
+    decoder.path.pop();
+    let phantom_id = fields.string(2)?;
+    decoder.path.push(".input".to_string());
+    let input = decoder.parameters(input, fields.last_len(3)?)?;
+    decoder.path.pop();
+
+    Ok(ProtoRequest {
+        constructor,
+        phantom_id,
+        input,
+    })
+}
+
+/// Encode a `<Method>Request` message.
+pub fn encode_request(
+    graph: &SchemaGraph,
+    constructor: &InputSchema,
+    input: &InputSchema,
+    request: &ProtoRequest,
+) -> Result<Vec<u8>, RenderError> {
+    let mut encoder = Encoder::new(graph);
+    let mut out = Vec::new();
+
+    encoder.path.push(".agent".to_string());
+    let agent = encoder.parameters(constructor, &request.constructor)?;
+    encoder.path.pop();
+    write_len(1, &agent, &mut out);
+    if let Some(phantom_id) = &request.phantom_id {
+        write_len(2, phantom_id.as_bytes(), &mut out);
+    }
+    encoder.path.push(".input".to_string());
+    let input = encoder.parameters(input, &request.input)?;
+    encoder.path.pop();
+    write_len(3, &input, &mut out);
+
+    Ok(out)
+}
+
+/// Encode a `<Method>Output` message. `value` must be present exactly when
+/// the method returns one.
+pub fn encode_output(
+    graph: &SchemaGraph,
+    output: &OutputSchema,
+    value: Option<&SchemaValue>,
+) -> Result<Vec<u8>, RenderError> {
+    match (output, value) {
+        (OutputSchema::Unit, None) => Ok(Vec::new()),
+        (OutputSchema::Single(ty), Some(value)) => encode_value_message(graph, ty, value),
+        (OutputSchema::Unit, Some(_)) => Err(Encoder::new(graph)
+            .mismatch("the method returns nothing, but a value was given".to_string())),
+        (OutputSchema::Single(_), None) => Err(Encoder::new(graph)
+            .mismatch("the method returns a value, but none was given".to_string())),
+    }
+}
+
+/// Decode a `<Method>Output` message; `None` for methods returning nothing.
+pub fn decode_output(
+    graph: &SchemaGraph,
+    output: &OutputSchema,
+    bytes: &[u8],
+) -> Result<Option<SchemaValue>, RenderError> {
+    match output {
+        OutputSchema::Unit => {
+            Fields::parse(bytes)?;
+            Ok(None)
+        }
+        OutputSchema::Single(ty) => decode_value_message(graph, ty, bytes).map(Some),
+    }
+}
+
+/// Encode a message whose only field, `value = 1`, holds `value`: the shape
+/// of `<Method>Output` and of the `<Method>Item` messages a streaming rpc
+/// yields.
+pub fn encode_value_message(
+    graph: &SchemaGraph,
+    ty: &SchemaType,
+    value: &SchemaValue,
+) -> Result<Vec<u8>, RenderError> {
+    let mut out = Vec::new();
+    Encoder::new(graph).field(ty, value, 1, &mut out)?;
+    Ok(out)
+}
+
+/// Decode a message written by [`encode_value_message`].
+pub fn decode_value_message(
+    graph: &SchemaGraph,
+    ty: &SchemaType,
+    bytes: &[u8],
+) -> Result<SchemaValue, RenderError> {
+    let fields = Fields::parse(bytes)?;
+    Decoder::new(graph).field(ty, &fields.get(1))
+}
+
+/// Encode the `<Method>Item` messages a streaming rpc yields for `value`, the
+/// list returned by a method with a list (or fixed-size list) result.
+pub fn encode_stream_items(
+    graph: &SchemaGraph,
+    output: &OutputSchema,
+    value: &SchemaValue,
+) -> Result<Vec<Vec<u8>>, RenderError> {
+    let encoder = Encoder::new(graph);
+    let element = stream_element(graph, output)?
+        .ok_or_else(|| encoder.mismatch("the method does not return a list".to_string()))?;
+    let elements = match value {
+        SchemaValue::List { elements } | SchemaValue::FixedList { elements } => elements,
+        other => return Err(encoder.expected("a list", other)),
+    };
+    elements
+        .iter()
+        .map(|element_value| encode_value_message(graph, element, element_value))
+        .collect()
+}
+
+/// Decode one `<Method>Item` message of a streaming rpc.
+pub fn decode_stream_item(
+    graph: &SchemaGraph,
+    output: &OutputSchema,
+    bytes: &[u8],
+) -> Result<SchemaValue, RenderError> {
+    let element = stream_element(graph, output)?.ok_or_else(|| {
+        Decoder::new(graph).mismatch("the method does not return a list".to_string())
+    })?;
+    decode_value_message(graph, element, bytes)
+}
+
+fn stream_element<'a>(
+    graph: &'a SchemaGraph,
+    output: &'a OutputSchema,
+) -> Result<Option<&'a SchemaType>, RenderError> {
+    match output {
+        OutputSchema::Single(ty) => list_element(graph, ty),
+        OutputSchema::Unit => Ok(None),
+    }
+}
+
+// ------------------------------------------------------------------ layout
+
+/// Protobuf wire types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Wire {
+    Varint = 0,
+    Fixed64 = 1,
+    Len = 2,
+    Fixed32 = 5,
+}
+
+/// How a field holding a schema type is laid out, mirroring the IDL
+/// renderer's `field_type`.
+enum Shape<'a> {
+    Scalar(Wire),
+    Enum(usize),
+    Message(Body<'a>),
+    Repeated(&'a SchemaType),
+    Optional(&'a SchemaType),
+    Map(&'a SchemaType, &'a SchemaType),
+}
+
+impl Shape<'_> {
+    /// Whether the shape can be a list element, an `optional` field or a
+    /// `oneof` member without a wrapper message.
+    fn is_singular(&self) -> bool {
+        matches!(self, Shape::Scalar(_) | Shape::Enum(_) | Shape::Message(_))
+    }
+}
+
+/// The contents of a message, mirroring the IDL renderer's `define` and its
+/// well-known and helper types.
+#[derive(Copy, Clone)]
+enum Body<'a> {
+    Composite(&'a SchemaType),
+    Wrapper(&'a SchemaType),
+    Timestamp,
+    Duration,
+    Text,
+    Binary,
+    Quantity,
+}
+
+fn shape<'a>(graph: &'a SchemaGraph, ty: &'a SchemaType) -> Result<Shape<'a>, RenderError> {
+    Ok(match ty {
+        SchemaType::Ref { id, .. } => {
+            let def = graph.lookup(id).ok_or(RenderError::Unsupported(
+                "reference to a type missing from the agent's schema graph",
+            ))?;
+            match &def.body {
+                SchemaType::Enum { cases, .. } => Shape::Enum(cases.len()),
+                body if is_composite(body) => Shape::Message(Body::Composite(body)),
+                body => Shape::Message(Body::Wrapper(body)),
+            }
+        }
+
+        SchemaType::Bool { .. }
+        | SchemaType::S8 { .. }
+        | SchemaType::S16 { .. }
+        | SchemaType::S32 { .. }
+        | SchemaType::S64 { .. }
+        | SchemaType::U8 { .. }
+        | SchemaType::U16 { .. }
+        | SchemaType::U32 { .. }
+        | SchemaType::U64 { .. } => Shape::Scalar(Wire::Varint),
+        SchemaType::F32 { .. } => Shape::Scalar(Wire::Fixed32),
+        SchemaType::F64 { .. } => Shape::Scalar(Wire::Fixed64),
+        SchemaType::Char { .. }
+        | SchemaType::String { .. }
+        | SchemaType::Path { .. }
+        | SchemaType::Url { .. }
+        | SchemaType::Secret { .. }
+        | SchemaType::QuotaToken { .. } => Shape::Scalar(Wire::Len),
+
+        SchemaType::Datetime { .. } => Shape::Message(Body::Timestamp),
+        SchemaType::Duration { .. } => Shape::Message(Body::Duration),
+        SchemaType::Text { .. } => Shape::Message(Body::Text),
+        SchemaType::Binary { .. } => Shape::Message(Body::Binary),
+        SchemaType::Quantity { .. } => Shape::Message(Body::Quantity),
+
+        SchemaType::Enum { cases, .. } => Shape::Enum(cases.len()),
+        composite if is_composite(composite) => Shape::Message(Body::Composite(composite)),
+
+        SchemaType::List { element, .. } | SchemaType::FixedList { element, .. } => {
+            Shape::Repeated(element)
+        }
+        SchemaType::Map { key, value, .. } => Shape::Map(key, value),
+        SchemaType::Option { inner, .. } => Shape::Optional(inner),
+
+        _ => {
+            return Err(RenderError::Unsupported(
+                "future and stream payloads have no protobuf mapping",
+            ));
+        }
+    })
+}
+
+fn is_composite(ty: &SchemaType) -> bool {
+    matches!(
+        ty,
+        SchemaType::Record { .. }
+            | SchemaType::Tuple { .. }
+            | SchemaType::Flags { .. }
+            | SchemaType::Variant { .. }
+            | SchemaType::Union { .. }
+            | SchemaType::Result { .. }
+    )
+}
+
+// ------------------------------------------------------------------ encode
+
+struct Encoder<'g> {
+    graph: &'g SchemaGraph,
+    path: Vec<String>,
+}
+
+impl<'g> Encoder<'g> {
+    fn new(graph: &'g SchemaGraph) -> Self {
+        Self {
+            graph,
+            path: Vec::new(),
+        }
+    }
+
+    fn mismatch(&self, reason: String) -> RenderError {
+        RenderError::ValueMismatch {
+            path: render_path(&self.path),
+            reason,
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        input: &InputSchema,
+        values: &[SchemaValue],
+    ) -> Result<Vec<u8>, RenderError> {
+        let SchemaType::Record { fields, .. } = user_supplied_record(input) else {
+            unreachable!("user-supplied parameters project onto a record")
+        };
+        let mut out = Vec::new();
+        self.record(&fields, values, &mut out)?;
+        Ok(out)
+    }
+
+    /// Write `value` as field `number` of the enclosing message.
+    fn field(
+        &mut self,
+        ty: &SchemaType,
+        value: &SchemaValue,
+        number: u32,
+        out: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        match shape(self.graph, ty)? {
+            Shape::Scalar(_) => self.scalar(ty, value, number, out),
+            Shape::Enum(count) => match value {
+                SchemaValue::Enum { case } if (*case as usize) < count => {
+                    write_tag(number, Wire::Varint, out);
+                    write_varint(*case as u64 + 1, out);
+                    Ok(())
+                }
+                SchemaValue::Enum { case } => {
+                    Err(self.mismatch(format!("enum case {case} out of range ({count} cases)")))
+                }
+                other => Err(self.expected("enum", other)),
+            },
+            Shape::Message(body) => {
+                let bytes = self.body(body, value)?;
+                write_len(number, &bytes, out);
+                Ok(())
+            }
+            Shape::Repeated(element) => {
+                let elements = match (ty, value) {
+                    (SchemaType::List { .. }, SchemaValue::List { elements }) => elements,
+                    (SchemaType::FixedList { length, .. }, SchemaValue::FixedList { elements })
+                        if elements.len() == *length as usize =>
+                    {
+                        elements
+                    }
+                    (SchemaType::FixedList { length, .. }, SchemaValue::FixedList { elements }) => {
+                        return Err(self.mismatch(format!(
+                            "fixed list length: expected {length}, found {}",
+                            elements.len()
+                        )));
+                    }
+                    (_, other) => return Err(self.expected("list", other)),
+                };
+                for (i, element_value) in elements.iter().enumerate() {
+                    self.path.push(format!("[{i}]"));
+                    self.member(element, element_value, number, out)?;
+                    self.path.pop();
+                }
+                Ok(())
+            }
+            Shape::Optional(inner) => match value {
+                SchemaValue::Option { inner: Some(v) } => {
+                    self.path.push(".some".to_string());
+                    self.member(inner, v, number, out)?;
+                    self.path.pop();
+                    Ok(())
+                }
+                SchemaValue::Option { inner: None } => Ok(()),
+                other => Err(self.expected("option", other)),
+            },
+            Shape::Map(key, value_ty) => {
+                let SchemaValue::Map { entries } = value else {
+                    return Err(self.expected("map", value));
+                };
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    let mut entry = Vec::new();
+                    self.path.push(format!(".key[{i}]"));
+                    self.field(key, k, 1, &mut entry)?;
+                    self.path.pop();
+                    self.path.push(format!(".value[{i}]"));
+                    self.field(value_ty, v, 2, &mut entry)?;
+                    self.path.pop();
+                    write_len(number, &entry, out);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Write a list element, `optional` value or `oneof` member, going
+    /// through a `value = 1` wrapper message where protobuf cannot nest the
+    /// shape directly.
+    fn member(
+        &mut self,
+        ty: &SchemaType,
+        value: &SchemaValue,
+        number: u32,
+        out: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        if shape(self.graph, ty)?.is_singular() {
+            self.field(ty, value, number, out)
+        } else {
+            let mut wrapper = Vec::new();
+            self.field(ty, value, 1, &mut wrapper)?;
+            write_len(number, &wrapper, out);
+            Ok(())
+        }
+    }
+
+    fn scalar(
+        &mut self,
+        ty: &SchemaType,
+        value: &SchemaValue,
+        number: u32,
+        out: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        let varint = match (ty, value) {
+            (SchemaType::Bool { .. }, SchemaValue::Bool(b)) => *b as u64,
+            // int32 values are sign-extended to 64 bits on the wire.
+            (SchemaType::S8 { .. }, SchemaValue::S8(i)) => *i as i64 as u64,
+            (SchemaType::S16 { .. }, SchemaValue::S16(i)) => *i as i64 as u64,
+            (SchemaType::S32 { .. }, SchemaValue::S32(i)) => *i as i64 as u64,
+            (SchemaType::S64 { .. }, SchemaValue::S64(i)) => *i as u64,
+            (SchemaType::U8 { .. }, SchemaValue::U8(u)) => *u as u64,
+            (SchemaType::U16 { .. }, SchemaValue::U16(u)) => *u as u64,
+            (SchemaType::U32 { .. }, SchemaValue::U32(u)) => *u as u64,
+            (SchemaType::U64 { .. }, SchemaValue::U64(u)) => *u,
+            (SchemaType::F32 { .. }, SchemaValue::F32(f)) => {
+                write_tag(number, Wire::Fixed32, out);
+                out.extend_from_slice(&f.to_bits().to_le_bytes());
+                return Ok(());
+            }
+            (SchemaType::F64 { .. }, SchemaValue::F64(f)) => {
+                write_tag(number, Wire::Fixed64, out);
+                out.extend_from_slice(&f.to_bits().to_le_bytes());
+                return Ok(());
+            }
+            _ => {
+                let text = match (ty, value) {
+                    (SchemaType::Char { .. }, SchemaValue::Char(c)) => c.to_string(),
+                    (SchemaType::String { .. }, SchemaValue::String(s)) => s.clone(),
+                    (SchemaType::Path { .. }, SchemaValue::Path { path }) => {
+                        canonical::path::to_text(path)?
+                    }
+                    (SchemaType::Url { .. }, SchemaValue::Url { url }) => {
+                        canonical::url::to_text(url)?
+                    }
+                    (SchemaType::Secret { .. }, SchemaValue::Secret(p)) => {
+                        canonical::secret::to_text(p)?
+                    }
+                    (SchemaType::QuotaToken { .. }, SchemaValue::QuotaToken(p)) => {
+                        canonical::quota_token::to_text(p)?
+                    }
+                    (ty, value) => return Err(self.expected(type_name(ty), value)),
+                };
+                write_len(number, text.as_bytes(), out);
+                return Ok(());
+            }
+        };
+        write_tag(number, Wire::Varint, out);
+        write_varint(varint, out);
+        Ok(())
+    }
+
+    fn body(&mut self, body: Body<'_>, value: &SchemaValue) -> Result<Vec<u8>, RenderError> {
+        let mut out = Vec::new();
+        match (body, value) {
+            (
+                Body::Composite(SchemaType::Record { fields, .. }),
+                SchemaValue::Record { fields: vs },
+            ) => {
+                self.record(fields, vs, &mut out)?;
+            }
+            (
+                Body::Composite(SchemaType::Tuple { elements, .. }),
+                SchemaValue::Tuple { elements: vs },
+            ) => {
+                if elements.len() != vs.len() {
+                    return Err(self.mismatch(format!(
+                        "tuple arity: expected {}, found {}",
+                        elements.len(),
+                        vs.len()
+                    )));
+                }
+                for (i, (element, v)) in elements.iter().zip(vs).enumerate() {
+                    self.path.push(format!("[{i}]"));
+                    self.field(element, v, i as u32 + 1, &mut out)?;
+                    self.path.pop();
+                }
+            }
+            (Body::Composite(SchemaType::Flags { flags, .. }), SchemaValue::Flags { bits }) => {
+                if flags.len() != bits.len() {
+                    return Err(self.mismatch(format!(
+                        "flags arity: expected {}, found {}",
+                        flags.len(),
+                        bits.len()
+                    )));
+                }
+                for (i, bit) in bits.iter().enumerate() {
+                    write_tag(i as u32 + 1, Wire::Varint, &mut out);
+                    write_varint(*bit as u64, &mut out);
+                }
+            }
+            (
+                Body::Composite(SchemaType::Variant { cases, .. }),
+                SchemaValue::Variant(VariantValuePayload { case, payload }),
+            ) => {
+                let case_ty = cases.get(*case as usize).ok_or_else(|| {
+                    self.mismatch(format!(
+                        "variant case {case} out of range ({} cases)",
+                        cases.len()
+                    ))
+                })?;
+                self.path.push(format!(".{}", case_ty.name));
+                self.oneof_member(
+                    case_ty.payload.as_ref(),
+                    payload.as_deref(),
+                    *case + 1,
+                    &mut out,
+                )?;
+                self.path.pop();
+            }
+            (
+                Body::Composite(SchemaType::Union { spec, .. }),
+                SchemaValue::Union(UnionValuePayload { tag, body }),
+            ) => {
+                let index = spec
+                    .branches
+                    .iter()
+                    .position(|b| &b.tag == tag)
+                    .ok_or_else(|| self.mismatch(format!("unknown union branch `{tag}`")))?;
+                self.path.push(format!(".{tag}"));
+                self.member(&spec.branches[index].body, body, index as u32 + 1, &mut out)?;
+                self.path.pop();
+            }
+            (Body::Composite(SchemaType::Result { spec, .. }), SchemaValue::Result(result)) => {
+                let (segment, number, ty, v) = match result {
+                    ResultValuePayload::Ok { value } => (".ok", 1, &spec.ok, value),
+                    ResultValuePayload::Err { value } => (".err", 2, &spec.err, value),
+                };
+                self.path.push(segment.to_string());
+                self.oneof_member(ty.as_deref(), v.as_deref(), number, &mut out)?;
+                self.path.pop();
+            }
+            (Body::Composite(ty), value) => return Err(self.expected(type_name(ty), value)),
+            (Body::Wrapper(inner), value) => self.field(inner, value, 1, &mut out)?,
+            (Body::Timestamp, SchemaValue::Datetime { value }) => {
+                write_int(1, value.timestamp(), &mut out);
+                write_int(2, value.timestamp_subsec_nanos() as i64, &mut out);
+            }
+            (Body::Duration, SchemaValue::Duration(DurationValuePayload { nanoseconds })) => {
+                // Seconds and nanos share the sign of the duration.
+                write_int(1, nanoseconds / NANOS_PER_SECOND, &mut out);
+                write_int(2, nanoseconds % NANOS_PER_SECOND, &mut out);
+            }
+            (Body::Text, SchemaValue::Text(TextValuePayload { text, language })) => {
+                write_len(1, text.as_bytes(), &mut out);
+                if let Some(language) = language {
+                    write_len(2, language.as_bytes(), &mut out);
+                }
+            }
+            (Body::Binary, SchemaValue::Binary(BinaryValuePayload { bytes, mime_type })) => {
+                write_len(1, bytes, &mut out);
+                if let Some(mime_type) = mime_type {
+                    write_len(2, mime_type.as_bytes(), &mut out);
+                }
+            }
+            (
+                Body::Quantity,
+                SchemaValue::Quantity(QuantityValue {
+                    mantissa,
+                    scale,
+                    unit,
+                }),
+            ) => {
+                write_int(1, *mantissa, &mut out);
+                write_int(2, *scale as i64, &mut out);
+                write_len(3, unit.as_bytes(), &mut out);
+            }
+            (Body::Timestamp, value) => return Err(self.expected("datetime", value)),
+            (Body::Duration, value) => return Err(self.expected("duration", value)),
+            (Body::Text, value) => return Err(self.expected("text", value)),
+            (Body::Binary, value) => return Err(self.expected("binary", value)),
+            (Body::Quantity, value) => return Err(self.expected("quantity", value)),
+        }
+        Ok(out)
+    }
+
+    fn record(
+        &mut self,
+        fields: &[NamedFieldType],
+        values: &[SchemaValue],
+        out: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        if fields.len() != values.len() {
+            return Err(self.mismatch(format!(
+                "record arity: expected {}, found {}",
+                fields.len(),
+                values.len()
+            )));
+        }
+        for (i, (field, value)) in fields.iter().zip(values).enumerate() {
+            self.path.push(format!(".{}", field.name));
+            self.field(&field.body, value, i as u32 + 1, out)?;
+            self.path.pop();
+        }
+        Ok(())
+    }
+
+    /// Write a variant case or result arm; payload-less ones are an empty
+    /// `google.protobuf.Empty` message.
+    fn oneof_member(
+        &mut self,
+        ty: Option<&SchemaType>,
+        value: Option<&SchemaValue>,
+        number: u32,
+        out: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        match (ty, value) {
+            (Some(ty), Some(value)) => self.member(ty, value, number, out),
+            (None, None) => {
+                write_len(number, &[], out);
+                Ok(())
+            }
+            (Some(_), None) => Err(self.mismatch("missing payload".to_string())),
+            (None, Some(_)) => Err(self.mismatch("unexpected payload".to_string())),
+        }
+    }
+
+    fn expected(&self, expected: &str, found: &SchemaValue) -> RenderError {
+        self.mismatch(format!("expected {expected}, found {}", value_name(found)))
+    }
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_tag(number: u32, wire: Wire, out: &mut Vec<u8>) {
+    write_varint(((number as u64) << 3) | wire as u64, out);
+}
+
+fn write_len(number: u32, bytes: &[u8], out: &mut Vec<u8>) {
+    write_tag(number, Wire::Len, out);
+    write_varint(bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+/// Write an `int32` / `int64` field.
+fn write_int(number: u32, value: i64, out: &mut Vec<u8>) {
+    write_tag(number, Wire::Varint, out);
+    write_varint(value as u64, out);
+}
+
+// ------------------------------------------------------------------ decode
+
+/// One occurrence of a field on the wire.
+#[derive(Copy, Clone, Debug)]
+enum Raw<'b> {
+    Varint(u64),
+    Fixed64(u64),
+    Len(&'b [u8]),
+    Fixed32(u32),
+}
+
+impl Raw<'_> {
+    fn wire(&self) -> Wire {
+        match self {
+            Raw::Varint(_) => Wire::Varint,
+            Raw::Fixed64(_) => Wire::Fixed64,
+            Raw::Len(_) => Wire::Len,
+            Raw::Fixed32(_) => Wire::Fixed32,
+        }
+    }
+
+    /// The value an absent scalar field of this wire type decodes from.
+    fn default_for(wire: Wire) -> Raw<'static> {
+        match wire {
+            Wire::Varint => Raw::Varint(0),
+            Wire::Fixed64 => Raw::Fixed64(0),
+            Wire::Len => Raw::Len(&[]),
+            Wire::Fixed32 => Raw::Fixed32(0),
+        }
+    }
+}
+
+/// The fields of a message, in wire order.
+struct Fields<'b> {
+    entries: Vec<(u32, Raw<'b>)>,
+}
+
+impl<'b> Fields<'b> {
+    fn parse(bytes: &'b [u8]) -> Result<Self, RenderError> {
+        let mut reader = Reader { bytes, pos: 0 };
+        let mut entries = Vec::new();
+        while !reader.is_empty() {
+            let key = reader.varint()?;
+            let number = u32::try_from(key >> 3)
+                .ok()
+                .filter(|n| *n != 0)
+                .ok_or_else(|| {
+                    RenderError::Protobuf(format!("invalid field number {}", key >> 3))
+                })?;
+            let raw = match key & 7 {
+                0 => Raw::Varint(reader.varint()?),
+                1 => Raw::Fixed64(u64::from_le_bytes(reader.array()?)),
+                2 => {
+                    let len = reader.varint()?;
+                    Raw::Len(reader.take(len)?)
+                }
+                5 => Raw::Fixed32(u32::from_le_bytes(reader.array()?)),
+                3 | 4 => {
+                    return Err(RenderError::Protobuf(
+                        "groups are not supported".to_string(),
+                    ));
+                }
+                other => {
+                    return Err(RenderError::Protobuf(format!("invalid wire type {other}")));
+                }
+            };
+            entries.push((number, raw));
+        }
+        Ok(Self { entries })
+    }
+
+    fn get(&self, number: u32) -> Vec<Raw<'b>> {
+        self.entries
+            .iter()
+            .filter(|(n, _)| *n == number)
+            .map(|(_, raw)| *raw)
+            .collect()
+    }
+
+    fn last(&self, number: u32) -> Option<Raw<'b>> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == number)
+            .map(|(_, raw)| *raw)
+    }
+
+    /// The set member of a `oneof` numbered `1..=count`: the last one on the
+    /// wire.
+    fn oneof(&self, count: usize) -> Option<(u32, Raw<'b>)> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(n, _)| (*n as usize) <= count)
+            .copied()
+    }
+
+    /// A length-delimited field, empty when absent.
+    fn last_len(&self, number: u32) -> Result<&'b [u8], RenderError> {
+        match self.last(number) {
+            None => Ok(&[]),
+            Some(raw) => len(raw, number),
+        }
+    }
+
+    fn varint(&self, number: u32) -> Result<u64, RenderError> {
+        match self.last(number) {
+            None => Ok(0),
+            Some(Raw::Varint(v)) => Ok(v),
+            Some(raw) => Err(wire_mismatch(number, Wire::Varint, raw)),
+        }
+    }
+
+    fn string(&self, number: u32) -> Result<Option<String>, RenderError> {
+        self.last(number)
+            .map(|raw| utf8(len(raw, number)?))
+            .transpose()
+    }
+}
+
+struct Reader<'b> {
+    bytes: &'b [u8],
+    pos: usize,
+}
+
+impl<'b> Reader<'b> {
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn varint(&mut self) -> Result<u64, RenderError> {
+        let mut value = 0u64;
+        for shift in (0..70).step_by(7) {
+            let byte = *self
+                .bytes
+                .get(self.pos)
+                .ok_or_else(|| RenderError::Protobuf("truncated varint".to_string()))?;
+            self.pos += 1;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte < 0x80 {
+                return Ok(value);
+            }
+        }
+        Err(RenderError::Protobuf(
+            "varint longer than 10 bytes".to_string(),
+        ))
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'b [u8], RenderError> {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.pos.checked_add(len))
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| RenderError::Protobuf("truncated length-delimited field".to_string()))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], RenderError> {
+        let bytes = self
+            .take(N as u64)
+            .map_err(|_| RenderError::Protobuf("truncated fixed-width field".to_string()))?;
+        Ok(bytes.try_into().expect("slice of requested length"))
+    }
+}
+
+fn len(raw: Raw<'_>, number: u32) -> Result<&[u8], RenderError> {
+    match raw {
+        Raw::Len(bytes) => Ok(bytes),
+        raw => Err(wire_mismatch(number, Wire::Len, raw)),
+    }
+}
+
+fn utf8(bytes: &[u8]) -> Result<String, RenderError> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| RenderError::Protobuf("string field is not valid UTF-8".to_string()))
+}
+
+fn wire_mismatch(number: u32, expected: Wire, found: Raw<'_>) -> RenderError {
+    RenderError::Protobuf(format!(
+        "field {number}: expected wire type {}, found {}",
+        expected as u8,
+        found.wire() as u8
+    ))
+}
+
+struct Decoder<'g> {
+    graph: &'g SchemaGraph,
+    path: Vec<String>,
+    depth: usize,
+}
+
+impl<'g> Decoder<'g> {
+    fn new(graph: &'g SchemaGraph) -> Self {
+        Self {
+            graph,
+            path: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    fn mismatch(&self, reason: String) -> RenderError {
+        RenderError::ValueMismatch {
+            path: render_path(&self.path),
+            reason,
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        input: &InputSchema,
+        bytes: &[u8],
+    ) -> Result<Vec<SchemaValue>, RenderError> {
+        let SchemaType::Record { fields, .. } = user_supplied_record(input) else {
+            unreachable!("user-supplied parameters project onto a record")
+        };
+        self.record(&fields, &Fields::parse(bytes)?)
+    }
+
+    /// Read the field holding `ty` from all of its occurrences in the
+    /// enclosing message.
+    fn field(
+        &mut self,
+        ty: &SchemaType,
+        occurrences: &[Raw<'_>],
+    ) -> Result<SchemaValue, RenderError> {
+        match shape(self.graph, ty)? {
+            Shape::Scalar(wire) => {
+                let raw = occurrences
+                    .last()
+                    .copied()
+                    .unwrap_or_else(|| Raw::default_for(wire));
+                self.scalar(ty, raw)
+            }
+            Shape::Enum(count) => {
+                let number = match occurrences.last() {
+                    None => 0,
+                    Some(Raw::Varint(v)) => *v as i32,
+                    Some(raw) => return Err(self.wire_mismatch(Wire::Varint, *raw)),
+                };
+                if number < 1 || number as usize > count {
+                    return Err(self.mismatch(format!(
+                        "enum value {number} is unspecified or unknown ({count} cases)"
+                    )));
+                }
+                Ok(SchemaValue::Enum {
+                    case: number as u32 - 1,
+                })
+            }
+            Shape::Message(body) => {
+                let bytes = match occurrences.last() {
+                    None => &[][..],
+                    Some(Raw::Len(bytes)) => *bytes,
+                    Some(raw) => return Err(self.wire_mismatch(Wire::Len, *raw)),
+                };
+                self.body(body, bytes)
+            }
+            Shape::Repeated(element) => {
+                let element_shape = shape(self.graph, element)?;
+                let mut elements = Vec::with_capacity(occurrences.len());
+                for raw in occurrences {
+                    match (&element_shape, raw) {
+                        (Shape::Scalar(wire), Raw::Len(bytes)) if *wire != Wire::Len => {
+                            for packed in unpack(*wire, bytes)? {
+                                self.path.push(format!("[{}]", elements.len()));
+                                elements.push(self.scalar(element, packed)?);
+                                self.path.pop();
+                            }
+                        }
+                        (Shape::Enum(_), Raw::Len(bytes)) => {
+                            for packed in unpack(Wire::Varint, bytes)? {
+                                self.path.push(format!("[{}]", elements.len()));
+                                elements.push(self.field(element, &[packed])?);
+                                self.path.pop();
+                            }
+                        }
+                        _ => {
+                            self.path.push(format!("[{}]", elements.len()));
+                            elements.push(self.member(element, *raw)?);
+                            self.path.pop();
+                        }
+                    }
+                }
+                match ty {
+                    SchemaType::FixedList { length, .. } if elements.len() != *length as usize => {
+                        Err(self.mismatch(format!(
+                            "fixed list length: expected {length}, found {}",
+                            elements.len()
+                        )))
+                    }
+                    SchemaType::FixedList { .. } => Ok(SchemaValue::FixedList { elements }),
+                    _ => Ok(SchemaValue::List { elements }),
+                }
+            }
+            Shape::Optional(inner) => match occurrences.last() {
+                None => Ok(SchemaValue::Option { inner: None }),
+                Some(raw) => {
+                    self.path.push(".some".to_string());
+                    let value = self.member(inner, *raw)?;
+                    self.path.pop();
+                    Ok(SchemaValue::Option {
+                        inner: Some(Box::new(value)),
+                    })
+                }
+            },
+            Shape::Map(key, value_ty) => {
+                let mut entries = Vec::with_capacity(occurrences.len());
+                for (i, raw) in occurrences.iter().enumerate() {
+                    let bytes = match raw {
+                        Raw::Len(bytes) => *bytes,
+                        raw => return Err(self.wire_mismatch(Wire::Len, *raw)),
+                    };
+                    let entry = self.nested(bytes)?;
+                    self.path.push(format!(".key[{i}]"));
+                    let k = self.field(key, &entry.get(1))?;
+                    self.path.pop();
+                    self.path.push(format!(".value[{i}]"));
+                    let v = self.field(value_ty, &entry.get(2))?;
+                    self.path.pop();
+                    self.depth -= 1;
+                    entries.push((k, v));
+                }
+                Ok(SchemaValue::Map { entries })
+            }
+        }
+    }
+
+    /// Read a list element, `optional` value or `oneof` member, unwrapping
+    /// the `value = 1` wrapper message of shapes protobuf cannot nest.
+    fn member(&mut self, ty: &SchemaType, raw: Raw<'_>) -> Result<SchemaValue, RenderError> {
+        if shape(self.graph, ty)?.is_singular() {
+            self.field(ty, &[raw])
+        } else {
+            let bytes = match raw {
+                Raw::Len(bytes) => bytes,
+                raw => return Err(self.wire_mismatch(Wire::Len, raw)),
+            };
+            let wrapper = self.nested(bytes)?;
+            let value = self.field(ty, &wrapper.get(1))?;
+            self.depth -= 1;
+            Ok(value)
+        }
+    }
+
+    /// Parse a nested message, entering one level of nesting; callers leave
+    /// it once done with the fields.
+    fn nested<'b>(&mut self, bytes: &'b [u8]) -> Result<Fields<'b>, RenderError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(RenderError::Protobuf(format!(
+                "messages nested deeper than {MAX_DEPTH} levels"
+            )));
+        }
+        let fields = Fields::parse(bytes)?;
+        self.depth += 1;
+        Ok(fields)
+    }
+
+    fn scalar(&self, ty: &SchemaType, raw: Raw<'_>) -> Result<SchemaValue, RenderError> {
+        let out_of_range = |this: &Self, v: i128| {
+            this.mismatch(format!("{v} is out of range for {}", type_name(ty)))
+        };
+        Ok(match (ty, raw) {
+            (SchemaType::Bool { .. }, Raw::Varint(v)) => SchemaValue::Bool(v != 0),
+            // int32 values arrive sign-extended; the low 32 bits carry them.
+            (SchemaType::S8 { .. }, Raw::Varint(v)) => SchemaValue::S8(
+                i8::try_from(v as i32).map_err(|_| out_of_range(self, (v as i32).into()))?,
+            ),
+            (SchemaType::S16 { .. }, Raw::Varint(v)) => SchemaValue::S16(
+                i16::try_from(v as i32).map_err(|_| out_of_range(self, (v as i32).into()))?,
+            ),
+            (SchemaType::S32 { .. }, Raw::Varint(v)) => SchemaValue::S32(v as i32),
+            (SchemaType::S64 { .. }, Raw::Varint(v)) => SchemaValue::S64(v as i64),
+            (SchemaType::U8 { .. }, Raw::Varint(v)) => {
+                SchemaValue::U8(u8::try_from(v).map_err(|_| out_of_range(self, v.into()))?)
+            }
+            (SchemaType::U16 { .. }, Raw::Varint(v)) => {
+                SchemaValue::U16(u16::try_from(v).map_err(|_| out_of_range(self, v.into()))?)
+            }
+            (SchemaType::U32 { .. }, Raw::Varint(v)) => {
+                SchemaValue::U32(u32::try_from(v).map_err(|_| out_of_range(self, v.into()))?)
+            }
+            (SchemaType::U64 { .. }, Raw::Varint(v)) => SchemaValue::U64(v),
+            (SchemaType::F32 { .. }, Raw::Fixed32(bits)) => SchemaValue::F32(f32::from_bits(bits)),
+            (SchemaType::F64 { .. }, Raw::Fixed64(bits)) => SchemaValue::F64(f64::from_bits(bits)),
+            (_, Raw::Len(bytes)) => {
+                let text = utf8(bytes)?;
+                match ty {
+                    SchemaType::Char { .. } => {
+                        let mut chars = text.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) => SchemaValue::Char(c),
+                            _ => {
+                                return Err(self.mismatch(format!(
+                                    "expected a single character, found {text:?}"
+                                )));
+                            }
+                        }
+                    }
+                    SchemaType::String { .. } => SchemaValue::String(text),
+                    SchemaType::Path { .. } => SchemaValue::Path {
+                        path: canonical::path::from_text(&text)?,
+                    },
+                    SchemaType::Url { .. } => SchemaValue::Url {
+                        url: canonical::url::from_text(&text)?,
+                    },
+                    SchemaType::Secret { .. } => {
+                        SchemaValue::Secret(canonical::secret::from_text(&text)?)
+                    }
+                    SchemaType::QuotaToken { .. } => {
+                        SchemaValue::QuotaToken(canonical::quota_token::from_text(&text)?)
+                    }
+                    _ => return Err(self.wire_mismatch(expected_wire(ty), raw)),
+                }
+            }
+            (ty, raw) => return Err(self.wire_mismatch(expected_wire(ty), raw)),
+        })
+    }
+
+    fn body(&mut self, body: Body<'_>, bytes: &[u8]) -> Result<SchemaValue, RenderError> {
+        let fields = self.nested(bytes)?;
+        let value = self.body_fields(body, &fields);
+        self.depth -= 1;
+        value
+    }
+
+    fn body_fields(
+        &mut self,
+        body: Body<'_>,
+        fields: &Fields<'_>,
+    ) -> Result<SchemaValue, RenderError> {
+        Ok(match body {
+            Body::Composite(SchemaType::Record { fields: record, .. }) => SchemaValue::Record {
+                fields: self.record(record, fields)?,
+            },
+            Body::Composite(SchemaType::Tuple { elements, .. }) => {
+                let mut values = Vec::with_capacity(elements.len());
+                for (i, element) in elements.iter().enumerate() {
+                    self.path.push(format!("[{i}]"));
+                    values.push(self.field(element, &fields.get(i as u32 + 1))?);
+                    self.path.pop();
+                }
+                SchemaValue::Tuple { elements: values }
+            }
+            Body::Composite(SchemaType::Flags { flags, .. }) => SchemaValue::Flags {
+                bits: (1..=flags.len() as u32)
+                    .map(|number| fields.varint(number).map(|v| v != 0))
+                    .collect::<Result<_, _>>()?,
+            },
+            Body::Composite(SchemaType::Variant { cases, .. }) => {
+                let (number, raw) = fields
+                    .oneof(cases.len())
+                    .ok_or_else(|| self.mismatch("no variant case is set".to_string()))?;
+                let case = &cases[number as usize - 1];
+                self.path.push(format!(".{}", case.name));
+                let payload = self.oneof_member(case.payload.as_ref(), raw)?;
+                self.path.pop();
+                SchemaValue::Variant(VariantValuePayload {
+                    case: number - 1,
+                    payload,
+                })
+            }
+            Body::Composite(SchemaType::Union { spec, .. }) => {
+                let (number, raw) = fields
+                    .oneof(spec.branches.len())
+                    .ok_or_else(|| self.mismatch("no union branch is set".to_string()))?;
+                let branch = &spec.branches[number as usize - 1];
+                self.path.push(format!(".{}", branch.tag));
+                let body = self.member(&branch.body, raw)?;
+                self.path.pop();
+                SchemaValue::Union(UnionValuePayload {
+                    tag: branch.tag.clone(),
+                    body: Box::new(body),
+                })
+            }
+            Body::Composite(SchemaType::Result { spec, .. }) => {
+                let (number, raw) = fields
+                    .oneof(2)
+                    .ok_or_else(|| self.mismatch("neither ok nor err is set".to_string()))?;
+                if number == 1 {
+                    self.path.push(".ok".to_string());
+                    let value = self.oneof_member(spec.ok.as_deref(), raw)?;
+                    self.path.pop();
+                    SchemaValue::Result(ResultValuePayload::Ok { value })
+                } else {
+                    self.path.push(".err".to_string());
+                    let value = self.oneof_member(spec.err.as_deref(), raw)?;
+                    self.path.pop();
+                    SchemaValue::Result(ResultValuePayload::Err { value })
+                }
+            }
+            Body::Composite(ty) => unreachable!("{} is not a composite", type_name(ty)),
+            Body::Wrapper(inner) => self.field(inner, &fields.get(1))?,
+            Body::Timestamp => {
+                let seconds = fields.varint(1)? as i64;
+                let nanos = u32::try_from(fields.varint(2)? as i32)
+                    .map_err(|_| self.mismatch("negative timestamp nanos".to_string()))?;
+                let value = DateTime::from_timestamp(seconds, nanos)
+                    .ok_or_else(|| self.mismatch("timestamp out of range".to_string()))?;
+                SchemaValue::Datetime { value }
+            }
+            Body::Duration => {
+                let seconds = fields.varint(1)? as i64;
+                let nanos = fields.varint(2)? as i32;
+                let nanoseconds = seconds
+                    .checked_mul(NANOS_PER_SECOND)
+                    .and_then(|ns| ns.checked_add(nanos as i64))
+                    .ok_or_else(|| self.mismatch("duration out of range".to_string()))?;
+                SchemaValue::Duration(DurationValuePayload { nanoseconds })
+            }
+            Body::Text => SchemaValue::Text(TextValuePayload {
+                text: fields.string(1)?.unwrap_or_default(),
+                language: fields.string(2)?,
+            }),
+            Body::Binary => SchemaValue::Binary(BinaryValuePayload {
+                bytes: fields.last_len(1)?.to_vec(),
+                mime_type: fields.string(2)?,
+            }),
+            Body::Quantity => SchemaValue::Quantity(QuantityValue {
+                mantissa: fields.varint(1)? as i64,
+                scale: fields.varint(2)? as i32,
+                unit: fields.string(3)?.unwrap_or_default(),
+            }),
+        })
+    }
+
+    fn record(
+        &mut self,
+        record: &[NamedFieldType],
+        fields: &Fields<'_>,
+    ) -> Result<Vec<SchemaValue>, RenderError> {
+        let mut values = Vec::with_capacity(record.len());
+        for (i, field) in record.iter().enumerate() {
+            self.path.push(format!(".{}", field.name));
+            values.push(self.field(&field.body, &fields.get(i as u32 + 1))?);
+            self.path.pop();
+        }
+        Ok(values)
+    }
+
+    /// Read a variant case or result arm; the `google.protobuf.Empty` of a
+    /// payload-less one is skipped.
+    fn oneof_member(
+        &mut self,
+        ty: Option<&SchemaType>,
+        raw: Raw<'_>,
+    ) -> Result<Option<Box<SchemaValue>>, RenderError> {
+        match ty {
+            Some(ty) => Ok(Some(Box::new(self.member(ty, raw)?))),
+            None => Ok(None),
+        }
+    }
+
+    fn wire_mismatch(&self, expected: Wire, found: Raw<'_>) -> RenderError {
+        self.mismatch(format!(
+            "expected wire type {}, found {}",
+            expected as u8,
+            found.wire() as u8
+        ))
+    }
+}
+
+/// Split a packed repeated field into its elements.
+fn unpack(wire: Wire, bytes: &[u8]) -> Result<Vec<Raw<'_>>, RenderError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let mut values = Vec::new();
+    while !reader.is_empty() {
+        values.push(match wire {
+            Wire::Varint => Raw::Varint(reader.varint()?),
+            Wire::Fixed64 => Raw::Fixed64(u64::from_le_bytes(reader.array()?)),
+            Wire::Fixed32 => Raw::Fixed32(u32::from_le_bytes(reader.array()?)),
+            Wire::Len => unreachable!("length-delimited fields are never packed"),
+        });
+    }
+    Ok(values)
+}
+
+fn expected_wire(ty: &SchemaType) -> Wire {
+    match ty {
+        SchemaType::F32 { .. } => Wire::Fixed32,
+        SchemaType::F64 { .. } => Wire::Fixed64,
+        SchemaType::Char { .. }
+        | SchemaType::String { .. }
+        | SchemaType::Path { .. }
+        | SchemaType::Url { .. }
+        | SchemaType::Secret { .. }
+        | SchemaType::QuotaToken { .. } => Wire::Len,
+        _ => Wire::Varint,
+    }
+}
+
+fn render_path(segments: &[String]) -> String {
+    if segments.is_empty() {
+        "$".to_string()
+    } else {
+        segments.concat()
+    }
+}
@@ -775,8 +775,8 @@ mod agent_entry_points {
     };
     use crate::schema::metadata::Role;
     use crate::schema::render::json_schema::{
-        JsonSchemaConfig, input_schema_to_json_schema, output_schema_to_json_schema,
-        to_json_schema_with_config,
+        JsonSchemaConfig, agent_type_to_json_schema, input_schema_to_json_schema,
+        output_schema_to_json_schema, to_json_schema_with_config,
     };
     use test_r::test;
 
@@ -988,4 +988,111 @@ mod agent_entry_points {
             "multimodal must not synthesise per-branch $defs: {doc}"
         );
     }
+
+    #[test]
+    fn agent_type_bundle_collects_payloads_in_defs() {
+        use crate::base_model::Empty;
+        use crate::base_model::agent::{AgentMode, AgentTypeName, Snapshotting};
+        use crate::schema::agent::{AgentConstructorSchema, AgentMethodSchema, AgentTypeSchema};
+        use crate::schema::graph::SchemaTypeDef;
+        use crate::schema::metadata::TypeId;
+
+        let method = |name: &str, input: Vec<NamedField>, output: OutputSchema| AgentMethodSchema {
+            name: name.to_string(),
+            description: String::new(),
+            prompt_hint: None,
+            input_schema: InputSchema::Parameters(input),
+            output_schema: output,
+            http_endpoint: vec![],
+            read_only: None,
+            timeout_nanos: None,
+        };
+        let agent = AgentTypeSchema {
+            type_name: AgentTypeName("shop-agent".to_string()),
+            description: "A shop".to_string(),
+            source_language: String::new(),
+            schema: SchemaGraph {
+                defs: vec![SchemaTypeDef {
+                    id: TypeId::new("item"),
+                    name: None,
+                    body: SchemaType::record(vec![NamedFieldType {
+                        name: "sku".to_string(),
+                        body: SchemaType::string(),
+                        metadata: Default::default(),
+                    }]),
+                }],
+                ..SchemaGraph::empty()
+            },
+            constructor: AgentConstructorSchema {
+                name: None,
+                description: String::new(),
+                prompt_hint: None,
+                input_schema: InputSchema::Parameters(vec![
+                    NamedField::user_supplied("region", SchemaType::string()),
+                    NamedField::auto_injected(
+                        "principal",
+                        AutoInjectedKind::Principal,
+                        SchemaType::string(),
+                    ),
+                ]),
+            },
+            methods: vec![
+                method(
+                    "add-item",
+                    vec![NamedField::user_supplied(
+                        "item",
+                        SchemaType::ref_to(TypeId::new("item")),
+                    )],
+                    OutputSchema::Unit,
+                ),
+                method(
+                    "list-items",
+                    vec![],
+                    OutputSchema::Single(Box::new(SchemaType::list(SchemaType::ref_to(
+                        TypeId::new("item"),
+                    )))),
+                ),
+            ],
+            dependencies: vec![],
+            mode: AgentMode::Durable,
+            http_mount: None,
+            snapshotting: Snapshotting::Disabled(Empty {}),
+            config: vec![],
+        };
+
+        let doc = agent_type_to_json_schema(&agent);
+        assert_eq!(doc["title"], json!("shop-agent"));
+        assert!(doc["$schema"].is_string());
+
+        let annotation = &doc["x-golem-agent"];
+        assert_eq!(
+            annotation["constructor"],
+            json!("#/$defs/ShopAgentConstructor")
+        );
+        assert_eq!(
+            annotation["methods"]["add-item"]["input"],
+            json!("#/$defs/AddItemInput")
+        );
+        assert_eq!(annotation["methods"]["add-item"]["output"], Value::Null);
+        assert_eq!(
+            annotation["methods"]["list-items"]["output"],
+            json!("#/$defs/ListItemsOutput")
+        );
+
+        let constructor = &doc["$defs"]["ShopAgentConstructor"];
+        assert!(constructor["properties"].get("region").is_some());
+        assert!(
+            constructor["properties"].get("principal").is_none(),
+            "auto-injected fields must not be surfaced: {doc}"
+        );
+        assert_eq!(
+            doc["$defs"]["AddItemInput"]["properties"]["item"]["$ref"],
+            json!("#/$defs/item")
+        );
+        assert_eq!(
+            doc["$defs"]["ListItemsOutput"]["items"]["$ref"],
+            json!("#/$defs/item")
+        );
+        assert!(doc["$defs"]["item"].is_object());
+    }
 }
@@ -19,4 +19,6 @@ mod json_schema_tests;
 mod json_value_tests;
 mod openapi_tests;
 mod paired_strategy;
+mod protobuf_idl_tests;
+mod protobuf_value_tests;
 mod walker_tests;
@@ -0,0 +1,382 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::base_model::Empty;
+use crate::base_model::agent::{AgentMode, AgentTypeName, Snapshotting};
+use crate::schema::agent::{
+    AgentConstructorSchema, AgentMethodSchema, AgentTypeSchema, AutoInjectedKind, InputSchema,
+    NamedField, OutputSchema,
+};
+use crate::schema::graph::{SchemaGraph, SchemaTypeDef};
+use crate::schema::metadata::{MetadataEnvelope, TypeId};
+use crate::schema::render::error::RenderError;
+use crate::schema::render::protobuf_idl::{
+    ProtoDefinition, ProtoLabel, ProtoMessage, ProtoType, agent_type_to_proto_file,
+    agent_type_to_protobuf,
+};
+use crate::schema::schema_type::{NamedFieldType, ResultSpec, SchemaType};
+use test_r::test;
+
+fn method(
+    name: &str,
+    description: &str,
+    input: Vec<NamedField>,
+    output: OutputSchema,
+) -> AgentMethodSchema {
+    AgentMethodSchema {
+        name: name.to_string(),
+        description: description.to_string(),
+        prompt_hint: None,
+        input_schema: InputSchema::Parameters(input),
+        output_schema: output,
+        http_endpoint: vec![],
+        read_only: None,
+        timeout_nanos: None,
+    }
+}
+
+fn agent_type(
+    defs: Vec<SchemaTypeDef>,
+    constructor: Vec<NamedField>,
+    methods: Vec<AgentMethodSchema>,
+) -> AgentTypeSchema {
+    AgentTypeSchema {
+        type_name: AgentTypeName("shop-agent".to_string()),
+        description: "A shop".to_string(),
+        source_language: String::new(),
+        schema: SchemaGraph {
+            defs,
+            ..SchemaGraph::empty()
+        },
+        constructor: AgentConstructorSchema {
+            name: None,
+            description: "Creates a shop".to_string(),
+            prompt_hint: None,
+            input_schema: InputSchema::Parameters(constructor),
+        },
+        methods,
+        dependencies: vec![],
+        mode: AgentMode::Durable,
+        http_mount: None,
+        snapshotting: Snapshotting::Disabled(Empty {}),
+        config: vec![],
+    }
+}
+
+fn field(name: &str, body: SchemaType) -> NamedFieldType {
+    NamedFieldType {
+        name: name.to_string(),
+        body,
+        metadata: MetadataEnvelope::default(),
+    }
+}
+
+fn message<'a>(definitions: &'a [ProtoDefinition], name: &str) -> &'a ProtoMessage {
+    definitions
+        .iter()
+        .find_map(|d| match d {
+            ProtoDefinition::Message(m) if m.name == name => Some(m),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("message {name} not found in {definitions:?}"))
+}
+
+fn shop_agent() -> AgentTypeSchema {
+    agent_type(
+        vec![
+            SchemaTypeDef {
+                id: TypeId::new("item"),
+                name: Some("Item".to_string()),
+                body: SchemaType::record(vec![
+                    field("sku", SchemaType::string()),
+                    field("qty", SchemaType::u32()),
+                    field(
+                        "tags",
+                        SchemaType::list(SchemaType::list(SchemaType::string())),
+                    ),
+                ]),
+            },
+            SchemaTypeDef {
+                id: TypeId::new("color"),
+                name: Some("Color".to_string()),
+                body: SchemaType::Enum {
+                    cases: vec!["red".to_string(), "dark-blue".to_string()],
+                    metadata: MetadataEnvelope::default(),
+                },
+            },
+        ],
+        vec![
+            NamedField::user_supplied("region", SchemaType::string()),
+            NamedField::auto_injected(
+                "principal",
+                AutoInjectedKind::Principal,
+                SchemaType::string(),
+            ),
+        ],
+        vec![
+            method(
+                "add-item",
+                "Adds an item",
+                vec![
+                    NamedField::user_supplied("item", SchemaType::ref_to(TypeId::new("item"))),
+                    NamedField::user_supplied("note", SchemaType::option(SchemaType::string())),
+                    NamedField::user_supplied("color", SchemaType::ref_to(TypeId::new("color"))),
+                ],
+                OutputSchema::Single(Box::new(SchemaType::result(ResultSpec {
+                    ok: None,
+                    err: Some(Box::new(SchemaType::string())),
+                }))),
+            ),
+            method(
+                "list-items",
+                "",
+                vec![],
+                OutputSchema::Single(Box::new(SchemaType::map(
+                    SchemaType::string(),
+                    SchemaType::ref_to(TypeId::new("item")),
+                ))),
+            ),
+        ],
+    )
+}
+
+#[test]
+fn agent_type_renders_to_proto3_idl() {
+    let idl = agent_type_to_protobuf(&shop_agent()).unwrap();
+    let expected = r#"syntax = "proto3";
+
+package golem.agent.shop_agent;
+
+import "google/protobuf/empty.proto";
+
+// A shop
+service ShopAgent {
+  // Adds an item
+  rpc AddItem(AddItemRequest) returns (AddItemOutput);
+  rpc ListItems(ListItemsRequest) returns (ListItemsOutput);
+}
+
+message Item {
+  string sku = 1;
+  uint32 qty = 2;
+  repeated ItemTagsItem tags = 3;
+}
+
+message ItemTagsItem {
+  repeated string value = 1;
+}
+
+enum Color {
+  COLOR_UNSPECIFIED = 0;
+  COLOR_RED = 1;
+  COLOR_DARK_BLUE = 2;
+}
+
+// Creates a shop
+message Constructor {
+  string region = 1;
+}
+
+// Adds an item
+message AddItemInput {
+  Item item = 1;
+  optional string note = 2;
+  Color color = 3;
+}
+
+message AddItemOutputValue {
+  oneof result {
+    google.protobuf.Empty ok = 1;
+    string err = 2;
+  }
+}
+
+message AddItemOutput {
+  AddItemOutputValue value = 1;
+}
+
+// Invocation of `add-item`.
+message AddItemRequest {
+  // Constructor parameters identifying the agent.
+  Constructor agent = 1;
+  optional string phantom_id = 2;
+  AddItemInput input = 3;
+}
+
+message ListItemsInput {}
+
+message ListItemsOutput {
+  map<string, Item> value = 1;
+}
+
+// Invocation of `list-items`.
+message ListItemsRequest {
+  // Constructor parameters identifying the agent.
+  Constructor agent = 1;
+  optional string phantom_id = 2;
+  ListItemsInput input = 3;
+}
+"#;
+    assert_eq!(idl, expected);
+}
+
+#[test]
+fn rpcs_keep_the_original_method_names() {
+    let file = agent_type_to_proto_file(&shop_agent()).unwrap();
+    let methods: Vec<(&str, &str)> = file
+        .service
+        .rpcs
+        .iter()
+        .map(|rpc| (rpc.name.as_str(), rpc.method.as_str()))
+        .collect();
+    assert_eq!(
+        methods,
+        vec![("AddItem", "add-item"), ("ListItems", "list-items")]
+    );
+}
+
+#[test]
+fn definitions_colliding_with_payload_messages_are_suffixed() {
+    let agent = agent_type(
+        vec![SchemaTypeDef {
+            id: TypeId::new("constructor"),
+            name: Some("Constructor".to_string()),
+            body: SchemaType::record(vec![field("id", SchemaType::u64())]),
+        }],
+        vec![NamedField::user_supplied(
+            "config",
+            SchemaType::ref_to(TypeId::new("constructor")),
+        )],
+        vec![],
+    );
+    let file = agent_type_to_proto_file(&agent).unwrap();
+    let constructor = message(&file.definitions, "Constructor");
+    assert_eq!(
+        constructor.fields[0].ty,
+        ProtoType::Named("Constructor2".to_string())
+    );
+    assert_eq!(
+        message(&file.definitions, "Constructor2").fields[0].name,
+        "id"
+    );
+}
+
+#[test]
+fn maps_with_non_scalar_keys_use_entry_messages() {
+    let agent = agent_type(
+        vec![],
+        vec![NamedField::user_supplied(
+            "weights",
+            SchemaType::map(
+                SchemaType::tuple(vec![SchemaType::s32(), SchemaType::s32()]),
+                SchemaType::f64(),
+            ),
+        )],
+        vec![],
+    );
+    let file = agent_type_to_proto_file(&agent).unwrap();
+    let weights = &message(&file.definitions, "Constructor").fields[0];
+    assert_eq!(weights.label, ProtoLabel::Repeated);
+    assert_eq!(
+        weights.ty,
+        ProtoType::Named("ConstructorWeightsEntry".to_string())
+    );
+    let entry = message(&file.definitions, "ConstructorWeightsEntry");
+    assert_eq!(
+        entry.fields[0].ty,
+        ProtoType::Named("ConstructorWeightsKey".to_string())
+    );
+    assert_eq!(entry.fields[1].name, "value");
+}
+
+#[test]
+fn optional_lists_are_wrapped() {
+    let agent = agent_type(
+        vec![],
+        vec![NamedField::user_supplied(
+            "tags",
+            SchemaType::option(SchemaType::list(SchemaType::string())),
+        )],
+        vec![],
+    );
+    let file = agent_type_to_proto_file(&agent).unwrap();
+    let tags = &message(&file.definitions, "Constructor").fields[0];
+    assert_eq!(tags.label, ProtoLabel::Optional);
+    assert_eq!(
+        tags.ty,
+        ProtoType::Named("ConstructorTagsValue".to_string())
+    );
+    let wrapper = message(&file.definitions, "ConstructorTagsValue");
+    assert_eq!(wrapper.fields[0].label, ProtoLabel::Repeated);
+}
+
+#[test]
+fn future_payloads_are_unsupported() {
+    let agent = agent_type(
+        vec![],
+        vec![NamedField::user_supplied(
+            "pending",
+            SchemaType::future(Some(SchemaType::string())),
+        )],
+        vec![],
+    );
+    assert!(matches!(
+        agent_type_to_proto_file(&agent),
+        Err(RenderError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn list_results_get_a_streaming_rpc() {
+    let agent = agent_type(
+        vec![],
+        vec![],
+        vec![
+            method(
+                "list-skus",
+                "",
+                vec![],
+                OutputSchema::Single(Box::new(SchemaType::list(SchemaType::string()))),
+            ),
+            method(
+                "list-skus-stream",
+                "",
+                vec![],
+                OutputSchema::Single(Box::new(SchemaType::string())),
+            ),
+        ],
+    );
+    let file = agent_type_to_proto_file(&agent).unwrap();
+    let rpcs: Vec<(&str, &str, bool)> = file
+        .service
+        .rpcs
+        .iter()
+        .map(|rpc| (rpc.name.as_str(), rpc.output.as_str(), rpc.server_streaming))
+        .collect();
+    assert_eq!(
+        rpcs,
+        vec![
+            ("ListSkus", "ListSkusOutput", false),
+            ("ListSkusStream2", "ListSkusItem", true),
+            ("ListSkusStream", "ListSkusStreamOutput", false),
+        ]
+    );
+    let item = message(&file.definitions, "ListSkusItem");
+    assert_eq!(item.fields[0].label, ProtoLabel::Singular);
+
+    let idl = file.to_idl();
+    assert!(
+        idl.contains("  rpc ListSkusStream2(ListSkusRequest) returns (stream ListSkusItem);\n")
+    );
+}
@@ -0,0 +1,480 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::schema::agent::{InputSchema, NamedField, OutputSchema};
+use crate::schema::graph::{SchemaGraph, SchemaTypeDef};
+use crate::schema::metadata::{MetadataEnvelope, TypeId};
+use crate::schema::render::error::RenderError;
+use crate::schema::render::protobuf_value::{
+    ProtoRequest, decode_output, decode_request, decode_stream_item, decode_value_message,
+    encode_output, encode_request, encode_stream_items, encode_value_message,
+};
+use crate::schema::schema_type::{
+    BinaryRestrictions, NamedFieldType, QuantitySpec, QuantityValue, ResultSpec, SchemaType,
+    TextRestrictions, VariantCaseType,
+};
+use crate::schema::schema_value::{
+    BinaryValuePayload, DurationValuePayload, ResultValuePayload, SchemaValue, TextValuePayload,
+    VariantValuePayload,
+};
+use chrono::{TimeZone, Utc};
+use test_r::test;
+
+fn field(name: &str, body: SchemaType) -> NamedFieldType {
+    NamedFieldType {
+        name: name.to_string(),
+        body,
+        metadata: MetadataEnvelope::default(),
+    }
+}
+
+fn case(name: &str, payload: Option<SchemaType>) -> VariantCaseType {
+    VariantCaseType {
+        name: name.to_string(),
+        payload,
+        metadata: MetadataEnvelope::default(),
+    }
+}
+
+fn string(s: &str) -> SchemaValue {
+    SchemaValue::String(s.to_string())
+}
+
+fn round_trip(graph: &SchemaGraph, ty: &SchemaType, value: SchemaValue) {
+    let bytes = encode_value_message(graph, ty, &value)
+        .unwrap_or_else(|e| panic!("encode failed for {value:?}: {e}"));
+    let decoded = decode_value_message(graph, ty, &bytes)
+        .unwrap_or_else(|e| panic!("decode failed for {value:?}: {e}"));
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn request_matches_the_hand_encoded_wire_form() {
+    let graph = SchemaGraph::empty();
+    let constructor = InputSchema::Parameters(vec![NamedField::user_supplied(
+        "region",
+        SchemaType::string(),
+    )]);
+    let input = InputSchema::Parameters(vec![
+        NamedField::user_supplied("qty", SchemaType::u32()),
+        NamedField::user_supplied("note", SchemaType::option(SchemaType::string())),
+    ]);
+    let request = ProtoRequest {
+        constructor: vec![string("eu")],
+        phantom_id: None,
+        input: vec![SchemaValue::U32(3), SchemaValue::Option { inner: None }],
+    };
+
+    let bytes = encode_request(&graph, &constructor, &input, &request).unwrap();
+    assert_eq!(
+        bytes,
+        vec![0x0a, 0x04, 0x0a, 0x02, b'e', b'u', 0x1a, 0x02, 0x08, 0x03]
+    );
+    assert_eq!(
+        decode_request(&graph, &constructor, &input, &bytes).unwrap(),
+        request
+    );
+}
+
+#[test]
+fn auto_injected_parameters_are_not_on_the_wire() {
+    let graph = SchemaGraph::empty();
+    let constructor = InputSchema::Parameters(vec![
+        NamedField::auto_injected(
+            "principal",
+            crate::schema::agent::AutoInjectedKind::Principal,
+            SchemaType::string(),
+        ),
+        NamedField::user_supplied("region", SchemaType::string()),
+    ]);
+    let input = InputSchema::Parameters(vec![]);
+    let request = ProtoRequest {
+        constructor: vec![string("eu")],
+        phantom_id: Some("2f9b3c1e-0000-4000-8000-000000000000".to_string()),
+        input: vec![],
+    };
+
+    let bytes = encode_request(&graph, &constructor, &input, &request).unwrap();
+    assert_eq!(&bytes[..6], &[0x0a, 0x04, 0x0a, 0x02, b'e', b'u']);
+    assert_eq!(
+        decode_request(&graph, &constructor, &input, &bytes).unwrap(),
+        request
+    );
+}
+
+#[test]
+fn negative_int32_values_are_sign_extended() {
+    let graph = SchemaGraph::empty();
+    let bytes = encode_value_message(&graph, &SchemaType::s16(), &SchemaValue::S16(-1)).unwrap();
+    assert_eq!(
+        bytes,
+        vec![
+            0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01
+        ]
+    );
+    assert_eq!(
+        decode_value_message(&graph, &SchemaType::s16(), &bytes).unwrap(),
+        SchemaValue::S16(-1)
+    );
+}
+
+#[test]
+fn composite_values_round_trip() {
+    let graph = SchemaGraph {
+        defs: vec![
+            SchemaTypeDef {
+                id: TypeId::new("item"),
+                name: Some("Item".to_string()),
+                body: SchemaType::record(vec![
+                    field("sku", SchemaType::string()),
+                    field(
+                        "tags",
+                        SchemaType::list(SchemaType::list(SchemaType::string())),
+                    ),
+                ]),
+            },
+            SchemaTypeDef {
+                id: TypeId::new("color"),
+                name: Some("Color".to_string()),
+                body: SchemaType::Enum {
+                    cases: vec!["red".to_string(), "blue".to_string()],
+                    metadata: MetadataEnvelope::default(),
+                },
+            },
+            SchemaTypeDef {
+                id: TypeId::new("ids"),
+                name: Some("Ids".to_string()),
+                body: SchemaType::list(SchemaType::u64()),
+            },
+        ],
+        ..SchemaGraph::empty()
+    };
+    let ty = SchemaType::record(vec![
+        field("item", SchemaType::ref_to(TypeId::new("item"))),
+        field("color", SchemaType::ref_to(TypeId::new("color"))),
+        field("ids", SchemaType::ref_to(TypeId::new("ids"))),
+        field(
+            "by_name",
+            SchemaType::map(
+                SchemaType::string(),
+                SchemaType::ref_to(TypeId::new("item")),
+            ),
+        ),
+        field(
+            "by_point",
+            SchemaType::map(
+                SchemaType::tuple(vec![SchemaType::s32(), SchemaType::s32()]),
+                SchemaType::option(SchemaType::f64()),
+            ),
+        ),
+        field(
+            "maybe_bytes",
+            SchemaType::option(SchemaType::list(SchemaType::u8())),
+        ),
+        field(
+            "status",
+            SchemaType::variant(vec![
+                case("idle", None),
+                case("busy", Some(SchemaType::list(SchemaType::char()))),
+            ]),
+        ),
+        field(
+            "outcome",
+            SchemaType::result(ResultSpec {
+                ok: None,
+                err: Some(Box::new(SchemaType::string())),
+            }),
+        ),
+        field(
+            "perms",
+            SchemaType::flags(vec!["read".to_string(), "write".to_string()]),
+        ),
+        field("corner", SchemaType::fixed_list(SchemaType::f32(), 2)),
+    ]);
+    let item = |sku: &str| SchemaValue::Record {
+        fields: vec![
+            string(sku),
+            SchemaValue::List {
+                elements: vec![
+                    SchemaValue::List {
+                        elements: vec![string("a"), string("b")],
+                    },
+                    SchemaValue::List { elements: vec![] },
+                ],
+            },
+        ],
+    };
+    let value = |status: SchemaValue, outcome: ResultValuePayload| SchemaValue::Record {
+        fields: vec![
+            item("sku-1"),
+            SchemaValue::Enum { case: 1 },
+            SchemaValue::List {
+                elements: vec![SchemaValue::U64(u64::MAX), SchemaValue::U64(0)],
+            },
+            SchemaValue::Map {
+                entries: vec![(string("x"), item("sku-2"))],
+            },
+            SchemaValue::Map {
+                entries: vec![
+                    (
+                        SchemaValue::Tuple {
+                            elements: vec![SchemaValue::S32(-4), SchemaValue::S32(7)],
+                        },
+                        SchemaValue::Option {
+                            inner: Some(Box::new(SchemaValue::F64(0.5))),
+                        },
+                    ),
+                    (
+                        SchemaValue::Tuple {
+                            elements: vec![SchemaValue::S32(0), SchemaValue::S32(0)],
+                        },
+                        SchemaValue::Option { inner: None },
+                    ),
+                ],
+            },
+            SchemaValue::Option {
+                inner: Some(Box::new(SchemaValue::List { elements: vec![] })),
+            },
+            status,
+            SchemaValue::Result(outcome),
+            SchemaValue::Flags {
+                bits: vec![true, false],
+            },
+            SchemaValue::FixedList {
+                elements: vec![SchemaValue::F32(1.5), SchemaValue::F32(-2.0)],
+            },
+        ],
+    };
+
+    round_trip(
+        &graph,
+        &ty,
+        value(
+            SchemaValue::Variant(VariantValuePayload {
+                case: 0,
+                payload: None,
+            }),
+            ResultValuePayload::Ok { value: None },
+        ),
+    );
+    round_trip(
+        &graph,
+        &ty,
+        value(
+            SchemaValue::Variant(VariantValuePayload {
+                case: 1,
+                payload: Some(Box::new(SchemaValue::List {
+                    elements: vec![SchemaValue::Char('λ')],
+                })),
+            }),
+            ResultValuePayload::Err {
+                value: Some(Box::new(string("boom"))),
+            },
+        ),
+    );
+}
+
+#[test]
+fn rich_scalars_round_trip() {
+    let graph = SchemaGraph::empty();
+    round_trip(
+        &graph,
+        &SchemaType::datetime(),
+        SchemaValue::Datetime {
+            value: Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap(),
+        },
+    );
+    round_trip(
+        &graph,
+        &SchemaType::duration(),
+        SchemaValue::Duration(DurationValuePayload {
+            nanoseconds: -1_500_000_000,
+        }),
+    );
+    round_trip(
+        &graph,
+        &SchemaType::text(TextRestrictions::default()),
+        SchemaValue::Text(TextValuePayload {
+            text: "hello".to_string(),
+            language: Some("en".to_string()),
+        }),
+    );
+    round_trip(
+        &graph,
+        &SchemaType::binary(BinaryRestrictions::default()),
+        SchemaValue::Binary(BinaryValuePayload {
+            bytes: vec![0, 1, 2],
+            mime_type: None,
+        }),
+    );
+    round_trip(
+        &graph,
+        &SchemaType::quantity(QuantitySpec {
+            base_unit: "kg".to_string(),
+            allowed_suffixes: vec![],
+            min: None,
+            max: None,
+        }),
+        SchemaValue::Quantity(QuantityValue {
+            mantissa: -125,
+            scale: 2,
+            unit: "kg".to_string(),
+        }),
+    );
+}
+
+#[test]
+fn absent_fields_take_defaults_and_unknown_fields_are_skipped() {
+    let graph = SchemaGraph::empty();
+    let ty = SchemaType::record(vec![
+        field("count", SchemaType::u32()),
+        field("name", SchemaType::string()),
+        field("note", SchemaType::option(SchemaType::string())),
+        field("tags", SchemaType::list(SchemaType::string())),
+    ]);
+    // value = { 9: 42 }
+    let bytes = [0x0a, 0x02, 0x48, 0x2a];
+    assert_eq!(
+        decode_value_message(&graph, &ty, &bytes).unwrap(),
+        SchemaValue::Record {
+            fields: vec![
+                SchemaValue::U32(0),
+                string(""),
+                SchemaValue::Option { inner: None },
+                SchemaValue::List { elements: vec![] },
+            ],
+        }
+    );
+}
+
+#[test]
+fn packed_repeated_scalars_are_accepted() {
+    let graph = SchemaGraph::empty();
+    let ty = SchemaType::list(SchemaType::u32());
+    // One packed run followed by an unpacked element.
+    let bytes = [0x0a, 0x03, 0x01, 0x02, 0x03, 0x08, 0x04];
+    assert_eq!(
+        decode_value_message(&graph, &ty, &bytes).unwrap(),
+        SchemaValue::List {
+            elements: (1..=4).map(SchemaValue::U32).collect(),
+        }
+    );
+}
+
+#[test]
+fn the_last_oneof_member_wins() {
+    let graph = SchemaGraph::empty();
+    let ty = SchemaType::variant(vec![
+        case("a", Some(SchemaType::u32())),
+        case("b", Some(SchemaType::string())),
+    ]);
+    // value = { b: "x", a: 5 }
+    let bytes = [0x0a, 0x05, 0x12, 0x01, b'x', 0x08, 0x05];
+    assert_eq!(
+        decode_value_message(&graph, &ty, &bytes).unwrap(),
+        SchemaValue::Variant(VariantValuePayload {
+            case: 0,
+            payload: Some(Box::new(SchemaValue::U32(5))),
+        })
+    );
+}
+
+#[test]
+fn unset_enums_and_oneofs_are_rejected() {
+    let graph = SchemaGraph::empty();
+    let enumeration = SchemaType::Enum {
+        cases: vec!["red".to_string()],
+        metadata: MetadataEnvelope::default(),
+    };
+    assert!(matches!(
+        decode_value_message(&graph, &enumeration, &[]),
+        Err(RenderError::ValueMismatch { .. })
+    ));
+    let variant = SchemaType::variant(vec![case("a", None)]);
+    assert!(matches!(
+        decode_value_message(&graph, &variant, &[0x0a, 0x00]),
+        Err(RenderError::ValueMismatch { .. })
+    ));
+}
+
+#[test]
+fn out_of_range_integers_are_rejected() {
+    let graph = SchemaGraph::empty();
+    let err = decode_value_message(&graph, &SchemaType::u8(), &[0x08, 0x80, 0x02]).unwrap_err();
+    assert_eq!(
+        err,
+        RenderError::ValueMismatch {
+            path: "$".to_string(),
+            reason: "256 is out of range for u8".to_string(),
+        }
+    );
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let graph = SchemaGraph::empty();
+    let ty = SchemaType::string();
+    assert!(matches!(
+        decode_value_message(&graph, &ty, &[0x0a, 0x05, b'a']),
+        Err(RenderError::Protobuf(_))
+    ));
+    assert!(matches!(
+        decode_value_message(&graph, &ty, &[0x0b]),
+        Err(RenderError::Protobuf(_))
+    ));
+}
+
+#[test]
+fn unit_outputs_are_empty_messages() {
+    let graph = SchemaGraph::empty();
+    assert_eq!(
+        encode_output(&graph, &OutputSchema::Unit, None).unwrap(),
+        Vec::<u8>::new()
+    );
+    assert_eq!(
+        decode_output(&graph, &OutputSchema::Unit, &[]).unwrap(),
+        None
+    );
+    assert!(encode_output(&graph, &OutputSchema::Unit, Some(&SchemaValue::Bool(true))).is_err());
+}
+
+#[test]
+fn streamed_items_are_the_list_elements() {
+    let graph = SchemaGraph {
+        defs: vec![SchemaTypeDef {
+            id: TypeId::new("skus"),
+            name: Some("Skus".to_string()),
+            body: SchemaType::list(SchemaType::string()),
+        }],
+        ..SchemaGraph::empty()
+    };
+    let output = OutputSchema::Single(SchemaType::ref_to(TypeId::new("skus")));
+    let value = SchemaValue::List {
+        elements: vec![string("a"), string("bc")],
+    };
+
+    let items = encode_stream_items(&graph, &output, &value).unwrap();
+    assert_eq!(
+        items,
+        vec![vec![0x0a, 0x01, b'a'], vec![0x0a, 0x02, b'b', b'c']]
+    );
+    let decoded = items
+        .iter()
+        .map(|item| decode_stream_item(&graph, &output, item).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(decoded, vec![string("a"), string("bc")]);
+
+    let scalar = OutputSchema::Single(SchemaType::string());
+    assert!(encode_stream_items(&graph, &scalar, &string("a")).is_err());
+}
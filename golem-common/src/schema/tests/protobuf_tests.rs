@@ -112,6 +112,7 @@ fn sample_agent_type_schema() -> AgentTypeSchema {
                 output_schema: OutputSchema::Unit,
                 http_endpoint: vec![],
                 read_only: None,
+                timeout_nanos: None,
             }],
         }],
         mode: AgentMode::Durable,
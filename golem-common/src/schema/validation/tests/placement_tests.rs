@@ -241,6 +241,7 @@ mod agent {
             output_schema: OutputSchema::Unit,
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         });
         validate_agent_type_placement(&agent)
             .expect("secret in method input should pass at Boundary scope");
@@ -332,6 +333,7 @@ mod agent {
             output_schema: OutputSchema::Unit,
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         });
         validate_agent_type_placement(&agent)
             .expect("secret def via method input ref must pass at Boundary scope");
@@ -350,6 +352,7 @@ mod agent {
             )),
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         });
         validate_agent_type_placement(&agent)
             .expect("secret in method output must pass at Boundary scope");
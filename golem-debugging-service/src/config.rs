@@ -20,11 +20,11 @@ use golem_service_base::clients::registry::GrpcRegistryServiceConfig;
 use golem_service_base::config::BlobStorageConfig;
 use golem_service_base::service::compiled_component::CompiledComponentServiceConfig;
 use golem_worker_executor::services::golem_config::{
-    ActiveWorkersConfig, AgentTypesServiceConfig, AgentWebhooksServiceConfig, ComponentCacheConfig,
-    EngineConfig, EnvironmentStateServiceConfig, GolemConfig, GrpcApiConfig, IndexedStorageConfig,
-    KeyValueStorageConfig, Limits, MemoryConfig, OplogConfig, QuotaServiceConfig, RdbmsConfig,
-    ResourceLimitsConfig, SchedulerConfig, SchedulerStorageConfig, SuspendConfig,
-    WorkerServiceGrpcConfig,
+    ActiveWorkersConfig, AgentMetricsConfig, AgentTypesServiceConfig, AgentWebhooksServiceConfig,
+    ComponentCacheConfig, EngineConfig, EnvironmentStateServiceConfig, GolemConfig, GrpcApiConfig,
+    IndexedStorageConfig, KeyValueStorageConfig, Limits, MemoryConfig, OplogConfig,
+    QuotaServiceConfig, RdbmsConfig, ResourceLimitsConfig, SchedulerConfig, SchedulerStorageConfig,
+    SuspendConfig, WorkerServiceGrpcConfig,
 };
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
@@ -70,6 +70,8 @@ impl DebugConfig {
             indexed_storage: self.indexed_storage,
             scheduler_storage: self.scheduler_storage,
             blob_storage: self.blob_storage,
+            storage_migration: Default::default(),
+            encryption: Default::default(),
             limits: self.limits,
             retry: self.retry,
             compiled_component_service: self.compiled_component_service,
@@ -78,6 +80,11 @@ impl DebugConfig {
             active_workers: self.active_workers,
             agent_status_flush: Default::default(),
             agent_status_checkpoint: Default::default(),
+            // debugging sessions must not contribute to the exposed agent metrics
+            agent_metrics: AgentMetricsConfig {
+                enabled: false,
+                ..Default::default()
+            },
             scheduler: self.scheduler,
             public_worker_api: self.public_worker_api,
             memory: self.memory,
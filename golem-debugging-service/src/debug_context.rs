@@ -19,7 +19,7 @@ use golem_common::base_model::OplogIndex;
 use golem_common::base_model::component_metadata::AgentTypeProvisionConfig;
 use golem_common::base_model::environment_plugin_grant::EnvironmentPluginGrantId;
 use golem_common::model::account::{AccountEmail, AccountId};
-use golem_common::model::agent::{AgentMode, LegacyParsedAgentId};
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, UntypedDataValue};
 use golem_common::model::component::CanonicalFilePath;
 use golem_common::model::component::ComponentRevision;
 use golem_common::model::invocation_context::{
@@ -62,6 +62,7 @@ use golem_worker_executor::services::resource_limits::{AtomicResourceEntry, Reso
 use golem_worker_executor::services::rpc::Rpc;
 use golem_worker_executor::services::scheduler::SchedulerService;
 use golem_worker_executor::services::shard::ShardService;
+use golem_worker_executor::services::topics::TopicService;
 use golem_worker_executor::services::worker::WorkerService;
 use golem_worker_executor::services::worker_event::WorkerEventService;
 use golem_worker_executor::services::worker_fork::WorkerForkService;
@@ -245,6 +246,16 @@ impl InvocationHooks for DebugContext {
             .await
     }
 
+    async fn on_agent_invocation_output_replaced(
+        &mut self,
+        original: &UntypedDataValue,
+        replaced: &UntypedDataValue,
+    ) -> Result<(), WorkerExecutorError> {
+        self.durable_ctx
+            .on_agent_invocation_output_replaced(original, replaced)
+            .await
+    }
+
     async fn get_current_retry_point(&self) -> OplogIndex {
         self.durable_ctx.get_current_retry_point().await
     }
@@ -256,6 +267,19 @@ impl InvocationHooks for DebugContext {
     fn exit_read_only_mode(&mut self) {
         self.durable_ctx.exit_read_only_mode()
     }
+
+    fn arm_invocation_deadline(&mut self, method_name: String, deadline: Timestamp) {
+        self.durable_ctx
+            .arm_invocation_deadline(method_name, deadline)
+    }
+
+    fn disarm_invocation_deadline(&mut self) {
+        self.durable_ctx.disarm_invocation_deadline()
+    }
+
+    fn check_invocation_deadline(&self) -> Option<AgentError> {
+        self.durable_ctx.check_invocation_deadline()
+    }
 }
 
 #[async_trait]
@@ -429,6 +453,14 @@ impl HostWasmRpc for DebugContext {
             .await
     }
 
+    async fn set_deadline(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        deadline: Option<wasmtime_wasi::p2::bindings::clocks::wall_clock::Datetime>,
+    ) -> anyhow::Result<()> {
+        self.durable_ctx.set_deadline(self_, deadline).await
+    }
+
     async fn drop(&mut self, rep: Resource<WasmRpc>) -> anyhow::Result<()> {
         HostWasmRpc::drop(&mut self.durable_ctx, rep).await
     }
@@ -554,6 +586,7 @@ impl WorkerCtx for DebugContext {
         worker_service: Arc<dyn WorkerService>,
         worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn RdbmsService>,
         quota_service: Arc<dyn QuotaService>,
@@ -597,6 +630,7 @@ impl WorkerCtx for DebugContext {
             worker_service,
             worker_enumeration_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             quota_service,
@@ -624,6 +658,7 @@ impl WorkerCtx for DebugContext {
             original_phantom_id,
             u64::MAX,
             u64::MAX,
+            None,
         )
         .await?;
         Ok(Self {
@@ -44,7 +44,9 @@ use golem_worker_executor::services::direct_invocation_auth::DirectInvocationAut
 use golem_worker_executor::services::environment_state::EnvironmentStateService;
 use golem_worker_executor::services::events::Events;
 use golem_worker_executor::services::file_loader::FileLoader;
+use golem_worker_executor::services::filesystem_checkpoint::FilesystemCheckpointService;
 use golem_worker_executor::services::golem_config::GolemConfig;
+use golem_worker_executor::services::invocation_interceptor::InvocationInterceptorPlugin;
 use golem_worker_executor::services::key_value::KeyValueService;
 use golem_worker_executor::services::oplog::OplogService;
 use golem_worker_executor::services::oplog::plugin::OplogProcessorPlugin;
@@ -55,6 +57,7 @@ use golem_worker_executor::services::rpc::{DirectWorkerInvocationRpc, RemoteInvo
 use golem_worker_executor::services::scheduler::SchedulerService;
 use golem_worker_executor::services::shard::ShardService;
 use golem_worker_executor::services::shard_manager::ShardManagerService;
+use golem_worker_executor::services::topics::TopicService;
 use golem_worker_executor::services::worker::WorkerService;
 use golem_worker_executor::services::worker_activator::{LazyWorkerActivator, WorkerActivator};
 use golem_worker_executor::services::worker_enumeration::{
@@ -141,6 +144,7 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
         golem_config: Arc<GolemConfig>,
         shard_service: Arc<dyn ShardService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn rdbms::RdbmsService>,
         worker_activator: Arc<dyn WorkerActivator<DebugContext>>,
@@ -149,7 +153,9 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
         worker_proxy: Arc<dyn WorkerProxy>,
         events: Arc<Events>,
         file_loader: Arc<FileLoader>,
+        filesystem_checkpoint_service: Arc<dyn FilesystemCheckpointService>,
         oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
         agent_types_service: Arc<dyn AgentTypesService>,
         environment_state_service: Arc<dyn EnvironmentStateService>,
         agent_webhooks_service: Arc<AgentWebhooksService>,
@@ -176,6 +182,7 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
             golem_config,
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             worker_activator,
@@ -184,7 +191,9 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
             worker_proxy,
             events,
             file_loader,
+            filesystem_checkpoint_service,
             oplog_processor_plugin,
+            invocation_interceptor_plugin,
             agent_types_service,
             environment_state_service,
             agent_webhooks_service,
@@ -220,6 +229,7 @@ pub async fn create_debugging_service_services(
     golem_config: Arc<GolemConfig>,
     shard_service: Arc<dyn ShardService>,
     key_value_service: Arc<dyn KeyValueService>,
+    topic_service: Arc<dyn TopicService>,
     blob_store_service: Arc<dyn BlobStoreService>,
     rdbms_service: Arc<dyn rdbms::RdbmsService>,
     worker_activator: Arc<dyn WorkerActivator<DebugContext>>,
@@ -228,7 +238,9 @@ pub async fn create_debugging_service_services(
     worker_proxy: Arc<dyn WorkerProxy>,
     events: Arc<Events>,
     file_loader: Arc<FileLoader>,
+    filesystem_checkpoint_service: Arc<dyn FilesystemCheckpointService>,
     oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+    invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
     agent_types_service: Arc<dyn AgentTypesService>,
     environment_state_service: Arc<dyn EnvironmentStateService>,
     agent_webhooks_service: Arc<AgentWebhooksService>,
@@ -266,6 +278,7 @@ pub async fn create_debugging_service_services(
         golem_config.clone(),
         shard_service.clone(),
         key_value_service.clone(),
+        topic_service.clone(),
         blob_store_service.clone(),
         rdbms_service.clone(),
         // When it comes to fork, it reads using the debug oplog service
@@ -275,7 +288,9 @@ pub async fn create_debugging_service_services(
         worker_activator.clone(),
         events.clone(),
         file_loader.clone(),
+        filesystem_checkpoint_service.clone(),
         oplog_processor_plugin.clone(),
+        invocation_interceptor_plugin.clone(),
         resource_limits.clone(),
         environment_state_service.clone(),
         agent_types_service.clone(),
@@ -308,6 +323,7 @@ pub async fn create_debugging_service_services(
         shard_manager_service.clone(),
         quota_service.clone(),
         key_value_service.clone(),
+        topic_service.clone(),
         blob_store_service.clone(),
         rdbms_service.clone(),
         debug_oplog_service.clone(),
@@ -315,7 +331,9 @@ pub async fn create_debugging_service_services(
         worker_activator.clone(),
         events.clone(),
         file_loader.clone(),
+        filesystem_checkpoint_service.clone(),
         oplog_processor_plugin.clone(),
+        invocation_interceptor_plugin.clone(),
         resource_limits.clone(),
         shutdown_token.clone(),
         environment_state_service.clone(),
@@ -345,6 +363,7 @@ pub async fn create_debugging_service_services(
         golem_config,
         shard_service,
         key_value_service,
+        topic_service,
         blob_store_service,
         rdbms_service,
         debug_oplog_service,
@@ -354,7 +373,9 @@ pub async fn create_debugging_service_services(
         worker_proxy,
         events,
         file_loader,
+        filesystem_checkpoint_service,
         oplog_processor_plugin,
+        invocation_interceptor_plugin,
         resource_limits,
         shutdown_token,
         http_connection_pool,
@@ -120,6 +120,8 @@ impl LogNotification {
                 message,
             }),
             InternalWorkerEvent::PluginError { .. } => None,
+            InternalWorkerEvent::WebSocketSend { .. } => None,
+            InternalWorkerEvent::WebSocketClose { .. } => None,
         }
     }
 }
@@ -20,7 +20,9 @@ use golem_worker_executor::services::direct_invocation_auth::DirectInvocationAut
 use golem_worker_executor::services::environment_state::EnvironmentStateService;
 use golem_worker_executor::services::events::Events;
 use golem_worker_executor::services::file_loader::FileLoader;
+use golem_worker_executor::services::filesystem_checkpoint::FilesystemCheckpointService;
 use golem_worker_executor::services::golem_config::{EnvironmentStateServiceConfig, GolemConfig};
+use golem_worker_executor::services::invocation_interceptor::InvocationInterceptorPlugin;
 use golem_worker_executor::services::key_value::KeyValueService;
 use golem_worker_executor::services::oplog::OplogService;
 use golem_worker_executor::services::oplog::plugin::OplogProcessorPlugin;
@@ -31,6 +33,7 @@ use golem_worker_executor::services::resource_limits::{ResourceLimits, ResourceL
 use golem_worker_executor::services::scheduler::SchedulerService;
 use golem_worker_executor::services::shard::ShardService;
 use golem_worker_executor::services::shard_manager::ShardManagerService;
+use golem_worker_executor::services::topics::TopicService;
 use golem_worker_executor::services::worker::WorkerService;
 use golem_worker_executor::services::worker_activator::WorkerActivator;
 use golem_worker_executor::services::worker_enumeration::{
@@ -155,6 +158,7 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
         golem_config: Arc<GolemConfig>,
         shard_service: Arc<dyn ShardService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn RdbmsService>,
         worker_activator: Arc<dyn WorkerActivator<DebugContext>>,
@@ -163,7 +167,9 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
         worker_proxy: Arc<dyn WorkerProxy>,
         events: Arc<Events>,
         file_loader: Arc<FileLoader>,
+        filesystem_checkpoint_service: Arc<dyn FilesystemCheckpointService>,
         oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
         agent_types_service: Arc<dyn AgentTypesService>,
         environment_state_service: Arc<dyn EnvironmentStateService>,
         agent_webhooks_service: Arc<AgentWebhooksService>,
@@ -190,6 +196,7 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
             golem_config,
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             worker_activator,
@@ -198,7 +205,9 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
             worker_proxy,
             events,
             file_loader,
+            filesystem_checkpoint_service,
             oplog_processor_plugin,
+            invocation_interceptor_plugin,
             agent_types_service,
             environment_state_service,
             agent_webhooks_service,
@@ -21,17 +21,22 @@ use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::result;
 
+use convert_case::{Case, Casing};
 use openapiv3::OpenAPI;
 
 pub use merger::merge_all_openapi_specs;
 
 use crate::rust::lib_gen::{Module, ModuleDef, ModuleName};
 use crate::rust::model_gen::RefCache;
+use crate::spec::ClientDef;
 
 pub(crate) mod merger;
 pub(crate) mod printer;
+mod python;
 mod rust;
+mod spec;
 mod toml;
+mod typescript;
 
 #[derive(Debug, Clone)]
 pub enum Error {
@@ -181,6 +186,138 @@ pub fn generate(
     Ok(())
 }
 
+fn client_defs(
+    open_api: &OpenAPI,
+    ref_cache: &mut RefCache,
+    ignored_paths: &[&str],
+) -> Result<Vec<ClientDef>> {
+    open_api
+        .tags
+        .iter()
+        .map(|tag| spec::client_def(open_api, Some(tag.clone()), ref_cache, ignored_paths))
+        .collect()
+}
+
+/// Generates a TypeScript package, using `fetch`, with the same structure as the Rust client.
+pub fn generate_typescript(
+    openapi_specs: Vec<OpenAPI>,
+    target: &Path,
+    name: &str,
+    version: &str,
+    ignored_paths: &[&str],
+) -> Result<()> {
+    let open_api = merge_all_openapi_specs(openapi_specs)?;
+
+    let src = target.join("src");
+    let api = src.join("api");
+
+    std::fs::create_dir_all(&api).unwrap();
+
+    let mut ref_cache = RefCache::new();
+    let clients = client_defs(&open_api, &mut ref_cache, ignored_paths)?;
+    let error_conventions = spec::infer_error_conventions(&open_api);
+
+    let mut api_modules = Vec::new();
+
+    for client in &clients {
+        let module = client.name.to_case(Case::Snake);
+        let code = typescript::client_gen::client_gen(client, &error_conventions)?;
+        std::fs::write(api.join(format!("{module}.ts")), code).unwrap();
+        api_modules.push(module);
+    }
+
+    let models = spec::collect_models(&open_api, ref_cache)?;
+    std::fs::write(
+        src.join("model.ts"),
+        typescript::model_gen::model_gen(&models),
+    )
+    .unwrap();
+
+    std::fs::write(
+        src.join("context.ts"),
+        typescript::context_gen::context_gen(&open_api),
+    )
+    .unwrap();
+    std::fs::write(src.join("errors.ts"), typescript::error_gen::error_gen()).unwrap();
+    std::fs::write(
+        src.join("index.ts"),
+        typescript::package_gen::index_gen(&api_modules),
+    )
+    .unwrap();
+
+    std::fs::write(
+        target.join("package.json"),
+        typescript::package_gen::package_json(name, version),
+    )
+    .unwrap();
+    std::fs::write(
+        target.join("tsconfig.json"),
+        typescript::package_gen::tsconfig(),
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+/// Generates a Python package, using `httpx`, with the same structure as the Rust client.
+pub fn generate_python(
+    openapi_specs: Vec<OpenAPI>,
+    target: &Path,
+    name: &str,
+    version: &str,
+    ignored_paths: &[&str],
+) -> Result<()> {
+    let open_api = merge_all_openapi_specs(openapi_specs)?;
+
+    let package_name = name.to_case(Case::Snake);
+    let package = target.join(&package_name);
+    let api = package.join("api");
+
+    std::fs::create_dir_all(&api).unwrap();
+
+    let mut ref_cache = RefCache::new();
+    let clients = client_defs(&open_api, &mut ref_cache, ignored_paths)?;
+    let error_conventions = spec::infer_error_conventions(&open_api);
+
+    let mut api_modules = Vec::new();
+
+    for client in &clients {
+        let module = client.name.to_case(Case::Snake);
+        let code = python::client_gen::client_gen(client, &error_conventions)?;
+        std::fs::write(api.join(format!("{module}.py")), code).unwrap();
+        api_modules.push(python::package_gen::ApiModule::new(module, client));
+    }
+    std::fs::write(api.join("__init__.py"), "").unwrap();
+
+    let models = spec::collect_models(&open_api, ref_cache)?;
+    std::fs::write(
+        package.join("models.py"),
+        python::model_gen::model_gen(&models),
+    )
+    .unwrap();
+
+    std::fs::write(
+        package.join("context.py"),
+        python::context_gen::context_gen(&open_api),
+    )
+    .unwrap();
+    std::fs::write(package.join("errors.py"), python::error_gen::error_gen()).unwrap();
+    std::fs::write(
+        package.join("__init__.py"),
+        python::package_gen::init_gen(&api_modules),
+    )
+    .unwrap();
+    std::fs::write(package.join("py.typed"), "").unwrap();
+
+    std::fs::write(
+        target.join("pyproject.toml"),
+        toml::pyproject::generate(name, version, &package_name),
+    )
+    .unwrap();
+
+    Ok(())
+}
+
 pub fn parse_openapi_specs(
     spec: &[PathBuf],
 ) -> std::result::Result<Vec<OpenAPI>, Box<dyn std::error::Error>> {
@@ -193,3 +330,96 @@ pub fn parse_openapi_specs(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{generate_python, generate_typescript, parse_openapi_specs};
+    use goldenfile::Mint;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+
+    const IGNORED_PATHS: &[&str] = &[
+        "/v1/components/{component_id}/workers/{agent_name}/connect",
+        "/v1/login/oauth2/web/callback",
+    ];
+
+    fn merged_spec() -> Vec<openapiv3::OpenAPI> {
+        parse_openapi_specs(&[PathBuf::from("../openapi/golem-service.yaml")]).unwrap()
+    }
+
+    fn check_snapshots(generated: &Path, snapshot_dir: &str, files: &[&str]) {
+        let mut mint = Mint::new(format!("tests/goldenfiles/{snapshot_dir}"));
+        for file in files {
+            let code = std::fs::read_to_string(generated.join(file)).unwrap();
+            let file_name = Path::new(file).file_name().unwrap();
+            let mut goldenfile = mint.new_goldenfile(file_name).unwrap();
+            goldenfile.write_all(code.as_bytes()).unwrap();
+        }
+    }
+
+    #[test]
+    fn typescript_client_matches_snapshot() {
+        let target = tempfile::tempdir().unwrap();
+        generate_typescript(
+            merged_spec(),
+            target.path(),
+            "golem-client",
+            "0.0.0",
+            IGNORED_PATHS,
+        )
+        .unwrap();
+
+        check_snapshots(
+            target.path(),
+            "typescript",
+            &[
+                "src/errors.ts",
+                "src/context.ts",
+                "src/api/agent.ts",
+                "src/api/health_check.ts",
+            ],
+        );
+    }
+
+    #[test]
+    fn python_client_matches_snapshot() {
+        let target = tempfile::tempdir().unwrap();
+        generate_python(
+            merged_spec(),
+            target.path(),
+            "golem-client",
+            "0.0.0",
+            IGNORED_PATHS,
+        )
+        .unwrap();
+
+        check_snapshots(
+            target.path(),
+            "python",
+            &[
+                "golem_client/errors.py",
+                "golem_client/context.py",
+                "golem_client/api/agent.py",
+                "golem_client/api/health_check.py",
+            ],
+        );
+    }
+
+    #[test]
+    fn raw_identifier_schemas_get_valid_model_names() {
+        let target = tempfile::tempdir().unwrap();
+        generate_typescript(
+            merged_spec(),
+            target.path(),
+            "golem-client",
+            "0.0.0",
+            IGNORED_PATHS,
+        )
+        .unwrap();
+
+        let models = std::fs::read_to_string(target.path().join("src/model.ts")).unwrap();
+        assert!(!models.contains('#'));
+    }
+}
@@ -15,9 +15,11 @@
 use std::fs::File;
 use std::path::PathBuf;
 
-use clap::{Args, Parser};
+use clap::{Args, Parser, ValueEnum};
 
-use golem_openapi_client_generator::{generate, parse_openapi_specs};
+use golem_openapi_client_generator::{
+    generate, generate_python, generate_typescript, parse_openapi_specs,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None, rename_all = "kebab-case")]
@@ -41,6 +43,20 @@ struct GenerateArgs {
 
     #[arg(short, long)]
     name: String,
+
+    #[arg(short, long, value_enum, default_value_t = Language::Rust)]
+    language: Language,
+
+    /// Paths to skip when generating the client
+    #[arg(long, value_name = "path")]
+    ignored_path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Language {
+    Rust,
+    Typescript,
+    Python,
 }
 
 #[derive(Debug, Args)]
@@ -57,16 +73,33 @@ fn main() {
     match command {
         Cli::Generate(args) => {
             let openapi_specs = parse_openapi_specs(&args.spec_yaml).unwrap();
-            generate(
-                openapi_specs,
-                &args.output_directory,
-                &args.name,
-                &args.client_version,
-                true,
-                false,
-                &[],
-                &[],
-            )
+            let ignored_paths: Vec<&str> = args.ignored_path.iter().map(|p| p.as_str()).collect();
+            match args.language {
+                Language::Rust => generate(
+                    openapi_specs,
+                    &args.output_directory,
+                    &args.name,
+                    &args.client_version,
+                    true,
+                    false,
+                    &[],
+                    &ignored_paths,
+                ),
+                Language::Typescript => generate_typescript(
+                    openapi_specs,
+                    &args.output_directory,
+                    &args.name,
+                    &args.client_version,
+                    &ignored_paths,
+                ),
+                Language::Python => generate_python(
+                    openapi_specs,
+                    &args.output_directory,
+                    &args.name,
+                    &args.client_version,
+                    &ignored_paths,
+                ),
+            }
             .unwrap();
         }
         Cli::Merge(args) => {
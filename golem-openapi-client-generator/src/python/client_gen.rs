@@ -0,0 +1,444 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::python::printer::{PythonContext, PythonPrinter, line, unit};
+use crate::python::types::{identifier, render_type, string_literal};
+use crate::rust::types::DataType;
+use crate::spec::{
+    ClientDef, ErrorConvention, ErrorDetail, ErrorExtraction, Method, MethodErrors, Param,
+    ParamKind, PathElement, error_extraction, error_name,
+};
+use crate::{Error, Result};
+use itertools::Itertools;
+use openapiv3::StatusCode;
+use std::collections::HashMap;
+
+fn variant_name(code: &StatusCode) -> String {
+    format!("Error{code}")
+}
+
+fn status_condition(code: &StatusCode) -> String {
+    match code {
+        StatusCode::Code(code) => format!("response.status_code == {code}"),
+        StatusCode::Range(range) => format!("response.status_code // 100 == {range}"),
+    }
+}
+
+fn default_return(detail: ErrorDetail) -> &'static str {
+    match detail {
+        ErrorDetail::Errors => "return []",
+        ErrorDetail::Code | ErrorDetail::AdditionalFields => "return None",
+    }
+}
+
+fn field(name: &str) -> String {
+    format!("body[{}]", string_literal(name))
+}
+
+fn render_extraction(extraction: &ErrorExtraction, detail: ErrorDetail) -> PythonPrinter {
+    match extraction {
+        ErrorExtraction::None => line(default_return(detail)),
+        ErrorExtraction::Error => line(unit() + "return [" + field("error") + "]"),
+        ErrorExtraction::Errors => line(unit() + "return list(" + field("errors") + ")"),
+        ErrorExtraction::Code => line(unit() + "return " + field("code")),
+        ErrorExtraction::Fields(names) => {
+            let names = names.iter().map(|n| string_literal(n)).join(", ");
+            line(unit() + "return pick_fields(body, [" + names + "])")
+        }
+        ErrorExtraction::Switch {
+            discriminator,
+            cases,
+        } => {
+            cases
+                .iter()
+                .map(|(tag, extraction)| {
+                    line(
+                        unit()
+                            + "if body.get("
+                            + string_literal(discriminator)
+                            + ") == "
+                            + string_literal(tag)
+                            + ":",
+                    ) + indented(render_extraction(extraction, detail))
+                })
+                .reduce(|acc, e| acc + e)
+                .unwrap_or_else(unit)
+                + line(default_return(detail))
+        }
+    }
+}
+
+fn render_error_detail(
+    errors: &MethodErrors,
+    detail: ErrorDetail,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> PythonPrinter {
+    let (method, result) = match detail {
+        ErrorDetail::Errors => ("errors", "List[str]"),
+        ErrorDetail::Code => ("code", "Optional[str]"),
+        ErrorDetail::AdditionalFields => ("additional_fields", "Optional[Dict[str, Any]]"),
+    };
+
+    let cases = errors
+        .codes
+        .iter()
+        .map(|(code, tpe)| (code, error_extraction(tpe, detail, conventions)))
+        .filter(|(_, extraction)| extraction != &ErrorExtraction::None)
+        .map(|(code, extraction)| {
+            line(unit() + "if self.variant == " + string_literal(&variant_name(code)) + ":")
+                + indented(render_extraction(&extraction, detail))
+        })
+        .reduce(|acc, e| acc + e);
+
+    let body = match cases {
+        Some(cases) => line("body = self.body") + cases + line(default_return(detail)),
+        None => line(default_return(detail)),
+    };
+
+    unit() + NewLine + line(unit() + "def " + method + "(self) -> " + result + ":") + indented(body)
+}
+
+fn body_type(tpe: &DataType) -> String {
+    render_type(tpe, "models.")
+}
+
+fn render_errors(
+    name: &str,
+    errors: &MethodErrors,
+    service_name: &str,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> PythonPrinter {
+    let body_types = errors
+        .codes
+        .values()
+        .map(body_type)
+        .unique()
+        .collect::<Vec<_>>();
+
+    let body_type = match body_types.len() {
+        0 => "Any".to_string(),
+        1 => body_types[0].clone(),
+        _ => format!("Union[{}]", body_types.join(", ")),
+    };
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line(unit() + "class " + name + "(ErrorInfo):") +
+        indented(
+            line(unit() + "SERVICE_NAME: ClassVar[str] = " + string_literal(service_name)) +
+            NewLine +
+            line(unit() + "def __init__(self, variant: str, status: int, body: " + body_type + ") -> None:") +
+            indented(
+                line("self.variant = variant") +
+                line("self.status = status") +
+                line("self.body = body")
+            ) +
+            NewLine +
+            line("def __repr__(self) -> str:") +
+            indented(line(r#"return f"{type(self).__name__}.{self.variant}({self.body!r})""#)) +
+            NewLine +
+            line("def status_code(self) -> int:") +
+            indented(line("return self.status")) +
+            render_error_detail(errors, ErrorDetail::Errors, conventions) +
+            render_error_detail(errors, ErrorDetail::Code, conventions) +
+            render_error_detail(errors, ErrorDetail::AdditionalFields, conventions)
+        );
+
+    code
+}
+
+fn render_param_declaration(param: &Param) -> String {
+    let tpe = render_type(&param.tpe, "models.");
+
+    if param.required {
+        format!("{}: {tpe}", identifier(&param.name))
+    } else {
+        format!("{}: Optional[{tpe}]", identifier(&param.name))
+    }
+}
+
+fn to_string(name: &str, tpe: &DataType) -> String {
+    match tpe {
+        DataType::String => name.to_string(),
+        _ => format!("to_param({name})"),
+    }
+}
+
+fn unwrap_optional_param(param: &Param, setter: PythonPrinter) -> PythonPrinter {
+    if param.required {
+        setter
+    } else {
+        line(unit() + "if " + identifier(&param.name) + " is not None:") + indented(setter)
+    }
+}
+
+fn render_path_segment(method: &Method, element: &PathElement) -> Result<String> {
+    match element {
+        PathElement::Const(segment) => Ok(string_literal(segment)),
+        PathElement::Param { name } => match method.get_path_param(name) {
+            None => Err(Error::unexpected(format!(
+                "Can't find path parameter {name}"
+            ))),
+            Some(param) => Ok(to_string(&identifier(&param.name), &param.tpe)),
+        },
+    }
+}
+
+fn query_setter(param: &Param) -> PythonPrinter {
+    let name = identifier(&param.name);
+
+    let setter = match &param.tpe {
+        DataType::Array(tpe) => {
+            line(unit() + "for item in " + &name + ":")
+                + indented(line(
+                    unit()
+                        + "params.append(("
+                        + string_literal(&param.original_name)
+                        + ", "
+                        + to_string("item", tpe)
+                        + "))",
+                ))
+        }
+        tpe => line(
+            unit()
+                + "params.append(("
+                + string_literal(&param.original_name)
+                + ", "
+                + to_string(&name, tpe)
+                + "))",
+        ),
+    };
+
+    unwrap_optional_param(param, setter)
+}
+
+fn header_setter(param: &Param) -> PythonPrinter {
+    let setter = line(
+        unit()
+            + "headers["
+            + string_literal(&param.original_name)
+            + "] = "
+            + to_string(&identifier(&param.name), &param.tpe),
+    );
+
+    unwrap_optional_param(param, setter)
+}
+
+fn multipart_setter(param: &Param) -> PythonPrinter {
+    let name = identifier(&param.name);
+    let field_name = string_literal(&param.original_name);
+
+    let part = match &param.tpe {
+        DataType::Binary => format!(r#"({field_name}, {name}, "application/octet-stream")"#),
+        DataType::String => format!(r#"(None, {name}, "text/plain; charset=utf-8")"#),
+        DataType::Model(_) => format!(r#"(None, json.dumps({name}), "application/json")"#),
+        tpe => format!(
+            r#"(None, {}, "text/plain; charset=utf-8")"#,
+            to_string(&name, tpe)
+        ),
+    };
+
+    let setter = line(unit() + "files.append((" + field_name + ", " + part + "))");
+
+    unwrap_optional_param(param, setter)
+}
+
+/// Statements preparing the request body and the matching keyword argument of `send`.
+fn body_setter(method: &Method) -> Option<(PythonPrinter, String)> {
+    let multipart_params = method
+        .params
+        .iter()
+        .filter(|p| p.kind == ParamKind::Multipart)
+        .collect::<Vec<_>>();
+
+    if !multipart_params.is_empty() {
+        let setters = multipart_params
+            .into_iter()
+            .map(multipart_setter)
+            .reduce(|acc, e| acc + e)
+            .unwrap_or_else(unit);
+
+        return Some((
+            line("files: List[Tuple[str, Tuple[Optional[str], Any, str]]] = []") + setters,
+            "files=files".to_string(),
+        ));
+    }
+
+    method
+        .params
+        .iter()
+        .find(|p| p.kind == ParamKind::Body)
+        .map(|param| {
+            let name = identifier(&param.name);
+
+            match &param.tpe {
+                DataType::Binary | DataType::Yaml => {
+                    let content_type = if param.tpe == DataType::Binary {
+                        "application/octet-stream"
+                    } else {
+                        "application/x-yaml"
+                    };
+
+                    (
+                        line(unit() + r#"headers["Content-Type"] = ""# + content_type + r#"""#),
+                        format!("content={name}"),
+                    )
+                }
+                _ => (unit(), format!("json={name}")),
+            }
+        })
+}
+
+fn parse_response(tpe: &DataType, error_name: &str) -> String {
+    match tpe {
+        DataType::Unit => "None".to_string(),
+        DataType::Binary => "response.content".to_string(),
+        _ => format!("self.context.json({error_name}.SERVICE_NAME, response)"),
+    }
+}
+
+fn render_method(method: &Method, error_name: &str) -> Result<PythonPrinter> {
+    if method.params.iter().any(|p| p.kind == ParamKind::Cookie) {
+        return Err(Error::unimplemented("Cookie parameters."));
+    }
+
+    let params = method
+        .params
+        .iter()
+        .map(|p| format!(", {}", render_param_declaration(p)))
+        .join("");
+
+    let segments = method
+        .path
+        .0
+        .iter()
+        .map(|e| render_path_segment(method, e))
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+
+    let query_setters = method
+        .params
+        .iter()
+        .filter(|p| p.kind == ParamKind::Query)
+        .map(query_setter)
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    let header_setters = method
+        .params
+        .iter()
+        .filter(|p| p.kind == ParamKind::Header)
+        .map(header_setter)
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    let (body_setter, body_argument) = match body_setter(method) {
+        Some((setter, argument)) => (setter, format!(", {argument}")),
+        None => (unit(), String::new()),
+    };
+
+    let error_cases = method
+        .errors
+        .codes
+        .iter()
+        .map(|(code, tpe)| {
+            line(unit() + "if " + status_condition(code) + ":")
+                + indented(line(
+                    unit()
+                        + "raise ItemError("
+                        + error_name
+                        + "("
+                        + string_literal(&variant_name(code))
+                        + ", response.status_code, "
+                        + parse_response(tpe, error_name)
+                        + "))",
+                ))
+        })
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line(unit() + "async def " + identifier(&method.name) + "(self" + params + ") -> " + render_type(&method.result, "models.") + ":") +
+        indented(
+            line(unit() + "url = self.context.url([" + segments + "])") +
+            line("params: List[Tuple[str, str]] = []") +
+            query_setters +
+            line("headers: Dict[str, str] = {}") +
+            header_setters +
+            body_setter +
+            NewLine +
+            line(unit() + "response = await self.context.send(" + error_name + ".SERVICE_NAME, " + string_literal(&method.http_method.to_uppercase()) + ", url, params, headers" + body_argument + ")") +
+            NewLine +
+            line(unit() + "if " + status_condition(&method.result_status_code) + ":") +
+            indented(line(unit() + "return " + parse_response(&method.result, error_name))) +
+            error_cases +
+            line(unit() + "raise UnexpectedResponseError(" + error_name + ".SERVICE_NAME, response.status_code, response.content)")
+        );
+
+    Ok(code)
+}
+
+pub fn client_gen(
+    client: &ClientDef,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> Result<String> {
+    let errors = client
+        .errors()
+        .iter()
+        .map(|(method_name, errors)| {
+            render_errors(
+                &error_name(method_name, &client.error_kind),
+                errors,
+                &client.name,
+                conventions,
+            )
+        })
+        .reduce(|acc, e| acc + NewLine + NewLine + e)
+        .unwrap_or_else(unit);
+
+    let methods = client
+        .methods
+        .iter()
+        .map(|m| render_method(m, &error_name(&m.name, &client.error_kind)))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| e.extend(format!("In Tag {}.", &client.name)))?
+        .into_iter()
+        .reduce(|acc, e| acc + NewLine + e)
+        .unwrap_or_else(unit);
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line("import json") +
+        line("from typing import Any, ClassVar, Dict, List, Optional, Tuple, Union") +
+        NewLine +
+        line("from .. import models") +
+        line("from ..context import Context, to_param") +
+        line("from ..errors import ErrorInfo, ItemError, UnexpectedResponseError, pick_fields") +
+        NewLine +
+        NewLine +
+        errors +
+        NewLine +
+        NewLine +
+        line(unit() + "class " + &client.name + "Client:") +
+        indented(
+            line("def __init__(self, context: Context) -> None:") +
+            indented(line("self.context = context")) +
+            NewLine +
+            methods
+        );
+
+    Ok(PythonContext::new().print_to_string(code))
+}
@@ -0,0 +1,108 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::python::printer::{PythonContext, line, unit};
+use crate::spec::uses_bearer_security;
+use openapiv3::OpenAPI;
+
+pub fn context_gen(open_api: &OpenAPI) -> String {
+    let bearer = uses_bearer_security(open_api);
+
+    let token_param = if bearer {
+        unit() + ", token: Optional[str] = None"
+    } else {
+        unit()
+    };
+
+    let token_field = if bearer {
+        line("self.token = token")
+    } else {
+        unit()
+    };
+
+    let bearer_token = if bearer {
+        line("return self.token")
+    } else {
+        line("return None")
+    };
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line("from typing import Any, Dict, List, Optional, Sequence, Tuple") +
+        line("from urllib.parse import quote") +
+        NewLine +
+        line("import httpx") +
+        NewLine +
+        line("from .errors import DeserializationError, TransportError") +
+        NewLine +
+        NewLine +
+        line("def to_param(value: Any) -> str:") +
+        indented(
+            line("if isinstance(value, bool):") +
+            indented(line(r#"return "true" if value else "false""#)) +
+            line("return str(value)")
+        ) +
+        NewLine +
+        NewLine +
+        line("class Context:") +
+        indented(
+            line(unit() + "def __init__(self, base_url: str" + token_param + ", client: Optional[httpx.AsyncClient] = None) -> None:") +
+            indented(
+                line(r#"self.base_url = base_url.rstrip("/")"#) +
+                token_field +
+                line("self.client = client if client is not None else httpx.AsyncClient()")
+            ) +
+            NewLine +
+            line("def bearer_token(self) -> Optional[str]:") +
+            indented(bearer_token) +
+            NewLine +
+            line("def url(self, segments: Sequence[str]) -> str:") +
+            indented(line(r#"return self.base_url + "/" + "/".join(quote(segment, safe="") for segment in segments)"#)) +
+            NewLine +
+            line("async def send(") +
+            indented(
+                line("self,") +
+                line("service_name: str,") +
+                line("method: str,") +
+                line("url: str,") +
+                line("params: List[Tuple[str, str]],") +
+                line("headers: Dict[str, str],") +
+                line("**kwargs: Any,")
+            ) +
+            line(") -> httpx.Response:") +
+            indented(
+                line("token = self.bearer_token()") +
+                line("if token is not None:") +
+                indented(line(r#"headers["Authorization"] = f"Bearer {token}""#)) +
+                line("try:") +
+                indented(line("return await self.client.request(method, url, params=params, headers=headers, **kwargs)")) +
+                line("except httpx.HTTPError as error:") +
+                indented(line("raise TransportError(service_name, error) from error"))
+            ) +
+            NewLine +
+            line("def json(self, service_name: str, response: httpx.Response) -> Any:") +
+            indented(
+                line("try:") +
+                indented(line("return response.json()")) +
+                line("except ValueError as error:") +
+                indented(line("raise DeserializationError(service_name, error) from error"))
+            ) +
+            NewLine +
+            line("async def aclose(self) -> None:") +
+            indented(line("await self.client.aclose()"))
+        );
+
+    PythonContext::new().print_to_string(code)
+}
@@ -0,0 +1,155 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indoc::indoc;
+
+pub fn error_gen() -> String {
+    let code = indoc! { r#"
+        from abc import ABC, abstractmethod
+        from typing import Any, ClassVar, Dict, Generic, List, Optional, TypeVar
+
+
+        class ErrorInfo(ABC):
+            SERVICE_NAME: ClassVar[str]
+
+            @classmethod
+            def service_name(cls) -> str:
+                return cls.SERVICE_NAME
+
+            @abstractmethod
+            def status_code(self) -> int: ...
+
+            @abstractmethod
+            def errors(self) -> List[str]: ...
+
+            @abstractmethod
+            def code(self) -> Optional[str]: ...
+
+            def additional_fields(self) -> Optional[Dict[str, Any]]:
+                return None
+
+            def is_status_code(self, status_code: int) -> bool:
+                return self.status_code() == status_code
+
+            def is_success(self) -> bool:
+                return 200 <= self.status_code() < 300
+
+            def is_client_error(self) -> bool:
+                return 400 <= self.status_code() < 500
+
+            def is_server_error(self) -> bool:
+                return 500 <= self.status_code() < 600
+
+            def is_not_found(self) -> bool:
+                return self.is_status_code(404)
+
+            def has_code(self, code: str) -> bool:
+                return self.code() == code
+
+
+        T = TypeVar("T", bound=ErrorInfo)
+
+
+        class ClientError(Exception):
+            def __init__(self, service_name: str, message: str) -> None:
+                super().__init__(message)
+                self._service_name = service_name
+
+            def service_name(self) -> str:
+                return self._service_name
+
+            def status_code(self) -> Optional[int]:
+                return None
+
+            def errors(self) -> List[str]:
+                return []
+
+            def code(self) -> Optional[str]:
+                return None
+
+            def additional_fields(self) -> Optional[Dict[str, Any]]:
+                return None
+
+            def is_status_code(self, status_code: int) -> bool:
+                return self.status_code() == status_code
+
+            def is_success(self) -> bool:
+                status_code = self.status_code()
+                return status_code is not None and 200 <= status_code < 300
+
+            def is_client_error(self) -> bool:
+                status_code = self.status_code()
+                return status_code is not None and 400 <= status_code < 500
+
+            def is_server_error(self) -> bool:
+                status_code = self.status_code()
+                return status_code is not None and 500 <= status_code < 600
+
+            def is_not_found(self) -> bool:
+                return self.is_status_code(404)
+
+            def has_code(self, code: str) -> bool:
+                return self.code() == code
+
+
+        class ItemError(ClientError, Generic[T]):
+            def __init__(self, item: T) -> None:
+                super().__init__(item.service_name(), repr(item))
+                self.item = item
+
+            def status_code(self) -> Optional[int]:
+                return self.item.status_code()
+
+            def errors(self) -> List[str]:
+                return self.item.errors()
+
+            def code(self) -> Optional[str]:
+                return self.item.code()
+
+            def additional_fields(self) -> Optional[Dict[str, Any]]:
+                return self.item.additional_fields()
+
+
+        class TransportError(ClientError):
+            def __init__(self, service_name: str, cause: Exception) -> None:
+                super().__init__(service_name, f"Client error: {cause}")
+                self.cause = cause
+
+
+        class DeserializationError(ClientError):
+            def __init__(self, service_name: str, cause: Exception) -> None:
+                super().__init__(service_name, f"Deserialization error: {cause}")
+                self.cause = cause
+
+
+        class UnexpectedResponseError(ClientError):
+            def __init__(self, service_name: str, status: int, data: bytes) -> None:
+                super().__init__(
+                    service_name,
+                    f"Unexpected response - status {status}, data: {data.decode('utf-8', errors='replace')}",
+                )
+                self.status = status
+                self.data = data
+
+            def status_code(self) -> Optional[int]:
+                return self.status
+
+
+        def pick_fields(body: Any, names: List[str]) -> Optional[Dict[str, Any]]:
+            fields = {name: body[name] for name in names if body.get(name) is not None}
+            return fields if fields else None
+    "#};
+
+    code.to_string()
+}
@@ -0,0 +1,266 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::python::printer::{PythonContext, PythonPrinter, line, unit};
+use crate::python::types::{is_identifier, render_type, string_literal};
+use crate::rust::types::DataType;
+use crate::spec::{Field, ModelDef, ModelKind, UnionCase};
+use convert_case::{Case, Casing};
+use itertools::Itertools;
+use std::collections::HashSet;
+
+fn field_type(field: &Field) -> String {
+    let tpe = render_type(&field.tpe, "");
+
+    if field.required {
+        tpe
+    } else {
+        format!("NotRequired[Optional[{tpe}]]")
+    }
+}
+
+fn render_object(name: &str, fields: &[Field]) -> PythonPrinter {
+    if fields.iter().all(|f| is_identifier(&f.name)) {
+        let fields = fields
+            .iter()
+            .map(|f| line(unit() + &f.name + ": " + field_type(f)))
+            .reduce(|acc, e| acc + e)
+            .unwrap_or_else(|| line("pass"));
+
+        line(unit() + "class " + name + "(TypedDict):") + indented(fields)
+    } else {
+        // Keys which are not valid identifiers are only supported by the functional syntax
+        let fields = fields
+            .iter()
+            .map(|f| line(unit() + string_literal(&f.name) + ": " + field_type(f) + ","))
+            .reduce(|acc, e| acc + e)
+            .unwrap_or_else(unit);
+
+        line(unit() + name + " = TypedDict(" + string_literal(name) + ", {")
+            + indented(fields)
+            + line("})")
+    }
+}
+
+/// Union cases with an object payload get a subclass declaring the discriminator.
+struct CaseClass<'a> {
+    name: String,
+    payload: &'a str,
+    discriminator: &'a str,
+    tag: &'a str,
+}
+
+fn case_class<'a>(
+    union_name: &str,
+    discriminator: &'a str,
+    case: &'a UnionCase,
+    objects: &HashSet<&str>,
+    model_names: &HashSet<&str>,
+) -> Option<CaseClass<'a>> {
+    match &case.tpe {
+        DataType::Model(model)
+            if objects.contains(model.name.as_str()) && is_identifier(discriminator) =>
+        {
+            let name = format!("{union_name}{}", case.tag.to_case(Case::UpperCamel));
+            let name = if model_names.contains(name.as_str()) {
+                format!("{name}Case")
+            } else {
+                name
+            };
+
+            Some(CaseClass {
+                name,
+                payload: &model.name,
+                discriminator,
+                tag: &case.tag,
+            })
+        }
+        _ => None,
+    }
+}
+
+pub fn model_gen(models: &[ModelDef]) -> String {
+    let model_names: HashSet<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    let objects: HashSet<&str> = models
+        .iter()
+        .filter(|m| m.is_object())
+        .map(|m| m.name.as_str())
+        .collect();
+
+    let mut aliases = Vec::new();
+    let mut classes = Vec::new();
+    let mut case_classes = Vec::new();
+    let mut unions = Vec::new();
+
+    for model in models {
+        match &model.kind {
+            ModelKind::StringEnum { values } => {
+                let values = values.iter().map(|v| string_literal(v)).join(", ");
+                aliases.push(line(unit() + &model.name + " = Literal[" + values + "]"));
+            }
+            ModelKind::Alias { tpe } => {
+                aliases.push(line(unit() + &model.name + " = " + render_type(tpe, "")));
+            }
+            ModelKind::Object { fields } => {
+                classes.push(render_object(&model.name, fields));
+            }
+            ModelKind::Union {
+                discriminator,
+                cases,
+            } => {
+                let cases = cases
+                    .iter()
+                    .map(|case| {
+                        match case_class(&model.name, discriminator, case, &objects, &model_names) {
+                            Some(class) => {
+                                let name = string_literal(&class.name);
+                                case_classes.push(
+                                    line(
+                                        unit()
+                                            + "class "
+                                            + &class.name
+                                            + "("
+                                            + class.payload
+                                            + "):",
+                                    ) + indented(line(
+                                        unit()
+                                            + class.discriminator
+                                            + ": Literal["
+                                            + string_literal(class.tag)
+                                            + "]",
+                                    )),
+                                );
+                                name
+                            }
+                            None => render_type(&case.tpe, ""),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                if cases.is_empty() {
+                    unions.push(line(unit() + &model.name + " = Any"));
+                } else {
+                    unions.push(line(
+                        unit() + &model.name + " = Union[" + cases.join(", ") + "]",
+                    ));
+                }
+            }
+        }
+    }
+
+    let definitions = [aliases, classes, case_classes, unions]
+        .into_iter()
+        .flatten()
+        .reduce(|acc, e| acc + NewLine + NewLine + e)
+        .unwrap_or_else(unit);
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line("from typing import Any, Dict, List, Literal, NotRequired, Optional, TypedDict, Union") +
+        NewLine +
+        NewLine +
+        definitions;
+
+    PythonContext::new().print_to_string(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::python::model_gen::model_gen;
+    use crate::rust::types::{DataType, ModelType};
+    use crate::spec::{Field, ModelDef, ModelKind, UnionCase};
+    use indoc::indoc;
+
+    #[test]
+    fn models() {
+        let models = vec![
+            ModelDef {
+                name: "Filter".to_string(),
+                kind: ModelKind::Union {
+                    discriminator: "type".to_string(),
+                    cases: vec![UnionCase {
+                        tag: "Name".to_string(),
+                        tpe: DataType::Model(ModelType {
+                            name: "NameFilter".to_string(),
+                        }),
+                    }],
+                },
+            },
+            ModelDef {
+                name: "NameFilter".to_string(),
+                kind: ModelKind::Object {
+                    fields: vec![
+                        Field {
+                            name: "name".to_string(),
+                            tpe: DataType::String,
+                            required: true,
+                        },
+                        Field {
+                            name: "status".to_string(),
+                            tpe: DataType::Model(ModelType {
+                                name: "Status".to_string(),
+                            }),
+                            required: false,
+                        },
+                    ],
+                },
+            },
+            ModelDef {
+                name: "Range".to_string(),
+                kind: ModelKind::Object {
+                    fields: vec![Field {
+                        name: "from".to_string(),
+                        tpe: DataType::Array(Box::new(DataType::Json)),
+                        required: true,
+                    }],
+                },
+            },
+            ModelDef {
+                name: "Status".to_string(),
+                kind: ModelKind::StringEnum {
+                    values: vec!["Running".to_string(), "Idle".to_string()],
+                },
+            },
+        ];
+
+        let expected = indoc! { r#"
+            from typing import Any, Dict, List, Literal, NotRequired, Optional, TypedDict, Union
+
+
+            Status = Literal["Running", "Idle"]
+
+
+            class NameFilter(TypedDict):
+                name: str
+                status: NotRequired[Optional["Status"]]
+
+
+            Range = TypedDict("Range", {
+                "from": List[Any],
+            })
+
+
+            class FilterName(NameFilter):
+                type: Literal["Name"]
+
+
+            Filter = Union["FilterName"]
+        "#};
+
+        assert_eq!(model_gen(&models), expected);
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::python::printer::{PythonContext, line, unit};
+use crate::python::types::string_literal;
+use crate::spec::{ClientDef, error_name};
+use itertools::Itertools;
+
+pub struct ApiModule {
+    pub name: String,
+    pub exports: Vec<String>,
+}
+
+impl ApiModule {
+    pub fn new(name: String, client: &ClientDef) -> ApiModule {
+        let mut exports: Vec<String> = client
+            .methods
+            .iter()
+            .map(|m| error_name(&m.name, &client.error_kind))
+            .unique()
+            .collect();
+
+        exports.push(format!("{}Client", client.name));
+
+        ApiModule { name, exports }
+    }
+}
+
+const RUNTIME_EXPORTS: &[(&str, &[&str])] = &[
+    ("context", &["Context"]),
+    (
+        "errors",
+        &[
+            "ClientError",
+            "DeserializationError",
+            "ErrorInfo",
+            "ItemError",
+            "TransportError",
+            "UnexpectedResponseError",
+        ],
+    ),
+];
+
+pub fn init_gen(api_modules: &[ApiModule]) -> String {
+    let imports = api_modules
+        .iter()
+        .sorted_by_key(|m| &m.name)
+        .map(|m| {
+            (
+                format!(".api.{}", m.name),
+                m.exports.iter().sorted().join(", "),
+            )
+        })
+        .chain(
+            RUNTIME_EXPORTS
+                .iter()
+                .map(|(module, exports)| (format!(".{module}"), exports.join(", "))),
+        )
+        .map(|(module, exports)| line(unit() + "from " + module + " import " + exports))
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    let all = api_modules
+        .iter()
+        .flat_map(|m| m.exports.iter().map(|e| e.as_str()))
+        .chain(
+            RUNTIME_EXPORTS
+                .iter()
+                .flat_map(|(_, exports)| exports.iter().copied()),
+        )
+        .chain(["models"])
+        .sorted()
+        .map(|e| line(unit() + string_literal(e) + ","))
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line("from . import models") +
+        imports +
+        NewLine +
+        line("__all__ = [") +
+        indented(all) +
+        line("]");
+
+    PythonContext::new().print_to_string(code)
+}
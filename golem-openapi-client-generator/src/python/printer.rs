@@ -0,0 +1,89 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{IndentContext, NewLine, PrintContext, Printer, TreePrinter};
+
+pub type PythonPrinter = TreePrinter<PythonContext>;
+
+pub struct PythonContext {
+    code: String,
+    depth: usize,
+}
+
+impl PythonContext {
+    pub fn new() -> PythonContext {
+        PythonContext {
+            code: String::new(),
+            depth: 0,
+        }
+    }
+
+    pub fn print_to_string(mut self, p: PythonPrinter) -> String {
+        p.print(&mut self);
+
+        self.code
+    }
+}
+
+impl PrintContext for PythonContext {
+    fn print_str(&mut self, s: &str) {
+        self.code.push_str(s)
+    }
+}
+
+impl IndentContext for PythonContext {
+    fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    fn increment_ident(&mut self) {
+        self.depth += 1;
+    }
+
+    fn decrement_ident(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn indent_width(&self) -> usize {
+        4
+    }
+}
+
+pub trait IntoPythonTree {
+    fn tree(self) -> PythonPrinter;
+}
+
+impl IntoPythonTree for PythonPrinter {
+    fn tree(self) -> PythonPrinter {
+        self
+    }
+}
+
+impl<T: Printer<PythonContext> + 'static> IntoPythonTree for T {
+    fn tree(self) -> PythonPrinter {
+        TreePrinter::leaf(self)
+    }
+}
+
+pub fn unit() -> PythonPrinter {
+    TreePrinter::unit()
+}
+
+pub fn indent() -> PythonPrinter {
+    TreePrinter::indent()
+}
+
+pub fn line<T: IntoPythonTree>(code: T) -> PythonPrinter {
+    indent() + code.tree() + NewLine
+}
@@ -0,0 +1,78 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rust::types::{DataType, ModelType};
+use convert_case::{Case, Casing};
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name)
+}
+
+/// Name of a parameter or method, snake cased and escaped if it is a keyword.
+pub fn identifier(name: &str) -> String {
+    let name = name.to_case(Case::Snake);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+pub fn string_literal(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Python type annotation of a data type. Models are always forward references, qualified
+/// by `model_prefix`, so the order of the definitions does not matter.
+pub fn render_type(tpe: &DataType, model_prefix: &str) -> String {
+    match tpe {
+        DataType::String | DataType::Uuid | DataType::DateTime | DataType::Yaml => {
+            "str".to_string()
+        }
+        DataType::Boolean => "bool".to_string(),
+        DataType::Number => "float".to_string(),
+        DataType::Int(_) => "int".to_string(),
+        DataType::Model(ModelType { name }) => string_literal(&format!("{model_prefix}{name}")),
+        DataType::Binary => "bytes".to_string(),
+        DataType::Array(item) => format!("List[{}]", render_type(item, model_prefix)),
+        DataType::MapOf(element) => format!("Dict[str, {}]", render_type(element, model_prefix)),
+        DataType::Json => "Any".to_string(),
+        DataType::Unit => "None".to_string(),
+    }
+}
@@ -16,128 +16,16 @@ use crate::printer::*;
 use crate::rust::lib_gen::{Module, ModuleDef, ModuleName};
 use crate::rust::model_gen::RefCache;
 use crate::rust::printer::*;
-use crate::rust::types::{
-    DataType, RustPrinter, RustResult, escape_keywords, ref_or_box_schema_type, ref_or_schema_type,
+use crate::rust::types::{DataType, RustPrinter, RustResult};
+use crate::spec::{
+    AdditionalField, ClientDef, CodeConvention, ErrorConvention, ErrorKind, Method, MethodErrors,
+    Param, ParamKind, PathElement, as_range, client_def, error_name, infer_error_conventions,
 };
 use crate::{Error, Result};
 use convert_case::{Case, Casing};
-use indexmap::IndexMap;
 use itertools::Itertools;
-use openapiv3::{
-    OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, PathItem, ReferenceOr,
-    RequestBody, Response, Schema, SchemaKind, StatusCode, Tag, Type,
-};
-use std::collections::{BTreeMap, HashMap, HashSet};
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum PathElement {
-    Const(String),
-    Param { name: String },
-}
-
-impl PathElement {
-    pub fn from_string(p: &str) -> PathElement {
-        if p.starts_with('{') && p.ends_with('}') {
-            PathElement::Param {
-                name: p
-                    .strip_prefix('{')
-                    .unwrap()
-                    .strip_suffix('}')
-                    .unwrap()
-                    .to_string(),
-            }
-        } else {
-            PathElement::Const(p.to_string())
-        }
-    }
-
-    pub fn name(&self) -> String {
-        match self {
-            PathElement::Const(name) => name.to_string(),
-            PathElement::Param { name } => name.to_string(),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Path(Vec<PathElement>);
-
-impl Path {
-    pub fn common_prefix(&self, that: Path) -> Path {
-        if self.0.starts_with(&that.0) {
-            return that;
-        }
-
-        let elems: Vec<PathElement> = self
-            .0
-            .iter()
-            .zip(that.0)
-            .take_while(|(a, b)| a == &b)
-            .map(|(_, e)| e)
-            .collect();
-
-        Path(elems)
-    }
-
-    pub fn from_string(path: &str) -> Path {
-        let elems: Vec<PathElement> = path
-            .split('/')
-            .filter(|p| !p.trim().is_empty())
-            .map(PathElement::from_string)
-            .collect();
-
-        Path(elems)
-    }
-
-    pub fn strip_prefix(&self, prefix_len: usize) -> Path {
-        let slice = &self.0[prefix_len..];
-        Path(slice.to_vec())
-    }
-
-    pub fn method_name(&self, method: &str) -> String {
-        #[allow(unstable_name_collisions)]
-        let name: String = self
-            .0
-            .iter()
-            .map(|e| e.name().to_case(Case::Snake))
-            .intersperse("_".to_string())
-            .collect();
-
-        if name.is_empty() {
-            method.to_string()
-        } else {
-            format!("{name}_{method}")
-        }
-    }
-}
-
-struct PathOperation {
-    path: Path,
-    original_path: String,
-    method: String,
-    op: Operation,
-}
-
-#[derive(Debug, Clone)]
-struct Method {
-    name: String,
-    path: Path,
-    original_path: String,
-    http_method: String,
-    params: Vec<Param>,
-    result: DataType,
-    result_status_code: StatusCode,
-    errors: MethodErrors,
-}
-
-fn error_name(method_name: &str, error_kind: &ErrorKind) -> String {
-    match error_kind {
-        ErrorKind::Common { name } => name.clone(),
-        ErrorKind::Custom { prefix } => {
-            format!("{prefix}{}Error", method_name.to_case(Case::UpperCamel))
-        }
-    }
-}
+use openapiv3::{OpenAPI, StatusCode, Tag};
+use std::collections::{HashMap, HashSet};
 
 fn client_error() -> RustPrinter {
     rust_name("crate", "Error")
@@ -159,62 +47,6 @@ impl Method {
 
         code
     }
-
-    fn get_path_param(&self, original_name: &str) -> Option<&Param> {
-        self.params
-            .iter()
-            .find(|p| p.kind == ParamKind::Path && p.original_name == original_name)
-    }
-}
-
-enum ErrorKind {
-    Common { name: String },
-    Custom { prefix: String },
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct MethodErrors {
-    codes: BTreeMap<StatusCode, DataType>,
-}
-
-#[derive(Debug, Clone)]
-struct Param {
-    original_name: String,
-    name: String,
-    tpe: DataType,
-    required: bool,
-    kind: ParamKind,
-}
-
-#[derive(Debug, Clone)]
-pub struct RequestBodyParams {
-    params: HashMap<ContentType, Vec<Param>>,
-}
-
-impl RequestBodyParams {
-    fn has_single_content_type(&self) -> bool {
-        self.params.len() == 1
-    }
-
-    fn get_default_request_body_param(&self) -> Option<&Vec<Param>> {
-        self.params
-            .values()
-            .next()
-            .filter(|_| self.has_single_content_type())
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub struct ContentType(pub String);
-
-impl ContentType {
-    pub fn is_json(&self) -> bool {
-        self.0 == "application/json"
-    }
-
-    pub fn is_yaml(&self) -> bool {
-        self.0 == "application/x-yaml"
-    }
 }
 
 impl Param {
@@ -231,637 +63,6 @@ impl Param {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum ParamKind {
-    Path,
-    Query,
-    Header,
-    Cookie,
-    Body,
-    Multipart,
-}
-
-impl ParamKind {
-    fn from_parameter(param: &Parameter) -> ParamKind {
-        match param {
-            Parameter::Query { .. } => ParamKind::Query,
-            Parameter::Header { .. } => ParamKind::Header,
-            Parameter::Path { .. } => ParamKind::Path,
-            Parameter::Cookie { .. } => ParamKind::Cookie,
-        }
-    }
-}
-
-fn tag_operations(
-    tag: &Option<Tag>,
-    path: &str,
-    path_item: &ReferenceOr<PathItem>,
-) -> Vec<PathOperation> {
-    if let Some(item) = path_item.as_item() {
-        if let Some(tag) = tag {
-            item.iter()
-                .filter(|(_, op)| op.tags.contains(&tag.name))
-                .map(|(method, op)| PathOperation {
-                    path: Path::from_string(path),
-                    original_path: path.to_string(),
-                    method: method.to_string(),
-                    op: op.clone(),
-                })
-                .collect()
-        } else {
-            item.iter()
-                .filter(|(_, op)| op.tags.is_empty())
-                .map(|(method, op)| PathOperation {
-                    path: Path::from_string(path),
-                    original_path: path.to_string(),
-                    method: method.to_string(),
-                    op: op.clone(),
-                })
-                .collect()
-        }
-    } else {
-        Vec::new()
-    }
-}
-
-fn match_tag(tag: &Option<Tag>, path_item: &ReferenceOr<PathItem>) -> bool {
-    if let Some(item) = path_item.as_item() {
-        if let Some(tag) = tag {
-            item.iter().any(|(_, op)| op.tags.contains(&tag.name))
-        } else {
-            item.iter().any(|(_, op)| op.tags.is_empty())
-        }
-    } else {
-        false
-    }
-}
-
-fn param_data_to_type(data: &ParameterData, ref_cache: &mut RefCache) -> Result<DataType> {
-    match &data.format {
-        ParameterSchemaOrContent::Schema(ref_or_schema) => {
-            ref_or_schema_type(ref_or_schema, ref_cache, None)
-        }
-        ParameterSchemaOrContent::Content(_) => {
-            Err(Error::unimplemented("Content parameter is not supported."))
-        }
-    }
-}
-
-fn parameter(p: &ReferenceOr<Parameter>, ref_cache: &mut RefCache) -> Result<Param> {
-    if let Some(param) = p.as_item() {
-        let param_type = param_data_to_type(param.parameter_data_ref(), ref_cache)?;
-
-        Ok(Param {
-            original_name: param.parameter_data_ref().name.to_string(),
-            name: param.parameter_data_ref().name.to_case(Case::Snake),
-            tpe: param_type,
-            required: param.parameter_data_ref().required,
-            kind: ParamKind::from_parameter(param),
-        })
-    } else {
-        Err(Error::unimplemented("Unexpected ref parameter."))
-    }
-}
-
-fn request_body_params(
-    body: &ReferenceOr<RequestBody>,
-    ref_cache: &mut RefCache,
-) -> Result<RequestBodyParams> {
-    let mut content_type_params = HashMap::new();
-
-    match body {
-        ReferenceOr::Reference { reference } => {
-            return Err(Error::unimplemented(format!(
-                "Unexpected ref request body: '{reference}'."
-            )));
-        }
-        ReferenceOr::Item(body) => {
-            for (content_type, media_type) in &body.content {
-                if content_type.starts_with("application/json") || content_type == "*/*" {
-                    let schema = match &media_type.schema {
-                        None => Err(Error::unimplemented("JSON content without schema.")),
-                        Some(schema) => Ok(schema),
-                    };
-
-                    content_type_params.insert(
-                        ContentType(content_type.clone()),
-                        vec![Param {
-                            original_name: "".to_string(),
-                            name: "value".to_string(),
-                            tpe: ref_or_schema_type(
-                                schema?,
-                                ref_cache,
-                                Some(content_type.clone()),
-                            )?,
-                            required: body.required,
-                            kind: ParamKind::Body,
-                        }],
-                    );
-                } else if content_type == "application/octet-stream" {
-                    content_type_params.insert(
-                        ContentType(content_type.clone()),
-                        vec![Param {
-                            original_name: "".to_string(),
-                            name: "value".to_string(),
-                            tpe: DataType::Binary,
-                            required: body.required,
-                            kind: ParamKind::Body,
-                        }],
-                    );
-                } else if content_type.contains("application/x-yaml") {
-                    let schema = match &media_type.schema {
-                        None => Err(Error::unimplemented("YAML content without schema.")),
-                        Some(schema) => Ok(schema),
-                    };
-
-                    let param = Param {
-                        original_name: "".to_string(),
-                        name: "value".to_string(),
-                        tpe: ref_or_schema_type(schema?, ref_cache, Some(content_type.clone()))?,
-                        required: body.required,
-                        kind: ParamKind::Body,
-                    };
-
-                    content_type_params.insert(ContentType(content_type.clone()), vec![param]);
-                } else if content_type == "multipart/form-data" {
-                    match &media_type.schema {
-                        None => {
-                            return Err(Error::unimplemented("Multipart content without schema."));
-                        }
-                        Some(schema) => match schema {
-                            ReferenceOr::Reference { reference } => {
-                                return Err(Error::unimplemented(format!(
-                                    "Unexpected ref multipart schema: '{reference}'."
-                                )));
-                            }
-                            ReferenceOr::Item(schema) => match &schema.schema_kind {
-                                SchemaKind::Type(Type::Object(obj)) => {
-                                    fn multipart_param(
-                                        name: &str,
-                                        required: bool,
-                                        schema: &ReferenceOr<Box<Schema>>,
-                                        ref_cache: &mut RefCache,
-                                    ) -> Result<Param> {
-                                        Ok(Param {
-                                            original_name: name.to_string(),
-                                            name: name.to_case(Case::Snake),
-                                            tpe: ref_or_box_schema_type(schema, ref_cache)?,
-                                            required,
-                                            kind: ParamKind::Multipart,
-                                        })
-                                    }
-
-                                    let params = obj
-                                        .properties
-                                        .iter()
-                                        .map(|(name, schema)| {
-                                            multipart_param(
-                                                name,
-                                                body.required && obj.required.contains(name),
-                                                schema,
-                                                ref_cache,
-                                            )
-                                        })
-                                        .collect::<Result<Vec<_>>>()?;
-
-                                    content_type_params
-                                        .insert(ContentType(content_type.clone()), params);
-                                }
-                                _ => {
-                                    return Err(Error::unimplemented(
-                                        "Object schema expected for multipart request body.",
-                                    ));
-                                }
-                            },
-                        },
-                    }
-                } else {
-                    return Err(Error::unimplemented(format!(
-                        "Request body content type: '{content_type}'."
-                    )));
-                }
-            }
-        }
-    }
-
-    Ok(RequestBodyParams {
-        params: content_type_params,
-    })
-}
-
-fn parameters(op: &PathOperation, ref_cache: &mut RefCache) -> Result<Vec<Param>> {
-    op.op
-        .parameters
-        .iter()
-        .map(|p| parameter(p, ref_cache))
-        .collect()
-}
-
-fn as_code(code: &StatusCode) -> Option<u16> {
-    match code {
-        StatusCode::Code(code) => Some(*code),
-        StatusCode::Range(_) => None,
-    }
-}
-
-fn as_range(code: &StatusCode) -> Option<u16> {
-    match code {
-        StatusCode::Code(_) => None,
-        StatusCode::Range(v) => Some(*v),
-    }
-}
-
-fn is_success_code(code: &StatusCode) -> bool {
-    if let Some(code) = as_code(code) {
-        (200..300).contains(&code)
-    } else {
-        false
-    }
-}
-
-fn is_success_range(code: &StatusCode) -> bool {
-    if let Some(v) = as_range(code) {
-        v == 2
-    } else {
-        false
-    }
-}
-
-fn response_type(response: &ReferenceOr<Response>, ref_cache: &mut RefCache) -> Result<DataType> {
-    match response {
-        ReferenceOr::Reference { reference } => Err(Error::unimplemented(format!(
-            "Reference in response top level: {reference}"
-        ))),
-        ReferenceOr::Item(resp) => {
-            if resp.content.is_empty() {
-                // No content case
-                Ok(DataType::Unit)
-            } else if resp.content.len() == 1 {
-                let (content_type, media_type) = resp.content.first().unwrap();
-
-                if content_type.starts_with("application/json") {
-                    let schema = match &media_type.schema {
-                        None => Err(Error::unimplemented(
-                            "JSON response content without schema.",
-                        )),
-                        Some(schema) => Ok(schema),
-                    };
-
-                    Ok(ref_or_schema_type(
-                        schema?,
-                        ref_cache,
-                        Some(content_type.clone()),
-                    )?)
-                } else if content_type == "application/octet-stream" {
-                    Ok(DataType::Binary)
-                } else {
-                    Err(Error::unimplemented(format!(
-                        "Response content type: {content_type}"
-                    )))
-                }
-            } else {
-                Err(Error::unimplemented(
-                    "Response content with not exactly 1 option.",
-                ))
-            }
-        }
-    }
-}
-
-fn method_result(
-    responses: &IndexMap<StatusCode, ReferenceOr<Response>>,
-    ref_cache: &mut RefCache,
-) -> Result<(StatusCode, DataType)> {
-    let code_res = responses
-        .iter()
-        .filter(|(code, _)| is_success_code(code))
-        .min_by_key(|(code, _)| as_code(code));
-
-    let res = code_res.or_else(|| responses.iter().find(|(code, _)| is_success_range(code)));
-
-    if let Some((code, resp)) = res {
-        Ok((code.clone(), response_type(resp, ref_cache)?))
-    } else {
-        Err(Error::unimplemented("No success results in API."))
-    }
-}
-
-fn method_errors(
-    responses: &IndexMap<StatusCode, ReferenceOr<Response>>,
-    result_code: StatusCode,
-    ref_cache: &mut RefCache,
-) -> Result<MethodErrors> {
-    let codes: Result<BTreeMap<StatusCode, DataType>> = responses
-        .iter()
-        .filter(|(code, _)| *code != &result_code)
-        .map(|(code, resp)| response_type(resp, ref_cache).map(|t| (code.clone(), t)))
-        .collect();
-
-    Ok(MethodErrors { codes: codes? })
-}
-
-fn trait_methods_specific_to_content_type(
-    op: &PathOperation,
-    prefix_length: usize,
-    ref_cache: &mut RefCache,
-) -> Result<Vec<Method>> {
-    let (result_code, result_type) = method_result(&op.op.responses.responses, ref_cache)?;
-
-    let name = op
-        .op
-        .operation_id
-        .as_ref()
-        .map(|op_id| op_id.to_case(Case::Snake))
-        .unwrap_or_else(|| op.path.strip_prefix(prefix_length).method_name(&op.method));
-
-    let mut main_params = parameters(op, ref_cache)?;
-
-    if let Some(body) = &op.op.request_body {
-        let content_specific = request_body_params(body, ref_cache)?;
-
-        if let Some(request_body_params) = content_specific.get_default_request_body_param() {
-            main_params.extend(request_body_params.iter().cloned());
-            return Ok(vec![create_method(
-                name,
-                op,
-                &main_params,
-                result_type,
-                result_code,
-                ref_cache,
-            )?]);
-        }
-
-        let mut methods = Vec::new();
-        for (content_type, params) in content_specific.params {
-            let method_name = match_content_type(content_type, &name)?;
-            let new_params = [main_params.clone(), params].concat();
-            methods.push(create_method(
-                method_name,
-                op,
-                &new_params,
-                result_type.clone(),
-                result_code.clone(),
-                ref_cache,
-            )?);
-        }
-
-        Ok(methods)
-    } else {
-        Ok(vec![create_method(
-            name,
-            op,
-            &main_params,
-            result_type,
-            result_code,
-            ref_cache,
-        )?])
-    }
-}
-
-fn create_method(
-    name: String,
-    op: &PathOperation,
-    params: &[Param],
-    result_type: DataType,
-    result_code: StatusCode,
-    ref_cache: &mut RefCache,
-) -> Result<Method> {
-    Ok(Method {
-        name,
-        path: op.path.clone(),
-        original_path: op.original_path.clone(),
-        http_method: op.method.to_string(),
-        params: params.to_vec(),
-        result: result_type,
-        result_status_code: result_code.clone(),
-        errors: method_errors(&op.op.responses.responses, result_code, ref_cache)?,
-    })
-}
-
-fn match_content_type(content_type: ContentType, base_name: &str) -> Result<String> {
-    if content_type.is_json() {
-        Ok(format!("{}_json", base_name))
-    } else if content_type.is_yaml() {
-        Ok(format!("{}_yaml", base_name))
-    } else {
-        Err(Error::unimplemented(
-            "Multiple content types supported only for JSON and YAML",
-        ))
-    }
-}
-
-fn trait_methods(
-    operations: &[PathOperation],
-    prefix_length: usize,
-    ref_cache: &mut RefCache,
-) -> Result<Vec<Method>> {
-    let res = operations
-        .iter()
-        .map(|op| trait_methods_specific_to_content_type(op, prefix_length, ref_cache))
-        .collect::<Result<Vec<Vec<_>>>>()?;
-
-    Ok(res.into_iter().flatten().collect())
-}
-
-#[derive(Debug, Clone)]
-enum CodeConvention {
-    Absent,
-    Required,
-}
-
-#[derive(Debug, Clone)]
-struct UnionVariant {
-    variant_name: String,
-    model_name: String,
-}
-
-#[derive(Debug, Clone)]
-struct AdditionalField {
-    wire_name: String,
-    rust_name: String,
-}
-
-#[derive(Debug, Clone)]
-enum ErrorConvention {
-    Single {
-        code: CodeConvention,
-        additional_fields: Vec<AdditionalField>,
-    },
-    Multiple {
-        code: CodeConvention,
-        additional_fields: Vec<AdditionalField>,
-    },
-    Union {
-        variants: Vec<UnionVariant>,
-    },
-    Unknown,
-}
-
-fn infer_error_conventions(open_api: &OpenAPI) -> HashMap<String, ErrorConvention> {
-    let mut result = HashMap::new();
-
-    if let Some(components) = &open_api.components {
-        for (schema_name, schema_ref) in &components.schemas {
-            let model_name = schema_name.to_case(Case::UpperCamel);
-
-            let convention = match schema_ref {
-                ReferenceOr::Item(schema) => infer_error_convention_for_schema(open_api, schema),
-                ReferenceOr::Reference { .. } => ErrorConvention::Unknown,
-            };
-
-            result.insert(model_name, convention);
-        }
-    }
-
-    result
-}
-
-fn infer_error_convention_for_schema(open_api: &OpenAPI, schema: &Schema) -> ErrorConvention {
-    match &schema.schema_kind {
-        SchemaKind::Type(Type::Object(obj)) => {
-            let has_error = obj
-                .properties
-                .get("error")
-                .is_some_and(|schema| is_string_schema_ref(open_api, schema));
-            let has_errors = obj
-                .properties
-                .get("errors")
-                .is_some_and(|schema| is_array_of_string_schema_ref(open_api, schema));
-
-            let code = infer_code_convention(open_api, obj);
-
-            if has_error {
-                ErrorConvention::Single {
-                    code,
-                    additional_fields: infer_additional_fields(obj, &["code", "error"]),
-                }
-            } else if has_errors {
-                ErrorConvention::Multiple {
-                    code,
-                    additional_fields: infer_additional_fields(obj, &["code", "errors"]),
-                }
-            } else {
-                ErrorConvention::Unknown
-            }
-        }
-        SchemaKind::Any(any) if !any.one_of.is_empty() => {
-            if let Some(discriminator) = &schema.schema_data.discriminator {
-                let variants = discriminator
-                    .mapping
-                    .iter()
-                    .filter_map(|(variant_name, case_reference)| {
-                        resolve_union_payload_model_name(open_api, case_reference).map(
-                            |model_name| UnionVariant {
-                                variant_name: variant_name.to_case(Case::UpperCamel),
-                                model_name,
-                            },
-                        )
-                    })
-                    .collect::<Vec<_>>();
-
-                if variants.is_empty() {
-                    ErrorConvention::Unknown
-                } else {
-                    ErrorConvention::Union { variants }
-                }
-            } else {
-                ErrorConvention::Unknown
-            }
-        }
-        _ => ErrorConvention::Unknown,
-    }
-}
-
-fn infer_additional_fields(
-    object_type: &openapiv3::ObjectType,
-    normalized_fields: &[&str],
-) -> Vec<AdditionalField> {
-    object_type
-        .properties
-        .keys()
-        .filter(|name| !normalized_fields.contains(&name.as_str()))
-        .map(|name| AdditionalField {
-            wire_name: name.clone(),
-            rust_name: escape_keywords(&name.to_case(Case::Snake)),
-        })
-        .collect()
-}
-
-fn resolve_union_payload_model_name(open_api: &OpenAPI, case_reference: &str) -> Option<String> {
-    let case_schema_name = case_reference.strip_prefix("#/components/schemas/")?;
-    let components = open_api.components.as_ref()?;
-    let case_schema = components.schemas.get(case_schema_name)?.as_item()?;
-
-    match &case_schema.schema_kind {
-        SchemaKind::AllOf { all_of } => all_of.iter().find_map(|schema| match schema {
-            ReferenceOr::Reference { reference } => reference
-                .strip_prefix("#/components/schemas/")
-                .map(|name| name.to_case(Case::UpperCamel)),
-            ReferenceOr::Item(_) => None,
-        }),
-        _ => None,
-    }
-}
-
-fn resolve_schema_by_reference<'a>(open_api: &'a OpenAPI, reference: &str) -> Option<&'a Schema> {
-    let schema_name = reference.strip_prefix("#/components/schemas/")?;
-    open_api
-        .components
-        .as_ref()?
-        .schemas
-        .get(schema_name)?
-        .as_item()
-}
-
-fn resolve_schema_ref<'a>(
-    open_api: &'a OpenAPI,
-    schema: &'a ReferenceOr<Box<Schema>>,
-) -> Option<&'a Schema> {
-    match schema {
-        ReferenceOr::Reference { reference } => resolve_schema_by_reference(open_api, reference),
-        ReferenceOr::Item(schema) => Some(schema),
-    }
-}
-
-fn is_string_schema(schema: &Schema) -> bool {
-    matches!(&schema.schema_kind, SchemaKind::Type(Type::String(_)))
-}
-
-fn is_string_schema_ref(open_api: &OpenAPI, schema: &ReferenceOr<Box<Schema>>) -> bool {
-    resolve_schema_ref(open_api, schema).is_some_and(is_string_schema)
-}
-
-fn is_array_of_string_schema(schema: &Schema) -> bool {
-    match &schema.schema_kind {
-        SchemaKind::Type(Type::Array(array)) => {
-            array.items.as_ref().is_some_and(|items| match items {
-                ReferenceOr::Reference { .. } => false,
-                ReferenceOr::Item(item) => is_string_schema(item),
-            })
-        }
-        _ => false,
-    }
-}
-
-fn is_array_of_string_schema_ref(open_api: &OpenAPI, schema: &ReferenceOr<Box<Schema>>) -> bool {
-    resolve_schema_ref(open_api, schema).is_some_and(is_array_of_string_schema)
-}
-
-fn infer_code_convention(
-    open_api: &OpenAPI,
-    object_type: &openapiv3::ObjectType,
-) -> CodeConvention {
-    if object_type
-        .properties
-        .get("code")
-        .is_some_and(|schema| is_string_schema_ref(open_api, schema))
-    {
-        CodeConvention::Required
-    } else {
-        CodeConvention::Absent
-    }
-}
-
 fn render_errors_expr_for_model(
     model_name: &str,
     body_var: &str,
@@ -882,7 +83,7 @@ fn render_errors_expr_for_model(
         ErrorConvention::Multiple { .. } => {
             unit() + rust_name("crate", "ErrorMessages") + "::Many(&" + body_var + ".errors)"
         }
-        ErrorConvention::Union { variants } => {
+        ErrorConvention::Union { variants, .. } => {
             let cases = variants
                 .iter()
                 .map(|variant| {
@@ -929,7 +130,7 @@ fn render_code_expr_for_model(
             CodeConvention::Absent => unit() + "None",
             CodeConvention::Required => unit() + "Some(" + body_var + ".code.as_str())",
         },
-        ErrorConvention::Union { variants } => {
+        ErrorConvention::Union { variants, .. } => {
             let cases = variants
                 .iter()
                 .map(|variant| {
@@ -1247,7 +448,7 @@ fn errors_needs_body_for_model(
     {
         ErrorConvention::Single { .. } | ErrorConvention::Multiple { .. } => true,
         ErrorConvention::Unknown => false,
-        ErrorConvention::Union { variants } => variants.iter().any(|variant| {
+        ErrorConvention::Union { variants, .. } => variants.iter().any(|variant| {
             errors_needs_body_for_model(&variant.model_name, conventions, &mut visited.clone())
         }),
     };
@@ -1286,7 +487,7 @@ fn code_needs_body_for_model(
             ..
         }
         | ErrorConvention::Unknown => false,
-        ErrorConvention::Union { variants } => variants.iter().any(|variant| {
+        ErrorConvention::Union { variants, .. } => variants.iter().any(|variant| {
             code_needs_body_for_model(&variant.model_name, conventions, &mut visited.clone())
         }),
     };
@@ -1315,7 +516,7 @@ fn render_additional_fields_expr_for_model(
         | ErrorConvention::Multiple {
             additional_fields, ..
         } => render_additional_fields_expr(body_var, additional_fields),
-        ErrorConvention::Union { variants } => {
+        ErrorConvention::Union { variants, .. } => {
             let cases = variants
                 .iter()
                 .map(|variant| {
@@ -1406,7 +607,7 @@ fn additional_fields_needs_body_for_model(
             additional_fields, ..
         } => !additional_fields.is_empty(),
         ErrorConvention::Unknown => false,
-        ErrorConvention::Union { variants } => variants.iter().any(|variant| {
+        ErrorConvention::Union { variants, .. } => variants.iter().any(|variant| {
             additional_fields_needs_body_for_model(
                 &variant.model_name,
                 conventions,
@@ -1811,52 +1012,14 @@ pub fn client_gen(
     ref_cache: &mut RefCache,
     ignored_paths: &[&str],
 ) -> Result<Module> {
-    let ignored_paths: HashSet<String> =
-        HashSet::from_iter(ignored_paths.iter().map(|ip| ip.to_string()));
-
-    let paths: HashMap<String, ReferenceOr<PathItem>> = open_api
-        .paths
-        .iter()
-        .filter_map(|(path_key, path_item)| {
-            if !ignored_paths.contains(path_key) && match_tag(&tag, path_item) {
-                Some((path_key.clone(), path_item.clone()))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    let common_prefix = paths
-        .keys()
-        .map(|p| Path::from_string(p))
-        .reduce(|acc, e| e.common_prefix(acc));
-
-    let prefix_length = common_prefix.map(|p| p.0.len()).unwrap_or(0);
-
-    let operations: Vec<PathOperation> = paths
-        .iter()
-        .flat_map(|(path, op)| tag_operations(&tag, path, op))
-        .collect();
-
-    let name = tag
-        .map(|t| t.name)
-        .unwrap_or("Other".to_string())
-        .to_case(Case::UpperCamel);
-
-    let methods: Vec<Method> = trait_methods(&operations, prefix_length, ref_cache)
-        .map_err(|e| e.extend(format!("In Tag {}.", &name)))?;
+    let client = client_def(open_api, tag, ref_cache, ignored_paths)?;
+    let all_errors = client.errors();
 
-    let common_error = methods.iter().map(|m| &m.errors).unique().count() == 1;
-
-    let error_kind = if common_error {
-        ErrorKind::Common {
-            name: format!("{name}Error"),
-        }
-    } else {
-        ErrorKind::Custom {
-            prefix: name.clone(),
-        }
-    };
+    let ClientDef {
+        name,
+        methods,
+        error_kind,
+    } = client;
 
     let methods_rendered = methods
         .iter()
@@ -1874,19 +1037,6 @@ pub fn client_gen(
         .reduce(|acc, e| acc + NewLine + e)
         .unwrap_or_else(unit);
 
-    let all_errors: Vec<(String, MethodErrors)> = if common_error {
-        methods
-            .first()
-            .iter()
-            .map(|m| (m.name.clone(), m.errors.clone()))
-            .collect()
-    } else {
-        methods
-            .iter()
-            .map(|m| (m.name.clone(), m.errors.clone()))
-            .collect()
-    };
-
     let error_conventions = infer_error_conventions(open_api);
 
     let rendered_errors: Result<Vec<_>> = all_errors
@@ -67,7 +67,7 @@ fn rename_line(to: &str) -> RustPrinter {
     line(unit() + r#"#[serde(rename = ""# + to + r#"")]"#)
 }
 
-fn enum_schema_sanity_check(any_schema: &AnySchema, data: &SchemaData) -> Result<()> {
+pub(crate) fn enum_schema_sanity_check(any_schema: &AnySchema, data: &SchemaData) -> Result<()> {
     if !any_schema.any_of.is_empty() {
         Err(Error::unimplemented(
             "oneOf expected for enum, but got anyOf",
@@ -91,10 +91,10 @@ fn enum_schema_sanity_check(any_schema: &AnySchema, data: &SchemaData) -> Result
     }
 }
 
-struct EnumCase {
-    name: String,
-    reference: String,
-    data_type: DataType,
+pub(crate) struct EnumCase {
+    pub name: String,
+    pub reference: String,
+    pub data_type: DataType,
 }
 
 impl EnumCase {
@@ -318,7 +318,7 @@ fn extract_enum_case(
     }
 }
 
-fn extract_enum_cases(
+pub(crate) fn extract_enum_cases(
     open_api: &OpenAPI,
     discriminator: &Discriminator,
     ref_cache: &mut RefCache,
@@ -163,10 +163,16 @@ pub fn ref_type_name(reference: &str, ref_cache: &mut RefCache) -> Result<DataTy
         .expect("Unexpected reference prefix.");
 
     Ok(DataType::Model(ModelType {
-        name: name.to_case(Case::UpperCamel),
+        name: model_type_name(name),
     }))
 }
 
+/// Type name of a schema. Schemas of raw Rust identifiers keep the `r#` prefix in their names,
+/// which is not a valid part of a type name in any of the generated languages.
+pub fn model_type_name(schema_name: &str) -> String {
+    schema_name.replace("r#", "").to_case(Case::UpperCamel)
+}
+
 fn schema_type(
     schema: &Schema,
     ref_cache: &mut RefCache,
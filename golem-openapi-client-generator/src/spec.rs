@@ -0,0 +1,1375 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Language independent description of the clients and the models they reference, shared by
+//! the generator back-ends.
+
+use crate::rust::model_gen::{RefCache, enum_schema_sanity_check, extract_enum_cases};
+use crate::rust::types::{
+    DataType, escape_keywords, model_type_name, ref_or_box_schema_type, ref_or_schema_type,
+    ref_type_name,
+};
+use crate::{Error, Result};
+use convert_case::{Case, Casing};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use openapiv3::{
+    OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, PathItem, ReferenceOr,
+    RequestBody, Response, Schema, SchemaKind, SecurityScheme, StatusCode, Tag, Type,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub tpe: DataType,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnionCase {
+    pub tag: String,
+    pub tpe: DataType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelKind {
+    StringEnum {
+        values: Vec<String>,
+    },
+    Object {
+        fields: Vec<Field>,
+    },
+    /// Internally tagged union, the discriminator property is merged into the case payload.
+    Union {
+        discriminator: String,
+        cases: Vec<UnionCase>,
+    },
+    /// Schemas without a dedicated shape, including the ones the generator cannot describe
+    /// precisely, which are aliased to arbitrary JSON.
+    Alias {
+        tpe: DataType,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDef {
+    pub name: String,
+    pub kind: ModelKind,
+}
+
+impl ModelDef {
+    pub fn is_object(&self) -> bool {
+        matches!(self.kind, ModelKind::Object { .. })
+    }
+}
+
+/// Resolves the type of a property, falling back to arbitrary JSON for unsupported schemas.
+fn field_type(schema: &ReferenceOr<Box<Schema>>, ref_cache: &mut RefCache) -> DataType {
+    match schema {
+        ReferenceOr::Item(item) => match &item.schema_kind {
+            // Poem wraps documented references in a single element allOf
+            SchemaKind::AllOf { all_of } if all_of.len() == 1 => {
+                ref_or_schema_type(&all_of[0], ref_cache, None).unwrap_or(DataType::Json)
+            }
+            _ => ref_or_box_schema_type(schema, ref_cache).unwrap_or(DataType::Json),
+        },
+        ReferenceOr::Reference { .. } => {
+            ref_or_box_schema_type(schema, ref_cache).unwrap_or(DataType::Json)
+        }
+    }
+}
+
+fn model_kind(schema: &Schema, open_api: &OpenAPI, ref_cache: &mut RefCache) -> ModelKind {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string_type))
+            if !string_type.enumeration.is_empty() && !string_type.enumeration.contains(&None) =>
+        {
+            ModelKind::StringEnum {
+                values: string_type.enumeration.iter().flatten().cloned().collect(),
+            }
+        }
+        SchemaKind::Type(Type::Object(obj))
+            if !obj.properties.is_empty() || obj.additional_properties.is_none() =>
+        {
+            let fields = obj
+                .properties
+                .iter()
+                .map(|(name, schema)| Field {
+                    name: name.clone(),
+                    tpe: field_type(schema, ref_cache),
+                    required: obj.required.contains(name),
+                })
+                .collect();
+
+            ModelKind::Object { fields }
+        }
+        SchemaKind::Any(any) if enum_schema_sanity_check(any, &schema.schema_data).is_ok() => {
+            let discriminator = schema.schema_data.discriminator.as_ref().unwrap();
+
+            match extract_enum_cases(open_api, discriminator, ref_cache) {
+                Ok(cases) => ModelKind::Union {
+                    discriminator: discriminator.property_name.clone(),
+                    cases: cases
+                        .into_iter()
+                        .map(|c| UnionCase {
+                            tag: c.name,
+                            tpe: c.data_type,
+                        })
+                        .collect(),
+                },
+                Err(_) => ModelKind::Alias {
+                    tpe: DataType::Json,
+                },
+            }
+        }
+        _ => ModelKind::Alias {
+            tpe: field_type(&ReferenceOr::Item(Box::new(schema.clone())), ref_cache),
+        },
+    }
+}
+
+fn model_def(reference: &str, open_api: &OpenAPI, ref_cache: &mut RefCache) -> Result<ModelDef> {
+    let schema_name =
+        reference
+            .strip_prefix("#/components/schemas/")
+            .ok_or(Error::unimplemented(format!(
+                "Unexpected reference format: {reference}."
+            )))?;
+
+    let schema = open_api
+        .components
+        .as_ref()
+        .ok_or(Error::unexpected("No components."))?
+        .schemas
+        .get(schema_name)
+        .ok_or(Error::unexpected(format!(
+            "Can't find schema by reference {schema_name}"
+        )))?;
+
+    let name = match ref_type_name(reference, &mut RefCache::new())? {
+        DataType::Model(model) => model.name,
+        other => {
+            return Err(Error::unexpected(format!(
+                "Unexpected model type {other:?} for {reference}"
+            )));
+        }
+    };
+
+    let kind = match schema {
+        ReferenceOr::Reference { reference } => ModelKind::Alias {
+            tpe: ref_type_name(reference, ref_cache)?,
+        },
+        ReferenceOr::Item(schema) => model_kind(schema, open_api, ref_cache),
+    };
+
+    Ok(ModelDef { name, kind })
+}
+
+/// Describes all models referenced from `ref_cache`, transitively, ordered by name.
+pub fn collect_models(open_api: &OpenAPI, mut ref_cache: RefCache) -> Result<Vec<ModelDef>> {
+    let mut known_refs = RefCache::new();
+    let mut models = Vec::new();
+
+    while !ref_cache.is_empty() {
+        let mut next_ref_cache = RefCache::new();
+
+        for ref_str in ref_cache.refs {
+            if !known_refs.refs.contains(&ref_str) {
+                models.push(model_def(&ref_str, open_api, &mut next_ref_cache)?);
+                known_refs.add(ref_str);
+            }
+        }
+
+        next_ref_cache
+            .refs
+            .retain(|ref_str| !known_refs.refs.contains(ref_str));
+
+        ref_cache = next_ref_cache;
+    }
+
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(models)
+}
+
+/// Whether the API accepts bearer tokens, the only authentication the generated clients support.
+pub fn uses_bearer_security(open_api: &OpenAPI) -> bool {
+    open_api.components.iter().any(|components| {
+        components.security_schemes.values().any(|scheme| {
+            matches!(
+                scheme,
+                ReferenceOr::Item(SecurityScheme::HTTP { scheme, .. }) if scheme == "bearer"
+            )
+        })
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorDetail {
+    Errors,
+    Code,
+    AdditionalFields,
+}
+
+/// How an [ErrorDetail] is read from an error body, following the same conventions as the
+/// `ErrorInfo` implementations of the Rust client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorExtraction {
+    None,
+    /// The single message of the `error` field
+    Error,
+    /// The messages of the `errors` field
+    Errors,
+    /// The `code` field
+    Code,
+    /// The listed fields which are present and not null
+    Fields(Vec<String>),
+    /// Depends on the case of an internally tagged union
+    Switch {
+        discriminator: String,
+        cases: Vec<(String, ErrorExtraction)>,
+    },
+}
+
+pub fn error_extraction(
+    tpe: &DataType,
+    detail: ErrorDetail,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> ErrorExtraction {
+    fn for_model(
+        model_name: &str,
+        detail: ErrorDetail,
+        conventions: &HashMap<String, ErrorConvention>,
+        visited: &mut HashSet<String>,
+    ) -> ErrorExtraction {
+        if !visited.insert(model_name.to_string()) {
+            return ErrorExtraction::None;
+        }
+
+        let result = match (
+            conventions
+                .get(model_name)
+                .unwrap_or(&ErrorConvention::Unknown),
+            detail,
+        ) {
+            (ErrorConvention::Single { .. }, ErrorDetail::Errors) => ErrorExtraction::Error,
+            (ErrorConvention::Multiple { .. }, ErrorDetail::Errors) => ErrorExtraction::Errors,
+            (
+                ErrorConvention::Single { code, .. } | ErrorConvention::Multiple { code, .. },
+                ErrorDetail::Code,
+            ) => match code {
+                CodeConvention::Absent => ErrorExtraction::None,
+                CodeConvention::Required => ErrorExtraction::Code,
+            },
+            (
+                ErrorConvention::Single {
+                    additional_fields, ..
+                }
+                | ErrorConvention::Multiple {
+                    additional_fields, ..
+                },
+                ErrorDetail::AdditionalFields,
+            ) => {
+                if additional_fields.is_empty() {
+                    ErrorExtraction::None
+                } else {
+                    ErrorExtraction::Fields(
+                        additional_fields
+                            .iter()
+                            .map(|field| field.wire_name.clone())
+                            .collect(),
+                    )
+                }
+            }
+            (
+                ErrorConvention::Union {
+                    discriminator,
+                    variants,
+                },
+                _,
+            ) => {
+                let cases: Vec<(String, ErrorExtraction)> = variants
+                    .iter()
+                    .map(|variant| {
+                        (
+                            variant.tag.clone(),
+                            for_model(
+                                &variant.model_name,
+                                detail,
+                                conventions,
+                                &mut visited.clone(),
+                            ),
+                        )
+                    })
+                    .filter(|(_, extraction)| extraction != &ErrorExtraction::None)
+                    .collect();
+
+                if cases.is_empty() {
+                    ErrorExtraction::None
+                } else {
+                    ErrorExtraction::Switch {
+                        discriminator: discriminator.clone(),
+                        cases,
+                    }
+                }
+            }
+            (ErrorConvention::Unknown, _) => ErrorExtraction::None,
+        };
+
+        visited.remove(model_name);
+        result
+    }
+
+    match tpe {
+        DataType::Model(model) => for_model(&model.name, detail, conventions, &mut HashSet::new()),
+        _ => ErrorExtraction::None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathElement {
+    Const(String),
+    Param { name: String },
+}
+
+impl PathElement {
+    pub fn from_string(p: &str) -> PathElement {
+        if p.starts_with('{') && p.ends_with('}') {
+            PathElement::Param {
+                name: p
+                    .strip_prefix('{')
+                    .unwrap()
+                    .strip_suffix('}')
+                    .unwrap()
+                    .to_string(),
+            }
+        } else {
+            PathElement::Const(p.to_string())
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            PathElement::Const(name) => name.to_string(),
+            PathElement::Param { name } => name.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Path(pub Vec<PathElement>);
+
+impl Path {
+    pub fn common_prefix(&self, that: Path) -> Path {
+        if self.0.starts_with(&that.0) {
+            return that;
+        }
+
+        let elems: Vec<PathElement> = self
+            .0
+            .iter()
+            .zip(that.0)
+            .take_while(|(a, b)| a == &b)
+            .map(|(_, e)| e)
+            .collect();
+
+        Path(elems)
+    }
+
+    pub fn from_string(path: &str) -> Path {
+        let elems: Vec<PathElement> = path
+            .split('/')
+            .filter(|p| !p.trim().is_empty())
+            .map(PathElement::from_string)
+            .collect();
+
+        Path(elems)
+    }
+
+    pub fn strip_prefix(&self, prefix_len: usize) -> Path {
+        let slice = &self.0[prefix_len..];
+        Path(slice.to_vec())
+    }
+
+    pub fn method_name(&self, method: &str) -> String {
+        #[allow(unstable_name_collisions)]
+        let name: String = self
+            .0
+            .iter()
+            .map(|e| e.name().to_case(Case::Snake))
+            .intersperse("_".to_string())
+            .collect();
+
+        if name.is_empty() {
+            method.to_string()
+        } else {
+            format!("{name}_{method}")
+        }
+    }
+}
+
+struct PathOperation {
+    path: Path,
+    original_path: String,
+    method: String,
+    op: Operation,
+}
+
+#[derive(Debug, Clone)]
+pub struct Method {
+    pub name: String,
+    pub path: Path,
+    pub original_path: String,
+    pub http_method: String,
+    pub params: Vec<Param>,
+    pub result: DataType,
+    pub result_status_code: StatusCode,
+    pub errors: MethodErrors,
+}
+
+pub fn error_name(method_name: &str, error_kind: &ErrorKind) -> String {
+    match error_kind {
+        ErrorKind::Common { name } => name.clone(),
+        ErrorKind::Custom { prefix } => {
+            format!("{prefix}{}Error", method_name.to_case(Case::UpperCamel))
+        }
+    }
+}
+
+impl Method {
+    pub fn get_path_param(&self, original_name: &str) -> Option<&Param> {
+        self.params
+            .iter()
+            .find(|p| p.kind == ParamKind::Path && p.original_name == original_name)
+    }
+}
+
+pub enum ErrorKind {
+    Common { name: String },
+    Custom { prefix: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodErrors {
+    pub codes: BTreeMap<StatusCode, DataType>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub original_name: String,
+    pub name: String,
+    pub tpe: DataType,
+    pub required: bool,
+    pub kind: ParamKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestBodyParams {
+    params: IndexMap<ContentType, Vec<Param>>,
+}
+
+impl RequestBodyParams {
+    fn has_single_content_type(&self) -> bool {
+        self.params.len() == 1
+    }
+
+    fn get_default_request_body_param(&self) -> Option<&Vec<Param>> {
+        self.params
+            .values()
+            .next()
+            .filter(|_| self.has_single_content_type())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ContentType(pub String);
+
+impl ContentType {
+    pub fn is_json(&self) -> bool {
+        self.0 == "application/json"
+    }
+
+    pub fn is_yaml(&self) -> bool {
+        self.0 == "application/x-yaml"
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamKind {
+    Path,
+    Query,
+    Header,
+    Cookie,
+    Body,
+    Multipart,
+}
+
+impl ParamKind {
+    fn from_parameter(param: &Parameter) -> ParamKind {
+        match param {
+            Parameter::Query { .. } => ParamKind::Query,
+            Parameter::Header { .. } => ParamKind::Header,
+            Parameter::Path { .. } => ParamKind::Path,
+            Parameter::Cookie { .. } => ParamKind::Cookie,
+        }
+    }
+}
+
+fn tag_operations(
+    tag: &Option<Tag>,
+    path: &str,
+    path_item: &ReferenceOr<PathItem>,
+) -> Vec<PathOperation> {
+    if let Some(item) = path_item.as_item() {
+        if let Some(tag) = tag {
+            item.iter()
+                .filter(|(_, op)| op.tags.contains(&tag.name))
+                .map(|(method, op)| PathOperation {
+                    path: Path::from_string(path),
+                    original_path: path.to_string(),
+                    method: method.to_string(),
+                    op: op.clone(),
+                })
+                .collect()
+        } else {
+            item.iter()
+                .filter(|(_, op)| op.tags.is_empty())
+                .map(|(method, op)| PathOperation {
+                    path: Path::from_string(path),
+                    original_path: path.to_string(),
+                    method: method.to_string(),
+                    op: op.clone(),
+                })
+                .collect()
+        }
+    } else {
+        Vec::new()
+    }
+}
+
+fn match_tag(tag: &Option<Tag>, path_item: &ReferenceOr<PathItem>) -> bool {
+    if let Some(item) = path_item.as_item() {
+        if let Some(tag) = tag {
+            item.iter().any(|(_, op)| op.tags.contains(&tag.name))
+        } else {
+            item.iter().any(|(_, op)| op.tags.is_empty())
+        }
+    } else {
+        false
+    }
+}
+
+fn param_data_to_type(data: &ParameterData, ref_cache: &mut RefCache) -> Result<DataType> {
+    match &data.format {
+        ParameterSchemaOrContent::Schema(ref_or_schema) => {
+            ref_or_schema_type(ref_or_schema, ref_cache, None)
+        }
+        ParameterSchemaOrContent::Content(_) => {
+            Err(Error::unimplemented("Content parameter is not supported."))
+        }
+    }
+}
+
+fn parameter(p: &ReferenceOr<Parameter>, ref_cache: &mut RefCache) -> Result<Param> {
+    if let Some(param) = p.as_item() {
+        let param_type = param_data_to_type(param.parameter_data_ref(), ref_cache)?;
+
+        Ok(Param {
+            original_name: param.parameter_data_ref().name.to_string(),
+            name: param.parameter_data_ref().name.to_case(Case::Snake),
+            tpe: param_type,
+            required: param.parameter_data_ref().required,
+            kind: ParamKind::from_parameter(param),
+        })
+    } else {
+        Err(Error::unimplemented("Unexpected ref parameter."))
+    }
+}
+
+fn request_body_params(
+    body: &ReferenceOr<RequestBody>,
+    ref_cache: &mut RefCache,
+) -> Result<RequestBodyParams> {
+    let mut content_type_params = IndexMap::new();
+
+    match body {
+        ReferenceOr::Reference { reference } => {
+            return Err(Error::unimplemented(format!(
+                "Unexpected ref request body: '{reference}'."
+            )));
+        }
+        ReferenceOr::Item(body) => {
+            for (content_type, media_type) in &body.content {
+                if content_type.starts_with("application/json") || content_type == "*/*" {
+                    let schema = match &media_type.schema {
+                        None => Err(Error::unimplemented("JSON content without schema.")),
+                        Some(schema) => Ok(schema),
+                    };
+
+                    content_type_params.insert(
+                        ContentType(content_type.clone()),
+                        vec![Param {
+                            original_name: "".to_string(),
+                            name: "value".to_string(),
+                            tpe: ref_or_schema_type(
+                                schema?,
+                                ref_cache,
+                                Some(content_type.clone()),
+                            )?,
+                            required: body.required,
+                            kind: ParamKind::Body,
+                        }],
+                    );
+                } else if content_type == "application/octet-stream" {
+                    content_type_params.insert(
+                        ContentType(content_type.clone()),
+                        vec![Param {
+                            original_name: "".to_string(),
+                            name: "value".to_string(),
+                            tpe: DataType::Binary,
+                            required: body.required,
+                            kind: ParamKind::Body,
+                        }],
+                    );
+                } else if content_type.contains("application/x-yaml") {
+                    let schema = match &media_type.schema {
+                        None => Err(Error::unimplemented("YAML content without schema.")),
+                        Some(schema) => Ok(schema),
+                    };
+
+                    let param = Param {
+                        original_name: "".to_string(),
+                        name: "value".to_string(),
+                        tpe: ref_or_schema_type(schema?, ref_cache, Some(content_type.clone()))?,
+                        required: body.required,
+                        kind: ParamKind::Body,
+                    };
+
+                    content_type_params.insert(ContentType(content_type.clone()), vec![param]);
+                } else if content_type == "multipart/form-data" {
+                    match &media_type.schema {
+                        None => {
+                            return Err(Error::unimplemented("Multipart content without schema."));
+                        }
+                        Some(schema) => match schema {
+                            ReferenceOr::Reference { reference } => {
+                                return Err(Error::unimplemented(format!(
+                                    "Unexpected ref multipart schema: '{reference}'."
+                                )));
+                            }
+                            ReferenceOr::Item(schema) => match &schema.schema_kind {
+                                SchemaKind::Type(Type::Object(obj)) => {
+                                    fn multipart_param(
+                                        name: &str,
+                                        required: bool,
+                                        schema: &ReferenceOr<Box<Schema>>,
+                                        ref_cache: &mut RefCache,
+                                    ) -> Result<Param> {
+                                        Ok(Param {
+                                            original_name: name.to_string(),
+                                            name: name.to_case(Case::Snake),
+                                            tpe: ref_or_box_schema_type(schema, ref_cache)?,
+                                            required,
+                                            kind: ParamKind::Multipart,
+                                        })
+                                    }
+
+                                    let params = obj
+                                        .properties
+                                        .iter()
+                                        .map(|(name, schema)| {
+                                            multipart_param(
+                                                name,
+                                                body.required && obj.required.contains(name),
+                                                schema,
+                                                ref_cache,
+                                            )
+                                        })
+                                        .collect::<Result<Vec<_>>>()?;
+
+                                    content_type_params
+                                        .insert(ContentType(content_type.clone()), params);
+                                }
+                                _ => {
+                                    return Err(Error::unimplemented(
+                                        "Object schema expected for multipart request body.",
+                                    ));
+                                }
+                            },
+                        },
+                    }
+                } else {
+                    return Err(Error::unimplemented(format!(
+                        "Request body content type: '{content_type}'."
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(RequestBodyParams {
+        params: content_type_params,
+    })
+}
+
+fn parameters(op: &PathOperation, ref_cache: &mut RefCache) -> Result<Vec<Param>> {
+    op.op
+        .parameters
+        .iter()
+        .map(|p| parameter(p, ref_cache))
+        .collect()
+}
+
+fn as_code(code: &StatusCode) -> Option<u16> {
+    match code {
+        StatusCode::Code(code) => Some(*code),
+        StatusCode::Range(_) => None,
+    }
+}
+
+pub fn as_range(code: &StatusCode) -> Option<u16> {
+    match code {
+        StatusCode::Code(_) => None,
+        StatusCode::Range(v) => Some(*v),
+    }
+}
+
+fn is_success_code(code: &StatusCode) -> bool {
+    if let Some(code) = as_code(code) {
+        (200..300).contains(&code)
+    } else {
+        false
+    }
+}
+
+fn is_success_range(code: &StatusCode) -> bool {
+    if let Some(v) = as_range(code) {
+        v == 2
+    } else {
+        false
+    }
+}
+
+fn response_type(response: &ReferenceOr<Response>, ref_cache: &mut RefCache) -> Result<DataType> {
+    match response {
+        ReferenceOr::Reference { reference } => Err(Error::unimplemented(format!(
+            "Reference in response top level: {reference}"
+        ))),
+        ReferenceOr::Item(resp) => {
+            if resp.content.is_empty() {
+                // No content case
+                Ok(DataType::Unit)
+            } else if resp.content.len() == 1 {
+                let (content_type, media_type) = resp.content.first().unwrap();
+
+                if content_type.starts_with("application/json") {
+                    let schema = match &media_type.schema {
+                        None => Err(Error::unimplemented(
+                            "JSON response content without schema.",
+                        )),
+                        Some(schema) => Ok(schema),
+                    };
+
+                    Ok(ref_or_schema_type(
+                        schema?,
+                        ref_cache,
+                        Some(content_type.clone()),
+                    )?)
+                } else if content_type == "application/octet-stream" {
+                    Ok(DataType::Binary)
+                } else {
+                    Err(Error::unimplemented(format!(
+                        "Response content type: {content_type}"
+                    )))
+                }
+            } else {
+                Err(Error::unimplemented(
+                    "Response content with not exactly 1 option.",
+                ))
+            }
+        }
+    }
+}
+
+fn method_result(
+    responses: &IndexMap<StatusCode, ReferenceOr<Response>>,
+    ref_cache: &mut RefCache,
+) -> Result<(StatusCode, DataType)> {
+    let code_res = responses
+        .iter()
+        .filter(|(code, _)| is_success_code(code))
+        .min_by_key(|(code, _)| as_code(code));
+
+    let res = code_res.or_else(|| responses.iter().find(|(code, _)| is_success_range(code)));
+
+    if let Some((code, resp)) = res {
+        Ok((code.clone(), response_type(resp, ref_cache)?))
+    } else {
+        Err(Error::unimplemented("No success results in API."))
+    }
+}
+
+fn method_errors(
+    responses: &IndexMap<StatusCode, ReferenceOr<Response>>,
+    result_code: StatusCode,
+    ref_cache: &mut RefCache,
+) -> Result<MethodErrors> {
+    let codes: Result<BTreeMap<StatusCode, DataType>> = responses
+        .iter()
+        .filter(|(code, _)| *code != &result_code)
+        .map(|(code, resp)| response_type(resp, ref_cache).map(|t| (code.clone(), t)))
+        .collect();
+
+    Ok(MethodErrors { codes: codes? })
+}
+
+fn trait_methods_specific_to_content_type(
+    op: &PathOperation,
+    prefix_length: usize,
+    ref_cache: &mut RefCache,
+) -> Result<Vec<Method>> {
+    let (result_code, result_type) = method_result(&op.op.responses.responses, ref_cache)?;
+
+    let name = op
+        .op
+        .operation_id
+        .as_ref()
+        .map(|op_id| op_id.to_case(Case::Snake))
+        .unwrap_or_else(|| op.path.strip_prefix(prefix_length).method_name(&op.method));
+
+    let mut main_params = parameters(op, ref_cache)?;
+
+    if let Some(body) = &op.op.request_body {
+        let content_specific = request_body_params(body, ref_cache)?;
+
+        if let Some(request_body_params) = content_specific.get_default_request_body_param() {
+            main_params.extend(request_body_params.iter().cloned());
+            return Ok(vec![create_method(
+                name,
+                op,
+                &main_params,
+                result_type,
+                result_code,
+                ref_cache,
+            )?]);
+        }
+
+        let mut methods = Vec::new();
+        for (content_type, params) in content_specific.params {
+            let method_name = match_content_type(content_type, &name)?;
+            let new_params = [main_params.clone(), params].concat();
+            methods.push(create_method(
+                method_name,
+                op,
+                &new_params,
+                result_type.clone(),
+                result_code.clone(),
+                ref_cache,
+            )?);
+        }
+
+        Ok(methods)
+    } else {
+        Ok(vec![create_method(
+            name,
+            op,
+            &main_params,
+            result_type,
+            result_code,
+            ref_cache,
+        )?])
+    }
+}
+
+fn create_method(
+    name: String,
+    op: &PathOperation,
+    params: &[Param],
+    result_type: DataType,
+    result_code: StatusCode,
+    ref_cache: &mut RefCache,
+) -> Result<Method> {
+    Ok(Method {
+        name,
+        path: op.path.clone(),
+        original_path: op.original_path.clone(),
+        http_method: op.method.to_string(),
+        params: params.to_vec(),
+        result: result_type,
+        result_status_code: result_code.clone(),
+        errors: method_errors(&op.op.responses.responses, result_code, ref_cache)?,
+    })
+}
+
+fn match_content_type(content_type: ContentType, base_name: &str) -> Result<String> {
+    if content_type.is_json() {
+        Ok(format!("{}_json", base_name))
+    } else if content_type.is_yaml() {
+        Ok(format!("{}_yaml", base_name))
+    } else {
+        Err(Error::unimplemented(
+            "Multiple content types supported only for JSON and YAML",
+        ))
+    }
+}
+
+fn trait_methods(
+    operations: &[PathOperation],
+    prefix_length: usize,
+    ref_cache: &mut RefCache,
+) -> Result<Vec<Method>> {
+    let res = operations
+        .iter()
+        .map(|op| trait_methods_specific_to_content_type(op, prefix_length, ref_cache))
+        .collect::<Result<Vec<Vec<_>>>>()?;
+
+    Ok(res.into_iter().flatten().collect())
+}
+
+#[derive(Debug, Clone)]
+pub enum CodeConvention {
+    Absent,
+    Required,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnionVariant {
+    pub tag: String,
+    pub variant_name: String,
+    pub model_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdditionalField {
+    pub wire_name: String,
+    pub rust_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum ErrorConvention {
+    Single {
+        code: CodeConvention,
+        additional_fields: Vec<AdditionalField>,
+    },
+    Multiple {
+        code: CodeConvention,
+        additional_fields: Vec<AdditionalField>,
+    },
+    Union {
+        discriminator: String,
+        variants: Vec<UnionVariant>,
+    },
+    Unknown,
+}
+
+pub fn infer_error_conventions(open_api: &OpenAPI) -> HashMap<String, ErrorConvention> {
+    let mut result = HashMap::new();
+
+    if let Some(components) = &open_api.components {
+        for (schema_name, schema_ref) in &components.schemas {
+            let model_name = model_type_name(schema_name);
+
+            let convention = match schema_ref {
+                ReferenceOr::Item(schema) => infer_error_convention_for_schema(open_api, schema),
+                ReferenceOr::Reference { .. } => ErrorConvention::Unknown,
+            };
+
+            result.insert(model_name, convention);
+        }
+    }
+
+    result
+}
+
+fn infer_error_convention_for_schema(open_api: &OpenAPI, schema: &Schema) -> ErrorConvention {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(obj)) => {
+            let has_error = obj
+                .properties
+                .get("error")
+                .is_some_and(|schema| is_string_schema_ref(open_api, schema));
+            let has_errors = obj
+                .properties
+                .get("errors")
+                .is_some_and(|schema| is_array_of_string_schema_ref(open_api, schema));
+
+            let code = infer_code_convention(open_api, obj);
+
+            if has_error {
+                ErrorConvention::Single {
+                    code,
+                    additional_fields: infer_additional_fields(obj, &["code", "error"]),
+                }
+            } else if has_errors {
+                ErrorConvention::Multiple {
+                    code,
+                    additional_fields: infer_additional_fields(obj, &["code", "errors"]),
+                }
+            } else {
+                ErrorConvention::Unknown
+            }
+        }
+        SchemaKind::Any(any) if !any.one_of.is_empty() => {
+            if let Some(discriminator) = &schema.schema_data.discriminator {
+                let variants = discriminator
+                    .mapping
+                    .iter()
+                    .filter_map(|(variant_name, case_reference)| {
+                        resolve_union_payload_model_name(open_api, case_reference).map(
+                            |model_name| UnionVariant {
+                                tag: variant_name.clone(),
+                                variant_name: variant_name.to_case(Case::UpperCamel),
+                                model_name,
+                            },
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                if variants.is_empty() {
+                    ErrorConvention::Unknown
+                } else {
+                    ErrorConvention::Union {
+                        discriminator: discriminator.property_name.clone(),
+                        variants,
+                    }
+                }
+            } else {
+                ErrorConvention::Unknown
+            }
+        }
+        _ => ErrorConvention::Unknown,
+    }
+}
+
+fn infer_additional_fields(
+    object_type: &openapiv3::ObjectType,
+    normalized_fields: &[&str],
+) -> Vec<AdditionalField> {
+    object_type
+        .properties
+        .keys()
+        .filter(|name| !normalized_fields.contains(&name.as_str()))
+        .map(|name| AdditionalField {
+            wire_name: name.clone(),
+            rust_name: escape_keywords(&name.to_case(Case::Snake)),
+        })
+        .collect()
+}
+
+fn resolve_union_payload_model_name(open_api: &OpenAPI, case_reference: &str) -> Option<String> {
+    let case_schema_name = case_reference.strip_prefix("#/components/schemas/")?;
+    let components = open_api.components.as_ref()?;
+    let case_schema = components.schemas.get(case_schema_name)?.as_item()?;
+
+    match &case_schema.schema_kind {
+        SchemaKind::AllOf { all_of } => all_of.iter().find_map(|schema| match schema {
+            ReferenceOr::Reference { reference } => reference
+                .strip_prefix("#/components/schemas/")
+                .map(model_type_name),
+            ReferenceOr::Item(_) => None,
+        }),
+        _ => None,
+    }
+}
+
+fn resolve_schema_by_reference<'a>(open_api: &'a OpenAPI, reference: &str) -> Option<&'a Schema> {
+    let schema_name = reference.strip_prefix("#/components/schemas/")?;
+    open_api
+        .components
+        .as_ref()?
+        .schemas
+        .get(schema_name)?
+        .as_item()
+}
+
+fn resolve_schema_ref<'a>(
+    open_api: &'a OpenAPI,
+    schema: &'a ReferenceOr<Box<Schema>>,
+) -> Option<&'a Schema> {
+    match schema {
+        ReferenceOr::Reference { reference } => resolve_schema_by_reference(open_api, reference),
+        ReferenceOr::Item(schema) => Some(schema),
+    }
+}
+
+fn is_string_schema(schema: &Schema) -> bool {
+    matches!(&schema.schema_kind, SchemaKind::Type(Type::String(_)))
+}
+
+fn is_string_schema_ref(open_api: &OpenAPI, schema: &ReferenceOr<Box<Schema>>) -> bool {
+    resolve_schema_ref(open_api, schema).is_some_and(is_string_schema)
+}
+
+fn is_array_of_string_schema(schema: &Schema) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Array(array)) => {
+            array.items.as_ref().is_some_and(|items| match items {
+                ReferenceOr::Reference { .. } => false,
+                ReferenceOr::Item(item) => is_string_schema(item),
+            })
+        }
+        _ => false,
+    }
+}
+
+fn is_array_of_string_schema_ref(open_api: &OpenAPI, schema: &ReferenceOr<Box<Schema>>) -> bool {
+    resolve_schema_ref(open_api, schema).is_some_and(is_array_of_string_schema)
+}
+
+fn infer_code_convention(
+    open_api: &OpenAPI,
+    object_type: &openapiv3::ObjectType,
+) -> CodeConvention {
+    if object_type
+        .properties
+        .get("code")
+        .is_some_and(|schema| is_string_schema_ref(open_api, schema))
+    {
+        CodeConvention::Required
+    } else {
+        CodeConvention::Absent
+    }
+}
+
+pub struct ClientDef {
+    pub name: String,
+    pub methods: Vec<Method>,
+    pub error_kind: ErrorKind,
+}
+
+impl ClientDef {
+    /// Method names and errors, one entry per distinct error type of the client.
+    pub fn errors(&self) -> Vec<(String, MethodErrors)> {
+        match &self.error_kind {
+            ErrorKind::Common { .. } => self
+                .methods
+                .first()
+                .iter()
+                .map(|m| (m.name.clone(), m.errors.clone()))
+                .collect(),
+            ErrorKind::Custom { .. } => self
+                .methods
+                .iter()
+                .map(|m| (m.name.clone(), m.errors.clone()))
+                .collect(),
+        }
+    }
+}
+
+pub fn client_def(
+    open_api: &OpenAPI,
+    tag: Option<Tag>,
+    ref_cache: &mut RefCache,
+    ignored_paths: &[&str],
+) -> Result<ClientDef> {
+    let ignored_paths: HashSet<String> =
+        HashSet::from_iter(ignored_paths.iter().map(|ip| ip.to_string()));
+
+    let paths: IndexMap<String, ReferenceOr<PathItem>> = open_api
+        .paths
+        .iter()
+        .filter_map(|(path_key, path_item)| {
+            if !ignored_paths.contains(path_key) && match_tag(&tag, path_item) {
+                Some((path_key.clone(), path_item.clone()))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let common_prefix = paths
+        .keys()
+        .map(|p| Path::from_string(p))
+        .reduce(|acc, e| e.common_prefix(acc));
+
+    let prefix_length = common_prefix.map(|p| p.0.len()).unwrap_or(0);
+
+    let operations: Vec<PathOperation> = paths
+        .iter()
+        .flat_map(|(path, op)| tag_operations(&tag, path, op))
+        .collect();
+
+    let name = tag
+        .map(|t| t.name)
+        .unwrap_or("Other".to_string())
+        .to_case(Case::UpperCamel);
+
+    let methods: Vec<Method> = trait_methods(&operations, prefix_length, ref_cache)
+        .map_err(|e| e.extend(format!("In Tag {}.", &name)))?;
+
+    let common_error = methods.iter().map(|m| &m.errors).unique().count() == 1;
+
+    let error_kind = if common_error {
+        ErrorKind::Common {
+            name: format!("{name}Error"),
+        }
+    } else {
+        ErrorKind::Custom {
+            prefix: name.clone(),
+        }
+    };
+
+    Ok(ClientDef {
+        name,
+        methods,
+        error_kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::rust::model_gen::RefCache;
+    use crate::rust::types::{DataType, ModelType};
+    use crate::spec::{Field, ModelDef, ModelKind, UnionCase, collect_models};
+    use indoc::indoc;
+    use openapiv3::OpenAPI;
+
+    fn model(name: &str) -> DataType {
+        DataType::Model(ModelType {
+            name: name.to_string(),
+        })
+    }
+
+    #[test]
+    fn collects_referenced_models_transitively() {
+        let open_api: OpenAPI = serde_yaml::from_str(indoc! { r#"
+            openapi: 3.0.0
+            info:
+              title: test
+              version: 0.0.0
+            paths: {}
+            components:
+              schemas:
+                Filter:
+                  type: object
+                  oneOf:
+                    - $ref: '#/components/schemas/Filter_NameFilter'
+                    - $ref: '#/components/schemas/Filter_StatusFilter'
+                  discriminator:
+                    propertyName: type
+                    mapping:
+                      Name: '#/components/schemas/Filter_NameFilter'
+                      Status: '#/components/schemas/Filter_StatusFilter'
+                Filter_NameFilter:
+                  allOf:
+                    - type: object
+                      required: [type]
+                      properties:
+                        type:
+                          type: string
+                          enum: [Name]
+                    - $ref: '#/components/schemas/NameFilter'
+                Filter_StatusFilter:
+                  allOf:
+                    - type: object
+                      required: [type]
+                      properties:
+                        type:
+                          type: string
+                          enum: [Status]
+                    - $ref: '#/components/schemas/StatusFilter'
+                NameFilter:
+                  type: object
+                  required: [name]
+                  properties:
+                    name:
+                      type: string
+                    labels:
+                      type: object
+                      additionalProperties:
+                        type: string
+                StatusFilter:
+                  type: object
+                  required: [status]
+                  properties:
+                    status:
+                      $ref: '#/components/schemas/Status'
+                    anything: {}
+                Status:
+                  type: string
+                  enum: [Running, Idle]
+        "#})
+        .unwrap();
+
+        let mut ref_cache = RefCache::new();
+        ref_cache.add("#/components/schemas/Filter");
+
+        let models = collect_models(&open_api, ref_cache).unwrap();
+
+        assert_eq!(
+            models,
+            vec![
+                ModelDef {
+                    name: "Filter".to_string(),
+                    kind: ModelKind::Union {
+                        discriminator: "type".to_string(),
+                        cases: vec![
+                            UnionCase {
+                                tag: "Name".to_string(),
+                                tpe: model("NameFilter"),
+                            },
+                            UnionCase {
+                                tag: "Status".to_string(),
+                                tpe: model("StatusFilter"),
+                            },
+                        ],
+                    },
+                },
+                ModelDef {
+                    name: "NameFilter".to_string(),
+                    kind: ModelKind::Object {
+                        fields: vec![
+                            Field {
+                                name: "name".to_string(),
+                                tpe: DataType::String,
+                                required: true,
+                            },
+                            Field {
+                                name: "labels".to_string(),
+                                tpe: DataType::MapOf(Box::new(DataType::String)),
+                                required: false,
+                            },
+                        ],
+                    },
+                },
+                ModelDef {
+                    name: "Status".to_string(),
+                    kind: ModelKind::StringEnum {
+                        values: vec!["Running".to_string(), "Idle".to_string()],
+                    },
+                },
+                ModelDef {
+                    name: "StatusFilter".to_string(),
+                    kind: ModelKind::Object {
+                        fields: vec![
+                            Field {
+                                name: "status".to_string(),
+                                tpe: model("Status"),
+                                required: true,
+                            },
+                            Field {
+                                name: "anything".to_string(),
+                                tpe: DataType::Json,
+                                required: false,
+                            },
+                        ],
+                    },
+                },
+            ]
+        );
+    }
+}
@@ -14,3 +14,4 @@
 
 pub(crate) mod cargo;
 mod printer;
+pub(crate) mod pyproject;
@@ -0,0 +1,40 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::NewLine;
+use crate::toml::printer::{StringContext, unit};
+
+pub fn generate(name: &str, version: &str, package: &str) -> String {
+    #[rustfmt::skip]
+    let code = unit() +
+        "[build-system]" + NewLine +
+        r#"requires = ["setuptools>=68"]"# + NewLine +
+        r#"build-backend = "setuptools.build_meta""# + NewLine +
+        NewLine +
+        "[project]" + NewLine +
+        r#"name = ""# + name + r#"""# + NewLine +
+        r#"version = ""# + version + r#"""# + NewLine +
+        r#"description = "Client for Golem Cloud's REST API""# + NewLine +
+        r#"license = { text = "Apache-2.0" }"# + NewLine +
+        r#"requires-python = ">=3.11""# + NewLine +
+        r#"dependencies = ["httpx>=0.27"]"# + NewLine +
+        NewLine +
+        "[tool.setuptools.packages.find]" + NewLine +
+        r#"include = [""# + package + r#"*"]"# + NewLine +
+        NewLine +
+        "[tool.setuptools.package-data]" + NewLine +
+        package + r#" = ["py.typed"]"# + NewLine;
+
+    StringContext::new().print_to_string(code)
+}
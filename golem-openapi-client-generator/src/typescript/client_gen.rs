@@ -0,0 +1,481 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::rust::types::DataType;
+use crate::spec::{
+    ClientDef, ErrorConvention, ErrorDetail, ErrorExtraction, Method, MethodErrors, Param,
+    ParamKind, PathElement, error_extraction, error_name,
+};
+use crate::typescript::printer::{TypeScriptContext, TypeScriptPrinter, line, unit};
+use crate::typescript::types::{identifier, property_access, render_type, string_literal};
+use crate::{Error, Result};
+use itertools::Itertools;
+use openapiv3::StatusCode;
+use std::collections::HashMap;
+
+fn info_name(error_name: &str) -> String {
+    format!("{error_name}Info")
+}
+
+fn variant_name(code: &StatusCode) -> String {
+    format!("Error{code}")
+}
+
+fn body_type(tpe: &DataType) -> String {
+    match tpe {
+        DataType::Unit => "undefined".to_string(),
+        _ => render_type(tpe, "model.", false),
+    }
+}
+
+fn status_condition(code: &StatusCode) -> String {
+    match code {
+        StatusCode::Code(code) => format!("response.status === {code}"),
+        StatusCode::Range(range) => format!("Math.floor(response.status / 100) === {range}"),
+    }
+}
+
+fn default_return(detail: ErrorDetail) -> &'static str {
+    match detail {
+        ErrorDetail::Errors => "return [];",
+        ErrorDetail::Code | ErrorDetail::AdditionalFields => "return undefined;",
+    }
+}
+
+fn render_extraction(extraction: &ErrorExtraction, detail: ErrorDetail) -> TypeScriptPrinter {
+    match extraction {
+        ErrorExtraction::None => line(default_return(detail)),
+        ErrorExtraction::Error => {
+            line(unit() + "return [" + property_access("body", "error") + "];")
+        }
+        ErrorExtraction::Errors => {
+            line(unit() + "return " + property_access("body", "errors") + ";")
+        }
+        ErrorExtraction::Code => line(unit() + "return " + property_access("body", "code") + ";"),
+        ErrorExtraction::Fields(names) => {
+            let names = names.iter().map(|n| string_literal(n)).join(", ");
+            line(unit() + "return pickFields(body, [" + names + "]);")
+        }
+        ErrorExtraction::Switch {
+            discriminator,
+            cases,
+        } => {
+            let cases = cases
+                .iter()
+                .map(|(tag, extraction)| {
+                    line(unit() + "case " + string_literal(tag) + ": {")
+                        + indented(render_extraction(extraction, detail))
+                        + line("}")
+                })
+                .reduce(|acc, e| acc + e)
+                .unwrap_or_else(unit);
+
+            line(unit() + "switch (" + property_access("body", discriminator) + ") {")
+                + indented(cases)
+                + line("}")
+                + line(default_return(detail))
+        }
+    }
+}
+
+fn render_error_detail(
+    name: &str,
+    errors: &MethodErrors,
+    detail: ErrorDetail,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> TypeScriptPrinter {
+    let (method, result) = match detail {
+        ErrorDetail::Errors => ("errors", "string[]"),
+        ErrorDetail::Code => ("code", "string | undefined"),
+        ErrorDetail::AdditionalFields => {
+            ("additionalFields", "Record<string, unknown> | undefined")
+        }
+    };
+
+    let cases = errors
+        .codes
+        .iter()
+        .map(|(code, tpe)| (code, error_extraction(tpe, detail, conventions)))
+        .filter(|(_, extraction)| extraction != &ErrorExtraction::None)
+        .map(|(code, extraction)| {
+            line(unit() + "case " + string_literal(&variant_name(code)) + ": {")
+                + indented(
+                    line("const body = error.body;") + render_extraction(&extraction, detail),
+                )
+                + line("}")
+        })
+        .reduce(|acc, e| acc + e);
+
+    let body = match cases {
+        Some(cases) => {
+            line("switch (error.type) {")
+                + indented(cases)
+                + line("}")
+                + line(default_return(detail))
+        }
+        None => line(default_return(detail)),
+    };
+
+    line(unit() + method + "(error: " + name + "): " + result + " {") + indented(body) + line("},")
+}
+
+fn render_errors(
+    name: &str,
+    errors: &MethodErrors,
+    service_name: &str,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> TypeScriptPrinter {
+    let error_type = if errors.codes.is_empty() {
+        line(unit() + "export type " + name + " = never;")
+    } else {
+        let last = errors.codes.len() - 1;
+        let variants = errors
+            .codes
+            .iter()
+            .enumerate()
+            .map(|(idx, (code, tpe))| {
+                line(
+                    unit()
+                        + "| { type: "
+                        + string_literal(&variant_name(code))
+                        + "; status: number; body: "
+                        + body_type(tpe)
+                        + " }"
+                        + if idx == last { ";" } else { "" },
+                )
+            })
+            .reduce(|acc, e| acc + e)
+            .unwrap_or_else(unit);
+
+        line(unit() + "export type " + name + " =") + indented(variants)
+    };
+
+    #[rustfmt::skip]
+    let code = unit() +
+        error_type +
+        NewLine +
+        line(unit() + "export const " + info_name(name) + ": ErrorInfo<" + name + "> = {") +
+        indented(
+            line(unit() + "serviceName: " + string_literal(service_name) + ",") +
+            line(unit() + "statusCode(error: " + name + "): number {") +
+            indented(line("return error.status;")) +
+            line("},") +
+            render_error_detail(name, errors, ErrorDetail::Errors, conventions) +
+            render_error_detail(name, errors, ErrorDetail::Code, conventions) +
+            render_error_detail(name, errors, ErrorDetail::AdditionalFields, conventions)
+        ) +
+        line("};");
+
+    code
+}
+
+fn render_param_declaration(param: &Param) -> String {
+    let tpe = render_type(&param.tpe, "model.", true);
+
+    if param.required {
+        format!("{}: {tpe}", identifier(&param.name))
+    } else {
+        format!("{}: {tpe} | undefined", identifier(&param.name))
+    }
+}
+
+fn to_string(name: &str, tpe: &DataType) -> String {
+    match tpe {
+        DataType::String => name.to_string(),
+        _ => format!("String({name})"),
+    }
+}
+
+fn unwrap_optional_param(param: &Param, setter: TypeScriptPrinter) -> TypeScriptPrinter {
+    if param.required {
+        setter
+    } else {
+        line(unit() + "if (" + identifier(&param.name) + " !== undefined) {")
+            + indented(setter)
+            + line("}")
+    }
+}
+
+fn render_path_segment(method: &Method, element: &PathElement) -> Result<String> {
+    match element {
+        PathElement::Const(segment) => Ok(string_literal(segment)),
+        PathElement::Param { name } => match method.get_path_param(name) {
+            None => Err(Error::unexpected(format!(
+                "Can't find path parameter {name}"
+            ))),
+            Some(param) => Ok(to_string(&identifier(&param.name), &param.tpe)),
+        },
+    }
+}
+
+fn query_setter(param: &Param) -> TypeScriptPrinter {
+    let name = identifier(&param.name);
+
+    let setter = match &param.tpe {
+        DataType::Array(tpe) => {
+            line(unit() + "for (const item of " + &name + ") {")
+                + indented(line(
+                    unit()
+                        + "url.searchParams.append("
+                        + string_literal(&param.original_name)
+                        + ", "
+                        + to_string("item", tpe)
+                        + ");",
+                ))
+                + line("}")
+        }
+        tpe => line(
+            unit()
+                + "url.searchParams.append("
+                + string_literal(&param.original_name)
+                + ", "
+                + to_string(&name, tpe)
+                + ");",
+        ),
+    };
+
+    unwrap_optional_param(param, setter)
+}
+
+fn header_setter(param: &Param) -> TypeScriptPrinter {
+    let setter = line(
+        unit()
+            + "headers.append("
+            + string_literal(&param.original_name)
+            + ", "
+            + to_string(&identifier(&param.name), &param.tpe)
+            + ");",
+    );
+
+    unwrap_optional_param(param, setter)
+}
+
+fn multipart_setter(param: &Param) -> TypeScriptPrinter {
+    let name = identifier(&param.name);
+
+    let value = match &param.tpe {
+        DataType::Binary | DataType::String => name,
+        DataType::Model(_) => {
+            format!(r#"new Blob([JSON.stringify({name})], {{ type: "application/json" }})"#)
+        }
+        tpe => to_string(&name, tpe),
+    };
+
+    let setter = line(
+        unit() + "requestBody.append(" + string_literal(&param.original_name) + ", " + value + ");",
+    );
+
+    unwrap_optional_param(param, setter)
+}
+
+fn body_setter(method: &Method) -> Option<TypeScriptPrinter> {
+    let multipart_params = method
+        .params
+        .iter()
+        .filter(|p| p.kind == ParamKind::Multipart)
+        .collect::<Vec<_>>();
+
+    if !multipart_params.is_empty() {
+        let setters = multipart_params
+            .into_iter()
+            .map(multipart_setter)
+            .reduce(|acc, e| acc + e)
+            .unwrap_or_else(unit);
+
+        return Some(line("const requestBody = new FormData();") + setters);
+    }
+
+    method
+        .params
+        .iter()
+        .find(|p| p.kind == ParamKind::Body)
+        .map(|param| {
+            let name = identifier(&param.name);
+
+            let (content_type, value) = match &param.tpe {
+                DataType::Binary => ("application/octet-stream", name.clone()),
+                DataType::Yaml => ("application/x-yaml", name.clone()),
+                _ => ("application/json", format!("JSON.stringify({name})")),
+            };
+
+            let value = if param.required {
+                value
+            } else {
+                format!("{name} === undefined ? undefined : {value}")
+            };
+
+            line(unit() + r#"headers.set("Content-Type", ""# + content_type + r#"");"#)
+                + line(unit() + "const requestBody = " + value + ";")
+        })
+}
+
+fn parse_response(tpe: &DataType, error_name: &str) -> String {
+    match tpe {
+        DataType::Unit => "undefined".to_string(),
+        DataType::Binary => "await response.arrayBuffer()".to_string(),
+        _ => format!(
+            "await this.context.json<{error_name}, {}>({}, response)",
+            render_type(tpe, "model.", false),
+            info_name(error_name)
+        ),
+    }
+}
+
+fn render_method(method: &Method, error_name: &str) -> Result<TypeScriptPrinter> {
+    if method.params.iter().any(|p| p.kind == ParamKind::Cookie) {
+        return Err(Error::unimplemented("Cookie parameters."));
+    }
+
+    let params = method
+        .params
+        .iter()
+        .map(render_param_declaration)
+        .join(", ");
+
+    let segments = method
+        .path
+        .0
+        .iter()
+        .map(|e| render_path_segment(method, e))
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+
+    let query_setters = method
+        .params
+        .iter()
+        .filter(|p| p.kind == ParamKind::Query)
+        .map(query_setter)
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    let header_setters = method
+        .params
+        .iter()
+        .filter(|p| p.kind == ParamKind::Header)
+        .map(header_setter)
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    let body_setter = body_setter(method);
+    let has_body = body_setter.is_some();
+
+    let send = unit()
+        + "const response = await this.context.send("
+        + info_name(error_name)
+        + ", "
+        + string_literal(&method.http_method.to_uppercase())
+        + ", url, headers"
+        + if has_body { ", requestBody" } else { "" }
+        + ");";
+
+    let success = match &method.result {
+        DataType::Unit => line("return;"),
+        tpe => line(unit() + "return " + parse_response(tpe, error_name) + ";"),
+    };
+
+    let error_cases = method
+        .errors
+        .codes
+        .iter()
+        .map(|(code, tpe)| {
+            line(unit() + "if (" + status_condition(code) + ") {")
+                + indented(
+                    line(unit() + "throw ClientError.item(" + info_name(error_name) + ", {")
+                        + indented(
+                            line(unit() + "type: " + string_literal(&variant_name(code)) + ",")
+                                + line("status: response.status,")
+                                + line(unit() + "body: " + parse_response(tpe, error_name) + ","),
+                        )
+                        + line("});"),
+                )
+                + line("}")
+        })
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    let result = match &method.result {
+        DataType::Unit => "void".to_string(),
+        tpe => render_type(tpe, "model.", false),
+    };
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line(unit() + "async " + identifier(&method.name) + "(" + params + "): Promise<" + result + "> {") +
+        indented(
+            line(unit() + "const url = this.context.url([" + segments + "]);") +
+            query_setters +
+            line("const headers = new Headers();") +
+            header_setters +
+            body_setter.unwrap_or_else(unit) +
+            NewLine +
+            line(send) +
+            NewLine +
+            line(unit() + "if (" + status_condition(&method.result_status_code) + ") {") +
+            indented(success) +
+            line("}") +
+            error_cases +
+            line(unit() + "throw ClientError.unexpected(" + info_name(error_name) + ", response.status, await response.arrayBuffer());")
+        ) +
+        line("}");
+
+    Ok(code)
+}
+
+pub fn client_gen(
+    client: &ClientDef,
+    conventions: &HashMap<String, ErrorConvention>,
+) -> Result<String> {
+    let errors = client
+        .errors()
+        .iter()
+        .map(|(method_name, errors)| {
+            render_errors(
+                &error_name(method_name, &client.error_kind),
+                errors,
+                &client.name,
+                conventions,
+            )
+        })
+        .reduce(|acc, e| acc + NewLine + e)
+        .unwrap_or_else(unit);
+
+    let methods = client
+        .methods
+        .iter()
+        .map(|m| render_method(m, &error_name(&m.name, &client.error_kind)))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| e.extend(format!("In Tag {}.", &client.name)))?
+        .into_iter()
+        .reduce(|acc, e| acc + NewLine + e)
+        .unwrap_or_else(unit);
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line(r#"import { Context } from "../context.js";"#) +
+        line(r#"import { ClientError, ErrorInfo, pickFields } from "../errors.js";"#) +
+        line(r#"import * as model from "../model.js";"#) +
+        NewLine +
+        errors +
+        NewLine +
+        line(unit() + "export class " + &client.name + "Client {") +
+        indented(
+            line("constructor(readonly context: Context) {}") +
+            NewLine +
+            methods
+        ) +
+        line("}");
+
+    Ok(TypeScriptContext::new().print_to_string(code))
+}
@@ -0,0 +1,113 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::spec::uses_bearer_security;
+use crate::typescript::printer::{TypeScriptContext, line, unit};
+use openapiv3::OpenAPI;
+
+pub fn context_gen(open_api: &OpenAPI) -> String {
+    let bearer = uses_bearer_security(open_api);
+
+    let token_option = if bearer {
+        line("token?: string;")
+    } else {
+        unit()
+    };
+
+    let token_field = if bearer {
+        line("readonly token?: string;")
+    } else {
+        unit()
+    };
+
+    let token_init = if bearer {
+        line("this.token = options.token;")
+    } else {
+        unit()
+    };
+
+    let bearer_token = if bearer {
+        line("return this.token;")
+    } else {
+        line("return undefined;")
+    };
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line(r#"import { ClientError, ErrorInfo } from "./errors.js";"#) +
+        NewLine +
+        line("export interface ContextOptions {") +
+        indented(
+            line("baseUrl: string | URL;") +
+            token_option +
+            line("fetch?: typeof fetch;")
+        ) +
+        line("}") +
+        NewLine +
+        line("export class Context {") +
+        indented(
+            line("readonly baseUrl: URL;") +
+            token_field +
+            line("private readonly fetchImpl: typeof fetch;") +
+            NewLine +
+            line("constructor(options: ContextOptions) {") +
+            indented(
+                line("this.baseUrl = new URL(options.baseUrl);") +
+                token_init +
+                line("this.fetchImpl = options.fetch ?? globalThis.fetch.bind(globalThis);")
+            ) +
+            line("}") +
+            NewLine +
+            line("bearerToken(): string | undefined {") +
+            indented(bearer_token) +
+            line("}") +
+            NewLine +
+            line("url(segments: string[]): URL {") +
+            indented(
+                line("const url = new URL(this.baseUrl);") +
+                line(r#"const basePath = url.pathname.replace(/\/+$/, "");"#) +
+                line(r#"url.pathname = basePath + "/" + segments.map(encodeURIComponent).join("/");"#) +
+                line("return url;")
+            ) +
+            line("}") +
+            NewLine +
+            line("async send<T>(info: ErrorInfo<T>, method: string, url: URL, headers: Headers, body?: BodyInit): Promise<Response> {") +
+            indented(
+                line("const token = this.bearerToken();") +
+                line("if (token !== undefined) {") +
+                indented(line(r#"headers.set("Authorization", `Bearer ${token}`);"#)) +
+                line("}") +
+                line("try {") +
+                indented(line("return await this.fetchImpl(url, { method, headers, body });")) +
+                line("} catch (error) {") +
+                indented(line("throw ClientError.transport(info, error);")) +
+                line("}")
+            ) +
+            line("}") +
+            NewLine +
+            line("async json<T, R>(info: ErrorInfo<T>, response: Response): Promise<R> {") +
+            indented(
+                line("try {") +
+                indented(line("return (await response.json()) as R;")) +
+                line("} catch (error) {") +
+                indented(line("throw ClientError.deserialization(info, error);")) +
+                line("}")
+            ) +
+            line("}")
+        ) +
+        line("}");
+
+    TypeScriptContext::new().print_to_string(code)
+}
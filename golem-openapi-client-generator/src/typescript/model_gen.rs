@@ -0,0 +1,156 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{NewLine, indented};
+use crate::spec::{Field, ModelDef, ModelKind};
+use crate::typescript::printer::{TypeScriptContext, TypeScriptPrinter, line, unit};
+use crate::typescript::types::{property_name, render_type, string_literal};
+use itertools::Itertools;
+
+fn render_field(field: &Field) -> TypeScriptPrinter {
+    let tpe = render_type(&field.tpe, "", false);
+
+    if field.required {
+        line(unit() + property_name(&field.name) + ": " + tpe + ";")
+    } else {
+        line(unit() + property_name(&field.name) + "?: " + tpe + " | null;")
+    }
+}
+
+fn render_model(model: &ModelDef) -> TypeScriptPrinter {
+    match &model.kind {
+        ModelKind::StringEnum { values } => {
+            let cases = values.iter().map(|v| string_literal(v)).join(" | ");
+
+            line(unit() + "export type " + &model.name + " = " + cases + ";")
+        }
+        ModelKind::Object { fields } => {
+            let fields = fields
+                .iter()
+                .map(render_field)
+                .reduce(|acc, e| acc + e)
+                .unwrap_or_else(unit);
+
+            line(unit() + "export interface " + &model.name + " {") + indented(fields) + line("}")
+        }
+        ModelKind::Union {
+            discriminator,
+            cases,
+        } => {
+            if cases.is_empty() {
+                line(unit() + "export type " + &model.name + " = never;")
+            } else {
+                let last = cases.len() - 1;
+                let cases = cases
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, case)| {
+                        line(
+                            unit()
+                                + "| ({ "
+                                + property_name(discriminator)
+                                + ": "
+                                + string_literal(&case.tag)
+                                + " } & "
+                                + render_type(&case.tpe, "", false)
+                                + ")"
+                                + if idx == last { ";" } else { "" },
+                        )
+                    })
+                    .reduce(|acc, e| acc + e)
+                    .unwrap_or_else(unit);
+
+                line(unit() + "export type " + &model.name + " =") + indented(cases)
+            }
+        }
+        ModelKind::Alias { tpe } => {
+            line(unit() + "export type " + &model.name + " = " + render_type(tpe, "", false) + ";")
+        }
+    }
+}
+
+pub fn model_gen(models: &[ModelDef]) -> String {
+    let code = models
+        .iter()
+        .map(render_model)
+        .reduce(|acc, e| acc + NewLine + e)
+        .unwrap_or_else(unit);
+
+    TypeScriptContext::new().print_to_string(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::rust::types::{DataType, ModelType};
+    use crate::spec::{Field, ModelDef, ModelKind, UnionCase};
+    use crate::typescript::model_gen::model_gen;
+    use indoc::indoc;
+
+    #[test]
+    fn models() {
+        let models = vec![
+            ModelDef {
+                name: "Filter".to_string(),
+                kind: ModelKind::Union {
+                    discriminator: "type".to_string(),
+                    cases: vec![UnionCase {
+                        tag: "Name".to_string(),
+                        tpe: DataType::Model(ModelType {
+                            name: "NameFilter".to_string(),
+                        }),
+                    }],
+                },
+            },
+            ModelDef {
+                name: "NameFilter".to_string(),
+                kind: ModelKind::Object {
+                    fields: vec![
+                        Field {
+                            name: "name".to_string(),
+                            tpe: DataType::String,
+                            required: true,
+                        },
+                        Field {
+                            name: "plugin-priority".to_string(),
+                            tpe: DataType::Array(Box::new(DataType::Json)),
+                            required: false,
+                        },
+                    ],
+                },
+            },
+            ModelDef {
+                name: "Status".to_string(),
+                kind: ModelKind::StringEnum {
+                    values: vec!["Running".to_string(), "Idle".to_string()],
+                },
+            },
+        ];
+
+        let expected = indoc! { r#"
+            export type Filter =
+              | ({ type: "Name" } & NameFilter);
+
+            export interface NameFilter {
+              name: string;
+              "plugin-priority"?: Array<unknown> | null;
+            }
+
+            export type Status = "Running" | "Idle";
+        "#};
+
+        assert_eq!(model_gen(&models), expected);
+    }
+}
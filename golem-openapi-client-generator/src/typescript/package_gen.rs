@@ -0,0 +1,77 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::NewLine;
+use crate::typescript::printer::{TypeScriptContext, line, unit};
+use crate::typescript::types::string_literal;
+use indoc::indoc;
+
+pub fn package_json(name: &str, version: &str) -> String {
+    #[rustfmt::skip]
+    let code = unit() +
+        "{" + NewLine +
+        r#"  "name": "# + string_literal(name) + "," + NewLine +
+        r#"  "version": "# + string_literal(version) + "," + NewLine +
+        r#"  "description": "Client for Golem Cloud's REST API","# + NewLine +
+        r#"  "license": "Apache-2.0","# + NewLine +
+        r#"  "type": "module","# + NewLine +
+        r#"  "main": "dist/index.js","# + NewLine +
+        r#"  "types": "dist/index.d.ts","# + NewLine +
+        r#"  "files": ["dist"],"# + NewLine +
+        r#"  "scripts": {"# + NewLine +
+        r#"    "build": "tsc""# + NewLine +
+        r#"  },"# + NewLine +
+        r#"  "devDependencies": {"# + NewLine +
+        r#"    "typescript": "^5.4.0""# + NewLine +
+        r#"  }"# + NewLine +
+        "}" + NewLine;
+
+    TypeScriptContext::new().print_to_string(code)
+}
+
+pub fn tsconfig() -> String {
+    indoc! { r#"
+        {
+          "compilerOptions": {
+            "target": "ES2022",
+            "lib": ["ES2022", "DOM"],
+            "module": "NodeNext",
+            "moduleResolution": "NodeNext",
+            "declaration": true,
+            "strict": true,
+            "rootDir": "src",
+            "outDir": "dist"
+          },
+          "include": ["src"]
+        }
+    "#}
+    .to_string()
+}
+
+pub fn index_gen(api_modules: &[String]) -> String {
+    let apis = api_modules
+        .iter()
+        .map(|module| line(unit() + r#"export * from "./api/"# + module + r#".js";"#))
+        .reduce(|acc, e| acc + e)
+        .unwrap_or_else(unit);
+
+    #[rustfmt::skip]
+    let code = unit() +
+        line(r#"export * from "./context.js";"#) +
+        line(r#"export * from "./errors.js";"#) +
+        line(r#"export * as model from "./model.js";"#) +
+        apis;
+
+    TypeScriptContext::new().print_to_string(code)
+}
@@ -0,0 +1,89 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::printer::{IndentContext, NewLine, PrintContext, Printer, TreePrinter};
+
+pub type TypeScriptPrinter = TreePrinter<TypeScriptContext>;
+
+pub struct TypeScriptContext {
+    code: String,
+    depth: usize,
+}
+
+impl TypeScriptContext {
+    pub fn new() -> TypeScriptContext {
+        TypeScriptContext {
+            code: String::new(),
+            depth: 0,
+        }
+    }
+
+    pub fn print_to_string(mut self, p: TypeScriptPrinter) -> String {
+        p.print(&mut self);
+
+        self.code
+    }
+}
+
+impl PrintContext for TypeScriptContext {
+    fn print_str(&mut self, s: &str) {
+        self.code.push_str(s)
+    }
+}
+
+impl IndentContext for TypeScriptContext {
+    fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    fn increment_ident(&mut self) {
+        self.depth += 1;
+    }
+
+    fn decrement_ident(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn indent_width(&self) -> usize {
+        2
+    }
+}
+
+pub trait IntoTypeScriptTree {
+    fn tree(self) -> TypeScriptPrinter;
+}
+
+impl IntoTypeScriptTree for TypeScriptPrinter {
+    fn tree(self) -> TypeScriptPrinter {
+        self
+    }
+}
+
+impl<T: Printer<TypeScriptContext> + 'static> IntoTypeScriptTree for T {
+    fn tree(self) -> TypeScriptPrinter {
+        TreePrinter::leaf(self)
+    }
+}
+
+pub fn unit() -> TypeScriptPrinter {
+    TreePrinter::unit()
+}
+
+pub fn indent() -> TypeScriptPrinter {
+    TreePrinter::indent()
+}
+
+pub fn line<T: IntoTypeScriptTree>(code: T) -> TypeScriptPrinter {
+    indent() + code.tree() + NewLine
+}
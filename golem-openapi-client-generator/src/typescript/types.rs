@@ -0,0 +1,145 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rust::types::{DataType, ModelType};
+use convert_case::{Case, Casing};
+
+const RESERVED_WORDS: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Name of a parameter or method, camel cased and escaped if it is a reserved word.
+pub fn identifier(name: &str) -> String {
+    let name = name.to_case(Case::Camel);
+    if RESERVED_WORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+pub fn string_literal(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Object property keys are kept as they are on the wire, quoted when necessary.
+pub fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        string_literal(name)
+    }
+}
+
+pub fn property_access(target: &str, name: &str) -> String {
+    if is_identifier(name) {
+        format!("{target}.{name}")
+    } else {
+        format!("{target}[{}]", string_literal(name))
+    }
+}
+
+/// TypeScript type of a data type, `model_prefix` qualifies the model names and `top_param`
+/// selects the types accepted by the client methods rather than the ones they return.
+pub fn render_type(tpe: &DataType, model_prefix: &str, top_param: bool) -> String {
+    match tpe {
+        DataType::String | DataType::Uuid | DataType::DateTime => "string".to_string(),
+        DataType::Boolean => "boolean".to_string(),
+        DataType::Number | DataType::Int(_) => "number".to_string(),
+        DataType::Model(ModelType { name }) => format!("{model_prefix}{name}"),
+        DataType::Binary => {
+            if top_param {
+                "Blob".to_string()
+            } else {
+                "ArrayBuffer".to_string()
+            }
+        }
+        DataType::Array(item) => format!("Array<{}>", render_type(item, model_prefix, false)),
+        DataType::MapOf(element) => {
+            format!(
+                "Record<string, {}>",
+                render_type(element, model_prefix, false)
+            )
+        }
+        DataType::Json => "unknown".to_string(),
+        DataType::Yaml => "string".to_string(),
+        DataType::Unit => "void".to_string(),
+    }
+}
@@ -0,0 +1,136 @@
+import json
+from typing import Any, ClassVar, Dict, List, Optional, Tuple, Union
+
+from .. import models
+from ..context import Context, to_param
+from ..errors import ErrorInfo, ItemError, UnexpectedResponseError, pick_fields
+
+
+class AgentError(ErrorInfo):
+    SERVICE_NAME: ClassVar[str] = "Agent"
+
+    def __init__(self, variant: str, status: int, body: Union["models.ErrorsBody", "models.ErrorBody", "models.ErrorBodyWithOptionalWorkerError"]) -> None:
+        self.variant = variant
+        self.status = status
+        self.body = body
+
+    def __repr__(self) -> str:
+        return f"{type(self).__name__}.{self.variant}({self.body!r})"
+
+    def status_code(self) -> int:
+        return self.status
+
+    def errors(self) -> List[str]:
+        body = self.body
+        if self.variant == "Error400":
+            return list(body["errors"])
+        if self.variant == "Error401":
+            return [body["error"]]
+        if self.variant == "Error403":
+            return [body["error"]]
+        if self.variant == "Error404":
+            return [body["error"]]
+        if self.variant == "Error409":
+            return [body["error"]]
+        if self.variant == "Error413":
+            return [body["error"]]
+        if self.variant == "Error415":
+            return [body["error"]]
+        if self.variant == "Error422":
+            return [body["error"]]
+        if self.variant == "Error500":
+            return [body["error"]]
+        return []
+
+    def code(self) -> Optional[str]:
+        body = self.body
+        if self.variant == "Error400":
+            return body["code"]
+        if self.variant == "Error401":
+            return body["code"]
+        if self.variant == "Error403":
+            return body["code"]
+        if self.variant == "Error404":
+            return body["code"]
+        if self.variant == "Error409":
+            return body["code"]
+        if self.variant == "Error413":
+            return body["code"]
+        if self.variant == "Error415":
+            return body["code"]
+        if self.variant == "Error422":
+            return body["code"]
+        if self.variant == "Error500":
+            return body["code"]
+        return None
+
+    def additional_fields(self) -> Optional[Dict[str, Any]]:
+        body = self.body
+        if self.variant == "Error500":
+            return pick_fields(body, ["workerError"])
+        return None
+
+
+class AgentClient:
+    def __init__(self, context: Context) -> None:
+        self.context = context
+
+    async def invoke_agent(self, idempotency_key: Optional[str], value: "models.AgentInvocationRequest") -> "models.AgentInvocationResult":
+        url = self.context.url(["v1", "agents", "invoke-agent"])
+        params: List[Tuple[str, str]] = []
+        headers: Dict[str, str] = {}
+        if idempotency_key is not None:
+            headers["Idempotency-Key"] = idempotency_key
+
+        response = await self.context.send(AgentError.SERVICE_NAME, "POST", url, params, headers, json=value)
+
+        if response.status_code == 200:
+            return self.context.json(AgentError.SERVICE_NAME, response)
+        if response.status_code == 400:
+            raise ItemError(AgentError("Error400", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 401:
+            raise ItemError(AgentError("Error401", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 403:
+            raise ItemError(AgentError("Error403", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 404:
+            raise ItemError(AgentError("Error404", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 409:
+            raise ItemError(AgentError("Error409", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 413:
+            raise ItemError(AgentError("Error413", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 415:
+            raise ItemError(AgentError("Error415", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 422:
+            raise ItemError(AgentError("Error422", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 500:
+            raise ItemError(AgentError("Error500", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        raise UnexpectedResponseError(AgentError.SERVICE_NAME, response.status_code, response.content)
+
+    async def create_agent(self, value: "models.CreateAgentRequest") -> "models.CreateAgentResponse":
+        url = self.context.url(["v1", "agents", "create-agent"])
+        params: List[Tuple[str, str]] = []
+        headers: Dict[str, str] = {}
+
+        response = await self.context.send(AgentError.SERVICE_NAME, "POST", url, params, headers, json=value)
+
+        if response.status_code == 200:
+            return self.context.json(AgentError.SERVICE_NAME, response)
+        if response.status_code == 400:
+            raise ItemError(AgentError("Error400", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 401:
+            raise ItemError(AgentError("Error401", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 403:
+            raise ItemError(AgentError("Error403", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 404:
+            raise ItemError(AgentError("Error404", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 409:
+            raise ItemError(AgentError("Error409", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 413:
+            raise ItemError(AgentError("Error413", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 415:
+            raise ItemError(AgentError("Error415", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 422:
+            raise ItemError(AgentError("Error422", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        if response.status_code == 500:
+            raise ItemError(AgentError("Error500", response.status_code, self.context.json(AgentError.SERVICE_NAME, response)))
+        raise UnexpectedResponseError(AgentError.SERVICE_NAME, response.status_code, response.content)
@@ -0,0 +1,51 @@
+from typing import Any, Dict, List, Optional, Sequence, Tuple
+from urllib.parse import quote
+
+import httpx
+
+from .errors import DeserializationError, TransportError
+
+
+def to_param(value: Any) -> str:
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    return str(value)
+
+
+class Context:
+    def __init__(self, base_url: str, token: Optional[str] = None, client: Optional[httpx.AsyncClient] = None) -> None:
+        self.base_url = base_url.rstrip("/")
+        self.token = token
+        self.client = client if client is not None else httpx.AsyncClient()
+
+    def bearer_token(self) -> Optional[str]:
+        return self.token
+
+    def url(self, segments: Sequence[str]) -> str:
+        return self.base_url + "/" + "/".join(quote(segment, safe="") for segment in segments)
+
+    async def send(
+        self,
+        service_name: str,
+        method: str,
+        url: str,
+        params: List[Tuple[str, str]],
+        headers: Dict[str, str],
+        **kwargs: Any,
+    ) -> httpx.Response:
+        token = self.bearer_token()
+        if token is not None:
+            headers["Authorization"] = f"Bearer {token}"
+        try:
+            return await self.client.request(method, url, params=params, headers=headers, **kwargs)
+        except httpx.HTTPError as error:
+            raise TransportError(service_name, error) from error
+
+    def json(self, service_name: str, response: httpx.Response) -> Any:
+        try:
+            return response.json()
+        except ValueError as error:
+            raise DeserializationError(service_name, error) from error
+
+    async def aclose(self) -> None:
+        await self.client.aclose()
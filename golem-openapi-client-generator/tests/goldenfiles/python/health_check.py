@@ -0,0 +1,57 @@
+import json
+from typing import Any, ClassVar, Dict, List, Optional, Tuple, Union
+
+from .. import models
+from ..context import Context, to_param
+from ..errors import ErrorInfo, ItemError, UnexpectedResponseError, pick_fields
+
+
+class HealthCheckError(ErrorInfo):
+    SERVICE_NAME: ClassVar[str] = "HealthCheck"
+
+    def __init__(self, variant: str, status: int, body: Any) -> None:
+        self.variant = variant
+        self.status = status
+        self.body = body
+
+    def __repr__(self) -> str:
+        return f"{type(self).__name__}.{self.variant}({self.body!r})"
+
+    def status_code(self) -> int:
+        return self.status
+
+    def errors(self) -> List[str]:
+        return []
+
+    def code(self) -> Optional[str]:
+        return None
+
+    def additional_fields(self) -> Optional[Dict[str, Any]]:
+        return None
+
+
+class HealthCheckClient:
+    def __init__(self, context: Context) -> None:
+        self.context = context
+
+    async def healthcheck(self) -> "models.Empty":
+        url = self.context.url(["healthcheck"])
+        params: List[Tuple[str, str]] = []
+        headers: Dict[str, str] = {}
+
+        response = await self.context.send(HealthCheckError.SERVICE_NAME, "GET", url, params, headers)
+
+        if response.status_code == 200:
+            return self.context.json(HealthCheckError.SERVICE_NAME, response)
+        raise UnexpectedResponseError(HealthCheckError.SERVICE_NAME, response.status_code, response.content)
+
+    async def version(self) -> "models.VersionInfo":
+        url = self.context.url(["version"])
+        params: List[Tuple[str, str]] = []
+        headers: Dict[str, str] = {}
+
+        response = await self.context.send(HealthCheckError.SERVICE_NAME, "GET", url, params, headers)
+
+        if response.status_code == 200:
+            return self.context.json(HealthCheckError.SERVICE_NAME, response)
+        raise UnexpectedResponseError(HealthCheckError.SERVICE_NAME, response.status_code, response.content)
@@ -0,0 +1,273 @@
+import { Context } from "../context.js";
+import { ClientError, ErrorInfo, pickFields } from "../errors.js";
+import * as model from "../model.js";
+
+export type AgentError =
+  | { type: "Error400"; status: number; body: model.ErrorsBody }
+  | { type: "Error401"; status: number; body: model.ErrorBody }
+  | { type: "Error403"; status: number; body: model.ErrorBody }
+  | { type: "Error404"; status: number; body: model.ErrorBody }
+  | { type: "Error409"; status: number; body: model.ErrorBody }
+  | { type: "Error413"; status: number; body: model.ErrorBody }
+  | { type: "Error415"; status: number; body: model.ErrorBody }
+  | { type: "Error422"; status: number; body: model.ErrorBody }
+  | { type: "Error500"; status: number; body: model.ErrorBodyWithOptionalWorkerError };
+
+export const AgentErrorInfo: ErrorInfo<AgentError> = {
+  serviceName: "Agent",
+  statusCode(error: AgentError): number {
+    return error.status;
+  },
+  errors(error: AgentError): string[] {
+    switch (error.type) {
+      case "Error400": {
+        const body = error.body;
+        return body.errors;
+      }
+      case "Error401": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error403": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error404": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error409": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error413": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error415": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error422": {
+        const body = error.body;
+        return [body.error];
+      }
+      case "Error500": {
+        const body = error.body;
+        return [body.error];
+      }
+    }
+    return [];
+  },
+  code(error: AgentError): string | undefined {
+    switch (error.type) {
+      case "Error400": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error401": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error403": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error404": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error409": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error413": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error415": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error422": {
+        const body = error.body;
+        return body.code;
+      }
+      case "Error500": {
+        const body = error.body;
+        return body.code;
+      }
+    }
+    return undefined;
+  },
+  additionalFields(error: AgentError): Record<string, unknown> | undefined {
+    switch (error.type) {
+      case "Error500": {
+        const body = error.body;
+        return pickFields(body, ["workerError"]);
+      }
+    }
+    return undefined;
+  },
+};
+
+export class AgentClient {
+  constructor(readonly context: Context) {}
+
+  async invokeAgent(idempotencyKey: string | undefined, value: model.AgentInvocationRequest): Promise<model.AgentInvocationResult> {
+    const url = this.context.url(["v1", "agents", "invoke-agent"]);
+    const headers = new Headers();
+    if (idempotencyKey !== undefined) {
+      headers.append("Idempotency-Key", idempotencyKey);
+    }
+    headers.set("Content-Type", "application/json");
+    const requestBody = JSON.stringify(value);
+
+    const response = await this.context.send(AgentErrorInfo, "POST", url, headers, requestBody);
+
+    if (response.status === 200) {
+      return await this.context.json<AgentError, model.AgentInvocationResult>(AgentErrorInfo, response);
+    }
+    if (response.status === 400) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error400",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorsBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 401) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error401",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 403) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error403",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 404) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error404",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 409) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error409",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 413) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error413",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 415) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error415",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 422) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error422",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 500) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error500",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBodyWithOptionalWorkerError>(AgentErrorInfo, response),
+      });
+    }
+    throw ClientError.unexpected(AgentErrorInfo, response.status, await response.arrayBuffer());
+  }
+
+  async createAgent(value: model.CreateAgentRequest): Promise<model.CreateAgentResponse> {
+    const url = this.context.url(["v1", "agents", "create-agent"]);
+    const headers = new Headers();
+    headers.set("Content-Type", "application/json");
+    const requestBody = JSON.stringify(value);
+
+    const response = await this.context.send(AgentErrorInfo, "POST", url, headers, requestBody);
+
+    if (response.status === 200) {
+      return await this.context.json<AgentError, model.CreateAgentResponse>(AgentErrorInfo, response);
+    }
+    if (response.status === 400) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error400",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorsBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 401) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error401",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 403) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error403",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 404) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error404",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 409) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error409",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 413) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error413",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 415) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error415",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 422) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error422",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBody>(AgentErrorInfo, response),
+      });
+    }
+    if (response.status === 500) {
+      throw ClientError.item(AgentErrorInfo, {
+        type: "Error500",
+        status: response.status,
+        body: await this.context.json<AgentError, model.ErrorBodyWithOptionalWorkerError>(AgentErrorInfo, response),
+      });
+    }
+    throw ClientError.unexpected(AgentErrorInfo, response.status, await response.arrayBuffer());
+  }
+}
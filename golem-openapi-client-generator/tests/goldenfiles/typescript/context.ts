@@ -0,0 +1,50 @@
+import { ClientError, ErrorInfo } from "./errors.js";
+
+export interface ContextOptions {
+  baseUrl: string | URL;
+  token?: string;
+  fetch?: typeof fetch;
+}
+
+export class Context {
+  readonly baseUrl: URL;
+  readonly token?: string;
+  private readonly fetchImpl: typeof fetch;
+
+  constructor(options: ContextOptions) {
+    this.baseUrl = new URL(options.baseUrl);
+    this.token = options.token;
+    this.fetchImpl = options.fetch ?? globalThis.fetch.bind(globalThis);
+  }
+
+  bearerToken(): string | undefined {
+    return this.token;
+  }
+
+  url(segments: string[]): URL {
+    const url = new URL(this.baseUrl);
+    const basePath = url.pathname.replace(/\/+$/, "");
+    url.pathname = basePath + "/" + segments.map(encodeURIComponent).join("/");
+    return url;
+  }
+
+  async send<T>(info: ErrorInfo<T>, method: string, url: URL, headers: Headers, body?: BodyInit): Promise<Response> {
+    const token = this.bearerToken();
+    if (token !== undefined) {
+      headers.set("Authorization", `Bearer ${token}`);
+    }
+    try {
+      return await this.fetchImpl(url, { method, headers, body });
+    } catch (error) {
+      throw ClientError.transport(info, error);
+    }
+  }
+
+  async json<T, R>(info: ErrorInfo<T>, response: Response): Promise<R> {
+    try {
+      return (await response.json()) as R;
+    } catch (error) {
+      throw ClientError.deserialization(info, error);
+    }
+  }
+}
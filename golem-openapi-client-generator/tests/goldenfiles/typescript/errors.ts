@@ -0,0 +1,124 @@
+export interface ErrorInfo<T> {
+  readonly serviceName: string;
+  statusCode(error: T): number;
+  errors(error: T): string[];
+  code(error: T): string | undefined;
+  additionalFields(error: T): Record<string, unknown> | undefined;
+}
+
+export type ClientErrorKind = "item" | "transport" | "deserialization" | "unexpected";
+
+export class ClientError<T> extends Error {
+  private constructor(
+    readonly kind: ClientErrorKind,
+    readonly info: ErrorInfo<T>,
+    message: string,
+    readonly item?: T,
+    readonly status?: number,
+    readonly data?: ArrayBuffer,
+    cause?: unknown,
+  ) {
+    super(message, { cause });
+    this.name = "ClientError";
+  }
+
+  static item<T>(info: ErrorInfo<T>, item: T): ClientError<T> {
+    return new ClientError(
+      "item",
+      info,
+      `${info.serviceName} error: ${JSON.stringify(item)}`,
+      item,
+      info.statusCode(item),
+    );
+  }
+
+  static transport<T>(info: ErrorInfo<T>, cause: unknown): ClientError<T> {
+    return new ClientError(
+      "transport",
+      info,
+      `Client error: ${String(cause)}`,
+      undefined,
+      undefined,
+      undefined,
+      cause,
+    );
+  }
+
+  static deserialization<T>(info: ErrorInfo<T>, cause: unknown): ClientError<T> {
+    return new ClientError(
+      "deserialization",
+      info,
+      `Deserialization error: ${String(cause)}`,
+      undefined,
+      undefined,
+      undefined,
+      cause,
+    );
+  }
+
+  static unexpected<T>(info: ErrorInfo<T>, status: number, data: ArrayBuffer): ClientError<T> {
+    return new ClientError(
+      "unexpected",
+      info,
+      `Unexpected response - status ${status}, data: ${new TextDecoder().decode(data)}`,
+      undefined,
+      status,
+      data,
+    );
+  }
+
+  get serviceName(): string {
+    return this.info.serviceName;
+  }
+
+  statusCode(): number | undefined {
+    return this.status;
+  }
+
+  errors(): string[] {
+    return this.kind === "item" ? this.info.errors(this.item as T) : [];
+  }
+
+  code(): string | undefined {
+    return this.kind === "item" ? this.info.code(this.item as T) : undefined;
+  }
+
+  additionalFields(): Record<string, unknown> | undefined {
+    return this.kind === "item" ? this.info.additionalFields(this.item as T) : undefined;
+  }
+
+  isStatusCode(statusCode: number): boolean {
+    return this.status === statusCode;
+  }
+
+  isSuccess(): boolean {
+    return this.status !== undefined && this.status >= 200 && this.status < 300;
+  }
+
+  isClientError(): boolean {
+    return this.status !== undefined && this.status >= 400 && this.status < 500;
+  }
+
+  isServerError(): boolean {
+    return this.status !== undefined && this.status >= 500 && this.status < 600;
+  }
+
+  isNotFound(): boolean {
+    return this.isStatusCode(404);
+  }
+
+  hasCode(code: string): boolean {
+    return this.code() === code;
+  }
+}
+
+export function pickFields(body: object, names: string[]): Record<string, unknown> | undefined {
+  const fields: Record<string, unknown> = {};
+  for (const name of names) {
+    const value = (body as Record<string, unknown>)[name];
+    if (value !== undefined && value !== null) {
+      fields[name] = value;
+    }
+  }
+  return Object.keys(fields).length === 0 ? undefined : fields;
+}
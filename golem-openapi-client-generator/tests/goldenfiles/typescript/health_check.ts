@@ -0,0 +1,49 @@
+import { Context } from "../context.js";
+import { ClientError, ErrorInfo, pickFields } from "../errors.js";
+import * as model from "../model.js";
+
+export type HealthCheckError = never;
+
+export const HealthCheckErrorInfo: ErrorInfo<HealthCheckError> = {
+  serviceName: "HealthCheck",
+  statusCode(error: HealthCheckError): number {
+    return error.status;
+  },
+  errors(error: HealthCheckError): string[] {
+    return [];
+  },
+  code(error: HealthCheckError): string | undefined {
+    return undefined;
+  },
+  additionalFields(error: HealthCheckError): Record<string, unknown> | undefined {
+    return undefined;
+  },
+};
+
+export class HealthCheckClient {
+  constructor(readonly context: Context) {}
+
+  async healthcheck(): Promise<model.Empty> {
+    const url = this.context.url(["healthcheck"]);
+    const headers = new Headers();
+
+    const response = await this.context.send(HealthCheckErrorInfo, "GET", url, headers);
+
+    if (response.status === 200) {
+      return await this.context.json<HealthCheckError, model.Empty>(HealthCheckErrorInfo, response);
+    }
+    throw ClientError.unexpected(HealthCheckErrorInfo, response.status, await response.arrayBuffer());
+  }
+
+  async version(): Promise<model.VersionInfo> {
+    const url = this.context.url(["version"]);
+    const headers = new Headers();
+
+    const response = await this.context.send(HealthCheckErrorInfo, "GET", url, headers);
+
+    if (response.status === 200) {
+      return await this.context.json<HealthCheckError, model.VersionInfo>(HealthCheckErrorInfo, response);
+    }
+    throw ClientError.unexpected(HealthCheckErrorInfo, response.status, await response.arrayBuffer());
+  }
+}
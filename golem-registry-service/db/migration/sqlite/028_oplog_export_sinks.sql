@@ -0,0 +1,39 @@
+CREATE TABLE oplog_export_sinks
+(
+    oplog_export_sink_id UUID      NOT NULL,
+
+    environment_id       UUID      NOT NULL,
+    name                 TEXT      NOT NULL,
+
+    created_at           TIMESTAMP NOT NULL,
+    updated_at           TIMESTAMP NOT NULL,
+    deleted_at           TIMESTAMP,
+    modified_by          UUID      NOT NULL,
+
+    current_revision_id  BIGINT    NOT NULL,
+
+    CONSTRAINT oplog_export_sinks_pk
+        PRIMARY KEY (oplog_export_sink_id),
+    CONSTRAINT oplog_export_sinks_environments_fk
+        FOREIGN KEY (environment_id) REFERENCES environments
+);
+
+CREATE UNIQUE INDEX oplog_export_sinks_environment_name_uk ON oplog_export_sinks (environment_id, name)
+    WHERE deleted_at IS NULL;
+
+CREATE TABLE oplog_export_sink_revisions
+(
+    oplog_export_sink_id UUID      NOT NULL,
+    revision_id          BIGINT    NOT NULL,
+
+    target_json          TEXT      NOT NULL,
+
+    created_at           TIMESTAMP NOT NULL,
+    created_by           UUID      NOT NULL,
+    deleted              BOOLEAN   NOT NULL,
+
+    CONSTRAINT oplog_export_sink_revisions_pk
+        PRIMARY KEY (oplog_export_sink_id, revision_id),
+    CONSTRAINT oplog_export_sink_revisions_oplog_export_sinks_fk
+        FOREIGN KEY (oplog_export_sink_id) REFERENCES oplog_export_sinks
+);
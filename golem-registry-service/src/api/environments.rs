@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use super::ApiResult;
+use super::error::ApiError;
 use crate::services::auth::AuthService;
 use crate::services::deployment::{DeploymentService, DeploymentWriteService};
 use crate::services::environment::EnvironmentService;
+use golem_common::base_model::api;
 use golem_common::model::Page;
 use golem_common::model::agent::AgentTypeName;
 use golem_common::model::agent::DeployedRegisteredAgentType;
+use golem_common::model::agent::{AgentTypeSchemaExport, AgentTypeSchemaFormat};
 use golem_common::model::application::ApplicationId;
 use golem_common::model::deployment::{
     CurrentDeployment, Deployment, DeploymentCreation, DeploymentPlan, DeploymentRevision,
@@ -27,6 +30,7 @@ use golem_common::model::deployment::{
 use golem_common::model::environment::*;
 use golem_common::model::poem::NoContentResponse;
 use golem_common::recorded_http_api_request;
+use golem_common::schema::render::{agent_type_to_json_schema, agent_type_to_protobuf};
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::model::auth::AuthCtx;
 use golem_service_base::model::auth::GolemSecurityScheme;
@@ -602,4 +606,87 @@ impl EnvironmentsApi {
             .await?;
         Ok(Json(agent_type))
     }
+
+    /// Export the schema of a registered agent type in a deployment
+    ///
+    /// Renders the agent type's constructor and methods either as a proto3 IDL file
+    /// or as a bundled JSON Schema document, for use with external code generators.
+    #[oai(
+        path = "/envs/:environment_id/deployments/:deployment_id/agent-types/:agent_type_name/schema",
+        method = "get",
+        operation_id = "export_deployment_agent_type_schema"
+    )]
+    async fn export_deployment_agent_type_schema(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        deployment_id: Path<DeploymentRevision>,
+        agent_type_name: Path<AgentTypeName>,
+        format: Query<AgentTypeSchemaFormat>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<AgentTypeSchemaExport>> {
+        let record = recorded_http_api_request!(
+            "export_deployment_agent_type_schema",
+            environment_id = environment_id.0.to_string(),
+            deployment_id = deployment_id.0.to_string(),
+            agent_type_name = agent_type_name.0.to_string(),
+            format = format.0.to_string(),
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .export_deployment_agent_type_schema_internal(
+                environment_id.0,
+                deployment_id.0,
+                agent_type_name.0,
+                format.0,
+                auth,
+            )
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn export_deployment_agent_type_schema_internal(
+        &self,
+        environment_id: EnvironmentId,
+        deployment_id: DeploymentRevision,
+        agent_type_name: AgentTypeName,
+        format: AgentTypeSchemaFormat,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<AgentTypeSchemaExport>> {
+        let agent_type = self
+            .deployment_service
+            .get_deployment_agent_type_with_schema(
+                environment_id,
+                deployment_id,
+                &agent_type_name,
+                &auth,
+            )
+            .await?;
+
+        let content = match format {
+            AgentTypeSchemaFormat::Protobuf => {
+                agent_type_to_protobuf(&agent_type.schema).map_err(|err| {
+                    ApiError::bad_request(
+                        api::error_code::AGENT_TYPE_SCHEMA_NOT_EXPORTABLE,
+                        format!(
+                            "Agent type {agent_type_name} cannot be exported as Protobuf: {err}"
+                        ),
+                    )
+                })?
+            }
+            AgentTypeSchemaFormat::JsonSchema => {
+                serde_json::to_string_pretty(&agent_type_to_json_schema(&agent_type.schema))
+                    .map_err(|err| anyhow::anyhow!(err))?
+            }
+        };
+
+        Ok(Json(AgentTypeSchemaExport {
+            agent_type_name,
+            format,
+            content,
+        }))
+    }
 }
@@ -25,6 +25,7 @@ use crate::services::environment_plugin_grant::EnvironmentPluginGrantError;
 use crate::services::http_api_deployment::HttpApiDeploymentError;
 use crate::services::mcp_deployment::McpDeploymentError;
 use crate::services::oauth2::OAuth2Error;
+use crate::services::oplog_export_sink::OplogExportSinkError;
 use crate::services::permission_share::PermissionShareError;
 use crate::services::plan::PlanError;
 use crate::services::plugin_registration::PluginRegistrationError;
@@ -161,6 +162,12 @@ fn deployment_validation_subcode(error: &DeployValidationError) -> &'static str
         DeployValidationError::HttpApiDeploymentInvalidRoute { .. } => {
             api::error_code::deployment_validation::HTTP_API_INVALID_ROUTE
         }
+        DeployValidationError::HttpApiDeploymentInvalidRateLimit { .. } => {
+            api::error_code::deployment_validation::HTTP_API_INVALID_RATE_LIMIT
+        }
+        DeployValidationError::HttpApiDeploymentRateLimitUnknownMethod { .. } => {
+            api::error_code::deployment_validation::HTTP_API_RATE_LIMIT_UNKNOWN_METHOD
+        }
         DeployValidationError::RouteIsAmbiguous { .. } => {
             api::error_code::deployment_validation::ROUTE_IS_AMBIGUOUS
         }
@@ -649,6 +656,14 @@ impl From<PluginRegistrationError> for ApiError {
                 }))
             }
 
+            PluginRegistrationError::InvocationInterceptorComponentDoesNotExist => {
+                Self::BadRequest(Json(ErrorsBody {
+                    errors: vec![error],
+                    code: api::error_code::INVOCATION_INTERCEPTOR_COMPONENT_NOT_FOUND.to_string(),
+                    cause: None,
+                }))
+            }
+
             PluginRegistrationError::PluginNameAndVersionAlreadyExists => {
                 Self::conflict(api::error_code::PLUGIN_REGISTRATION_ALREADY_EXISTS, error)
             }
@@ -1011,6 +1026,38 @@ impl From<RetryPolicyError> for ApiError {
     }
 }
 
+impl From<OplogExportSinkError> for ApiError {
+    fn from(value: OplogExportSinkError) -> Self {
+        let error: String = value.to_safe_string();
+        match value {
+            OplogExportSinkError::InvalidName(_) => {
+                Self::bad_request(api::error_code::OPLOG_EXPORT_SINK_INVALID_NAME, error)
+            }
+            OplogExportSinkError::InvalidTarget(_) => {
+                Self::bad_request(api::error_code::OPLOG_EXPORT_SINK_INVALID_TARGET, error)
+            }
+            OplogExportSinkError::ConcurrentModification => {
+                Self::conflict(api::error_code::CONCURRENT_UPDATE, error)
+            }
+            OplogExportSinkError::OplogExportSinkForNameAlreadyExists { .. } => {
+                Self::conflict(api::error_code::OPLOG_EXPORT_SINK_ALREADY_EXISTS, error)
+            }
+            OplogExportSinkError::OplogExportSinkNotFound(_) => {
+                Self::not_found(api::error_code::OPLOG_EXPORT_SINK_NOT_FOUND, error)
+            }
+            OplogExportSinkError::ParentEnvironmentNotFound(_) => {
+                Self::not_found(api::error_code::ENVIRONMENT_NOT_FOUND, error)
+            }
+            OplogExportSinkError::Unauthorized(inner) => inner.into(),
+            OplogExportSinkError::InternalError(_) => Self::InternalError(Json(ErrorBody {
+                code: api::error_code::INTERNAL_UNKNOWN.to_string(),
+                error,
+                cause: Some(value.into_anyhow()),
+            })),
+        }
+    }
+}
+
 impl From<ResourceDefinitionError> for ApiError {
     fn from(value: ResourceDefinitionError) -> Self {
         let error: String = value.to_safe_string();
@@ -26,6 +26,7 @@ pub mod http_api_deployments;
 pub mod login;
 pub mod mcp_deployments;
 pub mod me;
+pub mod oplog_export_sinks;
 pub mod permission_shares;
 pub mod plugin_registrations;
 pub mod reports;
@@ -47,6 +48,7 @@ use self::http_api_deployments::HttpApiDeploymentsApi;
 use self::login::LoginApi;
 use self::mcp_deployments::McpDeploymentsApi;
 use self::me::MeApi;
+use self::oplog_export_sinks::OplogExportSinksApi;
 use self::permission_shares::PermissionSharesApi;
 use self::plugin_registrations::PluginRegistrationsApi;
 use self::reports::ReportsApi;
@@ -70,6 +72,7 @@ pub type Apis = (
     (LoginApi, MeApi),
     (
         McpDeploymentsApi,
+        OplogExportSinksApi,
         PermissionSharesApi,
         PluginRegistrationsApi,
     ),
@@ -139,6 +142,10 @@ pub fn make_open_api_service(services: &Services) -> OpenApiService<Apis, ()> {
                     services.mcp_deployment_service.clone(),
                     services.auth_service.clone(),
                 ),
+                OplogExportSinksApi::new(
+                    services.oplog_export_sink_service.clone(),
+                    services.auth_service.clone(),
+                ),
                 PermissionSharesApi::new(
                     services.permission_share_service.clone(),
                     services.auth_service.clone(),
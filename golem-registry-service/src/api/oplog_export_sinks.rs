@@ -0,0 +1,256 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::ApiResult;
+use crate::services::auth::AuthService;
+use crate::services::oplog_export_sink::OplogExportSinkService;
+use golem_common::model::Page;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog_export::{
+    OplogExportSinkCreation, OplogExportSinkDto, OplogExportSinkId, OplogExportSinkRevision,
+    OplogExportSinkUpdate,
+};
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::model::auth::AuthCtx;
+use golem_service_base::model::auth::GolemSecurityScheme;
+use poem_openapi::OpenApi;
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::Json;
+use std::sync::Arc;
+use tracing::Instrument;
+
+pub struct OplogExportSinksApi {
+    oplog_export_sink_service: Arc<OplogExportSinkService>,
+    auth_service: Arc<AuthService>,
+}
+
+#[OpenApi(
+    prefix_path = "/v1",
+    tag = ApiTags::RegistryService,
+    tag = ApiTags::OplogExportSinks
+)]
+impl OplogExportSinksApi {
+    pub fn new(
+        oplog_export_sink_service: Arc<OplogExportSinkService>,
+        auth_service: Arc<AuthService>,
+    ) -> Self {
+        Self {
+            oplog_export_sink_service,
+            auth_service,
+        }
+    }
+
+    /// Create a new oplog export sink
+    #[oai(
+        path = "/envs/:environment_id/oplog-export-sinks",
+        method = "post",
+        operation_id = "create_oplog_export_sink",
+        tag = ApiTags::Environment
+    )]
+    async fn create_oplog_export_sink(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        payload: Json<OplogExportSinkCreation>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let record = recorded_http_api_request!(
+            "create_oplog_export_sink",
+            environment_id = environment_id.0.to_string(),
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .create_oplog_export_sink_internal(environment_id.0, payload.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn create_oplog_export_sink_internal(
+        &self,
+        environment_id: EnvironmentId,
+        payload: OplogExportSinkCreation,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let result = self
+            .oplog_export_sink_service
+            .create(environment_id, payload, &auth)
+            .await?;
+
+        Ok(Json(result.into()))
+    }
+
+    /// Get all oplog export sinks of the environment
+    #[oai(
+        path = "/envs/:environment_id/oplog-export-sinks",
+        method = "get",
+        operation_id = "list_environment_oplog_export_sinks",
+        tag = ApiTags::Environment
+    )]
+    async fn list_environment_oplog_export_sinks(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<Page<OplogExportSinkDto>>> {
+        let record = recorded_http_api_request!(
+            "list_environment_oplog_export_sinks",
+            environment_id = environment_id.0.to_string(),
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .list_environment_oplog_export_sinks_internal(environment_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn list_environment_oplog_export_sinks_internal(
+        &self,
+        environment_id: EnvironmentId,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<Page<OplogExportSinkDto>>> {
+        let result = self
+            .oplog_export_sink_service
+            .list_in_environment(environment_id, &auth)
+            .await?;
+
+        Ok(Json(Page {
+            values: result.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    /// Get oplog export sink by id.
+    #[oai(
+        path = "/oplog-export-sinks/:oplog_export_sink_id",
+        method = "get",
+        operation_id = "get_oplog_export_sink"
+    )]
+    pub async fn get_oplog_export_sink(
+        &self,
+        oplog_export_sink_id: Path<OplogExportSinkId>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let record = recorded_http_api_request!(
+            "get_oplog_export_sink",
+            oplog_export_sink_id = oplog_export_sink_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_oplog_export_sink_internal(oplog_export_sink_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_oplog_export_sink_internal(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let result = self
+            .oplog_export_sink_service
+            .get(oplog_export_sink_id, &auth)
+            .await?;
+        Ok(Json(result.into()))
+    }
+
+    /// Update oplog export sink
+    #[oai(
+        path = "/oplog-export-sinks/:oplog_export_sink_id",
+        method = "patch",
+        operation_id = "update_oplog_export_sink"
+    )]
+    pub async fn update_oplog_export_sink(
+        &self,
+        oplog_export_sink_id: Path<OplogExportSinkId>,
+        data: Json<OplogExportSinkUpdate>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let record = recorded_http_api_request!(
+            "update_oplog_export_sink",
+            oplog_export_sink_id = oplog_export_sink_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .update_oplog_export_sink_internal(oplog_export_sink_id.0, data.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn update_oplog_export_sink_internal(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        data: OplogExportSinkUpdate,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let result = self
+            .oplog_export_sink_service
+            .update(oplog_export_sink_id, data, &auth)
+            .await?;
+        Ok(Json(result.into()))
+    }
+
+    /// Delete oplog export sink
+    #[oai(
+        path = "/oplog-export-sinks/:oplog_export_sink_id",
+        method = "delete",
+        operation_id = "delete_oplog_export_sink"
+    )]
+    pub async fn delete_oplog_export_sink(
+        &self,
+        oplog_export_sink_id: Path<OplogExportSinkId>,
+        current_revision: Query<OplogExportSinkRevision>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let record = recorded_http_api_request!(
+            "delete_oplog_export_sink",
+            oplog_export_sink_id = oplog_export_sink_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .delete_oplog_export_sink_internal(oplog_export_sink_id.0, current_revision.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn delete_oplog_export_sink_internal(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        current_revision: OplogExportSinkRevision,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<OplogExportSinkDto>> {
+        let result = self
+            .oplog_export_sink_service
+            .delete(oplog_export_sink_id, current_revision, &auth)
+            .await?;
+        Ok(Json(result.into()))
+    }
+}
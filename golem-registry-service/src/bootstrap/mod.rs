@@ -32,6 +32,7 @@ use crate::repo::http_api_deployment::{DbHttpApiDeploymentRepo, HttpApiDeploymen
 use crate::repo::mcp_deployment::{DbMcpDeploymentRepo, McpDeploymentRepo};
 use crate::repo::oauth2_token::{DbOAuth2TokenRepo, OAuth2TokenRepo};
 use crate::repo::oauth2_webflow_state::{DbOAuth2WebflowStateRepo, OAuth2WebflowStateRepo};
+use crate::repo::oplog_export_sink::{DbOplogExportSinkRepo, OplogExportSinkRepo};
 use crate::repo::permission_share::{DbPermissionShareRepo, PermissionShareRepo};
 use crate::repo::plan::{DbPlanRepo, PlanRepo};
 use crate::repo::plugin::{DbPluginRepo, PluginRepo};
@@ -59,6 +60,7 @@ use crate::services::environment_plugin_grant::EnvironmentPluginGrantService;
 use crate::services::environment_state::EnvironmentStateService;
 use crate::services::http_api_deployment::HttpApiDeploymentService;
 use crate::services::mcp_deployment::McpDeploymentService;
+use crate::services::oplog_export_sink::OplogExportSinkService;
 use crate::services::permission_share::PermissionShareService;
 use crate::services::plan::PlanService;
 use crate::services::plugin_registration::PluginRegistrationService;
@@ -110,6 +112,7 @@ pub struct Services {
     pub http_api_deployment_service: Arc<HttpApiDeploymentService>,
     pub mcp_deployment_service: Arc<McpDeploymentService>,
     pub login_system: LoginSystem,
+    pub oplog_export_sink_service: Arc<OplogExportSinkService>,
     pub permission_share_service: Arc<PermissionShareService>,
     pub plan_service: Arc<PlanService>,
     pub plugin_registration_service: Arc<PluginRegistrationService>,
@@ -136,6 +139,7 @@ struct Repos {
     mcp_deployment_repo: Arc<dyn McpDeploymentRepo>,
     oauth2_token_repo: Arc<dyn OAuth2TokenRepo>,
     oauth2_webflow_state_repo: Arc<dyn OAuth2WebflowStateRepo>,
+    oplog_export_sink_repo: Arc<dyn OplogExportSinkRepo>,
     permission_share_repo: Arc<dyn PermissionShareRepo>,
     plan_repo: Arc<dyn PlanRepo>,
     plugin_repo: Arc<dyn PluginRepo>,
@@ -354,6 +358,12 @@ impl Services {
             registry_change_notifier.clone(),
         ));
 
+        let oplog_export_sink_service = Arc::new(OplogExportSinkService::new(
+            repos.oplog_export_sink_repo.clone(),
+            environment_service.clone(),
+            registry_change_notifier.clone(),
+        ));
+
         let deployment_write_service = Arc::new(DeploymentWriteService::new(
             environment_service.clone(),
             repos.deployment_repo.clone(),
@@ -379,6 +389,7 @@ impl Services {
             deployment_service.clone(),
             agent_secret_service.clone(),
             retry_policy_service.clone(),
+            oplog_export_sink_service.clone(),
         ));
 
         registry_change_notifier.start_background_tasks(join_set);
@@ -436,6 +447,7 @@ impl Services {
             http_api_deployment_service,
             mcp_deployment_service,
             login_system,
+            oplog_export_sink_service,
             permission_share_service,
             plan_service,
             plugin_registration_service,
@@ -491,6 +503,7 @@ async fn make_repos(
             let resource_definition_repo =
                 Arc::new(DbResourceDefinitionRepo::logged(db_pool.clone()));
             let retry_policy_repo = Arc::new(DbRetryPolicyRepo::logged(db_pool.clone()));
+            let oplog_export_sink_repo = Arc::new(DbOplogExportSinkRepo::logged(db_pool.clone()));
 
             Ok(Repos {
                 account_repo,
@@ -508,6 +521,7 @@ async fn make_repos(
                 mcp_deployment_repo,
                 oauth2_token_repo,
                 oauth2_webflow_state_repo,
+                oplog_export_sink_repo,
                 permission_share_repo,
                 plan_repo,
                 plugin_repo,
@@ -553,6 +567,7 @@ async fn make_repos(
             let resource_definition_repo =
                 Arc::new(DbResourceDefinitionRepo::logged(db_pool.clone()));
             let retry_policy_repo = Arc::new(DbRetryPolicyRepo::logged(db_pool.clone()));
+            let oplog_export_sink_repo = Arc::new(DbOplogExportSinkRepo::logged(db_pool.clone()));
 
             Ok(Repos {
                 account_repo,
@@ -570,6 +585,7 @@ async fn make_repos(
                 mcp_deployment_repo,
                 oauth2_token_repo,
                 oauth2_webflow_state_repo,
+                oplog_export_sink_repo,
                 permission_share_repo,
                 plan_repo,
                 plugin_repo,
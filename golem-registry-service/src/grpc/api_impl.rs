@@ -42,26 +42,30 @@ use golem_api_grpc::proto::golem::registry::v1::{
     GetCurrentEnvironmentStateRequest, GetCurrentEnvironmentStateResponse,
     GetCurrentEnvironmentStateSuccessResponse, GetDeployedComponentMetadataRequest,
     GetDeployedComponentMetadataResponse, GetDeployedComponentMetadataSuccessResponse,
-    GetResourceDefinitionByIdRequest, GetResourceDefinitionByIdResponse,
-    GetResourceDefinitionByIdSuccessResponse, GetResourceDefinitionByNameRequest,
-    GetResourceDefinitionByNameResponse, GetResourceDefinitionByNameSuccessResponse,
-    GetResourceLimitsRequest, GetResourceLimitsResponse, GetResourceLimitsSuccessResponse,
-    RegistryInvalidationEvent, RegistryServiceError, ResolveAgentTypeByNamesRequest,
-    ResolveAgentTypeByNamesResponse, ResolveAgentTypeByNamesSuccessResponse,
-    ResolveComponentRequest, ResolveComponentResponse, ResolveComponentSuccessResponse,
-    SubscribeRegistryInvalidationsRequest, UpdateWorkerConnectionLimitRequest,
-    UpdateWorkerConnectionLimitResponse, authenticate_token_response,
-    batch_update_resource_usage_response, download_component_response,
+    GetDeployedComponentsRequest, GetDeployedComponentsResponse,
+    GetDeployedComponentsSuccessResponse, GetResourceDefinitionByIdRequest,
+    GetResourceDefinitionByIdResponse, GetResourceDefinitionByIdSuccessResponse,
+    GetResourceDefinitionByNameRequest, GetResourceDefinitionByNameResponse,
+    GetResourceDefinitionByNameSuccessResponse, GetResourceLimitsRequest,
+    GetResourceLimitsResponse, GetResourceLimitsSuccessResponse, ListAgentTypesByNamesRequest,
+    ListAgentTypesByNamesResponse, ListAgentTypesByNamesSuccessResponse, RegistryInvalidationEvent,
+    RegistryServiceError, ResolveAgentTypeByNamesRequest, ResolveAgentTypeByNamesResponse,
+    ResolveAgentTypeByNamesSuccessResponse, ResolveComponentRequest, ResolveComponentResponse,
+    ResolveComponentSuccessResponse, SubscribeRegistryInvalidationsRequest,
+    UpdateWorkerConnectionLimitRequest, UpdateWorkerConnectionLimitResponse,
+    authenticate_token_response, batch_update_resource_usage_response, download_component_response,
     get_active_mcp_for_domain_response, get_active_routes_for_domain_response,
     get_agent_type_response, get_all_agent_types_response,
     get_all_deployed_component_revisions_response, get_component_metadata_response,
     get_current_environment_state_response, get_deployed_component_metadata_response,
-    get_resource_definition_by_id_response, get_resource_definition_by_name_response,
-    get_resource_limits_response, registry_service_error, resolve_agent_type_by_names_response,
-    resolve_component_response, update_worker_connection_limit_response,
+    get_deployed_components_response, get_resource_definition_by_id_response,
+    get_resource_definition_by_name_response, get_resource_limits_response,
+    list_agent_types_by_names_response, registry_service_error,
+    resolve_agent_type_by_names_response, resolve_component_response,
+    update_worker_connection_limit_response,
 };
 use golem_common::model::account::AccountId;
-use golem_common::model::agent::{AgentTypeName, RegisteredAgentType};
+use golem_common::model::agent::{AgentTypeName, RegisteredAgentType, ResolvedAgentType};
 use golem_common::model::application::{ApplicationId, ApplicationName};
 use golem_common::model::auth::TokenSecret;
 use golem_common::model::component::{ComponentId, ComponentRevision};
@@ -276,6 +280,25 @@ impl RegistryServiceGrpcApi {
         })
     }
 
+    async fn get_deployed_components_internal(
+        &self,
+        request: GetDeployedComponentsRequest,
+    ) -> Result<GetDeployedComponentsSuccessResponse, GrpcApiError> {
+        let environment_id: EnvironmentId = request
+            .environment_id
+            .ok_or("missing environment_id field")?
+            .try_into()?;
+
+        let components = self
+            .component_service
+            .list_deployed_components(environment_id, &AuthCtx::System)
+            .await?;
+
+        Ok(GetDeployedComponentsSuccessResponse {
+            components: components.into_iter().map(|c| c.into()).collect(),
+        })
+    }
+
     async fn resolve_component_internal(
         &self,
         request: ResolveComponentRequest,
@@ -501,11 +524,41 @@ impl RegistryServiceGrpcApi {
             )
             .await?;
 
-        Ok(ResolveAgentTypeByNamesSuccessResponse {
-            agent_type: Some(resolved.registered_agent_type.into()),
-            environment_id: Some(resolved.environment_id.into()),
-            deployment_revision: resolved.deployment_revision.get(),
-            current_deployment_revision: resolved.current_deployment_revision.map(|r| r.get()),
+        Ok(resolved_agent_type_to_grpc(resolved))
+    }
+
+    async fn list_agent_types_by_names_internal(
+        &self,
+        request: ListAgentTypesByNamesRequest,
+    ) -> Result<ListAgentTypesByNamesSuccessResponse, GrpcApiError> {
+        let auth_ctx: AuthCtx = request
+            .auth_ctx
+            .ok_or("missing auth_ctx field")?
+            .try_into()?;
+        let app_name = ApplicationName(request.app_name);
+        let environment_name = EnvironmentName(request.environment_name);
+        let deployment_revision = request
+            .deployment_revision
+            .map(DeploymentRevision::try_from)
+            .transpose()
+            .map_err(|e: String| e)?;
+
+        let resolved = self
+            .deployment_service
+            .list_agent_types_by_names(
+                &app_name,
+                &environment_name,
+                deployment_revision,
+                request.owner_account_email.as_deref(),
+                &auth_ctx,
+            )
+            .await?;
+
+        Ok(ListAgentTypesByNamesSuccessResponse {
+            agent_types: resolved
+                .into_iter()
+                .map(resolved_agent_type_to_grpc)
+                .collect(),
         })
     }
 }
@@ -732,6 +785,31 @@ impl golem_api_grpc::proto::golem::registry::v1::registry_service_server::Regist
         }))
     }
 
+    async fn get_deployed_components(
+        &self,
+        request: Request<GetDeployedComponentsRequest>,
+    ) -> Result<Response<GetDeployedComponentsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_deployed_components",
+            environment_id = EnvironmentId::render_proto(request.environment_id)
+        );
+
+        let response = match self
+            .get_deployed_components_internal(request)
+            .instrument(record.span.clone())
+            .await
+            .apply(|r| record.result(r))
+        {
+            Ok(result) => get_deployed_components_response::Result::Success(result),
+            Err(error) => get_deployed_components_response::Result::Error(error.into()),
+        };
+
+        Ok(Response::new(GetDeployedComponentsResponse {
+            result: Some(response),
+        }))
+    }
+
     async fn resolve_component(
         &self,
         request: Request<ResolveComponentRequest>,
@@ -838,6 +916,32 @@ impl golem_api_grpc::proto::golem::registry::v1::registry_service_server::Regist
         }))
     }
 
+    async fn list_agent_types_by_names(
+        &self,
+        request: Request<ListAgentTypesByNamesRequest>,
+    ) -> Result<Response<ListAgentTypesByNamesResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "list_agent_types_by_names",
+            app_name = &request.app_name,
+            environment_name = &request.environment_name,
+        );
+
+        let response = match self
+            .list_agent_types_by_names_internal(request)
+            .instrument(record.span.clone())
+            .await
+            .apply(|r| record.result(r))
+        {
+            Ok(result) => list_agent_types_by_names_response::Result::Success(result),
+            Err(error) => list_agent_types_by_names_response::Result::Error(error.into()),
+        };
+
+        Ok(Response::new(ListAgentTypesByNamesResponse {
+            result: Some(response),
+        }))
+    }
+
     async fn get_active_routes_for_domain(
         &self,
         request: Request<GetActiveRoutesForDomainRequest>,
@@ -994,3 +1098,14 @@ fn internal_error(error: &str) -> RegistryServiceError {
         )),
     }
 }
+
+fn resolved_agent_type_to_grpc(
+    resolved: ResolvedAgentType,
+) -> ResolveAgentTypeByNamesSuccessResponse {
+    ResolveAgentTypeByNamesSuccessResponse {
+        agent_type: Some(resolved.registered_agent_type.into()),
+        environment_id: Some(resolved.environment_id.into()),
+        deployment_revision: resolved.deployment_revision.get(),
+        current_deployment_revision: resolved.current_deployment_revision.map(|r| r.get()),
+    }
+}
@@ -20,8 +20,8 @@ use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::security_scheme::{SecuritySchemeId, SecuritySchemeName};
 use golem_service_base::custom_api::{
-    CorsOptions, PathSegment, RequestBodySchema, RouteBehaviour, RouteId, SecuritySchemeDetails,
-    SessionFromHeaderRouteSecurity,
+    CorsOptions, PathSegment, RequestBodySchema, RouteBehaviour, RouteId, RouteRateLimit,
+    SecuritySchemeDetails, SessionFromHeaderRouteSecurity,
 };
 use std::collections::HashMap;
 
@@ -51,6 +51,8 @@ pub struct UnboundCompiledRoute {
     pub behaviour: RouteBehaviour,
     pub security: UnboundRouteSecurity,
     pub cors: CorsOptions,
+    #[desert(default)]
+    pub rate_limits: Vec<RouteRateLimit>,
 }
 
 impl UnboundCompiledRoute {
@@ -50,7 +50,7 @@ use golem_service_base::db::sqlite::SqlitePool;
 use golem_service_base::db::{LabelledPoolApi, LabelledPoolTransaction, Pool, PoolApi};
 use golem_service_base::repo::{BindingsStack, RepoError, RepoResult, ResultExt};
 use indoc::{formatdoc, indoc};
-use sqlx::{Database, Row};
+use sqlx::{Database, Encode, Row, Type};
 use std::fmt::Debug;
 use tap::Pipe;
 use tracing::{Instrument, Span, info_span};
@@ -168,6 +168,15 @@ pub trait DeploymentRepo: Send + Sync {
         owner_email: Option<&str>,
     ) -> RepoResult<Option<ResolvedAgentTypeRecord>>;
 
+    async fn list_agent_types_by_names(
+        &self,
+        caller_account_id: Uuid,
+        app_name: &str,
+        env_name: &str,
+        deployment_revision: Option<i64>,
+        owner_email: Option<&str>,
+    ) -> RepoResult<Vec<ResolvedAgentTypeRecord>>;
+
     /// Set the current deployment and record a change event in the same transaction.
     async fn set_current_deployment(
         &self,
@@ -491,6 +500,30 @@ impl<Repo: DeploymentRepo> DeploymentRepo for LoggedDeploymentRepo<Repo> {
             .await
     }
 
+    async fn list_agent_types_by_names(
+        &self,
+        caller_account_id: Uuid,
+        app_name: &str,
+        env_name: &str,
+        deployment_revision: Option<i64>,
+        owner_email: Option<&str>,
+    ) -> RepoResult<Vec<ResolvedAgentTypeRecord>> {
+        self.repo
+            .list_agent_types_by_names(
+                caller_account_id,
+                app_name,
+                env_name,
+                deployment_revision,
+                owner_email,
+            )
+            .instrument(info_span!(
+                SPAN_NAME,
+                app_name = %app_name,
+                env_name = %env_name,
+            ))
+            .await
+    }
+
     async fn set_current_deployment(
         &self,
         user_account_id: Uuid,
@@ -1252,81 +1285,44 @@ impl DeploymentRepo for DbDeploymentRepo<PostgresPool> {
     ) -> RepoResult<Option<ResolvedAgentTypeRecord>> {
         let mut binding_stack = BindingsStack::new(5);
 
-        let owner_filter = if let Some(owner_email) = owner_email {
-            let i = binding_stack.push(owner_email);
-            format!("AND email = ${i}")
-        } else {
-            "AND account_id = $1".to_string()
-        };
+        let query = resolved_agent_types_query(
+            &mut binding_stack,
+            deployment_revision,
+            owner_email,
+            "WHERE r.agent_type_name = $4 LIMIT 1",
+        );
 
-        let deployment_revision_expr = if let Some(rev) = deployment_revision {
-            let i = binding_stack.push(rev);
-            format!("${i}")
-        } else {
-            indoc! { r#"
-                (SELECT cdr.deployment_revision_id
-                 FROM current_deployments cd
-                 JOIN current_deployment_revisions cdr
-                   ON cdr.environment_id = cd.environment_id
-                  AND cdr.revision_id = cd.current_revision_id
-                 WHERE cd.environment_id = env.environment_id)"#
-            }
-            .to_string()
+        let query_as = {
+            let binding_stack = binding_stack;
+            sqlx::query_as(&query)
+                .bind(caller_account_id) // $1
+                .bind(app_name) // $2
+                .bind(env_name) // $3
+                .bind(agent_type) // $4
+                .pipe(|q| binding_stack.apply(q))
         };
 
-        let current_deployment_revision_expr = if deployment_revision.is_some() {
-            "NULL".to_string()
-        } else {
-            indoc! { r#"
-                (SELECT cd.current_revision_id
-                 FROM current_deployments cd
-                 WHERE cd.environment_id = env.environment_id)"#
-            }
-            .to_string()
-        };
+        self.with_ro("resolve_agent_type_by_names")
+            .fetch_optional_as(query_as)
+            .await
+    }
 
-        let query = formatdoc! { r#"
-            WITH owner AS (
-              SELECT account_id
-              FROM accounts
-              WHERE deleted_at IS NULL
-                {owner_filter}
-            ),
-            env AS (
-              SELECT e.environment_id, owner.account_id AS owner_account_id, a.email AS owner_account_email
-              FROM owner
-              JOIN accounts a
-                ON a.account_id = owner.account_id
-              JOIN applications ap
-                ON ap.account_id = owner.account_id AND ap.deleted_at IS NULL
-              JOIN environments e
-                ON e.application_id = ap.application_id AND e.deleted_at IS NULL
-              WHERE ap.name = $2 AND e.name = $3
-            ),
-            target AS (
-              SELECT
-                env.environment_id,
-                env.owner_account_id,
-                env.owner_account_email,
-                {deployment_revision_expr} AS deployment_revision_id,
-                {current_deployment_revision_expr} AS current_deployment_revision_id
-              FROM env
-            )
-            SELECT
-              r.environment_id, r.deployment_revision_id, target.current_deployment_revision_id,
-              r.agent_type_name, r.canonical_agent_type_name,
-              r.component_id, c.name AS component_name, r.component_revision_id,
-              r.webhook_prefix_authority_and_path, r.agent_type,
-              target.owner_account_id, target.owner_account_email
-            FROM target
-            JOIN deployment_registered_agent_types r
-              ON r.environment_id = target.environment_id
-             AND r.deployment_revision_id = target.deployment_revision_id
-            JOIN components c
-              ON c.component_id = r.component_id
-            WHERE r.agent_type_name = $4
-            LIMIT 1
-        "#};
+    async fn list_agent_types_by_names(
+        &self,
+        caller_account_id: Uuid,
+        app_name: &str,
+        env_name: &str,
+        deployment_revision: Option<i64>,
+        owner_email: Option<&str>,
+    ) -> RepoResult<Vec<ResolvedAgentTypeRecord>> {
+        let mut binding_stack = BindingsStack::new(4);
+
+        let query = resolved_agent_types_query(
+            &mut binding_stack,
+            deployment_revision,
+            owner_email,
+            "ORDER BY r.agent_type_name",
+        );
 
         let query_as = {
             let binding_stack = binding_stack;
@@ -1334,12 +1330,11 @@ impl DeploymentRepo for DbDeploymentRepo<PostgresPool> {
                 .bind(caller_account_id) // $1
                 .bind(app_name) // $2
                 .bind(env_name) // $3
-                .bind(agent_type) // $4
                 .pipe(|q| binding_stack.apply(q))
         };
 
-        self.with_ro("resolve_agent_type_by_names")
-            .fetch_optional_as(query_as)
+        self.with_ro("list_agent_types_by_names")
+            .fetch_all_as(query_as)
             .await
     }
 
@@ -1953,3 +1948,91 @@ impl DeploymentRepoInternal for DbDeploymentRepo<PostgresPool> {
             .is_some())
     }
 }
+
+// Expects $1 to be bound to the caller account id, $2 to the application name and $3 to the
+// environment name; `filter` is appended after the joins.
+fn resolved_agent_types_query<'q, DB: Database, R>(
+    binding_stack: &mut BindingsStack<'q, DB, R>,
+    deployment_revision: Option<i64>,
+    owner_email: Option<&'q str>,
+    filter: &str,
+) -> String
+where
+    &'q str: Encode<'q, DB> + Type<DB>,
+    i64: Encode<'q, DB> + Type<DB>,
+{
+    let owner_filter = if let Some(owner_email) = owner_email {
+        let i = binding_stack.push(owner_email);
+        format!("AND email = ${i}")
+    } else {
+        "AND account_id = $1".to_string()
+    };
+
+    let deployment_revision_expr = if let Some(rev) = deployment_revision {
+        let i = binding_stack.push(rev);
+        format!("${i}")
+    } else {
+        indoc! { r#"
+            (SELECT cdr.deployment_revision_id
+             FROM current_deployments cd
+             JOIN current_deployment_revisions cdr
+               ON cdr.environment_id = cd.environment_id
+              AND cdr.revision_id = cd.current_revision_id
+             WHERE cd.environment_id = env.environment_id)"#
+        }
+        .to_string()
+    };
+
+    let current_deployment_revision_expr = if deployment_revision.is_some() {
+        "NULL".to_string()
+    } else {
+        indoc! { r#"
+            (SELECT cd.current_revision_id
+             FROM current_deployments cd
+             WHERE cd.environment_id = env.environment_id)"#
+        }
+        .to_string()
+    };
+
+    formatdoc! { r#"
+        WITH owner AS (
+          SELECT account_id
+          FROM accounts
+          WHERE deleted_at IS NULL
+            {owner_filter}
+        ),
+        env AS (
+          SELECT e.environment_id, owner.account_id AS owner_account_id, a.email AS owner_account_email
+          FROM owner
+          JOIN accounts a
+            ON a.account_id = owner.account_id
+          JOIN applications ap
+            ON ap.account_id = owner.account_id AND ap.deleted_at IS NULL
+          JOIN environments e
+            ON e.application_id = ap.application_id AND e.deleted_at IS NULL
+          WHERE ap.name = $2 AND e.name = $3
+        ),
+        target AS (
+          SELECT
+            env.environment_id,
+            env.owner_account_id,
+            env.owner_account_email,
+            {deployment_revision_expr} AS deployment_revision_id,
+            {current_deployment_revision_expr} AS current_deployment_revision_id
+          FROM env
+        )
+        SELECT
+          r.environment_id, r.deployment_revision_id, target.current_deployment_revision_id,
+          r.agent_type_name, r.canonical_agent_type_name,
+          r.component_id, c.name AS component_name, r.component_revision_id,
+          r.webhook_prefix_authority_and_path, r.agent_type,
+          target.owner_account_id, target.owner_account_email
+        FROM target
+        JOIN deployment_registered_agent_types r
+          ON r.environment_id = target.environment_id
+         AND r.deployment_revision_id = target.deployment_revision_id
+        JOIN components c
+          ON c.component_id = r.component_id
+        {filter}
+    "#}
+}
@@ -205,6 +205,7 @@ impl ComponentRevisionRecord {
                                     )
                                 })
                                 .collect(),
+                            lifecycle: config.lifecycle.clone(),
                         };
                     Ok((name.0.clone(), state.into()))
                 })
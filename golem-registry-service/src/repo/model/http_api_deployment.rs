@@ -26,7 +26,7 @@ use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::http_api_deployment::{
     HttpApiDeployment, HttpApiDeploymentAgentOptions, HttpApiDeploymentId,
-    HttpApiDeploymentRevision,
+    HttpApiDeploymentRevision, HttpApiRateLimit,
 };
 use golem_service_base::repo::Blob;
 use golem_service_base::repo::RepoError;
@@ -53,6 +53,10 @@ pub struct HttpApiDeploymentData {
     pub webhooks_prefix: String,
     pub openapi_endpoint_prefix: String,
     pub agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+    #[desert(default)]
+    pub rate_limits: Vec<HttpApiRateLimit>,
+    #[desert(default)]
+    pub graphql_endpoint: Option<String>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -96,6 +100,8 @@ impl HttpApiDeploymentRevisionRecord {
         webhooks_prefix: String,
         openapi_endpoint_prefix: String,
         agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+        rate_limits: Vec<HttpApiRateLimit>,
+        graphql_endpoint: Option<String>,
         actor: AccountId,
     ) -> Result<Self, HttpApiDeploymentRepoError> {
         let mut value = Self {
@@ -107,6 +113,8 @@ impl HttpApiDeploymentRevisionRecord {
                 webhooks_prefix,
                 openapi_endpoint_prefix,
                 agents,
+                rate_limits,
+                graphql_endpoint,
             }),
         };
         value.update_hash()?;
@@ -126,6 +134,8 @@ impl HttpApiDeploymentRevisionRecord {
                 webhooks_prefix: value.webhooks_prefix,
                 openapi_endpoint_prefix: value.openapi_endpoint_prefix,
                 agents: value.agents,
+                rate_limits: value.rate_limits,
+                graphql_endpoint: value.graphql_endpoint,
             }),
         };
         value.update_hash()?;
@@ -146,6 +156,8 @@ impl HttpApiDeploymentRevisionRecord {
                 webhooks_prefix: "".to_string(),
                 openapi_endpoint_prefix: "".to_string(),
                 agents: BTreeMap::new(),
+                rate_limits: Vec::new(),
+                graphql_endpoint: None,
             }),
         };
         value.update_hash()?;
@@ -163,6 +175,8 @@ impl HttpApiDeploymentRevisionRecord {
                 .iter()
                 .map(|(k, v)| (k.0.clone(), v.to_diffable()))
                 .collect(),
+            rate_limits: self.data.value().rate_limits.clone(),
+            graphql_endpoint: self.data.value().graphql_endpoint.clone(),
         }
     }
 
@@ -216,6 +230,8 @@ impl TryFrom<HttpApiDeploymentExtRevisionRecord> for HttpApiDeployment {
             webhooks_prefix: data.webhooks_prefix,
             openapi_endpoint_prefix: data.openapi_endpoint_prefix,
             agents: data.agents,
+            rate_limits: data.rate_limits,
+            graphql_endpoint: data.graphql_endpoint,
             created_at: value.entity_created_at.into(),
         })
     }
@@ -0,0 +1,141 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repo::model::audit::{AuditFields, DeletableRevisionAuditFields};
+use golem_common::error_forwarding;
+use golem_common::model::account::AccountId;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog_export::{OplogExportSinkId, OplogExportSinkRevision};
+use golem_service_base::model::oplog_export_sink::StoredOplogExportSink;
+use golem_service_base::repo::RepoError;
+use golem_service_base::repo::SqlDateTime;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OplogExportSinkRepoError {
+    #[error("There is already an oplog export sink with this name in this environment")]
+    NameViolatesUniqueness,
+    #[error("Concurrent modification")]
+    ConcurrentModification,
+    #[error(transparent)]
+    InternalError(#[from] anyhow::Error),
+}
+
+error_forwarding!(OplogExportSinkRepoError, RepoError);
+
+#[derive(Debug, Clone)]
+pub struct OplogExportSinkCreationRecord {
+    pub environment_id: Uuid,
+    pub name: String,
+    pub revision: OplogExportSinkRevisionRecord,
+}
+
+impl OplogExportSinkCreationRecord {
+    pub fn new(
+        id: OplogExportSinkId,
+        environment_id: EnvironmentId,
+        name: String,
+        target_json: String,
+        actor: AccountId,
+    ) -> Self {
+        Self {
+            environment_id: environment_id.0,
+            name,
+            revision: OplogExportSinkRevisionRecord {
+                oplog_export_sink_id: id.0,
+                revision_id: OplogExportSinkRevision::INITIAL.into(),
+                target_json,
+                audit: DeletableRevisionAuditFields::new(actor.0),
+            },
+        }
+    }
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub struct OplogExportSinkRecord {
+    pub oplog_export_sink_id: Uuid,
+    pub environment_id: Uuid,
+    pub name: String,
+
+    #[sqlx(flatten)]
+    pub audit: AuditFields,
+
+    pub current_revision_id: i64,
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub struct OplogExportSinkRevisionRecord {
+    pub oplog_export_sink_id: Uuid,
+    pub revision_id: i64,
+
+    pub target_json: String,
+
+    #[sqlx(flatten)]
+    pub audit: DeletableRevisionAuditFields,
+}
+
+impl OplogExportSinkRevisionRecord {
+    pub fn from_model(
+        value: StoredOplogExportSink,
+        audit: DeletableRevisionAuditFields,
+    ) -> Result<Self, OplogExportSinkRepoError> {
+        Ok(Self {
+            oplog_export_sink_id: value.id.0,
+            revision_id: value.revision.into(),
+            target_json: serde_json::to_string(&value.target)
+                .map_err(|err| OplogExportSinkRepoError::InternalError(err.into()))?,
+            audit,
+        })
+    }
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub struct OplogExportSinkExtRevisionRecord {
+    pub environment_id: Uuid,
+    pub name: String,
+
+    pub entity_created_at: SqlDateTime,
+
+    #[sqlx(flatten)]
+    pub revision: OplogExportSinkRevisionRecord,
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub struct OplogExportSinkAuthExtRevisionRecord {
+    #[sqlx(flatten)]
+    pub oplog_export_sink: OplogExportSinkExtRevisionRecord,
+
+    pub environment_name: String,
+    pub application_name: String,
+    pub owner_account_email: String,
+}
+
+impl TryFrom<OplogExportSinkExtRevisionRecord> for StoredOplogExportSink {
+    type Error = OplogExportSinkRepoError;
+    fn try_from(value: OplogExportSinkExtRevisionRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: OplogExportSinkId(value.revision.oplog_export_sink_id),
+            environment_id: EnvironmentId(value.environment_id),
+            name: value.name,
+            revision: value.revision.revision_id.try_into()?,
+            target: serde_json::from_str(&value.revision.target_json).map_err(|err| {
+                OplogExportSinkRepoError::InternalError(anyhow::anyhow!(
+                    "Invalid target JSON for oplog export sink '{}': {err}",
+                    value.revision.oplog_export_sink_id
+                ))
+            })?,
+        })
+    }
+}
@@ -17,13 +17,16 @@ use super::hash::SqlBlake3Hash;
 use anyhow::anyhow;
 use golem_common::model::account::{AccountEmail, AccountId};
 use golem_common::model::component::ComponentId;
-use golem_common::model::plugin_registration::{OplogProcessorPluginSpec, PluginRegistrationId};
+use golem_common::model::plugin_registration::{
+    InvocationInterceptorPluginSpec, OplogProcessorPluginSpec, PluginRegistrationId,
+};
 use golem_service_base::model::plugin_registration::{PluginRegistration, PluginSpec};
 use sqlx::FromRow;
 use sqlx::types::Json;
 use uuid::Uuid;
 
 const OPLOG_PROCESSOR_PLUGIN_TYPE: i16 = 3;
+const INVOCATION_INTERCEPTOR_PLUGIN_TYPE: i16 = 4;
 
 #[derive(Debug, Clone, PartialEq, FromRow)]
 pub struct PluginRecord {
@@ -47,7 +50,7 @@ pub struct PluginRecord {
     pub validate_url: Option<String>,
     pub transform_url: Option<String>,
 
-    // for OplogProcessor plugin type
+    // for OplogProcessor and InvocationInterceptor plugin types
     pub component_id: Option<Uuid>,
     pub component_revision_id: Option<i64>,
 
@@ -89,6 +92,24 @@ impl PluginRecord {
                 component_revision_id: Some(inner.component_revision.into()),
                 wasm_content_hash: None,
             },
+            PluginSpec::InvocationInterceptor(inner) => Self {
+                plugin_id: model.id.0,
+                account_id: model.account_id.0,
+                name: model.name,
+                version: model.version,
+                audit,
+                description: model.description,
+                icon: model.icon,
+                homepage: model.homepage,
+                plugin_type: INVOCATION_INTERCEPTOR_PLUGIN_TYPE,
+                provided_wit_package: None,
+                json_schema: None,
+                validate_url: None,
+                transform_url: None,
+                component_id: Some(inner.component_id.0),
+                component_revision_id: Some(inner.component_revision.into()),
+                wasm_content_hash: None,
+            },
         }
     }
 }
@@ -116,6 +137,24 @@ impl TryFrom<PluginRecord> for PluginRegistration {
                         .try_into()?,
                 }),
             }),
+            INVOCATION_INTERCEPTOR_PLUGIN_TYPE => Ok(Self {
+                id: PluginRegistrationId(value.plugin_id),
+                account_id: AccountId(value.account_id),
+                name: value.name,
+                version: value.version,
+                description: value.description,
+                icon: value.icon,
+                homepage: value.homepage,
+                spec: PluginSpec::InvocationInterceptor(InvocationInterceptorPluginSpec {
+                    component_id: ComponentId(
+                        value.component_id.ok_or(anyhow!("no component_id field"))?,
+                    ),
+                    component_revision: value
+                        .component_revision_id
+                        .ok_or(anyhow!("no component_revision field"))?
+                        .try_into()?,
+                }),
+            }),
             other => Err(anyhow!("Unknown plugin type {other}"))?,
         }
     }
@@ -0,0 +1,396 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::model::oplog_export_sink::{
+    OplogExportSinkAuthExtRevisionRecord, OplogExportSinkCreationRecord,
+    OplogExportSinkExtRevisionRecord, OplogExportSinkRepoError, OplogExportSinkRevisionRecord,
+};
+use super::registry_change::{
+    DbRegistryChangeRepo, NewRegistryChangeEvent, RequiresNotificationSignal, RequiresSignalExt,
+};
+use crate::repo::model::BindFields;
+use crate::repo::model::oplog_export_sink::OplogExportSinkRecord;
+use async_trait::async_trait;
+use conditional_trait_gen::trait_gen;
+use futures::FutureExt;
+use golem_service_base::db::postgres::PostgresPool;
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_service_base::db::{LabelledPoolApi, Pool, PoolApi};
+use golem_service_base::repo::{PoolLabelledTransaction, ResultExt};
+use indoc::indoc;
+use tracing::{Instrument, Span, info_span};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait OplogExportSinkRepo: Send + Sync {
+    async fn create(
+        &self,
+        record: OplogExportSinkCreationRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    >;
+
+    async fn update(
+        &self,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    >;
+
+    async fn delete(
+        &self,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    >;
+
+    async fn get_by_id(
+        &self,
+        oplog_export_sink_id: Uuid,
+    ) -> Result<Option<OplogExportSinkAuthExtRevisionRecord>, OplogExportSinkRepoError>;
+
+    async fn get_for_environment(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Vec<OplogExportSinkExtRevisionRecord>, OplogExportSinkRepoError>;
+}
+
+pub struct LoggedOplogExportSinkRepo<Repo: OplogExportSinkRepo> {
+    repo: Repo,
+}
+
+static SPAN_NAME: &str = "oplog export sink repository";
+
+impl<Repo: OplogExportSinkRepo> LoggedOplogExportSinkRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn span_environment_id(environment_id: Uuid) -> Span {
+        info_span!(SPAN_NAME, environment_id=%environment_id)
+    }
+
+    fn span_oplog_export_sink_id(oplog_export_sink_id: Uuid) -> Span {
+        info_span!(SPAN_NAME, oplog_export_sink_id=%oplog_export_sink_id)
+    }
+}
+
+#[async_trait]
+impl<Repo: OplogExportSinkRepo> OplogExportSinkRepo for LoggedOplogExportSinkRepo<Repo> {
+    async fn create(
+        &self,
+        record: OplogExportSinkCreationRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    > {
+        let span = Self::span_environment_id(record.environment_id);
+        self.repo.create(record).instrument(span).await
+    }
+
+    async fn update(
+        &self,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    > {
+        let span = Self::span_oplog_export_sink_id(revision.oplog_export_sink_id);
+        self.repo.update(revision).instrument(span).await
+    }
+
+    async fn delete(
+        &self,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    > {
+        let span = Self::span_oplog_export_sink_id(revision.oplog_export_sink_id);
+        self.repo.delete(revision).instrument(span).await
+    }
+
+    async fn get_by_id(
+        &self,
+        oplog_export_sink_id: Uuid,
+    ) -> Result<Option<OplogExportSinkAuthExtRevisionRecord>, OplogExportSinkRepoError> {
+        self.repo
+            .get_by_id(oplog_export_sink_id)
+            .instrument(Self::span_oplog_export_sink_id(oplog_export_sink_id))
+            .await
+    }
+
+    async fn get_for_environment(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Vec<OplogExportSinkExtRevisionRecord>, OplogExportSinkRepoError> {
+        self.repo
+            .get_for_environment(environment_id)
+            .instrument(Self::span_environment_id(environment_id))
+            .await
+    }
+}
+
+pub struct DbOplogExportSinkRepo<DBP: Pool> {
+    db_pool: DBP,
+}
+
+static METRICS_SVC_NAME: &str = "oplog-export-sink";
+
+impl<DBP: Pool> DbOplogExportSinkRepo<DBP> {
+    pub fn new(db_pool: DBP) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn logged(db_pool: DBP) -> LoggedOplogExportSinkRepo<Self>
+    where
+        Self: OplogExportSinkRepo,
+    {
+        LoggedOplogExportSinkRepo::new(Self::new(db_pool))
+    }
+
+    fn with_ro(&self, api_name: &'static str) -> DBP::LabelledApi {
+        self.db_pool.with_ro(METRICS_SVC_NAME, api_name)
+    }
+}
+
+#[trait_gen(PostgresPool -> PostgresPool, SqlitePool)]
+impl DbOplogExportSinkRepo<PostgresPool> {
+    async fn insert_revision(
+        tx: &mut PoolLabelledTransaction<PostgresPool>,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<OplogExportSinkRevisionRecord, OplogExportSinkRepoError> {
+        let revision: OplogExportSinkRevisionRecord = tx
+            .fetch_one_as(
+                sqlx::query_as(indoc! { r#"
+                    INSERT INTO oplog_export_sink_revisions
+                    (oplog_export_sink_id, revision_id, target_json, created_at, created_by, deleted)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    RETURNING oplog_export_sink_id, revision_id, target_json, created_at, created_by, deleted
+                "# })
+                .bind(revision.oplog_export_sink_id)
+                .bind(revision.revision_id)
+                .bind(revision.target_json)
+                .bind_deletable_revision_audit(revision.audit),
+            )
+            .await
+            .to_error_on_unique_violation(OplogExportSinkRepoError::ConcurrentModification)?;
+
+        Ok(revision)
+    }
+
+    pub async fn create_within_transaction(
+        tx: &mut PoolLabelledTransaction<PostgresPool>,
+        record: OplogExportSinkCreationRecord,
+    ) -> Result<OplogExportSinkExtRevisionRecord, OplogExportSinkRepoError> {
+        let sink_record: OplogExportSinkRecord = tx
+            .fetch_one_as(
+                sqlx::query_as(indoc! {r#"
+                    INSERT INTO oplog_export_sinks (oplog_export_sink_id, environment_id, name, created_at, updated_at, deleted_at, modified_by, current_revision_id)
+                    VALUES ($1, $2, $3, $4, $4, NULL, $5, $6)
+                    RETURNING oplog_export_sink_id, environment_id, name, created_at, updated_at, deleted_at, modified_by, current_revision_id
+                "#})
+                    .bind(record.revision.oplog_export_sink_id)
+                    .bind(record.environment_id)
+                    .bind(&record.name)
+                    .bind(&record.revision.audit.created_at)
+                    .bind(record.revision.audit.created_by)
+                    .bind(record.revision.revision_id)
+            )
+            .await
+            .to_error_on_unique_violation(OplogExportSinkRepoError::NameViolatesUniqueness)?;
+
+        let revision = Self::insert_revision(tx, record.revision).await?;
+
+        let change_event =
+            NewRegistryChangeEvent::oplog_export_sink_changed(sink_record.environment_id);
+        DbRegistryChangeRepo::<PostgresPool>::create_change_event_in_tx(tx, &change_event).await?;
+
+        Ok(OplogExportSinkExtRevisionRecord {
+            environment_id: sink_record.environment_id,
+            name: sink_record.name,
+            entity_created_at: sink_record.audit.created_at,
+            revision,
+        })
+    }
+
+    pub async fn update_within_transaction(
+        tx: &mut <<PostgresPool as Pool>::LabelledApi as LabelledPoolApi>::LabelledTransaction,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<OplogExportSinkExtRevisionRecord, OplogExportSinkRepoError> {
+        let revision = Self::insert_revision(tx, revision).await?;
+
+        let sink_record: OplogExportSinkRecord = tx
+            .fetch_optional_as(
+                sqlx::query_as(indoc! {r#"
+                    UPDATE oplog_export_sinks
+                    SET updated_at = $1, modified_by = $2, current_revision_id = $3
+                    WHERE oplog_export_sink_id = $4
+                    RETURNING oplog_export_sink_id, environment_id, name, created_at, updated_at, deleted_at, modified_by, current_revision_id
+                "#})
+                    .bind(&revision.audit.created_at)
+                    .bind(revision.audit.created_by)
+                    .bind(revision.revision_id)
+                    .bind(revision.oplog_export_sink_id)
+            ).await?
+            .ok_or(OplogExportSinkRepoError::ConcurrentModification)?;
+
+        let change_event =
+            NewRegistryChangeEvent::oplog_export_sink_changed(sink_record.environment_id);
+        DbRegistryChangeRepo::<PostgresPool>::create_change_event_in_tx(tx, &change_event).await?;
+
+        Ok(OplogExportSinkExtRevisionRecord {
+            environment_id: sink_record.environment_id,
+            name: sink_record.name,
+            entity_created_at: sink_record.audit.created_at,
+            revision,
+        })
+    }
+}
+
+#[trait_gen(PostgresPool -> PostgresPool, SqlitePool)]
+#[async_trait]
+impl OplogExportSinkRepo for DbOplogExportSinkRepo<PostgresPool> {
+    async fn create(
+        &self,
+        record: OplogExportSinkCreationRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    > {
+        let result = self
+            .db_pool
+            .with_tx_err(METRICS_SVC_NAME, "create", |tx| {
+                Self::create_within_transaction(tx, record).boxed()
+            })
+            .await?;
+
+        Ok(result.requires_notification_signal())
+    }
+
+    async fn update(
+        &self,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    > {
+        let result = self
+            .db_pool
+            .with_tx_err(METRICS_SVC_NAME, "update", |tx| {
+                Self::update_within_transaction(tx, revision).boxed()
+            })
+            .await?;
+
+        Ok(result.requires_notification_signal())
+    }
+
+    async fn delete(
+        &self,
+        revision: OplogExportSinkRevisionRecord,
+    ) -> Result<
+        RequiresNotificationSignal<OplogExportSinkExtRevisionRecord>,
+        OplogExportSinkRepoError,
+    > {
+        let result = self.db_pool.with_tx_err(METRICS_SVC_NAME, "update", |tx| {
+            async move {
+                let revision = Self::insert_revision(tx, revision.clone()).await?;
+
+                let sink_record: OplogExportSinkRecord = tx
+                    .fetch_optional_as(
+                        sqlx::query_as(indoc! {r#"
+                            UPDATE oplog_export_sinks
+                            SET updated_at = $1, deleted_at = $1, modified_by = $2, current_revision_id = $3
+                            WHERE oplog_export_sink_id = $4
+                            RETURNING oplog_export_sink_id, environment_id, name, created_at, updated_at, deleted_at, modified_by, current_revision_id
+                        "#})
+                            .bind(&revision.audit.created_at)
+                            .bind(revision.audit.created_by)
+                            .bind(revision.revision_id)
+                            .bind(revision.oplog_export_sink_id)
+                    ).await?
+                    .ok_or(OplogExportSinkRepoError::ConcurrentModification)?;
+
+                let change_event =
+                    NewRegistryChangeEvent::oplog_export_sink_changed(sink_record.environment_id);
+                DbRegistryChangeRepo::<PostgresPool>::create_change_event_in_tx(tx, &change_event)
+                    .await?;
+
+                Ok::<_, OplogExportSinkRepoError>(OplogExportSinkExtRevisionRecord {
+                    environment_id: sink_record.environment_id,
+                    name: sink_record.name,
+                    entity_created_at: sink_record.audit.created_at,
+                    revision
+                })
+            }.boxed()
+        }).await?;
+
+        Ok(result.requires_notification_signal())
+    }
+
+    async fn get_by_id(
+        &self,
+        oplog_export_sink_id: Uuid,
+    ) -> Result<Option<OplogExportSinkAuthExtRevisionRecord>, OplogExportSinkRepoError> {
+        let result: Option<OplogExportSinkAuthExtRevisionRecord> = self.with_ro("get_by_id")
+            .fetch_optional_as(
+                sqlx::query_as(indoc! {r#"
+                    SELECT s.environment_id, s.name, s.created_at AS entity_created_at, rev.oplog_export_sink_id, rev.revision_id, rev.target_json, rev.created_at, rev.created_by, rev.deleted,
+                        er.name AS environment_name,
+                        ap.name AS application_name,
+                        a.email AS owner_account_email
+                    FROM oplog_export_sinks s
+                    JOIN environments e ON e.environment_id = s.environment_id
+                    JOIN environment_revisions er
+                        ON er.environment_id = e.environment_id
+                        AND er.revision_id = e.current_revision_id
+                    JOIN applications ap ON ap.application_id = e.application_id
+                    JOIN accounts a ON a.account_id = ap.account_id
+                    JOIN oplog_export_sink_revisions rev ON rev.oplog_export_sink_id = s.oplog_export_sink_id AND rev.revision_id = s.current_revision_id
+                    WHERE s.oplog_export_sink_id = $1
+                        AND s.deleted_at IS NULL
+                        AND e.deleted_at IS NULL
+                        AND ap.deleted_at IS NULL
+                        AND a.deleted_at IS NULL
+                "#})
+                    .bind(oplog_export_sink_id),
+            )
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn get_for_environment(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Vec<OplogExportSinkExtRevisionRecord>, OplogExportSinkRepoError> {
+        let results: Vec<OplogExportSinkExtRevisionRecord> = self.with_ro("get_for_environment")
+            .fetch_all_as(
+                sqlx::query_as(indoc! {r#"
+                    SELECT s.environment_id, s.name, s.created_at AS entity_created_at, rev.oplog_export_sink_id, rev.revision_id, rev.target_json, rev.created_at, rev.created_by, rev.deleted
+                    FROM oplog_export_sinks s
+                    JOIN oplog_export_sink_revisions rev ON rev.oplog_export_sink_id = s.oplog_export_sink_id AND rev.revision_id = s.current_revision_id
+                    WHERE s.environment_id = $1 AND s.deleted_at IS NULL
+                    ORDER BY s.name ASC
+                "#})
+                    .bind(environment_id),
+            )
+            .await?;
+
+        Ok(results)
+    }
+}
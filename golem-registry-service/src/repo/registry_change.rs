@@ -67,6 +67,7 @@ pub enum RegistryEventType {
     ApplicationDeleted = 8,
     EnvironmentDeleted = 9,
     CardRevoked = 10,
+    OplogExportSinkChanged = 11,
 }
 
 impl TryFrom<i16> for RegistryEventType {
@@ -85,6 +86,7 @@ impl TryFrom<i16> for RegistryEventType {
             8 => Ok(RegistryEventType::ApplicationDeleted),
             9 => Ok(RegistryEventType::EnvironmentDeleted),
             10 => Ok(RegistryEventType::CardRevoked),
+            11 => Ok(RegistryEventType::OplogExportSinkChanged),
             other => Err(RepoError::InternalError(anyhow::anyhow!(
                 "Unknown registry event type: {other}"
             ))),
@@ -156,6 +158,10 @@ pub enum RegistryChangeEvent {
         event_id: ChangeEventId,
         card_ids: Vec<Uuid>,
     },
+    OplogExportSinkChanged {
+        event_id: ChangeEventId,
+        environment_id: Uuid,
+    },
 }
 
 impl RegistryChangeEvent {
@@ -172,6 +178,7 @@ impl RegistryChangeEvent {
             Self::ApplicationDeleted { event_id, .. } => *event_id,
             Self::EnvironmentDeleted { event_id, .. } => *event_id,
             Self::CardRevoked { event_id, .. } => *event_id,
+            Self::OplogExportSinkChanged { event_id, .. } => *event_id,
         }
     }
 }
@@ -325,6 +332,17 @@ impl TryFrom<RegistryChangeEventRow> for RegistryChangeEvent {
                     environment_id,
                 })
             }
+            RegistryEventType::OplogExportSinkChanged => {
+                let environment_id = row.environment_id.ok_or_else(|| {
+                    RepoError::InternalError(anyhow::anyhow!(
+                        "OplogExportSinkChanged event missing environment_id"
+                    ))
+                })?;
+                Ok(RegistryChangeEvent::OplogExportSinkChanged {
+                    event_id: row.event_id,
+                    environment_id,
+                })
+            }
             RegistryEventType::ApplicationDeleted => {
                 let application_id = row.application_id.ok_or_else(|| {
                     RepoError::InternalError(anyhow::anyhow!(
@@ -590,6 +608,25 @@ impl NewRegistryChangeEvent {
         }
     }
 
+    pub fn oplog_export_sink_changed(environment_id: Uuid) -> Self {
+        Self {
+            event_type: RegistryEventType::OplogExportSinkChanged,
+            environment_id: Some(environment_id),
+            deployment_revision_id: None,
+            current_deployment_revision_id: None,
+            account_id: None,
+            grantee_account_id: None,
+            domains: Vec::new(),
+            resource_definition_id: None,
+            resource_name: None,
+            application_id: None,
+            app_name: None,
+            environment_ids: Vec::new(),
+            env_name: None,
+            card_ids: Vec::new(),
+        }
+    }
+
     pub fn application_deleted(
         application_id: Uuid,
         account_id: Uuid,
@@ -1232,6 +1269,23 @@ mod tests {
                 env_name: None,
                 card_ids: Vec::new(),
             },
+            RegistryChangeEventRow {
+                event_id,
+                event_type: RegistryEventType::OplogExportSinkChanged,
+                environment_id: Some(environment_id),
+                deployment_revision_id: None,
+                current_deployment_revision_id: None,
+                account_id: None,
+                grantee_account_id: None,
+                domains: Vec::new(),
+                resource_definition_id: None,
+                resource_name: None,
+                application_id: None,
+                app_name: None,
+                environment_ids: Vec::new(),
+                env_name: None,
+                card_ids: Vec::new(),
+            },
             RegistryChangeEventRow {
                 event_id,
                 event_type: RegistryEventType::ApplicationDeleted,
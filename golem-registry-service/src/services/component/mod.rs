@@ -249,6 +249,55 @@ impl ComponentService {
             .collect())
     }
 
+    pub async fn list_deployed_components(
+        &self,
+        environment_id: EnvironmentId,
+        auth: &AuthCtx,
+    ) -> Result<Vec<Component>, ComponentError> {
+        info!(environment_id = %environment_id, "Get deployed components");
+
+        let environment = self
+            .environment_service
+            .get(environment_id, false, auth)
+            .await
+            .map_err(|err| match err {
+                EnvironmentError::EnvironmentNotFound(environment_id) => {
+                    ComponentError::ParentEnvironmentNotFound(environment_id)
+                }
+                other => other.into(),
+            })?;
+
+        let result: Vec<Component> = self
+            .component_repo
+            .list_deployed(environment.id.0)
+            .await?
+            .into_iter()
+            .map(|r| {
+                r.try_into_model(
+                    environment.application_id,
+                    environment.owner_account_id,
+                    environment.owner_account_email.clone(),
+                    environment.application_name.clone(),
+                    environment.name.clone(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(result
+            .into_iter()
+            .filter(|component: &Component| {
+                authorize_component_permission(
+                    auth,
+                    &environment,
+                    &component.component_name,
+                    ComponentVerb::View,
+                    ComponentResourcePattern::Any,
+                )
+                .is_ok()
+            })
+            .collect())
+    }
+
     pub async fn get_staged_component_by_name(
         &self,
         environment_id: EnvironmentId,
@@ -206,6 +206,10 @@ impl ComponentWriteService {
                     config,
                     plugins,
                     files,
+                    lifecycle: creation
+                        .lifecycle
+                        .clone()
+                        .filter(|policy| !policy.is_disabled()),
                 },
             );
         }
@@ -674,6 +678,12 @@ impl ComponentWriteService {
                         oplog_processor_component_revision: environment_plugin_grant
                             .plugin
                             .oplog_processor_component_revision(),
+                        invocation_interceptor_component_id: environment_plugin_grant
+                            .plugin
+                            .invocation_interceptor_component_id(),
+                        invocation_interceptor_component_revision: environment_plugin_grant
+                            .plugin
+                            .invocation_interceptor_component_revision(),
                         plugin_name: environment_plugin_grant.plugin.name,
                         plugin_version: environment_plugin_grant.plugin.version,
                     });
@@ -754,11 +764,18 @@ impl ComponentWriteService {
             .update_plugin_installations(environment, existing.plugins, update.plugin_updates, auth)
             .await?;
 
+        // Lifecycle policy: an empty policy removes the existing one
+        let lifecycle = match update.lifecycle {
+            Some(policy) => Some(policy).filter(|policy| !policy.is_disabled()),
+            None => existing.lifecycle,
+        };
+
         Ok(AgentTypeProvisionConfig {
             env,
             config,
             plugins,
             files,
+            lifecycle,
         })
     }
 }
@@ -827,6 +844,10 @@ fn resolve_plugins_for_creation(
             plugin_registration_id: grant.plugin.id,
             oplog_processor_component_id: grant.plugin.oplog_processor_component_id(),
             oplog_processor_component_revision: grant.plugin.oplog_processor_component_revision(),
+            invocation_interceptor_component_id: grant.plugin.invocation_interceptor_component_id(),
+            invocation_interceptor_component_revision: grant
+                .plugin
+                .invocation_interceptor_component_revision(),
             plugin_name: grant.plugin.name.clone(),
             plugin_version: grant.plugin.version.clone(),
         });
@@ -97,6 +97,16 @@ pub enum DeployValidationError {
         path: Vec<PathSegment>,
         error: String,
     },
+    #[error("Http api deployment {domain} has an invalid rate limit: {error}")]
+    HttpApiDeploymentInvalidRateLimit { domain: Domain, error: String },
+    #[error(
+        "Http api deployment {domain} declares rate limits for method {method_name} which agent type {agent_type} does not have"
+    )]
+    HttpApiDeploymentRateLimitUnknownMethod {
+        domain: Domain,
+        agent_type: AgentTypeName,
+        method_name: String,
+    },
     #[error("Overriding security scheme is only allowed if the environment level option is set")]
     SecurityOverrideDisabled,
     #[error("Http api for domain {domain} has multiple routes for pattern {rendered_method} {rendered_path}", rendered_method = render_http_method(method), rendered_path = itertools::join(path.iter().map(|p| p.to_string()), "/"))]
@@ -17,9 +17,9 @@ use super::DeploymentWriteError;
 use super::http_parameter_conversion::build_http_agent_constructor_parameters;
 use super::ok_or_continue;
 use super::route_compilation::{
-    add_agent_method_http_routes, add_cors_preflight_http_routes, add_openapi_spec_routes,
-    add_webhook_callback_routes, build_agent_http_api_deployment_details,
-    make_invalid_agent_mount_error_maker,
+    add_agent_method_http_routes, add_cors_preflight_http_routes, add_graphql_routes,
+    add_openapi_spec_routes, add_webhook_callback_routes, build_agent_http_api_deployment_details,
+    make_invalid_agent_mount_error_maker, validate_agent_rate_limits, validate_rate_limits,
 };
 use crate::model::agent_secret::{
     DeploymentAgentSecretCreation, DeploymentAgentSecretReplacement, DeploymentAgentSecretUpdate,
@@ -150,6 +150,9 @@ impl DeploymentContext {
 
         for deployment in self.http_api_deployments.values() {
             let mut deployment_routes = Vec::new();
+            let mut deployment_agent_types = Vec::new();
+
+            validate_rate_limits(deployment, None, &deployment.rate_limits, errors);
 
             for (agent_type, agent_options) in &deployment.agents {
                 let registered_agent_type = ok_or_continue!(
@@ -188,6 +191,13 @@ impl DeploymentContext {
                     errors
                 );
 
+                validate_agent_rate_limits(
+                    deployment,
+                    &registered_agent_type.agent_type,
+                    agent_options,
+                    errors,
+                );
+
                 let make_mount_validation_error = make_invalid_agent_mount_error_maker(
                     deployment,
                     http_mount,
@@ -224,10 +234,19 @@ impl DeploymentContext {
                     &mut current_route_id,
                     &mut deployment_routes,
                 );
+
+                deployment_agent_types.push(registered_agent_type);
             }
 
             add_openapi_spec_routes(deployment, &mut current_route_id, &mut deployment_routes);
 
+            add_graphql_routes(
+                deployment,
+                &deployment_agent_types,
+                &mut current_route_id,
+                &mut deployment_routes,
+            );
+
             add_cors_preflight_http_routes(
                 deployment,
                 &mut current_route_id,
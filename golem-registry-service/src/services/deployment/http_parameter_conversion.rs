@@ -14,10 +14,12 @@
 
 use golem_common::model::agent::{
     ComponentModelElementSchema, DataSchema, ElementSchema, HeaderVariable, HttpEndpointDetails,
-    HttpMountDetails, NamedElementSchema, NamedElementSchemas, PathSegment, QueryVariable,
+    HttpMethod, HttpMountDetails, NamedElementSchema, NamedElementSchemas, PathSegment,
+    QueryVariable,
 };
 use golem_service_base::custom_api::{
-    ConstructorParameter, MethodParameter, PathSegmentType, QueryOrHeaderType, RequestBodySchema,
+    ConstructorParameter, FormFieldSchema, FormFieldType, MethodParameter, PathSegmentType,
+    QueryOrHeaderType, RequestBodySchema,
 };
 use golem_service_base::model::SafeIndex;
 use golem_wasm::analysis::{AnalysedType, NameTypePair, TypeRecord};
@@ -146,6 +148,17 @@ pub fn build_http_agent_method_parameters<E>(
     let body_schema =
         handle_body_parameters(elements, &consumed, &mut method_parameters, make_error)?;
 
+    // Each incoming WebSocket message is decoded as the body, which rules out forms
+    if matches!(endpoint.http_method, HttpMethod::WebSocket(_))
+        && matches!(body_schema, RequestBodySchema::FormBody { .. })
+    {
+        return Err(make_error(
+            "WebSocket endpoints cannot bind form fields, incoming messages are decoded as \
+             a JSON, text or binary body"
+                .into(),
+        ));
+    }
+
     Ok((body_schema, method_parameters))
 }
 
@@ -240,12 +253,56 @@ fn handle_body_parameters<E>(
         }
     }
 
-    Err(make_error(
+    // form body, mixing form field values with file and text parts
+    let mut form_fields = Vec::with_capacity(leftovers.len());
+    for (_, named_schema) in &leftovers {
+        let field_type = match &named_schema.schema {
+            ElementSchema::ComponentModel(ComponentModelElementSchema { element_type }) => {
+                match QueryOrHeaderType::try_from(element_type.clone()) {
+                    Ok(ty) => FormFieldType::Value(ty),
+                    Err(_) => return Err(invalid_body_parameters_error(make_error)),
+                }
+            }
+            ElementSchema::UnstructuredBinary(descriptor) => FormFieldType::UnstructuredBinary {
+                allowed_mime_types: descriptor
+                    .restrictions
+                    .as_ref()
+                    .map(|v| v.iter().map(|bt| bt.mime_type.clone()).collect())
+                    .unwrap_or_default(),
+            },
+            ElementSchema::UnstructuredText(descriptor) => FormFieldType::UnstructuredText {
+                allowed_language_codes: descriptor
+                    .restrictions
+                    .as_ref()
+                    .map(|v| v.iter().map(|tt| tt.language_code.clone()).collect())
+                    .unwrap_or_default(),
+            },
+        };
+
+        let field_index = form_fields.len();
+        form_fields.push(FormFieldSchema {
+            name: named_schema.name.clone(),
+            field_type,
+        });
+
+        out.push(MethodParameter::FormField {
+            field_index: SafeIndex::try_from(field_index).map_err(make_error)?,
+        });
+    }
+
+    Ok(RequestBodySchema::FormBody {
+        fields: form_fields,
+    })
+}
+
+fn invalid_body_parameters_error<E>(make_error: &impl Fn(String) -> E) -> E {
+    make_error(
         "Invalid body parameters: expected either no body, \
          all ComponentModel parameters, a single UnstructuredBinary parameter, \
-         or a single UnstructuredText parameter"
+         a single UnstructuredText parameter, or form fields of UnstructuredBinary, \
+         UnstructuredText and ComponentModel parameters of query parameter compatible types"
             .into(),
-    ))
+    )
 }
 
 fn collect_path_bindings_from_segments<E>(
@@ -386,7 +443,8 @@ mod test {
     use assert2::{assert, let_assert};
     use golem_common::model::Empty;
     use golem_service_base::custom_api::{
-        ConstructorParameter, MethodParameter, PathSegmentType, RequestBodySchema,
+        ConstructorParameter, FormFieldSchema, FormFieldType, MethodParameter, PathSegmentType,
+        QueryOrHeaderType, RequestBodySchema,
     };
     use golem_service_base::model::SafeIndex;
 
@@ -698,7 +756,10 @@ mod test {
                 NamedElementSchema {
                     name: "a".into(),
                     schema: ElementSchema::ComponentModel(ComponentModelElementSchema {
-                        element_type: analysed_type::str(),
+                        element_type: analysed_type::record(vec![analysed_type::field(
+                            "x",
+                            analysed_type::u32(),
+                        )]),
                     }),
                 },
                 NamedElementSchema {
@@ -1061,6 +1122,111 @@ mod test {
         }
     }
 
+    #[test]
+    fn method_infers_form_body_from_mixed_parameters() {
+        let mount = empty_mount();
+        let endpoint = empty_get_endpoint();
+
+        let schema = DataSchema::Tuple(NamedElementSchemas {
+            elements: vec![
+                NamedElementSchema {
+                    name: "title".into(),
+                    schema: ElementSchema::ComponentModel(ComponentModelElementSchema {
+                        element_type: analysed_type::str(),
+                    }),
+                },
+                NamedElementSchema {
+                    name: "tags".into(),
+                    schema: ElementSchema::ComponentModel(ComponentModelElementSchema {
+                        element_type: analysed_type::list(analysed_type::str()),
+                    }),
+                },
+                NamedElementSchema {
+                    name: "attachment".into(),
+                    schema: ElementSchema::UnstructuredBinary(BinaryDescriptor {
+                        restrictions: Some(vec![BinaryType {
+                            mime_type: "image/png".into(),
+                        }]),
+                    }),
+                },
+            ],
+        });
+
+        let (body, params) =
+            build_http_agent_method_parameters(&mount, &endpoint, &schema, &|msg| msg).unwrap();
+
+        let_assert!(RequestBodySchema::FormBody { fields } = body);
+        assert_eq!(
+            fields,
+            vec![
+                FormFieldSchema {
+                    name: "title".into(),
+                    field_type: FormFieldType::Value(QueryOrHeaderType::Primitive(
+                        PathSegmentType::Str
+                    )),
+                },
+                FormFieldSchema {
+                    name: "tags".into(),
+                    field_type: FormFieldType::Value(QueryOrHeaderType::List {
+                        name: None,
+                        owner: None,
+                        inner: Box::new(PathSegmentType::Str),
+                    }),
+                },
+                FormFieldSchema {
+                    name: "attachment".into(),
+                    field_type: FormFieldType::UnstructuredBinary {
+                        allowed_mime_types: vec!["image/png".into()],
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            params,
+            vec![
+                MethodParameter::FormField {
+                    field_index: SafeIndex::new(0)
+                },
+                MethodParameter::FormField {
+                    field_index: SafeIndex::new(1)
+                },
+                MethodParameter::FormField {
+                    field_index: SafeIndex::new(2)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn websocket_method_rejects_form_body() {
+        let mount = empty_mount();
+        let endpoint = HttpEndpointDetails {
+            http_method: HttpMethod::WebSocket(Empty {}),
+            ..empty_get_endpoint()
+        };
+
+        let schema = DataSchema::Tuple(NamedElementSchemas {
+            elements: vec![
+                NamedElementSchema {
+                    name: "title".into(),
+                    schema: ElementSchema::ComponentModel(ComponentModelElementSchema {
+                        element_type: analysed_type::str(),
+                    }),
+                },
+                NamedElementSchema {
+                    name: "attachment".into(),
+                    schema: ElementSchema::UnstructuredBinary(BinaryDescriptor {
+                        restrictions: None,
+                    }),
+                },
+            ],
+        });
+
+        let result = build_http_agent_method_parameters(&mount, &endpoint, &schema, &|msg| msg);
+        let_assert!(Err(msg) = result);
+        assert!(msg.contains("WebSocket endpoints cannot bind form fields"));
+    }
+
     #[test]
     fn method_accepts_unstructured_text_body_unrestricted() {
         let mount = empty_mount();
@@ -147,6 +147,7 @@ mod tests {
                     output_schema: empty_data_schema(),
                     http_endpoint: vec![],
                     read_only: None,
+                    timeout_nanos: None,
                 },
                 AgentMethod {
                     name: "describe".to_string(),
@@ -158,6 +159,7 @@ mod tests {
                     output_schema: tuple_data_schema(&["value"]),
                     http_endpoint: vec![],
                     read_only: None,
+                    timeout_nanos: None,
                 },
                 AgentMethod {
                     name: "preview".to_string(),
@@ -167,6 +169,7 @@ mod tests {
                     output_schema: multimodal_data_schema(&["thumbnail", "caption"]),
                     http_endpoint: vec![],
                     read_only: None,
+                    timeout_nanos: None,
                 },
             ],
             dependencies: vec![AgentDependency {
@@ -186,6 +189,7 @@ mod tests {
                     output_schema: empty_data_schema(),
                     http_endpoint: vec![],
                     read_only: None,
+                    timeout_nanos: None,
                 }],
             }],
             mode: AgentMode::Durable,
@@ -15,7 +15,7 @@
 use super::authorize_environment_permission;
 use super::mirror::{DeployedAgentTypeMirror, ResolvedAgentTypeMirror, schema_mirror_error};
 use crate::repo::deployment::DeploymentRepo;
-use crate::repo::model::deployment::DeployRepoError;
+use crate::repo::model::deployment::{DeployRepoError, ResolvedAgentTypeRecord};
 use crate::services::application::{ApplicationError, ApplicationService};
 use crate::services::environment::{EnvironmentError, EnvironmentService};
 use golem_common::model::account::AccountId;
@@ -407,25 +407,74 @@ impl DeploymentService {
             .await?
             .ok_or_else(|| DeploymentError::AgentTypeNotFound(agent_type_name.0.clone()))?;
 
+        // Map authorization failure to NotFound to prevent resource enumeration
+        Self::visible_resolved_agent_type(record, app_name, environment_name, auth)?
+            .ok_or_else(|| DeploymentError::AgentTypeNotFound(agent_type_name.0.clone()))
+    }
+
+    pub async fn list_agent_types_by_names(
+        &self,
+        app_name: &ApplicationName,
+        environment_name: &EnvironmentName,
+        deployment_revision: Option<DeploymentRevision>,
+        owner_account_email: Option<&str>,
+        auth: &AuthCtx,
+    ) -> Result<Vec<ResolvedAgentType>, DeploymentError> {
+        let caller_account_id = auth.access_account_id();
+
+        let records = self
+            .deployment_repo
+            .list_agent_types_by_names(
+                caller_account_id.0,
+                &app_name.0,
+                &environment_name.0,
+                deployment_revision.map(|r| r.into()),
+                owner_account_email,
+            )
+            .await?;
+
+        // Agent types the caller may not view are left out, like in resolve_agent_type_by_names
+        let mut result = Vec::with_capacity(records.len());
+        for record in records {
+            if let Some(resolved) =
+                Self::visible_resolved_agent_type(record, app_name, environment_name, auth)?
+            {
+                result.push(resolved);
+            }
+        }
+        Ok(result)
+    }
+
+    fn visible_resolved_agent_type(
+        record: ResolvedAgentTypeRecord,
+        app_name: &ApplicationName,
+        environment_name: &EnvironmentName,
+        auth: &AuthCtx,
+    ) -> Result<Option<ResolvedAgentType>, DeploymentError> {
         let environment_id = EnvironmentId(record.environment_id);
 
-        // Map authorization failure to NotFound to prevent resource enumeration
-        auth.authorize_permission(&PermissionTarget::Agent(ClassPermissionTarget {
-            owner: AgentOwnerPattern::Agent {
-                account: golem_common::model::account::AccountEmail::new(
-                    record.owner_account_email.clone(),
-                ),
-                application: app_name.clone(),
-                environment: environment_name.clone(),
-                component: golem_common::model::component::ComponentName(
-                    record.component_name.clone(),
-                ),
-                agent: AgentOwnerLeafPattern::AgentTypeWildcard(agent_type_name.clone()),
-            },
-            verb: Some(AgentVerb::View),
-            resource: AgentResourcePattern::Any,
-        }))
-        .map_err(|_| DeploymentError::AgentTypeNotFound(agent_type_name.0.clone()))?;
+        let visible = auth
+            .authorize_permission(&PermissionTarget::Agent(ClassPermissionTarget {
+                owner: AgentOwnerPattern::Agent {
+                    account: golem_common::model::account::AccountEmail::new(
+                        record.owner_account_email.clone(),
+                    ),
+                    application: app_name.clone(),
+                    environment: environment_name.clone(),
+                    component: golem_common::model::component::ComponentName(
+                        record.component_name.clone(),
+                    ),
+                    agent: AgentOwnerLeafPattern::AgentTypeWildcard(AgentTypeName(
+                        record.agent_type_name.clone(),
+                    )),
+                },
+                verb: Some(AgentVerb::View),
+                resource: AgentResourcePattern::Any,
+            }))
+            .is_ok();
+        if !visible {
+            return Ok(None);
+        }
 
         let deployment_revision: DeploymentRevision = record
             .deployment_revision_id
@@ -438,12 +487,12 @@ impl DeploymentService {
             .map_err(DeploymentError::from)?;
         let deployed: DeployedRegisteredAgentType =
             record.try_into().map_err(DeploymentError::from)?;
-        Ok(ResolvedAgentType {
+        Ok(Some(ResolvedAgentType {
             registered_agent_type: deployed.into(),
             environment_id,
             deployment_revision,
             current_deployment_revision,
-        })
+        }))
     }
 
     pub async fn get_agent_type_by_names_at_deployment(
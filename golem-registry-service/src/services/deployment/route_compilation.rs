@@ -33,11 +33,13 @@ use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::Environment;
 use golem_common::model::http_api_deployment::{
     HttpApiDeployment, HttpApiDeploymentAgentOptions, HttpApiDeploymentAgentSecurity,
+    HttpApiRateLimit, HttpApiRateLimitKey,
 };
 use golem_service_base::custom_api::{
     CallAgentBehaviour, ConstructorParameter, CorsOptions, CorsPreflightBehaviour,
-    CorsPreflightMethodPolicy, MethodParameter, OpenApiSpecBehaviour, OpenApiSpecFormat,
-    OriginPattern, PathSegment, RequestBodySchema, RouteBehaviour, SessionFromHeaderRouteSecurity,
+    CorsPreflightMethodPolicy, GraphQlAgent, GraphQlBehaviour, MethodParameter,
+    OpenApiSpecBehaviour, OpenApiSpecFormat, OriginPattern, PathSegment, RateLimitScope,
+    RequestBodySchema, RouteBehaviour, RouteRateLimit, SessionFromHeaderRouteSecurity,
     WebhookCallbackBehaviour,
 };
 use heck::ToKebabCase;
@@ -157,6 +159,12 @@ pub fn add_agent_method_http_routes(
                 }),
                 security,
                 cors,
+                rate_limits: collect_route_rate_limits(
+                    deployment,
+                    deployment_agent_options,
+                    agent,
+                    agent_method,
+                ),
             };
 
             compiled_routes.push(compiled);
@@ -253,6 +261,11 @@ pub fn add_cors_preflight_http_routes(
     let mut preflight_map: HashMap<Vec<PathSegment>, PreflightMapEntry> = HashMap::new();
 
     for compiled_route in compiled_routes.iter() {
+        // Browsers never send preflight requests for WebSocket upgrades
+        if matches!(compiled_route.method, HttpMethod::WebSocket(_)) {
+            continue;
+        }
+
         if !compiled_route.cors.allowed_patterns.is_empty() {
             let entry = preflight_map
                 .entry(compiled_route.path.clone())
@@ -300,6 +313,7 @@ pub fn add_cors_preflight_http_routes(
             cors: CorsOptions {
                 allowed_patterns: vec![],
             },
+            rate_limits: Vec::new(),
         });
     }
 }
@@ -378,6 +392,7 @@ pub fn add_webhook_callback_routes(
             cors: CorsOptions {
                 allowed_patterns: Vec::new(),
             },
+            rate_limits: Vec::new(),
         };
 
         compiled_routes.push(compiled);
@@ -414,10 +429,78 @@ pub fn add_openapi_spec_routes(
             cors: CorsOptions {
                 allowed_patterns: Vec::new(),
             },
+            rate_limits: Vec::new(),
         });
     }
 }
 
+/// Adds the GraphQL endpoint of the deployment, if it is enabled.
+/// Only agent methods that can be called without authentication are exposed.
+pub fn add_graphql_routes(
+    deployment: &HttpApiDeployment,
+    agent_types: &[&InProgressDeployedRegisteredAgentType],
+    current_route_id: &mut i32,
+    compiled_routes: &mut Vec<UnboundCompiledRoute>,
+) {
+    let Some(graphql_endpoint) = &deployment.graphql_endpoint else {
+        return;
+    };
+
+    let agents = agent_types
+        .iter()
+        .filter(|agent_type| !http_mount_requires_auth(agent_type.agent_type.http_mount.as_ref()))
+        .map(|agent_type| {
+            let mut agent_type_without_auth = agent_type.agent_type.clone();
+            agent_type_without_auth.methods.retain(|method| {
+                !method
+                    .http_endpoint
+                    .iter()
+                    .any(|endpoint| endpoint.auth_details.as_ref().is_some_and(|a| a.required))
+            });
+
+            GraphQlAgent {
+                component_id: agent_type.implemented_by.component_id,
+                component_revision: agent_type.implemented_by.component_revision,
+                rate_limits: collect_graphql_agent_rate_limits(
+                    deployment.agents.get(&agent_type_without_auth.type_name),
+                    &agent_type_without_auth,
+                ),
+                agent_type: agent_type_without_auth,
+            }
+        })
+        .collect();
+
+    let route_id = *current_route_id;
+    *current_route_id = current_route_id.checked_add(1).unwrap();
+
+    compiled_routes.push(UnboundCompiledRoute {
+        route_id,
+        domain: deployment.domain.clone(),
+        method: HttpMethod::Post(Empty {}),
+        path: parse_literal_only_path_segments(graphql_endpoint),
+        body: RequestBodySchema::UnrestrictedBinary,
+        behaviour: RouteBehaviour::GraphQl(GraphQlBehaviour { agents }),
+        security: UnboundRouteSecurity::None,
+        cors: CorsOptions {
+            allowed_patterns: Vec::new(),
+        },
+        rate_limits: deployment
+            .rate_limits
+            .iter()
+            .map(|limit| RouteRateLimit {
+                scope: RateLimitScope::Deployment,
+                limit: limit.clone(),
+            })
+            .collect(),
+    });
+}
+
+fn http_mount_requires_auth(http_mount: Option<&HttpMountDetails>) -> bool {
+    http_mount
+        .and_then(|http_mount| http_mount.auth_details.as_ref())
+        .is_some_and(|auth_details| auth_details.required)
+}
+
 pub fn build_agent_http_api_deployment_details(
     agent_type_name: &AgentTypeName,
     agent_type: &AgentType,
@@ -577,6 +660,7 @@ pub fn render_http_method(method: &HttpMethod) -> String {
         HttpMethod::Trace(_) => "TRACE".to_string(),
         HttpMethod::Patch(_) => "PATCH".to_string(),
         HttpMethod::Custom(custom) => custom.value.clone(),
+        HttpMethod::WebSocket(_) => "WEBSOCKET".to_string(),
     }
 }
 
@@ -687,6 +771,161 @@ fn resolve_route_security(
     }
 }
 
+/// Rate limits enforced on the routes of an agent method, from the widest scope to the narrowest.
+/// A request has to fit into every one of them.
+fn collect_route_rate_limits(
+    deployment: &HttpApiDeployment,
+    deployment_agent_options: &HttpApiDeploymentAgentOptions,
+    agent: &AgentType,
+    agent_method: &AgentMethod,
+) -> Vec<RouteRateLimit> {
+    let with_scope = |scope: RateLimitScope, limits: &[HttpApiRateLimit]| {
+        limits
+            .iter()
+            .map(|limit| RouteRateLimit {
+                scope: scope.clone(),
+                limit: limit.clone(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut rate_limits = with_scope(RateLimitScope::Deployment, &deployment.rate_limits);
+
+    rate_limits.extend(with_scope(
+        RateLimitScope::AgentType {
+            agent_type: agent.type_name.clone(),
+        },
+        &deployment_agent_options.rate_limits,
+    ));
+
+    if let Some(method_rate_limits) = deployment_agent_options
+        .method_rate_limits
+        .get(&agent_method.name)
+    {
+        rate_limits.extend(with_scope(
+            RateLimitScope::Method {
+                agent_type: agent.type_name.clone(),
+                method_name: agent_method.name.clone(),
+            },
+            method_rate_limits,
+        ));
+    }
+
+    rate_limits
+}
+
+/// Agent and method level rate limits of the methods of an agent type exposed through GraphQL.
+/// The GraphQL route only enforces the deployment level ones, the others are enforced by the
+/// resolvers of the methods.
+fn collect_graphql_agent_rate_limits(
+    deployment_agent_options: Option<&HttpApiDeploymentAgentOptions>,
+    agent: &AgentType,
+) -> Vec<RouteRateLimit> {
+    let Some(deployment_agent_options) = deployment_agent_options else {
+        return Vec::new();
+    };
+
+    let mut rate_limits: Vec<RouteRateLimit> = deployment_agent_options
+        .rate_limits
+        .iter()
+        .map(|limit| RouteRateLimit {
+            scope: RateLimitScope::AgentType {
+                agent_type: agent.type_name.clone(),
+            },
+            limit: limit.clone(),
+        })
+        .collect();
+
+    for agent_method in &agent.methods {
+        if let Some(method_rate_limits) = deployment_agent_options
+            .method_rate_limits
+            .get(&agent_method.name)
+        {
+            rate_limits.extend(method_rate_limits.iter().map(|limit| RouteRateLimit {
+                scope: RateLimitScope::Method {
+                    agent_type: agent.type_name.clone(),
+                    method_name: agent_method.name.clone(),
+                },
+                limit: limit.clone(),
+            }));
+        }
+    }
+
+    rate_limits
+}
+
+pub fn validate_rate_limits(
+    deployment: &HttpApiDeployment,
+    agent: Option<&AgentType>,
+    rate_limits: &[HttpApiRateLimit],
+    errors: &mut Vec<DeployValidationError>,
+) {
+    for rate_limit in rate_limits {
+        let error = if rate_limit.value == 0 {
+            Some("value must be greater than zero".to_string())
+        } else if rate_limit.max == 0 {
+            Some("max must be greater than zero".to_string())
+        } else if let Some(api_key_header) = &rate_limit.api_key_header {
+            if rate_limit.key != HttpApiRateLimitKey::ApiKey {
+                Some(format!(
+                    "apiKeyHeader can only be used with the {} key",
+                    HttpApiRateLimitKey::ApiKey
+                ))
+            } else if http::HeaderName::from_bytes(api_key_header.trim().as_bytes()).is_err() {
+                Some(format!("{api_key_header} is not a valid header name"))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        if let Some(error) = error {
+            let error = match agent {
+                Some(agent) => format!("{error} (agent type {})", agent.type_name),
+                None => error,
+            };
+            errors.push(DeployValidationError::HttpApiDeploymentInvalidRateLimit {
+                domain: deployment.domain.clone(),
+                error,
+            });
+        }
+    }
+}
+
+pub fn validate_agent_rate_limits(
+    deployment: &HttpApiDeployment,
+    agent: &AgentType,
+    deployment_agent_options: &HttpApiDeploymentAgentOptions,
+    errors: &mut Vec<DeployValidationError>,
+) {
+    validate_rate_limits(
+        deployment,
+        Some(agent),
+        &deployment_agent_options.rate_limits,
+        errors,
+    );
+
+    for (method_name, rate_limits) in &deployment_agent_options.method_rate_limits {
+        if !agent
+            .methods
+            .iter()
+            .any(|method| &method.name == method_name)
+        {
+            errors.push(
+                DeployValidationError::HttpApiDeploymentRateLimitUnknownMethod {
+                    domain: deployment.domain.clone(),
+                    agent_type: agent.type_name.clone(),
+                    method_name: method_name.clone(),
+                },
+            );
+            continue;
+        }
+
+        validate_rate_limits(deployment, Some(agent), rate_limits, errors);
+    }
+}
+
 pub fn validate_path_segments(
     segments: &[PathSegment],
     domain: &Domain,
@@ -733,6 +972,7 @@ mod tests {
     use golem_common::model::http_api_deployment::{
         HttpApiDeployment, HttpApiDeploymentAgentOptions, HttpApiDeploymentId,
     };
+    use golem_common::model::quota::TimePeriod;
     use std::collections::{BTreeMap, BTreeSet};
     use test_r::test;
     use uuid::Uuid;
@@ -782,6 +1022,7 @@ mod tests {
                     },
                 }],
                 read_only: None,
+                timeout_nanos: None,
             }],
             dependencies: vec![],
             mode,
@@ -815,6 +1056,8 @@ mod tests {
             agents: BTreeMap::new(),
             webhooks_prefix: "/webhooks".to_string(),
             openapi_endpoint_prefix: "/".to_string(),
+            rate_limits: vec![],
+            graphql_endpoint: None,
             created_at: Utc::now(),
         }
     }
@@ -875,6 +1118,8 @@ mod tests {
             agents: BTreeMap::new(),
             webhooks_prefix: "/webhooks".to_string(),
             openapi_endpoint_prefix: openapi_endpoint.to_string(),
+            rate_limits: vec![],
+            graphql_endpoint: None,
             created_at: Utc::now(),
         }
     }
@@ -982,6 +1227,76 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn add_graphql_routes_exposes_methods_without_auth() {
+        let mut deployment = test_deployment(EnvironmentId(Uuid::new_v4()));
+        deployment.graphql_endpoint = Some("/graphql".to_string());
+
+        let mut agent_type = test_agent(AgentMode::Durable, false);
+        let mut secured_method = agent_type.methods[0].clone();
+        secured_method.name = "secret".to_string();
+        secured_method.http_endpoint[0].auth_details =
+            Some(golem_common::model::agent::AgentHttpAuthDetails { required: true });
+        agent_type.methods.push(secured_method);
+
+        let agent_type = InProgressDeployedRegisteredAgentType {
+            agent_type,
+            implemented_by: RegisteredAgentTypeImplementer {
+                component_id: ComponentId(Uuid::new_v4()),
+                component_revision: ComponentRevision::INITIAL,
+                component_name: "test-component".to_string(),
+                account_id: AccountId(Uuid::new_v4()),
+                account_email: golem_common::model::account::AccountEmail::new("test@golem"),
+            },
+            webhook_domain_and_segments: None,
+        };
+
+        let mut route_id = 1;
+        let mut compiled_routes = Vec::new();
+
+        add_graphql_routes(
+            &deployment,
+            &[&agent_type],
+            &mut route_id,
+            &mut compiled_routes,
+        );
+
+        assert_eq!(compiled_routes.len(), 1);
+        assert_eq!(route_id, 2);
+        assert_eq!(
+            compiled_routes[0].path,
+            vec![PathSegment::Literal {
+                value: "graphql".to_string(),
+            }]
+        );
+        let RouteBehaviour::GraphQl(GraphQlBehaviour { agents }) = &compiled_routes[0].behaviour
+        else {
+            panic!("expected graphql route");
+        };
+        assert_eq!(agents.len(), 1);
+        assert_eq!(
+            agents[0]
+                .agent_type
+                .methods
+                .iter()
+                .map(|method| method.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fetch"]
+        );
+    }
+
+    #[test]
+    fn add_graphql_routes_skips_disabled_endpoint() {
+        let deployment = test_deployment(EnvironmentId(Uuid::new_v4()));
+        let mut route_id = 1;
+        let mut compiled_routes = Vec::new();
+
+        add_graphql_routes(&deployment, &[], &mut route_id, &mut compiled_routes);
+
+        assert!(compiled_routes.is_empty());
+        assert_eq!(route_id, 1);
+    }
+
     #[test]
     fn preflight_routes_keep_origins_and_headers_per_method() {
         let path = vec![PathSegment::Literal {
@@ -1017,6 +1332,7 @@ mod tests {
                 cors: CorsOptions {
                     allowed_patterns: vec![OriginPattern("https://public.example.com".to_string())],
                 },
+                rate_limits: vec![],
             },
             UnboundCompiledRoute {
                 domain: Domain("example.com".to_string()),
@@ -1045,6 +1361,7 @@ mod tests {
                 cors: CorsOptions {
                     allowed_patterns: vec![OriginPattern("https://admin.example.com".to_string())],
                 },
+                rate_limits: vec![],
             },
         ];
 
@@ -1104,6 +1421,162 @@ mod tests {
         assert!(compiled_phantom_flag(AgentMode::Durable, true));
     }
 
+    #[test]
+    fn agent_routes_collect_rate_limits_of_all_scopes() {
+        let rate_limit = |value: u64| HttpApiRateLimit {
+            key: HttpApiRateLimitKey::ClientIp,
+            api_key_header: None,
+            value,
+            period: TimePeriod::Minute,
+            max: value,
+        };
+
+        let environment_id = EnvironmentId(Uuid::new_v4());
+        let environment = test_environment(environment_id);
+        let mut deployment = test_deployment(environment_id);
+        deployment.rate_limits = vec![rate_limit(1000)];
+        let agent = test_agent(AgentMode::Durable, false);
+        let http_mount = agent.http_mount.clone().unwrap();
+        let implementer = RegisteredAgentTypeImplementer {
+            component_id: ComponentId(Uuid::new_v4()),
+            component_revision: ComponentRevision::INITIAL,
+            component_name: "test-component".to_string(),
+            account_id: AccountId(Uuid::new_v4()),
+            account_email: golem_common::model::account::AccountEmail::new("test@golem"),
+        };
+        let agent_options = HttpApiDeploymentAgentOptions {
+            security: None,
+            rate_limits: vec![rate_limit(100)],
+            method_rate_limits: BTreeMap::from([
+                ("fetch".to_string(), vec![rate_limit(10)]),
+                ("other".to_string(), vec![rate_limit(1)]),
+            ]),
+        };
+        let mut current_route_id = 0;
+        let mut compiled_routes = Vec::new();
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        add_agent_method_http_routes(
+            &environment,
+            &deployment,
+            &agent,
+            &implementer,
+            &http_mount,
+            &agent.methods,
+            vec![],
+            &agent_options,
+            &mut current_route_id,
+            &mut compiled_routes,
+            &mut errors,
+            &mut warnings,
+        );
+
+        assert!(errors.is_empty());
+
+        let compiled_route = compiled_routes.into_iter().next().unwrap();
+        assert_eq!(
+            compiled_route.rate_limits,
+            vec![
+                RouteRateLimit {
+                    scope: RateLimitScope::Deployment,
+                    limit: rate_limit(1000),
+                },
+                RouteRateLimit {
+                    scope: RateLimitScope::AgentType {
+                        agent_type: AgentTypeName("note-agent".to_string()),
+                    },
+                    limit: rate_limit(100),
+                },
+                RouteRateLimit {
+                    scope: RateLimitScope::Method {
+                        agent_type: AgentTypeName("note-agent".to_string()),
+                        method_name: "fetch".to_string(),
+                    },
+                    limit: rate_limit(10),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn graphql_agents_collect_agent_and_method_rate_limits() {
+        let rate_limit = |value: u64| HttpApiRateLimit {
+            key: HttpApiRateLimitKey::ClientIp,
+            api_key_header: None,
+            value,
+            period: TimePeriod::Minute,
+            max: value,
+        };
+
+        let mut deployment = test_deployment(EnvironmentId(Uuid::new_v4()));
+        deployment.graphql_endpoint = Some("/graphql".to_string());
+        deployment.rate_limits = vec![rate_limit(1000)];
+        deployment.agents.insert(
+            AgentTypeName("note-agent".to_string()),
+            HttpApiDeploymentAgentOptions {
+                security: None,
+                rate_limits: vec![rate_limit(100)],
+                method_rate_limits: BTreeMap::from([
+                    ("fetch".to_string(), vec![rate_limit(10)]),
+                    ("other".to_string(), vec![rate_limit(1)]),
+                ]),
+            },
+        );
+
+        let agent_type = InProgressDeployedRegisteredAgentType {
+            agent_type: test_agent(AgentMode::Durable, false),
+            implemented_by: RegisteredAgentTypeImplementer {
+                component_id: ComponentId(Uuid::new_v4()),
+                component_revision: ComponentRevision::INITIAL,
+                component_name: "test-component".to_string(),
+                account_id: AccountId(Uuid::new_v4()),
+                account_email: golem_common::model::account::AccountEmail::new("test@golem"),
+            },
+            webhook_domain_and_segments: None,
+        };
+
+        let mut route_id = 1;
+        let mut compiled_routes = Vec::new();
+
+        add_graphql_routes(
+            &deployment,
+            &[&agent_type],
+            &mut route_id,
+            &mut compiled_routes,
+        );
+
+        assert_eq!(
+            compiled_routes[0].rate_limits,
+            vec![RouteRateLimit {
+                scope: RateLimitScope::Deployment,
+                limit: rate_limit(1000),
+            }]
+        );
+        let RouteBehaviour::GraphQl(GraphQlBehaviour { agents }) = &compiled_routes[0].behaviour
+        else {
+            panic!("expected graphql route");
+        };
+        assert_eq!(
+            agents[0].rate_limits,
+            vec![
+                RouteRateLimit {
+                    scope: RateLimitScope::AgentType {
+                        agent_type: AgentTypeName("note-agent".to_string()),
+                    },
+                    limit: rate_limit(100),
+                },
+                RouteRateLimit {
+                    scope: RateLimitScope::Method {
+                        agent_type: AgentTypeName("note-agent".to_string()),
+                        method_name: "fetch".to_string(),
+                    },
+                    limit: rate_limit(10),
+                },
+            ]
+        );
+    }
+
     fn run_route_compilation_for_warnings(agent: AgentType) -> Vec<DeployValidationWarning> {
         let environment_id = EnvironmentId(Uuid::new_v4());
         let environment = test_environment(environment_id);
@@ -115,6 +115,7 @@ impl DeployedRoutesService {
                 behavior: route.route.behaviour,
                 security,
                 cors: route.route.cors,
+                rate_limits: route.route.rate_limits,
             };
             converted_routes.push(converted);
         }
@@ -14,6 +14,7 @@
 
 use super::agent_secret::{AgentSecretError, AgentSecretService};
 use super::deployment::{DeploymentError, DeploymentService};
+use super::oplog_export_sink::{OplogExportSinkError, OplogExportSinkService};
 use super::retry_policy::{RetryPolicyError, RetryPolicyService};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::{SafeDisplay, error_forwarding};
@@ -39,13 +40,15 @@ error_forwarding!(
     EnvironmentStateError,
     DeploymentError,
     AgentSecretError,
-    RetryPolicyError
+    RetryPolicyError,
+    OplogExportSinkError
 );
 
 pub struct EnvironmentStateService {
     pub deployment_service: Arc<DeploymentService>,
     pub agent_secret_service: Arc<AgentSecretService>,
     pub retry_policy_service: Arc<RetryPolicyService>,
+    pub oplog_export_sink_service: Arc<OplogExportSinkService>,
 }
 
 impl EnvironmentStateService {
@@ -53,11 +56,13 @@ impl EnvironmentStateService {
         deployment_service: Arc<DeploymentService>,
         agent_secret_service: Arc<AgentSecretService>,
         retry_policy_service: Arc<RetryPolicyService>,
+        oplog_export_sink_service: Arc<OplogExportSinkService>,
     ) -> Self {
         Self {
             deployment_service,
             agent_secret_service,
             retry_policy_service,
+            oplog_export_sink_service,
         }
     }
 
@@ -106,10 +111,19 @@ impl EnvironmentStateService {
                 })
                 .collect();
 
+        let oplog_export_sinks = self
+            .oplog_export_sink_service
+            .list_in_environment_unchecked(environment_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
         Ok(EnvironmentState {
             agent_deployment_details,
             agent_secrets,
             retry_policies,
+            oplog_export_sinks,
         })
     }
 }
@@ -226,6 +226,9 @@ impl HttpApiDeploymentService {
                 data.openapi_endpoint_prefix,
             ),
             data.agents,
+            data.rate_limits,
+            data.graphql_endpoint
+                .and_then(HttpApiDeploymentCreation::normalize_graphql_endpoint),
             auth.actor_account_id(),
         )?;
 
@@ -296,6 +299,13 @@ impl HttpApiDeploymentService {
         if let Some(api_definitions) = update.agents {
             http_api_deployment.agents = api_definitions;
         };
+        if let Some(rate_limits) = update.rate_limits {
+            http_api_deployment.rate_limits = rate_limits;
+        };
+        if let Some(graphql_endpoint) = update.graphql_endpoint {
+            http_api_deployment.graphql_endpoint =
+                HttpApiDeploymentCreation::normalize_graphql_endpoint(graphql_endpoint);
+        };
 
         let record = HttpApiDeploymentRevisionRecord::from_model(
             http_api_deployment,
@@ -34,6 +34,7 @@ pub mod oauth2_github_client;
 pub mod permission_share;
 pub mod plan;
 pub mod plugin_registration;
+pub mod oplog_export_sink;
 pub mod registry_change_notifier;
 pub mod reports;
 pub mod resource_definition;
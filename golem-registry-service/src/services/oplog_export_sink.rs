@@ -0,0 +1,358 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::environment::{EnvironmentError, EnvironmentService};
+use super::registry_change_notifier::{RegistryChangeNotifier, RequiresNotificationSignalExt};
+use crate::repo::model::audit::DeletableRevisionAuditFields;
+use crate::repo::model::oplog_export_sink::{
+    OplogExportSinkAuthExtRevisionRecord, OplogExportSinkCreationRecord, OplogExportSinkRepoError,
+    OplogExportSinkRevisionRecord,
+};
+use crate::repo::oplog_export_sink::OplogExportSinkRepo;
+use golem_common::model::account::AccountEmail;
+use golem_common::model::application::ApplicationName;
+use golem_common::model::card::owner::EnvironmentOwnerPattern;
+use golem_common::model::card::{
+    ClassPermissionTarget, EnvironmentResourcePattern, EnvironmentVerb, PermissionTarget,
+};
+use golem_common::model::environment::{Environment, EnvironmentId, EnvironmentName};
+use golem_common::model::oplog_export::{
+    OplogExportSinkCreation, OplogExportSinkId, OplogExportSinkRevision, OplogExportSinkUpdate,
+    OplogExportTarget,
+};
+use golem_common::{SafeDisplay, error_forwarding};
+use golem_service_base::model::auth::{AuthCtx, AuthorizationError};
+use golem_service_base::model::oplog_export_sink::StoredOplogExportSink;
+use std::sync::Arc;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OplogExportSinkError {
+    #[error("Invalid oplog export sink name {0}: only letters, digits, '-' and '_' are allowed")]
+    InvalidName(String),
+    #[error("Invalid oplog export target: {0}")]
+    InvalidTarget(String),
+    #[error("Oplog export sink for name {name} already exists in environment")]
+    OplogExportSinkForNameAlreadyExists { name: String },
+    #[error("Environment {0} not found")]
+    ParentEnvironmentNotFound(EnvironmentId),
+    #[error("Oplog export sink {0} not found")]
+    OplogExportSinkNotFound(OplogExportSinkId),
+    #[error("Concurrent update attempt")]
+    ConcurrentModification,
+    #[error(transparent)]
+    Unauthorized(#[from] AuthorizationError),
+    #[error(transparent)]
+    InternalError(#[from] anyhow::Error),
+}
+
+impl SafeDisplay for OplogExportSinkError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            Self::InvalidName(_) => self.to_string(),
+            Self::InvalidTarget(_) => self.to_string(),
+            Self::OplogExportSinkForNameAlreadyExists { .. } => self.to_string(),
+            Self::ParentEnvironmentNotFound(_) => self.to_string(),
+            Self::OplogExportSinkNotFound(_) => self.to_string(),
+            Self::ConcurrentModification => self.to_string(),
+            Self::Unauthorized(inner) => inner.to_safe_string(),
+            Self::InternalError(_) => "Internal error".to_string(),
+        }
+    }
+}
+
+error_forwarding!(
+    OplogExportSinkError,
+    EnvironmentError,
+    OplogExportSinkRepoError
+);
+
+/// Manages the oplog export sinks of environments. The sinks are part of the environment's
+/// settings, so they are authorized with the environment's view and update permissions.
+pub struct OplogExportSinkService {
+    oplog_export_sink_repo: Arc<dyn OplogExportSinkRepo>,
+    environment_service: Arc<EnvironmentService>,
+    registry_change_notifier: Arc<dyn RegistryChangeNotifier>,
+}
+
+impl OplogExportSinkService {
+    pub fn new(
+        oplog_export_sink_repo: Arc<dyn OplogExportSinkRepo>,
+        environment_service: Arc<EnvironmentService>,
+        registry_change_notifier: Arc<dyn RegistryChangeNotifier>,
+    ) -> Self {
+        Self {
+            oplog_export_sink_repo,
+            environment_service,
+            registry_change_notifier,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        environment_id: EnvironmentId,
+        data: OplogExportSinkCreation,
+        auth: &AuthCtx,
+    ) -> Result<StoredOplogExportSink, OplogExportSinkError> {
+        let environment = self.get_environment(environment_id, auth).await?;
+
+        authorize_environment_permission(auth, &environment, EnvironmentVerb::Update)?;
+
+        validate_name(&data.name)?;
+        let target_json = target_json(&data.target)?;
+
+        let name = data.name.clone();
+        let create_record = OplogExportSinkCreationRecord::new(
+            OplogExportSinkId::new(),
+            environment_id,
+            data.name,
+            target_json,
+            auth.actor_account_id(),
+        );
+
+        let result = self.oplog_export_sink_repo.create(create_record).await;
+
+        match result {
+            Ok(record) => Ok(record
+                .signal_new_events_available(&self.registry_change_notifier)
+                .try_into()?),
+            Err(OplogExportSinkRepoError::NameViolatesUniqueness) => {
+                Err(OplogExportSinkError::OplogExportSinkForNameAlreadyExists { name })
+            }
+            Err(other) => Err(other.into()),
+        }
+    }
+
+    pub async fn update(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        update: OplogExportSinkUpdate,
+        auth: &AuthCtx,
+    ) -> Result<StoredOplogExportSink, OplogExportSinkError> {
+        let (mut sink, owner) = self
+            .get_with_environment(oplog_export_sink_id, auth)
+            .await?;
+
+        authorize_environment_permission_for_owner(auth, owner, EnvironmentVerb::Update)?;
+
+        if update.current_revision != sink.revision {
+            return Err(OplogExportSinkError::ConcurrentModification);
+        };
+
+        sink.revision = sink.revision.next()?;
+
+        if let Some(new_target) = update.target {
+            target_json(&new_target)?;
+            sink.target = new_target;
+        }
+
+        let audit = DeletableRevisionAuditFields::new(auth.actor_account_id().0);
+
+        let result = self
+            .oplog_export_sink_repo
+            .update(OplogExportSinkRevisionRecord::from_model(sink, audit)?)
+            .await;
+
+        match result {
+            Ok(record) => Ok(record
+                .signal_new_events_available(&self.registry_change_notifier)
+                .try_into()?),
+            Err(OplogExportSinkRepoError::ConcurrentModification) => {
+                Err(OplogExportSinkError::ConcurrentModification)
+            }
+            Err(other) => Err(other.into()),
+        }
+    }
+
+    pub async fn delete(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        current_revision: OplogExportSinkRevision,
+        auth: &AuthCtx,
+    ) -> Result<StoredOplogExportSink, OplogExportSinkError> {
+        let (mut sink, owner) = self
+            .get_with_environment(oplog_export_sink_id, auth)
+            .await?;
+
+        authorize_environment_permission_for_owner(auth, owner, EnvironmentVerb::Update)?;
+
+        if sink.revision != current_revision {
+            return Err(OplogExportSinkError::ConcurrentModification);
+        }
+
+        sink.revision = current_revision.next()?;
+
+        let audit = DeletableRevisionAuditFields::deletion(auth.actor_account_id().0);
+
+        let result = self
+            .oplog_export_sink_repo
+            .delete(OplogExportSinkRevisionRecord::from_model(sink, audit)?)
+            .await;
+
+        match result {
+            Ok(record) => Ok(record
+                .signal_new_events_available(&self.registry_change_notifier)
+                .try_into()?),
+            Err(OplogExportSinkRepoError::ConcurrentModification) => {
+                Err(OplogExportSinkError::ConcurrentModification)
+            }
+            Err(other) => Err(other.into()),
+        }
+    }
+
+    pub async fn get(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        auth: &AuthCtx,
+    ) -> Result<StoredOplogExportSink, OplogExportSinkError> {
+        let (sink, _) = self
+            .get_with_environment(oplog_export_sink_id, auth)
+            .await?;
+        Ok(sink)
+    }
+
+    pub async fn list_in_environment(
+        &self,
+        environment_id: EnvironmentId,
+        auth: &AuthCtx,
+    ) -> Result<Vec<StoredOplogExportSink>, OplogExportSinkError> {
+        let environment = self.get_environment(environment_id, auth).await?;
+
+        authorize_environment_permission(auth, &environment, EnvironmentVerb::View)?;
+
+        self.list_in_environment_unchecked(environment_id).await
+    }
+
+    pub async fn list_in_environment_unchecked(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<StoredOplogExportSink>, OplogExportSinkError> {
+        let result = self
+            .oplog_export_sink_repo
+            .get_for_environment(environment_id.0)
+            .await?
+            .into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<_, _>>()?;
+
+        Ok(result)
+    }
+
+    async fn get_environment(
+        &self,
+        environment_id: EnvironmentId,
+        auth: &AuthCtx,
+    ) -> Result<Environment, OplogExportSinkError> {
+        self.environment_service
+            .get(environment_id, false, auth)
+            .await
+            .map_err(|err| match err {
+                EnvironmentError::EnvironmentNotFound(_) => {
+                    OplogExportSinkError::ParentEnvironmentNotFound(environment_id)
+                }
+                other => other.into(),
+            })
+    }
+
+    async fn get_with_environment(
+        &self,
+        oplog_export_sink_id: OplogExportSinkId,
+        auth: &AuthCtx,
+    ) -> Result<(StoredOplogExportSink, EnvironmentOwnerPattern), OplogExportSinkError> {
+        let record = self
+            .oplog_export_sink_repo
+            .get_by_id(oplog_export_sink_id.0)
+            .await?
+            .ok_or(OplogExportSinkError::OplogExportSinkNotFound(
+                oplog_export_sink_id,
+            ))?;
+
+        let owner = environment_owner_from_oplog_export_sink(&record);
+        let sink: StoredOplogExportSink = record.oplog_export_sink.try_into()?;
+
+        authorize_environment_permission_for_owner(auth, owner.clone(), EnvironmentVerb::View)
+            .map_err(|_| OplogExportSinkError::OplogExportSinkNotFound(oplog_export_sink_id))?;
+
+        Ok((sink, owner))
+    }
+}
+
+fn environment_owner_from_oplog_export_sink(
+    sink: &OplogExportSinkAuthExtRevisionRecord,
+) -> EnvironmentOwnerPattern {
+    EnvironmentOwnerPattern::Environment {
+        account: AccountEmail::new(sink.owner_account_email.clone()),
+        application: ApplicationName(sink.application_name.clone()),
+        environment: EnvironmentName(sink.environment_name.clone()),
+    }
+}
+
+fn authorize_environment_permission(
+    auth: &AuthCtx,
+    environment: &Environment,
+    verb: EnvironmentVerb,
+) -> Result<(), AuthorizationError> {
+    authorize_environment_permission_for_owner(
+        auth,
+        EnvironmentOwnerPattern::Environment {
+            account: environment.owner_account_email.clone(),
+            application: environment.application_name.clone(),
+            environment: environment.name.clone(),
+        },
+        verb,
+    )
+}
+
+fn authorize_environment_permission_for_owner(
+    auth: &AuthCtx,
+    owner: EnvironmentOwnerPattern,
+    verb: EnvironmentVerb,
+) -> Result<(), AuthorizationError> {
+    auth.authorize_permission(&PermissionTarget::Environment(ClassPermissionTarget {
+        verb: Some(verb),
+        owner,
+        resource: EnvironmentResourcePattern::Any,
+    }))
+}
+
+/// The name is part of the executors' checkpoint keys and of the exported file paths
+fn validate_name(name: &str) -> Result<(), OplogExportSinkError> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(OplogExportSinkError::InvalidName(name.to_string()))
+    }
+}
+
+fn target_json(target: &OplogExportTarget) -> Result<String, OplogExportSinkError> {
+    if let OplogExportTarget::Http(http) = target {
+        let url = url::Url::parse(&http.url)
+            .map_err(|err| OplogExportSinkError::InvalidTarget(format!("{}: {err}", http.url)))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(OplogExportSinkError::InvalidTarget(format!(
+                "{}: only http and https URLs are supported",
+                http.url
+            )));
+        }
+        if http.timeout_ms == 0 {
+            return Err(OplogExportSinkError::InvalidTarget(
+                "the HTTP timeout must be positive".to_string(),
+            ));
+        }
+    }
+    serde_json::to_string(target).map_err(|err| OplogExportSinkError::InternalError(err.into()))
+}
@@ -24,7 +24,8 @@ use golem_common::model::card::{
     PermissionTarget,
 };
 use golem_common::model::plugin_registration::{
-    OplogProcessorPluginSpec, PluginRegistrationCreation, PluginRegistrationId, PluginSpecDto,
+    InvocationInterceptorPluginSpec, OplogProcessorPluginSpec, PluginRegistrationCreation,
+    PluginRegistrationId, PluginSpecDto,
 };
 use golem_common::{SafeDisplay, error_forwarding};
 use golem_service_base::model::auth::{AuthCtx, AuthorizationError};
@@ -38,6 +39,8 @@ pub enum PluginRegistrationError {
     PluginRegistrationNotFound(PluginRegistrationId),
     #[error("Target component for oplog processor does not exist")]
     OplogProcessorComponentDoesNotExist,
+    #[error("Target component for invocation interceptor does not exist")]
+    InvocationInterceptorComponentDoesNotExist,
     #[error("Plugin with this name and version already exists")]
     PluginNameAndVersionAlreadyExists,
     #[error("Parent account {0} not found")]
@@ -53,6 +56,7 @@ impl SafeDisplay for PluginRegistrationError {
         match self {
             Self::PluginRegistrationNotFound(_) => self.to_string(),
             Self::OplogProcessorComponentDoesNotExist => self.to_string(),
+            Self::InvocationInterceptorComponentDoesNotExist => self.to_string(),
             Self::PluginNameAndVersionAlreadyExists => self.to_string(),
             Self::ParentAccountNotFound(_) => self.to_string(),
             Self::Unauthorized(inner) => inner.to_safe_string(),
@@ -116,6 +120,11 @@ impl PluginRegistrationService {
                 self.validate_oplog_processor_plugin(&inner, auth).await?;
                 PluginSpec::OplogProcessor(inner)
             }
+            PluginSpecDto::InvocationInterceptor(inner) => {
+                self.validate_invocation_interceptor_plugin(&inner, auth)
+                    .await?;
+                PluginSpec::InvocationInterceptor(inner)
+            }
         };
 
         let id = PluginRegistrationId::new();
@@ -281,6 +290,34 @@ impl PluginRegistrationService {
 
         Ok(())
     }
+
+    async fn validate_invocation_interceptor_plugin(
+        &self,
+        definition: &InvocationInterceptorPluginSpec,
+        auth: &AuthCtx,
+    ) -> Result<(), PluginRegistrationError> {
+        let component = self
+            .component_service
+            .get_component_revision(
+                definition.component_id,
+                definition.component_revision,
+                false,
+                auth,
+            )
+            .await
+            .map_err(|err| match err {
+                ComponentError::ComponentNotFound(_) => {
+                    PluginRegistrationError::InvocationInterceptorComponentDoesNotExist
+                }
+                other => other.into(),
+            })?;
+
+        if !component.metadata.has_invocation_interceptor() {
+            return Err(PluginRegistrationError::InvocationInterceptorComponentDoesNotExist);
+        }
+
+        Ok(())
+    }
 }
 
 fn authorize_account_plugin_permission(
@@ -19,8 +19,9 @@ use golem_api_grpc::proto::golem::registry::v1::AgentSecretChangedEvent;
 use golem_api_grpc::proto::golem::registry::v1::{
     AccountTokensInvalidatedEvent, ApplicationDeletedEvent, CardRevokedEvent, CursorExpiredEvent,
     DeploymentChangedEvent, DomainRegistrationChangedEvent, EnvironmentDeletedEvent,
-    EnvironmentPermissionsChangedEvent, RegistryInvalidationEvent, ResourceDefinitionChangedEvent,
-    RetryPolicyChangedEvent, SecuritySchemeChangedEvent, registry_invalidation_event::Payload,
+    EnvironmentPermissionsChangedEvent, OplogExportSinkChangedEvent, RegistryInvalidationEvent,
+    ResourceDefinitionChangedEvent, RetryPolicyChangedEvent, SecuritySchemeChangedEvent,
+    registry_invalidation_event::Payload,
 };
 use golem_common::model::account::AccountId;
 use golem_common::model::application::ApplicationId;
@@ -419,6 +420,15 @@ fn to_registry_invalidation_event(event: &RegistryChangeEvent) -> RegistryInvali
                 card_ids: card_ids.iter().copied().map(Into::into).collect(),
             }),
         ),
+        RegistryChangeEvent::OplogExportSinkChanged {
+            event_id,
+            environment_id,
+        } => (
+            *event_id,
+            Payload::OplogExportSinkChanged(OplogExportSinkChangedEvent {
+                environment_id: Some(EnvironmentId(*environment_id).into()),
+            }),
+        ),
     };
     RegistryInvalidationEvent {
         event_id: event_id.0 as u64,
@@ -895,6 +895,8 @@ pub async fn test_http_api_deployment_stage(deps: &Deps) {
             )]),
             webhooks_prefix: "/webhooks/".to_string(),
             openapi_endpoint_prefix: "/".to_string(),
+            rate_limits: Vec::new(),
+            graphql_endpoint: None,
         }),
     }
     .with_updated_hash()
@@ -1513,6 +1515,42 @@ pub async fn test_resolve_agent_type_unknown_email_returns_none(deps: &Deps) {
     assert!(result.is_none());
 }
 
+/// Lists the agent types of the latest deployment, and nothing for a missing revision
+pub async fn test_list_agent_types_by_names(deps: &Deps) {
+    let env = setup_resolve_env(deps).await;
+
+    let records = deps
+        .full_deployment_repo
+        .list_agent_types_by_names(
+            env.owner_account_id,
+            &env.app_name,
+            &env.env_name,
+            None, // latest deployment
+            None,
+        )
+        .await
+        .unwrap();
+
+    let_assert!([record] = records.as_slice());
+    check!(record.agent_type_name == env.agent_type_name);
+    check!(record.environment_id == env.environment_id);
+    check!(record.deployment_revision_id == env.deployment_revision_id);
+
+    let records = deps
+        .full_deployment_repo
+        .list_agent_types_by_names(
+            env.owner_account_id,
+            &env.app_name,
+            &env.env_name,
+            Some(9999), // non-existent revision
+            None,
+        )
+        .await
+        .unwrap();
+
+    check!(records.is_empty());
+}
+
 pub async fn test_mcp_deployment_create_and_update(deps: &Deps) {
     let user = deps.create_account().await;
     let app = deps.create_application(user.revision.account_id).await;
@@ -361,6 +361,11 @@ async fn test_resolve_agent_type_unknown_email_returns_none(
     crate::repo::common::test_resolve_agent_type_unknown_email_returns_none(deps).await;
 }
 
+#[test]
+async fn test_list_agent_types_by_names(#[dimension(postgres_variant)] deps: &Deps) {
+    crate::repo::common::test_list_agent_types_by_names(deps).await;
+}
+
 #[test]
 async fn test_mcp_deployment_create_and_update(#[dimension(postgres_variant)] deps: &Deps) {
     crate::repo::common::test_mcp_deployment_create_and_update(deps).await;
@@ -35,6 +35,7 @@ pub enum ApiTags {
     /// The login endpoints are implementing an OAuth2 flow.
     Login,
     Me,
+    OplogExportSinks,
     Plugin,
     PermissionShares,
     Reports,
@@ -27,16 +27,19 @@ use golem_api_grpc::proto::golem::registry::v1::{
     GetActiveMcpForDomainRequest, GetActiveRoutesForDomainRequest, GetAgentTypeRequest,
     GetAllAgentTypesRequest, GetAllDeployedComponentRevisionsRequest, GetComponentMetadataRequest,
     GetCurrentEnvironmentStateRequest, GetDeployedComponentMetadataRequest,
-    GetResourceDefinitionByIdRequest, GetResourceDefinitionByNameRequest, GetResourceLimitsRequest,
-    ResolveAgentTypeByNamesRequest, ResolveComponentRequest, UpdateWorkerConnectionLimitRequest,
-    authenticate_token_response, batch_update_resource_usage_response, download_component_response,
+    GetDeployedComponentsRequest, GetResourceDefinitionByIdRequest,
+    GetResourceDefinitionByNameRequest, GetResourceLimitsRequest, ListAgentTypesByNamesRequest,
+    ResolveAgentTypeByNamesRequest, ResolveAgentTypeByNamesSuccessResponse,
+    ResolveComponentRequest, UpdateWorkerConnectionLimitRequest, authenticate_token_response,
+    batch_update_resource_usage_response, download_component_response,
     get_active_mcp_for_domain_response, get_active_routes_for_domain_response,
     get_agent_type_response, get_all_agent_types_response,
     get_all_deployed_component_revisions_response, get_component_metadata_response,
     get_current_environment_state_response, get_deployed_component_metadata_response,
-    get_resource_definition_by_id_response, get_resource_definition_by_name_response,
-    get_resource_limits_response, resolve_agent_type_by_names_response, resolve_component_response,
-    update_worker_connection_limit_response,
+    get_deployed_components_response, get_resource_definition_by_id_response,
+    get_resource_definition_by_name_response, get_resource_limits_response,
+    list_agent_types_by_names_response, resolve_agent_type_by_names_response,
+    resolve_component_response, update_worker_connection_limit_response,
 };
 use golem_common::config::{ConfigExample, HasConfigExamples};
 use golem_common::model::AgentId;
@@ -132,6 +135,12 @@ pub trait RegistryService: Send + Sync {
         component_id: ComponentId,
     ) -> Result<Vec<Component>, RegistryServiceError>;
 
+    // will only return non-deleted components
+    async fn get_deployed_components(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<Component>, RegistryServiceError>;
+
     // will only return non-deleted components
     async fn resolve_component(
         &self,
@@ -167,6 +176,16 @@ pub trait RegistryService: Send + Sync {
         auth_ctx: &AuthCtx,
     ) -> Result<ResolvedAgentType, RegistryServiceError>;
 
+    // will leave out agent types the caller is not allowed to see
+    async fn list_agent_types_by_names(
+        &self,
+        app_name: &ApplicationName,
+        environment_name: &EnvironmentName,
+        deployment_revision: Option<DeploymentRevision>,
+        owner_account_email: Option<&str>,
+        auth_ctx: &AuthCtx,
+    ) -> Result<Vec<ResolvedAgentType>, RegistryServiceError>;
+
     async fn get_active_routes_for_domain(
         &self,
         domain: &Domain,
@@ -674,6 +693,36 @@ impl RegistryService for GrpcRegistryService {
         }
     }
 
+    async fn get_deployed_components(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<Component>, RegistryServiceError> {
+        let response = self
+            .client
+            .call("get_deployed_components", move |client| {
+                let request = GetDeployedComponentsRequest {
+                    environment_id: Some(environment_id.into()),
+                };
+
+                Box::pin(client.get_deployed_components(request))
+            })
+            .await?
+            .into_inner();
+
+        match response.result {
+            None => Err(RegistryServiceError::empty_response()),
+            Some(get_deployed_components_response::Result::Success(payload)) => {
+                let converted = payload
+                    .components
+                    .into_iter()
+                    .map(Component::try_from)
+                    .collect::<Result<_, _>>()?;
+                Ok(converted)
+            }
+            Some(get_deployed_components_response::Result::Error(error)) => Err(error.into()),
+        }
+    }
+
     async fn resolve_component(
         &self,
         resolving_account_id: AccountId,
@@ -805,32 +854,46 @@ impl RegistryService for GrpcRegistryService {
         match response.result {
             None => Err(RegistryServiceError::empty_response()),
             Some(resolve_agent_type_by_names_response::Result::Success(payload)) => {
-                let registered_agent_type = payload
-                    .agent_type
-                    .ok_or("missing agent_type field")?
-                    .try_into()?;
-                let environment_id = payload
-                    .environment_id
-                    .ok_or("missing environment_id field")?
-                    .try_into()?;
-                let deployment_revision = DeploymentRevision::try_from(payload.deployment_revision)
-                    .map_err(|e: String| RegistryServiceError::internal_client_error(e))?;
-                let current_deployment_revision = payload
-                    .current_deployment_revision
-                    .map(CurrentDeploymentRevision::try_from)
-                    .transpose()
-                    .map_err(|e: String| RegistryServiceError::internal_client_error(e))?;
-                Ok(ResolvedAgentType {
-                    registered_agent_type,
-                    environment_id,
-                    deployment_revision,
-                    current_deployment_revision,
-                })
+                proto_resolved_agent_type_to_model(payload)
             }
             Some(resolve_agent_type_by_names_response::Result::Error(error)) => Err(error.into()),
         }
     }
 
+    async fn list_agent_types_by_names(
+        &self,
+        app_name: &ApplicationName,
+        environment_name: &EnvironmentName,
+        deployment_revision: Option<DeploymentRevision>,
+        owner_account_email: Option<&str>,
+        auth_ctx: &AuthCtx,
+    ) -> Result<Vec<ResolvedAgentType>, RegistryServiceError> {
+        let response = self
+            .client
+            .call("list_agent_types_by_names", move |client| {
+                let request = ListAgentTypesByNamesRequest {
+                    app_name: app_name.0.clone(),
+                    environment_name: environment_name.0.clone(),
+                    deployment_revision: deployment_revision.map(|r| r.get()),
+                    owner_account_email: owner_account_email.map(|e| e.to_string()),
+                    auth_ctx: Some(auth_ctx.clone().into()),
+                };
+                Box::pin(client.list_agent_types_by_names(request))
+            })
+            .await?
+            .into_inner();
+
+        match response.result {
+            None => Err(RegistryServiceError::empty_response()),
+            Some(list_agent_types_by_names_response::Result::Success(payload)) => payload
+                .agent_types
+                .into_iter()
+                .map(proto_resolved_agent_type_to_model)
+                .collect(),
+            Some(list_agent_types_by_names_response::Result::Error(error)) => Err(error.into()),
+        }
+    }
+
     async fn get_active_routes_for_domain(
         &self,
         domain: &Domain,
@@ -1012,6 +1075,32 @@ impl RegistryService for GrpcRegistryService {
     }
 }
 
+fn proto_resolved_agent_type_to_model(
+    payload: ResolveAgentTypeByNamesSuccessResponse,
+) -> Result<ResolvedAgentType, RegistryServiceError> {
+    let registered_agent_type = payload
+        .agent_type
+        .ok_or("missing agent_type field")?
+        .try_into()?;
+    let environment_id = payload
+        .environment_id
+        .ok_or("missing environment_id field")?
+        .try_into()?;
+    let deployment_revision = DeploymentRevision::try_from(payload.deployment_revision)
+        .map_err(|e: String| RegistryServiceError::internal_client_error(e))?;
+    let current_deployment_revision = payload
+        .current_deployment_revision
+        .map(CurrentDeploymentRevision::try_from)
+        .transpose()
+        .map_err(|e: String| RegistryServiceError::internal_client_error(e))?;
+    Ok(ResolvedAgentType {
+        registered_agent_type,
+        environment_id,
+        deployment_revision,
+        current_deployment_revision,
+    })
+}
+
 fn proto_registry_event_to_model(
     event: golem_api_grpc::proto::golem::registry::v1::RegistryInvalidationEvent,
 ) -> Result<golem_common::model::agent::RegistryInvalidationEvent, RegistryServiceError> {
@@ -1247,6 +1336,23 @@ fn proto_registry_event_to_model(
                 },
             )
         }
+        Some(Payload::OplogExportSinkChanged(oesc)) => {
+            let environment_id = oesc
+                .environment_id
+                .ok_or_else(|| {
+                    RegistryServiceError::internal_client_error(
+                        "Missing environment_id in OplogExportSinkChanged",
+                    )
+                })?
+                .try_into()
+                .map_err(|e: String| RegistryServiceError::internal_client_error(e))?;
+            Ok(
+                golem_common::model::agent::RegistryInvalidationEvent::OplogExportSinkChanged {
+                    event_id,
+                    environment_id,
+                },
+            )
+        }
         None => Err(RegistryServiceError::internal_client_error(
             "Missing payload in RegistryInvalidationEvent",
         )),
@@ -19,10 +19,13 @@ use crate::model::SafeIndex;
 use base64::Engine;
 use desert_rust::BinaryCodec;
 use golem_common::model::account::{AccountEmail, AccountId};
-use golem_common::model::agent::{AgentTypeName, DataSchema, HttpMethod, ReadOnlyConfig};
+use golem_common::model::agent::{
+    AgentType, AgentTypeName, DataSchema, HttpMethod, ReadOnlyConfig,
+};
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::deployment::DeploymentRevision;
 use golem_common::model::environment::EnvironmentId;
+use golem_common::model::http_api_deployment::HttpApiRateLimit;
 use golem_common::model::security_scheme::{Provider, SecuritySchemeId, SecuritySchemeName};
 use golem_common::model::{AgentId, OplogIndex, PromiseId};
 use golem_wasm::analysis::analysed_type;
@@ -198,6 +201,55 @@ pub enum RequestBodySchema {
     RestrictedBinary { allowed_mime_types: Vec<String> },
     UnrestrictedText,
     RestrictedText { allowed_language_codes: Vec<String> },
+    FormBody { fields: Vec<FormFieldSchema> },
+}
+
+impl RequestBodySchema {
+    /// The fields of the body when it is sent as `multipart/form-data` or
+    /// `application/x-www-form-urlencoded`. Form bodies mix component-model and unstructured
+    /// parameters; JSON object bodies are accepted as forms too, as long as all of their fields
+    /// can be parsed from form field values.
+    pub fn form_fields(&self) -> Option<Vec<FormFieldSchema>> {
+        match self {
+            Self::FormBody { fields } => Some(fields.clone()),
+            Self::JsonBody {
+                expected_type: AnalysedType::Record(record),
+            } => record
+                .fields
+                .iter()
+                .map(|field| {
+                    QueryOrHeaderType::try_from(field.typ.clone())
+                        .ok()
+                        .map(|field_type| FormFieldSchema {
+                            name: field.name.clone(),
+                            field_type: FormFieldType::Value(field_type),
+                        })
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub struct FormFieldSchema {
+    pub name: String,
+    pub field_type: FormFieldType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub enum FormFieldType {
+    Value(QueryOrHeaderType),
+    /// A file part; empty `allowed_mime_types` means any mime type is accepted
+    UnstructuredBinary {
+        allowed_mime_types: Vec<String>,
+    },
+    /// A text part; empty `allowed_language_codes` means any language is accepted
+    UnstructuredText {
+        allowed_language_codes: Vec<String>,
+    },
 }
 
 #[derive(Debug, Clone, BinaryCodec)]
@@ -229,6 +281,9 @@ pub enum MethodParameter {
     },
     UnstructuredBinaryBody,
     UnstructuredTextBody,
+    FormField {
+        field_index: SafeIndex,
+    },
 }
 
 #[derive(Debug)]
@@ -251,6 +306,29 @@ pub struct CompiledRoute {
     pub behavior: RouteBehaviour,
     pub security: RouteSecurity,
     pub cors: CorsOptions,
+    pub rate_limits: Vec<RouteRateLimit>,
+}
+
+/// Level of the http api deployment a rate limit was declared on.
+/// Routes sharing a scope share the request budget of the limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec)]
+#[desert(evolution())]
+pub enum RateLimitScope {
+    Deployment,
+    AgentType {
+        agent_type: AgentTypeName,
+    },
+    Method {
+        agent_type: AgentTypeName,
+        method_name: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec)]
+#[desert(evolution())]
+pub struct RouteRateLimit {
+    pub scope: RateLimitScope,
+    pub limit: HttpApiRateLimit,
 }
 
 #[derive(Debug, BinaryCodec)]
@@ -260,9 +338,10 @@ pub enum RouteBehaviour {
     CorsPreflight(CorsPreflightBehaviour),
     WebhookCallback(WebhookCallbackBehaviour),
     OpenApiSpec(OpenApiSpecBehaviour),
+    GraphQl(GraphQlBehaviour),
 }
 
-#[derive(Debug, BinaryCodec)]
+#[derive(Debug, Clone, BinaryCodec)]
 #[desert(evolution())]
 pub struct CallAgentBehaviour {
     pub component_id: ComponentId,
@@ -311,6 +390,24 @@ pub struct OpenApiSpecBehaviour {
     pub format: OpenApiSpecFormat,
 }
 
+/// GraphQL endpoint generated from the agent types of a deployment.
+#[derive(Debug, Clone, BinaryCodec)]
+#[desert(evolution())]
+pub struct GraphQlBehaviour {
+    pub agents: Vec<GraphQlAgent>,
+}
+
+#[derive(Debug, Clone, BinaryCodec)]
+#[desert(evolution())]
+pub struct GraphQlAgent {
+    pub component_id: ComponentId,
+    pub component_revision: ComponentRevision,
+    pub agent_type: AgentType,
+    /// Agent and method level rate limits, enforced when the method fields are resolved.
+    /// The deployment level ones are enforced by the GraphQL route itself.
+    pub rate_limits: Vec<RouteRateLimit>,
+}
+
 #[derive(Debug, Clone, Copy, BinaryCodec)]
 #[desert(evolution())]
 pub enum OpenApiSpecFormat {
@@ -13,18 +13,20 @@
 // limitations under the License.
 
 use super::{
-    CompiledRoute, CompiledRoutes, OpenApiSpecBehaviour, OpenApiSpecFormat, RouteSecurity,
+    CompiledRoute, CompiledRoutes, GraphQlAgent, GraphQlBehaviour, OpenApiSpecBehaviour,
+    OpenApiSpecFormat, RateLimitScope, RouteRateLimit, RouteSecurity,
 };
 use super::{CorsOptions, SecuritySchemeDetails};
 use super::{PathSegment, PathSegmentType, RequestBodySchema, RouteBehaviour};
 use crate::custom_api::{
     CallAgentBehaviour, ConstructorParameter, CorsPreflightBehaviour, CorsPreflightMethodPolicy,
-    MethodParameter, OriginPattern, QueryOrHeaderType, SecuritySchemeRouteSecurity,
-    SessionFromHeaderRouteSecurity, WebhookCallbackBehaviour,
+    FormFieldSchema, FormFieldType, MethodParameter, OriginPattern, QueryOrHeaderType,
+    SecuritySchemeRouteSecurity, SessionFromHeaderRouteSecurity, WebhookCallbackBehaviour,
 };
 use golem_api_grpc::proto;
 use golem_common::model::account::AccountEmail;
 use golem_common::model::agent::AgentTypeName;
+use golem_common::model::http_api_deployment::{HttpApiRateLimit, HttpApiRateLimitKey};
 use golem_common::model::security_scheme::{Provider, SecuritySchemeName};
 use golem_wasm::analysis::TypeEnum;
 use http::HeaderName;
@@ -147,6 +149,11 @@ impl TryFrom<proto::golem::customapi::CompiledRoute> for CompiledRoute {
             behavior: value.behavior.ok_or("Missing behavior")?.try_into()?,
             security: value.security.ok_or("Missing security")?.try_into()?,
             cors: value.cors.ok_or("Missing cors")?.try_into()?,
+            rate_limits: value
+                .rate_limits
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -161,6 +168,116 @@ impl From<CompiledRoute> for proto::golem::customapi::CompiledRoute {
             behavior: Some(value.behavior.into()),
             security: Some(value.security.into()),
             cors: Some(value.cors.into()),
+            rate_limits: value.rate_limits.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::golem::customapi::RouteRateLimit> for RouteRateLimit {
+    type Error = String;
+
+    fn try_from(value: proto::golem::customapi::RouteRateLimit) -> Result<Self, Self::Error> {
+        Ok(Self {
+            scope: value.scope.ok_or("Missing scope")?.try_into()?,
+            limit: value.limit.ok_or("Missing limit")?.try_into()?,
+        })
+    }
+}
+
+impl From<RouteRateLimit> for proto::golem::customapi::RouteRateLimit {
+    fn from(value: RouteRateLimit) -> Self {
+        Self {
+            scope: Some(value.scope.into()),
+            limit: Some(value.limit.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::golem::customapi::RateLimitScope> for RateLimitScope {
+    type Error = String;
+
+    fn try_from(value: proto::golem::customapi::RateLimitScope) -> Result<Self, Self::Error> {
+        use proto::golem::customapi::rate_limit_scope::Kind;
+
+        match value.kind.ok_or("RateLimitScope.kind missing")? {
+            Kind::Deployment(_) => Ok(RateLimitScope::Deployment),
+            Kind::AgentType(agent_type) => Ok(RateLimitScope::AgentType {
+                agent_type: AgentTypeName(agent_type.agent_type),
+            }),
+            Kind::Method(method) => Ok(RateLimitScope::Method {
+                agent_type: AgentTypeName(method.agent_type),
+                method_name: method.method_name,
+            }),
+        }
+    }
+}
+
+impl From<RateLimitScope> for proto::golem::customapi::RateLimitScope {
+    fn from(value: RateLimitScope) -> Self {
+        use proto::golem::customapi::rate_limit_scope;
+        use proto::golem::customapi::rate_limit_scope::Kind;
+
+        let kind = match value {
+            RateLimitScope::Deployment => Kind::Deployment(rate_limit_scope::Deployment {}),
+            RateLimitScope::AgentType { agent_type } => {
+                Kind::AgentType(rate_limit_scope::AgentType {
+                    agent_type: agent_type.0,
+                })
+            }
+            RateLimitScope::Method {
+                agent_type,
+                method_name,
+            } => Kind::Method(rate_limit_scope::Method {
+                agent_type: agent_type.0,
+                method_name,
+            }),
+        };
+
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<proto::golem::customapi::HttpApiRateLimit> for HttpApiRateLimit {
+    type Error = String;
+
+    fn try_from(value: proto::golem::customapi::HttpApiRateLimit) -> Result<Self, Self::Error> {
+        use proto::golem::customapi::HttpApiRateLimitKey as GrpcHttpApiRateLimitKey;
+
+        let key = match value.key() {
+            GrpcHttpApiRateLimitKey::ClientIp => HttpApiRateLimitKey::ClientIp,
+            GrpcHttpApiRateLimitKey::Principal => HttpApiRateLimitKey::Principal,
+            GrpcHttpApiRateLimitKey::ApiKey => HttpApiRateLimitKey::ApiKey,
+            GrpcHttpApiRateLimitKey::Unknown => {
+                return Err("Unknown HttpApiRateLimitKey".to_string());
+            }
+        };
+
+        Ok(Self {
+            key,
+            period: value.period().try_into()?,
+            api_key_header: value.api_key_header,
+            value: value.value,
+            max: value.max,
+        })
+    }
+}
+
+impl From<HttpApiRateLimit> for proto::golem::customapi::HttpApiRateLimit {
+    fn from(value: HttpApiRateLimit) -> Self {
+        use proto::golem::customapi::HttpApiRateLimitKey as GrpcHttpApiRateLimitKey;
+
+        let key = match value.key {
+            HttpApiRateLimitKey::ClientIp => GrpcHttpApiRateLimitKey::ClientIp,
+            HttpApiRateLimitKey::Principal => GrpcHttpApiRateLimitKey::Principal,
+            HttpApiRateLimitKey::ApiKey => GrpcHttpApiRateLimitKey::ApiKey,
+        };
+
+        Self {
+            key: key.into(),
+            api_key_header: value.api_key_header,
+            value: value.value,
+            period: proto::golem::common::TimePeriod::from(value.period).into(),
+            max: value.max,
         }
     }
 }
@@ -248,6 +365,27 @@ impl TryFrom<proto::golem::customapi::RouteBehaviour> for RouteBehaviour {
 
                 Ok(RouteBehaviour::OpenApiSpec(OpenApiSpecBehaviour { format }))
             }
+            Kind::GraphQl(graph_ql) => Ok(RouteBehaviour::GraphQl(GraphQlBehaviour {
+                agents: graph_ql
+                    .agents
+                    .into_iter()
+                    .map(|agent| {
+                        Ok::<_, String>(GraphQlAgent {
+                            component_id: agent
+                                .component_id
+                                .ok_or("Missing component_id")?
+                                .try_into()?,
+                            component_revision: agent.component_revision.try_into()?,
+                            agent_type: agent.agent_type.ok_or("Missing agent_type")?.try_into()?,
+                            rate_limits: agent
+                                .rate_limits
+                                .into_iter()
+                                .map(TryInto::try_into)
+                                .collect::<Result<_, _>>()?,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            })),
         }
     }
 }
@@ -334,6 +472,27 @@ impl From<RouteBehaviour> for proto::golem::customapi::RouteBehaviour {
                     )),
                 }
             }
+            RouteBehaviour::GraphQl(GraphQlBehaviour { agents }) => Self {
+                kind: Some(Kind::GraphQl(
+                    proto::golem::customapi::route_behaviour::GraphQl {
+                        agents: agents
+                            .into_iter()
+                            .map(|agent| {
+                                proto::golem::customapi::route_behaviour::graph_ql::Agent {
+                                    component_id: Some(agent.component_id.into()),
+                                    component_revision: agent.component_revision.into(),
+                                    agent_type: Some(agent.agent_type.into()),
+                                    rate_limits: agent
+                                        .rate_limits
+                                        .into_iter()
+                                        .map(Into::into)
+                                        .collect(),
+                                }
+                            })
+                            .collect(),
+                    },
+                )),
+            },
         }
     }
 }
@@ -467,6 +626,10 @@ impl TryFrom<proto::golem::customapi::MethodParameter> for MethodParameter {
             Kind::UnstructuredBinaryBody(_) => Ok(MethodParameter::UnstructuredBinaryBody),
 
             Kind::UnstructuredTextBody(_) => Ok(MethodParameter::UnstructuredTextBody),
+
+            Kind::FormField(field) => Ok(MethodParameter::FormField {
+                field_index: field.field_index.into(),
+            }),
         }
     }
 }
@@ -531,6 +694,14 @@ impl From<MethodParameter> for proto::golem::customapi::MethodParameter {
                     proto::golem::customapi::method_parameter::UnstructuredTextBody {},
                 )),
             },
+
+            MethodParameter::FormField { field_index } => Self {
+                kind: Some(Kind::FormField(
+                    proto::golem::customapi::method_parameter::FormField {
+                        field_index: field_index.into(),
+                    },
+                )),
+            },
         }
     }
 }
@@ -560,6 +731,14 @@ impl TryFrom<proto::golem::customapi::RequestBodySchema> for RequestBodySchema {
             Kind::RestrictedText(body) => Ok(RequestBodySchema::RestrictedText {
                 allowed_language_codes: body.allowed_language_codes,
             }),
+
+            Kind::FormBody(body) => Ok(RequestBodySchema::FormBody {
+                fields: body
+                    .fields
+                    .into_iter()
+                    .map(FormFieldSchema::try_from)
+                    .collect::<Result<_, _>>()?,
+            }),
         }
     }
 }
@@ -612,6 +791,69 @@ impl From<RequestBodySchema> for proto::golem::customapi::RequestBodySchema {
                     },
                 )),
             },
+
+            RequestBodySchema::FormBody { fields } => Self {
+                kind: Some(Kind::FormBody(
+                    proto::golem::customapi::request_body_schema::FormBody {
+                        fields: fields.into_iter().map(Into::into).collect(),
+                    },
+                )),
+            },
+        }
+    }
+}
+
+impl TryFrom<proto::golem::customapi::FormFieldSchema> for FormFieldSchema {
+    type Error = String;
+
+    fn try_from(value: proto::golem::customapi::FormFieldSchema) -> Result<Self, Self::Error> {
+        use proto::golem::customapi::form_field_schema::FieldType;
+
+        let field_type = match value
+            .field_type
+            .ok_or("FormFieldSchema.field_type missing")?
+        {
+            FieldType::Value(value) => FormFieldType::Value(value.try_into()?),
+            FieldType::UnstructuredBinary(binary) => FormFieldType::UnstructuredBinary {
+                allowed_mime_types: binary.allowed_mime_types,
+            },
+            FieldType::UnstructuredText(text) => FormFieldType::UnstructuredText {
+                allowed_language_codes: text.allowed_language_codes,
+            },
+        };
+
+        Ok(Self {
+            name: value.name,
+            field_type,
+        })
+    }
+}
+
+impl From<FormFieldSchema> for proto::golem::customapi::FormFieldSchema {
+    fn from(value: FormFieldSchema) -> Self {
+        use proto::golem::customapi::form_field_schema::FieldType;
+
+        let field_type = match value.field_type {
+            FormFieldType::Value(value) => FieldType::Value(value.into()),
+            FormFieldType::UnstructuredBinary { allowed_mime_types } => {
+                FieldType::UnstructuredBinary(
+                    proto::golem::customapi::form_field_schema::UnstructuredBinary {
+                        allowed_mime_types,
+                    },
+                )
+            }
+            FormFieldType::UnstructuredText {
+                allowed_language_codes,
+            } => FieldType::UnstructuredText(
+                proto::golem::customapi::form_field_schema::UnstructuredText {
+                    allowed_language_codes,
+                },
+            ),
+        };
+
+        Self {
+            name: value.name,
+            field_type: Some(field_type),
         }
     }
 }
@@ -14,6 +14,7 @@
 
 use super::AgentDeploymentDetails;
 use super::agent_secret::AgentSecret;
+use super::oplog_export_sink::NamedOplogExportSink;
 use golem_common::model::agent::AgentTypeName;
 use golem_common::model::agent_secret::CanonicalAgentSecretPath;
 use golem_common::model::retry_policy::NamedRetryPolicy;
@@ -26,6 +27,7 @@ pub struct EnvironmentState {
     pub agent_deployment_details: HashMap<AgentTypeName, AgentDeploymentDetails>,
     pub agent_secrets: HashMap<CanonicalAgentSecretPath, AgentSecret>,
     pub retry_policies: Vec<NamedRetryPolicy>,
+    pub oplog_export_sinks: Vec<NamedOplogExportSink>,
 }
 
 impl From<EnvironmentState> for golem_api_grpc::proto::golem::registry::EnvironmentState {
@@ -38,6 +40,11 @@ impl From<EnvironmentState> for golem_api_grpc::proto::golem::registry::Environm
                 .collect(),
             agent_secrets: value.agent_secrets.into_values().map(Into::into).collect(),
             retry_policies: value.retry_policies.into_iter().map(Into::into).collect(),
+            oplog_export_sinks: value
+                .oplog_export_sinks
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }
     }
 }
@@ -59,6 +66,12 @@ impl TryFrom<golem_api_grpc::proto::golem::registry::EnvironmentState> for Envir
             .map(TryInto::try_into)
             .collect::<Result<Vec<_>, _>>()?;
 
+        let oplog_export_sinks = value
+            .oplog_export_sinks
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Self {
             agent_deployment_details: value
                 .agent_deployment_details
@@ -68,6 +81,7 @@ impl TryFrom<golem_api_grpc::proto::golem::registry::EnvironmentState> for Envir
                 .collect(),
             agent_secrets,
             retry_policies,
+            oplog_export_sinks,
         })
     }
 }
@@ -16,6 +16,7 @@ pub mod agent_secret;
 pub mod auth;
 pub mod component;
 pub mod environment;
+pub mod oplog_export_sink;
 pub mod plugin_registration;
 pub mod quota_lease;
 pub mod retry_policy;
@@ -87,6 +88,9 @@ pub struct RevertWorkerResponse {}
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
 pub struct ForkWorkerResponse {}
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct ImportAgentResponse {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
 pub struct CancelInvocationResponse {
     pub canceled: bool,
@@ -0,0 +1,127 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_api_grpc::proto::golem::registry::oplog_export_sink::Target;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog_export::{
+    BlobStorageExportTarget, HttpExportTarget, JsonLinesFileExportTarget, OplogExportSinkDto,
+    OplogExportSinkId, OplogExportSinkRevision, OplogExportTarget, ParquetBlobStorageExportTarget,
+};
+
+#[derive(Debug, Clone)]
+pub struct StoredOplogExportSink {
+    pub id: OplogExportSinkId,
+    pub environment_id: EnvironmentId,
+    pub name: String,
+    pub revision: OplogExportSinkRevision,
+    pub target: OplogExportTarget,
+}
+
+impl From<StoredOplogExportSink> for OplogExportSinkDto {
+    fn from(value: StoredOplogExportSink) -> Self {
+        Self {
+            id: value.id,
+            environment_id: value.environment_id,
+            name: value.name,
+            revision: value.revision,
+            target: value.target,
+        }
+    }
+}
+
+/// An oplog export sink as seen by the executors exporting the oplogs of its environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedOplogExportSink {
+    pub name: String,
+    pub revision: OplogExportSinkRevision,
+    pub target: OplogExportTarget,
+}
+
+impl From<StoredOplogExportSink> for NamedOplogExportSink {
+    fn from(value: StoredOplogExportSink) -> Self {
+        Self {
+            name: value.name,
+            revision: value.revision,
+            target: value.target,
+        }
+    }
+}
+
+impl From<NamedOplogExportSink> for golem_api_grpc::proto::golem::registry::OplogExportSink {
+    fn from(value: NamedOplogExportSink) -> Self {
+        let target = match value.target {
+            OplogExportTarget::JsonLinesFile(JsonLinesFileExportTarget {}) => {
+                Target::JsonLinesFile(
+                    golem_api_grpc::proto::golem::registry::JsonLinesFileExportTarget {},
+                )
+            }
+            OplogExportTarget::BlobStorage(target) => Target::BlobStorage(
+                golem_api_grpc::proto::golem::registry::BlobStorageExportTarget {
+                    prefix: target.prefix,
+                },
+            ),
+            OplogExportTarget::ParquetBlobStorage(target) => Target::ParquetBlobStorage(
+                golem_api_grpc::proto::golem::registry::ParquetBlobStorageExportTarget {
+                    prefix: target.prefix,
+                },
+            ),
+            OplogExportTarget::Http(target) => {
+                Target::Http(golem_api_grpc::proto::golem::registry::HttpExportTarget {
+                    url: target.url,
+                    headers: target.headers.into_iter().collect(),
+                    timeout_ms: target.timeout_ms,
+                })
+            }
+        };
+        Self {
+            name: value.name,
+            revision: value.revision.into(),
+            target: Some(target),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::registry::OplogExportSink> for NamedOplogExportSink {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::registry::OplogExportSink,
+    ) -> Result<Self, Self::Error> {
+        let target = match value.target.ok_or("Missing target field")? {
+            Target::JsonLinesFile(_) => {
+                OplogExportTarget::JsonLinesFile(JsonLinesFileExportTarget {})
+            }
+            Target::BlobStorage(target) => {
+                OplogExportTarget::BlobStorage(BlobStorageExportTarget {
+                    prefix: target.prefix,
+                })
+            }
+            Target::ParquetBlobStorage(target) => {
+                OplogExportTarget::ParquetBlobStorage(ParquetBlobStorageExportTarget {
+                    prefix: target.prefix,
+                })
+            }
+            Target::Http(target) => OplogExportTarget::Http(HttpExportTarget {
+                url: target.url,
+                headers: target.headers.into_iter().collect(),
+                timeout_ms: target.timeout_ms,
+            }),
+        };
+        Ok(Self {
+            name: value.name,
+            revision: OplogExportSinkRevision::try_from(value.revision)?,
+            target,
+        })
+    }
+}
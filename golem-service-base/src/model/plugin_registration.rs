@@ -14,7 +14,9 @@
 
 use golem_common::model::account::AccountId;
 use golem_common::model::base64::Base64;
-use golem_common::model::plugin_registration::OplogProcessorPluginSpec;
+use golem_common::model::plugin_registration::{
+    InvocationInterceptorPluginSpec, OplogProcessorPluginSpec,
+};
 use golem_common::model::plugin_registration::{
     PluginRegistrationDto, PluginRegistrationId, PluginSpecDto,
 };
@@ -49,12 +51,14 @@ impl From<PluginRegistration> for PluginRegistrationDto {
 #[derive(Debug, Clone)]
 pub enum PluginSpec {
     OplogProcessor(OplogProcessorPluginSpec),
+    InvocationInterceptor(InvocationInterceptorPluginSpec),
 }
 
 impl From<PluginSpec> for PluginSpecDto {
     fn from(value: PluginSpec) -> Self {
         match value {
             PluginSpec::OplogProcessor(inner) => Self::OplogProcessor(inner),
+            PluginSpec::InvocationInterceptor(inner) => Self::InvocationInterceptor(inner),
         }
     }
 }
@@ -77,6 +77,7 @@ impl RegistryInvalidationHandler for ShardManagerRegistryInvalidationHandler {
             | RegistryInvalidationEvent::SecuritySchemeChanged { .. }
             | RegistryInvalidationEvent::RetryPolicyChanged { .. }
             | RegistryInvalidationEvent::AgentSecretChanged { .. }
+            | RegistryInvalidationEvent::OplogExportSinkChanged { .. }
             | RegistryInvalidationEvent::ApplicationDeleted { .. }
             | RegistryInvalidationEvent::EnvironmentDeleted { .. }
             | RegistryInvalidationEvent::CardRevoked { .. } => {}
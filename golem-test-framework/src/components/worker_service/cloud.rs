@@ -78,6 +78,10 @@ impl WorkerService for CloudWorkerService {
         panic!("mcp_port() is not available in cloud mode");
     }
 
+    fn agent_grpc_port(&self) -> u16 {
+        panic!("agent_grpc_port() is not available in cloud mode");
+    }
+
     async fn kill(&self) {}
 
     async fn base_http_client(&self) -> reqwest_middleware::ClientWithMiddleware {
@@ -47,6 +47,8 @@ pub trait WorkerService: Send + Sync {
 
     fn mcp_port(&self) -> u16;
 
+    fn agent_grpc_port(&self) -> u16;
+
     async fn kill(&self);
 
     async fn base_http_client(&self) -> reqwest_middleware::ClientWithMiddleware;
@@ -110,6 +112,8 @@ async fn env_vars(
     // Keep the MCP listener on a dedicated test port so it does not inherit the
     // production default and collide with other local Golem processes.
     let mcp_port = custom_request_port + 2;
+    // Same for the agent gRPC API, which is disabled by default
+    let agent_grpc_port = custom_request_port + 3;
 
     EnvVarBuilder::golem_service(verbosity)
         .with_str("GOLEM__BLOB_STORAGE__TYPE", "LocalFileSystem")
@@ -149,6 +153,8 @@ async fn env_vars(
             custom_request_port.to_string(),
         )
         .with("GOLEM__MCP_PORT", mcp_port.to_string())
+        .with_str("GOLEM__AGENT_GRPC__ENABLED", "true")
+        .with("GOLEM__AGENT_GRPC__PORT", agent_grpc_port.to_string())
         .with("GOLEM__GRPC__PORT", grpc_port.to_string())
         .with("GOLEM__PORT", http_port.to_string())
         .with(
@@ -24,6 +24,7 @@ pub struct ProvidedWorkerService {
     grpc_port: u16,
     custom_request_port: u16,
     mcp_port: u16,
+    agent_grpc_port: u16,
     base_http_client: OnceCell<reqwest_middleware::ClientWithMiddleware>,
 }
 
@@ -34,9 +35,10 @@ impl ProvidedWorkerService {
         grpc_port: u16,
         custom_request_port: u16,
         mcp_port: u16,
+        agent_grpc_port: u16,
     ) -> Self {
         info!(
-            "Using already running golem-worker-service on {host}, http port: {http_port}, grpc port: {grpc_port}, custom request port: {custom_request_port}, mcp port: {mcp_port}"
+            "Using already running golem-worker-service on {host}, http port: {http_port}, grpc port: {grpc_port}, custom request port: {custom_request_port}, mcp port: {mcp_port}, agent grpc port: {agent_grpc_port}"
         );
         Self {
             host: host.clone(),
@@ -44,6 +46,7 @@ impl ProvidedWorkerService {
             grpc_port,
             custom_request_port,
             mcp_port,
+            agent_grpc_port,
             base_http_client: OnceCell::new(),
         }
     }
@@ -76,6 +79,10 @@ impl WorkerService for ProvidedWorkerService {
         self.mcp_port
     }
 
+    fn agent_grpc_port(&self) -> u16 {
+        self.agent_grpc_port
+    }
+
     async fn base_http_client(&self) -> reqwest_middleware::ClientWithMiddleware {
         self.base_http_client
             .get_or_init(|| async { new_reqwest_client_with_tracing() })
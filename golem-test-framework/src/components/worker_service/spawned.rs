@@ -33,6 +33,7 @@ pub struct SpawnedWorkerService {
     grpc_port: u16,
     custom_request_port: u16,
     mcp_port: u16,
+    agent_grpc_port: u16,
     child: Arc<Mutex<Option<Child>>>,
     _logger: ChildProcessLogger,
     base_http_client: OnceCell<reqwest_middleware::ClientWithMiddleware>,
@@ -46,6 +47,7 @@ impl SpawnedWorkerService {
         grpc_port: u16,
         custom_request_port: u16,
         mcp_port: u16,
+        agent_grpc_port: u16,
         shard_manager: &Arc<dyn ShardManager>,
         rdb: &Arc<dyn Rdb>,
         redis: &Arc<dyn Redis>,
@@ -104,6 +106,7 @@ impl SpawnedWorkerService {
             grpc_port,
             custom_request_port,
             mcp_port,
+            agent_grpc_port,
             child: Arc::new(Mutex::new(Some(child))),
             _logger: logger,
             base_http_client: OnceCell::new(),
@@ -145,6 +148,10 @@ impl WorkerService for SpawnedWorkerService {
         self.mcp_port
     }
 
+    fn agent_grpc_port(&self) -> u16 {
+        self.agent_grpc_port
+    }
+
     async fn base_http_client(&self) -> reqwest_middleware::ClientWithMiddleware {
         self.base_http_client
             .get_or_init(|| async { new_reqwest_client_with_tracing() })
@@ -448,6 +448,7 @@ impl BenchmarkTestDependencies {
                 worker_service_grpc_port,
                 worker_service_custom_request_port,
                 worker_service_custom_request_port + 2,
+                worker_service_custom_request_port + 3,
                 &shard_manager,
                 &rdb,
                 &redis,
@@ -598,6 +599,7 @@ impl BenchmarkTestDependencies {
                         *worker_service_grpc_port,
                         *worker_service_custom_request_port,
                         *worker_service_custom_request_port + 2,
+                        *worker_service_custom_request_port + 3,
                     )
                     .await,
                 );
@@ -47,7 +47,7 @@ use golem_common::model::deployment::{CurrentDeployment, DeploymentCreation, Dep
 use golem_common::model::environment::{
     Environment, EnvironmentCreation, EnvironmentId, EnvironmentName,
 };
-use golem_common::model::oplog::PublicOplogEntryWithIndex;
+use golem_common::model::oplog::{AgentOplogSearchMatch, PublicOplogEntryWithIndex};
 use golem_common::model::worker::{
     AgentConfigEntryDto, AgentFileSystemNode, AgentMetadataDto, AgentUpdateMode, RevertWorkerTarget,
 };
@@ -392,6 +392,9 @@ impl<Deps: TestDependencies> TestDsl for TestUserContext<Deps> {
                     idempotency_key: None,
                     deployment_revision: None,
                     owner_account_email: None,
+                    deadline: None,
+                    at_oplog_index: None,
+                    at_timestamp: None,
                 },
             )
             .await
@@ -456,6 +459,9 @@ impl<Deps: TestDependencies> TestDsl for TestUserContext<Deps> {
                     idempotency_key: None,
                     deployment_revision: deployment_revision.map(i64::from),
                     owner_account_email: None,
+                    deadline: None,
+                    at_oplog_index: None,
+                    at_timestamp: None,
                 },
             )
             .await?;
@@ -977,6 +983,131 @@ impl<Deps: TestDependencies> TestDslExtended for TestUserContext<Deps> {
                 anyhow!("No deployment revision recorded for environment {environment_id}")
             })
     }
+
+    async fn search_component_oplogs(
+        &self,
+        component_id: &ComponentId,
+        query: &str,
+        max_results: Option<u64>,
+    ) -> anyhow::Result<Vec<AgentOplogSearchMatch>> {
+        let client = self
+            .deps
+            .worker_service()
+            .worker_http_client(&self.token)
+            .await;
+
+        let mut result = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = client
+                .search_component_oplogs(
+                    &component_id.0,
+                    query,
+                    None,
+                    None,
+                    None,
+                    cursor.as_deref(),
+                    Some(50),
+                    max_results,
+                )
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "search_component_oplogs failed for component {component_id}, query={query}: {e}"
+                    )
+                })?;
+
+            result.extend(response.matches);
+            match response.cursor {
+                None => break,
+                Some(next_cursor) => cursor = Some(next_cursor),
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn search_environment_oplogs(
+        &self,
+        environment_id: &EnvironmentId,
+        query: &str,
+    ) -> anyhow::Result<Vec<AgentOplogSearchMatch>> {
+        let client = self
+            .deps
+            .worker_service()
+            .worker_http_client(&self.token)
+            .await;
+
+        let mut result = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = client
+                .search_environment_oplogs(
+                    &environment_id.0,
+                    query,
+                    None,
+                    None,
+                    None,
+                    cursor.as_deref(),
+                    Some(50),
+                    None,
+                )
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "search_environment_oplogs failed for environment {environment_id}, query={query}: {e}"
+                    )
+                })?;
+
+            result.extend(response.matches);
+            match response.cursor {
+                None => break,
+                Some(next_cursor) => cursor = Some(next_cursor),
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn export_agent(&self, agent_id: &AgentId) -> anyhow::Result<Vec<u8>> {
+        let client = self
+            .deps
+            .worker_service()
+            .worker_http_client(&self.token)
+            .await;
+
+        let archive = client
+            .export_agent(&agent_id.component_id.0, &agent_id.agent_id)
+            .await
+            .map_err(|e| anyhow!("export_agent failed for {agent_id}: {e}"))?;
+
+        Ok(archive.to_vec())
+    }
+
+    async fn import_agent(
+        &self,
+        target_agent_id: &AgentId,
+        archive: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let client = self
+            .deps
+            .worker_service()
+            .worker_http_client(&self.token)
+            .await;
+
+        client
+            .import_agent(
+                &target_agent_id.component_id.0,
+                &target_agent_id.agent_id,
+                archive,
+            )
+            .await
+            .map_err(|e| anyhow!("import_agent failed for {target_agent_id}: {e}"))?;
+
+        Ok(())
+    }
 }
 
 struct HttpWorkerLogEventStream {
@@ -338,6 +338,7 @@ impl EnvBasedTestDependencies {
                 9092,
                 9093,
                 9095,
+                9096,
                 shard_manager,
                 rdb,
                 redis,
@@ -499,6 +500,7 @@ struct WorkerServiceDescriptor {
     grpc_port: u16,
     custom_request_port: u16,
     mcp_port: u16,
+    agent_grpc_port: u16,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -592,6 +594,7 @@ impl test_r::core::AsyncHostedDep for EnvBasedTestDependencies {
                 grpc_port: self.worker_service.gprc_port(),
                 custom_request_port: self.worker_service.custom_request_port(),
                 mcp_port: self.worker_service.mcp_port(),
+                agent_grpc_port: self.worker_service.agent_grpc_port(),
             },
             worker_executor_cluster: cluster_endpoints,
             registry_service: RegistryServiceDescriptor {
@@ -683,6 +686,7 @@ impl test_r::core::AsyncHostedDep for EnvBasedTestDependencies {
                 descriptor.worker_service.grpc_port,
                 descriptor.worker_service.custom_request_port,
                 descriptor.worker_service.mcp_port,
+                descriptor.worker_service.agent_grpc_port,
             )
             .await,
         );
@@ -1114,6 +1118,7 @@ mod tests {
                 grpc_port: 8004,
                 custom_request_port: 8005,
                 mcp_port: 8006,
+                agent_grpc_port: 8007,
             },
             worker_executor_cluster: vec![
                 WorkerExecutorDescriptor {
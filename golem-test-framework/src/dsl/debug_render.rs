@@ -123,6 +123,11 @@ pub fn debug_render_oplog_entry(entry: &PublicOplogEntry) -> String {
                     let _ = writeln!(result, "{pad}type:              manual update");
                     let _ = writeln!(result, "{pad}target revision:   {}", &inner.target_revision,);
                 }
+                PublicAgentInvocation::InterceptInvocation(inner) => {
+                    let _ = writeln!(result, "{pad}type:              intercept invocation");
+                    let _ = writeln!(result, "{pad}method:            {}", &inner.method_name);
+                    let _ = writeln!(result, "{pad}idempotency key:   {}", &inner.idempotency_key,);
+                }
             }
         }
         PublicOplogEntry::AgentInvocationFinished(params) => {
@@ -208,6 +213,20 @@ pub fn debug_render_oplog_entry(entry: &PublicOplogEntry) -> String {
                     &inner_params.target_revision,
                 );
             }
+            PublicAgentInvocation::InterceptInvocation(inner_params) => {
+                let _ = writeln!(result, "ENQUEUED INTERCEPT INVOCATION");
+                let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
+                let _ = writeln!(
+                    result,
+                    "{pad}method:            {}",
+                    &inner_params.method_name
+                );
+                let _ = writeln!(
+                    result,
+                    "{pad}idempotency key:   {}",
+                    &inner_params.idempotency_key,
+                );
+            }
         },
         PublicOplogEntry::PendingUpdate(params) => {
             let _ = writeln!(result, "ENQUEUED UPDATE");
@@ -469,6 +488,17 @@ pub fn debug_render_oplog_entry(entry: &PublicOplogEntry) -> String {
             let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
             let _ = writeln!(result, "{pad}name:              {}", &params.name);
         }
+        PublicOplogEntry::WebSocketConnected(params) => {
+            let _ = writeln!(result, "WEBSOCKET CONNECTED");
+            let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
+            let _ = writeln!(result, "{pad}connection id:     {}", &params.connection_id);
+            let _ = writeln!(result, "{pad}method name:       {}", &params.method_name);
+        }
+        PublicOplogEntry::WebSocketDisconnected(params) => {
+            let _ = writeln!(result, "WEBSOCKET DISCONNECTED");
+            let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
+            let _ = writeln!(result, "{pad}connection id:     {}", &params.connection_id);
+        }
     }
 
     result
@@ -41,7 +41,7 @@ use golem_common::model::deployment::{CurrentDeployment, DeploymentCreation, Dep
 use golem_common::model::domain_registration::{Domain, DomainRegistrationCreation};
 use golem_common::model::environment::{Environment, EnvironmentId};
 use golem_common::model::environment_plugin_grant::EnvironmentPluginGrantId;
-use golem_common::model::oplog::PublicOplogEntryWithIndex;
+use golem_common::model::oplog::{AgentOplogSearchMatch, PublicOplogEntryWithIndex};
 use golem_common::model::worker::{
     AgentConfigEntryDto, AgentFileSystemNode, AgentMetadataDto, RevertWorkerTarget, UpdateRecord,
 };
@@ -781,6 +781,24 @@ pub trait TestDslExtended: TestDsl {
         &self,
         environment_id: &EnvironmentId,
     ) -> anyhow::Result<DeploymentRevision>;
+
+    async fn search_component_oplogs(
+        &self,
+        component_id: &ComponentId,
+        query: &str,
+        max_results: Option<u64>,
+    ) -> anyhow::Result<Vec<AgentOplogSearchMatch>>;
+
+    async fn search_environment_oplogs(
+        &self,
+        environment_id: &EnvironmentId,
+        query: &str,
+    ) -> anyhow::Result<Vec<AgentOplogSearchMatch>>;
+
+    async fn export_agent(&self, agent_id: &AgentId) -> anyhow::Result<Vec<u8>>;
+
+    async fn import_agent(&self, target_agent_id: &AgentId, archive: Vec<u8>)
+    -> anyhow::Result<()>;
 }
 
 pub struct StoreComponentBuilder<'a, Dsl: TestDsl + ?Sized> {
@@ -1035,6 +1053,8 @@ pub fn log_event_to_string(event: &LogEvent) -> String {
         Some(log_event::Event::InvocationStarted(_)) => "".to_string(),
         Some(log_event::Event::ClientLagged { .. }) => "".to_string(),
         Some(log_event::Event::PluginError(err)) => err.message.clone(),
+        Some(log_event::Event::WebsocketSend(_)) => "".to_string(),
+        Some(log_event::Event::WebsocketClose(_)) => "".to_string(),
         None => std::panic!("Unexpected event type"),
     }
 }
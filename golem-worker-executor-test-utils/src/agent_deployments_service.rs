@@ -20,6 +20,7 @@ use golem_common::model::retry_policy::NamedRetryPolicy;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::model::AgentDeploymentDetails;
 use golem_service_base::model::agent_secret::AgentSecret;
+use golem_service_base::model::oplog_export_sink::NamedOplogExportSink;
 use golem_worker_executor::services::environment_state::EnvironmentStateService;
 use std::collections::HashMap;
 
@@ -48,6 +49,12 @@ impl EnvironmentStateService for DisabledEnvironmentStateService {
     ) -> Result<Vec<NamedRetryPolicy>, WorkerExecutorError> {
         Ok(vec![])
     }
+    async fn get_oplog_export_sinks(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedOplogExportSink>, WorkerExecutorError> {
+        Ok(vec![])
+    }
 }
 
 /// Test-only `EnvironmentStateService` that returns a fixed list of
@@ -81,4 +88,10 @@ impl EnvironmentStateService for ConfiguredRetryPoliciesEnvironmentStateService
     ) -> Result<Vec<NamedRetryPolicy>, WorkerExecutorError> {
         Ok(self.policies.clone())
     }
+    async fn get_oplog_export_sinks(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedOplogExportSink>, WorkerExecutorError> {
+        Ok(vec![])
+    }
 }
@@ -37,10 +37,12 @@ use golem_common::model::agent::{AgentFileContentHash, AgentTypeName};
 use golem_common::model::component::{
     AgentFilePath, AgentTypeProvisionConfigCreation, AgentTypeProvisionConfigUpdate,
     ArchiveFilePath, ComponentDto, ComponentId, ComponentName, ComponentRevision, InitialAgentFile,
+    InstalledPlugin, PluginInstallation,
 };
 use golem_common::model::deployment::DeploymentRevision;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::oplog::{PublicOplogEntry, PublicOplogEntryWithIndex};
+use golem_common::model::plugin_registration::PluginRegistrationId;
 use golem_common::model::worker::{
     AgentConfigEntryDto, AgentFileSystemNode, AgentMetadataDto, RevertWorkerTarget,
 };
@@ -79,9 +81,10 @@ impl TestDsl for TestWorkerExecutor {
         agent_type_provision_configs: BTreeMap<AgentTypeName, AgentTypeProvisionConfigCreation>,
         files_for_archive: Vec<IFSEntry>,
     ) -> anyhow::Result<ComponentDto> {
-        if agent_type_provision_configs
-            .values()
-            .any(|c| !c.plugin_installations.is_empty())
+        if !self.installs_invocation_interceptors
+            && agent_type_provision_configs
+                .values()
+                .any(|c| !c.plugin_installations.is_empty())
         {
             return Err(anyhow!("Plugins aren't supported in worker executor tests"));
         }
@@ -154,7 +157,12 @@ impl TestDsl for TestWorkerExecutor {
                             env: creation.env,
                             files,
                             config,
-                            plugins: vec![],
+                            plugins: creation
+                                .plugin_installations
+                                .iter()
+                                .map(test_invocation_interceptor)
+                                .collect(),
+                            lifecycle: creation.lifecycle,
                         },
                     ))
                 })
@@ -334,6 +342,10 @@ impl TestDsl for TestWorkerExecutor {
                         files: files.into_values().collect(),
                         config: new_config,
                         plugins: existing.plugins,
+                        lifecycle: match update.lifecycle {
+                            Some(policy) => Some(policy).filter(|policy| !policy.is_disabled()),
+                            None => existing.lifecycle,
+                        },
                     },
                 );
             }
@@ -419,6 +431,7 @@ impl TestDsl for TestWorkerExecutor {
                 method_parameters: Some(UntypedDataValue::from(params).into()),
                 mode: golem_api_grpc::proto::golem::worker::AgentInvocationMode::Schedule as i32,
                 schedule_at: None,
+                at: None,
                 idempotency_key: Some(idempotency_key.clone().into()),
                 component_owner_account_id: Some(component.account_id.into()),
                 environment_id: Some(component.environment_id.into()),
@@ -467,6 +480,7 @@ impl TestDsl for TestWorkerExecutor {
                 method_parameters: Some(UntypedDataValue::from(params).into()),
                 mode: golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await as i32,
                 schedule_at: None,
+                at: None,
                 idempotency_key: Some(key.into()),
                 component_owner_account_id: Some(component.account_id.into()),
                 environment_id: Some(component.environment_id.into()),
@@ -655,6 +669,8 @@ impl TestDsl for TestWorkerExecutor {
                     count: 100,
                     query: query.to_string(),
                     auth_ctx: Some(self.auth_ctx().into()),
+                    from: None,
+                    to: None,
                 })
                 .await?
                 .into_inner();
@@ -795,6 +811,7 @@ impl TestDsl for TestWorkerExecutor {
                 component_owner_account_id: Some(latest_version.account_id.into()),
                 auth_ctx: Some(self.auth_ctx().into()),
                 principal: None,
+                websocket_connection: None,
             })
             .await?
             .into_inner();
@@ -1240,3 +1257,20 @@ fn parse_provision_config_from_agent_type(
         })
         .collect()
 }
+
+/// The plugin stored for a plugin installation in a test executor started with an invocation
+/// interceptor plugin override, which handles every installed plugin as an interceptor.
+fn test_invocation_interceptor(installation: &PluginInstallation) -> InstalledPlugin {
+    InstalledPlugin {
+        environment_plugin_grant_id: installation.environment_plugin_grant_id,
+        priority: installation.priority,
+        parameters: installation.parameters.clone(),
+        plugin_registration_id: PluginRegistrationId(installation.environment_plugin_grant_id.0),
+        plugin_name: "test-invocation-interceptor".to_string(),
+        plugin_version: "0.0.1".to_string(),
+        oplog_processor_component_id: None,
+        oplog_processor_component_revision: None,
+        invocation_interceptor_component_id: Some(ComponentId::new()),
+        invocation_interceptor_component_revision: Some(ComponentRevision::INITIAL),
+    }
+}
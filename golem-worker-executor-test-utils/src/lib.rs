@@ -49,7 +49,7 @@ use golem_common::model::retry_policy::NamedRetryPolicy;
 use golem_common::model::worker::{AgentConfigEntryDto, AgentMetadataDto};
 use golem_common::model::{
     AgentFilter, AgentId, AgentInvocation, AgentInvocationOutput, AgentStatusRecord,
-    IdempotencyKey, OplogIndex, OwnedAgentId, RdbmsPoolKey, RetryConfig, TransactionId,
+    IdempotencyKey, OplogIndex, OwnedAgentId, RdbmsPoolKey, RetryConfig, Timestamp, TransactionId,
 };
 use golem_service_base::clients::registry::RegistryService;
 use golem_service_base::config::{BlobStorageConfig, LocalFileSystemBlobStorageConfig};
@@ -102,6 +102,7 @@ use golem_worker_executor::services::golem_config::{
     KeyValueStorageNamespaceRoutedConfig, MemoryConfig, OplogConfig, ResourceLimitsConfig,
     ResourceLimitsDisabledConfig, SchedulerStorageConfig, SnapshotPolicy,
 };
+use golem_worker_executor::services::invocation_interceptor::InvocationInterceptorPlugin;
 use golem_worker_executor::services::key_value::{DefaultKeyValueService, KeyValueService};
 use golem_worker_executor::services::oplog::{CommitLevel, Oplog, OplogService};
 use golem_worker_executor::services::promise::PromiseService;
@@ -119,12 +120,13 @@ use golem_worker_executor::services::resource_limits::{
 use golem_worker_executor::services::rpc::{Rpc, RpcDemand, RpcError as ServiceRpcError};
 use golem_worker_executor::services::scheduler::SchedulerService;
 use golem_worker_executor::services::shard::ShardService;
+use golem_worker_executor::services::topics::TopicService;
 use golem_worker_executor::services::worker::WorkerService;
 use golem_worker_executor::services::worker_enumeration::WorkerEnumerationService;
 use golem_worker_executor::services::worker_event::WorkerEventService;
 use golem_worker_executor::services::worker_fork::WorkerForkService;
 use golem_worker_executor::services::worker_proxy::WorkerProxy;
-use golem_worker_executor::services::{HasAll, NoAdditionalDeps, rdbms};
+use golem_worker_executor::services::{HasAll, rdbms};
 use golem_worker_executor::storage::keyvalue::KeyValueStorage;
 use golem_worker_executor::worker::{RetryDecision, Worker};
 use golem_worker_executor::workerctx::{
@@ -555,6 +557,9 @@ pub struct TestWorkerExecutor {
     /// wasmtime instance while keeping the `Worker` shell (and its read-only
     /// cache) alive, and to read per-agent instance load counts.
     additional_test_deps: AdditionalTestDeps,
+    /// Whether plugins installed on stored components are invocation interceptors, see
+    /// `TestExecutorOverrides::invocation_interceptor_plugin`.
+    installs_invocation_interceptors: bool,
     leak_detector: std::sync::Weak<()>,
 }
 
@@ -963,6 +968,9 @@ pub struct TestExecutorOverrides {
     /// should expose to running agents (mirrors `retryPolicyDefaults` in
     /// `golem.yaml`).  When `None`, an empty policy list is used.
     pub retry_policies: Option<Vec<NamedRetryPolicy>>,
+    /// Replaces the executor's invocation interceptor plugin. When set, the plugins installed on
+    /// the agent types of stored components are all invocation interceptors handled by it.
+    pub invocation_interceptor_plugin: Option<Arc<dyn InvocationInterceptorPlugin>>,
 }
 
 fn make_base_test_config(deps: &WorkerExecutorTestDependencies) -> GolemConfig {
@@ -1081,6 +1089,7 @@ async fn start_executor_with_config(
     // `TestWorkerExecutor` returned to the test (so tests can observe and
     // mutate per-worker test-only state, e.g. eviction).
     let additional_test_deps = AdditionalTestDeps::new();
+    let installs_invocation_interceptors = overrides.invocation_interceptor_plugin.is_some();
 
     let details = run(
         config,
@@ -1118,6 +1127,7 @@ async fn start_executor_with_config(
                 client,
                 context: context.clone(),
                 additional_test_deps,
+                installs_invocation_interceptors,
                 leak_detector,
             });
         } else if start.elapsed().as_secs() > 10 {
@@ -1383,6 +1393,16 @@ impl InvocationHooks for TestWorkerCtx {
             .await
     }
 
+    async fn on_agent_invocation_output_replaced(
+        &mut self,
+        original: &UntypedDataValue,
+        replaced: &UntypedDataValue,
+    ) -> Result<(), WorkerExecutorError> {
+        self.durable_ctx
+            .on_agent_invocation_output_replaced(original, replaced)
+            .await
+    }
+
     async fn get_current_retry_point(&self) -> OplogIndex {
         self.durable_ctx.get_current_retry_point().await
     }
@@ -1394,6 +1414,19 @@ impl InvocationHooks for TestWorkerCtx {
     fn exit_read_only_mode(&mut self) {
         self.durable_ctx.exit_read_only_mode()
     }
+
+    fn arm_invocation_deadline(&mut self, method_name: String, deadline: Timestamp) {
+        self.durable_ctx
+            .arm_invocation_deadline(method_name, deadline)
+    }
+
+    fn disarm_invocation_deadline(&mut self) {
+        self.durable_ctx.disarm_invocation_deadline()
+    }
+
+    fn check_invocation_deadline(&self) -> Option<AgentError> {
+        self.durable_ctx.check_invocation_deadline()
+    }
 }
 
 #[async_trait]
@@ -1470,6 +1503,7 @@ impl WorkerCtx for TestWorkerCtx {
         worker_service: Arc<dyn WorkerService>,
         worker_enumeration_service: Arc<dyn WorkerEnumerationService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn rdbms::RdbmsService>,
         quota_service: Arc<dyn QuotaService>,
@@ -1523,6 +1557,7 @@ impl WorkerCtx for TestWorkerCtx {
             worker_service,
             worker_enumeration_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             quota_service,
@@ -1550,6 +1585,7 @@ impl WorkerCtx for TestWorkerCtx {
             original_phantom_id,
             u64::MAX,
             u64::MAX,
+            None,
         )
         .await?;
         Ok(Self { durable_ctx })
@@ -1762,6 +1798,14 @@ impl HostWasmRpc for TestWorkerCtx {
             .await
     }
 
+    async fn set_deadline(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        deadline: Option<wasmtime_wasi::p2::bindings::clocks::wall_clock::Datetime>,
+    ) -> anyhow::Result<()> {
+        self.durable_ctx.set_deadline(self_, deadline).await
+    }
+
     async fn drop(&mut self, rep: Resource<WasmRpc>) -> anyhow::Result<()> {
         HostWasmRpc::drop(&mut self.durable_ctx, rep).await
     }
@@ -1979,6 +2023,16 @@ impl Bootstrap<TestWorkerCtx> for TestServerBootstrap {
             rpc
         }
     }
+
+    fn wrap_invocation_interceptor_plugin(
+        &self,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
+    ) -> Arc<dyn InvocationInterceptorPlugin> {
+        match &self.overrides.invocation_interceptor_plugin {
+            Some(overridden) => overridden.clone(),
+            None => invocation_interceptor_plugin,
+        }
+    }
 }
 
 // -------------------------------------------------------------------------
@@ -2047,8 +2101,8 @@ impl Bootstrap<golem_worker_executor::workerctx::default::Context>
     fn create_additional_deps(
         &self,
         _registry_service: Arc<dyn RegistryService>,
-    ) -> NoAdditionalDeps {
-        NoAdditionalDeps {}
+    ) -> golem_worker_executor::workerctx::default::ContextDeps {
+        golem_worker_executor::workerctx::default::ContextDeps::default()
     }
 
     fn create_direct_invocation_auth_service(
@@ -2187,7 +2241,7 @@ async fn run_production_context_bootstrap(
                 deps: deps.clone(),
                 client,
                 context: context.clone(),
-                // Production-context bootstrap path uses the real `NoAdditionalDeps`
+                // Production-context bootstrap path uses the real `ContextDeps`
                 // worker context, not `TestWorkerCtx`, so the worker-inspection
                 // helpers do not apply here. We hand the executor a fresh, empty
                 // `AdditionalTestDeps` purely to satisfy the field; calling
@@ -2195,6 +2249,7 @@ async fn run_production_context_bootstrap(
                 // on this path will report "no worker" because no `ActiveWorkers`
                 // handle was ever captured.
                 additional_test_deps: AdditionalTestDeps::new(),
+                installs_invocation_interceptors: false,
                 leak_detector,
             });
         } else if start.elapsed().as_secs() > 10 {
@@ -2789,6 +2844,30 @@ impl<T: RdbmsType> Rdbms<T> for TestRdms<T> {
         self.rdbms.query(key, agent_id, statement, params).await
     }
 
+    async fn execute_batch(
+        &self,
+        key: &RdbmsPoolKey,
+        agent_id: &AgentId,
+        statement: &str,
+        params: Vec<Vec<T::DbValue>>,
+    ) -> Result<Vec<u64>, rdbms::RdbmsError>
+    where
+        <T as RdbmsType>::DbValue: 'async_trait,
+    {
+        self.rdbms
+            .execute_batch(key, agent_id, statement, params)
+            .await
+    }
+
+    async fn prepare(
+        &self,
+        key: &RdbmsPoolKey,
+        agent_id: &AgentId,
+        statement: &str,
+    ) -> Result<(), rdbms::RdbmsError> {
+        self.rdbms.prepare(key, agent_id, statement).await
+    }
+
     async fn begin_transaction(
         &self,
         key: &RdbmsPoolKey,
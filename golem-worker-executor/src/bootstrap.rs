@@ -12,9 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::services::NoAdditionalDeps;
 use crate::services::golem_config::GolemConfig;
-use crate::workerctx::default::Context;
+use crate::workerctx::default::{Context, ContextDeps};
 use crate::{Bootstrap, RunDetails, bootstrap_and_run_worker_executor};
 use async_trait::async_trait;
 use golem_service_base::clients::registry::RegistryService;
@@ -30,11 +29,8 @@ pub struct ServerBootstrap;
 
 #[async_trait]
 impl Bootstrap<Context> for ServerBootstrap {
-    fn create_additional_deps(
-        &self,
-        _registry_service: Arc<dyn RegistryService>,
-    ) -> NoAdditionalDeps {
-        NoAdditionalDeps {}
+    fn create_additional_deps(&self, _registry_service: Arc<dyn RegistryService>) -> ContextDeps {
+        ContextDeps::default()
     }
 }
 
@@ -40,8 +40,13 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
 
         let result = handle
             .run(self, async |ctx| -> wasmtime::Result<_> {
-                let mut view = ctx.as_wasi_view();
-                let nanos = Host::now(&mut view.clocks()).await?;
+                let nanos = match ctx.deterministic_sources().await {
+                    Some(sources) => sources.monotonic_now(),
+                    None => {
+                        let mut view = ctx.as_wasi_view();
+                        Host::now(&mut view.clocks()).await?
+                    }
+                };
                 Ok(HostResponseMonotonicClockTimestamp { nanos })
             })
             .await?;
@@ -72,6 +77,18 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
 
     async fn subscribe_instant(&mut self, when: Instant) -> wasmtime::Result<Resource<Pollable>> {
         self.observe_function_call("monotonic_clock", "subscribe_instant");
+        // A deterministic clock is not the one the pollables are scheduled on, so the instant
+        // is translated to the same distance from the real clock's current time
+        let when = match self.deterministic_sources().await {
+            Some(sources) => {
+                let remaining = when.saturating_sub(sources.monotonic_now());
+                let mut view = self.as_wasi_view();
+                Host::now(&mut view.clocks())
+                    .await?
+                    .saturating_add(remaining)
+            }
+            None => when,
+        };
         let mut view = self.as_wasi_view();
         Host::subscribe_instant(&mut view.clocks(), when).await
     }
@@ -35,9 +35,12 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
 
         let result = handle
             .run(self, async |ctx| -> wasmtime::Result<_> {
-                let result = {
-                    let mut view = ctx.as_wasi_view();
-                    Host::now(&mut view.clocks()).await?
+                let result = match ctx.deterministic_sources().await {
+                    Some(sources) => sources.wall_clock_now(),
+                    None => {
+                        let mut view = ctx.as_wasi_view();
+                        Host::now(&mut view.clocks()).await?
+                    }
                 };
                 Ok(HostResponseWallClock {
                     time: SerializableDateTime {
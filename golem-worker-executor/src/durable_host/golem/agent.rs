@@ -15,10 +15,10 @@
 use crate::durable_host::concurrent::{CallHandle, CallReplayOutcome, NotCancellable};
 use crate::durable_host::durability::HostFailureKind;
 use crate::durable_host::{DurabilityHost, DurableWorkerCtx, InternalRetryResult};
-use crate::preview2::golem::agent::host::Host;
+use crate::model::event::InternalWorkerEvent;
+use crate::preview2::golem::agent::host::{Host, RpcError, TopicSubscriber, WebsocketMessage};
 use crate::workerctx::WorkerCtx;
 use anyhow::anyhow;
-use golem_common::model::PromiseId;
 use golem_common::model::agent::bindings::golem::agent::common::{
     AgentError, DataValue, RegisteredAgentType,
 };
@@ -26,6 +26,7 @@ use golem_common::model::agent::{
     AgentConfigDeclaration, AgentConfigSource, AgentTypeName, LegacyParsedAgentId,
 };
 use golem_common::model::agent_secret::CanonicalAgentSecretPath;
+use golem_common::model::invocation_context::{AttributeValue, WEBSOCKET_CONNECTION_ID_ATTRIBUTE};
 use golem_common::model::oplog::host_functions::{
     GolemAgentCreateWebhook, GolemAgentGetAgentType, GolemAgentGetAllAgentTypes,
     GolemAgentGetConfigValue,
@@ -36,6 +37,7 @@ use golem_common::model::oplog::{
     HostResponseGolemAgentAgentTypes, HostResponseGolemAgentGetConfigValue,
     HostResponseGolemAgentWebhookUrl,
 };
+use golem_common::model::{PromiseId, WebSocketMessage};
 use golem_common::schema::adapters::analysed_type::{
     analysed_type_to_schema_type_inline, schema_type_to_analysed_type,
 };
@@ -436,6 +438,62 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         response.result.map_err(|e| anyhow!(e))
     }
 
+    async fn current_websocket_connection(&mut self) -> anyhow::Result<Option<String>> {
+        DurabilityHost::observe_function_call(self, "golem_agent", "current_websocket_connection");
+
+        // The connection id is part of the invocation context, which is persisted
+        // together with the invocation, so it is available during replay as well
+        let span = self
+            .state
+            .invocation_context
+            .get(&self.state.current_span_id)
+            .map_err(|err| anyhow!(err))?;
+
+        Ok(span
+            .get_attribute(WEBSOCKET_CONNECTION_ID_ATTRIBUTE, true)
+            .map(|value| match value {
+                AttributeValue::String(connection_id) => connection_id,
+            }))
+    }
+
+    async fn websocket_send(
+        &mut self,
+        connection_id: String,
+        message: WebsocketMessage,
+    ) -> anyhow::Result<()> {
+        DurabilityHost::observe_function_call(self, "golem_agent", "websocket_send");
+
+        // Like log output, messages are only delivered live and never resent during replay
+        if self.state.is_live() {
+            let message = match message {
+                WebsocketMessage::Text(text) => WebSocketMessage::Text(text),
+                WebsocketMessage::Binary(bytes) => WebSocketMessage::Binary(bytes),
+            };
+            self.public_state.event_service.emit_event(
+                InternalWorkerEvent::websocket_send(&connection_id, message),
+                true,
+            );
+        }
+        Ok(())
+    }
+
+    async fn websocket_close(
+        &mut self,
+        connection_id: String,
+        code: Option<u16>,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        DurabilityHost::observe_function_call(self, "golem_agent", "websocket_close");
+
+        if self.state.is_live() {
+            self.public_state.event_service.emit_event(
+                InternalWorkerEvent::websocket_close(&connection_id, code, reason),
+                true,
+            );
+        }
+        Ok(())
+    }
+
     async fn get_config_value(
         &mut self,
         path: Vec<String>,
@@ -509,4 +567,30 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
             }
         }
     }
+
+    async fn subscribe(
+        &mut self,
+        topic: String,
+        subscriber: TopicSubscriber,
+        method_name: String,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        self.subscribe_to_topic(topic, subscriber, method_name)
+            .await
+    }
+
+    async fn unsubscribe(
+        &mut self,
+        topic: String,
+        subscriber: TopicSubscriber,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        self.unsubscribe_from_topic(topic, subscriber).await
+    }
+
+    async fn publish(
+        &mut self,
+        topic: String,
+        message: DataValue,
+    ) -> anyhow::Result<Result<u64, RpcError>> {
+        self.publish_to_topic(topic, message).await
+    }
 }
@@ -36,29 +36,34 @@ use crate::worker::status::calculate_last_known_status_with_checkpoint;
 use crate::workerctx::{StatusManagement, WorkerCtx};
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::DateTime;
 use golem_common::model::agent::LegacyParsedAgentId;
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::oplog::host_functions::{
-    GolemApiCompletePromise, GolemApiCreatePromise, GolemApiFork, GolemApiForkWorker,
-    GolemApiGenerateIdempotencyKey, GolemApiGetAgentMetadata, GolemApiGetPromiseResult,
-    GolemApiGetSelfMetadata, GolemApiResolveAgentIdStrict, GolemApiResolveComponentId,
-    GolemApiRevertWorker, GolemApiUpdateWorker,
+    GolemApiCancelPromise, GolemApiCompletePromise, GolemApiCreatePromise,
+    GolemApiCreatePromiseWithOptions, GolemApiFork, GolemApiForkWorker,
+    GolemApiGenerateIdempotencyKey, GolemApiGetAgentMetadata, GolemApiGetPromiseOutcome,
+    GolemApiGetPromiseResult, GolemApiGetSelfMetadata, GolemApiResolveAgentIdStrict,
+    GolemApiResolveComponentId, GolemApiRevertWorker, GolemApiUpdateWorker,
 };
 use golem_common::model::oplog::types::AgentMetadataForGuests;
 use golem_common::model::oplog::{
     DurableFunctionType, HostRequestGolemApiAgentId, HostRequestGolemApiComponentSlug,
     HostRequestGolemApiComponentSlugAndAgentName, HostRequestGolemApiForkAgent,
-    HostRequestGolemApiPromiseId, HostRequestGolemApiRevertAgent, HostRequestGolemApiUpdateAgent,
-    HostRequestNoInput, HostResponseGolemApiAgentId, HostResponseGolemApiAgentMetadata,
+    HostRequestGolemApiPromiseId, HostRequestGolemApiPromiseOptions,
+    HostRequestGolemApiRevertAgent, HostRequestGolemApiUpdateAgent, HostRequestNoInput,
+    HostResponseGolemApiAgentId, HostResponseGolemApiAgentMetadata,
     HostResponseGolemApiComponentId, HostResponseGolemApiFork, HostResponseGolemApiIdempotencyKey,
     HostResponseGolemApiPromiseCompletion, HostResponseGolemApiPromiseId,
-    HostResponseGolemApiPromiseResult, HostResponseGolemApiSelfAgentMetadata,
-    HostResponseGolemApiUnit, OplogEntry, PublicOplogEntry,
+    HostResponseGolemApiPromiseOutcome, HostResponseGolemApiPromiseResult,
+    HostResponseGolemApiSelfAgentMetadata, HostResponseGolemApiUnit, OplogEntry, PublicOplogEntry,
 };
+use golem_common::model::promise::{PromiseOptions, PromiseOutcome};
 use golem_common::model::regions::OplogRegion;
-use golem_common::model::{AgentId, OwnedAgentId, ScanCursor};
+use golem_common::model::{AgentId, OwnedAgentId, ScanCursor, ScheduledAction, Timestamp};
 use golem_common::model::{OplogIndex, PromiseId, RetryContext};
 use golem_service_base::error::worker_executor::{InterruptKind, WorkerExecutorError};
+use golem_wasm::analysis::AnalysedType;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::OnceCell;
@@ -193,7 +198,11 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
             let promise_id = self
                 .public_state
                 .promise_service
-                .create(&self.owned_agent_id.agent_id, oplog_idx)
+                .create(
+                    &self.owned_agent_id.agent_id,
+                    oplog_idx,
+                    &PromiseOptions::default(),
+                )
                 .await;
             handle
                 .complete(self, HostResponseGolemApiPromiseId { promise_id })
@@ -203,6 +212,73 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         Ok(result.promise_id.into())
     }
 
+    async fn create_promise_with_options(
+        &mut self,
+        options: golem_api_1_x::host::PromiseOptions,
+    ) -> anyhow::Result<golem_api_1_x::host::PromiseId> {
+        let timeout_nanos = options.timeout;
+        let payload_type = options.payload_type.map(AnalysedType::from);
+
+        let mut handle = CallHandle::<GolemApiCreatePromiseWithOptions, NotCancellable>::start(
+            self,
+            HostRequestGolemApiPromiseOptions {
+                timeout_nanos,
+                payload_type: payload_type.clone(),
+            },
+            DurableFunctionType::WriteLocal,
+        )
+        .await?;
+
+        let result = 'result: {
+            if !handle.is_live() {
+                match handle.replay(self).await? {
+                    CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                    CallReplayOutcome::Incomplete(live) => handle = live,
+                }
+            }
+
+            // The deadline is only calculated in live mode; on replay the promise and its timeout schedule already exist.
+            let deadline = timeout_nanos.map(|nanos| {
+                Timestamp::from(Timestamp::now_utc().to_millis() + nanos.div_ceil(1_000_000))
+            });
+            let oplog_idx = handle.start_index();
+            let promise_id = self
+                .public_state
+                .promise_service
+                .create(
+                    &self.owned_agent_id.agent_id,
+                    oplog_idx,
+                    &PromiseOptions {
+                        deadline,
+                        payload_type,
+                    },
+                )
+                .await;
+
+            if let Some(deadline) = deadline {
+                let when = DateTime::from_timestamp_millis(deadline.to_millis() as i64)
+                    .ok_or_else(|| anyhow!("Promise deadline {deadline} is out of range"))?;
+                self.state
+                    .scheduler_service
+                    .schedule(
+                        when,
+                        ScheduledAction::TimeOutPromise {
+                            account_id: self.created_by(),
+                            environment_id: self.owned_agent_id.environment_id(),
+                            promise_id: promise_id.clone(),
+                        },
+                    )
+                    .await;
+            }
+
+            handle
+                .complete(self, HostResponseGolemApiPromiseId { promise_id })
+                .await?
+        };
+
+        Ok(result.promise_id.into())
+    }
+
     async fn get_promise(
         &mut self,
         promise_id: golem_api_1_x::host::PromiseId,
@@ -290,6 +366,57 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         Ok(result.completed)
     }
 
+    async fn cancel_promise(
+        &mut self,
+        promise_id: golem_api_1_x::host::PromiseId,
+    ) -> anyhow::Result<bool> {
+        let promise_id: PromiseId = promise_id.into();
+
+        // Only the agent that originally created the promise can cancel it, so it is always owned by this executor.
+        if promise_id.agent_id != self.owned_agent_id.agent_id {
+            return Err(anyhow!(
+                "Tried cancelling a promise not created by the current agent"
+            ));
+        }
+
+        let mut handle = CallHandle::<GolemApiCancelPromise, NotCancellable>::start(
+            self,
+            HostRequestGolemApiPromiseId {
+                promise_id: promise_id.clone(),
+            },
+            DurableFunctionType::WriteLocal,
+        )
+        .await?;
+
+        let result = 'result: {
+            if !handle.is_live() {
+                match handle.replay(self).await? {
+                    CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                    CallReplayOutcome::Incomplete(live) => handle = live,
+                }
+            }
+
+            let cancelled = match self.public_state.promise_service.cancel(promise_id).await {
+                Ok(cancelled) => cancelled,
+                Err(err) => {
+                    handle.abandon_for_trap();
+                    return Err(err.into());
+                }
+            };
+
+            handle
+                .complete(
+                    self,
+                    HostResponseGolemApiPromiseCompletion {
+                        completed: cancelled,
+                    },
+                )
+                .await?
+        };
+
+        Ok(result.completed)
+    }
+
     async fn get_oplog_index(&mut self) -> anyhow::Result<golem_api_1_x::oplog::OplogIndex> {
         self.observe_function_call("golem::api", "get_oplog_index");
         if self.state.is_live() {
@@ -1211,24 +1338,10 @@ impl<Ctx: WorkerCtx> HostGetPromiseResult for DurableWorkerCtx<Ctx> {
 
         let result = handle
             .run(self, async |ctx| {
-                let self_agent_id = ctx.agent_id().clone();
-                let entry = ctx.table().get(&resource)?;
-
-                // only the agent that originally created the promise is woken up when it is completed.
-                if entry.promise_id.agent_id != self_agent_id {
-                    return Err(anyhow!(
-                        "Tried awaiting a promise not created by the current agent"
-                    ));
-                }
-
-                let result = match entry.get_handle().await {
-                    Ok(handle) => handle.get().await,
-                    Err(err) => {
-                        return Err(anyhow::Error::from(WorkerExecutorError::runtime(
-                            err.clone(),
-                        )));
-                    }
-                };
+                let result = ctx
+                    .poll_promise_outcome(&resource)
+                    .await?
+                    .and_then(PromiseOutcome::into_completed_payload);
                 Ok::<_, anyhow::Error>(HostResponseGolemApiPromiseResult { result })
             })
             .await?;
@@ -1236,6 +1349,27 @@ impl<Ctx: WorkerCtx> HostGetPromiseResult for DurableWorkerCtx<Ctx> {
         Ok(result.result)
     }
 
+    async fn get_outcome(
+        &mut self,
+        resource: Resource<GetPromiseResultEntry>,
+    ) -> anyhow::Result<Option<golem_api_1_x::host::PromiseOutcome>> {
+        let handle = CallHandle::<GolemApiGetPromiseOutcome, NotCancellable>::start(
+            self,
+            HostRequestNoInput {},
+            DurableFunctionType::ReadRemote,
+        )
+        .await?;
+
+        let result = handle
+            .run(self, async |ctx| {
+                let result = ctx.poll_promise_outcome(&resource).await?;
+                Ok::<_, anyhow::Error>(HostResponseGolemApiPromiseOutcome { result })
+            })
+            .await?;
+
+        Ok(result.result.map(|outcome| outcome.into()))
+    }
+
     async fn drop(&mut self, resource: Resource<GetPromiseResultEntry>) -> anyhow::Result<()> {
         self.observe_function_call("golem::api::promise-result", "drop");
         let resource_rep = resource.rep();
@@ -1368,6 +1502,8 @@ impl<Ctx: WorkerCtx> HostSearchOplog for DurableWorkerCtx<Ctx> {
             entry.next_oplog_index,
             entry.page_size,
             &entry.query,
+            None,
+            None,
         )
         .await
         .map_err(|msg| anyhow!(msg))?;
@@ -1401,6 +1537,28 @@ impl<Ctx: WorkerCtx> HostSearchOplog for DurableWorkerCtx<Ctx> {
 }
 
 impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    async fn poll_promise_outcome(
+        &mut self,
+        resource: &Resource<GetPromiseResultEntry>,
+    ) -> anyhow::Result<Option<PromiseOutcome>> {
+        let self_agent_id = self.agent_id().clone();
+        let entry = self.table().get(resource)?;
+
+        // only the agent that originally created the promise is woken up when it is completed.
+        if entry.promise_id.agent_id != self_agent_id {
+            return Err(anyhow!(
+                "Tried awaiting a promise not created by the current agent"
+            ));
+        }
+
+        match entry.get_handle().await {
+            Ok(handle) => Ok(handle.get().await),
+            Err(err) => Err(anyhow::Error::from(WorkerExecutorError::runtime(
+                err.clone(),
+            ))),
+        }
+    }
+
     async fn resolve_agent_id_strict_internal(
         &self,
         component_slug: String,
@@ -1725,6 +1883,16 @@ impl TryFrom<AgentAnyFilter> for golem_common::model::AgentFilter {
     }
 }
 
+impl From<PromiseOutcome> for golem_api_1_x::host::PromiseOutcome {
+    fn from(value: PromiseOutcome) -> Self {
+        match value {
+            PromiseOutcome::Completed(data) => Self::Completed(data),
+            PromiseOutcome::TimedOut => Self::TimedOut,
+            PromiseOutcome::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
 impl From<AgentMetadataForGuests> for golem_api_1_x::host::AgentMetadata {
     fn from(value: AgentMetadataForGuests) -> Self {
         Self {
@@ -113,7 +113,11 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         // check if all pollables are promise backed. In this case we can suspend immediately
         // This check only needs to be done in live mode, as we will never even persist the oplog entry for polling
         // if we suspended in the last pass. Doing it this way also prevents us from initializing the promises until we are actually in live mode.
-        if self.durable_execution_state().is_live && self.agent_mode() != AgentMode::Ephemeral {
+        // Invocations with a deadline keep waiting in memory instead, so the deadline can interrupt the poll.
+        if self.durable_execution_state().is_live
+            && self.agent_mode() != AgentMode::Ephemeral
+            && !self.has_invocation_deadline()
+        {
             let promise_backed_pollables = self.state.promise_backed_pollables.read().await;
             let mut all_blocked = true;
 
@@ -0,0 +1,108 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::durable_host::{DurabilityHost, DurableWorkerCtx};
+use crate::model::event::InternalWorkerEvent;
+use crate::preview2::golem::metrics::metrics::{Host, Label};
+use crate::services::agent_metrics::{AgentMetricKind, AgentMetricSource, agent_metrics};
+use crate::workerctx::WorkerCtx;
+use golem_common::model::LogLevel;
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Recorded values are not persisted in the oplog; they are only taken into account in live
+    /// mode so that replaying an invocation does not count them again. Recording never fails for
+    /// the agent, as the outcome could differ when the invocation is replayed; rejected values are
+    /// reported as a warning in the agent's log instead.
+    async fn record_agent_metric(
+        &self,
+        kind: AgentMetricKind,
+        name: String,
+        labels: Vec<Label>,
+        value: f64,
+    ) {
+        if self.state.is_replay() {
+            return;
+        }
+
+        let agent_type = self
+            .state
+            .agent_id
+            .as_ref()
+            .map(|agent_id| agent_id.agent_type.to_string())
+            .unwrap_or_default();
+        let source = AgentMetricSource {
+            environment_id: &self.owned_agent_id.environment_id,
+            component_id: &self.owned_agent_id.agent_id.component_id,
+            agent_type: &agent_type,
+        };
+
+        let result = agent_metrics().record(
+            &self.state.config.agent_metrics,
+            &source,
+            kind,
+            &name,
+            labels
+                .into_iter()
+                .map(|label| (label.name, label.value))
+                .collect(),
+            value,
+        );
+        if let Err(err) = result {
+            let event = InternalWorkerEvent::log(
+                LogLevel::Warn,
+                "golem:metrics",
+                &format!("Dropped value of metric {name}: {err}"),
+            );
+            self.emit_log_event(event).await;
+        }
+    }
+}
+
+impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
+    async fn increment_counter(
+        &mut self,
+        name: String,
+        labels: Vec<Label>,
+        value: f64,
+    ) -> anyhow::Result<()> {
+        self.observe_function_call("golem::metrics", "increment_counter");
+        self.record_agent_metric(AgentMetricKind::Counter, name, labels, value)
+            .await;
+        Ok(())
+    }
+
+    async fn set_gauge(
+        &mut self,
+        name: String,
+        labels: Vec<Label>,
+        value: f64,
+    ) -> anyhow::Result<()> {
+        self.observe_function_call("golem::metrics", "set_gauge");
+        self.record_agent_metric(AgentMetricKind::Gauge, name, labels, value)
+            .await;
+        Ok(())
+    }
+
+    async fn record_histogram(
+        &mut self,
+        name: String,
+        labels: Vec<Label>,
+        value: f64,
+    ) -> anyhow::Result<()> {
+        self.observe_function_call("golem::metrics", "record_histogram");
+        self.record_agent_metric(AgentMetricKind::Histogram, name, labels, value)
+            .await;
+        Ok(())
+    }
+}
@@ -27,16 +27,20 @@ pub mod http;
 pub mod io;
 pub mod keyvalue;
 mod logging;
+mod metrics;
 pub mod quota;
 mod random;
 pub mod rdbms;
 mod replay_state;
 mod sockets;
+pub mod test_hooks;
 pub mod wasm_rpc;
 pub mod websocket;
 
 use self::golem::v1x::GetPromiseResultEntry;
-use crate::durable_host::durability::collect_named_retry_policies;
+use self::test_hooks::{HTTP_MOCK_ORIGINAL_URL_HEADER, TestHooks};
+use crate::durable_host::concurrent::Resolution;
+use crate::durable_host::durability::{DurabilityHost, collect_named_retry_policies};
 use crate::durable_host::io::{ManagedStdErr, ManagedStdIn, ManagedStdOut};
 use crate::durable_host::replay_state::{OplogEntryLookupResult, ReplayState};
 use crate::metrics::ephemeral::record_non_suspending_failure;
@@ -64,11 +68,14 @@ use crate::services::resource_limits::AtomicResourceEntry;
 use crate::services::rpc::Rpc;
 use crate::services::scheduler::SchedulerService;
 use crate::services::shard::ShardService;
+use crate::services::topics::TopicService;
 use crate::services::worker::WorkerService;
 use crate::services::worker_event::WorkerEventService;
 use crate::services::worker_fork::WorkerForkService;
 use crate::services::worker_proxy::WorkerProxy;
-use crate::services::{HasAll, HasConfig, HasOplog, HasWorker, worker_enumeration};
+use crate::services::{
+    HasAll, HasConfig, HasFilesystemCheckpointService, HasOplog, HasWorker, worker_enumeration,
+};
 use crate::services::{HasComponentService, HasOplogService, HasWorkerService};
 use crate::wasi_host;
 use crate::worker::agent_config::{effective_agent_config, validate_agent_config};
@@ -92,7 +99,7 @@ use futures::TryStreamExt;
 use futures::future::try_join_all;
 use golem_common::model::TransactionId;
 use golem_common::model::account::{AccountEmail, AccountId};
-use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, Principal};
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, Principal, UntypedDataValue};
 use golem_common::model::component::{
     AgentFilePermissions, CanonicalFilePath, ComponentId, ComponentRevision, InitialAgentFile,
 };
@@ -103,9 +110,11 @@ use golem_common::model::invocation_context::{
 use golem_common::model::oplog::host_functions::HostFunctionName;
 use golem_common::model::oplog::{
     AgentError, AgentResourceId, DurableFunctionType, HostRequest, HostRequestHttpRequest,
-    HostResponse, LogLevel, OplogEntry, OplogIndex, PersistenceLevel, RawSnapshotData,
-    ScopeScanState, TimestampedUpdateDescription, UpdateDescription,
+    HostRequestNoInput, HostResponse, InvocationDeadlineExceededError, LogLevel, OplogEntry,
+    OplogIndex, PersistenceLevel, RawSnapshotData, ScopeScanState, TimestampedUpdateDescription,
+    UpdateDescription,
 };
+use golem_common::model::promise::PromiseOptions;
 use golem_common::model::regions::{DeletedRegions, DeletedRegionsBuilder, OplogRegion};
 use golem_common::model::retry_policy::NamedRetryPolicy;
 use golem_common::model::worker::TypedAgentConfigEntry;
@@ -122,8 +131,8 @@ use golem_service_base::model::component::Component;
 use golem_service_base::model::{
     ComponentFileSystemNode, ComponentFileSystemNodeDetails, GetFileSystemNodeResult,
 };
-use golem_wasm::Uri;
 use golem_wasm::wasmtime::{ResourceStore, ResourceTypeId};
+use golem_wasm::{FromValueAndType, IntoValueAndType, Uri};
 use replay_state::ReplayEvent;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
@@ -136,6 +145,10 @@ use std::vec;
 use tempfile::TempDir;
 use tokio::sync::RwLock as TRwLock;
 
+/// The function name of the durable call recording an agent method output replaced by
+/// invocation interceptor plugins, together with the original output returned by the agent.
+const REPLACED_AGENT_INVOCATION_OUTPUT: &str = "<invocation-interceptor:replaced-output>";
+
 /// A worker's filesystem root directory. Either a random OS temp directory
 /// (the default) or a deterministic path derived from the agent id.
 ///
@@ -181,6 +194,7 @@ use wasmtime_wasi::{
     I32Exit, IoCtx, IoData, IoView, ResourceTable, ResourceTableError, WasiCtx, WasiCtxView,
     WasiView,
 };
+use wasmtime_wasi_http::p2::bindings::http::types::ErrorCode;
 use wasmtime_wasi_http::p2::body::HyperOutgoingBody;
 use wasmtime_wasi_http::p2::types::{HostFutureIncomingResponse, OutgoingRequestConfig};
 use wasmtime_wasi_http::p2::{
@@ -201,6 +215,8 @@ pub struct DurableHttpHooks {
     /// between live and replay modes. When `true`, outgoing HTTP requests are
     /// deferred so that they can be replayed from the oplog instead.
     pub is_replay: Arc<AtomicBool>,
+    /// When set by a test harness, outgoing HTTP requests are redirected to this mock server
+    pub http_mock_url: Option<url::Url>,
 }
 
 impl WasiHttpHooks for DurableHttpHooks {
@@ -210,6 +226,10 @@ impl WasiHttpHooks for DurableHttpHooks {
         config: OutgoingRequestConfig,
         body_completion: Option<BodyCompletionReceiver>,
     ) -> HttpResult<HostFutureIncomingResponse> {
+        let (request, config) = match &self.http_mock_url {
+            Some(http_mock_url) => redirect_to_http_mock(request, config, http_mock_url)?,
+            None => (request, config),
+        };
         let connection_pool = self.connection_pool.clone();
         if self.is_replay.load(std::sync::atomic::Ordering::Acquire) {
             // If this is a replay, we must not actually send the request, but we have to store it in the
@@ -239,6 +259,45 @@ impl WasiHttpHooks for DurableHttpHooks {
     }
 }
 
+/// Sends a request to the HTTP mock server instead of its original destination, keeping its path
+/// and query and passing the original URL in the [`HTTP_MOCK_ORIGINAL_URL_HEADER`] header.
+fn redirect_to_http_mock(
+    mut request: hyper::Request<HyperOutgoingBody>,
+    mut config: OutgoingRequestConfig,
+    http_mock_url: &url::Url,
+) -> HttpResult<(hyper::Request<HyperOutgoingBody>, OutgoingRequestConfig)> {
+    let original_url = request.uri().to_string();
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or("/");
+    let uri: hyper::Uri = format!(
+        "{}{path_and_query}",
+        http_mock_url.as_str().trim_end_matches('/')
+    )
+    .parse()
+    .map_err(|_| ErrorCode::HttpRequestUriInvalid)?;
+    let authority = uri
+        .authority()
+        .ok_or(ErrorCode::HttpRequestUriInvalid)?
+        .as_str()
+        .to_string();
+
+    config.use_tls = uri.scheme() == Some(&http::uri::Scheme::HTTPS);
+    *request.uri_mut() = uri;
+    let headers = request.headers_mut();
+    headers.insert(
+        http::header::HOST,
+        http::HeaderValue::from_str(&authority).map_err(|_| ErrorCode::HttpRequestUriInvalid)?,
+    );
+    headers.insert(
+        HTTP_MOCK_ORIGINAL_URL_HEADER,
+        http::HeaderValue::from_str(&original_url).map_err(|_| ErrorCode::HttpRequestUriInvalid)?,
+    );
+    Ok((request, config))
+}
+
 /// Controls how strictly the host filters side-effects performed by user code during an
 /// agent invocation.
 ///
@@ -257,6 +316,14 @@ pub enum InvocationStrictness {
     ReadOnly,
 }
 
+/// Deadline of the agent method invocation currently running, armed by the worker-executor
+/// from the method's declared timeout and the deadline propagated in the invocation context.
+#[derive(Debug, Clone)]
+struct InvocationDeadline {
+    method_name: String,
+    deadline: Timestamp,
+}
+
 /// Partial implementation of the WorkerCtx interfaces for adding durable execution to workers.
 pub struct DurableWorkerCtx<Ctx: WorkerCtx> {
     table: Arc<Mutex<ResourceTable>>, // Required because of the dropped Sync constraints in https://github.com/bytecodealliance/wasmtime/pull/7802
@@ -271,6 +338,7 @@ pub struct DurableWorkerCtx<Ctx: WorkerCtx> {
     execution_status: Arc<RwLock<ExecutionStatus>>,
     pub websocket_connection_pool: websocket::WebSocketConnectionPool,
     resource_limits: Arc<AtomicResourceEntry>,
+    test_hooks: Option<Arc<TestHooks>>,
     _store_alive_guard: StoreAliveGuard,
 }
 
@@ -305,6 +373,37 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         IdempotencyKey::derived(&current_idempotency_key, idempotency_key_oplog_index)
     }
 
+    /// Fails only the invocation that exceeded its deadline. The error entry stays the recorded
+    /// result of the invocation, while a jump over the invocation makes replay and the agent's
+    /// status ignore it, so the agent restarts and continues with its pending invocations.
+    async fn fail_invocation_exceeding_deadline(
+        &self,
+        full_function_name: &str,
+        idempotency_key: &IdempotencyKey,
+        trap_type: &TrapType,
+        invocation_region: OplogRegion,
+    ) -> RetryDecision {
+        debug!(
+            "Invocation {idempotency_key} exceeded its deadline, skipping {invocation_region} and restarting"
+        );
+
+        let worker = self.public_state.worker();
+        worker
+            .add_and_commit_oplog(OplogEntry::jump(invocation_region))
+            .await;
+        worker
+            .store_single_invocation_failure(idempotency_key, trap_type)
+            .await;
+
+        self.public_state.event_service().emit_invocation_finished(
+            full_function_name,
+            idempotency_key,
+            self.is_live(),
+        );
+
+        RetryDecision::Immediate
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
         owned_agent_id: OwnedAgentId,
@@ -313,6 +412,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         worker_service: Arc<dyn WorkerService>,
         worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn RdbmsService>,
         quota_service: Arc<dyn QuotaService>,
@@ -340,6 +440,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         original_phantom_id: Option<Uuid>,
         per_invocation_http_call_limit: u64,
         per_invocation_rpc_call_limit: u64,
+        test_hooks: Option<Arc<TestHooks>>,
     ) -> Result<Self, WorkerExecutorError> {
         let worker_dir = Arc::new(
             if let Some(root) = &config.filesystem_storage.deterministic_root_dir {
@@ -461,6 +562,9 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         let http_hooks = DurableHttpHooks {
             connection_pool: http_connection_pool,
             is_replay: Arc::new(AtomicBool::new(false)),
+            http_mock_url: test_hooks
+                .as_ref()
+                .and_then(|test_hooks| test_hooks.http_mock_url.clone()),
         };
         Ok(DurableWorkerCtx {
             table: Arc::new(Mutex::new(table)),
@@ -485,6 +589,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
                 worker_service,
                 worker_enumeration_service,
                 key_value_service,
+                topic_service,
                 blob_store_service,
                 rdbms_service,
                 quota_service,
@@ -520,6 +625,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             worker_dir,
             execution_status,
             resource_limits,
+            test_hooks,
             _store_alive_guard: StoreAliveGuard::new(),
         })
     }
@@ -687,6 +793,51 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         &self.state.component_metadata
     }
 
+    /// Whether the agent's filesystem is checkpointed together with automatic snapshots
+    pub fn checkpoints_filesystem(&self) -> bool {
+        self.state.config.filesystem_storage.checkpoint_on_snapshot
+    }
+
+    /// Archives the agent's filesystem as the checkpoint belonging to the snapshot entry
+    /// at `snapshot_index`.
+    pub async fn checkpoint_filesystem(
+        &self,
+        snapshot_index: OplogIndex,
+    ) -> Result<(), WorkerExecutorError> {
+        let read_only_paths = self.state.read_only_paths.read().unwrap().clone();
+        self.public_state
+            .worker()
+            .filesystem_checkpoint_service()
+            .save(
+                &self.owned_agent_id,
+                self.agent_mode(),
+                snapshot_index,
+                self.worker_dir.path(),
+                &read_only_paths,
+            )
+            .await
+    }
+
+    /// Restores the agent's filesystem from the checkpoint belonging to the snapshot entry
+    /// at `snapshot_index`. Returns `false` if there is no such checkpoint.
+    pub async fn restore_filesystem_checkpoint(
+        &self,
+        snapshot_index: OplogIndex,
+    ) -> Result<bool, WorkerExecutorError> {
+        let read_only_paths = self.state.read_only_paths.read().unwrap().clone();
+        self.public_state
+            .worker()
+            .filesystem_checkpoint_service()
+            .restore(
+                &self.owned_agent_id,
+                self.agent_mode(),
+                snapshot_index,
+                self.worker_dir.path(),
+                &read_only_paths,
+            )
+            .await
+    }
+
     pub fn agent_type_provision_config(&self) -> Option<&AgentTypeProvisionConfig> {
         self.state.agent_id.as_ref().and_then(|agent_id| {
             self.component_metadata()
@@ -947,6 +1098,10 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
                 error: AgentError::ReadOnlyViolation(_),
                 ..
             } => Some(RetryDecision::None),
+            TrapType::Error {
+                error: AgentError::InvocationDeadlineExceeded(_),
+                ..
+            } => Some(RetryDecision::None),
             TrapType::Error {
                 error: AgentError::InternalError(_),
                 ..
@@ -1003,6 +1158,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             AgentError::EphemeralFuelExhausted(_) => "ephemeral-fuel-exhausted",
             AgentError::EphemeralCannotSuspend(_) => "ephemeral-cannot-suspend",
             AgentError::ReadOnlyViolation(_) => "read-only-violation",
+            AgentError::InvocationDeadlineExceeded(_) => "invocation-deadline-exceeded",
         }
     }
 
@@ -2163,17 +2319,17 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             .read(snapshot_index)
             .await;
 
-        let (data_payload, mime_type) = match oplog_entry {
+        let (data_payload, mime_type, is_automatic_snapshot) = match oplog_entry {
             OplogEntry::Snapshot {
                 data, mime_type, ..
-            } => (data, mime_type),
+            } => (data, mime_type, true),
             OplogEntry::PendingUpdate {
                 description:
                     UpdateDescription::SnapshotBased {
                         payload, mime_type, ..
                     },
                 ..
-            } => (payload, mime_type),
+            } => (payload, mime_type, false),
             _ => {
                 warn!(
                     "Expected Snapshot entry at oplog index {snapshot_index}, found different entry; falling back to full replay"
@@ -2221,6 +2377,48 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             }
         };
 
+        if is_automatic_snapshot
+            && store
+                .as_context()
+                .data()
+                .durable_ctx()
+                .checkpoints_filesystem()
+        {
+            let restored = store
+                .as_context()
+                .data()
+                .durable_ctx()
+                .restore_filesystem_checkpoint(snapshot_index)
+                .await;
+            let restored = match restored {
+                Ok(restored) => restored,
+                Err(err) => {
+                    warn!("Failed to restore filesystem checkpoint: {err}");
+                    false
+                }
+            };
+            if !restored {
+                warn!(
+                    "No usable filesystem checkpoint for snapshot at oplog index {snapshot_index}; falling back to full replay"
+                );
+                if let Err(err) = store
+                    .as_context_mut()
+                    .data_mut()
+                    .durable_ctx_mut()
+                    .state
+                    .replay_state
+                    .drop_override_and_restart()
+                    .await
+                {
+                    warn!(
+                        "Failed to restart replay state after missing filesystem checkpoint: {err}"
+                    );
+                    return SnapshotRecoveryResult::Failed;
+                }
+                return SnapshotRecoveryResult::NotAttempted;
+            }
+        }
+
         let component_metadata = store
             .as_context()
             .data()
@@ -2379,6 +2577,60 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         self.state.open_websocket_connections.remove(&rep);
     }
 
+    /// Reads the outputs recorded by `on_agent_invocation_output_replaced` when replaying an
+    /// agent method invocation whose output was replaced by invocation interceptor plugins.
+    ///
+    /// Returns `None` if the output of the replayed invocation was not replaced.
+    async fn replay_replaced_agent_invocation_output(
+        &mut self,
+    ) -> Result<Option<(UntypedDataValue, UntypedDataValue)>, WorkerExecutorError> {
+        let function_name = HostFunctionName::Custom(REPLACED_AGENT_INVOCATION_OUTPUT.to_string());
+        let Some(handle) = self
+            .state
+            .replay_state
+            .try_claim_concurrent_start(&function_name)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let response = match self.state.replay_state.await_resolution(handle).await? {
+            Resolution::Completed {
+                response: Some(response),
+                ..
+            } => response,
+            resolution => {
+                return Err(WorkerExecutorError::unexpected_oplog_entry(
+                    "End { response: Some(..) }",
+                    format!("{resolution:?}"),
+                ));
+            }
+        };
+        let response: HostResponse = self
+            .public_state
+            .worker()
+            .oplog()
+            .download_payload(response)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!("End payload cannot be downloaded: {err}"))
+            })?;
+        match response {
+            HostResponse::Custom(outputs) => {
+                <(UntypedDataValue, UntypedDataValue)>::from_value_and_type(outputs)
+                    .map(Some)
+                    .map_err(|err| {
+                        WorkerExecutorError::runtime(format!(
+                            "Failed to decode the replaced agent invocation output: {err}"
+                        ))
+                    })
+            }
+            other => Err(WorkerExecutorError::unexpected_oplog_entry(
+                "HostResponse::Custom",
+                format!("{other:?}"),
+            )),
+        }
+    }
+
     pub(crate) fn websocket_connection_info(&self, rep: u32) -> Option<WebSocketConnectionInfo> {
         self.state
             .open_websocket_connections
@@ -2642,6 +2894,7 @@ impl<Ctx: WorkerCtx> StatusManagement for DurableWorkerCtx<Ctx> {
                     agent_mode,
                     timestamp: Timestamp::now_utc(),
                     interrupt_signal,
+                    invocation_deadline: None,
                 };
             }
             ExecutionStatus::Interrupting { .. } => {}
@@ -2652,12 +2905,33 @@ impl<Ctx: WorkerCtx> StatusManagement for DurableWorkerCtx<Ctx> {
                     agent_mode,
                     timestamp: Timestamp::now_utc(),
                     interrupt_signal,
+                    invocation_deadline: None,
                 };
             }
         }
     }
 }
 
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Makes host calls waiting on the interrupt signal stop once the invocation deadline passes
+    fn set_execution_status_deadline(&self, deadline: Option<Timestamp>) {
+        let mut execution_status = self.execution_status.write().unwrap();
+        if let ExecutionStatus::Running {
+            invocation_deadline,
+            ..
+        } = &mut *execution_status
+        {
+            *invocation_deadline = deadline;
+        }
+    }
+
+    /// Whether the running invocation has a deadline. Such invocations must not suspend while
+    /// waiting for a promise, as nothing would resume them to report the deadline.
+    fn has_invocation_deadline(&self) -> bool {
+        self.state.invocation_deadline.is_some()
+    }
+}
+
 #[async_trait]
 impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
     async fn on_agent_invocation_started(
@@ -2767,10 +3041,37 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
             }
         };
 
-        if let Some(entry) = oplog_entry {
-            self.public_state.worker().add_and_commit_oplog(entry).await;
+        let added_entry_index = match oplog_entry {
+            Some(entry) => Some(self.public_state.worker().add_and_commit_oplog(entry).await),
+            None => None,
         };
 
+        if let TrapType::Error {
+            error: AgentError::InvocationDeadlineExceeded(_),
+            ..
+        } = trap_type
+            && self.agent_mode() == AgentMode::Durable
+            && let Some(error_index) = added_entry_index
+            && let Some(idempotency_key) = self.state.get_current_idempotency_key()
+            && let Some(invocation_start) = self
+                .public_state
+                .worker()
+                .find_nth_invocation_from_end(1)
+                .await
+        {
+            return self
+                .fail_invocation_exceeding_deadline(
+                    full_function_name,
+                    &idempotency_key,
+                    trap_type,
+                    OplogRegion {
+                        start: invocation_start,
+                        end: error_index.next(), // skipping the Jump entry too
+                    },
+                )
+                .await;
+        }
+
         let latest_status = self
             .public_state
             .worker()
@@ -2823,6 +3124,33 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
         self.state.read_only_method_name = Some(method_name);
     }
 
+    fn arm_invocation_deadline(&mut self, method_name: String, deadline: Timestamp) {
+        self.state.invocation_deadline = Some(InvocationDeadline {
+            method_name,
+            deadline,
+        });
+        self.set_execution_status_deadline(Some(deadline));
+    }
+
+    fn disarm_invocation_deadline(&mut self) {
+        self.state.invocation_deadline = None;
+        self.set_execution_status_deadline(None);
+    }
+
+    fn check_invocation_deadline(&self) -> Option<AgentError> {
+        let armed = self.state.invocation_deadline.as_ref()?;
+        if Timestamp::now_utc() >= armed.deadline {
+            Some(AgentError::InvocationDeadlineExceeded(
+                InvocationDeadlineExceededError {
+                    method: armed.method_name.clone(),
+                    deadline: armed.deadline,
+                },
+            ))
+        } else {
+            None
+        }
+    }
+
     fn exit_read_only_mode(&mut self) {
         match self.state.invocation_strictness {
             InvocationStrictness::ReadOnly => {
@@ -2870,7 +3198,8 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
                     | AgentInvocationResult::ManualUpdate
                     | AgentInvocationResult::LoadSnapshot { .. }
                     | AgentInvocationResult::SaveSnapshot { .. }
-                    | AgentInvocationResult::ProcessOplogEntries { .. } => true,
+                    | AgentInvocationResult::ProcessOplogEntries { .. }
+                    | AgentInvocationResult::InterceptInvocation { .. } => true,
                 };
 
                 self.public_state
@@ -2935,6 +3264,23 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
                 }
             }
         } else {
+            if let AgentInvocationResult::AgentMethod { output: replayed } = &mut output.result
+                && let Some((original, replaced)) =
+                    self.replay_replaced_agent_invocation_output().await?
+            {
+                let original = AgentInvocationResult::AgentMethod { output: original };
+                let replayed_result = AgentInvocationResult::AgentMethod {
+                    output: replayed.clone(),
+                };
+                if !original.replay_equivalent(&replayed_result) {
+                    return Err(WorkerExecutorError::unexpected_oplog_entry(
+                        format!("{full_function_name} => {original:?}"),
+                        format!("{full_function_name} => {replayed_result:?}"),
+                    ));
+                }
+                *replayed = replaced;
+            }
+
             let response = self
                 .state
                 .replay_state
@@ -2954,6 +3300,23 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
         Ok(())
     }
 
+    async fn on_agent_invocation_output_replaced(
+        &mut self,
+        original: &UntypedDataValue,
+        replaced: &UntypedDataValue,
+    ) -> Result<(), WorkerExecutorError> {
+        if self.state.is_live() && self.state.snapshotting_mode.is_none() {
+            self.persist_durable_function_invocation(
+                HostFunctionName::Custom(REPLACED_AGENT_INVOCATION_OUTPUT.to_string()),
+                &HostRequest::NoInput(HostRequestNoInput {}),
+                &HostResponse::Custom((original.clone(), replaced.clone()).into_value_and_type()),
+                DurableFunctionType::ReadRemote,
+            )
+            .await;
+        }
+        Ok(())
+    }
+
     async fn get_current_retry_point(&self) -> OplogIndex {
         self.state.effective_retry_point()
     }
@@ -3654,6 +4017,9 @@ impl<Ctx: WorkerCtx> ExternalOperations<Ctx> for DurableWorkerCtx<Ctx> {
         this.oplog_processor_plugin()
             .on_shard_assignment_changed()
             .await?;
+        this.invocation_interceptor_plugin()
+            .on_shard_assignment_changed()
+            .await?;
         let workers = this.worker_service().get_running_workers_in_shards().await;
 
         debug!(workers = ?workers, "Recovering running workers");
@@ -4387,6 +4753,7 @@ struct PrivateDurableWorkerState {
     worker_service: Arc<dyn WorkerService>,
     worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService>,
     key_value_service: Arc<dyn KeyValueService>,
+    topic_service: Arc<dyn TopicService>,
     blob_store_service: Arc<dyn BlobStoreService>,
     rdbms_service: Arc<dyn RdbmsService>,
     quota_service: Arc<dyn QuotaService>,
@@ -4442,6 +4809,11 @@ struct PrivateDurableWorkerState {
     /// the invocation entry point so it can be reported in `AgentError::ReadOnlyViolation`.
     read_only_method_name: Option<String>,
 
+    /// Deadline of the current agent method invocation, checked from the epoch deadline
+    /// callback and raced against host calls through the execution status' interrupt signal.
+    /// `None` when the invocation has no timeout and no propagated deadline.
+    invocation_deadline: Option<InvocationDeadline>,
+
     component_metadata: Component,
 
     total_linear_memory_size: u64,
@@ -4567,6 +4939,7 @@ impl PrivateDurableWorkerState {
         worker_service: Arc<dyn WorkerService>,
         worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn RdbmsService>,
         quota_service: Arc<dyn QuotaService>,
@@ -4627,6 +5000,7 @@ impl PrivateDurableWorkerState {
             worker_service,
             worker_enumeration_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             quota_service,
@@ -4652,6 +5026,7 @@ impl PrivateDurableWorkerState {
             snapshotting_mode: None,
             invocation_strictness: InvocationStrictness::Normal,
             read_only_method_name: None,
+            invocation_deadline: None,
             component_metadata,
             total_linear_memory_size,
             current_filesystem_storage_usage,
@@ -5019,6 +5394,7 @@ impl PrivateDurableWorkerState {
             .create(
                 &self.owned_agent_id.agent_id,
                 self.current_oplog_index().await,
+                &PromiseOptions::default(),
             )
             .await;
 
@@ -32,9 +32,12 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
             .await?;
         let result = handle
             .run(self, async |ctx| -> wasmtime::Result<_> {
-                let result = {
-                    let mut view = ctx.as_wasi_view();
-                    Host::insecure_seed(view.random()).await?
+                let result = match ctx.deterministic_sources().await {
+                    Some(mut sources) => (sources.random_u64(), sources.random_u64()),
+                    None => {
+                        let mut view = ctx.as_wasi_view();
+                        Host::insecure_seed(view.random()).await?
+                    }
                 };
                 Ok(HostResponseRandomSeed {
                     lo: result.0,
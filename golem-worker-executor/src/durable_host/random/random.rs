@@ -32,9 +32,12 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         .await?;
         let result = handle
             .run(self, async |ctx| -> wasmtime::Result<_> {
-                let bytes = {
-                    let mut view = ctx.as_wasi_view();
-                    Host::get_random_bytes(view.random(), length).await?
+                let bytes = match ctx.deterministic_sources().await {
+                    Some(mut sources) => sources.random_bytes(length),
+                    None => {
+                        let mut view = ctx.as_wasi_view();
+                        Host::get_random_bytes(view.random(), length).await?
+                    }
                 };
                 Ok(HostResponseRandomBytes { bytes })
             })
@@ -52,9 +55,12 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         .await?;
         let result = handle
             .run(self, async |ctx| -> wasmtime::Result<_> {
-                let value = {
-                    let mut view = ctx.as_wasi_view();
-                    Host::get_random_u64(view.random()).await?
+                let value = match ctx.deterministic_sources().await {
+                    Some(mut sources) => sources.random_u64(),
+                    None => {
+                        let mut view = ctx.as_wasi_view();
+                        Host::get_random_u64(view.random()).await?
+                    }
                 };
                 Ok(HostResponseRandomU64 { value })
             })
@@ -14,16 +14,21 @@
 
 use crate::durable_host::DurableWorkerCtx;
 use crate::durable_host::rdbms::{
-    FromRdbmsValue, RdbmsConnection, RdbmsDurabilityPairs, RdbmsResultStreamEntry,
-    RdbmsTransactionEntry, begin_db_transaction, db_connection_drop, db_connection_durable_execute,
-    db_connection_durable_query, db_connection_durable_query_stream, db_result_stream_drop,
-    db_result_stream_durable_get_columns, db_result_stream_durable_get_next, db_transaction_drop,
-    db_transaction_durable_commit, db_transaction_durable_execute, db_transaction_durable_query,
-    db_transaction_durable_query_stream, db_transaction_durable_rollback, open_db_connection,
+    FromRdbmsValue, RdbmsConnection, RdbmsDurabilityPairs, RdbmsPreparedStatementEntry,
+    RdbmsResultStreamEntry, RdbmsTransactionEntry, begin_db_transaction, db_connection_drop,
+    db_connection_durable_execute, db_connection_durable_execute_batch,
+    db_connection_durable_prepare, db_connection_durable_query, db_connection_durable_query_stream,
+    db_prepared_statement_drop, db_prepared_statement_durable_execute,
+    db_prepared_statement_durable_execute_batch, db_prepared_statement_durable_query,
+    db_result_stream_drop, db_result_stream_durable_get_columns, db_result_stream_durable_get_next,
+    db_transaction_drop, db_transaction_durable_commit, db_transaction_durable_execute,
+    db_transaction_durable_execute_batch, db_transaction_durable_prepare,
+    db_transaction_durable_query, db_transaction_durable_query_stream,
+    db_transaction_durable_rollback, open_db_connection,
 };
 use crate::preview2::golem::rdbms::ignite2::{
-    DbColumn, DbResult, DbRow, DbValue, Error, Host, HostDbConnection, HostDbResultStream,
-    HostDbTransaction,
+    DbColumn, DbResult, DbRow, DbValue, Error, Host, HostDbConnection, HostDbPreparedStatement,
+    HostDbResultStream, HostDbTransaction,
 };
 use crate::services::rdbms::ignite::IgniteType;
 use crate::services::rdbms::ignite::types as ignite_types;
@@ -41,6 +46,10 @@ impl RdbmsDurabilityPairs for IgniteType {
     type TxnQueryStream = RdbmsIgnite2DbTransactionQueryStream;
     type StreamGetColumns = RdbmsIgnite2DbResultStreamGetColumns;
     type StreamGetNext = RdbmsIgnite2DbResultStreamGetNext;
+    type ConnPrepare = RdbmsIgnite2DbConnectionPrepare;
+    type ConnExecuteBatch = RdbmsIgnite2DbConnectionExecuteBatch;
+    type TxnPrepare = RdbmsIgnite2DbTransactionPrepare;
+    type TxnExecuteBatch = RdbmsIgnite2DbTransactionExecuteBatch;
 }
 
 impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {}
@@ -84,6 +93,25 @@ impl<Ctx: WorkerCtx> HostDbConnection for DurableWorkerCtx<Ctx> {
         result.map(|r| r.map(|n| n as i64))
     }
 
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<Ignite2DbConnection>,
+        statement: String,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<i64>, Error>> {
+        let result: anyhow::Result<Result<Vec<u64>, Error>> =
+            db_connection_durable_execute_batch(statement, params, self, &self_).await;
+        result.map(|r| r.map(|counts| counts.into_iter().map(|n| n as i64).collect()))
+    }
+
+    async fn prepare(
+        &mut self,
+        self_: Resource<Ignite2DbConnection>,
+        statement: String,
+    ) -> anyhow::Result<Result<Resource<DbPreparedStatementEntry>, Error>> {
+        db_connection_durable_prepare(statement, self, &self_).await
+    }
+
     async fn begin_transaction(
         &mut self,
         self_: Resource<Ignite2DbConnection>,
@@ -154,6 +182,25 @@ impl<Ctx: WorkerCtx> HostDbTransaction for DurableWorkerCtx<Ctx> {
         result.map(|r| r.map(|n| n as i64))
     }
 
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        statement: String,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<i64>, Error>> {
+        let result: anyhow::Result<Result<Vec<u64>, Error>> =
+            db_transaction_durable_execute_batch(statement, params, self, &self_).await;
+        result.map(|r| r.map(|counts| counts.into_iter().map(|n| n as i64).collect()))
+    }
+
+    async fn prepare(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        statement: String,
+    ) -> anyhow::Result<Result<Resource<DbPreparedStatementEntry>, Error>> {
+        db_transaction_durable_prepare(statement, self, &self_).await
+    }
+
     async fn commit(
         &mut self,
         self_: Resource<DbTransactionEntry>,
@@ -173,6 +220,42 @@ impl<Ctx: WorkerCtx> HostDbTransaction for DurableWorkerCtx<Ctx> {
     }
 }
 
+pub type DbPreparedStatementEntry = RdbmsPreparedStatementEntry<IgniteType>;
+
+impl<Ctx: WorkerCtx> HostDbPreparedStatement for DurableWorkerCtx<Ctx> {
+    async fn query(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<DbValue>,
+    ) -> anyhow::Result<Result<DbResult, Error>> {
+        db_prepared_statement_durable_query(params, self, &self_).await
+    }
+
+    async fn execute(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<DbValue>,
+    ) -> anyhow::Result<Result<i64, Error>> {
+        let result: anyhow::Result<Result<u64, Error>> =
+            db_prepared_statement_durable_execute(params, self, &self_).await;
+        result.map(|r| r.map(|n| n as i64))
+    }
+
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<i64>, Error>> {
+        let result: anyhow::Result<Result<Vec<u64>, Error>> =
+            db_prepared_statement_durable_execute_batch(params, self, &self_).await;
+        result.map(|r| r.map(|counts| counts.into_iter().map(|n| n as i64).collect()))
+    }
+
+    async fn drop(&mut self, rep: Resource<DbPreparedStatementEntry>) -> anyhow::Result<()> {
+        db_prepared_statement_drop(self, rep).await
+    }
+}
+
 // ── FromRdbmsValue impls ──────────────────────────────────────────────────────
 
 // WIT params → service DbValue (used by durable execute/query helpers)
@@ -14,7 +14,7 @@
 
 use crate::durable_host::concurrent::{CallHandle, CallReplayOutcome, NotCancellable};
 use crate::durable_host::durability::{HostFailureKind, InFunctionRetryHost};
-use crate::durable_host::rdbms::serialized::RdbmsRequest;
+use crate::durable_host::rdbms::serialized::{RdbmsBatchRequest, RdbmsRequest};
 use crate::durable_host::{
     DurabilityHost, DurableWorkerCtx, InternalRetryResult, RemoteTransactionHandler,
 };
@@ -25,9 +25,10 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use golem_common::model::oplog::HostPayloadPair;
 use golem_common::model::oplog::{
-    DurableFunctionType, HostRequestGolemRdbmsRequest, HostRequestNoInput,
-    HostResponseGolemRdbmsColumns, HostResponseGolemRdbmsRequest, HostResponseGolemRdbmsResult,
-    HostResponseGolemRdbmsResultChunk, HostResponseGolemRdbmsRowCount,
+    DurableFunctionType, HostRequestGolemRdbmsBatchRequest, HostRequestGolemRdbmsRequest,
+    HostRequestGolemRdbmsSavepoint, HostRequestNoInput, HostResponseGolemRdbmsColumns,
+    HostResponseGolemRdbmsRequest, HostResponseGolemRdbmsResult, HostResponseGolemRdbmsResultChunk,
+    HostResponseGolemRdbmsRowCount, HostResponseGolemRdbmsRowCounts, HostResponseGolemRdbmsUnit,
 };
 use golem_common::model::retry_policy::RetryProperties;
 use golem_common::model::{AgentId, OplogIndex, RdbmsPoolKey, RetryContext, TransactionId};
@@ -76,15 +77,25 @@ pub trait RdbmsDurabilityPairs {
     type TxnQueryStream: HostPayloadPair<Req = HostRequestNoInput, Resp = HostResponseGolemRdbmsRequest>;
     type StreamGetColumns: HostPayloadPair<Req = HostRequestNoInput, Resp = HostResponseGolemRdbmsColumns>;
     type StreamGetNext: HostPayloadPair<Req = HostRequestNoInput, Resp = HostResponseGolemRdbmsResultChunk>;
+    type ConnPrepare: HostPayloadPair<Req = HostRequestGolemRdbmsRequest, Resp = HostResponseGolemRdbmsUnit>;
+    type ConnExecuteBatch: HostPayloadPair<
+            Req = HostRequestGolemRdbmsBatchRequest,
+            Resp = HostResponseGolemRdbmsRowCounts,
+        >;
+    type TxnPrepare: HostPayloadPair<Req = HostRequestGolemRdbmsRequest, Resp = HostResponseGolemRdbmsUnit>;
+    type TxnExecuteBatch: HostPayloadPair<
+            Req = HostRequestGolemRdbmsBatchRequest,
+            Resp = HostResponseGolemRdbmsRowCounts,
+        >;
 }
 
 /// A transaction request prepared up front (before its side effect): the pool key, the open
 /// transaction handle, and the converted parameters, or the error that prevented preparing it.
-type PreparedTransactionRequest<T> = Result<
+type PreparedTransactionRequest<T, P = Vec<<T as RdbmsType>::DbValue>> = Result<
     (
         RdbmsPoolKey,
         Arc<dyn crate::services::rdbms::DbTransaction<T> + Send + Sync>,
-        Vec<<T as RdbmsType>::DbValue>,
+        P,
     ),
     RdbmsError,
 >;
@@ -431,6 +442,191 @@ where
     }
 }
 
+async fn db_connection_durable_execute_batch<Ctx, T, P, E>(
+    statement: String,
+    params: Vec<Vec<P>>,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsConnection<T>>,
+) -> anyhow::Result<Result<Vec<u64>, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    T::DbValue: FromRdbmsValue<P>,
+    E: From<RdbmsError>,
+{
+    let agent_id = ctx.state.owned_agent_id.agent_id.clone();
+    let pool_key = ctx
+        .as_wasi_view()
+        .table()
+        .get::<RdbmsConnection<T>>(entry)
+        .map(|v| v.pool_key.clone());
+
+    // See `db_connection_durable_execute`: the request payload is computed up front for the eager
+    // host-call `Start`. The whole batch runs in one implicit transaction, so it is safe to retry.
+    let prepared: Result<(RdbmsPoolKey, Vec<Vec<T::DbValue>>), RdbmsError> = match pool_key {
+        Ok(pool_key) => match to_db_value_rows::<T, P>(params, ctx.as_wasi_view().table()) {
+            Ok(db_params) => Ok((pool_key, db_params)),
+            Err(error) => Err(RdbmsError::QueryParameterFailure(error)),
+        },
+        Err(error) => Err(RdbmsError::other_response_failure(error)),
+    };
+
+    let request = HostRequestGolemRdbmsBatchRequest {
+        request: prepared.as_ref().ok().map(|(pool_key, db_params)| {
+            RdbmsBatchRequest::<T>::new(
+                pool_key.clone(),
+                statement.clone(),
+                db_params.clone(),
+                None,
+            )
+            .into()
+        }),
+    };
+
+    let mut handle = CallHandle::<T::ConnExecuteBatch, NotCancellable>::start(
+        ctx,
+        request,
+        DurableFunctionType::WriteRemote,
+    )
+    .await?;
+
+    let result = 'result: {
+        if !handle.is_live() {
+            match handle.replay(ctx).await? {
+                CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                CallReplayOutcome::Incomplete(live) => handle = live,
+            }
+        }
+
+        let result = match &prepared {
+            Ok((pool_key, db_params)) => {
+                let properties = rdbms_retry_properties(ctx, "execute-batch", pool_key);
+                loop {
+                    let result = ctx
+                        .state
+                        .rdbms_service
+                        .deref()
+                        .rdbms_type_service()
+                        .execute_batch(pool_key, &agent_id, &statement, db_params.clone())
+                        .await;
+                    match handle
+                        .try_trigger_retry_or_loop_with_properties(
+                            ctx,
+                            &result,
+                            classify_rdbms_error,
+                            properties.clone(),
+                        )
+                        .await?
+                    {
+                        InternalRetryResult::Persist => break result,
+                        InternalRetryResult::RetryInternally => continue,
+                    }
+                }
+            }
+            Err(error) => Err(error.clone()),
+        };
+
+        let result = result.map_err(|e| e.into());
+        handle
+            .complete(ctx, HostResponseGolemRdbmsRowCounts { result })
+            .await?
+    };
+
+    Ok(result.result.map_err(|e| RdbmsError::from(e).into()))
+}
+
+async fn db_connection_durable_prepare<Ctx, T, E>(
+    statement: String,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsConnection<T>>,
+) -> anyhow::Result<Result<Resource<RdbmsPreparedStatementEntry<T>>, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    E: From<RdbmsError>,
+{
+    let agent_id = ctx.state.owned_agent_id.agent_id.clone();
+    let pool_key = ctx
+        .as_wasi_view()
+        .table()
+        .get::<RdbmsConnection<T>>(entry)
+        .map(|v| v.pool_key.clone())
+        .map_err(RdbmsError::other_response_failure);
+
+    let request = HostRequestGolemRdbmsRequest {
+        request: pool_key.as_ref().ok().map(|pool_key| {
+            RdbmsRequest::<T>::new(pool_key.clone(), statement.clone(), vec![], None).into()
+        }),
+    };
+
+    let mut handle = CallHandle::<T::ConnPrepare, NotCancellable>::start(
+        ctx,
+        request,
+        DurableFunctionType::ReadRemote,
+    )
+    .await?;
+
+    let result = 'result: {
+        if !handle.is_live() {
+            match handle.replay(ctx).await? {
+                CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                CallReplayOutcome::Incomplete(live) => handle = live,
+            }
+        }
+
+        let result = match &pool_key {
+            Ok(pool_key) => {
+                let properties = rdbms_retry_properties(ctx, "prepare", pool_key);
+                loop {
+                    let result = ctx
+                        .state
+                        .rdbms_service
+                        .deref()
+                        .rdbms_type_service()
+                        .prepare(pool_key, &agent_id, &statement)
+                        .await;
+                    match handle
+                        .try_trigger_retry_or_loop_with_properties(
+                            ctx,
+                            &result,
+                            classify_rdbms_error,
+                            properties.clone(),
+                        )
+                        .await?
+                    {
+                        InternalRetryResult::Persist => break result,
+                        InternalRetryResult::RetryInternally => continue,
+                    }
+                }
+            }
+            Err(error) => Err(error.clone()),
+        };
+
+        let result = result.map_err(|e| e.into());
+        handle
+            .complete(ctx, HostResponseGolemRdbmsUnit { result })
+            .await?
+    };
+
+    match (result.result, pool_key) {
+        (Ok(()), Ok(pool_key)) => {
+            let prepared_statement = RdbmsPreparedStatementEntry::new(
+                statement,
+                RdbmsPreparedStatementParent::Connection {
+                    handle: entry.rep(),
+                    pool_key,
+                },
+            );
+            let resource = ctx.as_wasi_view().table().push(prepared_statement)?;
+            Ok(Ok(resource))
+        }
+        (Ok(()), Err(error)) => Ok(Err(error.into())),
+        (Err(error), _) => Ok(Err(RdbmsError::from(error).into())),
+    }
+}
+
 async fn db_connection_drop<Ctx, T>(
     ctx: &mut DurableWorkerCtx<Ctx>,
     entry: Resource<RdbmsConnection<T>>,
@@ -922,6 +1118,280 @@ where
     }
 }
 
+async fn db_transaction_durable_execute_batch<Ctx, T, P, E>(
+    statement: String,
+    params: Vec<Vec<P>>,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsTransactionEntry<T>>,
+) -> anyhow::Result<Result<Vec<u64>, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    T::DbValue: FromRdbmsValue<P>,
+    E: From<RdbmsError>,
+{
+    let begin_oplog_idx = ctx.table().get(entry)?.begin_index;
+
+    let txn_pool_key = ctx
+        .as_wasi_view()
+        .table()
+        .get::<RdbmsTransactionEntry<T>>(entry)
+        .map(|v| v.pool_key.clone())
+        .ok();
+
+    // See `db_transaction_durable_execute`: the request payload is built up front, before the
+    // batch side effect.
+    let prepared: PreparedTransactionRequest<T, Vec<Vec<T::DbValue>>> =
+        match to_db_value_rows::<T, P>(params, ctx.as_wasi_view().table()) {
+            Ok(db_params) => match get_db_transaction(ctx, entry) {
+                Ok((pool_key, transaction)) => Ok((pool_key, transaction, db_params)),
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(RdbmsError::QueryParameterFailure(error)),
+        };
+
+    let request = HostRequestGolemRdbmsBatchRequest {
+        request: prepared
+            .as_ref()
+            .ok()
+            .map(|(pool_key, transaction, db_params)| {
+                RdbmsBatchRequest::<T>::new(
+                    pool_key.clone(),
+                    statement.clone(),
+                    db_params.clone(),
+                    Some(transaction.transaction_id()),
+                )
+                .into()
+            }),
+    };
+
+    let mut handle = CallHandle::<T::TxnExecuteBatch, NotCancellable>::start(
+        ctx,
+        request,
+        DurableFunctionType::WriteRemoteTransaction(Some(begin_oplog_idx)),
+    )
+    .await?;
+
+    let result = 'result: {
+        if !handle.is_live() {
+            break 'result handle.replay_expecting_completion(ctx).await?;
+        }
+
+        let result = match &prepared {
+            Ok((_pool_key, transaction, db_params)) => {
+                transaction
+                    .execute_batch(&statement, db_params.clone())
+                    .await
+            }
+            Err(error) => Err(error.clone()),
+        };
+        let pool_key_for_props = prepared
+            .as_ref()
+            .ok()
+            .map(|(pool_key, _, _)| pool_key.clone())
+            .or_else(|| txn_pool_key.clone());
+        if let Some(pool_key) = pool_key_for_props.as_ref() {
+            let properties = rdbms_retry_properties(ctx, "execute-batch", pool_key);
+            handle
+                .try_trigger_retry_with_properties(ctx, &result, classify_rdbms_error, properties)
+                .await?;
+        } else {
+            handle
+                .try_trigger_retry(ctx, &result, classify_rdbms_error)
+                .await?;
+        }
+
+        let result = result.map_err(|e| e.into());
+        handle
+            .complete(ctx, HostResponseGolemRdbmsRowCounts { result })
+            .await?
+    };
+
+    Ok(result.result.map_err(|e| RdbmsError::from(e).into()))
+}
+
+async fn db_transaction_durable_prepare<Ctx, T, E>(
+    statement: String,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsTransactionEntry<T>>,
+) -> anyhow::Result<Result<Resource<RdbmsPreparedStatementEntry<T>>, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    E: From<RdbmsError>,
+{
+    let begin_oplog_idx = ctx.table().get(entry)?.begin_index;
+
+    let txn_pool_key = ctx
+        .as_wasi_view()
+        .table()
+        .get::<RdbmsTransactionEntry<T>>(entry)
+        .map(|v| v.pool_key.clone())
+        .ok();
+
+    let transaction = get_db_transaction(ctx, entry);
+
+    let request = HostRequestGolemRdbmsRequest {
+        request: transaction.as_ref().ok().map(|(pool_key, transaction)| {
+            RdbmsRequest::<T>::new(
+                pool_key.clone(),
+                statement.clone(),
+                vec![],
+                Some(transaction.transaction_id()),
+            )
+            .into()
+        }),
+    };
+
+    let mut handle = CallHandle::<T::TxnPrepare, NotCancellable>::start(
+        ctx,
+        request,
+        DurableFunctionType::WriteRemoteTransaction(Some(begin_oplog_idx)),
+    )
+    .await?;
+
+    let result = 'result: {
+        if !handle.is_live() {
+            break 'result handle.replay_expecting_completion(ctx).await?;
+        }
+
+        let result = match &transaction {
+            Ok((_pool_key, transaction)) => transaction.prepare(&statement).await,
+            Err(error) => Err(error.clone()),
+        };
+        let pool_key_for_props = transaction
+            .as_ref()
+            .ok()
+            .map(|(pool_key, _)| pool_key.clone())
+            .or_else(|| txn_pool_key.clone());
+        if let Some(pool_key) = pool_key_for_props.as_ref() {
+            let properties = rdbms_retry_properties(ctx, "prepare", pool_key);
+            handle
+                .try_trigger_retry_with_properties(ctx, &result, classify_rdbms_error, properties)
+                .await?;
+        } else {
+            handle
+                .try_trigger_retry(ctx, &result, classify_rdbms_error)
+                .await?;
+        }
+
+        let result = result.map_err(|e| e.into());
+        handle
+            .complete(ctx, HostResponseGolemRdbmsUnit { result })
+            .await?
+    };
+
+    match result.result {
+        Ok(()) => {
+            let prepared_statement = RdbmsPreparedStatementEntry::new(
+                statement,
+                RdbmsPreparedStatementParent::Transaction {
+                    handle: entry.rep(),
+                    begin_index: begin_oplog_idx,
+                },
+            );
+            let resource = ctx.as_wasi_view().table().push(prepared_statement)?;
+            Ok(Ok(resource))
+        }
+        Err(error) => Ok(Err(RdbmsError::from(error).into())),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SavepointOperation {
+    Create,
+    Release,
+    RollbackTo,
+}
+
+impl SavepointOperation {
+    fn verb(&self) -> &'static str {
+        match self {
+            SavepointOperation::Create => "savepoint",
+            SavepointOperation::Release => "release-savepoint",
+            SavepointOperation::RollbackTo => "rollback-to-savepoint",
+        }
+    }
+}
+
+async fn db_transaction_durable_savepoint<Ctx, T, Pair, E>(
+    operation: SavepointOperation,
+    name: String,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsTransactionEntry<T>>,
+) -> anyhow::Result<Result<(), E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + 'static,
+    Pair: HostPayloadPair<Req = HostRequestGolemRdbmsSavepoint, Resp = HostResponseGolemRdbmsUnit>,
+    E: From<RdbmsError>,
+{
+    let begin_oplog_idx = ctx.table().get(entry)?.begin_index;
+
+    let txn_pool_key = ctx
+        .as_wasi_view()
+        .table()
+        .get::<RdbmsTransactionEntry<T>>(entry)
+        .map(|v| v.pool_key.clone())
+        .ok();
+
+    let transaction = get_db_transaction(ctx, entry);
+
+    let request = HostRequestGolemRdbmsSavepoint {
+        transaction_id: transaction
+            .as_ref()
+            .ok()
+            .map(|(_, transaction)| transaction.transaction_id()),
+        name: name.clone(),
+    };
+
+    let mut handle = CallHandle::<Pair, NotCancellable>::start(
+        ctx,
+        request,
+        DurableFunctionType::WriteRemoteTransaction(Some(begin_oplog_idx)),
+    )
+    .await?;
+
+    let result = 'result: {
+        if !handle.is_live() {
+            break 'result handle.replay_expecting_completion(ctx).await?;
+        }
+
+        let result = match &transaction {
+            Ok((_pool_key, transaction)) => match operation {
+                SavepointOperation::Create => transaction.savepoint(&name).await,
+                SavepointOperation::Release => transaction.release_savepoint(&name).await,
+                SavepointOperation::RollbackTo => transaction.rollback_to_savepoint(&name).await,
+            },
+            Err(error) => Err(error.clone()),
+        };
+        let pool_key_for_props = transaction
+            .as_ref()
+            .ok()
+            .map(|(pool_key, _)| pool_key.clone())
+            .or_else(|| txn_pool_key.clone());
+        if let Some(pool_key) = pool_key_for_props.as_ref() {
+            let properties = rdbms_retry_properties(ctx, operation.verb(), pool_key);
+            handle
+                .try_trigger_retry_with_properties(ctx, &result, classify_rdbms_error, properties)
+                .await?;
+        } else {
+            handle
+                .try_trigger_retry(ctx, &result, classify_rdbms_error)
+                .await?;
+        }
+
+        let result = result.map_err(|e| e.into());
+        handle
+            .complete(ctx, HostResponseGolemRdbmsUnit { result })
+            .await?
+    };
+
+    Ok(result.result.map_err(|e| RdbmsError::from(e).into()))
+}
+
 async fn db_transaction_durable_rollback<Ctx, T, E>(
     ctx: &mut DurableWorkerCtx<Ctx>,
     entry: &Resource<RdbmsTransactionEntry<T>>,
@@ -1227,6 +1697,184 @@ where
     }
 }
 
+/// A statement prepared on a connection or a transaction. Executions are delegated to the
+/// parent resource, so they are recorded exactly like the equivalent direct calls.
+pub struct RdbmsPreparedStatementEntry<T: RdbmsType> {
+    statement: String,
+    parent: RdbmsPreparedStatementParent,
+    _owner: PhantomData<T>,
+}
+
+impl<T: RdbmsType> RdbmsPreparedStatementEntry<T> {
+    fn new(statement: String, parent: RdbmsPreparedStatementParent) -> Self {
+        Self {
+            statement,
+            parent,
+            _owner: PhantomData,
+        }
+    }
+}
+
+/// Resource handle of the connection or transaction a statement was prepared on, together with
+/// enough of its identity to detect when the handle has been dropped and its slot reused.
+#[derive(Clone)]
+enum RdbmsPreparedStatementParent {
+    Connection {
+        handle: u32,
+        pool_key: RdbmsPoolKey,
+    },
+    Transaction {
+        handle: u32,
+        begin_index: OplogIndex,
+    },
+}
+
+/// Returns the statement and its parent, or an error if the parent resource no longer exists.
+fn get_db_prepared_statement<Ctx, T>(
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsPreparedStatementEntry<T>>,
+) -> anyhow::Result<Result<(String, RdbmsPreparedStatementParent), RdbmsError>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + 'static,
+{
+    let table = ctx.table();
+    let entry = table.get::<RdbmsPreparedStatementEntry<T>>(entry)?;
+    let parent_exists = match &entry.parent {
+        RdbmsPreparedStatementParent::Connection { handle, pool_key } => table
+            .get::<RdbmsConnection<T>>(&Resource::new_borrow(*handle))
+            .is_ok_and(|connection| &connection.pool_key == pool_key),
+        RdbmsPreparedStatementParent::Transaction {
+            handle,
+            begin_index,
+        } => table
+            .get::<RdbmsTransactionEntry<T>>(&Resource::new_borrow(*handle))
+            .is_ok_and(|transaction| &transaction.begin_index == begin_index),
+    };
+
+    if parent_exists {
+        Ok(Ok((entry.statement.clone(), entry.parent.clone())))
+    } else {
+        let parent = match entry.parent {
+            RdbmsPreparedStatementParent::Connection { .. } => "connection",
+            RdbmsPreparedStatementParent::Transaction { .. } => "transaction",
+        };
+        Ok(Err(RdbmsError::Other(format!(
+            "The {parent} this statement was prepared on has been dropped"
+        ))))
+    }
+}
+
+async fn db_prepared_statement_durable_query<Ctx, T, P, R, E>(
+    params: Vec<P>,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsPreparedStatementEntry<T>>,
+) -> anyhow::Result<Result<R, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    T::DbValue: FromRdbmsValue<P>,
+    R: FromRdbmsValue<crate::services::rdbms::DbResult<T>>,
+    E: From<RdbmsError>,
+{
+    let (statement, parent) = match get_db_prepared_statement(ctx, entry)? {
+        Ok(prepared) => prepared,
+        Err(error) => return Ok(Err(error.into())),
+    };
+    match parent {
+        RdbmsPreparedStatementParent::Connection { handle, .. } => {
+            db_connection_durable_query(statement, params, ctx, &Resource::new_borrow(handle)).await
+        }
+        RdbmsPreparedStatementParent::Transaction { handle, .. } => {
+            db_transaction_durable_query(statement, params, ctx, &Resource::new_borrow(handle))
+                .await
+        }
+    }
+}
+
+async fn db_prepared_statement_durable_execute<Ctx, T, P, E>(
+    params: Vec<P>,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsPreparedStatementEntry<T>>,
+) -> anyhow::Result<Result<u64, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    T::DbValue: FromRdbmsValue<P>,
+    E: From<RdbmsError>,
+{
+    let (statement, parent) = match get_db_prepared_statement(ctx, entry)? {
+        Ok(prepared) => prepared,
+        Err(error) => return Ok(Err(error.into())),
+    };
+    match parent {
+        RdbmsPreparedStatementParent::Connection { handle, .. } => {
+            db_connection_durable_execute(statement, params, ctx, &Resource::new_borrow(handle))
+                .await
+        }
+        RdbmsPreparedStatementParent::Transaction { handle, .. } => {
+            db_transaction_durable_execute(statement, params, ctx, &Resource::new_borrow(handle))
+                .await
+        }
+    }
+}
+
+async fn db_prepared_statement_durable_execute_batch<Ctx, T, P, E>(
+    params: Vec<Vec<P>>,
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: &Resource<RdbmsPreparedStatementEntry<T>>,
+) -> anyhow::Result<Result<Vec<u64>, E>>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + RdbmsDurabilityPairs + 'static,
+    dyn RdbmsService: RdbmsTypeService<T>,
+    T::DbValue: FromRdbmsValue<P>,
+    E: From<RdbmsError>,
+{
+    let (statement, parent) = match get_db_prepared_statement(ctx, entry)? {
+        Ok(prepared) => prepared,
+        Err(error) => return Ok(Err(error.into())),
+    };
+    match parent {
+        RdbmsPreparedStatementParent::Connection { handle, .. } => {
+            db_connection_durable_execute_batch(
+                statement,
+                params,
+                ctx,
+                &Resource::new_borrow(handle),
+            )
+            .await
+        }
+        RdbmsPreparedStatementParent::Transaction { handle, .. } => {
+            db_transaction_durable_execute_batch(
+                statement,
+                params,
+                ctx,
+                &Resource::new_borrow(handle),
+            )
+            .await
+        }
+    }
+}
+
+async fn db_prepared_statement_drop<Ctx, T>(
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    entry: Resource<RdbmsPreparedStatementEntry<T>>,
+) -> anyhow::Result<()>
+where
+    Ctx: WorkerCtx,
+    T: RdbmsType + 'static,
+{
+    ctx.observe_function_call(T::durability_prepared_statement_interface(), "drop");
+
+    ctx.as_wasi_view()
+        .table()
+        .delete::<RdbmsPreparedStatementEntry<T>>(entry)?;
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct RdbmsTransactionEntry<T: RdbmsType + 'static> {
     pool_key: RdbmsPoolKey,
@@ -1488,6 +2136,21 @@ where
     Ok(result)
 }
 
+fn to_db_value_rows<T, P>(
+    rows: Vec<Vec<P>>,
+    resource_table: &mut ResourceTable,
+) -> Result<Vec<Vec<T::DbValue>>, String>
+where
+    T: RdbmsType + 'static,
+    T::DbValue: FromRdbmsValue<P>,
+{
+    let mut result: Vec<Vec<T::DbValue>> = Vec::with_capacity(rows.len());
+    for row in rows {
+        result.push(to_db_values::<T, P>(row, resource_table)?);
+    }
+    Ok(result)
+}
+
 struct RdbmsRemoteTransactionHandler<T: RdbmsType> {
     pool_key: RdbmsPoolKey,
     agent_id: AgentId,
@@ -14,16 +14,21 @@
 
 use crate::durable_host::DurableWorkerCtx;
 use crate::durable_host::rdbms::{
-    FromRdbmsValue, RdbmsConnection, RdbmsDurabilityPairs, RdbmsResultStreamEntry,
-    RdbmsTransactionEntry, begin_db_transaction, db_connection_drop, db_connection_durable_execute,
-    db_connection_durable_query, db_connection_durable_query_stream, db_result_stream_drop,
-    db_result_stream_durable_get_columns, db_result_stream_durable_get_next, db_transaction_drop,
-    db_transaction_durable_commit, db_transaction_durable_execute, db_transaction_durable_query,
-    db_transaction_durable_query_stream, db_transaction_durable_rollback, open_db_connection,
+    FromRdbmsValue, RdbmsConnection, RdbmsDurabilityPairs, RdbmsPreparedStatementEntry,
+    RdbmsResultStreamEntry, RdbmsTransactionEntry, SavepointOperation, begin_db_transaction,
+    db_connection_drop, db_connection_durable_execute, db_connection_durable_execute_batch,
+    db_connection_durable_prepare, db_connection_durable_query, db_connection_durable_query_stream,
+    db_prepared_statement_drop, db_prepared_statement_durable_execute,
+    db_prepared_statement_durable_execute_batch, db_prepared_statement_durable_query,
+    db_result_stream_drop, db_result_stream_durable_get_columns, db_result_stream_durable_get_next,
+    db_transaction_drop, db_transaction_durable_commit, db_transaction_durable_execute,
+    db_transaction_durable_execute_batch, db_transaction_durable_prepare,
+    db_transaction_durable_query, db_transaction_durable_query_stream,
+    db_transaction_durable_rollback, db_transaction_durable_savepoint, open_db_connection,
 };
 use crate::preview2::golem::rdbms::mysql::{
     DbColumn, DbColumnType, DbResult, DbRow, DbValue, Error, Host, HostDbConnection,
-    HostDbResultStream, HostDbTransaction,
+    HostDbPreparedStatement, HostDbResultStream, HostDbTransaction,
 };
 use crate::services::rdbms::mysql::MysqlType;
 use crate::services::rdbms::mysql::types as mysql_types;
@@ -43,6 +48,10 @@ impl RdbmsDurabilityPairs for MysqlType {
     type TxnQueryStream = RdbmsMysqlDbTransactionQueryStream;
     type StreamGetColumns = RdbmsMysqlDbResultStreamGetColumns;
     type StreamGetNext = RdbmsMysqlDbResultStreamGetNext;
+    type ConnPrepare = RdbmsMysqlDbConnectionPrepare;
+    type ConnExecuteBatch = RdbmsMysqlDbConnectionExecuteBatch;
+    type TxnPrepare = RdbmsMysqlDbTransactionPrepare;
+    type TxnExecuteBatch = RdbmsMysqlDbTransactionExecuteBatch;
 }
 
 #[async_trait]
@@ -85,6 +94,23 @@ impl<Ctx: WorkerCtx> HostDbConnection for DurableWorkerCtx<Ctx> {
         db_connection_durable_execute(statement, params, self, &self_).await
     }
 
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<MysqlDbConnection>,
+        statement: String,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<u64>, Error>> {
+        db_connection_durable_execute_batch(statement, params, self, &self_).await
+    }
+
+    async fn prepare(
+        &mut self,
+        self_: Resource<MysqlDbConnection>,
+        statement: String,
+    ) -> anyhow::Result<Result<Resource<DbPreparedStatementEntry>, Error>> {
+        db_connection_durable_prepare(statement, self, &self_).await
+    }
+
     async fn begin_transaction(
         &mut self,
         self_: Resource<MysqlDbConnection>,
@@ -149,6 +175,65 @@ impl<Ctx: WorkerCtx> HostDbTransaction for DurableWorkerCtx<Ctx> {
         db_transaction_durable_execute(statement, params, self, &self_).await
     }
 
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        statement: String,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<u64>, Error>> {
+        db_transaction_durable_execute_batch(statement, params, self, &self_).await
+    }
+
+    async fn prepare(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        statement: String,
+    ) -> anyhow::Result<Result<Resource<DbPreparedStatementEntry>, Error>> {
+        db_transaction_durable_prepare(statement, self, &self_).await
+    }
+
+    async fn savepoint(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        name: String,
+    ) -> anyhow::Result<Result<(), Error>> {
+        db_transaction_durable_savepoint::<_, _, RdbmsMysqlDbTransactionSavepoint, _>(
+            SavepointOperation::Create,
+            name,
+            self,
+            &self_,
+        )
+        .await
+    }
+
+    async fn release_savepoint(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        name: String,
+    ) -> anyhow::Result<Result<(), Error>> {
+        db_transaction_durable_savepoint::<_, _, RdbmsMysqlDbTransactionReleaseSavepoint, _>(
+            SavepointOperation::Release,
+            name,
+            self,
+            &self_,
+        )
+        .await
+    }
+
+    async fn rollback_to_savepoint(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        name: String,
+    ) -> anyhow::Result<Result<(), Error>> {
+        db_transaction_durable_savepoint::<_, _, RdbmsMysqlDbTransactionRollbackToSavepoint, _>(
+            SavepointOperation::RollbackTo,
+            name,
+            self,
+            &self_,
+        )
+        .await
+    }
+
     async fn commit(
         &mut self,
         self_: Resource<DbTransactionEntry>,
@@ -168,6 +253,38 @@ impl<Ctx: WorkerCtx> HostDbTransaction for DurableWorkerCtx<Ctx> {
     }
 }
 
+pub type DbPreparedStatementEntry = RdbmsPreparedStatementEntry<MysqlType>;
+
+impl<Ctx: WorkerCtx> HostDbPreparedStatement for DurableWorkerCtx<Ctx> {
+    async fn query(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<DbValue>,
+    ) -> anyhow::Result<Result<DbResult, Error>> {
+        db_prepared_statement_durable_query(params, self, &self_).await
+    }
+
+    async fn execute(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<DbValue>,
+    ) -> anyhow::Result<Result<u64, Error>> {
+        db_prepared_statement_durable_execute(params, self, &self_).await
+    }
+
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<u64>, Error>> {
+        db_prepared_statement_durable_execute_batch(params, self, &self_).await
+    }
+
+    async fn drop(&mut self, rep: Resource<DbPreparedStatementEntry>) -> anyhow::Result<()> {
+        db_prepared_statement_drop(self, rep).await
+    }
+}
+
 impl TryFrom<DbValue> for mysql_types::DbValue {
     type Error = String;
     fn try_from(value: DbValue) -> Result<Self, Self::Error> {
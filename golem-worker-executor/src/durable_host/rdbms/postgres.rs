@@ -13,20 +13,25 @@
 // limitations under the License.
 
 use crate::durable_host::rdbms::{
-    FromRdbmsValue, RdbmsConnection, RdbmsDurabilityPairs, RdbmsResultStreamEntry,
-    RdbmsTransactionEntry, begin_db_transaction, db_connection_drop, db_connection_durable_execute,
-    db_connection_durable_query, db_connection_durable_query_stream, db_result_stream_drop,
-    db_result_stream_durable_get_columns, db_result_stream_durable_get_next, db_transaction_drop,
-    db_transaction_durable_commit, db_transaction_durable_execute, db_transaction_durable_query,
-    db_transaction_durable_query_stream, db_transaction_durable_rollback, open_db_connection,
+    FromRdbmsValue, RdbmsConnection, RdbmsDurabilityPairs, RdbmsPreparedStatementEntry,
+    RdbmsResultStreamEntry, RdbmsTransactionEntry, SavepointOperation, begin_db_transaction,
+    db_connection_drop, db_connection_durable_execute, db_connection_durable_execute_batch,
+    db_connection_durable_prepare, db_connection_durable_query, db_connection_durable_query_stream,
+    db_prepared_statement_drop, db_prepared_statement_durable_execute,
+    db_prepared_statement_durable_execute_batch, db_prepared_statement_durable_query,
+    db_result_stream_drop, db_result_stream_durable_get_columns, db_result_stream_durable_get_next,
+    db_transaction_drop, db_transaction_durable_commit, db_transaction_durable_execute,
+    db_transaction_durable_execute_batch, db_transaction_durable_prepare,
+    db_transaction_durable_query, db_transaction_durable_query_stream,
+    db_transaction_durable_rollback, db_transaction_durable_savepoint, open_db_connection,
 };
 use crate::durable_host::{DurabilityHost, DurableWorkerCtx};
 use crate::preview2::golem::rdbms::postgres::{
     Composite, CompositeType, Datebound, Daterange, DbColumn, DbColumnType, DbResult, DbRow,
     DbValue, Domain, DomainType, Enumeration, EnumerationType, Error, Host, HostDbConnection,
-    HostDbResultStream, HostDbTransaction, HostLazyDbColumnType, HostLazyDbValue, Int4bound,
-    Int4range, Int8bound, Int8range, Interval, Numbound, Numrange, Range, RangeType, SparseVec,
-    Tsbound, Tsrange, Tstzbound, Tstzrange, ValueBound, ValuesRange,
+    HostDbPreparedStatement, HostDbResultStream, HostDbTransaction, HostLazyDbColumnType,
+    HostLazyDbValue, Int4bound, Int4range, Int8bound, Int8range, Interval, Numbound, Numrange,
+    Range, RangeType, SparseVec, Tsbound, Tsrange, Tstzbound, Tstzrange, ValueBound, ValuesRange,
 };
 use crate::preview2::golem::rdbms::types::Timetz;
 use crate::services::rdbms::postgres::PostgresType;
@@ -49,6 +54,10 @@ impl RdbmsDurabilityPairs for PostgresType {
     type TxnQueryStream = RdbmsPostgresDbTransactionQueryStream;
     type StreamGetColumns = RdbmsPostgresDbResultStreamGetColumns;
     type StreamGetNext = RdbmsPostgresDbResultStreamGetNext;
+    type ConnPrepare = RdbmsPostgresDbConnectionPrepare;
+    type ConnExecuteBatch = RdbmsPostgresDbConnectionExecuteBatch;
+    type TxnPrepare = RdbmsPostgresDbTransactionPrepare;
+    type TxnExecuteBatch = RdbmsPostgresDbTransactionExecuteBatch;
 }
 
 impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {}
@@ -90,6 +99,23 @@ impl<Ctx: WorkerCtx> HostDbConnection for DurableWorkerCtx<Ctx> {
         db_connection_durable_execute(statement, params, self, &self_).await
     }
 
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<PostgresDbConnection>,
+        statement: String,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<u64>, Error>> {
+        db_connection_durable_execute_batch(statement, params, self, &self_).await
+    }
+
+    async fn prepare(
+        &mut self,
+        self_: Resource<PostgresDbConnection>,
+        statement: String,
+    ) -> anyhow::Result<Result<Resource<DbPreparedStatementEntry>, Error>> {
+        db_connection_durable_prepare(statement, self, &self_).await
+    }
+
     async fn begin_transaction(
         &mut self,
         self_: Resource<PostgresDbConnection>,
@@ -154,6 +180,65 @@ impl<Ctx: WorkerCtx> HostDbTransaction for DurableWorkerCtx<Ctx> {
         db_transaction_durable_execute(statement, params, self, &self_).await
     }
 
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        statement: String,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<u64>, Error>> {
+        db_transaction_durable_execute_batch(statement, params, self, &self_).await
+    }
+
+    async fn prepare(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        statement: String,
+    ) -> anyhow::Result<Result<Resource<DbPreparedStatementEntry>, Error>> {
+        db_transaction_durable_prepare(statement, self, &self_).await
+    }
+
+    async fn savepoint(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        name: String,
+    ) -> anyhow::Result<Result<(), Error>> {
+        db_transaction_durable_savepoint::<_, _, RdbmsPostgresDbTransactionSavepoint, _>(
+            SavepointOperation::Create,
+            name,
+            self,
+            &self_,
+        )
+        .await
+    }
+
+    async fn release_savepoint(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        name: String,
+    ) -> anyhow::Result<Result<(), Error>> {
+        db_transaction_durable_savepoint::<_, _, RdbmsPostgresDbTransactionReleaseSavepoint, _>(
+            SavepointOperation::Release,
+            name,
+            self,
+            &self_,
+        )
+        .await
+    }
+
+    async fn rollback_to_savepoint(
+        &mut self,
+        self_: Resource<DbTransactionEntry>,
+        name: String,
+    ) -> anyhow::Result<Result<(), Error>> {
+        db_transaction_durable_savepoint::<_, _, RdbmsPostgresDbTransactionRollbackToSavepoint, _>(
+            SavepointOperation::RollbackTo,
+            name,
+            self,
+            &self_,
+        )
+        .await
+    }
+
     async fn commit(
         &mut self,
         self_: Resource<DbTransactionEntry>,
@@ -173,6 +258,38 @@ impl<Ctx: WorkerCtx> HostDbTransaction for DurableWorkerCtx<Ctx> {
     }
 }
 
+pub type DbPreparedStatementEntry = RdbmsPreparedStatementEntry<PostgresType>;
+
+impl<Ctx: WorkerCtx> HostDbPreparedStatement for DurableWorkerCtx<Ctx> {
+    async fn query(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<DbValue>,
+    ) -> anyhow::Result<Result<DbResult, Error>> {
+        db_prepared_statement_durable_query(params, self, &self_).await
+    }
+
+    async fn execute(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<DbValue>,
+    ) -> anyhow::Result<Result<u64, Error>> {
+        db_prepared_statement_durable_execute(params, self, &self_).await
+    }
+
+    async fn execute_batch(
+        &mut self,
+        self_: Resource<DbPreparedStatementEntry>,
+        params: Vec<Vec<DbValue>>,
+    ) -> anyhow::Result<Result<Vec<u64>, Error>> {
+        db_prepared_statement_durable_execute_batch(params, self, &self_).await
+    }
+
+    async fn drop(&mut self, rep: Resource<DbPreparedStatementEntry>) -> anyhow::Result<()> {
+        db_prepared_statement_drop(self, rep).await
+    }
+}
+
 pub struct LazyDbColumnTypeEntry {
     value: DbColumnTypeWithResourceRep,
 }
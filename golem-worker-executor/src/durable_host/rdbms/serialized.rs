@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::services::rdbms::RdbmsType;
-use golem_common::model::oplog::types::SerializableRdbmsRequest;
+use golem_common::model::oplog::types::{SerializableRdbmsBatchRequest, SerializableRdbmsRequest};
 use golem_common::model::{RdbmsPoolKey, TransactionId};
 
 #[derive(Debug, Clone)]
@@ -67,3 +67,42 @@ impl<T: RdbmsType + 'static> TryFrom<SerializableRdbmsRequest> for RdbmsRequest<
         })
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct RdbmsBatchRequest<T: RdbmsType + 'static> {
+    pub pool_key: RdbmsPoolKey,
+    pub statement: String,
+    pub params: Vec<Vec<T::DbValue>>,
+    pub transaction_id: Option<TransactionId>,
+}
+
+impl<T: RdbmsType> RdbmsBatchRequest<T> {
+    pub fn new(
+        pool_key: RdbmsPoolKey,
+        statement: String,
+        params: Vec<Vec<T::DbValue>>,
+        transaction_id: Option<TransactionId>,
+    ) -> Self {
+        Self {
+            pool_key,
+            statement,
+            params,
+            transaction_id,
+        }
+    }
+}
+
+impl<T: RdbmsType + 'static> From<RdbmsBatchRequest<T>> for SerializableRdbmsBatchRequest {
+    fn from(request: RdbmsBatchRequest<T>) -> Self {
+        SerializableRdbmsBatchRequest {
+            pool_key: request.pool_key,
+            statement: request.statement,
+            params: request
+                .params
+                .into_iter()
+                .map(|row| row.into_iter().map(|p| p.into()).collect())
+                .collect(),
+            transaction_id: request.transaction_id,
+        }
+    }
+}
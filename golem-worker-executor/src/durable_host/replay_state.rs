@@ -840,6 +840,46 @@ impl ReplayState {
         }
     }
 
+    /// Claims the next `Start` entry like [`Self::claim_concurrent_start`], but only if it belongs
+    /// to `expected_function_name`. Otherwise the replay state remains unchanged and `None` is
+    /// returned.
+    ///
+    /// Used for durable calls that are only recorded by some executions of the same code path,
+    /// such as the agent method outputs replaced by invocation interceptor plugins.
+    pub async fn try_claim_concurrent_start(
+        &mut self,
+        expected_function_name: &HostFunctionName,
+    ) -> Result<Option<ReplayCallHandle>, WorkerExecutorError> {
+        if !self.is_replay() {
+            return Ok(None);
+        }
+        let read = self
+            .try_get_oplog_entry(|entry| {
+                matches!(
+                    entry,
+                    OplogEntry::Start { function_name, .. } if function_name == expected_function_name
+                )
+            })
+            .await?;
+        match read {
+            Some((start_idx, OplogEntry::Start { request, .. })) => {
+                if request.is_none() {
+                    return Err(WorkerExecutorError::unexpected_oplog_entry(
+                        "Start { request: Some(..) }",
+                        "Start { request: None }".to_string(),
+                    ));
+                }
+                let receiver = {
+                    let mut internal = self.internal.write().await;
+                    internal.concurrent_resolver.register(start_idx)
+                };
+                Ok(Some(ReplayCallHandle::new(start_idx, receiver)))
+            }
+            Some(_) => unreachable!("try_get_oplog_entry condition guarantees a Start entry"),
+            None => Ok(None),
+        }
+    }
+
     /// Drives the replay cursor forward, feeding the committed-consume hook, until the call
     /// identified by `handle` resolves.
     ///
@@ -0,0 +1,124 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test-only overrides of the nondeterministic inputs of agents.
+//!
+//! In-process test harnesses such as `golem test` install [`TestHooks`] through the extra
+//! dependencies of the default worker context. They are not part of the executor's
+//! configuration, so a deployed executor always uses the real clocks, random sources and HTTP
+//! destinations.
+
+use crate::durable_host::DurableWorkerCtx;
+use crate::workerctx::WorkerCtx;
+use golem_common::model::AgentId;
+use golem_common::model::oplog::OplogIndex;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::time::{Duration, SystemTime};
+use wasmtime_wasi::p2::bindings::clocks::wall_clock::Datetime;
+
+/// Header carrying the original URL of a request redirected to the HTTP mock server
+pub const HTTP_MOCK_ORIGINAL_URL_HEADER: &str = "x-golem-original-url";
+
+#[derive(Clone, Debug, Default)]
+pub struct TestHooks {
+    /// Replaces the clocks and the random sources seen by agents with deterministic ones, so
+    /// repeated runs of the same invocations produce the same results
+    pub determinism: Option<Determinism>,
+    /// Redirects the outgoing wasi:http requests of all agents to this mock server, keeping
+    /// their path and query, with the original URL passed in the
+    /// [`HTTP_MOCK_ORIGINAL_URL_HEADER`] header
+    pub http_mock_url: Option<url::Url>,
+}
+
+/// The values are derived from the agent ID and the oplog index of the host call reading them,
+/// so they do not depend on the timing of the invocations, nor on agent restarts.
+#[derive(Clone, Debug)]
+pub struct Determinism {
+    /// Time reported by the wall clock for the first oplog entry of each agent
+    pub start_time: SystemTime,
+    /// Time the wall and monotonic clocks advance with each oplog entry
+    pub clock_step: Duration,
+    /// Seed of the random sources
+    pub random_seed: u64,
+}
+
+/// Clocks and random source of a single host call when deterministic execution is enabled
+pub struct DeterministicSources {
+    start_time: SystemTime,
+    elapsed: Duration,
+    rng: StdRng,
+}
+
+impl DeterministicSources {
+    pub fn new(determinism: &Determinism, agent_id: &AgentId, oplog_index: OplogIndex) -> Self {
+        let index = oplog_index.as_u64();
+        let elapsed = determinism
+            .clock_step
+            .saturating_mul(u32::try_from(index).unwrap_or(u32::MAX));
+
+        let mut context = md5::Context::new();
+        context.consume(determinism.random_seed.to_le_bytes());
+        context.consume(agent_id.to_string().as_bytes());
+        context.consume(index.to_le_bytes());
+        let digest = context.compute().0;
+        let mut seed = [0u8; 32];
+        seed[..16].copy_from_slice(&digest);
+        seed[16..].copy_from_slice(&digest);
+
+        Self {
+            start_time: determinism.start_time,
+            elapsed,
+            rng: StdRng::from_seed(seed),
+        }
+    }
+
+    pub fn wall_clock_now(&self) -> Datetime {
+        let since_epoch = (self.start_time + self.elapsed)
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        Datetime {
+            seconds: since_epoch.as_secs(),
+            nanoseconds: since_epoch.subsec_nanos(),
+        }
+    }
+
+    /// Nanoseconds elapsed on the monotonic clock, which starts at zero for each agent
+    pub fn monotonic_now(&self) -> u64 {
+        u64::try_from(self.elapsed.as_nanos()).unwrap_or(u64::MAX)
+    }
+
+    pub fn random_bytes(&mut self, length: u64) -> Vec<u8> {
+        let mut bytes = vec![0u8; length as usize];
+        self.rng.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    pub fn random_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+}
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Deterministic sources for the current host call, if a test harness enabled deterministic
+    /// execution
+    pub(crate) async fn deterministic_sources(&self) -> Option<DeterministicSources> {
+        let determinism = self.test_hooks.as_ref()?.determinism.as_ref()?;
+        Some(DeterministicSources::new(
+            determinism,
+            &self.owned_agent_id.agent_id,
+            self.state.current_oplog_index().await,
+        ))
+    }
+}
@@ -34,7 +34,9 @@ use golem_common::model::agent::{
 };
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
-use golem_common::model::invocation_context::{AttributeValue, InvocationContextSpan, SpanId};
+use golem_common::model::invocation_context::{
+    AttributeValue, DEADLINE_ATTRIBUTE, InvocationContextSpan, SpanId,
+};
 use golem_common::model::oplog::host_functions::{
     GolemRpcCancellationTokenCancel, GolemRpcFutureInvokeResultCancel,
     GolemRpcFutureInvokeResultGet, GolemRpcWasmRpcInvoke, GolemRpcWasmRpcInvokeAndAwaitResult,
@@ -51,6 +53,7 @@ use golem_common::model::oplog::{
 use golem_common::model::{
     AgentFingerprint, AgentId, AgentInvocation, IdempotencyKey, NamedRetryPolicy, OplogIndex,
     OwnedAgentId, PredicateValue, RetryContext, RetryProperties, ScheduleId, ScheduledAction,
+    Timestamp,
 };
 use golem_common::schema::TypedSchemaValue;
 use golem_common::schema::adapters::{
@@ -78,6 +81,8 @@ use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::model::auth::AuthCtx;
 use golem_wasm::json::ValueAndTypeJsonExtensions;
 
+mod topics;
+
 fn classify_rpc_error(err: &InternalRpcError) -> HostFailureKind {
     match err {
         InternalRpcError::ProtocolError { .. }
@@ -210,6 +215,7 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         let payload = entry.payload.downcast_ref::<WasmRpcEntryPayload>().unwrap();
         let remote_agent_id = payload.remote_agent_id.clone();
         let connection_span_id = payload.span_id.clone();
+        let deadline = payload.deadline;
         let remote_agent_type = payload.remote_agent_type.clone();
 
         if remote_agent_id == own_agent_id {
@@ -244,9 +250,14 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         let oplog_index = self.state.oplog.current_oplog_index().await;
         let idempotency_key = self.derive_idempotency_key(oplog_index);
 
-        let span =
-            create_invocation_span(self, &connection_span_id, &method_name, &idempotency_key)
-                .await?;
+        let span = create_invocation_span(
+            self,
+            &connection_span_id,
+            &method_name,
+            &idempotency_key,
+            deadline,
+        )
+        .await?;
 
         let request = HostRequestGolemRpcInvoke {
             remote_agent_id: remote_agent_id.agent_id(),
@@ -417,6 +428,7 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         let payload = entry.payload.downcast_ref::<WasmRpcEntryPayload>().unwrap();
         let remote_agent_id = payload.remote_agent_id.clone();
         let connection_span_id = payload.span_id.clone();
+        let deadline = payload.deadline;
         let remote_agent_type = payload.remote_agent_type.clone();
 
         if remote_agent_id == own_agent_id {
@@ -449,9 +461,14 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         let oplog_index = self.state.oplog.current_oplog_index().await;
         let idempotency_key = self.derive_idempotency_key(oplog_index);
 
-        let span =
-            create_invocation_span(self, &connection_span_id, &method_name, &idempotency_key)
-                .await?;
+        let span = create_invocation_span(
+            self,
+            &connection_span_id,
+            &method_name,
+            &idempotency_key,
+            deadline,
+        )
+        .await?;
 
         let request = HostRequestGolemRpcInvoke {
             remote_agent_id: remote_agent_id.agent_id(),
@@ -547,6 +564,7 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         let payload = entry.payload.downcast_ref::<WasmRpcEntryPayload>().unwrap();
         let remote_agent_id = payload.remote_agent_id.clone();
         let connection_span_id = payload.span_id.clone();
+        let deadline = payload.deadline;
         let remote_agent_type = payload.remote_agent_type.clone();
 
         if remote_agent_id == own_agent_id {
@@ -577,9 +595,14 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         let oplog_index = self.state.oplog.current_oplog_index().await;
         let idempotency_key = self.derive_idempotency_key(oplog_index);
 
-        let span =
-            create_invocation_span(self, &connection_span_id, &method_name, &idempotency_key)
-                .await?;
+        let span = create_invocation_span(
+            self,
+            &connection_span_id,
+            &method_name,
+            &idempotency_key,
+            deadline,
+        )
+        .await?;
 
         // Resolve per-method schemas and lift the input. Failures here
         // are deterministic functions of the cached remote agent type
@@ -868,6 +891,23 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         Ok(resource)
     }
 
+    async fn set_deadline(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        deadline: Option<wasmtime_wasi::p2::bindings::clocks::wall_clock::Datetime>,
+    ) -> anyhow::Result<()> {
+        self.observe_function_call("golem::rpc::wasm-rpc", "set-deadline");
+
+        // Only affects the span attributes of subsequent invocations, which are derived
+        // deterministically on replay as well, so no oplog entry is needed.
+        let entry = self.table().get_mut(&self_)?;
+        let payload = entry.payload.downcast_mut::<WasmRpcEntryPayload>().unwrap();
+        payload.deadline = deadline.map(|deadline| {
+            Timestamp::from(deadline.seconds * 1000 + (deadline.nanoseconds / 1_000_000) as u64)
+        });
+        Ok(())
+    }
+
     async fn drop(&mut self, rep: Resource<WasmRpcEntry>) -> anyhow::Result<()> {
         self.observe_function_call("golem::rpc::wasm-rpc", "drop");
 
@@ -1506,6 +1546,7 @@ pub async fn construct_wasm_rpc_resource<Ctx: WorkerCtx>(
             span_id: span.span_id().clone(),
             target_fingerprint,
             remote_agent_type,
+            deadline: None,
         }),
     })?;
     Ok(entry)
@@ -1529,6 +1570,7 @@ async fn reconstruct_wasm_rpc_resource<Ctx: WorkerCtx>(
             span_id: span.span_id().clone(),
             target_fingerprint,
             remote_agent_type,
+            deadline: None,
         }),
     })?;
     Ok(entry)
@@ -1870,6 +1912,9 @@ pub struct WasmRpcEntryPayload {
     /// `get_agent_type` lookup performed in [`HostWasmRpc::new`], so it
     /// is consistent across live execution and replay.
     pub remote_agent_type: Arc<AgentType>,
+    /// Deadline set by the guest through `set-deadline`, recorded on the invocation span of
+    /// every subsequent invocation made through this client.
+    pub deadline: Option<Timestamp>,
 }
 
 impl Debug for WasmRpcEntryPayload {
@@ -2020,25 +2065,32 @@ pub async fn create_invocation_span<Ctx: InvocationContextManagement>(
     connection_span_id: &SpanId,
     function_name: &str,
     idempotency_key: &IdempotencyKey,
+    deadline: Option<Timestamp>,
 ) -> anyhow::Result<Arc<InvocationContextSpan>> {
+    let mut attributes = vec![
+        (
+            "name".to_string(),
+            AttributeValue::String("rpc-invocation".to_string()),
+        ),
+        (
+            "function_name".to_string(),
+            AttributeValue::String(function_name.to_string()),
+        ),
+        (
+            "idempotency_key".to_string(),
+            AttributeValue::String(idempotency_key.to_string()),
+        ),
+    ];
+    // Deadlines inherited from the caller's own invocation span stay in effect; the remote
+    // executor applies the earliest one in the chain.
+    if let Some(deadline) = deadline {
+        attributes.push((
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(deadline.to_string()),
+        ));
+    }
     Ok(ctx
-        .start_child_span(
-            connection_span_id,
-            &[
-                (
-                    "name".to_string(),
-                    AttributeValue::String("rpc-invocation".to_string()),
-                ),
-                (
-                    "function_name".to_string(),
-                    AttributeValue::String(function_name.to_string()),
-                ),
-                (
-                    "idempotency_key".to_string(),
-                    AttributeValue::String(idempotency_key.to_string()),
-                ),
-            ],
-        )
+        .start_child_span(connection_span_id, &attributes)
         .await?)
 }
 
@@ -0,0 +1,464 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{classify_rpc_error, resolve_method_and_lift_input, typed_rpc_input_to_untyped};
+use crate::durable_host::concurrent::{CallHandle, CallReplayOutcome, NotCancellable};
+use crate::durable_host::{DurabilityHost, DurableWorkerCtx, InternalRetryResult};
+use crate::preview2::golem::agent::host::{RpcError, TopicSubscriber as GuestTopicSubscriber};
+use crate::services::rpc::RpcError as InternalRpcError;
+use crate::workerctx::{InvocationContextManagement, WorkerCtx};
+use chrono::Utc;
+use golem_common::model::agent::bindings::golem::agent::common::DataValue;
+use golem_common::model::agent::{
+    AgentPrincipal, AgentType, AgentTypeName, LegacyParsedAgentId, Principal, RegisteredAgentType,
+};
+use golem_common::model::invocation_context::{AttributeValue, SpanId};
+use golem_common::model::oplog::host_functions::{
+    GolemAgentPublish, GolemAgentSubscribe, GolemAgentUnsubscribe,
+};
+use golem_common::model::oplog::{
+    DurableFunctionType, HostRequestGolemAgentTopicPublish, HostRequestGolemAgentTopicSubscriber,
+    HostRequestGolemAgentTopicSubscription, HostResponseGolemAgentTopicPublish,
+    HostResponseGolemRpcUnitOrFailure, OplogIndex,
+};
+use golem_common::model::topic::{TopicDelivery, TopicFanOut, TopicSubscriber, TopicSubscription};
+use golem_common::model::{
+    AgentInvocation, IdempotencyKey, OwnedAgentId, ScanCursor, ScheduleId, ScheduledAction,
+};
+use tracing::error;
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    pub(crate) async fn subscribe_to_topic(
+        &mut self,
+        topic: String,
+        subscriber: GuestTopicSubscriber,
+        method_name: String,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        self.check_read_only_allows("golem::agent::subscribe")
+            .map_err(wasmtime::Error::from)?;
+
+        let subscription = TopicSubscription {
+            subscriber: self.resolve_guest_topic_subscriber(subscriber)?,
+            method_name,
+        };
+
+        let mut handle = CallHandle::<GolemAgentSubscribe, NotCancellable>::start(
+            self,
+            HostRequestGolemAgentTopicSubscription {
+                topic: topic.clone(),
+                subscription: subscription.clone(),
+            },
+            DurableFunctionType::WriteRemote,
+        )
+        .await?;
+
+        let response = 'result: {
+            if !handle.is_live() {
+                match handle.replay(self).await? {
+                    CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                    CallReplayOutcome::Incomplete(live) => handle = live,
+                }
+            }
+
+            let result = loop {
+                let result = self.store_topic_subscription(&topic, &subscription).await;
+                match handle
+                    .try_trigger_retry_or_loop(self, &result, classify_rpc_error)
+                    .await?
+                {
+                    InternalRetryResult::Persist => break result,
+                    InternalRetryResult::RetryInternally => continue,
+                }
+            };
+
+            handle
+                .complete(
+                    self,
+                    HostResponseGolemRpcUnitOrFailure {
+                        result: result.map_err(|err| err.into()),
+                    },
+                )
+                .await?
+        };
+
+        Ok(response.result.map_err(|err| {
+            let rpc_error: InternalRpcError = err.into();
+            rpc_error.into()
+        }))
+    }
+
+    pub(crate) async fn unsubscribe_from_topic(
+        &mut self,
+        topic: String,
+        subscriber: GuestTopicSubscriber,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        self.check_read_only_allows("golem::agent::unsubscribe")
+            .map_err(wasmtime::Error::from)?;
+
+        let subscriber = self.resolve_guest_topic_subscriber(subscriber)?;
+
+        let mut handle = CallHandle::<GolemAgentUnsubscribe, NotCancellable>::start(
+            self,
+            HostRequestGolemAgentTopicSubscriber {
+                topic: topic.clone(),
+                subscriber: subscriber.clone(),
+            },
+            DurableFunctionType::WriteRemote,
+        )
+        .await?;
+
+        let response = 'result: {
+            if !handle.is_live() {
+                match handle.replay(self).await? {
+                    CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                    CallReplayOutcome::Incomplete(live) => handle = live,
+                }
+            }
+
+            let result = loop {
+                let result = self
+                    .state
+                    .topic_service
+                    .unsubscribe(self.owned_agent_id.environment_id, &topic, &subscriber)
+                    .await
+                    .map_err(|err| InternalRpcError::RemoteInternalError {
+                        details: format!("Failed to unsubscribe from topic '{topic}': {err}"),
+                    });
+                match handle
+                    .try_trigger_retry_or_loop(self, &result, classify_rpc_error)
+                    .await?
+                {
+                    InternalRetryResult::Persist => break result,
+                    InternalRetryResult::RetryInternally => continue,
+                }
+            };
+
+            handle
+                .complete(
+                    self,
+                    HostResponseGolemRpcUnitOrFailure {
+                        result: result.map_err(|err| err.into()),
+                    },
+                )
+                .await?
+        };
+
+        Ok(response.result.map_err(|err| {
+            let rpc_error: InternalRpcError = err.into();
+            rpc_error.into()
+        }))
+    }
+
+    pub(crate) async fn publish_to_topic(
+        &mut self,
+        topic: String,
+        message: DataValue,
+    ) -> anyhow::Result<Result<u64, RpcError>> {
+        // Trap immediately if the invocation is restricted to read-only side effects.
+        self.check_read_only_allows("golem::agent::publish")
+            .map_err(wasmtime::Error::from)?;
+
+        // A publish counts as a single RPC call regardless of the number of subscribers
+        self.state
+            .check_and_increment_rpc_call_count()
+            .map_err(wasmtime::Error::from)?;
+        self.record_monthly_rpc_call()?;
+
+        let oplog_index = self.state.oplog.current_oplog_index().await;
+        let idempotency_key = self.derive_idempotency_key(oplog_index);
+
+        let span = self
+            .start_span(
+                &[
+                    (
+                        "name".to_string(),
+                        AttributeValue::String("topic-publish".to_string()),
+                    ),
+                    ("topic".to_string(), AttributeValue::String(topic.clone())),
+                    (
+                        "idempotency_key".to_string(),
+                        AttributeValue::String(idempotency_key.to_string()),
+                    ),
+                ],
+                false,
+            )
+            .await?;
+
+        let mut handle = CallHandle::<GolemAgentPublish, NotCancellable>::start(
+            self,
+            HostRequestGolemAgentTopicPublish {
+                topic: topic.clone(),
+                idempotency_key: idempotency_key.clone(),
+                message: message.clone().into(),
+            },
+            DurableFunctionType::WriteRemote,
+        )
+        .await?;
+
+        let response = 'result: {
+            if !handle.is_live() {
+                match handle.replay(self).await? {
+                    CallReplayOutcome::Replayed(replayed) => break 'result replayed,
+                    CallReplayOutcome::Incomplete(live) => handle = live,
+                }
+            }
+
+            // Every retry schedules the deliveries of all the subscriptions again; the schedule
+            // ids and idempotency keys derived from the publish make sure they are not duplicated.
+            let result = loop {
+                let result = self
+                    .schedule_topic_deliveries(
+                        &topic,
+                        &idempotency_key,
+                        oplog_index,
+                        &message,
+                        span.span_id(),
+                    )
+                    .await;
+                match handle
+                    .try_trigger_retry_or_loop(self, &result, classify_rpc_error)
+                    .await?
+                {
+                    InternalRetryResult::Persist => break result,
+                    InternalRetryResult::RetryInternally => continue,
+                }
+            };
+
+            handle
+                .complete(
+                    self,
+                    HostResponseGolemAgentTopicPublish {
+                        result: result.map_err(|err| err.into()),
+                    },
+                )
+                .await?
+        };
+
+        self.finish_span(span.span_id()).await?;
+
+        match response.result {
+            Ok(count) => Ok(Ok(count)),
+            Err(err) => {
+                let rpc_error: InternalRpcError = err.into();
+                error!("Failed to publish to topic '{topic}': {rpc_error}");
+                Ok(Err(rpc_error.into()))
+            }
+        }
+    }
+
+    fn resolve_guest_topic_subscriber(
+        &self,
+        subscriber: GuestTopicSubscriber,
+    ) -> anyhow::Result<TopicSubscriber> {
+        match subscriber {
+            GuestTopicSubscriber::CurrentAgent => {
+                if self.state.agent_id.is_none() {
+                    return Err(anyhow::anyhow!(
+                        "Only agents can subscribe to topics as the current agent"
+                    ));
+                }
+                Ok(TopicSubscriber::Agent(self.owned_agent_id.agent_id.clone()))
+            }
+            GuestTopicSubscriber::AgentType(agent_type_name) => {
+                Ok(TopicSubscriber::AgentType(AgentTypeName(agent_type_name)))
+            }
+        }
+    }
+
+    /// Validates that the subscribed method exists on the subscriber's agent type before
+    /// storing the subscription.
+    async fn store_topic_subscription(
+        &self,
+        topic: &str,
+        subscription: &TopicSubscription,
+    ) -> Result<(), InternalRpcError> {
+        let registered_agent_type = self
+            .resolve_topic_subscriber_agent_type(&subscription.subscriber)
+            .await?;
+        if !registered_agent_type
+            .agent_type
+            .methods
+            .iter()
+            .any(|method| method.name == subscription.method_name)
+        {
+            return Err(InternalRpcError::NotFound {
+                details: format!(
+                    "Method '{}' not found on agent type '{}'",
+                    subscription.method_name, registered_agent_type.agent_type.type_name
+                ),
+            });
+        }
+
+        self.state
+            .topic_service
+            .subscribe(
+                self.owned_agent_id.environment_id,
+                topic,
+                subscription.clone(),
+            )
+            .await
+            .map_err(|err| InternalRpcError::RemoteInternalError {
+                details: format!("Failed to subscribe to topic '{topic}': {err}"),
+            })
+    }
+
+    async fn resolve_topic_subscriber_agent_type(
+        &self,
+        subscriber: &TopicSubscriber,
+    ) -> Result<RegisteredAgentType, InternalRpcError> {
+        let agent_type_name = match subscriber {
+            TopicSubscriber::Agent(agent_id) => {
+                LegacyParsedAgentId::parse_agent_type_name(&agent_id.agent_id)
+                    .map_err(|details| InternalRpcError::ProtocolError { details })?
+            }
+            TopicSubscriber::AgentType(agent_type_name) => agent_type_name.clone(),
+        };
+
+        self.agent_types_service()
+            .get(
+                self.owned_agent_id.environment_id,
+                self.owned_agent_id.agent_id.component_id,
+                self.state.component_metadata.revision,
+                &agent_type_name,
+            )
+            .await
+            .map_err(|err| InternalRpcError::RemoteInternalError {
+                details: err.to_string(),
+            })?
+            .ok_or_else(|| InternalRpcError::NotFound {
+                details: format!("Agent type '{agent_type_name}' not found"),
+            })
+    }
+
+    /// Schedules the delivery of the message to every subscription of the topic, returning the
+    /// number of subscriptions it was scheduled for.
+    ///
+    /// Explicitly subscribed agents get the message added to their delivery queue, while agent
+    /// type subscriptions get it added to the fan-out queue of the subscription, which adds it
+    /// to the delivery queues of the agents of the type a page at a time. The scheduler works
+    /// through the queues one message at a time, so every subscriber receives the messages of
+    /// this agent in the order they were published, the publisher never waits for its
+    /// subscribers, and a failing subscriber only holds up its own queue.
+    async fn schedule_topic_deliveries(
+        &self,
+        topic: &str,
+        idempotency_key: &IdempotencyKey,
+        publish_index: OplogIndex,
+        message: &DataValue,
+        span_id: &SpanId,
+    ) -> Result<u64, InternalRpcError> {
+        let subscriptions = self
+            .state
+            .topic_service
+            .get_subscriptions(self.owned_agent_id.environment_id, topic)
+            .await
+            .map_err(|err| InternalRpcError::RemoteInternalError {
+                details: format!("Failed to get the subscriptions of topic '{topic}': {err}"),
+            })?;
+
+        let environment_id = self.owned_agent_id.environment_id;
+        let invocation_context = self.clone_as_inherited_stack(span_id);
+        let now = Utc::now();
+        let queue_error = |err: anyhow::Error| InternalRpcError::RemoteInternalError {
+            details: format!("Failed to queue the message published to topic '{topic}': {err}"),
+        };
+
+        let mut count = 0;
+        for subscription in subscriptions {
+            let registered_agent_type = self
+                .resolve_topic_subscriber_agent_type(&subscription.subscriber)
+                .await?;
+            let input = self.lift_topic_message(
+                &registered_agent_type.agent_type,
+                &subscription.method_name,
+                message,
+            )?;
+
+            let (schedule_key, action) = match &subscription.subscriber {
+                TopicSubscriber::Agent(target) => {
+                    let delivery_key =
+                        subscription.delivery_idempotency_key(idempotency_key, target);
+                    let delivery = TopicDelivery {
+                        publisher: self.owned_agent_id.agent_id.clone(),
+                        publish_index,
+                        invocation: AgentInvocation::AgentMethod {
+                            idempotency_key: delivery_key.clone(),
+                            method_name: subscription.method_name.clone(),
+                            input,
+                            invocation_context: invocation_context.clone(),
+                            principal: Principal::Agent(AgentPrincipal {
+                                agent_id: self.owned_agent_id.agent_id.clone(),
+                            }),
+                        },
+                    };
+                    self.state
+                        .topic_service
+                        .enqueue_delivery(environment_id, target, &delivery)
+                        .await
+                        .map_err(queue_error)?;
+                    let action = ScheduledAction::DeliverTopicMessage {
+                        account_id: self.created_by(),
+                        owned_agent_id: OwnedAgentId::new(environment_id, target),
+                    };
+                    (delivery_key, action)
+                }
+                TopicSubscriber::AgentType(_) => {
+                    let fan_out = TopicFanOut {
+                        publisher: self.owned_agent_id.clone(),
+                        topic: topic.to_string(),
+                        component_id: registered_agent_type.implemented_by.component_id,
+                        subscription,
+                        publish_idempotency_key: idempotency_key.clone(),
+                        publish_index,
+                        input,
+                        invocation_context: invocation_context.clone(),
+                        cursor: ScanCursor::default(),
+                    };
+                    self.state
+                        .topic_service
+                        .enqueue_fan_out(&fan_out)
+                        .await
+                        .map_err(queue_error)?;
+                    let schedule_key = fan_out.schedule_idempotency_key();
+                    let action = ScheduledAction::FanOutTopicMessage {
+                        account_id: self.created_by(),
+                        fan_out: Box::new(fan_out),
+                    };
+                    (schedule_key, action)
+                }
+            };
+
+            self.state
+                .scheduler_service
+                .schedule_with_id(ScheduleId::from_idempotency_key(&schedule_key), now, action)
+                .await;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    fn lift_topic_message(
+        &self,
+        agent_type: &AgentType,
+        method_name: &str,
+        message: &DataValue,
+    ) -> Result<golem_common::model::agent::UntypedDataValue, InternalRpcError> {
+        let (input_typed, _) =
+            resolve_method_and_lift_input(agent_type, method_name, message.clone())?;
+        typed_rpc_input_to_untyped(&input_typed).map_err(|err| InternalRpcError::ProtocolError {
+            details: err.to_string(),
+        })
+    }
+}
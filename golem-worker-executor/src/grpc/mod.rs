@@ -20,11 +20,12 @@ use crate::model::public_oplog::{
     find_component_revision_at, get_public_oplog_chunk, search_public_oplog,
 };
 use crate::model::{LastError, ReadFileResult};
-use crate::services::events::Event;
+use crate::services::events::{AgentWrite, Event};
 use crate::services::worker_activator::{
     DefaultWorkerActivator, LazyWorkerActivator, WorkerActivator,
 };
 use crate::services::worker_event::WorkerEventReceiver;
+use crate::services::worker_fork::TimeTravelTarget;
 use crate::services::{
     All, HasActiveWorkers, HasAll, HasComponentService, HasEvents, HasOplogService,
     HasPromiseService, HasRunningWorkerEnumerationService, HasSchedulerService,
@@ -42,17 +43,23 @@ use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_server::Wo
 use golem_api_grpc::proto::golem::workerexecutor::v1::{
     ActivatePluginRequest, ActivatePluginResponse, CancelInvocationRequest,
     CancelInvocationResponse, ConnectWorkerRequest, DeactivatePluginRequest,
-    DeactivatePluginResponse, DeleteWorkerRequest, ForkWorkerRequest, ForkWorkerResponse,
-    GetFileContentsRequest, GetFileContentsResponse, GetFileSystemNodeRequest,
-    GetFileSystemNodeResponse, GetOplogRequest, GetOplogResponse, GetRunningWorkersMetadataRequest,
-    GetRunningWorkersMetadataResponse, GetWorkersMetadataRequest, GetWorkersMetadataResponse,
-    InvokeAgentRequest, InvokeAgentResponse, ProcessOplogEntriesRequest,
-    ProcessOplogEntriesResponse, RevertWorkerRequest, RevertWorkerResponse, SearchOplogRequest,
-    SearchOplogResponse, UpdateWorkerRequest, UpdateWorkerResponse, process_oplog_entries_response,
+    DeactivatePluginResponse, DeleteWorkerRequest, ExportAgentRequest, ExportAgentResponse,
+    ForkWorkerRequest, ForkWorkerResponse, GetAgentLifecycleAuditRequest,
+    GetAgentLifecycleAuditResponse, GetAgentLifecycleAuditSuccessResponse, GetFileContentsRequest,
+    GetFileContentsResponse, GetFileSystemNodeRequest, GetFileSystemNodeResponse, GetOplogRequest,
+    GetOplogResponse, GetRunningWorkersMetadataRequest, GetRunningWorkersMetadataResponse,
+    GetWorkersMetadataRequest, GetWorkersMetadataResponse, ImportAgentRequest,
+    ImportAgentRequestHeader, ImportAgentResponse, InvokeAgentRequest, InvokeAgentResponse,
+    ProcessOplogEntriesRequest, ProcessOplogEntriesResponse, RevertWorkerRequest,
+    RevertWorkerResponse, SearchOplogRequest, SearchOplogResponse, SubscribeAgentWritesRequest,
+    SubscribeAgentWritesResponse, UpdateWorkerRequest, UpdateWorkerResponse,
+    get_agent_lifecycle_audit_response, process_oplog_entries_response,
+    subscribe_agent_writes_response,
 };
 use golem_common::metrics::api::record_new_grpc_api_active_stream;
 use golem_common::model::account::AccountId;
 use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, Principal, UntypedDataValue};
+use golem_common::model::agent_archive::AgentArchiveDecoder;
 use golem_common::model::component::{CanonicalFilePath, ComponentId, PluginPriority};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
@@ -80,7 +87,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use tokio;
-use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tonic::{Request, Response, Status};
 use tracing::info_span;
@@ -115,6 +122,9 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 type ResponseResult<T> = Result<Response<T>, Status>;
 type ResponseStream = WorkerEventStream;
 
+/// Maximum size of the agent archive chunks in a single export response message
+const AGENT_ARCHIVE_CHUNK_SIZE: usize = 1024 * 1024;
+
 impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync + 'static>
     WorkerExecutorImpl<Ctx, Svcs>
 {
@@ -472,15 +482,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         )
         .await?;
 
-        info!("Interrupting worker before deletion");
-        worker
-            .set_interrupting(InterruptKind::Interrupt(Timestamp::now_utc()))
-            .await;
-        info!("Marking worker for deletion");
-        worker.start_deleting().await?;
-
-        self.worker_service().remove(&owned_agent_id).await;
-        self.active_workers().remove(&owned_agent_id.agent_id).await;
+        worker.delete().await?;
 
         // ensure we are holding the worker while we are doing cleanup.
         drop(worker);
@@ -555,6 +557,120 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         })
     }
 
+    async fn export_agent_internal(
+        &self,
+        request: ExportAgentRequest,
+    ) -> Result<<Self as WorkerExecutor>::ExportAgentStream, WorkerExecutorError> {
+        let owned_agent_id =
+            extract_owned_agent_id(&request, |r| &r.agent_id, |r| &r.environment_id)?;
+
+        self.ensure_worker_belongs_to_this_executor(&owned_agent_id)?;
+        Self::validate_auth_ctx(&request.auth_ctx)?;
+
+        let parts = self
+            .services
+            .worker_fork_service()
+            .export_agent(&owned_agent_id)
+            .await?;
+
+        // The archive is sent in chunks of at most AGENT_ARCHIVE_CHUNK_SIZE bytes, and the
+        // stream ends with the first failure
+        let stream = parts
+            .map(|part| part.and_then(|part| part.encode().map_err(WorkerExecutorError::unknown)))
+            .scan(false, |failed, encoded| {
+                let item = (!*failed).then(|| {
+                    *failed = encoded.is_err();
+                    encoded
+                });
+                futures::future::ready(item)
+            })
+            .flat_map(|encoded| {
+                let responses: Vec<_> = match encoded {
+                    Ok(bytes) => bytes
+                        .chunks(AGENT_ARCHIVE_CHUNK_SIZE)
+                        .map(|chunk| {
+                            golem::workerexecutor::v1::export_agent_response::Result::Chunk(
+                                chunk.to_vec(),
+                            )
+                        })
+                        .collect(),
+                    Err(err) => vec![
+                        golem::workerexecutor::v1::export_agent_response::Result::Failure(
+                            err.into(),
+                        ),
+                    ],
+                };
+                futures::stream::iter(responses.into_iter().map(|result| {
+                    Ok(ExportAgentResponse {
+                        result: Some(result),
+                    })
+                }))
+            });
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn import_agent_internal(
+        &self,
+        header: Result<ImportAgentRequestHeader, WorkerExecutorError>,
+        requests: tonic::Streaming<ImportAgentRequest>,
+    ) -> Result<(), WorkerExecutorError> {
+        let header = header?;
+        let owned_target_agent_id =
+            extract_owned_agent_id(&header, |r| &r.target_agent_id, |r| &r.environment_id)?;
+
+        self.ensure_worker_belongs_to_this_executor(&owned_target_agent_id)?;
+
+        let auth_ctx: AuthCtx = header
+            .auth_ctx
+            .ok_or(WorkerExecutorError::invalid_request("auth_ctx not found"))?
+            .try_into()
+            .map_err(WorkerExecutorError::invalid_request)?;
+
+        let parts = futures::stream::try_unfold(
+            (requests, AgentArchiveDecoder::default()),
+            |(mut requests, mut decoder)| async move {
+                loop {
+                    if let Some(part) = decoder
+                        .next_part()
+                        .map_err(WorkerExecutorError::invalid_request)?
+                    {
+                        return Ok(Some((part, (requests, decoder))));
+                    }
+                    let request = requests.message().await.map_err(|status| {
+                        WorkerExecutorError::runtime(format!(
+                            "Failed to receive agent archive: {status}"
+                        ))
+                    })?;
+                    match request {
+                        Some(ImportAgentRequest {
+                            request:
+                                Some(golem::workerexecutor::v1::import_agent_request::Request::Chunk(
+                                    chunk,
+                                )),
+                        }) => decoder.push(&chunk),
+                        Some(_) => {
+                            return Err(WorkerExecutorError::invalid_request(
+                                "unexpected message in agent archive stream",
+                            ));
+                        }
+                        None => {
+                            decoder
+                                .finish()
+                                .map_err(WorkerExecutorError::invalid_request)?;
+                            return Ok(None);
+                        }
+                    }
+                }
+            },
+        );
+
+        self.services
+            .worker_fork_service()
+            .import_agent(parts.boxed(), &owned_target_agent_id, &auth_ctx)
+            .await
+    }
+
     async fn revert_worker_internal(
         &self,
         request: RevertWorkerRequest,
@@ -994,7 +1110,14 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         )
         .await;
 
-        Self::create_proto_metadata(metadata, last_error_and_retry_count)
+        let promises = self
+            .promise_service()
+            .list(&owned_agent_id.agent_id)
+            .await?;
+
+        let mut proto_metadata = Self::create_proto_metadata(metadata, last_error_and_retry_count)?;
+        proto_metadata.promises = promises.into_iter().map(Into::into).collect();
+        Ok(proto_metadata)
     }
 
     async fn get_running_workers_metadata_internal(
@@ -1318,7 +1441,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             .await?;
 
         if metadata.last_known_status.status != AgentStatus::Interrupted {
-            let event_service = Worker::get_or_create_suspended(
+            let worker = Worker::get_or_create_suspended(
                 self,
                 &owned_agent_id,
                 None,
@@ -1328,15 +1451,37 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 &InvocationContextStack::fresh(),
                 principal,
             )
-            .await?
-            .event_service();
+            .await?;
 
-            let receiver = event_service.receiver();
+            let receiver = worker.event_service().receiver();
+            let mut stream = WorkerEventStream::new(receiver);
+
+            if let Some(websocket_connection) = request.websocket_connection {
+                worker
+                    .record_websocket_connected(
+                        websocket_connection.connection_id.clone(),
+                        websocket_connection.method_name,
+                    )
+                    .await?;
+
+                // The gateway drops the event stream when the WebSocket connection closes
+                let connection_id = websocket_connection.connection_id;
+                stream = stream.on_drop(move || {
+                    tokio::spawn(async move {
+                        if let Err(err) = worker
+                            .record_websocket_disconnected(connection_id.clone())
+                            .await
+                        {
+                            warn!("Failed to record closing WebSocket connection {connection_id}: {err}");
+                        }
+                    });
+                });
+            }
 
             info!("Client connected");
             record_new_grpc_api_active_stream();
 
-            Ok(Response::new(WorkerEventStream::new(receiver)))
+            Ok(Response::new(stream))
         } else {
             // We don't want 'connect' to resume interrupted workers
             Err(WorkerExecutorError::Interrupted {
@@ -1472,6 +1617,9 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 ))
             })?;
 
+        let from = request.from.map(Timestamp::from);
+        let to = request.to.map(Timestamp::from);
+
         let chunk = match request.cursor {
             Some(cursor) => {
                 let current_component_revision =
@@ -1494,6 +1642,8 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                         self.services.config().limits.max_oplog_query_pages_size,
                     ),
                     &request.query,
+                    from,
+                    to,
                 )
                 .await
                 .map_err(WorkerExecutorError::unknown)?
@@ -1520,6 +1670,8 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                         self.services.config().limits.max_oplog_query_pages_size,
                     ),
                     &request.query,
+                    from,
+                    to,
                 )
                 .await
                 .map_err(WorkerExecutorError::unknown)?
@@ -1919,6 +2071,26 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             principal,
         };
 
+        if let Some(at) = &request.at {
+            if !matches!(
+                mode,
+                golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await
+            ) {
+                return Err(WorkerExecutorError::invalid_request(
+                    "time-travel invocations can only be awaited",
+                ));
+            }
+            let at = extract_time_travel_target(at)?;
+            let owned_agent_id = self.canonicalize_owned_agent_id(&owned_agent_id).await?;
+            self.ensure_worker_belongs_to_this_executor(&owned_agent_id)?;
+            let invocation_output = self
+                .services
+                .worker_fork_service()
+                .invoke_at(&owned_agent_id, at, invocation)
+                .await?;
+            return Ok((Some(invocation_output), None));
+        }
+
         match mode {
             golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await => {
                 // Use the `pending` variant so we do NOT start the wasmtime instance
@@ -1962,6 +2134,78 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_agent_lifecycle_audit_internal(
+        &self,
+        request: GetAgentLifecycleAuditRequest,
+    ) -> Result<GetAgentLifecycleAuditSuccessResponse, WorkerExecutorError> {
+        let environment_id: EnvironmentId = request
+            .environment_id
+            .and_then(|t| t.try_into().ok())
+            .ok_or(WorkerExecutorError::invalid_request(
+                "Invalid environment id",
+            ))?;
+
+        let component_id: ComponentId = request
+            .component_id
+            .and_then(|t| t.try_into().ok())
+            .ok_or(WorkerExecutorError::invalid_request("Invalid component id"))?;
+
+        let from = request
+            .from
+            .map(Timestamp::from)
+            .unwrap_or(Timestamp::from(0));
+        let to = request
+            .to
+            .map(Timestamp::from)
+            .unwrap_or_else(Timestamp::now_utc);
+
+        let entries: Vec<_> = self
+            .scheduler_service()
+            .lifecycle_audit(environment_id, from, to)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!("Failed to read lifecycle audit: {err}"))
+            })?
+            .into_iter()
+            .filter(|entry| entry.agent_id.component_id == component_id)
+            .collect();
+
+        // Pages end on a timestamp boundary so continuing from `next_from` does not repeat or
+        // skip entries sharing the same timestamp
+        let mut end = entries.len().min(request.count as usize);
+        if end < entries.len() {
+            let boundary = entries[end].timestamp;
+            let mut cut = end;
+            while cut > 0 && entries[cut - 1].timestamp == boundary {
+                cut -= 1;
+            }
+            if cut == 0 {
+                cut = end;
+                while cut < entries.len() && entries[cut].timestamp == boundary {
+                    cut += 1;
+                }
+            }
+            end = cut;
+        }
+        let next_from = entries.get(end).map(|entry| entry.timestamp.into());
+
+        Ok(GetAgentLifecycleAuditSuccessResponse {
+            entries: entries
+                .into_iter()
+                .take(end)
+                .map(
+                    |entry| golem::workerexecutor::v1::AgentLifecycleAuditEntry {
+                        timestamp: Some(entry.timestamp.into()),
+                        agent_id: Some(entry.agent_id.into()),
+                        action: golem::component::AgentLifecycleAction::from(entry.action) as i32,
+                        last_oplog_index: entry.last_oplog_index.into(),
+                    },
+                )
+                .collect(),
+            next_from,
+        })
+    }
+
     async fn process_oplog_entries_internal(
         &self,
         request: ProcessOplogEntriesRequest,
@@ -2145,6 +2389,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 .collect(),
             oplog_idx: u64::from(latest_status.oplog_idx),
             fingerprint: Some(metadata.fingerprint.0.into()),
+            promises: Vec::new(),
         })
     }
 }
@@ -2747,6 +2992,98 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    type ExportAgentStream =
+        Pin<Box<dyn Stream<Item = Result<ExportAgentResponse, Status>> + Send + 'static>>;
+
+    async fn export_agent(
+        &self,
+        request: Request<ExportAgentRequest>,
+    ) -> ResponseResult<Self::ExportAgentStream> {
+        let request = request.into_inner();
+
+        let record = recorded_grpc_api_request!(
+            "export_agent",
+            agent_id = proto_agent_id_string(&request.agent_id),
+        );
+
+        let result = self
+            .export_agent_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        let stream: Self::ExportAgentStream = match result {
+            Ok(stream) => record.succeed(stream),
+            Err(mut err) => {
+                let res = ExportAgentResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::export_agent_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                };
+
+                let err_stream: Self::ExportAgentStream =
+                    Box::pin(tokio_stream::iter(vec![Ok(res)]));
+
+                record.fail(err_stream, &mut err)
+            }
+        };
+        Ok(Response::new(stream))
+    }
+
+    async fn import_agent(
+        &self,
+        request: Request<tonic::Streaming<ImportAgentRequest>>,
+    ) -> Result<Response<ImportAgentResponse>, Status> {
+        let mut requests = request.into_inner();
+
+        let header = match requests.message().await {
+            Ok(Some(ImportAgentRequest {
+                request:
+                    Some(golem::workerexecutor::v1::import_agent_request::Request::Header(header)),
+            })) => Ok(header),
+            Ok(_) => Err(WorkerExecutorError::invalid_request(
+                "import request does not start with a header",
+            )),
+            Err(status) => Err(WorkerExecutorError::invalid_request(format!(
+                "Failed to receive import request: {status}"
+            ))),
+        };
+
+        let record = recorded_grpc_api_request!(
+            "import_agent",
+            target_agent_id = header
+                .as_ref()
+                .ok()
+                .and_then(|header| proto_agent_id_string(&header.target_agent_id)),
+        );
+
+        let result = self
+            .import_agent_internal(header, requests)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(_) => record.succeed(Ok(Response::new(ImportAgentResponse {
+                result: Some(
+                    golem::workerexecutor::v1::import_agent_response::Result::Success(
+                        golem::common::Empty {},
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(ImportAgentResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::import_agent_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
     async fn revert_worker(
         &self,
         request: Request<RevertWorkerRequest>,
@@ -3071,17 +3408,140 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             ),
         }
     }
+
+    async fn get_agent_lifecycle_audit(
+        &self,
+        request: Request<GetAgentLifecycleAuditRequest>,
+    ) -> ResponseResult<GetAgentLifecycleAuditResponse> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_agent_lifecycle_audit",
+            component_id = ComponentId::render_proto(request.component_id),
+        );
+
+        let result = self
+            .get_agent_lifecycle_audit_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(success) => record.succeed(Ok(Response::new(GetAgentLifecycleAuditResponse {
+                result: Some(get_agent_lifecycle_audit_response::Result::Success(success)),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(GetAgentLifecycleAuditResponse {
+                    result: Some(get_agent_lifecycle_audit_response::Result::Failure(
+                        err.clone().into(),
+                    )),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
+    type SubscribeAgentWritesStream =
+        Pin<Box<dyn Stream<Item = Result<SubscribeAgentWritesResponse, Status>> + Send + 'static>>;
+
+    async fn subscribe_agent_writes(
+        &self,
+        _request: Request<SubscribeAgentWritesRequest>,
+    ) -> ResponseResult<Self::SubscribeAgentWritesStream> {
+        let record = recorded_grpc_api_request!("subscribe_agent_writes",);
+
+        let stream: Self::SubscribeAgentWritesStream =
+            Box::pin(agent_writes_stream(self.events().subscribe_agent_writes()));
+        Ok(Response::new(record.succeed(stream)))
+    }
+}
+
+/// Streams the agent writes in batches. Every batch holds the writes published since the
+/// previous one, merged per agent, so a busy agent does not flood the subscribers.
+fn agent_writes_stream(
+    receiver: tokio::sync::broadcast::Receiver<AgentWrite>,
+) -> impl Stream<Item = Result<SubscribeAgentWritesResponse, Status>> + Send + 'static {
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        let first = match receiver.recv().await {
+            Ok(write) => write,
+            Err(RecvError::Lagged(_)) => {
+                return Some((Ok(agent_writes_lagged()), receiver));
+            }
+            Err(RecvError::Closed) => return None,
+        };
+
+        let mut writes: HashMap<AgentId, AgentWrite> = HashMap::new();
+        let mut next = Some(first);
+        while let Some(write) = next {
+            match writes.get_mut(&write.agent_id) {
+                Some(merged) => {
+                    merged.oplog_index = write.oplog_index;
+                    merged.deleted |= write.deleted;
+                }
+                None => {
+                    writes.insert(write.agent_id.clone(), write);
+                }
+            }
+            next = match receiver.try_recv() {
+                Ok(write) => Some(write),
+                Err(TryRecvError::Lagged(_)) => {
+                    return Some((Ok(agent_writes_lagged()), receiver));
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => None,
+            };
+        }
+
+        let writes = writes
+            .into_values()
+            .map(|write| golem::workerexecutor::v1::AgentWrite {
+                agent_id: Some(write.agent_id.into()),
+                oplog_index: write.oplog_index.into(),
+                deleted: write.deleted,
+            })
+            .collect();
+
+        Some((
+            Ok(SubscribeAgentWritesResponse {
+                event: Some(subscribe_agent_writes_response::Event::Writes(
+                    golem::workerexecutor::v1::AgentWrites { writes },
+                )),
+            }),
+            receiver,
+        ))
+    })
+}
+
+fn agent_writes_lagged() -> SubscribeAgentWritesResponse {
+    SubscribeAgentWritesResponse {
+        event: Some(subscribe_agent_writes_response::Event::Lagged(
+            golem::common::Empty {},
+        )),
+    }
 }
 
 pub struct WorkerEventStream {
     inner:
         Pin<Box<dyn Stream<Item = Result<InternalWorkerEvent, BroadcastStreamRecvError>> + Send>>,
+    on_drop: Option<Box<dyn FnOnce() + Send>>,
 }
 
 impl WorkerEventStream {
     pub fn new(receiver: WorkerEventReceiver) -> Self {
         WorkerEventStream {
             inner: Box::pin(receiver.to_stream()),
+            on_drop: None,
+        }
+    }
+
+    /// Registers a callback to run when the client disconnects and the stream gets dropped
+    pub fn on_drop(mut self, f: impl FnOnce() + Send + 'static) -> Self {
+        self.on_drop = Some(Box::new(f));
+        self
+    }
+}
+
+impl Drop for WorkerEventStream {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop.take() {
+            on_drop();
         }
     }
 }
@@ -3090,7 +3550,7 @@ impl Stream for WorkerEventStream {
     type Item = Result<golem::worker::LogEvent, Status>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let WorkerEventStream { inner } = self.get_mut();
+        let WorkerEventStream { inner, .. } = self.get_mut();
         match inner.as_mut().poll_next(cx) {
             Poll::Ready(Some(Ok(event))) => {
                 Poll::Ready(Some(Ok(AgentEvent::from(event).try_into().unwrap())))
@@ -3120,6 +3580,27 @@ fn extract_principal(
         .unwrap_or_else(Principal::anonymous)
 }
 
+fn extract_time_travel_target(
+    at: &golem::workerexecutor::v1::TimeTravelTarget,
+) -> Result<TimeTravelTarget, WorkerExecutorError> {
+    match &at.target {
+        Some(golem::workerexecutor::v1::time_travel_target::Target::OplogIndex(idx)) => {
+            Ok(TimeTravelTarget::OplogIndex(OplogIndex::from_u64(*idx)))
+        }
+        Some(golem::workerexecutor::v1::time_travel_target::Target::Timestamp(ts)) => {
+            let timestamp = DateTime::<Utc>::from_timestamp(ts.seconds, ts.nanos as u32).ok_or(
+                WorkerExecutorError::invalid_request("invalid time-travel timestamp"),
+            )?;
+            Ok(TimeTravelTarget::Timestamp(Timestamp::from(
+                timestamp.timestamp_millis().max(0) as u64,
+            )))
+        }
+        None => Err(WorkerExecutorError::invalid_request(
+            "time-travel target not found",
+        )),
+    }
+}
+
 fn extract_owned_agent_id<T>(
     request: &T,
     get_agent_id: impl FnOnce(&T) -> &Option<golem::worker::AgentId>,
@@ -46,15 +46,21 @@ use self::services::worker_fork::DefaultWorkerFork;
 use self::wasi_host::create_linker;
 use crate::grpc::WorkerExecutorImpl;
 use crate::services::active_workers::ActiveWorkers;
+use crate::services::agent_lifecycle::{AgentLifecycleBackfill, DefaultAgentLifecycleAudit};
 use crate::services::agent_types::AgentTypesService;
 use crate::services::blob_store::{BlobStoreService, DefaultBlobStoreService};
 use crate::services::component::ComponentService;
 use crate::services::events::Events;
 use crate::services::golem_config::{
-    EngineConfig, GolemConfig, HttpClientConfig, IndexedStorageConfig, KeyValueStorageConfig,
-    KeyValueStorageInnerConfig, SchedulerStorageConfig,
+    EncryptionConfig, EngineConfig, GolemConfig, HttpClientConfig, IndexedStorageConfig,
+    KeyValueStorageConfig, KeyValueStorageInnerConfig, SchedulerStorageConfig,
+    StorageMigrationConfig,
+};
+use crate::services::invocation_interceptor::{
+    InvocationInterceptorPlugin, PerExecutorInvocationInterceptorPlugin,
 };
 use crate::services::key_value::{DefaultKeyValueService, KeyValueService};
+use crate::services::oplog::export::OplogExports;
 use crate::services::oplog::plugin::{
     ForwardingOplogService, OplogProcessorPlugin, PerExecutorOplogProcessorPlugin,
 };
@@ -69,6 +75,8 @@ use crate::services::registry_event_subscriber::WorkerExecutorRegistryInvalidati
 use crate::services::scheduler::{SchedulerService, SchedulerServiceDefault};
 use crate::services::shard::{ShardService, ShardServiceDefault};
 use crate::services::shard_manager::ShardManagerService;
+use crate::services::storage_migration::StorageMigrationService;
+use crate::services::topics::{DefaultTopicService, TopicService};
 use crate::services::worker::{DefaultWorkerService, WorkerService};
 use crate::services::worker_activator::{LazyWorkerActivator, WorkerActivator};
 use crate::services::worker_enumeration::{
@@ -78,21 +86,30 @@ use crate::services::worker_enumeration::{
 use crate::services::worker_proxy::{RemoteWorkerProxy, WorkerProxy};
 use crate::services::{
     All, HasActiveWorkers, HasAgentTypesService, HasComponentService, HasConfig,
-    HasEnvironmentStateService, HasOplogService, HasWorkerActivator, HasWorkerService, rdbms,
+    HasEnvironmentStateService, HasOplogService, HasSchedulerService, HasShardService,
+    HasWorkerActivator, HasWorkerEnumerationService, HasWorkerService, rdbms,
 };
+use crate::storage::blob::encrypting::EncryptingBlobStorage;
+use crate::storage::encryption::EnvelopeEncryption;
 use crate::storage::indexed::IndexedStorage;
+use crate::storage::indexed::encrypting::EncryptingIndexedStorage;
+use crate::storage::indexed::migrating::MigratingIndexedStorage;
 use crate::storage::indexed::multi_sqlite::MultiSqliteIndexedStorage;
 use crate::storage::indexed::postgres::PostgresIndexedStorage;
 use crate::storage::indexed::redis::RedisIndexedStorage;
 use crate::storage::indexed::sqlite::SqliteIndexedStorage;
 use crate::storage::keyvalue::KeyValueStorage;
+use crate::storage::keyvalue::encrypting::EncryptingKeyValueStorage;
 use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+use crate::storage::keyvalue::migrating::MigratingKeyValueStorage;
 use crate::storage::keyvalue::multi_sqlite::MultiSqliteKeyValueStorage;
 use crate::storage::keyvalue::namespace_routed::NamespaceRoutedKeyValueStorage;
 use crate::storage::keyvalue::postgres::PostgresKeyValueStorage;
 use crate::storage::keyvalue::redis::RedisKeyValueStorage;
+use crate::storage::migration::StorageMigrationState;
 use crate::storage::scheduler::SchedulerStorage;
 use crate::storage::scheduler::memory::InMemorySchedulerStorage;
+use crate::storage::scheduler::migrating::MigratingSchedulerStorage;
 use crate::storage::scheduler::postgres::PostgresSchedulerStorage;
 use crate::storage::scheduler::sqlite::SqliteSchedulerStorage;
 use crate::workerctx::WorkerCtx;
@@ -116,6 +133,9 @@ use log::debug;
 use nonempty_collections::NEVec;
 use prometheus::Registry;
 use services::file_loader::FileLoader;
+use services::filesystem_checkpoint::{
+    DefaultFilesystemCheckpointService, FilesystemCheckpointService,
+};
 use std::net::{Ipv4Addr, SocketAddrV4};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -258,6 +278,13 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         Arc::new(DefaultKeyValueService::new(key_value_storage.clone()))
     }
 
+    fn create_topic_service(
+        &self,
+        key_value_storage: &Arc<dyn KeyValueStorage + Send + Sync>,
+    ) -> Arc<dyn TopicService> {
+        Arc::new(DefaultTopicService::new(key_value_storage.clone()))
+    }
+
     fn create_blob_store_service(
         &self,
         blob_storage: &Arc<dyn BlobStorage>,
@@ -265,6 +292,17 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         Arc::new(DefaultBlobStoreService::new(blob_storage.clone()))
     }
 
+    fn create_filesystem_checkpoint_service(
+        &self,
+        golem_config: &GolemConfig,
+        blob_storage: &Arc<dyn BlobStorage>,
+    ) -> Arc<dyn FilesystemCheckpointService> {
+        Arc::new(DefaultFilesystemCheckpointService::new(
+            blob_storage.clone(),
+            golem_config.filesystem_storage.checkpoint_max_bytes,
+        ))
+    }
+
     fn create_additional_deps(&self, registry_service: Arc<dyn RegistryService>) -> Ctx::ExtraDeps;
 
     fn create_direct_invocation_auth_service(
@@ -293,6 +331,13 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         rpc
     }
 
+    fn wrap_invocation_interceptor_plugin(
+        &self,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
+    ) -> Arc<dyn InvocationInterceptorPlugin> {
+        invocation_interceptor_plugin
+    }
+
     async fn create_services(
         &self,
         direct_invocation_auth_service: Arc<dyn DirectInvocationAuthService>,
@@ -309,6 +354,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         golem_config: Arc<GolemConfig>,
         shard_service: Arc<dyn ShardService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn rdbms::RdbmsService>,
         worker_activator: Arc<dyn WorkerActivator<Ctx>>,
@@ -317,7 +363,9 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         worker_proxy: Arc<dyn WorkerProxy>,
         events: Arc<Events>,
         file_loader: Arc<FileLoader>,
+        filesystem_checkpoint_service: Arc<dyn FilesystemCheckpointService>,
         oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
         agent_types_service: Arc<dyn AgentTypesService>,
         environment_state_service: Arc<dyn EnvironmentStateService>,
         agent_webhooks_service: Arc<AgentWebhooksService>,
@@ -349,6 +397,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             golem_config.clone(),
             shard_service.clone(),
             key_value_service.clone(),
+            topic_service.clone(),
             blob_store_service.clone(),
             rdbms_service.clone(),
             oplog_service.clone(),
@@ -356,7 +405,9 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             worker_activator.clone(),
             events.clone(),
             file_loader.clone(),
+            filesystem_checkpoint_service.clone(),
             oplog_processor_plugin.clone(),
+            invocation_interceptor_plugin.clone(),
             resource_limits.clone(),
             environment_state_service.clone(),
             agent_types_service.clone(),
@@ -389,6 +440,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             shard_manager_service.clone(),
             quota_service.clone(),
             key_value_service.clone(),
+            topic_service.clone(),
             blob_store_service.clone(),
             rdbms_service.clone(),
             oplog_service.clone(),
@@ -396,7 +448,9 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             worker_activator.clone(),
             events.clone(),
             file_loader.clone(),
+            filesystem_checkpoint_service.clone(),
             oplog_processor_plugin.clone(),
+            invocation_interceptor_plugin.clone(),
             resource_limits.clone(),
             shutdown_token.clone(),
             environment_state_service.clone(),
@@ -427,6 +481,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             golem_config.clone(),
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service.clone(),
             oplog_service,
@@ -436,7 +491,9 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             worker_proxy.clone(),
             events.clone(),
             file_loader.clone(),
+            filesystem_checkpoint_service.clone(),
             oplog_processor_plugin.clone(),
+            invocation_interceptor_plugin.clone(),
             resource_limits,
             shutdown_token,
             http_connection_pool,
@@ -522,141 +579,114 @@ pub async fn create_worker_executor_impl<
     ),
     anyhow::Error,
 > {
-    let (redis, sqlite, key_value_storage): (
-        Option<RedisPool>,
-        Option<SqlitePool>,
-        Arc<dyn KeyValueStorage + Send + Sync>,
-    ) = match &golem_config.key_value_storage {
-        KeyValueStorageConfig::Redis(redis) => {
-            let pool = RedisPool::configured(redis)
-                .await
-                .map_err(|err| anyhow!(err))?;
-            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
-                Arc::new(RedisKeyValueStorage::new(pool.clone()));
-            (Some(pool), None, key_value_storage)
-        }
-        KeyValueStorageConfig::Postgres(postgres) => {
-            let kv = PostgresKeyValueStorage::configured(postgres)
-                .await
-                .map_err(|err| anyhow!(err))?;
-            let kv_metrics = kv.clone();
-            join_set.spawn(async move { kv_metrics.run_metrics_loop("key_value_storage").await });
-            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(kv);
-            (None, None, key_value_storage)
-        }
-        KeyValueStorageConfig::NamespaceRouted(namespace_routed) => {
-            let (cache_redis, cache_sqlite, cache_storage) = build_inner_key_value_storage(
-                &namespace_routed.cache,
-                "key_value_storage_cache",
-                join_set,
-            )
-            .await?;
-            let (persistent_redis, persistent_sqlite, persistent_storage) =
-                build_inner_key_value_storage(
-                    &namespace_routed.persistent,
-                    "key_value_storage_persistent",
-                    join_set,
-                )
-                .await?;
-
-            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(
-                NamespaceRoutedKeyValueStorage::new(cache_storage, persistent_storage),
-            );
-
-            (
-                cache_redis.or(persistent_redis),
-                cache_sqlite.or(persistent_sqlite),
-                key_value_storage,
-            )
-        }
-        KeyValueStorageConfig::InMemory(_) => {
-            (None, None, Arc::new(InMemoryKeyValueStorage::new()))
-        }
-        KeyValueStorageConfig::Sqlite(sqlite) => {
-            let storage = SqliteKeyValueStorage::configured(sqlite)
-                .await
-                .map_err(|err| anyhow!(err))?;
-            let pool = storage.pool();
-            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(storage);
-            (None, Some(pool), key_value_storage)
-        }
-        KeyValueStorageConfig::MultiSqlite(multi_sqlite) => {
-            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
-                Arc::new(MultiSqliteKeyValueStorage::new(
-                    &multi_sqlite.root_dir,
-                    multi_sqlite.max_connections,
-                    multi_sqlite.foreign_keys,
-                ));
-            (None, None, key_value_storage)
-        }
-    };
+    let (redis, sqlite, key_value_storage) = build_key_value_storage(
+        &golem_config.key_value_storage,
+        "key_value_storage",
+        join_set,
+    )
+    .await?;
 
     let scheduler_storage = build_scheduler_storage(&golem_config.scheduler_storage).await?;
 
-    let indexed_storage: Arc<dyn IndexedStorage + Send + Sync> = match &golem_config.indexed_storage
-    {
-        IndexedStorageConfig::KVStoreRedis(_) => {
-            let redis = redis
-                .expect("Redis must be configured as key-value storage when using KVStoreRedis");
-            Arc::new(RedisIndexedStorage::new(redis.clone()))
-        }
-        IndexedStorageConfig::Redis(redis) => {
-            let pool = RedisPool::configured(redis).await?;
-            Arc::new(RedisIndexedStorage::new(pool.clone()))
-        }
-        IndexedStorageConfig::Postgres(postgres) => {
-            let is = PostgresIndexedStorage::configured(postgres)
-                .await
-                .map_err(|err| anyhow!(err))?;
-            let is_metrics = is.clone();
-            join_set.spawn(async move { is_metrics.run_metrics_loop().await });
-            Arc::new(is)
-        }
-        IndexedStorageConfig::KVStoreSqlite(_) => {
-            let kv_sqlite_config = match &golem_config.key_value_storage {
-                KeyValueStorageConfig::Sqlite(sqlite_config) => sqlite_config,
-                _ => panic!(
-                    "Invalid configuration: sqlite must be used as key-value storage when using KVStoreSqlite"
-                ),
-            };
-            // The indexed storage uses its own SQLite DB file, disjoint from the
-            // key-value storage's one, so each module has an independent
-            // `_sqlx_migrations` table.
-            let indexed_sqlite_config = derive_disjoint_sqlite_config(kv_sqlite_config, "indexed");
-            Arc::new(
-                SqliteIndexedStorage::configured(&indexed_sqlite_config)
-                    .await
-                    .map_err(|err| anyhow!(err))?,
-            )
-        }
-        IndexedStorageConfig::KVStoreMultiSqlite(_) => match &golem_config.key_value_storage {
-            KeyValueStorageConfig::MultiSqlite(multi_sqlite) => {
-                Arc::new(MultiSqliteIndexedStorage::new(
-                    &multi_sqlite.root_dir,
-                    multi_sqlite.max_connections,
-                    multi_sqlite.foreign_keys,
-                ))
+    let indexed_storage = build_indexed_storage(
+        &golem_config.indexed_storage,
+        &golem_config.key_value_storage,
+        redis.clone(),
+        join_set,
+    )
+    .await?;
+
+    // When a storage migration is enabled, the storages configured above are the sources, wrapped
+    // together with the configured targets
+    let mut storage_migration = None;
+    let (key_value_storage, indexed_storage, scheduler_storage) =
+        match &golem_config.storage_migration {
+            StorageMigrationConfig::Enabled(config) => {
+                let (migrating_key_value_storage, target_redis, target_key_value_storage_config) =
+                    match &config.key_value_storage {
+                        Some(target_config) => {
+                            let (target_redis, _, target) = build_key_value_storage(
+                                target_config,
+                                "key_value_storage_migration_target",
+                                join_set,
+                            )
+                            .await?;
+                            (
+                                Some(Arc::new(MigratingKeyValueStorage::new(
+                                    key_value_storage.clone(),
+                                    target,
+                                ))),
+                                target_redis,
+                                target_config,
+                            )
+                        }
+                        None => (None, redis, &golem_config.key_value_storage),
+                    };
+                let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                    match &migrating_key_value_storage {
+                        Some(migrating) => migrating.clone(),
+                        None => key_value_storage,
+                    };
+
+                // The migration state is persisted to both key-value storages while they are
+                // migrated, so it is not lost by cutting over
+                let state = Arc::new(StorageMigrationState::new(key_value_storage.clone()));
+
+                let migrating_indexed_storage = match &config.indexed_storage {
+                    Some(target_config) => {
+                        let target = build_indexed_storage(
+                            target_config,
+                            target_key_value_storage_config,
+                            target_redis,
+                            join_set,
+                        )
+                        .await?;
+                        Some(Arc::new(MigratingIndexedStorage::new(
+                            indexed_storage.clone(),
+                            target,
+                            state.clone(),
+                        )))
+                    }
+                    None => None,
+                };
+
+                let migrating_scheduler_storage = match &config.scheduler_storage {
+                    Some(target_config) => {
+                        let target = build_scheduler_storage(target_config).await?;
+                        Some(Arc::new(MigratingSchedulerStorage::new(
+                            scheduler_storage.clone(),
+                            target,
+                            state.clone(),
+                        )))
+                    }
+                    None => None,
+                };
+
+                let indexed_storage: Arc<dyn IndexedStorage + Send + Sync> =
+                    match &migrating_indexed_storage {
+                        Some(migrating) => migrating.clone(),
+                        None => indexed_storage,
+                    };
+                let scheduler_storage: Arc<dyn SchedulerStorage + Send + Sync> =
+                    match &migrating_scheduler_storage {
+                        Some(migrating) => migrating.clone(),
+                        None => scheduler_storage,
+                    };
+
+                storage_migration = Some((
+                    state,
+                    indexed_storage.clone(),
+                    migrating_indexed_storage,
+                    migrating_key_value_storage,
+                    migrating_scheduler_storage,
+                    config.clone(),
+                ));
+                (key_value_storage, indexed_storage, scheduler_storage)
             }
-            _ => panic!(
-                "Invalid configuration: multi-sqlite must be used as key-value storage when using KVStoreMultiSqlite"
-            ),
-        },
-        IndexedStorageConfig::Sqlite(sqlite) => Arc::new(
-            SqliteIndexedStorage::configured(sqlite)
-                .await
-                .map_err(|err| anyhow!(err))?,
-        ),
-        IndexedStorageConfig::MultiSqlite(multi_sqlite) => {
-            Arc::new(MultiSqliteIndexedStorage::new(
-                &multi_sqlite.root_dir,
-                multi_sqlite.max_connections,
-                multi_sqlite.foreign_keys,
-            ))
-        }
-        IndexedStorageConfig::InMemory(_) => {
-            Arc::new(storage::indexed::memory::InMemoryIndexedStorage::new())
-        }
-    };
+            StorageMigrationConfig::Disabled(_) => {
+                (key_value_storage, indexed_storage, scheduler_storage)
+            }
+        };
 
     let blob_storage: Arc<dyn BlobStorage> = match &golem_config.blob_storage {
         BlobStorageConfig::S3(config) => Arc::new(S3BlobStorage::new(config.clone()).await),
@@ -689,6 +719,31 @@ pub async fn create_worker_executor_impl<
         }
     };
 
+    // When encryption at rest is enabled, the storages holding the agents' data encrypt it with
+    // the data keys, which are themselves stored in the unwrapped key-value storage
+    let encryption = match &golem_config.encryption {
+        EncryptionConfig::Enabled(config) => Some(Arc::new(
+            EnvelopeEncryption::configured(config, key_value_storage.clone())
+                .await
+                .map_err(|err| anyhow!(err))?,
+        )),
+        EncryptionConfig::Disabled(_) => None,
+    };
+    let (key_value_storage, indexed_storage, blob_storage) = match &encryption {
+        Some(encryption) => {
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(
+                EncryptingKeyValueStorage::new(key_value_storage, encryption.clone()),
+            );
+            let indexed_storage: Arc<dyn IndexedStorage + Send + Sync> = Arc::new(
+                EncryptingIndexedStorage::new(indexed_storage, encryption.clone()),
+            );
+            let blob_storage: Arc<dyn BlobStorage> =
+                Arc::new(EncryptingBlobStorage::new(blob_storage, encryption.clone()));
+            (key_value_storage, indexed_storage, blob_storage)
+        }
+        None => (key_value_storage, indexed_storage, blob_storage),
+    };
+
     let initial_files_service = Arc::new(InitialAgentFilesService::new(blob_storage.clone()));
 
     let registry_service = Arc::new(GrpcRegistryService::new(&golem_config.registry_service));
@@ -738,6 +793,28 @@ pub async fn create_worker_executor_impl<
 
     let shard_service = Arc::new(ShardServiceDefault::new());
 
+    if let Some((
+        state,
+        oplog_storage,
+        migrating_indexed_storage,
+        migrating_key_value_storage,
+        migrating_scheduler_storage,
+        config,
+    )) = storage_migration
+    {
+        let storage_migration_service = StorageMigrationService::new(
+            state,
+            oplog_storage,
+            migrating_indexed_storage,
+            migrating_key_value_storage,
+            migrating_scheduler_storage,
+            shard_service.clone(),
+            golem_config.oplog.indexed_storage_layers,
+            config,
+        );
+        join_set.spawn(storage_migration_service.run(shutdown_token.clone()));
+    }
+
     let mut oplog_archives: Vec<Arc<dyn OplogArchiveService>> = Vec::new();
     for idx in 1..golem_config.oplog.indexed_storage_layers {
         let svc: Arc<dyn OplogArchiveService> = Arc::new(CompressedOplogArchiveService::new(
@@ -795,6 +872,9 @@ pub async fn create_worker_executor_impl<
         Some(active_workers.filesystem_storage_semaphore()),
     )?);
 
+    let filesystem_checkpoint_service =
+        bootstrap.create_filesystem_checkpoint_service(&golem_config, &blob_storage);
+
     let running_worker_enumeration_service = Arc::new(RunningWorkerEnumerationServiceDefault::new(
         active_workers.clone(),
     ));
@@ -835,6 +915,8 @@ pub async fn create_worker_executor_impl<
 
     let key_value_service = bootstrap.create_key_value_service(&key_value_storage);
 
+    let topic_service = bootstrap.create_topic_service(&key_value_storage);
+
     let blob_store_service = bootstrap.create_blob_store_service(&blob_storage);
 
     let worker_proxy = bootstrap.create_worker_proxy(&golem_config);
@@ -850,6 +932,14 @@ pub async fn create_worker_executor_impl<
         worker_proxy.clone(),
     ));
 
+    let invocation_interceptor_plugin = bootstrap.wrap_invocation_interceptor_plugin(Arc::new(
+        PerExecutorInvocationInterceptorPlugin::new(
+            component_service.clone(),
+            shard_service.clone(),
+            lazy_worker_activator.clone(),
+        ),
+    ));
+
     let resource_limits = bootstrap.create_resource_limits(
         &golem_config,
         registry_service.clone(),
@@ -862,6 +952,12 @@ pub async fn create_worker_executor_impl<
         component_service.clone(),
         golem_config.oplog.plugin_max_commit_count,
         golem_config.oplog.plugin_max_elapsed_time,
+        Arc::new(OplogExports::new(
+            &golem_config.oplog.export,
+            environment_state_service.clone(),
+            key_value_storage.clone(),
+            blob_storage.clone(),
+        )),
     ));
 
     let oplog_service: Arc<dyn OplogService> = if golem_config.oplog.oplog_rate_limit_enabled {
@@ -878,6 +974,7 @@ pub async fn create_worker_executor_impl<
         shard_service.clone(),
         oplog_service.clone(),
         component_service.clone(),
+        encryption,
         golem_config.clone(),
     ));
     let worker_enumeration_service = Arc::new(DefaultWorkerEnumerationService::new(
@@ -896,6 +993,12 @@ pub async fn create_worker_executor_impl<
         Arc::new(lazy_worker_activator.clone() as Arc<dyn WorkerActivator<Ctx>>),
         oplog_service.clone(),
         worker_service.clone(),
+        worker_enumeration_service.clone(),
+        Arc::new(DefaultAgentLifecycleAudit::new(
+            key_value_storage.clone(),
+            golem_config.scheduler.lifecycle_audit_retention,
+        )),
+        topic_service.clone(),
         golem_config.scheduler.refresh_interval,
         golem_config.scheduler.claim_batch_size,
         golem_config.scheduler.lease_ttl,
@@ -928,6 +1031,7 @@ pub async fn create_worker_executor_impl<
             golem_config.clone(),
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             lazy_worker_activator.clone(),
@@ -936,7 +1040,9 @@ pub async fn create_worker_executor_impl<
             worker_proxy,
             events,
             file_loader,
+            filesystem_checkpoint_service,
             oplog_processor_plugin,
+            invocation_interceptor_plugin,
             agent_type_service,
             environment_state_service,
             agent_webhooks_service,
@@ -984,6 +1090,156 @@ fn derive_disjoint_sqlite_config(base: &DbSqliteConfig, suffix: &str) -> DbSqlit
     }
 }
 
+async fn build_key_value_storage(
+    config: &KeyValueStorageConfig,
+    svc_name: &'static str,
+    join_set: &mut JoinSet<Result<(), anyhow::Error>>,
+) -> Result<
+    (
+        Option<RedisPool>,
+        Option<SqlitePool>,
+        Arc<dyn KeyValueStorage + Send + Sync>,
+    ),
+    anyhow::Error,
+> {
+    match config {
+        KeyValueStorageConfig::Redis(redis) => {
+            let pool = RedisPool::configured(redis)
+                .await
+                .map_err(|err| anyhow!(err))?;
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                Arc::new(RedisKeyValueStorage::new(pool.clone()));
+            Ok((Some(pool), None, key_value_storage))
+        }
+        KeyValueStorageConfig::Postgres(postgres) => {
+            let kv = PostgresKeyValueStorage::configured(postgres)
+                .await
+                .map_err(|err| anyhow!(err))?;
+            let kv_metrics = kv.clone();
+            join_set.spawn(async move { kv_metrics.run_metrics_loop(svc_name).await });
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(kv);
+            Ok((None, None, key_value_storage))
+        }
+        KeyValueStorageConfig::NamespaceRouted(namespace_routed) => {
+            let (cache_redis, cache_sqlite, cache_storage) = build_inner_key_value_storage(
+                &namespace_routed.cache,
+                "key_value_storage_cache",
+                join_set,
+            )
+            .await?;
+            let (persistent_redis, persistent_sqlite, persistent_storage) =
+                build_inner_key_value_storage(
+                    &namespace_routed.persistent,
+                    "key_value_storage_persistent",
+                    join_set,
+                )
+                .await?;
+
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(
+                NamespaceRoutedKeyValueStorage::new(cache_storage, persistent_storage),
+            );
+
+            Ok((
+                cache_redis.or(persistent_redis),
+                cache_sqlite.or(persistent_sqlite),
+                key_value_storage,
+            ))
+        }
+        KeyValueStorageConfig::InMemory(_) => {
+            Ok((None, None, Arc::new(InMemoryKeyValueStorage::new())))
+        }
+        KeyValueStorageConfig::Sqlite(sqlite) => {
+            let storage = SqliteKeyValueStorage::configured(sqlite)
+                .await
+                .map_err(|err| anyhow!(err))?;
+            let pool = storage.pool();
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(storage);
+            Ok((None, Some(pool), key_value_storage))
+        }
+        KeyValueStorageConfig::MultiSqlite(multi_sqlite) => {
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                Arc::new(MultiSqliteKeyValueStorage::new(
+                    &multi_sqlite.root_dir,
+                    multi_sqlite.max_connections,
+                    multi_sqlite.foreign_keys,
+                ));
+            Ok((None, None, key_value_storage))
+        }
+    }
+}
+
+async fn build_indexed_storage(
+    config: &IndexedStorageConfig,
+    key_value_storage_config: &KeyValueStorageConfig,
+    redis: Option<RedisPool>,
+    join_set: &mut JoinSet<Result<(), anyhow::Error>>,
+) -> Result<Arc<dyn IndexedStorage + Send + Sync>, anyhow::Error> {
+    let indexed_storage: Arc<dyn IndexedStorage + Send + Sync> = match config {
+        IndexedStorageConfig::KVStoreRedis(_) => {
+            let redis = redis
+                .expect("Redis must be configured as key-value storage when using KVStoreRedis");
+            Arc::new(RedisIndexedStorage::new(redis.clone()))
+        }
+        IndexedStorageConfig::Redis(redis) => {
+            let pool = RedisPool::configured(redis).await?;
+            Arc::new(RedisIndexedStorage::new(pool.clone()))
+        }
+        IndexedStorageConfig::Postgres(postgres) => {
+            let is = PostgresIndexedStorage::configured(postgres)
+                .await
+                .map_err(|err| anyhow!(err))?;
+            let is_metrics = is.clone();
+            join_set.spawn(async move { is_metrics.run_metrics_loop().await });
+            Arc::new(is)
+        }
+        IndexedStorageConfig::KVStoreSqlite(_) => {
+            let kv_sqlite_config = match key_value_storage_config {
+                KeyValueStorageConfig::Sqlite(sqlite_config) => sqlite_config,
+                _ => panic!(
+                    "Invalid configuration: sqlite must be used as key-value storage when using KVStoreSqlite"
+                ),
+            };
+            // The indexed storage uses its own SQLite DB file, disjoint from the
+            // key-value storage's one, so each module has an independent
+            // `_sqlx_migrations` table.
+            let indexed_sqlite_config = derive_disjoint_sqlite_config(kv_sqlite_config, "indexed");
+            Arc::new(
+                SqliteIndexedStorage::configured(&indexed_sqlite_config)
+                    .await
+                    .map_err(|err| anyhow!(err))?,
+            )
+        }
+        IndexedStorageConfig::KVStoreMultiSqlite(_) => match key_value_storage_config {
+            KeyValueStorageConfig::MultiSqlite(multi_sqlite) => {
+                Arc::new(MultiSqliteIndexedStorage::new(
+                    &multi_sqlite.root_dir,
+                    multi_sqlite.max_connections,
+                    multi_sqlite.foreign_keys,
+                ))
+            }
+            _ => panic!(
+                "Invalid configuration: multi-sqlite must be used as key-value storage when using KVStoreMultiSqlite"
+            ),
+        },
+        IndexedStorageConfig::Sqlite(sqlite) => Arc::new(
+            SqliteIndexedStorage::configured(sqlite)
+                .await
+                .map_err(|err| anyhow!(err))?,
+        ),
+        IndexedStorageConfig::MultiSqlite(multi_sqlite) => {
+            Arc::new(MultiSqliteIndexedStorage::new(
+                &multi_sqlite.root_dir,
+                multi_sqlite.max_connections,
+                multi_sqlite.foreign_keys,
+            ))
+        }
+        IndexedStorageConfig::InMemory(_) => {
+            Arc::new(storage::indexed::memory::InMemoryIndexedStorage::new())
+        }
+    };
+    Ok(indexed_storage)
+}
+
 async fn build_scheduler_storage(
     config: &SchedulerStorageConfig,
 ) -> Result<Arc<dyn SchedulerStorage + Send + Sync>, anyhow::Error> {
@@ -1051,6 +1307,14 @@ pub async fn bootstrap_and_run_worker_executor<
         let component_service = worker_executor_impl.component_service();
         let environment_state_service = worker_executor_impl.environment_state_service();
         let agent_types_service = worker_executor_impl.agent_types();
+        let lifecycle_backfill = Arc::new(AgentLifecycleBackfill::new(
+            registry_service.clone(),
+            component_service.clone(),
+            worker_executor_impl.worker_enumeration_service(),
+            worker_executor_impl.oplog_service(),
+            worker_executor_impl.shard_service(),
+            worker_executor_impl.scheduler_service(),
+        ));
         let shutdown_token = shutdown.token();
         join_set.spawn(async move {
             WorkerExecutorRegistryInvalidationHandler::run(
@@ -1059,6 +1323,7 @@ pub async fn bootstrap_and_run_worker_executor<
                 component_service,
                 environment_state_service,
                 agent_types_service,
+                lifecycle_backfill,
                 shutdown_token,
             )
             .await;
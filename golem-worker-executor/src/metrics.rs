@@ -598,6 +598,11 @@ pub mod promises {
             "Number of scheduled promise completions"
         )
         .unwrap();
+        static ref PROMISES_TIMED_OUT_TOTAL: Counter = register_counter!(
+            "promises_timed_out_total",
+            "Number of promises failed because their deadline passed"
+        )
+        .unwrap();
         pub static ref PROMISE_COMPLETION_SECONDS: HistogramVec = register_histogram_vec!(
             "promise_completion_seconds",
             "Wall time of complete_promise from call entry to return, labelled by outcome",
@@ -628,6 +633,10 @@ pub mod promises {
         PROMISES_SCHEDULED_COMPLETE_TOTAL.inc();
     }
 
+    pub fn record_promise_timed_out() {
+        PROMISES_TIMED_OUT_TOTAL.inc();
+    }
+
     pub fn record_promise_completion(duration: Duration, outcome: &'static str) {
         PROMISE_COMPLETION_SECONDS
             .with_label_values(&[crate::metrics::storage::executor_id(), outcome])
@@ -686,6 +695,21 @@ pub mod scheduler {
             crate::metrics::BLOB_SIZE_BUCKETS.to_vec()
         )
         .unwrap();
+        pub static ref LIFECYCLE_ACTIONS_APPLIED_TOTAL: CounterVec = register_counter_vec!(
+            "lifecycle_actions_applied_total",
+            "Number of agent lifecycle policy actions applied by the scheduler",
+            &["executor_id", "action"]
+        )
+        .unwrap();
+    }
+
+    pub fn record_lifecycle_action_applied(
+        action: golem_common::model::component_metadata::AgentLifecycleAction,
+    ) {
+        let action = action.to_string();
+        LIFECYCLE_ACTIONS_APPLIED_TOTAL
+            .with_label_values(&[crate::metrics::storage::executor_id(), action.as_str()])
+            .inc();
     }
 
     pub fn record_scheduled_action_lag(lag: Duration) {
@@ -720,6 +744,10 @@ pub mod scheduler {
             ScheduledAction::ArchiveOplog { .. } => "archive_oplog",
             ScheduledAction::Invoke { .. } => "invoke",
             ScheduledAction::Resume { .. } => "resume",
+            ScheduledAction::ApplyLifecyclePolicy { .. } => "apply_lifecycle_policy",
+            ScheduledAction::TimeOutPromise { .. } => "time_out_promise",
+            ScheduledAction::DeliverTopicMessage { .. } => "deliver_topic_message",
+            ScheduledAction::FanOutTopicMessage { .. } => "fan_out_topic_message",
         }
     }
 }
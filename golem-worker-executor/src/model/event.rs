@@ -1,5 +1,7 @@
 use golem_common::model::oplog::OplogEntry;
-use golem_common::model::{AgentEvent, IdempotencyKey, LogLevel, Timestamp, oplog};
+use golem_common::model::{
+    AgentEvent, IdempotencyKey, LogLevel, Timestamp, WebSocketMessage, oplog,
+};
 
 // Internal version of AgentEvent, without any operational details.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -33,6 +35,17 @@ pub enum InternalWorkerEvent {
         plugin_name: String,
         message: String,
     },
+    WebSocketSend {
+        timestamp: Timestamp,
+        connection_id: String,
+        message: WebSocketMessage,
+    },
+    WebSocketClose {
+        timestamp: Timestamp,
+        connection_id: String,
+        code: Option<u16>,
+        reason: Option<String>,
+    },
 }
 
 impl InternalWorkerEvent {
@@ -83,6 +96,23 @@ impl InternalWorkerEvent {
         }
     }
 
+    pub fn websocket_send(connection_id: &str, message: WebSocketMessage) -> Self {
+        Self::WebSocketSend {
+            timestamp: Timestamp::now_utc(),
+            connection_id: connection_id.to_string(),
+            message,
+        }
+    }
+
+    pub fn websocket_close(connection_id: &str, code: Option<u16>, reason: Option<String>) -> Self {
+        Self::WebSocketClose {
+            timestamp: Timestamp::now_utc(),
+            connection_id: connection_id.to_string(),
+            code,
+            reason,
+        }
+    }
+
     pub fn as_oplog_entry(&self) -> Option<OplogEntry> {
         match self {
             Self::StdOut { timestamp, bytes } => Some(OplogEntry::Log {
@@ -118,6 +148,8 @@ impl InternalWorkerEvent {
             Self::InvocationStart { .. } => None,
             Self::InvocationFinished { .. } => None,
             Self::PluginError { .. } => None,
+            Self::WebSocketSend { .. } => None,
+            Self::WebSocketClose { .. } => None,
         }
     }
 }
@@ -165,6 +197,26 @@ impl From<InternalWorkerEvent> for AgentEvent {
                 plugin_name,
                 message,
             },
+            InternalWorkerEvent::WebSocketSend {
+                timestamp,
+                connection_id,
+                message,
+            } => Self::WebSocketSend {
+                timestamp,
+                connection_id,
+                message,
+            },
+            InternalWorkerEvent::WebSocketClose {
+                timestamp,
+                connection_id,
+                code,
+                reason,
+            } => Self::WebSocketClose {
+                timestamp,
+                connection_id,
+                code,
+                reason,
+            },
         }
     }
 }
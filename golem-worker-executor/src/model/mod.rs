@@ -40,6 +40,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::future::{Future, pending};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use wasmtime::Trap;
 
 pub mod event;
@@ -150,6 +151,9 @@ pub enum ExecutionStatus {
         agent_mode: AgentMode,
         timestamp: Timestamp,
         interrupt_signal: Arc<tokio::sync::broadcast::Sender<InterruptKind>>,
+        /// Deadline of the running agent method invocation, if it has one. Host calls waiting
+        /// on the interrupt signal are interrupted once it passes.
+        invocation_deadline: Option<Timestamp>,
     },
     Suspended {
         agent_mode: AgentMode,
@@ -225,13 +229,32 @@ impl ExecutionStatus {
         match self {
             ExecutionStatus::Loading { .. } => Box::pin(pending()),
             ExecutionStatus::Running {
-                interrupt_signal, ..
+                interrupt_signal,
+                invocation_deadline,
+                ..
             } => {
                 let mut rx = interrupt_signal.subscribe();
+                let invocation_deadline = *invocation_deadline;
                 Box::pin(async move {
-                    rx.recv()
-                        .await
-                        .unwrap_or(InterruptKind::Interrupt(Timestamp::now_utc()))
+                    let interrupted = async {
+                        rx.recv()
+                            .await
+                            .unwrap_or(InterruptKind::Interrupt(Timestamp::now_utc()))
+                    };
+                    match invocation_deadline {
+                        Some(deadline) => {
+                            let remaining = Duration::from_millis(
+                                deadline
+                                    .to_millis()
+                                    .saturating_sub(Timestamp::now_utc().to_millis()),
+                            );
+                            tokio::select! {
+                                interrupt_kind = interrupted => interrupt_kind,
+                                _ = tokio::time::sleep(remaining) => InterruptKind::Interrupt(deadline),
+                            }
+                        }
+                        None => interrupted.await,
+                    }
                 })
             }
             ExecutionStatus::Suspended { .. } => Box::pin(pending()),
@@ -34,23 +34,24 @@ use golem_common::model::oplog::public_oplog_entry::{
     PreCommitRemoteTransactionParams, PreRollbackRemoteTransactionParams, RemoveRetryPolicyParams,
     RestartParams, RevertParams, RolledBackRemoteTransactionParams, SetRetryPolicyParams,
     SetSpanAttributeParams, SnapshotParams, StartParams, StartSpanParams, SuccessfulUpdateParams,
-    SuspendParams,
+    SuspendParams, WebSocketConnectedParams, WebSocketDisconnectedParams,
 };
 use golem_common::model::oplog::types::encode_span_data;
 use golem_common::model::oplog::{
     AgentInitializationParameters, AgentInvocationOutputParameters,
     AgentMethodInvocationParameters, FallibleResultParameters, HostRequest,
     HostRequestGolemRpcInvoke, HostRequestGolemRpcScheduledInvocation, HostResponse,
-    JsonSnapshotData, LoadSnapshotParameters, ManualUpdateParameters, MultipartPartData,
-    MultipartSnapshotData, MultipartSnapshotPart, OplogEntry, OplogIndex,
-    PluginInstallationDescription, ProcessOplogEntriesParameters,
-    ProcessOplogEntriesResultParameters, PublicAgentInvocation, PublicAgentInvocationResult,
-    PublicAttribute, PublicOplogEntry, PublicSnapshotData, PublicUpdateDescription,
-    RawSnapshotData, SaveSnapshotResultParameters, SnapshotBasedUpdateParameters,
-    UpdateDescription,
+    InterceptInvocationParameters, InterceptInvocationResultParameters, JsonSnapshotData,
+    LoadSnapshotParameters, ManualUpdateParameters, MultipartPartData, MultipartSnapshotData,
+    MultipartSnapshotPart, OplogEntry, OplogIndex, PluginInstallationDescription,
+    ProcessOplogEntriesParameters, ProcessOplogEntriesResultParameters, PublicAgentInvocation,
+    PublicAgentInvocationResult, PublicAttribute, PublicOplogEntry, PublicSnapshotData,
+    PublicUpdateDescription, RawSnapshotData, SaveSnapshotResultParameters,
+    SnapshotBasedUpdateParameters, UpdateDescription,
 };
 use golem_common::model::{
-    AgentId, AgentInvocation, AgentInvocationPayload, AgentInvocationResult, Empty, OwnedAgentId,
+    AgentId, AgentInvocation, AgentInvocationPayload, AgentInvocationResult, Empty, Interception,
+    OwnedAgentId, Timestamp,
 };
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_wasm::IntoValueAndType;
@@ -131,6 +132,10 @@ pub struct PublicOplogSearchResult {
     pub last_index: OplogIndex,
 }
 
+/// Searches the public oplog for entries matching `query`, starting at `initial_oplog_index`.
+///
+/// Only entries created within the optional `[from, to]` time range are returned. Entries are
+/// appended in time order, so the search ends at the first entry created after `to`.
 pub async fn search_public_oplog(
     component_service: Arc<dyn ComponentService>,
     oplog_service: Arc<dyn OplogService>,
@@ -141,6 +146,8 @@ pub async fn search_public_oplog(
     initial_oplog_index: OplogIndex,
     count: usize,
     query: &str,
+    from: Option<Timestamp>,
+    to: Option<Timestamp>,
 ) -> Result<PublicOplogSearchResult, String> {
     let mut results = Vec::new();
     let mut last_index;
@@ -162,8 +169,14 @@ pub async fn search_public_oplog(
         )
         .await?;
 
+        let mut past_time_range = false;
         for (idx, entry) in chunk.entries.into_iter().enumerate() {
-            if entry.matches(&query) {
+            let timestamp = entry.timestamp();
+            if to.is_some_and(|to| timestamp > to) {
+                past_time_range = true;
+                break;
+            }
+            if from.is_none_or(|from| timestamp >= from) && entry.matches(&query) {
                 results.push((
                     OplogIndex::from_u64(u64::from(current_index) + idx as u64),
                     entry,
@@ -172,7 +185,11 @@ pub async fn search_public_oplog(
         }
 
         last_index = chunk.last_index;
-        current_index = chunk.next_oplog_index;
+        current_index = if past_time_range {
+            last_index.next()
+        } else {
+            chunk.next_oplog_index
+        };
         current_component_revision = chunk.current_component_revision;
 
         if current_index >= last_index || results.len() >= count {
@@ -857,6 +874,26 @@ impl PublicOplogEntryOps for PublicOplogEntry {
             OplogEntry::RemoveRetryPolicy { timestamp, name } => Ok(
                 PublicOplogEntry::RemoveRetryPolicy(RemoveRetryPolicyParams { timestamp, name }),
             ),
+            OplogEntry::WebSocketConnected {
+                timestamp,
+                connection_id,
+                method_name,
+            } => Ok(PublicOplogEntry::WebSocketConnected(
+                WebSocketConnectedParams {
+                    timestamp,
+                    connection_id,
+                    method_name,
+                },
+            )),
+            OplogEntry::WebSocketDisconnected {
+                timestamp,
+                connection_id,
+            } => Ok(PublicOplogEntry::WebSocketDisconnected(
+                WebSocketDisconnectedParams {
+                    timestamp,
+                    connection_id,
+                },
+            )),
         }
     }
 }
@@ -1072,6 +1109,16 @@ async fn agent_invocation_to_public(
         } => Ok(PublicAgentInvocation::ProcessOplogEntries(
             ProcessOplogEntriesParameters { idempotency_key },
         )),
+        AgentInvocation::InterceptInvocation {
+            idempotency_key,
+            intercepted,
+            ..
+        } => Ok(PublicAgentInvocation::InterceptInvocation(
+            InterceptInvocationParameters {
+                idempotency_key,
+                method_name: intercepted.method_name,
+            },
+        )),
     }
 }
 
@@ -1121,6 +1168,15 @@ async fn agent_invocation_result_to_public(
                 ProcessOplogEntriesResultParameters { error },
             ))
         }
+        AgentInvocationResult::InterceptInvocation { outcome } => {
+            let error = match outcome.interception {
+                Interception::Reject(reason) => Some(reason),
+                Interception::Proceed | Interception::Replace(_) => None,
+            };
+            Ok(PublicAgentInvocationResult::InterceptInvocation(
+                InterceptInvocationResultParameters { error },
+            ))
+        }
     }
 }
 
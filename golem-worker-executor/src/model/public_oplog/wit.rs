@@ -27,15 +27,15 @@ use golem_common::model::oplog::public_oplog_entry::{
     PublicAgentInvocationResult, PublicAttributeValue, PublicDurableFunctionType, PublicSpanData,
     RemoveRetryPolicyParams, RestartParams, RevertParams, RolledBackRemoteTransactionParams,
     SetRetryPolicyParams, SetSpanAttributeParams, SnapshotParams, StartParams, StartSpanParams,
-    StringAttributeValue, SuccessfulUpdateParams, SuspendParams, WriteRemoteBatchedParameters,
-    WriteRemoteTransactionParameters,
+    StringAttributeValue, SuccessfulUpdateParams, SuspendParams, WebSocketConnectedParams,
+    WebSocketDisconnectedParams, WriteRemoteBatchedParameters, WriteRemoteTransactionParameters,
 };
 use golem_common::model::oplog::{
     AgentInvocationOutputParameters, AgentTerminatedByQuotaError, EphemeralCannotSuspendError,
     EphemeralFuelExhaustedError, EphemeralSleepTooLongError, FallibleResultParameters,
-    JsonSnapshotData, MultipartPartData, MultipartSnapshotData, PublicOplogEntry,
-    PublicSnapshotData, PublicUpdateDescription, RawSnapshotData, ReadOnlyViolationError,
-    SaveSnapshotResultParameters, SnapshotBasedUpdateParameters,
+    InvocationDeadlineExceededError, JsonSnapshotData, MultipartPartData, MultipartSnapshotData,
+    PublicOplogEntry, PublicSnapshotData, PublicUpdateDescription, RawSnapshotData,
+    ReadOnlyViolationError, SaveSnapshotResultParameters, SnapshotBasedUpdateParameters,
 };
 use golem_common::model::quota::ResourceName;
 use golem_common::model::{Empty, Timestamp};
@@ -423,6 +423,22 @@ impl From<PublicOplogEntry> for oplog::PublicOplogEntry {
                     name,
                 })
             }
+            PublicOplogEntry::WebSocketConnected(WebSocketConnectedParams {
+                timestamp,
+                connection_id,
+                method_name,
+            }) => Self::WebsocketConnected(oplog::WebsocketConnectedParameters {
+                timestamp: timestamp.into(),
+                connection_id,
+                method_name,
+            }),
+            PublicOplogEntry::WebSocketDisconnected(WebSocketDisconnectedParams {
+                timestamp,
+                connection_id,
+            }) => Self::WebsocketDisconnected(oplog::WebsocketDisconnectedParameters {
+                timestamp: timestamp.into(),
+                connection_id,
+            }),
         }
     }
 }
@@ -538,6 +554,12 @@ impl From<PublicAgentInvocation> for oplog::AgentInvocation {
                     target_revision: target_revision.into(),
                 })
             }
+            PublicAgentInvocation::InterceptInvocation(params) => {
+                Self::InterceptInvocation(oplog::InterceptInvocationParameters {
+                    idempotency_key: params.idempotency_key.value,
+                    method_name: params.method_name,
+                })
+            }
         }
     }
 }
@@ -596,6 +618,11 @@ impl From<PublicAgentInvocationResult> for oplog::AgentInvocationResult {
                     error: result.error,
                 })
             }
+            PublicAgentInvocationResult::InterceptInvocation(result) => {
+                Self::InterceptInvocation(oplog::FallibleResultParameters {
+                    error: result.error,
+                })
+            }
         }
     }
 }
@@ -741,6 +768,12 @@ impl From<oplog::WorkerError> for golem_common::model::oplog::AgentError {
                     host_function: inner.host_function,
                 })
             }
+            oplog::WorkerError::InvocationDeadlineExceeded(inner) => {
+                Self::InvocationDeadlineExceeded(InvocationDeadlineExceededError {
+                    method: inner.method,
+                    deadline: timestamp_from_datetime(inner.deadline),
+                })
+            }
         }
     }
 }
@@ -1184,6 +1217,15 @@ impl TryFrom<oplog::OplogEntry> for golem_common::model::oplog::OplogEntry {
                 timestamp: timestamp_from_datetime(params.timestamp),
                 name: params.name,
             }),
+            oplog::OplogEntry::WebsocketConnected(params) => Ok(Self::WebSocketConnected {
+                timestamp: timestamp_from_datetime(params.timestamp),
+                connection_id: params.connection_id,
+                method_name: params.method_name,
+            }),
+            oplog::OplogEntry::WebsocketDisconnected(params) => Ok(Self::WebSocketDisconnected {
+                timestamp: timestamp_from_datetime(params.timestamp),
+                connection_id: params.connection_id,
+            }),
         }
     }
 }
@@ -50,12 +50,15 @@ wasmtime::component::bindgen!({
         "wasi:io/poll": wasmtime_wasi::p2::bindings::io::poll,
         "wasi:clocks/wall-clock": wasmtime_wasi::p2::bindings::clocks::wall_clock,
         "golem:rdbms/ignite2.db-connection": super::durable_host::rdbms::ignite::Ignite2DbConnection,
+        "golem:rdbms/ignite2.db-prepared-statement": super::durable_host::rdbms::ignite::DbPreparedStatementEntry,
         "golem:rdbms/ignite2.db-result-stream": super::durable_host::rdbms::ignite::DbResultStreamEntry,
         "golem:rdbms/ignite2.db-transaction": super::durable_host::rdbms::ignite::DbTransactionEntry,
         "golem:rdbms/mysql.db-connection": super::durable_host::rdbms::mysql::MysqlDbConnection,
+        "golem:rdbms/mysql.db-prepared-statement": super::durable_host::rdbms::mysql::DbPreparedStatementEntry,
         "golem:rdbms/mysql.db-result-stream": super::durable_host::rdbms::mysql::DbResultStreamEntry,
         "golem:rdbms/mysql.db-transaction": super::durable_host::rdbms::mysql::DbTransactionEntry,
         "golem:rdbms/postgres.db-connection": super::durable_host::rdbms::postgres::PostgresDbConnection,
+        "golem:rdbms/postgres.db-prepared-statement": super::durable_host::rdbms::postgres::DbPreparedStatementEntry,
         "golem:rdbms/postgres.db-result-stream": super::durable_host::rdbms::postgres::DbResultStreamEntry,
         "golem:rdbms/postgres.db-transaction": super::durable_host::rdbms::postgres::DbTransactionEntry,
         "golem:rdbms/postgres.lazy-db-column-type": super::durable_host::rdbms::postgres::LazyDbColumnTypeEntry,
@@ -0,0 +1,418 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::component::ComponentService;
+use crate::services::oplog::OplogService;
+use crate::services::scheduler::SchedulerService;
+use crate::services::shard::ShardService;
+use crate::services::worker_enumeration::WorkerEnumerationService;
+use crate::storage::keyvalue::{
+    KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
+};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use desert_rust::BinaryCodec;
+use golem_common::SafeDisplay;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId};
+use golem_common::model::component_metadata::{AgentLifecycleAction, AgentLifecyclePolicy};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::{
+    AgentId, AgentMetadata, AgentStatus, OwnedAgentId, ScanCursor, ScheduleId, ScheduledAction,
+    Timestamp,
+};
+use golem_service_base::clients::registry::RegistryService;
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// An action of an agent type's lifecycle policy that was applied to an agent
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct AgentLifecycleAuditEntry {
+    pub timestamp: Timestamp,
+    pub agent_id: AgentId,
+    pub action: AgentLifecycleAction,
+    /// The last oplog index of the agent at the time the action was applied
+    pub last_oplog_index: OplogIndex,
+}
+
+/// Keeps an audit trail of the lifecycle policy actions applied to the agents of an environment,
+/// so automatic deletions can be traced back after the agent itself is gone.
+///
+/// Entries older than the configured retention are trimmed whenever a new entry is recorded.
+#[async_trait]
+pub trait AgentLifecycleAudit: Send + Sync {
+    async fn record(
+        &self,
+        environment_id: EnvironmentId,
+        entry: AgentLifecycleAuditEntry,
+    ) -> anyhow::Result<()>;
+
+    /// Gets the entries recorded between the two timestamps (inclusive), oldest first
+    async fn entries(
+        &self,
+        environment_id: EnvironmentId,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> anyhow::Result<Vec<AgentLifecycleAuditEntry>>;
+}
+
+pub(crate) const AUDIT_KEY: &str = "entries";
+
+#[derive(Clone, Debug)]
+pub struct DefaultAgentLifecycleAudit {
+    key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    retention: Duration,
+}
+
+impl DefaultAgentLifecycleAudit {
+    pub fn new(
+        key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+        retention: Duration,
+    ) -> Self {
+        Self {
+            key_value_storage,
+            retention,
+        }
+    }
+}
+
+#[async_trait]
+impl AgentLifecycleAudit for DefaultAgentLifecycleAudit {
+    async fn record(
+        &self,
+        environment_id: EnvironmentId,
+        entry: AgentLifecycleAuditEntry,
+    ) -> anyhow::Result<()> {
+        let timestamp = entry.timestamp.to_millis();
+        self.key_value_storage
+            .with_entity("agent_lifecycle", "record", "audit_entry")
+            .add_to_sorted_set(
+                KeyValueStorageNamespace::AgentLifecycleAudit { environment_id },
+                AUDIT_KEY,
+                timestamp as f64,
+                &entry,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+
+        let cutoff = timestamp.saturating_sub(self.retention.as_millis() as u64);
+        if cutoff > 0 {
+            self.key_value_storage
+                .with_entity("agent_lifecycle", "trim", "audit_entry")
+                .remove_range_from_sorted_set(
+                    KeyValueStorageNamespace::AgentLifecycleAudit { environment_id },
+                    AUDIT_KEY,
+                    0.0,
+                    (cutoff - 1) as f64,
+                )
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+        Ok(())
+    }
+
+    async fn entries(
+        &self,
+        environment_id: EnvironmentId,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> anyhow::Result<Vec<AgentLifecycleAuditEntry>> {
+        let entries: Vec<(f64, AgentLifecycleAuditEntry)> = self
+            .key_value_storage
+            .with_entity("agent_lifecycle", "entries", "audit_entry")
+            .query_sorted_set(
+                KeyValueStorageNamespace::AgentLifecycleAudit { environment_id },
+                AUDIT_KEY,
+                from.to_millis() as f64,
+                to.to_millis() as f64,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(entries.into_iter().map(|(_, entry)| entry).collect())
+    }
+}
+
+/// The id of the scheduled lifecycle policy `action` of an agent whose last oplog index is
+/// `last_oplog_index`. Deriving it from these makes scheduling the same action again a no-op.
+pub fn lifecycle_action_schedule_id(
+    owned_agent_id: &OwnedAgentId,
+    last_oplog_index: OplogIndex,
+    action: AgentLifecycleAction,
+) -> ScheduleId {
+    let name = format!("lifecycle-{owned_agent_id}-{last_oplog_index}-{action}");
+    ScheduleId {
+        id: Uuid::new_v5(&Uuid::NAMESPACE_OID, name.as_bytes()),
+    }
+}
+
+/// Schedules the actions `policy` enables for an agent in `status`, each due its period after
+/// `since`, the time the agent entered that status.
+#[allow(clippy::too_many_arguments)]
+pub async fn schedule_lifecycle_actions(
+    scheduler_service: &dyn SchedulerService,
+    policy: &AgentLifecyclePolicy,
+    account_id: AccountId,
+    owned_agent_id: &OwnedAgentId,
+    agent_mode: AgentMode,
+    status: AgentStatus,
+    last_oplog_index: OplogIndex,
+    since: DateTime<Utc>,
+) {
+    for (action, period) in policy.actions_for_status(status) {
+        debug!(
+            worker_id = %owned_agent_id,
+            ?status,
+            %action,
+            "Scheduling lifecycle policy action"
+        );
+
+        scheduler_service
+            .schedule_with_id(
+                lifecycle_action_schedule_id(owned_agent_id, last_oplog_index, action),
+                since + period,
+                ScheduledAction::ApplyLifecyclePolicy {
+                    account_id,
+                    owned_agent_id: owned_agent_id.clone(),
+                    agent_mode,
+                    last_oplog_index,
+                    action,
+                },
+            )
+            .await;
+    }
+}
+
+const BACKFILL_PAGE_SIZE: u64 = 100;
+
+/// Schedules the lifecycle policy actions of agents that were already idle, failed or exited
+/// when their policy got deployed, as actions are otherwise only scheduled when an agent
+/// enters one of these statuses.
+///
+/// Only the agents of the shards assigned to this executor are backfilled. The actions are
+/// due relative to the agents' last oplog entry, so the ones already past their period are
+/// applied right away.
+pub struct AgentLifecycleBackfill {
+    registry_service: Arc<dyn RegistryService>,
+    component_service: Arc<dyn ComponentService>,
+    worker_enumeration_service: Arc<dyn WorkerEnumerationService>,
+    oplog_service: Arc<dyn OplogService>,
+    shard_service: Arc<dyn ShardService>,
+    scheduler_service: Arc<dyn SchedulerService>,
+}
+
+impl AgentLifecycleBackfill {
+    pub fn new(
+        registry_service: Arc<dyn RegistryService>,
+        component_service: Arc<dyn ComponentService>,
+        worker_enumeration_service: Arc<dyn WorkerEnumerationService>,
+        oplog_service: Arc<dyn OplogService>,
+        shard_service: Arc<dyn ShardService>,
+        scheduler_service: Arc<dyn SchedulerService>,
+    ) -> Self {
+        Self {
+            registry_service,
+            component_service,
+            worker_enumeration_service,
+            oplog_service,
+            shard_service,
+            scheduler_service,
+        }
+    }
+
+    /// Backfills the agents of the components currently deployed to the environment that have
+    /// a lifecycle policy for any of their agent types
+    pub async fn backfill_environment(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<(), WorkerExecutorError> {
+        let components = self
+            .registry_service
+            .get_deployed_components(environment_id)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to get the deployed components: {}",
+                    err.to_safe_string()
+                ))
+            })?;
+
+        for component in components.iter().filter(|component| {
+            component
+                .metadata
+                .agent_type_provision_configs()
+                .values()
+                .any(|config| config.lifecycle.is_some())
+        }) {
+            let mut cursor = Some(ScanCursor::default());
+            while let Some(current) = cursor {
+                let (next, agents) = self
+                    .worker_enumeration_service
+                    .get(
+                        &environment_id,
+                        &component.id,
+                        None,
+                        current,
+                        BACKFILL_PAGE_SIZE,
+                        false,
+                    )
+                    .await?;
+                for agent in agents {
+                    self.backfill_agent(&agent).await?;
+                }
+                cursor = next;
+            }
+        }
+        Ok(())
+    }
+
+    async fn backfill_agent(&self, agent: &AgentMetadata) -> Result<(), WorkerExecutorError> {
+        if self.shard_service.check_worker(&agent.agent_id).is_err() {
+            return Ok(());
+        }
+        let status = &agent.last_known_status;
+        if !matches!(
+            status.status,
+            AgentStatus::Idle | AgentStatus::Failed | AgentStatus::Exited
+        ) {
+            return Ok(());
+        }
+        let Ok(agent_type) = LegacyParsedAgentId::parse_agent_type_name(&agent.agent_id.agent_id)
+        else {
+            return Ok(());
+        };
+
+        // The policy of the agent's own component revision is the one being enforced
+        let component = self
+            .component_service
+            .get_metadata(agent.agent_id.component_id, Some(status.component_revision))
+            .await?;
+        let Some(policy) = component.metadata.agent_type_lifecycle_policy(&agent_type) else {
+            return Ok(());
+        };
+
+        let owned_agent_id = agent.owned_agent_id();
+        let Some(last_entry) = self
+            .oplog_service
+            .read(&owned_agent_id, agent.agent_mode, status.oplog_idx, 1)
+            .await
+            .into_values()
+            .next()
+        else {
+            warn!(
+                agent_id = %owned_agent_id,
+                "Skipping the lifecycle policy backfill of an agent without its last oplog entry"
+            );
+            return Ok(());
+        };
+        let since =
+            DateTime::<Utc>::from_timestamp_millis(last_entry.timestamp().to_millis() as i64)
+                .unwrap_or_else(Utc::now);
+
+        schedule_lifecycle_actions(
+            self.scheduler_service.as_ref(),
+            policy,
+            agent.created_by,
+            &owned_agent_id,
+            agent.agent_mode,
+            status.status,
+            status.oplog_idx,
+            since,
+        )
+        .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+    use golem_common::model::component::ComponentId;
+    use test_r::test;
+
+    fn entry(millis: u64, agent_name: &str) -> AgentLifecycleAuditEntry {
+        AgentLifecycleAuditEntry {
+            timestamp: Timestamp::from(millis),
+            agent_id: AgentId {
+                component_id: ComponentId::new(),
+                agent_id: agent_name.to_string(),
+            },
+            action: AgentLifecycleAction::DeleteAfterIdle,
+            last_oplog_index: OplogIndex::from_u64(10),
+        }
+    }
+
+    #[test]
+    async fn entries_are_scoped_to_environment_and_time_range() {
+        let audit = DefaultAgentLifecycleAudit::new(
+            Arc::new(InMemoryKeyValueStorage::new()),
+            Duration::from_secs(3600),
+        );
+        let environment_1 = EnvironmentId::new();
+        let environment_2 = EnvironmentId::new();
+
+        let early = entry(1_000, "early");
+        let late = entry(5_000, "late");
+        let other = entry(2_000, "other");
+        audit.record(environment_1, late.clone()).await.unwrap();
+        audit.record(environment_1, early.clone()).await.unwrap();
+        audit.record(environment_2, other.clone()).await.unwrap();
+
+        let all = audit
+            .entries(environment_1, Timestamp::from(0), Timestamp::from(10_000))
+            .await
+            .unwrap();
+        assert_eq!(all, vec![early.clone(), late]);
+
+        let first = audit
+            .entries(environment_1, Timestamp::from(0), Timestamp::from(2_000))
+            .await
+            .unwrap();
+        assert_eq!(first, vec![early]);
+
+        let others = audit
+            .entries(environment_2, Timestamp::from(0), Timestamp::from(10_000))
+            .await
+            .unwrap();
+        assert_eq!(others, vec![other]);
+    }
+
+    #[test]
+    async fn entries_past_retention_are_trimmed_on_record() {
+        let audit = DefaultAgentLifecycleAudit::new(
+            Arc::new(InMemoryKeyValueStorage::new()),
+            Duration::from_secs(10),
+        );
+        let environment_id = EnvironmentId::new();
+
+        let expired = entry(1_000, "expired");
+        let kept = entry(8_000, "kept");
+        let latest = entry(15_000, "latest");
+        audit.record(environment_id, expired).await.unwrap();
+        audit.record(environment_id, kept.clone()).await.unwrap();
+        audit.record(environment_id, latest.clone()).await.unwrap();
+
+        let all = audit
+            .entries(environment_id, Timestamp::from(0), Timestamp::from(20_000))
+            .await
+            .unwrap();
+        assert_eq!(all, vec![kept, latest]);
+    }
+}
@@ -0,0 +1,615 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::golem_config::AgentMetricsConfig;
+use golem_common::model::component::ComponentId;
+use golem_common::model::environment::EnvironmentId;
+use lazy_static::lazy_static;
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{CounterVec, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// Prefix of the exposed names, keeping agent metrics apart from the executor's own metrics
+const METRIC_NAME_PREFIX: &str = "app_";
+/// Label identifying the environment of every agent metric
+const ENVIRONMENT_LABEL_NAME: &str = "environment_id";
+/// Labels added by the executor to every agent metric
+const RESERVED_LABEL_NAMES: [&str; 2] = ["component_id", "agent_type"];
+
+lazy_static! {
+    static ref AGENT_METRICS: AgentMetricsRegistry =
+        AgentMetricsRegistry::new(prometheus::default_registry().clone());
+}
+
+/// The registry of agent metrics exposed on the executor's metrics endpoint
+pub fn agent_metrics() -> &'static AgentMetricsRegistry {
+    &AGENT_METRICS
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentMetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentMetricError {
+    InvalidName(String),
+    InvalidValue(String),
+    Mismatch(String),
+    LabelLimitExceeded(String),
+    CardinalityLimitExceeded(String),
+}
+
+impl Display for AgentMetricError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidName(details)
+            | Self::InvalidValue(details)
+            | Self::Mismatch(details)
+            | Self::LabelLimitExceeded(details)
+            | Self::CardinalityLimitExceeded(details) => write!(f, "{details}"),
+        }
+    }
+}
+
+/// Identifies where a recorded value comes from. Values are aggregated by the component and the
+/// agent type, while metric definitions and series limits belong to the environment.
+pub struct AgentMetricSource<'a> {
+    pub environment_id: &'a EnvironmentId,
+    pub component_id: &'a ComponentId,
+    pub agent_type: &'a str,
+}
+
+/// Application metrics recorded by agents through the `golem:metrics` host interface.
+///
+/// Every environment defines its own metrics: the first time a metric is recorded in an
+/// environment fixes its kind and label names there, without affecting other environments. Each
+/// environment has its own Prometheus registry labelled with the environment id, and all of them
+/// are exposed through a single collector in the executor's registry, which merges the metrics
+/// of the same name. If environments use the same name for different kinds of metrics, only the
+/// first kind is exposed. The label values seen by each environment are tracked to enforce
+/// `max_series_per_environment`; series are never removed, matching the lifetime of the
+/// registered collectors.
+pub struct AgentMetricsRegistry {
+    state: Arc<Mutex<AgentMetricsState>>,
+}
+
+#[derive(Default)]
+struct AgentMetricsState {
+    environments: HashMap<EnvironmentId, EnvironmentAgentMetrics>,
+}
+
+struct EnvironmentAgentMetrics {
+    registry: Registry,
+    metrics: HashMap<String, RegisteredAgentMetric>,
+    series: HashSet<(String, Vec<String>)>,
+}
+
+impl EnvironmentAgentMetrics {
+    fn new(environment_id: &EnvironmentId) -> Self {
+        let labels = HashMap::from([(
+            ENVIRONMENT_LABEL_NAME.to_string(),
+            environment_id.to_string(),
+        )]);
+        Self {
+            registry: Registry::new_custom(None, Some(labels))
+                .expect("environment label must be valid"),
+            metrics: HashMap::new(),
+            series: HashSet::new(),
+        }
+    }
+}
+
+struct RegisteredAgentMetric {
+    label_names: Vec<String>,
+    collector: AgentMetricCollector,
+}
+
+enum AgentMetricCollector {
+    Counter(CounterVec),
+    Gauge(GaugeVec),
+    Histogram(HistogramVec),
+}
+
+impl AgentMetricCollector {
+    fn kind(&self) -> AgentMetricKind {
+        match self {
+            Self::Counter(_) => AgentMetricKind::Counter,
+            Self::Gauge(_) => AgentMetricKind::Gauge,
+            Self::Histogram(_) => AgentMetricKind::Histogram,
+        }
+    }
+
+    fn create(
+        kind: AgentMetricKind,
+        name: &str,
+        label_names: &[String],
+    ) -> Result<Self, prometheus::Error> {
+        let help = format!("Agent metric {name}");
+        let label_names = label_names.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        Ok(match kind {
+            AgentMetricKind::Counter => {
+                Self::Counter(CounterVec::new(Opts::new(name, help), &label_names)?)
+            }
+            AgentMetricKind::Gauge => {
+                Self::Gauge(GaugeVec::new(Opts::new(name, help), &label_names)?)
+            }
+            AgentMetricKind::Histogram => Self::Histogram(HistogramVec::new(
+                HistogramOpts::new(name, help),
+                &label_names,
+            )?),
+        })
+    }
+
+    fn register(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        match self {
+            Self::Counter(vec) => registry.register(Box::new(vec.clone())),
+            Self::Gauge(vec) => registry.register(Box::new(vec.clone())),
+            Self::Histogram(vec) => registry.register(Box::new(vec.clone())),
+        }
+    }
+
+    fn record(&self, label_values: &[String], value: f64) {
+        let label_values = label_values.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        match self {
+            Self::Counter(vec) => vec.with_label_values(&label_values).inc_by(value),
+            Self::Gauge(vec) => vec.with_label_values(&label_values).set(value),
+            Self::Histogram(vec) => vec.with_label_values(&label_values).observe(value),
+        }
+    }
+}
+
+/// Exposes the metrics of every environment in the executor's registry
+struct AgentMetricsExporter {
+    state: Arc<Mutex<AgentMetricsState>>,
+}
+
+impl Collector for AgentMetricsExporter {
+    fn desc(&self) -> Vec<&Desc> {
+        // The agent metrics are not known in advance, so the collector is registered unchecked
+        Vec::new()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let registries = self
+            .state
+            .lock()
+            .unwrap()
+            .environments
+            .values()
+            .map(|environment| environment.registry.clone())
+            .collect::<Vec<_>>();
+
+        let mut families = BTreeMap::<String, MetricFamily>::new();
+        for registry in registries {
+            for mut family in registry.gather() {
+                match families.entry(family.name().to_string()) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(family);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        if entry.get().type_() == family.type_() {
+                            let metrics = std::mem::take(&mut family.metric);
+                            entry.get_mut().metric.extend(metrics);
+                        }
+                    }
+                }
+            }
+        }
+        families.into_values().collect()
+    }
+}
+
+impl AgentMetricsRegistry {
+    pub fn new(registry: Registry) -> Self {
+        let state = Arc::new(Mutex::new(AgentMetricsState::default()));
+        if let Err(err) = registry.register(Box::new(AgentMetricsExporter {
+            state: state.clone(),
+        })) {
+            warn!("Failed to register agent metrics: {err}");
+        }
+        Self { state }
+    }
+
+    pub fn record(
+        &self,
+        config: &AgentMetricsConfig,
+        source: &AgentMetricSource<'_>,
+        kind: AgentMetricKind,
+        name: &str,
+        mut labels: Vec<(String, String)>,
+        value: f64,
+    ) -> Result<(), AgentMetricError> {
+        if !config.enabled {
+            return Ok(());
+        }
+
+        if !is_valid_name(name) {
+            return Err(AgentMetricError::InvalidName(format!(
+                "invalid metric name: {name}"
+            )));
+        }
+        if !value.is_finite() || (kind == AgentMetricKind::Counter && value < 0.0) {
+            return Err(AgentMetricError::InvalidValue(format!(
+                "invalid value for metric {name}: {value}"
+            )));
+        }
+        if labels.len() > config.max_labels {
+            return Err(AgentMetricError::LabelLimitExceeded(format!(
+                "metric {name} has {} labels, at most {} are allowed",
+                labels.len(),
+                config.max_labels
+            )));
+        }
+
+        labels.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (idx, (label_name, label_value)) in labels.iter().enumerate() {
+            if !is_valid_name(label_name)
+                || label_name.starts_with("__")
+                || label_name == ENVIRONMENT_LABEL_NAME
+                || RESERVED_LABEL_NAMES.contains(&label_name.as_str())
+            {
+                return Err(AgentMetricError::InvalidName(format!(
+                    "invalid label name for metric {name}: {label_name}"
+                )));
+            }
+            if idx > 0 && labels[idx - 1].0 == *label_name {
+                return Err(AgentMetricError::InvalidName(format!(
+                    "duplicate label for metric {name}: {label_name}"
+                )));
+            }
+            if label_value.len() > config.max_label_value_length {
+                return Err(AgentMetricError::LabelLimitExceeded(format!(
+                    "value of label {label_name} of metric {name} is longer than {} bytes",
+                    config.max_label_value_length
+                )));
+            }
+        }
+
+        let full_name = format!("{METRIC_NAME_PREFIX}{name}");
+        let (label_names, label_values): (Vec<_>, Vec<_>) = RESERVED_LABEL_NAMES
+            .iter()
+            .map(|s| s.to_string())
+            .zip([
+                source.component_id.to_string(),
+                source.agent_type.to_string(),
+            ])
+            .chain(labels)
+            .unzip();
+
+        let mut state = self.state.lock().unwrap();
+        let environment = state
+            .environments
+            .entry(*source.environment_id)
+            .or_insert_with(|| EnvironmentAgentMetrics::new(source.environment_id));
+
+        if !environment.metrics.contains_key(&full_name) {
+            let collector = AgentMetricCollector::create(kind, &full_name, &label_names)
+                .and_then(|collector| {
+                    collector.register(&environment.registry)?;
+                    Ok(collector)
+                })
+                .map_err(|err| {
+                    AgentMetricError::Mismatch(format!("cannot register metric {name}: {err}"))
+                })?;
+            environment.metrics.insert(
+                full_name.clone(),
+                RegisteredAgentMetric {
+                    label_names: label_names.clone(),
+                    collector,
+                },
+            );
+        }
+
+        let metric = &environment.metrics[&full_name];
+        if metric.collector.kind() != kind {
+            return Err(AgentMetricError::Mismatch(format!(
+                "metric {name} is a {:?}, not a {kind:?}",
+                metric.collector.kind()
+            )));
+        }
+        if metric.label_names != label_names {
+            return Err(AgentMetricError::Mismatch(format!(
+                "metric {name} has labels {:?}",
+                &metric.label_names[RESERVED_LABEL_NAMES.len()..]
+            )));
+        }
+
+        let series_key = (full_name, label_values);
+        if !environment.series.contains(&series_key) {
+            if environment.series.len() >= config.max_series_per_environment {
+                return Err(AgentMetricError::CardinalityLimitExceeded(format!(
+                    "the environment already has {} metric series",
+                    config.max_series_per_environment
+                )));
+            }
+            environment.series.insert(series_key.clone());
+        }
+
+        environment.metrics[&series_key.0]
+            .collector
+            .record(&series_key.1, value);
+        Ok(())
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{Encoder, TextEncoder};
+    use test_r::test;
+
+    test_r::enable!();
+
+    fn config(max_series_per_environment: usize) -> AgentMetricsConfig {
+        AgentMetricsConfig {
+            max_series_per_environment,
+            ..AgentMetricsConfig::default()
+        }
+    }
+
+    fn labels(labels: &[(&str, &str)]) -> Vec<(String, String)> {
+        labels
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn records_values_with_source_labels() {
+        let registry = Registry::new();
+        let metrics = AgentMetricsRegistry::new(registry.clone());
+        let environment_id = EnvironmentId::new();
+        let component_id = ComponentId::new();
+        let source = AgentMetricSource {
+            environment_id: &environment_id,
+            component_id: &component_id,
+            agent_type: "order-agent",
+        };
+
+        for _ in 0..2 {
+            metrics
+                .record(
+                    &config(10),
+                    &source,
+                    AgentMetricKind::Counter,
+                    "orders_total",
+                    labels(&[("region", "eu")]),
+                    1.5,
+                )
+                .unwrap();
+        }
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut buffer)
+            .unwrap();
+        let exposed = String::from_utf8(buffer).unwrap();
+        assert!(exposed.contains(&format!(
+            "app_orders_total{{agent_type=\"order-agent\",component_id=\"{component_id}\",environment_id=\"{environment_id}\",region=\"eu\"}} 3\n"
+        )));
+    }
+
+    #[test]
+    fn rejects_mismatching_and_invalid_metrics() {
+        let metrics = AgentMetricsRegistry::new(Registry::new());
+        let environment_id = EnvironmentId::new();
+        let component_id = ComponentId::new();
+        let source = AgentMetricSource {
+            environment_id: &environment_id,
+            component_id: &component_id,
+            agent_type: "agent",
+        };
+        let config = config(10);
+
+        metrics
+            .record(
+                &config,
+                &source,
+                AgentMetricKind::Gauge,
+                "queue_size",
+                vec![],
+                3.0,
+            )
+            .unwrap();
+
+        let kind_mismatch = metrics.record(
+            &config,
+            &source,
+            AgentMetricKind::Counter,
+            "queue_size",
+            vec![],
+            1.0,
+        );
+        assert!(matches!(kind_mismatch, Err(AgentMetricError::Mismatch(_))));
+
+        let label_mismatch = metrics.record(
+            &config,
+            &source,
+            AgentMetricKind::Gauge,
+            "queue_size",
+            labels(&[("queue", "a")]),
+            1.0,
+        );
+        assert!(matches!(label_mismatch, Err(AgentMetricError::Mismatch(_))));
+
+        let reserved_label = metrics.record(
+            &config,
+            &source,
+            AgentMetricKind::Gauge,
+            "other",
+            labels(&[("agent_type", "a")]),
+            1.0,
+        );
+        assert!(matches!(
+            reserved_label,
+            Err(AgentMetricError::InvalidName(_))
+        ));
+
+        let environment_label = metrics.record(
+            &config,
+            &source,
+            AgentMetricKind::Gauge,
+            "other",
+            labels(&[("environment_id", "a")]),
+            1.0,
+        );
+        assert!(matches!(
+            environment_label,
+            Err(AgentMetricError::InvalidName(_))
+        ));
+
+        let negative_increment = metrics.record(
+            &config,
+            &source,
+            AgentMetricKind::Counter,
+            "requests_total",
+            vec![],
+            -1.0,
+        );
+        assert!(matches!(
+            negative_increment,
+            Err(AgentMetricError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn enforces_series_limit_per_environment() {
+        let metrics = AgentMetricsRegistry::new(Registry::new());
+        let environment_id = EnvironmentId::new();
+        let other_environment_id = EnvironmentId::new();
+        let component_id = ComponentId::new();
+        let source = AgentMetricSource {
+            environment_id: &environment_id,
+            component_id: &component_id,
+            agent_type: "agent",
+        };
+        let other_source = AgentMetricSource {
+            environment_id: &other_environment_id,
+            component_id: &component_id,
+            agent_type: "agent",
+        };
+        let config = config(2);
+
+        let record = |source: &AgentMetricSource<'_>, user: &str| {
+            metrics.record(
+                &config,
+                source,
+                AgentMetricKind::Counter,
+                "logins_total",
+                labels(&[("user", user)]),
+                1.0,
+            )
+        };
+
+        record(&source, "a").unwrap();
+        record(&source, "b").unwrap();
+        assert!(matches!(
+            record(&source, "c"),
+            Err(AgentMetricError::CardinalityLimitExceeded(_))
+        ));
+        // existing series can still be updated
+        record(&source, "a").unwrap();
+        // the limit is per environment
+        record(&other_source, "c").unwrap();
+    }
+
+    #[test]
+    fn metrics_are_defined_per_environment() {
+        let registry = Registry::new();
+        let metrics = AgentMetricsRegistry::new(registry.clone());
+        let environment_id = EnvironmentId::new();
+        let other_environment_id = EnvironmentId::new();
+        let component_id = ComponentId::new();
+        let source = AgentMetricSource {
+            environment_id: &environment_id,
+            component_id: &component_id,
+            agent_type: "agent",
+        };
+        let other_source = AgentMetricSource {
+            environment_id: &other_environment_id,
+            component_id: &component_id,
+            agent_type: "agent",
+        };
+        let config = config(10);
+
+        metrics
+            .record(
+                &config,
+                &source,
+                AgentMetricKind::Counter,
+                "jobs",
+                vec![],
+                1.0,
+            )
+            .unwrap();
+        metrics
+            .record(
+                &config,
+                &other_source,
+                AgentMetricKind::Counter,
+                "jobs",
+                labels(&[("queue", "a")]),
+                2.0,
+            )
+            .unwrap();
+        metrics
+            .record(
+                &config,
+                &other_source,
+                AgentMetricKind::Gauge,
+                "queue_size",
+                vec![],
+                3.0,
+            )
+            .unwrap();
+        metrics
+            .record(
+                &config,
+                &source,
+                AgentMetricKind::Counter,
+                "queue_size",
+                vec![],
+                4.0,
+            )
+            .unwrap();
+
+        let families = registry.gather();
+        let jobs = families
+            .iter()
+            .find(|family| family.name() == "app_jobs")
+            .unwrap();
+        assert_eq!(jobs.metric.len(), 2);
+        let queue_size = families
+            .iter()
+            .find(|family| family.name() == "app_queue_size")
+            .unwrap();
+        assert_eq!(queue_size.metric.len(), 1);
+    }
+}
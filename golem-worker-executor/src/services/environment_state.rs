@@ -23,6 +23,7 @@ use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::model::AgentDeploymentDetails;
 use golem_service_base::model::agent_secret::AgentSecret;
 use golem_service_base::model::environment::EnvironmentState;
+use golem_service_base::model::oplog_export_sink::NamedOplogExportSink;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -47,6 +48,11 @@ pub trait EnvironmentStateService: Send + Sync {
         environment_id: EnvironmentId,
     ) -> Result<Vec<NamedRetryPolicy>, WorkerExecutorError>;
 
+    async fn get_oplog_export_sinks(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedOplogExportSink>, WorkerExecutorError>;
+
     async fn invalidate_environment(&self, _environment_id: EnvironmentId) {}
     async fn invalidate_all(&self) {}
 }
@@ -131,6 +137,14 @@ impl EnvironmentStateService for GrpcEnvironmentStateService {
         Ok(environment_state.retry_policies.clone())
     }
 
+    async fn get_oplog_export_sinks(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedOplogExportSink>, WorkerExecutorError> {
+        let environment_state = self.get_environment_state(environment_id).await?;
+        Ok(environment_state.oplog_export_sinks.clone())
+    }
+
     async fn invalidate_environment(&self, environment_id: EnvironmentId) {
         self.cached_environment_state.remove(&environment_id).await;
     }
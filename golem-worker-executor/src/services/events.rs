@@ -12,13 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use golem_common::model::{AgentId, AgentInvocationOutput, IdempotencyKey};
+use golem_common::model::{AgentId, AgentInvocationOutput, IdempotencyKey, OplogIndex};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use tokio::sync::broadcast::error::RecvError;
 
 pub struct Events {
     sender: tokio::sync::broadcast::Sender<Event>,
     _receiver: tokio::sync::broadcast::Receiver<Event>,
+    agent_writes: tokio::sync::broadcast::Sender<AgentWrite>,
 }
 
 impl Default for Events {
@@ -30,9 +31,11 @@ impl Default for Events {
 impl Events {
     pub fn new(capacity: usize) -> Self {
         let (sender, receiver) = tokio::sync::broadcast::channel(capacity);
+        let (agent_writes, _) = tokio::sync::broadcast::channel(capacity);
         Self {
             sender,
             _receiver: receiver,
+            agent_writes,
         }
     }
 
@@ -45,6 +48,16 @@ impl Events {
             receiver: self.sender.subscribe(),
         }
     }
+
+    /// Agent writes are kept separate from the other events, as they are published for every
+    /// oplog commit and must not make the invocation waiters lag.
+    pub fn publish_agent_write(&self, write: AgentWrite) {
+        let _ = self.agent_writes.send(write);
+    }
+
+    pub fn subscribe_agent_writes(&self) -> tokio::sync::broadcast::Receiver<AgentWrite> {
+        self.agent_writes.subscribe()
+    }
 }
 
 pub struct EventsSubscription {
@@ -83,3 +96,12 @@ pub enum Event {
         result: Result<(), WorkerExecutorError>,
     },
 }
+
+/// The oplog of an agent advanced to `oplog_index`, or the agent got deleted. Subscribers use it
+/// to invalidate what they cached about the agent's state.
+#[derive(Debug, Clone)]
+pub struct AgentWrite {
+    pub agent_id: AgentId,
+    pub oplog_index: OplogIndex,
+    pub deleted: bool,
+}
@@ -0,0 +1,981 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use golem_common::model::OwnedAgentId;
+use golem_common::model::agent::AgentMode;
+use golem_common::model::oplog::OplogIndex;
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_service_base::replayable_stream::ReplayableStream;
+use golem_service_base::storage::blob::{BlobStorage, BlobStorageNamespace};
+use std::collections::HashSet;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+use tokio::task::spawn_blocking;
+use tracing::debug;
+
+/// Stores archives of agent filesystems in the blob storage, each belonging to an automatic
+/// `Snapshot` oplog entry.
+///
+/// Snapshot based recovery skips replaying the oplog up to the snapshot, so without a
+/// checkpoint the files written in the skipped region would be missing from the agent's
+/// filesystem. Read-only initial files are not archived, as they are provisioned from the
+/// component on every instance creation.
+#[async_trait]
+pub trait FilesystemCheckpointService: Send + Sync {
+    /// Archives the agent's filesystem rooted at `root` as the checkpoint of the snapshot at
+    /// `snapshot_index`. The agent's older checkpoints are deleted once the new one was read
+    /// back successfully.
+    async fn save(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+        root: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+    ) -> Result<(), WorkerExecutorError>;
+
+    /// Replaces the agent's filesystem rooted at `root` with the checkpoint of the snapshot at
+    /// `snapshot_index`. Returns `false` if there is no such checkpoint.
+    async fn restore(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+        root: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+    ) -> Result<bool, WorkerExecutorError>;
+
+    /// Streams the compressed archive of the agent's checkpoint of the snapshot at
+    /// `snapshot_index`, for exporting the agent. Returns `None` if there is no such checkpoint.
+    async fn export(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+    ) -> Result<Option<BoxStream<'static, Result<Bytes, WorkerExecutorError>>>, WorkerExecutorError>;
+
+    /// Stores an archive returned by `export` as the checkpoint of the imported agent's snapshot
+    /// at `snapshot_index`, after checking that it can be decoded.
+    async fn import(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+        archive: NamedTempFile,
+    ) -> Result<(), WorkerExecutorError>;
+}
+
+/// Checkpoints are zstd compressed tar archives, keeping the permissions, modification times
+/// and symbolic links of the agent's filesystem. Archives are staged in temporary files on both
+/// upload and download, so the filesystem is never held in memory.
+pub struct DefaultFilesystemCheckpointService {
+    blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+    max_bytes: u64,
+}
+
+impl DefaultFilesystemCheckpointService {
+    const ZSTD_LEVEL: i32 = 3;
+    const CHECKPOINTS_DIR: &'static str = "filesystem_checkpoints";
+
+    pub fn new(blob_storage: Arc<dyn BlobStorage + Send + Sync>, max_bytes: u64) -> Self {
+        Self {
+            blob_storage,
+            max_bytes,
+        }
+    }
+
+    fn namespace(owned_agent_id: &OwnedAgentId, agent_mode: AgentMode) -> BlobStorageNamespace {
+        // Stored next to the agent's oplog payloads so they share their lifecycle
+        BlobStorageNamespace::OplogPayload {
+            environment_id: owned_agent_id.environment_id(),
+            agent_id: owned_agent_id.agent_id(),
+            agent_mode,
+        }
+    }
+
+    fn path(snapshot_index: OplogIndex) -> PathBuf {
+        Path::new(Self::CHECKPOINTS_DIR).join(snapshot_index.to_string())
+    }
+
+    /// Downloads a checkpoint into a temporary file. Returns `None` if there is no such
+    /// checkpoint.
+    async fn download(
+        &self,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<NamedTempFile>, WorkerExecutorError> {
+        let stream = self
+            .blob_storage
+            .get_stream("filesystem_checkpoint", op_label, namespace, path)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to download filesystem checkpoint: {err}"
+                ))
+            })?;
+        let Some(mut stream) = stream else {
+            return Ok(None);
+        };
+
+        let temp_file = NamedTempFile::new().map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to create temporary file for filesystem checkpoint: {err}"
+            ))
+        })?;
+        let file = temp_file.reopen().map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to open temporary file for filesystem checkpoint: {err}"
+            ))
+        })?;
+        let mut file = tokio::fs::File::from_std(file);
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to download filesystem checkpoint: {err}"
+                ))
+            })?;
+            file.write_all(&chunk).await.map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to write temporary file for filesystem checkpoint: {err}"
+                ))
+            })?;
+        }
+        file.flush().await.map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to write temporary file for filesystem checkpoint: {err}"
+            ))
+        })?;
+
+        Ok(Some(temp_file))
+    }
+
+    /// Uploads an archive and reads it back, returning the number of its entries. Nothing can
+    /// fall back to the uploaded checkpoint until it is known to be readable.
+    async fn upload(
+        &self,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        archive: NamedTempFile,
+    ) -> Result<usize, WorkerExecutorError> {
+        let archive = Arc::new(archive);
+        let stream = archive
+            .clone()
+            .map_item(|chunk| chunk.map(|bytes| bytes.to_vec()));
+        self.blob_storage
+            .put_stream(
+                "filesystem_checkpoint",
+                op_label,
+                namespace.clone(),
+                path,
+                &stream.erased(),
+            )
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to upload filesystem checkpoint: {err}"
+                ))
+            })?;
+
+        let uploaded = self
+            .download("verify", namespace, path)
+            .await?
+            .ok_or_else(|| {
+                WorkerExecutorError::runtime(
+                    "Uploaded filesystem checkpoint is missing from the blob storage",
+                )
+            })?;
+        spawn_blocking(move || FilesystemArchive::verify(&uploaded))
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Filesystem checkpoint verification task failed: {err}"
+                ))
+            })?
+    }
+}
+
+#[async_trait]
+impl FilesystemCheckpointService for DefaultFilesystemCheckpointService {
+    async fn save(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+        root: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+    ) -> Result<(), WorkerExecutorError> {
+        let root = root.to_path_buf();
+        let read_only_paths = read_only_paths.clone();
+        let max_bytes = self.max_bytes;
+        let archive =
+            spawn_blocking(move || FilesystemArchive::write(&root, &read_only_paths, max_bytes))
+                .await
+                .map_err(|err| {
+                    WorkerExecutorError::runtime(format!(
+                        "Filesystem checkpoint task failed: {err}"
+                    ))
+                })??;
+        let namespace = Self::namespace(owned_agent_id, agent_mode);
+        let path = Self::path(snapshot_index);
+        let entry_count = self
+            .upload("save", namespace.clone(), &path, archive)
+            .await?;
+        debug!(
+            "Saved filesystem checkpoint of {owned_agent_id} at oplog index {snapshot_index} ({entry_count} entries)"
+        );
+
+        // Only deleted now that the new checkpoint was read back successfully by `upload`
+        let older_checkpoints: Vec<PathBuf> = self
+            .blob_storage
+            .list_dir(
+                "filesystem_checkpoint",
+                "save",
+                namespace.clone(),
+                Path::new(Self::CHECKPOINTS_DIR),
+            )
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to list filesystem checkpoints: {err}"
+                ))
+            })?
+            .into_iter()
+            .filter(|existing| *existing != path)
+            .collect();
+        if !older_checkpoints.is_empty() {
+            self.blob_storage
+                .delete_many(
+                    "filesystem_checkpoint",
+                    "save",
+                    namespace,
+                    &older_checkpoints,
+                )
+                .await
+                .map_err(|err| {
+                    WorkerExecutorError::runtime(format!(
+                        "Failed to delete old filesystem checkpoints: {err}"
+                    ))
+                })?;
+        }
+
+        Ok(())
+    }
+
+    async fn restore(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+        root: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+    ) -> Result<bool, WorkerExecutorError> {
+        let archive = self
+            .download(
+                "restore",
+                Self::namespace(owned_agent_id, agent_mode),
+                &Self::path(snapshot_index),
+            )
+            .await?;
+        let Some(archive) = archive else {
+            return Ok(false);
+        };
+
+        let root = root.to_path_buf();
+        let read_only_paths = read_only_paths.clone();
+        spawn_blocking(move || FilesystemArchive::restore(&archive, &root, &read_only_paths))
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!("Filesystem restore task failed: {err}"))
+            })??;
+        debug!(
+            "Restored filesystem checkpoint of {owned_agent_id} from oplog index {snapshot_index}"
+        );
+
+        Ok(true)
+    }
+
+    async fn export(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+    ) -> Result<Option<BoxStream<'static, Result<Bytes, WorkerExecutorError>>>, WorkerExecutorError>
+    {
+        let stream = self
+            .blob_storage
+            .get_stream(
+                "filesystem_checkpoint",
+                "export",
+                Self::namespace(owned_agent_id, agent_mode),
+                &Self::path(snapshot_index),
+            )
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to download filesystem checkpoint: {err}"
+                ))
+            })?;
+
+        Ok(stream.map(|stream| {
+            stream
+                .map(|chunk| {
+                    chunk.map_err(|err| {
+                        WorkerExecutorError::runtime(format!(
+                            "Failed to download filesystem checkpoint: {err}"
+                        ))
+                    })
+                })
+                .boxed()
+        }))
+    }
+
+    async fn import(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        snapshot_index: OplogIndex,
+        archive: NamedTempFile,
+    ) -> Result<(), WorkerExecutorError> {
+        let entry_count = self
+            .upload(
+                "import",
+                Self::namespace(owned_agent_id, agent_mode),
+                &Self::path(snapshot_index),
+                archive,
+            )
+            .await?;
+        debug!(
+            "Imported filesystem checkpoint of {owned_agent_id} at oplog index {snapshot_index} ({entry_count} entries)"
+        );
+
+        Ok(())
+    }
+}
+
+/// Blocking operations on the checkpoint archives. Paths in the archive are relative to the
+/// agent's filesystem root.
+struct FilesystemArchive;
+
+impl FilesystemArchive {
+    /// Writes the archive of the filesystem rooted at `root` into a temporary file, failing if
+    /// the files add up to more than `max_bytes`
+    fn write(
+        root: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+        max_bytes: u64,
+    ) -> Result<NamedTempFile, WorkerExecutorError> {
+        let temp_file = NamedTempFile::new().map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to create temporary file for filesystem checkpoint: {err}"
+            ))
+        })?;
+        let file = temp_file.reopen().map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to open temporary file for filesystem checkpoint: {err}"
+            ))
+        })?;
+
+        let mut encoder = zstd::Encoder::new(
+            BufWriter::new(file),
+            DefaultFilesystemCheckpointService::ZSTD_LEVEL,
+        )
+        .map_err(write_error)?;
+        // Lets reading the archive back detect a corrupted upload
+        encoder.include_checksum(true).map_err(write_error)?;
+
+        let mut builder = tar::Builder::new(encoder);
+        builder.follow_symlinks(false);
+        builder.mode(tar::HeaderMode::Complete);
+
+        let mut total_bytes = 0;
+        Self::append_dir(
+            root,
+            root,
+            read_only_paths,
+            max_bytes,
+            &mut total_bytes,
+            &mut builder,
+        )?;
+
+        let encoder = builder.into_inner().map_err(write_error)?;
+        let mut writer = encoder.finish().map_err(write_error)?;
+        std::io::Write::flush(&mut writer).map_err(write_error)?;
+
+        Ok(temp_file)
+    }
+
+    fn append_dir<W: std::io::Write>(
+        root: &Path,
+        dir: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+        max_bytes: u64,
+        total_bytes: &mut u64,
+        builder: &mut tar::Builder<W>,
+    ) -> Result<(), WorkerExecutorError> {
+        let mut children = std::fs::read_dir(dir)
+            .map_err(|err| fs_error(dir, root, "Failed to read directory", err))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| fs_error(dir, root, "Failed to read directory", err))?;
+        // Sorting keeps the archive deterministic and lists parents before their children
+        children.sort_by_key(|entry| entry.file_name());
+
+        for child in children {
+            let path = child.path();
+            if read_only_paths.contains(&path) {
+                continue;
+            }
+            let relative = relative_path(root, &path);
+            // Does not follow symbolic links, which are archived as links
+            let file_type = child
+                .file_type()
+                .map_err(|err| fs_error(&path, root, "Failed to get file type", err))?;
+            if file_type.is_dir() {
+                builder
+                    .append_dir(&relative, &path)
+                    .map_err(|err| fs_error(&path, root, "Failed to archive directory", err))?;
+                Self::append_dir(
+                    root,
+                    &path,
+                    read_only_paths,
+                    max_bytes,
+                    total_bytes,
+                    builder,
+                )?;
+            } else if file_type.is_file() || file_type.is_symlink() {
+                if file_type.is_file() {
+                    let size = child
+                        .metadata()
+                        .map_err(|err| fs_error(&path, root, "Failed to get metadata", err))?
+                        .len();
+                    *total_bytes += size;
+                    if *total_bytes > max_bytes {
+                        return Err(WorkerExecutorError::runtime(format!(
+                            "The agent's filesystem exceeds the filesystem checkpoint limit of {max_bytes} bytes"
+                        )));
+                    }
+                }
+                builder
+                    .append_path_with_name(&path, &relative)
+                    .map_err(|err| fs_error(&path, root, "Failed to archive file", err))?;
+            } else {
+                debug!(
+                    "Skipping {} from filesystem checkpoint as it is not a file, directory or symbolic link",
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn open(
+        archive: &NamedTempFile,
+    ) -> Result<tar::Archive<zstd::Decoder<'static, BufReader<std::fs::File>>>, WorkerExecutorError>
+    {
+        let file = archive.reopen().map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to open temporary file for filesystem checkpoint: {err}"
+            ))
+        })?;
+        let decoder = zstd::Decoder::new(file).map_err(archive_error)?;
+        Ok(tar::Archive::new(decoder))
+    }
+
+    /// Reads the whole archive, returning the paths of its entries relative to the root
+    fn entries(archive: &NamedTempFile) -> Result<Vec<PathBuf>, WorkerExecutorError> {
+        let mut archive = Self::open(archive)?;
+        let mut paths = Vec::new();
+        for entry in archive.entries().map_err(archive_error)? {
+            let mut entry = entry.map_err(archive_error)?;
+            paths.push(entry.path().map_err(archive_error)?.into_owned());
+            std::io::copy(&mut entry, &mut std::io::sink()).map_err(archive_error)?;
+        }
+        // Reading up to the end of the compressed stream validates its checksum
+        std::io::copy(&mut archive.into_inner(), &mut std::io::sink()).map_err(archive_error)?;
+        Ok(paths)
+    }
+
+    /// Checks that the archive can be fully decoded, returning the number of its entries
+    fn verify(archive: &NamedTempFile) -> Result<usize, WorkerExecutorError> {
+        Self::entries(archive).map(|paths| paths.len())
+    }
+
+    fn restore(
+        archive: &NamedTempFile,
+        root: &Path,
+        read_only_paths: &HashSet<PathBuf>,
+    ) -> Result<(), WorkerExecutorError> {
+        let archived_paths: HashSet<PathBuf> = Self::entries(archive)?
+            .into_iter()
+            .map(|path| root.join(path))
+            .collect();
+        Self::clear(root, root, &archived_paths, read_only_paths)?;
+
+        let mut archive = Self::open(archive)?;
+        archive.set_preserve_permissions(true);
+        archive.set_preserve_mtime(true);
+        archive.set_overwrite(true);
+        archive.unpack(root).map_err(archive_error)
+    }
+
+    /// Removes everything from `dir` except the read-only initial files and the archived
+    /// directories. Files and links are all recreated from the archive, and unpacking a file
+    /// over an existing link would write through the link.
+    fn clear(
+        root: &Path,
+        dir: &Path,
+        archived_paths: &HashSet<PathBuf>,
+        read_only_paths: &HashSet<PathBuf>,
+    ) -> Result<(), WorkerExecutorError> {
+        let children = std::fs::read_dir(dir)
+            .map_err(|err| fs_error(dir, root, "Failed to read directory", err))?;
+        for child in children {
+            let child =
+                child.map_err(|err| fs_error(dir, root, "Failed to read directory", err))?;
+            let path = child.path();
+            if read_only_paths.contains(&path) {
+                continue;
+            }
+            let file_type = child
+                .file_type()
+                .map_err(|err| fs_error(&path, root, "Failed to get file type", err))?;
+            if file_type.is_dir() {
+                Self::clear(root, &path, archived_paths, read_only_paths)?;
+                // Directories still holding read-only initial files are kept
+                if !archived_paths.contains(&path) && is_empty_dir(&path) {
+                    std::fs::remove_dir(&path)
+                        .map_err(|err| fs_error(&path, root, "Failed to remove directory", err))?;
+                }
+            } else {
+                std::fs::remove_file(&path)
+                    .map_err(|err| fs_error(&path, root, "Failed to remove file", err))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
+fn fs_error(path: &Path, root: &Path, message: &str, err: std::io::Error) -> WorkerExecutorError {
+    WorkerExecutorError::FileSystemError {
+        path: relative_path(root, path),
+        reason: format!("{message}: {err}"),
+    }
+}
+
+fn write_error(err: std::io::Error) -> WorkerExecutorError {
+    WorkerExecutorError::runtime(format!(
+        "Failed to write filesystem checkpoint archive: {err}"
+    ))
+}
+
+fn archive_error(err: std::io::Error) -> WorkerExecutorError {
+    WorkerExecutorError::runtime(format!("Invalid filesystem checkpoint archive: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultFilesystemCheckpointService, FilesystemCheckpointService};
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use futures::StreamExt;
+    use futures::stream::BoxStream;
+    use golem_common::model::agent::AgentMode;
+    use golem_common::model::component::ComponentId;
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::{AgentId, OwnedAgentId};
+    use golem_service_base::replayable_stream::ErasedReplayableStream;
+    use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
+    use golem_service_base::storage::blob::{
+        BlobMetadata, BlobStorage, BlobStorageNamespace, ExistsResult,
+    };
+    use std::collections::HashSet;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::TempDir;
+    use test_r::test;
+
+    fn owned_agent_id() -> OwnedAgentId {
+        OwnedAgentId::new(
+            EnvironmentId::new(),
+            &AgentId {
+                component_id: ComponentId::new(),
+                agent_id: "agent-1".to_string(),
+            },
+        )
+    }
+
+    fn service(
+        blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+    ) -> DefaultFilesystemCheckpointService {
+        DefaultFilesystemCheckpointService::new(blob_storage, 1024 * 1024)
+    }
+
+    async fn save(
+        service: &DefaultFilesystemCheckpointService,
+        owned_agent_id: &OwnedAgentId,
+        index: u64,
+        root: &Path,
+    ) -> bool {
+        service
+            .save(
+                owned_agent_id,
+                AgentMode::Durable,
+                OplogIndex::from_u64(index),
+                root,
+                &HashSet::new(),
+            )
+            .await
+            .is_ok()
+    }
+
+    async fn restore(
+        service: &DefaultFilesystemCheckpointService,
+        owned_agent_id: &OwnedAgentId,
+        index: u64,
+        root: &Path,
+    ) -> bool {
+        service
+            .restore(
+                owned_agent_id,
+                AgentMode::Durable,
+                OplogIndex::from_u64(index),
+                root,
+                &HashSet::new(),
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Blob storage that truncates the uploaded streams while `corrupt_uploads` is set
+    #[derive(Debug)]
+    struct CorruptingBlobStorage {
+        inner: InMemoryBlobStorage,
+        corrupt_uploads: AtomicBool,
+    }
+
+    #[async_trait]
+    impl BlobStorage for CorruptingBlobStorage {
+        async fn get_raw(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+            self.inner
+                .get_raw(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn get_stream(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<Option<BoxStream<'static, Result<Bytes, anyhow::Error>>>, anyhow::Error>
+        {
+            self.inner
+                .get_stream(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn get_metadata(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<Option<BlobMetadata>, anyhow::Error> {
+            self.inner
+                .get_metadata(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn put_raw(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+            data: &[u8],
+        ) -> Result<(), anyhow::Error> {
+            self.inner
+                .put_raw(target_label, op_label, namespace, path, data)
+                .await
+        }
+
+        async fn put_stream(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+            stream: &dyn ErasedReplayableStream<
+                Item = Result<Vec<u8>, anyhow::Error>,
+                Error = anyhow::Error,
+            >,
+        ) -> Result<(), anyhow::Error> {
+            if self.corrupt_uploads.load(Ordering::Relaxed) {
+                let mut data = Vec::new();
+                let mut chunks = stream.make_stream_erased().await?;
+                while let Some(chunk) = chunks.next().await {
+                    data.extend(chunk?);
+                }
+                data.truncate(data.len() / 2);
+                self.inner
+                    .put_raw(target_label, op_label, namespace, path, &data)
+                    .await
+            } else {
+                self.inner
+                    .put_stream(target_label, op_label, namespace, path, stream)
+                    .await
+            }
+        }
+
+        async fn delete(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<(), anyhow::Error> {
+            self.inner
+                .delete(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn create_dir(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<(), anyhow::Error> {
+            self.inner
+                .create_dir(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn list_dir(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<Vec<PathBuf>, anyhow::Error> {
+            self.inner
+                .list_dir(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn delete_dir(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<bool, anyhow::Error> {
+            self.inner
+                .delete_dir(target_label, op_label, namespace, path)
+                .await
+        }
+
+        async fn exists(
+            &self,
+            target_label: &'static str,
+            op_label: &'static str,
+            namespace: BlobStorageNamespace,
+            path: &Path,
+        ) -> Result<ExistsResult, anyhow::Error> {
+            self.inner
+                .exists(target_label, op_label, namespace, path)
+                .await
+        }
+    }
+
+    #[test]
+    async fn restore_replaces_filesystem_with_checkpoint() {
+        let service = service(Arc::new(InMemoryBlobStorage::new()));
+        let owned_agent_id = owned_agent_id();
+        let snapshot_index = OplogIndex::from_u64(10);
+
+        let original = TempDir::new().unwrap();
+        std::fs::create_dir_all(original.path().join("data/nested")).unwrap();
+        std::fs::write(original.path().join("data/nested/a.txt"), b"hello").unwrap();
+        std::fs::write(original.path().join("b.txt"), b"world").unwrap();
+        std::fs::create_dir(original.path().join("empty")).unwrap();
+        service
+            .save(
+                &owned_agent_id,
+                AgentMode::Durable,
+                snapshot_index,
+                original.path(),
+                &HashSet::new(),
+            )
+            .await
+            .unwrap();
+
+        let recovered = TempDir::new().unwrap();
+        std::fs::write(recovered.path().join("b.txt"), b"initial").unwrap();
+        std::fs::write(recovered.path().join("deleted.txt"), b"stale").unwrap();
+        std::fs::write(recovered.path().join("read-only.txt"), b"ifs").unwrap();
+        let read_only_paths = HashSet::from([recovered.path().join("read-only.txt")]);
+
+        let restored = service
+            .restore(
+                &owned_agent_id,
+                AgentMode::Durable,
+                snapshot_index,
+                recovered.path(),
+                &read_only_paths,
+            )
+            .await
+            .unwrap();
+
+        assert!(restored);
+        assert_eq!(
+            std::fs::read(recovered.path().join("data/nested/a.txt")).unwrap(),
+            b"hello"
+        );
+        assert_eq!(
+            std::fs::read(recovered.path().join("b.txt")).unwrap(),
+            b"world"
+        );
+        assert!(recovered.path().join("empty").is_dir());
+        assert!(!recovered.path().join("deleted.txt").exists());
+        assert_eq!(
+            std::fs::read(recovered.path().join("read-only.txt")).unwrap(),
+            b"ifs"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    async fn restore_keeps_permissions_modification_times_and_links() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let service = service(Arc::new(InMemoryBlobStorage::new()));
+        let owned_agent_id = owned_agent_id();
+
+        let original = TempDir::new().unwrap();
+        let script = original.path().join("run.sh");
+        std::fs::write(&script, b"#!/bin/sh").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o750)).unwrap();
+        let modified = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(&script)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        std::os::unix::fs::symlink("run.sh", original.path().join("link")).unwrap();
+        assert!(save(&service, &owned_agent_id, 3, original.path()).await);
+
+        let recovered = TempDir::new().unwrap();
+        // An existing file in place of the link is replaced by the link
+        std::fs::write(recovered.path().join("link"), b"stale").unwrap();
+        assert!(restore(&service, &owned_agent_id, 3, recovered.path()).await);
+
+        let metadata = std::fs::metadata(recovered.path().join("run.sh")).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o750);
+        assert_eq!(metadata.modified().unwrap(), modified);
+        assert_eq!(
+            std::fs::read_link(recovered.path().join("link")).unwrap(),
+            PathBuf::from("run.sh")
+        );
+    }
+
+    #[test]
+    async fn only_latest_checkpoint_is_kept() {
+        let service = service(Arc::new(InMemoryBlobStorage::new()));
+        let owned_agent_id = owned_agent_id();
+        let root = TempDir::new().unwrap();
+        std::fs::write(root.path().join("a.txt"), b"a").unwrap();
+
+        assert!(save(&service, &owned_agent_id, 5, root.path()).await);
+        assert!(save(&service, &owned_agent_id, 8, root.path()).await);
+
+        let target = TempDir::new().unwrap();
+        assert!(!restore(&service, &owned_agent_id, 5, target.path()).await);
+        assert!(restore(&service, &owned_agent_id, 8, target.path()).await);
+    }
+
+    #[test]
+    async fn filesystem_over_the_limit_is_not_checkpointed() {
+        let service =
+            DefaultFilesystemCheckpointService::new(Arc::new(InMemoryBlobStorage::new()), 8);
+        let owned_agent_id = owned_agent_id();
+        let root = TempDir::new().unwrap();
+        std::fs::write(root.path().join("a.txt"), b"1234").unwrap();
+        assert!(save(&service, &owned_agent_id, 5, root.path()).await);
+
+        std::fs::write(root.path().join("b.txt"), b"56789").unwrap();
+        assert!(!save(&service, &owned_agent_id, 8, root.path()).await);
+
+        let target = TempDir::new().unwrap();
+        assert!(restore(&service, &owned_agent_id, 5, target.path()).await);
+        assert!(!restore(&service, &owned_agent_id, 8, target.path()).await);
+    }
+
+    #[test]
+    async fn older_checkpoint_is_kept_if_the_new_one_is_unreadable() {
+        let blob_storage = Arc::new(CorruptingBlobStorage {
+            inner: InMemoryBlobStorage::new(),
+            corrupt_uploads: AtomicBool::new(false),
+        });
+        let service = service(blob_storage.clone());
+        let owned_agent_id = owned_agent_id();
+        let root = TempDir::new().unwrap();
+        std::fs::write(root.path().join("a.txt"), b"first").unwrap();
+        assert!(save(&service, &owned_agent_id, 5, root.path()).await);
+
+        blob_storage.corrupt_uploads.store(true, Ordering::Relaxed);
+        std::fs::write(root.path().join("a.txt"), b"second").unwrap();
+        assert!(!save(&service, &owned_agent_id, 8, root.path()).await);
+
+        let target = TempDir::new().unwrap();
+        assert!(restore(&service, &owned_agent_id, 5, target.path()).await);
+        assert_eq!(
+            std::fs::read(target.path().join("a.txt")).unwrap(),
+            b"first"
+        );
+    }
+}
@@ -45,6 +45,10 @@ pub struct GolemConfig {
     pub scheduler_storage: SchedulerStorageConfig,
     pub indexed_storage: IndexedStorageConfig,
     pub blob_storage: BlobStorageConfig,
+    #[serde(default)]
+    pub storage_migration: StorageMigrationConfig,
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
     pub limits: Limits,
     pub retry: RetryConfig,
     #[serde(with = "humantime_serde")]
@@ -58,6 +62,8 @@ pub struct GolemConfig {
     pub agent_status_flush: AgentStatusFlushConfig,
     #[serde(default)]
     pub agent_status_checkpoint: AgentStatusCheckpointConfig,
+    #[serde(default)]
+    pub agent_metrics: AgentMetricsConfig,
     pub scheduler: SchedulerConfig,
     pub public_worker_api: WorkerServiceGrpcConfig,
     pub memory: MemoryConfig,
@@ -123,6 +129,14 @@ impl SafeDisplay for GolemConfig {
             "{}",
             self.blob_storage.to_safe_string_indented()
         );
+        let _ = writeln!(&mut result, "storage migration:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.storage_migration.to_safe_string_indented()
+        );
+        let _ = writeln!(&mut result, "encryption:");
+        let _ = writeln!(&mut result, "{}", self.encryption.to_safe_string_indented());
         let _ = writeln!(&mut result, "limits:");
         let _ = writeln!(&mut result, "{}", self.limits.to_safe_string_indented());
         let _ = writeln!(&mut result, "retry:");
@@ -166,6 +180,12 @@ impl SafeDisplay for GolemConfig {
             "{}",
             self.agent_status_checkpoint.to_safe_string_indented()
         );
+        let _ = writeln!(&mut result, "agent_metrics:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.agent_metrics.to_safe_string_indented()
+        );
         let _ = writeln!(&mut result, "scheduler:");
         let _ = writeln!(&mut result, "{}", self.scheduler.to_safe_string_indented());
         let _ = writeln!(&mut result, "public worker api:");
@@ -278,6 +298,8 @@ impl Default for GolemConfig {
             scheduler_storage: SchedulerStorageConfig::default(),
             indexed_storage: IndexedStorageConfig::default(),
             blob_storage: BlobStorageConfig::default(),
+            storage_migration: StorageMigrationConfig::default(),
+            encryption: EncryptionConfig::default(),
             limits: Limits::default(),
             retry: RetryConfig::max_attempts_3(),
             max_in_function_retry_delay: Duration::from_secs(20),
@@ -289,6 +311,7 @@ impl Default for GolemConfig {
             active_workers: ActiveWorkersConfig::default(),
             agent_status_flush: AgentStatusFlushConfig::default(),
             agent_status_checkpoint: AgentStatusCheckpointConfig::default(),
+            agent_metrics: AgentMetricsConfig::default(),
             public_worker_api: WorkerServiceGrpcConfig::default(),
             memory: MemoryConfig::default(),
             filesystem_storage: FilesystemStorageConfig::default(),
@@ -610,6 +633,54 @@ impl Default for AgentStatusCheckpointConfig {
     }
 }
 
+/// Limits for the application metrics agents record through the `golem:metrics` host interface.
+///
+/// Metrics are exposed on the executor's `/metrics` endpoint with an `app_` name prefix and
+/// `component_id` / `agent_type` labels. Every distinct combination of label values is a separate
+/// series; once an environment reaches `max_series_per_environment`, values for new label
+/// combinations are rejected while already existing series keep being updated.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentMetricsConfig {
+    /// Whether recorded values are exposed. When disabled, recording is accepted but ignored.
+    pub enabled: bool,
+    /// Maximum number of labels an agent can attach to a single value.
+    pub max_labels: usize,
+    /// Maximum length of a single label value, in bytes.
+    pub max_label_value_length: usize,
+    /// Maximum number of distinct series the agents of a single environment can create.
+    pub max_series_per_environment: usize,
+}
+
+impl SafeDisplay for AgentMetricsConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "max labels: {}", self.max_labels);
+        let _ = writeln!(
+            &mut result,
+            "max label value length: {}",
+            self.max_label_value_length
+        );
+        let _ = writeln!(
+            &mut result,
+            "max series per environment: {}",
+            self.max_series_per_environment
+        );
+        result
+    }
+}
+
+impl Default for AgentMetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_labels: 8,
+            max_label_value_length: 128,
+            max_series_per_environment: 1000,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SchedulerConfig {
     #[serde(with = "humantime_serde")]
@@ -617,6 +688,9 @@ pub struct SchedulerConfig {
     pub claim_batch_size: u32,
     #[serde(with = "humantime_serde")]
     pub lease_ttl: Duration,
+    /// How long the audit trail of applied agent lifecycle policy actions is kept
+    #[serde(with = "humantime_serde")]
+    pub lifecycle_audit_retention: Duration,
     pub max_batches_per_tick: u32,
 }
 
@@ -626,6 +700,11 @@ impl SafeDisplay for SchedulerConfig {
         let _ = writeln!(&mut result, "refresh interval: {:?}", self.refresh_interval);
         let _ = writeln!(&mut result, "claim batch size: {}", self.claim_batch_size);
         let _ = writeln!(&mut result, "lease ttl: {:?}", self.lease_ttl);
+        let _ = writeln!(
+            &mut result,
+            "lifecycle audit retention: {:?}",
+            self.lifecycle_audit_retention
+        );
         let _ = writeln!(
             &mut result,
             "max batches per tick: {}",
@@ -663,6 +742,13 @@ pub struct OplogConfig {
     /// connection resets). Defaults to 3 attempts, 100 ms–1 s exponential backoff.
     #[serde(default = "default_oplog_indexed_storage_retry")]
     pub indexed_storage_retry: RetryConfig,
+    /// Controls archiving the oplog history that automatic snapshots made unnecessary for recovery.
+    #[serde(default)]
+    pub compaction: OplogCompactionConfig,
+    /// Built-in sinks receiving the committed oplog entries of the agents of an environment,
+    /// without an oplog processor plugin.
+    #[serde(default)]
+    pub export: OplogExportConfig,
 }
 
 impl SafeDisplay for OplogConfig {
@@ -723,6 +809,10 @@ impl SafeDisplay for OplogConfig {
             "indexed storage retry: {:?}",
             self.indexed_storage_retry
         );
+        let _ = writeln!(&mut result, "compaction:");
+        let _ = writeln!(&mut result, "{}", self.compaction.to_safe_string_indented());
+        let _ = writeln!(&mut result, "export:");
+        let _ = writeln!(&mut result, "{}", self.export.to_safe_string_indented());
         result
     }
 }
@@ -731,6 +821,74 @@ fn default_oplog_indexed_storage_retry() -> RetryConfig {
     RetryConfig::max_attempts_3()
 }
 
+/// Oplog compaction moves the entries preceding an automatic snapshot from the primary oplog to
+/// the archive layer once the snapshot has been committed, so durable agents with a snapshot
+/// policy no longer grow their primary oplog without bound.
+///
+/// Recovery starts from the latest snapshot via `load-snapshot`. The archived entries are not
+/// deleted, so a snapshot that fails to load still falls back to a full replay, and the public
+/// oplog API, forking and exporting keep seeing the whole history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompactionConfig {
+    pub enabled: bool,
+    /// Number of entries before each snapshot kept in the primary oplog, so the most recent
+    /// history is still read from there
+    pub retained_entries: u64,
+}
+
+impl SafeDisplay for OplogCompactionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "retained entries: {}", self.retained_entries);
+        result
+    }
+}
+
+impl Default for OplogCompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retained_entries: 1024,
+        }
+    }
+}
+
+/// Oplog export sinks are configured per environment through the registry API and flushed
+/// together with the oplog processor plugins (see `plugin_max_commit_count` and
+/// `plugin_max_elapsed_time`). Entries are exported as JSON Lines of public oplog entries with
+/// at-least-once delivery: the position of each agent in each sink is checkpointed in the
+/// key-value storage once the sink accepted a batch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogExportConfig {
+    /// Maximum number of oplog entries sent to a sink in a single batch
+    pub max_batch_size: usize,
+    /// Root of the files written by JSON Lines file sinks, one directory per environment and sink
+    pub json_lines_directory: PathBuf,
+}
+
+impl SafeDisplay for OplogExportConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "max batch size: {}", self.max_batch_size);
+        let _ = writeln!(
+            &mut result,
+            "json lines directory: {:?}",
+            self.json_lines_directory
+        );
+        result
+    }
+}
+
+impl Default for OplogExportConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1000,
+            json_lines_directory: PathBuf::from("data/oplog-export"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "config")]
 pub enum KeyValueStorageConfig {
@@ -1068,6 +1226,216 @@ fn default_indexed_storage_postgres_drop_prefix_delete_batch_size() -> u64 {
     1024
 }
 
+/// Online migration of the executor's state from the configured storage backends to new ones.
+///
+/// While enabled, the top-level `key_value_storage`, `indexed_storage` and `scheduler_storage`
+/// are the sources of the migration, and every storage that has a target configured here is
+/// written to both backends. The executor copies the existing data of its assigned shards in the
+/// background and cuts each shard over to the target once the copy has been verified. When all
+/// executors have finished, the targets can replace the top-level configuration and the migration
+/// can be disabled again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum StorageMigrationConfig {
+    Enabled(StorageMigrationEnabledConfig),
+    Disabled(StorageMigrationDisabledConfig),
+}
+
+impl Default for StorageMigrationConfig {
+    fn default() -> Self {
+        Self::Disabled(StorageMigrationDisabledConfig {})
+    }
+}
+
+impl SafeDisplay for StorageMigrationConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            StorageMigrationConfig::Enabled(enabled) => {
+                let _ = writeln!(&mut result, "enabled:");
+                let _ = writeln!(&mut result, "{}", enabled.to_safe_string_indented());
+            }
+            StorageMigrationConfig::Disabled(_) => {
+                let _ = writeln!(&mut result, "disabled");
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StorageMigrationEnabledConfig {
+    /// Target of the key-value storage migration. The namespaces shared by the agents of an
+    /// environment, and the namespaces of the agents of a shard, are fully copied before the
+    /// shard is cut over.
+    #[serde(default)]
+    pub key_value_storage: Option<KeyValueStorageConfig>,
+    /// Target of the indexed storage migration, copying the oplogs and the compressed oplog
+    /// archives stored in the indexed storage.
+    #[serde(default)]
+    pub indexed_storage: Option<IndexedStorageConfig>,
+    /// Target of the scheduler storage migration
+    #[serde(default)]
+    pub scheduler_storage: Option<SchedulerStorageConfig>,
+    /// Maximum number of keys scanned, or entries copied, in a single storage operation
+    pub batch_size: u64,
+    /// How often the executor checks for newly assigned shards that are not cut over yet
+    #[serde(with = "humantime_serde")]
+    pub check_interval: Duration,
+}
+
+impl SafeDisplay for StorageMigrationEnabledConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        if let Some(key_value_storage) = &self.key_value_storage {
+            let _ = writeln!(&mut result, "key-value storage:");
+            let _ = writeln!(
+                &mut result,
+                "{}",
+                key_value_storage.to_safe_string_indented()
+            );
+        }
+        if let Some(indexed_storage) = &self.indexed_storage {
+            let _ = writeln!(&mut result, "indexed storage:");
+            let _ = writeln!(&mut result, "{}", indexed_storage.to_safe_string_indented());
+        }
+        if let Some(scheduler_storage) = &self.scheduler_storage {
+            let _ = writeln!(&mut result, "scheduler storage:");
+            let _ = writeln!(
+                &mut result,
+                "{}",
+                scheduler_storage.to_safe_string_indented()
+            );
+        }
+        let _ = writeln!(&mut result, "batch size: {}", self.batch_size);
+        let _ = writeln!(&mut result, "check interval: {:?}", self.check_interval);
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StorageMigrationDisabledConfig {}
+
+/// Envelope encryption of the agents' data at rest.
+///
+/// Oplog entries, oplog payloads and the user-defined key-value data are encrypted with data
+/// keys generated per agent (oplogs) and per environment (key-value data). The data keys are
+/// stored wrapped by a master key loaded from the configured key provider. Existing plaintext
+/// data remains readable after enabling encryption until `reject_plaintext` is set; new writes
+/// are encrypted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum EncryptionConfig {
+    Enabled(EncryptionEnabledConfig),
+    Disabled(EncryptionDisabledConfig),
+}
+
+impl Default for EncryptionConfig {
+    fn default() -> Self {
+        Self::Disabled(EncryptionDisabledConfig {})
+    }
+}
+
+impl SafeDisplay for EncryptionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            EncryptionConfig::Enabled(enabled) => {
+                let _ = writeln!(&mut result, "enabled:");
+                let _ = writeln!(&mut result, "{}", enabled.to_safe_string_indented());
+            }
+            EncryptionConfig::Disabled(_) => {
+                let _ = writeln!(&mut result, "disabled");
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptionEnabledConfig {
+    pub master_key_provider: MasterKeyProviderConfig,
+    /// ID of the master key wrapping newly created data keys. Data keys wrapped by other master
+    /// keys of the provider are re-wrapped by this one when they are first loaded.
+    pub current_master_key_id: String,
+    /// Age after which a new version of a data key is created for new writes. Older versions
+    /// are kept to decrypt the data written with them.
+    #[serde(with = "humantime_serde")]
+    pub data_key_rotation_period: Duration,
+    /// Maximum number of scopes whose unwrapped data keys are kept in memory
+    pub data_key_cache_capacity: usize,
+    /// Rejects reading values that are not encrypted. Enable once all the data written before
+    /// encryption was enabled has been migrated.
+    #[serde(default)]
+    pub reject_plaintext: bool,
+}
+
+impl SafeDisplay for EncryptionEnabledConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "master key provider:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.master_key_provider.to_safe_string_indented()
+        );
+        let _ = writeln!(
+            &mut result,
+            "current master key id: {}",
+            self.current_master_key_id
+        );
+        let _ = writeln!(
+            &mut result,
+            "data key rotation period: {:?}",
+            self.data_key_rotation_period
+        );
+        let _ = writeln!(
+            &mut result,
+            "data key cache capacity: {}",
+            self.data_key_cache_capacity
+        );
+        let _ = writeln!(&mut result, "reject plaintext: {}", self.reject_plaintext);
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptionDisabledConfig {}
+
+/// Source of the master keys. Both providers read a list of `<key-id>=<base64 key>` entries
+/// separated by newlines or commas, where each key is 32 bytes long.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum MasterKeyProviderConfig {
+    File(FileMasterKeyProviderConfig),
+    Env(EnvMasterKeyProviderConfig),
+}
+
+impl SafeDisplay for MasterKeyProviderConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            MasterKeyProviderConfig::File(file) => {
+                let _ = writeln!(&mut result, "file: {}", file.path.display());
+            }
+            MasterKeyProviderConfig::Env(env) => {
+                let _ = writeln!(&mut result, "environment variable: {}", env.variable);
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FileMasterKeyProviderConfig {
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnvMasterKeyProviderConfig {
+    pub variable: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MemoryConfig {
     pub system_memory_override: Option<u64>,
@@ -1620,6 +1988,8 @@ impl Default for OplogConfig {
             plugin_max_elapsed_time: Duration::from_secs(5),
             oplog_rate_limit_enabled: false,
             indexed_storage_retry: default_oplog_indexed_storage_retry(),
+            compaction: OplogCompactionConfig::default(),
+            export: OplogExportConfig::default(),
         }
     }
 }
@@ -1648,6 +2018,7 @@ impl Default for SchedulerConfig {
             refresh_interval: Duration::from_secs(2),
             claim_batch_size: 100,
             lease_ttl: Duration::from_secs(30),
+            lifecycle_audit_retention: Duration::from_secs(90 * 24 * 60 * 60),
             max_batches_per_tick: 10,
         }
     }
@@ -1754,6 +2125,20 @@ pub struct FilesystemStorageConfig {
     /// Directories are cleaned up when the worker is dropped, just like temp
     /// dirs. When `None` (the default), random temp directories are used.
     pub deterministic_root_dir: Option<PathBuf>,
+    /// When enabled, every automatic snapshot also archives the agent's filesystem to the
+    /// blob storage, and snapshot based recovery restores it instead of replaying the whole
+    /// oplog. If the checkpoint belonging to the latest snapshot is missing, the agent falls
+    /// back to a full replay.
+    #[serde(default)]
+    pub checkpoint_on_snapshot: bool,
+    /// Upper limit of the total size of the files archived in a single filesystem checkpoint.
+    /// Agents with larger filesystems are not checkpointed and recover by a full replay.
+    #[serde(default = "default_checkpoint_max_bytes")]
+    pub checkpoint_max_bytes: u64,
+}
+
+fn default_checkpoint_max_bytes() -> u64 {
+    1024 * 1024 * 1024 // 1 GB
 }
 
 impl FilesystemStorageConfig {
@@ -1779,6 +2164,16 @@ impl SafeDisplay for FilesystemStorageConfig {
         if let Some(root) = &self.deterministic_root_dir {
             let _ = writeln!(&mut result, "deterministic root dir: {}", root.display());
         }
+        let _ = writeln!(
+            &mut result,
+            "checkpoint on snapshot: {}",
+            self.checkpoint_on_snapshot
+        );
+        let _ = writeln!(
+            &mut result,
+            "checkpoint max bytes: {}",
+            self.checkpoint_max_bytes
+        );
         result
     }
 }
@@ -1789,6 +2184,8 @@ impl Default for FilesystemStorageConfig {
             total_worker_filesystem_storage_bytes: None,
             acquire_retry_delay: Duration::from_millis(500),
             deterministic_root_dir: None,
+            checkpoint_on_snapshot: false,
+            checkpoint_max_bytes: default_checkpoint_max_bytes(),
         }
     }
 }
@@ -0,0 +1,264 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::component::ComponentService;
+use crate::services::oplog::plugin::generate_local_agent_id;
+use crate::services::shard::ShardService;
+use crate::services::worker_activator::WorkerActivator;
+use crate::workerctx::WorkerCtx;
+use anyhow::anyhow;
+use async_lock::{RwLock, RwLockUpgradableReadGuard};
+use async_trait::async_trait;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::{Principal, UntypedDataValue};
+use golem_common::model::component::{ComponentRevision, InstalledPlugin};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::environment_plugin_grant::EnvironmentPluginGrantId;
+use golem_common::model::invocation_context::InvocationContextStack;
+use golem_common::model::plugin_registration::PluginRegistrationId;
+use golem_common::model::{
+    AgentInvocation, AgentInvocationResult, IdempotencyKey, InterceptedInvocation,
+    InterceptionOutcome, OwnedAgentId, ShardId,
+};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+use uuid::{Uuid, uuid};
+
+#[async_trait]
+pub trait InvocationInterceptorPlugin: Send + Sync {
+    /// Passes an agent method invocation to the given invocation interceptor plugin.
+    ///
+    /// `output` is `None` when intercepting the start of the invocation and contains the
+    /// method's output when intercepting its end. `idempotency_key` is the key of the intercepted
+    /// invocation, used to make retried interceptions return the same outcome.
+    async fn intercept(
+        &self,
+        environment_id: EnvironmentId,
+        account_id: AccountId,
+        plugin: &InstalledPlugin,
+        idempotency_key: &IdempotencyKey,
+        intercepted: InterceptedInvocation,
+        output: Option<UntypedDataValue>,
+    ) -> Result<InterceptionOutcome, WorkerExecutorError>;
+
+    async fn on_shard_assignment_changed(&self) -> Result<(), WorkerExecutorError>;
+}
+
+/// An implementation of the `InvocationInterceptorPlugin` trait that runs a single instance of
+/// each used plugin on each worker executor node. Interceptors are always invoked locally, as
+/// they are on the hot path of every intercepted invocation.
+pub struct PerExecutorInvocationInterceptorPlugin<Ctx: WorkerCtx> {
+    workers: Arc<RwLock<HashMap<WorkerKey, RunningInterceptor>>>,
+    component_service: Arc<dyn ComponentService>,
+    shard_service: Arc<dyn ShardService>,
+    worker_activator: Arc<dyn WorkerActivator<Ctx>>,
+}
+
+type WorkerKey = (EnvironmentId, PluginRegistrationId);
+
+#[derive(Debug, Clone)]
+struct RunningInterceptor {
+    pub owned_agent_id: OwnedAgentId,
+    pub component_revision: ComponentRevision,
+}
+
+impl<Ctx: WorkerCtx> PerExecutorInvocationInterceptorPlugin<Ctx> {
+    pub fn new(
+        component_service: Arc<dyn ComponentService>,
+        shard_service: Arc<dyn ShardService>,
+        worker_activator: Arc<dyn WorkerActivator<Ctx>>,
+    ) -> Self {
+        Self {
+            workers: Arc::new(RwLock::new(HashMap::new())),
+            component_service,
+            shard_service,
+            worker_activator,
+        }
+    }
+
+    async fn resolve_interceptor_worker(
+        &self,
+        environment_id: EnvironmentId,
+        plugin: &InstalledPlugin,
+    ) -> Result<RunningInterceptor, WorkerExecutorError> {
+        let workers = self.workers.upgradable_read().await;
+        let key = (environment_id, plugin.plugin_registration_id);
+        match workers.get(&key) {
+            Some(running) => Ok(running.clone()),
+            None => {
+                let mut workers = RwLockUpgradableReadGuard::upgrade(workers).await;
+                match workers.get(&key) {
+                    Some(running) => Ok(running.clone()),
+                    None => {
+                        let plugin_component_id =
+                            plugin.invocation_interceptor_component_id.ok_or(anyhow!(
+                                "missing invocation interceptor plugin component id"
+                            ))?;
+                        let plugin_component_revision = plugin
+                            .invocation_interceptor_component_revision
+                            .ok_or(anyhow!(
+                                "missing invocation interceptor plugin component revision"
+                            ))?;
+
+                        let current_assignment = self.shard_service.current_assignment()?;
+                        let agent_id = generate_local_agent_id(
+                            plugin_component_id,
+                            &current_assignment.shard_ids,
+                            current_assignment.number_of_shards,
+                        );
+                        let plugin_component = self
+                            .component_service
+                            .get_metadata(plugin_component_id, Some(plugin_component_revision))
+                            .await?;
+                        let running = RunningInterceptor {
+                            owned_agent_id: OwnedAgentId {
+                                environment_id: plugin_component.environment_id,
+                                agent_id,
+                            },
+                            component_revision: plugin_component_revision,
+                        };
+                        workers.insert(key, running.clone());
+                        Ok(running)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<Ctx: WorkerCtx> InvocationInterceptorPlugin for PerExecutorInvocationInterceptorPlugin<Ctx> {
+    async fn intercept(
+        &self,
+        environment_id: EnvironmentId,
+        account_id: AccountId,
+        plugin: &InstalledPlugin,
+        idempotency_key: &IdempotencyKey,
+        intercepted: InterceptedInvocation,
+        output: Option<UntypedDataValue>,
+    ) -> Result<InterceptionOutcome, WorkerExecutorError> {
+        let running = self
+            .resolve_interceptor_worker(environment_id, plugin)
+            .await?;
+
+        let worker = self
+            .worker_activator
+            .get_or_create_running(
+                &running.owned_agent_id,
+                None,
+                Vec::new(),
+                Some(running.component_revision),
+                None,
+                &InvocationContextStack::fresh(),
+                Principal::anonymous(),
+            )
+            .await?;
+
+        let result = worker
+            .invoke_and_await(AgentInvocation::InterceptInvocation {
+                idempotency_key: interceptor_idempotency_key(
+                    idempotency_key,
+                    &plugin.environment_plugin_grant_id,
+                    output.is_some(),
+                ),
+                account_id,
+                config: plugin
+                    .parameters
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+                intercepted,
+                output,
+            })
+            .await?;
+
+        match result.result {
+            AgentInvocationResult::InterceptInvocation { outcome } => Ok(outcome),
+            other => Err(WorkerExecutorError::unknown(format!(
+                "Unexpected result from invocation interceptor plugin {}: {other:?}",
+                plugin.plugin_name
+            ))),
+        }
+    }
+
+    async fn on_shard_assignment_changed(&self) -> Result<(), WorkerExecutorError> {
+        let new_assignment = self.shard_service.current_assignment()?;
+
+        let mut workers = self.workers.write().await;
+        let keys = workers.keys().cloned().collect::<Vec<_>>();
+        for key in keys {
+            if let Entry::Occupied(entry) = workers.entry(key) {
+                let shard_id = ShardId::from_agent_id(
+                    &entry.get().owned_agent_id.agent_id,
+                    new_assignment.number_of_shards,
+                );
+                if !new_assignment.shard_ids.contains(&shard_id) {
+                    // Pending interceptions are left to finish on the old worker; new ones will
+                    // use a freshly generated local one.
+                    entry.remove();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<Ctx: WorkerCtx> Clone for PerExecutorInvocationInterceptorPlugin<Ctx> {
+    fn clone(&self) -> Self {
+        Self {
+            workers: self.workers.clone(),
+            component_service: self.component_service.clone(),
+            shard_service: self.shard_service.clone(),
+            worker_activator: self.worker_activator.clone(),
+        }
+    }
+}
+
+const INTERCEPTOR_NS: Uuid = uuid!("3C9B6E2A-5D7F-4A1E-8B0C-7F2E9D4A6B13");
+
+fn interceptor_idempotency_key(
+    intercepted_idempotency_key: &IdempotencyKey,
+    plugin_installation_id: &EnvironmentPluginGrantId,
+    after_invocation: bool,
+) -> IdempotencyKey {
+    let mut buf = Vec::with_capacity(128);
+    buf.extend_from_slice(b"interceptor-v1\0");
+    let key = intercepted_idempotency_key.value.as_bytes();
+    buf.extend_from_slice(&(key.len() as u32).to_be_bytes());
+    buf.extend_from_slice(key);
+    buf.extend_from_slice(plugin_installation_id.0.as_bytes());
+    buf.push(after_invocation as u8);
+    IdempotencyKey::from_uuid(Uuid::new_v5(&INTERCEPTOR_NS, &buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn interceptor_idempotency_key_depends_on_stage_and_plugin() {
+        let key = IdempotencyKey::fresh();
+        let grant_1 = EnvironmentPluginGrantId::new();
+        let grant_2 = EnvironmentPluginGrantId::new();
+
+        let before = interceptor_idempotency_key(&key, &grant_1, false);
+        assert_eq!(before, interceptor_idempotency_key(&key, &grant_1, false));
+        assert_ne!(before, interceptor_idempotency_key(&key, &grant_1, true));
+        assert_ne!(before, interceptor_idempotency_key(&key, &grant_2, false));
+    }
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub mod active_workers;
+pub mod agent_lifecycle;
+pub mod agent_metrics;
 pub mod agent_types;
 pub mod agent_webhooks;
 pub mod blob_store;
@@ -21,7 +23,9 @@ pub mod direct_invocation_auth;
 pub mod environment_state;
 pub mod events;
 pub mod file_loader;
+pub mod filesystem_checkpoint;
 pub mod golem_config;
+pub mod invocation_interceptor;
 pub mod key_value;
 pub mod oplog;
 pub mod promise;
@@ -34,6 +38,8 @@ pub mod scheduler;
 pub mod shard;
 pub mod shard_manager;
 pub mod shutdown;
+pub mod storage_migration;
+pub mod topics;
 pub mod worker;
 pub mod worker_activator;
 pub mod worker_enumeration;
@@ -134,6 +140,10 @@ pub trait HasKeyValueService {
     fn key_value_service(&self) -> Arc<dyn key_value::KeyValueService>;
 }
 
+pub trait HasTopicService {
+    fn topic_service(&self) -> Arc<dyn topics::TopicService>;
+}
+
 pub trait HasBlobStoreService {
     fn blob_store_service(&self) -> Arc<dyn blob_store::BlobStoreService>;
 }
@@ -178,10 +188,22 @@ pub trait HasFileLoader {
     fn file_loader(&self) -> Arc<FileLoader>;
 }
 
+pub trait HasFilesystemCheckpointService {
+    fn filesystem_checkpoint_service(
+        &self,
+    ) -> Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>;
+}
+
 pub trait HasOplogProcessorPlugin {
     fn oplog_processor_plugin(&self) -> Arc<dyn oplog::plugin::OplogProcessorPlugin>;
 }
 
+pub trait HasInvocationInterceptorPlugin {
+    fn invocation_interceptor_plugin(
+        &self,
+    ) -> Arc<dyn invocation_interceptor::InvocationInterceptorPlugin>;
+}
+
 pub trait HasRdbmsService {
     fn rdbms_service(&self) -> Arc<dyn rdbms::RdbmsService>;
 }
@@ -224,6 +246,7 @@ pub trait HasAll<Ctx: WorkerCtx>:
     + HasPromiseService
     + HasWasmtimeEngine<Ctx>
     + HasKeyValueService
+    + HasTopicService
     + HasBlobStoreService
     + HasRdbmsService
     + HasOplogService
@@ -236,7 +259,9 @@ pub trait HasAll<Ctx: WorkerCtx>:
     + HasQuotaService
     + HasShardService
     + HasFileLoader
+    + HasFilesystemCheckpointService
     + HasOplogProcessorPlugin
+    + HasInvocationInterceptorPlugin
     + HasResourceLimits
     + HasShutdownToken
     + HasHttpConnectionPool
@@ -263,6 +288,7 @@ impl<
         + HasPromiseService
         + HasWasmtimeEngine<Ctx>
         + HasKeyValueService
+        + HasTopicService
         + HasBlobStoreService
         + HasRdbmsService
         + HasOplogService
@@ -275,7 +301,9 @@ impl<
         + HasQuotaService
         + HasShardService
         + HasFileLoader
+        + HasFilesystemCheckpointService
         + HasOplogProcessorPlugin
+        + HasInvocationInterceptorPlugin
         + HasResourceLimits
         + HasShutdownToken
         + HasHttpConnectionPool
@@ -310,6 +338,7 @@ pub struct All<Ctx: WorkerCtx> {
     golem_config: Arc<golem_config::GolemConfig>,
     shard_service: Arc<dyn shard::ShardService>,
     key_value_service: Arc<dyn key_value::KeyValueService>,
+    topic_service: Arc<dyn topics::TopicService>,
     blob_store_service: Arc<dyn blob_store::BlobStoreService>,
     rdbms_service: Arc<dyn rdbms::RdbmsService>,
     oplog_service: Arc<dyn oplog::OplogService>,
@@ -319,7 +348,9 @@ pub struct All<Ctx: WorkerCtx> {
     worker_proxy: Arc<dyn worker_proxy::WorkerProxy>,
     events: Arc<Events>,
     file_loader: Arc<FileLoader>,
+    filesystem_checkpoint_service: Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>,
     oplog_processor_plugin: Arc<dyn oplog::plugin::OplogProcessorPlugin>,
+    invocation_interceptor_plugin: Arc<dyn invocation_interceptor::InvocationInterceptorPlugin>,
     resource_limits: Arc<dyn resource_limits::ResourceLimits>,
     shutdown_token: CancellationToken,
     http_connection_pool: Option<HttpConnectionPool>,
@@ -352,6 +383,7 @@ impl<Ctx: WorkerCtx> Clone for All<Ctx> {
             golem_config: self.golem_config.clone(),
             shard_service: self.shard_service.clone(),
             key_value_service: self.key_value_service.clone(),
+            topic_service: self.topic_service.clone(),
             blob_store_service: self.blob_store_service.clone(),
             oplog_service: self.oplog_service.clone(),
             rpc: self.rpc.clone(),
@@ -361,7 +393,9 @@ impl<Ctx: WorkerCtx> Clone for All<Ctx> {
             rdbms_service: self.rdbms_service.clone(),
             events: self.events.clone(),
             file_loader: self.file_loader.clone(),
+            filesystem_checkpoint_service: self.filesystem_checkpoint_service.clone(),
             oplog_processor_plugin: self.oplog_processor_plugin.clone(),
+            invocation_interceptor_plugin: self.invocation_interceptor_plugin.clone(),
             resource_limits: self.resource_limits.clone(),
             shutdown_token: self.shutdown_token.clone(),
             http_connection_pool: self.http_connection_pool.clone(),
@@ -395,6 +429,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
         golem_config: Arc<golem_config::GolemConfig>,
         shard_service: Arc<dyn shard::ShardService>,
         key_value_service: Arc<dyn key_value::KeyValueService>,
+        topic_service: Arc<dyn topics::TopicService>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService>,
         rdbms_service: Arc<dyn rdbms::RdbmsService>,
         oplog_service: Arc<dyn oplog::OplogService>,
@@ -404,7 +439,9 @@ impl<Ctx: WorkerCtx> All<Ctx> {
         worker_proxy: Arc<dyn worker_proxy::WorkerProxy>,
         events: Arc<Events>,
         file_loader: Arc<FileLoader>,
+        filesystem_checkpoint_service: Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>,
         oplog_processor_plugin: Arc<dyn oplog::plugin::OplogProcessorPlugin>,
+        invocation_interceptor_plugin: Arc<dyn invocation_interceptor::InvocationInterceptorPlugin>,
         resource_limits: Arc<dyn resource_limits::ResourceLimits>,
         shutdown_token: CancellationToken,
         http_connection_pool: Option<HttpConnectionPool>,
@@ -431,6 +468,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
             golem_config,
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             oplog_service,
@@ -440,7 +478,9 @@ impl<Ctx: WorkerCtx> All<Ctx> {
             worker_proxy,
             events,
             file_loader,
+            filesystem_checkpoint_service,
             oplog_processor_plugin,
+            invocation_interceptor_plugin,
             resource_limits,
             shutdown_token,
             http_connection_pool,
@@ -477,6 +517,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
             this.config(),
             this.shard_service(),
             this.key_value_service(),
+            this.topic_service(),
             this.blob_store_service(),
             this.rdbms_service(),
             this.oplog_service(),
@@ -486,7 +527,9 @@ impl<Ctx: WorkerCtx> All<Ctx> {
             this.worker_proxy(),
             this.events(),
             this.file_loader(),
+            this.filesystem_checkpoint_service(),
             this.oplog_processor_plugin(),
+            this.invocation_interceptor_plugin(),
             this.resource_limits(),
             this.shutdown_token(),
             this.http_connection_pool(),
@@ -612,6 +655,12 @@ impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasKeyValueService for T {
     }
 }
 
+impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasTopicService for T {
+    fn topic_service(&self) -> Arc<dyn topics::TopicService> {
+        self.all().topic_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasBlobStoreService for T {
     fn blob_store_service(&self) -> Arc<dyn blob_store::BlobStoreService> {
         self.all().blob_store_service.clone()
@@ -666,12 +715,28 @@ impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasFileLoader for T {
     }
 }
 
+impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasFilesystemCheckpointService for T {
+    fn filesystem_checkpoint_service(
+        &self,
+    ) -> Arc<dyn filesystem_checkpoint::FilesystemCheckpointService> {
+        self.all().filesystem_checkpoint_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasOplogProcessorPlugin for T {
     fn oplog_processor_plugin(&self) -> Arc<dyn oplog::plugin::OplogProcessorPlugin> {
         self.all().oplog_processor_plugin.clone()
     }
 }
 
+impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasInvocationInterceptorPlugin for T {
+    fn invocation_interceptor_plugin(
+        &self,
+    ) -> Arc<dyn invocation_interceptor::InvocationInterceptorPlugin> {
+        self.all().invocation_interceptor_plugin.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasResourceLimits for T {
     fn resource_limits(&self) -> Arc<dyn resource_limits::ResourceLimits> {
         self.all().resource_limits.clone()
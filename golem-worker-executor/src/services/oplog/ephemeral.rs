@@ -279,6 +279,19 @@ impl EphemeralOplog {
                         let _ = done.send(());
                     }
                 }
+                BackgroundTransferMessage::ArchivePrefix {
+                    mut keep_alive,
+                    done,
+                    ..
+                } => {
+                    // Ephemeral oplogs are never compacted — ignore.
+                    warn!(
+                        "Unexpected ArchivePrefix message in ephemeral oplog for {}",
+                        owned_agent_id
+                    );
+                    let _ = keep_alive.take();
+                    let _ = done.send(());
+                }
             }
         }
     }
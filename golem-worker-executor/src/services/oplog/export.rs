@@ -0,0 +1,911 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::public_oplog::get_public_oplog_chunk;
+use crate::services::component::ComponentService;
+use crate::services::environment_state::EnvironmentStateService;
+use crate::services::golem_config::OplogExportConfig;
+use crate::services::oplog::OplogService;
+use crate::storage::keyvalue::{
+    KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
+};
+use arrow_array::{RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use async_trait::async_trait;
+use desert_rust::BinaryCodec;
+use golem_common::model::agent::{AgentMode, AgentTypeName, LegacyParsedAgentId};
+use golem_common::model::component::{ComponentId, ComponentRevision};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog::{OplogIndex, PublicOplogEntry};
+use golem_common::model::oplog_export::{
+    HttpExportTarget, OplogExportSinkRevision, OplogExportTarget,
+};
+use golem_common::model::{AgentId, AgentMetadata, OwnedAgentId, Timestamp};
+use golem_service_base::model::oplog_export_sink::NamedOplogExportSink;
+use golem_service_base::storage::blob::{BlobStorage, BlobStorageNamespace};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// A batch of consecutive oplog entries of a single agent
+pub struct OplogExportBatch<'a> {
+    pub owned_agent_id: &'a OwnedAgentId,
+    pub first_index: OplogIndex,
+    pub last_index: OplogIndex,
+    pub entries: &'a [PublicOplogEntry],
+}
+
+impl OplogExportBatch<'_> {
+    pub fn rows(&self) -> impl Iterator<Item = ExportedOplogEntry<'_>> {
+        let agent_id = self.owned_agent_id.agent_id.to_string();
+        self.entries
+            .iter()
+            .enumerate()
+            .map(move |(offset, entry)| ExportedOplogEntry {
+                environment_id: &self.owned_agent_id.environment_id,
+                component_id: &self.owned_agent_id.agent_id.component_id,
+                agent_id: agent_id.clone(),
+                oplog_index: self.first_index.as_u64() + offset as u64,
+                entry,
+            })
+    }
+
+    /// Encodes the batch as JSON Lines of [`ExportedOplogEntry`] values
+    pub fn to_json_lines(&self) -> Result<Vec<u8>, String> {
+        let mut body = Vec::new();
+        for row in self.rows() {
+            serde_json::to_writer(&mut body, &row)
+                .map_err(|err| format!("Failed to encode oplog entry: {err}"))?;
+            body.push(b'\n');
+        }
+        Ok(body)
+    }
+
+    /// Encodes the batch as a zstd-compressed Parquet file with one row per entry. The entry
+    /// itself is stored as JSON, next to the columns identifying it.
+    pub fn to_parquet(&self) -> Result<Vec<u8>, String> {
+        let mut environment_ids = Vec::with_capacity(self.entries.len());
+        let mut component_ids = Vec::with_capacity(self.entries.len());
+        let mut agent_ids = Vec::with_capacity(self.entries.len());
+        let mut oplog_indices = Vec::with_capacity(self.entries.len());
+        let mut entry_types = Vec::with_capacity(self.entries.len());
+        let mut timestamps = Vec::with_capacity(self.entries.len());
+        let mut entries = Vec::with_capacity(self.entries.len());
+        for row in self.rows() {
+            let entry = serde_json::to_value(row.entry)
+                .map_err(|err| format!("Failed to encode oplog entry: {err}"))?;
+            environment_ids.push(row.environment_id.to_string());
+            component_ids.push(row.component_id.to_string());
+            agent_ids.push(row.agent_id);
+            oplog_indices.push(row.oplog_index);
+            entry_types.push(entry["type"].as_str().map(|s| s.to_string()));
+            timestamps.push(entry["timestamp"].as_str().map(|s| s.to_string()));
+            entries.push(entry.to_string());
+        }
+
+        let schema = Arc::new(parquet_schema());
+        let record_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(environment_ids)),
+                Arc::new(StringArray::from(component_ids)),
+                Arc::new(StringArray::from(agent_ids)),
+                Arc::new(UInt64Array::from(oplog_indices)),
+                Arc::new(StringArray::from(entry_types)),
+                Arc::new(StringArray::from(timestamps)),
+                Arc::new(StringArray::from(entries)),
+            ],
+        )
+        .map_err(|err| format!("Failed to build Parquet record batch: {err}"))?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let mut body = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut body, schema, Some(properties))
+            .map_err(|err| format!("Failed to create Parquet writer: {err}"))?;
+        writer
+            .write(&record_batch)
+            .map_err(|err| format!("Failed to write Parquet file: {err}"))?;
+        writer
+            .close()
+            .map_err(|err| format!("Failed to write Parquet file: {err}"))?;
+        Ok(body)
+    }
+}
+
+/// Columns of the Parquet files written by the Parquet sink
+fn parquet_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("environment_id", DataType::Utf8, false),
+        Field::new("component_id", DataType::Utf8, false),
+        Field::new("agent_id", DataType::Utf8, false),
+        Field::new("oplog_index", DataType::UInt64, false),
+        Field::new("entry_type", DataType::Utf8, true),
+        Field::new("timestamp", DataType::Utf8, true),
+        Field::new("entry", DataType::Utf8, false),
+    ])
+}
+
+/// A built-in destination of exported oplog entries. A batch is only considered delivered if
+/// `export` succeeded, otherwise it is sent again on the next flush.
+#[async_trait]
+pub trait OplogExportSink: Send + Sync {
+    async fn export(&self, batch: &OplogExportBatch<'_>) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedOplogEntry<'a> {
+    pub environment_id: &'a EnvironmentId,
+    pub component_id: &'a ComponentId,
+    pub agent_id: String,
+    pub oplog_index: u64,
+    pub entry: &'a PublicOplogEntry,
+}
+
+/// Delivery position of an agent's oplog in a single sink.
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct OplogExportPosition {
+    /// Distinguishes the agent from a previously deleted agent with the same id, whose exported
+    /// position must not be reused
+    pub agent_created_at: Timestamp,
+    pub exported_up_to: OplogIndex,
+    /// The component revision in effect at `exported_up_to`
+    pub component_revision: ComponentRevision,
+}
+
+/// Delivery positions of an agent's oplog in each sink of its environment, by sink name
+#[derive(Debug, Clone, Default, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct OplogExportPositions {
+    pub positions: BTreeMap<String, OplogExportPosition>,
+}
+
+type NamedSinks = Vec<(String, Arc<dyn OplogExportSink>)>;
+
+type BuiltSink = (OplogExportSinkRevision, Arc<dyn OplogExportSink>);
+
+enum OplogExportSinkSource {
+    /// The sinks configured for each environment through the registry. The sink instances are
+    /// kept until the revision of their configuration changes.
+    EnvironmentState {
+        environment_state_service: Arc<dyn EnvironmentStateService>,
+        blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+        json_lines_directory: PathBuf,
+        built: Mutex<HashMap<(EnvironmentId, String), BuiltSink>>,
+    },
+    Fixed(HashMap<EnvironmentId, NamedSinks>),
+}
+
+/// The oplog export sinks of all environments
+pub struct OplogExports {
+    source: OplogExportSinkSource,
+    key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    max_batch_size: usize,
+}
+
+impl OplogExports {
+    pub fn new(
+        config: &OplogExportConfig,
+        environment_state_service: Arc<dyn EnvironmentStateService>,
+        key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+        blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+    ) -> Self {
+        Self {
+            source: OplogExportSinkSource::EnvironmentState {
+                environment_state_service,
+                blob_storage,
+                json_lines_directory: config.json_lines_directory.clone(),
+                built: Mutex::new(HashMap::new()),
+            },
+            key_value_storage,
+            max_batch_size: config.max_batch_size.max(1),
+        }
+    }
+
+    pub fn with_sinks(
+        sinks: HashMap<EnvironmentId, NamedSinks>,
+        key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+        max_batch_size: usize,
+    ) -> Self {
+        Self {
+            source: OplogExportSinkSource::Fixed(sinks),
+            key_value_storage,
+            max_batch_size: max_batch_size.max(1),
+        }
+    }
+
+    /// The current sinks of the environment. Sinks with an invalid configuration are skipped.
+    async fn sinks(&self, environment_id: EnvironmentId) -> Result<NamedSinks, String> {
+        match &self.source {
+            OplogExportSinkSource::Fixed(sinks) => {
+                Ok(sinks.get(&environment_id).cloned().unwrap_or_default())
+            }
+            OplogExportSinkSource::EnvironmentState {
+                environment_state_service,
+                blob_storage,
+                json_lines_directory,
+                built,
+            } => {
+                let configured = environment_state_service
+                    .get_oplog_export_sinks(environment_id)
+                    .await
+                    .map_err(|err| format!("Failed to get the oplog export sinks: {err}"))?;
+
+                let mut built = built.lock().await;
+                built.retain(|(env, name), _| {
+                    *env != environment_id || configured.iter().any(|sink| &sink.name == name)
+                });
+
+                let mut sinks = Vec::with_capacity(configured.len());
+                for config in configured {
+                    let key = (environment_id, config.name.clone());
+                    match built.get(&key) {
+                        Some((revision, sink)) if *revision == config.revision => {
+                            sinks.push((config.name, sink.clone()));
+                        }
+                        _ => match build_sink(
+                            &config,
+                            &json_lines_directory.join(environment_id.to_string()),
+                            blob_storage,
+                        ) {
+                            Ok(sink) => {
+                                built.insert(key, (config.revision, sink.clone()));
+                                sinks.push((config.name, sink));
+                            }
+                            Err(err) => {
+                                tracing::warn!(
+                                    sink = config.name,
+                                    %environment_id,
+                                    "Skipping invalid oplog export sink: {err}"
+                                );
+                            }
+                        },
+                    }
+                }
+                Ok(sinks)
+            }
+        }
+    }
+
+    pub(crate) fn positions_key(agent_id: &AgentId) -> String {
+        format!("worker:oplog_export_positions:{}", agent_id.to_redis_key())
+    }
+
+    async fn get_positions(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+    ) -> Result<Option<OplogExportPositions>, String> {
+        self.key_value_storage
+            .with_entity("oplog_export", "get_positions", "positions")
+            .get(
+                KeyValueStorageNamespace::Worker {
+                    agent_id: owned_agent_id.agent_id(),
+                },
+                &Self::positions_key(&owned_agent_id.agent_id),
+            )
+            .await
+    }
+
+    async fn set_positions(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        positions: &OplogExportPositions,
+    ) -> Result<(), String> {
+        self.key_value_storage
+            .with_entity("oplog_export", "set_positions", "positions")
+            .set(
+                KeyValueStorageNamespace::Worker {
+                    agent_id: owned_agent_id.agent_id(),
+                },
+                &Self::positions_key(&owned_agent_id.agent_id),
+                positions,
+            )
+            .await
+    }
+}
+
+fn build_sink(
+    config: &NamedOplogExportSink,
+    environment_directory: &Path,
+    blob_storage: &Arc<dyn BlobStorage + Send + Sync>,
+) -> Result<Arc<dyn OplogExportSink>, String> {
+    Ok(match &config.target {
+        OplogExportTarget::JsonLinesFile(_) => Arc::new(JsonLinesFileSink::new(
+            environment_directory.join(&config.name),
+        )),
+        OplogExportTarget::BlobStorage(target) => Arc::new(BlobStorageSink::new(
+            target.prefix.clone(),
+            blob_storage.clone(),
+        )),
+        OplogExportTarget::ParquetBlobStorage(target) => Arc::new(ParquetBlobStorageSink::new(
+            target.prefix.clone(),
+            blob_storage.clone(),
+        )),
+        OplogExportTarget::Http(target) => Arc::new(HttpSink::new(target)?),
+    })
+}
+
+/// Exports the committed oplog entries of a single agent to the sinks of its environment
+pub struct AgentOplogExport {
+    exports: Arc<OplogExports>,
+    oplog_service: Arc<dyn OplogService>,
+    components: Arc<dyn ComponentService>,
+    owned_agent_id: OwnedAgentId,
+    agent_mode: AgentMode,
+    agent_type: Option<AgentTypeName>,
+    created_at: Timestamp,
+    initial_component_revision: ComponentRevision,
+    /// Positions already loaded from the key-value storage
+    positions: Option<OplogExportPositions>,
+}
+
+impl AgentOplogExport {
+    pub fn new(
+        exports: Arc<OplogExports>,
+        oplog_service: Arc<dyn OplogService>,
+        components: Arc<dyn ComponentService>,
+        metadata: &AgentMetadata,
+    ) -> Self {
+        Self {
+            exports,
+            oplog_service,
+            components,
+            owned_agent_id: metadata.owned_agent_id(),
+            agent_mode: metadata.agent_mode,
+            agent_type: LegacyParsedAgentId::parse_agent_type_name(&metadata.agent_id.agent_id)
+                .ok(),
+            created_at: metadata.created_at,
+            initial_component_revision: metadata.last_known_status.component_revision,
+            positions: None,
+        }
+    }
+
+    /// Sends the entries up to `committed_tail` that each sink of the environment has not
+    /// received yet. Failures are logged and retried on the next call, so entries may be
+    /// delivered more than once.
+    pub async fn export(&mut self, committed_tail: OplogIndex) {
+        let sinks = match self.exports.sinks(self.owned_agent_id.environment_id).await {
+            Ok(sinks) => sinks,
+            Err(err) => {
+                tracing::warn!(agent_id = %self.owned_agent_id, "{err}");
+                return;
+            }
+        };
+
+        for (sink_name, sink) in sinks {
+            if let Err(err) = self.export_to(&sink_name, &*sink, committed_tail).await {
+                tracing::warn!(
+                    sink = sink_name,
+                    agent_id = %self.owned_agent_id,
+                    "Failed to export oplog entries: {err}"
+                );
+            }
+        }
+    }
+
+    async fn export_to(
+        &mut self,
+        sink_name: &str,
+        sink: &dyn OplogExportSink,
+        committed_tail: OplogIndex,
+    ) -> Result<(), String> {
+        let mut position = self.position(sink_name).await?;
+
+        while position.exported_up_to < committed_tail {
+            let count = (committed_tail.as_u64() - position.exported_up_to.as_u64())
+                .min(self.exports.max_batch_size as u64) as usize;
+            let chunk = get_public_oplog_chunk(
+                self.components.clone(),
+                self.oplog_service.clone(),
+                &self.owned_agent_id,
+                self.agent_mode,
+                self.agent_type.as_ref(),
+                position.component_revision,
+                position.exported_up_to.next(),
+                count,
+            )
+            .await?;
+
+            if chunk.entries.is_empty() {
+                // The remaining range is not readable anymore (for example the agent has been deleted)
+                break;
+            }
+
+            let last_index = chunk.next_oplog_index.previous();
+            sink.export(&OplogExportBatch {
+                owned_agent_id: &self.owned_agent_id,
+                first_index: chunk.first_index_in_chunk,
+                last_index,
+                entries: &chunk.entries,
+            })
+            .await?;
+
+            position = OplogExportPosition {
+                agent_created_at: self.created_at,
+                exported_up_to: last_index,
+                component_revision: chunk.current_component_revision,
+            };
+            self.set_position(sink_name, position.clone()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn loaded_positions(&mut self) -> Result<&OplogExportPositions, String> {
+        if self.positions.is_none() {
+            let positions = self
+                .exports
+                .get_positions(&self.owned_agent_id)
+                .await?
+                .unwrap_or_default();
+            self.positions = Some(positions);
+        }
+        Ok(self.positions.get_or_insert_default())
+    }
+
+    async fn position(&mut self, sink_name: &str) -> Result<OplogExportPosition, String> {
+        let created_at = self.created_at;
+        let initial_component_revision = self.initial_component_revision;
+        let positions = self.loaded_positions().await?;
+
+        Ok(match positions.positions.get(sink_name) {
+            Some(position) if position.agent_created_at == created_at => position.clone(),
+            _ => OplogExportPosition {
+                agent_created_at: created_at,
+                exported_up_to: OplogIndex::NONE,
+                // The `Create` entry at the beginning of the oplog sets the actual revision
+                component_revision: initial_component_revision,
+            },
+        })
+    }
+
+    async fn set_position(
+        &mut self,
+        sink_name: &str,
+        position: OplogExportPosition,
+    ) -> Result<(), String> {
+        let mut positions = self.loaded_positions().await?.clone();
+        positions.positions.insert(sink_name.to_string(), position);
+        self.exports
+            .set_positions(&self.owned_agent_id, &positions)
+            .await?;
+        self.positions = Some(positions);
+        Ok(())
+    }
+}
+
+/// Appends batches to daily rotated files named `oplog-<date>.jsonl` in the sink's directory
+struct JsonLinesFileSink {
+    directory: PathBuf,
+    /// Serializes the appends so the lines of concurrently exported batches never interleave
+    lock: Mutex<()>,
+}
+
+impl JsonLinesFileSink {
+    fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        let date = chrono::Utc::now().format("%Y-%m-%d");
+        self.directory.join(format!("oplog-{date}.jsonl"))
+    }
+}
+
+#[async_trait]
+impl OplogExportSink for JsonLinesFileSink {
+    async fn export(&self, batch: &OplogExportBatch<'_>) -> Result<(), String> {
+        let body = batch.to_json_lines()?;
+        let path = self.path();
+        let _guard = self.lock.lock().await;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+        file.write_all(&body)
+            .await
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+        file.flush()
+            .await
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+    }
+}
+
+/// Writes each batch as a separate object to the environment's custom blob storage, at
+/// `<prefix>/<component-id>/<agent-id>/<first-index>-<last-index>.jsonl`. Redelivered batches
+/// overwrite the same object.
+struct BlobStorageSink {
+    prefix: String,
+    blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+}
+
+impl BlobStorageSink {
+    fn new(prefix: String, blob_storage: Arc<dyn BlobStorage + Send + Sync>) -> Self {
+        Self {
+            prefix,
+            blob_storage,
+        }
+    }
+}
+
+#[async_trait]
+impl OplogExportSink for BlobStorageSink {
+    async fn export(&self, batch: &OplogExportBatch<'_>) -> Result<(), String> {
+        let path = Path::new(&self.prefix)
+            .join(batch.owned_agent_id.agent_id.component_id.to_string())
+            .join(batch.owned_agent_id.agent_id.agent_id.to_string())
+            .join(format!(
+                "{:020}-{:020}.jsonl",
+                batch.first_index.as_u64(),
+                batch.last_index.as_u64()
+            ));
+        self.blob_storage
+            .put_raw(
+                "oplog_export",
+                "export",
+                BlobStorageNamespace::CustomStorage {
+                    environment_id: batch.owned_agent_id.environment_id,
+                },
+                &path,
+                &batch.to_json_lines()?,
+            )
+            .await
+    }
+}
+
+/// Writes each batch as a separate Parquet file to the environment's custom blob storage, at
+/// `<prefix>/date=<yyyy-mm-dd>/<component-id>/<agent-id>/<first-index>-<last-index>.parquet`.
+/// The files are rotated daily through the date partition. A batch redelivered on the same day
+/// overwrites the same object, otherwise it is written again in the new partition.
+struct ParquetBlobStorageSink {
+    prefix: String,
+    blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+}
+
+impl ParquetBlobStorageSink {
+    fn new(prefix: String, blob_storage: Arc<dyn BlobStorage + Send + Sync>) -> Self {
+        Self {
+            prefix,
+            blob_storage,
+        }
+    }
+
+    fn path(&self, batch: &OplogExportBatch<'_>) -> PathBuf {
+        let date = chrono::Utc::now().format("%Y-%m-%d");
+        Path::new(&self.prefix)
+            .join(format!("date={date}"))
+            .join(batch.owned_agent_id.agent_id.component_id.to_string())
+            .join(batch.owned_agent_id.agent_id.agent_id.to_string())
+            .join(format!(
+                "{:020}-{:020}.parquet",
+                batch.first_index.as_u64(),
+                batch.last_index.as_u64()
+            ))
+    }
+}
+
+#[async_trait]
+impl OplogExportSink for ParquetBlobStorageSink {
+    async fn export(&self, batch: &OplogExportBatch<'_>) -> Result<(), String> {
+        let body = batch.to_parquet()?;
+        self.blob_storage
+            .put_raw(
+                "oplog_export",
+                "export",
+                BlobStorageNamespace::CustomStorage {
+                    environment_id: batch.owned_agent_id.environment_id,
+                },
+                &self.path(batch),
+                &body,
+            )
+            .await
+    }
+}
+
+/// POSTs each batch as an `application/x-ndjson` body
+struct HttpSink {
+    client: reqwest::Client,
+    url: url::Url,
+    headers: Vec<(String, String)>,
+}
+
+impl HttpSink {
+    fn new(target: &HttpExportTarget) -> Result<Self, String> {
+        let url = url::Url::parse(&target.url)
+            .map_err(|err| format!("Invalid URL {}: {err}", target.url))?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(target.timeout_ms))
+            .build()
+            .map_err(|err| format!("Failed to create HTTP client: {err}"))?;
+        Ok(Self {
+            client,
+            url,
+            headers: target
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl OplogExportSink for HttpSink {
+    async fn export(&self, batch: &OplogExportBatch<'_>) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .header("Content-Type", "application/x-ndjson");
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+            .body(batch.to_json_lines()?)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|err| format!("Failed to POST oplog entries to {}: {err}", self.url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::oplog::public_oplog_entry::NoOpParams;
+    use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use test_r::test;
+
+    test_r::enable!();
+
+    fn owned_agent_id() -> OwnedAgentId {
+        OwnedAgentId::new(
+            EnvironmentId::new(),
+            &AgentId {
+                component_id: ComponentId::new(),
+                agent_id: "agent".to_string(),
+            },
+        )
+    }
+
+    fn entries(count: u64) -> Vec<PublicOplogEntry> {
+        (0..count)
+            .map(|millis| {
+                PublicOplogEntry::NoOp(NoOpParams {
+                    timestamp: Timestamp::from(millis),
+                })
+            })
+            .collect()
+    }
+
+    fn batch<'a>(
+        owned_agent_id: &'a OwnedAgentId,
+        entries: &'a [PublicOplogEntry],
+    ) -> OplogExportBatch<'a> {
+        OplogExportBatch {
+            owned_agent_id,
+            first_index: OplogIndex::from_u64(5),
+            last_index: OplogIndex::from_u64(4 + entries.len() as u64),
+            entries,
+        }
+    }
+
+    #[test]
+    fn exported_entry_fields_are_camel_case() {
+        let environment_id = EnvironmentId::new();
+        let component_id = ComponentId::new();
+        let entry = PublicOplogEntry::NoOp(NoOpParams {
+            timestamp: Timestamp::from(0),
+        });
+        let line = ExportedOplogEntry {
+            environment_id: &environment_id,
+            component_id: &component_id,
+            agent_id: "agent".to_string(),
+            oplog_index: 3,
+            entry: &entry,
+        };
+
+        let json = serde_json::to_value(&line).unwrap();
+        assert_eq!(json["oplogIndex"], 3);
+        assert_eq!(json["agentId"], "agent");
+        assert_eq!(json["entry"]["type"], "NoOp");
+        assert!(json.get("environmentId").is_some());
+        assert!(json.get("componentId").is_some());
+    }
+
+    #[test]
+    fn json_lines_are_numbered_from_the_first_index() {
+        let owned_agent_id = owned_agent_id();
+        let entries = entries(3);
+
+        let body = batch(&owned_agent_id, &entries).to_json_lines().unwrap();
+        let lines = String::from_utf8(body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        for (offset, line) in lines.iter().enumerate() {
+            assert_eq!(line["oplogIndex"], 5 + offset as u64);
+            assert_eq!(line["agentId"], owned_agent_id.agent_id.to_string());
+        }
+    }
+
+    #[test]
+    fn parquet_files_contain_one_row_per_entry() {
+        let owned_agent_id = owned_agent_id();
+        let entries = entries(3);
+
+        let body = batch(&owned_agent_id, &entries).to_parquet().unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(body))
+            .unwrap()
+            .build()
+            .unwrap();
+        let record_batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(record_batches.len(), 1);
+        let record_batch = &record_batches[0];
+        assert_eq!(record_batch.schema().as_ref(), &parquet_schema());
+        assert_eq!(record_batch.num_rows(), 3);
+
+        let column = |name: &str| record_batch.column_by_name(name).unwrap().clone();
+        let oplog_indices = column("oplog_index");
+        let oplog_indices = oplog_indices
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(oplog_indices.values().to_vec(), vec![5, 6, 7]);
+
+        let agent_ids = column("agent_id");
+        let agent_ids = agent_ids.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(agent_ids.value(0), owned_agent_id.agent_id.to_string());
+
+        let entry_types = column("entry_type");
+        let entry_types = entry_types.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(entry_types.value(2), "NoOp");
+
+        let stored_entries = column("entry");
+        let stored_entries = stored_entries
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let entry: PublicOplogEntry = serde_json::from_str(stored_entries.value(1)).unwrap();
+        assert_eq!(entry, entries[1]);
+    }
+
+    #[test]
+    async fn json_lines_file_sink_appends_batches() {
+        let directory = tempfile::tempdir().unwrap();
+        let sink = JsonLinesFileSink::new(directory.path().to_path_buf());
+        let owned_agent_id = owned_agent_id();
+        let entries = entries(2);
+
+        sink.export(&batch(&owned_agent_id, &entries))
+            .await
+            .unwrap();
+        sink.export(&batch(&owned_agent_id, &entries))
+            .await
+            .unwrap();
+
+        let content = std::fs::read_to_string(sink.path()).unwrap();
+        assert_eq!(content.lines().count(), 4);
+    }
+
+    #[test]
+    async fn parquet_sink_writes_date_partitioned_objects() {
+        let blob_storage = Arc::new(InMemoryBlobStorage::new());
+        let sink = ParquetBlobStorageSink::new("export".to_string(), blob_storage.clone());
+        let owned_agent_id = owned_agent_id();
+        let entries = entries(2);
+        let batch = batch(&owned_agent_id, &entries);
+
+        sink.export(&batch).await.unwrap();
+
+        let path = sink.path(&batch);
+        assert!(path.starts_with(format!(
+            "export/date={}",
+            chrono::Utc::now().format("%Y-%m-%d")
+        )));
+        assert!(
+            path.ends_with("00000000000000000005-00000000000000000006.parquet"),
+            "{path:?}"
+        );
+        let stored = blob_storage
+            .get_raw(
+                "test",
+                "test",
+                BlobStorageNamespace::CustomStorage {
+                    environment_id: owned_agent_id.environment_id,
+                },
+                &path,
+            )
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(batch.to_parquet().unwrap()));
+    }
+
+    #[test]
+    async fn http_sink_posts_json_lines_and_fails_on_error_status() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::<(String, String)>::new()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let route = axum::Router::new()
+            .route(
+                "/ok",
+                axum::routing::post({
+                    let received = received.clone();
+                    move |headers: axum::http::HeaderMap, body: bytes::Bytes| async move {
+                        received.lock().unwrap().push((
+                            headers["content-type"].to_str().unwrap().to_string(),
+                            String::from_utf8(body.to_vec()).unwrap(),
+                        ));
+                        "OK"
+                    }
+                }),
+            )
+            .route(
+                "/fail",
+                axum::routing::post(|| async { axum::http::StatusCode::SERVICE_UNAVAILABLE }),
+            );
+        let server = tokio::spawn(async move { axum::serve(listener, route).await.unwrap() });
+
+        let sink = |path: &str| {
+            HttpSink::new(&HttpExportTarget {
+                url: format!("http://127.0.0.1:{port}{path}"),
+                headers: Default::default(),
+                timeout_ms: 10_000,
+            })
+            .unwrap()
+        };
+        let owned_agent_id = owned_agent_id();
+        let entries = entries(2);
+        let batch = batch(&owned_agent_id, &entries);
+
+        sink("/ok").export(&batch).await.unwrap();
+        assert!(sink("/fail").export(&batch).await.is_err());
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(
+            received,
+            vec![(
+                "application/x-ndjson".to_string(),
+                String::from_utf8(batch.to_json_lines().unwrap()).unwrap()
+            )]
+        );
+        server.abort();
+    }
+}
@@ -37,6 +37,7 @@ use golem_service_base::error::worker_executor::WorkerExecutorError;
 pub use ephemeral::EphemeralOplog;
 pub use multilayer::{MultiLayerOplog, MultiLayerOplogService, OplogArchiveService};
 pub use primary::PrimaryOplogService;
+pub use time_travel::TimeTravelOplog;
 use std::any::{Any, TypeId};
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Formatter};
@@ -48,10 +49,12 @@ use std::time::Duration;
 mod blob;
 mod compressed;
 mod ephemeral;
+pub mod export;
 mod multilayer;
 pub mod plugin;
 mod primary;
 pub mod rate_limited;
+mod time_travel;
 
 #[cfg(test)]
 pub mod tests;
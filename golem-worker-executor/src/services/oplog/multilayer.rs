@@ -19,7 +19,7 @@ use crate::metrics::storage::{
 use crate::model::ExecutionStatus;
 use crate::services::oplog::ephemeral::EphemeralOplog;
 use crate::services::oplog::multilayer::BackgroundTransferMessage::{
-    TransferFromLower, TransferFromPrimary,
+    ArchivePrefix, TransferFromLower, TransferFromPrimary,
 };
 use crate::services::oplog::{
     CommitLevel, OpenOplogs, Oplog, OplogConstructor, OplogService, downcast_oplog, scan_modes,
@@ -840,10 +840,74 @@ impl MultiLayerOplog {
                         done.send(()).unwrap()
                     }
                 }
+                ArchivePrefix {
+                    last_archived_idx,
+                    mut keep_alive,
+                    done,
+                } => {
+                    info!(
+                        "Archiving oplog entries up to index {last_archived_idx} to the last layer"
+                    );
+
+                    // The primary oplog is transferred up to the requested index, then every
+                    // intermediate layer is transferred as a whole, the same way as archiving
+                    // does, so the layers stay ordered by oplog index
+                    if let Some(primary) = primary.upgrade() {
+                        let transfer = BackgroundTransferFromPrimary::new(
+                            owned_agent_id.clone(),
+                            agent_mode,
+                            last_archived_idx,
+                            multi_layer_oplog_service.clone(),
+                            primary.clone(),
+                            lower.clone(),
+                        );
+                        if let Err(error) = transfer.run().await {
+                            error!("Failed to transfer entries from the primary oplog: {error}");
+                        }
+                    }
+                    for source in 0..(lower.len().get() - 1) {
+                        if lower[source].length().await == 0 {
+                            continue;
+                        }
+                        let transfer = BackgroundTransferBetweenLowers::new(
+                            source,
+                            lower[source].current_oplog_index().await,
+                            lower.clone(),
+                        );
+                        if let Err(error) = transfer.run().await {
+                            error!("Failed to transfer entries from oplog layer {source}: {error}");
+                        }
+                    }
+                    let _ = keep_alive.take();
+
+                    let _ = done.send(());
+                }
             }
         }
     }
 
+    /// Moves every entry up to and including `last_archived_idx` to the last layer of the oplog,
+    /// where they stay readable. Returns `None` if the oplog is not a multi-layer oplog.
+    pub async fn try_archive_prefix(
+        this: &Arc<dyn Oplog>,
+        last_archived_idx: OplogIndex,
+    ) -> Option<()> {
+        let this = downcast_oplog::<MultiLayerOplog>(this)?;
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        this.transfer
+            .send(ArchivePrefix {
+                last_archived_idx,
+                keep_alive: Some(this.clone()),
+                done: done_tx,
+            })
+            .expect("Failed to enqueue archiving the oplog prefix");
+        this.last_transfer_point.max(last_archived_idx);
+        done_rx
+            .await
+            .expect("Failed to wait for archiving the oplog prefix");
+        Some(())
+    }
+
     pub async fn try_archive(this: &Arc<dyn Oplog>) -> Option<bool> {
         let this = downcast_oplog::<MultiLayerOplog>(this)?;
         Some(Self::archive(this, false).await)
@@ -1086,6 +1150,11 @@ pub enum BackgroundTransferMessage {
         keep_alive: Option<Arc<dyn Oplog>>,
         done: Option<Sender<()>>,
     },
+    ArchivePrefix {
+        last_archived_idx: OplogIndex,
+        keep_alive: Option<Arc<dyn Oplog>>,
+        done: Sender<()>,
+    },
 }
 
 #[async_trait]
@@ -15,6 +15,7 @@
 use crate::model::ExecutionStatus;
 use crate::model::event::InternalWorkerEvent;
 use crate::services::component::ComponentService;
+use crate::services::oplog::export::{AgentOplogExport, OplogExports};
 use crate::services::oplog::{CommitLevel, OpenOplogs, Oplog, OplogConstructor, OplogService};
 use crate::services::shard::ShardService;
 use crate::services::worker_activator::WorkerActivator;
@@ -194,7 +195,7 @@ impl<Ctx: WorkerCtx> PerExecutorOplogProcessorPlugin<Ctx> {
         plugin_component_id: ComponentId,
     ) -> Result<AgentId, WorkerExecutorError> {
         let current_assignment = self.shard_service.current_assignment()?;
-        let agent_id = Self::generate_local_agent_id(
+        let agent_id = generate_local_agent_id(
             plugin_component_id,
             &current_assignment.shard_ids,
             current_assignment.number_of_shards,
@@ -202,38 +203,38 @@ impl<Ctx: WorkerCtx> PerExecutorOplogProcessorPlugin<Ctx> {
 
         Ok(agent_id)
     }
+}
 
-    /// Converts a `TargetAgentId` to an `AgentId`. If the worker name was not specified,
-    /// it generates a new unique one, and if the `force_in_shard` set is not empty, it guarantees
-    /// that the generated worker ID will belong to one of the provided shards.
-    ///
-    /// If the worker name was specified, `force_in_shard` is ignored.
-    fn generate_local_agent_id(
-        component_id: ComponentId,
-        force_in_shard: &HashSet<ShardId>,
-        number_of_shards: usize,
-    ) -> AgentId {
-        if force_in_shard.is_empty() || number_of_shards == 0 {
-            let agent_name = Uuid::new_v4().to_string();
-            AgentId {
+/// Converts a `TargetAgentId` to an `AgentId`. If the worker name was not specified,
+/// it generates a new unique one, and if the `force_in_shard` set is not empty, it guarantees
+/// that the generated worker ID will belong to one of the provided shards.
+///
+/// If the worker name was specified, `force_in_shard` is ignored.
+pub(crate) fn generate_local_agent_id(
+    component_id: ComponentId,
+    force_in_shard: &HashSet<ShardId>,
+    number_of_shards: usize,
+) -> AgentId {
+    if force_in_shard.is_empty() || number_of_shards == 0 {
+        let agent_name = Uuid::new_v4().to_string();
+        AgentId {
+            component_id,
+            agent_id: agent_name,
+        }
+    } else {
+        let mut current = Uuid::new_v4().to_u128_le();
+        loop {
+            let uuid = Uuid::from_u128_le(current);
+            let agent_name = uuid.to_string();
+            let agent_id = AgentId {
                 component_id,
                 agent_id: agent_name,
+            };
+            let shard_id = ShardId::from_agent_id(&agent_id, number_of_shards);
+            if force_in_shard.contains(&shard_id) {
+                return agent_id;
             }
-        } else {
-            let mut current = Uuid::new_v4().to_u128_le();
-            loop {
-                let uuid = Uuid::from_u128_le(current);
-                let agent_name = uuid.to_string();
-                let agent_id = AgentId {
-                    component_id,
-                    agent_id: agent_name,
-                };
-                let shard_id = ShardId::from_agent_id(&agent_id, number_of_shards);
-                if force_in_shard.contains(&shard_id) {
-                    return agent_id;
-                }
-                current += 1;
-            }
+            current += 1;
         }
     }
 }
@@ -359,6 +360,7 @@ impl<Ctx: WorkerCtx> OplogProcessorPlugin for PerExecutorOplogProcessorPlugin<Ct
                         .collect(),
                     oplog_idx: u64::from(latest_status.oplog_idx),
                     fingerprint: Some(worker_metadata.fingerprint.0.into()),
+                    promises: Vec::new(),
                 }
             };
             let proto_entries: Vec<golem_api_grpc::proto::golem::worker::RawOplogEntry> = entries
@@ -510,6 +512,7 @@ struct CreateOplogConstructor {
     execution_status: read_only_lock::std::ReadOnlyLock<ExecutionStatus>,
     plugin_max_commit_count: usize,
     plugin_max_elapsed_time: Duration,
+    oplog_exports: Arc<OplogExports>,
 }
 
 impl CreateOplogConstructor {
@@ -527,6 +530,7 @@ impl CreateOplogConstructor {
         execution_status: read_only_lock::std::ReadOnlyLock<ExecutionStatus>,
         plugin_max_commit_count: usize,
         plugin_max_elapsed_time: Duration,
+        oplog_exports: Arc<OplogExports>,
     ) -> Self {
         Self {
             owned_agent_id,
@@ -541,6 +545,7 @@ impl CreateOplogConstructor {
             execution_status,
             plugin_max_commit_count,
             plugin_max_elapsed_time,
+            oplog_exports,
         }
     }
 }
@@ -580,6 +585,13 @@ impl OplogConstructor for CreateOplogConstructor {
                 .await
         };
 
+        let oplog_export = Some(AgentOplogExport::new(
+            self.oplog_exports,
+            self.inner,
+            self.components.clone(),
+            &self.initial_worker_metadata,
+        ));
+
         Arc::new(
             ForwardingOplog::new(
                 inner,
@@ -591,6 +603,7 @@ impl OplogConstructor for CreateOplogConstructor {
                 close,
                 self.plugin_max_commit_count,
                 self.plugin_max_elapsed_time,
+                oplog_export,
             )
             .await,
         )
@@ -605,6 +618,7 @@ pub struct ForwardingOplogService {
     components: Arc<dyn ComponentService>,
     plugin_max_commit_count: usize,
     plugin_max_elapsed_time: Duration,
+    oplog_exports: Arc<OplogExports>,
 }
 
 impl ForwardingOplogService {
@@ -614,6 +628,7 @@ impl ForwardingOplogService {
         components: Arc<dyn ComponentService>,
         plugin_max_commit_count: usize,
         plugin_max_elapsed_time: Duration,
+        oplog_exports: Arc<OplogExports>,
     ) -> Self {
         Self {
             inner,
@@ -622,6 +637,7 @@ impl ForwardingOplogService {
             components,
             plugin_max_commit_count,
             plugin_max_elapsed_time,
+            oplog_exports,
         }
     }
 }
@@ -659,6 +675,7 @@ impl OplogService for ForwardingOplogService {
                     execution_status,
                     self.plugin_max_commit_count,
                     self.plugin_max_elapsed_time,
+                    self.oplog_exports.clone(),
                 ),
             )
             .await
@@ -689,6 +706,7 @@ impl OplogService for ForwardingOplogService {
                     execution_status,
                     self.plugin_max_commit_count,
                     self.plugin_max_elapsed_time,
+                    self.oplog_exports.clone(),
                 ),
             )
             .await
@@ -758,6 +776,7 @@ impl OplogService for ForwardingOplogService {
 }
 
 /// A wrapper for `Oplog` that periodically sends buffered oplog entries to oplog processor plugins
+/// and to the built-in oplog export sinks of the agent's environment
 pub struct ForwardingOplog {
     inner: Arc<dyn Oplog>,
     state: Arc<Mutex<ForwardingOplogState>>,
@@ -777,6 +796,7 @@ impl ForwardingOplog {
         close_fn: Box<dyn FnOnce() + Send + Sync>,
         max_commit_count: usize,
         max_elapsed_time: Duration,
+        oplog_export: Option<AgentOplogExport>,
     ) -> Self {
         // Seed per-plugin live state from the current status snapshot (not the
         // potentially stale initial_worker_metadata.last_known_status)
@@ -824,6 +844,7 @@ impl ForwardingOplog {
             pending_direct_commits: BTreeMap::new(),
             worker_event_service: None,
             monitor_tasks: Vec::new(),
+            oplog_export,
         }));
 
         let timer = tokio::spawn({
@@ -995,6 +1016,8 @@ struct ForwardingOplogState {
     pending_direct_commits: BTreeMap<OplogIndex, OplogEntry>,
     worker_event_service: Option<Arc<dyn WorkerEventService>>,
     monitor_tasks: Vec<JoinHandle<()>>,
+    /// `None` if the oplog is not exported at all
+    oplog_export: Option<AgentOplogExport>,
 }
 
 impl ForwardingOplogState {
@@ -1005,6 +1028,10 @@ impl ForwardingOplogState {
     /// Entries are always read from the persisted oplog (canonical source) to avoid
     /// buffer/index drift caused by checkpoint entries injected during flush.
     pub async fn try_flush(&mut self) {
+        if let Some(oplog_export) = &mut self.oplog_export {
+            oplog_export.export(self.last_committed_idx).await;
+        }
+
         let status = self.last_known_status.read().await.clone();
         let flush_set = self.reconcile_plugin_state(&status);
 
@@ -1917,6 +1944,8 @@ mod tests {
                     plugin_version: "1.0.0".to_string(),
                     oplog_processor_component_id: Some(ComponentId::new()),
                     oplog_processor_component_revision: Some(ComponentRevision::INITIAL),
+                    invocation_interceptor_component_id: None,
+                    invocation_interceptor_component_revision: None,
                 }],
             }
         }
@@ -2180,6 +2209,7 @@ mod tests {
             pending_direct_commits: BTreeMap::new(),
             worker_event_service: None,
             monitor_tasks: Vec::new(),
+            oplog_export: None,
         };
 
         // No committed entries (last_committed_idx = NONE) — try_flush should be a no-op
@@ -2222,6 +2252,7 @@ mod tests {
             pending_direct_commits: BTreeMap::new(),
             worker_event_service: None,
             monitor_tasks: Vec::new(),
+            oplog_export: None,
         };
 
         state.try_flush().await;
@@ -2284,6 +2315,7 @@ mod tests {
             pending_direct_commits: BTreeMap::new(),
             worker_event_service: None,
             monitor_tasks: Vec::new(),
+            oplog_export: None,
         };
 
         state.try_flush().await;
@@ -2335,6 +2367,7 @@ mod tests {
             pending_direct_commits: BTreeMap::new(),
             worker_event_service: None,
             monitor_tasks: Vec::new(),
+            oplog_export: None,
         };
 
         state.try_flush().await;
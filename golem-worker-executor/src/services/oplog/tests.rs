@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use super::*;
+use crate::services::environment_state::EnvironmentStateService;
+use crate::services::golem_config::OplogExportConfig;
 use crate::services::oplog::compressed::CompressedOplogArchiveService;
 use crate::services::oplog::multilayer::OplogArchiveService;
 use crate::storage::indexed::IndexedStorage;
@@ -22,21 +24,31 @@ use crate::storage::indexed::sqlite::SqliteIndexedStorage;
 use assert2::check;
 use golem_common::config::RedisConfig;
 use golem_common::model::account::{AccountEmail, AccountId};
-use golem_common::model::agent::{AgentMode, Principal, UntypedDataValue, UntypedElementValue};
+use golem_common::model::agent::{
+    AgentMode, AgentTypeName, Principal, UntypedDataValue, UntypedElementValue,
+};
+use golem_common::model::agent_secret::CanonicalAgentSecretPath;
 use golem_common::model::component::ComponentId;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::{AgentError, LogLevel};
+use golem_common::model::oplog_export::{
+    JsonLinesFileExportTarget, OplogExportSinkRevision, OplogExportTarget,
+};
 use golem_common::model::regions::OplogRegion;
+use golem_common::model::retry_policy::NamedRetryPolicy;
 use golem_common::model::{
     AgentFingerprint, AgentMetadata, AgentStatusRecord, IdempotencyKey, OwnedAgentId,
 };
 use golem_common::model::{AgentInvocationPayload, RetryConfig};
 use golem_common::redis::RedisPool;
 use golem_common::tracing::{TracingConfig, init_tracing};
+use golem_service_base::model::AgentDeploymentDetails;
+use golem_service_base::model::agent_secret::AgentSecret;
+use golem_service_base::model::oplog_export_sink::NamedOplogExportSink;
 use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
 use golem_wasm::{FromValue, FromValueAndType, IntoValue, IntoValueAndType};
 use nonempty_collections::nev;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
 use std::time::Instant;
 use test_r::{test, test_dep};
@@ -1524,6 +1536,143 @@ async fn read_from_archive_impl(use_blob: bool) {
     assert_eq!(last10, original_last10);
 }
 
+#[test]
+async fn archive_prefix(_tracing: &Tracing) {
+    archive_prefix_impl(false).await;
+}
+
+#[test]
+async fn blob_archive_prefix(_tracing: &Tracing) {
+    archive_prefix_impl(true).await;
+}
+
+async fn archive_prefix_impl(use_blob: bool) {
+    let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+    let blob_storage = Arc::new(InMemoryBlobStorage::new());
+    let primary_oplog_service = Arc::new(
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            RetryConfig::default(),
+        )
+        .await,
+    );
+    let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+            RetryConfig::default(),
+        ))
+    };
+    let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            2,
+            RetryConfig::default(),
+        ))
+    };
+    // High enough limits so that only archiving the prefix moves entries between the layers
+    let oplog_service = Arc::new(MultiLayerOplogService::new(
+        primary_oplog_service.clone(),
+        nev![secondary_layer.clone(), tertiary_layer.clone()],
+        1000,
+        1000,
+    ));
+    let account_id = AccountId::new();
+    let environment_id = EnvironmentId::new();
+    let agent_id = AgentId {
+        component_id: ComponentId(Uuid::new_v4()),
+        agent_id: "test".to_string(),
+    };
+    let owned_agent_id = OwnedAgentId::new(environment_id, &agent_id);
+
+    let oplog = oplog_service
+        .open(
+            &owned_agent_id,
+            AgentMode::Durable,
+            None,
+            make_agent_metadata(agent_id.clone(), account_id, environment_id),
+            default_last_known_status(),
+            default_execution_status(AgentMode::Durable),
+        )
+        .await;
+
+    let timestamp = Timestamp::now_utc();
+    let entries: Vec<OplogEntry> = (0..100)
+        .map(|i| {
+            OplogEntry::Error {
+                timestamp,
+                error: AgentError::Unknown(i.to_string()),
+                retry_from: OplogIndex::NONE,
+                inside_atomic_region: false,
+                retry_policy_state: None,
+            }
+            .rounded()
+        })
+        .collect();
+
+    let initial_oplog_idx = oplog.current_oplog_index().await;
+    let initial_entry = oplog.read(initial_oplog_idx).await;
+
+    for (n, entry) in entries.iter().enumerate() {
+        oplog.add(entry.clone()).await;
+        if n % 10 == 9 {
+            oplog.commit(CommitLevel::Always).await;
+        }
+    }
+
+    // Moving the initial entry and the first 60 entries to the last layer
+    let last_archived_idx = OplogIndex::from_u64(u64::from(initial_oplog_idx) + 60);
+    let archived = MultiLayerOplog::try_archive_prefix(&oplog, last_archived_idx).await;
+    check!(archived == Some(()));
+
+    let primary_length = primary_oplog_service
+        .open(
+            &owned_agent_id,
+            AgentMode::Durable,
+            None,
+            make_agent_metadata(agent_id.clone(), account_id, environment_id),
+            default_last_known_status(),
+            default_execution_status(AgentMode::Durable),
+        )
+        .await
+        .length()
+        .await;
+    let secondary_length = secondary_layer
+        .open(&owned_agent_id, AgentMode::Durable)
+        .await
+        .length()
+        .await;
+    let tertiary = tertiary_layer
+        .open(&owned_agent_id, AgentMode::Durable)
+        .await;
+
+    check!(primary_length == 40);
+    check!(secondary_length == 0);
+    check!(tertiary.current_oplog_index().await == last_archived_idx);
+
+    // Nothing is lost, the archived entries are still readable
+    check!(oplog.read(initial_oplog_idx).await == initial_entry);
+    let all_entries = oplog_service
+        .read(
+            &owned_agent_id,
+            AgentMode::Durable,
+            initial_oplog_idx.next(),
+            100,
+        )
+        .await;
+    check!(all_entries.into_values().collect::<Vec<_>>() == entries);
+
+    drop(oplog);
+}
+
 #[test]
 async fn read_initial_from_archive(_tracing: &Tracing) {
     crate::services::oplog::tests::read_initial_from_archive_impl(false).await;
@@ -3165,3 +3314,326 @@ async fn scan_for_component_with_no_workers_terminates_immediately(_tracing: &Tr
         );
     }
 }
+
+struct RecordingExportSink {
+    batches: std::sync::Mutex<Vec<(OplogIndex, OplogIndex)>>,
+    failing: AtomicBool,
+}
+
+impl RecordingExportSink {
+    fn new(failing: bool) -> Self {
+        Self {
+            batches: std::sync::Mutex::new(Vec::new()),
+            failing: AtomicBool::new(failing),
+        }
+    }
+
+    fn take_batches(&self) -> Vec<(u64, u64)> {
+        std::mem::take(&mut *self.batches.lock().unwrap())
+            .into_iter()
+            .map(|(first, last)| (first.as_u64(), last.as_u64()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl export::OplogExportSink for RecordingExportSink {
+    async fn export(&self, batch: &export::OplogExportBatch<'_>) -> Result<(), String> {
+        if self.failing.load(Ordering::Acquire) {
+            return Err("sink is down".to_string());
+        }
+        assert_eq!(
+            batch.entries.len() as u64,
+            batch.last_index.as_u64() - batch.first_index.as_u64() + 1
+        );
+        self.batches
+            .lock()
+            .unwrap()
+            .push((batch.first_index, batch.last_index));
+        Ok(())
+    }
+}
+
+/// The exported entries are simple enough to never need component metadata
+struct NoComponentService;
+
+#[async_trait]
+impl crate::services::component::ComponentService for NoComponentService {
+    async fn get(
+        &self,
+        _engine: &wasmtime::Engine,
+        _component_id: ComponentId,
+        _component_revision: ComponentRevision,
+    ) -> Result<
+        (
+            wasmtime::component::Component,
+            golem_service_base::model::component::Component,
+        ),
+        WorkerExecutorError,
+    > {
+        unimplemented!("not needed for oplog export tests")
+    }
+
+    async fn get_metadata(
+        &self,
+        _component_id: ComponentId,
+        _forced_revision: Option<ComponentRevision>,
+    ) -> Result<golem_service_base::model::component::Component, WorkerExecutorError> {
+        unimplemented!("not needed for oplog export tests")
+    }
+
+    async fn resolve_component(
+        &self,
+        _component_reference: String,
+        _resolving_environment: EnvironmentId,
+        _resolving_application: golem_common::model::application::ApplicationId,
+        _resolving_account: AccountId,
+    ) -> Result<Option<ComponentId>, WorkerExecutorError> {
+        Ok(None)
+    }
+
+    async fn all_cached_metadata(&self) -> Vec<golem_service_base::model::component::Component> {
+        Vec::new()
+    }
+
+    async fn invalidate_all_metadata_for_environment(&self, _environment_id: EnvironmentId) {}
+}
+
+#[test]
+async fn oplog_export_checkpoints_positions_per_sink(_tracing: &Tracing) {
+    let oplog_service: Arc<dyn OplogService> = Arc::new(
+        PrimaryOplogService::new(
+            Arc::new(InMemoryIndexedStorage::new()),
+            Arc::new(InMemoryBlobStorage::new()),
+            1,
+            1,
+            100,
+            RetryConfig::default(),
+        )
+        .await,
+    );
+    let components: Arc<dyn crate::services::component::ComponentService> =
+        Arc::new(NoComponentService);
+    let environment_id = EnvironmentId::new();
+    let agent_id = AgentId {
+        component_id: ComponentId(Uuid::new_v4()),
+        agent_id: "test".to_string(),
+    };
+    let owned_agent_id = OwnedAgentId::new(environment_id, &agent_id);
+    let metadata = make_agent_metadata(agent_id.clone(), AccountId::new(), environment_id);
+
+    let healthy_sink = Arc::new(RecordingExportSink::new(false));
+    let failing_sink = Arc::new(RecordingExportSink::new(true));
+    let exports = Arc::new(export::OplogExports::with_sinks(
+        HashMap::from([(
+            environment_id,
+            vec![
+                (
+                    "healthy".to_string(),
+                    healthy_sink.clone() as Arc<dyn export::OplogExportSink>,
+                ),
+                (
+                    "failing".to_string(),
+                    failing_sink.clone() as Arc<dyn export::OplogExportSink>,
+                ),
+            ],
+        )]),
+        Arc::new(crate::storage::keyvalue::memory::InMemoryKeyValueStorage::new()),
+        2,
+    ));
+
+    let oplog = oplog_service
+        .open(
+            &owned_agent_id,
+            AgentMode::Durable,
+            None,
+            metadata.clone(),
+            default_last_known_status(),
+            default_execution_status(AgentMode::Durable),
+        )
+        .await;
+    for _ in 0..5 {
+        oplog.add(OplogEntry::suspend().rounded()).await;
+    }
+    oplog.commit(CommitLevel::Always).await;
+    let committed_tail = oplog.current_oplog_index().await;
+    check!(committed_tail == OplogIndex::from_u64(5));
+
+    let mut agent_export = export::AgentOplogExport::new(
+        exports.clone(),
+        oplog_service.clone(),
+        components.clone(),
+        &metadata,
+    );
+    agent_export.export(committed_tail).await;
+    check!(healthy_sink.take_batches() == vec![(1, 2), (3, 4), (5, 5)]);
+    check!(failing_sink.take_batches().is_empty());
+
+    // Only the sink that failed receives the entries again
+    failing_sink.failing.store(false, Ordering::Release);
+    agent_export.export(committed_tail).await;
+    check!(healthy_sink.take_batches().is_empty());
+    check!(failing_sink.take_batches() == vec![(1, 2), (3, 4), (5, 5)]);
+
+    // The positions are restored from the key-value storage when the agent is loaded again
+    oplog.add(OplogEntry::suspend().rounded()).await;
+    oplog.commit(CommitLevel::Always).await;
+    let committed_tail = oplog.current_oplog_index().await;
+    let mut agent_export = export::AgentOplogExport::new(
+        exports.clone(),
+        oplog_service.clone(),
+        components.clone(),
+        &metadata,
+    );
+    agent_export.export(committed_tail).await;
+    check!(healthy_sink.take_batches() == vec![(6, 6)]);
+    check!(failing_sink.take_batches() == vec![(6, 6)]);
+
+    // A new agent with the same id does not reuse the positions of the previous one
+    let recreated_metadata = AgentMetadata {
+        created_at: Timestamp::from(metadata.created_at.to_millis() + 1),
+        ..metadata.clone()
+    };
+    let mut agent_export = export::AgentOplogExport::new(
+        exports.clone(),
+        oplog_service.clone(),
+        components.clone(),
+        &recreated_metadata,
+    );
+    agent_export.export(committed_tail).await;
+    check!(healthy_sink.take_batches() == vec![(1, 2), (3, 4), (5, 6)]);
+
+    check!(failing_sink.take_batches() == vec![(1, 2), (3, 4), (5, 6)]);
+
+    // Environments without sinks are not exported
+    let other_metadata = make_agent_metadata(agent_id, AccountId::new(), EnvironmentId::new());
+    let mut agent_export =
+        export::AgentOplogExport::new(exports, oplog_service, components, &other_metadata);
+    agent_export.export(committed_tail).await;
+    check!(healthy_sink.take_batches().is_empty());
+    check!(failing_sink.take_batches().is_empty());
+}
+
+/// Serves the oplog export sinks of every environment from a list that can be changed in the test
+struct ConfiguredOplogExportSinks {
+    sinks: std::sync::Mutex<Vec<NamedOplogExportSink>>,
+}
+
+#[async_trait]
+impl EnvironmentStateService for ConfiguredOplogExportSinks {
+    async fn get_agent_deployment(
+        &self,
+        _environment_id: EnvironmentId,
+        _agent_type: &AgentTypeName,
+    ) -> Result<Option<AgentDeploymentDetails>, WorkerExecutorError> {
+        Ok(None)
+    }
+
+    async fn get_agent_secrets(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<HashMap<CanonicalAgentSecretPath, AgentSecret>, WorkerExecutorError> {
+        Ok(HashMap::new())
+    }
+
+    async fn get_retry_policies(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedRetryPolicy>, WorkerExecutorError> {
+        Ok(vec![])
+    }
+
+    async fn get_oplog_export_sinks(
+        &self,
+        _environment_id: EnvironmentId,
+    ) -> Result<Vec<NamedOplogExportSink>, WorkerExecutorError> {
+        Ok(self.sinks.lock().unwrap().clone())
+    }
+}
+
+#[test]
+async fn oplog_export_follows_the_sinks_of_the_environment(_tracing: &Tracing) {
+    let oplog_service: Arc<dyn OplogService> = Arc::new(
+        PrimaryOplogService::new(
+            Arc::new(InMemoryIndexedStorage::new()),
+            Arc::new(InMemoryBlobStorage::new()),
+            1,
+            1,
+            100,
+            RetryConfig::default(),
+        )
+        .await,
+    );
+    let components: Arc<dyn crate::services::component::ComponentService> =
+        Arc::new(NoComponentService);
+    let environment_id = EnvironmentId::new();
+    let agent_id = AgentId {
+        component_id: ComponentId(Uuid::new_v4()),
+        agent_id: "test".to_string(),
+    };
+    let owned_agent_id = OwnedAgentId::new(environment_id, &agent_id);
+    let metadata = make_agent_metadata(agent_id.clone(), AccountId::new(), environment_id);
+
+    let directory = tempfile::tempdir().unwrap();
+    let environment_state = Arc::new(ConfiguredOplogExportSinks {
+        sinks: std::sync::Mutex::new(vec![NamedOplogExportSink {
+            name: "files".to_string(),
+            revision: OplogExportSinkRevision::INITIAL,
+            target: OplogExportTarget::JsonLinesFile(JsonLinesFileExportTarget {}),
+        }]),
+    });
+    let exports = Arc::new(export::OplogExports::new(
+        &OplogExportConfig {
+            max_batch_size: 100,
+            json_lines_directory: directory.path().to_path_buf(),
+        },
+        environment_state.clone(),
+        Arc::new(crate::storage::keyvalue::memory::InMemoryKeyValueStorage::new()),
+        Arc::new(InMemoryBlobStorage::new()),
+    ));
+    let exported_lines = || {
+        let sink_directory = directory
+            .path()
+            .join(environment_id.to_string())
+            .join("files");
+        std::fs::read_dir(sink_directory)
+            .map(|files| {
+                files
+                    .map(|file| std::fs::read_to_string(file.unwrap().path()).unwrap())
+                    .map(|content| content.lines().count())
+                    .sum::<usize>()
+            })
+            .unwrap_or(0)
+    };
+
+    let oplog = oplog_service
+        .open(
+            &owned_agent_id,
+            AgentMode::Durable,
+            None,
+            metadata.clone(),
+            default_last_known_status(),
+            default_execution_status(AgentMode::Durable),
+        )
+        .await;
+    let mut agent_export = export::AgentOplogExport::new(
+        exports.clone(),
+        oplog_service.clone(),
+        components.clone(),
+        &metadata,
+    );
+
+    for _ in 0..3 {
+        oplog.add(OplogEntry::suspend().rounded()).await;
+    }
+    oplog.commit(CommitLevel::Always).await;
+    agent_export.export(oplog.current_oplog_index().await).await;
+    check!(exported_lines() == 3);
+
+    // Entries committed after the sink has been deleted are not exported anymore
+    environment_state.sinks.lock().unwrap().clear();
+    oplog.add(OplogEntry::suspend().rounded()).await;
+    oplog.commit(CommitLevel::Always).await;
+    agent_export.export(oplog.current_oplog_index().await).await;
+    check!(exported_lines() == 3);
+}
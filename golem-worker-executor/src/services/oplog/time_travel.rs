@@ -0,0 +1,215 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::metrics::oplog::record_oplog_call;
+use crate::services::oplog::{CommitLevel, Oplog, OplogService};
+use async_lock::Mutex;
+use async_trait::async_trait;
+use golem_common::model::OwnedAgentId;
+use golem_common::model::agent::AgentMode;
+use golem_common::model::oplog::{
+    OplogEntry, OplogIndex, PayloadId, PersistenceLevel, RawOplogPayload,
+};
+use std::cmp::min;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An oplog that only lives in memory, used to replay an agent up to a past point of its history.
+///
+/// Entries up to `cut_off` are read on demand from the source agent's oplog, except for the
+/// initial entry which is replaced by the replay instance's own `Create` entry. Entries added
+/// during the replay are kept in memory and are dropped together with the oplog, so nothing is
+/// ever written to the oplog storage.
+pub struct TimeTravelOplog {
+    source_agent_id: OwnedAgentId,
+    agent_mode: AgentMode,
+    source_service: Arc<dyn OplogService>,
+    initial_entry: OplogEntry,
+    cut_off: OplogIndex,
+    state: Mutex<TimeTravelOplogState>,
+}
+
+struct TimeTravelOplogState {
+    entries: BTreeMap<OplogIndex, OplogEntry>,
+    last_oplog_idx: OplogIndex,
+    last_committed_idx: OplogIndex,
+    last_added_non_hint_entry: Option<OplogIndex>,
+}
+
+impl TimeTravelOplog {
+    pub fn new(
+        source_service: Arc<dyn OplogService>,
+        source_agent_id: OwnedAgentId,
+        agent_mode: AgentMode,
+        initial_entry: OplogEntry,
+        cut_off: OplogIndex,
+    ) -> Self {
+        Self {
+            source_agent_id,
+            agent_mode,
+            source_service,
+            initial_entry,
+            cut_off,
+            state: Mutex::new(TimeTravelOplogState {
+                entries: BTreeMap::new(),
+                last_oplog_idx: cut_off,
+                last_committed_idx: cut_off,
+                last_added_non_hint_entry: None,
+            }),
+        }
+    }
+
+    async fn read_source(&self, idx: OplogIndex, n: u64) -> BTreeMap<OplogIndex, OplogEntry> {
+        let mut entries = self
+            .source_service
+            .read(&self.source_agent_id, self.agent_mode, idx, n)
+            .await;
+        if let Some(initial) = entries.get_mut(&OplogIndex::INITIAL) {
+            *initial = self.initial_entry.clone();
+        }
+        entries
+    }
+}
+
+impl Debug for TimeTravelOplog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeTravelOplog")
+            .field("source_agent_id", &self.source_agent_id)
+            .field("cut_off", &self.cut_off)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Oplog for TimeTravelOplog {
+    async fn add(&self, entry: OplogEntry) -> OplogIndex {
+        record_oplog_call("add");
+        let mut state = self.state.lock().await;
+        let is_hint = entry.is_hint();
+        state.last_oplog_idx = state.last_oplog_idx.next();
+        let idx = state.last_oplog_idx;
+        state.entries.insert(idx, entry);
+        if !is_hint {
+            state.last_added_non_hint_entry = Some(idx);
+        }
+        idx
+    }
+
+    async fn drop_prefix(&self, _last_dropped_id: OplogIndex) -> u64 {
+        0
+    }
+
+    async fn commit(&self, _level: CommitLevel) -> BTreeMap<OplogIndex, OplogEntry> {
+        record_oplog_call("commit");
+        let mut state = self.state.lock().await;
+        let committed = state
+            .entries
+            .range(state.last_committed_idx.next()..)
+            .map(|(idx, entry)| (*idx, entry.clone()))
+            .collect();
+        state.last_committed_idx = state.last_oplog_idx;
+        committed
+    }
+
+    async fn current_oplog_index(&self) -> OplogIndex {
+        self.state.lock().await.last_oplog_idx
+    }
+
+    async fn last_added_non_hint_entry(&self) -> Option<OplogIndex> {
+        self.state.lock().await.last_added_non_hint_entry
+    }
+
+    async fn wait_for_replicas(&self, _replicas: u8, _timeout: Duration) -> bool {
+        true
+    }
+
+    async fn read(&self, oplog_index: OplogIndex) -> OplogEntry {
+        record_oplog_call("read");
+        if oplog_index == OplogIndex::INITIAL {
+            self.initial_entry.clone()
+        } else if oplog_index <= self.cut_off {
+            self.read_source(oplog_index, 1)
+                .await
+                .remove(&oplog_index)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Missing oplog entry {oplog_index} of {} for time-travel replay",
+                        self.source_agent_id
+                    )
+                })
+        } else {
+            self.state
+                .lock()
+                .await
+                .entries
+                .get(&oplog_index)
+                .cloned()
+                .unwrap_or_else(|| panic!("Missing time-travel replay oplog entry {oplog_index}"))
+        }
+    }
+
+    async fn read_many(&self, oplog_index: OplogIndex, n: u64) -> BTreeMap<OplogIndex, OplogEntry> {
+        record_oplog_call("read_many");
+        if n == 0 {
+            return BTreeMap::new();
+        }
+        let last = oplog_index.range_end(n);
+
+        let mut result = if oplog_index <= self.cut_off {
+            let source_last = min(last, self.cut_off);
+            self.read_source(
+                oplog_index,
+                Into::<u64>::into(source_last) - Into::<u64>::into(oplog_index) + 1,
+            )
+            .await
+        } else {
+            BTreeMap::new()
+        };
+
+        if last > self.cut_off {
+            let state = self.state.lock().await;
+            result.extend(
+                state
+                    .entries
+                    .range(oplog_index..=last)
+                    .map(|(idx, entry)| (*idx, entry.clone())),
+            );
+        }
+        result
+    }
+
+    async fn length(&self) -> u64 {
+        self.state.lock().await.last_oplog_idx.into()
+    }
+
+    /// Payloads of the replay are kept inline, as there is no storage to upload them to.
+    async fn upload_raw_payload(&self, data: Vec<u8>) -> Result<RawOplogPayload, String> {
+        Ok(RawOplogPayload::SerializedInline(data))
+    }
+
+    /// Only entries copied from the source agent can reference external payloads.
+    async fn download_raw_payload(
+        &self,
+        payload_id: PayloadId,
+        md5_hash: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        self.source_service
+            .download_raw_payload(&self.source_agent_id, self.agent_mode, payload_id, md5_hash)
+            .await
+    }
+
+    async fn switch_persistence_level(&self, _mode: PersistenceLevel) {}
+}
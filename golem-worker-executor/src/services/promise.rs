@@ -31,6 +31,8 @@ use desert_rust::BinaryCodec;
 use golem_common::model::agent::Principal;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::OplogIndex;
+use golem_common::model::promise::{PromiseOptions, PromiseOutcome};
+use golem_common::model::worker::{PromiseMetadata, PromiseState};
 use golem_common::model::{AgentId, AgentStatus, OwnedAgentId, PromiseId};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use std::collections::HashMap;
@@ -50,7 +52,7 @@ pub struct PromiseHandle {
 #[derive(Debug)]
 pub struct PromiseHandleInner {
     notify: Notify,
-    state: Mutex<Option<Vec<u8>>>,
+    state: Mutex<Option<PromiseOutcome>>,
 }
 
 impl PromiseHandle {
@@ -78,14 +80,14 @@ impl PromiseHandle {
         self.inner.notify.notified().await;
     }
 
-    pub async fn get(&self) -> Option<Vec<u8>> {
+    pub async fn get(&self) -> Option<PromiseOutcome> {
         let state = self.inner.state.lock().await;
         state.clone()
     }
 
-    pub async fn complete(&self, data: Vec<u8>) {
+    pub async fn complete(&self, outcome: PromiseOutcome) {
         let mut state = self.inner.state.lock().await;
-        *state = Some(data);
+        *state = Some(outcome);
         self.inner.notify.notify_waiters();
     }
 }
@@ -94,18 +96,34 @@ impl PromiseHandle {
 #[async_trait]
 pub trait PromiseService: Send + Sync {
     /// poll and complete for a given promise must be called on the same
-    async fn create(&self, agent_id: &AgentId, oplog_idx: OplogIndex) -> PromiseId;
+    async fn create(
+        &self,
+        agent_id: &AgentId,
+        oplog_idx: OplogIndex,
+        options: &PromiseOptions,
+    ) -> PromiseId;
 
     async fn poll(&self, promise_id: PromiseId) -> Result<PromiseHandle, WorkerExecutorError>;
 
     /// Completes a promise with the given payload.
     /// If the promise was not completed before, it returns true. If the promise was completed before, it returns false.
+    /// Fails if the promise has a payload type and the payload does not match it.
     async fn complete(
         &self,
         promise_id: PromiseId,
         data: Vec<u8>,
     ) -> Result<bool, WorkerExecutorError>;
 
+    /// Cancels a promise. Returns false if the promise was already completed, timed out or cancelled.
+    async fn cancel(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError>;
+
+    /// Fails a promise because its deadline passed. Returns false if the promise was already completed,
+    /// timed out or cancelled.
+    async fn time_out(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError>;
+
+    /// Lists the promises created by the given agent
+    async fn list(&self, agent_id: &AgentId) -> Result<Vec<PromiseMetadata>, WorkerExecutorError>;
+
     // Hint the promise service that a promise might be dropped, making sure it collects any dangling references
     async fn cleanup(&self);
 }
@@ -130,9 +148,17 @@ impl LazyPromiseService {
 
 #[async_trait]
 impl PromiseService for LazyPromiseService {
-    async fn create(&self, agent_id: &AgentId, oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        agent_id: &AgentId,
+        oplog_idx: OplogIndex,
+        options: &PromiseOptions,
+    ) -> PromiseId {
         let lock = self.0.read().await;
-        lock.as_ref().unwrap().create(agent_id, oplog_idx).await
+        lock.as_ref()
+            .unwrap()
+            .create(agent_id, oplog_idx, options)
+            .await
     }
 
     async fn poll(&self, promise_id: PromiseId) -> Result<PromiseHandle, WorkerExecutorError> {
@@ -161,6 +187,21 @@ impl PromiseService for LazyPromiseService {
         result
     }
 
+    async fn cancel(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError> {
+        let lock = self.0.read().await;
+        lock.as_ref().unwrap().cancel(promise_id).await
+    }
+
+    async fn time_out(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError> {
+        let lock = self.0.read().await;
+        lock.as_ref().unwrap().time_out(promise_id).await
+    }
+
+    async fn list(&self, agent_id: &AgentId) -> Result<Vec<PromiseMetadata>, WorkerExecutorError> {
+        let lock = self.0.read().await;
+        lock.as_ref().unwrap().list(agent_id).await
+    }
+
     async fn cleanup(&self) {
         let lock = self.0.read().await;
         lock.as_ref().unwrap().cleanup().await
@@ -195,13 +236,13 @@ impl PromiseRegistry {
         })
     }
 
-    async fn complete(&mut self, id: &PromiseId, data: Vec<u8>) {
+    async fn complete(&mut self, id: &PromiseId, outcome: PromiseOutcome) {
         if let Some(weak) = self.handles.get(id)
             && let Some(inner) = weak.upgrade()
         {
             tokio::spawn(async move {
                 let mut state = inner.state.lock().await;
-                *state = Some(data.clone());
+                *state = Some(outcome);
                 inner.notify.notify_waiters();
             });
         }
@@ -257,17 +298,114 @@ impl DefaultPromiseService {
                 panic!("failed to check if promise {promise_id} exists in Redis: {err}")
             })
     }
+
+    async fn options(&self, promise_id: &PromiseId) -> PromiseOptions {
+        self.key_value_storage
+            .with_entity("promise", "complete", "promise")
+            .get(
+                KeyValueStorageNamespace::Promise {
+                    agent_id: promise_id.agent_id.clone(),
+                },
+                &get_promise_options_redis_key(promise_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get options of promise {promise_id} from Redis: {err}")
+            })
+            .unwrap_or_default()
+    }
+
+    /// Stores the final state of a promise unless it already has one, and wakes up its owner.
+    async fn resolve(
+        &self,
+        promise_id: PromiseId,
+        state: RedisPromiseState,
+    ) -> Result<bool, WorkerExecutorError> {
+        let key = get_promise_result_redis_key(&promise_id);
+
+        let written: bool = self
+            .key_value_storage
+            .with_entity("promise", "complete", "promise")
+            .set_if_not_exists(
+                KeyValueStorageNamespace::Promise {
+                    agent_id: promise_id.agent_id.clone(),
+                },
+                &key,
+                &state,
+            )
+            .await
+            .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
+
+        // Also wake any in-memory handle, ensuring that still running workers that wait on the pollable can continue.
+        // The handle always gets the first stored outcome, so a late cancellation does not override a completion.
+        let outcome = if written {
+            state.into_outcome()
+        } else {
+            self.key_value_storage
+                .with_entity("promise", "complete", "promise")
+                .get::<RedisPromiseState>(
+                    KeyValueStorageNamespace::Promise {
+                        agent_id: promise_id.agent_id.clone(),
+                    },
+                    &key,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to get promise {promise_id} from Redis: {err}")
+                })
+                .and_then(RedisPromiseState::into_outcome)
+        };
+        if let Some(outcome) = outcome {
+            let mut reg = self.registry.lock().await;
+            reg.complete(&promise_id, outcome).await;
+        }
+
+        // Decrement the pending count on the first successful completion.
+        if written {
+            crate::metrics::promises::dec_promise_pending_count();
+        }
+
+        // Wake up the worker that owns the promise, ensuring that it resumes its work.
+        // We do this unconditionally here as the only reason complete will be called again during replay is if we managed to write
+        // the result to redis, but failed before the worker could persist the result.
+        self.worker_access
+            .activate_worker_if_needed(&promise_id)
+            .await?;
+
+        Ok(written)
+    }
 }
 
 #[async_trait]
 impl PromiseService for DefaultPromiseService {
-    async fn create(&self, agent_id: &AgentId, oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        agent_id: &AgentId,
+        oplog_idx: OplogIndex,
+        options: &PromiseOptions,
+    ) -> PromiseId {
         let promise_id = PromiseId {
             agent_id: agent_id.clone(),
             oplog_idx,
         };
         debug!("Created promise {promise_id}");
 
+        if !options.is_default() {
+            self.key_value_storage
+                .with_entity("promise", "create", "promise")
+                .set(
+                    KeyValueStorageNamespace::Promise {
+                        agent_id: agent_id.clone(),
+                    },
+                    &get_promise_options_redis_key(&promise_id),
+                    options,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to set options of promise {promise_id} in Redis: {err}")
+                });
+        }
+
         let key = get_promise_redis_key(&promise_id);
         self.key_value_storage
             .with_entity("promise", "create", "promise")
@@ -309,7 +447,7 @@ impl PromiseService for DefaultPromiseService {
         };
 
         // Check if already completed in Redis
-        if let Some(RedisPromiseState::Complete(data)) = self
+        if let Some(outcome) = self
             .key_value_storage
             .with_entity("promise", "poll", "promise")
             .get(
@@ -320,8 +458,9 @@ impl PromiseService for DefaultPromiseService {
             )
             .await
             .unwrap_or_else(|err| panic!("failed to get promise {promise_id} from Redis: {err}"))
+            .and_then(RedisPromiseState::into_outcome)
         {
-            handle.complete(data).await;
+            handle.complete(outcome).await;
         }
 
         Ok(handle)
@@ -332,44 +471,106 @@ impl PromiseService for DefaultPromiseService {
         promise_id: PromiseId,
         data: Vec<u8>,
     ) -> Result<bool, WorkerExecutorError> {
-        let key = get_promise_result_redis_key(&promise_id);
-
         if !self.exists(&promise_id).await {
             return Err(WorkerExecutorError::PromiseNotFound { promise_id });
         };
 
-        let written: bool = self
-            .key_value_storage
-            .with_entity("promise", "complete", "promise")
-            .set_if_not_exists(
-                KeyValueStorageNamespace::Promise {
-                    agent_id: promise_id.agent_id.clone(),
-                },
-                &key,
-                &RedisPromiseState::Complete(data.clone()),
-            )
+        self.options(&promise_id)
             .await
-            .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
+            .validate_payload(&data)
+            .map_err(WorkerExecutorError::invalid_request)?;
 
-        // Also wake any in-memory handle, ensuring that still running workers that wait on the pollable can continue
-        {
-            let mut reg = self.registry.lock().await;
-            reg.complete(&promise_id, data.clone()).await;
-        }
+        self.resolve(promise_id, RedisPromiseState::Complete(data))
+            .await
+    }
 
-        // Decrement the pending count on the first successful completion.
-        if written {
-            crate::metrics::promises::dec_promise_pending_count();
-        }
+    async fn cancel(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError> {
+        if !self.exists(&promise_id).await {
+            return Err(WorkerExecutorError::PromiseNotFound { promise_id });
+        };
 
-        // Wake up the worker that owns the promise, ensuring that it resumes its work.
-        // We do this unconditionally here as the only reason complete will be called again during replay is if we managed to write
-        // the result to redis, but failed before the worker could persist the result.
-        self.worker_access
-            .activate_worker_if_needed(&promise_id)
-            .await?;
+        self.resolve(promise_id, RedisPromiseState::Cancelled).await
+    }
 
-        Ok(written)
+    async fn time_out(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError> {
+        if !self.exists(&promise_id).await {
+            return Err(WorkerExecutorError::PromiseNotFound { promise_id });
+        };
+
+        self.resolve(promise_id, RedisPromiseState::TimedOut).await
+    }
+
+    async fn list(&self, agent_id: &AgentId) -> Result<Vec<PromiseMetadata>, WorkerExecutorError> {
+        let namespace = KeyValueStorageNamespace::Promise {
+            agent_id: agent_id.clone(),
+        };
+        let prefix = format!("{}:", agent_id.to_redis_key());
+
+        let mut oplog_indices = self
+            .key_value_storage
+            .with("promise", "list")
+            .keys(namespace.clone())
+            .await
+            .map_err(WorkerExecutorError::runtime)?
+            .into_iter()
+            .filter_map(|key| {
+                key.strip_prefix(&prefix)
+                    .and_then(|rest| rest.parse::<u64>().ok())
+                    .map(OplogIndex::from_u64)
+            })
+            .collect::<Vec<_>>();
+        oplog_indices.sort();
+
+        let promise_ids = oplog_indices
+            .into_iter()
+            .map(|oplog_idx| PromiseId {
+                agent_id: agent_id.clone(),
+                oplog_idx,
+            })
+            .collect::<Vec<_>>();
+
+        let states: Vec<Option<RedisPromiseState>> = self
+            .key_value_storage
+            .with_entity("promise", "list", "promise")
+            .get_many(
+                namespace.clone(),
+                promise_ids
+                    .iter()
+                    .map(get_promise_result_redis_key)
+                    .collect(),
+            )
+            .await
+            .map_err(WorkerExecutorError::runtime)?;
+
+        let options: Vec<Option<PromiseOptions>> = self
+            .key_value_storage
+            .with_entity("promise", "list", "promise")
+            .get_many(
+                namespace,
+                promise_ids
+                    .iter()
+                    .map(get_promise_options_redis_key)
+                    .collect(),
+            )
+            .await
+            .map_err(WorkerExecutorError::runtime)?;
+
+        Ok(promise_ids
+            .into_iter()
+            .zip(states)
+            .zip(options)
+            .map(|((promise_id, state), options)| {
+                let options = options.unwrap_or_default();
+                PromiseMetadata {
+                    oplog_index: promise_id.oplog_idx,
+                    state: state
+                        .map(|state| state.promise_state())
+                        .unwrap_or(PromiseState::Pending),
+                    deadline: options.deadline,
+                    payload_type: options.payload_type,
+                }
+            })
+            .collect())
     }
 
     async fn cleanup(&self) {
@@ -521,20 +722,63 @@ fn get_promise_redis_key(promise_id: &PromiseId) -> String {
     promise_id.to_redis_key()
 }
 
+/// Whether a key of the promise namespace belongs to a promise of the given agent. Some backends
+/// keep the promises of all agents in a single namespace.
+pub(crate) fn is_promise_key_of(agent_id: &AgentId, key: &str) -> bool {
+    let Some(rest) = key
+        .strip_prefix(&agent_id.to_redis_key())
+        .and_then(|rest| rest.strip_prefix(':'))
+    else {
+        return false;
+    };
+    let (oplog_idx, suffix) = match rest.split_once(':') {
+        Some((oplog_idx, suffix)) => (oplog_idx, Some(suffix)),
+        None => (rest, None),
+    };
+    oplog_idx.parse::<u64>().is_ok() && matches!(suffix, None | Some("completed" | "options"))
+}
+
 fn get_promise_result_redis_key(promise_id: &PromiseId) -> String {
     format!("{}:completed", promise_id.to_redis_key())
 }
 
+fn get_promise_options_redis_key(promise_id: &PromiseId) -> String {
+    format!("{}:options", promise_id.to_redis_key())
+}
+
 #[derive(Debug, Eq, PartialEq, BinaryCodec)]
 #[desert(evolution())]
 pub enum RedisPromiseState {
     Pending,
     Complete(Vec<u8>),
+    TimedOut,
+    Cancelled,
+}
+
+impl RedisPromiseState {
+    fn into_outcome(self) -> Option<PromiseOutcome> {
+        match self {
+            RedisPromiseState::Pending => None,
+            RedisPromiseState::Complete(data) => Some(PromiseOutcome::Completed(data)),
+            RedisPromiseState::TimedOut => Some(PromiseOutcome::TimedOut),
+            RedisPromiseState::Cancelled => Some(PromiseOutcome::Cancelled),
+        }
+    }
+
+    fn promise_state(&self) -> PromiseState {
+        match self {
+            RedisPromiseState::Pending => PromiseState::Pending,
+            RedisPromiseState::Complete(_) => PromiseState::Completed,
+            RedisPromiseState::TimedOut => PromiseState::TimedOut,
+            RedisPromiseState::Cancelled => PromiseState::Cancelled,
+        }
+    }
 }
 
 #[cfg(test)]
 pub struct PromiseServiceMock {
     completed: Arc<Mutex<HashSet<PromiseId>>>,
+    timed_out: Arc<Mutex<HashSet<PromiseId>>>,
 }
 
 #[cfg(test)]
@@ -549,18 +793,28 @@ impl PromiseServiceMock {
     pub fn new() -> Self {
         Self {
             completed: Arc::new(Mutex::new(HashSet::new())),
+            timed_out: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
     pub async fn all_completed(&self) -> HashSet<PromiseId> {
         self.completed.lock().await.clone()
     }
+
+    pub async fn all_timed_out(&self) -> HashSet<PromiseId> {
+        self.timed_out.lock().await.clone()
+    }
 }
 
 #[cfg(test)]
 #[async_trait]
 impl PromiseService for PromiseServiceMock {
-    async fn create(&self, _agent_id: &AgentId, _oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        _agent_id: &AgentId,
+        _oplog_idx: OplogIndex,
+        _options: &PromiseOptions,
+    ) -> PromiseId {
         unimplemented!()
     }
 
@@ -577,5 +831,18 @@ impl PromiseService for PromiseServiceMock {
         Ok(true)
     }
 
+    async fn cancel(&self, _promise_id: PromiseId) -> Result<bool, WorkerExecutorError> {
+        unimplemented!()
+    }
+
+    async fn time_out(&self, promise_id: PromiseId) -> Result<bool, WorkerExecutorError> {
+        self.timed_out.lock().await.insert(promise_id);
+        Ok(true)
+    }
+
+    async fn list(&self, _agent_id: &AgentId) -> Result<Vec<PromiseMetadata>, WorkerExecutorError> {
+        unimplemented!()
+    }
+
     async fn cleanup(&self) {}
 }
@@ -325,6 +325,42 @@ impl DbTransaction<IgniteType> for IgniteDbTransaction {
         Ok(Arc::new(IgniteResultStream::new(columns, qs)))
     }
 
+    async fn execute_batch(
+        &self,
+        statement: &str,
+        params: Vec<Vec<DbValue>>,
+    ) -> Result<Vec<u64>, RdbmsError>
+    where
+        DbValue: 'async_trait,
+    {
+        let mut guard = self.inner.lock().await;
+        let tx = guard
+            .as_mut()
+            .ok_or_else(|| RdbmsError::Other("transaction already finalised".to_string()))?;
+        execute_batch_in_transaction(tx, statement, params).await
+    }
+
+    async fn prepare(&self, _statement: &str) -> Result<(), RdbmsError> {
+        // The thin client protocol has no separate prepare step, statements are parsed on first execution
+        let guard = self.inner.lock().await;
+        guard
+            .as_ref()
+            .map(|_| ())
+            .ok_or_else(|| RdbmsError::Other("transaction already finalised".to_string()))
+    }
+
+    async fn savepoint(&self, _name: &str) -> Result<(), RdbmsError> {
+        Err(savepoints_not_supported())
+    }
+
+    async fn release_savepoint(&self, _name: &str) -> Result<(), RdbmsError> {
+        Err(savepoints_not_supported())
+    }
+
+    async fn rollback_to_savepoint(&self, _name: &str) -> Result<(), RdbmsError> {
+        Err(savepoints_not_supported())
+    }
+
     async fn pre_commit(&self) -> Result<(), RdbmsError> {
         Ok(())
     }
@@ -376,6 +412,29 @@ impl DbTransaction<IgniteType> for IgniteDbTransaction {
     }
 }
 
+fn savepoints_not_supported() -> RdbmsError {
+    RdbmsError::Other("savepoints are not supported by Apache Ignite 2".to_string())
+}
+
+async fn execute_batch_in_transaction(
+    tx: &mut IgniteTransaction,
+    statement: &str,
+    params: Vec<Vec<DbValue>>,
+) -> Result<Vec<u64>, RdbmsError> {
+    let mut result = Vec::with_capacity(params.len());
+    for row_params in params {
+        let ignite_params: Vec<IgniteValue> =
+            row_params.into_iter().map(service_to_ignite).collect();
+        let rows = tx
+            .execute(statement, ignite_params)
+            .await
+            .map_err(RdbmsError::from)?
+            .rows_affected;
+        result.push(if rows < 0 { 0u64 } else { rows as u64 });
+    }
+    Ok(result)
+}
+
 // ── IgniteRdbms ───────────────────────────────────────────────────────────────
 
 type ClientPool = Arc<DashMap<RdbmsPoolKey, (Arc<IgniteClient>, HashSet<AgentId>)>>;
@@ -538,6 +597,62 @@ impl Rdbms<IgniteType> for IgniteRdbms {
         Ok(convert_query_result(result))
     }
 
+    async fn execute_batch(
+        &self,
+        key: &RdbmsPoolKey,
+        worker_id: &AgentId,
+        statement: &str,
+        params: Vec<Vec<DbValue>>,
+    ) -> Result<Vec<u64>, RdbmsError>
+    where
+        DbValue: 'async_trait,
+    {
+        debug!(
+            pool_key = key.to_string(),
+            "ignite execute_batch: {statement}, rows: {}",
+            params.len()
+        );
+        let client = self.get_or_create_client(key, worker_id).await?;
+        let mut tx = client.begin_transaction().await.map_err(|e| {
+            error!(
+                pool_key = key.to_string(),
+                "ignite execute_batch error: {e}"
+            );
+            RdbmsError::from(e)
+        })?;
+        match execute_batch_in_transaction(&mut tx, statement, params).await {
+            Ok(result) => {
+                tx.commit().await.map_err(|e| {
+                    error!(
+                        pool_key = key.to_string(),
+                        "ignite execute_batch error: {e}"
+                    );
+                    RdbmsError::from(e)
+                })?;
+                Ok(result)
+            }
+            Err(e) => {
+                error!(
+                    pool_key = key.to_string(),
+                    "ignite execute_batch error: {e}"
+                );
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn prepare(
+        &self,
+        key: &RdbmsPoolKey,
+        worker_id: &AgentId,
+        statement: &str,
+    ) -> Result<(), RdbmsError> {
+        debug!(pool_key = key.to_string(), "ignite prepare: {statement}");
+        // The thin client protocol has no separate prepare step, statements are parsed on first execution
+        self.get_or_create_client(key, worker_id).await.map(|_| ())
+    }
+
     async fn begin_transaction(
         &self,
         key: &RdbmsPoolKey,
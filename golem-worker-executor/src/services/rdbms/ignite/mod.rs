@@ -47,6 +47,10 @@ impl RdbmsType for IgniteType {
     fn durability_result_stream_interface() -> &'static str {
         "rdbms::ignite2::db-result-stream"
     }
+
+    fn durability_prepared_statement_interface() -> &'static str {
+        "rdbms::ignite2::db-prepared-statement"
+    }
 }
 
 impl Display for IgniteType {
@@ -56,6 +56,7 @@ pub trait RdbmsType: Debug + Display + Default + PartialEq + Clone + Send {
     fn durability_connection_interface() -> &'static str;
     fn durability_transaction_interface() -> &'static str;
     fn durability_result_stream_interface() -> &'static str;
+    fn durability_prepared_statement_interface() -> &'static str;
 }
 
 #[derive(Clone)]
@@ -97,6 +98,25 @@ pub trait DbTransaction<T: RdbmsType> {
     where
         <T as RdbmsType>::DbValue: 'async_trait;
 
+    async fn execute_batch(
+        &self,
+        statement: &str,
+        params: Vec<Vec<T::DbValue>>,
+    ) -> Result<Vec<u64>, RdbmsError>
+    where
+        <T as RdbmsType>::DbValue: 'async_trait;
+
+    /// Validates the statement on the transaction's connection. No handle is returned; drivers
+    /// with a per-connection statement cache reuse the parsed statement for later executions.
+    async fn prepare(&self, statement: &str) -> Result<(), RdbmsError>;
+
+    /// Savepoints are only available on Postgres and MySQL; Ignite 2.x rejects them.
+    async fn savepoint(&self, name: &str) -> Result<(), RdbmsError>;
+
+    async fn release_savepoint(&self, name: &str) -> Result<(), RdbmsError>;
+
+    async fn rollback_to_savepoint(&self, name: &str) -> Result<(), RdbmsError>;
+
     async fn pre_commit(&self) -> Result<(), RdbmsError>;
 
     async fn pre_rollback(&self) -> Result<(), RdbmsError>;
@@ -146,6 +166,26 @@ pub trait Rdbms<T: RdbmsType>: Send + Sync {
     where
         <T as RdbmsType>::DbValue: 'async_trait;
 
+    /// Executes the statement once per parameter row; all rows are applied atomically.
+    async fn execute_batch(
+        &self,
+        key: &RdbmsPoolKey,
+        agent_id: &AgentId,
+        statement: &str,
+        params: Vec<Vec<T::DbValue>>,
+    ) -> Result<Vec<u64>, RdbmsError>
+    where
+        <T as RdbmsType>::DbValue: 'async_trait;
+
+    /// Validates the statement on a pooled connection. This is a validation step only: later
+    /// executions may run on a different connection of the pool and are parsed again there.
+    async fn prepare(
+        &self,
+        key: &RdbmsPoolKey,
+        agent_id: &AgentId,
+        statement: &str,
+    ) -> Result<(), RdbmsError>;
+
     async fn begin_transaction(
         &self,
         key: &RdbmsPoolKey,
@@ -386,6 +426,32 @@ impl From<SerializableRdbmsError> for RdbmsError {
     }
 }
 
+/// Savepoint names are interpolated into SQL, so only plain identifiers are accepted.
+/// Names starting with `_sqlx_` are reserved for the savepoints sqlx uses for nested transactions.
+pub(crate) fn validate_savepoint_name(name: &str) -> Result<(), RdbmsError> {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(first) => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && name.len() <= 63
+        }
+        None => false,
+    };
+
+    if !valid {
+        Err(RdbmsError::QueryParameterFailure(format!(
+            "Invalid savepoint name '{name}', expected an SQL identifier of at most 63 characters"
+        )))
+    } else if name.starts_with("_sqlx_") {
+        Err(RdbmsError::QueryParameterFailure(format!(
+            "Savepoint name '{name}' is reserved"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
 impl From<WorkerExecutorError> for RdbmsError {
     fn from(value: WorkerExecutorError) -> Self {
         Self::other_response_failure(value)
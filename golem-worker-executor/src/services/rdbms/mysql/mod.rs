@@ -47,6 +47,10 @@ impl RdbmsType for MysqlType {
     fn durability_result_stream_interface() -> &'static str {
         "rdbms::mysql::db-result-stream"
     }
+
+    fn durability_prepared_statement_interface() -> &'static str {
+        "rdbms::mysql::db-prepared-statement"
+    }
 }
 
 impl Display for MysqlType {
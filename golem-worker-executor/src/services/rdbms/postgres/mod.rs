@@ -47,6 +47,10 @@ impl RdbmsType for PostgresType {
     fn durability_result_stream_interface() -> &'static str {
         "rdbms::postgres::db-result-stream"
     }
+
+    fn durability_prepared_statement_interface() -> &'static str {
+        "rdbms::postgres::db-prepared-statement"
+    }
 }
 
 impl Display for PostgresType {
@@ -16,7 +16,7 @@ use crate::services::golem_config::{RdbmsConfig, RdbmsPoolConfig, RdbmsQueryConf
 use crate::services::rdbms::metrics::record_rdbms_metrics;
 use crate::services::rdbms::{
     DbResult, DbResultStream, DbRow, DbTransaction, Rdbms, RdbmsError, RdbmsStatus,
-    RdbmsTransactionStatus, RdbmsType,
+    RdbmsTransactionStatus, RdbmsType, validate_savepoint_name,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -26,7 +26,7 @@ use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode,
 use golem_common::model::{AgentId, RdbmsPoolKey, TransactionId};
 use itertools::Either;
 use sqlx::pool::PoolConnection;
-use sqlx::{Database, Describe, Execute, Pool, Row, TransactionManager};
+use sqlx::{Database, Describe, Execute, Executor, Pool, Row, TransactionManager};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::fmt::Formatter;
@@ -327,6 +327,84 @@ where
         self.record_metrics("query", start, result)
     }
 
+    async fn execute_batch(
+        &self,
+        key: &RdbmsPoolKey,
+        agent_id: &AgentId,
+        statement: &str,
+        params: Vec<Vec<T::DbValue>>,
+    ) -> Result<Vec<u64>, RdbmsError>
+    where
+        <T as RdbmsType>::DbValue: 'async_trait,
+    {
+        let start = Instant::now();
+        debug!(
+            rdbms_type = self.rdbms_type.to_string(),
+            pool_key = key.to_string(),
+            "execute batch - statement: {}, rows: {}",
+            statement,
+            params.len()
+        );
+
+        let pool = self.get_or_create(key, agent_id).await;
+        let result = match pool {
+            Ok(pool) => {
+                execute_batch_in_transaction::<T, DB>(statement, params, pool.deref()).await
+            }
+            Err(e) => Err(e),
+        };
+
+        let result = result.map_err(|e| {
+            error!(
+                rdbms_type = self.rdbms_type.to_string(),
+                pool_key = key.to_string(),
+                "execute batch - statement: {}, error: {}",
+                statement,
+                e
+            );
+            e
+        });
+        self.record_metrics("execute-batch", start, result)
+    }
+
+    async fn prepare(
+        &self,
+        key: &RdbmsPoolKey,
+        agent_id: &AgentId,
+        statement: &str,
+    ) -> Result<(), RdbmsError> {
+        let start = Instant::now();
+        debug!(
+            rdbms_type = self.rdbms_type.to_string(),
+            pool_key = key.to_string(),
+            "prepare - statement: {}",
+            statement
+        );
+
+        let pool = self.get_or_create(key, agent_id).await;
+        let result = match pool {
+            Ok(pool) => pool
+                .deref()
+                .prepare(statement)
+                .await
+                .map(|_| ())
+                .map_err(RdbmsError::query_execution_failure),
+            Err(e) => Err(e),
+        };
+
+        let result = result.map_err(|e| {
+            error!(
+                rdbms_type = self.rdbms_type.to_string(),
+                pool_key = key.to_string(),
+                "prepare - statement: {}, error: {}",
+                statement,
+                e
+            );
+            e
+        });
+        self.record_metrics("prepare", start, result)
+    }
+
     async fn begin_transaction(
         &self,
         key: &RdbmsPoolKey,
@@ -561,6 +639,55 @@ where
     ) -> Result<R, RdbmsError> {
         record_rdbms_metrics(&self.rdbms_type, name, start, result)
     }
+
+    /// Runs one of the `SAVEPOINT` family of statements, which Postgres and MySQL share.
+    async fn savepoint_command(
+        &self,
+        name: &'static str,
+        command: &str,
+        savepoint: &str,
+    ) -> Result<(), RdbmsError>
+    where
+        for<'c> &'c mut <DB as Database>::Connection: sqlx::Executor<'c, Database = DB>,
+    {
+        let start = Instant::now();
+        debug!(
+            rdbms_type = self.rdbms_type.to_string(),
+            pool_key = self.pool_key.to_string(),
+            transaction_id = self.transaction_id.to_string(),
+            "{} - savepoint: {}",
+            name,
+            savepoint
+        );
+
+        let result = match validate_savepoint_name(savepoint) {
+            Ok(()) => {
+                let sql = format!("{command} {savepoint}");
+                let mut tx_conn = self.tx_connection.0.lock().await;
+                tx_conn
+                    .connection
+                    .execute(sql.as_str())
+                    .await
+                    .map(|_| ())
+                    .map_err(RdbmsError::query_execution_failure)
+            }
+            Err(e) => Err(e),
+        };
+
+        let result = result.map_err(|e| {
+            error!(
+                rdbms_type = self.rdbms_type.to_string(),
+                pool_key = self.pool_key.to_string(),
+                transaction_id = self.transaction_id.to_string(),
+                "{} - savepoint: {}, error: {}",
+                name,
+                savepoint,
+                e
+            );
+            e
+        });
+        self.record_metrics(name, start, result)
+    }
 }
 
 impl<'p, DB: Database> sqlx::Executor<'p> for SqlxDbTransactionConnection<DB>
@@ -756,6 +883,86 @@ where
         self.record_metrics("query-stream", start, result)
     }
 
+    async fn execute_batch(
+        &self,
+        statement: &str,
+        params: Vec<Vec<T::DbValue>>,
+    ) -> Result<Vec<u64>, RdbmsError>
+    where
+        <T as RdbmsType>::DbValue: 'async_trait,
+    {
+        let start = Instant::now();
+        debug!(
+            rdbms_type = self.rdbms_type.to_string(),
+            pool_key = self.pool_key.to_string(),
+            transaction_id = self.transaction_id.to_string(),
+            "execute batch - statement: {}, rows: {}",
+            statement,
+            params.len()
+        );
+
+        let mut tx_conn = self.tx_connection.0.lock().await;
+        let result = execute_batch::<T, DB>(statement, params, tx_conn.connection.deref_mut())
+            .await
+            .map_err(|e| {
+                error!(
+                    rdbms_type = self.rdbms_type.to_string(),
+                    pool_key = self.pool_key.to_string(),
+                    transaction_id = self.transaction_id.to_string(),
+                    "execute batch - statement: {}, error: {}",
+                    statement,
+                    e
+                );
+                e
+            });
+        self.record_metrics("execute-batch", start, result)
+    }
+
+    async fn prepare(&self, statement: &str) -> Result<(), RdbmsError> {
+        let start = Instant::now();
+        debug!(
+            rdbms_type = self.rdbms_type.to_string(),
+            pool_key = self.pool_key.to_string(),
+            transaction_id = self.transaction_id.to_string(),
+            "prepare - statement: {}",
+            statement
+        );
+
+        let mut tx_conn = self.tx_connection.0.lock().await;
+        let result = tx_conn
+            .connection
+            .prepare(statement)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                let e = RdbmsError::query_execution_failure(e);
+                error!(
+                    rdbms_type = self.rdbms_type.to_string(),
+                    pool_key = self.pool_key.to_string(),
+                    transaction_id = self.transaction_id.to_string(),
+                    "prepare - statement: {}, error: {}",
+                    statement,
+                    e
+                );
+                e
+            });
+        self.record_metrics("prepare", start, result)
+    }
+
+    async fn savepoint(&self, name: &str) -> Result<(), RdbmsError> {
+        self.savepoint_command("savepoint", "SAVEPOINT", name).await
+    }
+
+    async fn release_savepoint(&self, name: &str) -> Result<(), RdbmsError> {
+        self.savepoint_command("release-savepoint", "RELEASE SAVEPOINT", name)
+            .await
+    }
+
+    async fn rollback_to_savepoint(&self, name: &str) -> Result<(), RdbmsError> {
+        self.savepoint_command("rollback-to-savepoint", "ROLLBACK TO SAVEPOINT", name)
+            .await
+    }
+
     async fn pre_commit(&self) -> Result<(), RdbmsError> {
         let start = Instant::now();
         debug!(
@@ -1058,6 +1265,57 @@ where
     }
 }
 
+/// Executes the statement once per parameter row on a single connection, stopping at the first failure.
+async fn execute_batch<T, DB>(
+    statement: &str,
+    params: Vec<Vec<T::DbValue>>,
+    connection: &mut DB::Connection,
+) -> Result<Vec<u64>, RdbmsError>
+where
+    T: RdbmsType + QueryExecutor<T, DB>,
+    DB: Database,
+    for<'c> &'c mut <DB as Database>::Connection: sqlx::Executor<'c, Database = DB>,
+{
+    let mut result = Vec::with_capacity(params.len());
+    for row_params in params {
+        result.push(T::execute(statement, row_params, &mut *connection).await?);
+    }
+    Ok(result)
+}
+
+async fn execute_batch_in_transaction<T, DB>(
+    statement: &str,
+    params: Vec<Vec<T::DbValue>>,
+    pool: &Pool<DB>,
+) -> Result<Vec<u64>, RdbmsError>
+where
+    T: RdbmsType + QueryExecutor<T, DB>,
+    DB: Database,
+    for<'c> &'c mut <DB as Database>::Connection: sqlx::Executor<'c, Database = DB>,
+{
+    let mut connection = pool
+        .acquire()
+        .await
+        .map_err(RdbmsError::connection_failure)?;
+
+    DB::TransactionManager::begin(&mut connection, None)
+        .await
+        .map_err(RdbmsError::query_execution_failure)?;
+
+    match execute_batch::<T, DB>(statement, params, connection.deref_mut()).await {
+        Ok(result) => {
+            DB::TransactionManager::commit(&mut connection)
+                .await
+                .map_err(RdbmsError::query_execution_failure)?;
+            Ok(result)
+        }
+        Err(error) => {
+            let _ = DB::TransactionManager::rollback(&mut connection).await;
+            Err(error)
+        }
+    }
+}
+
 #[async_trait]
 pub(crate) trait BeginTransactionSupport<T: RdbmsType, DB: Database> {
     async fn begin_transaction(
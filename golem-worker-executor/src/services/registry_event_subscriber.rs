@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::services::active_workers::ActiveWorkers;
+use crate::services::agent_lifecycle::AgentLifecycleBackfill;
 use crate::services::agent_types::AgentTypesService;
 use crate::services::component::ComponentService;
 use crate::services::environment_state::EnvironmentStateService;
@@ -28,6 +29,7 @@ pub(crate) struct WorkerExecutorRegistryInvalidationHandler<Ctx: WorkerCtx> {
     component_service: Arc<dyn ComponentService>,
     environment_state_service: Arc<dyn EnvironmentStateService>,
     agent_types_service: Arc<dyn AgentTypesService>,
+    lifecycle_backfill: Arc<AgentLifecycleBackfill>,
 }
 
 impl<Ctx: WorkerCtx> WorkerExecutorRegistryInvalidationHandler<Ctx> {
@@ -37,6 +39,7 @@ impl<Ctx: WorkerCtx> WorkerExecutorRegistryInvalidationHandler<Ctx> {
         component_service: Arc<dyn ComponentService>,
         environment_state_service: Arc<dyn EnvironmentStateService>,
         agent_types_service: Arc<dyn AgentTypesService>,
+        lifecycle_backfill: Arc<AgentLifecycleBackfill>,
         shutdown_token: CancellationToken,
     ) {
         registry_service
@@ -48,6 +51,7 @@ impl<Ctx: WorkerCtx> WorkerExecutorRegistryInvalidationHandler<Ctx> {
                     component_service,
                     environment_state_service,
                     agent_types_service,
+                    lifecycle_backfill,
                 }),
             )
             .await;
@@ -80,6 +84,22 @@ impl<Ctx: WorkerCtx> RegistryInvalidationHandler
                 self.agent_types_service
                     .invalidate_environment(*environment_id)
                     .await;
+
+                // The deployment may have added lifecycle policies affecting agents that are
+                // already idle
+                let lifecycle_backfill = self.lifecycle_backfill.clone();
+                let environment_id = *environment_id;
+                tokio::spawn(async move {
+                    if let Err(error) = lifecycle_backfill
+                        .backfill_environment(environment_id)
+                        .await
+                    {
+                        warn!(
+                            environment_id = %environment_id,
+                            "Failed to backfill agent lifecycle policy actions: {error}"
+                        );
+                    }
+                });
             }
             RegistryInvalidationEvent::DomainRegistrationChanged { environment_id, .. } => {
                 debug!(
@@ -141,6 +161,15 @@ impl<Ctx: WorkerCtx> RegistryInvalidationHandler
                     .invalidate_environment(*environment_id)
                     .await;
             }
+            RegistryInvalidationEvent::OplogExportSinkChanged { environment_id, .. } => {
+                debug!(
+                    environment_id = %environment_id,
+                    "Received oplog export sink changed event, invalidating environment cache"
+                );
+                self.environment_state_service
+                    .invalidate_environment(*environment_id)
+                    .await;
+            }
             RegistryInvalidationEvent::CardRevoked { .. } => {}
             RegistryInvalidationEvent::ApplicationDeleted {
                 application_id,
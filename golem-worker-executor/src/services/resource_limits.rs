@@ -1453,6 +1453,13 @@ mod tests {
             unimplemented!()
         }
 
+        async fn get_deployed_components(
+            &self,
+            _environment_id: EnvironmentId,
+        ) -> Result<Vec<Component>, RegistryServiceError> {
+            unimplemented!()
+        }
+
         async fn resolve_component(
             &self,
             _resolving_account_id: AccountId,
@@ -1494,6 +1501,17 @@ mod tests {
             unimplemented!()
         }
 
+        async fn list_agent_types_by_names(
+            &self,
+            _app_name: &ApplicationName,
+            _environment_name: &EnvironmentName,
+            _deployment_revision: Option<DeploymentRevision>,
+            _owner_account_email: Option<&str>,
+            _auth_ctx: &AuthCtx,
+        ) -> Result<Vec<ResolvedAgentType>, RegistryServiceError> {
+            unimplemented!()
+        }
+
         async fn get_active_routes_for_domain(
             &self,
             _domain: &Domain,
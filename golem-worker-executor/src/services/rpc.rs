@@ -19,20 +19,22 @@ use super::file_loader::FileLoader;
 use super::{HasAgentWebhooksService, HasEnvironmentStateService, HasWebSocketConnectionPool};
 use crate::durable_host::websocket::WebSocketConnectionPool;
 use crate::services::events::Events;
+use crate::services::invocation_interceptor::InvocationInterceptorPlugin;
 use crate::services::oplog::plugin::OplogProcessorPlugin;
 use crate::services::resource_limits::ResourceLimits;
 use crate::services::shard::ShardService;
 use crate::services::worker_proxy::{WorkerProxy, WorkerProxyError};
 use crate::services::{
     HasActiveWorkers, HasAgentTypesService, HasBlobStoreService, HasComponentService, HasConfig,
-    HasEvents, HasExtraDeps, HasFileLoader, HasHttpConnectionPool, HasKeyValueService,
-    HasLeakSentinel, HasOplogProcessorPlugin, HasOplogService, HasPromiseService, HasQuotaService,
-    HasRdbmsService, HasResourceLimits, HasRpc, HasRunningWorkerEnumerationService,
-    HasSchedulerService, HasShardManagerService, HasShardService, HasShutdownToken,
-    HasWasmtimeEngine, HasWorkerActivator, HasWorkerEnumerationService, HasWorkerForkService,
-    HasWorkerProxy, HasWorkerService, active_workers, agent_types, blob_store, component,
-    golem_config, key_value, oplog, promise, rdbms, scheduler, shard_manager, worker,
-    worker_activator, worker_enumeration, worker_fork,
+    HasEvents, HasExtraDeps, HasFileLoader, HasFilesystemCheckpointService, HasHttpConnectionPool,
+    HasInvocationInterceptorPlugin, HasKeyValueService, HasLeakSentinel, HasOplogProcessorPlugin,
+    HasOplogService, HasPromiseService, HasQuotaService, HasRdbmsService, HasResourceLimits,
+    HasRpc, HasRunningWorkerEnumerationService, HasSchedulerService, HasShardManagerService,
+    HasShardService, HasShutdownToken, HasTopicService, HasWasmtimeEngine, HasWorkerActivator,
+    HasWorkerEnumerationService, HasWorkerForkService, HasWorkerProxy, HasWorkerService,
+    active_workers, agent_types, blob_store, component, filesystem_checkpoint, golem_config,
+    key_value, oplog, promise, rdbms, scheduler, shard_manager, topics, worker, worker_activator,
+    worker_enumeration, worker_fork,
 };
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
@@ -425,6 +427,7 @@ pub struct DirectWorkerInvocationRpc<Ctx: WorkerCtx> {
     golem_config: Arc<golem_config::GolemConfig>,
     shard_service: Arc<dyn ShardService>,
     key_value_service: Arc<dyn key_value::KeyValueService>,
+    topic_service: Arc<dyn topics::TopicService>,
     blob_store_service: Arc<dyn blob_store::BlobStoreService>,
     rdbms_service: Arc<dyn rdbms::RdbmsService>,
     oplog_service: Arc<dyn oplog::OplogService>,
@@ -432,7 +435,9 @@ pub struct DirectWorkerInvocationRpc<Ctx: WorkerCtx> {
     worker_activator: Arc<dyn worker_activator::WorkerActivator<Ctx>>,
     events: Arc<Events>,
     file_loader: Arc<FileLoader>,
+    filesystem_checkpoint_service: Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>,
     oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+    invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
     resource_limits: Arc<dyn ResourceLimits>,
     shutdown_token: tokio_util::sync::CancellationToken,
     environment_state_service: Arc<dyn EnvironmentStateService>,
@@ -464,6 +469,7 @@ impl<Ctx: WorkerCtx> Clone for DirectWorkerInvocationRpc<Ctx> {
             golem_config: self.golem_config.clone(),
             shard_service: self.shard_service.clone(),
             key_value_service: self.key_value_service.clone(),
+            topic_service: self.topic_service.clone(),
             blob_store_service: self.blob_store_service.clone(),
             rdbms_service: self.rdbms_service.clone(),
             oplog_service: self.oplog_service.clone(),
@@ -471,7 +477,9 @@ impl<Ctx: WorkerCtx> Clone for DirectWorkerInvocationRpc<Ctx> {
             worker_activator: self.worker_activator.clone(),
             events: self.events.clone(),
             file_loader: self.file_loader.clone(),
+            filesystem_checkpoint_service: self.filesystem_checkpoint_service.clone(),
             oplog_processor_plugin: self.oplog_processor_plugin.clone(),
+            invocation_interceptor_plugin: self.invocation_interceptor_plugin.clone(),
             resource_limits: self.resource_limits.clone(),
             shutdown_token: self.shutdown_token.clone(),
             environment_state_service: self.environment_state_service.clone(),
@@ -567,6 +575,12 @@ impl<Ctx: WorkerCtx> HasKeyValueService for DirectWorkerInvocationRpc<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> HasTopicService for DirectWorkerInvocationRpc<Ctx> {
+    fn topic_service(&self) -> Arc<dyn topics::TopicService> {
+        self.topic_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasBlobStoreService for DirectWorkerInvocationRpc<Ctx> {
     fn blob_store_service(&self) -> Arc<dyn blob_store::BlobStoreService> {
         self.blob_store_service.clone()
@@ -645,12 +659,26 @@ impl<Ctx: WorkerCtx> HasFileLoader for DirectWorkerInvocationRpc<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> HasFilesystemCheckpointService for DirectWorkerInvocationRpc<Ctx> {
+    fn filesystem_checkpoint_service(
+        &self,
+    ) -> Arc<dyn filesystem_checkpoint::FilesystemCheckpointService> {
+        self.filesystem_checkpoint_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasOplogProcessorPlugin for DirectWorkerInvocationRpc<Ctx> {
     fn oplog_processor_plugin(&self) -> Arc<dyn OplogProcessorPlugin> {
         self.oplog_processor_plugin.clone()
     }
 }
 
+impl<Ctx: WorkerCtx> HasInvocationInterceptorPlugin for DirectWorkerInvocationRpc<Ctx> {
+    fn invocation_interceptor_plugin(&self) -> Arc<dyn InvocationInterceptorPlugin> {
+        self.invocation_interceptor_plugin.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasRdbmsService for DirectWorkerInvocationRpc<Ctx> {
     fn rdbms_service(&self) -> Arc<dyn rdbms::RdbmsService> {
         self.rdbms_service.clone()
@@ -710,6 +738,7 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
         shard_manager_service: Arc<dyn shard_manager::ShardManagerService>,
         quota_service: Arc<dyn crate::services::quota::QuotaService>,
         key_value_service: Arc<dyn key_value::KeyValueService>,
+        topic_service: Arc<dyn topics::TopicService>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService>,
         rdbms_service: Arc<dyn rdbms::RdbmsService>,
         oplog_service: Arc<dyn oplog::OplogService>,
@@ -717,7 +746,9 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
         worker_activator: Arc<dyn worker_activator::WorkerActivator<Ctx>>,
         events: Arc<Events>,
         file_loader: Arc<FileLoader>,
+        filesystem_checkpoint_service: Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>,
         oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
         resource_limits: Arc<dyn ResourceLimits>,
         shutdown_token: tokio_util::sync::CancellationToken,
         environment_state_service: Arc<dyn EnvironmentStateService>,
@@ -746,6 +777,7 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
             golem_config,
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             oplog_service,
@@ -753,7 +785,9 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
             worker_activator,
             events,
             file_loader,
+            filesystem_checkpoint_service,
             oplog_processor_plugin,
+            invocation_interceptor_plugin,
             resource_limits,
             shutdown_token,
             environment_state_service,
@@ -13,25 +13,37 @@
 // limitations under the License.
 
 use crate::metrics::oplog::record_scheduled_archive;
-use crate::metrics::promises::record_scheduled_promise_completed;
+use crate::metrics::promises::{record_promise_timed_out, record_scheduled_promise_completed};
+use crate::metrics::scheduler::record_lifecycle_action_applied;
 use crate::services::HasOplog;
+use crate::services::agent_lifecycle::{AgentLifecycleAudit, AgentLifecycleAuditEntry};
 use crate::services::oplog::{EphemeralOplog, MultiLayerOplog, Oplog, OplogService};
 use crate::services::promise::PromiseService;
 use crate::services::shard::ShardService;
+use crate::services::topics::TopicService;
 use crate::services::worker::WorkerService;
 use crate::services::worker_activator::WorkerActivator;
+use crate::services::worker_enumeration::WorkerEnumerationService;
 use crate::storage::scheduler::{ClaimedScheduledAction, SchedulerStorage};
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use golem_common::model::agent::Principal;
+use golem_common::base_model::worker_filter::StringFilterComparator;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::{AgentPrincipal, LegacyParsedAgentId, Principal};
+use golem_common::model::component_metadata::AgentLifecycleAction;
+use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::topic::{TopicDelivery, TopicFanOut, TopicSubscriber};
 use golem_common::model::{
-    AgentFingerprint, AgentInvocation, OwnedAgentId, ScheduleId, ScheduledAction, ShardId,
+    AgentFilter, AgentFingerprint, AgentInvocation, OwnedAgentId, ScheduleId, ScheduledAction,
+    ShardId, Timestamp,
 };
 use golem_common::serialization::serialize;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::collections::HashSet;
 use std::future::Future;
 use std::ops::{Add, Deref};
 use std::sync::{Arc, Mutex};
@@ -41,6 +53,9 @@ use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Level, debug, error, info, span, warn};
 
+/// Number of agents a single scheduled topic fan-out action delivers a message to
+const TOPIC_FAN_OUT_PAGE_SIZE: u64 = 100;
+
 #[async_trait]
 pub trait SchedulerService: Send + Sync {
     async fn schedule(&self, time: DateTime<Utc>, action: ScheduledAction) -> ScheduleId;
@@ -53,6 +68,15 @@ pub trait SchedulerService: Send + Sync {
     ) -> ScheduleId;
 
     async fn cancel(&self, id: ScheduleId);
+
+    /// Gets the lifecycle policy actions applied to the agents of an environment between the
+    /// two timestamps (inclusive), oldest first
+    async fn lifecycle_audit(
+        &self,
+        environment_id: EnvironmentId,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> anyhow::Result<Vec<AgentLifecycleAuditEntry>>;
 }
 
 /// A lighter trait than `WorkerActivator` that only provides the required functionality
@@ -76,6 +100,16 @@ pub trait SchedulerWorkerAccess {
         owned_agent_id: &OwnedAgentId,
         invocation: AgentInvocation,
     ) -> Result<(), WorkerExecutorError>;
+
+    /// Deletes the worker because of the given lifecycle policy action, if its last oplog index
+    /// is still `last_oplog_index` and its agent type's policy still has the action enabled.
+    /// Returns whether the worker was deleted.
+    async fn delete_worker(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        last_oplog_index: OplogIndex,
+        action: AgentLifecycleAction,
+    ) -> Result<bool, WorkerExecutorError>;
 }
 
 #[async_trait]
@@ -132,6 +166,40 @@ impl<Ctx: WorkerCtx> SchedulerWorkerAccess for Arc<dyn WorkerActivator<Ctx>> {
 
         Ok(())
     }
+
+    async fn delete_worker(
+        &self,
+        owned_agent_id: &OwnedAgentId,
+        last_oplog_index: OplogIndex,
+        action: AgentLifecycleAction,
+    ) -> Result<bool, WorkerExecutorError> {
+        let worker = self
+            .get_or_create_suspended(
+                owned_agent_id,
+                None,
+                Vec::new(),
+                None,
+                None,
+                &InvocationContextStack::fresh(),
+                Principal::anonymous(),
+            )
+            .await?;
+
+        // The policy may have been changed or removed by a component update since scheduling
+        let enabled = worker
+            .lifecycle_policy()
+            .is_some_and(|policy| policy.period_of(action).is_some());
+        if !enabled || worker.oplog().current_oplog_index().await != last_oplog_index {
+            return Ok(false);
+        }
+
+        worker.delete().await?;
+
+        // ensure we are holding the worker while we are doing cleanup.
+        drop(worker);
+
+        Ok(true)
+    }
 }
 
 #[derive(Clone)]
@@ -143,6 +211,9 @@ pub struct SchedulerServiceDefault {
     worker_access: Arc<dyn SchedulerWorkerAccess + Send + Sync>,
     oplog_service: Arc<dyn OplogService>,
     worker_service: Arc<dyn WorkerService>,
+    worker_enumeration_service: Arc<dyn WorkerEnumerationService>,
+    lifecycle_audit: Arc<dyn AgentLifecycleAudit>,
+    topic_service: Arc<dyn TopicService>,
     claim_batch_size: u32,
     lease_ttl: Duration,
     max_batches_per_tick: u32,
@@ -156,6 +227,9 @@ impl SchedulerServiceDefault {
         worker_access: Arc<dyn SchedulerWorkerAccess + Send + Sync>,
         oplog_service: Arc<dyn OplogService>,
         worker_service: Arc<dyn WorkerService>,
+        worker_enumeration_service: Arc<dyn WorkerEnumerationService>,
+        lifecycle_audit: Arc<dyn AgentLifecycleAudit>,
+        topic_service: Arc<dyn TopicService>,
         process_interval: Duration,
         claim_batch_size: u32,
         lease_ttl: Duration,
@@ -169,7 +243,10 @@ impl SchedulerServiceDefault {
             promise_service,
             oplog_service,
             worker_service,
+            worker_enumeration_service,
             worker_access,
+            lifecycle_audit,
+            topic_service,
             claim_batch_size,
             lease_ttl,
             max_batches_per_tick,
@@ -306,6 +383,269 @@ impl SchedulerServiceDefault {
         }
     }
 
+    /// Works on the oldest queued fan-out of the subscription of `fan_out`: queues its message
+    /// for one page of the agents of the subscribed agent type, and schedules the fan-out of
+    /// the next page, or of the next queued message. Fan-outs of one subscription are carried
+    /// out one at a time, so that every page of a message is queued before the next message.
+    /// Every scheduled action has an id derived from the publish, so a retried fan-out does
+    /// not duplicate the actions still pending.
+    async fn fan_out_topic_message(
+        &self,
+        account_id: AccountId,
+        fan_out: TopicFanOut,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let agent_type_name = match &fan_out.subscription.subscriber {
+            TopicSubscriber::AgentType(agent_type_name) => agent_type_name.clone(),
+            TopicSubscriber::Agent(_) => {
+                warn!(
+                    topic = fan_out.topic,
+                    "Dropping topic fan-out of an explicit agent subscription"
+                );
+                return true;
+            }
+        };
+
+        let pending = match self
+            .topic_service
+            .pending_fan_outs(
+                &fan_out.publisher,
+                &fan_out.topic,
+                &fan_out.subscription.subscriber,
+            )
+            .await
+        {
+            Ok(pending) => pending,
+            Err(e) => {
+                error!(
+                    topic = fan_out.topic,
+                    "Failed to get the queued fan-outs of a topic: {e}"
+                );
+                return false;
+            }
+        };
+        // Only the oldest fan-out is carried out; the others are scheduled once it finished.
+        // Scheduling it again is a no-op while its own action is still pending.
+        let Some(head) = pending.first().cloned() else {
+            return true;
+        };
+        if head.schedule_idempotency_key() != fan_out.schedule_idempotency_key() {
+            self.schedule_with_id(
+                ScheduleId::from_idempotency_key(&head.schedule_idempotency_key()),
+                now,
+                ScheduledAction::FanOutTopicMessage {
+                    account_id,
+                    fan_out: Box::new(head),
+                },
+            )
+            .await;
+            return true;
+        }
+
+        let filter = AgentFilter::new_name(
+            StringFilterComparator::StartsWith,
+            format!("{agent_type_name}("),
+        );
+        let (next, page) = match self
+            .worker_enumeration_service
+            .get(
+                &head.publisher.environment_id,
+                &head.component_id,
+                Some(filter),
+                head.cursor.clone(),
+                TOPIC_FAN_OUT_PAGE_SIZE,
+                true,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                error!(
+                    topic = head.topic,
+                    agent_type = agent_type_name.to_string(),
+                    "Failed to enumerate the subscribers of a topic: {e}"
+                );
+                return false;
+            }
+        };
+
+        for metadata in page {
+            let agent_id = metadata.agent_id;
+            if LegacyParsedAgentId::parse_agent_type_name(&agent_id.agent_id).as_ref()
+                != Ok(&agent_type_name)
+            {
+                continue;
+            }
+
+            let idempotency_key = head
+                .subscription
+                .delivery_idempotency_key(&head.publish_idempotency_key, &agent_id);
+            let delivery = TopicDelivery {
+                publisher: head.publisher.agent_id.clone(),
+                publish_index: head.publish_index,
+                invocation: AgentInvocation::AgentMethod {
+                    idempotency_key: idempotency_key.clone(),
+                    method_name: head.subscription.method_name.clone(),
+                    input: head.input.clone(),
+                    invocation_context: head.invocation_context.clone(),
+                    principal: Principal::Agent(AgentPrincipal {
+                        agent_id: head.publisher.agent_id.clone(),
+                    }),
+                },
+            };
+            if let Err(e) = self
+                .topic_service
+                .enqueue_delivery(head.publisher.environment_id, &agent_id, &delivery)
+                .await
+            {
+                error!(
+                    topic = head.topic,
+                    agent_id = agent_id.to_string(),
+                    "Failed to queue topic message: {e}"
+                );
+                return false;
+            }
+            self.schedule_with_id(
+                ScheduleId::from_idempotency_key(&idempotency_key),
+                now,
+                ScheduledAction::DeliverTopicMessage {
+                    account_id,
+                    owned_agent_id: OwnedAgentId::new(head.publisher.environment_id, &agent_id),
+                },
+            )
+            .await;
+        }
+
+        let following = match next {
+            Some(cursor) => {
+                let next_fan_out = TopicFanOut {
+                    cursor,
+                    ..head.clone()
+                };
+                // Queued before the current page is removed, so a failure in between only
+                // repeats the page
+                if let Err(e) = self.topic_service.enqueue_fan_out(&next_fan_out).await {
+                    error!(
+                        topic = head.topic,
+                        "Failed to queue the next page of a topic fan-out: {e}"
+                    );
+                    return false;
+                }
+                Some(next_fan_out)
+            }
+            None => pending
+                .iter()
+                .find(|pending| {
+                    pending.schedule_idempotency_key() != head.schedule_idempotency_key()
+                })
+                .cloned(),
+        };
+
+        // Retried publishes may have queued the same fan-out more than once
+        for done in pending
+            .iter()
+            .filter(|pending| pending.schedule_idempotency_key() == head.schedule_idempotency_key())
+        {
+            if let Err(e) = self.topic_service.remove_fan_out(done).await {
+                error!(
+                    topic = head.topic,
+                    "Failed to remove a finished page of a topic fan-out: {e}"
+                );
+                return false;
+            }
+        }
+
+        if let Some(following) = following {
+            self.schedule_with_id(
+                ScheduleId::from_idempotency_key(&following.schedule_idempotency_key()),
+                now,
+                ScheduledAction::FanOutTopicMessage {
+                    account_id,
+                    fan_out: Box::new(following),
+                },
+            )
+            .await;
+        }
+        true
+    }
+
+    /// Delivers the queued topic messages of an agent, oldest first. The messages of a
+    /// publisher are delivered one at a time: once one of them fails, the later ones of the
+    /// same publisher wait for its retry.
+    async fn deliver_topic_messages(&self, owned_agent_id: &OwnedAgentId) -> bool {
+        let pending = match self
+            .topic_service
+            .pending_deliveries(owned_agent_id.environment_id, &owned_agent_id.agent_id)
+            .await
+        {
+            Ok(pending) => pending,
+            Err(e) => {
+                error!(
+                    agent_id = owned_agent_id.to_string(),
+                    "Failed to get the queued topic messages: {e}"
+                );
+                return false;
+            }
+        };
+        if pending.is_empty() {
+            return true;
+        }
+
+        let exists = self
+            .worker_access
+            .active_worker_fingerprint(owned_agent_id)
+            .await
+            .is_some()
+            || self.worker_service.get(owned_agent_id).await.is_some();
+
+        let mut blocked_publishers = HashSet::new();
+        for delivery in pending {
+            if blocked_publishers.contains(&delivery.publisher) {
+                continue;
+            }
+
+            if exists {
+                if let Err(e) = self
+                    .worker_access
+                    .enqueue_invocation(owned_agent_id, delivery.invocation.clone())
+                    .await
+                {
+                    // Left in the queue, so the delivery is retried after the lease expires
+                    error!(
+                        agent_id = owned_agent_id.to_string(),
+                        "Failed to deliver topic message: {e}"
+                    );
+                    blocked_publishers.insert(delivery.publisher);
+                    continue;
+                }
+            } else {
+                info!(
+                    agent_id = owned_agent_id.to_string(),
+                    "Dropping topic message: the subscribed agent no longer exists"
+                );
+            }
+
+            if let Err(e) = self
+                .topic_service
+                .remove_delivery(
+                    owned_agent_id.environment_id,
+                    &owned_agent_id.agent_id,
+                    &delivery,
+                )
+                .await
+            {
+                // The invocation is delivered again on retry, and deduplicated by its
+                // idempotency key
+                error!(
+                    agent_id = owned_agent_id.to_string(),
+                    "Failed to remove delivered topic message from the queue: {e}"
+                );
+                blocked_publishers.insert(delivery.publisher);
+            }
+        }
+        blocked_publishers.is_empty()
+    }
+
     async fn process_claimed_action(
         &self,
         claimed_action: ClaimedScheduledAction,
@@ -355,6 +695,40 @@ impl SchedulerServiceDefault {
                     }
                 }
             }
+            ScheduledAction::TimeOutPromise {
+                account_id: _,
+                promise_id,
+                environment_id,
+            } => {
+                let owned_agent_id = OwnedAgentId::new(environment_id, &promise_id.agent_id);
+
+                // Completing the promise with the timeout outcome also wakes up the agent awaiting it.
+                // If the promise was completed or cancelled in the meantime this has no effect.
+                match self.promise_service.time_out(promise_id.clone()).await {
+                    Ok(timed_out) => {
+                        if timed_out {
+                            record_promise_timed_out();
+                        }
+                        true
+                    }
+                    Err(WorkerExecutorError::PromiseNotFound { .. }) => {
+                        debug!(
+                            agent_id = owned_agent_id.to_string(),
+                            promise_id = promise_id.to_string(),
+                            "Promise to time out no longer exists"
+                        );
+                        true
+                    }
+                    Err(e) => {
+                        error!(
+                            agent_id = owned_agent_id.to_string(),
+                            promise_id = promise_id.to_string(),
+                            "Failed to time out promise: {e}"
+                        );
+                        false
+                    }
+                }
+            }
             ScheduledAction::ArchiveOplog {
                 account_id,
                 owned_agent_id,
@@ -486,6 +860,92 @@ impl SchedulerServiceDefault {
                 self.worker_access.activate_worker(&owned_agent_id).await;
                 true
             }
+            ScheduledAction::ApplyLifecyclePolicy {
+                account_id,
+                owned_agent_id,
+                agent_mode,
+                last_oplog_index,
+                action,
+            } => {
+                debug!(
+                    "Running scheduled lifecycle action {action} for {account_id}/{owned_agent_id}"
+                );
+
+                if !self.oplog_service.exists(&owned_agent_id, agent_mode).await
+                    || self
+                        .oplog_service
+                        .get_last_index(&owned_agent_id, agent_mode)
+                        .await
+                        != last_oplog_index
+                {
+                    // The agent got deleted or became active again since the action was scheduled
+                    return true;
+                }
+
+                if action.is_deletion() {
+                    match self
+                        .worker_access
+                        .delete_worker(&owned_agent_id, last_oplog_index, action)
+                        .await
+                    {
+                        Ok(true) => {}
+                        Ok(false) => return true,
+                        Err(error) => {
+                            error!(
+                                agent_id = owned_agent_id.to_string(),
+                                "Failed to delete worker by its lifecycle policy: {error}"
+                            );
+                            return false;
+                        }
+                    }
+                    info!(
+                        agent_id = owned_agent_id.to_string(),
+                        %action,
+                        "Deleted worker by its lifecycle policy"
+                    );
+                } else {
+                    // Archiving all the layers at once, as the agent is not expected to be
+                    // used anytime soon
+                    self.schedule(
+                        now,
+                        ScheduledAction::ArchiveOplog {
+                            account_id,
+                            owned_agent_id: owned_agent_id.clone(),
+                            agent_mode,
+                            last_oplog_index,
+                            next_after: Duration::ZERO,
+                        },
+                    )
+                    .await;
+                }
+
+                record_lifecycle_action_applied(action);
+                let entry = AgentLifecycleAuditEntry {
+                    timestamp: Timestamp::now_utc(),
+                    agent_id: owned_agent_id.agent_id.clone(),
+                    action,
+                    last_oplog_index,
+                };
+                if let Err(error) = self
+                    .lifecycle_audit
+                    .record(owned_agent_id.environment_id, entry)
+                    .await
+                {
+                    warn!(
+                        agent_id = owned_agent_id.to_string(),
+                        "Failed to record lifecycle action in the audit trail: {error}"
+                    );
+                }
+                true
+            }
+            ScheduledAction::DeliverTopicMessage {
+                account_id: _,
+                owned_agent_id,
+            } => self.deliver_topic_messages(&owned_agent_id).await,
+            ScheduledAction::FanOutTopicMessage {
+                account_id,
+                fan_out,
+            } => self.fan_out_topic_message(account_id, *fan_out, now).await,
         }
     }
 }
@@ -542,39 +1002,63 @@ impl SchedulerService for SchedulerServiceDefault {
                 panic!("failed to remove schedule {id} from scheduler storage: {err}")
             });
     }
+
+    async fn lifecycle_audit(
+        &self,
+        environment_id: EnvironmentId,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> anyhow::Result<Vec<AgentLifecycleAuditEntry>> {
+        self.lifecycle_audit.entries(environment_id, from, to).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::services::oplog::{Oplog, OplogService, PrimaryOplogService};
+    use crate::model::ExecutionStatus;
+    use crate::services::agent_lifecycle::{
+        AgentLifecycleAudit, DefaultAgentLifecycleAudit, schedule_lifecycle_actions,
+    };
+    use crate::services::oplog::{CommitLevel, Oplog, OplogService, PrimaryOplogService};
     use crate::services::promise::PromiseServiceMock;
     use crate::services::scheduler::{
         SchedulerService, SchedulerServiceDefault, SchedulerWorkerAccess,
     };
     use crate::services::shard::{ShardService, ShardServiceDefault};
+    use crate::services::topics::{DefaultTopicService, TopicService};
     use crate::services::worker::{GetWorkerMetadataResult, WorkerService};
+    use crate::services::worker_enumeration::WorkerEnumerationService;
     use crate::storage::indexed::memory::InMemoryIndexedStorage;
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
     use crate::storage::scheduler::SchedulerStorage;
     use crate::storage::scheduler::memory::InMemorySchedulerStorage;
     use async_trait::async_trait;
     use chrono::DateTime;
-    use golem_common::model::AgentStatusRecord;
-    use golem_common::model::account::AccountId;
-    use golem_common::model::agent::{AgentMode, Principal, UntypedDataValue};
+    use golem_common::model::account::{AccountEmail, AccountId};
+    use golem_common::model::agent::{
+        AgentMode, AgentPrincipal, AgentTypeName, Principal, UntypedDataValue,
+    };
     use golem_common::model::component::ComponentId;
+    use golem_common::model::component_metadata::{AgentLifecycleAction, AgentLifecyclePolicy};
     use golem_common::model::environment::EnvironmentId;
     use golem_common::model::invocation_context::InvocationContextStack;
-    use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::oplog::{OplogEntry, OplogIndex};
+    use golem_common::model::regions::OplogRegion;
+    use golem_common::model::topic::{
+        TopicDelivery, TopicFanOut, TopicSubscriber, TopicSubscription,
+    };
     use golem_common::model::{
-        AgentFingerprint, AgentId, AgentInvocation, IdempotencyKey, OwnedAgentId, PromiseId,
-        ScheduleId, ScheduledAction, ShardAssignment, ShardId,
+        AgentFilter, AgentFingerprint, AgentId, AgentInvocation, AgentMetadata, AgentStatus,
+        AgentStatusRecord, IdempotencyKey, OwnedAgentId, PromiseId, ScanCursor, ScheduleId,
+        ScheduledAction, ShardAssignment, ShardId, Timestamp,
     };
+    use golem_common::read_only_lock;
     use golem_service_base::error::worker_executor::WorkerExecutorError;
     use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
     use std::collections::HashSet;
     use std::str::FromStr;
-    use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
     use std::time::Duration;
     use test_r::test;
     use tokio_util::sync::CancellationToken;
@@ -607,6 +1091,15 @@ mod tests {
         ) -> Result<(), WorkerExecutorError> {
             unimplemented!()
         }
+
+        async fn delete_worker(
+            &self,
+            _owned_agent_id: &OwnedAgentId,
+            _last_oplog_index: OplogIndex,
+            _action: AgentLifecycleAction,
+        ) -> Result<bool, WorkerExecutorError> {
+            unimplemented!()
+        }
     }
 
     struct ActiveWorkerAccessMock {
@@ -640,6 +1133,15 @@ mod tests {
             self.enqueue_count.fetch_add(1, Ordering::SeqCst);
             Ok(())
         }
+
+        async fn delete_worker(
+            &self,
+            _owned_agent_id: &OwnedAgentId,
+            _last_oplog_index: OplogIndex,
+            _action: AgentLifecycleAction,
+        ) -> Result<bool, WorkerExecutorError> {
+            unimplemented!()
+        }
     }
 
     struct WorkerServiceMock;
@@ -647,7 +1149,7 @@ mod tests {
     #[async_trait]
     impl WorkerService for WorkerServiceMock {
         async fn get(&self, _owned_agent_id: &OwnedAgentId) -> Option<GetWorkerMetadataResult> {
-            unimplemented!()
+            None
         }
 
         async fn get_running_workers_in_shards(&self) -> Vec<GetWorkerMetadataResult> {
@@ -728,17 +1230,153 @@ mod tests {
         Arc::new(WorkerServiceMock)
     }
 
-    async fn create_scheduler(
+    /// Returns the given pages of agents one by one, ignoring the filter and the requested count
+    struct WorkerEnumerationServiceMock {
+        pages: Vec<Vec<AgentId>>,
+    }
+
+    #[async_trait]
+    impl WorkerEnumerationService for WorkerEnumerationServiceMock {
+        async fn get(
+            &self,
+            environment_id: &EnvironmentId,
+            _component_id: &ComponentId,
+            _filter: Option<AgentFilter>,
+            cursor: ScanCursor,
+            _count: u64,
+            _precise: bool,
+        ) -> Result<(Option<ScanCursor>, Vec<AgentMetadata>), WorkerExecutorError> {
+            let index = cursor.cursor as usize;
+            let page = self
+                .pages
+                .get(index)
+                .into_iter()
+                .flatten()
+                .map(|agent_id| agent_metadata(*environment_id, agent_id.clone()))
+                .collect();
+            let next = (index + 1 < self.pages.len()).then(|| ScanCursor {
+                cursor: (index + 1) as u64,
+                layer: 0,
+            });
+            Ok((next, page))
+        }
+    }
+
+    fn agent_metadata(environment_id: EnvironmentId, agent_id: AgentId) -> AgentMetadata {
+        AgentMetadata {
+            agent_id,
+            env: vec![],
+            environment_id,
+            created_by: AccountId::new(),
+            created_by_email: AccountEmail::new("test@golem"),
+            config: vec![],
+            created_at: Timestamp::now_utc(),
+            parent: None,
+            last_known_status: AgentStatusRecord::default(),
+            original_phantom_id: None,
+            fingerprint: AgentFingerprint::new(),
+            agent_mode: AgentMode::Durable,
+        }
+    }
+
+    fn create_worker_enumeration_service_mock(
+        pages: Vec<Vec<AgentId>>,
+    ) -> Arc<dyn WorkerEnumerationService> {
+        Arc::new(WorkerEnumerationServiceMock { pages })
+    }
+
+    /// Worker access of topic delivery tests: only `existing` agents exist, and enqueueing an
+    /// invocation fails for the `failing` ones.
+    struct TopicWorkerAccessMock {
+        existing: HashSet<AgentId>,
+        failing: Mutex<HashSet<AgentId>>,
+        enqueued: Mutex<Vec<(AgentId, AgentInvocation)>>,
+    }
+
+    impl TopicWorkerAccessMock {
+        fn new(existing: &[AgentId], failing: &[AgentId]) -> Arc<Self> {
+            Arc::new(Self {
+                existing: existing.iter().cloned().collect(),
+                failing: Mutex::new(failing.iter().cloned().collect()),
+                enqueued: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn recover(&self, agent_id: &AgentId) {
+            self.failing.lock().unwrap().remove(agent_id);
+        }
+
+        fn enqueued(&self) -> Vec<(AgentId, AgentInvocation)> {
+            self.enqueued.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl SchedulerWorkerAccess for TopicWorkerAccessMock {
+        async fn active_worker_fingerprint(
+            &self,
+            owned_agent_id: &OwnedAgentId,
+        ) -> Option<AgentFingerprint> {
+            self.existing
+                .contains(&owned_agent_id.agent_id)
+                .then(AgentFingerprint::new)
+        }
+
+        async fn activate_worker(&self, _owned_agent_id: &OwnedAgentId) {}
+
+        async fn open_oplog(
+            &self,
+            _owned_agent_id: &OwnedAgentId,
+        ) -> Result<Arc<dyn Oplog>, WorkerExecutorError> {
+            unimplemented!()
+        }
+
+        async fn enqueue_invocation(
+            &self,
+            owned_agent_id: &OwnedAgentId,
+            invocation: AgentInvocation,
+        ) -> Result<(), WorkerExecutorError> {
+            if self
+                .failing
+                .lock()
+                .unwrap()
+                .contains(&owned_agent_id.agent_id)
+            {
+                return Err(WorkerExecutorError::runtime("subscriber unavailable"));
+            }
+            self.enqueued
+                .lock()
+                .unwrap()
+                .push((owned_agent_id.agent_id.clone(), invocation));
+            Ok(())
+        }
+
+        async fn delete_worker(
+            &self,
+            _owned_agent_id: &OwnedAgentId,
+            _last_oplog_index: OplogIndex,
+            _action: AgentLifecycleAction,
+        ) -> Result<bool, WorkerExecutorError> {
+            unimplemented!()
+        }
+    }
+
+    async fn create_topic_scheduler(
         scheduler_storage: Arc<dyn SchedulerStorage + Send + Sync>,
-        promise_service: Arc<PromiseServiceMock>,
+        worker_access: Arc<TopicWorkerAccessMock>,
+        topic_service: Arc<dyn TopicService>,
+        pages: Vec<Vec<AgentId>>,
     ) -> Arc<SchedulerServiceDefault> {
         SchedulerServiceDefault::new(
             scheduler_storage,
             create_shard_service_mock(),
-            promise_service,
-            create_worker_access_mock(),
+            create_promise_service_mock(),
+            worker_access,
             create_oplog_service_mock().await,
             create_worker_service_mock(),
+            create_worker_enumeration_service_mock(pages),
+            create_lifecycle_audit(),
+            topic_service,
             Duration::from_secs(1000),
             100,
             Duration::from_secs(30),
@@ -747,45 +1385,155 @@ mod tests {
         )
     }
 
-    fn promise(agent_id: AgentId, idx: u64) -> PromiseId {
-        PromiseId {
-            agent_id,
-            oplog_idx: OplogIndex::from_u64(idx),
-        }
+    fn create_topic_service() -> Arc<dyn TopicService> {
+        Arc::new(DefaultTopicService::new(Arc::new(
+            InMemoryKeyValueStorage::new(),
+        )))
     }
 
-    fn agent(name: &str) -> AgentId {
-        AgentId {
+    fn topic_fan_out(
+        environment_id: EnvironmentId,
+        publish_idempotency_key: IdempotencyKey,
+        publish_index: OplogIndex,
+    ) -> TopicFanOut {
+        TopicFanOut {
+            publisher: OwnedAgentId::new(environment_id, &agent("publisher()")),
+            topic: "orders".to_string(),
+            subscription: TopicSubscription {
+                subscriber: TopicSubscriber::AgentType(AgentTypeName("subscriber".to_string())),
+                method_name: "on-order".to_string(),
+            },
             component_id: ComponentId(Uuid::new_v4()),
-            agent_id: name.to_string(),
-        }
-    }
-
-    fn complete_promise_action(promise_id: PromiseId) -> ScheduledAction {
-        ScheduledAction::CompletePromise {
-            account_id: AccountId::new(),
-            environment_id: EnvironmentId::new(),
-            promise_id,
-        }
-    }
-
-    fn agent_method_invocation() -> AgentInvocation {
-        AgentInvocation::AgentMethod {
-            idempotency_key: IdempotencyKey::fresh(),
-            method_name: "run".to_string(),
+            publish_idempotency_key,
+            publish_index,
             input: UntypedDataValue::Tuple(vec![]),
             invocation_context: InvocationContextStack::fresh(),
-            principal: Principal::anonymous(),
+            cursor: ScanCursor::default(),
         }
     }
 
-    #[test]
-    async fn schedule_returns_uuid_backed_id_and_cancel_removes_entry() {
-        let storage = Arc::new(InMemorySchedulerStorage::new());
-        let promise_service = create_promise_service_mock();
-        let svc = create_scheduler(storage.clone(), promise_service).await;
-
-        let action = complete_promise_action(promise(agent("inst1"), 101));
+    /// Queues and schedules a fan-out the way publishing to a topic does
+    async fn publish_fan_out(
+        svc: &SchedulerServiceDefault,
+        topic_service: &Arc<dyn TopicService>,
+        fan_out: TopicFanOut,
+    ) {
+        topic_service.enqueue_fan_out(&fan_out).await.unwrap();
+        svc.schedule_with_id(
+            ScheduleId::from_idempotency_key(&fan_out.schedule_idempotency_key()),
+            DateTime::from_str("2023-07-17T10:05:00Z").unwrap(),
+            ScheduledAction::FanOutTopicMessage {
+                account_id: AccountId::new(),
+                fan_out: Box::new(fan_out),
+            },
+        )
+        .await;
+    }
+
+    /// Queues and schedules a message for an explicitly subscribed agent the way publishing to
+    /// a topic does
+    async fn publish_delivery(
+        svc: &SchedulerServiceDefault,
+        topic_service: &Arc<dyn TopicService>,
+        target: &OwnedAgentId,
+        delivery: TopicDelivery,
+    ) {
+        topic_service
+            .enqueue_delivery(target.environment_id, &target.agent_id, &delivery)
+            .await
+            .unwrap();
+        svc.schedule(
+            DateTime::from_str("2023-07-17T10:05:00Z").unwrap(),
+            ScheduledAction::DeliverTopicMessage {
+                account_id: AccountId::new(),
+                owned_agent_id: target.clone(),
+            },
+        )
+        .await;
+    }
+
+    fn delivery_keys(enqueued: &[(AgentId, AgentInvocation)]) -> Vec<(AgentId, IdempotencyKey)> {
+        let mut keys: Vec<_> = enqueued
+            .iter()
+            .map(|(agent_id, invocation)| match invocation {
+                AgentInvocation::AgentMethod {
+                    idempotency_key, ..
+                } => (agent_id.clone(), idempotency_key.clone()),
+                other => panic!("unexpected invocation {other:?}"),
+            })
+            .collect();
+        keys.sort_by_key(|(agent_id, _)| agent_id.agent_id.clone());
+        keys
+    }
+
+    fn create_lifecycle_audit() -> Arc<dyn AgentLifecycleAudit> {
+        Arc::new(DefaultAgentLifecycleAudit::new(
+            Arc::new(InMemoryKeyValueStorage::new()),
+            Duration::from_secs(90 * 24 * 60 * 60),
+        ))
+    }
+
+    async fn create_scheduler(
+        scheduler_storage: Arc<dyn SchedulerStorage + Send + Sync>,
+        promise_service: Arc<PromiseServiceMock>,
+    ) -> Arc<SchedulerServiceDefault> {
+        SchedulerServiceDefault::new(
+            scheduler_storage,
+            create_shard_service_mock(),
+            promise_service,
+            create_worker_access_mock(),
+            create_oplog_service_mock().await,
+            create_worker_service_mock(),
+            create_worker_enumeration_service_mock(vec![]),
+            create_lifecycle_audit(),
+            create_topic_service(),
+            Duration::from_secs(1000),
+            100,
+            Duration::from_secs(30),
+            10,
+            CancellationToken::new(),
+        )
+    }
+
+    fn promise(agent_id: AgentId, idx: u64) -> PromiseId {
+        PromiseId {
+            agent_id,
+            oplog_idx: OplogIndex::from_u64(idx),
+        }
+    }
+
+    fn agent(name: &str) -> AgentId {
+        AgentId {
+            component_id: ComponentId(Uuid::new_v4()),
+            agent_id: name.to_string(),
+        }
+    }
+
+    fn complete_promise_action(promise_id: PromiseId) -> ScheduledAction {
+        ScheduledAction::CompletePromise {
+            account_id: AccountId::new(),
+            environment_id: EnvironmentId::new(),
+            promise_id,
+        }
+    }
+
+    fn agent_method_invocation() -> AgentInvocation {
+        AgentInvocation::AgentMethod {
+            idempotency_key: IdempotencyKey::fresh(),
+            method_name: "run".to_string(),
+            input: UntypedDataValue::Tuple(vec![]),
+            invocation_context: InvocationContextStack::fresh(),
+            principal: Principal::anonymous(),
+        }
+    }
+
+    #[test]
+    async fn schedule_returns_uuid_backed_id_and_cancel_removes_entry() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let promise_service = create_promise_service_mock();
+        let svc = create_scheduler(storage.clone(), promise_service).await;
+
+        let action = complete_promise_action(promise(agent("inst1"), 101));
         let schedule_id = svc
             .schedule(DateTime::from_str("2023-07-17T10:05:00Z").unwrap(), action)
             .await;
@@ -891,6 +1639,39 @@ mod tests {
         assert!(completed_promises.contains(&promise_id));
     }
 
+    #[test]
+    async fn process_times_out_promises_past_their_deadline() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let promise_service = create_promise_service_mock();
+        let svc = create_scheduler(storage, promise_service.clone()).await;
+
+        let due_promise_id = promise(agent("inst1"), 101);
+        let later_promise_id = promise(agent("inst1"), 102);
+        for (promise_id, deadline) in [
+            (due_promise_id.clone(), "2023-07-17T10:05:00Z"),
+            (later_promise_id.clone(), "2023-07-17T10:25:00Z"),
+        ] {
+            svc.schedule(
+                DateTime::from_str(deadline).unwrap(),
+                ScheduledAction::TimeOutPromise {
+                    account_id: AccountId::new(),
+                    environment_id: EnvironmentId::new(),
+                    promise_id,
+                },
+            )
+            .await;
+        }
+
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        let timed_out_promises = promise_service.all_timed_out().await;
+        assert!(timed_out_promises.contains(&due_promise_id));
+        assert!(!timed_out_promises.contains(&later_promise_id));
+        assert!(promise_service.all_completed().await.is_empty());
+    }
+
     #[test]
     async fn scheduled_invoke_uses_active_worker_fingerprint_before_worker_service_lookup() {
         let storage = Arc::new(InMemorySchedulerStorage::new());
@@ -909,6 +1690,9 @@ mod tests {
             worker_access,
             create_oplog_service_mock().await,
             create_worker_service_mock(),
+            create_worker_enumeration_service_mock(vec![]),
+            create_lifecycle_audit(),
+            create_topic_service(),
             Duration::from_secs(1000),
             100,
             Duration::from_secs(30),
@@ -935,6 +1719,380 @@ mod tests {
         assert_eq!(enqueue_count.load(Ordering::SeqCst), 1);
     }
 
+    #[test]
+    async fn lifecycle_action_of_missing_agent_is_dropped() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let svc = create_scheduler(storage.clone(), create_promise_service_mock()).await;
+
+        svc.schedule(
+            DateTime::from_str("2023-07-17T07:05:00Z").unwrap(),
+            ScheduledAction::ApplyLifecyclePolicy {
+                account_id: AccountId::new(),
+                owned_agent_id: OwnedAgentId::new(EnvironmentId::new(), &agent("inst1")),
+                agent_mode: AgentMode::Durable,
+                last_oplog_index: OplogIndex::from_u64(5),
+                action: AgentLifecycleAction::DeleteAfterIdle,
+            },
+        )
+        .await;
+
+        // The worker access mock panics on `delete_worker`, so this also checks that deletion
+        // is not attempted for agents without an oplog
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        let assignment = ShardAssignment {
+            number_of_shards: 1,
+            shard_ids: HashSet::from_iter([ShardId::new(0)]),
+        };
+        let remaining = storage
+            .claim_due(
+                DateTime::from_str("2023-07-17T11:15:00Z").unwrap(),
+                &assignment,
+                10,
+                Duration::from_secs(30),
+            )
+            .await
+            .unwrap();
+        assert!(remaining.is_empty());
+    }
+
+    /// Worker access of lifecycle policy tests, recording the deleted agents and the agents
+    /// whose oplog got opened for archiving
+    #[derive(Default)]
+    struct LifecycleWorkerAccessMock {
+        deleted: Mutex<Vec<(OwnedAgentId, AgentLifecycleAction)>>,
+        archived: Mutex<Vec<OwnedAgentId>>,
+    }
+
+    #[async_trait]
+    impl SchedulerWorkerAccess for LifecycleWorkerAccessMock {
+        async fn active_worker_fingerprint(
+            &self,
+            _owned_agent_id: &OwnedAgentId,
+        ) -> Option<AgentFingerprint> {
+            None
+        }
+
+        async fn activate_worker(&self, _owned_agent_id: &OwnedAgentId) {}
+
+        async fn open_oplog(
+            &self,
+            owned_agent_id: &OwnedAgentId,
+        ) -> Result<Arc<dyn Oplog>, WorkerExecutorError> {
+            self.archived.lock().unwrap().push(owned_agent_id.clone());
+            Err(WorkerExecutorError::runtime("not archiving in tests"))
+        }
+
+        async fn enqueue_invocation(
+            &self,
+            _owned_agent_id: &OwnedAgentId,
+            _invocation: AgentInvocation,
+        ) -> Result<(), WorkerExecutorError> {
+            unimplemented!()
+        }
+
+        async fn delete_worker(
+            &self,
+            owned_agent_id: &OwnedAgentId,
+            _last_oplog_index: OplogIndex,
+            action: AgentLifecycleAction,
+        ) -> Result<bool, WorkerExecutorError> {
+            self.deleted
+                .lock()
+                .unwrap()
+                .push((owned_agent_id.clone(), action));
+            Ok(true)
+        }
+    }
+
+    async fn create_lifecycle_scheduler(
+        worker_access: Arc<LifecycleWorkerAccessMock>,
+        oplog_service: Arc<dyn OplogService>,
+        lifecycle_audit: Arc<dyn AgentLifecycleAudit>,
+    ) -> Arc<SchedulerServiceDefault> {
+        SchedulerServiceDefault::new(
+            Arc::new(InMemorySchedulerStorage::new()),
+            create_shard_service_mock(),
+            create_promise_service_mock(),
+            worker_access,
+            oplog_service,
+            create_worker_service_mock(),
+            create_worker_enumeration_service_mock(vec![]),
+            lifecycle_audit,
+            create_topic_service(),
+            Duration::from_secs(1000),
+            100,
+            Duration::from_secs(30),
+            10,
+            CancellationToken::new(),
+        )
+    }
+
+    /// Creates the oplog of an agent that went idle, returning its last oplog index
+    async fn create_idle_agent_oplog(
+        oplog_service: &Arc<dyn OplogService>,
+        owned_agent_id: &OwnedAgentId,
+    ) -> OplogIndex {
+        let oplog = oplog_service
+            .create(
+                owned_agent_id,
+                AgentMode::Durable,
+                OplogEntry::jump(OplogRegion {
+                    start: OplogIndex::INITIAL,
+                    end: OplogIndex::INITIAL,
+                }),
+                agent_metadata(
+                    owned_agent_id.environment_id,
+                    owned_agent_id.agent_id.clone(),
+                ),
+                read_only_lock::tokio::ReadOnlyLock::new(Arc::new(tokio::sync::RwLock::new(
+                    AgentStatusRecord::default(),
+                ))),
+                read_only_lock::std::ReadOnlyLock::new(Arc::new(std::sync::RwLock::new(
+                    ExecutionStatus::Suspended {
+                        agent_mode: AgentMode::Durable,
+                        timestamp: Timestamp::now_utc(),
+                    },
+                ))),
+            )
+            .await;
+        oplog.commit(CommitLevel::Always).await;
+        oplog_service
+            .get_last_index(owned_agent_id, AgentMode::Durable)
+            .await
+    }
+
+    #[test]
+    async fn idle_agent_is_deleted_after_its_policy_period_and_audited() {
+        let worker_access = Arc::new(LifecycleWorkerAccessMock::default());
+        let oplog_service = create_oplog_service_mock().await;
+        let lifecycle_audit = create_lifecycle_audit();
+        let svc = create_lifecycle_scheduler(
+            worker_access.clone(),
+            oplog_service.clone(),
+            lifecycle_audit.clone(),
+        )
+        .await;
+
+        let owned_agent_id = OwnedAgentId::new(EnvironmentId::new(), &agent("idle()"));
+        let last_oplog_index = create_idle_agent_oplog(&oplog_service, &owned_agent_id).await;
+        let policy = AgentLifecyclePolicy {
+            delete_after_idle_nanos: Some(Duration::from_secs(60 * 60).as_nanos() as u64),
+            ..Default::default()
+        };
+
+        schedule_lifecycle_actions(
+            svc.as_ref(),
+            &policy,
+            AccountId::new(),
+            &owned_agent_id,
+            AgentMode::Durable,
+            AgentStatus::Idle,
+            last_oplog_index,
+            DateTime::from_str("2023-07-17T10:00:00Z").unwrap(),
+        )
+        .await;
+
+        svc.process(DateTime::from_str("2023-07-17T10:30:00Z").unwrap())
+            .await
+            .unwrap();
+        let deleted_before_expiry = worker_access.deleted.lock().unwrap().clone();
+
+        svc.process(DateTime::from_str("2023-07-17T11:05:00Z").unwrap())
+            .await
+            .unwrap();
+        let deleted = worker_access.deleted.lock().unwrap().clone();
+
+        let audit = lifecycle_audit
+            .entries(
+                owned_agent_id.environment_id,
+                Timestamp::from(0),
+                Timestamp::now_utc(),
+            )
+            .await
+            .unwrap();
+
+        assert!(deleted_before_expiry.is_empty());
+        assert_eq!(
+            deleted,
+            vec![(
+                owned_agent_id.clone(),
+                AgentLifecycleAction::DeleteAfterIdle
+            )]
+        );
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].agent_id, owned_agent_id.agent_id);
+        assert_eq!(audit[0].action, AgentLifecycleAction::DeleteAfterIdle);
+        assert_eq!(audit[0].last_oplog_index, last_oplog_index);
+    }
+
+    #[test]
+    async fn idle_agent_is_archived_after_its_policy_period() {
+        let worker_access = Arc::new(LifecycleWorkerAccessMock::default());
+        let oplog_service = create_oplog_service_mock().await;
+        let lifecycle_audit = create_lifecycle_audit();
+        let svc = create_lifecycle_scheduler(
+            worker_access.clone(),
+            oplog_service.clone(),
+            lifecycle_audit.clone(),
+        )
+        .await;
+
+        let owned_agent_id = OwnedAgentId::new(EnvironmentId::new(), &agent("idle()"));
+        let last_oplog_index = create_idle_agent_oplog(&oplog_service, &owned_agent_id).await;
+        let policy = AgentLifecyclePolicy {
+            archive_after_idle_nanos: Some(Duration::from_secs(60 * 60).as_nanos() as u64),
+            ..Default::default()
+        };
+
+        schedule_lifecycle_actions(
+            svc.as_ref(),
+            &policy,
+            AccountId::new(),
+            &owned_agent_id,
+            AgentMode::Durable,
+            AgentStatus::Idle,
+            last_oplog_index,
+            DateTime::from_str("2023-07-17T10:00:00Z").unwrap(),
+        )
+        .await;
+
+        svc.process(DateTime::from_str("2023-07-17T11:05:00Z").unwrap())
+            .await
+            .unwrap();
+
+        let audit = lifecycle_audit
+            .entries(
+                owned_agent_id.environment_id,
+                Timestamp::from(0),
+                Timestamp::now_utc(),
+            )
+            .await
+            .unwrap();
+
+        assert!(worker_access.deleted.lock().unwrap().is_empty());
+        assert!(
+            worker_access
+                .archived
+                .lock()
+                .unwrap()
+                .contains(&owned_agent_id)
+        );
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, AgentLifecycleAction::ArchiveAfterIdle);
+    }
+
+    #[test]
+    async fn lifecycle_action_of_reactivated_agent_is_dropped() {
+        let worker_access = Arc::new(LifecycleWorkerAccessMock::default());
+        let oplog_service = create_oplog_service_mock().await;
+        let lifecycle_audit = create_lifecycle_audit();
+        let svc = create_lifecycle_scheduler(
+            worker_access.clone(),
+            oplog_service.clone(),
+            lifecycle_audit.clone(),
+        )
+        .await;
+
+        let owned_agent_id = OwnedAgentId::new(EnvironmentId::new(), &agent("idle()"));
+        let last_oplog_index = create_idle_agent_oplog(&oplog_service, &owned_agent_id).await;
+        let policy = AgentLifecyclePolicy {
+            delete_after_idle_nanos: Some(Duration::from_secs(60 * 60).as_nanos() as u64),
+            ..Default::default()
+        };
+
+        // The action was scheduled when the agent went idle at an earlier oplog index
+        schedule_lifecycle_actions(
+            svc.as_ref(),
+            &policy,
+            AccountId::new(),
+            &owned_agent_id,
+            AgentMode::Durable,
+            AgentStatus::Idle,
+            last_oplog_index.previous(),
+            DateTime::from_str("2023-07-17T10:00:00Z").unwrap(),
+        )
+        .await;
+
+        svc.process(DateTime::from_str("2023-07-17T11:05:00Z").unwrap())
+            .await
+            .unwrap();
+
+        let audit = lifecycle_audit
+            .entries(
+                owned_agent_id.environment_id,
+                Timestamp::from(0),
+                Timestamp::now_utc(),
+            )
+            .await
+            .unwrap();
+
+        assert!(worker_access.deleted.lock().unwrap().is_empty());
+        assert!(audit.is_empty());
+    }
+
+    #[test]
+    async fn backfilled_lifecycle_actions_are_not_duplicated() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let svc = create_scheduler(storage.clone(), create_promise_service_mock()).await;
+
+        let owned_agent_id = OwnedAgentId::new(EnvironmentId::new(), &agent("idle()"));
+        let policy = AgentLifecyclePolicy {
+            delete_after_idle_nanos: Some(Duration::from_secs(60 * 60).as_nanos() as u64),
+            archive_after_idle_nanos: Some(Duration::from_secs(30 * 60).as_nanos() as u64),
+            ..Default::default()
+        };
+
+        // Once when the agent went idle, and once more by a backfill
+        for _ in 0..2 {
+            schedule_lifecycle_actions(
+                svc.as_ref(),
+                &policy,
+                AccountId::new(),
+                &owned_agent_id,
+                AgentMode::Durable,
+                AgentStatus::Idle,
+                OplogIndex::from_u64(5),
+                DateTime::from_str("2023-07-17T10:00:00Z").unwrap(),
+            )
+            .await;
+        }
+
+        let assignment = ShardAssignment {
+            number_of_shards: 1,
+            shard_ids: HashSet::from_iter([ShardId::new(0)]),
+        };
+        let mut due = storage
+            .claim_due(
+                DateTime::from_str("2023-07-17T11:00:00Z").unwrap(),
+                &assignment,
+                10,
+                Duration::from_secs(30),
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|claimed| (claimed.due_at, claimed.action.to_string()))
+            .collect::<Vec<_>>();
+        due.sort();
+
+        assert_eq!(
+            due,
+            vec![
+                (
+                    DateTime::from_str("2023-07-17T10:30:00Z").unwrap(),
+                    format!("lifecycle[archive-after-idle][{owned_agent_id}]")
+                ),
+                (
+                    DateTime::from_str("2023-07-17T11:00:00Z").unwrap(),
+                    format!("lifecycle[delete-after-idle][{owned_agent_id}]")
+                ),
+            ]
+        );
+    }
+
     #[test]
     async fn leases_prevent_duplicate_claims_until_expiry() {
         let storage = Arc::new(InMemorySchedulerStorage::new());
@@ -1130,6 +2288,326 @@ mod tests {
         );
     }
 
+    #[test]
+    async fn topic_message_is_fanned_out_to_every_page_of_subscribers() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let subscribers = vec![
+            agent("subscriber(\"a\")"),
+            agent("subscriber(\"b\")"),
+            agent("subscriber(\"c\")"),
+        ];
+        let worker_access = TopicWorkerAccessMock::new(&subscribers, &[]);
+        let topic_service = create_topic_service();
+        let svc = create_topic_scheduler(
+            storage,
+            worker_access.clone(),
+            topic_service.clone(),
+            vec![subscribers[..2].to_vec(), subscribers[2..].to_vec()],
+        )
+        .await;
+
+        let environment_id = EnvironmentId::new();
+        let publish_key = IdempotencyKey::fresh();
+        let fan_out = topic_fan_out(environment_id, publish_key.clone(), OplogIndex::INITIAL);
+        let subscription = fan_out.subscription.clone();
+        let publisher = fan_out.publisher.clone();
+        publish_fan_out(&svc, &topic_service, fan_out).await;
+
+        // Each tick runs the actions scheduled by the previous one: the deliveries of the
+        // first page with the fan-out of the second page, then the deliveries of the second
+        for _ in 0..3 {
+            svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+                .await
+                .unwrap();
+        }
+
+        let enqueued = worker_access.enqueued();
+        assert_eq!(
+            delivery_keys(&enqueued),
+            subscribers
+                .iter()
+                .map(|agent_id| (
+                    agent_id.clone(),
+                    subscription.delivery_idempotency_key(&publish_key, agent_id)
+                ))
+                .collect::<Vec<_>>()
+        );
+        for (_, invocation) in enqueued {
+            match invocation {
+                AgentInvocation::AgentMethod {
+                    method_name,
+                    principal,
+                    ..
+                } => {
+                    assert_eq!(method_name, "on-order");
+                    assert_eq!(
+                        principal,
+                        Principal::Agent(AgentPrincipal {
+                            agent_id: publisher.agent_id.clone()
+                        })
+                    );
+                }
+                other => panic!("unexpected invocation {other:?}"),
+            }
+        }
+        assert!(
+            topic_service
+                .pending_fan_outs(&publisher, "orders", &subscription.subscriber)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    async fn failing_topic_subscriber_does_not_block_the_others() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let healthy = agent("subscriber(\"healthy\")");
+        let failing = agent("subscriber(\"failing\")");
+        let worker_access = TopicWorkerAccessMock::new(
+            &[healthy.clone(), failing.clone()],
+            std::slice::from_ref(&failing),
+        );
+        let topic_service = create_topic_service();
+        let svc = create_topic_scheduler(
+            storage.clone(),
+            worker_access.clone(),
+            topic_service.clone(),
+            vec![vec![failing.clone(), healthy.clone()]],
+        )
+        .await;
+
+        let environment_id = EnvironmentId::new();
+        publish_fan_out(
+            &svc,
+            &topic_service,
+            topic_fan_out(environment_id, IdempotencyKey::fresh(), OplogIndex::INITIAL),
+        )
+        .await;
+        for _ in 0..2 {
+            svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+                .await
+                .unwrap();
+        }
+
+        let enqueued = worker_access.enqueued();
+        assert_eq!(enqueued.len(), 1);
+        assert_eq!(enqueued[0].0, healthy);
+
+        // The failed delivery stays queued and scheduled, to be retried once its lease expires
+        let pending = storage.list(ShardId::new(0)).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(matches!(
+            &pending[0].action,
+            ScheduledAction::DeliverTopicMessage { owned_agent_id, .. } if owned_agent_id.agent_id == failing
+        ));
+        assert_eq!(
+            topic_service
+                .pending_deliveries(environment_id, &failing)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    async fn topic_messages_are_delivered_in_order_after_a_retry() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let subscriber = agent("subscriber(\"flaky\")");
+        let worker_access = TopicWorkerAccessMock::new(
+            std::slice::from_ref(&subscriber),
+            std::slice::from_ref(&subscriber),
+        );
+        let topic_service = create_topic_service();
+        let svc = create_topic_scheduler(
+            storage,
+            worker_access.clone(),
+            topic_service.clone(),
+            vec![],
+        )
+        .await;
+
+        let target = OwnedAgentId::new(EnvironmentId::new(), &subscriber);
+        let publisher = agent("publisher()");
+        let deliveries = (1..=3)
+            .map(|index| TopicDelivery {
+                publisher: publisher.clone(),
+                publish_index: OplogIndex::from_u64(index),
+                invocation: agent_method_invocation(),
+            })
+            .collect::<Vec<_>>();
+        // The messages are published in order, but their scheduled actions may run in any order
+        for delivery in deliveries.iter().rev() {
+            publish_delivery(&svc, &topic_service, &target, delivery.clone()).await;
+        }
+
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+        assert!(worker_access.enqueued().is_empty());
+
+        // Retried after the leases expired
+        worker_access.recover(&subscriber);
+        svc.process(DateTime::from_str("2023-07-17T10:20:00Z").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            worker_access
+                .enqueued()
+                .into_iter()
+                .map(|(_, invocation)| invocation)
+                .collect::<Vec<_>>(),
+            deliveries
+                .into_iter()
+                .map(|delivery| delivery.invocation)
+                .collect::<Vec<_>>()
+        );
+        assert!(
+            topic_service
+                .pending_deliveries(target.environment_id, &subscriber)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    async fn topic_fan_out_waits_for_every_page_of_the_previous_message() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let first = agent("subscriber(\"first\")");
+        let second = agent("subscriber(\"second\")");
+        let worker_access = TopicWorkerAccessMock::new(&[first.clone(), second.clone()], &[]);
+        let topic_service = create_topic_service();
+        let svc = create_topic_scheduler(
+            storage,
+            worker_access.clone(),
+            topic_service.clone(),
+            vec![vec![first.clone()], vec![second.clone()]],
+        )
+        .await;
+
+        let earlier = topic_fan_out(
+            EnvironmentId::new(),
+            IdempotencyKey::fresh(),
+            OplogIndex::from_u64(3),
+        );
+        let later = TopicFanOut {
+            publish_idempotency_key: IdempotencyKey::fresh(),
+            publish_index: OplogIndex::from_u64(7),
+            ..earlier.clone()
+        };
+        publish_fan_out(&svc, &topic_service, later.clone()).await;
+        publish_fan_out(&svc, &topic_service, earlier.clone()).await;
+
+        for _ in 0..6 {
+            svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+                .await
+                .unwrap();
+        }
+
+        let delivered_to = |target: &AgentId| {
+            worker_access
+                .enqueued()
+                .into_iter()
+                .filter(|(agent_id, _)| agent_id == target)
+                .map(|(_, invocation)| invocation.idempotency_key().cloned())
+                .collect::<Vec<_>>()
+        };
+        for target in [&first, &second] {
+            assert_eq!(
+                delivered_to(target),
+                vec![
+                    Some(
+                        earlier
+                            .subscription
+                            .delivery_idempotency_key(&earlier.publish_idempotency_key, target)
+                    ),
+                    Some(
+                        later
+                            .subscription
+                            .delivery_idempotency_key(&later.publish_idempotency_key, target)
+                    ),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    async fn topic_message_to_a_deleted_agent_is_dropped() {
+        let storage = Arc::new(InMemorySchedulerStorage::new());
+        let worker_access = TopicWorkerAccessMock::new(&[], &[]);
+        let topic_service = create_topic_service();
+        let svc = create_topic_scheduler(
+            storage.clone(),
+            worker_access.clone(),
+            topic_service.clone(),
+            vec![],
+        )
+        .await;
+
+        let deleted = OwnedAgentId::new(EnvironmentId::new(), &agent("subscriber(\"deleted\")"));
+        publish_delivery(
+            &svc,
+            &topic_service,
+            &deleted,
+            TopicDelivery {
+                publisher: agent("publisher()"),
+                publish_index: OplogIndex::INITIAL,
+                invocation: agent_method_invocation(),
+            },
+        )
+        .await;
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        assert!(worker_access.enqueued().is_empty());
+        assert!(storage.list(ShardId::new(0)).await.unwrap().is_empty());
+        assert!(
+            topic_service
+                .pending_deliveries(deleted.environment_id, &deleted.agent_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    async fn replayed_topic_fan_out_delivers_with_the_same_idempotency_keys() {
+        let subscribers = vec![agent("subscriber(\"a\")"), agent("subscriber(\"b\")")];
+        let environment_id = EnvironmentId::new();
+        let publish_key = IdempotencyKey::fresh();
+
+        // Replaying the publishing agent, or retrying a fan-out whose lease expired, schedules
+        // the same fan-out again; the agents deduplicate the deliveries by their idempotency keys
+        let mut runs = Vec::new();
+        for _ in 0..2 {
+            let storage = Arc::new(InMemorySchedulerStorage::new());
+            let worker_access = TopicWorkerAccessMock::new(&subscribers, &[]);
+            let topic_service = create_topic_service();
+            let svc = create_topic_scheduler(
+                storage,
+                worker_access.clone(),
+                topic_service.clone(),
+                vec![subscribers.clone()],
+            )
+            .await;
+            let fan_out = topic_fan_out(environment_id, publish_key.clone(), OplogIndex::INITIAL);
+            publish_fan_out(&svc, &topic_service, fan_out).await;
+            for _ in 0..2 {
+                svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+                    .await
+                    .unwrap();
+            }
+            runs.push(delivery_keys(&worker_access.enqueued()));
+        }
+
+        assert_eq!(runs[0].len(), 2);
+        assert_eq!(runs[0], runs[1]);
+    }
+
     #[test]
     fn shard_id_from_routing_hash_handles_negative_hashes() {
         assert_eq!(ShardId::from_routing_hash(-i64::MAX, 10), ShardId::new(7));
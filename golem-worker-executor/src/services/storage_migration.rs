@@ -0,0 +1,358 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::agent_lifecycle::AUDIT_KEY;
+use crate::services::golem_config::StorageMigrationEnabledConfig;
+use crate::services::oplog::export::OplogExports;
+use crate::services::promise::is_promise_key_of;
+use crate::services::shard::ShardService;
+use crate::services::worker::DefaultWorkerService;
+use crate::storage::indexed::migrating::MigratingIndexedStorage;
+use crate::storage::indexed::{IndexedStorage, IndexedStorageMetaNamespace};
+use crate::storage::keyvalue::migrating::MigratingKeyValueStorage;
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use crate::storage::migration::StorageMigrationState;
+use crate::storage::scheduler::migrating::MigratingSchedulerStorage;
+use golem_common::model::agent::AgentMode;
+use golem_common::model::{AgentId, ShardAssignment, ShardId};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, info, info_span, warn};
+
+const SVC_NAME: &str = "storage_migration";
+
+/// Background process of an online storage migration.
+///
+/// Periodically copies the key-value namespaces, indexed storage keys and scheduled actions of the
+/// shards assigned to this executor to the target backends, and cuts each shard over once its copy
+/// is complete. The namespaces shared by the agents of an environment are copied before any shard
+/// is cut over. Passes that fail are retried on the next check, as the migrating storages keep
+/// writing to both backends in the meantime.
+///
+/// A shard that was already cut over by another executor only needs the keys written since to be
+/// copied again, as the writes of every executor keep both backends up to date.
+pub struct StorageMigrationService {
+    state: Arc<StorageMigrationState>,
+    /// Indexed storage used to enumerate the agents of the shards
+    oplog_storage: Arc<dyn IndexedStorage + Send + Sync>,
+    indexed_storage: Option<Arc<MigratingIndexedStorage>>,
+    key_value_storage: Option<Arc<MigratingKeyValueStorage>>,
+    scheduler_storage: Option<Arc<MigratingSchedulerStorage>>,
+    shard_service: Arc<dyn ShardService>,
+    indexed_storage_layers: usize,
+    shared_namespaces_copied: AtomicBool,
+    config: StorageMigrationEnabledConfig,
+}
+
+impl StorageMigrationService {
+    pub fn new(
+        state: Arc<StorageMigrationState>,
+        oplog_storage: Arc<dyn IndexedStorage + Send + Sync>,
+        indexed_storage: Option<Arc<MigratingIndexedStorage>>,
+        key_value_storage: Option<Arc<MigratingKeyValueStorage>>,
+        scheduler_storage: Option<Arc<MigratingSchedulerStorage>>,
+        shard_service: Arc<dyn ShardService>,
+        indexed_storage_layers: usize,
+        config: StorageMigrationEnabledConfig,
+    ) -> Self {
+        Self {
+            state,
+            oplog_storage,
+            indexed_storage,
+            key_value_storage,
+            scheduler_storage,
+            shard_service,
+            indexed_storage_layers,
+            shared_namespaces_copied: AtomicBool::new(false),
+            config,
+        }
+    }
+
+    pub async fn run(self, shutdown_token: CancellationToken) -> Result<(), anyhow::Error> {
+        async move {
+            loop {
+                if let Some(assignment) = self.shard_service.try_get_current_assignment()
+                    && let Err(err) = self.migrate(&assignment).await
+                {
+                    warn!(
+                        "Storage migration pass failed, retrying in {:?}: {err}",
+                        self.config.check_interval
+                    );
+                }
+
+                tokio::select! {
+                    _ = shutdown_token.cancelled() => break,
+                    _ = tokio::time::sleep(self.config.check_interval) => {}
+                }
+            }
+            Ok(())
+        }
+        .instrument(info_span!("Storage migration"))
+        .await
+    }
+
+    /// Runs a single migration pass over the shards of the given assignment that are not cut
+    /// over yet
+    pub async fn migrate(&self, assignment: &ShardAssignment) -> Result<(), String> {
+        self.state.set_number_of_shards(assignment.number_of_shards);
+        self.state.retain_cut_over(&assignment.shard_ids);
+        if let Some(indexed_storage) = &self.indexed_storage {
+            indexed_storage.retain_shards(&assignment.shard_ids);
+        }
+
+        let cut_over_shards = self.state.cut_over_shards();
+        let pending_shards: HashSet<ShardId> = assignment
+            .shard_ids
+            .difference(&cut_over_shards)
+            .copied()
+            .collect();
+        if pending_shards.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            shards = pending_shards.len(),
+            "Migrating the storage of the assigned shards"
+        );
+
+        // Shards cut over before only need a final pass over the keys written since
+        let persisted_cut_over_shards = self.state.persisted_cut_over_shards().await?;
+        let copied_shards: HashSet<ShardId> = pending_shards
+            .difference(&persisted_cut_over_shards)
+            .copied()
+            .collect();
+
+        let agents = self.copy_indexed_storage(&copied_shards).await?;
+
+        if let Some(key_value_storage) = &self.key_value_storage {
+            self.copy_shared_namespaces(key_value_storage).await?;
+            for shard_id in &copied_shards {
+                self.copy_shard_namespaces(
+                    key_value_storage,
+                    *shard_id,
+                    agents.get(shard_id).map(Vec::as_slice).unwrap_or_default(),
+                )
+                .await?;
+            }
+        }
+
+        for shard_id in pending_shards {
+            let _guard = self.state.cut_over_guard(shard_id).await;
+            if let Some(indexed_storage) = &self.indexed_storage {
+                indexed_storage
+                    .cut_over_shard(shard_id, self.config.batch_size)
+                    .await
+                    .map_err(|err| err.to_string())?;
+            }
+            if let Some(scheduler_storage) = &self.scheduler_storage {
+                scheduler_storage.cut_over_shard(shard_id).await?;
+            }
+            self.state.mark_cut_over(shard_id).await?;
+            info!(shard_id = %shard_id, "Shard cut over to the new storage backends");
+        }
+
+        Ok(())
+    }
+
+    /// Enumerates the agents of the given shards, copying their indexed storage keys if the
+    /// indexed storage is migrated
+    async fn copy_indexed_storage(
+        &self,
+        shards: &HashSet<ShardId>,
+    ) -> Result<HashMap<ShardId, Vec<AgentId>>, String> {
+        if shards.is_empty() || (self.indexed_storage.is_none() && self.key_value_storage.is_none())
+        {
+            return Ok(HashMap::new());
+        }
+
+        let storage = match &self.indexed_storage {
+            Some(indexed_storage) => indexed_storage.source(),
+            None => &self.oplog_storage,
+        };
+
+        let mut agents = HashMap::<ShardId, HashSet<AgentId>>::new();
+        for meta_namespace in self.meta_namespaces() {
+            let mut cursor = 0;
+            loop {
+                let (next_cursor, keys) = storage
+                    .scan(
+                        SVC_NAME,
+                        "scan",
+                        meta_namespace.clone(),
+                        None,
+                        cursor,
+                        self.config.batch_size,
+                    )
+                    .await
+                    .map_err(|err| err.to_string())?;
+
+                for key in keys {
+                    let namespace = meta_namespace.namespace_of(&key)?;
+                    let Some(shard_id) = self
+                        .state
+                        .shard_of(namespace.agent_id())
+                        .filter(|shard_id| shards.contains(shard_id))
+                    else {
+                        continue;
+                    };
+
+                    agents
+                        .entry(shard_id)
+                        .or_default()
+                        .insert(namespace.agent_id().clone());
+                    if let Some(indexed_storage) = &self.indexed_storage {
+                        indexed_storage
+                            .migrate_key(namespace, &key, self.config.batch_size)
+                            .await
+                            .map_err(|err| err.to_string())?;
+                    }
+                }
+
+                if next_cursor == 0 {
+                    break;
+                }
+                cursor = next_cursor;
+            }
+        }
+
+        Ok(agents
+            .into_iter()
+            .map(|(shard_id, agents)| (shard_id, agents.into_iter().collect()))
+            .collect())
+    }
+
+    /// Copies the namespaces shared by the agents of the environments. Every executor writes
+    /// them to both backends, so they only have to be copied once.
+    async fn copy_shared_namespaces(
+        &self,
+        key_value_storage: &MigratingKeyValueStorage,
+    ) -> Result<(), String> {
+        if self.shared_namespaces_copied.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        for namespace in key_value_storage
+            .shared_namespaces(SVC_NAME, "shared_namespaces")
+            .await?
+        {
+            match namespace {
+                KeyValueStorageNamespace::Topic { .. } => {
+                    // Subscriptions are values, their index and the delivery queues are sets
+                    key_value_storage
+                        .copy_all_values(namespace.clone(), self.config.batch_size as usize)
+                        .await?;
+                    key_value_storage.copy_all_sets(namespace).await?
+                }
+                KeyValueStorageNamespace::AgentLifecycleAudit { .. } => {
+                    key_value_storage
+                        .copy_sorted_sets(namespace, &[AUDIT_KEY.to_string()])
+                        .await?
+                }
+                _ => {
+                    key_value_storage
+                        .copy_all_values(namespace, self.config.batch_size as usize)
+                        .await?
+                }
+            }
+        }
+
+        self.shared_namespaces_copied.store(true, Ordering::Release);
+        info!("Copied the shared key-value namespaces");
+        Ok(())
+    }
+
+    /// Copies the key-value namespaces of a shard and of its agents
+    async fn copy_shard_namespaces(
+        &self,
+        key_value_storage: &MigratingKeyValueStorage,
+        shard_id: ShardId,
+        agents: &[AgentId],
+    ) -> Result<(), String> {
+        let batch_size = self.config.batch_size as usize;
+
+        key_value_storage
+            .copy_sets(
+                KeyValueStorageNamespace::RunningWorkers,
+                &[DefaultWorkerService::running_in_shard_key(&shard_id)],
+            )
+            .await?;
+
+        for agent_id in agents {
+            key_value_storage
+                .copy_all_values(
+                    KeyValueStorageNamespace::AgentStatus {
+                        agent_id: agent_id.clone(),
+                    },
+                    batch_size,
+                )
+                .await?;
+            key_value_storage
+                .copy_all_values(
+                    KeyValueStorageNamespace::AgentStatusCheckpoint {
+                        agent_id: agent_id.clone(),
+                    },
+                    batch_size,
+                )
+                .await?;
+
+            let worker_keys = vec![
+                DefaultWorkerService::agent_mode_key(agent_id),
+                OplogExports::positions_key(agent_id),
+            ];
+            key_value_storage
+                .copy_values(
+                    KeyValueStorageNamespace::Worker {
+                        agent_id: agent_id.clone(),
+                    },
+                    worker_keys,
+                    batch_size,
+                )
+                .await?;
+
+            // Promises may share a single structure between agents, so the keys are filtered
+            let promise_namespace = KeyValueStorageNamespace::Promise {
+                agent_id: agent_id.clone(),
+            };
+            let promise_keys = key_value_storage
+                .keys(SVC_NAME, "promise_keys", promise_namespace.clone())
+                .await?
+                .into_iter()
+                .filter(|key| is_promise_key_of(agent_id, key))
+                .collect();
+            key_value_storage
+                .copy_values(promise_namespace, promise_keys, batch_size)
+                .await?;
+        }
+
+        info!(
+            shard_id = %shard_id,
+            agents = agents.len(),
+            "Copied the key-value namespaces of the shard"
+        );
+        Ok(())
+    }
+
+    fn meta_namespaces(&self) -> Vec<IndexedStorageMetaNamespace> {
+        let mut result = Vec::new();
+        for agent_mode in [AgentMode::Durable, AgentMode::Ephemeral] {
+            result.push(IndexedStorageMetaNamespace::Oplog { agent_mode });
+            for level in 1..self.indexed_storage_layers {
+                result.push(IndexedStorageMetaNamespace::CompressedOplog { agent_mode, level });
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,349 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::keyvalue::{
+    KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
+};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::topic::{TopicDelivery, TopicFanOut, TopicSubscriber, TopicSubscription};
+use golem_common::model::{AgentId, OwnedAgentId};
+use std::sync::Arc;
+
+/// Service maintaining the subscriptions of durable agent-to-agent pub/sub topics, and the
+/// queues of the messages being delivered to them.
+///
+/// Topics are scoped to an environment. The publishing agent queues the published messages
+/// (see `golem:agent/host.publish`) and schedules their delivery, which the scheduler carries
+/// out one message at a time per subscriber.
+#[async_trait]
+pub trait TopicService: Send + Sync {
+    /// Subscribes to a topic. If the subscriber was already subscribed to the topic, its
+    /// subscription is replaced with the new one in a single write.
+    async fn subscribe(
+        &self,
+        environment_id: EnvironmentId,
+        topic: &str,
+        subscription: TopicSubscription,
+    ) -> anyhow::Result<()>;
+
+    /// Removes the subscription of the given subscriber from the topic.
+    async fn unsubscribe(
+        &self,
+        environment_id: EnvironmentId,
+        topic: &str,
+        subscriber: &TopicSubscriber,
+    ) -> anyhow::Result<()>;
+
+    /// Gets the subscriptions of a topic, in a stable order
+    async fn get_subscriptions(
+        &self,
+        environment_id: EnvironmentId,
+        topic: &str,
+    ) -> anyhow::Result<Vec<TopicSubscription>>;
+
+    /// Adds a message to the delivery queue of a subscribed agent
+    async fn enqueue_delivery(
+        &self,
+        environment_id: EnvironmentId,
+        target: &AgentId,
+        delivery: &TopicDelivery,
+    ) -> anyhow::Result<()>;
+
+    /// Gets the delivery queue of a subscribed agent, ordered by publisher and then by the
+    /// order the publisher published the messages
+    async fn pending_deliveries(
+        &self,
+        environment_id: EnvironmentId,
+        target: &AgentId,
+    ) -> anyhow::Result<Vec<TopicDelivery>>;
+
+    /// Removes a delivered message from the delivery queue of a subscribed agent
+    async fn remove_delivery(
+        &self,
+        environment_id: EnvironmentId,
+        target: &AgentId,
+        delivery: &TopicDelivery,
+    ) -> anyhow::Result<()>;
+
+    /// Adds a fan-out to the queue of the fan-outs of its publisher, topic and subscriber
+    async fn enqueue_fan_out(&self, fan_out: &TopicFanOut) -> anyhow::Result<()>;
+
+    /// Gets the queued fan-outs of a publisher, topic and subscriber, in the order they were
+    /// published
+    async fn pending_fan_outs(
+        &self,
+        publisher: &OwnedAgentId,
+        topic: &str,
+        subscriber: &TopicSubscriber,
+    ) -> anyhow::Result<Vec<TopicFanOut>>;
+
+    /// Removes a fan-out from its queue
+    async fn remove_fan_out(&self, fan_out: &TopicFanOut) -> anyhow::Result<()>;
+}
+
+#[derive(Clone, Debug)]
+pub struct DefaultTopicService {
+    key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+}
+
+impl DefaultTopicService {
+    pub fn new(key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>) -> Self {
+        Self { key_value_storage }
+    }
+
+    /// Builds a key from a prefix and length-prefixed parts, so that no combination of topic
+    /// names and agent ids can produce the key of another entry
+    fn key(prefix: &str, parts: &[&str]) -> String {
+        let mut key = prefix.to_string();
+        for part in parts {
+            key.push_str(&format!(":{}:{part}", part.len()));
+        }
+        key
+    }
+
+    /// The set of the subscribers of a topic
+    fn subscribers_key(topic: &str) -> String {
+        Self::key("subscribers", &[topic])
+    }
+
+    /// The subscription of one subscriber of a topic
+    fn subscription_key(topic: &str, subscriber: &TopicSubscriber) -> String {
+        Self::key("subscription", &[topic, &subscriber.to_string()])
+    }
+
+    fn delivery_queue_key(target: &AgentId) -> String {
+        Self::key("delivery", &[&target.to_string()])
+    }
+
+    fn fan_out_queue_key(
+        publisher: &OwnedAgentId,
+        topic: &str,
+        subscriber: &TopicSubscriber,
+    ) -> String {
+        Self::key(
+            "fan-out",
+            &[
+                &publisher.agent_id.to_string(),
+                topic,
+                &subscriber.to_string(),
+            ],
+        )
+    }
+}
+
+#[async_trait]
+impl TopicService for DefaultTopicService {
+    async fn subscribe(
+        &self,
+        environment_id: EnvironmentId,
+        topic: &str,
+        subscription: TopicSubscription,
+    ) -> anyhow::Result<()> {
+        // The subscription itself is a single value, so replacing it is atomic; the subscriber
+        // set only indexes it and is idempotent to add to
+        self.key_value_storage
+            .with_entity("topic", "subscribe", "subscription")
+            .set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::subscription_key(topic, &subscription.subscriber),
+                &subscription,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        self.key_value_storage
+            .with_entity("topic", "subscribe", "subscriber")
+            .add_to_set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::subscribers_key(topic),
+                &subscription.subscriber,
+            )
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    async fn unsubscribe(
+        &self,
+        environment_id: EnvironmentId,
+        topic: &str,
+        subscriber: &TopicSubscriber,
+    ) -> anyhow::Result<()> {
+        self.key_value_storage
+            .with_entity("topic", "unsubscribe", "subscriber")
+            .remove_from_set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::subscribers_key(topic),
+                subscriber,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        self.key_value_storage
+            .with("topic", "unsubscribe")
+            .del(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::subscription_key(topic, subscriber),
+            )
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    async fn get_subscriptions(
+        &self,
+        environment_id: EnvironmentId,
+        topic: &str,
+    ) -> anyhow::Result<Vec<TopicSubscription>> {
+        let mut subscribers: Vec<TopicSubscriber> = self
+            .key_value_storage
+            .with_entity("topic", "get_subscriptions", "subscriber")
+            .members_of_set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::subscribers_key(topic),
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        // Set members have no guaranteed order; sorting keeps the fan-out order deterministic
+        subscribers.sort_by_cached_key(|subscriber| subscriber.to_string());
+
+        let keys = subscribers
+            .iter()
+            .map(|subscriber| Self::subscription_key(topic, subscriber))
+            .collect::<Vec<_>>();
+        let subscriptions: Vec<Option<TopicSubscription>> = self
+            .key_value_storage
+            .with_entity("topic", "get_subscriptions", "subscription")
+            .get_many(KeyValueStorageNamespace::Topic { environment_id }, keys)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        // A subscriber without a subscription is being unsubscribed
+        Ok(subscriptions.into_iter().flatten().collect())
+    }
+
+    async fn enqueue_delivery(
+        &self,
+        environment_id: EnvironmentId,
+        target: &AgentId,
+        delivery: &TopicDelivery,
+    ) -> anyhow::Result<()> {
+        self.key_value_storage
+            .with_entity("topic", "enqueue_delivery", "delivery")
+            .add_to_set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::delivery_queue_key(target),
+                delivery,
+            )
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    async fn pending_deliveries(
+        &self,
+        environment_id: EnvironmentId,
+        target: &AgentId,
+    ) -> anyhow::Result<Vec<TopicDelivery>> {
+        let mut deliveries: Vec<TopicDelivery> = self
+            .key_value_storage
+            .with_entity("topic", "pending_deliveries", "delivery")
+            .members_of_set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::delivery_queue_key(target),
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        deliveries.sort_by_cached_key(|delivery| {
+            (delivery.publisher.to_string(), delivery.publish_index)
+        });
+        Ok(deliveries)
+    }
+
+    async fn remove_delivery(
+        &self,
+        environment_id: EnvironmentId,
+        target: &AgentId,
+        delivery: &TopicDelivery,
+    ) -> anyhow::Result<()> {
+        self.key_value_storage
+            .with_entity("topic", "remove_delivery", "delivery")
+            .remove_from_set(
+                KeyValueStorageNamespace::Topic { environment_id },
+                &Self::delivery_queue_key(target),
+                delivery,
+            )
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    async fn enqueue_fan_out(&self, fan_out: &TopicFanOut) -> anyhow::Result<()> {
+        self.key_value_storage
+            .with_entity("topic", "enqueue_fan_out", "fan_out")
+            .add_to_set(
+                KeyValueStorageNamespace::Topic {
+                    environment_id: fan_out.publisher.environment_id,
+                },
+                &Self::fan_out_queue_key(
+                    &fan_out.publisher,
+                    &fan_out.topic,
+                    &fan_out.subscription.subscriber,
+                ),
+                fan_out,
+            )
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    async fn pending_fan_outs(
+        &self,
+        publisher: &OwnedAgentId,
+        topic: &str,
+        subscriber: &TopicSubscriber,
+    ) -> anyhow::Result<Vec<TopicFanOut>> {
+        let mut fan_outs: Vec<TopicFanOut> = self
+            .key_value_storage
+            .with_entity("topic", "pending_fan_outs", "fan_out")
+            .members_of_set(
+                KeyValueStorageNamespace::Topic {
+                    environment_id: publisher.environment_id,
+                },
+                &Self::fan_out_queue_key(publisher, topic, subscriber),
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        fan_outs.sort_by_key(|fan_out| {
+            (
+                fan_out.publish_index,
+                fan_out.cursor.layer,
+                fan_out.cursor.cursor,
+            )
+        });
+        Ok(fan_outs)
+    }
+
+    async fn remove_fan_out(&self, fan_out: &TopicFanOut) -> anyhow::Result<()> {
+        self.key_value_storage
+            .with_entity("topic", "remove_fan_out", "fan_out")
+            .remove_from_set(
+                KeyValueStorageNamespace::Topic {
+                    environment_id: fan_out.publisher.environment_id,
+                },
+                &Self::fan_out_queue_key(
+                    &fan_out.publisher,
+                    &fan_out.topic,
+                    &fan_out.subscription.subscriber,
+                ),
+                fan_out,
+            )
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+}
@@ -18,6 +18,7 @@ use super::{HasComponentService, HasConfig, HasOplogService};
 use crate::metrics::workers::record_worker_call;
 use crate::services::oplog::OplogService;
 use crate::services::shard::ShardService;
+use crate::storage::encryption::{DataKeyScope, EnvelopeEncryption};
 use crate::storage::keyvalue::{
     KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
 };
@@ -326,6 +327,8 @@ pub struct DefaultWorkerService {
     shard_service: Arc<dyn ShardService>,
     oplog_service: Arc<dyn OplogService>,
     component_service: Arc<dyn ComponentService>,
+    /// Present when encryption at rest is enabled, to shred the data key of removed agents
+    encryption: Option<Arc<EnvelopeEncryption>>,
     config: Arc<GolemConfig>,
 }
 
@@ -335,6 +338,7 @@ impl DefaultWorkerService {
         shard_service: Arc<dyn ShardService>,
         oplog_service: Arc<dyn OplogService>,
         component_service: Arc<dyn ComponentService>,
+        encryption: Option<Arc<EnvelopeEncryption>>,
         config: Arc<GolemConfig>,
     ) -> Self {
         Self {
@@ -342,6 +346,7 @@ impl DefaultWorkerService {
             shard_service,
             oplog_service,
             component_service,
+            encryption,
             config,
         }
     }
@@ -393,11 +398,11 @@ impl DefaultWorkerService {
     /// only); never written on the per-commit hot path. The value never changes for the life of
     /// the worker. Lives in the `Worker` namespace (not `AgentStatus`) since it has an independent
     /// lifecycle from the status fields.
-    fn agent_mode_key(agent_id: &AgentId) -> String {
+    pub(crate) fn agent_mode_key(agent_id: &AgentId) -> String {
         format!("worker:agent_mode:{}", agent_id.to_redis_key())
     }
 
-    fn running_in_shard_key(shard_id: &ShardId) -> String {
+    pub(crate) fn running_in_shard_key(shard_id: &ShardId) -> String {
         format!("worker:running_in_shard:{shard_id}")
     }
 
@@ -783,6 +788,16 @@ impl WorkerService for DefaultWorkerService {
         if let Some(agent_mode) = self.get_agent_mode(owned_agent_id).await {
             self.oplog_service.delete(owned_agent_id, agent_mode).await;
         }
+        if let Some(encryption) = &self.encryption {
+            // Crypto-shredding: whatever is left of the agent's oplog and payloads in the
+            // storages (archives, replicas, backups) becomes unreadable
+            encryption
+                .shred(&DataKeyScope::Agent(owned_agent_id.agent_id()))
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to shred the data key of {owned_agent_id}: {err}")
+                });
+        }
         self.remove_cached_status(owned_agent_id).await;
 
         let shard_assignment = self
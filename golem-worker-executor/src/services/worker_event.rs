@@ -211,6 +211,8 @@ fn label(event: &InternalWorkerEvent) -> &'static str {
         InternalWorkerEvent::InvocationStart { .. } => "invocation_start",
         InternalWorkerEvent::InvocationFinished { .. } => "invocation_finished",
         InternalWorkerEvent::PluginError { .. } => "plugin_error",
+        InternalWorkerEvent::WebSocketSend { .. } => "websocket_send",
+        InternalWorkerEvent::WebSocketClose { .. } => "websocket_close",
     }
 }
 
@@ -20,47 +20,67 @@ use crate::durable_host::websocket::WebSocketConnectionPool;
 use crate::metrics::workers::record_worker_call;
 use crate::model::ExecutionStatus;
 use crate::services::events::Events;
+use crate::services::invocation_interceptor::InvocationInterceptorPlugin;
 use crate::services::oplog::plugin::OplogProcessorPlugin;
-use crate::services::oplog::{CommitLevel, Oplog, OplogOps};
+use crate::services::oplog::{CommitLevel, Oplog, OplogOps, TimeTravelOplog};
 use crate::services::resource_limits::ResourceLimits;
 use crate::services::rpc::Rpc;
 use crate::services::shard::ShardService;
 use crate::services::worker_proxy::WorkerProxy;
 use crate::services::{
     HasActiveWorkers, HasAgentTypesService, HasBlobStoreService, HasComponentService, HasConfig,
-    HasEvents, HasExtraDeps, HasFileLoader, HasHttpConnectionPool, HasKeyValueService,
-    HasLeakSentinel, HasOplogProcessorPlugin, HasOplogService, HasPromiseService, HasQuotaService,
-    HasResourceLimits, HasRpc, HasRunningWorkerEnumerationService, HasSchedulerService,
-    HasShardManagerService, HasShardService, HasShutdownToken, HasWasmtimeEngine,
+    HasEvents, HasExtraDeps, HasFileLoader, HasFilesystemCheckpointService, HasHttpConnectionPool,
+    HasInvocationInterceptorPlugin, HasKeyValueService, HasLeakSentinel, HasOplogProcessorPlugin,
+    HasOplogService, HasPromiseService, HasQuotaService, HasResourceLimits, HasRpc,
+    HasRunningWorkerEnumerationService, HasSchedulerService, HasShardManagerService,
+    HasShardService, HasShutdownToken, HasTopicService, HasWasmtimeEngine,
     HasWebSocketConnectionPool, HasWorkerActivator, HasWorkerEnumerationService, HasWorkerProxy,
-    HasWorkerService, active_workers, agent_types, blob_store, component, golem_config, key_value,
-    oplog, promise, scheduler, shard_manager, worker, worker_activator, worker_enumeration,
+    HasWorkerService, active_workers, agent_types, blob_store, component, filesystem_checkpoint,
+    golem_config, key_value, oplog, promise, scheduler, shard_manager, topics, worker,
+    worker_activator, worker_enumeration,
 };
 use crate::services::{HasOplog, HasRdbmsService, HasWorkerForkService, rdbms};
 use crate::worker::Worker;
+use crate::worker::status::update_status_with_new_entries;
 use crate::workerctx::WorkerCtx;
 use async_trait::async_trait;
+use futures::StreamExt;
+use futures::future;
+use futures::stream::{self, BoxStream};
 use golem_common::base_model::component::ComponentRevision;
 use golem_common::base_model::regions::DeletedRegionsBuilder;
 use golem_common::model::account::AccountId;
-use golem_common::model::agent::Principal;
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, Principal};
+use golem_common::model::agent_archive::{
+    AgentArchiveComponentRevision, AgentArchiveFilesystemChunk, AgentArchiveHeader,
+    AgentArchivePart, AgentArchivePayload,
+};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::host_functions::GolemApiFork;
 use golem_common::model::oplog::{
     DurableFunctionType, HostPayloadPair, HostRequest, HostRequestNoInput, HostResponse,
-    HostResponseGolemApiFork, OplogEntry, OplogIndex, OplogIndexRange,
+    HostResponseGolemApiFork, OplogEntry, OplogIndex, OplogIndexRange, PayloadId, RawOplogPayload,
+};
+use golem_common::model::{
+    AgentFingerprint, AgentInvocation, AgentInvocationOutput, AgentMetadata, AgentStatusRecord,
+    Timestamp,
 };
-use golem_common::model::{AgentFingerprint, AgentMetadata, Timestamp};
 use golem_common::model::{AgentId, IdempotencyKey, OwnedAgentId};
 use golem_common::read_only_lock;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::model::auth::AuthCtx;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
 use tokio::runtime::Handle;
 use uuid::Uuid;
 use wasmtime_wasi_http::HttpConnectionPool;
 
+/// The parts of an agent archive, in the order described by [`AgentArchivePart`]
+pub type AgentArchiveStream = BoxStream<'static, Result<AgentArchivePart, WorkerExecutorError>>;
+
 #[async_trait]
 pub trait WorkerForkService: Send + Sync {
     // TODO: this should be restricted to targets within the same component
@@ -84,6 +104,40 @@ pub trait WorkerForkService: Send + Sync {
         forked_phantom_id: Uuid,
         auth_ctx: &AuthCtx,
     ) -> Result<(), WorkerExecutorError>;
+
+    /// Exports the full oplog of an agent, its external payloads and its filesystem checkpoint as
+    /// the parts of a portable archive. Agents that used key-value or blob storage are rejected,
+    /// as that data belongs to their environment and can't be moved with them.
+    async fn export_agent(
+        &self,
+        agent_id: &OwnedAgentId,
+    ) -> Result<AgentArchiveStream, WorkerExecutorError>;
+
+    /// Recreates an exported agent as `target_agent_id`, which can belong to a different
+    /// component and environment than the exported one.
+    async fn import_agent(
+        &self,
+        archive: AgentArchiveStream,
+        target_agent_id: &OwnedAgentId,
+        auth_ctx: &AuthCtx,
+    ) -> Result<(), WorkerExecutorError>;
+
+    /// Invokes a read-only method on a temporary copy of the agent, replayed up to the given
+    /// point of its oplog. The copy is deleted once the invocation completes.
+    async fn invoke_at(
+        &self,
+        source_agent_id: &OwnedAgentId,
+        at: TimeTravelTarget,
+        invocation: AgentInvocation,
+    ) -> Result<AgentInvocationOutput, WorkerExecutorError>;
+}
+
+/// Point of an agent's history a time-travel invocation is made against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeTravelTarget {
+    OplogIndex(OplogIndex),
+    /// The last oplog entry written at or before the given time
+    Timestamp(Timestamp),
 }
 
 pub struct DefaultWorkerFork<Ctx: WorkerCtx> {
@@ -106,6 +160,7 @@ pub struct DefaultWorkerFork<Ctx: WorkerCtx> {
     pub golem_config: Arc<golem_config::GolemConfig>,
     pub shard_service: Arc<dyn ShardService>,
     pub key_value_service: Arc<dyn key_value::KeyValueService>,
+    pub topic_service: Arc<dyn topics::TopicService>,
     pub blob_store_service: Arc<dyn blob_store::BlobStoreService>,
     pub rdbms_service: Arc<dyn rdbms::RdbmsService>,
     pub oplog_service: Arc<dyn oplog::OplogService>,
@@ -113,7 +168,9 @@ pub struct DefaultWorkerFork<Ctx: WorkerCtx> {
     pub worker_activator: Arc<dyn worker_activator::WorkerActivator<Ctx>>,
     pub events: Arc<Events>,
     pub file_loader: Arc<FileLoader>,
+    pub filesystem_checkpoint_service: Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>,
     pub oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+    pub invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
     pub resource_limits: Arc<dyn ResourceLimits>,
     pub shutdown_token: tokio_util::sync::CancellationToken,
     pub http_connection_pool: Option<HttpConnectionPool>,
@@ -205,6 +262,12 @@ impl<Ctx: WorkerCtx> HasKeyValueService for DefaultWorkerFork<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> HasTopicService for DefaultWorkerFork<Ctx> {
+    fn topic_service(&self) -> Arc<dyn topics::TopicService> {
+        self.topic_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasRdbmsService for DefaultWorkerFork<Ctx> {
     fn rdbms_service(&self) -> Arc<dyn rdbms::RdbmsService> {
         self.rdbms_service.clone()
@@ -283,12 +346,26 @@ impl<Ctx: WorkerCtx> HasFileLoader for DefaultWorkerFork<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> HasFilesystemCheckpointService for DefaultWorkerFork<Ctx> {
+    fn filesystem_checkpoint_service(
+        &self,
+    ) -> Arc<dyn filesystem_checkpoint::FilesystemCheckpointService> {
+        self.filesystem_checkpoint_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasOplogProcessorPlugin for DefaultWorkerFork<Ctx> {
     fn oplog_processor_plugin(&self) -> Arc<dyn OplogProcessorPlugin> {
         self.oplog_processor_plugin.clone()
     }
 }
 
+impl<Ctx: WorkerCtx> HasInvocationInterceptorPlugin for DefaultWorkerFork<Ctx> {
+    fn invocation_interceptor_plugin(&self) -> Arc<dyn InvocationInterceptorPlugin> {
+        self.invocation_interceptor_plugin.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasResourceLimits for DefaultWorkerFork<Ctx> {
     fn resource_limits(&self) -> Arc<dyn ResourceLimits> {
         self.resource_limits.clone()
@@ -346,6 +423,7 @@ impl<Ctx: WorkerCtx> Clone for DefaultWorkerFork<Ctx> {
             golem_config: self.golem_config.clone(),
             shard_service: self.shard_service.clone(),
             key_value_service: self.key_value_service.clone(),
+            topic_service: self.topic_service.clone(),
             blob_store_service: self.blob_store_service.clone(),
             rdbms_service: self.rdbms_service.clone(),
             oplog_service: self.oplog_service.clone(),
@@ -353,7 +431,9 @@ impl<Ctx: WorkerCtx> Clone for DefaultWorkerFork<Ctx> {
             worker_activator: self.worker_activator.clone(),
             events: self.events.clone(),
             file_loader: self.file_loader.clone(),
+            filesystem_checkpoint_service: self.filesystem_checkpoint_service.clone(),
             oplog_processor_plugin: self.oplog_processor_plugin.clone(),
+            invocation_interceptor_plugin: self.invocation_interceptor_plugin.clone(),
             resource_limits: self.resource_limits.clone(),
             shutdown_token: self.shutdown_token.clone(),
             http_connection_pool: self.http_connection_pool.clone(),
@@ -386,6 +466,7 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
         golem_config: Arc<golem_config::GolemConfig>,
         shard_service: Arc<dyn ShardService>,
         key_value_service: Arc<dyn key_value::KeyValueService>,
+        topic_service: Arc<dyn topics::TopicService>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService>,
         rdbms_service: Arc<dyn rdbms::RdbmsService>,
         oplog_service: Arc<dyn oplog::OplogService>,
@@ -393,7 +474,9 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
         worker_activator: Arc<dyn worker_activator::WorkerActivator<Ctx>>,
         events: Arc<Events>,
         file_loader: Arc<FileLoader>,
+        filesystem_checkpoint_service: Arc<dyn filesystem_checkpoint::FilesystemCheckpointService>,
         oplog_processor_plugin: Arc<dyn OplogProcessorPlugin>,
+        invocation_interceptor_plugin: Arc<dyn InvocationInterceptorPlugin>,
         resource_limits: Arc<dyn ResourceLimits>,
         environment_state_service: Arc<dyn EnvironmentStateService>,
         agent_types: Arc<dyn agent_types::AgentTypesService>,
@@ -423,6 +506,7 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
             golem_config,
             shard_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             oplog_service,
@@ -430,7 +514,9 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
             worker_activator,
             events,
             file_loader,
+            filesystem_checkpoint_service,
             oplog_processor_plugin,
+            invocation_interceptor_plugin,
             resource_limits,
             shutdown_token,
             http_connection_pool,
@@ -663,6 +749,111 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
         Ok(new_oplog)
     }
 
+    /// Resolves the last oplog index a time-travel invocation replays the agent up to. If an
+    /// invocation is still in progress at the requested point, the cut-off is moved before its
+    /// start, so the replay does not execute the rest of it live.
+    async fn resolve_time_travel_cut_off(
+        &self,
+        agent_id: &OwnedAgentId,
+        agent_mode: AgentMode,
+        at: TimeTravelTarget,
+    ) -> Result<OplogIndex, WorkerExecutorError> {
+        let last_index = self
+            .oplog_service
+            .get_last_index(agent_id, agent_mode)
+            .await;
+        if let TimeTravelTarget::OplogIndex(idx) = at
+            && idx > last_index
+        {
+            return Err(WorkerExecutorError::invalid_request(format!(
+                "oplog index {idx} is beyond the last oplog index of the agent ({last_index})"
+            )));
+        }
+
+        let mut cut_off = None;
+        let mut invocation_start = None;
+        let mut idx = OplogIndex::INITIAL;
+        'read: while idx <= last_index {
+            let entries = self
+                .oplog_service
+                .read(
+                    agent_id,
+                    agent_mode,
+                    idx,
+                    Self::oplog_batch_size(idx, last_index),
+                )
+                .await;
+            let Some(next_idx) = entries.keys().next_back().map(|last| last.next()) else {
+                break;
+            };
+            for (idx, entry) in entries {
+                let included = match at {
+                    TimeTravelTarget::OplogIndex(at) => idx <= at,
+                    TimeTravelTarget::Timestamp(at) => entry.timestamp() <= at,
+                };
+                if !included {
+                    break 'read;
+                }
+                match entry {
+                    OplogEntry::AgentInvocationStarted { .. } => invocation_start = Some(idx),
+                    OplogEntry::AgentInvocationFinished { .. } => invocation_start = None,
+                    _ => {}
+                }
+                cut_off = Some(idx);
+            }
+            idx = next_idx;
+        }
+
+        invocation_start
+            .map(|start| start.previous())
+            .or(cut_off)
+            .ok_or(WorkerExecutorError::invalid_request(
+                "the agent did not exist at the requested point of time",
+            ))
+    }
+
+    /// Folds the status of a time-travel replay from its oplog, reading the entries copied from
+    /// the source agent in batches
+    async fn fold_time_travel_status(
+        &self,
+        replay_oplog: &TimeTravelOplog,
+        agent_mode: AgentMode,
+        cut_off: OplogIndex,
+    ) -> Result<AgentStatusRecord, WorkerExecutorError> {
+        let retry = &self.golem_config.retry;
+        let mut status = AgentStatusRecord::default();
+        let mut idx = OplogIndex::INITIAL;
+        while idx <= cut_off {
+            let entries = replay_oplog
+                .read_many(idx, Self::oplog_batch_size(idx, cut_off))
+                .await;
+            let Some(next_idx) = entries.keys().next_back().map(|last| last.next()) else {
+                break;
+            };
+            status = match update_status_with_new_entries(agent_mode, status, entries, retry) {
+                Some(status) => status,
+                None => {
+                    // A region dropped by a later entry covers the status folded so far, so it
+                    // has to be recomputed from the start
+                    let entries = replay_oplog
+                        .read_many(OplogIndex::INITIAL, cut_off.into())
+                        .await;
+                    return update_status_with_new_entries(
+                        agent_mode,
+                        AgentStatusRecord::default(),
+                        entries,
+                        retry,
+                    )
+                    .ok_or(WorkerExecutorError::unknown(
+                        "Failed to calculate the status of the time-travel replay",
+                    ));
+                }
+            };
+            idx = next_idx;
+        }
+        Ok(status)
+    }
+
     pub fn update_agent_id(
         entry: OplogEntry,
         agent_id: &AgentId,
@@ -703,6 +894,335 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
             _ => None,
         }
     }
+
+    /// Checks that every component revision the archived oplog refers to exists for the target
+    /// component and is built from the same WASM, so the imported agent replays deterministically.
+    async fn validate_import_target(
+        &self,
+        header: &AgentArchiveHeader,
+        target_agent_id: &OwnedAgentId,
+    ) -> Result<(), WorkerExecutorError> {
+        if header.format_version != AgentArchiveHeader::FORMAT_VERSION {
+            return Err(WorkerExecutorError::invalid_request(format!(
+                "unsupported agent archive format version: {}",
+                header.format_version
+            )));
+        }
+
+        if self.worker_service.get(target_agent_id).await.is_some() {
+            return Err(WorkerExecutorError::worker_already_exists(
+                target_agent_id.agent_id.clone(),
+            ));
+        }
+
+        for expected in &header.component_revisions {
+            let component = self
+                .component_service
+                .get_metadata(
+                    target_agent_id.agent_id.component_id,
+                    Some(expected.revision),
+                )
+                .await
+                .map_err(|_| {
+                    WorkerExecutorError::invalid_request(format!(
+                        "target component {} has no revision {}",
+                        target_agent_id.agent_id.component_id, expected.revision
+                    ))
+                })?;
+
+            if component.wasm_hash.to_string() != expected.wasm_hash {
+                return Err(WorkerExecutorError::invalid_request(format!(
+                    "revision {} of target component {} does not match the exported component revision",
+                    expected.revision, target_agent_id.agent_id.component_id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of oplog entries read at once while streaming an oplog, never reading past `last_index`
+    fn oplog_batch_size(idx: OplogIndex, last_index: OplogIndex) -> u64 {
+        const OPLOG_BATCH_SIZE: u64 = 100;
+        (u64::from(last_index) - u64::from(idx) + 1).min(OPLOG_BATCH_SIZE)
+    }
+
+    /// Key-value buckets and blob store containers belong to the environment and are shared by
+    /// its agents, so an agent that used them can't be moved without them
+    fn uses_environment_storage(entry: &OplogEntry) -> bool {
+        match entry {
+            OplogEntry::Start { function_name, .. } => {
+                let function_name = function_name.to_string();
+                function_name.starts_with("keyvalue::") || function_name.starts_with("blobstore::")
+            }
+            _ => false,
+        }
+    }
+
+    async fn import_archive_part(
+        &self,
+        import: &mut AgentImport,
+        part: AgentArchivePart,
+        target_agent_id: &OwnedAgentId,
+    ) -> Result<(), WorkerExecutorError> {
+        let agent_mode = import.header.agent_mode;
+        if import.ended {
+            return Err(WorkerExecutorError::invalid_request(
+                "agent archive continues after its end",
+            ));
+        }
+        match part {
+            AgentArchivePart::Header(_) => Err(WorkerExecutorError::invalid_request(
+                "agent archive contains more than one header",
+            )),
+            AgentArchivePart::End => match &import.oplog {
+                Some(oplog) => {
+                    oplog.commit(CommitLevel::Always).await;
+                    import.ended = true;
+                    Ok(())
+                }
+                None => Err(WorkerExecutorError::invalid_request(
+                    "agent archive contains no oplog entries",
+                )),
+            },
+            AgentArchivePart::Payload(payload) if import.oplog.is_none() => {
+                let md5_hash = md5::compute(&payload.data).to_vec();
+                if md5_hash != payload.md5_hash {
+                    return Err(WorkerExecutorError::invalid_request(format!(
+                        "corrupted payload {} in agent archive",
+                        payload.payload_id
+                    )));
+                }
+                // External payloads are stored per agent, so they are uploaded again for the
+                // target agent and the entries are updated to refer to the new copies
+                let target = self
+                    .oplog_service
+                    .upload_raw_payload(target_agent_id, agent_mode, payload.data)
+                    .await
+                    .map_err(WorkerExecutorError::runtime)?;
+                import.relocated.insert(payload.payload_id, target);
+                Ok(())
+            }
+            AgentArchivePart::FilesystemCheckpoint(chunk) if import.oplog.is_none() => {
+                import.write_filesystem_checkpoint(chunk).await
+            }
+            AgentArchivePart::Entries(entries) => {
+                let mut entries = entries.into_iter();
+                let oplog = match &import.oplog {
+                    Some(oplog) => oplog.clone(),
+                    None => {
+                        // Stored before the oplog exists, as recovering the imported agent
+                        // from its latest snapshot relies on it
+                        if let Some((snapshot_index, archive)) =
+                            import.finish_filesystem_checkpoint().await?
+                        {
+                            self.filesystem_checkpoint_service
+                                .import(target_agent_id, agent_mode, snapshot_index, archive)
+                                .await?;
+                        }
+                        let create_entry = entries.next().ok_or_else(|| {
+                            WorkerExecutorError::invalid_request(
+                                "agent archive contains an empty batch of oplog entries",
+                            )
+                        })?;
+                        let oplog = self
+                            .create_imported_oplog(&import.header, create_entry, target_agent_id)
+                            .await?;
+                        import.oplog = Some(oplog.clone());
+                        oplog
+                    }
+                };
+
+                for mut entry in entries {
+                    entry
+                        .relocate_external_payloads(&import.relocated)
+                        .map_err(WorkerExecutorError::runtime)?;
+                    oplog.add(entry).await;
+                }
+                oplog.commit(CommitLevel::Always).await;
+                Ok(())
+            }
+            AgentArchivePart::Payload(_) | AgentArchivePart::FilesystemCheckpoint(_) => {
+                Err(WorkerExecutorError::invalid_request(
+                    "agent archive contains payloads or filesystem data after the oplog entries",
+                ))
+            }
+        }
+    }
+
+    /// Creates the oplog of the imported agent, starting with the archived `Create` entry
+    async fn create_imported_oplog(
+        &self,
+        header: &AgentArchiveHeader,
+        create_entry: OplogEntry,
+        target_agent_id: &OwnedAgentId,
+    ) -> Result<Arc<dyn Oplog>, WorkerExecutorError> {
+        let agent_mode = header.agent_mode;
+
+        let OplogEntry::Create {
+            timestamp,
+            component_revision,
+            env,
+            component_size,
+            initial_total_linear_memory_size,
+            local_agent_config,
+            original_phantom_id,
+            ..
+        } = create_entry
+        else {
+            return Err(WorkerExecutorError::invalid_request(
+                "agent archive oplog does not start with a create entry",
+            ));
+        };
+
+        let component = self
+            .component_service
+            .get_metadata(
+                target_agent_id.agent_id.component_id,
+                Some(component_revision),
+            )
+            .await?;
+
+        let instance_id = Uuid::new_v4();
+
+        // The owner of the target component becomes the creator of the imported agent, and
+        // environment specific references (parent agent, plugin grants) are dropped.
+        let create_entry = OplogEntry::Create {
+            timestamp,
+            agent_id: target_agent_id.agent_id.clone(),
+            agent_mode,
+            component_revision,
+            env,
+            environment_id: target_agent_id.environment_id,
+            created_by: component.account_id,
+            parent: None,
+            component_size,
+            initial_total_linear_memory_size,
+            initial_active_plugins: HashSet::new(),
+            local_agent_config,
+            original_phantom_id,
+            instance_id,
+        };
+
+        let last_known_status = AgentStatusRecord {
+            component_revision,
+            component_revision_for_replay: component_revision,
+            component_size,
+            agent_mode,
+            ..AgentStatusRecord::default()
+        };
+
+        let metadata = AgentMetadata {
+            agent_id: target_agent_id.agent_id.clone(),
+            created_by: component.account_id,
+            created_by_email: component.account_email,
+            environment_id: target_agent_id.environment_id,
+            env: header.env.clone(),
+            config: header.config.clone(),
+            created_at: Timestamp::now_utc(),
+            parent: None,
+            last_known_status: last_known_status.clone(),
+            original_phantom_id,
+            fingerprint: AgentFingerprint(instance_id),
+            agent_mode,
+        };
+
+        Ok(self
+            .oplog_service
+            .create(
+                target_agent_id,
+                agent_mode,
+                create_entry,
+                metadata,
+                read_only_lock::tokio::ReadOnlyLock::new(Arc::new(tokio::sync::RwLock::new(
+                    last_known_status,
+                ))),
+                read_only_lock::std::ReadOnlyLock::new(Arc::new(std::sync::RwLock::new(
+                    ExecutionStatus::Suspended {
+                        agent_mode,
+                        timestamp: Timestamp::now_utc(),
+                    },
+                ))),
+            )
+            .await)
+    }
+}
+
+/// State of an agent import in progress
+struct AgentImport {
+    header: AgentArchiveHeader,
+    /// New locations of the external payloads, by their ID in the archive
+    relocated: HashMap<PayloadId, RawOplogPayload>,
+    /// The filesystem checkpoint received so far, staged in a temporary file
+    filesystem_checkpoint: Option<(OplogIndex, NamedTempFile, tokio::fs::File)>,
+    /// Created when the first oplog entries arrive
+    oplog: Option<Arc<dyn Oplog>>,
+    ended: bool,
+}
+
+impl AgentImport {
+    fn new(header: AgentArchiveHeader) -> Self {
+        Self {
+            header,
+            relocated: HashMap::new(),
+            filesystem_checkpoint: None,
+            oplog: None,
+            ended: false,
+        }
+    }
+
+    async fn write_filesystem_checkpoint(
+        &mut self,
+        chunk: AgentArchiveFilesystemChunk,
+    ) -> Result<(), WorkerExecutorError> {
+        if self.filesystem_checkpoint.is_none() {
+            let temp_file = NamedTempFile::new().map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to create temporary file for imported filesystem checkpoint: {err}"
+                ))
+            })?;
+            let file = temp_file.reopen().map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to open temporary file for imported filesystem checkpoint: {err}"
+                ))
+            })?;
+            self.filesystem_checkpoint = Some((
+                chunk.snapshot_index,
+                temp_file,
+                tokio::fs::File::from_std(file),
+            ));
+        }
+
+        let Some((snapshot_index, _, file)) = &mut self.filesystem_checkpoint else {
+            unreachable!("filesystem checkpoint was just initialized")
+        };
+        if *snapshot_index != chunk.snapshot_index {
+            return Err(WorkerExecutorError::invalid_request(
+                "agent archive contains chunks of more than one filesystem checkpoint",
+            ));
+        }
+        file.write_all(&chunk.data).await.map_err(|err| {
+            WorkerExecutorError::runtime(format!(
+                "Failed to write imported filesystem checkpoint: {err}"
+            ))
+        })
+    }
+
+    async fn finish_filesystem_checkpoint(
+        &mut self,
+    ) -> Result<Option<(OplogIndex, NamedTempFile)>, WorkerExecutorError> {
+        match self.filesystem_checkpoint.take() {
+            Some((snapshot_index, temp_file, mut file)) => {
+                file.flush().await.map_err(|err| {
+                    WorkerExecutorError::runtime(format!(
+                        "Failed to write imported filesystem checkpoint: {err}"
+                    ))
+                })?;
+                Ok(Some((snapshot_index, temp_file)))
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 #[async_trait]
@@ -827,4 +1347,417 @@ impl<Ctx: WorkerCtx> WorkerForkService for DefaultWorkerFork<Ctx> {
 
         Ok(())
     }
+
+    async fn invoke_at(
+        &self,
+        source_agent_id: &OwnedAgentId,
+        at: TimeTravelTarget,
+        invocation: AgentInvocation,
+    ) -> Result<AgentInvocationOutput, WorkerExecutorError> {
+        record_worker_call("invoke_at");
+
+        let AgentInvocation::AgentMethod {
+            method_name,
+            invocation_context,
+            principal,
+            ..
+        } = &invocation
+        else {
+            return Err(WorkerExecutorError::invalid_request(
+                "only agent methods can be invoked at a past point of the agent's history",
+            ));
+        };
+
+        self.shard_service.check_worker(&source_agent_id.agent_id)?;
+
+        let source_metadata = self
+            .worker_service
+            .get(source_agent_id)
+            .await
+            .ok_or(WorkerExecutorError::worker_not_found(
+                source_agent_id.agent_id.clone(),
+            ))?
+            .initial_worker_metadata;
+
+        let source_worker = Worker::get_or_create_suspended(
+            self,
+            source_agent_id,
+            None,
+            Vec::new(),
+            None,
+            None,
+            &InvocationContextStack::fresh(),
+            Principal::anonymous(),
+        )
+        .await?;
+
+        // Only methods served by the read-only cache are allowed, as anything else could make
+        // the replay diverge from the agent's actual history
+        if source_worker.agent_method_invalidates_read_only_cache(method_name) {
+            return Err(WorkerExecutorError::invalid_request(format!(
+                "{method_name} is not a read-only method, so it cannot be invoked at a past point of the agent's history"
+            )));
+        }
+
+        let cut_off = self
+            .resolve_time_travel_cut_off(source_agent_id, source_metadata.agent_mode, at)
+            .await?;
+
+        // The replay is a phantom of the source agent, so it shares its agent type and parameters
+        let phantom_id = Uuid::new_v4();
+        let replay_agent_id = AgentId {
+            component_id: source_agent_id.agent_id.component_id,
+            agent_id: match source_worker.parsed_agent_id() {
+                Some(parsed) => LegacyParsedAgentId::new(
+                    parsed.agent_type.clone(),
+                    parsed.parameters.clone(),
+                    Some(phantom_id),
+                )
+                .map_err(WorkerExecutorError::unknown)?
+                .to_string(),
+                None => format!("{}-{phantom_id}", source_agent_id.agent_id.agent_id),
+            },
+        };
+        let agent_mode = source_worker.agent_mode();
+        let initial_entry = source_worker.oplog().read(OplogIndex::INITIAL).await;
+        drop(source_worker);
+
+        tracing::debug!(
+            "Invoking {method_name} on {replay_agent_id}, replaying {source_agent_id} up to index {cut_off}"
+        );
+
+        // The replay only lives in memory: it reads the source agent's oplog up to the cut-off,
+        // and is never registered as an agent, so nothing of it remains once it is dropped
+        let instance_id = Uuid::new_v4();
+        let replay_initial_entry =
+            Self::update_agent_id(initial_entry, &replay_agent_id, instance_id).ok_or(
+                WorkerExecutorError::unknown("Failed to update worker id in oplog entry"),
+            )?;
+        let replay_oplog = Arc::new(TimeTravelOplog::new(
+            self.oplog_service.clone(),
+            source_agent_id.clone(),
+            agent_mode,
+            replay_initial_entry,
+            cut_off,
+        ));
+
+        let status = self
+            .fold_time_travel_status(&replay_oplog, agent_mode, cut_off)
+            .await?;
+
+        // Invocations and updates pending at the cut-off are cancelled, so the replay only
+        // executes the requested invocation
+        let now = Timestamp::now_utc();
+        let mut cancellations = BTreeMap::new();
+        for idempotency_key in status
+            .pending_invocations
+            .iter()
+            .filter_map(|pending| pending.idempotency_key())
+        {
+            let entry = OplogEntry::CancelPendingInvocation {
+                timestamp: now,
+                idempotency_key: idempotency_key.clone(),
+            };
+            cancellations.insert(replay_oplog.add(entry.clone()).await, entry);
+        }
+        for pending_update in &status.pending_updates {
+            let entry = OplogEntry::FailedUpdate {
+                timestamp: now,
+                target_revision: pending_update.target_revision,
+                details: Some("cancelled by time travel".to_string()),
+            };
+            cancellations.insert(replay_oplog.add(entry.clone()).await, entry);
+        }
+        replay_oplog.commit(CommitLevel::Always).await;
+        let status = if cancellations.is_empty() {
+            status
+        } else {
+            update_status_with_new_entries(
+                agent_mode,
+                status,
+                cancellations,
+                &self.golem_config.retry,
+            )
+            .ok_or(WorkerExecutorError::unknown(
+                "Failed to calculate the status of the time-travel replay",
+            ))?
+        };
+
+        let replay_metadata = AgentMetadata {
+            agent_id: replay_agent_id.clone(),
+            created_by: source_metadata.created_by,
+            created_by_email: source_metadata.created_by_email,
+            environment_id: source_agent_id.environment_id,
+            env: source_metadata.env,
+            config: source_metadata.config,
+            created_at: Timestamp::now_utc(),
+            parent: None,
+            last_known_status: status.clone(),
+            original_phantom_id: source_metadata.original_phantom_id,
+            fingerprint: AgentFingerprint(instance_id),
+            agent_mode,
+        };
+
+        let replay = Arc::new(
+            Worker::new_detached(
+                self,
+                OwnedAgentId::new(source_agent_id.environment_id, &replay_agent_id),
+                replay_metadata,
+                status,
+                replay_oplog,
+                invocation_context,
+                principal.clone(),
+            )
+            .await?,
+        );
+
+        let result = replay.clone().invoke_and_await(invocation).await;
+        if let Err(err) = replay.start_deleting().await {
+            tracing::warn!("Failed to stop time-travel replay {replay_agent_id}: {err}");
+        }
+        result
+    }
+
+    async fn export_agent(
+        &self,
+        agent_id: &OwnedAgentId,
+    ) -> Result<AgentArchiveStream, WorkerExecutorError> {
+        record_worker_call("export");
+
+        self.shard_service.check_worker(&agent_id.agent_id)?;
+
+        let metadata = self
+            .worker_service
+            .get(agent_id)
+            .await
+            .ok_or(WorkerExecutorError::worker_not_found(
+                agent_id.agent_id.clone(),
+            ))?
+            .initial_worker_metadata;
+
+        let agent_mode = metadata.agent_mode;
+        let last_index = self
+            .oplog_service
+            .get_last_index(agent_id, agent_mode)
+            .await;
+
+        // The oplog is scanned once before streaming it, so the header can list the referenced
+        // component revisions and the payloads can precede the entries referring to them
+        let mut payload_ids = HashSet::new();
+        let mut payloads: Vec<(PayloadId, Vec<u8>)> = Vec::new();
+        let mut revisions: Vec<ComponentRevision> = Vec::new();
+        let mut last_snapshot_index = None;
+        let mut idx = OplogIndex::INITIAL;
+        while idx <= last_index {
+            let entries = self
+                .oplog_service
+                .read(
+                    agent_id,
+                    agent_mode,
+                    idx,
+                    Self::oplog_batch_size(idx, last_index),
+                )
+                .await;
+            let Some(next) = entries.keys().next_back().map(|last| last.next()) else {
+                break;
+            };
+            for (entry_idx, entry) in &entries {
+                if Self::uses_environment_storage(entry) {
+                    return Err(WorkerExecutorError::invalid_request(format!(
+                        "agent {} used key-value or blob storage, which belongs to its environment and can't be exported",
+                        agent_id.agent_id
+                    )));
+                }
+                for (payload_id, md5_hash) in entry.external_payloads() {
+                    if payload_ids.insert(payload_id.clone()) {
+                        payloads.push((payload_id.clone(), md5_hash.to_vec()));
+                    }
+                }
+                if let Some(revision) = entry.specifies_component_revision()
+                    && !revisions.contains(&revision)
+                {
+                    revisions.push(revision);
+                }
+                if matches!(entry, OplogEntry::Snapshot { .. }) {
+                    last_snapshot_index = Some(*entry_idx);
+                }
+            }
+            idx = next;
+        }
+
+        let mut component_revisions: Vec<AgentArchiveComponentRevision> = Vec::new();
+        let mut current_component = None;
+        for revision in revisions {
+            let component = self
+                .component_service
+                .get_metadata(agent_id.agent_id.component_id, Some(revision))
+                .await?;
+            component_revisions.push(AgentArchiveComponentRevision {
+                revision,
+                wasm_hash: component.wasm_hash.to_string(),
+            });
+            current_component = Some(component);
+        }
+
+        let current_component = current_component.ok_or(WorkerExecutorError::unknown(
+            "exported oplog does not specify a component revision",
+        ))?;
+
+        let header = AgentArchivePart::Header(AgentArchiveHeader {
+            format_version: AgentArchiveHeader::FORMAT_VERSION,
+            agent_id: agent_id.agent_id.clone(),
+            agent_mode,
+            component_name: current_component.component_name,
+            component_revision: current_component.revision,
+            component_revisions,
+            env: metadata.env,
+            config: metadata.config,
+            exported_at: Timestamp::now_utc(),
+        });
+
+        // Only the latest snapshot can have a checkpoint, the older ones are deleted when a new
+        // one is saved
+        let filesystem_checkpoint = match last_snapshot_index {
+            Some(snapshot_index) => self
+                .filesystem_checkpoint_service
+                .export(agent_id, agent_mode, snapshot_index)
+                .await?
+                .map(|chunks| {
+                    chunks.map(move |chunk| {
+                        chunk.map(|data| {
+                            AgentArchivePart::FilesystemCheckpoint(AgentArchiveFilesystemChunk {
+                                snapshot_index,
+                                data: data.to_vec(),
+                            })
+                        })
+                    })
+                }),
+            None => None,
+        };
+
+        let oplog_service = self.oplog_service.clone();
+        let owned_agent_id = agent_id.clone();
+        let payloads = stream::iter(payloads).then(move |(payload_id, md5_hash)| {
+            let oplog_service = oplog_service.clone();
+            let owned_agent_id = owned_agent_id.clone();
+            async move {
+                let data = oplog_service
+                    .download_raw_payload(
+                        &owned_agent_id,
+                        agent_mode,
+                        payload_id.clone(),
+                        md5_hash.clone(),
+                    )
+                    .await
+                    .map_err(WorkerExecutorError::runtime)?;
+                Ok(AgentArchivePart::Payload(AgentArchivePayload {
+                    payload_id,
+                    md5_hash,
+                    data,
+                }))
+            }
+        });
+
+        // Entries added while the export is running are not part of the archive
+        let oplog_service = self.oplog_service.clone();
+        let owned_agent_id = agent_id.clone();
+        let entries = stream::unfold(OplogIndex::INITIAL, move |idx| {
+            let oplog_service = oplog_service.clone();
+            let owned_agent_id = owned_agent_id.clone();
+            async move {
+                if idx > last_index {
+                    return None;
+                }
+                let entries = oplog_service
+                    .read(
+                        &owned_agent_id,
+                        agent_mode,
+                        idx,
+                        Self::oplog_batch_size(idx, last_index),
+                    )
+                    .await;
+                let next = entries.keys().next_back()?.next();
+                Some((
+                    Ok(AgentArchivePart::Entries(entries.into_values().collect())),
+                    next,
+                ))
+            }
+        });
+
+        Ok(stream::once(future::ready(Ok(header)))
+            .chain(payloads)
+            .chain(stream::iter(filesystem_checkpoint).flatten())
+            .chain(entries)
+            .chain(stream::once(future::ready(Ok(AgentArchivePart::End))))
+            .boxed())
+    }
+
+    async fn import_agent(
+        &self,
+        mut archive: AgentArchiveStream,
+        target_agent_id: &OwnedAgentId,
+        auth_ctx: &AuthCtx,
+    ) -> Result<(), WorkerExecutorError> {
+        record_worker_call("import");
+
+        self.shard_service.check_worker(&target_agent_id.agent_id)?;
+
+        let header = match archive.next().await {
+            Some(Ok(AgentArchivePart::Header(header))) => header,
+            Some(Err(err)) => return Err(err),
+            _ => {
+                return Err(WorkerExecutorError::invalid_request(
+                    "agent archive does not start with a header",
+                ));
+            }
+        };
+
+        tracing::debug!(
+            "Importing agent {} exported at {} as {target_agent_id}",
+            header.agent_id,
+            header.exported_at
+        );
+
+        self.validate_import_target(&header, target_agent_id)
+            .await?;
+
+        let agent_mode = header.agent_mode;
+        let mut import = AgentImport::new(header);
+        let result = async {
+            while let Some(part) = archive.next().await {
+                self.import_archive_part(&mut import, part?, target_agent_id)
+                    .await?;
+            }
+            if import.ended {
+                Ok(())
+            } else {
+                Err(WorkerExecutorError::invalid_request(
+                    "agent archive is truncated",
+                ))
+            }
+        }
+        .await;
+
+        if let Err(err) = result {
+            // A partially imported agent could never be recovered, so it is removed
+            if import.oplog.take().is_some() {
+                self.oplog_service.delete(target_agent_id, agent_mode).await;
+                self.worker_service.remove(target_agent_id).await;
+            }
+            return Err(err);
+        }
+
+        // Resuming through the worker proxy replays the imported oplog
+        self.worker_proxy
+            .resume(&target_agent_id.agent_id, true, auth_ctx)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::failed_to_resume_worker(
+                    target_agent_id.agent_id.clone(),
+                    err.into(),
+                )
+            })?;
+
+        Ok(())
+    }
 }
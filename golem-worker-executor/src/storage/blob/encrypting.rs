@@ -0,0 +1,320 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::encryption::{DataKeyScope, EnvelopeEncryption};
+use anyhow::{Error, anyhow};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use golem_service_base::replayable_stream::ErasedReplayableStream;
+use golem_service_base::storage::blob::{
+    BlobMetadata, BlobStorage, BlobStorageNamespace, ExistsResult,
+};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Blob storage encrypting the oplog payloads with the data key of their agent.
+///
+/// Encrypted payloads are always read as a whole, so streaming and slicing them requires
+/// buffering, and their metadata reports the size of the encrypted blob. As each payload is bound
+/// to its path, copying or moving one re-encrypts it.
+#[derive(Debug)]
+pub struct EncryptingBlobStorage {
+    inner: Arc<dyn BlobStorage>,
+    encryption: Arc<EnvelopeEncryption>,
+}
+
+impl EncryptingBlobStorage {
+    pub fn new(inner: Arc<dyn BlobStorage>, encryption: Arc<EnvelopeEncryption>) -> Self {
+        Self { inner, encryption }
+    }
+
+    fn scope(namespace: &BlobStorageNamespace) -> Option<DataKeyScope> {
+        match namespace {
+            BlobStorageNamespace::OplogPayload { agent_id, .. } => {
+                Some(DataKeyScope::Agent(agent_id.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    async fn get_decrypted(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        scope: &DataKeyScope,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        match self
+            .inner
+            .get_raw(target_label, op_label, namespace, path)
+            .await?
+        {
+            Some(data) => Ok(Some(
+                self.encryption
+                    .decrypt(scope, &path.to_string_lossy(), data)
+                    .await
+                    .map_err(|err| anyhow!(err))?,
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl BlobStorage for EncryptingBlobStorage {
+    async fn get_raw(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        match Self::scope(&namespace) {
+            Some(scope) => {
+                self.get_decrypted(target_label, op_label, namespace, path, &scope)
+                    .await
+            }
+            None => {
+                self.inner
+                    .get_raw(target_label, op_label, namespace, path)
+                    .await
+            }
+        }
+    }
+
+    async fn get_stream(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<BoxStream<'static, Result<Bytes, Error>>>, Error> {
+        match Self::scope(&namespace) {
+            Some(scope) => {
+                let data = self
+                    .get_decrypted(target_label, op_label, namespace, path, &scope)
+                    .await?;
+                Ok(data.map(|data| futures::stream::once(async { Ok(Bytes::from(data)) }).boxed()))
+            }
+            None => {
+                self.inner
+                    .get_stream(target_label, op_label, namespace, path)
+                    .await
+            }
+        }
+    }
+
+    async fn get_raw_slice(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        start: u64,
+        end: u64,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        match Self::scope(&namespace) {
+            Some(scope) => {
+                let data = self
+                    .get_decrypted(target_label, op_label, namespace, path, &scope)
+                    .await?;
+                Ok(data.map(|data| data[(start as usize)..(end as usize)].to_vec()))
+            }
+            None => {
+                self.inner
+                    .get_raw_slice(target_label, op_label, namespace, path, start, end)
+                    .await
+            }
+        }
+    }
+
+    async fn get_metadata(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<BlobMetadata>, Error> {
+        self.inner
+            .get_metadata(target_label, op_label, namespace, path)
+            .await
+    }
+
+    async fn put_raw(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        match Self::scope(&namespace) {
+            Some(scope) => {
+                let encrypted = self
+                    .encryption
+                    .encrypt(&scope, &path.to_string_lossy(), data)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                self.inner
+                    .put_raw(target_label, op_label, namespace, path, &encrypted)
+                    .await
+            }
+            None => {
+                self.inner
+                    .put_raw(target_label, op_label, namespace, path, data)
+                    .await
+            }
+        }
+    }
+
+    async fn put_stream(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        stream: &dyn ErasedReplayableStream<Item = Result<Vec<u8>, Error>, Error = Error>,
+    ) -> Result<(), Error> {
+        if Self::scope(&namespace).is_some() {
+            let chunks: Vec<Vec<u8>> = stream.make_stream_erased().await?.try_collect().await?;
+            self.put_raw(target_label, op_label, namespace, path, &chunks.concat())
+                .await
+        } else {
+            self.inner
+                .put_stream(target_label, op_label, namespace, path, stream)
+                .await
+        }
+    }
+
+    async fn delete(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), Error> {
+        self.inner
+            .delete(target_label, op_label, namespace, path)
+            .await
+    }
+
+    async fn delete_many(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        paths: &[PathBuf],
+    ) -> Result<(), Error> {
+        self.inner
+            .delete_many(target_label, op_label, namespace, paths)
+            .await
+    }
+
+    async fn create_dir(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), Error> {
+        self.inner
+            .create_dir(target_label, op_label, namespace, path)
+            .await
+    }
+
+    async fn list_dir(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Vec<PathBuf>, Error> {
+        self.inner
+            .list_dir(target_label, op_label, namespace, path)
+            .await
+    }
+
+    async fn delete_dir(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<bool, Error> {
+        self.inner
+            .delete_dir(target_label, op_label, namespace, path)
+            .await
+    }
+
+    async fn exists(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<ExistsResult, Error> {
+        self.inner
+            .exists(target_label, op_label, namespace, path)
+            .await
+    }
+
+    async fn copy(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Error> {
+        match Self::scope(&namespace) {
+            Some(scope) => {
+                let data = self
+                    .get_decrypted(target_label, op_label, namespace.clone(), from, &scope)
+                    .await?
+                    .ok_or_else(|| anyhow!("Blob {} does not exist", from.display()))?;
+                self.put_raw(target_label, op_label, namespace, to, &data)
+                    .await
+            }
+            None => {
+                self.inner
+                    .copy(target_label, op_label, namespace, from, to)
+                    .await
+            }
+        }
+    }
+
+    async fn r#move(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Error> {
+        if Self::scope(&namespace).is_some() {
+            self.copy(target_label, op_label, namespace.clone(), from, to)
+                .await?;
+            self.inner
+                .delete(target_label, op_label, namespace, from)
+                .await
+        } else {
+            self.inner
+                .r#move(target_label, op_label, namespace, from, to)
+                .await
+        }
+    }
+}
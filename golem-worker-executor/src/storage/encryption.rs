@@ -0,0 +1,593 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::golem_config::{EncryptionEnabledConfig, MasterKeyProviderConfig};
+use crate::storage::keyvalue::{
+    KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
+};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use desert_rust::BinaryCodec;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::{AgentId, Timestamp};
+use golem_common::serialization::deserialize;
+use rand::RngCore;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+const SVC_NAME: &str = "encryption";
+const ENTITY_NAME: &str = "data_key";
+
+/// Prefix of every encrypted value. Values without it were written before encryption was enabled
+/// and are returned as they are, unless plaintext values are rejected.
+const ENVELOPE_MAGIC: &[u8; 4] = b"GENC";
+const ENVELOPE_VERSION: u8 = 1;
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const ENVELOPE_HEADER_LENGTH: usize = ENVELOPE_MAGIC.len() + 1 + 4 + NONCE_LENGTH;
+
+/// The owner of a data key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DataKeyScope {
+    /// Oplog entries and oplog payloads of an agent
+    Agent(AgentId),
+    /// User-defined key-value data of an environment
+    Environment(EnvironmentId),
+}
+
+impl DataKeyScope {
+    fn storage_key(&self) -> String {
+        match self {
+            DataKeyScope::Agent(agent_id) => format!("agent:{}", agent_id.to_redis_key()),
+            DataKeyScope::Environment(environment_id) => format!("environment:{environment_id}"),
+        }
+    }
+
+    fn namespace(&self) -> KeyValueStorageNamespace {
+        KeyValueStorageNamespace::EncryptionKeys {
+            scope: self.storage_key(),
+        }
+    }
+}
+
+/// A 256-bit key wrapping the data keys
+#[derive(Clone)]
+pub struct MasterKey([u8; KEY_LENGTH]);
+
+impl MasterKey {
+    pub fn new(key: [u8; KEY_LENGTH]) -> Self {
+        Self(key)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+}
+
+impl Debug for MasterKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MasterKey(*******)")
+    }
+}
+
+/// Source of the master keys
+#[async_trait]
+pub trait MasterKeyProvider: Debug + Send + Sync {
+    /// Loads every available master key by its ID
+    async fn master_keys(&self) -> Result<HashMap<String, MasterKey>, String>;
+}
+
+/// Reads the master keys from a file
+#[derive(Debug)]
+pub struct FileMasterKeyProvider {
+    path: PathBuf,
+}
+
+impl FileMasterKeyProvider {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl MasterKeyProvider for FileMasterKeyProvider {
+    async fn master_keys(&self) -> Result<HashMap<String, MasterKey>, String> {
+        let source = tokio::fs::read_to_string(&self.path).await.map_err(|err| {
+            format!(
+                "Failed to read the master keys from {}: {err}",
+                self.path.display()
+            )
+        })?;
+        parse_master_keys(&source)
+    }
+}
+
+/// Reads the master keys from an environment variable
+#[derive(Debug)]
+pub struct EnvMasterKeyProvider {
+    variable: String,
+}
+
+impl EnvMasterKeyProvider {
+    pub fn new(variable: String) -> Self {
+        Self { variable }
+    }
+}
+
+#[async_trait]
+impl MasterKeyProvider for EnvMasterKeyProvider {
+    async fn master_keys(&self) -> Result<HashMap<String, MasterKey>, String> {
+        let source = std::env::var(&self.variable).map_err(|err| {
+            format!(
+                "Failed to read the master keys from environment variable {}: {err}",
+                self.variable
+            )
+        })?;
+        parse_master_keys(&source)
+    }
+}
+
+pub fn configured_master_key_provider(
+    config: &MasterKeyProviderConfig,
+) -> Arc<dyn MasterKeyProvider> {
+    match config {
+        MasterKeyProviderConfig::File(file) => {
+            Arc::new(FileMasterKeyProvider::new(file.path.clone()))
+        }
+        MasterKeyProviderConfig::Env(env) => {
+            Arc::new(EnvMasterKeyProvider::new(env.variable.clone()))
+        }
+    }
+}
+
+/// Parses `<key-id>=<base64 key>` entries separated by newlines or commas. Empty lines and lines
+/// starting with `#` are ignored.
+fn parse_master_keys(source: &str) -> Result<HashMap<String, MasterKey>, String> {
+    let mut keys = HashMap::new();
+    for entry in source
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && !entry.starts_with('#'))
+    {
+        let (id, key) = entry.split_once('=').ok_or_else(|| {
+            "Invalid master key entry, expected <key-id>=<base64 key>".to_string()
+        })?;
+        let id = id.trim();
+        let key = STANDARD
+            .decode(key.trim())
+            .map_err(|err| format!("Invalid master key {id}: {err}"))?;
+        let key: [u8; KEY_LENGTH] = key
+            .try_into()
+            .map_err(|_| format!("Master key {id} must be {KEY_LENGTH} bytes long"))?;
+        keys.insert(id.to_string(), MasterKey::new(key));
+    }
+    Ok(keys)
+}
+
+/// A version of a data key as stored, wrapped by a master key
+#[derive(Debug, Clone, BinaryCodec)]
+#[desert(evolution())]
+struct WrappedDataKey {
+    created_at: Timestamp,
+    master_key_id: String,
+    nonce: Vec<u8>,
+    wrapped_key: Vec<u8>,
+}
+
+#[derive(Clone)]
+struct DataKey {
+    created_at: Timestamp,
+    cipher: Aes256Gcm,
+}
+
+/// The unwrapped versions of the data key of a scope
+#[derive(Clone, Default)]
+struct DataKeyRing {
+    keys: BTreeMap<u32, DataKey>,
+}
+
+impl DataKeyRing {
+    fn current(&self) -> Option<(u32, &DataKey)> {
+        self.keys
+            .last_key_value()
+            .map(|(version, key)| (*version, key))
+    }
+}
+
+/// Envelope encryption of the data at rest.
+///
+/// Each scope has its own data key, generated on first use and stored in the key-value storage
+/// wrapped by the current master key. Data keys are rotated by adding a new version once the
+/// newest one is older than the rotation period; the previous versions are kept to decrypt the
+/// data written with them. Deleting the data key of a scope makes all of its data unreadable.
+///
+/// Every value is bound to its location in the storage (its key, index or path), so an encrypted
+/// value copied to another location of the same scope fails to decrypt.
+pub struct EnvelopeEncryption {
+    key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    master_keys: HashMap<String, MasterKey>,
+    current_master_key_id: String,
+    data_key_rotation_period: Duration,
+    reject_plaintext: bool,
+    data_keys: Cache<DataKeyScope, (), Arc<DataKeyRing>, String>,
+}
+
+impl EnvelopeEncryption {
+    pub async fn configured(
+        config: &EncryptionEnabledConfig,
+        key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    ) -> Result<Self, String> {
+        let master_keys = configured_master_key_provider(&config.master_key_provider)
+            .master_keys()
+            .await?;
+        Self::new(
+            key_value_storage,
+            master_keys,
+            config.current_master_key_id.clone(),
+            config.data_key_rotation_period,
+            config.data_key_cache_capacity,
+            config.reject_plaintext,
+        )
+    }
+
+    pub fn new(
+        key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+        master_keys: HashMap<String, MasterKey>,
+        current_master_key_id: String,
+        data_key_rotation_period: Duration,
+        data_key_cache_capacity: usize,
+        reject_plaintext: bool,
+    ) -> Result<Self, String> {
+        if !master_keys.contains_key(&current_master_key_id) {
+            return Err(format!(
+                "The current master key {current_master_key_id} is not provided by the master key provider"
+            ));
+        }
+        Ok(Self {
+            key_value_storage,
+            master_keys,
+            current_master_key_id,
+            data_key_rotation_period,
+            reject_plaintext,
+            data_keys: Cache::new(
+                Some(data_key_cache_capacity),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::None,
+                "data_keys",
+            ),
+        })
+    }
+
+    /// Returns true if the value was encrypted by [`EnvelopeEncryption::encrypt`]
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        data.len() >= ENVELOPE_HEADER_LENGTH && data.starts_with(ENVELOPE_MAGIC)
+    }
+
+    /// Encrypts a value stored at `location` of the scope with the current data key of the scope
+    pub async fn encrypt(
+        &self,
+        scope: &DataKeyScope,
+        location: &str,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let mut ring = self.data_key_ring(scope).await?;
+        if self.is_rotation_due(&ring) {
+            ring = self.rotate(scope, false).await?;
+        }
+        let (version, key) = ring
+            .current()
+            .ok_or_else(|| format!("No data key for {}", scope.storage_key()))?;
+
+        let nonce = random_nonce();
+        let ciphertext = key
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: data_aad(scope, location).as_bytes(),
+                },
+            )
+            .map_err(|_| format!("Failed to encrypt data of {}", scope.storage_key()))?;
+
+        let mut result = Vec::with_capacity(ENVELOPE_HEADER_LENGTH + ciphertext.len());
+        result.extend_from_slice(ENVELOPE_MAGIC);
+        result.push(ENVELOPE_VERSION);
+        result.extend_from_slice(&version.to_be_bytes());
+        result.extend_from_slice(&nonce);
+        result.extend(ciphertext);
+        Ok(result)
+    }
+
+    /// Decrypts a value read from `location` of the scope with the data key version it was
+    /// encrypted with. Values written before encryption was enabled are returned unchanged, or
+    /// rejected if plaintext values are not accepted anymore.
+    pub async fn decrypt(
+        &self,
+        scope: &DataKeyScope,
+        location: &str,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        if !Self::is_encrypted(&data) {
+            return if self.reject_plaintext {
+                Err(format!(
+                    "Rejected unencrypted data of {} at {location}",
+                    scope.storage_key()
+                ))
+            } else {
+                Ok(data)
+            };
+        }
+        let envelope_version = data[ENVELOPE_MAGIC.len()];
+        if envelope_version != ENVELOPE_VERSION {
+            return Err(format!(
+                "Unsupported encryption envelope version {envelope_version}"
+            ));
+        }
+        let version_start = ENVELOPE_MAGIC.len() + 1;
+        let nonce_start = version_start + 4;
+        let version = u32::from_be_bytes(
+            data[version_start..nonce_start]
+                .try_into()
+                .expect("slice has the length of an u32"),
+        );
+        let nonce = &data[nonce_start..ENVELOPE_HEADER_LENGTH];
+        let ciphertext = &data[ENVELOPE_HEADER_LENGTH..];
+
+        let mut ring = self.data_key_ring(scope).await?;
+        if !ring.keys.contains_key(&version) {
+            // The data key may have been rotated by another executor since it was cached
+            self.data_keys.remove(scope).await;
+            ring = self.data_key_ring(scope).await?;
+        }
+        let key = ring.keys.get(&version).ok_or_else(|| {
+            format!(
+                "Data key version {version} of {} does not exist",
+                scope.storage_key()
+            )
+        })?;
+
+        key.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: data_aad(scope, location).as_bytes(),
+                },
+            )
+            .map_err(|_| format!("Failed to decrypt data of {}", scope.storage_key()))
+    }
+
+    /// Creates a new version of the data key of the scope, used for all the new writes
+    pub async fn rotate_data_key(&self, scope: &DataKeyScope) -> Result<(), String> {
+        self.rotate(scope, true).await.map(|_| ())
+    }
+
+    fn is_rotation_due(&self, ring: &DataKeyRing) -> bool {
+        match ring.current() {
+            Some((_, key)) => {
+                let age = Timestamp::now_utc()
+                    .to_millis()
+                    .saturating_sub(key.created_at.to_millis());
+                age >= self.data_key_rotation_period.as_millis() as u64
+            }
+            None => true,
+        }
+    }
+
+    async fn rotate(&self, scope: &DataKeyScope, force: bool) -> Result<Arc<DataKeyRing>, String> {
+        // Reloading first, as the data key may have been rotated by another executor already
+        self.data_keys.remove(scope).await;
+        let ring = self.data_key_ring(scope).await?;
+        if !force && !self.is_rotation_due(&ring) {
+            return Ok(ring);
+        }
+        let version = ring.current().map(|(version, _)| version + 1).unwrap_or(1);
+
+        let mut data_key = [0u8; KEY_LENGTH];
+        rand::rng().fill_bytes(&mut data_key);
+        let wrapped = self.wrap(scope, version, &data_key, Timestamp::now_utc())?;
+
+        let created = self
+            .key_value_storage
+            .with_entity(SVC_NAME, "rotate_data_key", ENTITY_NAME)
+            .set_if_not_exists(scope.namespace(), &version.to_string(), &wrapped)
+            .await?;
+        if created {
+            debug!(scope = %scope.storage_key(), version, "Created new data key");
+        }
+
+        // Reloading picks up the version created by a concurrent rotation if this one lost
+        self.data_keys.remove(scope).await;
+        self.data_key_ring(scope).await
+    }
+
+    /// Deletes every version of the data key of the scope, making the data encrypted with them
+    /// permanently unreadable
+    pub async fn shred(&self, scope: &DataKeyScope) -> Result<(), String> {
+        let storage = self.key_value_storage.with(SVC_NAME, "shred");
+        let versions = storage.keys(scope.namespace()).await?;
+        if !versions.is_empty() {
+            storage.del_many(scope.namespace(), versions).await?;
+        }
+        self.data_keys.remove(scope).await;
+        debug!(scope = %scope.storage_key(), "Shredded data key");
+        Ok(())
+    }
+
+    async fn data_key_ring(&self, scope: &DataKeyScope) -> Result<Arc<DataKeyRing>, String> {
+        self.data_keys
+            .get_or_insert_simple(scope, async || self.load_data_key_ring(scope).await)
+            .await
+    }
+
+    async fn load_data_key_ring(&self, scope: &DataKeyScope) -> Result<Arc<DataKeyRing>, String> {
+        let stored = self
+            .key_value_storage
+            .with_entity(SVC_NAME, "load_data_keys", ENTITY_NAME)
+            .get_all_raw(scope.namespace())
+            .await?;
+
+        let mut ring = DataKeyRing::default();
+        for (version, bytes) in stored {
+            let version: u32 = version
+                .parse()
+                .map_err(|err| format!("Invalid data key version {version}: {err}"))?;
+            let wrapped: WrappedDataKey = deserialize(&bytes)?;
+            let data_key = self.unwrap(scope, version, &wrapped)?;
+
+            if wrapped.master_key_id != self.current_master_key_id {
+                // Lazily re-wrapping the data keys by the current master key, so the previous
+                // master keys can be retired
+                let rewrapped = self.wrap(scope, version, &data_key, wrapped.created_at)?;
+                self.key_value_storage
+                    .with_entity(SVC_NAME, "rewrap_data_key", ENTITY_NAME)
+                    .set(scope.namespace(), &version.to_string(), &rewrapped)
+                    .await?;
+                debug!(
+                    scope = %scope.storage_key(),
+                    version,
+                    previous_master_key_id = %wrapped.master_key_id,
+                    "Re-wrapped data key by the current master key"
+                );
+            }
+
+            ring.keys.insert(
+                version,
+                DataKey {
+                    created_at: wrapped.created_at,
+                    cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key)),
+                },
+            );
+        }
+        Ok(Arc::new(ring))
+    }
+
+    fn wrap(
+        &self,
+        scope: &DataKeyScope,
+        version: u32,
+        data_key: &[u8],
+        created_at: Timestamp,
+    ) -> Result<WrappedDataKey, String> {
+        let master_key = &self.master_keys[&self.current_master_key_id];
+        let nonce = random_nonce();
+        let wrapped_key = master_key
+            .cipher()
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: data_key,
+                    aad: wrapping_aad(scope, version).as_bytes(),
+                },
+            )
+            .map_err(|_| format!("Failed to wrap data key of {}", scope.storage_key()))?;
+        Ok(WrappedDataKey {
+            created_at,
+            master_key_id: self.current_master_key_id.clone(),
+            nonce: nonce.to_vec(),
+            wrapped_key,
+        })
+    }
+
+    fn unwrap(
+        &self,
+        scope: &DataKeyScope,
+        version: u32,
+        wrapped: &WrappedDataKey,
+    ) -> Result<Vec<u8>, String> {
+        let master_key = self.master_keys.get(&wrapped.master_key_id).ok_or_else(|| {
+            format!(
+                "Master key {} of the data key of {} is not provided by the master key provider",
+                wrapped.master_key_id,
+                scope.storage_key()
+            )
+        })?;
+        if wrapped.nonce.len() != NONCE_LENGTH {
+            return Err(format!(
+                "Invalid nonce of the data key of {}",
+                scope.storage_key()
+            ));
+        }
+        master_key
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(&wrapped.nonce),
+                Payload {
+                    msg: &wrapped.wrapped_key,
+                    aad: wrapping_aad(scope, version).as_bytes(),
+                },
+            )
+            .map_err(|_| format!("Failed to unwrap data key of {}", scope.storage_key()))
+    }
+}
+
+impl Debug for EnvelopeEncryption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnvelopeEncryption")
+            .field("current_master_key_id", &self.current_master_key_id)
+            .field("data_key_rotation_period", &self.data_key_rotation_period)
+            .field("reject_plaintext", &self.reject_plaintext)
+            .finish()
+    }
+}
+
+/// Binds a wrapped data key to its scope and version, so it cannot be swapped with another one
+fn wrapping_aad(scope: &DataKeyScope, version: u32) -> String {
+    format!("{}/{version}", scope.storage_key())
+}
+
+/// Binds an encrypted value to its scope and location, so it cannot be moved to another key,
+/// index or path
+fn data_aad(scope: &DataKeyScope, location: &str) -> String {
+    format!("{}#{location}", scope.storage_key())
+}
+
+fn random_nonce() -> [u8; NONCE_LENGTH] {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn master_keys_are_parsed_from_lines_or_comma_separated_entries() {
+        let key_1 = STANDARD.encode([1u8; KEY_LENGTH]);
+        let key_2 = STANDARD.encode([2u8; KEY_LENGTH]);
+
+        let keys =
+            parse_master_keys(&format!("# previous key\nk1={key_1}\n\nk2 = {key_2}\n")).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys["k1"].0, [1u8; KEY_LENGTH]);
+        assert_eq!(keys["k2"].0, [2u8; KEY_LENGTH]);
+
+        let keys = parse_master_keys(&format!("k1={key_1},k2={key_2}")).unwrap();
+        assert_eq!(keys.len(), 2);
+
+        assert!(parse_master_keys("k1").is_err());
+        assert!(parse_master_keys(&format!("k1={}", STANDARD.encode([1u8; 16]))).is_err());
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::encryption::{DataKeyScope, EnvelopeEncryption};
+use crate::storage::indexed::{
+    IndexedStorage, IndexedStorageError, IndexedStorageMetaNamespace, IndexedStorageNamespace,
+    ScanCursor,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Indexed storage encrypting the entries of the oplogs and compressed oplog archives with the
+/// data key of their agent
+#[derive(Debug)]
+pub struct EncryptingIndexedStorage {
+    inner: Arc<dyn IndexedStorage + Send + Sync>,
+    encryption: Arc<EnvelopeEncryption>,
+}
+
+impl EncryptingIndexedStorage {
+    pub fn new(
+        inner: Arc<dyn IndexedStorage + Send + Sync>,
+        encryption: Arc<EnvelopeEncryption>,
+    ) -> Self {
+        Self { inner, encryption }
+    }
+
+    fn scope(namespace: &IndexedStorageNamespace) -> DataKeyScope {
+        DataKeyScope::Agent(namespace.agent_id().clone())
+    }
+
+    async fn encrypt(
+        &self,
+        namespace: &IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+        value: &[u8],
+    ) -> Result<Vec<u8>, IndexedStorageError> {
+        self.encryption
+            .encrypt(&Self::scope(namespace), &format!("{key}/{id}"), value)
+            .await
+            .map_err(IndexedStorageError::Other)
+    }
+
+    async fn decrypt(
+        &self,
+        namespace: &IndexedStorageNamespace,
+        key: &str,
+        (id, value): (u64, Vec<u8>),
+    ) -> Result<(u64, Vec<u8>), IndexedStorageError> {
+        let value = self
+            .encryption
+            .decrypt(&Self::scope(namespace), &format!("{key}/{id}"), value)
+            .await
+            .map_err(IndexedStorageError::Other)?;
+        Ok((id, value))
+    }
+
+    async fn decrypt_optional(
+        &self,
+        namespace: &IndexedStorageNamespace,
+        key: &str,
+        entry: Option<(u64, Vec<u8>)>,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        match entry {
+            Some(entry) => Ok(Some(self.decrypt(namespace, key, entry).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl IndexedStorage for EncryptingIndexedStorage {
+    async fn number_of_replicas(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<u8, IndexedStorageError> {
+        self.inner.number_of_replicas(svc_name, api_name).await
+    }
+
+    async fn wait_for_replicas(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        replicas: u8,
+        timeout: Duration,
+    ) -> Result<u8, IndexedStorageError> {
+        self.inner
+            .wait_for_replicas(svc_name, api_name, replicas, timeout)
+            .await
+    }
+
+    async fn exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<bool, IndexedStorageError> {
+        self.inner.exists(svc_name, api_name, namespace, key).await
+    }
+
+    async fn scan(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageMetaNamespace,
+        prefix: Option<&str>,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(ScanCursor, Vec<String>), IndexedStorageError> {
+        self.inner
+            .scan(svc_name, api_name, namespace, prefix, cursor, count)
+            .await
+    }
+
+    async fn append(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+        value: Vec<u8>,
+    ) -> Result<(), IndexedStorageError> {
+        let value = self.encrypt(&namespace, key, id, &value).await?;
+        self.inner
+            .append(svc_name, api_name, entity_name, namespace, key, id, value)
+            .await
+    }
+
+    async fn append_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        pairs: Vec<(u64, Vec<u8>)>,
+    ) -> Result<(), IndexedStorageError> {
+        let mut encrypted_pairs = Vec::with_capacity(pairs.len());
+        for (id, value) in pairs {
+            encrypted_pairs.push((id, self.encrypt(&namespace, key, id, &value).await?));
+        }
+        self.inner
+            .append_many(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace,
+                key,
+                encrypted_pairs,
+            )
+            .await
+    }
+
+    async fn length(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<u64, IndexedStorageError> {
+        self.inner.length(svc_name, api_name, namespace, key).await
+    }
+
+    async fn delete(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<(), IndexedStorageError> {
+        self.inner.delete(svc_name, api_name, namespace, key).await
+    }
+
+    async fn read(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        start_id: u64,
+        end_id: u64,
+    ) -> Result<Vec<(u64, Vec<u8>)>, IndexedStorageError> {
+        let entries = self
+            .inner
+            .read(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                start_id,
+                end_id,
+            )
+            .await?;
+        let mut result = Vec::with_capacity(entries.len());
+        for entry in entries {
+            result.push(self.decrypt(&namespace, key, entry).await?);
+        }
+        Ok(result)
+    }
+
+    async fn first(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        let entry = self
+            .inner
+            .first(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        self.decrypt_optional(&namespace, key, entry).await
+    }
+
+    async fn last(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        let entry = self
+            .inner
+            .last(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        self.decrypt_optional(&namespace, key, entry).await
+    }
+
+    async fn closest(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        let entry = self
+            .inner
+            .closest(svc_name, api_name, entity_name, namespace.clone(), key, id)
+            .await?;
+        self.decrypt_optional(&namespace, key, entry).await
+    }
+
+    async fn drop_prefix(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        last_dropped_id: u64,
+    ) -> Result<(), IndexedStorageError> {
+        self.inner
+            .drop_prefix(svc_name, api_name, namespace, key, last_dropped_id)
+            .await
+    }
+}
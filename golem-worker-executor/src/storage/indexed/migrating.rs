@@ -0,0 +1,663 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::indexed::{
+    IndexedStorage, IndexedStorageError, IndexedStorageMetaNamespace, IndexedStorageNamespace,
+    ScanCursor, agent_mode_prefix,
+};
+use crate::storage::migration::{MigratedKeySet, StorageMigrationState};
+use async_trait::async_trait;
+use golem_common::model::ShardId;
+use golem_common::model::agent::AgentMode;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+const SVC_NAME: &str = "storage_migration";
+const ENTITY_NAME: &str = "migrated_entry";
+const KEY_LOCK_STRIPES: usize = 256;
+
+type MigratedKey = (IndexedStorageNamespace, String);
+
+/// Indexed storage used while migrating the oplogs and oplog archives to a new indexed storage
+/// backend.
+///
+/// Until the shard of an agent is cut over, its reads are served by the source and its writes are
+/// applied to the source first. Writes are mirrored to the target only for keys that have already
+/// been copied (synced keys); writes to other keys mark them as pending, and the migration copies
+/// them again before cutting their shard over. Both sets are persisted in the migration state, so
+/// an executor taking over a shard, or restarting, still knows which keys have to be copied again.
+///
+/// Once a shard is cut over, the target is its primary storage, and every write is also applied
+/// to the source, failing if the source cannot be updated.
+#[derive(Debug)]
+pub struct MigratingIndexedStorage {
+    source: Arc<dyn IndexedStorage + Send + Sync>,
+    target: Arc<dyn IndexedStorage + Send + Sync>,
+    state: Arc<StorageMigrationState>,
+    keys: Mutex<MigratedKeys>,
+    /// Serializes copying a key with the writes to the same key
+    key_locks: Vec<tokio::sync::Mutex<()>>,
+}
+
+/// The keys known to this executor, mirroring the persisted sets of the migration state
+#[derive(Debug, Default)]
+struct MigratedKeys {
+    /// Keys copied to the target and kept in sync by every write since
+    synced: HashSet<MigratedKey>,
+    /// Keys written only to the source since they were last copied
+    pending: HashSet<MigratedKey>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct KeyDigest {
+    entries: u64,
+    md5: [u8; 16],
+}
+
+impl MigratingIndexedStorage {
+    pub fn new(
+        source: Arc<dyn IndexedStorage + Send + Sync>,
+        target: Arc<dyn IndexedStorage + Send + Sync>,
+        state: Arc<StorageMigrationState>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            state,
+            keys: Mutex::new(MigratedKeys::default()),
+            key_locks: (0..KEY_LOCK_STRIPES)
+                .map(|_| tokio::sync::Mutex::new(()))
+                .collect(),
+        }
+    }
+
+    pub fn source(&self) -> &Arc<dyn IndexedStorage + Send + Sync> {
+        &self.source
+    }
+
+    /// Copies a key of a shard that is not cut over yet to the target, unless the target already
+    /// holds the same entries, and keeps it in sync from then on.
+    pub async fn migrate_key(
+        &self,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        batch_size: u64,
+    ) -> Result<(), IndexedStorageError> {
+        let _guard = self.state.write_guard(namespace.agent_id()).await;
+        let _key_guard = self.key_lock(&namespace, key).lock().await;
+
+        if self.state.is_agent_cut_over(namespace.agent_id()) {
+            return Ok(());
+        }
+
+        self.copy_key(&namespace, key, batch_size).await?;
+
+        let encoded = encode_key(&namespace, key);
+        self.state
+            .persist_key(MigratedKeySet::Synced, &encoded)
+            .await
+            .map_err(IndexedStorageError::Other)?;
+        self.state
+            .forget_key(MigratedKeySet::Pending, &encoded)
+            .await
+            .map_err(IndexedStorageError::Other)?;
+
+        let mut keys = self.keys.lock().unwrap();
+        let migrated_key = (namespace, key.to_string());
+        keys.pending.remove(&migrated_key);
+        keys.synced.insert(migrated_key);
+        Ok(())
+    }
+
+    /// Copies the keys of the shard that may have been written since they were last copied, as
+    /// the final pass before cutting the shard over. These are the keys marked as pending by any
+    /// executor, and the keys synced by another executor, which may have written them only to the
+    /// source after losing the shard.
+    ///
+    /// Must be called while holding the guard of [`StorageMigrationState::cut_over_guard`].
+    pub async fn cut_over_shard(
+        &self,
+        shard_id: ShardId,
+        batch_size: u64,
+    ) -> Result<(), IndexedStorageError> {
+        let persisted_pending = self
+            .persisted_keys_of(MigratedKeySet::Pending, shard_id)
+            .await?;
+        let persisted_synced = self
+            .persisted_keys_of(MigratedKeySet::Synced, shard_id)
+            .await?;
+
+        let to_copy = {
+            let keys = self.keys.lock().unwrap();
+            keys.pending
+                .iter()
+                .filter(|(namespace, _)| {
+                    self.state.shard_of(namespace.agent_id()) == Some(shard_id)
+                })
+                .chain(
+                    persisted_pending
+                        .iter()
+                        .map(|(migrated_key, _)| migrated_key),
+                )
+                .chain(
+                    persisted_synced
+                        .iter()
+                        .map(|(migrated_key, _)| migrated_key)
+                        .filter(|migrated_key| !keys.synced.contains(*migrated_key)),
+                )
+                .cloned()
+                .collect::<HashSet<_>>()
+        };
+
+        for (namespace, key) in &to_copy {
+            self.copy_key(namespace, key, batch_size).await?;
+        }
+
+        for (set, persisted) in [
+            (MigratedKeySet::Pending, &persisted_pending),
+            (MigratedKeySet::Synced, &persisted_synced),
+        ] {
+            for (_, encoded) in persisted {
+                self.state
+                    .forget_key(set, encoded)
+                    .await
+                    .map_err(IndexedStorageError::Other)?;
+            }
+        }
+
+        let mut keys = self.keys.lock().unwrap();
+        keys.pending
+            .retain(|(namespace, _)| self.state.shard_of(namespace.agent_id()) != Some(shard_id));
+        keys.synced
+            .retain(|(namespace, _)| self.state.shard_of(namespace.agent_id()) != Some(shard_id));
+
+        debug!(
+            shard_id = shard_id.to_string(),
+            "Copied {} indexed storage keys before cut over",
+            to_copy.len()
+        );
+        Ok(())
+    }
+
+    /// Forgets the keys of the shards no longer assigned to this executor. Their persisted state
+    /// is kept for the executor taking them over.
+    pub fn retain_shards(&self, assigned_shards: &HashSet<ShardId>) {
+        let is_assigned = |(namespace, _): &MigratedKey| {
+            self.state
+                .shard_of(namespace.agent_id())
+                .is_some_and(|shard_id| assigned_shards.contains(&shard_id))
+        };
+        let mut keys = self.keys.lock().unwrap();
+        keys.pending.retain(is_assigned);
+        keys.synced.retain(is_assigned);
+    }
+
+    async fn persisted_keys_of(
+        &self,
+        set: MigratedKeySet,
+        shard_id: ShardId,
+    ) -> Result<Vec<(MigratedKey, String)>, IndexedStorageError> {
+        let mut result = Vec::new();
+        for encoded in self
+            .state
+            .persisted_keys(set)
+            .await
+            .map_err(IndexedStorageError::Other)?
+        {
+            let migrated_key = decode_key(&encoded).map_err(IndexedStorageError::Other)?;
+            if self.state.shard_of(migrated_key.0.agent_id()) == Some(shard_id) {
+                result.push((migrated_key, encoded));
+            }
+        }
+        Ok(result)
+    }
+
+    async fn copy_key(
+        &self,
+        namespace: &IndexedStorageNamespace,
+        key: &str,
+        batch_size: u64,
+    ) -> Result<(), IndexedStorageError> {
+        let source_digest = digest(&self.source, namespace, key, batch_size).await?;
+        if digest(&self.target, namespace, key, batch_size).await? == source_digest {
+            return Ok(());
+        }
+
+        self.target
+            .delete(SVC_NAME, "copy", namespace.clone(), key)
+            .await?;
+
+        let mut from = 0;
+        loop {
+            let batch = read_batch(&self.source, namespace, key, from, batch_size).await?;
+            let Some((last_id, _)) = batch.last() else {
+                break;
+            };
+            from = last_id + 1;
+            self.target
+                .append_many(SVC_NAME, "copy", ENTITY_NAME, namespace.clone(), key, batch)
+                .await?;
+        }
+
+        let target_digest = digest(&self.target, namespace, key, batch_size).await?;
+        if target_digest == source_digest {
+            Ok(())
+        } else {
+            Err(IndexedStorageError::Other(format!(
+                "Checksum mismatch after copying indexed storage key {key} ({} entries in the source, {} in the target)",
+                source_digest.entries, target_digest.entries
+            )))
+        }
+    }
+
+    /// Marks a key as pending, persisting it before the write it is recorded for, so the key is
+    /// copied again even if this executor stops right after the write.
+    async fn mark_pending(&self, migrated_key: &MigratedKey) -> Result<(), IndexedStorageError> {
+        let encoded = encode_key(&migrated_key.0, &migrated_key.1);
+        self.state
+            .persist_key(MigratedKeySet::Pending, &encoded)
+            .await
+            .map_err(IndexedStorageError::Other)?;
+        self.state
+            .forget_key(MigratedKeySet::Synced, &encoded)
+            .await
+            .map_err(IndexedStorageError::Other)?;
+
+        let mut keys = self.keys.lock().unwrap();
+        keys.synced.remove(migrated_key);
+        keys.pending.insert(migrated_key.clone());
+        Ok(())
+    }
+
+    fn reader(
+        &self,
+        namespace: &IndexedStorageNamespace,
+    ) -> &Arc<dyn IndexedStorage + Send + Sync> {
+        if self.state.is_agent_cut_over(namespace.agent_id()) {
+            &self.target
+        } else {
+            &self.source
+        }
+    }
+
+    fn key_lock(&self, namespace: &IndexedStorageNamespace, key: &str) -> &tokio::sync::Mutex<()> {
+        let mut hasher = DefaultHasher::new();
+        namespace.hash(&mut hasher);
+        key.hash(&mut hasher);
+        &self.key_locks[(hasher.finish() % KEY_LOCK_STRIPES as u64) as usize]
+    }
+
+    async fn write<F, Fut>(
+        &self,
+        namespace: &IndexedStorageNamespace,
+        key: &str,
+        op: F,
+    ) -> Result<(), IndexedStorageError>
+    where
+        F: Fn(Arc<dyn IndexedStorage + Send + Sync>) -> Fut,
+        Fut: Future<Output = Result<(), IndexedStorageError>>,
+    {
+        let _guard = self.state.write_guard(namespace.agent_id()).await;
+        let _key_guard = self.key_lock(namespace, key).lock().await;
+
+        if self.state.is_agent_cut_over(namespace.agent_id()) {
+            op(self.target.clone()).await?;
+            op(self.source.clone()).await?;
+        } else {
+            let migrated_key = (namespace.clone(), key.to_string());
+            let (synced, pending) = {
+                let keys = self.keys.lock().unwrap();
+                (
+                    keys.synced.contains(&migrated_key),
+                    keys.pending.contains(&migrated_key),
+                )
+            };
+
+            if !synced && !pending {
+                self.mark_pending(&migrated_key).await?;
+            }
+
+            op(self.source.clone()).await?;
+
+            if synced && let Err(err) = op(self.target.clone()).await {
+                warn!(
+                    key,
+                    "Failed to mirror write to the target indexed storage, the key will be copied again: {err}"
+                );
+                self.mark_pending(&migrated_key).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a key for the persisted key sets of the migration state
+fn encode_key(namespace: &IndexedStorageNamespace, key: &str) -> String {
+    match namespace {
+        IndexedStorageNamespace::OpLog { agent_mode, .. } => {
+            format!("oplog:{}:{key}", agent_mode_prefix(*agent_mode))
+        }
+        IndexedStorageNamespace::CompressedOpLog {
+            agent_mode, level, ..
+        } => format!(
+            "compressed-oplog:{}:{level}:{key}",
+            agent_mode_prefix(*agent_mode)
+        ),
+    }
+}
+
+fn decode_key(encoded: &str) -> Result<MigratedKey, String> {
+    let invalid = || format!("Invalid persisted indexed storage key: {encoded}");
+    let parse_mode = |mode: &str| match mode {
+        "durable" => Ok(AgentMode::Durable),
+        "ephemeral" => Ok(AgentMode::Ephemeral),
+        _ => Err(invalid()),
+    };
+
+    let (kind, rest) = encoded.split_once(':').ok_or_else(invalid)?;
+    let (mode, rest) = rest.split_once(':').ok_or_else(invalid)?;
+    let agent_mode = parse_mode(mode)?;
+    let (meta_namespace, key) = match kind {
+        "oplog" => (IndexedStorageMetaNamespace::Oplog { agent_mode }, rest),
+        "compressed-oplog" => {
+            let (level, key) = rest.split_once(':').ok_or_else(invalid)?;
+            let level = level.parse().map_err(|_| invalid())?;
+            (
+                IndexedStorageMetaNamespace::CompressedOplog { agent_mode, level },
+                key,
+            )
+        }
+        _ => return Err(invalid()),
+    };
+    Ok((meta_namespace.namespace_of(key)?, key.to_string()))
+}
+
+/// Reads the next batch of entries with ids greater or equal to `from`, skipping gaps between
+/// the ids.
+async fn read_batch(
+    storage: &Arc<dyn IndexedStorage + Send + Sync>,
+    namespace: &IndexedStorageNamespace,
+    key: &str,
+    from: u64,
+    batch_size: u64,
+) -> Result<Vec<(u64, Vec<u8>)>, IndexedStorageError> {
+    match storage
+        .closest(
+            SVC_NAME,
+            "read_batch",
+            ENTITY_NAME,
+            namespace.clone(),
+            key,
+            from,
+        )
+        .await?
+    {
+        Some((first_id, _)) => {
+            storage
+                .read(
+                    SVC_NAME,
+                    "read_batch",
+                    ENTITY_NAME,
+                    namespace.clone(),
+                    key,
+                    first_id,
+                    first_id.saturating_add(batch_size.max(1) - 1),
+                )
+                .await
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn digest(
+    storage: &Arc<dyn IndexedStorage + Send + Sync>,
+    namespace: &IndexedStorageNamespace,
+    key: &str,
+    batch_size: u64,
+) -> Result<KeyDigest, IndexedStorageError> {
+    let mut context = md5::Context::new();
+    let mut entries = 0;
+    let mut from = 0;
+    loop {
+        let batch = read_batch(storage, namespace, key, from, batch_size).await?;
+        let Some((last_id, _)) = batch.last() else {
+            break;
+        };
+        from = last_id + 1;
+        for (id, value) in &batch {
+            context.consume(id.to_le_bytes());
+            context.consume((value.len() as u64).to_le_bytes());
+            context.consume(value);
+            entries += 1;
+        }
+    }
+    Ok(KeyDigest {
+        entries,
+        md5: context.compute().0,
+    })
+}
+
+#[async_trait]
+impl IndexedStorage for MigratingIndexedStorage {
+    async fn number_of_replicas(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<u8, IndexedStorageError> {
+        let source = self.source.number_of_replicas(svc_name, api_name).await?;
+        let target = self.target.number_of_replicas(svc_name, api_name).await?;
+        Ok(source.min(target))
+    }
+
+    async fn wait_for_replicas(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        replicas: u8,
+        timeout: Duration,
+    ) -> Result<u8, IndexedStorageError> {
+        let source = self
+            .source
+            .wait_for_replicas(svc_name, api_name, replicas, timeout)
+            .await?;
+        let target = self
+            .target
+            .wait_for_replicas(svc_name, api_name, replicas, timeout)
+            .await?;
+        Ok(source.min(target))
+    }
+
+    async fn exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<bool, IndexedStorageError> {
+        self.reader(&namespace)
+            .exists(svc_name, api_name, namespace, key)
+            .await
+    }
+
+    async fn scan(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageMetaNamespace,
+        prefix: Option<&str>,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(ScanCursor, Vec<String>), IndexedStorageError> {
+        // Every key is created in the source, including the ones of cut over shards
+        self.source
+            .scan(svc_name, api_name, namespace, prefix, cursor, count)
+            .await
+    }
+
+    async fn append(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+        value: Vec<u8>,
+    ) -> Result<(), IndexedStorageError> {
+        self.write(&namespace, key, |storage| {
+            let namespace = namespace.clone();
+            let value = value.clone();
+            async move {
+                storage
+                    .append(svc_name, api_name, entity_name, namespace, key, id, value)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn append_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        pairs: Vec<(u64, Vec<u8>)>,
+    ) -> Result<(), IndexedStorageError> {
+        self.write(&namespace, key, |storage| {
+            let namespace = namespace.clone();
+            let pairs = pairs.clone();
+            async move {
+                storage
+                    .append_many(svc_name, api_name, entity_name, namespace, key, pairs)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn length(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<u64, IndexedStorageError> {
+        self.reader(&namespace)
+            .length(svc_name, api_name, namespace, key)
+            .await
+    }
+
+    async fn delete(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<(), IndexedStorageError> {
+        self.write(&namespace, key, |storage| {
+            let namespace = namespace.clone();
+            async move { storage.delete(svc_name, api_name, namespace, key).await }
+        })
+        .await
+    }
+
+    async fn read(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        start_id: u64,
+        end_id: u64,
+    ) -> Result<Vec<(u64, Vec<u8>)>, IndexedStorageError> {
+        self.reader(&namespace)
+            .read(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace,
+                key,
+                start_id,
+                end_id,
+            )
+            .await
+    }
+
+    async fn first(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        self.reader(&namespace)
+            .first(svc_name, api_name, entity_name, namespace, key)
+            .await
+    }
+
+    async fn last(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        self.reader(&namespace)
+            .last(svc_name, api_name, entity_name, namespace, key)
+            .await
+    }
+
+    async fn closest(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, IndexedStorageError> {
+        self.reader(&namespace)
+            .closest(svc_name, api_name, entity_name, namespace, key, id)
+            .await
+    }
+
+    async fn drop_prefix(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        last_dropped_id: u64,
+    ) -> Result<(), IndexedStorageError> {
+        self.write(&namespace, key, |storage| {
+            let namespace = namespace.clone();
+            async move {
+                storage
+                    .drop_prefix(svc_name, api_name, namespace, key, last_dropped_id)
+                    .await
+            }
+        })
+        .await
+    }
+}
@@ -19,9 +19,13 @@ use async_trait::async_trait;
 use desert_rust::{BinaryDeserializer, BinarySerializer};
 use golem_common::model::AgentId;
 use golem_common::model::agent::AgentMode;
+use golem_common::model::component::ComponentId;
 use golem_common::serialization::{deserialize, serialize};
+use uuid::Uuid;
 
+pub mod encrypting;
 pub mod memory;
+pub mod migrating;
 pub mod multi_sqlite;
 pub mod postgres;
 pub mod redis;
@@ -673,6 +677,15 @@ pub enum IndexedStorageNamespace {
     },
 }
 
+impl IndexedStorageNamespace {
+    pub fn agent_id(&self) -> &AgentId {
+        match self {
+            IndexedStorageNamespace::OpLog { agent_id, .. } => agent_id,
+            IndexedStorageNamespace::CompressedOpLog { agent_id, .. } => agent_id,
+        }
+    }
+}
+
 /// Various namespaces for operations working on multiple indexed storage namespaces such as scan
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub enum IndexedStorageMetaNamespace {
@@ -680,6 +693,36 @@ pub enum IndexedStorageMetaNamespace {
     CompressedOplog { agent_mode: AgentMode, level: usize },
 }
 
+impl IndexedStorageMetaNamespace {
+    /// Gets the namespace of a key returned by [`IndexedStorage::scan`] for this meta-namespace.
+    /// The keys of all the namespaces are the agent IDs' redis keys.
+    pub fn namespace_of(&self, key: &str) -> Result<IndexedStorageNamespace, String> {
+        let (component_id, agent_id) = key
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid indexed storage key: {key}"))?;
+        let agent_id = AgentId {
+            component_id: ComponentId(
+                Uuid::parse_str(component_id)
+                    .map_err(|err| format!("Invalid indexed storage key {key}: {err}"))?,
+            ),
+            agent_id: agent_id.to_string(),
+        };
+        Ok(match self {
+            IndexedStorageMetaNamespace::Oplog { agent_mode } => IndexedStorageNamespace::OpLog {
+                agent_id,
+                agent_mode: *agent_mode,
+            },
+            IndexedStorageMetaNamespace::CompressedOplog { agent_mode, level } => {
+                IndexedStorageNamespace::CompressedOpLog {
+                    agent_id,
+                    agent_mode: *agent_mode,
+                    level: *level,
+                }
+            }
+        })
+    }
+}
+
 /// Returns the symmetric per-mode prefix used by all indexed-storage backends.
 pub fn agent_mode_prefix(mode: AgentMode) -> &'static str {
     match mode {
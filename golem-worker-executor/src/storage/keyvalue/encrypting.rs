@@ -0,0 +1,393 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::encryption::{DataKeyScope, EnvelopeEncryption};
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::sync::Arc;
+
+/// Key-value storage encrypting the values of the user-defined buckets (`wasi:keyvalue`) with the
+/// data key of their environment.
+///
+/// The internal namespaces, and the members of sets and sorted sets (which are compared by
+/// value), are stored unchanged.
+#[derive(Debug)]
+pub struct EncryptingKeyValueStorage {
+    inner: Arc<dyn KeyValueStorage + Send + Sync>,
+    encryption: Arc<EnvelopeEncryption>,
+}
+
+impl EncryptingKeyValueStorage {
+    pub fn new(
+        inner: Arc<dyn KeyValueStorage + Send + Sync>,
+        encryption: Arc<EnvelopeEncryption>,
+    ) -> Self {
+        Self { inner, encryption }
+    }
+
+    /// The data key scope and the bucket of the encrypted namespaces
+    fn scope(namespace: &KeyValueStorageNamespace) -> Option<(DataKeyScope, &str)> {
+        match namespace {
+            KeyValueStorageNamespace::UserDefined {
+                environment_id,
+                bucket,
+            } => Some((DataKeyScope::Environment(*environment_id), bucket)),
+            _ => None,
+        }
+    }
+
+    async fn encrypt(
+        &self,
+        namespace: &KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<Option<Vec<u8>>, String> {
+        match Self::scope(namespace) {
+            Some((scope, bucket)) => Ok(Some(
+                self.encryption
+                    .encrypt(&scope, &format!("{bucket}/{key}"), value)
+                    .await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    async fn decrypt(
+        &self,
+        namespace: &KeyValueStorageNamespace,
+        key: &str,
+        value: Bytes,
+    ) -> Result<Bytes, String> {
+        match Self::scope(namespace) {
+            Some((scope, bucket)) => Ok(Bytes::from(
+                self.encryption
+                    .decrypt(&scope, &format!("{bucket}/{key}"), value.to_vec())
+                    .await?,
+            )),
+            None => Ok(value),
+        }
+    }
+}
+
+#[async_trait]
+impl KeyValueStorage for EncryptingKeyValueStorage {
+    async fn set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let encrypted = self.encrypt(&namespace, key, value).await?;
+        let value = encrypted.as_deref().unwrap_or(value);
+        self.inner
+            .set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn set_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        pairs: &[(&str, &[u8])],
+    ) -> Result<(), String> {
+        if Self::scope(&namespace).is_none() {
+            return self
+                .inner
+                .set_many(svc_name, api_name, entity_name, namespace, pairs)
+                .await;
+        }
+
+        let mut encrypted = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            let value = self
+                .encrypt(&namespace, key, value)
+                .await?
+                .unwrap_or_default();
+            encrypted.push((*key, value));
+        }
+        let pairs = encrypted
+            .iter()
+            .map(|(key, value)| (*key, value.as_slice()))
+            .collect::<Vec<_>>();
+        self.inner
+            .set_many(svc_name, api_name, entity_name, namespace, &pairs)
+            .await
+    }
+
+    async fn set_if_not_exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        let encrypted = self.encrypt(&namespace, key, value).await?;
+        let value = encrypted.as_deref().unwrap_or(value);
+        self.inner
+            .set_if_not_exists(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn get(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Option<Bytes>, String> {
+        match self
+            .inner
+            .get(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?
+        {
+            Some(value) => Ok(Some(self.decrypt(&namespace, key, value).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<Bytes>>, String> {
+        let values = self
+            .inner
+            .get_many(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                keys.clone(),
+            )
+            .await?;
+        let mut result = Vec::with_capacity(values.len());
+        for (key, value) in keys.iter().zip(values) {
+            result.push(match value {
+                Some(value) => Some(self.decrypt(&namespace, key, value).await?),
+                None => None,
+            });
+        }
+        Ok(result)
+    }
+
+    async fn get_all(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<(String, Bytes)>, String> {
+        let values = self
+            .inner
+            .get_all(svc_name, api_name, entity_name, namespace.clone())
+            .await?;
+        let mut result = Vec::with_capacity(values.len());
+        for (key, value) in values {
+            let value = self.decrypt(&namespace, &key, value).await?;
+            result.push((key, value));
+        }
+        Ok(result)
+    }
+
+    async fn del(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<(), String> {
+        self.inner.del(svc_name, api_name, namespace, key).await
+    }
+
+    async fn del_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<(), String> {
+        self.inner
+            .del_many(svc_name, api_name, namespace, keys)
+            .await
+    }
+
+    async fn exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<bool, String> {
+        self.inner.exists(svc_name, api_name, namespace, key).await
+    }
+
+    async fn keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        self.inner.keys(svc_name, api_name, namespace).await
+    }
+
+    async fn add_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        self.inner
+            .add_to_set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn remove_from_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        self.inner
+            .remove_from_set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn members_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<Bytes>, String> {
+        self.inner
+            .members_of_set(svc_name, api_name, entity_name, namespace, key)
+            .await
+    }
+
+    async fn add_to_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        score: f64,
+        value: &[u8],
+    ) -> Result<(), String> {
+        self.inner
+            .add_to_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace,
+                key,
+                score,
+                value,
+            )
+            .await
+    }
+
+    async fn remove_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        self.inner
+            .remove_from_sorted_set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        self.inner
+            .remove_range_from_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
+            .await
+    }
+
+    async fn get_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        self.inner
+            .get_sorted_set(svc_name, api_name, entity_name, namespace, key)
+            .await
+    }
+
+    async fn query_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        self.inner
+            .query_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
+            .await
+    }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        self.inner.set_keys(svc_name, api_name, namespace).await
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        self.inner.shared_namespaces(svc_name, api_name).await
+    }
+}
@@ -22,6 +22,8 @@ pub struct InMemoryKeyValueStorage {
     kvs: scc::HashMap<String, Vec<u8>>,
     sets: scc::HashMap<String, scc::HashSet<Vec<u8>>>,
     sorted_sets: scc::HashMap<String, Vec<(f64, Vec<u8>)>>,
+    /// Every namespace written to, for listing the shared ones
+    namespaces: scc::HashSet<KeyValueStorageNamespace>,
     /// Guards the plain key-value (`kvs`) operations so that multi-key writes/reads
     /// (`set_many`/`get_many`/`del_many`) are atomic with respect to each other and to single-key
     /// writes. `scc::HashMap` only provides per-key atomicity; this lock provides the cross-key
@@ -42,6 +44,7 @@ impl InMemoryKeyValueStorage {
             kvs: scc::HashMap::new(),
             sets: scc::HashMap::new(),
             sorted_sets: scc::HashMap::new(),
+            namespaces: scc::HashSet::new(),
             kvs_lock: tokio::sync::RwLock::new(()),
         }
     }
@@ -61,6 +64,12 @@ impl InMemoryKeyValueStorage {
     fn composite_key(namespace: &KeyValueStorageNamespace, key: &str) -> String {
         format!("{namespace:?}/{key}")
     }
+
+    async fn track_namespace(&self, namespace: &KeyValueStorageNamespace) {
+        if !self.namespaces.contains_async(namespace).await {
+            let _ = self.namespaces.insert_async(namespace.clone()).await;
+        }
+    }
 }
 
 #[async_trait]
@@ -74,6 +83,7 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         key: &str,
         value: &[u8],
     ) -> Result<(), String> {
+        self.track_namespace(&namespace).await;
         let _guard = self.kvs_lock.write().await;
         self.kvs
             .upsert_async(Self::composite_key(&namespace, key), value.to_vec())
@@ -89,6 +99,7 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         namespace: KeyValueStorageNamespace,
         pairs: &[(&str, &[u8])],
     ) -> Result<(), String> {
+        self.track_namespace(&namespace).await;
         let _guard = self.kvs_lock.write().await;
         for (key, value) in pairs {
             self.kvs
@@ -107,6 +118,7 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         key: &str,
         value: &[u8],
     ) -> Result<bool, String> {
+        self.track_namespace(&namespace).await;
         let _guard = self.kvs_lock.write().await;
         match self
             .kvs
@@ -256,6 +268,7 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         key: &str,
         value: &[u8],
     ) -> Result<(), String> {
+        self.track_namespace(&namespace).await;
         let set = self
             .sets
             .entry_async(Self::composite_key(&namespace, key))
@@ -324,6 +337,7 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         score: f64,
         value: &[u8],
     ) -> Result<(), String> {
+        self.track_namespace(&namespace).await;
         let mut entry = self
             .sorted_sets
             .entry_async(Self::composite_key(&namespace, key))
@@ -354,6 +368,28 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         Ok(())
     }
 
+    async fn remove_range_from_sorted_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        if let Some(mut entry) = self
+            .sorted_sets
+            .get_async(&Self::composite_key(&namespace, key))
+            .await
+        {
+            entry
+                .get_mut()
+                .retain(|(score, _)| *score < min || *score > max);
+        }
+        Ok(())
+    }
+
     async fn get_sorted_set(
         &self,
         _svc_name: &'static str,
@@ -396,4 +432,40 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
             .await
             .unwrap_or_default())
     }
+
+    async fn set_keys(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        let prefix = Self::composite_key(&namespace, "");
+        let mut result = Vec::new();
+        self.sets
+            .iter_async(|key, _| {
+                if key.starts_with(&prefix) {
+                    result.push(key[prefix.len()..].to_string());
+                }
+                true
+            })
+            .await;
+        Ok(result)
+    }
+
+    async fn shared_namespaces(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        let mut result = Vec::new();
+        self.namespaces
+            .iter_async(|namespace| {
+                if namespace.is_shared() {
+                    result.push(namespace.clone());
+                }
+                true
+            })
+            .await;
+        Ok(result)
+    }
 }
@@ -0,0 +1,833 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+const SVC_NAME: &str = "storage_migration";
+const ENTITY_NAME: &str = "migrated_entry";
+const NAMESPACE_LOCK_STRIPES: usize = 256;
+
+/// Key-value storage used while migrating to a new key-value storage backend.
+///
+/// Every write is applied to both backends, and reads are served from the target, falling back
+/// to the source for values that were not copied yet, while sets are read as the union of both
+/// backends. The migration copies every namespace with the `copy_*` methods, and does not cut a
+/// shard over before the namespaces of its agents have been copied. Copies and writes of the same
+/// namespace are serialized, so a copy never overwrites a newer value in the target.
+#[derive(Debug, Clone)]
+pub struct MigratingKeyValueStorage {
+    source: Arc<dyn KeyValueStorage + Send + Sync>,
+    target: Arc<dyn KeyValueStorage + Send + Sync>,
+    /// Held for reading by writes and for writing by copies of the namespaces hashed to the stripe
+    namespace_locks: Arc<Vec<RwLock<()>>>,
+}
+
+impl MigratingKeyValueStorage {
+    pub fn new(
+        source: Arc<dyn KeyValueStorage + Send + Sync>,
+        target: Arc<dyn KeyValueStorage + Send + Sync>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            namespace_locks: Arc::new(
+                (0..NAMESPACE_LOCK_STRIPES)
+                    .map(|_| RwLock::new(()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn source(&self) -> &Arc<dyn KeyValueStorage + Send + Sync> {
+        &self.source
+    }
+
+    /// Copies the values of the given keys to the target, deleting the ones that no longer exist
+    /// in the source, and verifies that both backends hold the same values.
+    pub async fn copy_values(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+        batch_size: usize,
+    ) -> Result<(), String> {
+        for batch in keys.chunks(batch_size.max(1)) {
+            let _guard = self.copy_guard(&namespace).await;
+
+            let values = self
+                .source
+                .get_many(
+                    SVC_NAME,
+                    "copy_values",
+                    ENTITY_NAME,
+                    namespace.clone(),
+                    batch.to_vec(),
+                )
+                .await?;
+
+            let mut pairs = Vec::new();
+            let mut deleted = Vec::new();
+            for (key, value) in batch.iter().zip(&values) {
+                match value {
+                    Some(value) => pairs.push((key.as_str(), value.as_ref())),
+                    None => deleted.push(key.clone()),
+                }
+            }
+            if !pairs.is_empty() {
+                self.target
+                    .set_many(
+                        SVC_NAME,
+                        "copy_values",
+                        ENTITY_NAME,
+                        namespace.clone(),
+                        &pairs,
+                    )
+                    .await?;
+            }
+            if !deleted.is_empty() {
+                self.target
+                    .del_many(SVC_NAME, "copy_values", namespace.clone(), deleted)
+                    .await?;
+            }
+
+            let copied = self
+                .target
+                .get_many(
+                    SVC_NAME,
+                    "copy_values",
+                    ENTITY_NAME,
+                    namespace.clone(),
+                    batch.to_vec(),
+                )
+                .await?;
+            if copied != values {
+                return Err(format!(
+                    "Values of key-value namespace {namespace:?} differ after copying"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies every value of a namespace to the target
+    pub async fn copy_all_values(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        batch_size: usize,
+    ) -> Result<(), String> {
+        let mut keys = self
+            .source
+            .keys(SVC_NAME, "copy_all_values", namespace.clone())
+            .await?;
+        let mut present = keys.iter().cloned().collect::<HashSet<_>>();
+        for key in self
+            .target
+            .keys(SVC_NAME, "copy_all_values", namespace.clone())
+            .await?
+        {
+            if present.insert(key.clone()) {
+                keys.push(key);
+            }
+        }
+        self.copy_values(namespace, keys, batch_size).await
+    }
+
+    /// Copies the sets stored at the given keys to the target, removing the members that no
+    /// longer exist in the source, and verifies that both backends hold the same members.
+    pub async fn copy_sets(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+    ) -> Result<(), String> {
+        for key in keys {
+            let _guard = self.copy_guard(&namespace).await;
+
+            let source = self.members(&self.source, namespace.clone(), key).await?;
+            let target = self.members(&self.target, namespace.clone(), key).await?;
+            for member in source.difference(&target) {
+                self.target
+                    .add_to_set(
+                        SVC_NAME,
+                        "copy_sets",
+                        ENTITY_NAME,
+                        namespace.clone(),
+                        key,
+                        member,
+                    )
+                    .await?;
+            }
+            for member in target.difference(&source) {
+                self.target
+                    .remove_from_set(
+                        SVC_NAME,
+                        "copy_sets",
+                        ENTITY_NAME,
+                        namespace.clone(),
+                        key,
+                        member,
+                    )
+                    .await?;
+            }
+
+            if self.members(&self.target, namespace.clone(), key).await? != source {
+                return Err(format!(
+                    "Set {key} of key-value namespace {namespace:?} differs after copying"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies every set of a namespace to the target
+    pub async fn copy_all_sets(&self, namespace: KeyValueStorageNamespace) -> Result<(), String> {
+        let mut keys = self
+            .source
+            .set_keys(SVC_NAME, "copy_all_sets", namespace.clone())
+            .await?;
+        let mut present = keys.iter().cloned().collect::<HashSet<_>>();
+        for key in self
+            .target
+            .set_keys(SVC_NAME, "copy_all_sets", namespace.clone())
+            .await?
+        {
+            if present.insert(key.clone()) {
+                keys.push(key);
+            }
+        }
+        self.copy_sets(namespace, &keys).await
+    }
+
+    /// Copies the sorted sets stored at the given keys to the target, removing the members that
+    /// no longer exist in the source, and verifies that both backends hold the same members with
+    /// the same scores.
+    pub async fn copy_sorted_sets(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+    ) -> Result<(), String> {
+        for key in keys {
+            let _guard = self.copy_guard(&namespace).await;
+
+            let source = self
+                .sorted_members(&self.source, namespace.clone(), key)
+                .await?;
+            let target = self
+                .sorted_members(&self.target, namespace.clone(), key)
+                .await?;
+            for member in target.keys() {
+                if !source.contains_key(member) {
+                    self.target
+                        .remove_from_sorted_set(
+                            SVC_NAME,
+                            "copy_sorted_sets",
+                            ENTITY_NAME,
+                            namespace.clone(),
+                            key,
+                            member,
+                        )
+                        .await?;
+                }
+            }
+            for (member, score) in &source {
+                if target.get(member) != Some(score) {
+                    self.target
+                        .add_to_sorted_set(
+                            SVC_NAME,
+                            "copy_sorted_sets",
+                            ENTITY_NAME,
+                            namespace.clone(),
+                            key,
+                            f64::from_bits(*score),
+                            member,
+                        )
+                        .await?;
+                }
+            }
+
+            if self
+                .sorted_members(&self.target, namespace.clone(), key)
+                .await?
+                != source
+            {
+                return Err(format!(
+                    "Sorted set {key} of key-value namespace {namespace:?} differs after copying"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn members(
+        &self,
+        storage: &Arc<dyn KeyValueStorage + Send + Sync>,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<HashSet<Bytes>, String> {
+        Ok(storage
+            .members_of_set(SVC_NAME, "members", ENTITY_NAME, namespace, key)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    /// The members of a sorted set with the bits of their scores
+    async fn sorted_members(
+        &self,
+        storage: &Arc<dyn KeyValueStorage + Send + Sync>,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<HashMap<Bytes, u64>, String> {
+        Ok(storage
+            .get_sorted_set(SVC_NAME, "sorted_members", ENTITY_NAME, namespace, key)
+            .await?
+            .into_iter()
+            .map(|(score, value)| (value, score.to_bits()))
+            .collect())
+    }
+
+    fn namespace_lock(&self, namespace: &KeyValueStorageNamespace) -> &RwLock<()> {
+        let mut hasher = DefaultHasher::new();
+        namespace.hash(&mut hasher);
+        &self.namespace_locks[(hasher.finish() % NAMESPACE_LOCK_STRIPES as u64) as usize]
+    }
+
+    async fn write_guard(&self, namespace: &KeyValueStorageNamespace) -> RwLockReadGuard<'_, ()> {
+        self.namespace_lock(namespace).read().await
+    }
+
+    async fn copy_guard(&self, namespace: &KeyValueStorageNamespace) -> RwLockWriteGuard<'_, ()> {
+        self.namespace_lock(namespace).write().await
+    }
+}
+
+/// Merges the members of a sorted set read from both backends
+fn merge_sorted_sets(
+    mut target: Vec<(f64, Bytes)>,
+    source: Vec<(f64, Bytes)>,
+) -> Vec<(f64, Bytes)> {
+    let mut present = target
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect::<HashSet<_>>();
+    let mut merged = false;
+    for (score, value) in source {
+        if present.insert(value.clone()) {
+            target.push((score, value));
+            merged = true;
+        }
+    }
+    if merged {
+        target.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    }
+    target
+}
+
+#[async_trait]
+impl KeyValueStorage for MigratingKeyValueStorage {
+    async fn set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                value,
+            )
+            .await?;
+        self.target
+            .set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn set_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        pairs: &[(&str, &[u8])],
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .set_many(svc_name, api_name, entity_name, namespace.clone(), pairs)
+            .await?;
+        self.target
+            .set_many(svc_name, api_name, entity_name, namespace, pairs)
+            .await
+    }
+
+    async fn set_if_not_exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        let _guard = self.write_guard(&namespace).await;
+        // The source holds every value, so it decides whether the value already exists
+        let written = self
+            .source
+            .set_if_not_exists(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                value,
+            )
+            .await?;
+        if written {
+            self.target
+                .set(svc_name, api_name, entity_name, namespace, key, value)
+                .await?;
+        }
+        Ok(written)
+    }
+
+    async fn get(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Option<Bytes>, String> {
+        if let Some(value) = self
+            .target
+            .get(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?
+        {
+            return Ok(Some(value));
+        }
+
+        let value = self
+            .source
+            .get(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        Ok(value)
+    }
+
+    async fn get_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<Bytes>>, String> {
+        let mut values = self
+            .target
+            .get_many(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                keys.clone(),
+            )
+            .await?;
+
+        let missing = values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_none())
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(values);
+        }
+
+        let source_values = self
+            .source
+            .get_many(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                missing.iter().map(|idx| keys[*idx].clone()).collect(),
+            )
+            .await?;
+        for (idx, value) in missing.into_iter().zip(source_values) {
+            if value.is_some() {
+                values[idx] = value;
+            }
+        }
+        Ok(values)
+    }
+
+    async fn get_all(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<(String, Bytes)>, String> {
+        let mut values = self
+            .target
+            .get_all(svc_name, api_name, entity_name, namespace.clone())
+            .await?;
+        let present = values
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<HashSet<_>>();
+
+        let source_values = self
+            .source
+            .get_all(svc_name, api_name, entity_name, namespace.clone())
+            .await?;
+        for (key, value) in source_values {
+            if !present.contains(&key) {
+                values.push((key, value));
+            }
+        }
+        Ok(values)
+    }
+
+    async fn del(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .del(svc_name, api_name, namespace.clone(), key)
+            .await?;
+        self.target.del(svc_name, api_name, namespace, key).await
+    }
+
+    async fn del_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .del_many(svc_name, api_name, namespace.clone(), keys.clone())
+            .await?;
+        self.target
+            .del_many(svc_name, api_name, namespace, keys)
+            .await
+    }
+
+    async fn exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<bool, String> {
+        if self
+            .target
+            .exists(svc_name, api_name, namespace.clone(), key)
+            .await?
+        {
+            Ok(true)
+        } else {
+            self.source.exists(svc_name, api_name, namespace, key).await
+        }
+    }
+
+    async fn keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        let mut keys = self
+            .target
+            .keys(svc_name, api_name, namespace.clone())
+            .await?;
+        let mut present = keys.iter().cloned().collect::<HashSet<_>>();
+        for key in self.source.keys(svc_name, api_name, namespace).await? {
+            if present.insert(key.clone()) {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn add_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .add_to_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                value,
+            )
+            .await?;
+        self.target
+            .add_to_set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn remove_from_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .remove_from_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                value,
+            )
+            .await?;
+        self.target
+            .remove_from_set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn members_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<Bytes>, String> {
+        let mut members = self
+            .target
+            .members_of_set(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        let mut present = members.iter().cloned().collect::<HashSet<_>>();
+
+        let source_members = self
+            .source
+            .members_of_set(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        for member in source_members {
+            if present.insert(member.clone()) {
+                members.push(member);
+            }
+        }
+        Ok(members)
+    }
+
+    async fn add_to_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        score: f64,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .add_to_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                score,
+                value,
+            )
+            .await?;
+        self.target
+            .add_to_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace,
+                key,
+                score,
+                value,
+            )
+            .await
+    }
+
+    async fn remove_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .remove_from_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                value,
+            )
+            .await?;
+        self.target
+            .remove_from_sorted_set(svc_name, api_name, entity_name, namespace, key, value)
+            .await
+    }
+
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        let _guard = self.write_guard(&namespace).await;
+        self.source
+            .remove_range_from_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                min,
+                max,
+            )
+            .await?;
+        self.target
+            .remove_range_from_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
+            .await
+    }
+
+    async fn get_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        let target = self
+            .target
+            .get_sorted_set(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        let source = self
+            .source
+            .get_sorted_set(svc_name, api_name, entity_name, namespace.clone(), key)
+            .await?;
+        Ok(merge_sorted_sets(target, source))
+    }
+
+    async fn query_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        let target = self
+            .target
+            .query_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                min,
+                max,
+            )
+            .await?;
+        let source = self
+            .source
+            .query_sorted_set(
+                svc_name,
+                api_name,
+                entity_name,
+                namespace.clone(),
+                key,
+                min,
+                max,
+            )
+            .await?;
+        Ok(merge_sorted_sets(target, source))
+    }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        let mut keys = self
+            .target
+            .set_keys(svc_name, api_name, namespace.clone())
+            .await?;
+        let mut present = keys.iter().cloned().collect::<HashSet<_>>();
+        for key in self.source.set_keys(svc_name, api_name, namespace).await? {
+            if present.insert(key.clone()) {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        let mut namespaces = self.target.shared_namespaces(svc_name, api_name).await?;
+        let mut present = namespaces.iter().cloned().collect::<HashSet<_>>();
+        for namespace in self.source.shared_namespaces(svc_name, api_name).await? {
+            if present.insert(namespace.clone()) {
+                namespaces.push(namespace);
+            }
+        }
+        Ok(namespaces)
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod encrypting;
 pub mod memory;
+pub mod migrating;
 pub mod multi_sqlite;
 pub mod namespace_routed;
 pub mod postgres;
@@ -171,6 +173,18 @@ pub trait KeyValueStorage: Debug {
         value: &[u8],
     ) -> Result<(), String>;
 
+    /// Removes the values whose score is between `min` and `max` (inclusive)
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String>;
+
     async fn get_sorted_set(
         &self,
         svc_name: &'static str,
@@ -190,6 +204,23 @@ pub trait KeyValueStorage: Debug {
         min: f64,
         max: f64,
     ) -> Result<Vec<(f64, Bytes)>, String>;
+
+    /// Returns the keys of the sets stored under `namespace`
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String>;
+
+    /// Returns every namespace holding data that is shared by the agents of an environment (see
+    /// [`KeyValueStorageNamespace::is_shared`]). Their names are not known by the executor in
+    /// advance, so copying them to another backend requires listing them.
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String>;
 }
 
 pub trait KeyValueStorageLabelledApi<T: KeyValueStorage + ?Sized> {
@@ -595,6 +626,26 @@ impl<'a, S: ?Sized + KeyValueStorage> LabelledEntityKeyValueStorage<'a, S> {
             .await
     }
 
+    pub async fn remove_range_from_sorted_set(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        self.storage
+            .remove_range_from_sorted_set(
+                self.svc_name,
+                self.api_name,
+                self.entity_name,
+                namespace,
+                key,
+                min,
+                max,
+            )
+            .await
+    }
+
     pub async fn get_sorted_set<V: BinaryDeserializer>(
         &self,
         namespace: KeyValueStorageNamespace,
@@ -675,8 +726,69 @@ pub enum KeyValueStorageNamespace {
         agent_id: AgentId,
     },
     Schedule,
+    /// Subscriptions of the durable agent-to-agent pub/sub topics of an environment. Each topic
+    /// name is a key holding the set of its subscriptions.
+    Topic {
+        environment_id: EnvironmentId,
+    },
+    /// Audit trail of the lifecycle policy actions applied to the agents of an environment
+    AgentLifecycleAudit {
+        environment_id: EnvironmentId,
+    },
     UserDefined {
         environment_id: EnvironmentId,
         bucket: String,
     },
+    /// Versions of the data key of an at-rest encryption scope (an agent or an environment),
+    /// wrapped by a master key. Each key is a data key version.
+    EncryptionKeys {
+        scope: String,
+    },
+    /// State of an online storage migration, shared by the executors of the cluster
+    StorageMigration,
+}
+
+impl KeyValueStorageNamespace {
+    /// Whether the namespace is shared by the agents of an environment, rather than belonging to
+    /// a single agent or to the executor itself
+    pub fn is_shared(&self) -> bool {
+        matches!(
+            self,
+            KeyValueStorageNamespace::Topic { .. }
+                | KeyValueStorageNamespace::AgentLifecycleAudit { .. }
+                | KeyValueStorageNamespace::UserDefined { .. }
+                | KeyValueStorageNamespace::EncryptionKeys { .. }
+        )
+    }
+
+    /// Parses the name a shared namespace is stored under by the Redis, SQLite and PostgreSQL
+    /// backends. Redis stores sets and sorted sets under `{namespace}:{key}`, so the key is
+    /// ignored for the namespaces that hold them.
+    pub(crate) fn parse_shared(name: &str) -> Option<Self> {
+        fn environment_id(rest: &str) -> Option<EnvironmentId> {
+            rest.split(':').next()?.parse().ok()
+        }
+
+        if let Some(rest) = name.strip_prefix("topic:") {
+            Some(KeyValueStorageNamespace::Topic {
+                environment_id: environment_id(rest)?,
+            })
+        } else if let Some(rest) = name.strip_prefix("agent-lifecycle-audit:") {
+            Some(KeyValueStorageNamespace::AgentLifecycleAudit {
+                environment_id: environment_id(rest)?,
+            })
+        } else if let Some(rest) = name.strip_prefix("user-defined:") {
+            let (environment_id, bucket) = rest.split_once(':')?;
+            Some(KeyValueStorageNamespace::UserDefined {
+                environment_id: environment_id.parse().ok()?,
+                bucket: bucket.to_string(),
+            })
+        } else {
+            name.strip_prefix("encryption-keys:").map(|scope| {
+                KeyValueStorageNamespace::EncryptionKeys {
+                    scope: scope.to_string(),
+                }
+            })
+        }
+    }
 }
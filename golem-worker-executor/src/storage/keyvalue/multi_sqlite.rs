@@ -42,6 +42,14 @@ struct HashCache {
 }
 
 impl MultiSqliteKeyValueStorage {
+    /// Databases holding the namespaces shared by the agents of an environment
+    const SHARED_DBS: [&'static str; 4] = [
+        "kv-topic.db",
+        "kv-agent-lifecycle-audit.db",
+        "kv-user-defined.db",
+        "kv-encryption-keys.db",
+    ];
+
     pub fn new(root_dir: &Path, max_connections: u32, foreign_keys: bool) -> Self {
         if !root_dir.exists() {
             std::fs::create_dir_all(root_dir)
@@ -85,6 +93,10 @@ impl MultiSqliteKeyValueStorage {
         namespace: &KeyValueStorageNamespace,
     ) -> Result<SqliteKeyValueStorage, String> {
         let db = self.namespace_to_db(namespace).await;
+        self.storage_by_db(db).await
+    }
+
+    async fn storage_by_db(&self, db: String) -> Result<SqliteKeyValueStorage, String> {
         let max_connections = self.max_connections;
         let foreign_keys = self.foreign_keys;
         let db_path = self.root_dir.join(db.clone()).to_string_lossy().to_string();
@@ -111,7 +123,13 @@ impl MultiSqliteKeyValueStorage {
                 format!("kv-worker-{}.db", self.agent_id_hash(agent_id).await)
             }
             KeyValueStorageNamespace::Schedule => "kv-schedule.db".to_string(),
+            KeyValueStorageNamespace::Topic { .. } => "kv-topic.db".to_string(),
+            KeyValueStorageNamespace::AgentLifecycleAudit { .. } => {
+                "kv-agent-lifecycle-audit.db".to_string()
+            }
             KeyValueStorageNamespace::UserDefined { .. } => "kv-user-defined.db".to_string(),
+            KeyValueStorageNamespace::EncryptionKeys { .. } => "kv-encryption-keys.db".to_string(),
+            KeyValueStorageNamespace::StorageMigration => "kv-storage-migration.db".to_string(),
         }
     }
 
@@ -360,6 +378,22 @@ impl KeyValueStorage for MultiSqliteKeyValueStorage {
             .await
     }
 
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        self.storage_by_namespace(&namespace)
+            .await?
+            .remove_range_from_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
+            .await
+    }
+
     async fn get_sorted_set(
         &self,
         svc_name: &'static str,
@@ -389,4 +423,33 @@ impl KeyValueStorage for MultiSqliteKeyValueStorage {
             .query_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
             .await
     }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        self.storage_by_namespace(&namespace)
+            .await?
+            .set_keys(svc_name, api_name, namespace)
+            .await
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        let mut result = Vec::new();
+        for db in Self::SHARED_DBS {
+            result.extend(
+                self.storage_by_db(db.to_string())
+                    .await?
+                    .shared_namespaces(svc_name, api_name)
+                    .await?,
+            );
+        }
+        Ok(result)
+    }
 }
@@ -264,6 +264,21 @@ impl KeyValueStorage for NamespaceRoutedKeyValueStorage {
             .await
     }
 
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        self.backend_for_namespace(&namespace)
+            .remove_range_from_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
+            .await
+    }
+
     async fn get_sorted_set(
         &self,
         svc_name: &'static str,
@@ -291,4 +306,24 @@ impl KeyValueStorage for NamespaceRoutedKeyValueStorage {
             .query_sorted_set(svc_name, api_name, entity_name, namespace, key, min, max)
             .await
     }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        self.backend_for_namespace(&namespace)
+            .set_keys(svc_name, api_name, namespace)
+            .await
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        // The shared namespaces are never routed to the cache
+        self.persistent.shared_namespaces(svc_name, api_name).await
+    }
 }
@@ -79,10 +79,20 @@ impl PostgresKeyValueStorage {
             }
             KeyValueStorageNamespace::Promise { .. } => "promises".to_string(),
             KeyValueStorageNamespace::Schedule => "schedule".to_string(),
+            KeyValueStorageNamespace::Topic { environment_id } => {
+                format!("topic:{environment_id}")
+            }
+            KeyValueStorageNamespace::AgentLifecycleAudit { environment_id } => {
+                format!("agent-lifecycle-audit:{environment_id}")
+            }
             KeyValueStorageNamespace::UserDefined {
                 environment_id,
                 bucket,
             } => format!("user-defined:{environment_id}:{bucket}"),
+            KeyValueStorageNamespace::EncryptionKeys { scope } => {
+                format!("encryption-keys:{scope}")
+            }
+            KeyValueStorageNamespace::StorageMigration => "storage-migration".to_string(),
         }
     }
 
@@ -492,6 +502,32 @@ impl KeyValueStorage for PostgresKeyValueStorage {
             .map_err(|err| err.to_safe_string())
     }
 
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM sorted_set_storage WHERE namespace = $1 AND key = $2 AND score BETWEEN $3 AND $4;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(min)
+        .bind(max);
+
+        self.pool
+            .with_rw(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_safe_string())
+    }
+
     async fn get_sorted_set(
         &self,
         svc_name: &'static str,
@@ -539,6 +575,46 @@ impl KeyValueStorage for PostgresKeyValueStorage {
             .map(|rows| rows.into_iter().map(DBScoreValue::into_pair).collect())
             .map_err(|err| err.to_safe_string())
     }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        let query = sqlx::query_as::<_, (String,)>(
+            "SELECT DISTINCT key FROM set_storage WHERE namespace = $1 ORDER BY key ASC;",
+        )
+        .bind(Self::namespace(namespace));
+
+        self.pool
+            .with_ro(svc_name, api_name)
+            .fetch_all_as::<(String,), _>(query)
+            .await
+            .map(|rows| rows.into_iter().map(|row| row.0).collect())
+            .map_err(|err| err.to_safe_string())
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        let query = sqlx::query_as::<_, (String,)>(
+            "SELECT namespace FROM kv_storage UNION SELECT namespace FROM set_storage UNION SELECT namespace FROM sorted_set_storage;",
+        );
+
+        self.pool
+            .with_ro(svc_name, api_name)
+            .fetch_all_as::<(String,), _>(query)
+            .await
+            .map(|rows| {
+                rows.into_iter()
+                    .filter_map(|row| KeyValueStorageNamespace::parse_shared(&row.0))
+                    .collect()
+            })
+            .map_err(|err| err.to_safe_string())
+    }
 }
 
 #[derive(sqlx::FromRow, Debug)]
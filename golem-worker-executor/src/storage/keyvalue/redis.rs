@@ -17,7 +17,7 @@ use bytes::Bytes;
 use fred::types::SetOptions;
 use golem_common::metrics::redis::{record_redis_deserialized_size, record_redis_serialized_size};
 use golem_common::redis::RedisPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
 
@@ -27,6 +27,8 @@ pub struct RedisKeyValueStorage {
 }
 
 impl RedisKeyValueStorage {
+    const SCAN_COUNT: u64 = 1000;
+
     pub fn new(redis: RedisPool) -> Self {
         Self { redis }
     }
@@ -47,11 +49,58 @@ impl RedisKeyValueStorage {
             KeyValueStorageNamespace::RunningWorkers => None,
             KeyValueStorageNamespace::Promise { .. } => Some("promises".to_string()),
             KeyValueStorageNamespace::Schedule => None,
+            KeyValueStorageNamespace::Topic { environment_id } => {
+                Some(format!("topic:{environment_id}"))
+            }
+            KeyValueStorageNamespace::AgentLifecycleAudit { environment_id } => {
+                Some(format!("agent-lifecycle-audit:{environment_id}"))
+            }
             KeyValueStorageNamespace::UserDefined {
                 environment_id,
                 bucket,
             } => Some(format!("user-defined:{environment_id}:{bucket}")),
+            KeyValueStorageNamespace::EncryptionKeys { scope } => {
+                Some(format!("encryption-keys:{scope}"))
+            }
+            KeyValueStorageNamespace::StorageMigration => Some("storage-migration".to_string()),
+        }
+    }
+
+    /// Returns every key matching the pattern, scanning the key space in batches
+    async fn scan_all(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        pattern: &str,
+    ) -> Result<Vec<String>, String> {
+        let mut result = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next_cursor, keys) = self
+                .redis
+                .with(svc_name, api_name)
+                .scan(pattern, cursor, Self::SCAN_COUNT)
+                .await
+                .map_err(|redis_err| redis_err.to_string())?;
+            result.extend(keys);
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
         }
+        Ok(result)
+    }
+
+    /// Escapes the glob special characters of a literal key prefix used in a `SCAN` pattern
+    fn escape_pattern(prefix: &str) -> String {
+        let mut result = String::with_capacity(prefix.len());
+        for c in prefix.chars() {
+            if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+                result.push('\\');
+            }
+            result.push(c);
+        }
+        result
     }
 }
 
@@ -443,6 +492,29 @@ impl KeyValueStorage for RedisKeyValueStorage {
             .map_err(|e| e.to_string())
     }
 
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        let key = match Self::use_hash(&namespace) {
+            Some(ns) => format!("{ns}:{key}"),
+            None => key.to_string(),
+        };
+        let _: u64 = self
+            .redis
+            .with(svc_name, api_name)
+            .zremrangebyscore(&key, min, max)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
     async fn get_sorted_set(
         &self,
         svc_name: &'static str,
@@ -502,4 +574,51 @@ impl KeyValueStorage for RedisKeyValueStorage {
             .map(|(data, score)| (score, data))
             .collect())
     }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        match Self::use_hash(&namespace) {
+            Some(ns) => {
+                let prefix = format!("{ns}:");
+                let keys = self
+                    .scan_all(
+                        svc_name,
+                        api_name,
+                        &format!("{}*", Self::escape_pattern(&prefix)),
+                    )
+                    .await?;
+                Ok(keys
+                    .into_iter()
+                    .filter_map(|key| key.strip_prefix(&prefix).map(|key| key.to_string()))
+                    .collect())
+            }
+            None => Err("set_keys is only supported for Redis hash namespaces".to_string()),
+        }
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        let mut namespaces = HashSet::new();
+        for prefix in [
+            "topic:",
+            "agent-lifecycle-audit:",
+            "user-defined:",
+            "encryption-keys:",
+        ] {
+            for key in self
+                .scan_all(svc_name, api_name, &format!("{prefix}*"))
+                .await?
+            {
+                namespaces.extend(KeyValueStorageNamespace::parse_shared(&key));
+            }
+        }
+        Ok(namespaces.into_iter().collect())
+    }
 }
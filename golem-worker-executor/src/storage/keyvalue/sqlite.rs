@@ -74,6 +74,12 @@ impl SqliteKeyValueStorage {
             }
             KeyValueStorageNamespace::Promise { .. } => "promise".to_string(),
             KeyValueStorageNamespace::Schedule => "schedule".to_string(),
+            KeyValueStorageNamespace::Topic { environment_id } => {
+                format!("topic:{environment_id}")
+            }
+            KeyValueStorageNamespace::AgentLifecycleAudit { environment_id } => {
+                format!("agent-lifecycle-audit:{environment_id}")
+            }
             KeyValueStorageNamespace::UserDefined {
                 environment_id,
                 bucket,
@@ -81,6 +87,10 @@ impl SqliteKeyValueStorage {
                 format!("user-defined:{environment_id}:{bucket}")
             }
             KeyValueStorageNamespace::RunningWorkers => "running-workers".to_string(),
+            KeyValueStorageNamespace::EncryptionKeys { scope } => {
+                format!("encryption-keys:{scope}")
+            }
+            KeyValueStorageNamespace::StorageMigration => "storage-migration".to_string(),
         }
     }
 }
@@ -454,6 +464,32 @@ impl KeyValueStorage for SqliteKeyValueStorage {
             .map_err(|err| err.to_safe_string())
     }
 
+    async fn remove_range_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM sorted_set_storage WHERE key = ? AND namespace = ? AND score BETWEEN ? AND ?;",
+        )
+        .bind(key)
+        .bind(Self::namespace(namespace))
+        .bind(min)
+        .bind(max);
+
+        self.pool
+            .with_rw(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_safe_string())
+    }
+
     async fn get_sorted_set(
         &self,
         svc_name: &'static str,
@@ -507,6 +543,44 @@ impl KeyValueStorage for SqliteKeyValueStorage {
             })
             .map_err(|err| err.to_safe_string())
     }
+
+    async fn set_keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        let query = sqlx::query_as("SELECT DISTINCT key FROM set_storage WHERE namespace = ?;")
+            .bind(Self::namespace(namespace));
+
+        self.pool
+            .with_ro(svc_name, api_name)
+            .fetch_all_as::<(String,), _>(query)
+            .await
+            .map(|vec| vec.into_iter().map(|k| k.0).collect::<Vec<String>>())
+            .map_err(|err| err.to_safe_string())
+    }
+
+    async fn shared_namespaces(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+    ) -> Result<Vec<KeyValueStorageNamespace>, String> {
+        let query = sqlx::query_as(
+            "SELECT namespace FROM kv_storage UNION SELECT namespace FROM set_storage UNION SELECT namespace FROM sorted_set_storage;",
+        );
+
+        self.pool
+            .with_ro(svc_name, api_name)
+            .fetch_all_as::<(String,), _>(query)
+            .await
+            .map(|vec| {
+                vec.into_iter()
+                    .filter_map(|ns| KeyValueStorageNamespace::parse_shared(&ns.0))
+                    .collect()
+            })
+            .map_err(|err| err.to_safe_string())
+    }
 }
 
 #[derive(sqlx::FromRow, Debug)]
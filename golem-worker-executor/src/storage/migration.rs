@@ -0,0 +1,231 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use golem_common::model::{AgentId, ShardId};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock as AsyncRwLock};
+
+const SVC_NAME: &str = "storage_migration";
+const CUT_OVER_SHARDS_KEY: &str = "cut_over_shards";
+
+/// State of an online migration between two storage backends, shared by the migrating storage
+/// wrappers and the background migration process.
+///
+/// A shard is served from the source backends until this executor cuts it over, after which the
+/// targets become its primary storage. Key-value and scheduler writes always go to both backends.
+/// Indexed storage writes are mirrored to the target only for the keys that were already copied,
+/// and the other keys are recorded as pending (see
+/// [`crate::storage::indexed::migrating::MigratingIndexedStorage`]). The source stays up to date
+/// after the cut over, so a shard reassigned to an executor that did not cut it over yet still
+/// finds its data there.
+///
+/// The cut over shards and the indexed storage keys still to be copied are persisted in the
+/// [`KeyValueStorageNamespace::StorageMigration`] namespace, shared by the executors of the
+/// cluster, so they survive restarts and shard reassignments.
+#[derive(Debug)]
+pub struct StorageMigrationState {
+    key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    /// Number of shards in the cluster, or zero if the executor did not get its assignment yet
+    number_of_shards: AtomicUsize,
+    cut_over_shards: RwLock<HashSet<ShardId>>,
+    /// Per shard lock, held for reading by every write to the shard, and for writing while the
+    /// final pass of the shard's cut over runs, so no write can slip in between the last copy and
+    /// switching the shard over.
+    shard_locks: Mutex<HashMap<ShardId, Arc<AsyncRwLock<()>>>>,
+}
+
+/// Persisted sets of indexed storage keys, see
+/// [`crate::storage::indexed::migrating::MigratingIndexedStorage`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigratedKeySet {
+    /// Keys copied to the target and mirrored by every write since
+    Synced,
+    /// Keys written only to the source since they were last copied
+    Pending,
+}
+
+impl MigratedKeySet {
+    fn key(&self) -> &'static str {
+        match self {
+            MigratedKeySet::Synced => "synced_keys",
+            MigratedKeySet::Pending => "pending_keys",
+        }
+    }
+}
+
+impl StorageMigrationState {
+    pub fn new(key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>) -> Self {
+        Self {
+            key_value_storage,
+            number_of_shards: AtomicUsize::new(0),
+            cut_over_shards: RwLock::new(HashSet::new()),
+            shard_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_number_of_shards(&self, number_of_shards: usize) {
+        self.number_of_shards
+            .store(number_of_shards, Ordering::Release);
+    }
+
+    pub fn shard_of(&self, agent_id: &AgentId) -> Option<ShardId> {
+        match self.number_of_shards.load(Ordering::Acquire) {
+            0 => None,
+            number_of_shards => Some(ShardId::from_agent_id(agent_id, number_of_shards)),
+        }
+    }
+
+    /// Whether the reads of the given agent are served from the target backend
+    pub fn is_agent_cut_over(&self, agent_id: &AgentId) -> bool {
+        self.shard_of(agent_id)
+            .is_some_and(|shard_id| self.is_cut_over(&shard_id))
+    }
+
+    pub fn is_cut_over(&self, shard_id: &ShardId) -> bool {
+        self.cut_over_shards.read().unwrap().contains(shard_id)
+    }
+
+    pub fn cut_over_shards(&self) -> HashSet<ShardId> {
+        self.cut_over_shards.read().unwrap().clone()
+    }
+
+    /// Guard to be held while a write of the given agent is applied to both backends. Returns
+    /// `None` before the executor knows its shards, as no shard can be cut over until then.
+    pub async fn write_guard(&self, agent_id: &AgentId) -> Option<OwnedRwLockReadGuard<()>> {
+        match self.shard_of(agent_id) {
+            Some(shard_id) => Some(self.shard_write_guard(shard_id).await),
+            None => None,
+        }
+    }
+
+    /// Guard to be held while a write to the given shard is applied to both backends
+    pub async fn shard_write_guard(&self, shard_id: ShardId) -> OwnedRwLockReadGuard<()> {
+        self.shard_lock(shard_id).read_owned().await
+    }
+
+    /// Guard blocking the writes to the shard while the final pass of its cut over is running
+    pub async fn cut_over_guard(&self, shard_id: ShardId) -> OwnedRwLockWriteGuard<()> {
+        self.shard_lock(shard_id).write_owned().await
+    }
+
+    fn shard_lock(&self, shard_id: ShardId) -> Arc<AsyncRwLock<()>> {
+        self.shard_locks
+            .lock()
+            .unwrap()
+            .entry(shard_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Switches the reads of the shard to the target backend, and records that its copy has been
+    /// completed. Must be called while holding the guard returned by [`Self::cut_over_guard`].
+    pub async fn mark_cut_over(&self, shard_id: ShardId) -> Result<(), String> {
+        self.key_value_storage
+            .add_to_set(
+                SVC_NAME,
+                "mark_cut_over",
+                "shard_id",
+                KeyValueStorageNamespace::StorageMigration,
+                CUT_OVER_SHARDS_KEY,
+                shard_id.value().to_string().as_bytes(),
+            )
+            .await?;
+        self.cut_over_shards.write().unwrap().insert(shard_id);
+        Ok(())
+    }
+
+    /// The shards cut over by any executor of the cluster. Their copy has been completed, so
+    /// cutting them over again only requires copying the keys written since.
+    pub async fn persisted_cut_over_shards(&self) -> Result<HashSet<ShardId>, String> {
+        let members = self
+            .key_value_storage
+            .members_of_set(
+                SVC_NAME,
+                "persisted_cut_over_shards",
+                "shard_id",
+                KeyValueStorageNamespace::StorageMigration,
+                CUT_OVER_SHARDS_KEY,
+            )
+            .await?;
+        members
+            .into_iter()
+            .map(|member| {
+                std::str::from_utf8(&member)
+                    .ok()
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .map(ShardId::new)
+                    .ok_or_else(|| "Invalid persisted cut over shard".to_string())
+            })
+            .collect()
+    }
+
+    /// Forgets the cut over of shards no longer assigned to this executor. While a shard is owned
+    /// by another executor that did not cut it over yet, the keys it writes only to the source
+    /// are recorded as pending, so they have to be copied again if it gets assigned back.
+    pub fn retain_cut_over(&self, assigned_shards: &HashSet<ShardId>) {
+        self.cut_over_shards
+            .write()
+            .unwrap()
+            .retain(|shard_id| assigned_shards.contains(shard_id));
+    }
+
+    pub async fn persisted_keys(&self, set: MigratedKeySet) -> Result<Vec<String>, String> {
+        let members = self
+            .key_value_storage
+            .members_of_set(
+                SVC_NAME,
+                "persisted_keys",
+                "migrated_key",
+                KeyValueStorageNamespace::StorageMigration,
+                set.key(),
+            )
+            .await?;
+        members
+            .into_iter()
+            .map(|member| {
+                String::from_utf8(member.to_vec())
+                    .map_err(|_| "Invalid persisted migrated key".to_string())
+            })
+            .collect()
+    }
+
+    pub async fn persist_key(&self, set: MigratedKeySet, key: &str) -> Result<(), String> {
+        self.key_value_storage
+            .add_to_set(
+                SVC_NAME,
+                "persist_key",
+                "migrated_key",
+                KeyValueStorageNamespace::StorageMigration,
+                set.key(),
+                key.as_bytes(),
+            )
+            .await
+    }
+
+    pub async fn forget_key(&self, set: MigratedKeySet, key: &str) -> Result<(), String> {
+        self.key_value_storage
+            .remove_from_set(
+                SVC_NAME,
+                "forget_key",
+                "migrated_key",
+                KeyValueStorageNamespace::StorageMigration,
+                set.key(),
+                key.as_bytes(),
+            )
+            .await
+    }
+}
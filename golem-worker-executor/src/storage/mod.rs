@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod blob;
+pub mod encryption;
 pub mod indexed;
 pub mod keyvalue;
+pub mod migration;
 pub mod scheduler;
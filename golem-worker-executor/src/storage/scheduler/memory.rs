@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{ClaimedScheduledAction, SchedulerStorage, datetime_to_millis, millis_to_datetime};
+use super::{
+    ClaimedScheduledAction, ScheduledActionEntry, SchedulerStorage, datetime_to_millis,
+    millis_to_datetime,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use golem_common::model::{ScheduleId, ScheduledAction, ShardAssignment, ShardId};
@@ -146,4 +149,20 @@ impl SchedulerStorage for InMemorySchedulerStorage {
             Ok(false)
         }
     }
+
+    async fn list(&self, shard_id: ShardId) -> Result<Vec<ScheduledActionEntry>, String> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .iter()
+            .filter(|(_, entry)| entry.shard_id == shard_id)
+            .map(|(id, entry)| {
+                Ok(ScheduledActionEntry {
+                    schedule_id: ScheduleId { id: *id },
+                    due_at: millis_to_datetime(entry.due_at_ms)?,
+                    shard_id: entry.shard_id,
+                    action: entry.action.clone(),
+                })
+            })
+            .collect()
+    }
 }
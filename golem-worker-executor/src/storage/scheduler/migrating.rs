@@ -0,0 +1,222 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ClaimedScheduledAction, ScheduledActionEntry, SchedulerStorage};
+use crate::storage::migration::StorageMigrationState;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::model::{ScheduleId, ScheduledAction, ShardAssignment, ShardId};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Scheduler storage used while migrating to a new scheduler storage backend.
+///
+/// Scheduled actions are inserted into and removed from both backends. The actions of a shard are
+/// claimed from the source until the shard is cut over, and from the target afterwards. Inserts
+/// and claims hold the write guard of their shards, while cancellations, which do not know the
+/// shard of the action, are checked by the verification of the cut over instead. Leases are not
+/// copied, so an action claimed from the source right before its shard is cut over may be claimed
+/// again from the target; like after an expired lease, scheduled actions are delivered at least
+/// once.
+#[derive(Debug)]
+pub struct MigratingSchedulerStorage {
+    source: Arc<dyn SchedulerStorage + Send + Sync>,
+    target: Arc<dyn SchedulerStorage + Send + Sync>,
+    state: Arc<StorageMigrationState>,
+}
+
+impl MigratingSchedulerStorage {
+    pub fn new(
+        source: Arc<dyn SchedulerStorage + Send + Sync>,
+        target: Arc<dyn SchedulerStorage + Send + Sync>,
+        state: Arc<StorageMigrationState>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            state,
+        }
+    }
+
+    /// Copies the scheduled actions of a shard to the target, removes the ones that no longer
+    /// exist in the source, and verifies that both backends hold the same actions. An action
+    /// cancelled while copying makes the verification fail, and the cut over has to be retried.
+    ///
+    /// Must be called while holding the guard of [`StorageMigrationState::cut_over_guard`].
+    pub async fn cut_over_shard(&self, shard_id: ShardId) -> Result<(), String> {
+        let source_entries = self.source.list(shard_id).await?;
+        for entry in &source_entries {
+            self.target
+                .insert(
+                    entry.schedule_id,
+                    entry.due_at,
+                    entry.shard_id,
+                    &entry.action,
+                )
+                .await?;
+        }
+
+        let source_ids = self.schedule_ids(&self.source, shard_id).await?;
+        for entry in self.target.list(shard_id).await? {
+            if !source_ids.contains(&entry.schedule_id) {
+                self.target.cancel(&entry.schedule_id).await?;
+            }
+        }
+
+        let source_ids = self.schedule_ids(&self.source, shard_id).await?;
+        let target_ids = self.schedule_ids(&self.target, shard_id).await?;
+        if target_ids == source_ids {
+            Ok(())
+        } else {
+            Err(format!(
+                "Scheduled actions of shard {shard_id} differ after copying ({} in the source, {} in the target)",
+                source_ids.len(),
+                target_ids.len()
+            ))
+        }
+    }
+
+    async fn schedule_ids(
+        &self,
+        storage: &Arc<dyn SchedulerStorage + Send + Sync>,
+        shard_id: ShardId,
+    ) -> Result<HashSet<ScheduleId>, String> {
+        Ok(storage
+            .list(shard_id)
+            .await?
+            .into_iter()
+            .map(|entry| entry.schedule_id)
+            .collect())
+    }
+
+    fn primary(&self, shard_id: &ShardId) -> &Arc<dyn SchedulerStorage + Send + Sync> {
+        if self.state.is_cut_over(shard_id) {
+            &self.target
+        } else {
+            &self.source
+        }
+    }
+}
+
+#[async_trait]
+impl SchedulerStorage for MigratingSchedulerStorage {
+    async fn insert(
+        &self,
+        schedule_id: ScheduleId,
+        due_at: DateTime<Utc>,
+        shard_id: ShardId,
+        action: &ScheduledAction,
+    ) -> Result<(), String> {
+        let _guard = self.state.shard_write_guard(shard_id).await;
+        self.source
+            .insert(schedule_id, due_at, shard_id, action)
+            .await?;
+        self.target
+            .insert(schedule_id, due_at, shard_id, action)
+            .await
+    }
+
+    async fn cancel(&self, schedule_id: &ScheduleId) -> Result<(), String> {
+        self.source.cancel(schedule_id).await?;
+        self.target.cancel(schedule_id).await
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        assignment: &ShardAssignment,
+        limit: u32,
+        lease_ttl: Duration,
+    ) -> Result<Vec<ClaimedScheduledAction>, String> {
+        let mut shard_ids = assignment.shard_ids.iter().copied().collect::<Vec<_>>();
+        shard_ids.sort();
+        let mut guards = Vec::with_capacity(shard_ids.len());
+        for shard_id in shard_ids {
+            guards.push(self.state.shard_write_guard(shard_id).await);
+        }
+
+        let (cut_over, not_cut_over): (HashSet<ShardId>, HashSet<ShardId>) = assignment
+            .shard_ids
+            .iter()
+            .copied()
+            .partition(|shard_id| self.state.is_cut_over(shard_id));
+
+        let mut claimed = self
+            .source
+            .claim_due(
+                now,
+                &ShardAssignment {
+                    number_of_shards: assignment.number_of_shards,
+                    shard_ids: not_cut_over,
+                },
+                limit,
+                lease_ttl,
+            )
+            .await?;
+        let remaining = limit.saturating_sub(claimed.len() as u32);
+        if remaining > 0 {
+            claimed.extend(
+                self.target
+                    .claim_due(
+                        now,
+                        &ShardAssignment {
+                            number_of_shards: assignment.number_of_shards,
+                            shard_ids: cut_over,
+                        },
+                        remaining,
+                        lease_ttl,
+                    )
+                    .await?,
+            );
+        }
+        Ok(claimed)
+    }
+
+    async fn extend_lease(
+        &self,
+        schedule_id: &ScheduleId,
+        lease_owner: Uuid,
+        lease_until: DateTime<Utc>,
+    ) -> Result<bool, String> {
+        // The lease is only known by the backend the action was claimed from
+        if self
+            .source
+            .extend_lease(schedule_id, lease_owner, lease_until)
+            .await?
+        {
+            Ok(true)
+        } else {
+            self.target
+                .extend_lease(schedule_id, lease_owner, lease_until)
+                .await
+        }
+    }
+
+    async fn ack(&self, schedule_id: &ScheduleId, lease_owner: Uuid) -> Result<bool, String> {
+        let acked = self.source.ack(schedule_id, lease_owner).await?
+            || self.target.ack(schedule_id, lease_owner).await?;
+        if acked {
+            // Removing the copy of the action that was not claimed
+            self.source.cancel(schedule_id).await?;
+            self.target.cancel(schedule_id).await?;
+        }
+        Ok(acked)
+    }
+
+    async fn list(&self, shard_id: ShardId) -> Result<Vec<ScheduledActionEntry>, String> {
+        self.primary(&shard_id).list(shard_id).await
+    }
+}
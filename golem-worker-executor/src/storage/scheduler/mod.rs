@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod memory;
+pub mod migrating;
 pub mod postgres;
 pub mod sqlite;
 
@@ -32,6 +33,15 @@ pub struct ClaimedScheduledAction {
     pub attempt_count: u32,
 }
 
+/// A scheduled action as stored, independently of its lease
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledActionEntry {
+    pub schedule_id: ScheduleId,
+    pub due_at: DateTime<Utc>,
+    pub shard_id: ShardId,
+    pub action: ScheduledAction,
+}
+
 #[async_trait]
 pub trait SchedulerStorage: Debug {
     async fn insert(
@@ -60,6 +70,9 @@ pub trait SchedulerStorage: Debug {
     ) -> Result<bool, String>;
 
     async fn ack(&self, schedule_id: &ScheduleId, lease_owner: Uuid) -> Result<bool, String>;
+
+    /// Lists every scheduled action of the given shard, including the currently leased ones
+    async fn list(&self, shard_id: ShardId) -> Result<Vec<ScheduledActionEntry>, String>;
 }
 
 pub fn datetime_to_millis(time: DateTime<Utc>) -> i64 {
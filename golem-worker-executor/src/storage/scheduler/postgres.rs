@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{ClaimedScheduledAction, SchedulerStorage, datetime_to_millis, millis_to_datetime};
+use super::{
+    ClaimedScheduledAction, ScheduledActionEntry, SchedulerStorage, datetime_to_millis,
+    millis_to_datetime,
+};
 use crate::services::golem_config::SchedulerStoragePostgresConfig;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -41,6 +44,13 @@ struct ScheduledActionRow {
     attempt_count: i32,
 }
 
+#[derive(sqlx::FromRow)]
+struct ScheduledActionEntryRow {
+    schedule_id: Uuid,
+    due_at_ms: i64,
+    action: Vec<u8>,
+}
+
 impl PostgresSchedulerStorage {
     pub async fn configured(config: &SchedulerStoragePostgresConfig) -> Result<Self, String> {
         let migrations = IncludedMigrationsDir::new(&DB_MIGRATIONS);
@@ -209,4 +219,31 @@ impl SchedulerStorage for PostgresSchedulerStorage {
             .map(|result| result.rows_affected() == 1)
             .map_err(|err| err.to_safe_string())
     }
+
+    async fn list(&self, shard_id: ShardId) -> Result<Vec<ScheduledActionEntry>, String> {
+        let query = sqlx::query_as::<_, ScheduledActionEntryRow>(
+            "SELECT schedule_id, due_at_ms, action FROM scheduled_actions WHERE shard_id = $1 ORDER BY due_at_ms ASC, schedule_id ASC;",
+        )
+        .bind(shard_id.value());
+
+        let rows = self
+            .pool
+            .with_ro("scheduler_storage", "list")
+            .fetch_all_as::<ScheduledActionEntryRow, _>(query)
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ScheduledActionEntry {
+                    schedule_id: ScheduleId {
+                        id: row.schedule_id,
+                    },
+                    due_at: millis_to_datetime(row.due_at_ms)?,
+                    shard_id,
+                    action: deserialize(&row.action)?,
+                })
+            })
+            .collect()
+    }
 }
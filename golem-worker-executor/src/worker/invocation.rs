@@ -23,11 +23,13 @@ use golem_common::model::agent::{
     DataSchema, ElementSchema, NamedElementSchema, UntypedElementValue,
 };
 use golem_common::model::component_metadata::ComponentMetadata;
+use golem_common::model::invocation_context::InvocationContextSpan;
 use golem_common::model::oplog::AgentError as OplogAgentError;
 use golem_common::model::oplog::RawSnapshotData;
 use golem_common::model::parsed_function_name::{ParsedFunctionName, ParsedFunctionReference};
 use golem_common::model::{
-    AgentInvocation, AgentInvocationKind, AgentInvocationResult, OplogIndex,
+    AgentInvocation, AgentInvocationKind, AgentInvocationResult, Interception, InterceptionOutcome,
+    OplogIndex, Timestamp,
 };
 use golem_service_base::error::worker_executor::{InterruptKind, WorkerExecutorError};
 
@@ -43,6 +45,8 @@ use golem_wasm::analysis::AnalysedType;
 use golem_wasm::validate_value_matches_type;
 use golem_wasm::wasmtime::{DecodeParamResult, decode_param, encode_output};
 use golem_wasm::{FromValue, IntoValue, Value};
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::{Instrument, Level, debug, span};
 use wasmtime::component::{Func, Val};
 use wasmtime::{AsContextMut, StoreContextMut};
@@ -215,6 +219,14 @@ async fn invoke_observed<Ctx: WorkerCtx>(
         store.data_mut().enter_read_only_mode(method_name.clone());
     }
 
+    // The invocation deadline is polled from the epoch deadline callback while guest code runs,
+    // and interrupts host calls waiting on the interrupt signal once it passes.
+    if let Some(deadline) = lowered.deadline {
+        store
+            .data_mut()
+            .arm_invocation_deadline(lowered.display_name.clone(), deadline);
+    }
+
     let kind = lowered.kind;
     let call_future = async {
         match function {
@@ -242,12 +254,33 @@ async fn invoke_observed<Ctx: WorkerCtx>(
     if read_only_method.is_some() {
         store.data_mut().exit_read_only_mode();
     }
+    let deadline_exceeded = if lowered.deadline.is_some() {
+        let error = store.data().check_invocation_deadline();
+        store.data_mut().disarm_invocation_deadline();
+        error
+    } else {
+        None
+    };
 
     let call_result = match call_outcome {
         Ok(result) => result,
         Err(payload) => std::panic::resume_unwind(payload),
     };
 
+    // A host call interrupted by the deadline traps with an interrupt; report it as the
+    // deadline failure instead, which is not retried.
+    let call_result = match (call_result, deadline_exceeded) {
+        (Ok(InvokeResult::Interrupted { consumed_fuel, .. }), Some(error)) => {
+            Ok(InvokeResult::Failed {
+                consumed_fuel,
+                error,
+                retry_from: store.data().get_current_retry_point().await,
+                semantic_trap_retry_override: None,
+            })
+        }
+        (call_result, _) => call_result,
+    };
+
     store.data().set_suspended();
 
     call_result
@@ -394,6 +427,7 @@ async fn invoke<Ctx: WorkerCtx>(
 /// - `SaveSnapshot` → `raw-snapshot-data` (record)
 /// - `LoadSnapshot` → `result<_, string>`
 /// - `ProcessOplogEntries` → `result<_, string>`
+/// - `InterceptInvocation` → `result<interception-result, string>`
 /// - `ManualUpdate` → no return value
 fn analysed_result_type_for_kind(kind: AgentInvocationKind) -> Option<AnalysedType> {
     use golem_wasm::analysis::analysed_type::{result, result_err, str};
@@ -409,6 +443,9 @@ fn analysed_result_type_for_kind(kind: AgentInvocationKind) -> Option<AnalysedTy
         AgentInvocationKind::SaveSnapshot => Some(RawSnapshotData::get_type()),
         AgentInvocationKind::LoadSnapshot => Some(result_err(str())),
         AgentInvocationKind::ProcessOplogEntries => Some(result_err(str())),
+        AgentInvocationKind::InterceptInvocation => {
+            Some(result(InterceptionOutcome::get_type(), str()))
+        }
         AgentInvocationKind::ManualUpdate => None,
     }
 }
@@ -609,6 +646,31 @@ pub struct LoweredInvocation {
     /// read-only invocation strictness mode for the duration of the call, trapping
     /// outgoing HTTP / RPC host calls with `AgentError::ReadOnlyViolation`.
     pub read_only_method: Option<String>,
+    /// The declared `timeout` of the targeted `AgentMethod`, if any. Combined with the
+    /// deadline propagated in the invocation context to arm the invocation deadline.
+    pub method_timeout: Option<Duration>,
+    /// Absolute deadline of a live invocation, computed by the invocation loop with
+    /// [`invocation_deadline`]. Always `None` in replay mode.
+    pub deadline: Option<Timestamp>,
+}
+
+/// Computes the deadline of a live invocation: the earlier of the method's declared timeout,
+/// counted from now, and the deadline propagated in the invocation context.
+pub fn invocation_deadline(
+    invocation_span: &Arc<InvocationContextSpan>,
+    method_timeout: Option<Duration>,
+) -> Option<Timestamp> {
+    let from_timeout = method_timeout.map(|timeout| {
+        Timestamp::from(
+            Timestamp::now_utc()
+                .to_millis()
+                .saturating_add(timeout.as_millis() as u64),
+        )
+    });
+    match (from_timeout, invocation_span.effective_deadline()) {
+        (Some(from_timeout), Some(propagated)) => Some(from_timeout.min(propagated)),
+        (from_timeout, propagated) => from_timeout.or(propagated),
+    }
 }
 
 pub fn lower_invocation(
@@ -641,6 +703,8 @@ pub fn lower_invocation(
                     principal.into_value(),
                 ],
                 read_only_method: None,
+                method_timeout: None,
+                deadline: None,
             })
         }
         AgentInvocation::AgentMethod {
@@ -650,6 +714,7 @@ pub fn lower_invocation(
             ..
         } => {
             let mut read_only_method: Option<String> = None;
+            let mut method_timeout: Option<Duration> = None;
             if let Some(agent_id) = agent_id {
                 let agent_type = component_metadata
                     .find_agent_type_by_name(&agent_id.agent_type)
@@ -672,6 +737,7 @@ pub fn lower_invocation(
                         if method.read_only.is_some() {
                             read_only_method = Some(method_name.clone());
                         }
+                        method_timeout = method.timeout_nanos.map(Duration::from_nanos);
                     }
                 }
             }
@@ -693,6 +759,8 @@ pub fn lower_invocation(
                     principal.into_value(),
                 ],
                 read_only_method,
+                method_timeout,
+                deadline: None,
             })
         }
         AgentInvocation::ManualUpdate { .. } => Err(WorkerExecutorError::invalid_request(
@@ -712,6 +780,8 @@ pub fn lower_invocation(
                 display_name: "save-snapshot".to_string(),
                 params: vec![],
                 read_only_method: None,
+                method_timeout: None,
+                deadline: None,
             })
         }
         AgentInvocation::LoadSnapshot { snapshot, .. } => {
@@ -728,6 +798,8 @@ pub fn lower_invocation(
                 display_name: "load-snapshot".to_string(),
                 params: vec![snapshot.into_value()],
                 read_only_method: None,
+                method_timeout: None,
+                deadline: None,
             })
         }
         AgentInvocation::ProcessOplogEntries {
@@ -778,6 +850,62 @@ pub fn lower_invocation(
                     val_entries,
                 ],
                 read_only_method: None,
+                method_timeout: None,
+                deadline: None,
+            })
+        }
+        AgentInvocation::InterceptInvocation {
+            account_id,
+            config,
+            intercepted,
+            output,
+            ..
+        } => {
+            let wit_fqfn = match output {
+                None => component_metadata.invocation_interceptor_before_function_name(),
+                Some(_) => component_metadata.invocation_interceptor_after_function_name(),
+            }
+            .ok_or_else(|| {
+                WorkerExecutorError::invalid_request(
+                    "invocation interceptor functions not found in component".to_string(),
+                )
+            })?;
+
+            let string_pairs = |pairs: Vec<(String, String)>| {
+                Value::List(
+                    pairs
+                        .into_iter()
+                        .map(|(key, value)| {
+                            Value::Tuple(vec![key.into_value(), value.into_value()])
+                        })
+                        .collect(),
+                )
+            };
+
+            let val_invocation = Value::Record(vec![
+                Value::Record(vec![account_id.into_value()]),
+                string_pairs(config),
+                intercepted.agent_id.into_value(),
+                Value::String(intercepted.method_name),
+                intercepted.input.into_value(),
+                intercepted.principal.into_value(),
+                Value::String(intercepted.trace_id),
+                string_pairs(intercepted.context_attributes),
+            ]);
+
+            let mut params = vec![val_invocation];
+            if let Some(output) = output {
+                params.push(output.into_value());
+            }
+
+            Ok(LoweredInvocation {
+                kind,
+                wit_fqfn,
+                display_name: "intercept-invocation".to_string(),
+                params,
+                read_only_method: None,
+                method_timeout: None,
+                deadline: None,
             })
         }
     }
@@ -897,6 +1025,32 @@ fn wrap_output_as_agent_result(
                 result: AgentInvocationResult::ProcessOplogEntries { error },
             })
         }
+        AgentInvocationKind::InterceptInvocation => {
+            let outcome = match output {
+                Some(value) => {
+                    let result: Result<InterceptionOutcome, String> = FromValue::from_value(value)
+                        .map_err(|e| {
+                            WorkerExecutorError::runtime(format!(
+                                "Failed to decode result from invocation interceptor: {e}"
+                            ))
+                        })?;
+                    // A failing interceptor is treated as rejecting the invocation
+                    result.unwrap_or_else(|error| InterceptionOutcome {
+                        interception: Interception::Reject(error),
+                        span_attributes: Vec::new(),
+                    })
+                }
+                None => {
+                    return Err(WorkerExecutorError::runtime(
+                        "Unexpected empty result from invocation interceptor",
+                    ));
+                }
+            };
+            Ok(InvokeResult::Succeeded {
+                consumed_fuel,
+                result: AgentInvocationResult::InterceptInvocation { outcome },
+            })
+        }
     }
 }
 
@@ -1038,6 +1192,10 @@ mod tests {
             analysed_result_type_for_kind(AgentInvocationKind::ProcessOplogEntries),
             Some(result_err(str()))
         );
+        assert_eq!(
+            analysed_result_type_for_kind(AgentInvocationKind::InterceptInvocation),
+            Some(result(InterceptionOutcome::get_type(), str()))
+        );
         assert_eq!(
             analysed_result_type_for_kind(AgentInvocationKind::ManualUpdate),
             None
@@ -1116,4 +1274,28 @@ mod tests {
             other => panic!("unexpected invoke result: {other:?}"),
         }
     }
+
+    #[test]
+    fn wrap_output_treats_failing_interceptor_as_rejection() {
+        let result = wrap_output_as_agent_result(
+            AgentInvocationKind::InterceptInvocation,
+            Some(Err::<InterceptionOutcome, _>("not allowed".to_string()).into_value()),
+            3,
+        )
+        .unwrap();
+
+        match result {
+            InvokeResult::Succeeded {
+                result: AgentInvocationResult::InterceptInvocation { outcome },
+                ..
+            } => {
+                assert_eq!(
+                    outcome.interception,
+                    Interception::Reject("not allowed".to_string())
+                );
+                assert!(outcome.span_attributes.is_empty());
+            }
+            other => panic!("unexpected invoke result: {other:?}"),
+        }
+    }
 }
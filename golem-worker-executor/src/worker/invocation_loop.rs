@@ -16,9 +16,9 @@ use crate::model::{ReadFileResult, TrapType};
 use crate::services::events::Event;
 use crate::services::golem_config::SnapshotPolicy;
 use crate::services::oplog::{CommitLevel, OplogOps};
-use crate::services::{HasEvents, HasOplog, HasWorker};
+use crate::services::{HasEvents, HasInvocationInterceptorPlugin, HasOplog, HasWorker};
 use crate::worker::invocation::{
-    InvocationMode, InvokeResult, invoke_observed_and_traced, lower_invocation,
+    InvocationMode, InvokeResult, invocation_deadline, invoke_observed_and_traced, lower_invocation,
 };
 use crate::worker::status_checkpointer;
 use crate::worker::{
@@ -30,16 +30,16 @@ use async_lock::Mutex;
 use drop_stream::DropStream;
 use futures::channel::oneshot;
 use futures::channel::oneshot::Sender;
-use golem_common::model::agent::{AgentMode, LegacyParsedAgentId};
-use golem_common::model::component::{CanonicalFilePath, ComponentRevision};
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, UntypedDataValue};
+use golem_common::model::component::{CanonicalFilePath, ComponentRevision, InstalledPlugin};
 use golem_common::model::oplog::{AgentError, OplogEntry};
 use golem_common::model::{
     AgentId, AgentInvocation, AgentInvocationKind, AgentInvocationOutput, AgentInvocationResult,
-    IdempotencyKey, OwnedAgentId, TimestampedAgentInvocation,
+    IdempotencyKey, InterceptedInvocation, Interception, OwnedAgentId, TimestampedAgentInvocation,
 };
 use golem_common::model::{
     AgentStatusRecord, OplogIndex, Timestamp,
-    invocation_context::{AttributeValue, InvocationContextStack},
+    invocation_context::{AttributeValue, DEADLINE_ATTRIBUTE, InvocationContextStack},
 };
 use golem_common::retries::get_delay;
 use golem_service_base::error::worker_executor::{InterruptKind, WorkerExecutorError};
@@ -885,14 +885,46 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
     /// The inner implementation of `invoke_agent` to be instrumented with a span.
     async fn invoke_agent_inner(
         &mut self,
-        invocation_context: InvocationContextStack,
+        mut invocation_context: InvocationContextStack,
         idempotency_key: IdempotencyKey,
         invocation: AgentInvocation,
     ) -> CommandOutcome {
         let kind = invocation.kind();
         let display_name = invocation.display_name();
+
+        self.store
+            .data_mut()
+            .set_current_idempotency_key(idempotency_key.clone())
+            .await;
+
+        Self::extend_invocation_context(
+            &mut invocation_context,
+            &idempotency_key,
+            &invocation,
+            &self.owned_agent_id.agent_id(),
+            &self.parent.parsed_agent_id,
+        );
+
+        let (invocation, interception) = match self
+            .intercept_invocation_start(&invocation_context, &idempotency_key, invocation)
+            .instrument(span!(Level::INFO, "intercept_invocation_start"))
+            .await
+        {
+            Ok(intercepted) => intercepted,
+            Err(refusal) => {
+                return self
+                    .agent_invocation_refused(&display_name, &idempotency_key, refusal)
+                    .await;
+            }
+        };
+
         let result = self
-            .invoke_agent_with_context(invocation_context, idempotency_key, invocation)
+            .invoke_agent_with_context(
+                invocation_context,
+                idempotency_key,
+                invocation,
+                interception,
+            )
             .await;
 
         match result {
@@ -911,27 +943,16 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
     /// invocation.
     async fn invoke_agent_with_context(
         &mut self,
-        mut invocation_context: InvocationContextStack,
+        invocation_context: InvocationContextStack,
         idempotency_key: IdempotencyKey,
         invocation: AgentInvocation,
+        interception: Option<(Vec<InstalledPlugin>, InterceptedInvocation)>,
     ) -> Result<InvokeResult, WorkerExecutorError> {
-        let (lowered, local_span_ids, inherited_span_ids) = async {
-            self.store
-                .data_mut()
-                .set_current_idempotency_key(idempotency_key.clone())
-                .await;
-
+        let (invocation, lowered, local_span_ids, inherited_span_ids) = async {
             let component_metadata = self.store.data().component_metadata().metadata.clone();
 
-            Self::extend_invocation_context(
-                &mut invocation_context,
-                &idempotency_key,
-                &invocation,
-                &self.owned_agent_id.agent_id(),
-                &self.parent.parsed_agent_id,
-            );
-
             let (local_span_ids, inherited_span_ids) = invocation_context.span_ids();
+            let invocation_span = invocation_context.spans.first().clone();
             self.store
                 .data_mut()
                 .set_current_invocation_context(invocation_context)
@@ -947,13 +968,23 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
             }
 
             let invocation_for_lowering = invocation.clone();
-            let lowered = lower_invocation(
+            let mut lowered = lower_invocation(
                 invocation_for_lowering,
                 &component_metadata,
                 self.parent.parsed_agent_id.as_ref(),
             )?;
 
-            Ok::<_, WorkerExecutorError>((lowered, local_span_ids, inherited_span_ids))
+            // Recording the effective deadline on the invocation span lets every
+            // agent-to-agent RPC made by this invocation inherit it.
+            lowered.deadline = invocation_deadline(&invocation_span, lowered.method_timeout);
+            if let Some(deadline) = lowered.deadline {
+                invocation_span.set_attribute(
+                    DEADLINE_ATTRIBUTE.to_string(),
+                    AttributeValue::String(deadline.to_string()),
+                );
+            }
+
+            Ok::<_, WorkerExecutorError>((invocation, lowered, local_span_ids, inherited_span_ids))
         }
         .instrument(span!(Level::INFO, "prepare_invocation_context"))
         .await?;
@@ -966,6 +997,36 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         )
         .await;
 
+        let result = match (result, interception) {
+            (
+                Ok(InvokeResult::Succeeded {
+                    result: AgentInvocationResult::AgentMethod { output },
+                    consumed_fuel,
+                }),
+                Some((interceptors, intercepted)),
+            ) => {
+                let replaced = self
+                    .intercept_invocation_end(
+                        &interceptors,
+                        &idempotency_key,
+                        intercepted,
+                        output.clone(),
+                    )
+                    .await;
+                if replaced != output {
+                    self.store
+                        .data_mut()
+                        .on_agent_invocation_output_replaced(&output, &replaced)
+                        .await?;
+                }
+                Ok(InvokeResult::Succeeded {
+                    result: AgentInvocationResult::AgentMethod { output: replaced },
+                    consumed_fuel,
+                })
+            }
+            (result, _) => result,
+        };
+
         // We are removing the spans introduced by the invocation. Not calling `finish_span` here,
         // as it would add FinishSpan oplog entries without corresponding StartSpan ones. Instead,
         // the oplog processor should assume that spans implicitly created by AgentInvocationStarted
@@ -980,6 +1041,158 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         result
     }
 
+    /// Passes an agent method invocation to the invocation interceptor plugins active on the
+    /// agent before it starts, in the order of their priorities.
+    ///
+    /// Each interceptor sees the input as replaced by the previous ones, and the final input is
+    /// the one recorded in the oplog. Span attributes returned by the interceptors are added to
+    /// the invocation span. If the invocation is intercepted, the interceptors and the intercepted
+    /// invocation are returned so they can be passed to `intercept_invocation_end`.
+    ///
+    /// Fails with an [`InvocationRefusal`] if an interceptor rejects the invocation or cannot be
+    /// reached, in which case the invocation must not start.
+    async fn intercept_invocation_start(
+        &self,
+        invocation_context: &InvocationContextStack,
+        idempotency_key: &IdempotencyKey,
+        mut invocation: AgentInvocation,
+    ) -> Result<
+        (
+            AgentInvocation,
+            Option<(Vec<InstalledPlugin>, InterceptedInvocation)>,
+        ),
+        InvocationRefusal,
+    > {
+        let AgentInvocation::AgentMethod {
+            method_name,
+            input,
+            principal,
+            ..
+        } = &mut invocation
+        else {
+            return Ok((invocation, None));
+        };
+
+        let interceptors = self.parent.active_invocation_interceptors().await;
+        if interceptors.is_empty() {
+            return Ok((invocation, None));
+        }
+
+        let mut context_attributes = invocation_context
+            .spans
+            .first()
+            .get_attributes(true)
+            .into_iter()
+            .collect::<Vec<_>>();
+        context_attributes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut intercepted = InterceptedInvocation {
+            agent_id: self.owned_agent_id.agent_id(),
+            method_name: method_name.clone(),
+            input: input.clone(),
+            principal: principal.clone(),
+            trace_id: invocation_context.trace_id.to_string(),
+            context_attributes: context_attributes
+                .into_iter()
+                .flat_map(|(key, values)| {
+                    values
+                        .into_iter()
+                        .map(move |value| (key.clone(), value.to_string()))
+                })
+                .collect(),
+        };
+
+        let invocation_interceptor_plugin = self.parent.invocation_interceptor_plugin();
+        for plugin in &interceptors {
+            let outcome = invocation_interceptor_plugin
+                .intercept(
+                    self.owned_agent_id.environment_id,
+                    self.parent.initial_worker_metadata.created_by,
+                    plugin,
+                    idempotency_key,
+                    intercepted.clone(),
+                    None,
+                )
+                .await
+                .map_err(|error| InvocationRefusal::InterceptorUnavailable {
+                    plugin_name: plugin.plugin_name.clone(),
+                    error,
+                })?;
+
+            for (key, value) in outcome.span_attributes {
+                invocation_context
+                    .spans
+                    .first()
+                    .set_attribute(key, AttributeValue::String(value));
+            }
+
+            match outcome.interception {
+                Interception::Proceed => {}
+                Interception::Replace(replaced_input) => {
+                    intercepted.input = replaced_input;
+                }
+                Interception::Reject(reason) => {
+                    return Err(InvocationRefusal::Rejected {
+                        plugin_name: plugin.plugin_name.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        *input = intercepted.input.clone();
+        Ok((invocation, Some((interceptors, intercepted))))
+    }
+
+    /// Passes the output of a successful agent method invocation to the invocation interceptor
+    /// plugins that intercepted its start, returning the output to be recorded.
+    ///
+    /// The method has already run at this point, so failing or rejecting interceptors are only
+    /// logged and the output is kept.
+    async fn intercept_invocation_end(
+        &self,
+        interceptors: &[InstalledPlugin],
+        idempotency_key: &IdempotencyKey,
+        intercepted: InterceptedInvocation,
+        mut output: UntypedDataValue,
+    ) -> UntypedDataValue {
+        let invocation_interceptor_plugin = self.parent.invocation_interceptor_plugin();
+        for plugin in interceptors {
+            match invocation_interceptor_plugin
+                .intercept(
+                    self.owned_agent_id.environment_id,
+                    self.parent.initial_worker_metadata.created_by,
+                    plugin,
+                    idempotency_key,
+                    intercepted.clone(),
+                    Some(output.clone()),
+                )
+                .await
+            {
+                Ok(outcome) => match outcome.interception {
+                    Interception::Proceed => {}
+                    Interception::Replace(replaced_output) => {
+                        output = replaced_output;
+                    }
+                    Interception::Reject(reason) => {
+                        warn!(
+                            plugin = %plugin.plugin_name,
+                            reason = %reason,
+                            "Invocation interceptor cannot reject a finished invocation"
+                        );
+                    }
+                },
+                Err(error) => {
+                    warn!(
+                        plugin = %plugin.plugin_name,
+                        error = %error,
+                        "Invocation interceptor failed to intercept the end of the invocation"
+                    );
+                }
+            }
+        }
+        output
+    }
+
     /// The logic handling a successfully finished agent invocation
     ///
     /// Successful here means that the invocation function returned with
@@ -1064,6 +1277,70 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         CommandOutcome::BreakInnerLoop(decision)
     }
 
+    /// The logic handling an agent invocation refused by an invocation interceptor plugin before
+    /// it started.
+    ///
+    /// Only the refused invocation fails, and the agent's status is left untouched: the pending
+    /// invocation is cancelled and the error is returned to its caller. A rejection is stored as
+    /// the result of the invocation, while an unavailable interceptor is only reported to the
+    /// caller, so the invocation can be retried with the same idempotency key.
+    async fn agent_invocation_refused(
+        &mut self,
+        full_function_name: &str,
+        idempotency_key: &IdempotencyKey,
+        refusal: InvocationRefusal,
+    ) -> CommandOutcome {
+        self.parent
+            .add_and_commit_oplog(OplogEntry::cancel_pending_invocation(
+                idempotency_key.clone(),
+            ))
+            .await;
+
+        match refusal {
+            InvocationRefusal::Rejected {
+                plugin_name,
+                reason,
+            } => {
+                let trap_type = TrapType::Error {
+                    error: AgentError::InvalidRequest(format!(
+                        "Invocation rejected by plugin {plugin_name}: {reason}"
+                    )),
+                    retry_from: OplogIndex::INITIAL,
+                    semantic_trap_retry_override: None,
+                };
+                self.parent
+                    .store_single_invocation_failure(idempotency_key, &trap_type)
+                    .await;
+            }
+            InvocationRefusal::InterceptorUnavailable { plugin_name, error } => {
+                warn!(
+                    plugin = %plugin_name,
+                    error = %error,
+                    "Invocation interceptor failed to intercept the start of the invocation"
+                );
+                self.parent
+                    .report_retriable_invocation_failure(
+                        idempotency_key,
+                        WorkerExecutorError::InvocationFailed {
+                            error: AgentError::TransientError(format!(
+                                "Invocation interceptor plugin {plugin_name} is unavailable: {error}"
+                            )),
+                            stderr: String::new(),
+                        },
+                    )
+                    .await;
+            }
+        }
+
+        self.store
+            .data()
+            .get_public_state()
+            .event_service()
+            .emit_invocation_finished(full_function_name, idempotency_key, true);
+
+        CommandOutcome::Continue
+    }
+
     /// Try to perform the save-snapshot step of a manual update on the worker
     async fn manual_update(&mut self, target_revision: ComponentRevision) -> CommandOutcome {
         let span = span!(
@@ -1396,7 +1673,8 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                         match self.parent.oplog.upload_raw_payload(serialized_bytes).await {
                             Ok(raw_payload) => match raw_payload.into_payload::<Vec<u8>>() {
                                 Ok(payload) => {
-                                    self.parent
+                                    let snapshot_index = self
+                                        .parent
                                         .add_and_commit_oplog(OplogEntry::snapshot(
                                             payload,
                                             snapshot.mime_type,
@@ -1404,6 +1682,17 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                                         .await;
                                     debug!("Periodic snapshot saved successfully");
 
+                                    let durable_ctx = self.store.data().durable_ctx();
+                                    if durable_ctx.checkpoints_filesystem() {
+                                        // Without the checkpoint, recovery falls back to a full
+                                        // replay, so a failure here is not fatal
+                                        if let Err(err) =
+                                            durable_ctx.checkpoint_filesystem(snapshot_index).await
+                                        {
+                                            warn!("Failed to checkpoint filesystem: {err}");
+                                        }
+                                    }
+
                                     // A snapshot is committed between invocations, so no jumpable
                                     // region is open: a clean boundary to checkpoint the status,
                                     // aligning the checkpoint with the snapshot index.
@@ -1412,6 +1701,7 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                                             status_checkpointer::CheckpointReason::Snapshot,
                                         )
                                         .await;
+                                    self.parent.compact_oplog(snapshot_index).await;
                                 }
                                 Err(err) => {
                                     warn!("Failed to convert snapshot payload: {err}");
@@ -1458,6 +1748,17 @@ enum CommandOutcome {
     WaitForWakeup,
 }
 
+/// Why an invocation interceptor plugin prevented an agent method invocation from starting
+enum InvocationRefusal {
+    /// The interceptor rejected the invocation
+    Rejected { plugin_name: String, reason: String },
+    /// The interceptor could not be invoked
+    InterceptorUnavailable {
+        plugin_name: String,
+        error: WorkerExecutorError,
+    },
+}
+
 #[derive(Debug, PartialEq, Eq)]
 enum PeriodicSnapshotAction {
     NotNeeded,
@@ -31,10 +31,11 @@ use crate::services::active_workers::{
     FilesystemStoragePermit, HeldComponentCharge, MemoryGrant, RegisteredConcurrentAccount,
     WorkerComponentCharge,
 };
-use crate::services::events::{Event, EventsSubscription};
+use crate::services::agent_lifecycle::schedule_lifecycle_actions;
+use crate::services::events::{AgentWrite, Event, EventsSubscription};
 use crate::services::golem_config::SnapshotPolicy;
 use crate::services::oplog::plugin::ForwardingOplog;
-use crate::services::oplog::{CommitLevel, Oplog, OplogOps, downcast_oplog};
+use crate::services::oplog::{CommitLevel, MultiLayerOplog, Oplog, OplogOps, downcast_oplog};
 use crate::services::worker::GetWorkerMetadataResult;
 use crate::services::worker_event::{WorkerEventService, WorkerEventServiceDefault};
 use crate::services::{
@@ -64,6 +65,8 @@ use golem_common::model::agent::{
 use golem_common::model::component::CanonicalFilePath;
 use golem_common::model::component::ComponentId;
 use golem_common::model::component::ComponentRevision;
+use golem_common::model::component::InstalledPlugin;
+use golem_common::model::component_metadata::AgentLifecyclePolicy;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::{
     OplogEntry, OplogIndex, TimestampedUpdateDescription, UpdateDescription,
@@ -407,15 +410,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         principal: Principal,
     ) -> Result<Self, WorkerExecutorError> {
         let start = std::time::Instant::now();
-        let GetOrCreateWorkerResult {
-            initial_worker_metadata,
-            current_status,
-            execution_status,
-            agent_id,
-            snapshot_policy,
-            oplog,
-            initial_component,
-        } = match Self::get_or_create_worker_metadata(
+        let result = match Self::get_or_create_worker_metadata(
             deps,
             &owned_agent_id,
             component_revision,
@@ -432,6 +427,98 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
             }
         };
 
+        let worker = Self::from_parts(
+            deps,
+            owned_agent_id,
+            result,
+            false,
+            invocation_context_stack,
+            principal,
+        )
+        .await?;
+        crate::metrics::wasm::record_create_worker(start.elapsed());
+
+        Ok(worker)
+    }
+
+    /// Creates a worker that is not registered in the worker service or the set of active
+    /// workers, on top of an already opened oplog and a precomputed status. Its status is never
+    /// flushed or checkpointed, so dropping it leaves no trace of the worker. Used to replay an
+    /// agent at a past point of its history.
+    pub(crate) async fn new_detached<T: HasAll<Ctx>>(
+        deps: &T,
+        owned_agent_id: OwnedAgentId,
+        initial_worker_metadata: AgentMetadata,
+        status: AgentStatusRecord,
+        oplog: Arc<dyn Oplog>,
+        invocation_context_stack: &InvocationContextStack,
+        principal: Principal,
+    ) -> Result<Self, WorkerExecutorError> {
+        let initial_component = deps
+            .component_service()
+            .get_metadata(
+                owned_agent_id.component_id(),
+                Some(initial_worker_metadata.last_known_status.component_revision),
+            )
+            .await?;
+
+        let agent_id = if initial_component.metadata.is_agent() {
+            let agent_id = LegacyParsedAgentId::parse(
+                &owned_agent_id.agent_id.agent_id,
+                &initial_component.metadata,
+            )
+            .map_err(|err| {
+                WorkerExecutorError::invalid_request(format!("Invalid agent id: {}", err))
+            })?;
+            Some(agent_id)
+        } else {
+            None
+        };
+
+        let ResolvedAgentProperties {
+            snapshot_policy, ..
+        } = resolve_agent_properties(deps, agent_id.as_ref(), &initial_component.metadata);
+
+        let execution_status = Arc::new(std::sync::RwLock::new(ExecutionStatus::Suspended {
+            agent_mode: initial_worker_metadata.agent_mode,
+            timestamp: Timestamp::now_utc(),
+        }));
+
+        Self::from_parts(
+            deps,
+            owned_agent_id,
+            GetOrCreateWorkerResult {
+                initial_worker_metadata,
+                current_status: Arc::new(RwLock::new(status)),
+                execution_status,
+                agent_id,
+                snapshot_policy,
+                oplog,
+                initial_component: Arc::new(initial_component),
+            },
+            true,
+            invocation_context_stack,
+            principal,
+        )
+        .await
+    }
+
+    async fn from_parts<T: HasAll<Ctx>>(
+        deps: &T,
+        owned_agent_id: OwnedAgentId,
+        GetOrCreateWorkerResult {
+            initial_worker_metadata,
+            current_status,
+            execution_status,
+            agent_id,
+            snapshot_policy,
+            oplog,
+            initial_component,
+        }: GetOrCreateWorkerResult,
+        detached: bool,
+        invocation_context_stack: &InvocationContextStack,
+        principal: Principal,
+    ) -> Result<Self, WorkerExecutorError> {
         let current_status_guard = current_status.read().await;
         let metrics_status = WorkerStatusMetric::new(current_status_guard.status);
         let initial_pending_invocations = current_status_guard.pending_invocations.clone();
@@ -493,10 +580,13 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
 
         let current_component = Arc::new(arc_swap::ArcSwap::from(initial_component));
 
+        // Ephemeral and detached workers never persist their status
+        let skip_status_persistence =
+            detached || initial_worker_metadata.agent_mode == AgentMode::Ephemeral;
         let last_known_status_detached = Arc::new(AtomicBool::new(false));
         let status_flusher = status_flusher::AgentStatusFlusher::new(
             owned_agent_id.clone(),
-            initial_worker_metadata.agent_mode == AgentMode::Ephemeral,
+            skip_status_persistence,
             deps.config().agent_status_flush.enabled,
             deps.worker_service(),
             deps.active_workers().status_flush_queue(),
@@ -506,7 +596,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
 
         let status_checkpointer = status_checkpointer::StatusCheckpointer::new(
             owned_agent_id.clone(),
-            initial_worker_metadata.agent_mode == AgentMode::Ephemeral,
+            skip_status_persistence,
             deps.config().agent_status_checkpoint.enabled,
             deps.config().agent_status_checkpoint.min_oplog_delta,
             deps.worker_service(),
@@ -572,7 +662,6 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                 .await
                 .expect("Failed enqueuing initial agent invocations to worker");
         };
-        crate::metrics::wasm::record_create_worker(start.elapsed());
 
         Ok(worker)
     }
@@ -705,6 +794,27 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         Ok(())
     }
 
+    /// Interrupts the worker, marks it as deleted and removes it from the worker service and the
+    /// set of active workers. The caller must keep holding the worker until this returns.
+    pub async fn delete(&self) -> Result<(), WorkerExecutorError> {
+        info!("Interrupting worker before deletion");
+        self.set_interrupting(InterruptKind::Interrupt(Timestamp::now_utc()))
+            .await;
+        info!("Marking worker for deletion");
+        self.start_deleting().await?;
+
+        self.worker_service().remove(&self.owned_agent_id).await;
+        self.active_workers()
+            .remove(&self.owned_agent_id.agent_id)
+            .await;
+        self.events().publish_agent_write(AgentWrite {
+            agent_id: self.owned_agent_id.agent_id(),
+            oplog_index: self.last_known_status.read().await.oplog_idx,
+            deleted: true,
+        });
+        Ok(())
+    }
+
     pub fn event_service(&self) -> Arc<dyn WorkerEventService + Send + Sync> {
         self.worker_event_service.clone()
     }
@@ -1392,6 +1502,50 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    /// Stores the failure of a single invocation, without failing the pending invocations
+    /// queued behind it.
+    // should only be called from invocation loop
+    pub async fn store_single_invocation_failure(
+        &self,
+        key: &IdempotencyKey,
+        trap_type: &TrapType,
+    ) {
+        let stderr = self.worker_event_service.get_last_invocation_errors();
+        let golem_error = trap_type.as_golem_error(&stderr);
+        self.invocation_results.write().await.insert(
+            key.clone(),
+            InvocationResult::Cached {
+                result: Err(FailedInvocationResult {
+                    trap_type: trap_type.clone(),
+                    stderr,
+                }),
+            },
+        );
+        if let Some(golem_error) = golem_error {
+            self.events().publish(Event::InvocationCompleted {
+                agent_id: self.owned_agent_id.agent_id(),
+                idempotency_key: key.clone(),
+                result: Err(golem_error),
+            });
+        }
+    }
+
+    /// Returns an error to the callers awaiting a single invocation without storing it as the
+    /// result of the invocation, so it can be retried with the same idempotency key.
+    // should only be called from invocation loop
+    pub async fn report_retriable_invocation_failure(
+        &self,
+        key: &IdempotencyKey,
+        error: WorkerExecutorError,
+    ) {
+        self.invocation_results.write().await.remove(key);
+        self.events().publish(Event::InvocationCompleted {
+            agent_id: self.owned_agent_id.agent_id(),
+            idempotency_key: key.clone(),
+            result: Err(error),
+        });
+    }
+
     pub(super) async fn store_invocation_resuming(&self, key: &IdempotencyKey) {
         let mut map = self.invocation_results.write().await;
         map.remove(key);
@@ -1401,6 +1555,10 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         self.execution_status.read().unwrap().agent_mode()
     }
 
+    pub fn parsed_agent_id(&self) -> Option<&LegacyParsedAgentId> {
+        self.parsed_agent_id.as_ref()
+    }
+
     /// Gets the estimated memory requirement of the worker.
     ///
     /// This covers only the per-worker linear memory. The compiled component
@@ -1861,6 +2019,46 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    /// The lifecycle policy of this agent's type in the current component revision, if any.
+    pub fn lifecycle_policy(&self) -> Option<AgentLifecyclePolicy> {
+        let parsed = self.parsed_agent_id.as_ref()?;
+        self.current_component
+            .load()
+            .metadata
+            .agent_type_lifecycle_policy(&parsed.agent_type)
+            .cloned()
+    }
+
+    /// Invocation interceptor plugins installed for this agent's type and currently active on it,
+    /// in the order of their priorities.
+    pub async fn active_invocation_interceptors(&self) -> Vec<InstalledPlugin> {
+        let Some(parsed) = self.parsed_agent_id.as_ref() else {
+            return Vec::new();
+        };
+        let snapshot = self.current_component.load();
+        let Some(plugins) = snapshot.metadata.agent_type_plugins(&parsed.agent_type) else {
+            return Vec::new();
+        };
+        if !plugins
+            .iter()
+            .any(|plugin| plugin.invocation_interceptor_component_id.is_some())
+        {
+            return Vec::new();
+        }
+
+        let active_plugins = self.last_known_status.read().await.active_plugins.clone();
+        let mut result = plugins
+            .iter()
+            .filter(|plugin| {
+                plugin.invocation_interceptor_component_id.is_some()
+                    && active_plugins.contains(&plugin.environment_plugin_grant_id)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        result.sort_by_key(|plugin| plugin.priority);
+        result
+    }
+
     /// Enqueue invocation of an exported function. Uses
     /// `UnknownAssumeMutating` as a safe default for callers without
     /// classification; the epoch is no longer bumped at enqueue time.
@@ -2159,6 +2357,57 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         Ok(())
     }
 
+    /// Records that a WebSocket connection terminated by the http api was opened to this worker
+    pub async fn record_websocket_connected(
+        &self,
+        connection_id: String,
+        method_name: String,
+    ) -> Result<(), WorkerExecutorError> {
+        let instance_guard = self.lock_non_stopping_worker().await;
+
+        if instance_guard.is_deleting() {
+            return Err(WorkerExecutorError::invalid_request(
+                "Cannot open a WebSocket connection to a deleting worker",
+            ));
+        };
+
+        // Connection lifecycle entries are hints that do not affect invocation
+        // results: do not bump the read-only cache epoch.
+        self.add_and_commit_oplog_internal(
+            &instance_guard,
+            OplogEntry::web_socket_connected(connection_id, method_name),
+            None,
+        )
+        .await;
+
+        drop(instance_guard);
+        Ok(())
+    }
+
+    /// Records that a WebSocket connection previously recorded by `record_websocket_connected` was closed
+    pub async fn record_websocket_disconnected(
+        &self,
+        connection_id: String,
+    ) -> Result<(), WorkerExecutorError> {
+        let instance_guard = self.lock_non_stopping_worker().await;
+
+        if instance_guard.is_deleting() {
+            return Err(WorkerExecutorError::invalid_request(
+                "Cannot close a WebSocket connection of a deleting worker",
+            ));
+        };
+
+        self.add_and_commit_oplog_internal(
+            &instance_guard,
+            OplogEntry::web_socket_disconnected(connection_id),
+            None,
+        )
+        .await;
+
+        drop(instance_guard);
+        Ok(())
+    }
+
     pub async fn deactivate_plugin(
         &self,
         plugin_grant_id: EnvironmentPluginGrantId,
@@ -2236,7 +2485,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
     }
 
     /// Starting from the end of the oplog, find the Nth AgentInvocationStarted entry's index.
-    async fn find_nth_invocation_from_end(&self, n: usize) -> Option<OplogIndex> {
+    pub(crate) async fn find_nth_invocation_from_end(&self, n: usize) -> Option<OplogIndex> {
         let mut current = self.oplog.current_oplog_index().await;
         let mut found = 0;
         loop {
@@ -3000,6 +3249,8 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
 
                     self.schedule_oplog_archive_if_needed(&old_status, &updated_status)
                         .await;
+                    self.schedule_lifecycle_actions_if_needed(&old_status, &updated_status)
+                        .await;
 
                     true
                 } else {
@@ -3058,6 +3309,31 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    async fn schedule_lifecycle_actions_if_needed(
+        &self,
+        old_status: &AgentStatusRecord,
+        new_status: &AgentStatusRecord,
+    ) {
+        if old_status.status == new_status.status {
+            return;
+        }
+        let Some(policy) = self.lifecycle_policy() else {
+            return;
+        };
+
+        schedule_lifecycle_actions(
+            self.scheduler_service().as_ref(),
+            &policy,
+            self.initial_worker_metadata.created_by,
+            &self.owned_agent_id,
+            self.agent_mode(),
+            new_status.status,
+            new_status.oplog_idx,
+            Utc::now(),
+        )
+        .await;
+    }
+
     async fn start_waiting_worker(
         this: Arc<Worker<Ctx>>,
         memory_grant: MemoryGrant,
@@ -3114,6 +3390,35 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
             .await;
     }
 
+    /// Moves the oplog entries made unnecessary for recovery by the automatic snapshot committed
+    /// at `snapshot_index` to the archive layer, keeping the configured number of entries before
+    /// it in the primary oplog.
+    ///
+    /// Nothing is deleted: the archived entries remain readable, so a failing snapshot can still
+    /// fall back to a full replay, and forking, exporting and the public oplog are unaffected.
+    pub(crate) async fn compact_oplog(&self, snapshot_index: OplogIndex) {
+        let config = &self.config().oplog.compaction;
+        if !config.enabled || self.agent_mode() != AgentMode::Durable {
+            return;
+        }
+
+        let last_archived_index = OplogIndex::from_u64(
+            u64::from(snapshot_index).saturating_sub(config.retained_entries + 1),
+        );
+        if last_archived_index <= OplogIndex::INITIAL {
+            return;
+        }
+
+        match MultiLayerOplog::try_archive_prefix(&self.oplog, last_archived_index).await {
+            Some(()) => {
+                debug!(
+                    "Compacted the oplog by archiving the entries up to index {last_archived_index}"
+                )
+            }
+            None => warn!("Oplog compaction is only supported for multi-layer oplogs"),
+        }
+    }
+
     /// Writes a *clean* status checkpoint *during* a long-running invocation, taken from the current
     /// committed in-memory status (the caller must only invoke this right after a durable commit, so
     /// `last_known_status` reflects the committed oplog tip).
@@ -3176,6 +3481,13 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         self.status_flusher
             .on_status_changed(&previous_status, &new_status)
             .await;
+        if previous_status.oplog_idx != new_status.oplog_idx {
+            self.events().publish_agent_write(AgentWrite {
+                agent_id: self.owned_agent_id.agent_id(),
+                oplog_index: new_status.oplog_idx,
+                deleted: false,
+            });
+        }
     }
 }
 
@@ -3683,6 +3995,7 @@ impl RunningWorker {
             parent.worker_service(),
             parent.worker_enumeration_service(),
             parent.key_value_service(),
+            parent.topic_service(),
             parent.blob_store_service(),
             parent.rdbms_service(),
             parent.quota_service(),
@@ -3748,6 +4061,15 @@ impl RunningWorker {
                 }
             }
 
+            if let Some(error) = data_mut.check_invocation_deadline() {
+                warn!(error = ?error, "Invocation deadline exceeded, interrupting");
+                return Err(WorkerExecutorError::InvocationFailed {
+                    error,
+                    stderr: String::new(),
+                }
+                .into());
+            }
+
             match data_mut.check_interrupt() {
                 Some(kind) => Err(kind.into()),
                 None => Ok(UpdateDeadline::YieldCustom(
@@ -152,7 +152,8 @@ pub fn classify_invocation(
         | AgentInvocation::ManualUpdate { .. }
         | AgentInvocation::LoadSnapshot { .. }
         | AgentInvocation::SaveSnapshot { .. }
-        | AgentInvocation::ProcessOplogEntries { .. } => InvocationEffect::Mutating,
+        | AgentInvocation::ProcessOplogEntries { .. }
+        | AgentInvocation::InterceptInvocation { .. } => InvocationEffect::Mutating,
     }
 }
 
@@ -226,6 +227,7 @@ mod tests {
             output_schema: empty_schema(),
             http_endpoint: vec![],
             read_only: ro,
+            timeout_nanos: None,
         }
     }
 
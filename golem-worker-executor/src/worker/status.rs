@@ -488,6 +488,8 @@ fn calculate_latest_worker_status(
             }
             OplogEntry::ActivatePlugin { .. } => {}
             OplogEntry::DeactivatePlugin { .. } => {}
+            OplogEntry::WebSocketConnected { .. } => {}
+            OplogEntry::WebSocketDisconnected { .. } => {}
             OplogEntry::Revert { .. } => {}
             OplogEntry::CancelPendingInvocation { .. } => {}
             OplogEntry::StartSpan { .. } => {
@@ -869,6 +871,16 @@ fn calculate_invocation_results(
                 }
                 current_idempotency_key = None;
             }
+            // An invocation exceeding its deadline is never retried, so it has finished
+            OplogEntry::Error {
+                error: AgentError::InvocationDeadlineExceeded(_),
+                ..
+            } => {
+                if let Some(idempotency_key) = &current_idempotency_key {
+                    invocation_results.insert(idempotency_key.clone(), *oplog_idx);
+                }
+                current_idempotency_key = None;
+            }
             OplogEntry::Error { .. } => {
                 if let Some(idempotency_key) = &current_idempotency_key {
                     invocation_results.insert(idempotency_key.clone(), *oplog_idx);
@@ -1141,6 +1153,7 @@ fn is_worker_error_retriable(
         AgentError::EphemeralFuelExhausted(_) => false,
         AgentError::EphemeralCannotSuspend(_) => false,
         AgentError::ReadOnlyViolation(_) => false,
+        AgentError::InvocationDeadlineExceeded(_) => false,
     }
 }
 
@@ -1167,8 +1180,9 @@ mod test {
     use golem_common::model::invocation_context::{InvocationContextStack, TraceId};
     use golem_common::model::oplog::host_functions::HostFunctionName;
     use golem_common::model::oplog::{
-        AgentError, DurableFunctionType, HostRequest, HostRequestNoInput, HostResponse, OplogEntry,
-        OplogPayload, PayloadId, RawOplogPayload, UpdateDescription,
+        AgentError, DurableFunctionType, HostRequest, HostRequestNoInput, HostResponse,
+        InvocationDeadlineExceededError, OplogEntry, OplogPayload, PayloadId, RawOplogPayload,
+        UpdateDescription,
     };
     use golem_common::model::regions::{DeletedRegions, OplogRegion};
     use golem_common::model::{
@@ -1365,6 +1379,59 @@ mod test {
         run_test_case(test_case).await;
     }
 
+    #[test]
+    async fn invocation_exceeding_deadline_is_finished_and_skipped() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+        let error = AgentError::InvocationDeadlineExceeded(InvocationDeadlineExceededError {
+            method: "b".to_string(),
+            deadline: Timestamp::now_utc(),
+        });
+
+        let builder = TestCase::builder(0)
+            .agent_invocation_started("a", vec![], k1.clone())
+            .agent_invocation_finished(
+                AgentInvocationResult::AgentInitialization,
+                k1,
+                ComponentRevision::INITIAL,
+            )
+            .agent_invocation_started("b", vec![], k2.clone())
+            .grow_memory(10);
+        let idle_status = builder.entries[2].expected_status.clone();
+        let error_key = k2.clone();
+
+        let test_case = builder
+            .add(
+                OplogEntry::error(error, OplogIndex::from_u64(4), false, None),
+                move |mut status| {
+                    status.status = AgentStatus::Failed;
+                    status.current_idempotency_key = None;
+                    status
+                        .invocation_results
+                        .insert(error_key, status.oplog_idx);
+                    status
+                },
+            )
+            .add(
+                OplogEntry::jump(OplogRegion {
+                    start: OplogIndex::from_u64(4),
+                    end: OplogIndex::from_u64(6),
+                }),
+                move |mut status| {
+                    status.status = idle_status.status;
+                    status.total_linear_memory_size = idle_status.total_linear_memory_size;
+                    status.skipped_regions.add(OplogRegion {
+                        start: OplogIndex::from_u64(4),
+                        end: OplogIndex::from_u64(6),
+                    });
+                    status
+                },
+            )
+            .build();
+
+        run_test_case(test_case).await;
+    }
+
     #[test]
     async fn single_auto_update_for_running() {
         let k1 = IdempotencyKey::fresh();
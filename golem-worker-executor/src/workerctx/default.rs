@@ -13,13 +13,14 @@
 // limitations under the License.
 
 use super::LogEventEmitBehaviour;
+use crate::durable_host::test_hooks::TestHooks;
 use crate::durable_host::websocket::WebSocketConnectionPool;
 use crate::durable_host::{DurableWorkerCtx, DurableWorkerCtxView, PublicDurableWorkerState};
 use crate::metrics::wasm::record_allocated_memory;
 use crate::model::{AgentConfig, ExecutionStatus, LastError, ReadFileResult, TrapType};
 use crate::preview2::golem::agent::host::{
     CancellationToken, FutureInvokeResult, Host as AgentHost, HostCancellationToken,
-    HostFutureInvokeResult, HostWasmRpc, RpcError, WasmRpc,
+    HostFutureInvokeResult, HostWasmRpc, RpcError, TopicSubscriber, WasmRpc, WebsocketMessage,
 };
 use crate::services::active_workers::ActiveWorkers;
 use crate::services::agent_types::AgentTypesService;
@@ -38,11 +39,12 @@ use crate::services::resource_limits::{AtomicResourceEntry, ResourceLimits};
 use crate::services::rpc::Rpc;
 use crate::services::scheduler::SchedulerService;
 use crate::services::shard::ShardService;
+use crate::services::topics::TopicService;
 use crate::services::worker::WorkerService;
 use crate::services::worker_event::WorkerEventService;
 use crate::services::worker_fork::WorkerForkService;
 use crate::services::worker_proxy::WorkerProxy;
-use crate::services::{HasAll, NoAdditionalDeps, worker_enumeration};
+use crate::services::{HasAll, worker_enumeration};
 use crate::worker::{RetryDecision, Worker};
 use crate::workerctx::{
     CallCountManagement, ExternalOperations, FileSystemReading, FuelManagement,
@@ -55,7 +57,7 @@ use golem_common::base_model::OplogIndex;
 use golem_common::base_model::component_metadata::AgentTypeProvisionConfig;
 use golem_common::base_model::environment_plugin_grant::EnvironmentPluginGrantId;
 use golem_common::model::account::{AccountEmail, AccountId};
-use golem_common::model::agent::{AgentMode, LegacyParsedAgentId};
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, UntypedDataValue};
 use golem_common::model::component::{CanonicalFilePath, ComponentRevision};
 use golem_common::model::invocation_context::{
     self, AttributeValue, InvocationContextStack, SpanId,
@@ -66,7 +68,7 @@ use golem_common::model::oplog::{
 };
 use golem_common::model::{
     AgentId, AgentInvocation, AgentInvocationOutput, AgentStatusRecord, IdempotencyKey,
-    OwnedAgentId,
+    OwnedAgentId, Timestamp,
 };
 use golem_service_base::error::worker_executor::{
     GolemSpecificWasmTrap, InterruptKind, WorkerExecutorError,
@@ -225,6 +227,14 @@ impl FuelTracker {
     }
 }
 
+/// Extra dependencies of the default worker context
+#[derive(Clone, Default)]
+pub struct ContextDeps {
+    /// Overrides of the agents' clocks, random sources and outgoing HTTP installed by in-process
+    /// test harnesses. Deployed executors never set them.
+    pub test_hooks: Option<Arc<TestHooks>>,
+}
+
 pub struct Context {
     pub durable_ctx: DurableWorkerCtx<Context>,
     resource_limit_entry: Arc<AtomicResourceEntry>,
@@ -423,6 +433,16 @@ impl InvocationHooks for Context {
             .await
     }
 
+    async fn on_agent_invocation_output_replaced(
+        &mut self,
+        original: &UntypedDataValue,
+        replaced: &UntypedDataValue,
+    ) -> Result<(), WorkerExecutorError> {
+        self.durable_ctx
+            .on_agent_invocation_output_replaced(original, replaced)
+            .await
+    }
+
     async fn get_current_retry_point(&self) -> OplogIndex {
         self.durable_ctx.get_current_retry_point().await
     }
@@ -434,6 +454,19 @@ impl InvocationHooks for Context {
     fn exit_read_only_mode(&mut self) {
         self.durable_ctx.exit_read_only_mode()
     }
+
+    fn arm_invocation_deadline(&mut self, method_name: String, deadline: Timestamp) {
+        self.durable_ctx
+            .arm_invocation_deadline(method_name, deadline)
+    }
+
+    fn disarm_invocation_deadline(&mut self) {
+        self.durable_ctx.disarm_invocation_deadline()
+    }
+
+    fn check_invocation_deadline(&self) -> Option<AgentError> {
+        self.durable_ctx.check_invocation_deadline()
+    }
 }
 
 #[async_trait]
@@ -489,7 +522,7 @@ impl ResourceLimiterAsync for Context {
 
 #[async_trait]
 impl ExternalOperations<Context> for Context {
-    type ExtraDeps = NoAdditionalDeps;
+    type ExtraDeps = ContextDeps;
 
     async fn get_last_error_and_retry_count<T: HasAll<Context> + Send + Sync>(
         this: &T,
@@ -669,6 +702,14 @@ impl HostWasmRpc for Context {
             .await
     }
 
+    async fn set_deadline(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        deadline: Option<wasmtime_wasi::p2::bindings::clocks::wall_clock::Datetime>,
+    ) -> anyhow::Result<()> {
+        self.durable_ctx.set_deadline(self_, deadline).await
+    }
+
     async fn drop(&mut self, rep: Resource<WasmRpc>) -> anyhow::Result<()> {
         HostWasmRpc::drop(&mut self.durable_ctx, rep).await
     }
@@ -764,6 +805,27 @@ impl AgentHost for Context {
         AgentHost::create_webhook(&mut self.durable_ctx, promise_id).await
     }
 
+    async fn current_websocket_connection(&mut self) -> anyhow::Result<Option<String>> {
+        AgentHost::current_websocket_connection(&mut self.durable_ctx).await
+    }
+
+    async fn websocket_send(
+        &mut self,
+        connection_id: String,
+        message: WebsocketMessage,
+    ) -> anyhow::Result<()> {
+        AgentHost::websocket_send(&mut self.durable_ctx, connection_id, message).await
+    }
+
+    async fn websocket_close(
+        &mut self,
+        connection_id: String,
+        code: Option<u16>,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        AgentHost::websocket_close(&mut self.durable_ctx, connection_id, code, reason).await
+    }
+
     async fn get_config_value(
         &mut self,
         key: Vec<String>,
@@ -771,6 +833,31 @@ impl AgentHost for Context {
     ) -> anyhow::Result<golem_wasm::WitValue> {
         AgentHost::get_config_value(&mut self.durable_ctx, key, expected_type).await
     }
+
+    async fn subscribe(
+        &mut self,
+        topic: String,
+        subscriber: TopicSubscriber,
+        method_name: String,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        AgentHost::subscribe(&mut self.durable_ctx, topic, subscriber, method_name).await
+    }
+
+    async fn unsubscribe(
+        &mut self,
+        topic: String,
+        subscriber: TopicSubscriber,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        AgentHost::unsubscribe(&mut self.durable_ctx, topic, subscriber).await
+    }
+
+    async fn publish(
+        &mut self,
+        topic: String,
+        message: golem_common::model::agent::bindings::golem::agent::common::DataValue,
+    ) -> anyhow::Result<Result<u64, RpcError>> {
+        AgentHost::publish(&mut self.durable_ctx, topic, message).await
+    }
 }
 
 impl wasmtime_wasi::p2::bindings::cli::environment::Host for Context {
@@ -855,6 +942,7 @@ impl WorkerCtx for Context {
         worker_service: Arc<dyn WorkerService>,
         worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn RdbmsService>,
         quota_service: Arc<dyn QuotaService>,
@@ -867,7 +955,7 @@ impl WorkerCtx for Context {
         rpc: Arc<dyn Rpc>,
         worker_proxy: Arc<dyn WorkerProxy>,
         component_service: Arc<dyn ComponentService>,
-        _extra_deps: Self::ExtraDeps,
+        extra_deps: Self::ExtraDeps,
         config: Arc<GolemConfig>,
         worker_config: AgentConfig,
         execution_status: Arc<std::sync::RwLock<ExecutionStatus>>,
@@ -891,6 +979,7 @@ impl WorkerCtx for Context {
             worker_service,
             worker_enumeration_service,
             key_value_service,
+            topic_service,
             blob_store_service,
             rdbms_service,
             quota_service,
@@ -918,6 +1007,7 @@ impl WorkerCtx for Context {
             original_phantom_id,
             account_resource_limits.per_invocation_http_call_limit(),
             account_resource_limits.per_invocation_rpc_call_limit(),
+            extra_deps.test_hooks,
         )
         .await?;
         Ok(Self::new(golem_ctx, config, account_resource_limits))
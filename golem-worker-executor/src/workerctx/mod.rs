@@ -34,6 +34,7 @@ use crate::services::resource_limits::ResourceLimits;
 use crate::services::rpc::Rpc;
 use crate::services::scheduler::SchedulerService;
 use crate::services::shard::ShardService;
+use crate::services::topics::TopicService;
 use crate::services::worker::WorkerService;
 use crate::services::worker_event::WorkerEventService;
 use crate::services::worker_fork::WorkerForkService;
@@ -44,7 +45,7 @@ use async_trait::async_trait;
 use golem_common::base_model::component_metadata::AgentTypeProvisionConfig;
 use golem_common::base_model::environment_plugin_grant::EnvironmentPluginGrantId;
 use golem_common::model::account::{AccountEmail, AccountId};
-use golem_common::model::agent::{AgentMode, LegacyParsedAgentId};
+use golem_common::model::agent::{AgentMode, LegacyParsedAgentId, UntypedDataValue};
 use golem_common::model::component::{CanonicalFilePath, ComponentRevision};
 use golem_common::model::invocation_context::{
     AttributeValue, InvocationContextSpan, InvocationContextStack, SpanId,
@@ -52,7 +53,7 @@ use golem_common::model::invocation_context::{
 use golem_common::model::oplog::{AgentError, TimestampedUpdateDescription};
 use golem_common::model::{
     AgentId, AgentInvocation, AgentInvocationOutput, AgentStatusRecord, IdempotencyKey, OplogIndex,
-    OwnedAgentId,
+    OwnedAgentId, Timestamp,
 };
 use golem_service_base::error::worker_executor::{InterruptKind, WorkerExecutorError};
 use golem_service_base::model::GetFileSystemNodeResult;
@@ -104,6 +105,7 @@ pub trait WorkerCtx:
     /// - `promise_service`: The service for managing promises
     /// - `worker_service`: The service for managing workers
     /// - `key_value_service`: The service for storing key-value pairs
+    /// - `topic_service`: The service keeping track of agent-to-agent topic subscriptions
     /// - `blob_store_service`: The service for storing arbitrary blobs
     /// - `event_service`: The service for publishing worker events
     /// - `active_workers`: The service for managing active workers
@@ -126,6 +128,7 @@ pub trait WorkerCtx:
         worker_service: Arc<dyn WorkerService>,
         worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService>,
         key_value_service: Arc<dyn KeyValueService>,
+        topic_service: Arc<dyn TopicService>,
         blob_store_service: Arc<dyn BlobStoreService>,
         rdbms_service: Arc<dyn RdbmsService>,
         quota_service: Arc<dyn QuotaService>,
@@ -334,6 +337,18 @@ pub trait InvocationHooks {
         output: &mut AgentInvocationOutput,
     ) -> Result<(), WorkerExecutorError>;
 
+    /// Called when invocation interceptor plugins replaced the output of a successful agent
+    /// method invocation, before [`InvocationHooks::on_agent_invocation_success`] is called with
+    /// the `replaced` output.
+    ///
+    /// The replacement is recorded so replay can check the output produced again by the agent
+    /// against the `original` one, and continue with the `replaced` one.
+    async fn on_agent_invocation_output_replaced(
+        &mut self,
+        original: &UntypedDataValue,
+        replaced: &UntypedDataValue,
+    ) -> Result<(), WorkerExecutorError>;
+
     /// Gets the retry point that should be associated with a current error. Errors are grouped
     /// by this information. The current oplog index is a good default.
     async fn get_current_retry_point(&self) -> OplogIndex;
@@ -350,6 +365,21 @@ pub trait InvocationHooks {
     /// [`InvocationHooks::enter_read_only_mode`] call. Calling this without a matching enter is
     /// a no-op other than a `warn!` trace.
     fn exit_read_only_mode(&mut self);
+
+    /// Arms the deadline of the agent method invocation about to start. Once `deadline` has
+    /// passed, [`InvocationHooks::check_invocation_deadline`] reports
+    /// `AgentError::InvocationDeadlineExceeded` for `method_name`.
+    ///
+    /// Each call must be paired with [`InvocationHooks::disarm_invocation_deadline`].
+    fn arm_invocation_deadline(&mut self, method_name: String, deadline: Timestamp);
+
+    /// Clears the deadline armed by [`InvocationHooks::arm_invocation_deadline`].
+    fn disarm_invocation_deadline(&mut self);
+
+    /// Returns the error to trap with if an armed invocation deadline has passed. Polled from
+    /// the epoch deadline callback while guest code is executing; host calls waiting on the
+    /// interrupt signal are interrupted at the deadline and reported with this error.
+    fn check_invocation_deadline(&self) -> Option<AgentError>;
 }
 
 #[async_trait]
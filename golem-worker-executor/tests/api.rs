@@ -617,6 +617,166 @@ fn extract_oplog_idx_from_promise_id(promise_id_value: &Value) -> OplogIndex {
     OplogIndex::from_u64(oplog_idx)
 }
 
+fn promise_id_argument(promise_id_value: Value) -> DataValue {
+    DataValue::Tuple(ElementValues {
+        elements: vec![ElementValue::ComponentModel(ComponentModelElementValue {
+            value: ValueAndType::new(promise_id_value, PromiseId::get_type()),
+        })],
+    })
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout("4m")]
+async fn promise_deadline_wakes_up_awaiting_agent(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+    #[tagged_as("host_api_tests")] host_api_tests: &PrecompiledComponent,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, host_api_tests)
+        .store()
+        .await?;
+
+    let agent_id = agent_id!("GolemHostApi", "promise-deadline-1");
+    let worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let promise_id_value = executor
+        .invoke_and_await_agent(
+            &component,
+            &agent_id,
+            "create_promise_with_timeout",
+            data_value!(5000u64),
+        )
+        .await?
+        .into_return_value()
+        .ok_or_else(|| anyhow!("expected return value"))?;
+    let promise_data = promise_id_argument(promise_id_value);
+
+    let executor_clone = executor.clone();
+    let component_clone = component.clone();
+    let agent_id_clone = agent_id.clone();
+    let promise_data_clone = promise_data.clone();
+
+    let fiber = tokio::spawn(
+        async move {
+            executor_clone
+                .invoke_and_await_agent(
+                    &component_clone,
+                    &agent_id_clone,
+                    "await_promise_outcome",
+                    promise_data_clone,
+                )
+                .await
+        }
+        .in_current_span(),
+    );
+
+    // nothing completes the promise, the agent is suspended until the scheduled deadline
+    executor
+        .wait_for_status(&worker_id, AgentStatus::Suspended, Duration::from_secs(10))
+        .await?;
+
+    let result = tokio::time::timeout(Duration::from_secs(30), fiber)
+        .await
+        .map_err(|_| anyhow!("the promise deadline did not wake up the agent"))???;
+
+    let poll = executor
+        .invoke_and_await_agent(&component, &agent_id, "poll_promise", promise_data)
+        .await?;
+
+    executor.check_oplog_is_queryable(&worker_id).await?;
+
+    assert_eq!(
+        result.into_return_value(),
+        Some(Value::String("timed-out".to_string()))
+    );
+    // the untyped accessor has no payload for timed out promises
+    assert_eq!(poll.into_return_value(), Some(Value::Option(None)));
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout("4m")]
+async fn typed_promise_rejects_mismatching_payloads(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+    #[tagged_as("host_api_tests")] host_api_tests: &PrecompiledComponent,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, host_api_tests)
+        .store()
+        .await?;
+
+    let agent_id = agent_id!("GolemHostApi", "typed-promise-1");
+    let worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let promise_id_value = executor
+        .invoke_and_await_agent(&component, &agent_id, "create_typed_promise", data_value!())
+        .await?
+        .into_return_value()
+        .ok_or_else(|| anyhow!("expected return value"))?;
+    let promise_id = PromiseId {
+        agent_id: worker_id.clone(),
+        oplog_idx: extract_oplog_idx_from_promise_id(&promise_id_value),
+    };
+    let promise_data = promise_id_argument(promise_id_value);
+
+    let not_json = executor
+        .complete_promise(&promise_id, b"\x00\x01".to_vec())
+        .await;
+    let wrong_type = executor
+        .complete_promise(&promise_id, br#""ten""#.to_vec())
+        .await;
+
+    // rejected completions leave the promise pending
+    let poll = executor
+        .invoke_and_await_agent(&component, &agent_id, "poll_promise", promise_data.clone())
+        .await?;
+
+    executor
+        .complete_promise(&promise_id, b"10".to_vec())
+        .await?;
+
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "await_promise_outcome", promise_data)
+        .await?;
+
+    executor.check_oplog_is_queryable(&worker_id).await?;
+
+    let not_json_error = not_json.expect_err("non-JSON payload must be rejected");
+    assert!(
+        not_json_error.to_string().contains("not valid JSON"),
+        "unexpected error: {not_json_error}"
+    );
+    let wrong_type_error = wrong_type.expect_err("payload of the wrong type must be rejected");
+    assert!(
+        wrong_type_error
+            .to_string()
+            .contains("does not match the expected type"),
+        "unexpected error: {wrong_type_error}"
+    );
+    assert_eq!(poll.into_return_value(), Some(Value::Option(None)));
+    assert_eq!(
+        result.into_return_value(),
+        Some(Value::String("completed:10".to_string()))
+    );
+    Ok(())
+}
+
 #[test]
 #[tracing::instrument]
 #[timeout("4m")]
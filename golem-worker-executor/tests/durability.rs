@@ -25,15 +25,17 @@ use golem_common::model::oplog::{
 use golem_common::{agent_id, data_value};
 use golem_test_framework::dsl::TestDsl;
 use golem_wasm::Value;
-use golem_worker_executor::services::golem_config::SnapshotPolicy;
+use golem_worker_executor::services::golem_config::{OplogCompactionConfig, SnapshotPolicy};
 use golem_worker_executor_test_utils::{
-    LastUniqueId, PrecompiledComponent, TestContext, WorkerExecutorTestDependencies, start,
-    start_with_snapshot_policy,
+    LastUniqueId, PrecompiledComponent, TestContext, TestExecutorOverrides, TestWorkerExecutor,
+    WorkerExecutorTestDependencies, start, start_with_overrides, start_with_snapshot_policy,
 };
 use http::StatusCode;
 use pretty_assertions::assert_eq;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
@@ -519,6 +521,161 @@ async fn snapshot_based_recovery(
     Ok(())
 }
 
+async fn start_with_oplog_compaction(
+    deps: &WorkerExecutorTestDependencies,
+    context: &TestContext,
+) -> anyhow::Result<TestWorkerExecutor> {
+    let overrides = TestExecutorOverrides {
+        configure: Some(Arc::new(|config| {
+            config.oplog.default_snapshotting = SnapshotPolicy::EveryNInvocation { count: 1 };
+            config.oplog.compaction = OplogCompactionConfig {
+                enabled: true,
+                retained_entries: 2,
+            };
+            config.filesystem_storage.checkpoint_on_snapshot = true;
+        })),
+        ..Default::default()
+    };
+    start_with_overrides(deps, context, overrides).await
+}
+
+fn remove_filesystem_checkpoints(dir: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name() == Some(OsStr::new("filesystem_checkpoints")) {
+                std::fs::remove_dir_all(&path)?;
+            } else {
+                remove_filesystem_checkpoints(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn compacted_oplog_recovers_from_snapshot(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_counters")] agent_counters: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start_with_oplog_compaction(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_counters)
+        .store()
+        .await?;
+    let agent_id = agent_id!("SnapshotCounter", "compacted-recovery");
+    let worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    for _ in 0..10 {
+        executor
+            .invoke_and_await_agent(&component, &agent_id, "increment", data_value!())
+            .await?;
+    }
+
+    // The compacted entries are archived, not dropped
+    let oplog = executor.get_oplog(&worker_id, OplogIndex::INITIAL).await?;
+    assert!(matches!(
+        oplog.first().map(|entry| &entry.entry),
+        Some(PublicOplogEntry::Create(_))
+    ));
+    assert_eq!(
+        oplog
+            .iter()
+            .filter(|entry| matches!(&entry.entry, PublicOplogEntry::Snapshot(_)))
+            .count(),
+        10
+    );
+
+    drop(executor);
+    let executor = start_with_oplog_compaction(deps, &context).await?;
+
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "get", data_value!())
+        .await?;
+    assert_eq!(result.into_return_value(), Some(Value::U32(10)));
+
+    let was_recovered = executor
+        .invoke_and_await_agent(
+            &component,
+            &agent_id,
+            "was_recovered_from_snapshot",
+            data_value!(),
+        )
+        .await?;
+    assert_eq!(was_recovered.into_return_value(), Some(Value::Bool(true)));
+
+    let increment_after = executor
+        .invoke_and_await_agent(&component, &agent_id, "increment", data_value!())
+        .await?;
+    assert_eq!(increment_after.into_return_value(), Some(Value::U32(11)));
+
+    drop(executor);
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn compacted_oplog_falls_back_to_full_replay_if_snapshot_cannot_be_loaded(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_counters")] agent_counters: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start_with_oplog_compaction(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_counters)
+        .store()
+        .await?;
+    let agent_id = agent_id!("SnapshotCounter", "compacted-fallback");
+    executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    for _ in 0..10 {
+        executor
+            .invoke_and_await_agent(&component, &agent_id, "increment", data_value!())
+            .await?;
+    }
+
+    drop(executor);
+
+    // Without its filesystem checkpoint the snapshot cannot be used for recovery
+    remove_filesystem_checkpoints(&deps.blob_storage_root())?;
+
+    let executor = start_with_oplog_compaction(deps, &context).await?;
+
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "get", data_value!())
+        .await?;
+    assert_eq!(result.into_return_value(), Some(Value::U32(10)));
+
+    let was_recovered = executor
+        .invoke_and_await_agent(
+            &component,
+            &agent_id,
+            "was_recovered_from_snapshot",
+            data_value!(),
+        )
+        .await?;
+    assert_eq!(
+        was_recovered.into_return_value(),
+        Some(Value::Bool(false)),
+        "Worker should have been recovered by replaying the archived oplog"
+    );
+
+    drop(executor);
+    Ok(())
+}
+
 #[test]
 #[tracing::instrument]
 async fn snapshot_based_recovery_preserves_state_across_multiple_restarts(
@@ -0,0 +1,439 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::model::AgentId;
+use golem_common::model::agent::AgentMode;
+use golem_common::model::component::ComponentId;
+use golem_common::model::environment::EnvironmentId;
+use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
+use golem_service_base::storage::blob::{BlobStorage, BlobStorageNamespace};
+use golem_worker_executor::storage::blob::encrypting::EncryptingBlobStorage;
+use golem_worker_executor::storage::encryption::{DataKeyScope, EnvelopeEncryption, MasterKey};
+use golem_worker_executor::storage::indexed::encrypting::EncryptingIndexedStorage;
+use golem_worker_executor::storage::indexed::memory::InMemoryIndexedStorage;
+use golem_worker_executor::storage::indexed::{IndexedStorage, IndexedStorageNamespace};
+use golem_worker_executor::storage::keyvalue::encrypting::EncryptingKeyValueStorage;
+use golem_worker_executor::storage::keyvalue::memory::InMemoryKeyValueStorage;
+use golem_worker_executor::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use pretty_assertions::{assert_eq, assert_ne};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use test_r::test;
+
+fn agent_id(name: &str) -> AgentId {
+    AgentId {
+        component_id: ComponentId::new(),
+        agent_id: name.to_string(),
+    }
+}
+
+fn oplog_namespace(agent_id: &AgentId) -> IndexedStorageNamespace {
+    IndexedStorageNamespace::OpLog {
+        agent_id: agent_id.clone(),
+        agent_mode: AgentMode::Durable,
+    }
+}
+
+fn master_keys(ids: &[&str]) -> HashMap<String, MasterKey> {
+    ids.iter()
+        .enumerate()
+        .map(|(idx, id)| (id.to_string(), MasterKey::new([idx as u8 + 1; 32])))
+        .collect()
+}
+
+fn encryption(
+    key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    master_key_ids: &[&str],
+    current_master_key_id: &str,
+) -> Arc<EnvelopeEncryption> {
+    Arc::new(
+        EnvelopeEncryption::new(
+            key_value_storage,
+            master_keys(master_key_ids),
+            current_master_key_id.to_string(),
+            Duration::from_secs(3600),
+            16,
+            false,
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+async fn oplog_entries_are_encrypted_at_rest() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let inner = Arc::new(InMemoryIndexedStorage::new());
+    let storage = EncryptingIndexedStorage::new(inner.clone(), encryption(keys, &["k1"], "k1"));
+    let agent_id = agent_id("encrypted");
+    let namespace = oplog_namespace(&agent_id);
+
+    storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            namespace.clone(),
+            "key",
+            1,
+            b"first".to_vec(),
+        )
+        .await
+        .unwrap();
+    storage
+        .append_many(
+            "test",
+            "append_many",
+            "entry",
+            namespace.clone(),
+            "key",
+            vec![(2, b"second".to_vec()), (3, b"third".to_vec())],
+        )
+        .await
+        .unwrap();
+
+    let stored = inner
+        .read("test", "read", "entry", namespace.clone(), "key", 1, 3)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 3);
+    for (_, value) in &stored {
+        assert!(EnvelopeEncryption::is_encrypted(value));
+    }
+
+    let read = storage
+        .read("test", "read", "entry", namespace.clone(), "key", 1, 3)
+        .await
+        .unwrap();
+    assert_eq!(
+        read,
+        vec![
+            (1, b"first".to_vec()),
+            (2, b"second".to_vec()),
+            (3, b"third".to_vec())
+        ]
+    );
+    let last = storage
+        .last("test", "last", "entry", namespace, "key")
+        .await
+        .unwrap();
+    assert_eq!(last, Some((3, b"third".to_vec())));
+}
+
+#[test]
+async fn plaintext_written_before_enabling_encryption_remains_readable() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let inner = Arc::new(InMemoryIndexedStorage::new());
+    let agent_id = agent_id("plaintext");
+    let namespace = oplog_namespace(&agent_id);
+
+    inner
+        .append(
+            "test",
+            "append",
+            "entry",
+            namespace.clone(),
+            "key",
+            1,
+            b"old".to_vec(),
+        )
+        .await
+        .unwrap();
+
+    let storage = EncryptingIndexedStorage::new(inner, encryption(keys, &["k1"], "k1"));
+    storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            namespace.clone(),
+            "key",
+            2,
+            b"new".to_vec(),
+        )
+        .await
+        .unwrap();
+
+    let read = storage
+        .read("test", "read", "entry", namespace, "key", 1, 2)
+        .await
+        .unwrap();
+    assert_eq!(read, vec![(1, b"old".to_vec()), (2, b"new".to_vec())]);
+}
+
+#[test]
+async fn plaintext_is_rejected_once_migrated() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let encryption = EnvelopeEncryption::new(
+        keys,
+        master_keys(&["k1"]),
+        "k1".to_string(),
+        Duration::from_secs(3600),
+        16,
+        true,
+    )
+    .unwrap();
+    let scope = DataKeyScope::Agent(agent_id("migrated"));
+
+    assert!(
+        encryption
+            .decrypt(&scope, "key", b"old".to_vec())
+            .await
+            .is_err()
+    );
+    let data = encryption.encrypt(&scope, "key", b"new").await.unwrap();
+    assert_eq!(
+        encryption.decrypt(&scope, "key", data).await.unwrap(),
+        b"new".to_vec()
+    );
+}
+
+#[test]
+async fn encrypted_entries_cannot_be_moved_to_another_index() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let inner = Arc::new(InMemoryIndexedStorage::new());
+    let storage = EncryptingIndexedStorage::new(inner.clone(), encryption(keys, &["k1"], "k1"));
+    let agent_id = agent_id("moved");
+    let namespace = oplog_namespace(&agent_id);
+
+    storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            namespace.clone(),
+            "key",
+            1,
+            b"first".to_vec(),
+        )
+        .await
+        .unwrap();
+    let (_, stored) = inner
+        .last("test", "last", "entry", namespace.clone(), "key")
+        .await
+        .unwrap()
+        .unwrap();
+    inner
+        .append(
+            "test",
+            "append",
+            "entry",
+            namespace.clone(),
+            "key",
+            2,
+            stored,
+        )
+        .await
+        .unwrap();
+
+    assert!(
+        storage
+            .read("test", "read", "entry", namespace, "key", 2, 2)
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+async fn oplog_payloads_are_encrypted_at_rest() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let inner = Arc::new(InMemoryBlobStorage::new());
+    let storage = EncryptingBlobStorage::new(inner.clone(), encryption(keys, &["k1"], "k1"));
+    let namespace = BlobStorageNamespace::OplogPayload {
+        environment_id: EnvironmentId::new(),
+        agent_id: agent_id("payloads"),
+        agent_mode: AgentMode::Durable,
+    };
+    let path = Path::new("hash/payload");
+
+    storage
+        .put_raw("test", "put", namespace.clone(), path, b"large payload")
+        .await
+        .unwrap();
+
+    let stored = inner
+        .get_raw("test", "get", namespace.clone(), path)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(EnvelopeEncryption::is_encrypted(&stored));
+
+    let read = storage
+        .get_raw("test", "get", namespace.clone(), path)
+        .await
+        .unwrap();
+    assert_eq!(read, Some(b"large payload".to_vec()));
+    let slice = storage
+        .get_raw_slice("test", "get", namespace, path, 6, 13)
+        .await
+        .unwrap();
+    assert_eq!(slice, Some(b"payload".to_vec()));
+}
+
+#[test]
+async fn only_user_defined_key_value_data_is_encrypted() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let inner = Arc::new(InMemoryKeyValueStorage::new());
+    let storage = EncryptingKeyValueStorage::new(inner.clone(), encryption(keys, &["k1"], "k1"));
+    let user_defined = KeyValueStorageNamespace::UserDefined {
+        environment_id: EnvironmentId::new(),
+        bucket: "bucket".to_string(),
+    };
+    let internal = KeyValueStorageNamespace::Schedule;
+
+    storage
+        .set("test", "set", "value", user_defined.clone(), "a", b"secret")
+        .await
+        .unwrap();
+    storage
+        .set("test", "set", "value", internal.clone(), "a", b"internal")
+        .await
+        .unwrap();
+
+    let stored = inner
+        .get("test", "get", "value", user_defined.clone(), "a")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(EnvelopeEncryption::is_encrypted(&stored));
+    let stored_internal = inner
+        .get("test", "get", "value", internal, "a")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_internal.as_ref(), b"internal");
+
+    let read = storage
+        .get_many(
+            "test",
+            "get_many",
+            "value",
+            user_defined,
+            vec!["a".to_string(), "b".to_string()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        read.iter()
+            .map(|value| value.as_ref().map(|value| value.to_vec()))
+            .collect::<Vec<_>>(),
+        vec![Some(b"secret".to_vec()), None]
+    );
+}
+
+#[test]
+async fn shredding_makes_the_data_of_the_scope_unreadable() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let encryption = encryption(keys, &["k1"], "k1");
+    let shredded = DataKeyScope::Agent(agent_id("shredded"));
+    let kept = DataKeyScope::Agent(agent_id("kept"));
+
+    let shredded_data = encryption.encrypt(&shredded, "key", b"data").await.unwrap();
+    let kept_data = encryption.encrypt(&kept, "key", b"data").await.unwrap();
+
+    encryption.shred(&shredded).await.unwrap();
+
+    assert!(
+        encryption
+            .decrypt(&shredded, "key", shredded_data)
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        encryption.decrypt(&kept, "key", kept_data).await.unwrap(),
+        b"data".to_vec()
+    );
+}
+
+#[test]
+async fn data_encrypted_for_one_scope_cannot_be_decrypted_for_another() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let encryption = encryption(keys, &["k1"], "k1");
+    let environment_1 = DataKeyScope::Environment(EnvironmentId::new());
+    let environment_2 = DataKeyScope::Environment(EnvironmentId::new());
+
+    let data = encryption
+        .encrypt(&environment_1, "key", b"data")
+        .await
+        .unwrap();
+    encryption
+        .encrypt(&environment_2, "key", b"other")
+        .await
+        .unwrap();
+
+    assert!(
+        encryption
+            .decrypt(&environment_2, "key", data)
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+async fn rotated_data_keys_keep_decrypting_older_data() {
+    let keys = Arc::new(InMemoryKeyValueStorage::new());
+    let encryption = encryption(keys, &["k1"], "k1");
+    let scope = DataKeyScope::Agent(agent_id("rotated"));
+
+    let before = encryption.encrypt(&scope, "key", b"before").await.unwrap();
+    encryption.rotate_data_key(&scope).await.unwrap();
+    let after = encryption.encrypt(&scope, "key", b"after").await.unwrap();
+
+    // The envelope header holds the data key version after the magic and envelope version
+    assert_ne!(&before[5..9], &after[5..9]);
+    assert_eq!(
+        encryption.decrypt(&scope, "key", before).await.unwrap(),
+        b"before".to_vec()
+    );
+    assert_eq!(
+        encryption.decrypt(&scope, "key", after).await.unwrap(),
+        b"after".to_vec()
+    );
+}
+
+#[test]
+async fn data_keys_are_rewrapped_by_the_current_master_key() {
+    let keys: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(InMemoryKeyValueStorage::new());
+    let scope = DataKeyScope::Environment(EnvironmentId::new());
+
+    let data = encryption(keys.clone(), &["k1"], "k1")
+        .encrypt(&scope, "key", b"data")
+        .await
+        .unwrap();
+
+    // The new master key is introduced while the previous one is still provided
+    let rotated = encryption(keys.clone(), &["k1", "k2"], "k2");
+    assert_eq!(
+        rotated.decrypt(&scope, "key", data.clone()).await.unwrap(),
+        b"data".to_vec()
+    );
+
+    // After the first load the previous master key can be retired
+    let retired = Arc::new(
+        EnvelopeEncryption::new(
+            keys,
+            HashMap::from([("k2".to_string(), MasterKey::new([2; 32]))]),
+            "k2".to_string(),
+            Duration::from_secs(3600),
+            16,
+            false,
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        retired.decrypt(&scope, "key", data).await.unwrap(),
+        b"data".to_vec()
+    );
+}
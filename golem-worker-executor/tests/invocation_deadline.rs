@@ -0,0 +1,163 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Invocation deadline tests, using the `DeadlineAgent` of the shared
+//! `agent_sdk_rust` test component.
+
+use crate::Tracing;
+use golem_common::model::AgentStatus;
+use golem_common::{agent_id, data_value};
+use golem_test_framework::dsl::TestDsl;
+use golem_wasm::Value;
+use golem_worker_executor_test_utils::{
+    LastUniqueId, PrecompiledComponent, TestContext, WorkerExecutorTestDependencies, start,
+};
+use std::time::{Duration, Instant};
+use test_r::{inherit_test_dep, test, timeout};
+
+inherit_test_dep!(WorkerExecutorTestDependencies);
+inherit_test_dep!(LastUniqueId);
+inherit_test_dep!(
+    #[tagged_as("agent_sdk_rust")]
+    PrecompiledComponent
+);
+inherit_test_dep!(Tracing);
+
+const AGENT_TYPE: &str = "DeadlineAgent";
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn awaiting_a_never_completed_promise_exceeds_the_deadline(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("forever-{unique_id}"));
+    let _worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let started = Instant::now();
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "await_forever", data_value!())
+        .await;
+    let elapsed = started.elapsed();
+
+    let err = result.expect_err("await_forever must exceed its deadline");
+    let rendered = format!("{err:?}");
+    assert!(
+        rendered.contains("InvocationDeadlineExceeded") && rendered.contains("await_forever"),
+        "expected InvocationDeadlineExceeded for await_forever, got: {rendered}"
+    );
+    // The promise wait is interrupted at the 2s deadline, not at some later suspension
+    assert!(
+        elapsed >= Duration::from_secs(2) && elapsed < Duration::from_secs(30),
+        "unexpected invocation duration: {elapsed:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn invocation_finishing_in_time_is_not_affected_by_the_deadline(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("quick-{unique_id}"));
+    let _worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "quick", data_value!())
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+
+    assert_eq!(result, Value::U64(1));
+
+    Ok(())
+}
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn agent_keeps_working_after_an_invocation_exceeded_its_deadline(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("timed-out-{unique_id}"));
+    let worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let timed_out = executor
+        .invoke_and_await_agent(&component, &agent_id, "await_forever", data_value!())
+        .await;
+
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "quick", data_value!())
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+    let metadata = executor.get_worker_metadata(&worker_id).await?;
+
+    let err = format!(
+        "{:?}",
+        timed_out.expect_err("await_forever must exceed its deadline")
+    );
+    assert!(
+        err.contains("InvocationDeadlineExceeded"),
+        "expected InvocationDeadlineExceeded, got: {err}"
+    );
+    assert_eq!(result, Value::U64(1));
+    assert_ne!(metadata.status, AgentStatus::Failed);
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Invocation interceptor tests, using the `DeadlineAgent` of the shared
+//! `agent_sdk_rust` test component and a scripted in-process interceptor plugin.
+
+use crate::Tracing;
+use async_trait::async_trait;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::{UntypedDataValue, UntypedElementValue};
+use golem_common::model::component::InstalledPlugin;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::environment_plugin_grant::EnvironmentPluginGrantId;
+use golem_common::model::{
+    AgentStatus, IdempotencyKey, InterceptedInvocation, Interception, InterceptionOutcome,
+};
+use golem_common::{agent_id, data_value};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_test_framework::dsl::TestDsl;
+use golem_wasm::Value;
+use golem_worker_executor::services::invocation_interceptor::InvocationInterceptorPlugin;
+use golem_worker_executor_test_utils::{
+    LastUniqueId, PrecompiledComponent, TestContext, TestExecutorOverrides,
+    WorkerExecutorTestDependencies, start_with_overrides,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use test_r::{inherit_test_dep, test, timeout};
+
+inherit_test_dep!(WorkerExecutorTestDependencies);
+inherit_test_dep!(LastUniqueId);
+inherit_test_dep!(
+    #[tagged_as("agent_sdk_rust")]
+    PrecompiledComponent
+);
+inherit_test_dep!(Tracing);
+
+const AGENT_TYPE: &str = "DeadlineAgent";
+
+/// Interceptor plugin returning preconfigured outcomes. A `None` start interception makes
+/// the plugin behave as if its agent was unreachable.
+#[derive(Clone)]
+struct ScriptedInterceptor {
+    on_start: Arc<Mutex<Option<Interception>>>,
+    on_end: Arc<Mutex<Interception>>,
+    end_interceptions: Arc<AtomicUsize>,
+}
+
+impl ScriptedInterceptor {
+    fn new(on_start: Option<Interception>, on_end: Interception) -> Self {
+        Self {
+            on_start: Arc::new(Mutex::new(on_start)),
+            on_end: Arc::new(Mutex::new(on_end)),
+            end_interceptions: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn set_on_start(&self, interception: Option<Interception>) {
+        *self.on_start.lock().unwrap() = interception;
+    }
+
+    fn overrides(&self) -> TestExecutorOverrides {
+        TestExecutorOverrides {
+            invocation_interceptor_plugin: Some(Arc::new(self.clone())),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl InvocationInterceptorPlugin for ScriptedInterceptor {
+    async fn intercept(
+        &self,
+        _environment_id: EnvironmentId,
+        _account_id: AccountId,
+        _plugin: &InstalledPlugin,
+        _idempotency_key: &IdempotencyKey,
+        _intercepted: InterceptedInvocation,
+        output: Option<UntypedDataValue>,
+    ) -> Result<InterceptionOutcome, WorkerExecutorError> {
+        let interception = match output {
+            None => self
+                .on_start
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or_else(|| WorkerExecutorError::runtime("interceptor agent is down"))?,
+            Some(_) => {
+                self.end_interceptions.fetch_add(1, Ordering::SeqCst);
+                self.on_end.lock().unwrap().clone()
+            }
+        };
+        Ok(InterceptionOutcome {
+            interception,
+            span_attributes: vec![],
+        })
+    }
+
+    async fn on_shard_assignment_changed(&self) -> Result<(), WorkerExecutorError> {
+        Ok(())
+    }
+}
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn rejected_invocation_fails_only_the_invocation(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let interceptor = ScriptedInterceptor::new(
+        Some(Interception::Reject("not allowed".to_string())),
+        Interception::Proceed,
+    );
+    let executor = start_with_overrides(deps, &context, interceptor.overrides()).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .with_plugin(AGENT_TYPE, &EnvironmentPluginGrantId::new(), 0)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("rejected-{unique_id}"));
+    let worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let rejected = executor
+        .invoke_and_await_agent(&component, &agent_id, "quick", data_value!())
+        .await;
+
+    interceptor.set_on_start(Some(Interception::Proceed));
+    let result = executor
+        .invoke_and_await_agent(&component, &agent_id, "quick", data_value!())
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+    let metadata = executor.get_worker_metadata(&worker_id).await?;
+
+    let err = format!("{:?}", rejected.expect_err("quick must be rejected"));
+    assert!(
+        err.contains("rejected by plugin") && err.contains("not allowed"),
+        "expected a plugin rejection, got: {err}"
+    );
+    assert_eq!(result, Value::U64(1));
+    assert_ne!(metadata.status, AgentStatus::Failed);
+
+    Ok(())
+}
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn replaced_output_is_returned_and_survives_replay(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let interceptor = ScriptedInterceptor::new(
+        Some(Interception::Proceed),
+        Interception::Replace(UntypedDataValue::Tuple(vec![
+            UntypedElementValue::ComponentModel(Value::U64(42)),
+        ])),
+    );
+    let executor = start_with_overrides(deps, &context, interceptor.overrides()).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .with_plugin(AGENT_TYPE, &EnvironmentPluginGrantId::new(), 0)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("replaced-{unique_id}"));
+    let _worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let result1 = executor
+        .invoke_and_await_agent(&component, &agent_id, "quick", data_value!())
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+
+    // Restarting the executor replays the first invocation, which must match the recorded
+    // replacement instead of diverging
+    drop(executor);
+    let executor = start_with_overrides(deps, &context, interceptor.overrides()).await?;
+
+    let result2 = executor
+        .invoke_and_await_agent(&component, &agent_id, "quick", data_value!())
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+
+    drop(executor);
+
+    assert_eq!(result1, Value::U64(42));
+    assert_eq!(result2, Value::U64(42));
+    assert_eq!(interceptor.end_interceptions.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn unavailable_interceptor_is_a_retriable_error(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let interceptor = ScriptedInterceptor::new(None, Interception::Proceed);
+    let executor = start_with_overrides(deps, &context, interceptor.overrides()).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .with_plugin(AGENT_TYPE, &EnvironmentPluginGrantId::new(), 0)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("unavailable-{unique_id}"));
+    let _worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+
+    let idempotency_key = IdempotencyKey::fresh();
+    let unavailable = executor
+        .invoke_and_await_agent_with_key(
+            &component,
+            &agent_id,
+            &idempotency_key,
+            "quick",
+            data_value!(),
+        )
+        .await;
+
+    // Retrying with the same idempotency key runs the invocation once the interceptor is back
+    interceptor.set_on_start(Some(Interception::Proceed));
+    let result = executor
+        .invoke_and_await_agent_with_key(
+            &component,
+            &agent_id,
+            &idempotency_key,
+            "quick",
+            data_value!(),
+        )
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+
+    let err = format!("{:?}", unavailable.expect_err("quick must fail"));
+    assert!(
+        err.contains("is unavailable"),
+        "expected an unavailable interceptor error, got: {err}"
+    );
+    assert_eq!(result, Value::U64(1));
+
+    Ok(())
+}
@@ -1018,6 +1018,59 @@ async fn add_to_sorted_set_updates_score(
     assert_eq!(result, vec![(1.0, value1.into()), (3.0, value2.into())]);
 }
 
+#[test]
+#[tracing::instrument]
+async fn remove_range_from_sorted_set(
+    _deps: &WorkerExecutorTestDependencies,
+    #[dimension(kvs)] kvs: &Arc<dyn GetKeyValueStorage + Send + Sync>,
+    #[dimension(nss)] nss: &Namespaces,
+) {
+    let kvs = kvs.get_key_value_storage().await;
+    let ns = nss.ns.clone();
+
+    let set1 = "set1";
+    let set2 = "set2";
+
+    let value1 = "value1".as_bytes();
+    let value2 = "value2".as_bytes();
+    let value3 = "value3".as_bytes();
+    let value4 = "value4".as_bytes();
+
+    // removing from a non-existing set is allowed
+    kvs.remove_range_from_sorted_set("test", "api", "entity", ns.clone(), set1, 0.0, 2.0)
+        .await
+        .unwrap();
+
+    kvs.add_to_sorted_set("test", "api", "entity", ns.clone(), set1, 1.0, value1)
+        .await
+        .unwrap();
+    kvs.add_to_sorted_set("test", "api", "entity", ns.clone(), set1, 2.0, value2)
+        .await
+        .unwrap();
+    kvs.add_to_sorted_set("test", "api", "entity", ns.clone(), set1, 3.0, value3)
+        .await
+        .unwrap();
+    kvs.add_to_sorted_set("test", "api", "entity", ns.clone(), set2, 1.0, value4)
+        .await
+        .unwrap();
+
+    kvs.remove_range_from_sorted_set("test", "api", "entity", ns.clone(), set1, 0.0, 2.0)
+        .await
+        .unwrap();
+
+    let result1 = kvs
+        .get_sorted_set("test", "api", "entity", ns.clone(), set1)
+        .await
+        .unwrap();
+    let result2 = kvs
+        .get_sorted_set("test", "api", "entity", ns.clone(), set2)
+        .await
+        .unwrap();
+
+    assert_eq!(result1, vec![(3.0, value3.into())]);
+    assert_eq!(result2, vec![(1.0, value4.into())]);
+}
+
 #[test]
 #[tracing::instrument]
 async fn query_sorted_set(
@@ -30,12 +30,15 @@ pub mod api;
 pub mod blobstore;
 pub mod compatibility;
 pub mod durability;
+pub mod encryption;
 pub mod fuel;
 pub mod hot_update;
 pub mod http;
 pub mod ignite_service;
 pub mod in_function_retry;
 pub mod indexed_storage;
+pub mod invocation_deadline;
+pub mod invocation_interceptor;
 pub mod key_value_storage;
 pub mod keyvalue;
 pub mod namespace_routed_key_value_storage;
@@ -49,6 +52,7 @@ pub mod retry_policies;
 pub mod revert;
 pub mod rpc;
 pub mod scalability;
+pub mod storage_migration;
 pub mod storage_quota;
 pub mod transactions;
 pub mod wasi;
@@ -83,6 +87,8 @@ tag_suite!(rdbms_service, rdbms_service);
 tag_suite!(resource_limits, group1);
 tag_suite!(oplog_metrics, group1);
 tag_suite!(storage_quota, group1);
+tag_suite!(storage_migration, group1);
+tag_suite!(encryption, group1);
 
 sequential_suite!(key_value_storage);
 sequential_suite!(namespace_routed_key_value_storage);
@@ -20,14 +20,17 @@
 //! over RPC.
 
 use crate::Tracing;
+use anyhow::anyhow;
+use golem_common::model::agent::{UntypedDataValue, UntypedElementValue};
+use golem_common::model::component::ComponentDto;
 use golem_common::model::oplog::OplogIndex;
-use golem_common::model::{AgentId, OwnedAgentId};
+use golem_common::model::{AgentId, OwnedAgentId, ScanCursor};
 use golem_common::{agent_id, data_value};
 use golem_test_framework::dsl::{TestDsl, count_agent_invocation_pair_since};
 use golem_wasm::Value;
 use golem_worker_executor::worker::EvictionClass;
 use golem_worker_executor_test_utils::{
-    LastUniqueId, PrecompiledComponent, TestContext, TestExecutorOverrides,
+    LastUniqueId, PrecompiledComponent, TestContext, TestExecutorOverrides, TestWorkerExecutor,
     WorkerExecutorTestDependencies, start, start_with_overrides,
 };
 use pretty_assertions::assert_eq;
@@ -1683,3 +1686,141 @@ async fn r4_rpc_read_only_method_cannot_make_rpc(
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// T12 — time-travel invocation reads past state without touching the agent
+// ---------------------------------------------------------------------------
+
+/// Invokes `method_name` through the executor API on a replay of the agent up to `at`.
+async fn invoke_at(
+    executor: &TestWorkerExecutor,
+    component: &ComponentDto,
+    agent_id: &AgentId,
+    method_name: &str,
+    at: OplogIndex,
+) -> anyhow::Result<u64> {
+    use golem_api_grpc::proto::golem::workerexecutor::v1::{
+        InvokeAgentRequest, TimeTravelTarget, invoke_agent_response, time_travel_target,
+    };
+
+    let response = executor
+        .client
+        .clone()
+        .invoke_agent(InvokeAgentRequest {
+            agent_id: Some(agent_id.clone().into()),
+            method_name: Some(method_name.to_string()),
+            method_parameters: Some(UntypedDataValue::from(data_value!()).into()),
+            mode: golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await as i32,
+            schedule_at: None,
+            at: Some(TimeTravelTarget {
+                target: Some(time_travel_target::Target::OplogIndex(at.as_u64())),
+            }),
+            idempotency_key: None,
+            component_owner_account_id: Some(component.account_id.into()),
+            environment_id: Some(component.environment_id.into()),
+            auth_ctx: Some(executor.auth_ctx().into()),
+            context: None,
+            principal: None,
+        })
+        .await?
+        .into_inner();
+
+    match response.result {
+        Some(invoke_agent_response::Result::Success(success)) => {
+            let output = UntypedDataValue::try_from(
+                success
+                    .result
+                    .ok_or_else(|| anyhow!("time-travel invocation returned no result"))?,
+            )
+            .map_err(|err| anyhow!(err))?;
+            match output {
+                UntypedDataValue::Tuple(elements) => match elements.as_slice() {
+                    [UntypedElementValue::ComponentModel(value)] => Ok(as_u64(value)),
+                    other => Err(anyhow!("unexpected result: {other:?}")),
+                },
+                other => Err(anyhow!("unexpected result: {other:?}")),
+            }
+        }
+        Some(invoke_agent_response::Result::Failure(error)) => {
+            Err(anyhow!("time-travel invocation failed: {error:?}"))
+        }
+        None => Err(anyhow!("no response from time-travel invocation")),
+    }
+}
+
+#[test]
+#[timeout("60s")]
+#[tracing::instrument]
+async fn t12_time_travel_reads_past_state(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    #[tagged_as("agent_sdk_rust")] agent_sdk_rust: &PrecompiledComponent,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component_dep(&context.default_environment_id, agent_sdk_rust)
+        .store()
+        .await?;
+
+    let unique_id = context.redis_prefix();
+    let agent_id = agent_id!(AGENT_TYPE, format!("t12-{unique_id}"));
+    let worker_id = executor
+        .start_agent(&component.id, agent_id.clone())
+        .await?;
+    wait_oplog_settled(&executor, &worker_id).await?;
+
+    let _ = executor
+        .invoke_and_await_agent(&component, &agent_id, "increment", data_value!())
+        .await?;
+    let after_first_increment = executor.oplog_max_index(&worker_id).await?;
+    let _ = executor
+        .invoke_and_await_agent(&component, &agent_id, "increment", data_value!())
+        .await?;
+    let before_time_travel = executor.oplog_max_index(&worker_id).await?;
+
+    let past = invoke_at(
+        &executor,
+        &component,
+        &worker_id,
+        "get_count",
+        after_first_increment,
+    )
+    .await?;
+    assert_eq!(past, 1);
+
+    // Mutating methods cannot be invoked on the replay
+    let err = invoke_at(
+        &executor,
+        &component,
+        &worker_id,
+        "increment",
+        after_first_increment,
+    )
+    .await
+    .expect_err("increment is not read-only");
+    assert!(
+        format!("{err:?}").contains("not a read-only method"),
+        "unexpected error: {err:?}"
+    );
+
+    // The agent itself is untouched by the replay, and the replay is never registered as an agent
+    assert_eq!(
+        executor.oplog_max_index(&worker_id).await?,
+        before_time_travel
+    );
+    let (_, agents) = executor
+        .get_workers_metadata(&component.id, None, ScanCursor::default(), 10, true)
+        .await?;
+    assert_eq!(agents.len(), 1);
+    let current = executor
+        .invoke_and_await_agent(&component, &agent_id, "get_count", data_value!())
+        .await?
+        .into_return_value()
+        .expect("expected return value");
+    assert_eq!(as_u64(&current), 2);
+
+    Ok(())
+}
@@ -0,0 +1,527 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use chrono::Utc;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::AgentMode;
+use golem_common::model::component::ComponentId;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::{
+    AgentId, PromiseId, ScheduleId, ScheduledAction, ShardAssignment, ShardId,
+};
+use golem_worker_executor::services::golem_config::StorageMigrationEnabledConfig;
+use golem_worker_executor::services::shard::ShardServiceDefault;
+use golem_worker_executor::services::storage_migration::StorageMigrationService;
+use golem_worker_executor::storage::indexed::memory::InMemoryIndexedStorage;
+use golem_worker_executor::storage::indexed::migrating::MigratingIndexedStorage;
+use golem_worker_executor::storage::indexed::{IndexedStorage, IndexedStorageNamespace};
+use golem_worker_executor::storage::keyvalue::memory::InMemoryKeyValueStorage;
+use golem_worker_executor::storage::keyvalue::migrating::MigratingKeyValueStorage;
+use golem_worker_executor::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use golem_worker_executor::storage::migration::StorageMigrationState;
+use golem_worker_executor::storage::scheduler::SchedulerStorage;
+use golem_worker_executor::storage::scheduler::memory::InMemorySchedulerStorage;
+use golem_worker_executor::storage::scheduler::migrating::MigratingSchedulerStorage;
+use pretty_assertions::assert_eq;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use test_r::test;
+
+fn agent_id(name: &str) -> AgentId {
+    AgentId {
+        component_id: ComponentId::new(),
+        agent_id: name.to_string(),
+    }
+}
+
+fn oplog_namespace(agent_id: &AgentId) -> IndexedStorageNamespace {
+    IndexedStorageNamespace::OpLog {
+        agent_id: agent_id.clone(),
+        agent_mode: AgentMode::Durable,
+    }
+}
+
+fn single_shard_assignment() -> ShardAssignment {
+    ShardAssignment {
+        number_of_shards: 1,
+        shard_ids: HashSet::from([ShardId::new(0)]),
+    }
+}
+
+fn migration_state(
+    key_value_storage: &Arc<dyn KeyValueStorage + Send + Sync>,
+) -> Arc<StorageMigrationState> {
+    Arc::new(StorageMigrationState::new(key_value_storage.clone()))
+}
+
+fn migration_service(
+    state: Arc<StorageMigrationState>,
+    oplog_storage: Arc<dyn IndexedStorage + Send + Sync>,
+    indexed_storage: Option<Arc<MigratingIndexedStorage>>,
+    key_value_storage: Option<Arc<MigratingKeyValueStorage>>,
+    scheduler_storage: Option<Arc<MigratingSchedulerStorage>>,
+) -> StorageMigrationService {
+    StorageMigrationService::new(
+        state,
+        oplog_storage,
+        indexed_storage,
+        key_value_storage,
+        scheduler_storage,
+        Arc::new(ShardServiceDefault::new()),
+        1,
+        StorageMigrationEnabledConfig {
+            key_value_storage: None,
+            indexed_storage: None,
+            scheduler_storage: None,
+            batch_size: 2,
+            check_interval: Duration::from_secs(1),
+        },
+    )
+}
+
+async fn read_all(
+    storage: &(dyn IndexedStorage + Send + Sync),
+    agent_id: &AgentId,
+) -> Vec<(u64, Vec<u8>)> {
+    storage
+        .read(
+            "test",
+            "read",
+            "entry",
+            oplog_namespace(agent_id),
+            &agent_id.to_redis_key(),
+            1,
+            u64::MAX,
+        )
+        .await
+        .unwrap()
+}
+
+#[test]
+async fn indexed_storage_is_copied_and_cut_over() {
+    let source: Arc<dyn IndexedStorage + Send + Sync> = Arc::new(InMemoryIndexedStorage::new());
+    let target: Arc<dyn IndexedStorage + Send + Sync> = Arc::new(InMemoryIndexedStorage::new());
+    let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+        Arc::new(InMemoryKeyValueStorage::new());
+    let state = migration_state(&key_value_storage);
+    let storage = Arc::new(MigratingIndexedStorage::new(
+        source.clone(),
+        target.clone(),
+        state.clone(),
+    ));
+
+    let existing = agent_id("existing");
+    let existing_key = existing.to_redis_key();
+    for id in 1..=5 {
+        source
+            .append(
+                "test",
+                "append",
+                "entry",
+                oplog_namespace(&existing),
+                &existing_key,
+                id,
+                vec![id as u8],
+            )
+            .await
+            .unwrap();
+    }
+
+    // Written before the executor knows its shards, only going to the source
+    let early = agent_id("early");
+    let early_key = early.to_redis_key();
+    storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            oplog_namespace(&early),
+            &early_key,
+            1,
+            vec![42],
+        )
+        .await
+        .unwrap();
+    assert_eq!(read_all(target.as_ref(), &early).await, vec![]);
+
+    let service = migration_service(
+        state.clone(),
+        source.clone(),
+        Some(storage.clone()),
+        None,
+        None,
+    );
+    service.migrate(&single_shard_assignment()).await.unwrap();
+
+    assert!(state.is_cut_over(&ShardId::new(0)));
+    assert_eq!(
+        read_all(target.as_ref(), &existing).await,
+        read_all(source.as_ref(), &existing).await
+    );
+    assert_eq!(read_all(target.as_ref(), &early).await, vec![(1, vec![42])]);
+
+    // After the cut over, writes go to both backends and reads are served from the target
+    storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            oplog_namespace(&existing),
+            &existing_key,
+            6,
+            vec![6],
+        )
+        .await
+        .unwrap();
+    let expected = (1..=6).map(|id| (id, vec![id as u8])).collect::<Vec<_>>();
+    assert_eq!(read_all(target.as_ref(), &existing).await, expected);
+    assert_eq!(read_all(source.as_ref(), &existing).await, expected);
+    assert_eq!(read_all(storage.as_ref(), &existing).await, expected);
+}
+
+#[test]
+async fn migration_state_survives_executor_restarts() {
+    let source: Arc<dyn IndexedStorage + Send + Sync> = Arc::new(InMemoryIndexedStorage::new());
+    let target: Arc<dyn IndexedStorage + Send + Sync> = Arc::new(InMemoryIndexedStorage::new());
+    let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+        Arc::new(InMemoryKeyValueStorage::new());
+    let shard_id = ShardId::new(0);
+
+    // Written only to the source, as the key was not copied yet
+    let first_state = migration_state(&key_value_storage);
+    first_state.set_number_of_shards(1);
+    let first_storage = MigratingIndexedStorage::new(source.clone(), target.clone(), first_state);
+    let before_restart = agent_id("before-restart");
+    first_storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            oplog_namespace(&before_restart),
+            &before_restart.to_redis_key(),
+            1,
+            vec![1],
+        )
+        .await
+        .unwrap();
+
+    // The executor restarts and cuts the shard over, copying the pending key
+    let second_state = migration_state(&key_value_storage);
+    let second_storage = Arc::new(MigratingIndexedStorage::new(
+        source.clone(),
+        target.clone(),
+        second_state.clone(),
+    ));
+    migration_service(
+        second_state.clone(),
+        source.clone(),
+        Some(second_storage),
+        None,
+        None,
+    )
+    .migrate(&single_shard_assignment())
+    .await
+    .unwrap();
+    assert_eq!(
+        read_all(target.as_ref(), &before_restart).await,
+        vec![(1, vec![1])]
+    );
+
+    // Another executor taking the shard over knows that it was cut over, and only copies the
+    // keys written before its own cut over
+    let third_state = migration_state(&key_value_storage);
+    assert_eq!(
+        third_state.persisted_cut_over_shards().await.unwrap(),
+        HashSet::from([shard_id])
+    );
+    third_state.set_number_of_shards(1);
+    let third_storage = Arc::new(MigratingIndexedStorage::new(
+        source.clone(),
+        target.clone(),
+        third_state.clone(),
+    ));
+    let after_restart = agent_id("after-restart");
+    third_storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            oplog_namespace(&after_restart),
+            &after_restart.to_redis_key(),
+            1,
+            vec![2],
+        )
+        .await
+        .unwrap();
+    assert_eq!(read_all(target.as_ref(), &after_restart).await, vec![]);
+
+    migration_service(
+        third_state.clone(),
+        source.clone(),
+        Some(third_storage),
+        None,
+        None,
+    )
+    .migrate(&single_shard_assignment())
+    .await
+    .unwrap();
+    assert!(third_state.is_cut_over(&shard_id));
+    assert_eq!(
+        read_all(target.as_ref(), &after_restart).await,
+        vec![(1, vec![2])]
+    );
+}
+
+#[test]
+async fn key_value_storage_is_copied_before_cut_over() {
+    let oplog_storage: Arc<dyn IndexedStorage + Send + Sync> =
+        Arc::new(InMemoryIndexedStorage::new());
+    let source: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(InMemoryKeyValueStorage::new());
+    let target: Arc<dyn KeyValueStorage + Send + Sync> = Arc::new(InMemoryKeyValueStorage::new());
+    let storage = Arc::new(MigratingKeyValueStorage::new(
+        source.clone(),
+        target.clone(),
+    ));
+    let state = migration_state(&(storage.clone() as Arc<dyn KeyValueStorage + Send + Sync>));
+
+    // The agents of a shard are enumerated from their oplogs
+    let agent = agent_id("copied");
+    oplog_storage
+        .append(
+            "test",
+            "append",
+            "entry",
+            oplog_namespace(&agent),
+            &agent.to_redis_key(),
+            1,
+            vec![1],
+        )
+        .await
+        .unwrap();
+
+    let status_namespace = KeyValueStorageNamespace::AgentStatus {
+        agent_id: agent.clone(),
+    };
+    source
+        .set(
+            "test",
+            "set",
+            "entry",
+            status_namespace.clone(),
+            "core",
+            b"status",
+        )
+        .await
+        .unwrap();
+    // A value deleted from the source while the target was out of date
+    target
+        .set(
+            "test",
+            "set",
+            "entry",
+            status_namespace.clone(),
+            "stale",
+            b"stale",
+        )
+        .await
+        .unwrap();
+    source
+        .add_to_set(
+            "test",
+            "add_to_set",
+            "entry",
+            KeyValueStorageNamespace::RunningWorkers,
+            "worker:running_in_shard:<0>",
+            b"running",
+        )
+        .await
+        .unwrap();
+
+    let topic_namespace = KeyValueStorageNamespace::Topic {
+        environment_id: EnvironmentId::new(),
+    };
+    source
+        .add_to_set(
+            "test",
+            "add_to_set",
+            "entry",
+            topic_namespace.clone(),
+            "subscribers:5:topic",
+            b"subscriber",
+        )
+        .await
+        .unwrap();
+    source
+        .set(
+            "test",
+            "set",
+            "entry",
+            topic_namespace.clone(),
+            "subscription:5:topic:10:subscriber",
+            b"subscription",
+        )
+        .await
+        .unwrap();
+    let user_defined_namespace = KeyValueStorageNamespace::UserDefined {
+        environment_id: EnvironmentId::new(),
+        bucket: "bucket".to_string(),
+    };
+    source
+        .set(
+            "test",
+            "set",
+            "entry",
+            user_defined_namespace.clone(),
+            "key",
+            b"value",
+        )
+        .await
+        .unwrap();
+
+    migration_service(
+        state.clone(),
+        oplog_storage,
+        None,
+        Some(storage.clone()),
+        None,
+    )
+    .migrate(&single_shard_assignment())
+    .await
+    .unwrap();
+    assert!(state.is_cut_over(&ShardId::new(0)));
+
+    let mut status = target
+        .get_all("test", "get_all", "entry", status_namespace)
+        .await
+        .unwrap();
+    status.sort();
+    assert_eq!(
+        status,
+        vec![("core".to_string(), Bytes::from_static(b"status"))]
+    );
+    assert_eq!(
+        target
+            .members_of_set(
+                "test",
+                "members_of_set",
+                "entry",
+                KeyValueStorageNamespace::RunningWorkers,
+                "worker:running_in_shard:<0>",
+            )
+            .await
+            .unwrap(),
+        vec![Bytes::from_static(b"running")]
+    );
+    assert_eq!(
+        target
+            .members_of_set(
+                "test",
+                "members_of_set",
+                "entry",
+                topic_namespace.clone(),
+                "subscribers:5:topic"
+            )
+            .await
+            .unwrap(),
+        vec![Bytes::from_static(b"subscriber")]
+    );
+    assert_eq!(
+        target
+            .get(
+                "test",
+                "get",
+                "entry",
+                topic_namespace,
+                "subscription:5:topic:10:subscriber"
+            )
+            .await
+            .unwrap()
+            .as_deref(),
+        Some(b"subscription".as_slice())
+    );
+    assert_eq!(
+        target
+            .get("test", "get", "entry", user_defined_namespace, "key")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some(b"value".as_slice())
+    );
+}
+
+#[test]
+async fn scheduled_actions_are_copied_on_cut_over() {
+    let source: Arc<dyn SchedulerStorage + Send + Sync> = Arc::new(InMemorySchedulerStorage::new());
+    let target: Arc<dyn SchedulerStorage + Send + Sync> = Arc::new(InMemorySchedulerStorage::new());
+    let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+        Arc::new(InMemoryKeyValueStorage::new());
+    let state = migration_state(&key_value_storage);
+    let storage = Arc::new(MigratingSchedulerStorage::new(
+        source.clone(),
+        target.clone(),
+        state.clone(),
+    ));
+
+    let shard_id = ShardId::new(0);
+    let action = ScheduledAction::CompletePromise {
+        account_id: AccountId::new(),
+        environment_id: EnvironmentId::new(),
+        promise_id: PromiseId {
+            agent_id: agent_id("scheduled"),
+            oplog_idx: OplogIndex::from_u64(1),
+        },
+    };
+
+    let existing = ScheduleId::fresh();
+    source
+        .insert(existing, Utc::now(), shard_id, &action)
+        .await
+        .unwrap();
+    let new = ScheduleId::fresh();
+    storage
+        .insert(new, Utc::now(), shard_id, &action)
+        .await
+        .unwrap();
+
+    let service = migration_service(
+        state.clone(),
+        Arc::new(InMemoryIndexedStorage::new()),
+        None,
+        None,
+        Some(storage.clone()),
+    );
+    service.migrate(&single_shard_assignment()).await.unwrap();
+
+    let copied = target
+        .list(shard_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.schedule_id)
+        .collect::<HashSet<_>>();
+    assert_eq!(copied, HashSet::from([existing, new]));
+
+    storage.cancel(&existing).await.unwrap();
+    let remaining = storage
+        .list(shard_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.schedule_id)
+        .collect::<Vec<_>>();
+    assert_eq!(remaining, vec![new]);
+}
@@ -0,0 +1,11 @@
+CREATE TABLE bulk_invocation_job (
+    job_id         TEXT NOT NULL,
+    running        INTEGER NOT NULL,
+    value          TEXT NOT NULL,
+    lease_owner    TEXT NULL,
+    lease_until_ms INTEGER NULL,
+    CONSTRAINT bulk_invocation_job_pk PRIMARY KEY (job_id)
+);
+
+CREATE INDEX bulk_invocation_job_running_idx
+    ON bulk_invocation_job (running);
@@ -1,5 +1,6 @@
 use crate::api::common::ApiEndpointError;
 use crate::service::auth::AuthService;
+use crate::service::bulk_invocation::{BulkInvocationJob, BulkInvocationService};
 use crate::service::worker::WorkerService;
 use chrono::{DateTime, Utc};
 use golem_common::base_model::api;
@@ -7,12 +8,13 @@ use golem_common::model::agent::{AgentTypeName, UntypedJsonDataValue};
 use golem_common::model::application::ApplicationName;
 use golem_common::model::component::ComponentRevision;
 use golem_common::model::environment::EnvironmentName;
+use golem_common::model::oplog::OplogIndex;
 use golem_common::model::worker::AgentConfigEntryDto;
-use golem_common::model::{AgentId, IdempotencyKey};
+use golem_common::model::{AgentFilter, AgentId, IdempotencyKey};
 use golem_common::recorded_http_api_request;
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::model::auth::GolemSecurityScheme;
-use poem_openapi::param::Header;
+use poem_openapi::param::{Header, Path};
 use poem_openapi::payload::Json;
 use poem_openapi_derive::{Enum, Object, OpenApi};
 use serde::{Deserialize, Serialize};
@@ -24,14 +26,20 @@ type Result<T> = std::result::Result<T, ApiEndpointError>;
 
 pub struct AgentsApi {
     worker_service: Arc<WorkerService>,
+    bulk_invocation_service: Arc<BulkInvocationService>,
     auth_service: Arc<dyn AuthService>,
 }
 
 #[OpenApi(prefix_path = "/v1/agents", tag = ApiTags::Agent)]
 impl AgentsApi {
-    pub fn new(worker_service: Arc<WorkerService>, auth_service: Arc<dyn AuthService>) -> Self {
+    pub fn new(
+        worker_service: Arc<WorkerService>,
+        bulk_invocation_service: Arc<BulkInvocationService>,
+        auth_service: Arc<dyn AuthService>,
+    ) -> Self {
         Self {
             worker_service,
+            bulk_invocation_service,
             auth_service,
         }
     }
@@ -103,6 +111,96 @@ impl AgentsApi {
 
         record.result(response).map(Json)
     }
+
+    /// Start invoking a method on every agent matching a filter
+    ///
+    /// Creates a durable job that enumerates the agents of the given agent type matching the
+    /// optional filter, and invokes the method on each of them with bounded concurrency. The job
+    /// keeps running in the background, and is resumed if the worker service restarts.
+    #[oai(
+        path = "/bulk-invocations",
+        method = "post",
+        operation_id = "start_bulk_invocation"
+    )]
+    async fn start_bulk_invocation(
+        &self,
+        request: Json<BulkInvocationRequest>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<BulkInvocationJob>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let record = recorded_http_api_request!(
+            "start_bulk_invocation",
+            app = %request.app_name,
+            env = %request.env_name,
+            agent_type = %request.agent_type_name,
+            method = %request.method_name
+        );
+
+        let response = self
+            .bulk_invocation_service
+            .start(request.0, auth)
+            .instrument(record.span.clone())
+            .await
+            .map_err(Into::into);
+
+        record.result(response).map(Json)
+    }
+
+    /// Get the progress of a bulk invocation job
+    #[oai(
+        path = "/bulk-invocations/:job_id",
+        method = "get",
+        operation_id = "get_bulk_invocation"
+    )]
+    async fn get_bulk_invocation(
+        &self,
+        job_id: Path<Uuid>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<BulkInvocationJob>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let record =
+            recorded_http_api_request!("get_bulk_invocation", job_id = job_id.0.to_string());
+
+        let response = self
+            .bulk_invocation_service
+            .get(job_id.0, auth)
+            .instrument(record.span.clone())
+            .await
+            .map_err(Into::into);
+
+        record.result(response).map(Json)
+    }
+
+    /// Cancel a bulk invocation job
+    ///
+    /// The job stops before invoking the next page of agents; invocations already started are
+    /// not interrupted.
+    #[oai(
+        path = "/bulk-invocations/:job_id/cancel",
+        method = "post",
+        operation_id = "cancel_bulk_invocation"
+    )]
+    async fn cancel_bulk_invocation(
+        &self,
+        job_id: Path<Uuid>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<BulkInvocationJob>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let record =
+            recorded_http_api_request!("cancel_bulk_invocation", job_id = job_id.0.to_string());
+
+        let response = self
+            .bulk_invocation_service
+            .cancel(job_id.0, auth)
+            .instrument(record.span.clone())
+            .await
+            .map_err(Into::into);
+
+        record.result(response).map(Json)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Enum)]
@@ -129,6 +227,21 @@ pub struct AgentInvocationRequest {
     pub idempotency_key: Option<IdempotencyKey>,
     pub deployment_revision: Option<i64>,
     pub owner_account_email: Option<String>,
+    /// Point in time after which the invocation is interrupted. Combined with the method's own
+    /// timeout, the earlier of the two applies.
+    #[oai(default)]
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
+    /// Invokes a read-only method on a temporary replay of the agent as of the given oplog
+    /// index, leaving the agent itself untouched.
+    #[oai(default)]
+    #[serde(default)]
+    pub at_oplog_index: Option<OplogIndex>,
+    /// Like `atOplogIndex`, replaying the agent up to its last oplog entry written at or before
+    /// the given time.
+    #[oai(default)]
+    #[serde(default)]
+    pub at_timestamp: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Object)]
@@ -161,3 +274,19 @@ pub struct CreateAgentResponse {
     pub agent_id: AgentId,
     pub component_revision: ComponentRevision,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInvocationRequest {
+    pub app_name: ApplicationName,
+    pub env_name: EnvironmentName,
+    pub agent_type_name: AgentTypeName,
+    /// Restricts the invoked agents of the agent type; all of them are invoked if missing
+    pub filter: Option<AgentFilter>,
+    pub method_name: String,
+    pub method_parameters: UntypedJsonDataValue,
+    pub mode: AgentInvocationMode,
+    /// Maximum number of concurrent invocations, defaults to the worker service configuration
+    pub concurrency: Option<u32>,
+}
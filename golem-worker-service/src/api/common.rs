@@ -15,6 +15,7 @@
 use crate::custom_api::error::RequestHandlerError;
 use crate::custom_api::route_resolver::RouteResolverError;
 use crate::service::auth::AuthServiceError;
+use crate::service::bulk_invocation::BulkInvocationError;
 use crate::service::component::ComponentServiceError;
 use crate::service::limit::LimitServiceError;
 use crate::service::worker::{CallWorkerExecutorError, WorkerServiceError};
@@ -239,6 +240,20 @@ impl From<WorkerServiceError> for ApiEndpointError {
     }
 }
 
+impl From<BulkInvocationError> for ApiEndpointError {
+    fn from(error: BulkInvocationError) -> Self {
+        match error {
+            BulkInvocationError::JobNotFound(_) => {
+                Self::not_found(api::error_code::BULK_INVOCATION_JOB_NOT_FOUND, error)
+            }
+            BulkInvocationError::WorkerService(inner) => inner.into(),
+            BulkInvocationError::Store(_) => {
+                Self::internal(api::error_code::INTERNAL_UNKNOWN, error)
+            }
+        }
+    }
+}
+
 impl From<LimitServiceError> for ApiEndpointError {
     fn from(error: LimitServiceError) -> Self {
         match error {
@@ -439,6 +454,23 @@ impl From<RequestHandlerError> for ApiEndpointError {
                 api::error_code::REQUEST_MULTI_VALUED_CONTENT_LANGUAGE,
                 value,
             ),
+            RequestHandlerError::UnsupportedFormContentType { .. } => Self::unsupported_media_type(
+                api::error_code::REQUEST_UNSUPPORTED_FORM_CONTENT_TYPE,
+                value,
+            ),
+            RequestHandlerError::FormBodyParsingFailed { .. } => {
+                Self::bad_request(api::error_code::REQUEST_FORM_BODY_PARSING_FAILED, value)
+            }
+            RequestHandlerError::FormBodyTooLarge { .. }
+            | RequestHandlerError::RequestBodyTooLarge { .. } => {
+                Self::payload_too_large(api::error_code::REQUEST_PAYLOAD_TOO_LARGE, value)
+            }
+            RequestHandlerError::WebSocketUpgradeFailed { .. } => {
+                Self::bad_request(api::error_code::REQUEST_WEBSOCKET_UPGRADE_FAILED, value)
+            }
+            RequestHandlerError::UnexpectedWebSocketMessage { .. } => {
+                Self::bad_request(api::error_code::REQUEST_UNEXPECTED_WEBSOCKET_MESSAGE, value)
+            }
             RequestHandlerError::ResolvingRouteFailed(
                 RouteResolverError::CouldNotGetDomainFromRequest(_),
             ) => Self::bad_request(api::error_code::REQUEST_DOMAIN_EXTRACTION_FAILED, value),
@@ -479,7 +511,8 @@ impl From<RequestHandlerError> for ApiEndpointError {
             RequestHandlerError::InternalError(_) => {
                 Self::internal(api::error_code::INTERNAL_UNKNOWN, value)
             }
-            RequestHandlerError::OpenApiSpecGenerationFailed => {
+            RequestHandlerError::OpenApiSpecGenerationFailed
+            | RequestHandlerError::GraphQlSchemaGenerationFailed { .. } => {
                 Self::internal(api::error_code::INTERNAL_UNKNOWN, value)
             }
         }
@@ -0,0 +1,102 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::common::ApiEndpointError;
+use super::worker::search_oplogs;
+use crate::model;
+use crate::service::auth::AuthService;
+use crate::service::worker::{OplogSearchScope, WorkerService};
+use golem_common::model::Timestamp;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::model::auth::GolemSecurityScheme;
+use poem_openapi::OpenApi;
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::Json;
+use std::sync::Arc;
+use tracing::Instrument;
+
+type Result<T> = std::result::Result<T, ApiEndpointError>;
+
+pub struct EnvironmentsApi {
+    worker_service: Arc<WorkerService>,
+    auth_service: Arc<dyn AuthService>,
+}
+
+#[OpenApi(prefix_path = "/v1/envs", tag = ApiTags::Worker)]
+impl EnvironmentsApi {
+    pub fn new(worker_service: Arc<WorkerService>, auth_service: Arc<dyn AuthService>) -> Self {
+        Self {
+            worker_service,
+            auth_service,
+        }
+    }
+
+    /// Search the oplogs of all workers of an environment
+    ///
+    /// Works like the component oplog search, over the workers of every deployed component of
+    /// the environment whose workers the caller can view. Components are searched one after the
+    /// other, and each page lists workers of a single component, so a page can be empty while
+    /// the returned `cursor` is set. If the `cursor` is empty, every worker has been searched.
+    #[oai(
+        path = "/:environment_id/oplog/search",
+        method = "get",
+        operation_id = "search_environment_oplogs"
+    )]
+    async fn search_environment_oplogs(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        /// Oplog query, in the same format as the single worker oplog search
+        query: Query<String>,
+        /// Only return entries created at or after this time
+        from: Query<Option<Timestamp>>,
+        /// Only return entries created at or before this time
+        to: Query<Option<Timestamp>>,
+        /// Filter for the searched workers in form of `property op value`, like in `get_workers_metadata`
+        filter: Query<Option<Vec<String>>>,
+        /// Cursor returned by the previous page, if not provided, starts from the beginning
+        cursor: Query<Option<String>>,
+        /// Number of workers listed in this page, default: 50, at most 500
+        count: Query<Option<u64>>,
+        /// Number of matches after which the page ends, default: 1000, at most 10000
+        #[oai(name = "max-results")]
+        max_results: Query<Option<u64>>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<model::SearchOplogsResponse>> {
+        let record = recorded_http_api_request!(
+            "search_environment_oplogs",
+            environment_id = environment_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = search_oplogs(
+            &self.worker_service,
+            OplogSearchScope::Environment(environment_id.0),
+            query.0,
+            from.0,
+            to.0,
+            filter.0,
+            cursor.0,
+            count.0,
+            max_results.0,
+            auth,
+        )
+        .instrument(record.span.clone())
+        .await;
+
+        record.result(response)
+    }
+}
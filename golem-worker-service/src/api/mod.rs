@@ -14,15 +14,17 @@
 
 pub mod agents;
 pub mod common;
+mod environments;
 mod worker;
 
 use crate::api::agents::AgentsApi;
+use crate::api::environments::EnvironmentsApi;
 use crate::api::worker::WorkerApi;
 use crate::bootstrap::Services;
 use golem_service_base::api::HealthcheckApi;
 use poem_openapi::OpenApiService;
 
-pub type Apis = (HealthcheckApi, WorkerApi, AgentsApi);
+pub type Apis = (HealthcheckApi, WorkerApi, AgentsApi, EnvironmentsApi);
 
 pub fn make_open_api_service(services: &Services) -> OpenApiService<Apis, ()> {
     OpenApiService::new(
@@ -34,6 +36,11 @@ pub fn make_open_api_service(services: &Services) -> OpenApiService<Apis, ()> {
                 services.auth_service.clone(),
             ),
             AgentsApi::new(
+                services.worker_service.clone(),
+                services.bulk_invocation_service.clone(),
+                services.auth_service.clone(),
+            ),
+            EnvironmentsApi::new(
                 services.worker_service.clone(),
                 services.auth_service.clone(),
             ),
@@ -17,7 +17,10 @@ use crate::model;
 use crate::service::auth::AuthService;
 use crate::service::component::ComponentService;
 use crate::service::worker::ConnectWorkerStream;
-use crate::service::worker::{WorkerService, proxy_worker_connection};
+use crate::service::worker::{
+    OPLOG_SEARCH_DEFAULT_AGENT_COUNT, OPLOG_SEARCH_DEFAULT_MAX_RESULTS, OplogSearchCursor,
+    OplogSearchScope, WorkerService, WorkerServiceError, proxy_worker_connection,
+};
 use futures::StreamExt;
 use futures::TryStreamExt;
 use golem_common::base_model::api;
@@ -27,7 +30,7 @@ use golem_common::model::component::{CanonicalFilePath, ComponentId, PluginPrior
 use golem_common::model::oplog::OplogCursor;
 use golem_common::model::oplog::OplogIndex;
 use golem_common::model::worker::{AgentCreationRequest, AgentMetadataDto, RevertWorkerTarget};
-use golem_common::model::{AgentFilter, AgentId, IdempotencyKey, ScanCursor};
+use golem_common::model::{AgentFilter, AgentId, IdempotencyKey, ScanCursor, Timestamp};
 use golem_common::{SafeDisplay, recorded_http_api_request};
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::model::auth::{AuthCtx, GolemSecurityScheme, WrappedGolemSecuritySchema};
@@ -660,6 +663,116 @@ impl WorkerApi {
         Ok(Json(response))
     }
 
+    /// Search the oplogs of all workers of a component
+    ///
+    /// Runs the same query language as the `query` parameter of the single worker oplog endpoint
+    /// over every worker of the component. Each page lists up to `count` workers, searches their
+    /// oplogs and returns their matching entries, optionally restricted to entries created between
+    /// `from` and `to`. A page stops early once it holds about `max-results` matches. To get the
+    /// next page, pass the returned `cursor`; if it is empty, every worker has been searched.
+    #[oai(
+        path = "/:component_id/oplog/search",
+        method = "get",
+        operation_id = "search_component_oplogs"
+    )]
+    async fn search_component_oplogs(
+        &self,
+        component_id: Path<ComponentId>,
+        /// Oplog query, in the same format as the single worker oplog search
+        query: Query<String>,
+        /// Only return entries created at or after this time
+        from: Query<Option<Timestamp>>,
+        /// Only return entries created at or before this time
+        to: Query<Option<Timestamp>>,
+        /// Filter for the searched workers in form of `property op value`, like in `get_workers_metadata`
+        filter: Query<Option<Vec<String>>>,
+        /// Cursor returned by the previous page, if not provided, starts from the beginning
+        cursor: Query<Option<String>>,
+        /// Number of workers listed in this page, default: 50, at most 500
+        count: Query<Option<u64>>,
+        /// Number of matches after which the page ends, default: 1000, at most 10000
+        #[oai(name = "max-results")]
+        max_results: Query<Option<u64>>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<model::SearchOplogsResponse>> {
+        let record = recorded_http_api_request!(
+            "search_component_oplogs",
+            component_id = component_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = search_oplogs(
+            &self.worker_service,
+            OplogSearchScope::Component(component_id.0),
+            query.0,
+            from.0,
+            to.0,
+            filter.0,
+            cursor.0,
+            count.0,
+            max_results.0,
+            auth,
+        )
+        .instrument(record.span.clone())
+        .await;
+
+        record.result(response)
+    }
+
+    /// Get the lifecycle policy actions applied to the workers of a component
+    ///
+    /// Returns the automatic deletions and archivals done by the agent types' lifecycle policies
+    /// between `from` and `to`, oldest first. To get the next page, pass the returned `next_from`
+    /// as `from`; if it is empty, all entries have been returned. Entries are kept for the
+    /// executors' configured audit retention period.
+    #[oai(
+        path = "/:component_id/lifecycle-audit",
+        method = "get",
+        operation_id = "get_lifecycle_audit"
+    )]
+    async fn get_lifecycle_audit(
+        &self,
+        component_id: Path<ComponentId>,
+        /// Only return entries recorded at or after this time
+        from: Query<Option<Timestamp>>,
+        /// Only return entries recorded at or before this time, default: now
+        to: Query<Option<Timestamp>>,
+        /// Maximum number of entries returned in this page, default: 100, maximum: 1000
+        count: Query<Option<u64>>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<model::LifecycleAuditResponse>> {
+        let record = recorded_http_api_request!(
+            "get_lifecycle_audit",
+            component_id = component_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_lifecycle_audit_internal(component_id.0, from.0, to.0, count.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_lifecycle_audit_internal(
+        &self,
+        component_id: ComponentId,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        count: Option<u64>,
+        auth: AuthCtx,
+    ) -> Result<Json<model::LifecycleAuditResponse>> {
+        let (entries, next_from) = self
+            .worker_service
+            .get_lifecycle_audit(component_id, from, to, count, auth)
+            .await?;
+
+        Ok(Json(model::LifecycleAuditResponse { entries, next_from }))
+    }
+
     /// List files in a worker
     #[oai(
         path = "/:component_id/workers/:agent_name/files/:file_name",
@@ -943,6 +1056,104 @@ impl WorkerApi {
         Ok(Json(ForkWorkerResponse {}))
     }
 
+    /// Export an agent
+    ///
+    /// Exports the agent's full oplog, its externally stored payloads, its filesystem checkpoint,
+    /// metadata and the referenced component revisions into a portable archive that can be
+    /// imported into another environment. The archive is streamed as it is produced.
+    ///
+    /// Key-value buckets and blob store containers belong to the environment and are shared by
+    /// its agents, so they are not part of the archive, and agents that used them can't be
+    /// exported.
+    #[oai(
+        path = "/:component_id/workers/:agent_name/export",
+        method = "get",
+        operation_id = "export_agent"
+    )]
+    async fn export_agent(
+        &self,
+        component_id: Path<ComponentId>,
+        agent_name: Path<String>,
+        token: GolemSecurityScheme,
+    ) -> Result<Binary<Body>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let agent_id = self
+            .normalize_agent_id(component_id.0, agent_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!("export_agent", agent_id = agent_id.to_string());
+
+        let response = self
+            .export_agent_internal(agent_id, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn export_agent_internal(
+        &self,
+        agent_id: AgentId,
+        auth: AuthCtx,
+    ) -> Result<Binary<Body>> {
+        let archive = self.worker_service.export_agent(&agent_id, auth).await?;
+
+        Ok(Binary(Body::from_bytes_stream(
+            archive.map_err(|e| std::io::Error::other(e.to_string())),
+        )))
+    }
+
+    /// Import an agent
+    ///
+    /// Recreates an agent from an archive created by the export endpoint. Every component revision
+    /// referenced by the archived oplog must exist for the target component and must be built
+    /// from the same WASM as the exported one.
+    #[oai(
+        path = "/:component_id/workers/:agent_name/import",
+        method = "post",
+        operation_id = "import_agent"
+    )]
+    async fn import_agent(
+        &self,
+        component_id: Path<ComponentId>,
+        agent_name: Path<String>,
+        archive: Binary<Body>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<ImportAgentResponse>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let agent_id = self
+            .normalize_agent_id(component_id.0, agent_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!("import_agent", agent_id = agent_id.to_string());
+
+        let response = self
+            .import_agent_internal(agent_id, archive.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn import_agent_internal(
+        &self,
+        agent_id: AgentId,
+        archive: Body,
+        auth: AuthCtx,
+    ) -> Result<Json<ImportAgentResponse>> {
+        let archive = archive.into_bytes_stream().map_err(|err| {
+            WorkerServiceError::Internal(format!("Failed to receive agent archive: {err}"))
+        });
+
+        self.worker_service
+            .import_agent(&agent_id, Box::pin(archive), auth)
+            .await?;
+
+        Ok(Json(ImportAgentResponse {}))
+    }
+
     /// Cancels a pending invocation if it has not started yet
     ///
     /// The invocation to be cancelled is identified by the idempotency key passed to the invoke API.
@@ -1055,7 +1266,7 @@ impl WorkerApi {
         agent_id: AgentId,
         auth: AuthCtx,
     ) -> Result<ConnectWorkerStream> {
-        let stream = self.worker_service.connect(&agent_id, auth).await?;
+        let stream = self.worker_service.connect(&agent_id, auth, None).await?;
         Ok(stream)
     }
 
@@ -1143,6 +1354,80 @@ fn make_component_file_path(name: String) -> Result<CanonicalFilePath> {
     })
 }
 
+/// Validates the parameters of a cross-agent oplog search and runs it, shared by the component
+/// and environment scoped endpoints.
+pub(super) async fn search_oplogs(
+    worker_service: &WorkerService,
+    scope: OplogSearchScope,
+    query: String,
+    from: Option<Timestamp>,
+    to: Option<Timestamp>,
+    filter: Option<Vec<String>>,
+    cursor: Option<String>,
+    count: Option<u64>,
+    max_results: Option<u64>,
+    auth: AuthCtx,
+) -> Result<Json<model::SearchOplogsResponse>> {
+    golem_common::model::lucene::Query::parse(&query).map_err(|e| {
+        ApiEndpointError::bad_request(
+            api::error_code::INVALID_OPLOG_QUERY_PARAMS,
+            golem_common::safe(format!("Invalid oplog query: {e}")),
+        )
+    })?;
+
+    let filter = match filter {
+        Some(filters) if !filters.is_empty() => Some(AgentFilter::from(filters).map_err(|e| {
+            ApiEndpointError::bad_request(
+                api::error_code::INVALID_AGENT_FILTER,
+                golem_common::safe(e),
+            )
+        })?),
+        _ => None,
+    };
+
+    let cursor = match cursor {
+        Some(cursor) => {
+            let cursor = OplogSearchCursor::from_token(&cursor).map_err(|e| {
+                ApiEndpointError::bad_request(
+                    api::error_code::INVALID_SCAN_CURSOR,
+                    golem_common::safe(e),
+                )
+            })?;
+            if let OplogSearchScope::Component(component_id) = scope
+                && cursor.component_id != component_id
+            {
+                return Err(ApiEndpointError::bad_request(
+                    api::error_code::INVALID_SCAN_CURSOR,
+                    golem_common::safe(
+                        "The cursor belongs to a search of another component".to_string(),
+                    ),
+                ));
+            }
+            Some(cursor)
+        }
+        None => None,
+    };
+
+    let (matches, cursor) = worker_service
+        .search_oplogs(
+            scope,
+            filter,
+            query,
+            from,
+            to,
+            cursor,
+            count.unwrap_or(OPLOG_SEARCH_DEFAULT_AGENT_COUNT),
+            max_results.unwrap_or(OPLOG_SEARCH_DEFAULT_MAX_RESULTS),
+            auth,
+        )
+        .await?;
+
+    Ok(Json(model::SearchOplogsResponse {
+        matches,
+        cursor: cursor.map(|cursor| cursor.to_token()),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::normalize_agent_name_with_latest_component;
@@ -1183,6 +1468,7 @@ mod tests {
                 output_schema: DataSchema::Tuple(NamedElementSchemas::empty()),
                 http_endpoint: vec![],
                 read_only: None,
+                timeout_nanos: None,
             }],
             dependencies: vec![],
             mode,
@@ -17,15 +17,23 @@ use crate::custom_api::api_definition_lookup::{
     HttpApiDefinitionsLookup, RegistryServiceApiDefinitionsLookup,
 };
 use crate::custom_api::call_agent::CallAgentHandler;
+use crate::custom_api::call_agent::response_cache::ResponseCache;
+use crate::custom_api::graphql::GraphQlHandler;
 use crate::custom_api::oidc::handler::OidcHandler;
 use crate::custom_api::oidc::session_store::{RedisSessionStore, SessionStore, SqliteSessionStore};
 use crate::custom_api::oidc::{DefaultIdentityProvider, IdentityProvider};
+use crate::custom_api::rate_limit::RateLimiter;
 use crate::custom_api::request_handler::RequestHandler;
 use crate::custom_api::route_resolver::RouteResolver;
 use crate::custom_api::webhooks::WebhookCallbackHandler;
 use crate::mcp::{McpCapabilityLookup, RegistryServiceMcpCapabilityLookup};
 use crate::service::agent_resolution_cache::AgentResolutionCache;
+use crate::service::agent_write_subscriber::AgentWriteSubscriber;
 use crate::service::auth::{AuthService, RemoteAuthService};
+use crate::service::bulk_invocation::{
+    BulkInvocationJobStore, BulkInvocationService, RedisBulkInvocationJobStore,
+    SqliteBulkInvocationJobStore,
+};
 use crate::service::component::{ComponentService, RemoteComponentService};
 use crate::service::limit::{LimitService, RemoteLimitService};
 use crate::service::worker::{WorkerClient, WorkerExecutorWorkerClient, WorkerService};
@@ -52,6 +60,8 @@ pub struct Services {
     pub route_resolver: Arc<RouteResolver>,
     pub identity_provider: Arc<dyn IdentityProvider>,
     pub session_store: Arc<dyn SessionStore>,
+    pub bulk_invocation_service: Arc<BulkInvocationService>,
+    pub agent_write_subscriber: Option<Arc<AgentWriteSubscriber>>,
 }
 
 impl Services {
@@ -119,6 +129,7 @@ impl Services {
 
         let route_resolver = Arc::new(RouteResolver::new(
             &config.route_resolver,
+            &config.graphql,
             api_definition_lookup_service.clone(),
         ));
 
@@ -126,35 +137,63 @@ impl Services {
             registry_service_client.clone(),
         ));
 
-        let call_agent_handler = Arc::new(CallAgentHandler::new(worker_service.clone()));
+        let response_cache = config
+            .response_cache
+            .enabled
+            .then(|| Arc::new(ResponseCache::new(&config.response_cache)));
+
+        let agent_write_subscriber = response_cache.clone().map(|response_cache| {
+            Arc::new(AgentWriteSubscriber::new(
+                routing_table_service.clone(),
+                worker_executor_clients.clone(),
+                response_cache,
+            ))
+        });
+
+        let call_agent_handler = Arc::new(CallAgentHandler::new(
+            worker_service.clone(),
+            response_cache,
+            config.form_body.clone(),
+        ));
 
         let identity_provider = Arc::new(DefaultIdentityProvider);
 
-        let session_store: Arc<dyn SessionStore> = match &config.gateway_session_storage {
+        // Bulk invocation jobs are persisted in the same storage as the gateway sessions
+        let (session_store, bulk_invocation_job_store): (
+            Arc<dyn SessionStore>,
+            Arc<dyn BulkInvocationJobStore>,
+        ) = match &config.gateway_session_storage {
             SessionStoreConfig::Redis(inner) => {
                 let redis = RedisPool::configured(&inner.redis_config).await?;
 
                 let session_store = RedisSessionStore::new(
-                    redis,
+                    redis.clone(),
                     fred::types::Expiration::EX(
                         inner.pending_login_expiration.as_secs().try_into()?,
                     ),
                 );
 
-                Arc::new(session_store)
+                (
+                    Arc::new(session_store),
+                    Arc::new(RedisBulkInvocationJobStore::new(redis)),
+                )
             }
 
             SessionStoreConfig::Sqlite(inner) => {
+                SqliteBulkInvocationJobStore::migrate(&inner.sqlite_config).await?;
                 let pool = SqlitePool::configured(&inner.sqlite_config).await?;
 
                 let gateway_session_with_sqlite = SqliteSessionStore::new(
-                    pool,
+                    pool.clone(),
                     inner.pending_login_expiration.as_secs().try_into()?,
                     inner.cleanup_interval,
                 )
                 .await?;
 
-                Arc::new(gateway_session_with_sqlite)
+                (
+                    Arc::new(gateway_session_with_sqlite),
+                    Arc::new(SqliteBulkInvocationJobStore::new(pool)),
+                )
             }
         };
 
@@ -168,11 +207,29 @@ impl Services {
             config.webhook_callback_handler.hmac_key.0.clone(),
         ));
 
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.trusted_proxies.clone()));
+
+        let graphql_handler = Arc::new(GraphQlHandler::new(
+            worker_service.clone(),
+            rate_limiter.clone(),
+            config.form_body.clone(),
+        ));
+
         let request_handler = Arc::new(RequestHandler::new(
             route_resolver.clone(),
             call_agent_handler.clone(),
             oidc_handler.clone(),
             webhook_callback_handler.clone(),
+            graphql_handler,
+            rate_limiter,
+        ));
+
+        let bulk_invocation_service = Arc::new(BulkInvocationService::new(
+            worker_service.clone(),
+            component_service.clone(),
+            agent_resolution_cache.clone(),
+            bulk_invocation_job_store,
+            config.bulk_invocation.clone(),
         ));
 
         Ok(Self {
@@ -187,6 +244,8 @@ impl Services {
             route_resolver,
             identity_provider,
             session_store,
+            bulk_invocation_service,
+            agent_write_subscriber,
         })
     }
 }
@@ -26,6 +26,7 @@ use golem_service_base::grpc::server::GrpcServerTlsConfig;
 use golem_service_base::service::routing_table::RoutingTableConfig;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Write};
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -37,6 +38,8 @@ pub struct WorkerServiceConfig {
     pub port: u16,
     pub custom_request_port: u16,
     pub grpc: GrpcApiConfig,
+    #[serde(default)]
+    pub agent_grpc: AgentGrpcApiConfig,
     pub shard_manager: GrpcShardManagerConfig,
     pub routing_table: RoutingTableConfig,
     pub worker_executor: WorkerExecutorClientConfig,
@@ -45,11 +48,21 @@ pub struct WorkerServiceConfig {
     pub mcp_port: u16,
     pub cors_origin_regex: String,
     pub route_resolver: RouteResolverConfig,
+    #[serde(default)]
+    pub graphql: GraphQlConfig,
     pub component_service: ComponentServiceConfig,
     pub auth_service: AuthServiceConfig,
     pub webhook_callback_handler: WebhookCallbackHandlerConfig,
     #[serde(default)]
     pub agent_resolution_cache: AgentResolutionCacheConfig,
+    #[serde(default)]
+    pub bulk_invocation: BulkInvocationConfig,
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+    #[serde(default)]
+    pub form_body: FormBodyConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
 }
 
 impl WorkerServiceConfig {
@@ -81,6 +94,9 @@ impl SafeDisplay for WorkerServiceConfig {
         let _ = writeln!(&mut result, "grpc:");
         let _ = writeln!(&mut result, "{}", self.grpc.to_safe_string_indented());
 
+        let _ = writeln!(&mut result, "agent grpc:");
+        let _ = writeln!(&mut result, "{}", self.agent_grpc.to_safe_string_indented());
+
         let _ = writeln!(&mut result, "shard manager:");
         let _ = writeln!(
             &mut result,
@@ -113,6 +129,9 @@ impl SafeDisplay for WorkerServiceConfig {
             self.route_resolver.to_safe_string_indented()
         );
 
+        let _ = writeln!(&mut result, "graphql:");
+        let _ = writeln!(&mut result, "{}", self.graphql.to_safe_string_indented());
+
         let _ = writeln!(&mut result, "component service:");
         let _ = writeln!(
             &mut result,
@@ -141,6 +160,26 @@ impl SafeDisplay for WorkerServiceConfig {
             self.agent_resolution_cache.to_safe_string_indented()
         );
 
+        let _ = writeln!(&mut result, "bulk invocation:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.bulk_invocation.to_safe_string_indented()
+        );
+
+        let _ = writeln!(&mut result, "response cache:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.response_cache.to_safe_string_indented()
+        );
+
+        let _ = writeln!(&mut result, "form body:");
+        let _ = writeln!(&mut result, "{}", self.form_body.to_safe_string_indented());
+
+        let _ = writeln!(&mut result, "rate limit:");
+        let _ = writeln!(&mut result, "{}", self.rate_limit.to_safe_string_indented());
+
         result
     }
 }
@@ -155,6 +194,7 @@ impl Default for WorkerServiceConfig {
             custom_request_port: 9006,
             mcp_port: 9007,
             grpc: GrpcApiConfig::default(),
+            agent_grpc: AgentGrpcApiConfig::default(),
             shard_manager: GrpcShardManagerConfig::default(),
             routing_table: RoutingTableConfig::default(),
             worker_executor: WorkerExecutorClientConfig::default(),
@@ -162,10 +202,15 @@ impl Default for WorkerServiceConfig {
             registry_service: GrpcRegistryServiceConfig::default(),
             cors_origin_regex: "https://*.golem.cloud".to_string(),
             route_resolver: RouteResolverConfig::default(),
+            graphql: GraphQlConfig::default(),
             component_service: ComponentServiceConfig::default(),
             auth_service: AuthServiceConfig::default(),
             webhook_callback_handler: WebhookCallbackHandlerConfig::default(),
             agent_resolution_cache: AgentResolutionCacheConfig::default(),
+            bulk_invocation: BulkInvocationConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            form_body: FormBodyConfig::default(),
+            rate_limit: RateLimitConfig::default(),
         }
     }
 }
@@ -212,6 +257,42 @@ impl Default for GrpcApiConfig {
     }
 }
 
+/// gRPC server presenting each deployed agent type as a gRPC service.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentGrpcApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub tls: GrpcServerTlsConfig,
+    #[serde(default = "default_grpc_max_message_size")]
+    pub max_message_size: usize,
+}
+
+impl SafeDisplay for AgentGrpcApiConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "port: {}", self.port);
+        let _ = writeln!(&mut result, "max_message_size: {}", self.max_message_size);
+
+        let _ = writeln!(&mut result, "tls:");
+        let _ = writeln!(&mut result, "{}", self.tls.to_safe_string_indented());
+
+        result
+    }
+}
+
+impl Default for AgentGrpcApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9008,
+            tls: GrpcServerTlsConfig::disabled(),
+            max_message_size: default_grpc_max_message_size(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "config")]
 pub enum SessionStoreConfig {
@@ -356,6 +437,116 @@ impl Default for AgentResolutionCacheConfig {
     }
 }
 
+/// Limits of the operations accepted by the GraphQL endpoints of http api deployments.
+/// Operations exceeding them are rejected before any agent is invoked.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GraphQlConfig {
+    /// Maximum nesting depth of the selection sets
+    pub max_depth: usize,
+    /// Maximum number of fields selected, counting every field once
+    pub max_complexity: usize,
+}
+
+impl SafeDisplay for GraphQlConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "max_depth: {}", self.max_depth);
+        let _ = writeln!(&mut result, "max_complexity: {}", self.max_complexity);
+        result
+    }
+}
+
+impl Default for GraphQlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 16,
+            max_complexity: 256,
+        }
+    }
+}
+
+/// Gateway-side cache for the responses of read-only agent methods bound to
+/// `GET` / `HEAD` routes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    pub enabled: bool,
+    pub max_capacity: usize,
+    /// Entries that are not accessed for this long are evicted
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+    #[serde(with = "humantime_serde")]
+    pub eviction_period: Duration,
+}
+
+impl SafeDisplay for ResponseCacheConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "max_capacity: {}", self.max_capacity);
+        let _ = writeln!(&mut result, "ttl: {:?}", self.ttl);
+        let _ = writeln!(&mut result, "eviction_period: {:?}", self.eviction_period);
+        result
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_capacity: 10_000,
+            ttl: Duration::from_mins(10),
+            eviction_period: Duration::from_mins(1),
+        }
+    }
+}
+
+/// Limits of the bulk invocation jobs. The jobs themselves are persisted in the
+/// `gateway_session_storage`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BulkInvocationConfig {
+    /// Number of parallel invocations of a job if the request does not specify it
+    pub default_concurrency: u32,
+    /// Upper limit of the number of parallel invocations a job can request
+    pub max_concurrency: u32,
+    /// Number of agents enumerated at once; the progress of a job is persisted after each page
+    pub page_size: u64,
+    /// How long a running job stays leased to the instance running it without a heartbeat.
+    /// The lease is renewed every third of this period.
+    #[serde(with = "humantime_serde")]
+    pub lease_ttl: Duration,
+    /// How often the running jobs are checked for expired leases to resume them
+    #[serde(with = "humantime_serde")]
+    pub resume_interval: Duration,
+}
+
+impl SafeDisplay for BulkInvocationConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "default_concurrency: {}",
+            self.default_concurrency
+        );
+        let _ = writeln!(&mut result, "max_concurrency: {}", self.max_concurrency);
+        let _ = writeln!(&mut result, "page_size: {}", self.page_size);
+        let _ = writeln!(&mut result, "lease_ttl: {:?}", self.lease_ttl);
+        let _ = writeln!(&mut result, "resume_interval: {:?}", self.resume_interval);
+        result
+    }
+}
+
+impl Default for BulkInvocationConfig {
+    fn default() -> Self {
+        Self {
+            default_concurrency: 8,
+            max_concurrency: 64,
+            page_size: 100,
+            lease_ttl: Duration::from_mins(1),
+            resume_interval: Duration::from_secs(30),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ComponentServiceConfig {
     pub component_cache_max_capacity: usize,
@@ -539,3 +730,48 @@ mod tests {
             .expect("Failed to load config");
     }
 }
+
+/// Size limits of `multipart/form-data` and `application/x-www-form-urlencoded` request bodies.
+/// Bodies are read in a streaming way and rejected as soon as a limit is exceeded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FormBodyConfig {
+    /// Maximum size of the whole form body in bytes
+    pub max_body_size: usize,
+    /// Maximum size of a single file part in bytes
+    pub max_file_size: usize,
+}
+
+impl SafeDisplay for FormBodyConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "max_body_size: {}", self.max_body_size);
+        let _ = writeln!(&mut result, "max_file_size: {}", self.max_file_size);
+        result
+    }
+}
+
+impl Default for FormBodyConfig {
+    fn default() -> Self {
+        Self {
+            max_body_size: 16 * 1024 * 1024,
+            max_file_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+/// Settings of the rate limits of http api deployment routes.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Addresses of the reverse proxies in front of the custom request port. The
+    /// `X-Forwarded-For` header is only taken into account for connections coming from
+    /// one of these, otherwise clients are keyed by the peer address of the connection.
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl SafeDisplay for RateLimitConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "trusted_proxies: {:?}", self.trusted_proxies);
+        result
+    }
+}
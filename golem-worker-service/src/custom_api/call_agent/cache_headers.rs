@@ -23,6 +23,9 @@
 //! can produce a `304 Not Modified` response without invoking the executor
 //! when the supplied `ETag` matches the agent's current oplog index.
 //!
+//! The same responses are also cached by the worker-service itself, see
+//! [`super::response_cache`].
+//!
 //! See [#3392](https://github.com/golemcloud/golem/issues/3392) and
 //! [`read-only-agent-methods.md`](../../../../read-only-agent-methods.md) for
 //! the full design.
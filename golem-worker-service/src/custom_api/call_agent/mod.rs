@@ -14,7 +14,9 @@
 
 mod cache_headers;
 mod parameter_parsing;
+pub mod response_cache;
 mod response_mapping;
+mod websocket;
 
 use self::cache_headers::{
     add_vary_header, build_cache_control_value, build_etag_value, headers as cache_header,
@@ -24,12 +26,14 @@ use self::parameter_parsing::{
     parse_path_segment_value, parse_path_segment_value_to_component_model,
     parse_query_or_header_value,
 };
+use self::response_cache::{CachedResponse, ResponseCache, ResponseCacheKey};
 use self::response_mapping::interpret_agent_response;
 use super::RichRequest;
 use super::error::RequestHandlerError;
 use super::model::{ResponseBody, RichRouteSecurity};
 use super::route_resolver::ResolvedRouteEntry;
 use super::{ParsedRequestBody, RouteExecutionResult};
+use crate::config::FormBodyConfig;
 use crate::service::worker::WorkerService;
 use anyhow::anyhow;
 use golem_common::model::OplogIndex;
@@ -39,22 +43,36 @@ use golem_common::model::agent::{
     TextReferenceValue, UntypedDataValue, UntypedElementValue,
 };
 use golem_common::model::{AgentFingerprint, AgentId, IdempotencyKey};
-use golem_service_base::custom_api::{CallAgentBehaviour, ConstructorParameter, MethodParameter};
+use golem_service_base::custom_api::{
+    CallAgentBehaviour, ConstructorParameter, FormFieldType, MethodParameter,
+};
+use golem_service_base::model::SafeIndex;
 use golem_service_base::model::auth::AuthCtx;
 use golem_wasm::ValueAndType;
 use http::{Method, StatusCode};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
 pub struct CallAgentHandler {
     worker_service: Arc<WorkerService>,
+    response_cache: Option<Arc<ResponseCache>>,
+    form_body_limits: FormBodyConfig,
 }
 
 impl CallAgentHandler {
-    pub fn new(worker_service: Arc<WorkerService>) -> Self {
-        Self { worker_service }
+    pub fn new(
+        worker_service: Arc<WorkerService>,
+        response_cache: Option<Arc<ResponseCache>>,
+        form_body_limits: FormBodyConfig,
+    ) -> Self {
+        Self {
+            worker_service,
+            response_cache,
+            form_body_limits,
+        }
     }
 
     pub async fn handle_call_agent_behaviour(
@@ -67,6 +85,34 @@ impl CallAgentHandler {
 
         let request_method = request.underlying.method().clone();
 
+        // Responses of read-only methods bound to GET/HEAD routes with a
+        // revalidatable cache policy are cached by the gateway; a still valid
+        // cached response is served without invoking the agent.
+        let response_cache_key = match behaviour.read_only.as_ref() {
+            Some(read_only)
+                if self.response_cache.is_some()
+                    && is_cacheable_method(&request_method)
+                    && supports_http_revalidation(read_only) =>
+            {
+                ResponseCacheKey::for_request(
+                    request,
+                    resolved_route,
+                    behaviour,
+                    &agent_id,
+                    read_only,
+                )
+            }
+            _ => None,
+        };
+
+        if let Some(key) = &response_cache_key
+            && let Some(cached) = self
+                .try_serve_from_response_cache(request, key, &agent_id, &request_method)
+                .await?
+        {
+            return Ok(cached);
+        }
+
         // For read-only methods bound to GET/HEAD routes with a revalidatable
         // cache policy, try to serve a `304 Not Modified` without invoking the
         // executor when the client's `If-None-Match` value matches the agent's
@@ -84,7 +130,7 @@ impl CallAgentHandler {
         }
 
         let parsed_body = request
-            .parse_request_body(&resolved_route.route.body)
+            .parse_request_body(&resolved_route.route.body, &self.form_body_limits)
             .await?;
 
         let method_params =
@@ -103,6 +149,12 @@ impl CallAgentHandler {
             tracing: Some(request.invocation_context().into()),
         });
 
+        if let Some(response_cache) = &self.response_cache
+            && response_cache_key.is_some()
+        {
+            response_cache.track_agent(&agent_id).await;
+        }
+
         let principal = principal_from_request(request)?;
         debug!("Using principal for invocation: {principal:?}");
         let proto_principal: golem_api_grpc::proto::golem::component::Principal = principal.into();
@@ -143,6 +195,16 @@ impl CallAgentHandler {
             _ => None,
         };
 
+        // Read-your-writes: the write event of the executor may arrive after
+        // the caller's next read, so the written agent's cached responses are
+        // dropped right away.
+        if behaviour.read_only.is_none()
+            && let Some(response_cache) = &self.response_cache
+            && let Some(idx) = oplog_index
+        {
+            response_cache.agent_written(&agent_id, idx).await;
+        }
+
         let mut route_result =
             interpret_agent_response(agent_result, &behaviour.expected_agent_response)?;
 
@@ -162,6 +224,24 @@ impl CallAgentHandler {
                 resolved_route,
             );
 
+            if let Some(response_cache) = &self.response_cache
+                && let Some(key) = response_cache_key
+                && let (Some(idx), Some(fp)) = (oplog_index, agent_fingerprint)
+            {
+                response_cache
+                    .insert(
+                        key,
+                        CachedResponse::new(
+                            route_result.clone(),
+                            fp,
+                            idx,
+                            read_only,
+                            Instant::now(),
+                        ),
+                    )
+                    .await;
+            }
+
             if request_method == Method::HEAD {
                 route_result.body = ResponseBody::NoBody;
             }
@@ -172,6 +252,66 @@ impl CallAgentHandler {
         Ok(route_result)
     }
 
+    /// Serves the request from the gateway's response cache if it holds a
+    /// response that is still valid: `Ttl` responses until they expire, and
+    /// `UntilWrite` responses until the agent is written. Writes drop the
+    /// cached responses through the executors' write events; only while those
+    /// are not received is the agent's current `(fingerprint, oplog index)`
+    /// pair fetched and compared to the one the response was produced at.
+    /// Stale responses are dropped. A matching `If-None-Match` is answered
+    /// with `304 Not Modified`.
+    async fn try_serve_from_response_cache(
+        &self,
+        request: &RichRequest,
+        key: &ResponseCacheKey,
+        agent_id: &AgentId,
+        request_method: &Method,
+    ) -> Result<Option<RouteExecutionResult>, RequestHandlerError> {
+        let Some(response_cache) = &self.response_cache else {
+            return Ok(None);
+        };
+        let Some(cached) = response_cache.get(key).await else {
+            return Ok(None);
+        };
+
+        let now = Instant::now();
+        let is_valid = if cached.is_revalidated_by_ttl() {
+            !cached.is_expired(now)
+        } else if response_cache.is_invalidated_by_writes() {
+            true
+        } else {
+            self.fetch_current_validator(agent_id).await?
+                == Some((cached.agent_fingerprint, cached.oplog_index))
+        };
+
+        if !is_valid {
+            debug!("Dropping stale cached response of agent {agent_id}");
+            response_cache.remove(key).await;
+            return Ok(None);
+        }
+
+        let not_modified = request
+            .headers()
+            .get(&cache_header::IF_NONE_MATCH)
+            .and_then(|h| h.to_str().ok())
+            .is_some_and(|raw| {
+                if_none_match_hits(
+                    &parse_if_none_match_entries(raw),
+                    agent_id,
+                    cached.agent_fingerprint,
+                    cached.oplog_index,
+                )
+            });
+
+        debug!("Serving cached response of agent {agent_id}");
+
+        Ok(Some(cached.to_route_result(
+            now,
+            not_modified,
+            *request_method == Method::HEAD,
+        )))
+    }
+
     /// Pre-invocation `If-None-Match` revalidation. When the request supplies
     /// an `If-None-Match` header containing the ETag for the agent's current
     /// `(fingerprint, oplog index)` pair, we short-circuit with
@@ -303,121 +443,170 @@ impl CallAgentHandler {
         resolved_route: &ResolvedRouteEntry,
         request: &RichRequest,
         behaviour: &CallAgentBehaviour,
-        mut body: ParsedRequestBody,
+        body: ParsedRequestBody,
     ) -> Result<Vec<UntypedElementValue>, RequestHandlerError> {
-        let query_params = request.query_params();
-        let headers = request.headers();
+        let request_arguments = resolve_request_arguments(resolved_route, request, behaviour)?;
+        resolve_body_arguments(resolved_route, behaviour, request_arguments, body)
+    }
+}
 
-        let mut values = Vec::with_capacity(behaviour.method_parameters.len());
+/// Resolves the method parameters bound to the request path, query parameters and headers.
+/// Parameters bound to the request body are left as `None`, see [`resolve_body_arguments`].
+fn resolve_request_arguments(
+    resolved_route: &ResolvedRouteEntry,
+    request: &RichRequest,
+    behaviour: &CallAgentBehaviour,
+) -> Result<Vec<Option<UntypedElementValue>>, RequestHandlerError> {
+    let query_params = request.query_params();
+    let headers = request.headers();
 
-        for param in &behaviour.method_parameters {
-            let value = match param {
-                MethodParameter::Path {
-                    path_segment_index,
-                    parameter_type,
-                } => {
-                    let raw = resolved_route.captured_path_parameters
-                        [usize::from(*path_segment_index)]
+    let mut values = Vec::with_capacity(behaviour.method_parameters.len());
+
+    for param in &behaviour.method_parameters {
+        let value = match param {
+            MethodParameter::Path {
+                path_segment_index,
+                parameter_type,
+            } => {
+                let raw = resolved_route.captured_path_parameters[usize::from(*path_segment_index)]
                     .clone();
 
-                    parse_path_segment_value(raw, parameter_type)?
-                }
+                Some(parse_path_segment_value(raw, parameter_type)?)
+            }
 
-                MethodParameter::Query {
-                    query_parameter_name,
-                    parameter_type,
-                } => {
-                    let empty = Vec::new();
-                    let vals = query_params.get(query_parameter_name).unwrap_or(&empty);
+            MethodParameter::Query {
+                query_parameter_name,
+                parameter_type,
+            } => {
+                let empty = Vec::new();
+                let vals = query_params.get(query_parameter_name).unwrap_or(&empty);
 
-                    parse_query_or_header_value(vals, parameter_type)?
-                }
+                Some(parse_query_or_header_value(vals, parameter_type)?)
+            }
 
-                MethodParameter::Header {
-                    header_name,
-                    parameter_type,
-                } => {
-                    let vals = headers
-                        .get_all(header_name)
-                        .iter()
-                        .map(|h| {
-                            h.to_str().map(String::from).map_err(|_| {
-                                RequestHandlerError::HeaderIsNotAscii {
-                                    header_name: header_name.clone(),
-                                }
-                            })
+            MethodParameter::Header {
+                header_name,
+                parameter_type,
+            } => {
+                let vals = headers
+                    .get_all(header_name)
+                    .iter()
+                    .map(|h| {
+                        h.to_str().map(String::from).map_err(|_| {
+                            RequestHandlerError::HeaderIsNotAscii {
+                                header_name: header_name.clone(),
+                            }
                         })
-                        .collect::<Result<Vec<_>, _>>()?;
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Some(parse_query_or_header_value(&vals, parameter_type)?)
+            }
+
+            MethodParameter::JsonObjectBodyField { .. }
+            | MethodParameter::FormField { .. }
+            | MethodParameter::UnstructuredBinaryBody
+            | MethodParameter::UnstructuredTextBody => None,
+        };
+
+        values.push(value);
+    }
+
+    Ok(values)
+}
 
-                    parse_query_or_header_value(&vals, parameter_type)?
+/// Completes the arguments resolved by [`resolve_request_arguments`] with the parameters bound
+/// to the parsed request body.
+fn resolve_body_arguments(
+    resolved_route: &ResolvedRouteEntry,
+    behaviour: &CallAgentBehaviour,
+    request_arguments: Vec<Option<UntypedElementValue>>,
+    mut body: ParsedRequestBody,
+) -> Result<Vec<UntypedElementValue>, RequestHandlerError> {
+    let mut values = Vec::with_capacity(behaviour.method_parameters.len());
+
+    for (param, request_argument) in behaviour.method_parameters.iter().zip(request_arguments) {
+        if let Some(value) = request_argument {
+            values.push(value);
+            continue;
+        }
+
+        let value = match param {
+            MethodParameter::JsonObjectBodyField { field_index } => match &body {
+                ParsedRequestBody::JsonBody(golem_wasm::Value::Record(fields)) => {
+                    UntypedElementValue::ComponentModel(fields[usize::from(*field_index)].clone())
                 }
 
-                MethodParameter::JsonObjectBodyField { field_index } => match &body {
-                    ParsedRequestBody::JsonBody(golem_wasm::Value::Record(fields)) => {
-                        UntypedElementValue::ComponentModel(
-                            fields[usize::from(*field_index)].clone(),
-                        )
-                    }
-
-                    ParsedRequestBody::JsonBody(_) => {
-                        return Err(RequestHandlerError::invariant_violated(
-                            "Inconsistent API definition: JSON field parameter but body is not an object",
-                        ));
-                    }
-
-                    _ => {
-                        return Err(RequestHandlerError::invariant_violated(
-                            "JSON body parameter used but no JSON body schema",
-                        ));
-                    }
-                },
+                ParsedRequestBody::JsonBody(_) => {
+                    return Err(RequestHandlerError::invariant_violated(
+                        "Inconsistent API definition: JSON field parameter but body is not an object",
+                    ));
+                }
 
-                MethodParameter::UnstructuredBinaryBody => match &mut body {
-                    ParsedRequestBody::UnstructuredBinary(binary_source) => {
-                        let binary_source = binary_source.take().ok_or_else(|| {
-                            RequestHandlerError::invariant_violated(
-                                "Parsed body was already consumed",
-                            )
-                        })?;
+                ParsedRequestBody::Form(_) => {
+                    form_field_value(resolved_route, &mut body, *field_index)?
+                }
 
-                        UntypedElementValue::UnstructuredBinary(BinaryReferenceValue {
-                            value: BinaryReference::Inline(binary_source),
-                        })
-                    }
+                _ => {
+                    return Err(RequestHandlerError::invariant_violated(
+                        "JSON body parameter used but no JSON body schema",
+                    ));
+                }
+            },
 
-                    _ => {
-                        return Err(RequestHandlerError::invariant_violated(
-                            "Binary body parameter used but no binary body schema",
-                        ));
-                    }
-                },
+            MethodParameter::FormField { field_index } => {
+                form_field_value(resolved_route, &mut body, *field_index)?
+            }
 
-                MethodParameter::UnstructuredTextBody => match &mut body {
-                    ParsedRequestBody::UnstructuredText(text_source) => {
-                        let text_source = text_source.take().ok_or_else(|| {
-                            RequestHandlerError::invariant_violated(
-                                "Parsed body was already consumed",
-                            )
-                        })?;
+            MethodParameter::UnstructuredBinaryBody => match &mut body {
+                ParsedRequestBody::UnstructuredBinary(binary_source) => {
+                    let binary_source = binary_source.take().ok_or_else(|| {
+                        RequestHandlerError::invariant_violated("Parsed body was already consumed")
+                    })?;
 
-                        UntypedElementValue::UnstructuredText(TextReferenceValue {
-                            value: TextReference::Inline(text_source),
-                        })
-                    }
+                    UntypedElementValue::UnstructuredBinary(BinaryReferenceValue {
+                        value: BinaryReference::Inline(binary_source),
+                    })
+                }
 
-                    _ => {
-                        return Err(RequestHandlerError::invariant_violated(
-                            "Text body parameter used but no text body schema",
-                        ));
-                    }
-                },
-            };
+                _ => {
+                    return Err(RequestHandlerError::invariant_violated(
+                        "Binary body parameter used but no binary body schema",
+                    ));
+                }
+            },
 
-            values.push(value);
-        }
+            MethodParameter::UnstructuredTextBody => match &mut body {
+                ParsedRequestBody::UnstructuredText(text_source) => {
+                    let text_source = text_source.take().ok_or_else(|| {
+                        RequestHandlerError::invariant_violated("Parsed body was already consumed")
+                    })?;
 
-        Ok(values)
+                    UntypedElementValue::UnstructuredText(TextReferenceValue {
+                        value: TextReference::Inline(text_source),
+                    })
+                }
+
+                _ => {
+                    return Err(RequestHandlerError::invariant_violated(
+                        "Text body parameter used but no text body schema",
+                    ));
+                }
+            },
+
+            MethodParameter::Path { .. }
+            | MethodParameter::Query { .. }
+            | MethodParameter::Header { .. } => {
+                return Err(RequestHandlerError::invariant_violated(
+                    "Request bound parameter was not resolved",
+                ));
+            }
+        };
+
+        values.push(value);
     }
+
+    Ok(values)
 }
 
 /// HTTP methods for which the worker-service emits read-only cache headers
@@ -494,7 +683,9 @@ fn principal_vary_header_name(security: &RichRouteSecurity) -> &str {
     }
 }
 
-fn principal_from_request(request: &RichRequest) -> Result<Principal, RequestHandlerError> {
+pub(super) fn principal_from_request(
+    request: &RichRequest,
+) -> Result<Principal, RequestHandlerError> {
     match request.authenticated_session() {
         Some(session) => Ok(Principal::Oidc(OidcPrincipal {
             sub: session.subject.clone(),
@@ -512,3 +703,58 @@ fn principal_from_request(request: &RichRequest) -> Result<Principal, RequestHan
         None => Ok(Principal::anonymous()),
     }
 }
+
+fn form_field_value(
+    resolved_route: &ResolvedRouteEntry,
+    body: &mut ParsedRequestBody,
+    field_index: SafeIndex,
+) -> Result<UntypedElementValue, RequestHandlerError> {
+    let ParsedRequestBody::Form(form) = body else {
+        return Err(RequestHandlerError::invariant_violated(
+            "Form field parameter used but no form body was parsed",
+        ));
+    };
+
+    let field = resolved_route
+        .route
+        .body
+        .form_fields()
+        .and_then(|fields| fields.into_iter().nth(usize::from(field_index)))
+        .ok_or_else(|| {
+            RequestHandlerError::invariant_violated(
+                "Inconsistent API definition: form field parameter without matching form field",
+            )
+        })?;
+
+    let missing_field = || RequestHandlerError::FormBodyParsingFailed {
+        error: format!("Missing form field {}", field.name),
+    };
+
+    match &field.field_type {
+        FormFieldType::Value(parameter_type) => {
+            let empty = Vec::new();
+            let vals = form.values.get(&field.name).unwrap_or(&empty);
+
+            parse_query_or_header_value(vals, parameter_type)
+        }
+        FormFieldType::UnstructuredBinary { .. } => {
+            let binary_source = form
+                .binaries
+                .remove(&field.name)
+                .ok_or_else(missing_field)?;
+
+            Ok(UntypedElementValue::UnstructuredBinary(
+                BinaryReferenceValue {
+                    value: BinaryReference::Inline(binary_source),
+                },
+            ))
+        }
+        FormFieldType::UnstructuredText { .. } => {
+            let text_source = form.texts.remove(&field.name).ok_or_else(missing_field)?;
+
+            Ok(UntypedElementValue::UnstructuredText(TextReferenceValue {
+                value: TextReference::Inline(text_source),
+            }))
+        }
+    }
+}
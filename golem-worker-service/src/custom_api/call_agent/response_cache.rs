@@ -0,0 +1,604 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//! Gateway-side cache for the responses of read-only `AgentMethod` routes.
+//!
+//! Successful `GET` / `HEAD` responses of read-only methods whose cache policy
+//! is not `NoCache` are kept in memory, so repeated reads are answered by the
+//! worker-service without invoking the agent:
+//!
+//! - `Ttl` entries are served until the policy's TTL elapses.
+//! - `UntilWrite` entries are served until the agent is written. Every write to
+//!   the agent (a mutating invocation, an update or a revert) appends to its
+//!   oplog, and the executors stream the new oplog index of every written agent
+//!   to the worker-service (see [`AgentWriteSubscriber`](crate::service::agent_write_subscriber::AgentWriteSubscriber)),
+//!   which drops the entries produced at an older index. While the worker-service
+//!   is not subscribed to every executor, `UntilWrite` entries are revalidated
+//!   against the agent's current `(fingerprint, oplog index)` pair instead.
+//!
+//! Entries are grouped by agent, and keyed by the route, the values bound to the
+//! method's parameters and, for principal-aware methods, the caller's principal.
+//! The number of agents is bounded (least recently used agents are evicted first)
+//! and agents whose entries are not accessed for a while are evicted in the
+//! background.
+
+use crate::config::ResponseCacheConfig;
+use crate::custom_api::route_resolver::ResolvedRouteEntry;
+use crate::custom_api::{ResponseBody, RichRequest, RouteExecutionResult};
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::model::agent::{CachePolicy, ReadOnlyConfig};
+use golem_common::model::domain_registration::Domain;
+use golem_common::model::{AgentFingerprint, AgentId, OplogIndex};
+use golem_service_base::custom_api::{CallAgentBehaviour, MethodParameter, RouteId};
+use http::{HeaderMap, StatusCode};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub struct ResponseCache {
+    agents: Cache<AgentId, (), Arc<Mutex<AgentResponses>>, ()>,
+    subscribed_to_writes: AtomicBool,
+}
+
+/// The cached responses of a single agent.
+#[derive(Default)]
+struct AgentResponses {
+    /// Oplog index of the last write of the agent seen by the cache. Responses produced at an
+    /// older index are stale, even if they only get inserted after the write was seen.
+    last_write: Option<OplogIndex>,
+    responses: HashMap<ResponseCacheKey, Arc<CachedResponse>>,
+}
+
+impl ResponseCache {
+    pub fn new(config: &ResponseCacheConfig) -> Self {
+        Self {
+            agents: Cache::new(
+                Some(config.max_capacity),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::OlderThan {
+                    ttl: config.ttl,
+                    period: config.eviction_period,
+                },
+                "http_api_response",
+            ),
+            subscribed_to_writes: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn get(&self, key: &ResponseCacheKey) -> Option<Arc<CachedResponse>> {
+        let agent = self.agents.try_get(&key.agent_id).await?;
+        let agent = agent.lock().unwrap();
+        agent.responses.get(key).cloned()
+    }
+
+    /// Starts tracking the writes of the agent before it is invoked, so a write racing with
+    /// the invocation prevents caching its (already stale) response.
+    pub async fn track_agent(&self, agent_id: &AgentId) {
+        let _ = self
+            .agents
+            .get_or_insert_simple(agent_id, async || Ok(Arc::default()))
+            .await;
+    }
+
+    pub async fn insert(&self, key: ResponseCacheKey, response: CachedResponse) {
+        let Ok(agent) = self
+            .agents
+            .get_or_insert_simple(&key.agent_id, async || Ok(Arc::default()))
+            .await
+        else {
+            return;
+        };
+        let mut agent = agent.lock().unwrap();
+        if agent
+            .last_write
+            .is_some_and(|last_write| last_write > response.oplog_index)
+        {
+            return;
+        }
+        // Replaces the previous (stale) response of the same key, if any
+        agent.responses.insert(key, Arc::new(response));
+    }
+
+    pub async fn remove(&self, key: &ResponseCacheKey) {
+        if let Some(agent) = self.agents.try_get(&key.agent_id).await {
+            agent.lock().unwrap().responses.remove(key);
+        }
+    }
+
+    /// Drops the responses of the agent produced before its oplog reached `oplog_index`.
+    pub async fn agent_written(&self, agent_id: &AgentId, oplog_index: OplogIndex) {
+        if let Some(agent) = self.agents.try_get(agent_id).await {
+            let mut agent = agent.lock().unwrap();
+            agent.last_write = agent.last_write.max(Some(oplog_index));
+            agent
+                .responses
+                .retain(|_, response| response.oplog_index >= oplog_index);
+        }
+    }
+
+    pub async fn agent_deleted(&self, agent_id: &AgentId) {
+        self.agents.remove(agent_id).await;
+    }
+
+    pub async fn clear(&self) {
+        for agent_id in self.agents.keys().await {
+            self.agents.remove(&agent_id).await;
+        }
+    }
+
+    /// `UntilWrite` responses are only served without revalidation while the writes of every
+    /// executor are delivered to the cache.
+    pub fn is_invalidated_by_writes(&self) -> bool {
+        self.subscribed_to_writes.load(Ordering::Acquire)
+    }
+
+    /// Writes may have been missed while the cache was not subscribed to them, so the cached
+    /// responses are dropped when the subscription is (re-)established.
+    pub async fn set_subscribed_to_writes(&self, subscribed: bool) {
+        if subscribed {
+            self.clear().await;
+        }
+        self.subscribed_to_writes
+            .store(subscribed, Ordering::Release);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey {
+    domain: Domain,
+    route_id: RouteId,
+    agent_id: AgentId,
+    method_name: String,
+    parameters: Vec<Vec<String>>,
+    principal: Option<String>,
+}
+
+impl ResponseCacheKey {
+    /// Builds the cache key of the request, or `None` if the response of the
+    /// request must not be cached: phantom agents are never reused, and
+    /// request bodies are not part of the key.
+    pub fn for_request(
+        request: &RichRequest,
+        resolved_route: &ResolvedRouteEntry,
+        behaviour: &CallAgentBehaviour,
+        agent_id: &AgentId,
+        read_only: &ReadOnlyConfig,
+    ) -> Option<Self> {
+        if behaviour.phantom {
+            return None;
+        }
+
+        let parameters = parameter_values(
+            &behaviour.method_parameters,
+            &resolved_route.captured_path_parameters,
+            request.query_params(),
+            request.headers(),
+        )?;
+
+        let principal = read_only
+            .uses_principal
+            .then(|| match request.authenticated_session() {
+                Some(session) => format!("{}:{}", session.issuer, session.subject),
+                None => "anonymous".to_string(),
+            });
+
+        Some(Self {
+            domain: resolved_route.domain.clone(),
+            route_id: resolved_route.route.route_id,
+            agent_id: agent_id.clone(),
+            method_name: behaviour.method_name.clone(),
+            parameters,
+            principal,
+        })
+    }
+}
+
+/// The raw values bound to each of the method's parameters, in parameter order.
+fn parameter_values(
+    method_parameters: &[MethodParameter],
+    captured_path_parameters: &[String],
+    query_params: &HashMap<String, Vec<String>>,
+    headers: &HeaderMap,
+) -> Option<Vec<Vec<String>>> {
+    let mut values = Vec::with_capacity(method_parameters.len());
+
+    for param in method_parameters {
+        let value = match param {
+            MethodParameter::Path {
+                path_segment_index, ..
+            } => vec![
+                captured_path_parameters
+                    .get(usize::from(*path_segment_index))?
+                    .clone(),
+            ],
+            MethodParameter::Query {
+                query_parameter_name,
+                ..
+            } => query_params
+                .get(query_parameter_name)
+                .cloned()
+                .unwrap_or_default(),
+            MethodParameter::Header { header_name, .. } => headers
+                .get_all(header_name)
+                .iter()
+                .map(|value| value.to_str().ok().map(String::from))
+                .collect::<Option<Vec<_>>>()?,
+            MethodParameter::JsonObjectBodyField { .. }
+            | MethodParameter::UnstructuredBinaryBody
+            | MethodParameter::UnstructuredTextBody
+            | MethodParameter::FormField { .. } => return None,
+        };
+
+        values.push(value);
+    }
+
+    Some(values)
+}
+
+/// A successful response of a read-only method, including its cache headers,
+/// together with the validator of the agent state it was produced from.
+#[derive(Debug)]
+pub struct CachedResponse {
+    result: RouteExecutionResult,
+    pub agent_fingerprint: AgentFingerprint,
+    pub oplog_index: OplogIndex,
+    stored_at: Instant,
+    expires_at: Option<Instant>,
+}
+
+impl CachedResponse {
+    pub fn new(
+        result: RouteExecutionResult,
+        agent_fingerprint: AgentFingerprint,
+        oplog_index: OplogIndex,
+        read_only: &ReadOnlyConfig,
+        now: Instant,
+    ) -> Self {
+        let expires_at = match &read_only.cache_policy {
+            CachePolicy::Ttl(ttl) => Some(now + Duration::from_nanos(ttl.duration_nanos)),
+            CachePolicy::NoCache(_) | CachePolicy::UntilWrite(_) => None,
+        };
+
+        Self {
+            result,
+            agent_fingerprint,
+            oplog_index,
+            stored_at: now,
+            expires_at,
+        }
+    }
+
+    /// `Ttl` responses are fresh until they expire, `UntilWrite` responses
+    /// need to be revalidated against the agent's current state.
+    pub fn is_revalidated_by_ttl(&self) -> bool {
+        self.expires_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Builds the response served from the cache. The `Age` header tells
+    /// downstream caches how long the response has been stored by the gateway.
+    pub fn to_route_result(
+        &self,
+        now: Instant,
+        not_modified: bool,
+        omit_body: bool,
+    ) -> RouteExecutionResult {
+        let mut headers = self.result.headers.clone();
+        headers.insert(
+            http::header::AGE,
+            now.saturating_duration_since(self.stored_at)
+                .as_secs()
+                .to_string(),
+        );
+
+        let (status, body) = if not_modified {
+            (StatusCode::NOT_MODIFIED, ResponseBody::NoBody)
+        } else if omit_body {
+            (self.result.status, ResponseBody::NoBody)
+        } else {
+            (self.result.status, self.result.body.clone())
+        };
+
+        RouteExecutionResult {
+            status,
+            headers,
+            body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::Empty;
+    use golem_common::model::agent::CachePolicyTtl;
+    use golem_common::model::component::ComponentId;
+    use golem_service_base::custom_api::{PathSegmentType, QueryOrHeaderType};
+    use golem_service_base::model::SafeIndex;
+    use test_r::test;
+    use uuid::uuid;
+
+    fn agent_id_for_test() -> AgentId {
+        AgentId {
+            component_id: ComponentId(uuid!("00000000-0000-0000-0000-000000000001")),
+            agent_id: "demo".to_string(),
+        }
+    }
+
+    fn fingerprint_for_test() -> AgentFingerprint {
+        AgentFingerprint(uuid!("00000000-0000-0000-0000-000000000aaa"))
+    }
+
+    fn key_for_test(parameters: Vec<Vec<String>>) -> ResponseCacheKey {
+        ResponseCacheKey {
+            domain: Domain("example.com".to_string()),
+            route_id: 1,
+            agent_id: agent_id_for_test(),
+            method_name: "get".to_string(),
+            parameters,
+            principal: None,
+        }
+    }
+
+    fn until_write() -> ReadOnlyConfig {
+        ReadOnlyConfig {
+            cache_policy: CachePolicy::UntilWrite(Empty {}),
+            uses_principal: false,
+        }
+    }
+
+    fn ttl(nanos: u64) -> ReadOnlyConfig {
+        ReadOnlyConfig {
+            cache_policy: CachePolicy::Ttl(CachePolicyTtl {
+                duration_nanos: nanos,
+            }),
+            uses_principal: false,
+        }
+    }
+
+    fn text_result() -> RouteExecutionResult {
+        RouteExecutionResult {
+            status: StatusCode::OK,
+            headers: HashMap::from([(http::header::ETAG, "\"etag\"".to_string())]),
+            body: ResponseBody::UnstructuredTextBody {
+                body: golem_common::model::agent::TextSource {
+                    data: "hello".to_string(),
+                    text_type: None,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn parameter_values_follow_method_parameter_order() {
+        let method_parameters = vec![
+            MethodParameter::Query {
+                query_parameter_name: "tag".to_string(),
+                parameter_type: QueryOrHeaderType::Primitive(PathSegmentType::Str),
+            },
+            MethodParameter::Path {
+                path_segment_index: SafeIndex::new(1),
+                parameter_type: PathSegmentType::Str,
+            },
+            MethodParameter::Header {
+                header_name: "x-tenant".to_string(),
+                parameter_type: QueryOrHeaderType::Primitive(PathSegmentType::Str),
+            },
+        ];
+        let query_params = HashMap::from([
+            ("tag".to_string(), vec!["a".to_string(), "b".to_string()]),
+            ("unused".to_string(), vec!["x".to_string()]),
+        ]);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", "acme".parse().unwrap());
+
+        let values = parameter_values(
+            &method_parameters,
+            &["users".to_string(), "42".to_string()],
+            &query_params,
+            &headers,
+        );
+
+        assert_eq!(
+            values,
+            Some(vec![
+                vec!["a".to_string(), "b".to_string()],
+                vec!["42".to_string()],
+                vec!["acme".to_string()],
+            ])
+        );
+    }
+
+    #[test]
+    fn requests_with_body_parameters_are_not_cached() {
+        let values = parameter_values(
+            &[MethodParameter::UnstructuredTextBody],
+            &[],
+            &HashMap::new(),
+            &HeaderMap::new(),
+        );
+
+        assert_eq!(values, None);
+    }
+
+    #[test]
+    fn ttl_responses_expire_after_the_policy_ttl() {
+        let now = Instant::now();
+        let response = CachedResponse::new(
+            text_result(),
+            fingerprint_for_test(),
+            OplogIndex::from_u64(10),
+            &ttl(2_000_000_000),
+            now,
+        );
+
+        assert!(response.is_revalidated_by_ttl());
+        assert!(!response.is_expired(now + Duration::from_secs(1)));
+        assert!(response.is_expired(now + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn until_write_responses_never_expire_by_time() {
+        let now = Instant::now();
+        let response = CachedResponse::new(
+            text_result(),
+            fingerprint_for_test(),
+            OplogIndex::from_u64(10),
+            &until_write(),
+            now,
+        );
+
+        assert!(!response.is_revalidated_by_ttl());
+        assert!(!response.is_expired(now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn served_responses_carry_age_and_cached_headers() {
+        let now = Instant::now();
+        let response = CachedResponse::new(
+            text_result(),
+            fingerprint_for_test(),
+            OplogIndex::from_u64(10),
+            &until_write(),
+            now,
+        );
+
+        let result = response.to_route_result(now + Duration::from_secs(5), false, false);
+        assert_eq!(result.status, StatusCode::OK);
+        assert_eq!(
+            result.headers.get(&http::header::AGE),
+            Some(&"5".to_string())
+        );
+        assert_eq!(
+            result.headers.get(&http::header::ETAG),
+            Some(&"\"etag\"".to_string())
+        );
+        assert!(matches!(
+            result.body,
+            ResponseBody::UnstructuredTextBody { .. }
+        ));
+
+        let head = response.to_route_result(now, false, true);
+        assert_eq!(head.status, StatusCode::OK);
+        assert!(matches!(head.body, ResponseBody::NoBody));
+
+        let not_modified = response.to_route_result(now, true, false);
+        assert_eq!(not_modified.status, StatusCode::NOT_MODIFIED);
+        assert!(matches!(not_modified.body, ResponseBody::NoBody));
+    }
+
+    #[test]
+    async fn insert_replaces_the_previous_response_of_the_key() {
+        let cache = ResponseCache::new(&ResponseCacheConfig::default());
+        let key = key_for_test(vec![vec!["42".to_string()]]);
+        let now = Instant::now();
+
+        cache
+            .insert(
+                key.clone(),
+                CachedResponse::new(
+                    text_result(),
+                    fingerprint_for_test(),
+                    OplogIndex::from_u64(10),
+                    &until_write(),
+                    now,
+                ),
+            )
+            .await;
+        cache
+            .insert(
+                key.clone(),
+                CachedResponse::new(
+                    text_result(),
+                    fingerprint_for_test(),
+                    OplogIndex::from_u64(12),
+                    &until_write(),
+                    now,
+                ),
+            )
+            .await;
+
+        let cached = cache.get(&key).await.unwrap();
+        assert_eq!(cached.oplog_index, OplogIndex::from_u64(12));
+        assert!(
+            cache
+                .get(&key_for_test(vec![vec!["43".to_string()]]))
+                .await
+                .is_none()
+        );
+
+        cache.remove(&key).await;
+        assert!(cache.get(&key).await.is_none());
+    }
+
+    fn cached_at(oplog_index: u64) -> CachedResponse {
+        CachedResponse::new(
+            text_result(),
+            fingerprint_for_test(),
+            OplogIndex::from_u64(oplog_index),
+            &until_write(),
+            Instant::now(),
+        )
+    }
+
+    #[test]
+    async fn agent_writes_drop_older_responses() {
+        let cache = ResponseCache::new(&ResponseCacheConfig::default());
+        let older = key_for_test(vec![vec!["42".to_string()]]);
+        let newer = key_for_test(vec![vec!["43".to_string()]]);
+        cache.insert(older.clone(), cached_at(10)).await;
+        cache.insert(newer.clone(), cached_at(12)).await;
+
+        cache
+            .agent_written(&agent_id_for_test(), OplogIndex::from_u64(11))
+            .await;
+
+        assert!(cache.get(&older).await.is_none());
+        assert!(cache.get(&newer).await.is_some());
+
+        cache.agent_deleted(&agent_id_for_test()).await;
+        assert!(cache.get(&newer).await.is_none());
+    }
+
+    #[test]
+    async fn responses_racing_with_a_write_are_not_cached() {
+        let cache = ResponseCache::new(&ResponseCacheConfig::default());
+        let key = key_for_test(vec![vec!["42".to_string()]]);
+
+        cache.track_agent(&agent_id_for_test()).await;
+        cache
+            .agent_written(&agent_id_for_test(), OplogIndex::from_u64(11))
+            .await;
+        cache.insert(key.clone(), cached_at(10)).await;
+
+        assert!(cache.get(&key).await.is_none());
+    }
+
+    #[test]
+    async fn subscribing_to_writes_drops_all_responses() {
+        let cache = ResponseCache::new(&ResponseCacheConfig::default());
+        let key = key_for_test(vec![vec!["42".to_string()]]);
+        cache.insert(key.clone(), cached_at(10)).await;
+        assert!(!cache.is_invalidated_by_writes());
+
+        cache.set_subscribed_to_writes(true).await;
+
+        assert!(cache.is_invalidated_by_writes());
+        assert!(cache.get(&key).await.is_none());
+    }
+}
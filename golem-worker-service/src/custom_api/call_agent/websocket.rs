@@ -0,0 +1,437 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::response_mapping::interpret_agent_response;
+use super::{
+    CallAgentHandler, principal_from_request, resolve_body_arguments, resolve_request_arguments,
+};
+use crate::custom_api::RichRequest;
+use crate::custom_api::error::RequestHandlerError;
+use crate::custom_api::model::{ParsedRequestBody, ResponseBody, RouteExecutionResult};
+use crate::custom_api::route_resolver::ResolvedRouteEntry;
+use crate::service::worker::{ConnectWorkerStream, WorkerService};
+use anyhow::anyhow;
+use futures::{SinkExt, StreamExt};
+use golem_api_grpc::proto::golem::workerexecutor::v1::WebSocketConnection;
+use golem_common::SafeDisplay;
+use golem_common::model::agent::{
+    BinarySource, BinaryType, TextSource, UntypedDataValue, UntypedElementValue,
+};
+use golem_common::model::invocation_context::{
+    AttributeValue, InvocationContextStack, WEBSOCKET_CONNECTION_ID_ATTRIBUTE,
+};
+use golem_common::model::{AgentEvent, AgentId, IdempotencyKey, WebSocketMessage};
+use golem_service_base::custom_api::{CallAgentBehaviour, RequestBodySchema};
+use golem_service_base::model::auth::AuthCtx;
+use golem_wasm::ValueAndType;
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use poem::web::websocket::{CloseCode, Message, WebSocket, WebSocketStream};
+use poem::{FromRequest, IntoResponse};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{Instrument, debug, warn};
+use uuid::Uuid;
+
+/// Maximum length of a close frame reason, as defined by RFC 6455
+const MAX_CLOSE_REASON_LENGTH: usize = 123;
+
+/// Maximum number of incoming messages waiting for their invocation (and of replies waiting to
+/// be sent) per connection
+const MAX_QUEUED_MESSAGES: usize = 32;
+
+impl CallAgentHandler {
+    /// Upgrades the request to a WebSocket connection bound to the agent. Every incoming message
+    /// invokes the endpoint's agent method with the message decoded as the request body, and
+    /// non-unit results are sent back to the client. Messages the agent sends to the connection
+    /// through the `websocket-send` host function are forwarded from the agent's event stream.
+    pub async fn handle_websocket_behaviour(
+        &self,
+        request: &mut RichRequest,
+        resolved_route: &ResolvedRouteEntry,
+        behaviour: &CallAgentBehaviour,
+    ) -> Result<RouteExecutionResult, RequestHandlerError> {
+        let websocket = WebSocket::from_request_without_body(&request.underlying)
+            .await
+            .map_err(|err| RequestHandlerError::WebSocketUpgradeFailed {
+                error: err.to_string(),
+            })?;
+
+        let agent_id = self.build_agent_id(resolved_route, behaviour)?;
+        let request_arguments = resolve_request_arguments(resolved_route, request, behaviour)?;
+        let invocation_context = request.invocation_context();
+
+        let principal = principal_from_request(request)?;
+        debug!("Using principal for WebSocket connection: {principal:?}");
+        let proto_principal: golem_api_grpc::proto::golem::component::Principal = principal.into();
+
+        // Connecting requires an existing agent, so it is created on the first connection
+        self.worker_service
+            .create(
+                &agent_id,
+                HashMap::new(),
+                Vec::new(),
+                true,
+                AuthCtx::System,
+                Some(proto_invocation_context(&invocation_context)),
+                Some(proto_principal.clone()),
+            )
+            .await?;
+
+        let connection_id = Uuid::new_v4().to_string();
+
+        let events = self
+            .worker_service
+            .connect(
+                &agent_id,
+                AuthCtx::System,
+                Some(WebSocketConnection {
+                    connection_id: connection_id.clone(),
+                    method_name: behaviour.method_name.clone(),
+                }),
+            )
+            .await?;
+
+        debug!("Opening WebSocket connection {connection_id} to agent {agent_id}");
+
+        let session = WebSocketSession {
+            worker_service: self.worker_service.clone(),
+            resolved_route: resolved_route.clone(),
+            behaviour: behaviour.clone(),
+            agent_id,
+            connection_id,
+            request_arguments,
+            invocation_context,
+            principal: proto_principal,
+        };
+
+        let response = websocket
+            .on_upgrade(move |socket| session.run(socket, events))
+            .into_response();
+
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.clone(), value.to_string()))
+            })
+            .collect();
+
+        Ok(RouteExecutionResult {
+            status: response.status(),
+            headers,
+            body: ResponseBody::NoBody,
+        })
+    }
+}
+
+/// State of a single upgraded WebSocket connection. Arguments bound to the upgrade request
+/// (path, query and headers) are resolved once and reused for every incoming message.
+struct WebSocketSession {
+    worker_service: Arc<WorkerService>,
+    resolved_route: ResolvedRouteEntry,
+    behaviour: CallAgentBehaviour,
+    agent_id: AgentId,
+    connection_id: String,
+    request_arguments: Vec<Option<UntypedElementValue>>,
+    invocation_context: InvocationContextStack,
+    principal: golem_api_grpc::proto::golem::component::Principal,
+}
+
+impl WebSocketSession {
+    /// Incoming messages are queued for a separate task invoking the agent one message at a
+    /// time (preserving their order), so the agent's events keep being forwarded and the
+    /// client stream keeps being polled (for close frames) while an invocation is running.
+    async fn run(self, socket: WebSocketStream, mut events: ConnectWorkerStream) {
+        let session = Arc::new(self);
+        let (mut sink, mut stream) = socket.split();
+
+        let (queue, mut queued) = mpsc::channel::<Message>(MAX_QUEUED_MESSAGES);
+        let (reply_sender, mut replies) = mpsc::channel(MAX_QUEUED_MESSAGES);
+        let invoker = tokio::spawn({
+            let session = session.clone();
+            async move {
+                while let Some(message) = queued.recv().await {
+                    let reply = session.handle_message(message).await;
+                    let failed = reply.is_err();
+                    if reply_sender.send(reply).await.is_err() || failed {
+                        break;
+                    }
+                }
+            }
+            .in_current_span()
+        });
+
+        loop {
+            tokio::select! {
+                // Reading further messages is paused while the queue is full
+                incoming = stream.next(), if queue.capacity() > 0 => {
+                    let message = match incoming {
+                        Some(Ok(message @ (Message::Text(_) | Message::Binary(_)))) => message,
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Ok(_)) => continue,
+                        Some(Err(err)) => {
+                            debug!("WebSocket connection {} failed: {err}", session.connection_id);
+                            break;
+                        }
+                    };
+
+                    if queue.try_send(message).is_err() {
+                        break;
+                    }
+                }
+
+                reply = replies.recv() => {
+                    match reply {
+                        Some(Ok(Some(reply))) => {
+                            if sink.send(reply).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(None)) => {}
+                        Some(Err(err)) => {
+                            warn!(
+                                "Handling message of WebSocket connection {} failed: {err}",
+                                session.connection_id
+                            );
+                            let _ = sink
+                                .send(Message::Close(Some((
+                                    CloseCode::Error,
+                                    close_reason(err.to_safe_string()),
+                                ))))
+                                .await;
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+
+                event = events.next() => {
+                    let event = match event {
+                        Some(Ok(event)) => event,
+                        Some(Err(status)) => {
+                            warn!(
+                                "Event stream of WebSocket connection {} failed: {status}",
+                                session.connection_id
+                            );
+                            break;
+                        }
+                        None => break,
+                    };
+
+                    match AgentEvent::try_from(event) {
+                        Ok(AgentEvent::WebSocketSend {
+                            connection_id,
+                            message,
+                            ..
+                        }) if connection_id == session.connection_id => {
+                            let message = match message {
+                                WebSocketMessage::Text(text) => Message::Text(text),
+                                WebSocketMessage::Binary(data) => Message::Binary(data),
+                            };
+                            if sink.send(message).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(AgentEvent::WebSocketClose {
+                            connection_id,
+                            code,
+                            reason,
+                            ..
+                        }) if connection_id == session.connection_id => {
+                            let payload = code.map(|code| {
+                                (CloseCode::from(code), close_reason(reason.unwrap_or_default()))
+                            });
+                            let _ = sink.send(Message::Close(payload)).await;
+                            break;
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            warn!(
+                                "Failed decoding event of WebSocket connection {}: {err}",
+                                session.connection_id
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        // Messages of a closed connection are not processed anymore
+        invoker.abort();
+
+        debug!(
+            "Closing WebSocket connection {} to agent {}",
+            session.connection_id, session.agent_id
+        );
+        let _ = sink.close().await;
+    }
+
+    async fn handle_message(
+        &self,
+        message: Message,
+    ) -> Result<Option<Message>, RequestHandlerError> {
+        let body = self.parse_message(message)?;
+
+        let method_params = resolve_body_arguments(
+            &self.resolved_route,
+            &self.behaviour,
+            self.request_arguments.clone(),
+            body,
+        )?;
+
+        let proto_method_parameters: golem_api_grpc::proto::golem::component::UntypedDataValue =
+            UntypedDataValue::Tuple(method_params).into();
+
+        // Every message is handled in its own span, carrying the connection id the agent
+        // can reply to
+        let mut invocation_context = self.invocation_context.clone();
+        let span = invocation_context.spans.first().start_span(None);
+        span.set_attribute(
+            WEBSOCKET_CONNECTION_ID_ATTRIBUTE.to_string(),
+            AttributeValue::String(self.connection_id.clone()),
+        );
+        invocation_context.push(span);
+
+        let agent_response = self
+            .worker_service
+            .invoke_agent(
+                &self.agent_id,
+                Some(self.behaviour.method_name.clone()),
+                Some(proto_method_parameters),
+                golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await as i32,
+                None,
+                Some(IdempotencyKey::fresh()),
+                Some(proto_invocation_context(&invocation_context)),
+                AuthCtx::System,
+                self.principal.clone(),
+                Some(self.resolved_route.route.environment_id),
+            )
+            .await?;
+
+        let agent_result = match agent_response.result {
+            golem_common::model::AgentInvocationResult::AgentMethod { output } => Some(output),
+            _ => None,
+        };
+
+        let route_result =
+            interpret_agent_response(agent_result, &self.behaviour.expected_agent_response)?;
+
+        reply_message(route_result.body)
+    }
+
+    /// Decodes an incoming message the same way the request body of an HTTP route is parsed.
+    /// JSON and text bodies are sent as text messages, binary bodies as binary messages.
+    fn parse_message(&self, message: Message) -> Result<ParsedRequestBody, RequestHandlerError> {
+        match (&self.resolved_route.route.body, message) {
+            (RequestBodySchema::Unused, _) => Ok(ParsedRequestBody::Unused),
+
+            (RequestBodySchema::JsonBody { expected_type }, Message::Text(text)) => {
+                let json: serde_json::Value = serde_json::from_str(&text).map_err(|err| {
+                    RequestHandlerError::BodyIsNotValidJson {
+                        error: err.to_string(),
+                    }
+                })?;
+                let parsed = ValueAndType::parse_with_type(&json, expected_type)
+                    .map_err(|errors| RequestHandlerError::JsonBodyParsingFailed { errors })?;
+                Ok(ParsedRequestBody::JsonBody(parsed.value))
+            }
+            (RequestBodySchema::JsonBody { .. }, _) => {
+                Err(RequestHandlerError::UnexpectedWebSocketMessage { expected: "text" })
+            }
+
+            (
+                RequestBodySchema::UnrestrictedText | RequestBodySchema::RestrictedText { .. },
+                Message::Text(data),
+            ) => Ok(ParsedRequestBody::UnstructuredText(Some(TextSource {
+                data,
+                text_type: None,
+            }))),
+            (RequestBodySchema::UnrestrictedText | RequestBodySchema::RestrictedText { .. }, _) => {
+                Err(RequestHandlerError::UnexpectedWebSocketMessage { expected: "text" })
+            }
+
+            (RequestBodySchema::UnrestrictedBinary, Message::Binary(data)) => {
+                Ok(binary_body(data, None))
+            }
+            (RequestBodySchema::RestrictedBinary { allowed_mime_types }, Message::Binary(data)) => {
+                Ok(binary_body(data, allowed_mime_types.first().cloned()))
+            }
+            (
+                RequestBodySchema::UnrestrictedBinary | RequestBodySchema::RestrictedBinary { .. },
+                _,
+            ) => Err(RequestHandlerError::UnexpectedWebSocketMessage { expected: "binary" }),
+
+            (RequestBodySchema::FormBody { .. }, _) => {
+                Err(RequestHandlerError::invariant_violated(
+                    "Inconsistent API definition: WebSocket endpoint with form body",
+                ))
+            }
+        }
+    }
+}
+
+/// Binary messages carry no content type, they are assumed to be of the first allowed mime type
+fn binary_body(data: Vec<u8>, mime_type: Option<String>) -> ParsedRequestBody {
+    ParsedRequestBody::UnstructuredBinary(Some(BinarySource {
+        data,
+        binary_type: BinaryType {
+            mime_type: mime_type.unwrap_or_else(|| "application/octet-stream".to_string()),
+        },
+    }))
+}
+
+fn reply_message(body: ResponseBody) -> Result<Option<Message>, RequestHandlerError> {
+    match body {
+        ResponseBody::NoBody => Ok(None),
+        ResponseBody::ComponentModelJsonBody { body } => {
+            let json = body
+                .to_json_value()
+                .map_err(|e| anyhow!("ComponentModelJsonBody conversion error: {e}"))?;
+            Ok(Some(Message::Text(json.to_string())))
+        }
+        ResponseBody::UnstructuredBinaryBody { body } => Ok(Some(Message::Binary(body.data))),
+        ResponseBody::UnstructuredTextBody { body } => Ok(Some(Message::Text(body.data))),
+        ResponseBody::OpenApiSchema { .. } => Err(RequestHandlerError::invariant_violated(
+            "Agent method returned an OpenAPI schema",
+        )),
+        ResponseBody::GraphQl { .. } => Err(RequestHandlerError::invariant_violated(
+            "Agent method returned a GraphQL response",
+        )),
+    }
+}
+
+fn close_reason(mut reason: String) -> String {
+    if reason.len() > MAX_CLOSE_REASON_LENGTH {
+        let mut end = MAX_CLOSE_REASON_LENGTH;
+        while !reason.is_char_boundary(end) {
+            end -= 1;
+        }
+        reason.truncate(end);
+    }
+    reason
+}
+
+fn proto_invocation_context(
+    invocation_context: &InvocationContextStack,
+) -> golem_api_grpc::proto::golem::worker::InvocationContext {
+    golem_api_grpc::proto::golem::worker::InvocationContext {
+        parent: None,
+        env: Default::default(),
+        tracing: Some(invocation_context.clone().into()),
+    }
+}
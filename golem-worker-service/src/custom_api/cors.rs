@@ -407,6 +407,7 @@ mod tests {
                 }),
                 security: RichRouteSecurity::None,
                 cors: CorsOptions { allowed_patterns },
+                rate_limits: vec![],
             }),
             captured_path_parameters: vec![],
             openapi_spec: None,
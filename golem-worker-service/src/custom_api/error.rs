@@ -55,12 +55,33 @@ pub enum RequestHandlerError {
     UnsupportedTextContentType { content_type: String },
     #[error("Multi-valued or comma-separated Content-Language header is not supported")]
     MultiValuedContentLanguageHeader,
+    #[error(
+        "Content type {content_type} is not supported for form body. Only multipart/form-data and application/x-www-form-urlencoded are allowed."
+    )]
+    UnsupportedFormContentType { content_type: String },
+    #[error("Failed parsing form body: {error}")]
+    FormBodyParsingFailed { error: String },
+    #[error(
+        "Form body is too large. Allowed sizes: {max_file_size} bytes per file, {max_body_size} bytes in total"
+    )]
+    FormBodyTooLarge {
+        max_body_size: usize,
+        max_file_size: usize,
+    },
+    #[error("Request body is too large. Allowed size: {max_body_size} bytes")]
+    RequestBodyTooLarge { max_body_size: usize },
+    #[error("WebSocket upgrade failed: {error}")]
+    WebSocketUpgradeFailed { error: String },
+    #[error("Expected a {expected} WebSocket message")]
+    UnexpectedWebSocketMessage { expected: &'static str },
     #[error("Unknown OIDC state")]
     UnknownOidcState,
     #[error("OIDC token exchange failed")]
     OidcTokenExchangeFailed,
     #[error("OpenAPI spec generation for api failed")]
     OpenApiSpecGenerationFailed,
+    #[error("GraphQL schema generation for api failed: {error}")]
+    GraphQlSchemaGenerationFailed { error: String },
     #[error("Invariant violated: {msg}")]
     InvariantViolated { msg: &'static str },
     #[error("Resolving route failed: {0}")]
@@ -94,10 +115,17 @@ impl SafeDisplay for RequestHandlerError {
             Self::UnsupportedLanguage { .. } => self.to_string(),
             Self::UnsupportedTextContentType { .. } => self.to_string(),
             Self::MultiValuedContentLanguageHeader => self.to_string(),
+            Self::UnsupportedFormContentType { .. } => self.to_string(),
+            Self::FormBodyParsingFailed { .. } => self.to_string(),
+            Self::FormBodyTooLarge { .. } => self.to_string(),
+            Self::RequestBodyTooLarge { .. } => self.to_string(),
+            Self::WebSocketUpgradeFailed { .. } => self.to_string(),
+            Self::UnexpectedWebSocketMessage { .. } => self.to_string(),
             Self::UnknownOidcState => self.to_string(),
             Self::OidcTokenExchangeFailed => self.to_string(),
             Self::OidcSchemeMismatch => self.to_string(),
             Self::OpenApiSpecGenerationFailed { .. } => self.to_string(),
+            Self::GraphQlSchemaGenerationFailed { .. } => self.to_string(),
 
             Self::InvariantViolated { .. } => "internal error".to_string(),
 
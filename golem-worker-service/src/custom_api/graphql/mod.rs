@@ -0,0 +1,211 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod schema;
+mod values;
+
+use super::RichRequest;
+use super::call_agent::principal_from_request;
+use super::error::RequestHandlerError;
+use super::rate_limit::{RateLimitedClient, RateLimiter};
+use super::route_resolver::ResolvedRouteEntry;
+use super::{ResponseBody, RouteExecutionResult};
+use crate::config::{FormBodyConfig, GraphQlConfig};
+use crate::service::worker::WorkerService;
+use anyhow::anyhow;
+use async_graphql::ErrorExtensions;
+use golem_common::SafeDisplay;
+use golem_common::model::agent::{UntypedDataValue, UntypedElementValue};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::{AgentId, AgentInvocationResult, IdempotencyKey};
+use golem_service_base::custom_api::{GraphQlBehaviour, RouteRateLimit};
+use golem_service_base::model::auth::AuthCtx;
+use http::StatusCode;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tracing::debug;
+
+/// GraphQL schema generated from the agent types exposed by a GraphQL route.
+///
+/// The schema is built when the routes of the domain are loaded. If that fails, the error is
+/// kept and returned for every request of the route, instead of failing the whole domain.
+pub struct GraphQlApi {
+    schema: Result<async_graphql::dynamic::Schema, String>,
+}
+
+impl GraphQlApi {
+    pub fn new(behaviour: GraphQlBehaviour, config: &GraphQlConfig) -> Self {
+        let schema = schema::build_schema(&behaviour, config);
+
+        if let Err(e) = &schema {
+            tracing::error!("Failed to build GraphQL schema for http api: {e}");
+        }
+
+        Self { schema }
+    }
+}
+
+impl fmt::Debug for GraphQlApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQlApi")
+            .field("schema_error", &self.schema.as_ref().err())
+            .finish()
+    }
+}
+
+pub struct GraphQlHandler {
+    worker_service: Arc<WorkerService>,
+    rate_limiter: Arc<RateLimiter>,
+    body_limits: FormBodyConfig,
+}
+
+impl GraphQlHandler {
+    pub fn new(
+        worker_service: Arc<WorkerService>,
+        rate_limiter: Arc<RateLimiter>,
+        body_limits: FormBodyConfig,
+    ) -> Self {
+        Self {
+            worker_service,
+            rate_limiter,
+            body_limits,
+        }
+    }
+
+    pub async fn handle_graphql_behaviour(
+        &self,
+        request: &mut RichRequest,
+        resolved_route: &ResolvedRouteEntry,
+        api: &GraphQlApi,
+    ) -> Result<RouteExecutionResult, RequestHandlerError> {
+        let schema = api.schema.as_ref().map_err(|error| {
+            RequestHandlerError::GraphQlSchemaGenerationFailed {
+                error: error.clone(),
+            }
+        })?;
+
+        let principal = principal_from_request(request)?;
+        debug!("Using principal for GraphQL request: {principal:?}");
+
+        let body_binary = request.read_limited_body(&self.body_limits).await?;
+
+        let graphql_request: async_graphql::Request = serde_json::from_slice(&body_binary)
+            .map_err(|err| RequestHandlerError::BodyIsNotValidJson {
+                error: err.to_string(),
+            })?;
+
+        let graphql_request = graphql_request.data(GraphQlRequestContext {
+            worker_service: self.worker_service.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            client: self.rate_limiter.client(request, &resolved_route.domain),
+            environment_id: resolved_route.route.environment_id,
+            principal: principal.into(),
+            invocation_context: golem_api_grpc::proto::golem::worker::InvocationContext {
+                parent: None,
+                env: Default::default(),
+                tracing: Some(request.invocation_context().into()),
+            },
+        });
+
+        let response = schema.execute(graphql_request).await;
+
+        let body = serde_json::to_value(&response)
+            .map_err(|e| anyhow!("GraphQL response serialization error: {e}"))?;
+
+        Ok(RouteExecutionResult {
+            status: StatusCode::OK,
+            headers: HashMap::new(),
+            body: ResponseBody::GraphQl { body },
+        })
+    }
+}
+
+/// Data of the http request a GraphQL operation is executed for, available to all resolvers.
+struct GraphQlRequestContext {
+    worker_service: Arc<WorkerService>,
+    rate_limiter: Arc<RateLimiter>,
+    client: RateLimitedClient,
+    environment_id: EnvironmentId,
+    principal: golem_api_grpc::proto::golem::component::Principal,
+    invocation_context: golem_api_grpc::proto::golem::worker::InvocationContext,
+}
+
+impl GraphQlRequestContext {
+    /// Takes a token from each of the agent and method level rate limits of the invoked method.
+    /// Unlike an exhausted route level limit, which rejects the whole request, an exhausted
+    /// method level one only fails the field invoking the method.
+    fn acquire_rate_limits(&self, rate_limits: &[RouteRateLimit]) -> async_graphql::Result<()> {
+        self.rate_limiter
+            .try_acquire_for(&self.client, rate_limits)
+            .map_err(|retry_after| {
+                debug!(
+                    "Rate limit exceeded for GraphQL field, retry after {}s",
+                    retry_after.as_secs()
+                );
+                async_graphql::Error::new("Too many requests").extend_with(|_, extensions| {
+                    extensions.set("code", "TOO_MANY_REQUESTS");
+                    extensions.set(
+                        "retryAfter",
+                        retry_after.as_secs_f64().ceil().max(1.0) as u64,
+                    );
+                })
+            })
+    }
+
+    async fn invoke_agent(
+        &self,
+        agent_id: &AgentId,
+        method_name: &str,
+        parameters: Vec<golem_wasm::Value>,
+    ) -> async_graphql::Result<Vec<UntypedElementValue>> {
+        debug!("Invoking agent {agent_id} from GraphQL");
+
+        let method_params = UntypedDataValue::Tuple(
+            parameters
+                .into_iter()
+                .map(UntypedElementValue::ComponentModel)
+                .collect(),
+        );
+
+        let proto_method_parameters: golem_api_grpc::proto::golem::component::UntypedDataValue =
+            method_params.into();
+
+        let agent_response = self
+            .worker_service
+            .invoke_agent(
+                agent_id,
+                Some(method_name.to_string()),
+                Some(proto_method_parameters),
+                golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await as i32,
+                None,
+                Some(IdempotencyKey::fresh()),
+                Some(self.invocation_context.clone()),
+                AuthCtx::System,
+                self.principal.clone(),
+                Some(self.environment_id),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_safe_string()))?;
+
+        match agent_response.result {
+            AgentInvocationResult::AgentMethod {
+                output: UntypedDataValue::Tuple(elements),
+            } => Ok(elements),
+            _ => Err(async_graphql::Error::new(
+                "Agent response did not match expected type",
+            )),
+        }
+    }
+}
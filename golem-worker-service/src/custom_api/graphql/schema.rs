@@ -0,0 +1,813 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates a GraphQL schema from agent types.
+//!
+//! Every agent type gets a field on the `Query` and `Mutation` root types, taking the constructor
+//! parameters as arguments. The field resolves to an object with one field per agent method:
+//! read-only methods are queries, all other methods are mutations. Methods without a result
+//! return `true`.
+//!
+//! Records and enums are mapped to GraphQL object, input object and enum types, `u32`, `s64`
+//! and `u64` to the `Long` scalar. All other types that have no GraphQL counterpart (variants,
+//! results, tuples, flags) use the `JSON` scalar with the same JSON encoding as MCP tools.
+//! Methods with unstructured or multimodal parameters or results are not exposed.
+
+use super::GraphQlRequestContext;
+use super::values::{graphql_value_to_value, value_to_field_value};
+use crate::config::GraphQlConfig;
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, FieldValue, InputObject, InputValue, Object,
+    ResolverContext, Scalar, Schema, SchemaBuilder, TypeRef,
+};
+use golem_common::model::AgentId;
+use golem_common::model::agent::{
+    AgentMethod, AgentMode, AgentTypeName, ComponentModelElementSchema, ComponentModelElementValue,
+    DataSchema, DataValue, ElementSchema, ElementValue, ElementValues, LegacyParsedAgentId,
+    UntypedElementValue,
+};
+use golem_common::model::component::ComponentId;
+use golem_service_base::custom_api::{
+    GraphQlAgent, GraphQlBehaviour, RateLimitScope, RouteRateLimit,
+};
+use golem_wasm::ValueAndType;
+use golem_wasm::analysis::{AnalysedType, TypeEnum, TypeRecord};
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const QUERY_TYPE: &str = "Query";
+const MUTATION_TYPE: &str = "Mutation";
+const JSON_SCALAR: &str = "JSON";
+const LONG_SCALAR: &str = "Long";
+
+pub fn build_schema(
+    behaviour: &GraphQlBehaviour,
+    config: &GraphQlConfig,
+) -> Result<Schema, String> {
+    let mut types = TypeRegistry::new();
+
+    let mut query = Object::new(QUERY_TYPE);
+    let mut mutation = Object::new(MUTATION_TYPE);
+    let mut has_mutations = false;
+    let mut agent_type_names = Vec::new();
+
+    for agent in &behaviour.agents {
+        let Some(constructor_parameters) =
+            component_model_elements(&agent.agent_type.constructor.input_schema)
+        else {
+            tracing::debug!(
+                "Agent type {} has a constructor GraphQL cannot call",
+                agent.agent_type.type_name
+            );
+            continue;
+        };
+
+        let constructor = Arc::new(AgentConstructorSpec::new(
+            agent,
+            constructor_parameters
+                .into_iter()
+                .map(|(name, typ)| ParameterSpec::new(name, typ))
+                .collect(),
+        ));
+
+        let agent_type_name = type_name(&agent.agent_type.type_name.0);
+        let mut query_object = Object::new(types.reserve_name(&format!("{agent_type_name}Query")));
+        let mut mutation_object =
+            Object::new(types.reserve_name(&format!("{agent_type_name}Mutation")));
+        let mut query_count = 0;
+        let mut mutation_count = 0;
+
+        for method in &agent.agent_type.methods {
+            let Some(field) = method_field(&mut types, &agent_type_name, agent, method) else {
+                tracing::debug!(
+                    "Method {} of agent type {} cannot be exposed through GraphQL",
+                    method.name,
+                    agent.agent_type.type_name
+                );
+                continue;
+            };
+
+            if method.read_only.is_some() {
+                query_object = query_object.field(field);
+                query_count += 1;
+            } else {
+                mutation_object = mutation_object.field(field);
+                mutation_count += 1;
+            }
+        }
+
+        if query_count > 0 {
+            query = query.field(agent_field(
+                &mut types,
+                &agent_type_name,
+                query_object.type_name(),
+                &constructor,
+                &agent.agent_type.description,
+            ));
+            types.objects.push(query_object);
+        }
+
+        if mutation_count > 0 {
+            mutation = mutation.field(agent_field(
+                &mut types,
+                &agent_type_name,
+                mutation_object.type_name(),
+                &constructor,
+                &agent.agent_type.description,
+            ));
+            types.objects.push(mutation_object);
+            has_mutations = true;
+        }
+
+        agent_type_names.push(agent.agent_type.type_name.0.clone());
+    }
+
+    // The query type must have at least one field, even if no agent has read-only methods
+    query = query.field(
+        Field::new(
+            "agentTypes",
+            TypeRef::named_nn_list_nn(TypeRef::STRING),
+            move |_| {
+                let agent_type_names = agent_type_names.clone();
+                FieldFuture::new(async move {
+                    Ok(Some(FieldValue::list(
+                        agent_type_names.into_iter().map(FieldValue::value),
+                    )))
+                })
+            },
+        )
+        .description("Names of the agent types exposed by this endpoint"),
+    );
+
+    let mut builder = Schema::build(QUERY_TYPE, has_mutations.then_some(MUTATION_TYPE), None)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+        .register(query);
+
+    if has_mutations {
+        builder = builder.register(mutation);
+    }
+
+    types
+        .register_all(builder)
+        .finish()
+        .map_err(|e| e.to_string())
+}
+
+/// Root field of an agent type, resolving to the agent identified by the constructor arguments.
+fn agent_field(
+    types: &mut TypeRegistry,
+    agent_type_name: &str,
+    object_type_name: &str,
+    constructor: &Arc<AgentConstructorSpec>,
+    description: &str,
+) -> Field {
+    let resolver_constructor = constructor.clone();
+
+    let mut field = Field::new(
+        field_name(agent_type_name),
+        TypeRef::named_nn(object_type_name),
+        move |ctx| {
+            let constructor = resolver_constructor.clone();
+            FieldFuture::new(async move {
+                let arguments = parse_arguments(&ctx, &constructor.parameters)?;
+                let agent_id = constructor
+                    .agent_id(arguments)
+                    .map_err(async_graphql::Error::new)?;
+                Ok(Some(FieldValue::owned_any(ResolvedAgent { agent_id })))
+            })
+        },
+    );
+
+    for parameter in &constructor.parameters {
+        field = field.argument(InputValue::new(
+            &parameter.field_name,
+            types.input_type(
+                &parameter.typ,
+                &format!("{agent_type_name}{}", type_name(&parameter.name)),
+            ),
+        ));
+    }
+
+    if !description.is_empty() {
+        field = field.description(description);
+    }
+
+    field
+}
+
+/// Field of an agent method, or `None` if the method's parameters or result cannot be
+/// represented in GraphQL.
+fn method_field(
+    types: &mut TypeRegistry,
+    agent_type_name: &str,
+    agent: &GraphQlAgent,
+    method: &AgentMethod,
+) -> Option<Field> {
+    let parameters = component_model_elements(&method.input_schema)?;
+
+    let result_type = match component_model_elements(&method.output_schema)?.as_slice() {
+        [] => None,
+        [(_, typ)] => Some(typ.clone()),
+        _ => return None,
+    };
+
+    let method_type_name = format!("{agent_type_name}{}", type_name(&method.name));
+
+    let output_type_ref = match &result_type {
+        Some(typ) => types.output_type(typ, &format!("{method_type_name}Result")),
+        None => TypeRef::named_nn(TypeRef::BOOLEAN),
+    };
+
+    let spec = Arc::new(MethodSpec {
+        name: method.name.clone(),
+        parameters: parameters
+            .into_iter()
+            .map(|(name, typ)| ParameterSpec::new(name, typ))
+            .collect(),
+        result_type,
+        rate_limits: agent
+            .rate_limits
+            .iter()
+            .filter(|rate_limit| match &rate_limit.scope {
+                RateLimitScope::Deployment => false,
+                RateLimitScope::AgentType { .. } => true,
+                RateLimitScope::Method { method_name, .. } => method_name == &method.name,
+            })
+            .cloned()
+            .collect(),
+    });
+
+    let resolver_spec = spec.clone();
+
+    let mut field = Field::new(field_name(&method.name), output_type_ref, move |ctx| {
+        let spec = resolver_spec.clone();
+        FieldFuture::new(async move {
+            let agent = ctx.parent_value.try_downcast_ref::<ResolvedAgent>()?;
+            let request_context = ctx.data::<GraphQlRequestContext>()?;
+            let arguments = parse_arguments(&ctx, &spec.parameters)?;
+
+            request_context.acquire_rate_limits(&spec.rate_limits)?;
+
+            let mut elements = request_context
+                .invoke_agent(&agent.agent_id, &spec.name, arguments)
+                .await?;
+
+            match &spec.result_type {
+                None => Ok(Some(FieldValue::value(true))),
+                Some(result_type) => match (elements.pop(), elements.is_empty()) {
+                    (Some(UntypedElementValue::ComponentModel(value)), true) => {
+                        value_to_field_value(value, result_type)
+                            .map(Some)
+                            .map_err(async_graphql::Error::new)
+                    }
+                    _ => Err(async_graphql::Error::new(
+                        "Agent response did not match expected type",
+                    )),
+                },
+            }
+        })
+    });
+
+    for parameter in &spec.parameters {
+        field = field.argument(InputValue::new(
+            &parameter.field_name,
+            types.input_type(
+                &parameter.typ,
+                &format!("{method_type_name}{}", type_name(&parameter.name)),
+            ),
+        ));
+    }
+
+    if !method.description.is_empty() {
+        field = field.description(&method.description);
+    }
+
+    Some(field)
+}
+
+fn parse_arguments(
+    ctx: &ResolverContext<'_>,
+    parameters: &[ParameterSpec],
+) -> async_graphql::Result<Vec<golem_wasm::Value>> {
+    parameters
+        .iter()
+        .map(|parameter| {
+            let value = ctx
+                .args
+                .get(&parameter.field_name)
+                .map(|value| value.as_value().clone())
+                .unwrap_or(async_graphql::Value::Null);
+
+            graphql_value_to_value(&value, &parameter.typ).map_err(|e| {
+                async_graphql::Error::new(format!(
+                    "Invalid value for argument {}: {e}",
+                    parameter.field_name
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Names and types of the elements of a data schema, if all of them are component model values.
+fn component_model_elements(schema: &DataSchema) -> Option<Vec<(String, AnalysedType)>> {
+    match schema {
+        DataSchema::Tuple(elements) => elements
+            .elements
+            .iter()
+            .map(|element| match &element.schema {
+                ElementSchema::ComponentModel(ComponentModelElementSchema { element_type }) => {
+                    Some((element.name.clone(), element_type.clone()))
+                }
+                ElementSchema::UnstructuredText(_) | ElementSchema::UnstructuredBinary(_) => None,
+            })
+            .collect(),
+        DataSchema::Multimodal(_) => None,
+    }
+}
+
+/// Agent selected by a root field, passed to the resolvers of its method fields.
+struct ResolvedAgent {
+    agent_id: AgentId,
+}
+
+struct AgentConstructorSpec {
+    component_id: ComponentId,
+    agent_type: AgentTypeName,
+    mode: AgentMode,
+    phantom: bool,
+    parameters: Vec<ParameterSpec>,
+}
+
+impl AgentConstructorSpec {
+    fn new(agent: &GraphQlAgent, parameters: Vec<ParameterSpec>) -> Self {
+        Self {
+            component_id: agent.component_id,
+            agent_type: agent.agent_type.type_name.clone(),
+            mode: agent.agent_type.mode,
+            phantom: agent
+                .agent_type
+                .http_mount
+                .as_ref()
+                .is_some_and(|http_mount| http_mount.phantom_agent),
+            parameters,
+        }
+    }
+
+    fn agent_id(&self, arguments: Vec<golem_wasm::Value>) -> Result<AgentId, String> {
+        let elements = arguments
+            .into_iter()
+            .zip(&self.parameters)
+            .map(|(value, parameter)| {
+                ElementValue::ComponentModel(ComponentModelElementValue {
+                    value: ValueAndType::new(value, parameter.typ.clone()),
+                })
+            })
+            .collect();
+
+        let agent_id = LegacyParsedAgentId::new_auto_phantom(
+            self.agent_type.clone(),
+            DataValue::Tuple(ElementValues { elements }),
+            self.phantom.then(Uuid::new_v4),
+            self.mode,
+        )?;
+
+        Ok(AgentId {
+            component_id: self.component_id,
+            agent_id: agent_id.to_string(),
+        })
+    }
+}
+
+struct MethodSpec {
+    name: String,
+    parameters: Vec<ParameterSpec>,
+    result_type: Option<AnalysedType>,
+    /// Agent and method level rate limits applying to the method
+    rate_limits: Vec<RouteRateLimit>,
+}
+
+struct ParameterSpec {
+    name: String,
+    field_name: String,
+    typ: AnalysedType,
+}
+
+impl ParameterSpec {
+    fn new(name: String, typ: AnalysedType) -> Self {
+        Self {
+            field_name: field_name(&name),
+            name,
+            typ,
+        }
+    }
+}
+
+/// Named GraphQL types generated for component model types.
+struct TypeRegistry {
+    /// All type names in use, with the component model type they were generated for
+    names: HashMap<String, Option<AnalysedType>>,
+    objects: Vec<Object>,
+    input_objects: Vec<InputObject>,
+    enums: Vec<Enum>,
+}
+
+impl TypeRegistry {
+    fn new() -> Self {
+        let names = [
+            QUERY_TYPE,
+            MUTATION_TYPE,
+            JSON_SCALAR,
+            LONG_SCALAR,
+            TypeRef::INT,
+            TypeRef::FLOAT,
+            TypeRef::STRING,
+            TypeRef::BOOLEAN,
+            TypeRef::ID,
+        ]
+        .into_iter()
+        .map(|name| (name.to_string(), None))
+        .collect();
+
+        Self {
+            names,
+            objects: Vec::new(),
+            input_objects: Vec::new(),
+            enums: Vec::new(),
+        }
+    }
+
+    /// Reserves a type name not generated for a component model type.
+    fn reserve_name(&mut self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut suffix = 1;
+        while self.names.contains_key(&candidate) {
+            suffix += 1;
+            candidate = format!("{name}{suffix}");
+        }
+        self.names.insert(candidate.clone(), None);
+        candidate
+    }
+
+    /// Type name for a component model type. Returns `false` if the type was already registered.
+    fn type_name_for(&mut self, name: &str, typ: &AnalysedType) -> (String, bool) {
+        let mut candidate = name.to_string();
+        let mut suffix = 1;
+        loop {
+            match self.names.get(&candidate) {
+                Some(Some(existing)) if existing == typ => return (candidate, false),
+                Some(_) => {
+                    suffix += 1;
+                    candidate = format!("{name}{suffix}");
+                }
+                None => {
+                    self.names.insert(candidate.clone(), Some(typ.clone()));
+                    return (candidate, true);
+                }
+            }
+        }
+    }
+
+    fn output_type(&mut self, typ: &AnalysedType, name_hint: &str) -> TypeRef {
+        match typ {
+            AnalysedType::Option(inner) => nullable(self.output_type(&inner.inner, name_hint)),
+            AnalysedType::List(inner) => TypeRef::NonNull(Box::new(TypeRef::List(Box::new(
+                self.output_type(&inner.inner, name_hint),
+            )))),
+            AnalysedType::Record(record) if !record.fields.is_empty() => {
+                TypeRef::named_nn(self.record_object(typ, record, name_hint))
+            }
+            AnalysedType::Enum(enum_type) if !enum_type.cases.is_empty() => {
+                TypeRef::named_nn(self.enum_type(typ, enum_type, name_hint))
+            }
+            _ => TypeRef::named_nn(scalar_type_name(typ)),
+        }
+    }
+
+    fn input_type(&mut self, typ: &AnalysedType, name_hint: &str) -> TypeRef {
+        match typ {
+            AnalysedType::Option(inner) => nullable(self.input_type(&inner.inner, name_hint)),
+            AnalysedType::List(inner) => TypeRef::NonNull(Box::new(TypeRef::List(Box::new(
+                self.input_type(&inner.inner, name_hint),
+            )))),
+            AnalysedType::Record(record) if !record.fields.is_empty() => {
+                TypeRef::named_nn(self.record_input_object(typ, record, name_hint))
+            }
+            AnalysedType::Enum(enum_type) if !enum_type.cases.is_empty() => {
+                TypeRef::named_nn(self.enum_type(typ, enum_type, name_hint))
+            }
+            _ => TypeRef::named_nn(scalar_type_name(typ)),
+        }
+    }
+
+    fn record_object(
+        &mut self,
+        typ: &AnalysedType,
+        record: &TypeRecord,
+        name_hint: &str,
+    ) -> String {
+        let (name, is_new) =
+            self.type_name_for(&type_name(record.name.as_deref().unwrap_or(name_hint)), typ);
+
+        if is_new {
+            let mut object = Object::new(&name);
+            for (index, field) in record.fields.iter().enumerate() {
+                let field_type = field.typ.clone();
+                let field_type_ref =
+                    self.output_type(&field.typ, &format!("{name}{}", type_name(&field.name)));
+
+                object = object.field(Field::new(
+                    field_name(&field.name),
+                    field_type_ref,
+                    move |ctx| {
+                        let field_type = field_type.clone();
+                        FieldFuture::new(async move {
+                            let golem_wasm::Value::Record(fields) =
+                                ctx.parent_value.try_downcast_ref::<golem_wasm::Value>()?
+                            else {
+                                return Err(async_graphql::Error::new("Expected a record value"));
+                            };
+                            let value = fields
+                                .get(index)
+                                .cloned()
+                                .ok_or_else(|| async_graphql::Error::new("Missing record field"))?;
+                            value_to_field_value(value, &field_type)
+                                .map(Some)
+                                .map_err(async_graphql::Error::new)
+                        })
+                    },
+                ));
+            }
+            self.objects.push(object);
+        }
+
+        name
+    }
+
+    fn record_input_object(
+        &mut self,
+        typ: &AnalysedType,
+        record: &TypeRecord,
+        name_hint: &str,
+    ) -> String {
+        let base_name = type_name(record.name.as_deref().unwrap_or(name_hint));
+        let (name, is_new) = self.type_name_for(&format!("{base_name}Input"), typ);
+
+        if is_new {
+            let mut input_object = InputObject::new(&name);
+            for field in &record.fields {
+                input_object = input_object.field(InputValue::new(
+                    field_name(&field.name),
+                    self.input_type(
+                        &field.typ,
+                        &format!("{base_name}{}", type_name(&field.name)),
+                    ),
+                ));
+            }
+            self.input_objects.push(input_object);
+        }
+
+        name
+    }
+
+    fn enum_type(&mut self, typ: &AnalysedType, enum_type: &TypeEnum, name_hint: &str) -> String {
+        let (name, is_new) = self.type_name_for(
+            &type_name(enum_type.name.as_deref().unwrap_or(name_hint)),
+            typ,
+        );
+
+        if is_new {
+            let graphql_enum = enum_type
+                .cases
+                .iter()
+                .fold(Enum::new(&name), |graphql_enum, case| {
+                    graphql_enum.item(EnumItem::new(enum_item_name(case)))
+                });
+            self.enums.push(graphql_enum);
+        }
+
+        name
+    }
+
+    fn register_all(self, mut builder: SchemaBuilder) -> SchemaBuilder {
+        builder = builder
+            .register(
+                Scalar::new(JSON_SCALAR)
+                    .description("Component model value without a GraphQL counterpart, as JSON"),
+            )
+            .register(Scalar::new(LONG_SCALAR).description("64-bit integer"));
+
+        for object in self.objects {
+            builder = builder.register(object);
+        }
+        for input_object in self.input_objects {
+            builder = builder.register(input_object);
+        }
+        for graphql_enum in self.enums {
+            builder = builder.register(graphql_enum);
+        }
+
+        builder
+    }
+}
+
+fn nullable(type_ref: TypeRef) -> TypeRef {
+    match type_ref {
+        TypeRef::NonNull(inner) => *inner,
+        other => other,
+    }
+}
+
+fn scalar_type_name(typ: &AnalysedType) -> &'static str {
+    match typ {
+        AnalysedType::Bool(_) => TypeRef::BOOLEAN,
+        AnalysedType::S8(_)
+        | AnalysedType::S16(_)
+        | AnalysedType::S32(_)
+        | AnalysedType::U8(_)
+        | AnalysedType::U16(_) => TypeRef::INT,
+        AnalysedType::U32(_) | AnalysedType::S64(_) | AnalysedType::U64(_) => LONG_SCALAR,
+        AnalysedType::F32(_) | AnalysedType::F64(_) => TypeRef::FLOAT,
+        AnalysedType::Chr(_) | AnalysedType::Str(_) => TypeRef::STRING,
+        _ => JSON_SCALAR,
+    }
+}
+
+pub(super) fn field_name(name: &str) -> String {
+    valid_name(name.to_lower_camel_case())
+}
+
+pub(super) fn type_name(name: &str) -> String {
+    valid_name(name.to_upper_camel_case())
+}
+
+pub(super) fn enum_item_name(name: &str) -> String {
+    valid_name(name.to_shouty_snake_case())
+}
+
+/// GraphQL names may only contain ASCII letters, digits and underscores and must not start with a digit.
+fn valid_name(name: String) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::Empty;
+    use golem_common::model::agent::{
+        AgentConstructor, AgentType, CachePolicy, NamedElementSchema, NamedElementSchemas,
+        ReadOnlyConfig, Snapshotting,
+    };
+    use golem_common::model::component::ComponentRevision;
+    use golem_wasm::analysis::analysed_type::{field, list, option, record, str, u64};
+    use test_r::test;
+
+    fn element(name: &str, element_type: AnalysedType) -> NamedElementSchema {
+        NamedElementSchema {
+            name: name.to_string(),
+            schema: ElementSchema::ComponentModel(ComponentModelElementSchema { element_type }),
+        }
+    }
+
+    fn method(name: &str, inputs: Vec<NamedElementSchema>, read_only: bool) -> AgentMethod {
+        AgentMethod {
+            name: name.to_string(),
+            description: String::new(),
+            prompt_hint: None,
+            input_schema: DataSchema::Tuple(NamedElementSchemas { elements: inputs }),
+            output_schema: DataSchema::Tuple(NamedElementSchemas {
+                elements: vec![element(
+                    "result",
+                    list(record(vec![
+                        field("title", str()),
+                        field("votes", option(u64())),
+                    ]))
+                    .named("note"),
+                )],
+            }),
+            http_endpoint: vec![],
+            read_only: read_only.then(|| ReadOnlyConfig {
+                cache_policy: CachePolicy::UntilWrite(Empty {}),
+                uses_principal: false,
+            }),
+            timeout_nanos: None,
+        }
+    }
+
+    fn behaviour() -> GraphQlBehaviour {
+        GraphQlBehaviour {
+            agents: vec![GraphQlAgent {
+                rate_limits: Vec::new(),
+                component_id: ComponentId(Uuid::new_v4()),
+                component_revision: ComponentRevision::INITIAL,
+                agent_type: AgentType {
+                    type_name: AgentTypeName("note-agent".to_string()),
+                    description: String::new(),
+                    source_language: String::new(),
+                    constructor: AgentConstructor {
+                        name: None,
+                        description: String::new(),
+                        prompt_hint: None,
+                        input_schema: DataSchema::Tuple(NamedElementSchemas {
+                            elements: vec![element("owner", str())],
+                        }),
+                    },
+                    methods: vec![
+                        method("list-notes", vec![], true),
+                        method("add-note", vec![element("title", str())], false),
+                    ],
+                    dependencies: vec![],
+                    mode: AgentMode::Durable,
+                    http_mount: None,
+                    snapshotting: Snapshotting::Disabled(Empty {}),
+                    config: vec![],
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn read_only_methods_are_queries_and_others_mutations() {
+        let sdl = build_schema(&behaviour(), &GraphQlConfig::default())
+            .unwrap()
+            .sdl();
+
+        assert!(sdl.contains("noteAgent(owner: String!): NoteAgentQuery!"));
+        assert!(sdl.contains("noteAgent(owner: String!): NoteAgentMutation!"));
+        assert!(sdl.contains("listNotes: [Note!]!"));
+        assert!(sdl.contains("addNote(title: String!)"));
+        assert!(sdl.contains("votes: Long"));
+    }
+
+    #[test]
+    fn schema_without_agents_only_lists_agent_types() {
+        let schema = build_schema(
+            &GraphQlBehaviour { agents: vec![] },
+            &GraphQlConfig::default(),
+        )
+        .unwrap();
+        let sdl = schema.sdl();
+
+        assert!(sdl.contains("agentTypes: [String!]!"));
+        assert!(!sdl.contains("type Mutation"));
+    }
+
+    #[test]
+    async fn operations_over_the_configured_limits_are_rejected() {
+        let query = r#"{ noteAgent(owner: "alice") { listNotes { title votes } } }"#;
+
+        let schema = build_schema(
+            &behaviour(),
+            &GraphQlConfig {
+                max_depth: 2,
+                max_complexity: 100,
+            },
+        )
+        .unwrap();
+        let response = schema.execute(query).await;
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("nested too deep"));
+
+        let schema = build_schema(
+            &behaviour(),
+            &GraphQlConfig {
+                max_depth: 10,
+                max_complexity: 3,
+            },
+        )
+        .unwrap();
+        let response = schema.execute(query).await;
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("too complex"));
+    }
+
+    #[test]
+    fn names_are_valid_graphql_names() {
+        assert_eq!(field_name("list-notes"), "listNotes");
+        assert_eq!(type_name("note-agent"), "NoteAgent");
+        assert_eq!(enum_item_name("in-progress"), "IN_PROGRESS");
+        assert_eq!(field_name("1st"), "_1st");
+    }
+}
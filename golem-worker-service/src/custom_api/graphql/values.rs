@@ -0,0 +1,253 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion between component model values and GraphQL values, following the type mapping
+//! of the generated schema.
+
+use super::schema::{enum_item_name, field_name};
+use crate::mcp::invoke::{component_model_value_to_json, parse_component_model_value};
+use async_graphql::dynamic::FieldValue;
+use async_graphql::{Name, Number};
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm::{Value, ValueAndType};
+
+/// Converts an agent method result to the value of a GraphQL output field.
+/// Non-empty records are kept as component model values and resolved field by field.
+pub fn value_to_field_value(
+    value: Value,
+    typ: &AnalysedType,
+) -> Result<FieldValue<'static>, String> {
+    match (typ, value) {
+        (AnalysedType::Option(option), Value::Option(inner)) => match inner {
+            Some(inner) => value_to_field_value(*inner, &option.inner),
+            None => Ok(FieldValue::NULL),
+        },
+        (AnalysedType::List(list), Value::List(items)) => Ok(FieldValue::list(
+            items
+                .into_iter()
+                .map(|item| value_to_field_value(item, &list.inner))
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        (AnalysedType::Record(record), value @ Value::Record(_)) if !record.fields.is_empty() => {
+            Ok(FieldValue::owned_any(value))
+        }
+        (typ, value) => value_to_graphql_value(value, typ).map(FieldValue::value),
+    }
+}
+
+fn value_to_graphql_value(
+    value: Value,
+    typ: &AnalysedType,
+) -> Result<async_graphql::Value, String> {
+    match (typ, value) {
+        (AnalysedType::Enum(enum_type), Value::Enum(case_idx)) if !enum_type.cases.is_empty() => {
+            let case = enum_type
+                .cases
+                .get(case_idx as usize)
+                .ok_or_else(|| format!("Enum case index {case_idx} out of range"))?;
+            Ok(async_graphql::Value::Enum(Name::new(enum_item_name(case))))
+        }
+        (AnalysedType::Bool(_), Value::Bool(value)) => Ok(async_graphql::Value::Boolean(value)),
+        (AnalysedType::S8(_), Value::S8(value)) => Ok(value.into()),
+        (AnalysedType::S16(_), Value::S16(value)) => Ok(value.into()),
+        (AnalysedType::S32(_), Value::S32(value)) => Ok(value.into()),
+        (AnalysedType::S64(_), Value::S64(value)) => Ok(value.into()),
+        (AnalysedType::U8(_), Value::U8(value)) => Ok(value.into()),
+        (AnalysedType::U16(_), Value::U16(value)) => Ok(value.into()),
+        (AnalysedType::U32(_), Value::U32(value)) => Ok(value.into()),
+        (AnalysedType::U64(_), Value::U64(value)) => Ok(value.into()),
+        (AnalysedType::F32(_), Value::F32(value)) => float_value(value as f64),
+        (AnalysedType::F64(_), Value::F64(value)) => float_value(value),
+        (AnalysedType::Chr(_), Value::Char(value)) => {
+            Ok(async_graphql::Value::String(value.to_string()))
+        }
+        (AnalysedType::Str(_), Value::String(value)) => Ok(async_graphql::Value::String(value)),
+        (typ, value) => {
+            let json = component_model_value_to_json(&ValueAndType::new(value, typ.clone()))?;
+            async_graphql::Value::from_json(json).map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn float_value(value: f64) -> Result<async_graphql::Value, String> {
+    Number::from_f64(value)
+        .map(async_graphql::Value::Number)
+        .ok_or_else(|| format!("{value} cannot be represented in GraphQL"))
+}
+
+/// Converts a GraphQL argument value to a component model value of the given type.
+pub fn graphql_value_to_value(
+    value: &async_graphql::Value,
+    typ: &AnalysedType,
+) -> Result<Value, String> {
+    match (typ, value) {
+        (AnalysedType::Option(_), async_graphql::Value::Null) => Ok(Value::Option(None)),
+        (AnalysedType::Option(option), value) => Ok(Value::Option(Some(Box::new(
+            graphql_value_to_value(value, &option.inner)?,
+        )))),
+        (AnalysedType::List(list), async_graphql::Value::List(items)) => items
+            .iter()
+            .map(|item| graphql_value_to_value(item, &list.inner))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::List),
+        (AnalysedType::Record(record), async_graphql::Value::Object(fields))
+            if !record.fields.is_empty() =>
+        {
+            record
+                .fields
+                .iter()
+                .map(|field| {
+                    let field_value = fields
+                        .get(field_name(&field.name).as_str())
+                        .unwrap_or(&async_graphql::Value::Null);
+                    graphql_value_to_value(field_value, &field.typ)
+                        .map_err(|e| format!("{}: {e}", field.name))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Record)
+        }
+        (
+            AnalysedType::Enum(enum_type),
+            async_graphql::Value::Enum(_) | async_graphql::Value::String(_),
+        ) if !enum_type.cases.is_empty() => {
+            let name = match value {
+                async_graphql::Value::Enum(name) => name.as_str(),
+                async_graphql::Value::String(name) => name.as_str(),
+                _ => unreachable!(),
+            };
+            enum_type
+                .cases
+                .iter()
+                .position(|case| enum_item_name(case) == name)
+                .map(|case_idx| Value::Enum(case_idx as u32))
+                .ok_or_else(|| format!("Unknown enum value {name}"))
+        }
+        (AnalysedType::Bool(_), async_graphql::Value::Boolean(value)) => Ok(Value::Bool(*value)),
+        (AnalysedType::S8(_), value) => integer(value).map(Value::S8),
+        (AnalysedType::S16(_), value) => integer(value).map(Value::S16),
+        (AnalysedType::S32(_), value) => integer(value).map(Value::S32),
+        (AnalysedType::S64(_), value) => integer(value).map(Value::S64),
+        (AnalysedType::U8(_), value) => integer(value).map(Value::U8),
+        (AnalysedType::U16(_), value) => integer(value).map(Value::U16),
+        (AnalysedType::U32(_), value) => integer(value).map(Value::U32),
+        (AnalysedType::U64(_), value) => integer(value).map(Value::U64),
+        (AnalysedType::F32(_), async_graphql::Value::Number(number)) => number
+            .as_f64()
+            .map(|value| Value::F32(value as f32))
+            .ok_or_else(|| format!("Expected a float, got {number}")),
+        (AnalysedType::F64(_), async_graphql::Value::Number(number)) => number
+            .as_f64()
+            .map(Value::F64)
+            .ok_or_else(|| format!("Expected a float, got {number}")),
+        (AnalysedType::Chr(_), async_graphql::Value::String(value)) => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Value::Char(c)),
+                _ => Err(format!("Expected a single character, got {value:?}")),
+            }
+        }
+        (AnalysedType::Str(_), async_graphql::Value::String(value)) => {
+            Ok(Value::String(value.clone()))
+        }
+        (typ, value) => {
+            let json = value.clone().into_json().map_err(|e| e.to_string())?;
+            parse_component_model_value(&json, typ)
+        }
+    }
+}
+
+/// Integer from a GraphQL number. The `Long` scalar also accepts its value as a string.
+fn integer<T>(value: &async_graphql::Value) -> Result<T, String>
+where
+    T: TryFrom<i64> + TryFrom<u64> + std::str::FromStr,
+{
+    let out_of_range = || format!("Integer {value} out of range");
+    match value {
+        async_graphql::Value::Number(number) => {
+            if let Some(n) = number.as_u64() {
+                T::try_from(n).map_err(|_| out_of_range())
+            } else if let Some(n) = number.as_i64() {
+                T::try_from(n).map_err(|_| out_of_range())
+            } else {
+                Err(format!("Expected an integer, got {number}"))
+            }
+        }
+        async_graphql::Value::String(s) => s.parse().map_err(|_| out_of_range()),
+        other => Err(format!("Expected an integer, got {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm::analysis::analysed_type::{r#enum, field, list, option, record, str, u64};
+    use test_r::test;
+
+    #[test]
+    fn record_arguments_are_read_by_graphql_field_name() {
+        let typ = record(vec![
+            field("display-name", str()),
+            field("status", r#enum(&["in-progress", "done"])),
+            field("votes", option(u64())),
+            field("tags", list(str())),
+        ]);
+
+        let argument = async_graphql::Value::from_json(serde_json::json!({
+            "displayName": "first",
+            "status": "IN_PROGRESS",
+            "votes": "18446744073709551615",
+            "tags": ["a", "b"],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            graphql_value_to_value(&argument, &typ).unwrap(),
+            Value::Record(vec![
+                Value::String("first".to_string()),
+                Value::Enum(0),
+                Value::Option(Some(Box::new(Value::U64(u64::MAX)))),
+                Value::List(vec![
+                    Value::String("a".to_string()),
+                    Value::String("b".to_string()),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn missing_optional_arguments_are_none() {
+        let typ = record(vec![field("votes", option(u64()))]);
+        let argument = async_graphql::Value::from_json(serde_json::json!({})).unwrap();
+
+        assert_eq!(
+            graphql_value_to_value(&argument, &typ).unwrap(),
+            Value::Record(vec![Value::Option(None)])
+        );
+    }
+
+    #[test]
+    fn enum_results_use_graphql_item_names() {
+        assert_eq!(
+            value_to_graphql_value(Value::Enum(1), &r#enum(&["in-progress", "done"])).unwrap(),
+            async_graphql::Value::Enum(Name::new("DONE"))
+        );
+    }
+
+    #[test]
+    fn integers_out_of_range_are_rejected() {
+        let value = async_graphql::Value::from_json(serde_json::json!(300)).unwrap();
+        assert!(integer::<u8>(&value).is_err());
+        assert_eq!(integer::<u16>(&value), Ok(300));
+    }
+}
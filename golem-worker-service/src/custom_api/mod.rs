@@ -16,10 +16,12 @@ pub mod api_definition_lookup;
 pub mod call_agent;
 mod cors;
 pub mod error;
+pub mod graphql;
 pub mod model;
 pub mod oidc;
 mod openapi;
 pub mod poem_endpoint;
+pub mod rate_limit;
 pub mod request_handler;
 mod rich_request;
 pub mod route_resolver;
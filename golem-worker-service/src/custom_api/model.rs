@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::config::GraphQlConfig;
+use crate::custom_api::graphql::GraphQlApi;
 use crate::custom_api::openapi::HttpApiOpenApiSpec;
 use chrono::{DateTime, Utc};
 use golem_common::model::account::{AccountEmail, AccountId};
@@ -22,7 +24,9 @@ use golem_service_base::custom_api::{
     OpenApiSpecFormat, SecuritySchemeDetails, SessionFromHeaderRouteSecurity,
     WebhookCallbackBehaviour,
 };
-use golem_service_base::custom_api::{PathSegment, RequestBodySchema, RouteBehaviour, RouteId};
+use golem_service_base::custom_api::{
+    PathSegment, RequestBodySchema, RouteBehaviour, RouteId, RouteRateLimit,
+};
 use http::Method;
 use http::{HeaderName, StatusCode};
 use openidconnect::Scope;
@@ -71,6 +75,7 @@ pub struct RichCompiledRoute {
     pub behavior: RichRouteBehaviour,
     pub security: RichRouteSecurity,
     pub cors: CorsOptions,
+    pub rate_limits: Vec<RouteRateLimit>,
 }
 
 #[derive(Debug)]
@@ -80,15 +85,20 @@ pub enum RichRouteBehaviour {
     WebhookCallback(WebhookCallbackBehaviour),
     OpenApiSpec(OpenApiSpecBehaviour),
     OidcCallback(OidcCallbackBehaviour),
+    WebSocket(CallAgentBehaviour),
+    GraphQl(Arc<GraphQlApi>),
 }
 
-impl From<RouteBehaviour> for RichRouteBehaviour {
-    fn from(value: RouteBehaviour) -> Self {
+impl RichRouteBehaviour {
+    pub fn new(value: RouteBehaviour, graphql_config: &GraphQlConfig) -> Self {
         match value {
             RouteBehaviour::CallAgent(inner) => Self::CallAgent(inner),
             RouteBehaviour::CorsPreflight(inner) => Self::CorsPreflight(inner),
             RouteBehaviour::WebhookCallback(inner) => Self::WebhookCallback(inner),
             RouteBehaviour::OpenApiSpec(inner) => Self::OpenApiSpec(inner),
+            RouteBehaviour::GraphQl(inner) => {
+                Self::GraphQl(Arc::new(GraphQlApi::new(inner, graphql_config)))
+            }
         }
     }
 }
@@ -110,13 +120,14 @@ pub struct RichSecuritySchemeRouteSecurity {
     pub security_scheme: Arc<SecuritySchemeDetails>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RouteExecutionResult {
     pub status: StatusCode,
     pub headers: HashMap<HeaderName, String>,
     pub body: ResponseBody,
 }
 
+#[derive(Clone)]
 pub enum ResponseBody {
     NoBody,
     ComponentModelJsonBody {
@@ -132,6 +143,9 @@ pub enum ResponseBody {
         spec: Arc<HttpApiOpenApiSpec>,
         format: OpenApiSpecFormat,
     },
+    GraphQl {
+        body: serde_json::Value,
+    },
 }
 
 impl fmt::Debug for ResponseBody {
@@ -149,6 +163,9 @@ impl fmt::Debug for ResponseBody {
                 .field("spec", &spec.0)
                 .field("format", format)
                 .finish(),
+            ResponseBody::GraphQl { body } => {
+                f.debug_struct("GraphQl").field("body", body).finish()
+            }
         }
     }
 }
@@ -160,6 +177,16 @@ pub enum ParsedRequestBody {
     UnstructuredBinary(Option<BinarySource>),
     // Always Some initially, will be None after being consumed by handler code
     UnstructuredText(Option<TextSource>),
+    Form(FormBody),
+}
+
+/// Fields of a `multipart/form-data` or `application/x-www-form-urlencoded` request body.
+/// Unstructured fields are removed from the maps when consumed by handler code.
+#[derive(Default)]
+pub struct FormBody {
+    pub values: HashMap<String, Vec<String>>,
+    pub binaries: HashMap<String, BinarySource>,
+    pub texts: HashMap<String, TextSource>,
 }
 
 impl fmt::Debug for ParsedRequestBody {
@@ -169,6 +196,12 @@ impl fmt::Debug for ParsedRequestBody {
             ParsedRequestBody::JsonBody(value) => f.debug_tuple("JsonBody").field(value).finish(),
             ParsedRequestBody::UnstructuredBinary(_) => f.write_str("UnstructuredBinary"),
             ParsedRequestBody::UnstructuredText(_) => f.write_str("UnstructuredText"),
+            ParsedRequestBody::Form(form) => f
+                .debug_struct("Form")
+                .field("values", &form.values)
+                .field("binaries", &form.binaries.keys())
+                .field("texts", &form.texts.keys())
+                .finish(),
         }
     }
 }
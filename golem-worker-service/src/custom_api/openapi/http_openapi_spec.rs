@@ -21,7 +21,9 @@
 use super::response_schema::{
     ResponseBodyOpenApiSchema, RouteResponseOpenApiSchema, get_route_response_schema,
 };
-use super::route_schema::{RequestBodyModel, RouteSchema, build_document_schema};
+use super::route_schema::{
+    FormFieldKind, FormFieldModel, RequestBodyModel, RouteSchema, build_document_schema,
+};
 use super::schema_mapping::{
     arbitrary_binary_schema, render_schema, string_enum_schema, string_schema,
 };
@@ -44,6 +46,14 @@ impl HttpApiOpenApiSpec {
         let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
 
         for (route, route_schema) in routes.iter().zip(document.per_route.iter()) {
+            // WebSocket upgrades have no request / response bodies OpenAPI could describe,
+            // GraphQL endpoints describe themselves through introspection
+            if let RichRouteBehaviour::WebSocket(_) | RichRouteBehaviour::GraphQl(_) =
+                &route.behavior
+            {
+                continue;
+            }
+
             collect_security_scheme(route, &mut security_schemes);
 
             let operation = build_operation(route, route_schema, graph, &mut component_schemas)?;
@@ -186,12 +196,16 @@ fn build_request_body(
 ) -> Result<Option<Value>, String> {
     Ok(match body {
         RequestBodyModel::Unused => None,
-        RequestBodyModel::Json(ty) => {
-            let schema = render_schema(graph, ty, components)?;
-            Some(request_body_value(
-                "JSON body",
-                vec![("application/json".to_string(), schema)],
-            ))
+        RequestBodyModel::Json {
+            schema,
+            form_fields,
+        } => {
+            let schema = render_schema(graph, schema, components)?;
+            let mut content = vec![("application/json".to_string(), schema)];
+            if let Some(fields) = form_fields {
+                content.extend(form_content(fields, graph, components)?);
+            }
+            Some(request_body_value("JSON body", content))
         }
         RequestBodyModel::UnrestrictedBinary => Some(request_body_value(
             "Unrestricted binary body",
@@ -222,9 +236,56 @@ fn build_request_body(
                 vec![("text/plain".to_string(), string_schema())],
             ))
         }
+        RequestBodyModel::Form { fields } => Some(request_body_value(
+            "Form body",
+            form_content(fields, graph, components)?,
+        )),
     })
 }
 
+/// Form bodies are described as an object with one property per field. File
+/// fields can only be sent as `multipart/form-data`.
+fn form_content(
+    fields: &[FormFieldModel],
+    graph: &SchemaGraph,
+    components: &mut Map<String, Value>,
+) -> Result<Vec<(String, Value)>, String> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut has_files = false;
+
+    for field in fields {
+        let schema = match &field.kind {
+            FormFieldKind::Value(ty) => render_schema(graph, ty, components)?,
+            FormFieldKind::File { mime_types } => {
+                has_files = true;
+                let mut schema = arbitrary_binary_schema();
+                if !mime_types.is_empty() {
+                    schema["contentMediaType"] = json!(mime_types.join(", "));
+                }
+                schema
+            }
+            FormFieldKind::Text => string_schema(),
+        };
+        properties.insert(field.name.clone(), schema);
+        if field.required {
+            required.push(json!(field.name));
+        }
+    }
+
+    let schema = json!({
+        "type": "object",
+        "properties": Value::Object(properties),
+        "required": required,
+    });
+
+    let mut content = vec![("multipart/form-data".to_string(), schema.clone())];
+    if !has_files {
+        content.push(("application/x-www-form-urlencoded".to_string(), schema));
+    }
+    Ok(content)
+}
+
 fn request_body_value(description: &str, content: Vec<(String, Value)>) -> Value {
     let content_map: Map<String, Value> = content
         .into_iter()
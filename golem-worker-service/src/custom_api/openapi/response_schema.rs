@@ -116,6 +116,9 @@ pub fn get_route_response_schema(
                 },
             );
         }
+        RichRouteBehaviour::WebSocket(_) => {
+            responses.insert(101, ResponseBodyOpenApiSchema::NoBody);
+        }
         RichRouteBehaviour::WebhookCallback(_) => {
             responses.insert(204, ResponseBodyOpenApiSchema::NoBody);
             responses.insert(404, ResponseBodyOpenApiSchema::NoBody);
@@ -136,6 +139,18 @@ pub fn get_route_response_schema(
                 },
             );
         }
+        RichRouteBehaviour::GraphQl(_) => {
+            responses.insert(
+                200,
+                ResponseBodyOpenApiSchema::Known {
+                    schema: serde_json::json!({
+                        "type": "object",
+                        "additionalProperties": true,
+                    }),
+                    content_type: "application/json".to_string(),
+                },
+            );
+        }
         RichRouteBehaviour::OidcCallback(_) => {
             // Preserves the legacy emitter behaviour: only response headers are
             // described and no status code is registered, so the rendered
@@ -300,5 +315,6 @@ fn http_method_name(method: &HttpMethod) -> String {
         HttpMethod::Trace(_) => "TRACE".to_string(),
         HttpMethod::Patch(_) => "PATCH".to_string(),
         HttpMethod::Custom(custom) => custom.value.to_uppercase(),
+        HttpMethod::WebSocket(_) => "GET".to_string(),
     }
 }
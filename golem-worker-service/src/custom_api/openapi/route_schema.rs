@@ -40,7 +40,8 @@ use golem_common::schema::adapters::{
 use golem_common::schema::graph::SchemaGraph;
 use golem_common::schema::schema_type::{BinaryRestrictions, SchemaType, TextRestrictions};
 use golem_service_base::custom_api::{
-    CallAgentBehaviour, PathSegment, QueryOrHeaderType, RequestBodySchema,
+    CallAgentBehaviour, FormFieldSchema, FormFieldType, PathSegment, QueryOrHeaderType,
+    RequestBodySchema,
 };
 use golem_wasm::analysis::AnalysedType;
 
@@ -92,11 +93,40 @@ pub struct NamedParamSchema {
 /// the body is a named composite).
 pub enum RequestBodyModel {
     Unused,
-    Json(Box<SchemaType>),
+    /// `form_fields` is set when the JSON object body can also be sent as a form.
+    Json {
+        schema: Box<SchemaType>,
+        form_fields: Option<Vec<FormFieldModel>>,
+    },
     UnrestrictedBinary,
-    RestrictedBinary { mime_types: Vec<String> },
+    RestrictedBinary {
+        mime_types: Vec<String>,
+    },
     UnrestrictedText,
-    RestrictedText { language_codes: Vec<String> },
+    RestrictedText {
+        language_codes: Vec<String>,
+    },
+    Form {
+        fields: Vec<FormFieldModel>,
+    },
+}
+
+/// A field of a form body, with its HTTP-required flag (`option<…>` value
+/// fields are not required).
+pub struct FormFieldModel {
+    pub name: String,
+    pub kind: FormFieldKind,
+    pub required: bool,
+}
+
+pub enum FormFieldKind {
+    /// A simple value, with its inline scalar/enum/list schema.
+    Value(SchemaType),
+    /// A file part; an empty `mime_types` list accepts any MIME type.
+    File {
+        mime_types: Vec<String>,
+    },
+    Text,
 }
 
 /// Response schema model. The HTTP response policy (status-code split for
@@ -149,9 +179,13 @@ fn lower_request_body(
 ) -> Result<RequestBodyModel, SchemaAdapterError> {
     Ok(match body {
         RequestBodySchema::Unused => RequestBodyModel::Unused,
-        RequestBodySchema::JsonBody { expected_type } => {
-            RequestBodyModel::Json(Box::new(builder.lower(expected_type)?))
-        }
+        RequestBodySchema::JsonBody { expected_type } => RequestBodyModel::Json {
+            schema: Box::new(builder.lower(expected_type)?),
+            form_fields: body
+                .form_fields()
+                .map(|fields| lower_form_fields(&fields))
+                .transpose()?,
+        },
         RequestBodySchema::UnrestrictedBinary => RequestBodyModel::UnrestrictedBinary,
         RequestBodySchema::RestrictedBinary { allowed_mime_types } => {
             RequestBodyModel::RestrictedBinary {
@@ -164,9 +198,42 @@ fn lower_request_body(
         } => RequestBodyModel::RestrictedText {
             language_codes: allowed_language_codes.clone(),
         },
+        RequestBodySchema::FormBody { fields } => RequestBodyModel::Form {
+            fields: lower_form_fields(fields)?,
+        },
     })
 }
 
+fn lower_form_fields(
+    fields: &[FormFieldSchema],
+) -> Result<Vec<FormFieldModel>, SchemaAdapterError> {
+    fields
+        .iter()
+        .map(|field| {
+            let (kind, required) = match &field.field_type {
+                FormFieldType::Value(value_type) => (
+                    FormFieldKind::Value(analysed_type_to_schema_type_inline(
+                        &AnalysedType::from(value_type.clone()),
+                    )?),
+                    !matches!(value_type, QueryOrHeaderType::Option { .. }),
+                ),
+                FormFieldType::UnstructuredBinary { allowed_mime_types } => (
+                    FormFieldKind::File {
+                        mime_types: allowed_mime_types.clone(),
+                    },
+                    true,
+                ),
+                FormFieldType::UnstructuredText { .. } => (FormFieldKind::Text, true),
+            };
+            Ok(FormFieldModel {
+                name: field.name.clone(),
+                kind,
+                required,
+            })
+        })
+        .collect()
+}
+
 fn lower_call_agent(
     builder: &mut SchemaGraphBuilder,
     path: &[PathSegment],
@@ -26,8 +26,9 @@ use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
 use golem_service_base::custom_api::{
-    CallAgentBehaviour, CorsOptions, MethodParameter, OpenApiSpecBehaviour, OpenApiSpecFormat,
-    PathSegment, PathSegmentType, QueryOrHeaderType, RequestBodySchema, WebhookCallbackBehaviour,
+    CallAgentBehaviour, CorsOptions, FormFieldSchema, FormFieldType, MethodParameter,
+    OpenApiSpecBehaviour, OpenApiSpecFormat, PathSegment, PathSegmentType, QueryOrHeaderType,
+    RequestBodySchema, WebhookCallbackBehaviour,
 };
 use golem_service_base::model::SafeIndex;
 use golem_wasm::analysis::AnalysedType;
@@ -113,6 +114,7 @@ fn call_agent_route(
         cors: CorsOptions {
             allowed_patterns: vec![],
         },
+        rate_limits: vec![],
     }
 }
 
@@ -471,6 +473,78 @@ fn restricted_binary_body_lists_each_mime_type() {
     );
 }
 
+#[test]
+fn json_object_request_body_also_accepts_forms() {
+    let op = operation_for(
+        call_agent_route(
+            Method::POST,
+            vec![PathSegment::Literal {
+                value: "json".to_string(),
+            }],
+            RequestBodySchema::JsonBody {
+                expected_type: record(vec![field("name", str()), field("nick", option(str()))]),
+            },
+            vec![],
+            unit_response(),
+            None,
+        ),
+        "/json",
+        "post",
+    );
+    let content = &op["requestBody"]["content"];
+    for content_type in ["application/x-www-form-urlencoded", "multipart/form-data"] {
+        assert_eq!(
+            content[content_type]["schema"]["properties"]["name"]["type"],
+            json!("string")
+        );
+        assert_eq!(content[content_type]["schema"]["required"], json!(["name"]));
+    }
+}
+
+#[test]
+fn form_body_with_file_field_is_multipart_only() {
+    let op = operation_for(
+        call_agent_route(
+            Method::POST,
+            vec![PathSegment::Literal {
+                value: "upload".to_string(),
+            }],
+            RequestBodySchema::FormBody {
+                fields: vec![
+                    FormFieldSchema {
+                        name: "title".to_string(),
+                        field_type: FormFieldType::Value(QueryOrHeaderType::Primitive(
+                            PathSegmentType::Str,
+                        )),
+                    },
+                    FormFieldSchema {
+                        name: "image".to_string(),
+                        field_type: FormFieldType::UnstructuredBinary {
+                            allowed_mime_types: vec!["image/png".to_string()],
+                        },
+                    },
+                ],
+            },
+            vec![],
+            unit_response(),
+            None,
+        ),
+        "/upload",
+        "post",
+    );
+    let body = &op["requestBody"];
+    assert_eq!(body["description"], json!("Form body"));
+    assert!(body["content"]["application/x-www-form-urlencoded"].is_null());
+
+    let schema = &body["content"]["multipart/form-data"]["schema"];
+    assert_eq!(
+        schema["properties"]["image"],
+        json!({ "type": "string", "format": "binary", "contentMediaType": "image/png" })
+    );
+    assert_eq!(schema["properties"]["title"]["type"], json!("string"));
+    assert_eq!(schema["required"], json!(["title", "image"]));
+}
+
 // --------------------------------------------------------------------------
 // Parameters
 // --------------------------------------------------------------------------
@@ -628,6 +702,7 @@ fn raw_route(
         cors: CorsOptions {
             allowed_patterns: vec![],
         },
+        rate_limits: vec![],
     }
 }
 
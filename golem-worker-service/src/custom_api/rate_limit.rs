@@ -0,0 +1,571 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::route_resolver::ResolvedRouteEntry;
+use super::{ResponseBody, RichRequest, RouteExecutionResult};
+use dashmap::DashMap;
+use golem_common::model::domain_registration::Domain;
+use golem_common::model::http_api_deployment::{HttpApiRateLimit, HttpApiRateLimitKey};
+use golem_service_base::custom_api::{RateLimitScope, RouteRateLimit};
+use http::{HeaderMap, StatusCode};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Token bucket rate limiter for the routes of http api deployments.
+///
+/// Buckets are kept in memory, so every worker service instance enforces the limits
+/// independently of the others.
+pub struct RateLimiter {
+    buckets: DashMap<BucketKey, Arc<Mutex<TokenBucket>>>,
+    last_cleanup: Mutex<Instant>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BucketKey {
+    domain: Domain,
+    scope: RateLimitScope,
+    limit: HttpApiRateLimit,
+    client: String,
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: u64,
+    last_refill: Instant,
+    /// Set when the cleanup drops the bucket from the map; a request that got hold of it
+    /// before has to retry with the bucket replacing it.
+    removed: bool,
+}
+
+impl TokenBucket {
+    fn new(limit: &HttpApiRateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.max,
+            last_refill: now,
+            removed: false,
+        }
+    }
+
+    /// Adds `value` tokens for every full period elapsed since the last refill, up to `max`.
+    fn refill(&mut self, limit: &HttpApiRateLimit, now: Instant) {
+        let period = limit.period.duration();
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let periods = elapsed.as_nanos() / period.as_nanos();
+
+        if periods == 0 {
+            return;
+        }
+
+        let added = u64::try_from(periods)
+            .unwrap_or(u64::MAX)
+            .saturating_mul(limit.value);
+        self.tokens = self.tokens.saturating_add(added).min(limit.max);
+
+        // keep the partially elapsed period so refills stay aligned to full periods
+        let remainder = elapsed.as_nanos() % period.as_nanos();
+        self.last_refill = now - Duration::from_nanos(remainder as u64);
+    }
+
+    fn is_full(&self, limit: &HttpApiRateLimit, now: Instant) -> bool {
+        let mut bucket = self.clone();
+        bucket.refill(limit, now);
+        bucket.tokens == limit.max
+    }
+
+    fn time_until_refill(&self, limit: &HttpApiRateLimit, now: Instant) -> Duration {
+        limit
+            .period
+            .duration()
+            .saturating_sub(now.saturating_duration_since(self.last_refill))
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl RateLimiter {
+    pub fn new(trusted_proxies: Vec<IpAddr>) -> Self {
+        Self {
+            buckets: DashMap::new(),
+            last_cleanup: Mutex::new(Instant::now()),
+            trusted_proxies: trusted_proxies
+                .into_iter()
+                .map(|ip| ip.to_canonical())
+                .collect(),
+        }
+    }
+
+    /// Rejects the request with `429 Too Many Requests` if any of the route's rate limits is exhausted.
+    /// Tokens are only taken if the request fits into all of them.
+    pub fn apply_rate_limit_middleware(
+        &self,
+        request: &RichRequest,
+        resolved_route: &ResolvedRouteEntry,
+    ) -> Option<RouteExecutionResult> {
+        if resolved_route.route.rate_limits.is_empty() {
+            return None;
+        }
+
+        let client = self.client(request, &resolved_route.domain);
+
+        match self.try_acquire_for(&client, &resolved_route.route.rate_limits) {
+            Ok(()) => None,
+            Err(retry_after) => {
+                debug!(
+                    "Rate limit exceeded for route, retry after {}s",
+                    retry_after.as_secs()
+                );
+                Some(too_many_requests(retry_after))
+            }
+        }
+    }
+
+    /// Identifies the client of the request, so rate limits can be applied to it after the
+    /// request itself is not available anymore (for example in GraphQL resolvers).
+    pub fn client(&self, request: &RichRequest, domain: &Domain) -> RateLimitedClient {
+        RateLimitedClient {
+            domain: domain.clone(),
+            ip: self.client_ip(request),
+            principal: request
+                .authenticated_session()
+                .map(|session| format!("{}:{}", session.issuer, session.subject)),
+            headers: request.headers().clone(),
+        }
+    }
+
+    /// Takes a token of the client from each of the rate limits, or none of them if any is
+    /// exhausted, in which case the time until it is refilled is returned.
+    pub fn try_acquire_for(
+        &self,
+        client: &RateLimitedClient,
+        rate_limits: &[RouteRateLimit],
+    ) -> Result<(), Duration> {
+        if rate_limits.is_empty() {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        self.cleanup_if_needed(now);
+
+        let keys: Vec<BucketKey> = rate_limits
+            .iter()
+            .map(|rate_limit| BucketKey {
+                domain: client.domain.clone(),
+                scope: rate_limit.scope.clone(),
+                limit: rate_limit.limit.clone(),
+                client: client.key(&rate_limit.limit),
+            })
+            .collect();
+
+        self.try_acquire(&keys, now)
+    }
+
+    /// Takes a token from each of the buckets, or none of them if any is empty. The buckets
+    /// are locked together (in address order, so concurrent requests sharing some of them
+    /// cannot deadlock) for the check and the decrement.
+    fn try_acquire(&self, keys: &[BucketKey], now: Instant) -> Result<(), Duration> {
+        loop {
+            let mut buckets: Vec<(&BucketKey, Arc<Mutex<TokenBucket>>)> = keys
+                .iter()
+                .map(|key| {
+                    let bucket = self
+                        .buckets
+                        .entry(key.clone())
+                        .or_insert_with(|| Arc::new(Mutex::new(TokenBucket::new(&key.limit, now))))
+                        .clone();
+                    (key, bucket)
+                })
+                .collect();
+            buckets.sort_by_key(|(_, bucket)| Arc::as_ptr(bucket));
+            buckets.dedup_by(|(_, a), (_, b)| Arc::ptr_eq(a, b));
+
+            let mut guards: Vec<(&BucketKey, MutexGuard<TokenBucket>)> = buckets
+                .iter()
+                .map(|(key, bucket)| (*key, lock(bucket)))
+                .collect();
+
+            if guards.iter().any(|(_, bucket)| bucket.removed) {
+                continue;
+            }
+
+            let mut retry_after = None;
+            for (key, bucket) in guards.iter_mut() {
+                bucket.refill(&key.limit, now);
+
+                if bucket.tokens == 0 {
+                    let wait = bucket.time_until_refill(&key.limit, now);
+                    retry_after =
+                        Some(retry_after.map_or(wait, |current: Duration| current.max(wait)));
+                }
+            }
+
+            if let Some(retry_after) = retry_after {
+                return Err(retry_after);
+            }
+
+            for (_, bucket) in guards.iter_mut() {
+                bucket.tokens -= 1;
+            }
+
+            return Ok(());
+        }
+    }
+
+    /// Full buckets behave the same as missing ones, so they can be dropped to bound memory usage.
+    fn cleanup_if_needed(&self, now: Instant) {
+        let Ok(mut last_cleanup) = self.last_cleanup.try_lock() else {
+            return;
+        };
+
+        if now.saturating_duration_since(*last_cleanup) < CLEANUP_INTERVAL {
+            return;
+        }
+        *last_cleanup = now;
+
+        self.buckets.retain(|key, bucket| {
+            // buckets in use by a request right now are kept until the next cleanup
+            let Ok(mut bucket) = bucket.try_lock() else {
+                return true;
+            };
+            if bucket.is_full(&key.limit, now) {
+                bucket.removed = true;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn client_ip(&self, request: &RichRequest) -> String {
+        let remote_addr = request.underlying.remote_addr();
+        let Some(peer) = remote_addr.as_socket_addr().map(|addr| addr.ip()) else {
+            return remote_addr.to_string();
+        };
+
+        let forwarded_for: Vec<&str> = request
+            .headers()
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect();
+
+        self.resolve_client_ip(peer, &forwarded_for).to_string()
+    }
+
+    /// `X-Forwarded-For` can be set to anything by the client, so it is only used if the peer
+    /// is a trusted proxy. In that case the hops are walked from the right (the ones appended
+    /// by our own proxies) and the first untrusted one is the client.
+    fn resolve_client_ip(&self, peer: IpAddr, forwarded_for: &[&str]) -> IpAddr {
+        let mut client = peer.to_canonical();
+        if !self.is_trusted_proxy(client) {
+            return client;
+        }
+
+        let hops = forwarded_for
+            .iter()
+            .rev()
+            .flat_map(|value| value.rsplit(','))
+            .map(str::trim);
+
+        for hop in hops {
+            match hop.parse::<IpAddr>() {
+                Ok(ip) => {
+                    client = ip.to_canonical();
+                    if !self.is_trusted_proxy(client) {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        client
+    }
+
+    fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.contains(&ip)
+    }
+}
+
+/// The properties of a request its rate limit buckets are keyed by.
+#[derive(Debug, Clone)]
+pub struct RateLimitedClient {
+    domain: Domain,
+    ip: String,
+    principal: Option<String>,
+    headers: HeaderMap,
+}
+
+impl RateLimitedClient {
+    fn key(&self, limit: &HttpApiRateLimit) -> String {
+        match limit.key {
+            HttpApiRateLimitKey::ClientIp => format!("ip:{}", self.ip),
+            HttpApiRateLimitKey::Principal => match &self.principal {
+                Some(principal) => format!("principal:{principal}"),
+                None => format!("ip:{}", self.ip),
+            },
+            HttpApiRateLimitKey::ApiKey => {
+                let header_name = limit
+                    .api_key_header
+                    .as_deref()
+                    .map(str::trim)
+                    .unwrap_or(DEFAULT_API_KEY_HEADER);
+
+                match self
+                    .headers
+                    .get(header_name)
+                    .and_then(|value| value.to_str().ok())
+                {
+                    Some(api_key) => format!("api-key:{api_key}"),
+                    None => format!("ip:{}", self.ip),
+                }
+            }
+        }
+    }
+}
+
+fn lock(bucket: &Mutex<TokenBucket>) -> MutexGuard<'_, TokenBucket> {
+    bucket
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn too_many_requests(retry_after: Duration) -> RouteExecutionResult {
+    // Retry-After only supports whole seconds
+    let retry_after_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+
+    RouteExecutionResult {
+        status: StatusCode::TOO_MANY_REQUESTS,
+        headers: HashMap::from([(http::header::RETRY_AFTER, retry_after_secs.to_string())]),
+        body: ResponseBody::NoBody,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::quota::TimePeriod;
+    use poem::{Body, Request};
+    use test_r::test;
+
+    fn limit(value: u64, max: u64) -> HttpApiRateLimit {
+        HttpApiRateLimit {
+            key: HttpApiRateLimitKey::ClientIp,
+            api_key_header: None,
+            value,
+            period: TimePeriod::Second,
+            max,
+        }
+    }
+
+    fn key(scope: RateLimitScope, limit: HttpApiRateLimit, client: &str) -> BucketKey {
+        BucketKey {
+            domain: Domain("example.com".to_string()),
+            scope,
+            limit,
+            client: client.to_string(),
+        }
+    }
+
+    #[test]
+    fn bucket_refills_value_tokens_per_elapsed_period_up_to_max() {
+        let limit = limit(2, 5);
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&limit, start);
+        bucket.tokens = 0;
+
+        bucket.refill(&limit, start + Duration::from_millis(999));
+        assert_eq!(bucket.tokens, 0);
+
+        bucket.refill(&limit, start + Duration::from_millis(2500));
+        assert_eq!(bucket.tokens, 4);
+        assert_eq!(
+            bucket.time_until_refill(&limit, start + Duration::from_millis(2500)),
+            Duration::from_millis(500)
+        );
+
+        bucket.refill(&limit, start + Duration::from_secs(10));
+        assert_eq!(bucket.tokens, 5);
+    }
+
+    #[test]
+    fn requests_over_the_limit_are_rejected_until_refill() {
+        let limiter = RateLimiter::default();
+        let keys = vec![key(RateLimitScope::Deployment, limit(1, 2), "ip:1.2.3.4")];
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(&keys, start).is_ok());
+        assert!(limiter.try_acquire(&keys, start).is_ok());
+        assert_eq!(
+            limiter.try_acquire(&keys, start + Duration::from_millis(400)),
+            Err(Duration::from_millis(600))
+        );
+        assert!(
+            limiter
+                .try_acquire(&keys, start + Duration::from_secs(1))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejected_requests_do_not_consume_tokens_of_other_scopes() {
+        let limiter = RateLimiter::default();
+        let deployment_key = key(RateLimitScope::Deployment, limit(1, 2), "ip:1.2.3.4");
+        let method_key = key(
+            RateLimitScope::Method {
+                agent_type: golem_common::model::agent::AgentTypeName("agent".to_string()),
+                method_name: "method".to_string(),
+            },
+            limit(1, 1),
+            "ip:1.2.3.4",
+        );
+        let start = Instant::now();
+
+        let both = vec![deployment_key.clone(), method_key];
+        assert!(limiter.try_acquire(&both, start).is_ok());
+        assert!(limiter.try_acquire(&both, start).is_err());
+
+        // the deployment level bucket still has a token left
+        assert!(
+            limiter
+                .try_acquire(std::slice::from_ref(&deployment_key), start)
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .try_acquire(std::slice::from_ref(&deployment_key), start)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn concurrent_requests_do_not_take_more_tokens_than_available() {
+        let limiter = RateLimiter::default();
+        let keys = vec![key(RateLimitScope::Deployment, limit(1, 100), "ip:1.2.3.4")];
+        let now = Instant::now();
+
+        let accepted: usize = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..50)
+                            .filter(|_| limiter.try_acquire(&keys, now).is_ok())
+                            .count()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+
+        assert_eq!(accepted, 100);
+    }
+
+    #[test]
+    fn clients_are_keyed_by_api_key_with_ip_fallback() {
+        let limiter = RateLimiter::default();
+        let request = RichRequest::new(
+            Request::builder()
+                .header("x-tenant-key", "secret")
+                .body(Body::empty()),
+        );
+        let client = limiter.client(&request, &Domain("example.com".to_string()));
+        let ip_key = format!("ip:{}", limiter.client_ip(&request));
+
+        assert_eq!(client.key(&limit(1, 1)), ip_key);
+
+        let api_key_limit = HttpApiRateLimit {
+            key: HttpApiRateLimitKey::ApiKey,
+            api_key_header: Some("X-Tenant-Key".to_string()),
+            ..limit(1, 1)
+        };
+        assert_eq!(client.key(&api_key_limit), "api-key:secret");
+
+        let missing_api_key_limit = HttpApiRateLimit {
+            key: HttpApiRateLimitKey::ApiKey,
+            api_key_header: Some("X-Other-Key".to_string()),
+            ..limit(1, 1)
+        };
+        assert_eq!(client.key(&missing_api_key_limit), ip_key);
+
+        let principal_limit = HttpApiRateLimit {
+            key: HttpApiRateLimitKey::Principal,
+            ..limit(1, 1)
+        };
+        assert_eq!(client.key(&principal_limit), ip_key);
+    }
+
+    #[test]
+    fn forwarded_for_is_ignored_for_untrusted_peers() {
+        let limiter = RateLimiter::new(vec!["10.0.0.254".parse().unwrap()]);
+        let peer: IpAddr = "203.0.113.7".parse().unwrap();
+
+        assert_eq!(
+            limiter.resolve_client_ip(peer, &["10.0.0.1, 192.168.0.1"]),
+            peer
+        );
+    }
+
+    #[test]
+    fn forwarded_for_is_walked_from_the_right_behind_trusted_proxies() {
+        let limiter = RateLimiter::new(vec![
+            "10.0.0.254".parse().unwrap(),
+            "10.0.0.253".parse().unwrap(),
+        ]);
+        let proxy: IpAddr = "10.0.0.254".parse().unwrap();
+
+        // the left-most value is whatever the client sent, only the hops appended by the proxies count
+        assert_eq!(
+            limiter.resolve_client_ip(proxy, &["1.1.1.1, 203.0.113.7, 10.0.0.253"]),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+        // multiple headers are treated as one list
+        assert_eq!(
+            limiter.resolve_client_ip(proxy, &["1.1.1.1", "203.0.113.7"]),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+        // a malformed hop stops the walk at the last trusted address
+        assert_eq!(
+            limiter.resolve_client_ip(proxy, &["203.0.113.7, not-an-ip"]),
+            proxy
+        );
+        assert_eq!(limiter.resolve_client_ip(proxy, &[]), proxy);
+        // IPv4-mapped peers match the configured IPv4 proxies
+        assert_eq!(
+            limiter.resolve_client_ip("::ffff:10.0.0.254".parse().unwrap(), &["203.0.113.7"]),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn rejection_rounds_retry_after_up_to_whole_seconds() {
+        let result = too_many_requests(Duration::from_millis(1200));
+
+        assert_eq!(result.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            result.headers.get(&http::header::RETRY_AFTER),
+            Some(&"2".to_string())
+        );
+    }
+}
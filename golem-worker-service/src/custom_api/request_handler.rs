@@ -15,8 +15,10 @@
 use super::call_agent::CallAgentHandler;
 use super::cors::{apply_cors_outgoing_middleware, handle_cors_preflight_behaviour};
 use super::error::RequestHandlerError;
+use super::graphql::GraphQlHandler;
 use super::model::RichRouteBehaviour;
 use super::oidc::handler::OidcHandler;
+use super::rate_limit::RateLimiter;
 use super::route_resolver::{ResolvedRouteEntry, RouteResolver};
 use super::session_from_header_security::apply_session_from_header_security_middleware;
 use super::webhooks::WebhookCallbackHandler;
@@ -37,6 +39,8 @@ pub struct RequestHandler {
     call_agent_handler: Arc<CallAgentHandler>,
     oidc_handler: Arc<OidcHandler>,
     webhook_callback_handler: Arc<WebhookCallbackHandler>,
+    graphql_handler: Arc<GraphQlHandler>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 #[allow(irrefutable_let_patterns)]
@@ -46,12 +50,16 @@ impl RequestHandler {
         call_agent_handler: Arc<CallAgentHandler>,
         oidc_handler: Arc<OidcHandler>,
         webhook_callback_handler: Arc<WebhookCallbackHandler>,
+        graphql_handler: Arc<GraphQlHandler>,
+        rate_limiter: Arc<RateLimiter>,
     ) -> Self {
         Self {
             route_resolver,
             call_agent_handler,
             oidc_handler,
             webhook_callback_handler,
+            graphql_handler,
+            rate_limiter,
         }
     }
 
@@ -96,6 +104,14 @@ impl RequestHandler {
             return Ok(short_circuit);
         }
 
+        if let Some(mut short_circuit) = self
+            .rate_limiter
+            .apply_rate_limit_middleware(request, resolved_route)
+        {
+            apply_cors_outgoing_middleware(&mut short_circuit, request, resolved_route).await?;
+            return Ok(short_circuit);
+        }
+
         let mut result = self.execute_route(request, resolved_route).await?;
 
         apply_cors_outgoing_middleware(&mut result, request, resolved_route).await?;
@@ -115,6 +131,12 @@ impl RequestHandler {
                     .await
             }
 
+            RichRouteBehaviour::WebSocket(behaviour) => {
+                self.call_agent_handler
+                    .handle_websocket_behaviour(request, resolved_route, behaviour)
+                    .await
+            }
+
             RichRouteBehaviour::CorsPreflight(cors_preflight) => {
                 handle_cors_preflight_behaviour(request, cors_preflight)
             }
@@ -146,6 +168,12 @@ impl RequestHandler {
                     .handle_webhook_callback_behaviour(request, resolved_route, behaviour)
                     .await
             }
+
+            RichRouteBehaviour::GraphQl(api) => {
+                self.graphql_handler
+                    .handle_graphql_behaviour(request, resolved_route, api)
+                    .await
+            }
         }
     }
 }
@@ -217,5 +245,13 @@ fn route_execution_result_to_response(
 
             Ok(response)
         }
+
+        ResponseBody::GraphQl { body } => {
+            let body = poem::Body::from_json(body).map_err(anyhow::Error::from)?;
+
+            Ok(response_builder
+                .body(body)
+                .set_content_type("application/json"))
+        }
     }
 }
@@ -1,4 +1,4 @@
-use super::{OidcSession, ParsedRequestBody};
+use super::{FormBody, OidcSession, ParsedRequestBody};
 
 // Copyright 2024-2026 Golem Cloud
 //
@@ -15,23 +15,33 @@ use super::{OidcSession, ParsedRequestBody};
 // limitations under the License.
 
 use super::error::RequestHandlerError;
+use crate::config::FormBodyConfig;
 use anyhow::anyhow;
 use golem_common::model::agent::{BinarySource, BinaryType, TextSource, TextType};
 use golem_common::model::invocation_context::{
     InvocationContextSpan, InvocationContextStack, TraceId,
 };
 use golem_common::model::{IdempotencyKey, invocation_context};
-use golem_service_base::custom_api::RequestBodySchema;
+use golem_service_base::custom_api::{FormFieldSchema, FormFieldType, RequestBodySchema};
 use golem_service_base::headers::TraceContextHeaders;
 use golem_wasm::ValueAndType;
 use golem_wasm::json::ValueAndTypeJsonExtensions;
 use http::HeaderMap;
+use poem::FromRequest;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::sync::OnceLock;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use uuid::Uuid;
 
 const COOKIE_HEADER_NAMES: [&str; 2] = ["cookie", "Cookie"];
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormContentType {
+    UrlEncoded,
+    Multipart,
+}
+
 pub struct RichRequest {
     pub underlying: poem::Request,
     pub request_id: Uuid,
@@ -129,15 +139,45 @@ impl RichRequest {
         self.authenticated_session.as_ref()
     }
 
+    /// Reads the raw request body, rejecting it as soon as it exceeds `max_body_size`.
+    /// Will consume the request body
+    pub async fn read_limited_body(
+        &mut self,
+        limits: &FormBodyConfig,
+    ) -> Result<Vec<u8>, RequestHandlerError> {
+        read_limited(
+            self.underlying.take_body().into_async_read(),
+            limits.max_body_size,
+            limits,
+        )
+        .await
+        .map_err(|err| match err {
+            RequestHandlerError::FormBodyTooLarge { max_body_size, .. } => {
+                RequestHandlerError::RequestBodyTooLarge { max_body_size }
+            }
+            other => other,
+        })
+    }
+
     // Will consume the request body
     pub async fn parse_request_body(
         &mut self,
         expected: &RequestBodySchema,
+        form_limits: &FormBodyConfig,
     ) -> Result<ParsedRequestBody, RequestHandlerError> {
         match expected {
             RequestBodySchema::Unused => Ok(ParsedRequestBody::Unused),
 
             RequestBodySchema::JsonBody { expected_type } => {
+                // JSON object bodies can also be sent as forms if all of their fields are simple values
+                if let Some(form_content_type) = self.form_content_type()?
+                    && let Some(fields) = expected.form_fields()
+                {
+                    return self
+                        .parse_form_body(form_content_type, &fields, form_limits)
+                        .await;
+                }
+
                 let json_body: serde_json::Value = self
                     .underlying
                     .take_body()
@@ -160,7 +200,215 @@ impl RichRequest {
             RequestBodySchema::RestrictedText {
                 allowed_language_codes,
             } => self.parse_text_body(Some(allowed_language_codes)).await,
+
+            RequestBodySchema::FormBody { fields } => match self.form_content_type()? {
+                Some(form_content_type) => {
+                    self.parse_form_body(form_content_type, fields, form_limits)
+                        .await
+                }
+                None => Err(RequestHandlerError::UnsupportedFormContentType {
+                    content_type: self.content_type()?.unwrap_or_default().to_string(),
+                }),
+            },
+        }
+    }
+
+    fn content_type(&self) -> Result<Option<&str>, RequestHandlerError> {
+        self.headers()
+            .get(http::header::CONTENT_TYPE)
+            .map(|value| value.to_str())
+            .transpose()
+            .map_err(|_| RequestHandlerError::HeaderIsNotAscii {
+                header_name: http::header::CONTENT_TYPE.to_string(),
+            })
+    }
+
+    fn form_content_type(&self) -> Result<Option<FormContentType>, RequestHandlerError> {
+        let Some(content_type) = self.content_type()? else {
+            return Ok(None);
+        };
+
+        let media_type = content_type.split(';').next().unwrap_or("").trim();
+
+        if media_type.eq_ignore_ascii_case("application/x-www-form-urlencoded") {
+            Ok(Some(FormContentType::UrlEncoded))
+        } else if media_type.eq_ignore_ascii_case("multipart/form-data") {
+            Ok(Some(FormContentType::Multipart))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn parse_form_body(
+        &mut self,
+        form_content_type: FormContentType,
+        fields: &[FormFieldSchema],
+        limits: &FormBodyConfig,
+    ) -> Result<ParsedRequestBody, RequestHandlerError> {
+        let form = match form_content_type {
+            FormContentType::UrlEncoded => self.parse_url_encoded_form(fields, limits).await?,
+            FormContentType::Multipart => self.parse_multipart_form(fields, limits).await?,
+        };
+        Ok(ParsedRequestBody::Form(form))
+    }
+
+    async fn parse_url_encoded_form(
+        &mut self,
+        fields: &[FormFieldSchema],
+        limits: &FormBodyConfig,
+    ) -> Result<FormBody, RequestHandlerError> {
+        let data = read_limited(
+            self.underlying.take_body().into_async_read(),
+            limits.max_body_size,
+            limits,
+        )
+        .await?;
+
+        let mut form = FormBody::default();
+
+        for (name, value) in url::form_urlencoded::parse(&data) {
+            let Some(field) = fields.iter().find(|field| field.name == name) else {
+                continue;
+            };
+
+            match &field.field_type {
+                FormFieldType::Value(_) => form
+                    .values
+                    .entry(field.name.clone())
+                    .or_default()
+                    .push(value.into_owned()),
+                FormFieldType::UnstructuredText { .. } => insert_form_part(
+                    &mut form.texts,
+                    &field.name,
+                    TextSource {
+                        data: value.into_owned(),
+                        text_type: None,
+                    },
+                )?,
+                FormFieldType::UnstructuredBinary { .. } => {
+                    return Err(RequestHandlerError::FormBodyParsingFailed {
+                        error: format!(
+                            "File field {} can only be sent as multipart/form-data",
+                            field.name
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(form)
+    }
+
+    async fn parse_multipart_form(
+        &mut self,
+        fields: &[FormFieldSchema],
+        limits: &FormBodyConfig,
+    ) -> Result<FormBody, RequestHandlerError> {
+        let mut body = poem::RequestBody::new(self.underlying.take_body());
+        let mut multipart = poem::web::Multipart::from_request(&self.underlying, &mut body)
+            .await
+            .map_err(|err| RequestHandlerError::FormBodyParsingFailed {
+                error: err.to_string(),
+            })?;
+
+        let mut form = FormBody::default();
+        let mut remaining = limits.max_body_size;
+
+        while let Some(part) = multipart.next_field().await.map_err(|err| {
+            RequestHandlerError::FormBodyParsingFailed {
+                error: err.to_string(),
+            }
+        })? {
+            let Some(field) = part
+                .name()
+                .and_then(|name| fields.iter().find(|field| field.name == name))
+            else {
+                // Unknown parts are still read from the body, so they count against the limit
+                remaining -= skip_limited(part.into_async_read(), remaining, limits).await?;
+                continue;
+            };
+
+            match &field.field_type {
+                FormFieldType::Value(_) => {
+                    let data = read_limited(part.into_async_read(), remaining, limits).await?;
+                    remaining -= data.len();
+
+                    let value = String::from_utf8(data).map_err(|err| {
+                        RequestHandlerError::BodyIsNotValidUtf8 {
+                            error: err.to_string(),
+                        }
+                    })?;
+                    form.values
+                        .entry(field.name.clone())
+                        .or_default()
+                        .push(value);
+                }
+                FormFieldType::UnstructuredBinary { allowed_mime_types } => {
+                    let mime_type = part
+                        .content_type()
+                        .unwrap_or("application/octet-stream")
+                        .to_string();
+
+                    if !allowed_mime_types.is_empty()
+                        && !allowed_mime_types
+                            .iter()
+                            .any(|allowed| mime_type_essence_eq(allowed, &mime_type))
+                    {
+                        return Err(RequestHandlerError::UnsupportedMimeType {
+                            mime_type,
+                            allowed_mime_types: allowed_mime_types.clone(),
+                        });
+                    }
+
+                    let data = read_limited(
+                        part.into_async_read(),
+                        remaining.min(limits.max_file_size),
+                        limits,
+                    )
+                    .await?;
+                    remaining -= data.len();
+
+                    insert_form_part(
+                        &mut form.binaries,
+                        &field.name,
+                        BinarySource {
+                            data,
+                            binary_type: BinaryType { mime_type },
+                        },
+                    )?;
+                }
+                FormFieldType::UnstructuredText { .. } => {
+                    if let Some(content_type) = part.content_type() {
+                        validate_text_content_type(content_type)?;
+                    }
+
+                    let data = read_limited(
+                        part.into_async_read(),
+                        remaining.min(limits.max_file_size),
+                        limits,
+                    )
+                    .await?;
+                    remaining -= data.len();
+
+                    let text = String::from_utf8(data).map_err(|err| {
+                        RequestHandlerError::BodyIsNotValidUtf8 {
+                            error: err.to_string(),
+                        }
+                    })?;
+
+                    insert_form_part(
+                        &mut form.texts,
+                        &field.name,
+                        TextSource {
+                            data: text,
+                            text_type: None,
+                        },
+                    )?;
+                }
+            }
         }
+
+        Ok(form)
     }
 
     async fn parse_text_body(
@@ -329,6 +577,81 @@ impl RichRequest {
     }
 }
 
+/// Reads at most `limit` bytes, failing as soon as the reader yields more than that.
+async fn read_limited(
+    reader: impl AsyncRead + Send,
+    limit: usize,
+    limits: &FormBodyConfig,
+) -> Result<Vec<u8>, RequestHandlerError> {
+    let mut data = Vec::new();
+    Box::pin(reader)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut data)
+        .await
+        .map_err(|err| RequestHandlerError::FormBodyParsingFailed {
+            error: err.to_string(),
+        })?;
+
+    if data.len() > limit {
+        return Err(RequestHandlerError::FormBodyTooLarge {
+            max_body_size: limits.max_body_size,
+            max_file_size: limits.max_file_size,
+        });
+    }
+
+    Ok(data)
+}
+
+/// Reads and drops the data of the reader, returning its length.
+async fn skip_limited(
+    reader: impl AsyncRead + Send,
+    limit: usize,
+    limits: &FormBodyConfig,
+) -> Result<usize, RequestHandlerError> {
+    let skipped = tokio::io::copy(
+        &mut Box::pin(reader).take(limit as u64 + 1),
+        &mut tokio::io::sink(),
+    )
+    .await
+    .map_err(|err| RequestHandlerError::FormBodyParsingFailed {
+        error: err.to_string(),
+    })? as usize;
+
+    if skipped > limit {
+        return Err(RequestHandlerError::FormBodyTooLarge {
+            max_body_size: limits.max_body_size,
+            max_file_size: limits.max_file_size,
+        });
+    }
+
+    Ok(skipped)
+}
+
+/// Compares the essence (type and subtype, without parameters) of two mime types, which is
+/// case-insensitive.
+fn mime_type_essence_eq(left: &str, right: &str) -> bool {
+    fn essence(mime_type: &str) -> &str {
+        mime_type.split(';').next().unwrap_or("").trim()
+    }
+    essence(left).eq_ignore_ascii_case(essence(right))
+}
+
+fn insert_form_part<T>(
+    parts: &mut HashMap<String, T>,
+    name: &str,
+    part: T,
+) -> Result<(), RequestHandlerError> {
+    match parts.entry(name.to_string()) {
+        Entry::Occupied(_) => Err(RequestHandlerError::FormBodyParsingFailed {
+            error: format!("Form field {name} was provided more than once"),
+        }),
+        Entry::Vacant(entry) => {
+            entry.insert(part);
+            Ok(())
+        }
+    }
+}
+
 /// Validate Content-Type for text bodies.
 ///
 /// Accept only `text/plain` (with no parameters) or `text/plain; charset=utf-8` (charset case-insensitive).
@@ -396,7 +719,7 @@ fn extract_request_attributes(
 mod request_body_tests {
     use super::*;
     use assert2::{assert, let_assert};
-    use golem_service_base::custom_api::RequestBodySchema;
+    use golem_service_base::custom_api::{PathSegmentType, QueryOrHeaderType, RequestBodySchema};
     use golem_wasm::analysis::{NameTypePair, analysed_type};
     use http::Method;
     use poem::{Body, Request};
@@ -433,7 +756,7 @@ mod request_body_tests {
         let mut request = json_request(json!({ "x": 1 }));
 
         let result = request
-            .parse_request_body(&RequestBodySchema::Unused)
+            .parse_request_body(&RequestBodySchema::Unused, &FormBodyConfig::default())
             .await
             .unwrap();
 
@@ -453,7 +776,10 @@ mod request_body_tests {
             }]),
         };
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(ParsedRequestBody::JsonBody(golem_wasm::Value::Record(_)) = result);
     }
@@ -466,7 +792,10 @@ mod request_body_tests {
             expected_type: analysed_type::u8(),
         };
 
-        let err = request.parse_request_body(&schema).await.unwrap_err();
+        let err = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap_err();
 
         assert!(let RequestHandlerError::BodyIsNotValidJson { .. } = err);
     }
@@ -483,7 +812,10 @@ mod request_body_tests {
             }]),
         };
 
-        let err = request.parse_request_body(&schema).await.unwrap_err();
+        let err = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap_err();
 
         assert!(let RequestHandlerError::JsonBodyParsingFailed { .. } = err);
     }
@@ -496,7 +828,10 @@ mod request_body_tests {
             allowed_mime_types: vec!["application/octet-stream".to_string()],
         };
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(
             ParsedRequestBody::UnstructuredBinary(Some(BinarySource { data, binary_type })) =
@@ -515,7 +850,10 @@ mod request_body_tests {
             allowed_mime_types: vec!["application/octet-stream".to_string()],
         };
 
-        let err = request.parse_request_body(&schema).await.unwrap_err();
+        let err = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap_err();
 
         {
             let_assert!(
@@ -538,7 +876,10 @@ mod request_body_tests {
             allowed_mime_types: vec!["application/octet-stream".to_string()],
         };
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(
             ParsedRequestBody::UnstructuredBinary(Some(BinarySource { binary_type, .. })) = result
@@ -553,7 +894,10 @@ mod request_body_tests {
 
         let schema = RequestBodySchema::UnrestrictedBinary;
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(
             ParsedRequestBody::UnstructuredBinary(Some(BinarySource { binary_type, .. })) = result
@@ -583,7 +927,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello world", Some("text/plain"), &[]);
 
         let result = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap();
 
@@ -597,7 +944,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello world", None, &[]);
 
         let result = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap();
 
@@ -611,7 +961,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello", Some("text/plain; charset=utf-8"), &[]);
 
         let result = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap();
 
@@ -624,7 +977,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello", Some("Text/Plain; Charset=UTF-8"), &[]);
 
         let result = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap();
 
@@ -636,7 +992,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello", Some("application/json"), &[]);
 
         let err = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap_err();
 
@@ -649,7 +1008,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello", Some("text/plain; charset=iso-8859-1"), &[]);
 
         let err = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap_err();
 
@@ -662,7 +1024,10 @@ mod request_body_tests {
         let mut request = text_request(b"\xff\xfe\xfd", Some("text/plain"), &[]);
 
         let err = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap_err();
 
@@ -674,7 +1039,10 @@ mod request_body_tests {
         let mut request = text_request(b"bonjour", Some("text/plain"), &["fr"]);
 
         let result = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap();
 
@@ -688,7 +1056,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello", Some("text/plain"), &["en", "fr"]);
 
         let err = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap_err();
 
@@ -700,7 +1071,10 @@ mod request_body_tests {
         let mut request = text_request(b"hello", Some("text/plain"), &["en, fr"]);
 
         let err = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedText)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedText,
+                &FormBodyConfig::default(),
+            )
             .await
             .unwrap_err();
 
@@ -715,7 +1089,10 @@ mod request_body_tests {
             allowed_language_codes: vec!["en".to_string(), "de".to_string()],
         };
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(ParsedRequestBody::UnstructuredText(Some(text_source)) = result);
         let text_type = text_source.text_type.unwrap();
@@ -730,7 +1107,10 @@ mod request_body_tests {
             allowed_language_codes: vec!["en".to_string()],
         };
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(ParsedRequestBody::UnstructuredText(Some(text_source)) = result);
         let text_type = text_source.text_type.unwrap();
@@ -745,7 +1125,10 @@ mod request_body_tests {
             allowed_language_codes: vec!["en".to_string(), "de".to_string()],
         };
 
-        let err = request.parse_request_body(&schema).await.unwrap_err();
+        let err = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap_err();
 
         let_assert!(
             RequestHandlerError::UnsupportedLanguage {
@@ -766,9 +1149,178 @@ mod request_body_tests {
             allowed_language_codes: vec!["en".to_string()],
         };
 
-        let result = request.parse_request_body(&schema).await.unwrap();
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
 
         let_assert!(ParsedRequestBody::UnstructuredText(Some(text_source)) = result);
         assert!(text_source.text_type.is_none());
     }
+
+    const MULTIPART_BODY: &[u8] = b"--boundary\r\n\
+Content-Disposition: form-data; name=\"name\"\r\n\r\n\
+alice\r\n\
+--boundary\r\n\
+Content-Disposition: form-data; name=\"avatar\"; filename=\"avatar.png\"\r\n\
+Content-Type: image/png\r\n\r\n\
+PNGDATA\r\n\
+--boundary\r\n\
+Content-Disposition: form-data; name=\"unknown\"\r\n\r\n\
+ignored\r\n\
+--boundary--\r\n";
+
+    fn multipart_schema() -> RequestBodySchema {
+        RequestBodySchema::FormBody {
+            fields: vec![
+                FormFieldSchema {
+                    name: "name".to_string(),
+                    field_type: FormFieldType::Value(QueryOrHeaderType::Primitive(
+                        PathSegmentType::Str,
+                    )),
+                },
+                FormFieldSchema {
+                    name: "avatar".to_string(),
+                    field_type: FormFieldType::UnstructuredBinary {
+                        allowed_mime_types: vec!["image/png".to_string()],
+                    },
+                },
+            ],
+        }
+    }
+
+    #[test]
+    async fn url_encoded_form_is_accepted_for_json_object_body() {
+        let mut request = raw_request_with_content_type(
+            b"x=1&name=a%20b&other=2",
+            "application/x-www-form-urlencoded",
+        );
+
+        let schema = RequestBodySchema::JsonBody {
+            expected_type: analysed_type::record(vec![
+                NameTypePair {
+                    name: String::from("x"),
+                    typ: analysed_type::s32(),
+                },
+                NameTypePair {
+                    name: String::from("name"),
+                    typ: analysed_type::str(),
+                },
+            ]),
+        };
+
+        let result = request
+            .parse_request_body(&schema, &FormBodyConfig::default())
+            .await
+            .unwrap();
+
+        let_assert!(ParsedRequestBody::Form(form) = result);
+        assert!(form.values.get("x") == Some(&vec!["1".to_string()]));
+        assert!(form.values.get("name") == Some(&vec!["a b".to_string()]));
+        assert!(!form.values.contains_key("other"));
+    }
+
+    #[test]
+    async fn multipart_form_with_file_part_is_parsed() {
+        let mut request =
+            raw_request_with_content_type(MULTIPART_BODY, "multipart/form-data; boundary=boundary");
+
+        let result = request
+            .parse_request_body(&multipart_schema(), &FormBodyConfig::default())
+            .await
+            .unwrap();
+
+        let_assert!(ParsedRequestBody::Form(form) = result);
+        assert!(form.values.get("name") == Some(&vec!["alice".to_string()]));
+        let_assert!(Some(avatar) = form.binaries.get("avatar"));
+        assert!(avatar.data == b"PNGDATA".to_vec());
+        assert!(avatar.binary_type.mime_type == "image/png");
+    }
+
+    #[test]
+    async fn multipart_file_part_over_the_limit_is_rejected() {
+        let mut request =
+            raw_request_with_content_type(MULTIPART_BODY, "multipart/form-data; boundary=boundary");
+
+        let limits = FormBodyConfig {
+            max_body_size: 1024,
+            max_file_size: 4,
+        };
+
+        let err = request
+            .parse_request_body(&multipart_schema(), &limits)
+            .await
+            .unwrap_err();
+
+        assert!(let RequestHandlerError::FormBodyTooLarge { .. } = err);
+    }
+
+    #[test]
+    async fn multipart_unknown_parts_count_against_the_body_limit() {
+        let mut request =
+            raw_request_with_content_type(MULTIPART_BODY, "multipart/form-data; boundary=boundary");
+
+        // Enough for the known parts ("alice" and "PNGDATA"), but not for the unknown one
+        let limits = FormBodyConfig {
+            max_body_size: 14,
+            max_file_size: 1024,
+        };
+
+        let err = request
+            .parse_request_body(&multipart_schema(), &limits)
+            .await
+            .unwrap_err();
+
+        assert!(let RequestHandlerError::FormBodyTooLarge { .. } = err);
+    }
+
+    #[test]
+    async fn multipart_file_mime_type_is_compared_case_insensitively() {
+        let body = b"--boundary\r\n\
+Content-Disposition: form-data; name=\"avatar\"; filename=\"avatar.png\"\r\n\
+Content-Type: Image/PNG; charset=binary\r\n\r\n\
+PNGDATA\r\n\
+--boundary--\r\n";
+        let mut request =
+            raw_request_with_content_type(body, "multipart/form-data; boundary=boundary");
+
+        let result = request
+            .parse_request_body(&multipart_schema(), &FormBodyConfig::default())
+            .await
+            .unwrap();
+
+        let_assert!(ParsedRequestBody::Form(form) = result);
+        let avatar = form.binaries.get("avatar").unwrap();
+        assert!(avatar.data == b"PNGDATA".to_vec());
+    }
+
+    #[test]
+    async fn url_encoded_form_over_the_limit_is_rejected() {
+        let mut request =
+            raw_request_with_content_type(b"name=alice", "application/x-www-form-urlencoded");
+
+        let limits = FormBodyConfig {
+            max_body_size: 4,
+            max_file_size: 4,
+        };
+
+        let err = request
+            .parse_request_body(&multipart_schema(), &limits)
+            .await
+            .unwrap_err();
+
+        assert!(let RequestHandlerError::FormBodyTooLarge { .. } = err);
+    }
+
+    #[test]
+    async fn form_body_requires_form_content_type() {
+        let mut request = json_request(json!({ "name": "alice" }));
+
+        let err = request
+            .parse_request_body(&multipart_schema(), &FormBodyConfig::default())
+            .await
+            .unwrap_err();
+
+        assert!(let RequestHandlerError::UnsupportedFormContentType { .. } = err);
+    }
 }
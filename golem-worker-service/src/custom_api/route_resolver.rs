@@ -16,25 +16,27 @@ use super::RichRouteSecurity;
 use super::api_definition_lookup::{ApiDefinitionLookupError, HttpApiDefinitionsLookup};
 use super::model::RichCompiledRoute;
 use super::openapi::HttpApiOpenApiSpec;
-use crate::config::RouteResolverConfig;
+use crate::config::{GraphQlConfig, RouteResolverConfig};
 use crate::custom_api::{
     OidcCallbackBehaviour, RichRouteBehaviour, RichSecuritySchemeRouteSecurity,
 };
 use golem_common::SafeDisplay;
 use golem_common::cache::SimpleCache;
 use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode};
+use golem_common::model::agent::HttpMethod;
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::security_scheme::SecuritySchemeId;
 use golem_service_base::custom_api::router::Router;
 use golem_service_base::custom_api::{
-    CompiledRoutes, CorsOptions, PathSegment, RequestBodySchema, RouteSecurity,
+    CompiledRoutes, CorsOptions, PathSegment, RequestBodySchema, RouteBehaviour, RouteSecurity,
     SecuritySchemeDetails,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::debug;
 
+#[derive(Clone)]
 pub struct ResolvedRouteEntry {
     pub domain: Domain,
     pub route: Arc<RichCompiledRoute>,
@@ -63,11 +65,13 @@ impl SafeDisplay for RouteResolverError {
 pub struct RouteResolver {
     domain_api_cache: Cache<Domain, (), DomainHttpApi, ()>,
     api_definition_lookup: Arc<dyn HttpApiDefinitionsLookup>,
+    graphql_config: GraphQlConfig,
 }
 
 impl RouteResolver {
     pub fn new(
         config: &RouteResolverConfig,
+        graphql_config: &GraphQlConfig,
         api_definition_lookup: Arc<dyn HttpApiDefinitionsLookup>,
     ) -> Self {
         Self {
@@ -81,6 +85,7 @@ impl RouteResolver {
                 "route_resolver_routers",
             ),
             api_definition_lookup,
+            graphql_config: graphql_config.clone(),
         }
     }
 
@@ -165,13 +170,14 @@ impl RouteResolver {
         };
 
         let environment_id = compiled_routes.environment_id;
-        let finalized_routes = match Self::finalize_routes(compiled_routes).await {
-            Ok(value) => value,
-            Err(err) => {
-                tracing::warn!("Failed to finalize routes for domain {domain}: {err:?}");
-                return Err(());
-            }
-        };
+        let finalized_routes =
+            match Self::finalize_routes(compiled_routes, &self.graphql_config).await {
+                Ok(value) => value,
+                Err(err) => {
+                    tracing::warn!("Failed to finalize routes for domain {domain}: {err:?}");
+                    return Err(());
+                }
+            };
 
         let openapi_spec = match HttpApiOpenApiSpec::from_routes(&finalized_routes, domain) {
             Ok(spec) => Some(Arc::new(spec)),
@@ -192,6 +198,7 @@ impl RouteResolver {
 
     async fn finalize_routes(
         compiled_routes: CompiledRoutes,
+        graphql_config: &GraphQlConfig,
     ) -> Result<Vec<RichCompiledRoute>, String> {
         let security_schemes: HashMap<_, _> = compiled_routes
             .security_schemes
@@ -204,6 +211,15 @@ impl RouteResolver {
         for route in compiled_routes.routes {
             let security = compile_route_security(&security_schemes, route.security)?;
 
+            let behavior = match route.behavior {
+                RouteBehaviour::CallAgent(inner)
+                    if matches!(route.method, HttpMethod::WebSocket(_)) =>
+                {
+                    RichRouteBehaviour::WebSocket(inner)
+                }
+                other => RichRouteBehaviour::new(other, graphql_config),
+            };
+
             let enriched = RichCompiledRoute {
                 account_id: compiled_routes.account_id,
                 account_email: compiled_routes.account_email.clone(),
@@ -215,9 +231,10 @@ impl RouteResolver {
                     .map_err(|e| format!("Failed converting HttpMethod to http::Method: {e}"))?,
                 path: route.path,
                 body: route.body,
-                behavior: route.behavior.into(),
+                behavior,
                 security,
                 cors: route.cors,
+                rate_limits: route.rate_limits,
             };
 
             enriched_routes.push(enriched);
@@ -254,6 +271,7 @@ impl RouteResolver {
                 cors: CorsOptions {
                     allowed_patterns: Vec::new(),
                 },
+                rate_limits: Vec::new(),
             };
 
             enriched_routes.push(callback_route);
@@ -16,6 +16,7 @@ use super::RichRequest;
 use super::error::RequestHandlerError;
 use super::route_resolver::ResolvedRouteEntry;
 use super::{ParsedRequestBody, RouteExecutionResult};
+use crate::config::FormBodyConfig;
 use crate::custom_api::ResponseBody;
 use crate::service::worker::WorkerService;
 use golem_service_base::custom_api::{AgentWebhookId, RequestBodySchema, WebhookCallbackBehaviour};
@@ -70,7 +71,10 @@ impl WebhookCallbackHandler {
         let promise_id = webhook_id.into_promise_id(behaviour.component_id);
 
         let body = request
-            .parse_request_body(&RequestBodySchema::UnrestrictedBinary)
+            .parse_request_body(
+                &RequestBodySchema::UnrestrictedBinary,
+                &FormBodyConfig::default(),
+            )
             .await?;
 
         let ParsedRequestBody::UnstructuredBinary(mut body_data) = body else {
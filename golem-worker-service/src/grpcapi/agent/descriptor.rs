@@ -0,0 +1,456 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of the [`ProtoFile`] rendered for an agent type into the descriptors served by
+//! server reflection.
+
+use golem_common::schema::render::ProtoFile;
+use golem_common::schema::render::protobuf_idl::{
+    ProtoDefinition, ProtoField, ProtoLabel, ProtoMessage, ProtoScalar, ProtoType,
+};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, MessageOptions, MethodDescriptorProto, OneofDescriptorProto,
+    ServiceDescriptorProto,
+};
+use std::collections::HashSet;
+
+const WELL_KNOWN_PREFIX: &str = "google.protobuf.";
+
+/// Name under which the agent type's file is served, derived from its package.
+pub fn file_name(file: &ProtoFile) -> String {
+    format!("{}.proto", file.package.replace('.', "/"))
+}
+
+/// Descriptor of the agent type's file.
+pub fn file_descriptor(file: &ProtoFile) -> FileDescriptorProto {
+    let enums: HashSet<&str> = file
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            ProtoDefinition::Enum(enumeration) => Some(enumeration.name.as_str()),
+            ProtoDefinition::Message(_) => None,
+        })
+        .collect();
+    let types = TypeNames {
+        package: &file.package,
+        enums,
+    };
+
+    let mut message_type = Vec::new();
+    let mut enum_type = Vec::new();
+    for definition in &file.definitions {
+        match definition {
+            ProtoDefinition::Message(message) => message_type.push(types.message(message)),
+            ProtoDefinition::Enum(enumeration) => enum_type.push(EnumDescriptorProto {
+                name: Some(enumeration.name.clone()),
+                value: enumeration
+                    .values
+                    .iter()
+                    .map(|value| EnumValueDescriptorProto {
+                        name: Some(value.name.clone()),
+                        number: Some(value.number),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    let service = ServiceDescriptorProto {
+        name: Some(file.service.name.clone()),
+        method: file
+            .service
+            .rpcs
+            .iter()
+            .map(|rpc| MethodDescriptorProto {
+                name: Some(rpc.name.clone()),
+                input_type: Some(types.qualified(&rpc.input)),
+                output_type: Some(types.qualified(&rpc.output)),
+                client_streaming: Some(false),
+                server_streaming: Some(rpc.server_streaming),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    FileDescriptorProto {
+        name: Some(file_name(file)),
+        package: Some(file.package.clone()),
+        dependency: file.imports.iter().cloned().collect(),
+        message_type,
+        enum_type,
+        service: vec![service],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Descriptor of a well-known file an agent type's file may import.
+pub fn well_known_file_descriptor(name: &str) -> Option<FileDescriptorProto> {
+    let message = match name {
+        "google/protobuf/empty.proto" => DescriptorProto {
+            name: Some("Empty".to_string()),
+            ..Default::default()
+        },
+        "google/protobuf/timestamp.proto" => seconds_and_nanos("Timestamp"),
+        "google/protobuf/duration.proto" => seconds_and_nanos("Duration"),
+        _ => return None,
+    };
+    Some(FileDescriptorProto {
+        name: Some(name.to_string()),
+        package: Some("google.protobuf".to_string()),
+        message_type: vec![message],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    })
+}
+
+/// Name of the well-known file defining `symbol`, if it is one of the well-known types agent
+/// files use.
+pub fn well_known_file_name(symbol: &str) -> Option<&'static str> {
+    match symbol.strip_prefix(WELL_KNOWN_PREFIX)? {
+        "Empty" => Some("google/protobuf/empty.proto"),
+        "Timestamp" => Some("google/protobuf/timestamp.proto"),
+        "Duration" => Some("google/protobuf/duration.proto"),
+        _ => None,
+    }
+}
+
+fn seconds_and_nanos(name: &str) -> DescriptorProto {
+    DescriptorProto {
+        name: Some(name.to_string()),
+        field: vec![
+            scalar_field("seconds", 1, Type::Int64),
+            scalar_field("nanos", 2, Type::Int32),
+        ],
+        ..Default::default()
+    }
+}
+
+fn scalar_field(name: &str, number: i32, ty: Type) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(Label::Optional as i32),
+        r#type: Some(ty as i32),
+        ..Default::default()
+    }
+}
+
+struct TypeNames<'a> {
+    package: &'a str,
+    enums: HashSet<&'a str>,
+}
+
+impl TypeNames<'_> {
+    fn qualified(&self, name: &str) -> String {
+        if name.starts_with(WELL_KNOWN_PREFIX) {
+            format!(".{name}")
+        } else {
+            format!(".{}.{name}", self.package)
+        }
+    }
+
+    fn message(&self, message: &ProtoMessage) -> DescriptorProto {
+        let mut descriptor = DescriptorProto {
+            name: Some(message.name.clone()),
+            ..Default::default()
+        };
+        if let Some(oneof) = &message.oneof {
+            descriptor.oneof_decl.push(OneofDescriptorProto {
+                name: Some(oneof.clone()),
+                ..Default::default()
+            });
+        }
+        // Proto3 `optional` fields are members of synthetic oneofs, declared after the real ones.
+        let mut synthetic = Vec::new();
+        for field in &message.fields {
+            let mut field_descriptor =
+                self.field(&message.name, field, &mut descriptor.nested_type);
+            if message.oneof.is_some() {
+                field_descriptor.oneof_index = Some(0);
+            } else if field.label == ProtoLabel::Optional {
+                field_descriptor.proto3_optional = Some(true);
+                field_descriptor.oneof_index = Some(synthetic.len() as i32);
+                synthetic.push(OneofDescriptorProto {
+                    name: Some(format!("_{}", field.name)),
+                    ..Default::default()
+                });
+            }
+            descriptor.field.push(field_descriptor);
+        }
+        descriptor.oneof_decl.extend(synthetic);
+        descriptor
+    }
+
+    fn field(
+        &self,
+        message: &str,
+        field: &ProtoField,
+        nested: &mut Vec<DescriptorProto>,
+    ) -> FieldDescriptorProto {
+        let label = match field.label {
+            ProtoLabel::Singular | ProtoLabel::Optional => Label::Optional,
+            ProtoLabel::Repeated => Label::Repeated,
+        };
+        let mut descriptor = FieldDescriptorProto {
+            name: Some(field.name.clone()),
+            number: Some(field.number as i32),
+            label: Some(label as i32),
+            ..Default::default()
+        };
+        match &field.ty {
+            ProtoType::Map { key, value } => {
+                // Maps are repeated fields of a nested entry message, named the way protoc names
+                // them.
+                let entry = format!("{}Entry", upper_camel(&field.name));
+                let mut value_field = FieldDescriptorProto {
+                    name: Some("value".to_string()),
+                    number: Some(2),
+                    label: Some(Label::Optional as i32),
+                    ..Default::default()
+                };
+                self.set_type(&mut value_field, value);
+                nested.push(DescriptorProto {
+                    name: Some(entry.clone()),
+                    field: vec![scalar_field("key", 1, scalar_type(*key)), value_field],
+                    options: Some(MessageOptions {
+                        map_entry: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                });
+                descriptor.label = Some(Label::Repeated as i32);
+                descriptor.r#type = Some(Type::Message as i32);
+                descriptor.type_name = Some(format!("{}.{entry}", self.qualified(message)));
+            }
+            ty => self.set_type(&mut descriptor, ty),
+        }
+        descriptor
+    }
+
+    fn set_type(&self, descriptor: &mut FieldDescriptorProto, ty: &ProtoType) {
+        match ty {
+            ProtoType::Scalar(scalar) => descriptor.r#type = Some(scalar_type(*scalar) as i32),
+            ProtoType::Named(name) => {
+                let ty = if self.enums.contains(name.as_str()) {
+                    Type::Enum
+                } else {
+                    Type::Message
+                };
+                descriptor.r#type = Some(ty as i32);
+                descriptor.type_name = Some(self.qualified(name));
+            }
+            // Map values cannot be maps themselves; the renderer wraps them.
+            ProtoType::Map { .. } => descriptor.r#type = Some(Type::Message as i32),
+        }
+    }
+}
+
+fn scalar_type(scalar: ProtoScalar) -> Type {
+    match scalar {
+        ProtoScalar::Bool => Type::Bool,
+        ProtoScalar::Int32 => Type::Int32,
+        ProtoScalar::Int64 => Type::Int64,
+        ProtoScalar::Uint32 => Type::Uint32,
+        ProtoScalar::Uint64 => Type::Uint64,
+        ProtoScalar::Float => Type::Float,
+        ProtoScalar::Double => Type::Double,
+        ProtoScalar::String => Type::String,
+        ProtoScalar::Bytes => Type::Bytes,
+    }
+}
+
+/// protoc's field-name-to-CamelCase conversion used for map entry messages: underscores are
+/// dropped and the character following one is upper-cased.
+fn upper_camel(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize = true;
+    for c in name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            result.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_descriptor, file_name, well_known_file_descriptor, well_known_file_name};
+    use golem_common::schema::render::ProtoFile;
+    use golem_common::schema::render::protobuf_idl::{
+        ProtoDefinition, ProtoEnum, ProtoEnumValue, ProtoField, ProtoLabel, ProtoMessage, ProtoRpc,
+        ProtoScalar, ProtoService, ProtoType,
+    };
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use std::collections::BTreeSet;
+    use test_r::test;
+
+    fn field(name: &str, number: u32, label: ProtoLabel, ty: ProtoType) -> ProtoField {
+        ProtoField {
+            name: name.to_string(),
+            number,
+            label,
+            ty,
+            doc: None,
+        }
+    }
+
+    fn inventory_file() -> ProtoFile {
+        ProtoFile {
+            package: "golem.agent.inventory".to_string(),
+            imports: BTreeSet::from(["google/protobuf/empty.proto".to_string()]),
+            definitions: vec![
+                ProtoDefinition::Enum(ProtoEnum {
+                    name: "Color".to_string(),
+                    doc: None,
+                    values: vec![
+                        ProtoEnumValue {
+                            name: "COLOR_UNSPECIFIED".to_string(),
+                            number: 0,
+                            case: None,
+                        },
+                        ProtoEnumValue {
+                            name: "COLOR_RED".to_string(),
+                            number: 1,
+                            case: Some("red".to_string()),
+                        },
+                    ],
+                }),
+                ProtoDefinition::Message(ProtoMessage {
+                    name: "ListSkusRequest".to_string(),
+                    doc: None,
+                    oneof: None,
+                    fields: vec![
+                        field(
+                            "stock_by_sku",
+                            1,
+                            ProtoLabel::Singular,
+                            ProtoType::Map {
+                                key: ProtoScalar::String,
+                                value: Box::new(ProtoType::Named("Color".to_string())),
+                            },
+                        ),
+                        field(
+                            "phantom_id",
+                            2,
+                            ProtoLabel::Optional,
+                            ProtoType::Scalar(ProtoScalar::String),
+                        ),
+                    ],
+                }),
+                ProtoDefinition::Message(ProtoMessage {
+                    name: "ListSkusItem".to_string(),
+                    doc: None,
+                    oneof: Some("value".to_string()),
+                    fields: vec![
+                        field(
+                            "none",
+                            1,
+                            ProtoLabel::Singular,
+                            ProtoType::Named("google.protobuf.Empty".to_string()),
+                        ),
+                        field(
+                            "color",
+                            2,
+                            ProtoLabel::Singular,
+                            ProtoType::Named("Color".to_string()),
+                        ),
+                    ],
+                }),
+            ],
+            service: ProtoService {
+                name: "Inventory".to_string(),
+                doc: None,
+                rpcs: vec![ProtoRpc {
+                    name: "ListSkusStream".to_string(),
+                    method: "list-skus".to_string(),
+                    doc: None,
+                    input: "ListSkusRequest".to_string(),
+                    output: "ListSkusItem".to_string(),
+                    server_streaming: true,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn agent_file_descriptor_mirrors_the_rendered_file() {
+        let file = inventory_file();
+        let descriptor = file_descriptor(&file);
+
+        assert_eq!(file_name(&file), "golem/agent/inventory.proto");
+        assert_eq!(descriptor.package(), "golem.agent.inventory");
+        assert_eq!(descriptor.dependency, vec!["google/protobuf/empty.proto"]);
+        assert_eq!(descriptor.syntax(), "proto3");
+
+        let method = &descriptor.service[0].method[0];
+        assert_eq!(
+            method.input_type(),
+            ".golem.agent.inventory.ListSkusRequest"
+        );
+        assert_eq!(method.output_type(), ".golem.agent.inventory.ListSkusItem");
+        assert!(method.server_streaming());
+
+        let request = &descriptor.message_type[0];
+        let map = &request.field[0];
+        assert_eq!(map.label(), Label::Repeated);
+        assert_eq!(
+            map.type_name(),
+            ".golem.agent.inventory.ListSkusRequest.StockBySkuEntry"
+        );
+        let entry = &request.nested_type[0];
+        assert_eq!(entry.name(), "StockBySkuEntry");
+        assert!(entry.options.as_ref().unwrap().map_entry());
+        assert_eq!(entry.field[0].r#type(), Type::String);
+        assert_eq!(entry.field[1].r#type(), Type::Enum);
+        assert_eq!(entry.field[1].type_name(), ".golem.agent.inventory.Color");
+
+        let phantom_id = &request.field[1];
+        assert!(phantom_id.proto3_optional());
+        assert_eq!(phantom_id.oneof_index, Some(0));
+        assert_eq!(request.oneof_decl[0].name(), "_phantom_id");
+
+        let item = &descriptor.message_type[1];
+        assert_eq!(item.oneof_decl.len(), 1);
+        assert_eq!(item.oneof_decl[0].name(), "value");
+        assert!(item.field.iter().all(|field| field.oneof_index == Some(0)));
+        assert_eq!(item.field[0].type_name(), ".google.protobuf.Empty");
+        assert_eq!(item.field[0].r#type(), Type::Message);
+    }
+
+    #[test]
+    fn well_known_files_are_resolved_by_name_and_symbol() {
+        assert_eq!(
+            well_known_file_name("google.protobuf.Timestamp"),
+            Some("google/protobuf/timestamp.proto")
+        );
+        assert_eq!(well_known_file_name("google.protobuf.Any"), None);
+
+        let timestamp = well_known_file_descriptor("google/protobuf/timestamp.proto").unwrap();
+        assert_eq!(timestamp.package(), "google.protobuf");
+        assert_eq!(timestamp.message_type[0].name(), "Timestamp");
+        assert_eq!(timestamp.message_type[0].field[0].r#type(), Type::Int64);
+        assert!(well_known_file_descriptor("google/protobuf/any.proto").is_none());
+    }
+}
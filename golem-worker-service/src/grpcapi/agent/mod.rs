@@ -0,0 +1,632 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! gRPC API presenting each deployed agent type as a gRPC service.
+//!
+//! The services are the ones rendered by
+//! [`golem_common::schema::render::protobuf_idl`]: package `golem.agent.<agent-type>`, one unary
+//! rpc per method mapped to invoke-and-await, plus a server-streaming rpc yielding the elements
+//! of list results. As the set of services depends on what is deployed, rpcs are not registered
+//! statically: [`AgentRpcLayer`] routes every `/golem.agent.*` path to this module, which encodes
+//! and decodes the messages from the agent schema. Rendered services are cached per deployment
+//! revision.
+//!
+//! Requests address an agent type with metadata:
+//!
+//! - `authorization`: `Bearer <token>`;
+//! - `x-golem-application`, `x-golem-environment` and `x-golem-agent-type`;
+//! - optionally `x-golem-deployment-revision` to pin a deployment and `x-golem-account-email` for
+//!   environments shared by another account;
+//! - optionally `x-golem-idempotency-key`.
+//!
+//! The `grpc-timeout` of a call becomes the deadline of the invocation. Server reflection (v1)
+//! describes the service of the agent type given in the metadata, or without `x-golem-agent-type`
+//! the services of every agent type deployed to the environment.
+
+mod codec;
+mod descriptor;
+mod reflection;
+mod router;
+
+use crate::api::agents::AgentInvocationMode;
+use crate::bootstrap::Services;
+use crate::config::AgentGrpcApiConfig;
+use crate::grpcapi::agent::reflection::AgentReflection;
+use crate::grpcapi::agent::router::AgentRpcLayer;
+use crate::grpcapi::error_to_status;
+use crate::service::auth::AuthService;
+use crate::service::worker::{TypedAgentInvocation, WorkerService, WorkerServiceError};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::TryFutureExt;
+use golem_api_grpc::proto::golem::worker::v1::AgentError;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::model::IdempotencyKey;
+use golem_common::model::agent::{
+    AgentType, AgentTypeName, DataSchema, DataValue, ResolvedAgentType, UntypedDataValue,
+};
+use golem_common::model::application::ApplicationName;
+use golem_common::model::auth::TokenSecret;
+use golem_common::model::deployment::DeploymentRevision;
+use golem_common::model::environment::{EnvironmentId, EnvironmentName};
+use golem_common::schema::adapters::{
+    agent_type_to_schema, typed_input_to_untyped_data_value,
+    untyped_data_value_to_typed_schema_output,
+};
+use golem_common::schema::agent::{AgentTypeSchema, InputSchema, OutputSchema};
+use golem_common::schema::render::protobuf_value::{
+    decode_request, encode_output, encode_stream_items,
+};
+use golem_common::schema::render::{ProtoFile, agent_type_to_proto_file};
+use golem_common::schema::schema_value::SchemaValue;
+use golem_service_base::clients::registry::RegistryService;
+use golem_service_base::grpc::server::GrpcServerTlsConfig;
+use golem_service_base::model::auth::AuthCtx;
+use http::HeaderMap;
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::Status;
+use tonic::transport::Server;
+use tonic_reflection::pb::v1::server_reflection_server::ServerReflectionServer;
+use tonic_tracing_opentelemetry::middleware;
+use tonic_tracing_opentelemetry::middleware::filters;
+use tracing::Instrument;
+use uuid::Uuid;
+
+const APPLICATION_HEADER: &str = "x-golem-application";
+const ENVIRONMENT_HEADER: &str = "x-golem-environment";
+const AGENT_TYPE_HEADER: &str = "x-golem-agent-type";
+const DEPLOYMENT_REVISION_HEADER: &str = "x-golem-deployment-revision";
+const ACCOUNT_EMAIL_HEADER: &str = "x-golem-account-email";
+const IDEMPOTENCY_KEY_HEADER: &str = "x-golem-idempotency-key";
+const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+const RENDERED_SERVICE_CACHE_MAX_CAPACITY: usize = 1024;
+const RENDERED_SERVICE_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const RENDERED_SERVICE_CACHE_EVICTION_PERIOD: Duration = Duration::from_secs(60);
+
+pub async fn start_agent_grpc_server(
+    config: &AgentGrpcApiConfig,
+    services: Services,
+    join_set: &mut JoinSet<Result<(), anyhow::Error>>,
+) -> anyhow::Result<u16> {
+    let (_health_reporter, health_service) = tonic_health::server::health_reporter();
+
+    let addr = SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), config.port);
+    let listener = TcpListener::bind(addr).await?;
+
+    let port = listener.local_addr()?.port();
+
+    let api = AgentGrpcApi::new(
+        services.worker_service.clone(),
+        services.auth_service.clone(),
+        services.registry_service.clone(),
+    );
+
+    join_set.spawn({
+        let mut server = Server::builder();
+
+        if let GrpcServerTlsConfig::Enabled(tls) = &config.tls {
+            server = server.tls_config(tls.to_tonic())?;
+        };
+
+        server
+            .layer(middleware::server::OtelGrpcLayer::default().filter(filters::reject_healthcheck))
+            .layer(AgentRpcLayer::new(api.clone(), config.max_message_size))
+            .add_service(ServerReflectionServer::new(AgentReflection::new(api)))
+            .add_service(health_service)
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .map_err(anyhow::Error::from)
+            .in_current_span()
+    });
+
+    Ok(port)
+}
+
+/// Resolves the agent types addressed by requests and invokes their methods.
+#[derive(Clone)]
+pub struct AgentGrpcApi {
+    worker_service: Arc<WorkerService>,
+    auth_service: Arc<dyn AuthService>,
+    registry_service: Arc<dyn RegistryService>,
+    rendered_services: Cache<RenderedServiceKey, (), Arc<RenderedService>, Status>,
+}
+
+/// A deployed agent type together with the gRPC service presenting it.
+pub struct AgentGrpcService {
+    resolved: ResolvedAgentType,
+    rendered: Arc<RenderedService>,
+}
+
+impl AgentGrpcService {
+    /// Fully qualified name of the gRPC service.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.proto().package, self.proto().service.name)
+    }
+
+    /// The proto file declaring the service.
+    pub fn proto(&self) -> &ProtoFile {
+        &self.rendered.proto
+    }
+
+    fn schema(&self) -> &AgentTypeSchema {
+        &self.rendered.schema
+    }
+}
+
+/// An agent type's schema and the proto file rendered from it. An agent type does not change
+/// within a deployment revision, so this is cached by [`RenderedServiceKey`].
+struct RenderedService {
+    schema: AgentTypeSchema,
+    proto: ProtoFile,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct RenderedServiceKey {
+    environment_id: EnvironmentId,
+    deployment_revision: DeploymentRevision,
+    agent_type_name: AgentTypeName,
+}
+
+/// The environment a request addresses, as given in its metadata.
+struct EnvironmentTarget {
+    app_name: ApplicationName,
+    env_name: EnvironmentName,
+    deployment_revision: Option<i64>,
+    owner_account_email: Option<String>,
+}
+
+impl EnvironmentTarget {
+    fn from_headers(headers: &HeaderMap) -> Result<Self, Status> {
+        let app_name = ApplicationName::try_from(required_header(headers, APPLICATION_HEADER)?)
+            .map_err(|err| {
+                Status::invalid_argument(format!("Invalid {APPLICATION_HEADER}: {err}"))
+            })?;
+        let env_name = EnvironmentName::try_from(required_header(headers, ENVIRONMENT_HEADER)?)
+            .map_err(|err| {
+                Status::invalid_argument(format!("Invalid {ENVIRONMENT_HEADER}: {err}"))
+            })?;
+        let deployment_revision = optional_header(headers, DEPLOYMENT_REVISION_HEADER)?
+            .map(|revision| {
+                revision.parse::<i64>().map_err(|_| {
+                    Status::invalid_argument(format!(
+                        "Invalid {DEPLOYMENT_REVISION_HEADER}: {revision}"
+                    ))
+                })
+            })
+            .transpose()?;
+        let owner_account_email = optional_header(headers, ACCOUNT_EMAIL_HEADER)?;
+
+        Ok(Self {
+            app_name,
+            env_name,
+            deployment_revision,
+            owner_account_email,
+        })
+    }
+}
+
+/// The agent type a request addresses, as given in its metadata.
+struct AgentTypeTarget {
+    environment: EnvironmentTarget,
+    agent_type_name: AgentTypeName,
+}
+
+impl AgentTypeTarget {
+    fn from_headers(headers: &HeaderMap) -> Result<Self, Status> {
+        Ok(Self {
+            environment: EnvironmentTarget::from_headers(headers)?,
+            agent_type_name: AgentTypeName(required_header(headers, AGENT_TYPE_HEADER)?),
+        })
+    }
+}
+
+/// A request to an agent rpc, authenticated and resolved to the agent type's service.
+pub struct AgentRpcCall {
+    api: AgentGrpcApi,
+    auth: AuthCtx,
+    target: AgentTypeTarget,
+    service: AgentGrpcService,
+    rpc: usize,
+    idempotency_key: Option<IdempotencyKey>,
+    timeout: Option<Duration>,
+}
+
+impl AgentGrpcApi {
+    pub fn new(
+        worker_service: Arc<WorkerService>,
+        auth_service: Arc<dyn AuthService>,
+        registry_service: Arc<dyn RegistryService>,
+    ) -> Self {
+        Self {
+            worker_service,
+            auth_service,
+            registry_service,
+            rendered_services: Cache::new(
+                Some(RENDERED_SERVICE_CACHE_MAX_CAPACITY),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::OlderThan {
+                    ttl: RENDERED_SERVICE_CACHE_TTL,
+                    period: RENDERED_SERVICE_CACHE_EVICTION_PERIOD,
+                },
+                "agent_grpc_rendered_services",
+            ),
+        }
+    }
+
+    /// Authenticates `headers` and resolves the agent type they address to its service.
+    async fn service(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<(AuthCtx, AgentTypeTarget, AgentGrpcService), Status> {
+        let auth = self.authenticate(headers).await?;
+        let target = AgentTypeTarget::from_headers(headers)?;
+
+        let resolved = self
+            .worker_service
+            .resolve_agent_type(
+                &target.environment.app_name,
+                &target.environment.env_name,
+                &target.agent_type_name,
+                target.environment.deployment_revision,
+                target.environment.owner_account_email.as_deref(),
+                &auth,
+            )
+            .await
+            .map_err(worker_error_to_status)?;
+        let rendered = self.render(&resolved).await?;
+
+        Ok((auth, target, AgentGrpcService { resolved, rendered }))
+    }
+
+    /// Authenticates `headers` and resolves the services they address: the agent type's if
+    /// `x-golem-agent-type` is given, otherwise those of every agent type deployed to the
+    /// environment that can be presented as a gRPC service.
+    async fn services(&self, headers: &HeaderMap) -> Result<Vec<AgentGrpcService>, Status> {
+        if headers.contains_key(AGENT_TYPE_HEADER) {
+            let (_, _, service) = self.service(headers).await?;
+            return Ok(vec![service]);
+        }
+
+        let auth = self.authenticate(headers).await?;
+        let target = EnvironmentTarget::from_headers(headers)?;
+        let deployment_revision = target
+            .deployment_revision
+            .map(|revision| {
+                u64::try_from(revision)
+                    .ok()
+                    .and_then(|revision| DeploymentRevision::new(revision).ok())
+                    .ok_or_else(|| {
+                        Status::invalid_argument(format!(
+                            "Invalid {DEPLOYMENT_REVISION_HEADER}: {revision}"
+                        ))
+                    })
+            })
+            .transpose()?;
+
+        let agent_types = self
+            .registry_service
+            .list_agent_types_by_names(
+                &target.app_name,
+                &target.env_name,
+                deployment_revision,
+                target.owner_account_email.as_deref(),
+                &auth,
+            )
+            .await
+            .map_err(|err| worker_error_to_status(err.into()))?;
+
+        let mut services = Vec::with_capacity(agent_types.len());
+        for resolved in agent_types {
+            if let Ok(rendered) = self.render(&resolved).await {
+                services.push(AgentGrpcService { resolved, rendered });
+            }
+        }
+        Ok(services)
+    }
+
+    async fn render(&self, resolved: &ResolvedAgentType) -> Result<Arc<RenderedService>, Status> {
+        let agent_type = &resolved.registered_agent_type.agent_type;
+        let key = RenderedServiceKey {
+            environment_id: resolved.environment_id,
+            deployment_revision: resolved.deployment_revision,
+            agent_type_name: agent_type.type_name.clone(),
+        };
+        self.rendered_services
+            .get_or_insert_simple(&key, async || render_service(agent_type).map(Arc::new))
+            .await
+    }
+
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<AuthCtx, Status> {
+        let authorization = required_header(headers, http::header::AUTHORIZATION.as_str())
+            .map_err(|_| Status::unauthenticated("Missing bearer token"))?;
+        let token = authorization
+            .strip_prefix("Bearer ")
+            .and_then(|token| TokenSecret::from_str(token.trim()).ok())
+            .ok_or_else(|| Status::unauthenticated("Invalid bearer token"))?;
+
+        self.auth_service
+            .authenticate_token(token)
+            .await
+            .map_err(|err| worker_error_to_status(err.into()))
+    }
+
+    /// Prepares a call of `rpc` on `service` (the fully qualified service name of the path).
+    pub async fn prepare(
+        &self,
+        service: &str,
+        rpc: &str,
+        headers: &HeaderMap,
+    ) -> Result<AgentRpcCall, Status> {
+        let (auth, target, agent_service) = self.service(headers).await?;
+
+        let expected = agent_service.full_name();
+        if service != expected {
+            return Err(Status::unimplemented(format!(
+                "Agent type {} is served as {expected}, not {service}",
+                target.agent_type_name
+            )));
+        }
+        let rpc = agent_service
+            .proto()
+            .service
+            .rpcs
+            .iter()
+            .position(|candidate| candidate.name == rpc)
+            .ok_or_else(|| Status::unimplemented(format!("Unknown rpc {service}/{rpc}")))?;
+
+        let idempotency_key =
+            optional_header(headers, IDEMPOTENCY_KEY_HEADER)?.map(IdempotencyKey::new);
+        let timeout = optional_header(headers, GRPC_TIMEOUT_HEADER)?
+            .map(|timeout| {
+                parse_grpc_timeout(&timeout).ok_or_else(|| {
+                    Status::invalid_argument(format!("Invalid {GRPC_TIMEOUT_HEADER}: {timeout}"))
+                })
+            })
+            .transpose()?;
+
+        Ok(AgentRpcCall {
+            api: self.clone(),
+            auth,
+            target,
+            service: agent_service,
+            rpc,
+            idempotency_key,
+            timeout,
+        })
+    }
+}
+
+impl AgentRpcCall {
+    pub fn server_streaming(&self) -> bool {
+        self.service.proto().service.rpcs[self.rpc].server_streaming
+    }
+
+    /// Invokes the agent method with the `<Method>Request` message `request` and awaits its
+    /// result, returning the response messages: the `<Method>Output` of unary rpcs or one
+    /// `<Method>Item` per element for streaming ones.
+    pub async fn invoke(self, request: Bytes) -> Result<Vec<Bytes>, Status> {
+        let rpc = &self.service.proto().service.rpcs[self.rpc];
+        let schema = self.service.schema();
+        let graph = &schema.schema;
+        let agent_type = &self.service.resolved.registered_agent_type.agent_type;
+
+        let method_schema = schema
+            .methods
+            .iter()
+            .find(|method| method.name == rpc.method)
+            .ok_or_else(|| Status::internal(format!("Agent method {} not found", rpc.method)))?;
+        let method = agent_type
+            .methods
+            .iter()
+            .find(|method| method.name == rpc.method)
+            .ok_or_else(|| Status::internal(format!("Agent method {} not found", rpc.method)))?;
+
+        let request = decode_request(
+            graph,
+            &schema.constructor.input_schema,
+            &method_schema.input_schema,
+            &request,
+        )
+        .map_err(|err| Status::invalid_argument(format!("Invalid {}: {err}", rpc.input)))?;
+
+        let parameters = typed_parameters(
+            &schema.constructor.input_schema,
+            &agent_type.constructor.input_schema,
+            &request.constructor,
+        )
+        .map_err(|err| {
+            Status::invalid_argument(format!("Agent constructor parameters type error: {err}"))
+        })?;
+        let method_parameters = typed_parameters(
+            &method_schema.input_schema,
+            &method.input_schema,
+            &request.input,
+        )
+        .map_err(|err| {
+            Status::invalid_argument(format!("Agent method parameters type error: {err}"))
+        })?;
+        let phantom_id = request
+            .phantom_id
+            .map(|phantom_id| {
+                Uuid::parse_str(&phantom_id)
+                    .map_err(|err| Status::invalid_argument(format!("Invalid phantom_id: {err}")))
+            })
+            .transpose()?;
+        let deadline = self
+            .timeout
+            .and_then(|timeout| chrono::Duration::from_std(timeout).ok())
+            .map(|timeout| Utc::now() + timeout);
+
+        let result = self
+            .api
+            .worker_service
+            .invoke_resolved_agent(
+                self.service.resolved.clone(),
+                TypedAgentInvocation {
+                    app_name: self.target.environment.app_name,
+                    env_name: self.target.environment.env_name,
+                    agent_type_name: self.target.agent_type_name,
+                    parameters,
+                    phantom_id,
+                    method_name: method.name.clone(),
+                    method_parameters,
+                    mode: AgentInvocationMode::Await,
+                    schedule_at: None,
+                    idempotency_key: self.idempotency_key,
+                    deadline,
+                    at_oplog_index: None,
+                    at_timestamp: None,
+                },
+                self.auth,
+            )
+            .await
+            .map_err(worker_error_to_status)?;
+
+        let value = match (&method_schema.output_schema, result.result) {
+            (OutputSchema::Unit, _) => None,
+            (OutputSchema::Single(_), Some(value)) => {
+                Some(typed_output(value, &method.output_schema).map_err(|err| {
+                    Status::internal(format!("Agent method result type error: {err}"))
+                })?)
+            }
+            (OutputSchema::Single(_), None) => {
+                return Err(Status::internal(format!(
+                    "Agent method {} returned no result",
+                    method.name
+                )));
+            }
+        };
+
+        let messages = match (rpc.server_streaming, &value) {
+            (true, Some(value)) => encode_stream_items(graph, &method_schema.output_schema, value),
+            (true, None) => Ok(Vec::new()),
+            (false, value) => encode_output(graph, &method_schema.output_schema, value.as_ref())
+                .map(|message| vec![message]),
+        }
+        .map_err(|err| Status::internal(format!("Failed to encode {}: {err}", rpc.output)))?;
+
+        Ok(messages.into_iter().map(Bytes::from).collect())
+    }
+}
+
+/// Renders the gRPC service presenting `agent_type`.
+fn render_service(agent_type: &AgentType) -> Result<RenderedService, Status> {
+    let schema = agent_type_to_schema(agent_type)
+        .map_err(|err| Status::internal(format!("Agent type schema error: {err}")))?;
+    let proto = agent_type_to_proto_file(&schema).map_err(|err| {
+        Status::failed_precondition(format!(
+            "Agent type {} cannot be presented as a gRPC service: {err}",
+            agent_type.type_name
+        ))
+    })?;
+    Ok(RenderedService { schema, proto })
+}
+
+/// Types the decoded values of an agent's parameters against the legacy schema the worker
+/// executor expects.
+fn typed_parameters(
+    input: &InputSchema,
+    data_schema: &DataSchema,
+    values: &[SchemaValue],
+) -> Result<DataValue, String> {
+    let untyped =
+        typed_input_to_untyped_data_value(input, values).map_err(|err| err.to_string())?;
+    DataValue::try_from_untyped(untyped, data_schema.clone())
+}
+
+/// Projects a method result into the schema layer, to encode it from the method's schema.
+fn typed_output(value: DataValue, data_schema: &DataSchema) -> Result<SchemaValue, String> {
+    let typed =
+        untyped_data_value_to_typed_schema_output(UntypedDataValue::from(value), data_schema)
+            .map_err(|err| err.to_string())?;
+    Ok(typed.into_parts().1)
+}
+
+fn worker_error_to_status(error: WorkerServiceError) -> Status {
+    error_to_status(AgentError::from(error))
+}
+
+fn required_header(headers: &HeaderMap, name: &str) -> Result<String, Status> {
+    optional_header(headers, name)?
+        .ok_or_else(|| Status::invalid_argument(format!("Missing {name} metadata")))
+}
+
+fn optional_header(headers: &HeaderMap, name: &str) -> Result<Option<String>, Status> {
+    headers
+        .get(name)
+        .map(|value| {
+            value
+                .to_str()
+                .map(|value| value.to_string())
+                .map_err(|_| Status::invalid_argument(format!("Invalid {name} metadata")))
+        })
+        .transpose()
+}
+
+/// Parses a `grpc-timeout` value: at most eight digits followed by a unit.
+fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || value.len() > 9 {
+        return None;
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    if !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = amount.parse().ok()?;
+    match unit {
+        "H" => Some(Duration::from_secs(amount * 60 * 60)),
+        "M" => Some(Duration::from_secs(amount * 60)),
+        "S" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_millis(amount)),
+        "u" => Some(Duration::from_micros(amount)),
+        "n" => Some(Duration::from_nanos(amount)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_grpc_timeout;
+    use std::time::Duration;
+    use test_r::test;
+
+    #[test]
+    fn grpc_timeouts_are_parsed_with_their_unit() {
+        assert_eq!(parse_grpc_timeout("2H"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_grpc_timeout("3M"), Some(Duration::from_secs(180)));
+        assert_eq!(parse_grpc_timeout("10S"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_grpc_timeout("250m"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_grpc_timeout("7u"), Some(Duration::from_micros(7)));
+        assert_eq!(
+            parse_grpc_timeout("99999999n"),
+            Some(Duration::from_nanos(99_999_999))
+        );
+    }
+
+    #[test]
+    fn malformed_grpc_timeouts_are_rejected() {
+        assert_eq!(parse_grpc_timeout(""), None);
+        assert_eq!(parse_grpc_timeout("S"), None);
+        assert_eq!(parse_grpc_timeout("10"), None);
+        assert_eq!(parse_grpc_timeout("10s"), None);
+        assert_eq!(parse_grpc_timeout("-1S"), None);
+        assert_eq!(parse_grpc_timeout("123456789S"), None);
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::grpcapi::agent::AgentGrpcApi;
+use crate::grpcapi::agent::descriptor::{
+    file_descriptor, file_name, well_known_file_descriptor, well_known_file_name,
+};
+use http::HeaderMap;
+use prost::Message;
+use prost_types::FileDescriptorProto;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::server_reflection_server::ServerReflection;
+use tonic_reflection::pb::v1::{
+    ErrorResponse, FileDescriptorResponse, ListServiceResponse, ServerReflectionRequest,
+    ServerReflectionResponse, ServiceResponse,
+};
+use tracing::Instrument;
+
+/// Server reflection describing the services addressed by the metadata of the reflection stream:
+/// the agent type's if `x-golem-agent-type` is given, otherwise those of every agent type deployed
+/// to the environment. Listing services requires the same credentials as calling them.
+pub struct AgentReflection {
+    api: AgentGrpcApi,
+}
+
+impl AgentReflection {
+    pub fn new(api: AgentGrpcApi) -> Self {
+        Self { api }
+    }
+}
+
+#[async_trait::async_trait]
+impl ServerReflection for AgentReflection {
+    type ServerReflectionInfoStream = ReceiverStream<Result<ServerReflectionResponse, Status>>;
+
+    async fn server_reflection_info(
+        &self,
+        request: Request<Streaming<ServerReflectionRequest>>,
+    ) -> Result<Response<Self::ServerReflectionInfoStream>, Status> {
+        let headers = request.metadata().clone().into_headers();
+        let mut requests = request.into_inner();
+        let api = self.api.clone();
+        let (sender, receiver) = mpsc::channel(16);
+
+        tokio::spawn(
+            async move {
+                while let Ok(Some(request)) = requests.message().await {
+                    let response = reflect(&api, &headers, request).await;
+                    if sender.send(Ok(response)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+async fn reflect(
+    api: &AgentGrpcApi,
+    headers: &HeaderMap,
+    request: ServerReflectionRequest,
+) -> ServerReflectionResponse {
+    let message_response = match &request.message_request {
+        Some(MessageRequest::ListServices(_)) => list_services(api, headers).await,
+        Some(MessageRequest::FileByFilename(name)) => match well_known_file_descriptor(name) {
+            Some(file) => Ok(vec![file]),
+            None => agent_files(api, headers, |_, file| file == name).await,
+        }
+        .map(file_descriptor_response),
+        Some(MessageRequest::FileContainingSymbol(symbol)) => {
+            match well_known_file_name(symbol).and_then(well_known_file_descriptor) {
+                Some(file) => Ok(vec![file]),
+                None => {
+                    agent_files(api, headers, |package, _| {
+                        symbol == package
+                            || symbol
+                                .strip_prefix(package)
+                                .is_some_and(|rest| rest.starts_with('.'))
+                    })
+                    .await
+                }
+            }
+            .map(file_descriptor_response)
+        }
+        Some(MessageRequest::FileContainingExtension(_))
+        | Some(MessageRequest::AllExtensionNumbersOfType(_)) => Err(Status::not_found(
+            "Agent services do not declare extensions",
+        )),
+        None => Err(Status::invalid_argument("Missing reflection request")),
+    };
+
+    ServerReflectionResponse {
+        valid_host: request.host.clone(),
+        original_request: Some(request),
+        message_response: Some(message_response.unwrap_or_else(|status| {
+            MessageResponse::ErrorResponse(ErrorResponse {
+                error_code: status.code() as i32,
+                error_message: status.message().to_string(),
+            })
+        })),
+    }
+}
+
+async fn list_services(api: &AgentGrpcApi, headers: &HeaderMap) -> Result<MessageResponse, Status> {
+    // Reflection and health are not listed, as they are not described either.
+    let service = api
+        .services(headers)
+        .await?
+        .iter()
+        .map(|service| ServiceResponse {
+            name: service.full_name(),
+        })
+        .collect();
+    Ok(MessageResponse::ListServicesResponse(ListServiceResponse {
+        service,
+    }))
+}
+
+/// The file of the first addressed service whose package and file name `matches` accepts,
+/// followed by the well-known files it imports.
+async fn agent_files(
+    api: &AgentGrpcApi,
+    headers: &HeaderMap,
+    matches: impl Fn(&str, &str) -> bool,
+) -> Result<Vec<FileDescriptorProto>, Status> {
+    let services = api.services(headers).await?;
+    let proto = services
+        .iter()
+        .map(|service| service.proto())
+        .find(|proto| matches(&proto.package, &file_name(proto)))
+        .ok_or_else(|| Status::not_found("Not described by any agent service"))?;
+    let mut files = vec![file_descriptor(proto)];
+    files.extend(
+        proto
+            .imports
+            .iter()
+            .filter_map(|import| well_known_file_descriptor(import)),
+    );
+    Ok(files)
+}
+
+fn file_descriptor_response(files: Vec<FileDescriptorProto>) -> MessageResponse {
+    MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+        file_descriptor_proto: files.iter().map(|file| file.encode_to_vec()).collect(),
+    })
+}
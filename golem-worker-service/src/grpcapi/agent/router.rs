@@ -0,0 +1,207 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::grpcapi::agent::codec::RawCodec;
+use crate::grpcapi::agent::{AgentGrpcApi, AgentRpcCall};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use std::task::{Context, Poll};
+use tonic::body::Body;
+use tonic::codec::CompressionEncoding;
+use tonic::server::{Grpc, ServerStreamingService, UnaryService};
+use tonic::{Request, Response, Status};
+use tower::{Layer, Service};
+
+const AGENT_PACKAGE_PREFIX: &str = "golem.agent.";
+
+/// Routes the rpcs of agent services to [`AgentGrpcApi`], and everything else (reflection,
+/// health) to the statically registered services.
+#[derive(Clone)]
+pub struct AgentRpcLayer {
+    api: AgentGrpcApi,
+    max_message_size: usize,
+}
+
+impl AgentRpcLayer {
+    pub fn new(api: AgentGrpcApi, max_message_size: usize) -> Self {
+        Self {
+            api,
+            max_message_size,
+        }
+    }
+}
+
+impl<S> Layer<S> for AgentRpcLayer {
+    type Service = AgentRpcRouter<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AgentRpcRouter {
+            inner,
+            api: self.api.clone(),
+            max_message_size: self.max_message_size,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AgentRpcRouter<S> {
+    inner: S,
+    api: AgentGrpcApi,
+    max_message_size: usize,
+}
+
+impl<S, B> Service<http::Request<Body>> for AgentRpcRouter<S>
+where
+    S: Service<http::Request<Body>, Response = http::Response<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError>,
+{
+    type Response = http::Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        match agent_rpc_path(request.uri().path()) {
+            Some((service, rpc)) => {
+                let (service, rpc) = (service.to_string(), rpc.to_string());
+                let api = self.api.clone();
+                let max_message_size = self.max_message_size;
+                async move { Ok(serve(api, service, rpc, request, max_message_size).await) }.boxed()
+            }
+            None => {
+                // `inner` is the instance that was polled ready; leave a fresh clone behind.
+                let clone = self.inner.clone();
+                let mut inner = std::mem::replace(&mut self.inner, clone);
+                async move {
+                    inner
+                        .call(request)
+                        .await
+                        .map(|response| response.map(Body::new))
+                }
+                .boxed()
+            }
+        }
+    }
+}
+
+/// Splits `/<package>.<Service>/<Rpc>` into the fully qualified service name and the rpc name
+/// when it addresses an agent service.
+fn agent_rpc_path(path: &str) -> Option<(&str, &str)> {
+    let (service, rpc) = path.strip_prefix('/')?.split_once('/')?;
+    if service.starts_with(AGENT_PACKAGE_PREFIX) && !rpc.is_empty() && !rpc.contains('/') {
+        Some((service, rpc))
+    } else {
+        None
+    }
+}
+
+async fn serve(
+    api: AgentGrpcApi,
+    service: String,
+    rpc: String,
+    request: http::Request<Body>,
+    max_message_size: usize,
+) -> http::Response<Body> {
+    let call = match api.prepare(&service, &rpc, request.headers()).await {
+        Ok(call) => call,
+        Err(status) => return status.into_http(),
+    };
+
+    let mut grpc = Grpc::new(RawCodec)
+        .accept_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Gzip)
+        .max_decoding_message_size(max_message_size)
+        .max_encoding_message_size(max_message_size);
+
+    if call.server_streaming() {
+        grpc.server_streaming(StreamingRpc(Some(call)), request)
+            .await
+    } else {
+        grpc.unary(UnaryRpc(Some(call)), request).await
+    }
+}
+
+/// Tonic takes the handler of a call by value and invokes it once; the prepared call is moved out
+/// of it on that invocation.
+struct UnaryRpc(Option<AgentRpcCall>);
+
+impl UnaryService<Bytes> for UnaryRpc {
+    type Response = Bytes;
+    type Future = BoxFuture<'static, Result<Response<Bytes>, Status>>;
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        unary(self.0.take(), request).boxed()
+    }
+}
+
+struct StreamingRpc(Option<AgentRpcCall>);
+
+impl ServerStreamingService<Bytes> for StreamingRpc {
+    type Response = Bytes;
+    type ResponseStream = BoxStream<'static, Result<Bytes, Status>>;
+    type Future = BoxFuture<'static, Result<Response<Self::ResponseStream>, Status>>;
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        server_streaming(self.0.take(), request).boxed()
+    }
+}
+
+async fn unary(
+    call: Option<AgentRpcCall>,
+    request: Request<Bytes>,
+) -> Result<Response<Bytes>, Status> {
+    let call = call.ok_or_else(|| Status::internal("Agent rpc invoked twice"))?;
+    let mut messages = call.invoke(request.into_inner()).await?;
+    Ok(Response::new(messages.pop().unwrap_or_default()))
+}
+
+async fn server_streaming(
+    call: Option<AgentRpcCall>,
+    request: Request<Bytes>,
+) -> Result<Response<BoxStream<'static, Result<Bytes, Status>>>, Status> {
+    let call = call.ok_or_else(|| Status::internal("Agent rpc invoked twice"))?;
+    let messages = call.invoke(request.into_inner()).await?;
+    Ok(Response::new(
+        stream::iter(messages.into_iter().map(Ok)).boxed(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::agent_rpc_path;
+    use test_r::test;
+
+    #[test]
+    fn only_agent_service_paths_are_routed() {
+        assert_eq!(
+            agent_rpc_path("/golem.agent.inventory.Inventory/ListSkus"),
+            Some(("golem.agent.inventory.Inventory", "ListSkus"))
+        );
+        assert_eq!(
+            agent_rpc_path("/grpc.reflection.v1.ServerReflection/ServerReflectionInfo"),
+            None
+        );
+        assert_eq!(agent_rpc_path("/grpc.health.v1.Health/Check"), None);
+        assert_eq!(agent_rpc_path("/golem.agent.inventory.Inventory/"), None);
+        assert_eq!(agent_rpc_path("/golem.agent.inventory.Inventory"), None);
+    }
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod agent;
 mod error;
 mod worker;
 
+pub use agent::start_agent_grpc_server;
+
 use crate::bootstrap::Services;
 use crate::config::GrpcApiConfig;
 use crate::grpcapi::worker::WorkerGrpcApi;
@@ -50,12 +50,15 @@ pub struct RunDetails {
     pub grpc_port: u16,
     pub custom_request_port: u16,
     pub mcp_port: u16,
+    /// Port of the agent gRPC API, when enabled
+    pub agent_grpc_port: Option<u16>,
 }
 
 pub struct TrafficReadyEndpoints {
     pub grpc_port: u16,
     pub custom_request_port: u16,
     pub mcp_port: u16,
+    pub agent_grpc_port: Option<u16>,
     pub api_endpoint: BoxEndpoint<'static>,
 }
 
@@ -101,23 +104,41 @@ impl WorkerService {
             Ok(())
         });
 
+        if let Some(agent_write_subscriber) = self.services.agent_write_subscriber.clone() {
+            join_set.spawn(async move {
+                agent_write_subscriber.run().await;
+                Ok(())
+            });
+        }
+
+        let bulk_invocation_service = self.services.bulk_invocation_service.clone();
+        join_set.spawn(async move {
+            bulk_invocation_service.run_job_resumption().await;
+            Ok(())
+        });
+
         let grpc_port = self.start_grpc_server(join_set).await?;
         let http_port = self.start_http_server(join_set, tracer.clone()).await?;
         let custom_request_port = self
             .start_api_gateway_server(join_set, tracer.clone())
             .await?;
         let mcp_port = self.start_mcp_server(join_set, tracer).await?;
+        let agent_grpc_port = self.start_agent_grpc_server(join_set).await?;
 
         info!(
             "Started worker service on ports: http: {}, grpc: {}, gateway: {}, mcp: {}",
             http_port, grpc_port, custom_request_port, mcp_port
         );
+        if let Some(agent_grpc_port) = agent_grpc_port {
+            info!("Started agent gRPC API on port: {agent_grpc_port}");
+        }
 
         Ok(RunDetails {
             http_port,
             grpc_port,
             custom_request_port,
             mcp_port,
+            agent_grpc_port,
         })
     }
 
@@ -143,11 +164,25 @@ impl WorkerService {
             Ok(())
         });
 
+        if let Some(agent_write_subscriber) = self.services.agent_write_subscriber.clone() {
+            join_set.spawn(async move {
+                agent_write_subscriber.run().await;
+                Ok(())
+            });
+        }
+
+        let bulk_invocation_service = self.services.bulk_invocation_service.clone();
+        join_set.spawn(async move {
+            bulk_invocation_service.run_job_resumption().await;
+            Ok(())
+        });
+
         let grpc_port = self.start_grpc_server(join_set).await?;
         let custom_request_port = self
             .start_api_gateway_server(join_set, tracer.clone())
             .await?;
         let mcp_port = self.start_mcp_server(join_set, tracer).await?;
+        let agent_grpc_port = self.start_agent_grpc_server(join_set).await?;
         let api_endpoint = api::make_open_api_service(&self.services).boxed();
 
         Ok(TrafficReadyEndpoints {
@@ -155,6 +190,7 @@ impl WorkerService {
             api_endpoint,
             mcp_port,
             custom_request_port,
+            agent_grpc_port,
         })
     }
 
@@ -167,6 +203,19 @@ impl WorkerService {
             .context("gRPC server failed")
     }
 
+    async fn start_agent_grpc_server(
+        &self,
+        join_set: &mut JoinSet<anyhow::Result<()>>,
+    ) -> Result<Option<u16>, anyhow::Error> {
+        if !self.config.agent_grpc.enabled {
+            return Ok(None);
+        }
+        grpcapi::start_agent_grpc_server(&self.config.agent_grpc, self.services.clone(), join_set)
+            .await
+            .map(Some)
+            .context("Agent gRPC server failed")
+    }
+
     async fn start_http_server(
         &self,
         join_set: &mut JoinSet<anyhow::Result<()>>,
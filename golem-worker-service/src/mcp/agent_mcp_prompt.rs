@@ -272,6 +272,7 @@ mod tests {
             output_schema: output,
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }
     }
 
@@ -22,6 +22,7 @@ use bytes::Bytes;
 use chrono::Utc;
 use futures::Stream;
 use golem_api_grpc::proto::golem::worker::{InvocationContext, LogEvent};
+use golem_api_grpc::proto::golem::workerexecutor;
 use golem_common::base_model::component_metadata::KnownExports;
 use golem_common::model::AgentInvocationOutput;
 use golem_common::model::account::{AccountEmail, AccountId};
@@ -35,8 +36,12 @@ use golem_common::model::component_metadata::ComponentMetadata;
 use golem_common::model::diff::Hash;
 use golem_common::model::environment::{EnvironmentId, EnvironmentName};
 use golem_common::model::oplog::{OplogCursor, OplogIndex};
-use golem_common::model::worker::{AgentConfigEntryDto, AgentMetadataDto, RevertWorkerTarget};
-use golem_common::model::{AgentFilter, AgentFingerprint, AgentId, IdempotencyKey, ScanCursor};
+use golem_common::model::worker::{
+    AgentConfigEntryDto, AgentLifecycleAuditEntry, AgentMetadataDto, RevertWorkerTarget,
+};
+use golem_common::model::{
+    AgentFilter, AgentFingerprint, AgentId, IdempotencyKey, ScanCursor, Timestamp,
+};
 use golem_service_base::clients::registry::{RegistryService, RegistryServiceError};
 use golem_service_base::model::auth::AuthCtx;
 use golem_service_base::model::component::Component;
@@ -112,6 +117,13 @@ impl RegistryService for NoopRegistryService {
         unimplemented!()
     }
 
+    async fn get_deployed_components(
+        &self,
+        _: EnvironmentId,
+    ) -> Result<Vec<Component>, RegistryServiceError> {
+        unimplemented!()
+    }
+
     async fn resolve_component(
         &self,
         _: AccountId,
@@ -153,6 +165,17 @@ impl RegistryService for NoopRegistryService {
         unimplemented!()
     }
 
+    async fn list_agent_types_by_names(
+        &self,
+        _: &golem_common::model::application::ApplicationName,
+        _: &golem_common::model::environment::EnvironmentName,
+        _: Option<golem_common::model::deployment::DeploymentRevision>,
+        _: Option<&str>,
+        _: &AuthCtx,
+    ) -> Result<Vec<golem_common::model::agent::ResolvedAgentType>, RegistryServiceError> {
+        unimplemented!()
+    }
+
     async fn get_active_routes_for_domain(
         &self,
         _: &golem_common::model::domain_registration::Domain,
@@ -262,6 +285,17 @@ impl ComponentService for StaticComponentService {
             Err(ComponentServiceError::ComponentNotFound)
         }
     }
+
+    async fn get_all_deployed(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<Component>, ComponentServiceError> {
+        if self.component.environment_id == environment_id {
+            Ok(vec![self.component.clone()])
+        } else {
+            Ok(Vec::new())
+        }
+    }
 }
 
 struct AllowAllAuthService;
@@ -320,6 +354,7 @@ impl WorkerClient for RecordingWorkerClient {
         _: EnvironmentId,
         _: AccountId,
         _: AuthCtx,
+        _: Option<workerexecutor::v1::WebSocketConnection>,
     ) -> WorkerResult<WorkerStream<LogEvent>> {
         unimplemented!()
     }
@@ -405,6 +440,8 @@ impl WorkerClient for RecordingWorkerClient {
         _: Option<OplogCursor>,
         _: u64,
         _: String,
+        _: Option<Timestamp>,
+        _: Option<Timestamp>,
         _: EnvironmentId,
         _: AuthCtx,
     ) -> Result<GetOplogResponse, crate::service::worker::WorkerServiceError> {
@@ -466,6 +503,25 @@ impl WorkerClient for RecordingWorkerClient {
         unimplemented!()
     }
 
+    async fn export_agent(
+        &self,
+        _: &AgentId,
+        _: EnvironmentId,
+        _: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>> {
+        unimplemented!()
+    }
+
+    async fn import_agent(
+        &self,
+        _: &AgentId,
+        _: Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>,
+        _: EnvironmentId,
+        _: AuthCtx,
+    ) -> WorkerResult<()> {
+        unimplemented!()
+    }
+
     async fn revert_worker(
         &self,
         _: &AgentId,
@@ -493,6 +549,7 @@ impl WorkerClient for RecordingWorkerClient {
         method_params: Option<golem_api_grpc::proto::golem::component::UntypedDataValue>,
         _: i32,
         _: Option<::prost_types::Timestamp>,
+        _: Option<workerexecutor::v1::TimeTravelTarget>,
         _: Option<IdempotencyKey>,
         _: Option<InvocationContext>,
         _: EnvironmentId,
@@ -520,6 +577,18 @@ impl WorkerClient for RecordingWorkerClient {
     ) -> WorkerResult<()> {
         unimplemented!()
     }
+
+    async fn get_lifecycle_audit(
+        &self,
+        _: ComponentId,
+        _: EnvironmentId,
+        _: Option<Timestamp>,
+        _: Option<Timestamp>,
+        _: u64,
+        _: AuthCtx,
+    ) -> WorkerResult<(Vec<AgentLifecycleAuditEntry>, Option<Timestamp>)> {
+        unimplemented!()
+    }
 }
 
 pub(crate) struct InvocationHarness {
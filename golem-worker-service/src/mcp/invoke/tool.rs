@@ -558,6 +558,7 @@ mod tests {
                 output_schema: OutputSchema::Unit,
                 http_endpoint: vec![],
                 read_only: None,
+                timeout_nanos: None,
             },
             component_id: harness.component_id,
             agent_type_name: AgentTypeName("mcp-agent".to_string()),
@@ -625,6 +626,7 @@ mod tests {
                 output_schema: OutputSchema::Unit,
                 http_endpoint: vec![],
                 read_only: None,
+                timeout_nanos: None,
             },
             component_id: harness.component_id,
             agent_type_name: AgentTypeName("mcp-agent".to_string()),
@@ -206,6 +206,7 @@ mod tests {
             output_schema: OutputSchema::Unit,
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }
     }
 
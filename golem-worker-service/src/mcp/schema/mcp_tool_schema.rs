@@ -180,6 +180,7 @@ mod tests {
             output_schema: output,
             http_endpoint: vec![],
             read_only: None,
+            timeout_nanos: None,
         }
     }
 
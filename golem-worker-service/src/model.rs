@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use golem_common::model::ScanCursor;
-use golem_common::model::worker::AgentMetadataDto;
+use golem_common::model::oplog::AgentOplogSearchMatch;
+use golem_common::model::worker::{AgentLifecycleAuditEntry, AgentMetadataDto};
+use golem_common::model::{ScanCursor, Timestamp};
 use poem_openapi::Object;
 use std::fmt::Debug;
 
@@ -22,3 +23,15 @@ pub struct WorkersMetadataResponse {
     pub workers: Vec<AgentMetadataDto>,
     pub cursor: Option<ScanCursor>,
 }
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, Object)]
+pub struct SearchOplogsResponse {
+    pub matches: Vec<AgentOplogSearchMatch>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, Object)]
+pub struct LifecycleAuditResponse {
+    pub entries: Vec<AgentLifecycleAuditEntry>,
+    pub next_from: Option<Timestamp>,
+}
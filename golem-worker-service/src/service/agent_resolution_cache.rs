@@ -407,6 +407,13 @@ mod tests {
         {
             unimplemented!()
         }
+        async fn get_deployed_components(
+            &self,
+            _: EnvironmentId,
+        ) -> Result<Vec<golem_service_base::model::component::Component>, RegistryServiceError>
+        {
+            unimplemented!()
+        }
         async fn resolve_component(
             &self,
             _: AccountId,
@@ -449,6 +456,18 @@ mod tests {
                 DeploymentRevision::new(rev).unwrap_or(DeploymentRevision::INITIAL),
             ))
         }
+
+        async fn list_agent_types_by_names(
+            &self,
+            _app_name: &ApplicationName,
+            _environment_name: &EnvironmentName,
+            _deployment_revision: Option<DeploymentRevision>,
+            _owner_account_email: Option<&str>,
+            _auth_ctx: &AuthCtx,
+        ) -> Result<Vec<ResolvedAgentType>, RegistryServiceError> {
+            unimplemented!()
+        }
+
         async fn get_active_routes_for_domain(
             &self,
             _: &golem_common::base_model::domain_registration::Domain,
@@ -0,0 +1,179 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::custom_api::call_agent::response_cache::ResponseCache;
+use futures::{StreamExt, stream};
+use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_client::WorkerExecutorClient;
+use golem_api_grpc::proto::golem::workerexecutor::v1::{
+    AgentWrite, SubscribeAgentWritesRequest, SubscribeAgentWritesResponse,
+    subscribe_agent_writes_response,
+};
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::{AgentId, Pod};
+use golem_service_base::grpc::client::MultiTargetGrpcClient;
+use golem_service_base::service::routing_table::RoutingTableService;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::Status;
+use tonic::transport::Channel;
+use tonic_tracing_opentelemetry::middleware::client::OtelGrpcService;
+use tracing::{debug, info, warn};
+
+/// Delay before subscribing again after a subscription to the executors failed or ended
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// How often the routing table is checked for added or removed executors
+const EXECUTORS_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Keeps the gateway's response cache subscribed to the agent writes of every executor, so
+/// cached `UntilWrite` responses are dropped when their agent is written instead of being
+/// revalidated on every request.
+pub struct AgentWriteSubscriber {
+    routing_table_service: Arc<RoutingTableService>,
+    worker_executor_clients: MultiTargetGrpcClient<WorkerExecutorClient<OtelGrpcService<Channel>>>,
+    response_cache: Arc<ResponseCache>,
+}
+
+impl AgentWriteSubscriber {
+    pub fn new(
+        routing_table_service: Arc<RoutingTableService>,
+        worker_executor_clients: MultiTargetGrpcClient<
+            WorkerExecutorClient<OtelGrpcService<Channel>>,
+        >,
+        response_cache: Arc<ResponseCache>,
+    ) -> Self {
+        Self {
+            routing_table_service,
+            worker_executor_clients,
+            response_cache,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            if let Err(err) = self.subscribe_to_all_executors().await {
+                warn!("Agent write subscription failed: {err}");
+            }
+            self.response_cache.set_subscribed_to_writes(false).await;
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    /// Subscribes to the agent writes of every executor and applies them to the response
+    /// cache. Returns when any of the subscriptions ends or the set of executors changes.
+    async fn subscribe_to_all_executors(&self) -> Result<(), String> {
+        let executors = self.executors().await?;
+        if executors.is_empty() {
+            return Ok(());
+        }
+
+        let mut subscriptions = Vec::with_capacity(executors.len());
+        for pod in &executors {
+            let subscription = self
+                .worker_executor_clients
+                .call(
+                    "subscribe_agent_writes",
+                    pod.uri(self.worker_executor_clients.uses_tls()),
+                    |client| {
+                        Box::pin(async move {
+                            Ok(client
+                                .subscribe_agent_writes(SubscribeAgentWritesRequest {})
+                                .await?
+                                .into_inner())
+                        })
+                    },
+                )
+                .await
+                .map_err(|status| format!("failed to subscribe to {pod:?}: {status}"))?;
+
+            // The executor's writes are only delivered as long as its stream is open, so its
+            // end has to end the whole subscription
+            let pod = *pod;
+            subscriptions.push(subscription.chain(stream::once(async move {
+                Err(Status::unavailable(format!(
+                    "agent write stream of {pod:?} ended"
+                )))
+            })));
+        }
+
+        self.response_cache.set_subscribed_to_writes(true).await;
+        info!(
+            executors = executors.len(),
+            "Subscribed to the agent writes of the executors"
+        );
+
+        let mut events = stream::select_all(subscriptions);
+        let mut executors_check = tokio::time::interval(EXECUTORS_CHECK_INTERVAL);
+        executors_check.tick().await;
+
+        loop {
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(Ok(response)) => self.on_response(response).await,
+                    Some(Err(status)) => return Err(status.to_string()),
+                    None => return Ok(()),
+                },
+                _ = executors_check.tick() => {
+                    if self.executors().await? != executors {
+                        debug!("Executors changed, subscribing to agent writes again");
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    async fn executors(&self) -> Result<BTreeSet<Pod>, String> {
+        let routing_table = self
+            .routing_table_service
+            .get_routing_table()
+            .await
+            .map_err(|err| format!("failed to get the routing table: {err}"))?;
+        Ok(routing_table.all().into_iter().copied().collect())
+    }
+
+    async fn on_response(&self, response: SubscribeAgentWritesResponse) {
+        match response.event {
+            Some(subscribe_agent_writes_response::Event::Writes(writes)) => {
+                for write in writes.writes {
+                    self.on_agent_write(write).await;
+                }
+            }
+            Some(subscribe_agent_writes_response::Event::Lagged(_)) => {
+                warn!("Agent write subscription lagged behind, dropping all cached responses");
+                self.response_cache.clear().await;
+            }
+            None => {}
+        }
+    }
+
+    async fn on_agent_write(&self, write: AgentWrite) {
+        let Some(agent_id) = write
+            .agent_id
+            .and_then(|agent_id| AgentId::try_from(agent_id).ok())
+        else {
+            warn!("Received agent write without a valid agent id");
+            return;
+        };
+
+        if write.deleted {
+            self.response_cache.agent_deleted(&agent_id).await;
+        } else {
+            self.response_cache
+                .agent_written(&agent_id, OplogIndex::from_u64(write.oplog_index))
+                .await;
+        }
+    }
+}
@@ -0,0 +1,1121 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod store;
+
+pub use store::*;
+
+use crate::api::agents::{AgentInvocationMode, BulkInvocationRequest};
+use crate::config::BulkInvocationConfig;
+use crate::service::agent_resolution_cache::AgentResolutionCache;
+use crate::service::auth::AuthServiceError;
+use crate::service::component::ComponentService;
+use crate::service::worker::{WorkerService, WorkerServiceError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use futures::{StreamExt, stream};
+use golem_common::SafeDisplay;
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::{
+    AgentTypeName, DataValue, GolemUserPrincipal, Principal, UntypedDataValue, UntypedJsonDataValue,
+};
+use golem_common::model::card::owner::AgentOwnerPattern;
+use golem_common::model::card::{
+    AgentMethodName, AgentResourcePattern, AgentVerb, ClassPermissionTarget, PermissionTarget,
+};
+use golem_common::model::component::{ComponentId, ComponentName, ComponentRevision};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::{
+    AgentFilter, AgentId, IdempotencyKey, ScanCursor, StringFilterComparator,
+};
+use golem_service_base::model::auth::AuthCtx;
+use poem_openapi_derive::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, error, info, warn};
+use uuid::Uuid;
+
+/// Number of individual invocation failures kept in the job record
+const MAX_RECORDED_FAILURES: usize = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BulkInvocationError {
+    #[error("Bulk invocation job not found: {0}")]
+    JobNotFound(Uuid),
+    #[error(transparent)]
+    WorkerService(#[from] WorkerServiceError),
+    #[error(transparent)]
+    Store(#[from] BulkInvocationJobStoreError),
+}
+
+impl SafeDisplay for BulkInvocationError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            Self::JobNotFound(_) => self.to_string(),
+            Self::WorkerService(inner) => inner.to_safe_string(),
+            Self::Store(_) => "Internal error".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub enum BulkInvocationJobStatus {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInvocationFailure {
+    pub agent_id: AgentId,
+    pub error: String,
+}
+
+/// Durable record of invoking a method on every agent of an agent type matching a filter
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInvocationJob {
+    pub id: Uuid,
+    pub environment_id: EnvironmentId,
+    pub component_id: ComponentId,
+    /// The component revision the method parameters were type checked against
+    pub component_revision: ComponentRevision,
+    pub agent_type_name: AgentTypeName,
+    pub filter: Option<AgentFilter>,
+    pub method_name: String,
+    pub method_parameters: UntypedJsonDataValue,
+    pub mode: AgentInvocationMode,
+    pub concurrency: u32,
+    /// The account the invocations are made on behalf of
+    pub account_id: AccountId,
+    pub status: BulkInvocationJobStatus,
+    /// Position of the next page of agents to invoke; empty once all matching agents were visited
+    pub cursor: Option<ScanCursor>,
+    pub succeeded: u64,
+    pub failed: u64,
+    /// The first failed invocations, up to 100
+    pub failures: Vec<BulkInvocationFailure>,
+    /// Error that stopped the whole job
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BulkInvocationJob {
+    fn record_failure(&mut self, agent_id: AgentId, error: String) {
+        self.failed += 1;
+        if self.failures.len() < MAX_RECORDED_FAILURES {
+            self.failures
+                .push(BulkInvocationFailure { agent_id, error });
+        }
+    }
+
+    fn fail(&mut self, error: String) {
+        self.status = BulkInvocationJobStatus::Failed;
+        self.error = Some(error);
+        self.updated_at = Utc::now();
+    }
+}
+
+/// Runs bulk invocation jobs in the background of the worker service.
+///
+/// Every job is persisted in the [`BulkInvocationJobStore`] after each page of agents. A running
+/// job is leased to the worker service instance running it, and the lease is renewed while the
+/// job makes progress. [`BulkInvocationService::resume_running_jobs`] periodically claims the
+/// running jobs whose lease expired, so the jobs of a stopped instance are resumed by exactly one
+/// of the remaining ones. Each agent is invoked with an idempotency key derived from the job id,
+/// so the agents of a page that was in progress when a job was taken over are not invoked twice.
+pub struct BulkInvocationService {
+    agent_resolution_cache: Arc<AgentResolutionCache>,
+    config: BulkInvocationConfig,
+    jobs: BulkInvocationJobs,
+}
+
+impl BulkInvocationService {
+    pub fn new(
+        worker_service: Arc<WorkerService>,
+        component_service: Arc<dyn ComponentService>,
+        agent_resolution_cache: Arc<AgentResolutionCache>,
+        store: Arc<dyn BulkInvocationJobStore>,
+        config: BulkInvocationConfig,
+    ) -> Self {
+        let invoker = Arc::new(WorkerServiceAgentInvoker {
+            worker_service,
+            component_service,
+        });
+        Self {
+            agent_resolution_cache,
+            jobs: BulkInvocationJobs::new(invoker, store, &config),
+            config,
+        }
+    }
+
+    pub async fn start(
+        &self,
+        request: BulkInvocationRequest,
+        auth: AuthCtx,
+    ) -> Result<BulkInvocationJob, BulkInvocationError> {
+        let resolved = self
+            .agent_resolution_cache
+            .resolve(
+                &request.app_name,
+                &request.env_name,
+                &request.agent_type_name,
+                None,
+                &auth,
+            )
+            .await
+            .map_err(WorkerServiceError::from)?;
+
+        let registered_agent_type = resolved.registered_agent_type;
+        let implemented_by = registered_agent_type.implemented_by;
+
+        let method = registered_agent_type
+            .agent_type
+            .methods
+            .iter()
+            .find(|m| m.name == request.method_name)
+            .ok_or_else(|| {
+                WorkerServiceError::TypeChecker(format!(
+                    "Agent method {} not found in agent type {}",
+                    request.method_name, request.agent_type_name
+                ))
+            })?;
+
+        DataValue::try_from_untyped_json(
+            request.method_parameters.clone(),
+            method.input_schema.clone(),
+        )
+        .map_err(|err| {
+            WorkerServiceError::TypeChecker(format!("Agent method parameters type error: {err}"))
+        })?;
+
+        // Listing the agents and invoking them happens in the background with system
+        // privileges, so both permissions are checked upfront
+        for (verb, resource) in [
+            (AgentVerb::View, AgentResourcePattern::Any),
+            (
+                AgentVerb::Invoke,
+                AgentResourcePattern::Method(AgentMethodName(request.method_name.clone())),
+            ),
+        ] {
+            auth.authorize_permission(&PermissionTarget::Agent(ClassPermissionTarget {
+                owner: AgentOwnerPattern::ComponentAgents {
+                    account: implemented_by.account_email.clone(),
+                    application: request.app_name.clone(),
+                    environment: request.env_name.clone(),
+                    component: ComponentName(implemented_by.component_name.clone()),
+                },
+                verb: Some(verb),
+                resource,
+            }))
+            .map_err(|err| WorkerServiceError::from(AuthServiceError::from(err)))?;
+        }
+
+        let now = Utc::now();
+        let job = BulkInvocationJob {
+            id: Uuid::new_v4(),
+            environment_id: resolved.environment_id,
+            component_id: implemented_by.component_id,
+            component_revision: implemented_by.component_revision,
+            agent_type_name: request.agent_type_name,
+            filter: request.filter,
+            method_name: request.method_name,
+            method_parameters: request.method_parameters,
+            mode: request.mode,
+            concurrency: request
+                .concurrency
+                .unwrap_or(self.config.default_concurrency)
+                .clamp(1, self.config.max_concurrency.max(1)),
+            account_id: auth.account_id(),
+            status: BulkInvocationJobStatus::Running,
+            cursor: Some(ScanCursor::default()),
+            succeeded: 0,
+            failed: 0,
+            failures: Vec::new(),
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.jobs.launch(job.clone()).await?;
+
+        Ok(job)
+    }
+
+    pub async fn get(
+        &self,
+        job_id: Uuid,
+        auth: AuthCtx,
+    ) -> Result<BulkInvocationJob, BulkInvocationError> {
+        self.get_visible(job_id, &auth).await
+    }
+
+    /// Cancels a running job. Invocations already in progress are not interrupted, the job
+    /// stops before starting the next page of agents.
+    pub async fn cancel(
+        &self,
+        job_id: Uuid,
+        auth: AuthCtx,
+    ) -> Result<BulkInvocationJob, BulkInvocationError> {
+        self.get_visible(job_id, &auth).await?;
+        self.jobs.cancel(job_id).await
+    }
+
+    /// Resumes the running jobs not leased by any live worker service instance, including the
+    /// ones that were running on this instance before it was restarted
+    pub async fn resume_running_jobs(&self) -> Result<(), BulkInvocationError> {
+        self.jobs.resume_running_jobs().await
+    }
+
+    /// Keeps resuming the jobs of stopped worker service instances
+    pub async fn run_job_resumption(&self) {
+        let mut interval = tokio::time::interval(self.config.resume_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.resume_running_jobs().await {
+                warn!("Failed to resume bulk invocation jobs: {err}");
+            }
+        }
+    }
+
+    async fn get_visible(
+        &self,
+        job_id: Uuid,
+        auth: &AuthCtx,
+    ) -> Result<BulkInvocationJob, BulkInvocationError> {
+        match self.jobs.store().get(&job_id).await? {
+            // Jobs of other accounts are reported as missing to not leak their existence
+            Some(job) if auth.is_system() || job.account_id == auth.account_id() => Ok(job),
+            _ => Err(BulkInvocationError::JobNotFound(job_id)),
+        }
+    }
+}
+
+/// The jobs running on this worker service instance, identified by `instance_id` in the leases
+struct BulkInvocationJobs {
+    runner: JobRunner,
+    instance_id: Uuid,
+    running_jobs: Arc<DashMap<Uuid, CancellationToken>>,
+}
+
+impl BulkInvocationJobs {
+    fn new(
+        invoker: Arc<dyn AgentInvoker>,
+        store: Arc<dyn BulkInvocationJobStore>,
+        config: &BulkInvocationConfig,
+    ) -> Self {
+        let instance_id = Uuid::new_v4();
+        Self {
+            runner: JobRunner {
+                invoker,
+                store,
+                page_size: config.page_size,
+                instance_id,
+                lease_ttl: config.lease_ttl,
+            },
+            instance_id,
+            running_jobs: Arc::new(DashMap::new()),
+        }
+    }
+
+    fn store(&self) -> &Arc<dyn BulkInvocationJobStore> {
+        &self.runner.store
+    }
+
+    async fn launch(&self, job: BulkInvocationJob) -> Result<(), BulkInvocationError> {
+        self.store().create(&job, &self.runner.lease()).await?;
+        self.spawn(job);
+        Ok(())
+    }
+
+    async fn cancel(&self, job_id: Uuid) -> Result<BulkInvocationJob, BulkInvocationError> {
+        let job = self
+            .store()
+            .cancel(&job_id, Utc::now())
+            .await?
+            .ok_or(BulkInvocationError::JobNotFound(job_id))?;
+
+        // If the job runs on another instance, that instance notices the cancellation when
+        // it fails to store its progress or to renew the lease
+        if let Some(cancellation_token) = self.running_jobs.get(&job_id) {
+            cancellation_token.cancel();
+        }
+
+        Ok(job)
+    }
+
+    async fn resume_running_jobs(&self) -> Result<(), BulkInvocationError> {
+        for job in self.store().get_running().await? {
+            if self.running_jobs.contains_key(&job.id) {
+                continue;
+            }
+            if let Some(job) = self
+                .store()
+                .try_claim(&job.id, Utc::now(), &self.runner.lease())
+                .await?
+            {
+                info!(
+                    "Resuming bulk invocation job {} on instance {}",
+                    job.id, self.instance_id
+                );
+                self.spawn(job);
+            }
+        }
+        Ok(())
+    }
+
+    fn spawn(&self, job: BulkInvocationJob) {
+        let job_id = job.id;
+        let cancellation_token = CancellationToken::new();
+        self.running_jobs.insert(job_id, cancellation_token.clone());
+
+        let runner = self.runner.clone();
+        let running_jobs = self.running_jobs.clone();
+        tokio::spawn(
+            async move {
+                runner.run(job, cancellation_token).await;
+                running_jobs.remove(&job_id);
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+/// The operations a job performs on the agents, separated from [`WorkerService`] so the job
+/// lifecycle does not depend on the executors
+#[async_trait]
+trait AgentInvoker: Send + Sync {
+    /// Converts the job's method parameters using the schema of the component revision the
+    /// job was created with.
+    async fn method_parameters(
+        &self,
+        job: &BulkInvocationJob,
+    ) -> Result<golem_api_grpc::proto::golem::component::UntypedDataValue, WorkerServiceError>;
+
+    async fn find_agents(
+        &self,
+        job: &BulkInvocationJob,
+        filter: AgentFilter,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(Option<ScanCursor>, Vec<AgentId>), WorkerServiceError>;
+
+    async fn invoke(
+        &self,
+        job: &BulkInvocationJob,
+        agent_id: &AgentId,
+        method_parameters: &golem_api_grpc::proto::golem::component::UntypedDataValue,
+        principal: &golem_api_grpc::proto::golem::component::Principal,
+    ) -> Result<(), WorkerServiceError>;
+}
+
+struct WorkerServiceAgentInvoker {
+    worker_service: Arc<WorkerService>,
+    component_service: Arc<dyn ComponentService>,
+}
+
+#[async_trait]
+impl AgentInvoker for WorkerServiceAgentInvoker {
+    async fn method_parameters(
+        &self,
+        job: &BulkInvocationJob,
+    ) -> Result<golem_api_grpc::proto::golem::component::UntypedDataValue, WorkerServiceError> {
+        let component = self
+            .component_service
+            .get_revision(job.component_id, job.component_revision)
+            .await?;
+        let agent_type = component
+            .metadata
+            .find_agent_type_by_name(&job.agent_type_name)
+            .ok_or_else(|| {
+                WorkerServiceError::Internal(format!(
+                    "Agent type {} not found in component metadata at revision {}",
+                    job.agent_type_name, job.component_revision
+                ))
+            })?;
+        let method = agent_type
+            .methods
+            .iter()
+            .find(|m| m.name == job.method_name)
+            .ok_or_else(|| {
+                WorkerServiceError::Internal(format!(
+                    "Agent method {} not found in agent type {}",
+                    job.method_name, job.agent_type_name
+                ))
+            })?;
+        let method_parameters = DataValue::try_from_untyped_json(
+            job.method_parameters.clone(),
+            method.input_schema.clone(),
+        )
+        .map_err(|err| {
+            WorkerServiceError::TypeChecker(format!("Agent method parameters type error: {err}"))
+        })?;
+
+        Ok(UntypedDataValue::from(method_parameters).into())
+    }
+
+    async fn find_agents(
+        &self,
+        job: &BulkInvocationJob,
+        filter: AgentFilter,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(Option<ScanCursor>, Vec<AgentId>), WorkerServiceError> {
+        let (next_cursor, agents) = self
+            .worker_service
+            .find_metadata(
+                job.component_id,
+                Some(filter),
+                cursor,
+                count,
+                false,
+                AuthCtx::System,
+            )
+            .await?;
+
+        Ok((
+            next_cursor,
+            agents.into_iter().map(|agent| agent.agent_id).collect(),
+        ))
+    }
+
+    async fn invoke(
+        &self,
+        job: &BulkInvocationJob,
+        agent_id: &AgentId,
+        method_parameters: &golem_api_grpc::proto::golem::component::UntypedDataValue,
+        principal: &golem_api_grpc::proto::golem::component::Principal,
+    ) -> Result<(), WorkerServiceError> {
+        let mode = match job.mode {
+            AgentInvocationMode::Await => {
+                golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await
+            }
+            AgentInvocationMode::Schedule => {
+                golem_api_grpc::proto::golem::worker::AgentInvocationMode::Schedule
+            }
+        };
+
+        self.worker_service
+            .invoke_agent(
+                agent_id,
+                Some(job.method_name.clone()),
+                Some(method_parameters.clone()),
+                mode as i32,
+                None,
+                Some(invocation_idempotency_key(job, agent_id)),
+                None,
+                AuthCtx::System,
+                principal.clone(),
+                Some(job.environment_id),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn invocation_idempotency_key(job: &BulkInvocationJob, agent_id: &AgentId) -> IdempotencyKey {
+    IdempotencyKey::derived_for_target(&IdempotencyKey::from_uuid(job.id), agent_id)
+}
+
+#[derive(Clone)]
+struct JobRunner {
+    invoker: Arc<dyn AgentInvoker>,
+    store: Arc<dyn BulkInvocationJobStore>,
+    page_size: u64,
+    instance_id: Uuid,
+    lease_ttl: Duration,
+}
+
+impl JobRunner {
+    fn lease(&self) -> BulkInvocationJobLease {
+        BulkInvocationJobLease {
+            owner: self.instance_id,
+            until: Utc::now() + self.lease_ttl,
+        }
+    }
+
+    /// Runs the job until it finishes, is cancelled, or its lease is lost
+    async fn run(&self, job: BulkInvocationJob, cancellation_token: CancellationToken) {
+        let heartbeat = tokio::spawn(
+            self.clone()
+                .heartbeat(job.id, cancellation_token.clone())
+                .in_current_span(),
+        );
+        self.run_pages(job, cancellation_token).await;
+        heartbeat.abort();
+    }
+
+    /// Renews the lease of the job while it is running, and stops the job once the lease
+    /// cannot be renewed anymore because another instance took it over or the job was cancelled
+    async fn heartbeat(self, job_id: Uuid, cancellation_token: CancellationToken) {
+        let mut interval = tokio::time::interval(self.lease_ttl / 3);
+        interval.tick().await;
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            match self.store.renew(&job_id, &self.lease()).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    info!("Bulk invocation job {job_id} is no longer leased to this instance");
+                    cancellation_token.cancel();
+                    break;
+                }
+                Err(err) => {
+                    warn!("Failed to renew the lease of bulk invocation job {job_id}: {err}");
+                }
+            }
+        }
+    }
+
+    async fn run_pages(&self, mut job: BulkInvocationJob, cancellation_token: CancellationToken) {
+        let method_parameters = match self.invoker.method_parameters(&job).await {
+            Ok(method_parameters) => method_parameters,
+            Err(err) => {
+                job.fail(err.to_safe_string());
+                self.update(&job).await;
+                return;
+            }
+        };
+
+        let principal: golem_api_grpc::proto::golem::component::Principal =
+            Principal::GolemUser(GolemUserPrincipal {
+                account_id: job.account_id,
+            })
+            .into();
+        let filter = agent_type_filter(&job.agent_type_name, job.filter.clone());
+
+        while let Some(cursor) = job.cursor.clone() {
+            if cancellation_token.is_cancelled() {
+                // The cancellation was already stored by whoever cancelled the job
+                return;
+            }
+
+            let (next_cursor, agents) = match self
+                .invoker
+                .find_agents(&job, filter.clone(), cursor, self.page_size)
+                .await
+            {
+                Ok(page) => page,
+                Err(err) => {
+                    job.fail(err.to_safe_string());
+                    break;
+                }
+            };
+
+            let results: Vec<_> = stream::iter(agents)
+                .map(|agent_id| {
+                    let job = &job;
+                    let method_parameters = &method_parameters;
+                    let principal = &principal;
+                    async move {
+                        let result = self
+                            .invoker
+                            .invoke(job, &agent_id, method_parameters, principal)
+                            .await;
+                        (agent_id, result)
+                    }
+                })
+                .buffer_unordered(job.concurrency as usize)
+                .collect()
+                .await;
+
+            for (agent_id, result) in results {
+                match result {
+                    Ok(()) => job.succeeded += 1,
+                    Err(err) => job.record_failure(agent_id, err.to_safe_string()),
+                }
+            }
+
+            job.cursor = next_cursor;
+            job.updated_at = Utc::now();
+            if job.cursor.is_none() {
+                job.status = BulkInvocationJobStatus::Completed;
+                break;
+            }
+            if !self.update(&job).await {
+                return;
+            }
+        }
+
+        job.updated_at = Utc::now();
+        if self.update(&job).await {
+            info!(
+                "Bulk invocation job {} finished with status {:?} ({} succeeded, {} failed)",
+                job.id, job.status, job.succeeded, job.failed
+            );
+        }
+    }
+
+    /// Stores the progress of the job, returning `false` if the job must stop because it was
+    /// cancelled or taken over by another instance
+    async fn update(&self, job: &BulkInvocationJob) -> bool {
+        match self.store.update(job, &self.lease()).await {
+            Ok(true) => true,
+            Ok(false) => {
+                info!(
+                    "Bulk invocation job {} was cancelled or is no longer leased to this instance",
+                    job.id
+                );
+                false
+            }
+            Err(err) => {
+                // The progress is lost, but the job can continue; the next page's update or
+                // the instance resuming it after the lease expires catches up
+                error!("Failed to save bulk invocation job {}: {err}", job.id);
+                true
+            }
+        }
+    }
+}
+
+/// Restricts the filter to the agents of the given agent type, as a component can implement
+/// multiple agent types.
+fn agent_type_filter(agent_type_name: &AgentTypeName, filter: Option<AgentFilter>) -> AgentFilter {
+    let agent_type_filter = AgentFilter::new_name(
+        StringFilterComparator::StartsWith,
+        format!("{agent_type_name}("),
+    );
+    match filter {
+        Some(filter) => AgentFilter::new_and(vec![agent_type_filter, filter]),
+        None => agent_type_filter,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AgentInvoker, BulkInvocationJob, BulkInvocationJobLease, BulkInvocationJobStatus,
+        BulkInvocationJobStore, BulkInvocationJobs, SqliteBulkInvocationJobStore,
+        invocation_idempotency_key,
+    };
+    use crate::api::agents::AgentInvocationMode;
+    use crate::config::BulkInvocationConfig;
+    use crate::service::worker::WorkerServiceError;
+    use async_trait::async_trait;
+    use chrono::{TimeDelta, Utc};
+    use golem_common::config::DbSqliteConfig;
+    use golem_common::model::account::AccountId;
+    use golem_common::model::agent::{
+        AgentTypeName, UntypedJsonDataValue, UntypedJsonElementValues,
+    };
+    use golem_common::model::component::{ComponentId, ComponentRevision};
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::{AgentFilter, AgentId, IdempotencyKey, ScanCursor};
+    use golem_service_base::db::sqlite::SqlitePool;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tempfile::NamedTempFile;
+    use test_r::test;
+    use tokio::sync::Notify;
+    use uuid::Uuid;
+
+    struct AgentInvokerMock {
+        pages: Vec<Vec<AgentId>>,
+        failing: HashSet<AgentId>,
+        invoked: Mutex<Vec<(AgentId, IdempotencyKey)>>,
+        /// Page at which enumerating the agents waits for `release`, after notifying `reached`
+        blocked_page: Option<u64>,
+        reached: Notify,
+        release: Notify,
+    }
+
+    impl AgentInvokerMock {
+        fn new(pages: Vec<Vec<AgentId>>) -> Self {
+            Self {
+                pages,
+                failing: HashSet::new(),
+                invoked: Mutex::new(Vec::new()),
+                blocked_page: None,
+                reached: Notify::new(),
+                release: Notify::new(),
+            }
+        }
+
+        fn invoked_agents(&self) -> Vec<AgentId> {
+            self.invoked
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(agent_id, _)| agent_id.clone())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl AgentInvoker for AgentInvokerMock {
+        async fn method_parameters(
+            &self,
+            _job: &BulkInvocationJob,
+        ) -> Result<golem_api_grpc::proto::golem::component::UntypedDataValue, WorkerServiceError>
+        {
+            Ok(Default::default())
+        }
+
+        async fn find_agents(
+            &self,
+            _job: &BulkInvocationJob,
+            _filter: AgentFilter,
+            cursor: ScanCursor,
+            _count: u64,
+        ) -> Result<(Option<ScanCursor>, Vec<AgentId>), WorkerServiceError> {
+            if self.blocked_page == Some(cursor.cursor) {
+                self.reached.notify_one();
+                self.release.notified().await;
+            }
+            let page = cursor.cursor as usize;
+            let next_cursor = (page + 1 < self.pages.len()).then(|| ScanCursor {
+                cursor: cursor.cursor + 1,
+                layer: 0,
+            });
+            Ok((next_cursor, self.pages[page].clone()))
+        }
+
+        async fn invoke(
+            &self,
+            job: &BulkInvocationJob,
+            agent_id: &AgentId,
+            _method_parameters: &golem_api_grpc::proto::golem::component::UntypedDataValue,
+            _principal: &golem_api_grpc::proto::golem::component::Principal,
+        ) -> Result<(), WorkerServiceError> {
+            self.invoked
+                .lock()
+                .unwrap()
+                .push((agent_id.clone(), invocation_idempotency_key(job, agent_id)));
+            if self.failing.contains(agent_id) {
+                Err(WorkerServiceError::Internal(
+                    "invocation failed".to_string(),
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    async fn sqlite_store() -> (NamedTempFile, Arc<dyn BulkInvocationJobStore>) {
+        let db_file = NamedTempFile::new().unwrap();
+        let db_config = DbSqliteConfig {
+            database: db_file.path().to_string_lossy().to_string(),
+            max_connections: 10,
+            foreign_keys: false,
+        };
+        SqliteBulkInvocationJobStore::migrate(&db_config)
+            .await
+            .unwrap();
+        let pool = SqlitePool::configured(&db_config).await.unwrap();
+        (db_file, Arc::new(SqliteBulkInvocationJobStore::new(pool)))
+    }
+
+    fn agent_pages(component_id: ComponentId, pages: usize, page_size: usize) -> Vec<Vec<AgentId>> {
+        (0..pages)
+            .map(|page| {
+                (0..page_size)
+                    .map(|i| AgentId {
+                        component_id,
+                        agent_id: format!("counter(\"{page}-{i}\")"),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn job(component_id: ComponentId) -> BulkInvocationJob {
+        let now = Utc::now();
+        BulkInvocationJob {
+            id: Uuid::new_v4(),
+            environment_id: EnvironmentId::new(),
+            component_id,
+            component_revision: ComponentRevision::INITIAL,
+            agent_type_name: AgentTypeName("counter".to_string()),
+            filter: None,
+            method_name: "increment".to_string(),
+            method_parameters: UntypedJsonDataValue::Tuple(UntypedJsonElementValues {
+                elements: vec![],
+            }),
+            mode: AgentInvocationMode::Await,
+            concurrency: 2,
+            account_id: AccountId::new(),
+            status: BulkInvocationJobStatus::Running,
+            cursor: Some(ScanCursor::default()),
+            succeeded: 0,
+            failed: 0,
+            failures: Vec::new(),
+            error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn jobs(
+        invoker: &Arc<AgentInvokerMock>,
+        store: &Arc<dyn BulkInvocationJobStore>,
+    ) -> BulkInvocationJobs {
+        BulkInvocationJobs::new(
+            invoker.clone(),
+            store.clone(),
+            &BulkInvocationConfig::default(),
+        )
+    }
+
+    fn lease(owner: Uuid, until_from_now: TimeDelta) -> BulkInvocationJobLease {
+        BulkInvocationJobLease {
+            owner,
+            until: Utc::now() + until_from_now,
+        }
+    }
+
+    async fn wait_until_finished(jobs: &BulkInvocationJobs, job_id: Uuid) {
+        for _ in 0..500 {
+            if !jobs.running_jobs.contains_key(&job_id) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("Bulk invocation job {job_id} did not finish");
+    }
+
+    #[test]
+    async fn job_invokes_every_page_and_completes() {
+        let (_db_file, store) = sqlite_store().await;
+        let component_id = ComponentId::new();
+        let pages = agent_pages(component_id, 3, 2);
+        let mut invoker = AgentInvokerMock::new(pages.clone());
+        invoker.failing.insert(pages[1][0].clone());
+        let invoker = Arc::new(invoker);
+        let jobs = jobs(&invoker, &store);
+
+        let job = job(component_id);
+        jobs.launch(job.clone()).await.unwrap();
+        wait_until_finished(&jobs, job.id).await;
+
+        let stored = store.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Completed);
+        assert_eq!(stored.succeeded, 5);
+        assert_eq!(stored.failed, 1);
+        assert_eq!(stored.failures.len(), 1);
+        assert_eq!(stored.failures[0].agent_id, pages[1][0]);
+        assert!(stored.cursor.is_none());
+
+        let invoked = invoker.invoked.lock().unwrap().clone();
+        assert_eq!(invoked.len(), 6);
+        for (agent_id, idempotency_key) in &invoked {
+            assert_eq!(*idempotency_key, invocation_idempotency_key(&job, agent_id));
+        }
+
+        // A finished job releases its lease and cannot be claimed anymore
+        let claimed = store
+            .try_claim(
+                &job.id,
+                Utc::now(),
+                &lease(Uuid::new_v4(), TimeDelta::minutes(1)),
+            )
+            .await
+            .unwrap();
+        assert!(claimed.is_none());
+    }
+
+    #[test]
+    async fn job_progress_is_stored_after_each_page() {
+        let (_db_file, store) = sqlite_store().await;
+        let component_id = ComponentId::new();
+        let mut invoker = AgentInvokerMock::new(agent_pages(component_id, 3, 2));
+        invoker.blocked_page = Some(1);
+        let invoker = Arc::new(invoker);
+        let jobs = jobs(&invoker, &store);
+
+        let job = job(component_id);
+        jobs.launch(job.clone()).await.unwrap();
+        invoker.reached.notified().await;
+
+        let stored = store.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Running);
+        assert_eq!(stored.succeeded, 2);
+        assert_eq!(
+            stored.cursor,
+            Some(ScanCursor {
+                cursor: 1,
+                layer: 0
+            })
+        );
+
+        invoker.release.notify_one();
+        wait_until_finished(&jobs, job.id).await;
+
+        let stored = store.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Completed);
+        assert_eq!(stored.succeeded, 6);
+    }
+
+    #[test]
+    async fn running_job_is_only_resumed_after_its_lease_expired() {
+        let (_db_file, store) = sqlite_store().await;
+        let component_id = ComponentId::new();
+        let pages = agent_pages(component_id, 3, 2);
+        let invoker = Arc::new(AgentInvokerMock::new(pages.clone()));
+        let jobs = jobs(&invoker, &store);
+
+        // Both jobs already processed their first page on another instance
+        let mut leased_job = job(component_id);
+        leased_job.cursor = Some(ScanCursor {
+            cursor: 1,
+            layer: 0,
+        });
+        leased_job.succeeded = 2;
+        store
+            .create(&leased_job, &lease(Uuid::new_v4(), TimeDelta::minutes(1)))
+            .await
+            .unwrap();
+
+        let mut abandoned_job = job(component_id);
+        abandoned_job.cursor = Some(ScanCursor {
+            cursor: 1,
+            layer: 0,
+        });
+        abandoned_job.succeeded = 2;
+        store
+            .create(
+                &abandoned_job,
+                &lease(Uuid::new_v4(), TimeDelta::seconds(-1)),
+            )
+            .await
+            .unwrap();
+
+        jobs.resume_running_jobs().await.unwrap();
+        assert!(!jobs.running_jobs.contains_key(&leased_job.id));
+        wait_until_finished(&jobs, abandoned_job.id).await;
+
+        let stored = store.get(&abandoned_job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Completed);
+        assert_eq!(stored.succeeded, 6);
+        let invoked: HashSet<_> = invoker.invoked_agents().into_iter().collect();
+        let expected: HashSet<_> = pages[1..].iter().flatten().cloned().collect();
+        assert_eq!(invoked, expected);
+
+        let stored = store.get(&leased_job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Running);
+        assert_eq!(stored.succeeded, 2);
+    }
+
+    #[test]
+    async fn cancelled_job_stops_before_the_next_page() {
+        let (_db_file, store) = sqlite_store().await;
+        let component_id = ComponentId::new();
+        let pages = agent_pages(component_id, 3, 2);
+        let mut invoker = AgentInvokerMock::new(pages.clone());
+        invoker.blocked_page = Some(1);
+        let invoker = Arc::new(invoker);
+        let jobs = jobs(&invoker, &store);
+
+        let job = job(component_id);
+        jobs.launch(job.clone()).await.unwrap();
+        invoker.reached.notified().await;
+
+        let cancelled = jobs.cancel(job.id).await.unwrap();
+        assert_eq!(cancelled.status, BulkInvocationJobStatus::Cancelled);
+
+        invoker.release.notify_one();
+        wait_until_finished(&jobs, job.id).await;
+
+        let stored = store.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Cancelled);
+        assert_eq!(stored.succeeded, 2);
+        let invoked = invoker.invoked_agents();
+        assert!(pages[2].iter().all(|agent_id| !invoked.contains(agent_id)));
+    }
+
+    #[test]
+    async fn job_cancelled_through_another_instance_stops() {
+        let (_db_file, store) = sqlite_store().await;
+        let component_id = ComponentId::new();
+        let pages = agent_pages(component_id, 3, 2);
+        let mut invoker = AgentInvokerMock::new(pages.clone());
+        invoker.blocked_page = Some(1);
+        let invoker = Arc::new(invoker);
+        let running_instance = jobs(&invoker, &store);
+        let other_instance = jobs(&invoker, &store);
+
+        let job = job(component_id);
+        running_instance.launch(job.clone()).await.unwrap();
+        invoker.reached.notified().await;
+
+        other_instance.cancel(job.id).await.unwrap();
+        invoker.release.notify_one();
+        wait_until_finished(&running_instance, job.id).await;
+
+        let stored = store.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, BulkInvocationJobStatus::Cancelled);
+        let invoked = invoker.invoked_agents();
+        assert!(pages[2].iter().all(|agent_id| !invoked.contains(agent_id)));
+    }
+
+    #[test]
+    async fn job_lease_is_exclusive_until_it_expires() {
+        let (_db_file, store) = sqlite_store().await;
+        let job = job(ComponentId::new());
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        store
+            .create(&job, &lease(owner, TimeDelta::minutes(1)))
+            .await
+            .unwrap();
+
+        let other_lease = lease(other, TimeDelta::minutes(1));
+        assert!(
+            store
+                .try_claim(&job.id, Utc::now(), &other_lease)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(!store.renew(&job.id, &other_lease).await.unwrap());
+        assert!(!store.update(&job, &other_lease).await.unwrap());
+        assert!(
+            store
+                .renew(&job.id, &lease(owner, TimeDelta::minutes(1)))
+                .await
+                .unwrap()
+        );
+
+        // Once the owner's lease expired, the other instance takes over and the owner cannot
+        // store its progress anymore
+        let later = Utc::now() + TimeDelta::minutes(2);
+        assert!(
+            store
+                .try_claim(&job.id, later, &other_lease)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            !store
+                .update(&job, &lease(owner, TimeDelta::minutes(1)))
+                .await
+                .unwrap()
+        );
+        assert!(store.update(&job, &other_lease).await.unwrap());
+    }
+}
@@ -0,0 +1,636 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{BulkInvocationJob, BulkInvocationJobStatus};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::config::DbSqliteConfig;
+use golem_common::error_forwarding;
+use golem_common::redis::{RedisError, RedisPool};
+use golem_service_base::db::PoolApi;
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use golem_service_base::repo::RepoError;
+use include_dir::include_dir;
+use sqlx::Row;
+use uuid::Uuid;
+
+static DB_MIGRATIONS: include_dir::Dir =
+    include_dir!("$CARGO_MANIFEST_DIR/db/migration/bulk_invocation");
+
+/// Number of times cancelling retries when the job is concurrently updated by its runner
+const MAX_CANCEL_ATTEMPTS: usize = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BulkInvocationJobStoreError {
+    #[error(transparent)]
+    InternalError(#[from] anyhow::Error),
+}
+
+error_forwarding!(BulkInvocationJobStoreError, RepoError);
+
+impl From<RedisError> for BulkInvocationJobStoreError {
+    fn from(value: RedisError) -> Self {
+        Self::InternalError(anyhow::Error::from(value).context("RedisError"))
+    }
+}
+
+/// Exclusive right of a worker service instance to run a job until the lease expires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkInvocationJobLease {
+    pub owner: Uuid,
+    pub until: DateTime<Utc>,
+}
+
+/// Durable record of the bulk invocation jobs, so they can be queried, cancelled and resumed
+/// after a restart of the worker service.
+///
+/// A running job is leased to the worker service instance running it. Only the lease owner can
+/// update the job, and a job is only resumed by another instance once its lease has expired.
+#[async_trait]
+pub trait BulkInvocationJobStore: Send + Sync {
+    /// Inserts a new job, leased to the instance starting it
+    async fn create(
+        &self,
+        job: &BulkInvocationJob,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<(), BulkInvocationJobStoreError>;
+
+    async fn get(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError>;
+
+    /// Gets every job still in the [`BulkInvocationJobStatus::Running`] state
+    async fn get_running(&self) -> Result<Vec<BulkInvocationJob>, BulkInvocationJobStoreError>;
+
+    /// Takes over the lease of a running job if it is free, expired or already owned by
+    /// `lease.owner`. Returns the job if the lease was claimed.
+    async fn try_claim(
+        &self,
+        job_id: &Uuid,
+        now: DateTime<Utc>,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError>;
+
+    /// Extends the lease of a running job. Returns `false` if the lease is no longer owned by
+    /// `lease.owner`, or the job is not running anymore.
+    async fn renew(
+        &self,
+        job_id: &Uuid,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<bool, BulkInvocationJobStoreError>;
+
+    /// Stores the progress of a running job and extends its lease, releasing it once the job
+    /// finished. Returns `false` without storing anything if the lease is no longer owned by
+    /// `lease.owner`, or the job is not running anymore (it was cancelled).
+    async fn update(
+        &self,
+        job: &BulkInvocationJob,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<bool, BulkInvocationJobStoreError>;
+
+    /// Marks a running job as cancelled and releases its lease, regardless of which instance
+    /// owns it. Returns the stored job after the cancellation.
+    async fn cancel(
+        &self,
+        job_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError>;
+}
+
+fn serialize_job(job: &BulkInvocationJob) -> Result<String, BulkInvocationJobStoreError> {
+    serde_json::to_string(job)
+        .map_err(|e| anyhow!("BulkInvocationJob serialization error: {e}").into())
+}
+
+fn deserialize_job(value: &str) -> Result<BulkInvocationJob, BulkInvocationJobStoreError> {
+    serde_json::from_str(value)
+        .map_err(|e| anyhow!("BulkInvocationJob deserialization error: {e}").into())
+}
+
+fn cancelled(mut job: BulkInvocationJob, now: DateTime<Utc>) -> BulkInvocationJob {
+    job.status = BulkInvocationJobStatus::Cancelled;
+    job.updated_at = now;
+    job
+}
+
+fn lease_ttl_millis(lease: &BulkInvocationJobLease) -> i64 {
+    (lease.until - Utc::now()).num_milliseconds().max(1)
+}
+
+/// Claims `KEYS[2]` (the lease) for `ARGV[2]` if `ARGV[1]` is in the running set `KEYS[1]`
+/// and the lease is free or already owned.
+const REDIS_CLAIM_SCRIPT: &str = r#"
+if redis.call('SISMEMBER', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+local owner = redis.call('GET', KEYS[2])
+if owner and owner ~= ARGV[2] then
+    return 0
+end
+redis.call('SET', KEYS[2], ARGV[2], 'PX', ARGV[3])
+return 1
+"#;
+
+/// Extends the lease `KEYS[2]` if it is owned by `ARGV[2]` and `ARGV[1]` is still running
+const REDIS_RENEW_SCRIPT: &str = r#"
+if redis.call('SISMEMBER', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+if redis.call('GET', KEYS[2]) ~= ARGV[2] then
+    return 0
+end
+redis.call('PEXPIRE', KEYS[2], ARGV[3])
+return 1
+"#;
+
+/// Stores the job `KEYS[3]` if the lease `KEYS[2]` is owned by `ARGV[2]` and the job is still
+/// in the running set `KEYS[1]`. `ARGV[5]` is `1` if the job is still running after the update.
+const REDIS_UPDATE_SCRIPT: &str = r#"
+if redis.call('SISMEMBER', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+if redis.call('GET', KEYS[2]) ~= ARGV[2] then
+    return 0
+end
+redis.call('SET', KEYS[3], ARGV[4])
+if ARGV[5] == '1' then
+    redis.call('PEXPIRE', KEYS[2], ARGV[3])
+else
+    redis.call('SREM', KEYS[1], ARGV[1])
+    redis.call('DEL', KEYS[2])
+end
+return 1
+"#;
+
+/// Replaces the job `KEYS[3]` with `ARGV[3]` if it still equals `ARGV[2]`, then removes it from
+/// the running set `KEYS[1]` and drops its lease `KEYS[2]`.
+const REDIS_CANCEL_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[3]) ~= ARGV[2] then
+    return 0
+end
+redis.call('SET', KEYS[3], ARGV[3])
+redis.call('SREM', KEYS[1], ARGV[1])
+redis.call('DEL', KEYS[2])
+return 1
+"#;
+
+#[derive(Clone)]
+pub struct RedisBulkInvocationJobStore {
+    redis: RedisPool,
+}
+
+impl RedisBulkInvocationJobStore {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn redis_key_for_job(job_id: &Uuid) -> String {
+        format!("bulk_invocation_job:{job_id}")
+    }
+
+    fn redis_key_for_lease(job_id: &Uuid) -> String {
+        format!("bulk_invocation_job_lease:{job_id}")
+    }
+
+    fn redis_key_for_running_jobs() -> String {
+        "bulk_invocation_jobs:running".to_string()
+    }
+
+    async fn get_raw(&self, job_id: &Uuid) -> Result<Option<String>, BulkInvocationJobStoreError> {
+        Ok(self
+            .redis
+            .with("bulk_invocation_job_store", "get")
+            .get(Self::redis_key_for_job(job_id))
+            .await?)
+    }
+}
+
+#[async_trait]
+impl BulkInvocationJobStore for RedisBulkInvocationJobStore {
+    async fn create(
+        &self,
+        job: &BulkInvocationJob,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<(), BulkInvocationJobStoreError> {
+        let serialized = serialize_job(job)?;
+
+        let _: () = self
+            .redis
+            .with("bulk_invocation_job_store", "create")
+            .set(
+                Self::redis_key_for_lease(&job.id),
+                lease.owner.to_string(),
+                Some(fred::types::Expiration::PX(lease_ttl_millis(lease))),
+                None,
+                false,
+            )
+            .await?;
+
+        let _: () = self
+            .redis
+            .with("bulk_invocation_job_store", "create")
+            .set(
+                Self::redis_key_for_job(&job.id),
+                serialized,
+                None,
+                None,
+                false,
+            )
+            .await?;
+
+        let _: i64 = self
+            .redis
+            .with("bulk_invocation_job_store", "create")
+            .sadd(Self::redis_key_for_running_jobs(), job.id.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        self.get_raw(job_id)
+            .await?
+            .as_deref()
+            .map(deserialize_job)
+            .transpose()
+    }
+
+    async fn get_running(&self) -> Result<Vec<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        let job_ids: Vec<String> = self
+            .redis
+            .with("bulk_invocation_job_store", "get_running")
+            .smembers(Self::redis_key_for_running_jobs())
+            .await?;
+
+        let mut jobs = Vec::with_capacity(job_ids.len());
+        for job_id in job_ids {
+            let job_id = Uuid::parse_str(&job_id)
+                .map_err(|e| anyhow!("Invalid bulk invocation job id {job_id}: {e}"))?;
+            if let Some(job) = self.get(&job_id).await? {
+                jobs.push(job);
+            }
+        }
+        Ok(jobs)
+    }
+
+    async fn try_claim(
+        &self,
+        job_id: &Uuid,
+        _now: DateTime<Utc>,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        // Expired leases are removed by Redis itself
+        let claimed: i64 = self
+            .redis
+            .with("bulk_invocation_job_store", "try_claim")
+            .eval(
+                REDIS_CLAIM_SCRIPT,
+                vec![
+                    Self::redis_key_for_running_jobs(),
+                    Self::redis_key_for_lease(job_id),
+                ],
+                vec![
+                    job_id.to_string(),
+                    lease.owner.to_string(),
+                    lease_ttl_millis(lease).to_string(),
+                ],
+            )
+            .await?;
+
+        if claimed == 1 {
+            self.get(job_id).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn renew(
+        &self,
+        job_id: &Uuid,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<bool, BulkInvocationJobStoreError> {
+        let renewed: i64 = self
+            .redis
+            .with("bulk_invocation_job_store", "renew")
+            .eval(
+                REDIS_RENEW_SCRIPT,
+                vec![
+                    Self::redis_key_for_running_jobs(),
+                    Self::redis_key_for_lease(job_id),
+                ],
+                vec![
+                    job_id.to_string(),
+                    lease.owner.to_string(),
+                    lease_ttl_millis(lease).to_string(),
+                ],
+            )
+            .await?;
+
+        Ok(renewed == 1)
+    }
+
+    async fn update(
+        &self,
+        job: &BulkInvocationJob,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<bool, BulkInvocationJobStoreError> {
+        let serialized = serialize_job(job)?;
+        let still_running = if job.status == BulkInvocationJobStatus::Running {
+            "1"
+        } else {
+            "0"
+        };
+
+        let updated: i64 = self
+            .redis
+            .with("bulk_invocation_job_store", "update")
+            .eval(
+                REDIS_UPDATE_SCRIPT,
+                vec![
+                    Self::redis_key_for_running_jobs(),
+                    Self::redis_key_for_lease(&job.id),
+                    Self::redis_key_for_job(&job.id),
+                ],
+                vec![
+                    job.id.to_string(),
+                    lease.owner.to_string(),
+                    lease_ttl_millis(lease).to_string(),
+                    serialized,
+                    still_running.to_string(),
+                ],
+            )
+            .await?;
+
+        Ok(updated == 1)
+    }
+
+    async fn cancel(
+        &self,
+        job_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        for _ in 0..MAX_CANCEL_ATTEMPTS {
+            let Some(raw) = self.get_raw(job_id).await? else {
+                return Ok(None);
+            };
+            let job = deserialize_job(&raw)?;
+            if job.status != BulkInvocationJobStatus::Running {
+                return Ok(Some(job));
+            }
+
+            let job = cancelled(job, now);
+            let replaced: i64 = self
+                .redis
+                .with("bulk_invocation_job_store", "cancel")
+                .eval(
+                    REDIS_CANCEL_SCRIPT,
+                    vec![
+                        Self::redis_key_for_running_jobs(),
+                        Self::redis_key_for_lease(job_id),
+                        Self::redis_key_for_job(job_id),
+                    ],
+                    vec![job_id.to_string(), raw, serialize_job(&job)?],
+                )
+                .await?;
+            if replaced == 1 {
+                return Ok(Some(job));
+            }
+        }
+
+        Err(anyhow!("Bulk invocation job {job_id} kept changing while cancelling it").into())
+    }
+}
+
+pub struct SqliteBulkInvocationJobStore {
+    pool: SqlitePool,
+}
+
+impl SqliteBulkInvocationJobStore {
+    pub async fn migrate(config: &DbSqliteConfig) -> anyhow::Result<()> {
+        let migrations = IncludedMigrationsDir::new(&DB_MIGRATIONS);
+        golem_service_base::db::sqlite::migrate(config, migrations.sqlite_migrations())
+            .await
+            .map_err(|err| anyhow!("Sqlite bulk invocation job store migration failed: {err:?}"))
+    }
+
+    /// Creates the store on a pool whose database was migrated with [`Self::migrate`]
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn get_raw(&self, job_id: &Uuid) -> Result<Option<String>, BulkInvocationJobStoreError> {
+        let row = self
+            .pool
+            .with_ro("bulk_invocation_job_store", "get")
+            .fetch_optional(
+                sqlx::query("SELECT value FROM bulk_invocation_job WHERE job_id = ?")
+                    .bind(job_id.to_string()),
+            )
+            .await?;
+
+        Ok(row.map(|row| row.get(0)))
+    }
+}
+
+#[async_trait]
+impl BulkInvocationJobStore for SqliteBulkInvocationJobStore {
+    async fn create(
+        &self,
+        job: &BulkInvocationJob,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<(), BulkInvocationJobStoreError> {
+        let serialized = serialize_job(job)?;
+
+        self.pool
+            .with_rw("bulk_invocation_job_store", "create")
+            .execute(
+                sqlx::query(
+                    "INSERT INTO bulk_invocation_job (job_id, running, value, lease_owner, lease_until_ms) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(job.id.to_string())
+                .bind(job.status == BulkInvocationJobStatus::Running)
+                .bind(serialized)
+                .bind(lease.owner.to_string())
+                .bind(lease.until.timestamp_millis()),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        self.get_raw(job_id)
+            .await?
+            .as_deref()
+            .map(deserialize_job)
+            .transpose()
+    }
+
+    async fn get_running(&self) -> Result<Vec<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        let rows = self
+            .pool
+            .with_ro("bulk_invocation_job_store", "get_running")
+            .fetch_all(sqlx::query(
+                "SELECT value FROM bulk_invocation_job WHERE running = 1",
+            ))
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let value: String = row.get(0);
+                deserialize_job(&value)
+            })
+            .collect()
+    }
+
+    async fn try_claim(
+        &self,
+        job_id: &Uuid,
+        now: DateTime<Utc>,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        let result = self
+            .pool
+            .with_rw("bulk_invocation_job_store", "try_claim")
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE bulk_invocation_job
+                    SET lease_owner = ?, lease_until_ms = ?
+                    WHERE job_id = ?
+                      AND running = 1
+                      AND (lease_owner IS NULL OR lease_owner = ? OR lease_until_ms <= ?)
+                    "#,
+                )
+                .bind(lease.owner.to_string())
+                .bind(lease.until.timestamp_millis())
+                .bind(job_id.to_string())
+                .bind(lease.owner.to_string())
+                .bind(now.timestamp_millis()),
+            )
+            .await?;
+
+        if result.rows_affected() == 1 {
+            self.get(job_id).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn renew(
+        &self,
+        job_id: &Uuid,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<bool, BulkInvocationJobStoreError> {
+        let result = self
+            .pool
+            .with_rw("bulk_invocation_job_store", "renew")
+            .execute(
+                sqlx::query(
+                    "UPDATE bulk_invocation_job SET lease_until_ms = ? WHERE job_id = ? AND running = 1 AND lease_owner = ?",
+                )
+                .bind(lease.until.timestamp_millis())
+                .bind(job_id.to_string())
+                .bind(lease.owner.to_string()),
+            )
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn update(
+        &self,
+        job: &BulkInvocationJob,
+        lease: &BulkInvocationJobLease,
+    ) -> Result<bool, BulkInvocationJobStoreError> {
+        let serialized = serialize_job(job)?;
+        let still_running = job.status == BulkInvocationJobStatus::Running;
+
+        let result = self
+            .pool
+            .with_rw("bulk_invocation_job_store", "update")
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE bulk_invocation_job
+                    SET running = ?,
+                        value = ?,
+                        lease_owner = CASE WHEN ? THEN lease_owner ELSE NULL END,
+                        lease_until_ms = CASE WHEN ? THEN ? ELSE NULL END
+                    WHERE job_id = ? AND running = 1 AND lease_owner = ?
+                    "#,
+                )
+                .bind(still_running)
+                .bind(serialized)
+                .bind(still_running)
+                .bind(still_running)
+                .bind(lease.until.timestamp_millis())
+                .bind(job.id.to_string())
+                .bind(lease.owner.to_string()),
+            )
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn cancel(
+        &self,
+        job_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<BulkInvocationJob>, BulkInvocationJobStoreError> {
+        for _ in 0..MAX_CANCEL_ATTEMPTS {
+            let Some(raw) = self.get_raw(job_id).await? else {
+                return Ok(None);
+            };
+            let job = deserialize_job(&raw)?;
+            if job.status != BulkInvocationJobStatus::Running {
+                return Ok(Some(job));
+            }
+
+            // The runner might store its progress in the meantime, so the record is only
+            // replaced if it did not change since it was read
+            let job = cancelled(job, now);
+            let result = self
+                .pool
+                .with_rw("bulk_invocation_job_store", "cancel")
+                .execute(
+                    sqlx::query(
+                        r#"
+                        UPDATE bulk_invocation_job
+                        SET running = 0, value = ?, lease_owner = NULL, lease_until_ms = NULL
+                        WHERE job_id = ? AND value = ?
+                        "#,
+                    )
+                    .bind(serialize_job(&job)?)
+                    .bind(job_id.to_string())
+                    .bind(raw),
+                )
+                .await?;
+            if result.rows_affected() == 1 {
+                return Ok(Some(job));
+            }
+        }
+
+        Err(anyhow!("Bulk invocation job {job_id} kept changing while cancelling it").into())
+    }
+}
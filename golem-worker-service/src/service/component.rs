@@ -18,6 +18,7 @@ use async_trait::async_trait;
 use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
 use golem_common::model::component::ComponentId;
 use golem_common::model::component::ComponentRevision;
+use golem_common::model::environment::EnvironmentId;
 use golem_common::{SafeDisplay, error_forwarding};
 use golem_service_base::clients::registry::{RegistryService, RegistryServiceError};
 use golem_service_base::model::component::Component;
@@ -72,6 +73,12 @@ pub trait ComponentService: Send + Sync {
         &self,
         component_id: ComponentId,
     ) -> Result<Vec<Component>, ComponentServiceError>;
+
+    /// Current revisions of the deployed components of an environment
+    async fn get_all_deployed(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<Component>, ComponentServiceError>;
 }
 
 // The error is not actually cached, just something that can be cloned and returned
@@ -198,4 +205,17 @@ impl ComponentService for RemoteComponentService {
 
         Ok(results)
     }
+
+    async fn get_all_deployed(
+        &self,
+        environment_id: EnvironmentId,
+    ) -> Result<Vec<Component>, ComponentServiceError> {
+        let results = self.client.get_deployed_components(environment_id).await?;
+
+        for result in &results {
+            self.store_component_in_cache(result.clone()).await;
+        }
+
+        Ok(results)
+    }
 }
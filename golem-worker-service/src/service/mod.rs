@@ -13,7 +13,9 @@
 // limitations under the License.
 
 pub mod agent_resolution_cache;
+pub mod agent_write_subscriber;
 pub mod auth;
+pub mod bulk_invocation;
 pub mod component;
 pub mod limit;
 pub mod registry_event_subscriber;
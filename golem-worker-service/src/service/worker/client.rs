@@ -26,9 +26,9 @@ use golem_api_grpc::proto::golem::workerexecutor;
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_client::WorkerExecutorClient;
 use golem_api_grpc::proto::golem::workerexecutor::v1::{
     ActivatePluginRequest, CancelInvocationRequest, CompletePromiseRequest, ConnectWorkerRequest,
-    CreateWorkerRequest, DeactivatePluginRequest, ForkWorkerRequest, InterruptWorkerRequest,
-    ProcessOplogEntriesRequest, ResumeWorkerRequest, RevertWorkerRequest, SearchOplogResponse,
-    UpdateWorkerRequest,
+    CreateWorkerRequest, DeactivatePluginRequest, ForkWorkerRequest, GetAgentLifecycleAuditRequest,
+    InterruptWorkerRequest, ProcessOplogEntriesRequest, ResumeWorkerRequest, RevertWorkerRequest,
+    SearchOplogResponse, UpdateWorkerRequest,
 };
 use golem_common::model::RetryConfig;
 use golem_common::model::account::{AccountEmail, AccountId};
@@ -41,10 +41,10 @@ use golem_common::model::oplog::{OplogCursor, PublicOplogEntry};
 use golem_common::model::oplog::{OplogIndex, PublicOplogEntryWithIndex};
 use golem_common::model::worker::AgentConfigEntryDto;
 use golem_common::model::worker::AgentUpdateMode;
-use golem_common::model::worker::{AgentMetadataDto, RevertWorkerTarget};
+use golem_common::model::worker::{AgentLifecycleAuditEntry, AgentMetadataDto, RevertWorkerTarget};
 use golem_common::model::{
     AgentFilter, AgentFingerprint, AgentId, AgentStatus, FilterComparator, IdempotencyKey,
-    PromiseId, ScanCursor,
+    PromiseId, ScanCursor, Timestamp,
 };
 use golem_common::model::{AgentInvocationOutput, AgentInvocationResult, InvocationStatus};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
@@ -54,8 +54,8 @@ use golem_service_base::model::{ComponentFileSystemNode, GetOplogResponse};
 use golem_service_base::service::routing_table::{HasRoutingTableService, RoutingTableService};
 use std::pin::Pin;
 use std::{collections::HashMap, sync::Arc};
-use tonic::Code;
 use tonic::transport::Channel;
+use tonic::{Code, Status};
 use tonic_tracing_opentelemetry::middleware::client::OtelGrpcService;
 
 #[async_trait]
@@ -79,6 +79,7 @@ pub trait WorkerClient: Send + Sync {
         environment_id: EnvironmentId,
         account_id: AccountId,
         auth_ctx: AuthCtx,
+        websocket_connection: Option<workerexecutor::v1::WebSocketConnection>,
     ) -> WorkerResult<WorkerStream<LogEvent>>;
 
     async fn delete(
@@ -157,6 +158,8 @@ pub trait WorkerClient: Send + Sync {
         cursor: Option<OplogCursor>,
         count: u64,
         query: String,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
         environment_id: EnvironmentId,
         auth_ctx: AuthCtx,
     ) -> Result<GetOplogResponse, WorkerServiceError>;
@@ -206,6 +209,21 @@ pub trait WorkerClient: Send + Sync {
         auth_ctx: AuthCtx,
     ) -> WorkerResult<()>;
 
+    async fn export_agent(
+        &self,
+        agent_id: &AgentId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>>;
+
+    async fn import_agent(
+        &self,
+        target_agent_id: &AgentId,
+        archive: Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()>;
+
     async fn revert_worker(
         &self,
         agent_id: &AgentId,
@@ -229,6 +247,7 @@ pub trait WorkerClient: Send + Sync {
         method_parameters: Option<golem_api_grpc::proto::golem::component::UntypedDataValue>,
         mode: i32,
         schedule_at: Option<::prost_types::Timestamp>,
+        at: Option<workerexecutor::v1::TimeTravelTarget>,
         idempotency_key: Option<IdempotencyKey>,
         invocation_context: Option<InvocationContext>,
         environment_id: EnvironmentId,
@@ -250,6 +269,17 @@ pub trait WorkerClient: Send + Sync {
         entries: Vec<golem_api_grpc::proto::golem::worker::RawOplogEntry>,
         auth_ctx: AuthCtx,
     ) -> WorkerResult<()>;
+    /// Gets one page of the lifecycle policy actions applied to the agents of a component
+    /// between `from` and `to`, and the time the next page starts at, if there is one
+    async fn get_lifecycle_audit(
+        &self,
+        component_id: ComponentId,
+        environment_id: EnvironmentId,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<(Vec<AgentLifecycleAuditEntry>, Option<Timestamp>)>;
 }
 
 #[derive(Clone)]
@@ -482,6 +512,7 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         environment_id: EnvironmentId,
         account_id: AccountId,
         auth_ctx: AuthCtx,
+        websocket_connection: Option<workerexecutor::v1::WebSocketConnection>,
     ) -> WorkerResult<WorkerStream<LogEvent>> {
         let agent_id_clone = agent_id.clone();
         let account_id_clone = account_id;
@@ -497,6 +528,7 @@ impl WorkerClient for WorkerExecutorWorkerClient {
                         environment_id: Some(environment_id.into()),
                         auth_ctx: Some(auth_ctx.clone().into()),
                         principal: None,
+                        websocket_connection: websocket_connection.clone(),
                     }))
                 },
                 |response| Ok(WorkerStream::new(response.into_inner())),
@@ -878,6 +910,8 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         cursor: Option<OplogCursor>,
         count: u64,
         query: String,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
         environment_id: EnvironmentId,
         auth_ctx: AuthCtx,
     ) -> Result<GetOplogResponse, WorkerServiceError> {
@@ -895,7 +929,9 @@ impl WorkerClient for WorkerExecutorWorkerClient {
                         cursor: cursor.clone().map(|c| c.into()),
                         count,
                         environment_id: Some(environment_id.into()),
-                        auth_ctx: Some(auth_ctx.clone().into())
+                        auth_ctx: Some(auth_ctx.clone().into()),
+                        from: from.map(|from| from.into()),
+                        to: to.map(|to| to.into()),
                     }),
                 )
             },
@@ -1215,6 +1251,120 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         Ok(())
     }
 
+    async fn export_agent(
+        &self,
+        agent_id: &AgentId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>> {
+        let agent_id = agent_id.clone();
+        let stream = self
+            .call_worker_executor(
+                agent_id.clone(),
+                "export_agent",
+                move |worker_executor_client| {
+                    let agent_id = agent_id.clone();
+                    Box::pin(worker_executor_client.export_agent(
+                        workerexecutor::v1::ExportAgentRequest {
+                            agent_id: Some(agent_id.into()),
+                            environment_id: Some(environment_id.into()),
+                            auth_ctx: Some(auth_ctx.clone().into()),
+                        },
+                    ))
+                },
+                |response| Ok(WorkerStream::new(response.into_inner())),
+                WorkerServiceError::InternalCallError,
+            )
+            .await?;
+
+        let stream = stream
+            .map_err(|_| WorkerServiceError::Internal("Stream error".to_string()))
+            .map(|item| {
+                item.and_then(|response| match response.result {
+                    Some(workerexecutor::v1::export_agent_response::Result::Chunk(bytes)) => {
+                        Ok(Bytes::from(bytes))
+                    }
+                    Some(workerexecutor::v1::export_agent_response::Result::Failure(err)) => {
+                        let converted = WorkerExecutorError::try_from(err).map_err(|err| {
+                            WorkerServiceError::Internal(format!("Failed converting errors {err}"))
+                        })?;
+                        Err(converted.into())
+                    }
+                    None => Err(WorkerServiceError::Internal("Malformed chunk".to_string())),
+                })
+            });
+
+        // Failures detected before the export started are reported instead of the archive
+        let (first, stream) = stream.into_future().await;
+        let first = first.ok_or(WorkerServiceError::Internal("Empty stream".to_string()))??;
+
+        Ok(Box::pin(
+            futures::stream::once(futures::future::ready(Ok(first))).chain(stream),
+        ))
+    }
+
+    async fn import_agent(
+        &self,
+        target_agent_id: &AgentId,
+        archive: Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()> {
+        let target_agent_id = target_agent_id.clone();
+        let archive = Arc::new(std::sync::Mutex::new(Some(archive)));
+        self.call_worker_executor(
+            target_agent_id.clone(),
+            "import_agent",
+            move |worker_executor_client| {
+                let header = workerexecutor::v1::ImportAgentRequest {
+                    request: Some(workerexecutor::v1::import_agent_request::Request::Header(
+                        workerexecutor::v1::ImportAgentRequestHeader {
+                            target_agent_id: Some(target_agent_id.clone().into()),
+                            environment_id: Some(environment_id.into()),
+                            auth_ctx: Some(auth_ctx.clone().into()),
+                        },
+                    )),
+                };
+                // The archive is streamed from the caller, so it can't be sent again if an
+                // attempt fails after sending it. A failing archive stream ends the request
+                // early, which the executor rejects as a truncated archive.
+                let archive = archive.lock().unwrap().take();
+                Box::pin(async move {
+                    let archive = archive.ok_or_else(|| {
+                        Status::aborted("agent archive was already sent by a failed attempt")
+                    })?;
+                    let chunks = archive
+                        .take_while(|chunk| futures::future::ready(chunk.is_ok()))
+                        .filter_map(|chunk| futures::future::ready(chunk.ok()))
+                        .map(|chunk| workerexecutor::v1::ImportAgentRequest {
+                            request: Some(
+                                workerexecutor::v1::import_agent_request::Request::Chunk(
+                                    chunk.to_vec(),
+                                ),
+                            ),
+                        });
+                    worker_executor_client
+                        .import_agent(
+                            futures::stream::once(futures::future::ready(header)).chain(chunks),
+                        )
+                        .await
+                })
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::ImportAgentResponse {
+                    result: Some(workerexecutor::v1::import_agent_response::Result::Success(_)),
+                } => Ok(()),
+                workerexecutor::v1::ImportAgentResponse {
+                    result: Some(workerexecutor::v1::import_agent_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::ImportAgentResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn revert_worker(
         &self,
         agent_id: &AgentId,
@@ -1297,6 +1447,7 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         method_parameters: Option<golem_api_grpc::proto::golem::component::UntypedDataValue>,
         mode: i32,
         schedule_at: Option<::prost_types::Timestamp>,
+        at: Option<workerexecutor::v1::TimeTravelTarget>,
         idempotency_key: Option<IdempotencyKey>,
         invocation_context: Option<InvocationContext>,
         environment_id: EnvironmentId,
@@ -1319,6 +1470,7 @@ impl WorkerClient for WorkerExecutorWorkerClient {
                             method_parameters: method_parameters.clone(),
                             mode,
                             schedule_at,
+                            at: at.clone(),
                             idempotency_key: idempotency_key.clone().map(|k| k.into()),
                             component_owner_account_id: Some(account_id.into()),
                             environment_id: Some(environment_id.into()),
@@ -1438,6 +1590,73 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         .await?;
         Ok(())
     }
+
+    async fn get_lifecycle_audit(
+        &self,
+        component_id: ComponentId,
+        environment_id: EnvironmentId,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<(Vec<AgentLifecycleAuditEntry>, Option<Timestamp>)> {
+        let result = self
+            .call_worker_executor(
+                RandomExecutor,
+                "get_agent_lifecycle_audit",
+                move |worker_executor_client| {
+                    Box::pin(worker_executor_client.get_agent_lifecycle_audit(
+                        GetAgentLifecycleAuditRequest {
+                            environment_id: Some(environment_id.into()),
+                            component_id: Some(component_id.into()),
+                            from: from.map(|from| from.into()),
+                            to: to.map(|to| to.into()),
+                            count,
+                            auth_ctx: Some(auth_ctx.clone().into()),
+                        },
+                    ))
+                },
+                |response| match response.into_inner() {
+                    workerexecutor::v1::GetAgentLifecycleAuditResponse {
+                        result:
+                            Some(
+                                workerexecutor::v1::get_agent_lifecycle_audit_response::Result::Success(
+                                    workerexecutor::v1::GetAgentLifecycleAuditSuccessResponse {
+                                        entries,
+                                        next_from,
+                                    },
+                                ),
+                            ),
+                    } => {
+                        let entries = entries
+                            .into_iter()
+                            .map(lifecycle_audit_entry_from_proto)
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(|err| {
+                                WorkerExecutorError::unknown(format!(
+                                    "Unexpected lifecycle audit entry in response: {err}"
+                                ))
+                            })?;
+                        Ok((entries, next_from.map(Timestamp::from)))
+                    }
+                    workerexecutor::v1::GetAgentLifecycleAuditResponse {
+                        result:
+                            Some(
+                                workerexecutor::v1::get_agent_lifecycle_audit_response::Result::Failure(
+                                    err,
+                                ),
+                            ),
+                    } => Err(err.into()),
+                    workerexecutor::v1::GetAgentLifecycleAuditResponse { .. } => {
+                        Err("Empty response".into())
+                    }
+                },
+                WorkerServiceError::InternalCallError,
+            )
+            .await?;
+
+        Ok(result)
+    }
 }
 
 fn is_filter_with_running_status(filter: &AgentFilter) -> bool {
@@ -1451,3 +1670,18 @@ fn is_filter_with_running_status(filter: &AgentFilter) -> bool {
         _ => false,
     }
 }
+
+fn lifecycle_audit_entry_from_proto(
+    entry: workerexecutor::v1::AgentLifecycleAuditEntry,
+) -> Result<AgentLifecycleAuditEntry, String> {
+    let action =
+        golem_api_grpc::proto::golem::component::AgentLifecycleAction::try_from(entry.action)
+            .map_err(|err| format!("invalid lifecycle action: {err}"))?;
+
+    Ok(AgentLifecycleAuditEntry {
+        timestamp: entry.timestamp.ok_or("missing timestamp")?.into(),
+        agent_id: entry.agent_id.ok_or("missing agent id")?.try_into()?,
+        action: action.into(),
+        last_oplog_index: OplogIndex::from_u64(entry.last_oplog_index),
+    })
+}
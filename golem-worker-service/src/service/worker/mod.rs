@@ -16,6 +16,7 @@ mod client;
 mod connect;
 mod connect_proxy;
 mod error;
+mod oplog_search;
 mod routing_logic;
 mod service;
 mod worker_stream;
@@ -24,6 +25,7 @@ pub use client::*;
 pub use connect::*;
 pub use connect_proxy::*;
 pub use error::*;
+pub use oplog_search::*;
 pub use routing_logic::*;
 pub use service::*;
 pub use worker_stream::*;
@@ -0,0 +1,235 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use golem_common::model::component::ComponentId;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog::{AgentOplogSearchMatch, OplogCursor};
+use golem_common::model::{AgentId, ScanCursor};
+use golem_service_base::model::GetOplogResponse;
+use serde::{Deserialize, Serialize};
+
+/// Default and maximum number of agents scanned in one page of a cross-agent oplog search
+pub const OPLOG_SEARCH_DEFAULT_AGENT_COUNT: u64 = 50;
+pub const OPLOG_SEARCH_MAX_AGENT_COUNT: u64 = 500;
+/// Default and maximum number of matches returned in one page of a cross-agent oplog search
+pub const OPLOG_SEARCH_DEFAULT_MAX_RESULTS: u64 = 1000;
+pub const OPLOG_SEARCH_MAX_RESULTS: u64 = 10000;
+
+/// The agents a cross-agent oplog search runs over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OplogSearchScope {
+    Component(ComponentId),
+    /// Every deployed component of the environment the caller can view the agents of
+    Environment(EnvironmentId),
+}
+
+/// Position to continue a cross-agent oplog search from, passed to clients as an opaque token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OplogSearchCursor {
+    /// The component whose agents are being scanned
+    pub component_id: ComponentId,
+    /// Start of the page of agents being scanned
+    pub agent_scan_cursor: ScanCursor,
+    /// Size of that page, so the same agents are listed again when continuing inside it
+    pub agent_count: u64,
+    /// Number of agents of the page that were completely searched
+    pub agents_done: usize,
+    /// Where to continue in the oplog of the first agent that was not completely searched
+    pub oplog_cursor: Option<OplogCursor>,
+}
+
+impl OplogSearchCursor {
+    pub fn start_of(component_id: ComponentId, agent_scan_cursor: ScanCursor) -> Self {
+        Self {
+            component_id,
+            agent_scan_cursor,
+            agent_count: 0,
+            agents_done: 0,
+            oplog_cursor: None,
+        }
+    }
+
+    /// Whether the cursor points into a page of agents that was already partially searched
+    pub fn is_inside_page(&self) -> bool {
+        self.agents_done > 0 || self.oplog_cursor.is_some()
+    }
+
+    pub fn to_token(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).expect("cursor is serializable"))
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, String> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|err| format!("Invalid oplog search cursor: {err}"))?;
+        serde_json::from_slice(&bytes).map_err(|err| format!("Invalid oplog search cursor: {err}"))
+    }
+}
+
+/// How far the matches of one agent were taken into a search page
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum AgentSearchProgress {
+    Finished,
+    /// The page is full; the search continues at `next` in the agent's oplog, or with the next
+    /// agent if there is nothing left to search in this one
+    LimitReached {
+        next: Option<OplogCursor>,
+    },
+}
+
+/// Appends the matches of one agent's oplog search pages, stopping after the first page that
+/// fills the result up to `max_results`.
+pub(super) fn append_agent_matches(
+    matches: &mut Vec<AgentOplogSearchMatch>,
+    agent_id: &AgentId,
+    pages: Vec<GetOplogResponse>,
+    max_results: usize,
+) -> AgentSearchProgress {
+    for page in pages {
+        matches.extend(page.entries.into_iter().map(|entry| AgentOplogSearchMatch {
+            agent_id: agent_id.clone(),
+            oplog_index: entry.oplog_index,
+            entry: entry.entry,
+        }));
+
+        if matches.len() >= max_results {
+            return AgentSearchProgress::LimitReached { next: page.next };
+        }
+    }
+    AgentSearchProgress::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::Timestamp;
+    use golem_common::model::oplog::{
+        OplogIndex, PublicOplogEntry, PublicOplogEntryWithIndex, public_oplog_entry::NoOpParams,
+    };
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn agent_id() -> AgentId {
+        AgentId {
+            component_id: ComponentId(Uuid::new_v4()),
+            agent_id: "agent-1".to_string(),
+        }
+    }
+
+    fn page(indexes: &[u64], next: Option<u64>) -> GetOplogResponse {
+        GetOplogResponse {
+            entries: indexes
+                .iter()
+                .map(|idx| PublicOplogEntryWithIndex {
+                    oplog_index: OplogIndex::from_u64(*idx),
+                    entry: PublicOplogEntry::NoOp(NoOpParams {
+                        timestamp: Timestamp::now_utc(),
+                    }),
+                })
+                .collect(),
+            next: next.map(|next_oplog_index| OplogCursor {
+                next_oplog_index,
+                current_component_revision: 0,
+            }),
+            first_index_in_chunk: 0,
+            last_index: 0,
+        }
+    }
+
+    #[test]
+    fn cursor_token_round_trips() {
+        let cursor = OplogSearchCursor {
+            component_id: ComponentId(Uuid::new_v4()),
+            agent_scan_cursor: ScanCursor {
+                cursor: 42,
+                layer: 1,
+            },
+            agent_count: 50,
+            agents_done: 3,
+            oplog_cursor: Some(OplogCursor {
+                next_oplog_index: 120,
+                current_component_revision: 2,
+            }),
+        };
+
+        assert_eq!(
+            OplogSearchCursor::from_token(&cursor.to_token()),
+            Ok(cursor)
+        );
+        assert!(OplogSearchCursor::from_token("not a cursor").is_err());
+    }
+
+    #[test]
+    fn all_pages_are_taken_below_the_limit() {
+        let agent_id = agent_id();
+        let mut matches = Vec::new();
+
+        let progress = append_agent_matches(
+            &mut matches,
+            &agent_id,
+            vec![page(&[1, 2], Some(3)), page(&[5], None)],
+            10,
+        );
+
+        assert_eq!(progress, AgentSearchProgress::Finished);
+        assert_eq!(matches.len(), 3);
+    }
+
+    #[test]
+    fn search_continues_after_the_page_reaching_the_limit() {
+        let agent_id = agent_id();
+        let mut matches = Vec::new();
+
+        let progress = append_agent_matches(
+            &mut matches,
+            &agent_id,
+            vec![
+                page(&[1, 2], Some(3)),
+                page(&[5, 6], Some(7)),
+                page(&[9], None),
+            ],
+            3,
+        );
+
+        assert_eq!(
+            progress,
+            AgentSearchProgress::LimitReached {
+                next: Some(OplogCursor {
+                    next_oplog_index: 7,
+                    current_component_revision: 0,
+                })
+            }
+        );
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| u64::from(m.oplog_index))
+                .collect::<Vec<_>>(),
+            vec![1, 2, 5, 6]
+        );
+    }
+
+    #[test]
+    fn limit_reached_on_the_last_page_continues_with_the_next_agent() {
+        let agent_id = agent_id();
+        let mut matches = Vec::new();
+
+        let progress = append_agent_matches(&mut matches, &agent_id, vec![page(&[1, 2], None)], 2);
+
+        assert_eq!(progress, AgentSearchProgress::LimitReached { next: None });
+    }
+}
@@ -13,7 +13,11 @@
 // limitations under the License.
 
 use super::WorkerResult;
-use super::{ConnectWorkerStream, WorkerClient, WorkerServiceError};
+use super::oplog_search::{AgentSearchProgress, append_agent_matches};
+use super::{
+    ConnectWorkerStream, OPLOG_SEARCH_MAX_AGENT_COUNT, OPLOG_SEARCH_MAX_RESULTS, OplogSearchCursor,
+    OplogSearchScope, WorkerClient, WorkerServiceError,
+};
 use crate::api::agents::{
     AgentInvocationMode, AgentInvocationRequest, AgentInvocationResult, CreateAgentRequest,
     CreateAgentResponse,
@@ -23,14 +27,17 @@ use crate::service::auth::{AuthService, AuthServiceError};
 use crate::service::component::ComponentService;
 use crate::service::limit::LimitService;
 use bytes::Bytes;
-use futures::Stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
 use golem_api_grpc::proto::golem::worker::InvocationContext;
+use golem_api_grpc::proto::golem::workerexecutor;
 use golem_common::model::AgentInvocationOutput;
 use golem_common::model::account::AccountId;
 use golem_common::model::agent::{
     AgentMode, AgentTypeName, DataValue, GolemUserPrincipal, LegacyParsedAgentId, Principal,
-    UntypedDataValue,
+    ResolvedAgentType, UntypedDataValue,
 };
+use golem_common::model::application::ApplicationName;
 use golem_common::model::card::owner::{AgentOwnerLeafPattern, AgentOwnerPattern};
 use golem_common::model::card::{
     AgentMethodName, AgentResourcePattern, AgentVerb, ClassPermissionTarget, PermissionTarget,
@@ -39,19 +46,33 @@ use golem_common::model::component::{
     CanonicalFilePath, ComponentId, ComponentName, ComponentRevision, PluginPriority,
 };
 use golem_common::model::deployment::DeploymentRevision;
-use golem_common::model::environment::EnvironmentId;
-use golem_common::model::oplog::OplogCursor;
+use golem_common::model::environment::{EnvironmentId, EnvironmentName};
+use golem_common::model::invocation_context::{
+    AttributeValue, DEADLINE_ATTRIBUTE, InvocationContextSpan, InvocationContextStack, TraceId,
+};
 use golem_common::model::oplog::OplogIndex;
+use golem_common::model::oplog::{AgentOplogSearchMatch, OplogCursor};
 use golem_common::model::worker::AgentConfigEntryDto;
 use golem_common::model::worker::AgentUpdateMode;
-use golem_common::model::worker::{AgentMetadataDto, RevertWorkerTarget};
-use golem_common::model::{AgentFilter, AgentFingerprint, AgentId, IdempotencyKey, ScanCursor};
+use golem_common::model::worker::{AgentLifecycleAuditEntry, AgentMetadataDto, RevertWorkerTarget};
+use golem_common::model::{
+    AgentFilter, AgentFingerprint, AgentId, IdempotencyKey, ScanCursor, Timestamp,
+};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::model::auth::AuthCtx;
 use golem_service_base::model::component::Component;
 use golem_service_base::model::{ComponentFileSystemNode, GetOplogResponse};
 use std::pin::Pin;
 use std::{collections::HashMap, sync::Arc};
 
+/// Number of matching entries requested per agent oplog page during cross-agent searches
+const OPLOG_SEARCH_PAGE_SIZE: u64 = 100;
+/// Number of agent oplogs searched at the same time during cross-agent searches
+const OPLOG_SEARCH_CONCURRENCY: usize = 8;
+/// Default and maximum number of lifecycle audit entries returned in one page
+const LIFECYCLE_AUDIT_DEFAULT_PAGE_SIZE: u64 = 100;
+const LIFECYCLE_AUDIT_MAX_PAGE_SIZE: u64 = 1000;
+
 fn build_public_agent_id(
     component_id: ComponentId,
     agent_type_name: AgentTypeName,
@@ -127,6 +148,30 @@ fn authorize_component_agents_permission(
     Ok(())
 }
 
+/// Invocation of an agent method whose parameters are already typed against the resolved agent
+/// type, shared by the REST and the agent gRPC APIs.
+pub struct TypedAgentInvocation {
+    pub app_name: ApplicationName,
+    pub env_name: EnvironmentName,
+    pub agent_type_name: AgentTypeName,
+    pub parameters: DataValue,
+    pub phantom_id: Option<uuid::Uuid>,
+    pub method_name: String,
+    pub method_parameters: DataValue,
+    pub mode: AgentInvocationMode,
+    pub schedule_at: Option<DateTime<Utc>>,
+    pub idempotency_key: Option<IdempotencyKey>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub at_oplog_index: Option<OplogIndex>,
+    pub at_timestamp: Option<DateTime<Utc>>,
+}
+
+pub struct TypedAgentInvocationResult {
+    pub agent_id: AgentId,
+    pub result: Option<DataValue>,
+    pub component_revision: Option<ComponentRevision>,
+}
+
 pub struct WorkerService {
     component_service: Arc<dyn ComponentService>,
     _auth_service: Arc<dyn AuthService>,
@@ -224,6 +269,7 @@ impl WorkerService {
         &self,
         agent_id: &AgentId,
         auth_ctx: AuthCtx,
+        websocket_connection: Option<workerexecutor::v1::WebSocketConnection>,
     ) -> WorkerResult<ConnectWorkerStream> {
         let component = self
             .component_service
@@ -245,6 +291,7 @@ impl WorkerService {
                 component.environment_id,
                 component.account_id,
                 auth_ctx,
+                websocket_connection,
             )
             .await?;
 
@@ -528,6 +575,8 @@ impl WorkerService {
                 cursor,
                 count,
                 query,
+                None,
+                None,
                 component.environment_id,
                 auth_ctx,
             )
@@ -536,6 +585,245 @@ impl WorkerService {
         Ok(result)
     }
 
+    /// Runs an oplog query over every agent of a component or an environment.
+    ///
+    /// Each page lists up to `count` agents of one component, searches their oplogs
+    /// concurrently and returns their matching entries that fall into the optional `[from, to]`
+    /// time range, stopping after the agent oplog page that reaches `max_results` matches. The
+    /// returned cursor continues the search, also in the middle of an agent's oplog.
+    pub async fn search_oplogs(
+        &self,
+        scope: OplogSearchScope,
+        filter: Option<AgentFilter>,
+        query: String,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        cursor: Option<OplogSearchCursor>,
+        count: u64,
+        max_results: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<(Vec<AgentOplogSearchMatch>, Option<OplogSearchCursor>)> {
+        let components = match scope {
+            OplogSearchScope::Component(component_id) => {
+                let component = self
+                    .component_service
+                    .get_current_by_id(component_id)
+                    .await?;
+
+                authorize_component_agents_permission(
+                    &auth_ctx,
+                    &component,
+                    AgentVerb::View,
+                    AgentResourcePattern::Any,
+                )?;
+
+                vec![component]
+            }
+            OplogSearchScope::Environment(environment_id) => {
+                let mut components: Vec<Component> = self
+                    .component_service
+                    .get_all_deployed(environment_id)
+                    .await?
+                    .into_iter()
+                    .filter(|component| {
+                        authorize_component_agents_permission(
+                            &auth_ctx,
+                            component,
+                            AgentVerb::View,
+                            AgentResourcePattern::Any,
+                        )
+                        .is_ok()
+                    })
+                    .collect();
+                components.sort_by_key(|component| component.id);
+                components
+            }
+        };
+
+        let count = count.clamp(1, OPLOG_SEARCH_MAX_AGENT_COUNT);
+        let max_results = max_results.clamp(1, OPLOG_SEARCH_MAX_RESULTS) as usize;
+
+        // Components are searched in id order, so a cursor continues at the component it points
+        // to, or at the next one if that is no longer deployed
+        let position = match &cursor {
+            Some(cursor) => components
+                .iter()
+                .position(|component| component.id >= cursor.component_id),
+            None => (!components.is_empty()).then_some(0),
+        };
+        let Some(position) = position else {
+            return Ok((Vec::new(), None));
+        };
+        let component = &components[position];
+        let cursor = match cursor {
+            Some(cursor) if cursor.component_id == component.id => cursor,
+            _ => OplogSearchCursor::start_of(component.id, ScanCursor::default()),
+        };
+        let page_size = if cursor.is_inside_page() {
+            cursor.agent_count.clamp(1, OPLOG_SEARCH_MAX_AGENT_COUNT)
+        } else {
+            count
+        };
+
+        let (next_scan_cursor, agents) = self
+            .worker_client
+            .find_metadata(
+                component.id,
+                filter,
+                cursor.agent_scan_cursor.clone(),
+                page_size,
+                false,
+                component.environment_id,
+                auth_ctx.clone(),
+            )
+            .await?;
+
+        let environment_id = component.environment_id;
+        let resume_at = cursor.agents_done;
+        let resume_oplog_cursor = cursor.oplog_cursor.clone();
+        let mut searches = futures::stream::iter(agents.into_iter().enumerate().skip(resume_at))
+            .map(move |(agent_index, agent)| {
+                let start = if agent_index == resume_at {
+                    resume_oplog_cursor.clone()
+                } else {
+                    None
+                };
+                let query = query.clone();
+                let auth_ctx = auth_ctx.clone();
+                async move {
+                    let pages = self
+                        .search_agent_oplog(
+                            &agent.agent_id,
+                            start,
+                            query,
+                            from,
+                            to,
+                            max_results,
+                            environment_id,
+                            auth_ctx,
+                        )
+                        .await?;
+                    Ok::<_, WorkerServiceError>((agent_index, agent.agent_id, pages))
+                }
+            })
+            .buffered(OPLOG_SEARCH_CONCURRENCY);
+
+        let mut matches = Vec::new();
+        while let Some(result) = searches.next().await {
+            let (agent_index, agent_id, pages) = result?;
+            if let AgentSearchProgress::LimitReached { next } =
+                append_agent_matches(&mut matches, &agent_id, pages, max_results)
+            {
+                let agents_done = if next.is_some() {
+                    agent_index
+                } else {
+                    agent_index + 1
+                };
+                return Ok((
+                    matches,
+                    Some(OplogSearchCursor {
+                        component_id: component.id,
+                        agent_scan_cursor: cursor.agent_scan_cursor,
+                        agent_count: page_size,
+                        agents_done,
+                        oplog_cursor: next,
+                    }),
+                ));
+            }
+        }
+
+        let next_cursor = match next_scan_cursor {
+            Some(next_scan_cursor) => {
+                Some(OplogSearchCursor::start_of(component.id, next_scan_cursor))
+            }
+            None => components.get(position + 1).map(|next_component| {
+                OplogSearchCursor::start_of(next_component.id, ScanCursor::default())
+            }),
+        };
+
+        Ok((matches, next_cursor))
+    }
+
+    /// Searches the oplog of one agent from `start`, until at least `max_results` matching
+    /// entries are found, returning the pages read.
+    async fn search_agent_oplog(
+        &self,
+        agent_id: &AgentId,
+        start: Option<OplogCursor>,
+        query: String,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        max_results: usize,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<GetOplogResponse>> {
+        let mut pages = Vec::new();
+        let mut found = 0;
+        let mut oplog_cursor = start;
+        loop {
+            let page = self
+                .worker_client
+                .search_oplog(
+                    agent_id,
+                    oplog_cursor,
+                    OPLOG_SEARCH_PAGE_SIZE,
+                    query.clone(),
+                    from,
+                    to,
+                    environment_id,
+                    auth_ctx.clone(),
+                )
+                .await?;
+
+            found += page.entries.len();
+            oplog_cursor = page.next.clone();
+            pages.push(page);
+
+            if oplog_cursor.is_none() || found >= max_results {
+                break;
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Gets the lifecycle policy actions applied to the agents of a component between `from`
+    /// and `to`, oldest first. Returns the time the next page starts at, if there is one.
+    pub async fn get_lifecycle_audit(
+        &self,
+        component_id: ComponentId,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        count: Option<u64>,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<(Vec<AgentLifecycleAuditEntry>, Option<Timestamp>)> {
+        let component = self
+            .component_service
+            .get_current_by_id(component_id)
+            .await?;
+
+        authorize_component_agents_permission(
+            &auth_ctx,
+            &component,
+            AgentVerb::View,
+            AgentResourcePattern::Any,
+        )?;
+
+        let count = count
+            .unwrap_or(LIFECYCLE_AUDIT_DEFAULT_PAGE_SIZE)
+            .clamp(1, LIFECYCLE_AUDIT_MAX_PAGE_SIZE);
+
+        self.worker_client
+            .get_lifecycle_audit(
+                component_id,
+                component.environment_id,
+                from,
+                to,
+                count,
+                auth_ctx,
+            )
+            .await
+    }
+
     pub async fn get_file_system_node(
         &self,
         agent_id: &AgentId,
@@ -699,6 +987,55 @@ impl WorkerService {
         Ok(())
     }
 
+    pub async fn export_agent(
+        &self,
+        agent_id: &AgentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>> {
+        let component = self
+            .component_service
+            .get_current_by_id(agent_id.component_id)
+            .await?;
+
+        authorize_agent_permission(
+            &auth_ctx,
+            &component,
+            agent_id,
+            AgentVerb::View,
+            AgentResourcePattern::Any,
+        )?;
+
+        self.worker_client
+            .export_agent(agent_id, component.environment_id, auth_ctx)
+            .await
+    }
+
+    /// Imports an agent archive as `target_agent_id`. Importing is authorized like forking, as
+    /// both create a new agent from an existing oplog.
+    pub async fn import_agent(
+        &self,
+        target_agent_id: &AgentId,
+        archive: Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()> {
+        let component = self
+            .component_service
+            .get_current_by_id(target_agent_id.component_id)
+            .await?;
+
+        authorize_agent_permission(
+            &auth_ctx,
+            &component,
+            target_agent_id,
+            AgentVerb::Fork,
+            AgentResourcePattern::Any,
+        )?;
+
+        self.worker_client
+            .import_agent(target_agent_id, archive, component.environment_id, auth_ctx)
+            .await
+    }
+
     pub async fn revert_worker(
         &self,
         agent_id: &AgentId,
@@ -845,6 +1182,7 @@ impl WorkerService {
                 method_parameters,
                 mode,
                 schedule_at,
+                None,
                 idempotency_key,
                 invocation_context,
                 environment_id,
@@ -928,8 +1266,88 @@ impl WorkerService {
         request: AgentInvocationRequest,
         auth: AuthCtx,
     ) -> WorkerResult<AgentInvocationResult> {
-        let deployment_revision = request
-            .deployment_revision
+        let resolved = self
+            .resolve_agent_type(
+                &request.app_name,
+                &request.env_name,
+                &request.agent_type_name,
+                request.deployment_revision,
+                request.owner_account_email.as_deref(),
+                &auth,
+            )
+            .await?;
+        let agent_type = &resolved.registered_agent_type.agent_type;
+
+        let constructor_parameters: DataValue = DataValue::try_from_untyped_json(
+            request.parameters,
+            agent_type.constructor.input_schema.clone(),
+        )
+        .map_err(|err| {
+            WorkerServiceError::TypeChecker(format!(
+                "Agent constructor parameters type error: {err}"
+            ))
+        })?;
+
+        let method = agent_type
+            .methods
+            .iter()
+            .find(|m| m.name == request.method_name)
+            .ok_or_else(|| {
+                WorkerServiceError::Internal(format!(
+                    "Agent method {} not found in agent type {}",
+                    request.method_name, request.agent_type_name
+                ))
+            })?;
+
+        let method_parameters: DataValue = DataValue::try_from_untyped_json(
+            request.method_parameters,
+            method.input_schema.clone(),
+        )
+        .map_err(|err| {
+            WorkerServiceError::TypeChecker(format!("Agent method parameters type error: {err}"))
+        })?;
+
+        let result = self
+            .invoke_resolved_agent(
+                resolved,
+                TypedAgentInvocation {
+                    app_name: request.app_name,
+                    env_name: request.env_name,
+                    agent_type_name: request.agent_type_name,
+                    parameters: constructor_parameters,
+                    phantom_id: request.phantom_id,
+                    method_name: request.method_name,
+                    method_parameters,
+                    mode: request.mode,
+                    schedule_at: request.schedule_at,
+                    idempotency_key: request.idempotency_key,
+                    deadline: request.deadline,
+                    at_oplog_index: request.at_oplog_index,
+                    at_timestamp: request.at_timestamp,
+                },
+                auth,
+            )
+            .await?;
+
+        Ok(AgentInvocationResult {
+            agent_id: result.agent_id,
+            result: result.result.map(|value| value.into()),
+            component_revision: result.component_revision,
+        })
+    }
+
+    /// Resolves a deployed agent type, either at the environment's current deployment or at an
+    /// explicitly requested deployment revision.
+    pub async fn resolve_agent_type(
+        &self,
+        app_name: &ApplicationName,
+        env_name: &EnvironmentName,
+        agent_type_name: &AgentTypeName,
+        deployment_revision: Option<i64>,
+        owner_account_email: Option<&str>,
+        auth: &AuthCtx,
+    ) -> WorkerResult<ResolvedAgentType> {
+        let deployment_revision = deployment_revision
             .map(|rev| {
                 let rev_u64 = u64::try_from(rev).map_err(|_| {
                     WorkerServiceError::Internal(format!(
@@ -946,48 +1364,49 @@ impl WorkerService {
             None => {
                 self.agent_resolution_cache
                     .resolve(
-                        &request.app_name,
-                        &request.env_name,
-                        &request.agent_type_name,
-                        request.owner_account_email.as_deref(),
-                        &auth,
+                        app_name,
+                        env_name,
+                        agent_type_name,
+                        owner_account_email,
+                        auth,
                     )
                     .await?
             }
             Some(rev) => {
                 self.agent_resolution_cache
                     .resolve_pinned(
-                        &request.app_name,
-                        &request.env_name,
-                        &request.agent_type_name,
+                        app_name,
+                        env_name,
+                        agent_type_name,
                         rev,
-                        request.owner_account_email.as_deref(),
-                        &auth,
+                        owner_account_email,
+                        auth,
                     )
                     .await?
             }
         };
 
+        Ok(resolved)
+    }
+
+    /// Invokes a method of an already resolved agent type with typed parameters, decoding the
+    /// result with the agent type of the component revision that produced it.
+    pub async fn invoke_resolved_agent(
+        &self,
+        resolved: ResolvedAgentType,
+        invocation: TypedAgentInvocation,
+        auth: AuthCtx,
+    ) -> WorkerResult<TypedAgentInvocationResult> {
         let registered_agent_type = resolved.registered_agent_type;
         let environment_id = resolved.environment_id;
         let component_id = registered_agent_type.implemented_by.component_id;
         let agent_type = &registered_agent_type.agent_type;
 
-        let constructor_parameters: DataValue = DataValue::try_from_untyped_json(
-            request.parameters,
-            agent_type.constructor.input_schema.clone(),
-        )
-        .map_err(|err| {
-            WorkerServiceError::TypeChecker(format!(
-                "Agent constructor parameters type error: {err}"
-            ))
-        })?;
-
         let agent_id = build_public_agent_id(
             component_id,
-            request.agent_type_name.clone(),
-            constructor_parameters,
-            request.phantom_id,
+            invocation.agent_type_name.clone(),
+            invocation.parameters,
+            invocation.phantom_id,
             agent_type.mode,
         )?;
 
@@ -996,29 +1415,10 @@ impl WorkerService {
         let component_owner_account_email =
             registered_agent_type.implemented_by.account_email.clone();
 
-        let method = agent_type
-            .methods
-            .iter()
-            .find(|m| m.name == request.method_name)
-            .ok_or_else(|| {
-                WorkerServiceError::Internal(format!(
-                    "Agent method {} not found in agent type {}",
-                    request.method_name, request.agent_type_name
-                ))
-            })?;
-
-        let method_parameters: DataValue = DataValue::try_from_untyped_json(
-            request.method_parameters,
-            method.input_schema.clone(),
-        )
-        .map_err(|err| {
-            WorkerServiceError::TypeChecker(format!("Agent method parameters type error: {err}"))
-        })?;
-
         let proto_method_parameters: golem_api_grpc::proto::golem::component::UntypedDataValue =
-            UntypedDataValue::from(method_parameters).into();
+            UntypedDataValue::from(invocation.method_parameters).into();
 
-        let proto_mode = match request.mode {
+        let proto_mode = match invocation.mode {
             AgentInvocationMode::Await => {
                 golem_api_grpc::proto::golem::worker::AgentInvocationMode::Await as i32
             }
@@ -1027,7 +1427,7 @@ impl WorkerService {
             }
         };
 
-        let proto_schedule_at = request.schedule_at.map(|dt| ::prost_types::Timestamp {
+        let proto_schedule_at = invocation.schedule_at.map(|dt| ::prost_types::Timestamp {
             seconds: dt.timestamp(),
             nanos: dt.timestamp_subsec_nanos() as i32,
         });
@@ -1038,14 +1438,23 @@ impl WorkerService {
             })
             .into();
 
-        let method_name = request.method_name.clone();
-        let agent_type_name = request.agent_type_name.clone();
+        let invocation_context = invocation.deadline.map(invocation_context_with_deadline);
+        let at = time_travel_target(invocation.at_oplog_index, invocation.at_timestamp)?;
+        if at.is_some() && !matches!(invocation.mode, AgentInvocationMode::Await) {
+            return Err(WorkerExecutorError::invalid_request(
+                "Time-travel invocations can only be awaited",
+            )
+            .into());
+        }
+
+        let method_name = invocation.method_name;
+        let agent_type_name = invocation.agent_type_name;
 
         auth.authorize_permission(&PermissionTarget::Agent(ClassPermissionTarget {
             owner: AgentOwnerPattern::Agent {
                 account: component_owner_account_email,
-                application: request.app_name,
-                environment: request.env_name,
+                application: invocation.app_name,
+                environment: invocation.env_name,
                 component: ComponentName(component_name),
                 agent: AgentOwnerLeafPattern::Agent(agent_id.agent_id.clone()),
             },
@@ -1062,8 +1471,9 @@ impl WorkerService {
                 Some(proto_method_parameters),
                 proto_mode,
                 proto_schedule_at,
-                request.idempotency_key,
-                None,
+                at,
+                invocation.idempotency_key,
+                invocation_context,
                 environment_id,
                 component_owner_account_id,
                 auth,
@@ -1106,13 +1516,13 @@ impl WorkerService {
                 .map_err(|err| {
                     WorkerServiceError::TypeChecker(format!("DataValue conversion error: {err}"))
                 })?;
-                Ok(AgentInvocationResult {
+                Ok(TypedAgentInvocationResult {
                     agent_id: agent_id.clone(),
-                    result: Some(typed_data_value.into()),
+                    result: Some(typed_data_value),
                     component_revision: Some(decode_revision),
                 })
             }
-            _ => Ok(AgentInvocationResult {
+            _ => Ok(TypedAgentInvocationResult {
                 agent_id,
                 result: None,
                 component_revision: output.component_revision,
@@ -1121,9 +1531,59 @@ impl WorkerService {
     }
 }
 
+/// Starts a fresh trace whose root span carries the caller-provided invocation deadline, so the
+/// executor enforces it and propagates it to agent-to-agent calls.
+fn invocation_context_with_deadline(deadline: DateTime<Utc>) -> InvocationContext {
+    let deadline = Timestamp::from(deadline.timestamp_millis().max(0) as u64);
+    let stack = InvocationContextStack::new(
+        TraceId::generate(),
+        InvocationContextSpan::local()
+            .with_attributes(HashMap::from([(
+                DEADLINE_ATTRIBUTE.to_string(),
+                AttributeValue::String(deadline.to_string()),
+            )]))
+            .build(),
+        Vec::new(),
+    );
+    InvocationContext {
+        parent: None,
+        env: Default::default(),
+        tracing: Some(stack.into()),
+    }
+}
+
+/// Point of the agent's oplog a time-travel invocation replays the agent up to, if requested
+fn time_travel_target(
+    at_oplog_index: Option<OplogIndex>,
+    at_timestamp: Option<DateTime<Utc>>,
+) -> WorkerResult<Option<workerexecutor::v1::TimeTravelTarget>> {
+    use workerexecutor::v1::time_travel_target::Target;
+
+    let target = match (at_oplog_index, at_timestamp) {
+        (None, None) => return Ok(None),
+        (Some(_), Some(_)) => {
+            return Err(WorkerExecutorError::invalid_request(
+                "Only one of atOplogIndex and atTimestamp can be specified",
+            )
+            .into());
+        }
+        (Some(idx), None) => Target::OplogIndex(u64::from(idx)),
+        (None, Some(timestamp)) => Target::Timestamp(::prost_types::Timestamp {
+            seconds: timestamp.timestamp(),
+            nanos: timestamp.timestamp_subsec_nanos() as i32,
+        }),
+    };
+    Ok(Some(workerexecutor::v1::TimeTravelTarget {
+        target: Some(target),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{WorkerService, agent_verb_for_invocation_mode};
+    use super::{
+        WorkerService, agent_verb_for_invocation_mode, invocation_context_with_deadline,
+        time_travel_target,
+    };
     use crate::api::agents::{AgentInvocationMode, AgentInvocationRequest, CreateAgentRequest};
     use crate::service::agent_resolution_cache::AgentResolutionCache;
     use crate::service::auth::{AuthService, AuthServiceError};
@@ -1135,6 +1595,7 @@ mod tests {
     use chrono::Utc;
     use futures::Stream;
     use golem_api_grpc::proto::golem::worker::{InvocationContext, LogEvent};
+    use golem_api_grpc::proto::golem::workerexecutor;
     use golem_common::base_model::component_metadata::KnownExports;
     use golem_common::model::AgentInvocationOutput;
     use golem_common::model::Empty;
@@ -1153,9 +1614,14 @@ mod tests {
     use golem_common::model::deployment::{CurrentDeploymentRevision, DeploymentRevision};
     use golem_common::model::diff::Hash;
     use golem_common::model::environment::{EnvironmentId, EnvironmentName};
+    use golem_common::model::invocation_context::InvocationContextStack;
     use golem_common::model::oplog::{OplogCursor, OplogIndex};
-    use golem_common::model::worker::{AgentConfigEntryDto, AgentMetadataDto, RevertWorkerTarget};
-    use golem_common::model::{AgentFilter, AgentFingerprint, AgentId, IdempotencyKey, ScanCursor};
+    use golem_common::model::worker::{
+        AgentConfigEntryDto, AgentLifecycleAuditEntry, AgentMetadataDto, RevertWorkerTarget,
+    };
+    use golem_common::model::{
+        AgentFilter, AgentFingerprint, AgentId, IdempotencyKey, ScanCursor, Timestamp,
+    };
     use golem_service_base::clients::registry::{RegistryService, RegistryServiceError};
     use golem_service_base::model::auth::AuthCtx;
     use golem_service_base::model::component::Component;
@@ -1235,6 +1701,13 @@ mod tests {
             unimplemented!()
         }
 
+        async fn get_deployed_components(
+            &self,
+            _: EnvironmentId,
+        ) -> Result<Vec<Component>, RegistryServiceError> {
+            unimplemented!()
+        }
+
         async fn resolve_component(
             &self,
             _: AccountId,
@@ -1276,6 +1749,17 @@ mod tests {
             Ok(self.resolved.clone())
         }
 
+        async fn list_agent_types_by_names(
+            &self,
+            _: &ApplicationName,
+            _: &EnvironmentName,
+            _: Option<DeploymentRevision>,
+            _: Option<&str>,
+            _: &AuthCtx,
+        ) -> Result<Vec<ResolvedAgentType>, RegistryServiceError> {
+            Ok(vec![self.resolved.clone()])
+        }
+
         async fn get_active_routes_for_domain(
             &self,
             _: &golem_common::model::domain_registration::Domain,
@@ -1385,6 +1869,17 @@ mod tests {
                 Err(ComponentServiceError::ComponentNotFound)
             }
         }
+
+        async fn get_all_deployed(
+            &self,
+            environment_id: EnvironmentId,
+        ) -> Result<Vec<Component>, ComponentServiceError> {
+            if self.component.environment_id == environment_id {
+                Ok(vec![self.component.clone()])
+            } else {
+                Ok(Vec::new())
+            }
+        }
     }
 
     struct AllowAllAuthService;
@@ -1464,6 +1959,7 @@ mod tests {
             _: EnvironmentId,
             _: AccountId,
             _: AuthCtx,
+            _: Option<workerexecutor::v1::WebSocketConnection>,
         ) -> WorkerResult<WorkerStream<LogEvent>> {
             unimplemented!()
         }
@@ -1555,6 +2051,8 @@ mod tests {
             _: Option<OplogCursor>,
             _: u64,
             _: String,
+            _: Option<Timestamp>,
+            _: Option<Timestamp>,
             _: EnvironmentId,
             _: AuthCtx,
         ) -> Result<GetOplogResponse, super::WorkerServiceError> {
@@ -1617,6 +2115,26 @@ mod tests {
             unimplemented!()
         }
 
+        async fn export_agent(
+            &self,
+            _: &AgentId,
+            _: EnvironmentId,
+            _: AuthCtx,
+        ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>>
+        {
+            unimplemented!()
+        }
+
+        async fn import_agent(
+            &self,
+            _: &AgentId,
+            _: Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>,
+            _: EnvironmentId,
+            _: AuthCtx,
+        ) -> WorkerResult<()> {
+            unimplemented!()
+        }
+
         async fn revert_worker(
             &self,
             _: &AgentId,
@@ -1644,6 +2162,7 @@ mod tests {
             _: Option<golem_api_grpc::proto::golem::component::UntypedDataValue>,
             _: i32,
             _: Option<::prost_types::Timestamp>,
+            _: Option<workerexecutor::v1::TimeTravelTarget>,
             _: Option<IdempotencyKey>,
             _: Option<InvocationContext>,
             _: EnvironmentId,
@@ -1673,6 +2192,18 @@ mod tests {
         ) -> WorkerResult<()> {
             unimplemented!()
         }
+
+        async fn get_lifecycle_audit(
+            &self,
+            _: ComponentId,
+            _: EnvironmentId,
+            _: Option<Timestamp>,
+            _: Option<Timestamp>,
+            _: u64,
+            _: AuthCtx,
+        ) -> WorkerResult<(Vec<AgentLifecycleAuditEntry>, Option<Timestamp>)> {
+            unimplemented!()
+        }
     }
 
     struct RestHarness {
@@ -1770,6 +2301,9 @@ mod tests {
                 idempotency_key: None,
                 deployment_revision: None,
                 owner_account_email: None,
+                deadline: None,
+                at_oplog_index: None,
+                at_timestamp: None,
             }
         }
     }
@@ -1802,6 +2336,7 @@ mod tests {
                     },
                 }],
                 read_only: None,
+                timeout_nanos: None,
             }],
             dependencies: vec![],
             mode,
@@ -1878,6 +2413,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rest_deadline_is_recorded_on_the_invocation_context() {
+        let deadline = Utc::now();
+        let context = invocation_context_with_deadline(deadline);
+        let stack = InvocationContextStack::try_from(context.tracing.unwrap()).unwrap();
+
+        assert_eq!(
+            stack.spans.first().effective_deadline(),
+            Some(Timestamp::from(deadline.timestamp_millis() as u64))
+        );
+    }
+
+    #[test]
+    fn rest_time_travel_target_accepts_either_oplog_index_or_timestamp() {
+        use workerexecutor::v1::time_travel_target::Target;
+
+        assert_eq!(time_travel_target(None, None).unwrap(), None);
+        assert_eq!(
+            time_travel_target(Some(OplogIndex::from_u64(5)), None)
+                .unwrap()
+                .and_then(|at| at.target),
+            Some(Target::OplogIndex(5))
+        );
+
+        let timestamp = Utc::now();
+        assert_eq!(
+            time_travel_target(None, Some(timestamp))
+                .unwrap()
+                .and_then(|at| at.target),
+            Some(Target::Timestamp(::prost_types::Timestamp {
+                seconds: timestamp.timestamp(),
+                nanos: timestamp.timestamp_subsec_nanos() as i32,
+            }))
+        );
+        assert!(time_travel_target(Some(OplogIndex::from_u64(5)), Some(timestamp)).is_err());
+    }
+
     #[test]
     async fn create_agent_rest_auto_generates_phantom_for_ephemeral_agents() {
         let harness = RestHarness::new(AgentMode::Ephemeral);
@@ -158,6 +158,7 @@ pub fn resolved_route_entry_with_oidc(scheme: Arc<SecuritySchemeDetails>) -> Res
         cors: CorsOptions {
             allowed_patterns: vec![OriginPattern("*".to_string())],
         },
+        rate_limits: vec![],
     };
 
     ResolvedRouteEntry {
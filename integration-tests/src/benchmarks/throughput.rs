@@ -623,6 +623,8 @@ impl ThroughputBenchmark {
                         HttpApiDeploymentAgentOptions::default(),
                     ),
                 ]),
+                rate_limits: Vec::new(),
+                graphql_endpoint: None,
             };
 
             client
@@ -782,6 +782,7 @@ async fn list_agent_types(deps: &EnvBasedTestDependencies) -> anyhow::Result<()>
             }),
             http_endpoint: Vec::new(),
             read_only: None,
+            timeout_nanos: None,
         }],
         dependencies: vec![],
         mode: AgentMode::Durable,
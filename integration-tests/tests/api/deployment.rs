@@ -371,6 +371,8 @@ async fn full_deployment(deps: &EnvBasedTestDependencies) -> anyhow::Result<()>
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment = client
@@ -49,6 +49,8 @@ async fn create_http_api_deployment_for_nonexitant_domain(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let result = client
@@ -82,6 +84,8 @@ async fn create_http_api_deployment(deps: &EnvBasedTestDependencies) -> anyhow::
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment = client
@@ -140,6 +144,8 @@ async fn create_http_api_deployment_normalizes_webhooks_url(
                 webhooks_prefix: "webhooks".to_string(),
                 openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(
                 ),
+                rate_limits: Vec::new(),
+                graphql_endpoint: None,
             },
         )
         .await?;
@@ -169,6 +175,8 @@ async fn update_http_api_deployment(deps: &EnvBasedTestDependencies) -> anyhow::
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment = client
@@ -189,6 +197,8 @@ async fn update_http_api_deployment(deps: &EnvBasedTestDependencies) -> anyhow::
         ])),
         webhook_prefix: Some("/webhooks2/".to_string()),
         openapi_endpoint_prefix: None,
+        rate_limits: None,
+        graphql_endpoint: None,
     };
 
     let updated_http_api_deployment = client
@@ -276,6 +286,8 @@ async fn update_http_api_deployment_normalizes_webhooks_url(
                 webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
                 openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(
                 ),
+                rate_limits: Vec::new(),
+                graphql_endpoint: None,
             },
         )
         .await?;
@@ -288,6 +300,8 @@ async fn update_http_api_deployment_normalizes_webhooks_url(
                 agents: None,
                 webhook_prefix: Some("webhooks2".to_string()),
                 openapi_endpoint_prefix: None,
+                rate_limits: None,
+                graphql_endpoint: None,
             },
         )
         .await?;
@@ -323,6 +337,8 @@ async fn update_http_api_deployment_openapi_endpoint_can_be_set_and_cleared(
                 webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
                 openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(
                 ),
+                rate_limits: Vec::new(),
+                graphql_endpoint: None,
             },
         )
         .await?;
@@ -335,6 +351,8 @@ async fn update_http_api_deployment_openapi_endpoint_can_be_set_and_cleared(
                 webhook_prefix: None,
                 openapi_endpoint_prefix: Some("/docs/".to_string()),
                 agents: None,
+                rate_limits: None,
+                graphql_endpoint: None,
             },
         )
         .await?;
@@ -364,6 +382,8 @@ async fn delete_http_api_deployment(deps: &EnvBasedTestDependencies) -> anyhow::
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment = client
@@ -430,6 +450,8 @@ async fn cannot_create_two_http_api_deployments_for_same_domain(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     client
@@ -469,6 +491,8 @@ async fn updates_with_wrong_revision_number_are_rejected(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment = client
@@ -489,6 +513,8 @@ async fn updates_with_wrong_revision_number_are_rejected(
                 HttpApiDeploymentAgentOptions::default(),
             ),
         ])),
+        rate_limits: None,
+        graphql_endpoint: None,
     };
 
     let result = client
@@ -522,6 +548,8 @@ async fn http_api_deployment_recreation(deps: &EnvBasedTestDependencies) -> anyh
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment_1 = client
@@ -577,6 +605,8 @@ async fn fetch_in_deployment(deps: &EnvBasedTestDependencies) -> anyhow::Result<
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let http_api_deployment = client
@@ -624,6 +654,8 @@ async fn cannot_access_http_api_deployment_from_another_user(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let deployment = client_a
@@ -665,6 +697,8 @@ async fn cannot_delete_http_api_deployment_from_another_user(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let deployment = client_a
@@ -702,6 +736,8 @@ async fn delete_with_wrong_revision_is_rejected(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let deployment = client
@@ -741,6 +777,8 @@ async fn deleting_twice_returns_404(deps: &EnvBasedTestDependencies) -> anyhow::
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     let deployment = client
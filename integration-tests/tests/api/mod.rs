@@ -22,6 +22,7 @@ mod domain_registration;
 mod environment;
 mod environment_plugin_grants;
 mod http_api_deployment;
+mod oplog_export_sinks;
 mod permission_share;
 mod plugin_registration;
 mod reports;
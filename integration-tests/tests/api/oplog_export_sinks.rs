@@ -0,0 +1,229 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_client::api::RegistryServiceClient;
+use golem_common::model::oplog_export::{
+    BlobStorageExportTarget, HttpExportTarget, OplogExportSinkCreation, OplogExportSinkRevision,
+    OplogExportSinkUpdate, OplogExportTarget,
+};
+use golem_common::{agent_id, data_value};
+use golem_test_framework::config::{EnvBasedTestDependencies, TestDependencies};
+use golem_test_framework::dsl::{TestDsl, TestDslExtended};
+use pretty_assertions::assert_eq;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(EnvBasedTestDependencies);
+
+fn blob_storage_target(prefix: &str) -> OplogExportTarget {
+    OplogExportTarget::BlobStorage(BlobStorageExportTarget {
+        prefix: prefix.to_string(),
+    })
+}
+
+#[test]
+#[tracing::instrument]
+async fn create_update_and_delete_oplog_export_sink(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let creation = OplogExportSinkCreation {
+        name: "archive".to_string(),
+        target: blob_storage_target("oplogs"),
+    };
+    let created = client
+        .create_oplog_export_sink(&env.id.0, &creation)
+        .await?;
+
+    assert_eq!(created.name, "archive");
+    assert_eq!(created.target, creation.target);
+    assert_eq!(created.revision, OplogExportSinkRevision::INITIAL);
+
+    {
+        let fetched = client.get_oplog_export_sink(&created.id.0).await?;
+        assert_eq!(fetched, created);
+    }
+
+    let updated = client
+        .update_oplog_export_sink(
+            &created.id.0,
+            &OplogExportSinkUpdate {
+                current_revision: created.revision,
+                target: Some(blob_storage_target("other-oplogs")),
+            },
+        )
+        .await?;
+    assert_eq!(updated.target, blob_storage_target("other-oplogs"));
+    assert!(updated.revision > created.revision);
+
+    {
+        let all = client
+            .list_environment_oplog_export_sinks(&env.id.0)
+            .await?;
+        assert_eq!(all.values, vec![updated.clone()]);
+    }
+
+    client
+        .delete_oplog_export_sink(&updated.id.0, updated.revision.into())
+        .await?;
+
+    let all = client
+        .list_environment_oplog_export_sinks(&env.id.0)
+        .await?;
+    assert!(all.values.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn invalid_oplog_export_sinks_are_rejected(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let invalid_name = client
+        .create_oplog_export_sink(
+            &env.id.0,
+            &OplogExportSinkCreation {
+                name: "not a name".to_string(),
+                target: blob_storage_target("oplogs"),
+            },
+        )
+        .await;
+    assert!(invalid_name.is_err());
+
+    let invalid_url = client
+        .create_oplog_export_sink(
+            &env.id.0,
+            &OplogExportSinkCreation {
+                name: "http".to_string(),
+                target: OplogExportTarget::Http(HttpExportTarget {
+                    url: "not a url".to_string(),
+                    headers: Default::default(),
+                    timeout_ms: 1000,
+                }),
+            },
+        )
+        .await;
+    assert!(invalid_url.is_err());
+
+    let creation = OplogExportSinkCreation {
+        name: "archive".to_string(),
+        target: blob_storage_target("oplogs"),
+    };
+    client
+        .create_oplog_export_sink(&env.id.0, &creation)
+        .await?;
+    let duplicate = client.create_oplog_export_sink(&env.id.0, &creation).await;
+    assert!(duplicate.is_err());
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn oplog_is_exported_to_the_http_sink_of_the_environment(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+    let client = deps.registry_service().client(&user.token).await;
+
+    let received = Arc::new(Mutex::new(Vec::<String>::new()));
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:0").await?;
+    let port = listener.local_addr()?.port();
+    let http_server = tokio::spawn({
+        let received = received.clone();
+        async move {
+            use axum::{Router, routing::post};
+            let route = Router::new().route(
+                "/export",
+                post(move |body: String| {
+                    let received = received.clone();
+                    async move {
+                        received.lock().unwrap().push(body);
+                        "ok"
+                    }
+                }),
+            );
+            axum::serve(listener, route).await.unwrap();
+        }
+    });
+
+    client
+        .create_oplog_export_sink(
+            &env.id.0,
+            &OplogExportSinkCreation {
+                name: "collector".to_string(),
+                target: OplogExportTarget::Http(HttpExportTarget {
+                    url: format!("http://localhost:{port}/export"),
+                    headers: Default::default(),
+                    timeout_ms: 10_000,
+                }),
+            },
+        )
+        .await?;
+
+    let component = user
+        .component(&env.id, "golem_it_host_api_tests_release")
+        .name("golem-it:host-api-tests")
+        .store()
+        .await?;
+    let agent_id = agent_id!("GolemHostApi", "oplog-export");
+    user.start_agent(&component.id, agent_id.clone()).await?;
+    user.invoke_and_await_agent(
+        &component,
+        &agent_id,
+        "has_retry_policy",
+        data_value!("unknown".to_string()),
+    )
+    .await?;
+
+    let exported_invocation = tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let lines = received
+                .lock()
+                .unwrap()
+                .iter()
+                .flat_map(|body| body.lines().map(|line| line.to_string()))
+                .collect::<Vec<_>>();
+            if let Some(line) = lines.into_iter().find(|line| {
+                let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+                entry["entry"]["type"] == "AgentInvocationFinished"
+            }) {
+                break serde_json::from_str::<serde_json::Value>(&line).unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    })
+    .await?;
+
+    http_server.abort();
+
+    assert_eq!(
+        exported_invocation["environmentId"],
+        serde_json::Value::String(env.id.to_string())
+    );
+
+    Ok(())
+}
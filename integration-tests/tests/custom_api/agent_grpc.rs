@@ -0,0 +1,324 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::model::auth::TokenSecret;
+use golem_test_framework::config::{EnvBasedTestDependencies, TestDependencies};
+use golem_test_framework::dsl::{TestDsl, TestDslExtended};
+use pretty_assertions::assert_eq;
+use std::fmt::{Debug, Formatter};
+use test_r::{inherit_test_dep, test, test_dep, timeout};
+use tonic::client::Grpc;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+use tonic_prost::ProstCodec;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use uuid::Uuid;
+
+inherit_test_dep!(EnvBasedTestDependencies);
+
+const REPOSITORY_SERVICE: &str = "golem.agent.repository.Repository";
+const COUNTER_SERVICE: &str = "golem.agent.counter.Counter";
+
+// Messages of the service rendered for the `Repository` agent of `it:agent-counters`
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct Constructor {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct AddRequest {
+    #[prost(message, optional, tag = "1")]
+    agent: Option<Constructor>,
+    #[prost(string, optional, tag = "2")]
+    phantom_id: Option<String>,
+    #[prost(message, optional, tag = "3")]
+    input: Option<AddInput>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct AddInput {
+    #[prost(string, tag = "1")]
+    id: String,
+    #[prost(string, tag = "2")]
+    description: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct AddOutput {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ListRequest {
+    #[prost(message, optional, tag = "1")]
+    agent: Option<Constructor>,
+    #[prost(string, optional, tag = "2")]
+    phantom_id: Option<String>,
+    #[prost(message, optional, tag = "3")]
+    input: Option<ListInput>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ListInput {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ListItem {
+    #[prost(message, optional, tag = "1")]
+    value: Option<Item>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct Item {
+    #[prost(string, tag = "1")]
+    id: String,
+    #[prost(string, tag = "2")]
+    description: String,
+    #[prost(uint64, tag = "3")]
+    count: u64,
+}
+
+pub struct AgentGrpcTestContext {
+    token: TokenSecret,
+    application: String,
+    environment: String,
+    agent_grpc_port: u16,
+}
+
+impl Debug for AgentGrpcTestContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AgentGrpcTestContext")
+    }
+}
+
+impl AgentGrpcTestContext {
+    async fn channel(&self) -> anyhow::Result<Channel> {
+        Ok(
+            Channel::from_shared(format!("http://127.0.0.1:{}", self.agent_grpc_port))?
+                .connect()
+                .await?,
+        )
+    }
+
+    /// Wraps `message` in a request carrying the metadata addressing the environment and, if
+    /// given, one of its agent types.
+    fn request<T>(&self, message: T, agent_type: Option<&str>) -> Request<T> {
+        let mut request = Request::new(message);
+        let metadata = request.metadata_mut();
+        metadata.insert(
+            "authorization",
+            format!("Bearer {}", self.token.secret()).parse().unwrap(),
+        );
+        metadata.insert("x-golem-application", self.application.parse().unwrap());
+        metadata.insert("x-golem-environment", self.environment.parse().unwrap());
+        if let Some(agent_type) = agent_type {
+            metadata.insert("x-golem-agent-type", agent_type.parse().unwrap());
+        }
+        request
+    }
+
+    async fn grpc(&self) -> anyhow::Result<Grpc<Channel>> {
+        let mut grpc = Grpc::new(self.channel().await?);
+        grpc.ready().await?;
+        Ok(grpc)
+    }
+
+    async fn unary<Req, Resp>(
+        &self,
+        agent_type: &str,
+        path: &'static str,
+        message: Req,
+    ) -> Result<Resp, Status>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut grpc = self
+            .grpc()
+            .await
+            .map_err(|err| Status::unavailable(err.to_string()))?;
+        let response = grpc
+            .unary(
+                self.request(message, Some(agent_type)),
+                PathAndQuery::from_static(path),
+                ProstCodec::<Req, Resp>::default(),
+            )
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    async fn server_streaming<Req, Resp>(
+        &self,
+        agent_type: &str,
+        path: &'static str,
+        message: Req,
+    ) -> Result<Vec<Resp>, Status>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut grpc = self
+            .grpc()
+            .await
+            .map_err(|err| Status::unavailable(err.to_string()))?;
+        let mut stream = grpc
+            .server_streaming(
+                self.request(message, Some(agent_type)),
+                PathAndQuery::from_static(path),
+                ProstCodec::<Req, Resp>::default(),
+            )
+            .await?
+            .into_inner();
+        let mut messages = Vec::new();
+        while let Some(message) = stream.message().await? {
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    /// Lists the services described by server reflection for the given metadata.
+    async fn list_services(&self, agent_type: Option<&str>) -> anyhow::Result<Vec<String>> {
+        let mut client = ServerReflectionClient::new(self.channel().await?);
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let mut responses = client
+            .server_reflection_info(self.request(tokio_stream::iter(vec![request]), agent_type))
+            .await?
+            .into_inner();
+        let response = responses
+            .message()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Reflection stream ended without a response"))?;
+        match response.message_response {
+            Some(MessageResponse::ListServicesResponse(list)) => {
+                let mut services: Vec<String> = list
+                    .service
+                    .into_iter()
+                    .map(|service| service.name)
+                    .collect();
+                services.sort();
+                Ok(services)
+            }
+            other => anyhow::bail!("Unexpected reflection response: {other:?}"),
+        }
+    }
+}
+
+#[test_dep(scope = PerWorker)]
+async fn test_context(deps: &EnvBasedTestDependencies) -> AgentGrpcTestContext {
+    let user = deps.user().await.unwrap();
+    let (_, env) = user.app_and_env().await.unwrap();
+
+    user.component(&env.id, "it_agent_counters_release")
+        .name("it:agent-counters")
+        .store()
+        .await
+        .unwrap();
+
+    AgentGrpcTestContext {
+        token: user.token.clone(),
+        application: env.application_name.0.clone(),
+        environment: env.name.0.clone(),
+        agent_grpc_port: deps.worker_service().agent_grpc_port(),
+    }
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout("4m")]
+async fn agent_grpc_unary_and_server_streaming_rpcs(
+    ctx: &AgentGrpcTestContext,
+) -> anyhow::Result<()> {
+    let agent = Constructor {
+        name: Uuid::new_v4().to_string(),
+    };
+
+    for (id, description) in [("G1002", "Mud Golem"), ("G1001", "Clay Golem")] {
+        let _: AddOutput = ctx
+            .unary(
+                "Repository",
+                "/golem.agent.repository.Repository/Add",
+                AddRequest {
+                    agent: Some(agent.clone()),
+                    phantom_id: None,
+                    input: Some(AddInput {
+                        id: id.to_string(),
+                        description: description.to_string(),
+                    }),
+                },
+            )
+            .await?;
+    }
+
+    let items: Vec<ListItem> = ctx
+        .server_streaming(
+            "Repository",
+            "/golem.agent.repository.Repository/ListStream",
+            ListRequest {
+                agent: Some(agent),
+                phantom_id: None,
+                input: Some(ListInput {}),
+            },
+        )
+        .await?;
+
+    assert_eq!(
+        items,
+        vec![
+            ListItem {
+                value: Some(Item {
+                    id: "G1001".to_string(),
+                    description: "Clay Golem".to_string(),
+                    count: 1,
+                }),
+            },
+            ListItem {
+                value: Some(Item {
+                    id: "G1002".to_string(),
+                    description: "Mud Golem".to_string(),
+                    count: 1,
+                }),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout("4m")]
+async fn agent_grpc_reflection_lists_deployed_agent_types(
+    ctx: &AgentGrpcTestContext,
+) -> anyhow::Result<()> {
+    let services = ctx.list_services(None).await?;
+    assert!(
+        services.contains(&REPOSITORY_SERVICE.to_string()),
+        "Expected {REPOSITORY_SERVICE} in {services:?}"
+    );
+    assert!(
+        services.contains(&COUNTER_SERVICE.to_string()),
+        "Expected {COUNTER_SERVICE} in {services:?}"
+    );
+
+    let services = ctx.list_services(Some("Repository")).await?;
+    assert_eq!(services, vec![REPOSITORY_SERVICE.to_string()]);
+
+    Ok(())
+}
@@ -39,6 +39,7 @@ async fn test_context(deps: &EnvBasedTestDependencies) -> HttpTestContext {
                         header_name: "x-golem-test-session".to_string(),
                     },
                 )),
+                ..Default::default()
             },
         )],
         "golem_it_agent_sdk_ts",
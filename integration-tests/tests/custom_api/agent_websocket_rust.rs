@@ -0,0 +1,226 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::custom_api::http_test_context::{HttpTestContext, make_test_context};
+use futures::{SinkExt, StreamExt};
+use golem_common::base_model::agent::AgentTypeName;
+use golem_common::base_model::http_api_deployment::HttpApiDeploymentAgentOptions;
+use golem_test_framework::config::EnvBasedTestDependencies;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::time::Duration;
+use test_r::test_dep;
+use test_r::{inherit_test_dep, test};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::{Error, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+
+inherit_test_dep!(EnvBasedTestDependencies);
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+#[test_dep(scope = PerWorker)]
+async fn test_context(deps: &EnvBasedTestDependencies) -> HttpTestContext {
+    make_test_context(
+        deps,
+        vec![(
+            AgentTypeName("WebSocketAgent".to_string()),
+            HttpApiDeploymentAgentOptions::default(),
+        )],
+        "golem_it_agent_sdk_rust_release",
+        "golem-it:agent-sdk-rust",
+    )
+    .await
+    .unwrap()
+}
+
+async fn open(agent: &HttpTestContext, path: &str) -> anyhow::Result<Socket> {
+    let mut url = agent.base_url.join(path)?;
+    url.set_scheme("ws")
+        .map_err(|_| anyhow::anyhow!("Failed to set ws scheme"))?;
+
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Host", agent.host_header.clone());
+
+    let (socket, response) = connect_async(request).await?;
+    assert_eq!(response.status().as_u16(), 101);
+
+    Ok(socket)
+}
+
+/// Next data or close message, skipping pings and pongs
+async fn next_message(socket: &mut Socket) -> anyhow::Result<Option<Message>> {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(30), socket.next()).await?;
+        match message {
+            Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+            Some(Ok(message)) => return Ok(Some(message)),
+            Some(Err(Error::ConnectionClosed)) | None => return Ok(None),
+            Some(Err(err)) => return Err(err.into()),
+        }
+    }
+}
+
+async fn next_json(socket: &mut Socket) -> anyhow::Result<serde_json::Value> {
+    match next_message(socket).await? {
+        Some(Message::Text(text)) => Ok(serde_json::from_str(&text)?),
+        other => Err(anyhow::anyhow!("Expected a text message, got {other:?}")),
+    }
+}
+
+async fn next_close_frame(socket: &mut Socket) -> anyhow::Result<CloseFrame<'static>> {
+    match next_message(socket).await? {
+        Some(Message::Close(Some(frame))) => Ok(frame),
+        other => Err(anyhow::anyhow!("Expected a close frame, got {other:?}")),
+    }
+}
+
+#[test]
+#[tracing::instrument]
+async fn request_without_upgrade_is_rejected(agent: &HttpTestContext) -> anyhow::Result<()> {
+    let response = agent
+        .client
+        .get(agent.base_url.join("/websocket-agents/no-upgrade/echo")?)
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn messages_are_replied_with_the_method_result(
+    agent: &HttpTestContext,
+) -> anyhow::Result<()> {
+    let mut socket = open(agent, "/websocket-agents/echo-agent/echo").await?;
+
+    socket
+        .send(Message::text(json!({ "message": "first" }).to_string()))
+        .await?;
+    assert_eq!(
+        next_json(&mut socket).await?,
+        json!({ "message": "first", "count": 1 })
+    );
+
+    socket
+        .send(Message::text(json!({ "message": "second" }).to_string()))
+        .await?;
+    assert_eq!(
+        next_json(&mut socket).await?,
+        json!({ "message": "second", "count": 2 })
+    );
+
+    socket.close(None).await?;
+
+    // a new connection is bound to the same agent and sees its state
+    let mut socket = open(agent, "/websocket-agents/echo-agent/echo").await?;
+    socket
+        .send(Message::text(json!({ "message": "third" }).to_string()))
+        .await?;
+    assert_eq!(
+        next_json(&mut socket).await?,
+        json!({ "message": "third", "count": 3 })
+    );
+
+    socket.close(None).await?;
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn agent_pushes_messages_and_closes_the_connection(
+    agent: &HttpTestContext,
+) -> anyhow::Result<()> {
+    let mut socket = open(agent, "/websocket-agents/push-agent/push").await?;
+
+    socket
+        .send(Message::text(json!({ "message": "hello" }).to_string()))
+        .await?;
+    assert_eq!(
+        next_message(&mut socket).await?,
+        Some(Message::text("pushed: hello"))
+    );
+
+    socket
+        .send(Message::text(json!({ "message": "close" }).to_string()))
+        .await?;
+    let frame = next_close_frame(&mut socket).await?;
+    assert_eq!(frame.code, CloseCode::from(4000));
+    assert_eq!(frame.reason, "closed by agent");
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn invalid_messages_close_the_connection_with_an_error(
+    agent: &HttpTestContext,
+) -> anyhow::Result<()> {
+    let mut socket = open(agent, "/websocket-agents/invalid-json-agent/echo").await?;
+    socket.send(Message::text("not json")).await?;
+    let frame = next_close_frame(&mut socket).await?;
+    assert_eq!(frame.code, CloseCode::Error);
+    assert!(
+        frame.reason.contains("not valid json"),
+        "unexpected close reason: {}",
+        frame.reason
+    );
+
+    let mut socket = open(agent, "/websocket-agents/invalid-type-agent/echo").await?;
+    socket
+        .send(Message::text(json!({ "message": 42 }).to_string()))
+        .await?;
+    let frame = next_close_frame(&mut socket).await?;
+    assert_eq!(frame.code, CloseCode::Error);
+
+    let mut socket = open(agent, "/websocket-agents/binary-agent/echo").await?;
+    socket.send(Message::binary(vec![1, 2, 3])).await?;
+    let frame = next_close_frame(&mut socket).await?;
+    assert_eq!(frame.code, CloseCode::Error);
+    assert_eq!(frame.reason, "Expected a text WebSocket message");
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn client_close_ends_the_connection(agent: &HttpTestContext) -> anyhow::Result<()> {
+    let mut socket = open(agent, "/websocket-agents/client-close-agent/echo").await?;
+
+    socket
+        .send(Message::text(json!({ "message": "before" }).to_string()))
+        .await?;
+    assert_eq!(
+        next_json(&mut socket).await?,
+        json!({ "message": "before", "count": 1 })
+    );
+
+    socket.close(None).await?;
+    // the server acknowledges the close and ends the stream
+    loop {
+        match next_message(&mut socket).await? {
+            Some(Message::Close(_)) => continue,
+            None => break,
+            Some(other) => return Err(anyhow::anyhow!("Unexpected message {other:?}")),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::custom_api::http_test_context::{
+    HttpTestContext, make_test_context_with_graphql_endpoint,
+};
+use golem_common::base_model::agent::AgentTypeName;
+use golem_common::base_model::http_api_deployment::{
+    HttpApiDeploymentAgentOptions, HttpApiRateLimit, HttpApiRateLimitKey,
+};
+use golem_common::model::quota::TimePeriod;
+use golem_test_framework::config::EnvBasedTestDependencies;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::collections::BTreeMap;
+use test_r::test_dep;
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(EnvBasedTestDependencies);
+
+#[test_dep(scope = PerWorker)]
+async fn test_context(deps: &EnvBasedTestDependencies) -> HttpTestContext {
+    make_test_context_with_graphql_endpoint(
+        deps,
+        vec![(
+            AgentTypeName("ReadonlyAgent".to_string()),
+            HttpApiDeploymentAgentOptions {
+                method_rate_limits: BTreeMap::from([(
+                    "slow_increment".to_string(),
+                    vec![HttpApiRateLimit {
+                        key: HttpApiRateLimitKey::ClientIp,
+                        api_key_header: None,
+                        value: 1,
+                        period: TimePeriod::Hour,
+                        max: 2,
+                    }],
+                )]),
+                ..HttpApiDeploymentAgentOptions::default()
+            },
+        )],
+        "golem_it_agent_sdk_rust_release",
+        "golem-it:agent-sdk-rust",
+        "/graphql".to_string(),
+    )
+    .await
+    .unwrap()
+}
+
+async fn execute(agent: &HttpTestContext, query: &str) -> anyhow::Result<serde_json::Value> {
+    let response = agent
+        .client
+        .post(agent.base_url.join("/graphql")?)
+        .json(&json!({ "query": query }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    Ok(response.json().await?)
+}
+
+#[test]
+#[tracing::instrument]
+async fn graphql_query_and_mutation_invoke_the_agent(
+    agent: &HttpTestContext,
+) -> anyhow::Result<()> {
+    let count = r#"{ readonlyAgent(id: "graphql-agent") { getCount } }"#;
+    let increment = r#"mutation { readonlyAgent(id: "graphql-agent") { increment } }"#;
+
+    let response = execute(agent, count).await?;
+    assert_eq!(
+        response,
+        json!({ "data": { "readonlyAgent": { "getCount": 0 } } })
+    );
+
+    let response = execute(agent, increment).await?;
+    assert_eq!(
+        response,
+        json!({ "data": { "readonlyAgent": { "increment": 1 } } })
+    );
+
+    let response = execute(agent, count).await?;
+    assert_eq!(
+        response,
+        json!({ "data": { "readonlyAgent": { "getCount": 1 } } })
+    );
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn graphql_fields_enforce_method_rate_limits(agent: &HttpTestContext) -> anyhow::Result<()> {
+    let increment =
+        r#"mutation { readonlyAgent(id: "graphql-rate-limited") { slowIncrement(ms: 0) } }"#;
+
+    // The method level limit allows a burst of two invocations
+    for expected in 1..=2 {
+        let response = execute(agent, increment).await?;
+        assert_eq!(
+            response,
+            json!({ "data": { "readonlyAgent": { "slowIncrement": expected } } })
+        );
+    }
+
+    let response = execute(agent, increment).await?;
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        json!("TOO_MANY_REQUESTS")
+    );
+
+    // The method does not share its limit with the other methods of the agent
+    let response = execute(
+        agent,
+        r#"{ readonlyAgent(id: "graphql-rate-limited") { getCount } }"#,
+    )
+    .await?;
+    assert_eq!(
+        response,
+        json!({ "data": { "readonlyAgent": { "getCount": 2 } } })
+    );
+
+    Ok(())
+}
@@ -52,6 +52,43 @@ pub async fn make_test_context_with_openapi_endpoint(
     component_name: &str,
     package_name: &str,
     openapi_endpoint: String,
+) -> anyhow::Result<HttpTestContext> {
+    make_test_context_with_endpoints(
+        deps,
+        agent_and_http_options,
+        component_name,
+        package_name,
+        openapi_endpoint,
+        None,
+    )
+    .await
+}
+
+pub async fn make_test_context_with_graphql_endpoint(
+    deps: &EnvBasedTestDependencies,
+    agent_and_http_options: Vec<(AgentTypeName, HttpApiDeploymentAgentOptions)>,
+    component_name: &str,
+    package_name: &str,
+    graphql_endpoint: String,
+) -> anyhow::Result<HttpTestContext> {
+    make_test_context_with_endpoints(
+        deps,
+        agent_and_http_options,
+        component_name,
+        package_name,
+        HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        Some(graphql_endpoint),
+    )
+    .await
+}
+
+async fn make_test_context_with_endpoints(
+    deps: &EnvBasedTestDependencies,
+    agent_and_http_options: Vec<(AgentTypeName, HttpApiDeploymentAgentOptions)>,
+    component_name: &str,
+    package_name: &str,
+    openapi_endpoint: String,
+    graphql_endpoint: Option<String>,
 ) -> anyhow::Result<HttpTestContext> {
     let user = deps.user().await?.with_auto_deploy(false);
     let client = deps.registry_service().client(&user.token).await;
@@ -84,6 +121,8 @@ pub async fn make_test_context_with_openapi_endpoint(
         agents: BTreeMap::from_iter(agent_and_http_options),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: openapi_endpoint,
+        rate_limits: Vec::new(),
+        graphql_endpoint,
     };
 
     client
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod agent_grpc;
 mod agent_http_principal_ts;
 mod agent_http_routes_rust;
 mod agent_http_routes_ts;
+mod agent_websocket_rust;
+mod graphql;
 mod http_test_context;
 mod mcp;
 mod openapi_generation;
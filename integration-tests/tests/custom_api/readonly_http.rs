@@ -13,9 +13,9 @@
 // limitations under the License.
 
 //! HTTP integration tests for the read-only agent method feature (issue #3393,
-//! tests H1..H5).
+//! tests H1..H6).
 //!
-//! All six tests (H4 is split into principal-unaware / principal-aware halves)
+//! All seven tests (H4 is split into principal-unaware / principal-aware halves)
 //! exercise the `ReadonlyAgent` agent declared in the shared
 //! `agent-sdk-rust` test component. They drive the worker-service HTTP layer
 //! through `HttpTestContext` and assert the cache-header contract documented
@@ -28,6 +28,8 @@
 //!   matches the current `(fingerprint, oplog-index)`
 //! - `Vary` includes `Authorization` (or the configured session header) for
 //!   principal-aware methods
+//! - repeated reads are answered from the gateway's response cache (with an
+//!   `Age` header) until the agent is written
 
 use super::assert_json_content_type;
 use crate::custom_api::http_test_context::{HttpTestContext, make_test_context};
@@ -35,7 +37,7 @@ use golem_common::base_model::agent::AgentTypeName;
 use golem_common::base_model::http_api_deployment::HttpApiDeploymentAgentOptions;
 use golem_test_framework::config::EnvBasedTestDependencies;
 use pretty_assertions::assert_eq;
-use reqwest::header::{CACHE_CONTROL, CONTENT_LENGTH, ETAG, HeaderValue, IF_NONE_MATCH, VARY};
+use reqwest::header::{AGE, CACHE_CONTROL, CONTENT_LENGTH, ETAG, HeaderValue, IF_NONE_MATCH, VARY};
 use test_r::test_dep;
 use test_r::{inherit_test_dep, test};
 
@@ -307,3 +309,65 @@ async fn h5_ttl_method_returns_max_age(agent: &HttpTestContext) -> anyhow::Resul
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// H6 — repeated reads are served by the gateway until the agent is written
+// ---------------------------------------------------------------------------
+
+#[test]
+#[tracing::instrument]
+async fn h6_repeated_get_is_served_from_gateway_cache_until_write(
+    agent: &HttpTestContext,
+) -> anyhow::Result<()> {
+    let path = "/readonly-agents/h6-agent/count";
+    let increment_path = "/readonly-agents/h6-agent/increment";
+
+    let first = agent.client.get(agent.base_url.join(path)?).send().await?;
+    assert_eq!(first.status(), reqwest::StatusCode::OK);
+    assert_eq!(first.headers().get(AGE), None);
+    let etag = header_str(first.headers().get(ETAG))
+        .expect("expected ETag on first GET")
+        .to_string();
+
+    // The gateway drops its cached responses once when it (re)subscribes to the
+    // executors' agent writes, so the read is repeated until it is a cache hit.
+    let mut cached = None;
+    for _ in 0..20 {
+        let response = agent.client.get(agent.base_url.join(path)?).send().await?;
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        if response.headers().contains_key(AGE) {
+            cached = Some(response);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+    let cached = cached.expect("expected a repeated GET to be served from the gateway cache");
+    // Reads that reach the agent advance its oplog, so an unchanged ETag shows
+    // that the cached response was served without invoking the agent.
+    assert_eq!(header_str(cached.headers().get(ETAG)), Some(etag.as_str()));
+    let body: serde_json::Value = cached.json().await?;
+    assert_eq!(body, serde_json::json!(0));
+
+    let write = agent
+        .client
+        .post(agent.base_url.join(increment_path)?)
+        .send()
+        .await?;
+    assert_eq!(write.status(), reqwest::StatusCode::OK);
+
+    let after_write = agent.client.get(agent.base_url.join(path)?).send().await?;
+    assert_eq!(after_write.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        after_write.headers().get(AGE),
+        None,
+        "a write must invalidate the cached response"
+    );
+    assert_ne!(
+        header_str(after_write.headers().get(ETAG)),
+        Some(etag.as_str())
+    );
+    let body: serde_json::Value = after_write.json().await?;
+    assert_eq!(body, serde_json::json!(1));
+
+    Ok(())
+}
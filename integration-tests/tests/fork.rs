@@ -837,3 +837,85 @@ async fn fork_and_sync_with_promise(
 
     Ok(())
 }
+
+#[test]
+#[tracing::instrument]
+#[timeout("4m")]
+async fn export_and_import_idle_worker(
+    deps: &EnvBasedTestDependencies,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+    let component = user
+        .component(&env.id, "it_agent_counters_release")
+        .name("it:agent-counters")
+        .store()
+        .await?;
+
+    let source_name = Uuid::new_v4().to_string();
+    let parsed_source_agent_id = agent_id!("Repository", source_name.clone());
+    let source_agent_id = user
+        .start_agent(&component.id, parsed_source_agent_id.clone())
+        .await?;
+
+    user.invoke_and_await_agent(
+        &component,
+        &parsed_source_agent_id,
+        "add",
+        data_value!("G1001", "Golem Cloud Subscription 1y"),
+    )
+    .await?;
+
+    let archive = user.export_agent(&source_agent_id).await?;
+
+    let target_name = Uuid::new_v4().to_string();
+    let parsed_target_agent_id = agent_id!("Repository", target_name.clone());
+    let target_agent_id = AgentId {
+        component_id: component.id,
+        agent_id: parsed_target_agent_id.to_string(),
+    };
+
+    user.import_agent(&target_agent_id, archive.clone()).await?;
+
+    user.invoke_and_await_agent(
+        &component,
+        &parsed_target_agent_id,
+        "add",
+        data_value!("G1002", "Mud Golem"),
+    )
+    .await?;
+
+    let imported_contents = user
+        .invoke_and_await_agent(&component, &parsed_target_agent_id, "list", data_value!())
+        .await?
+        .into_return_value()
+        .expect("Expected return value");
+
+    assert_eq!(
+        imported_contents,
+        Value::List(vec![
+            Value::Record(vec![
+                Value::String("G1001".to_string()),
+                Value::String("Golem Cloud Subscription 1y".to_string()),
+                Value::U64(1),
+            ]),
+            Value::Record(vec![
+                Value::String("G1002".to_string()),
+                Value::String("Mud Golem".to_string()),
+                Value::U64(1),
+            ]),
+        ])
+    );
+
+    // Importing into an existing agent is rejected
+    let error = user
+        .import_agent(&target_agent_id, archive)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("Worker already exists"));
+
+    Ok(())
+}
@@ -94,6 +94,8 @@ async fn invocation_context_test(deps: &EnvBasedTestDependencies) -> anyhow::Res
         )]),
         webhooks_url: HttpApiDeploymentCreation::default_webhooks_url(),
         openapi_endpoint: None,
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     client
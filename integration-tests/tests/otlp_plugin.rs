@@ -107,6 +107,8 @@ async fn otlp_basic_trace_export(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     client
@@ -211,6 +213,8 @@ async fn otlp_all_signals_export(
         )]),
         webhooks_prefix: HttpApiDeploymentCreation::default_webhooks_prefix(),
         openapi_endpoint_prefix: HttpApiDeploymentCreation::default_openapi_endpoint_prefix(),
+        rate_limits: Vec::new(),
+        graphql_endpoint: None,
     };
 
     client
@@ -965,6 +965,73 @@ async fn search_oplog_1(deps: &EnvBasedTestDependencies, _tracing: &Tracing) ->
     Ok(())
 }
 
+#[test]
+#[tracing::instrument]
+#[timeout("4m")]
+async fn search_component_oplogs(
+    deps: &EnvBasedTestDependencies,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+    let component = user
+        .component(&env.id, "it_agent_counters_release")
+        .name("it:agent-counters")
+        .unique()
+        .store()
+        .await?;
+
+    let repo_id1 = agent_id!("Repository", "searchoplogs1");
+    let agent_id1 = user.start_agent(&component.id, repo_id1.clone()).await?;
+    let repo_id2 = agent_id!("Repository", "searchoplogs2");
+    let agent_id2 = user.start_agent(&component.id, repo_id2.clone()).await?;
+
+    user.invoke_and_await_agent(
+        &component,
+        &repo_id1,
+        "add",
+        data_value!("G1000", "Golem T-Shirt M"),
+    )
+    .await?;
+
+    user.invoke_and_await_agent(
+        &component,
+        &repo_id2,
+        "add",
+        data_value!("G1001", "Golem Cloud Subscription 1y"),
+    )
+    .await?;
+
+    let result1 = user
+        .search_component_oplogs(&component.id, "G1000", None)
+        .await?;
+    let result2 = user
+        .search_component_oplogs(&component.id, "G1001 OR G1000", None)
+        .await?;
+    // Pages ending after every match continue where the previous one stopped
+    let result3 = user
+        .search_component_oplogs(&component.id, "G1001 OR G1000", Some(1))
+        .await?;
+    let environment_result = user.search_environment_oplogs(&env.id, "G1000").await?;
+
+    assert!(
+        !result1.is_empty() && result1.iter().all(|m| m.agent_id == agent_id1),
+        "G1000"
+    );
+    assert!(
+        result2.iter().any(|m| m.agent_id == agent_id1)
+            && result2.iter().any(|m| m.agent_id == agent_id2),
+        "G1001 OR G1000"
+    );
+    assert_eq!(result3, result2);
+    assert!(
+        result1.iter().all(|m| environment_result.contains(m)),
+        "environment wide G1000"
+    );
+
+    Ok(())
+}
+
 #[test]
 #[tracing::instrument]
 #[timeout(600000)]
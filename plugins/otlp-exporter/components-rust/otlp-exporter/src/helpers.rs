@@ -80,11 +80,14 @@ pub(crate) fn worker_error_to_string(e: &WorkerError) -> String {
         WorkerError::ExceededHttpCallLimit => "exceeded http call limit".to_string(),
         WorkerError::ExceededRpcCallLimit => "exceeded rpc call limit".to_string(),
         WorkerError::NodeOutOfFilesystemStorage => "node out of filesystem storage".to_string(),
-        WorkerError::AgentExceededFilesystemStorageLimit => "agent exceeded filesystem storage limit".to_string(),
+        WorkerError::AgentExceededFilesystemStorageLimit => {
+            "agent exceeded filesystem storage limit".to_string()
+        }
         WorkerError::AgentTerminatedByQuota(_) => "agent terminated by quota".to_string(),
         WorkerError::EphemeralSleepTooLong(_) => "ephemeral sleep too long".to_string(),
         WorkerError::EphemeralFuelExhausted(_) => "ephemeral fuel exhausted".to_string(),
         WorkerError::EphemeralCannotSuspend(_) => "ephemeral cannot suspend".to_string(),
         WorkerError::ReadOnlyViolation(_) => "read-only violation".to_string(),
+        WorkerError::InvocationDeadlineExceeded(_) => "invocation deadline exceeded".to_string(),
     }
 }
@@ -287,6 +287,9 @@ fn worker_error_variant_name(e: &golem_rust::bindings::golem::api::oplog::Worker
         golem_rust::bindings::golem::api::oplog::WorkerError::ReadOnlyViolation(_) => {
             "ReadOnlyViolation".to_string()
         }
+        golem_rust::bindings::golem::api::oplog::WorkerError::InvocationDeadlineExceeded(_) => {
+            "InvocationDeadlineExceeded".to_string()
+        }
     }
 }
 
@@ -1047,10 +1050,7 @@ mod tests {
             HashMap::from([("external".to_string(), Some("external".to_string()))]);
 
         assert_eq!(
-            resolve_parent_through_inherited(
-                Some("external".to_string()),
-                &inherited_span_parents,
-            ),
+            resolve_parent_through_inherited(Some("external".to_string()), &inherited_span_parents,),
             Some("external".to_string())
         );
     }
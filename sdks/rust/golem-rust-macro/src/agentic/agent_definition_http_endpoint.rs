@@ -59,7 +59,8 @@ pub fn extract_http_endpoints(
                         || nv.path.is_ident("post")
                         || nv.path.is_ident("put")
                         || nv.path.is_ident("delete")
-                        || nv.path.is_ident("patch") =>
+                        || nv.path.is_ident("patch")
+                        || nv.path.is_ident("websocket") =>
                 {
                     if let syn::Expr::Lit(syn::ExprLit {
                         lit: syn::Lit::Str(s),
@@ -19,6 +19,7 @@ use crate::agentic::agent_definition_http_endpoint::{
     ParsedHttpEndpointDetails, extract_http_endpoints,
 };
 use crate::agentic::agent_definition_read_only::extract_read_only;
+use crate::agentic::agent_definition_timeout::extract_timeout;
 use crate::agentic::helpers::{
     AgentConfigAttrRemover, has_agent_config_attr, is_async_trait_attr, is_constructor_method,
     is_static_method,
@@ -263,6 +264,12 @@ fn get_agent_type_with_remote_client(
                 );
             }
 
+            let timeout_tokens = match extract_timeout(&trait_fn.attrs) {
+                Ok(Some(nanos)) => quote! { Some(#nanos) },
+                Ok(None) => quote! { None },
+                Err(err) => return Some(err.to_compile_error()),
+            };
+
             let mut uses_principal = false;
             for input in &trait_fn.sig.inputs {
                 if let syn::FnArg::Typed(pat_type) = input
@@ -444,6 +451,7 @@ fn get_agent_type_with_remote_client(
                     output_schema: #output_schema,
                     http_endpoint: #endpoint_details,
                     read_only: #read_only_tokens,
+                    timeout: #timeout_tokens,
                 }
             })
         } else {
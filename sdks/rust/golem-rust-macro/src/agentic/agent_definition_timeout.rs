@@ -0,0 +1,71 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use syn::{Attribute, Error, Expr, ExprLit, Lit};
+
+/// Looks for a `#[timeout("<duration>")]` attribute on a method.
+///
+/// Returns:
+/// - `Ok(None)` if no `#[timeout]` attribute is present.
+/// - `Ok(Some(nanos))` with the parsed duration in nanoseconds.
+/// - `Err(_)` if the attribute is malformed or appears more than once.
+pub fn extract_timeout(attrs: &[Attribute]) -> Result<Option<u64>, Error> {
+    let mut found: Option<u64> = None;
+
+    for attr in attrs {
+        if !attr.path().is_ident("timeout") {
+            continue;
+        }
+
+        if found.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "#[timeout] can only be specified once per method",
+            ));
+        }
+
+        let expr: Expr = attr.parse_args().map_err(|_| {
+            Error::new_spanned(
+                attr,
+                "timeout must be a string literal duration (e.g. #[timeout(\"30s\")])",
+            )
+        })?;
+
+        let Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) = &expr
+        else {
+            return Err(Error::new_spanned(
+                &expr,
+                "timeout must be a string literal duration (e.g. #[timeout(\"30s\")])",
+            ));
+        };
+
+        let duration = lit.value().parse::<humantime::Duration>().map_err(|e| {
+            Error::new_spanned(
+                lit,
+                format!("invalid timeout duration `{}`: {}", lit.value(), e),
+            )
+        })?;
+
+        let nanos = duration.as_nanos() as u64;
+        if nanos == 0 {
+            return Err(Error::new_spanned(lit, "timeout must be greater than zero"));
+        }
+
+        found = Some(nanos);
+    }
+
+    Ok(found)
+}
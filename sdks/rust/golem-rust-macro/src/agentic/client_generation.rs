@@ -223,6 +223,12 @@ pub fn get_remote_client(
                 ).expect("Internal Error: Failed to make agent id")
             }
 
+            /// Sets the deadline of the invocations subsequently made through this client.
+            /// The remote agent interrupts an invocation still running when the deadline passes.
+            pub fn set_deadline(&self, deadline: Option<golem_rust::wasip2::clocks::wall_clock::Datetime>) {
+                self.wasm_rpc.set_deadline(deadline);
+            }
+
             #methods_impl
         }
     }
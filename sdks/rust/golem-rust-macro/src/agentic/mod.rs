@@ -26,6 +26,7 @@ mod agent_definition_attributes;
 mod agent_definition_http_endpoint;
 mod agent_definition_impl;
 mod agent_definition_read_only;
+mod agent_definition_timeout;
 mod agent_implementation_impl;
 mod allowed_language_derivation;
 mod allowed_mimetypes_derivation;
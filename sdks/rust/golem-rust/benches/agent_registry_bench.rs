@@ -67,6 +67,7 @@ mod bench {
                 EnrichedElementSchema::ElementSchema(make_element_schema()),
             )]),
             read_only: None,
+            timeout: None,
         }
     }
 
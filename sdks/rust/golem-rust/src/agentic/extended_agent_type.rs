@@ -83,6 +83,8 @@ pub struct EnrichedAgentMethod {
     pub input_schema: ExtendedDataSchema,
     pub output_schema: ExtendedDataSchema,
     pub read_only: Option<ReadOnlyConfig>,
+    /// Maximum execution time of one invocation, in nanoseconds
+    pub timeout: Option<u64>,
 }
 
 impl EnrichedAgentMethod {
@@ -95,6 +97,7 @@ impl EnrichedAgentMethod {
             input_schema: self.input_schema.to_data_schema(),
             output_schema: self.output_schema.to_data_schema(),
             read_only: self.read_only.clone(),
+            timeout: self.timeout,
         }
     }
 }
@@ -44,6 +44,7 @@ pub fn get_http_endpoint_details(
         "options" => HttpMethod::Options,
         "connect" => HttpMethod::Connect,
         "trace" => HttpMethod::Trace,
+        "websocket" => HttpMethod::Websocket,
         other => return Err(format!("Unsupported HTTP method: {}", other)),
     };
 
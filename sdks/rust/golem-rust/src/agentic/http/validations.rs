@@ -721,6 +721,7 @@ mod tests {
             output_schema: ExtendedDataSchema::Tuple(vec![]),
             http_endpoint: endpoints,
             read_only: None,
+            timeout: None,
         }
     }
 
@@ -27,6 +27,7 @@ pub use multimodal::*;
 pub use resolved_agent::*;
 pub use schema::*;
 pub use webhook::*;
+pub use websocket_endpoint::*;
 pub use wit_utils::*;
 
 mod agent;
@@ -46,4 +47,5 @@ pub mod snapshot_auto;
 mod unstructured_binary;
 mod unstructured_text;
 mod webhook;
+mod websocket_endpoint;
 mod wit_utils;
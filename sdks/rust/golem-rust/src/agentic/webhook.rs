@@ -36,9 +36,14 @@ impl WebhookHandler {
     }
 
     async fn wait(self) -> WebhookRequestPayload {
-        let result = await_promise(&self.promise_id).await;
+        // Webhook promises have no deadline, and their id is not exposed, so nothing can
+        // cancel them
+        let payload = await_promise(&self.promise_id)
+            .await
+            .completed()
+            .unwrap_or_default();
 
-        WebhookRequestPayload { payload: result }
+        WebhookRequestPayload { payload }
     }
 
     pub fn url(&self) -> &str {
@@ -0,0 +1,57 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::golem_agentic::golem::agent::host::{self, WebsocketMessage};
+
+/// A client connection to one of the agent's WebSocket endpoints.
+///
+/// The connection id can be stored in the agent's state to push messages to the client
+/// outside of the invocation handling its incoming message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WebSocketClientConnection {
+    id: String,
+}
+
+impl WebSocketClientConnection {
+    /// The connection whose incoming message is being handled by the current invocation
+    pub fn current() -> Option<WebSocketClientConnection> {
+        host::current_websocket_connection().map(Self::from_id)
+    }
+
+    pub fn from_id(id: impl Into<String>) -> WebSocketClientConnection {
+        WebSocketClientConnection { id: id.into() }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn send_text(&self, text: impl Into<String>) {
+        host::websocket_send(&self.id, &WebsocketMessage::Text(text.into()));
+    }
+
+    pub fn send_binary(&self, data: impl Into<Vec<u8>>) {
+        host::websocket_send(&self.id, &WebsocketMessage::Binary(data.into()));
+    }
+
+    pub fn send_json<T: serde::Serialize>(&self, value: &T) -> Result<(), String> {
+        let text = serde_json::to_string(value).map_err(|e| format!("Invalid message: {}", e))?;
+        self.send_text(text);
+        Ok(())
+    }
+
+    pub fn close(&self, code: Option<u16>, reason: Option<&str>) {
+        host::websocket_close(&self.id, code, reason);
+    }
+}
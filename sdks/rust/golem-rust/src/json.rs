@@ -12,13 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::await_promise;
 use crate::bindings::golem::api::host::PromiseId;
+use crate::{PromiseOutcome, await_promise, blocking_await_promise};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
+/// The outcome of a promise, with the payload of a completed promise decoded as JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPromiseOutcome<T> {
+    Completed(T),
+    TimedOut,
+    Cancelled,
+}
+
+impl<T> JsonPromiseOutcome<T> {
+    /// Returns the decoded payload of a completed promise, or `None` if it timed out or was
+    /// cancelled
+    pub fn completed(self) -> Option<T> {
+        match self {
+            JsonPromiseOutcome::Completed(value) => Some(value),
+            JsonPromiseOutcome::TimedOut | JsonPromiseOutcome::Cancelled => None,
+        }
+    }
+}
+
 /// Awaits a promise blocking the execution of the agent. The agent is going to be
-/// suspended until the promise is completed.
+/// suspended until the promise is completed, times out or gets cancelled.
 ///
 /// The completed promise's payload is decoded as JSON.
 ///
@@ -26,14 +45,12 @@ use serde::de::DeserializeOwned;
 /// awaiting of the promise with other operations.
 pub fn blocking_await_promise_json<T: DeserializeOwned>(
     promise_id: &PromiseId,
-) -> Result<T, serde_json::Error> {
-    let promise = crate::bindings::golem::api::host::get_promise(promise_id);
-    promise.subscribe().block();
-    let bytes = promise.get().unwrap();
-    serde_json::from_slice(&bytes)
+) -> Result<JsonPromiseOutcome<T>, serde_json::Error> {
+    decode_outcome(blocking_await_promise(promise_id))
 }
 
-/// Awaits a promise.
+/// Awaits a promise, resolving to its outcome once it is completed, times out or gets
+/// cancelled.
 ///
 /// The completed promise's payload is decoded as JSON.
 ///
@@ -41,9 +58,20 @@ pub fn blocking_await_promise_json<T: DeserializeOwned>(
 /// suspended until any of them completes.
 pub async fn await_promise_json<T: DeserializeOwned>(
     promise_id: &PromiseId,
-) -> Result<T, serde_json::Error> {
-    let bytes = await_promise(promise_id).await;
-    serde_json::from_slice(&bytes)
+) -> Result<JsonPromiseOutcome<T>, serde_json::Error> {
+    decode_outcome(await_promise(promise_id).await)
+}
+
+fn decode_outcome<T: DeserializeOwned>(
+    outcome: PromiseOutcome,
+) -> Result<JsonPromiseOutcome<T>, serde_json::Error> {
+    match outcome {
+        PromiseOutcome::Completed(bytes) => Ok(JsonPromiseOutcome::Completed(
+            serde_json::from_slice(&bytes)?,
+        )),
+        PromiseOutcome::TimedOut => Ok(JsonPromiseOutcome::TimedOut),
+        PromiseOutcome::Cancelled => Ok(JsonPromiseOutcome::Cancelled),
+    }
 }
 
 /// Completes a promise with a JSON payload
@@ -56,6 +56,7 @@ pub mod load_snapshot {
             "golem:api/oplog@1.5.0": crate::bindings::golem::api::oplog,
             "golem:api/context@1.5.0": crate::bindings::golem::api::context,
             "golem:durability/durability@1.5.0": crate::bindings::golem::durability::durability,
+            "golem:metrics/metrics@1.5.0": crate::bindings::golem::metrics::metrics,
             "golem:quota/types@1.5.0": crate::bindings::golem::quota::types,
             "golem:rdbms/mysql@1.5.0": crate::bindings::golem::rdbms::mysql,
             "golem:rdbms/postgres@1.5.0": crate::bindings::golem::rdbms::postgres,
@@ -94,6 +95,7 @@ pub mod save_snapshot {
             "golem:api/oplog@1.5.0": crate::bindings::golem::api::oplog,
             "golem:api/context@1.5.0": crate::bindings::golem::api::context,
             "golem:durability/durability@1.5.0": crate::bindings::golem::durability::durability,
+            "golem:metrics/metrics@1.5.0": crate::bindings::golem::metrics::metrics,
             "golem:quota/types@1.5.0": crate::bindings::golem::quota::types,
             "golem:rdbms/mysql@1.5.0": crate::bindings::golem::rdbms::mysql,
             "golem:rdbms/postgres@1.5.0": crate::bindings::golem::rdbms::postgres,
@@ -133,6 +135,7 @@ pub mod golem_agentic {
             "golem:api/oplog@1.5.0": crate::bindings::golem::api::oplog,
             "golem:api/context@1.5.0": crate::bindings::golem::api::context,
             "golem:durability/durability@1.5.0": crate::bindings::golem::durability::durability,
+            "golem:metrics/metrics@1.5.0": crate::bindings::golem::metrics::metrics,
             "golem:quota/types@1.5.0": crate::bindings::golem::quota::types,
             "golem:rdbms/mysql@1.5.0": crate::bindings::golem::rdbms::mysql,
             "golem:rdbms/postgres@1.5.0": crate::bindings::golem::rdbms::postgres,
@@ -183,6 +186,7 @@ pub mod oplog_processor {
             "golem:api/oplog@1.5.0": crate::bindings::golem::api::oplog,
             "golem:api/context@1.5.0": crate::bindings::golem::api::context,
             "golem:durability/durability@1.5.0": crate::bindings::golem::durability::durability,
+            "golem:metrics/metrics@1.5.0": crate::bindings::golem::metrics::metrics,
             "golem:quota/types@1.5.0": crate::bindings::golem::quota::types,
             "golem:rdbms/mysql@1.5.0": crate::bindings::golem::rdbms::mysql,
             "golem:rdbms/postgres@1.5.0": crate::bindings::golem::rdbms::postgres,
@@ -214,6 +218,7 @@ mod json;
 pub use json::*;
 
 mod checkpoint;
+pub mod metrics;
 pub mod quota;
 mod transaction;
 pub mod value_and_type;
@@ -224,9 +229,12 @@ use bindings::golem::api::host::*;
 
 pub use golem_wasm;
 
-pub use bindings::golem::api::host::{ForkResult, PersistenceLevel, PromiseId};
 pub use bindings::golem::api::host::{
-    complete_promise, create_promise, fork, get_promise, oplog_commit,
+    ForkResult, PersistenceLevel, PromiseId, PromiseOptions, PromiseOutcome,
+};
+pub use bindings::golem::api::host::{
+    cancel_promise, complete_promise, create_promise, create_promise_with_options, fork,
+    get_promise, oplog_commit,
 };
 
 pub mod websocket;
@@ -239,27 +247,52 @@ pub use websocket::{WebSocketCloseInfo, WebSocketError, WebSocketMessage, Websoc
 #[cfg(feature = "macro")]
 pub use golem_rust_macro::*;
 
+/// Creates a promise which times out if it is not completed within the given duration
+pub fn create_promise_with_timeout(timeout: std::time::Duration) -> PromiseId {
+    create_promise_with_options(&PromiseOptions {
+        timeout: Some(u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX)),
+        payload_type: None,
+    })
+}
+
+impl PromiseOutcome {
+    /// Returns the payload of a completed promise, or `None` if it timed out or was cancelled
+    pub fn completed(self) -> Option<Vec<u8>> {
+        match self {
+            PromiseOutcome::Completed(payload) => Some(payload),
+            PromiseOutcome::TimedOut | PromiseOutcome::Cancelled => None,
+        }
+    }
+}
+
 /// Awaits a promise blocking the execution of the agent. The agent is going to be
-/// suspended until the promise is completed.
+/// suspended until the promise is completed, times out or gets cancelled.
 ///
 /// Use `await_promise` for an async version of this function, allowing to interleave
 /// awaiting of the promise with other operations.
-pub fn blocking_await_promise(promise_id: &PromiseId) -> Vec<u8> {
+pub fn blocking_await_promise(promise_id: &PromiseId) -> PromiseOutcome {
     let promise = get_promise(promise_id);
     let pollable = promise.subscribe();
     pollable.block();
-    promise.get().unwrap()
+    ready_promise_outcome(&promise)
 }
 
-/// Awaits a promise.
+/// Awaits a promise, resolving to its outcome once it is completed, times out or gets
+/// cancelled.
 ///
 /// If only promises or timeouts are awaited simultaneously, the agent is going to be
 /// suspended until any of them completes.
-pub async fn await_promise(promise_id: &PromiseId) -> Vec<u8> {
+pub async fn await_promise(promise_id: &PromiseId) -> PromiseOutcome {
     let promise = get_promise(promise_id);
     let pollable = promise.subscribe();
     wstd::io::AsyncPollable::new(pollable).wait_for().await;
-    promise.get().unwrap()
+    ready_promise_outcome(&promise)
+}
+
+fn ready_promise_outcome(promise: &GetPromiseResult) -> PromiseOutcome {
+    promise
+        .get_outcome()
+        .expect("Promise has no outcome after its pollable became ready")
 }
 
 pub struct PersistenceLevelGuard {
@@ -0,0 +1,100 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ergonomic wrappers for the `golem:metrics/metrics` WIT interface.
+//!
+//! # Typical usage
+//!
+//! ```rust,ignore
+//! use golem_rust::metrics::{Counter, Histogram};
+//!
+//! const ORDERS: Counter = Counter::new("orders_total");
+//! const ORDER_VALUE: Histogram = Histogram::new("order_value");
+//!
+//! ORDERS.inc(&[("region", "eu")]);
+//! ORDER_VALUE.record(42.5, &[("region", "eu")]);
+//! ```
+//!
+//! Values recorded while the agent is replaying its oplog are ignored by the executor, so
+//! metrics can be recorded anywhere in agent code without being counted twice on recovery.
+//! Values the executor cannot record (for example a negative counter increment) are dropped
+//! with a warning in the agent's log.
+
+use crate::bindings::golem::metrics::metrics::{self, Label};
+
+/// A monotonically increasing counter.
+#[derive(Debug, Clone, Copy)]
+pub struct Counter {
+    name: &'static str,
+}
+
+impl Counter {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    /// Increments the counter by one.
+    pub fn inc(&self, labels: &[(&str, &str)]) {
+        self.add(1.0, labels)
+    }
+
+    /// Increments the counter by `value`, which must not be negative.
+    pub fn add(&self, value: f64, labels: &[(&str, &str)]) {
+        metrics::increment_counter(self.name, &to_labels(labels), value)
+    }
+}
+
+/// A value that can go up and down.
+#[derive(Debug, Clone, Copy)]
+pub struct Gauge {
+    name: &'static str,
+}
+
+impl Gauge {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    /// Sets the gauge to `value`.
+    pub fn set(&self, value: f64, labels: &[(&str, &str)]) {
+        metrics::set_gauge(self.name, &to_labels(labels), value)
+    }
+}
+
+/// A distribution of observed values.
+#[derive(Debug, Clone, Copy)]
+pub struct Histogram {
+    name: &'static str,
+}
+
+impl Histogram {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    /// Records an observation of `value`.
+    pub fn record(&self, value: f64, labels: &[(&str, &str)]) {
+        metrics::record_histogram(self.name, &to_labels(labels), value)
+    }
+}
+
+fn to_labels(labels: &[(&str, &str)]) -> Vec<Label> {
+    labels
+        .iter()
+        .map(|(name, value)| Label {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
+}
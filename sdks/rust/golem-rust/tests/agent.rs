@@ -31,7 +31,9 @@ mod tests {
     use golem_rust::value_and_type::IntoValue;
     use golem_rust::{AllowedLanguages, AllowedMimeTypes, ConfigSchema, MultimodalSchema};
     use golem_rust::{Schema, agent_definition, agent_implementation, agentic::BaseAgent};
-    use golem_rust_macro::{FromValueAndType, IntoValue, description, endpoint, prompt, read_only};
+    use golem_rust_macro::{
+        FromValueAndType, IntoValue, description, endpoint, prompt, read_only, timeout,
+    };
     use std::fmt::Debug;
     use test_r::test;
     use wasip2::clocks::wall_clock::Datetime;
@@ -1513,6 +1515,60 @@ mod tests {
         );
     }
 
+    // --- Method timeout tests ---
+
+    #[agent_definition]
+    trait TimeoutAgent: BaseAgent {
+        fn new(name: String) -> Self;
+
+        #[timeout("30s")]
+        fn slow(&mut self) -> u32;
+
+        #[timeout("1m 500ms")]
+        fn slower(&mut self) -> u32;
+
+        fn unbounded(&mut self) -> u32;
+    }
+
+    struct TimeoutAgentImpl {}
+
+    #[agent_implementation]
+    impl TimeoutAgent for TimeoutAgentImpl {
+        fn new(_name: String) -> Self {
+            TimeoutAgentImpl {}
+        }
+        fn slow(&mut self) -> u32 {
+            0
+        }
+        fn slower(&mut self) -> u32 {
+            0
+        }
+        fn unbounded(&mut self) -> u32 {
+            0
+        }
+    }
+
+    #[test]
+    fn test_method_timeout() {
+        TimeoutAgentImpl::__register_agent_type();
+        let agent_name = AgentTypeName("TimeoutAgent".to_string());
+        let agent =
+            golem_rust::agentic::get_agent_type_by_name(&agent_name).expect("Agent type not found");
+
+        let timeout_of = |name: &str| {
+            agent
+                .methods
+                .iter()
+                .find(|m| m.name == name)
+                .unwrap_or_else(|| panic!("method `{}` not found", name))
+                .timeout
+        };
+
+        assert_eq!(timeout_of("slow"), Some(30_000_000_000));
+        assert_eq!(timeout_of("slower"), Some(60_500_000_000));
+        assert_eq!(timeout_of("unbounded"), None);
+    }
+
     // --- Schema::from_element_value roundtrip tests ---
     // These verify that the optimized from_element_value (which bypasses T::get_type())
     // produces the same results as constructing a full ValueAndType.
@@ -243,6 +243,21 @@ declare module 'golem:api/oplog@1.5.0' {
     timestamp: Datetime;
     name: string;
   };
+  /**
+   * Parameters for a websocket-connected oplog entry.
+   */
+  export type WebsocketConnectedParameters = {
+    timestamp: Datetime;
+    connectionId: string;
+    methodName: string;
+  };
+  /**
+   * Parameters for a websocket-disconnected oplog entry.
+   */
+  export type WebsocketDisconnectedParameters = {
+    timestamp: Datetime;
+    connectionId: string;
+  };
   export type EndAtomicRegionParameters = {
     timestamp: Datetime;
     beginIndex: OplogIndex;
@@ -498,6 +513,10 @@ declare module 'golem:api/oplog@1.5.0' {
     method: string;
     hostFunction: string;
   };
+  export type InvocationDeadlineExceeded = {
+    method: string;
+    deadline: Datetime;
+  };
   /**
    * Describes the error that occurred in the agent
    */
@@ -569,6 +588,10 @@ declare module 'golem:api/oplog@1.5.0' {
   {
     tag: 'read-only-violation'
     val: ReadOnlyViolation
+  } |
+  {
+    tag: 'invocation-deadline-exceeded'
+    val: InvocationDeadlineExceeded
   };
   export type RawCreateParameters = {
     timestamp: Datetime;
@@ -929,6 +952,16 @@ declare module 'golem:api/oplog@1.5.0' {
   {
     tag: 'remove-retry-policy'
     val: RemoveRetryPolicyParameters
+  } |
+  /** A WebSocket connection was opened to the agent through the http api */
+  {
+    tag: 'websocket-connected'
+    val: WebsocketConnectedParameters
+  } |
+  /** A WebSocket connection opened through the http api was closed */
+  {
+    tag: 'websocket-disconnected'
+    val: WebsocketDisconnectedParameters
   };
   export type PublicOplogEntry = 
   /** The initial agent oplog entry */
@@ -1156,6 +1189,16 @@ declare module 'golem:api/oplog@1.5.0' {
   {
     tag: 'remove-retry-policy'
     val: RemoveRetryPolicyParameters
+  } |
+  /** A WebSocket connection was opened to the agent through the http api */
+  {
+    tag: 'websocket-connected'
+    val: WebsocketConnectedParameters
+  } |
+  /** A WebSocket connection opened through the http api was closed */
+  {
+    tag: 'websocket-disconnected'
+    val: WebsocketDisconnectedParameters
   };
   export type Result<T, E> = { tag: 'ok', val: T } | { tag: 'err', val: E };
 }
@@ -9,6 +9,30 @@ declare module 'golem:rdbms/ignite2@1.5.0' {
      */
     getNext(): DbRow[] | undefined;
   }
+  /**
+   * A statement registered once and executed repeatedly with different parameters.
+   * The thin client protocol has no prepare step, so `prepare` only checks that the connection
+   * or transaction is usable; Ignite caches the query plan server-side on first execution.
+   * Executions fail once the connection or transaction the statement was prepared on is dropped.
+   */
+  export class DbPreparedStatement {
+    /**
+     * @throws Error
+     */
+    query(params: DbValue[]): DbResult;
+    /**
+     * @throws Error
+     */
+    execute(params: DbValue[]): bigint;
+    /**
+     * @throws Error
+     */
+    executeBatch(params: DbValue[][]): BigInt64Array;
+  }
+  /**
+   * Ignite 2.x has no savepoints, so unlike the Postgres and MySQL transactions this
+   * resource offers no savepoint functions.
+   */
   export class DbTransaction {
     /**
      * @throws Error
@@ -22,6 +46,14 @@ declare module 'golem:rdbms/ignite2@1.5.0' {
      * @throws Error
      */
     execute(statement: string, params: DbValue[]): bigint;
+    /**
+     * @throws Error
+     */
+    executeBatch(statement: string, params: DbValue[][]): BigInt64Array;
+    /**
+     * @throws Error
+     */
+    prepare(statement: string): DbPreparedStatement;
     /**
      * @throws Error
      */
@@ -51,6 +83,15 @@ declare module 'golem:rdbms/ignite2@1.5.0' {
      * @throws Error
      */
     execute(statement: string, params: DbValue[]): bigint;
+    /**
+     * Executes the statement once per parameter row inside a single transaction.
+     * @throws Error
+     */
+    executeBatch(statement: string, params: DbValue[][]): BigInt64Array;
+    /**
+     * @throws Error
+     */
+    prepare(statement: string): DbPreparedStatement;
     /**
      * @throws Error
      */
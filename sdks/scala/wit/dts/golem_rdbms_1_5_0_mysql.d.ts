@@ -4,6 +4,31 @@ declare module 'golem:rdbms/mysql@1.5.0' {
     getColumns(): DbColumn[];
     getNext(): DbRow[] | undefined;
   }
+  /**
+   * A statement validated once and executed repeatedly with different parameters.
+   *
+   * `prepare` validates the statement against the database but keeps no server-side handle.
+   * Executions on a transaction reuse the statement cached on the transaction's connection,
+   * while executions on a connection may be parsed again on another pooled connection.
+   *
+   * Executions are recorded like the equivalent calls on the connection or transaction
+   * the statement was prepared on, and fail once that connection or transaction is dropped.
+   */
+  export class DbPreparedStatement {
+    /**
+     * @throws Error
+     */
+    query(params: DbValue[]): DbResult;
+    /**
+     * @throws Error
+     */
+    execute(params: DbValue[]): bigint;
+    /**
+     * Executes the statement once per parameter row, returning the affected row count of each.
+     * @throws Error
+     */
+    executeBatch(params: DbValue[][]): BigUint64Array;
+  }
   export class DbConnection {
     /**
      * @throws Error
@@ -21,6 +46,16 @@ declare module 'golem:rdbms/mysql@1.5.0' {
      * @throws Error
      */
     execute(statement: string, params: DbValue[]): bigint;
+    /**
+     * Executes the statement once per parameter row in a single implicit transaction:
+     * either every row is applied or none is.
+     * @throws Error
+     */
+    executeBatch(statement: string, params: DbValue[][]): BigUint64Array;
+    /**
+     * @throws Error
+     */
+    prepare(statement: string): DbPreparedStatement;
     /**
      * @throws Error
      */
@@ -39,6 +74,28 @@ declare module 'golem:rdbms/mysql@1.5.0' {
      * @throws Error
      */
     execute(statement: string, params: DbValue[]): bigint;
+    /**
+     * @throws Error
+     */
+    executeBatch(statement: string, params: DbValue[][]): BigUint64Array;
+    /**
+     * @throws Error
+     */
+    prepare(statement: string): DbPreparedStatement;
+    /**
+     * Creates a named savepoint. Names must be plain SQL identifiers.
+     * @throws Error
+     */
+    savepoint(name: string): void;
+    /**
+     * @throws Error
+     */
+    releaseSavepoint(name: string): void;
+    /**
+     * Undoes every change made after the savepoint was created, keeping the transaction open.
+     * @throws Error
+     */
+    rollbackToSavepoint(name: string): void;
     /**
      * @throws Error
      */
@@ -0,0 +1,68 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+import { TypeMetadata } from '@golemcloud/golem-ts-types-core';
+import { AgentMethodRegistry } from '../internal/registry/agentMethodRegistry';
+import ms from 'ms';
+
+function parseDurationToNanoseconds(duration: string): bigint {
+  const milliseconds = ms(duration as ms.StringValue);
+  if (milliseconds === undefined) {
+    throw new Error(
+      `Invalid duration string: '${duration}'. Use formats like '5s', '10m', '1h', '2 days', etc.`,
+    );
+  }
+  if (milliseconds <= 0) {
+    throw new Error(`Invalid timeout '${duration}': the timeout must be positive`);
+  }
+  return BigInt(milliseconds) * 1_000_000n;
+}
+
+/**
+ * Sets the maximum execution time of a single invocation of an agent method.
+ *
+ * When an invocation runs longer than the given duration it is interrupted and
+ * fails with an `invocation-deadline-exceeded` error, which is not retried. The
+ * resulting deadline is also propagated to any agent-to-agent calls made while
+ * handling the invocation.
+ *
+ * @example
+ * ```ts
+ * @agent()
+ * class Reporter {
+ *   @timeout('30s')
+ *   async generate(): Promise<string> { ... }
+ * }
+ * ```
+ */
+export function timeout(duration: string) {
+  return function (
+    target: Object,
+    propertyKey: string | symbol,
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    _descriptor?: PropertyDescriptor,
+  ) {
+    const className = target.constructor.name;
+
+    const classMetadata = TypeMetadata.get(className);
+    if (!classMetadata) {
+      throw new Error(
+        `Class metadata not found for agent ${className}. Ensure metadata is generated.`,
+      );
+    }
+
+    const methodName = String(propertyKey);
+    AgentMethodRegistry.setTimeout(className, methodName, parseDurationToNanoseconds(duration));
+  };
+}
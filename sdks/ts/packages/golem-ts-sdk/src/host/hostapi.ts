@@ -14,7 +14,9 @@
 
 import {
   PromiseId,
+  PromiseOutcome,
   getPromise,
+  GetPromiseResult,
   generateIdempotencyKey as rawGenerateIdempotencyKey,
   resolveComponentId as rawResolveComponentId,
   fork as rawFork,
@@ -36,8 +38,10 @@ import { ComponentId, EnvironmentId } from '../ids';
 // Re-export functions (pass-through — these don't return or accept UUID-containing types)
 export {
   createPromise,
+  createPromiseWithOptions,
   getPromise,
   completePromise,
+  cancelPromise,
   getOplogIndex,
   setOplogIndex,
   oplogCommit,
@@ -62,6 +66,8 @@ export { GetPromiseResult } from 'golem:api/host@1.5.0';
 export type {
   ValueAndType,
   PromiseId,
+  PromiseOptions,
+  PromiseOutcome,
   OplogIndex,
   Pollable,
   ComponentRevision,
@@ -201,10 +207,22 @@ export function fork(): ForkResult {
   };
 }
 
-export async function awaitPromise(promiseId: PromiseId): Promise<Uint8Array> {
+function readyOutcome(promise: GetPromiseResult): PromiseOutcome {
+  const outcome = promise.getOutcome();
+  if (outcome === undefined) {
+    throw new Error('Promise has no outcome after its pollable became ready');
+  }
+  return outcome;
+}
+
+/**
+ * Awaits a Golem promise, resolving to its outcome once it is completed, times out
+ * or gets cancelled.
+ */
+export async function awaitPromise(promiseId: PromiseId): Promise<PromiseOutcome> {
   const promise = getPromise(promiseId);
   await promise.subscribe().promise();
-  return promise.get()!;
+  return readyOutcome(promise);
 }
 
 /**
@@ -217,10 +235,10 @@ export async function awaitPromise(promiseId: PromiseId): Promise<Uint8Array> {
 export async function awaitAbortablePromise(
   promiseId: PromiseId,
   signal: AbortSignal,
-): Promise<Uint8Array> {
+): Promise<PromiseOutcome> {
   const promise = getPromise(promiseId);
   await awaitPollable(promise.subscribe(), signal);
-  return promise.get()!;
+  return readyOutcome(promise);
 }
 
 /**
@@ -39,6 +39,7 @@ export {
 export { prompt } from './decorators/prompt';
 export { endpoint, EndpointDecoratorOptions } from './decorators/httpEndpoint';
 export { readonly, ReadOnlyOptions, CachePolicyOption } from './decorators/readOnly';
+export { timeout } from './decorators/timeout';
 export * from './agentClassName';
 export * from './newTypes/textInput';
 export * from './newTypes/binaryInput';
@@ -21,6 +21,7 @@ export interface AgentMethodMetadata {
   returnType?: TypeInfoInternal;
   httpEndpoint?: HttpEndpointDetails[];
   readOnly?: CachePolicy;
+  timeout?: bigint;
 }
 
 /**
@@ -83,6 +84,12 @@ class AgentMethodRegistryImpl {
     classMeta.get(method)!.readOnly = cachePolicy;
   }
 
+  setTimeout(agentClassName: string, method: string, timeoutNanos: bigint): void {
+    this.ensureMeta(agentClassName, method);
+    const classMeta = this.registry.get(agentClassName)!;
+    classMeta.get(method)!.timeout = timeoutNanos;
+  }
+
   debugDump(): void {
     console.log(JSON.stringify(this.registry));
   }
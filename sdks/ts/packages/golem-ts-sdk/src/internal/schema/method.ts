@@ -71,6 +71,7 @@ export function getAgentMethodSchema(
       outputSchema,
       httpEndpoint: baseMeta.httpEndpoint ?? [],
       readOnly,
+      timeout: baseMeta.timeout,
     };
 
     return agentMethod;
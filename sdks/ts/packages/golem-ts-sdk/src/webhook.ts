@@ -38,9 +38,11 @@ export class WebhookHandler implements PromiseLike<WebhookRequestPayload> {
   }
 
   private async wait(): Promise<WebhookRequestPayload> {
-    const bytes = await awaitPromise(this.promiseId);
+    const outcome = await awaitPromise(this.promiseId);
 
-    return new WebhookRequestPayload(bytes);
+    // Webhook promises have no deadline, and their id is not exposed, so nothing can
+    // cancel them
+    return new WebhookRequestPayload(outcome.tag === 'completed' ? outcome.val : new Uint8Array());
   }
 
   then<TResult1 = WebhookRequestPayload, TResult2 = never>(
@@ -24,6 +24,7 @@ import {
   SnapshottingEveryNAgentClassName,
   ConstructorUnionOrderAgentClassName,
   ReadOnlyAgentClassName,
+  TimeoutAgentClassName,
 } from './testUtils';
 import { DataSchema, DataValue, ElementSchema } from 'golem:agent/common@1.5.0';
 import * as util from 'node:util';
@@ -980,6 +981,23 @@ describe('Agent decorator should register the agent class and its methods into A
     const method = agent.methods.find((m) => m.name === 'notReadOnly');
     expect(method!.readOnly).toBeUndefined();
   });
+
+  it('should expose the method timeout in nanoseconds', () => {
+    const agent = AgentTypeRegistry.get(TimeoutAgentClassName);
+    if (!agent) throw new Error('TimeoutAgent not found');
+    const slow = agent.methods.find((m) => m.name === 'slow');
+    expect(slow!.timeout).toEqual(30_000_000_000n);
+    const slowRead = agent.methods.find((m) => m.name === 'slowRead');
+    expect(slowRead!.timeout).toEqual(120_000_000_000n);
+    expect(slowRead!.readOnly).toBeDefined();
+  });
+
+  it('should not set a timeout for unannotated methods', () => {
+    const agent = AgentTypeRegistry.get(TimeoutAgentClassName);
+    if (!agent) throw new Error('TimeoutAgent not found');
+    const method = agent.methods.find((m) => m.name === 'unbounded');
+    expect(method!.timeout).toBeUndefined();
+  });
 });
 
 describe('Annotated FooAgent class', () => {
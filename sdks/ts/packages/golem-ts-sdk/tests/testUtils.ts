@@ -32,6 +32,7 @@ export const SnapshottingPeriodicAgentClassName = new AgentClassName('Snapshotti
 export const SnapshottingEveryNAgentClassName = new AgentClassName('SnapshottingEveryNAgent');
 export const ConstructorUnionOrderAgentClassName = new AgentClassName('ConstructorUnionOrderAgent');
 export const ReadOnlyAgentClassName = new AgentClassName('ReadOnlyAgent');
+export const TimeoutAgentClassName = new AgentClassName('TimeoutAgent');
 
 // Fetch the analysed type set in the global registry, and the original (ts-morph-lite) `Type` from BarAgent metadata
 export function getTestInterfaceType(): [AnalysedType, Type.Type] {
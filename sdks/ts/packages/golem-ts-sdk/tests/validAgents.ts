@@ -26,6 +26,7 @@ import {
   Config,
   Secret,
   readonly,
+  timeout,
 } from '../src';
 import * as Types from './testTypes';
 import {
@@ -739,6 +740,29 @@ export class ReadOnlyAgent extends BaseAgent {
   }
 }
 
+@agent()
+export class TimeoutAgent extends BaseAgent {
+  constructor(readonly input: string) {
+    super();
+    this.input = input;
+  }
+
+  @timeout('30s')
+  async slow(name: string): Promise<string> {
+    return Promise.resolve(`Hello, ${name}!`);
+  }
+
+  @timeout('2m')
+  @readonly()
+  async slowRead(name: string): Promise<string> {
+    return Promise.resolve(`Hello, ${name}!`);
+  }
+
+  async unbounded(name: string): Promise<string> {
+    return Promise.resolve(`Hello, ${name}!`);
+  }
+}
+
 // If this class is decorated with agent, it will fail
 // This is kept here to ensure that any internal user class is not part of metadata generation.
 // See package.json for metadata generation command.
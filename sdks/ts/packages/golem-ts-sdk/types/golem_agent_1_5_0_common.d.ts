@@ -68,6 +68,9 @@ declare module 'golem:agent/common@1.5.0' {
   {
     tag: 'custom'
     val: string
+  } |
+  {
+    tag: 'websocket'
   };
   export type SystemVariable = "agent-type" | "agent-version";
   export type PathVariable = {
@@ -125,6 +128,7 @@ declare module 'golem:agent/common@1.5.0' {
     inputSchema: DataSchema;
     outputSchema: DataSchema;
     readOnly?: ReadOnlyConfig;
+    timeout?: Duration;
   };
   export type OidcPrincipal = {
     sub: string;
@@ -33,6 +33,42 @@ declare module 'golem:agent/host@1.5.0' {
    *   from a different agent type will trap.
    */
   export function createWebhook(promiseId: PromiseId): string;
+  /**
+   * Returns the id of the WebSocket connection whose incoming message triggered the current invocation, if any.
+   */
+  export function currentWebsocketConnection(): string | undefined;
+  /**
+   * Sends a message to the client of a WebSocket connection terminated by the http api.
+   * Messages sent to a connection that is no longer open are dropped.
+   */
+  export function websocketSend(connectionId: string, message: WebsocketMessage): void;
+  /**
+   * Closes a WebSocket connection terminated by the http api, with an optional close code and reason.
+   */
+  export function websocketClose(connectionId: string, code: number | undefined, reason: string | undefined): void;
+  /**
+   * Subscribes to a durable topic. Every message published to the topic is delivered to the subscriber
+   * by invoking `method-name` with the message as its input.
+   * Subscribing the same subscriber to a topic again replaces its previous subscription.
+   * @throws RpcError
+   */
+  export function subscribe(topic: string, subscriber: TopicSubscriber, methodName: string): void;
+  /**
+   * Removes the subscription of the given subscriber from a topic.
+   * @throws RpcError
+   */
+  export function unsubscribe(topic: string, subscriber: TopicSubscriber): void;
+  /**
+   * Publishes a message to a durable topic, and returns the number of subscriptions its delivery was scheduled for.
+   * The deliveries are carried out by the scheduler, so publishing does not wait for the subscribers. Every subscriber
+   * gets the message as a durable invocation in its invocation queue, including the publishing agent if it is subscribed.
+   * Delivery is at-least-once and retried per subscriber. Each subscriber receives the messages published by an agent
+   * one at a time and in the order they were published, so a delivery that has to be retried holds back the later messages
+   * of the same publisher to that subscriber. For agent type subscriptions the message is delivered to every agent of the
+   * type existing when the delivery runs.
+   * @throws RpcError
+   */
+  export function publish(topic: string, message: DataValue): bigint;
   /**
    * Get the current value of the config key.
    * The expected type is a hint to the host what type of value is expected by the guest and can be used
@@ -74,6 +110,13 @@ declare module 'golem:agent/host@1.5.0' {
      * Schedule invocation for later. Call cancel on the returned resource to cancel the invocation before the scheduled time.
      */
     scheduleCancelableInvocation(scheduledTime: Datetime, methodName: string, input: DataValue): CancellationToken;
+    /**
+     * Sets the deadline of the invocations subsequently started through this client with `invoke`,
+     * `invoke-and-await` or `async-invoke-and-await`. The remote agent interrupts an invocation still
+     * running when the deadline passes. The effective deadline is never later than the one inherited
+     * from the current invocation. Passing `none` clears the deadline.
+     */
+    setDeadline(deadline: Datetime | undefined): void;
   }
   export class FutureInvokeResult {
     /**
@@ -108,6 +151,18 @@ declare module 'golem:agent/host@1.5.0' {
   export type DataValue = golemAgent150Common.DataValue;
   export type RegisteredAgentType = golemAgent150Common.RegisteredAgentType;
   export type TypedAgentConfigValue = golemAgent150Common.TypedAgentConfigValue;
+  /**
+   * A message exchanged with the client of a WebSocket endpoint
+   */
+  export type WebsocketMessage = 
+  {
+    tag: 'text'
+    val: string
+  } |
+  {
+    tag: 'binary'
+    val: Uint8Array
+  };
   /**
    * Possible failures of an RPC call
    */
@@ -137,5 +192,18 @@ declare module 'golem:agent/host@1.5.0' {
     tag: 'remote-agent-error'
     val: AgentError
   };
+  /**
+   * The receiver of the messages published to a topic
+   */
+  export type TopicSubscriber = 
+  /** The calling agent itself */
+  {
+    tag: 'current-agent'
+  } |
+  /** Every agent of the given agent type */
+  {
+    tag: 'agent-type'
+    val: string
+  };
   export type Result<T, E> = { tag: 'ok', val: T } | { tag: 'err', val: E };
 }
@@ -4,11 +4,16 @@
  */
 declare module 'golem:api/host@1.5.0' {
   import * as golemCore150Types from 'golem:core/types@1.5.0';
+  import * as wasiClocks023MonotonicClock from 'wasi:clocks/monotonic-clock@0.2.3';
   import * as wasiIo023Poll from 'wasi:io/poll@0.2.3';
   /**
    * Create a new promise
    */
   export function createPromise(): PromiseId;
+  /**
+   * Create a new promise with an optional deadline and payload type
+   */
+  export function createPromiseWithOptions(options: PromiseOptions): PromiseId;
   /**
    * Gets a handle to the result of the promise. Can only be called in the same agent that orignally created the promise.
    */
@@ -18,6 +23,12 @@ declare module 'golem:api/host@1.5.0' {
    * if the promise was already completed. The payload is passed to the agent that is awaiting the promise.
    */
   export function completePromise(promiseId: PromiseId, data: Uint8Array): boolean;
+  /**
+   * Cancels the given promise, waking up the agent awaiting it with a `cancelled` outcome. Returns true if the
+   * promise was cancelled, false if it was already completed. Can only be called in the same agent that originally
+   * created the promise.
+   */
+  export function cancelPromise(promiseId: PromiseId): boolean;
   /**
    * Returns the current position in the persistent op log
    */
@@ -147,8 +158,13 @@ declare module 'golem:api/host@1.5.0' {
     subscribe(): Pollable;
     /**
      * Poll the result of the promise, returning none if it is not yet ready.
+     * Also returns none if the promise timed out or was cancelled, even though its pollable is ready; use `get-outcome` for promises with a deadline or that can be cancelled.
      */
     get(): Uint8Array | undefined;
+    /**
+     * Poll the outcome of the promise, returning none if it is not yet ready.
+     */
+    getOutcome(): PromiseOutcome | undefined;
   }
   export type ComponentId = golemCore150Types.ComponentId;
   export type Uuid = golemCore150Types.Uuid;
@@ -156,6 +172,8 @@ declare module 'golem:api/host@1.5.0' {
   export type AgentId = golemCore150Types.AgentId;
   export type PromiseId = golemCore150Types.PromiseId;
   export type OplogIndex = golemCore150Types.OplogIndex;
+  export type WitType = golemCore150Types.WitType;
+  export type Duration = wasiClocks023MonotonicClock.Duration;
   export type Pollable = wasiIo023Poll.Pollable;
   /**
    * Represents a Golem component's version
@@ -337,6 +355,32 @@ declare module 'golem:api/host@1.5.0' {
     tag: 'forked'
     val: ForkDetails
   };
+  /**
+   * Options for creating a promise
+   */
+  export type PromiseOptions = {
+    /** If set, the promise is failed with `timed-out` when it is not completed within this duration */
+    timeout?: Duration;
+    /** If set, completions are required to be JSON payloads matching this type */
+    payloadType?: WitType;
+  };
+  /**
+   * The final outcome of a promise
+   */
+  export type PromiseOutcome = 
+  /** The promise was completed with the given payload */
+  {
+    tag: 'completed'
+    val: Uint8Array
+  } |
+  /** The promise was not completed before its deadline */
+  {
+    tag: 'timed-out'
+  } |
+  /** The promise was cancelled by the agent that created it */
+  {
+    tag: 'cancelled'
+  };
   /**
    * Snapshot payload
    */
@@ -5,9 +5,17 @@ import {
     createPromise,
     awaitPromise,
     fork,
-    completePromise
+    completePromise,
+    PromiseOutcome
 } from '@golemcloud/golem-ts-sdk';
 
+function completedPayload(outcome: PromiseOutcome): Uint8Array {
+    if (outcome.tag !== "completed") {
+        throw new Error(`Promise was not completed: ${outcome.tag}`);
+    }
+    return outcome.val;
+}
+
 @agent()
 class PromiseAgent extends BaseAgent {
     private readonly name: string;
@@ -22,7 +30,7 @@ class PromiseAgent extends BaseAgent {
     }
 
     async awaitPromise(id: PromiseId): Promise<string> {
-      const resultBytes = await awaitPromise(id)
+      const resultBytes = completedPayload(await awaitPromise(id))
       return new TextDecoder().decode(resultBytes)
     }
 
@@ -31,7 +39,7 @@ class PromiseAgent extends BaseAgent {
       const forkResult = fork();
       switch (forkResult.tag) {
         case "original":
-          const result = await awaitPromise(promiseId);
+          const result = completedPayload(await awaitPromise(promiseId));
           const string = new TextDecoder().decode(result);
           return string;
         case "forked":
@@ -0,0 +1,52 @@
+// Copyright 2024-2026 Golem Cloud
+//
+// Licensed under the Golem Source License v1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Agent used by the invocation deadline tests.
+
+use golem_rust::{agent_definition, agent_implementation, await_promise, create_promise, timeout};
+
+#[agent_definition]
+pub trait DeadlineAgent {
+    fn new(id: String) -> Self;
+
+    /// Awaits a promise nobody completes, so the invocation can only end by
+    /// exceeding its deadline.
+    #[timeout("2s")]
+    async fn await_forever(&self) -> u64;
+
+    /// Returns well within its deadline.
+    #[timeout("30s")]
+    fn quick(&self) -> u64;
+}
+
+pub struct DeadlineAgentImpl {
+    _id: String,
+}
+
+#[agent_implementation]
+impl DeadlineAgent for DeadlineAgentImpl {
+    fn new(id: String) -> Self {
+        Self { _id: id }
+    }
+
+    async fn await_forever(&self) -> u64 {
+        let promise_id = create_promise();
+        let _ = await_promise(&promise_id).await;
+        0
+    }
+
+    fn quick(&self) -> u64 {
+        1
+    }
+}
@@ -1,6 +1,6 @@
 use super::model::*;
 use golem_rust::{agent_definition, agent_implementation, description, endpoint, AllowedMimeTypes};
-use golem_rust::agentic::{create_webhook, UnstructuredBinary};
+use golem_rust::agentic::{create_webhook, UnstructuredBinary, WebSocketClientConnection};
 use serde::Deserialize;
 use serde::Serialize;
 use wstd::http::{Body, Client, HeaderValue, Request};
@@ -381,3 +381,44 @@ impl WebhookAgent for WebhookAgentImpl {
         }
     }
 }
+
+#[agent_definition(mount = "/websocket-agents/{agent_name}")]
+pub trait WebSocketAgent {
+    fn new(agent_name: String) -> Self;
+
+    #[endpoint(websocket = "/echo")]
+    #[description("Replies to every message with the message and the number of messages received so far")]
+    fn echo(&mut self, message: String) -> EchoResponse;
+
+    #[endpoint(websocket = "/push")]
+    #[description("Pushes the message back through the connection, and closes it on a close message")]
+    fn push(&mut self, message: String);
+}
+
+struct WebSocketAgentImpl {
+    count: u64,
+}
+
+#[agent_implementation]
+impl WebSocketAgent for WebSocketAgentImpl {
+    fn new(_agent_name: String) -> Self {
+        WebSocketAgentImpl { count: 0 }
+    }
+
+    fn echo(&mut self, message: String) -> EchoResponse {
+        self.count += 1;
+        EchoResponse {
+            message,
+            count: self.count,
+        }
+    }
+
+    fn push(&mut self, message: String) {
+        let connection = WebSocketClientConnection::current().expect("no current connection");
+        if message == "close" {
+            connection.close(Some(4000), Some("closed by agent"));
+        } else {
+            connection.send_text(format!("pushed: {message}"));
+        }
+    }
+}
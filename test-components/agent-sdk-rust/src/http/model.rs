@@ -72,6 +72,12 @@ pub struct WebhookResponse {
     pub payload_length: u64,
 }
 
+#[derive(Schema)]
+pub struct EchoResponse {
+    pub message: String,
+    pub count: u64,
+}
+
 // New response types for comprehensive HTTP method testing
 
 #[derive(Schema)]
@@ -1,4 +1,5 @@
 mod config;
+mod deadline;
 mod http;
 pub mod quota_rpc;
 mod readonly;
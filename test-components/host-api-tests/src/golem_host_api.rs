@@ -1,6 +1,6 @@
 use golem_rust::bindings::golem::api::host::{
-    AgentAnyFilter, AgentMetadata, ComponentId as HostComponentId, GetAgents, UpdateMode,
-    get_agent_metadata, get_oplog_index, get_self_metadata, resolve_agent_id,
+    AgentAnyFilter, AgentMetadata, ComponentId as HostComponentId, GetAgents, PromiseOptions,
+    PromiseOutcome, UpdateMode, cancel_promise, create_promise_with_options, get_agent_metadata, get_oplog_index, get_self_metadata, resolve_agent_id,
     resolve_agent_id_strict, resolve_component_id, set_oplog_index, update_agent,
 };
 use golem_rust::{
@@ -12,6 +12,7 @@ use golem_rust::{
     Checkpoint, CheckpointResultExt, PersistenceLevel,
     ForkResult, PromiseId, Transaction, Uuid,
 };
+use golem_rust::value_and_type::IntoValue;
 use golem_rust::retry::{
     get_retry_policies, get_retry_policy_by_name, set_retry_policy, remove_retry_policy,
     use_retry_policy, NamedRetryPolicy, RetryPolicy, RetryPredicate,
@@ -38,6 +39,10 @@ pub trait GolemHostApi {
     fn create_promise(&self) -> PromiseId;
     fn await_promise(&self, promise_id: PromiseId) -> Vec<u8>;
     fn poll_promise(&self, promise_id: PromiseId) -> Option<Vec<u8>>;
+    fn create_promise_with_timeout(&self, timeout_ms: u64) -> PromiseId;
+    fn create_typed_promise(&self) -> PromiseId;
+    fn cancel_promise(&self, promise_id: PromiseId) -> bool;
+    fn await_promise_outcome(&self, promise_id: PromiseId) -> String;
 
     fn fail_with_custom_max_retries(&self, max_retries: u64);
     fn explicit_commit(&self, replicas: u8);
@@ -106,12 +111,46 @@ impl GolemHostApi for GolemHostApiImpl {
 
     fn await_promise(&self, promise_id: PromiseId) -> Vec<u8> {
         golem_rust::blocking_await_promise(&promise_id)
+            .completed()
+            .expect("Promise timed out or was cancelled")
     }
 
     fn poll_promise(&self, promise_id: PromiseId) -> Option<Vec<u8>> {
         get_promise(&promise_id).get()
     }
 
+    fn create_promise_with_timeout(&self, timeout_ms: u64) -> PromiseId {
+        create_promise_with_options(&PromiseOptions {
+            timeout: Some(timeout_ms * 1_000_000),
+            payload_type: None,
+        })
+    }
+
+    fn create_typed_promise(&self) -> PromiseId {
+        create_promise_with_options(&PromiseOptions {
+            timeout: None,
+            payload_type: Some(u32::get_type()),
+        })
+    }
+
+    fn cancel_promise(&self, promise_id: PromiseId) -> bool {
+        cancel_promise(&promise_id)
+    }
+
+    fn await_promise_outcome(&self, promise_id: PromiseId) -> String {
+        let promise = get_promise(&promise_id);
+        promise.subscribe().block();
+        let payload = promise.get();
+        match promise.get_outcome() {
+            Some(PromiseOutcome::Completed(data)) if payload.as_ref() == Some(&data) => {
+                format!("completed:{}", String::from_utf8_lossy(&data))
+            }
+            Some(PromiseOutcome::TimedOut) if payload.is_none() => "timed-out".to_string(),
+            Some(PromiseOutcome::Cancelled) if payload.is_none() => "cancelled".to_string(),
+            other => format!("unexpected outcome {other:?} with payload {payload:?}"),
+        }
+    }
+
     fn fail_with_custom_max_retries(&self, max_retries: u64) {
         let policy = NamedRetryPolicy {
             name: "__fail_with_custom_max_retries".to_string(),
@@ -108,6 +108,9 @@ impl OplogProcessorGuest for OplogProcessorComponent {
                             "process-oplog-entries".to_string()
                         }
                         AgentInvocation::ManualUpdate(_) => "manual-update".to_string(),
+                        AgentInvocation::InterceptInvocation(_) => {
+                            "intercept-invocation".to_string()
+                        }
                     }
                 } else {
                     // AgentInvocationStarted was in a previous batch sent to a